@@ -5,6 +5,20 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 //
+pub mod c99;
+pub mod emitter;
 pub mod error;
+pub mod folder;
+pub mod heap;
+pub mod inliner;
+pub mod interpreter;
 pub mod ir;
+pub mod layout;
+pub mod lowering;
+pub mod manifest;
 pub mod mangling;
+pub mod panic;
+pub mod reachability;
+pub mod rust_bindings;
+pub mod spirv;
+pub mod verifier;
@@ -0,0 +1,3329 @@
+//
+// Copyright (c) 2025 Łukasz Szpakowski
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+use std::collections::BTreeSet;
+use std::fmt::Write;
+use crate::frontend::error::Pos;
+use crate::frontend::printf::*;
+use crate::backend::error::*;
+use crate::backend::heap::*;
+use crate::backend::ir::*;
+use crate::backend::ir::utils::*;
+use crate::backend::layout::*;
+use crate::backend::mangling::*;
+use crate::backend::panic::*;
+
+pub const C99_DRIVER_PREFIX: &str = "vscfl_run_";
+
+const VECTOR_ELEM_TYPES: [IrType; 10] = [IrType::Char, IrType::Short, IrType::Int, IrType::Long, IrType::Uchar, IrType::Ushort, IrType::Uint, IrType::Ulong, IrType::Float, IrType::Double];
+const VECTOR_LENS: [usize; 5] = [2, 3, 4, 8, 16];
+
+const PRELUDE: &str = "#include <math.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdio.h>
+#include <stdlib.h>
+#include <string.h>
+";
+
+const THREAD_INCLUDE: &str = "#include <pthread.h>
+";
+
+const MACROS: &str = "
+#if defined(_MSC_VER)
+#define VSCFL_ALIGNED(n) __declspec(align(n))
+#define VSCFL_THREAD_LOCAL __declspec(thread)
+#define VSCFL_UNUSED
+#else
+#define VSCFL_ALIGNED(n) __attribute__((aligned(n)))
+#define VSCFL_THREAD_LOCAL __thread
+#define VSCFL_UNUSED __attribute__((unused))
+#endif
+
+#if !defined(__GNUC__) || defined(__clang__)
+#pragma STDC FP_CONTRACT OFF
+#endif
+
+";
+
+const ND_RANGE_TYPES: &str = "typedef struct
+{
+    uint32_t work_dim;
+    size_t global_offset[3];
+    size_t global_size[3];
+    size_t local_size[3];
+} vscfl_nd_range;
+
+";
+
+const THREAD_TYPES: &str = "typedef struct
+{
+    pthread_mutex_t mutex;
+    pthread_cond_t cond;
+    size_t count;
+    size_t waiting;
+    unsigned long generation;
+} vscfl_barrier_state;
+
+";
+
+const WORK_ITEM_FUNS: &str = "static VSCFL_UNUSED int vscfl_init_work_item(vscfl_work_item *wi, const vscfl_nd_range *nd_range)
+{
+    size_t i;
+    if(nd_range->work_dim < 1U || nd_range->work_dim > 3U) {
+        return -1;
+    }
+    memset(wi, 0, sizeof(vscfl_work_item));
+    wi->nd_range = nd_range;
+    for(i = 0; i < 3; i++) {
+        if(nd_range->local_size[i] == 0 || nd_range->global_size[i] % nd_range->local_size[i] != 0) {
+            return -1;
+        }
+        wi->num_groups[i] = nd_range->global_size[i] / nd_range->local_size[i];
+    }
+    return 0;
+}
+
+static VSCFL_UNUSED int vscfl_next_id(size_t *id, const size_t *size)
+{
+    size_t i;
+    for(i = 0; i < 3; i++) {
+        id[i]++;
+        if(id[i] < size[i]) {
+            return 1;
+        }
+        id[i] = 0;
+    }
+    return 0;
+}
+
+static VSCFL_UNUSED uint32_t get_work_dim(void)
+{ return vscfl_wi->nd_range->work_dim; }
+
+static VSCFL_UNUSED size_t get_global_size(uint32_t dim)
+{ return dim < vscfl_wi->nd_range->work_dim ? vscfl_wi->nd_range->global_size[dim] : 1U; }
+
+static VSCFL_UNUSED size_t get_global_id(uint32_t dim)
+{ return dim < vscfl_wi->nd_range->work_dim ? vscfl_wi->nd_range->global_offset[dim] + vscfl_wi->group_id[dim] * vscfl_wi->nd_range->local_size[dim] + vscfl_wi->local_id[dim] : 0U; }
+
+static VSCFL_UNUSED size_t get_local_size(uint32_t dim)
+{ return dim < vscfl_wi->nd_range->work_dim ? vscfl_wi->nd_range->local_size[dim] : 1U; }
+
+static VSCFL_UNUSED size_t get_local_id(uint32_t dim)
+{ return dim < vscfl_wi->nd_range->work_dim ? vscfl_wi->local_id[dim] : 0U; }
+
+static VSCFL_UNUSED size_t get_num_groups(uint32_t dim)
+{ return dim < vscfl_wi->nd_range->work_dim ? vscfl_wi->num_groups[dim] : 1U; }
+
+static VSCFL_UNUSED size_t get_group_id(uint32_t dim)
+{ return dim < vscfl_wi->nd_range->work_dim ? vscfl_wi->group_id[dim] : 0U; }
+
+static VSCFL_UNUSED size_t get_global_offset(uint32_t dim)
+{ return dim < vscfl_wi->nd_range->work_dim ? vscfl_wi->nd_range->global_offset[dim] : 0U; }
+
+";
+
+const THREAD_FUNS: &str = "static VSCFL_UNUSED pthread_mutex_t vscfl_mutex = PTHREAD_MUTEX_INITIALIZER;
+
+#define VSCFL_LOCK() pthread_mutex_lock(&vscfl_mutex)
+#define VSCFL_UNLOCK() pthread_mutex_unlock(&vscfl_mutex)
+
+static VSCFL_UNUSED void barrier(uint32_t flags)
+{
+    vscfl_barrier_state *state = vscfl_wi->barrier;
+    unsigned long generation;
+    (void) flags;
+    pthread_mutex_lock(&state->mutex);
+    generation = state->generation;
+    state->waiting++;
+    if(state->waiting == state->count) {
+        state->waiting = 0;
+        state->generation++;
+        pthread_cond_broadcast(&state->cond);
+    } else {
+        while(generation == state->generation) {
+            pthread_cond_wait(&state->cond, &state->mutex);
+        }
+    }
+    pthread_mutex_unlock(&state->mutex);
+}
+
+static VSCFL_UNUSED int vscfl_run_work_group(vscfl_work_item *wi, void *(*fun)(void *))
+{
+    const size_t *local_size = wi->nd_range->local_size;
+    size_t count = local_size[0] * local_size[1] * local_size[2];
+    vscfl_work_item *wis = (vscfl_work_item *) malloc(count * sizeof(vscfl_work_item));
+    pthread_t *threads = (pthread_t *) malloc(count * sizeof(pthread_t));
+    vscfl_barrier_state state;
+    size_t i, created = 0;
+    int res = 0;
+    if(wis == NULL || threads == NULL) {
+        free(wis);
+        free(threads);
+        return -2;
+    }
+    pthread_mutex_init(&state.mutex, NULL);
+    pthread_cond_init(&state.cond, NULL);
+    state.count = count;
+    state.waiting = 0;
+    state.generation = 0;
+    memset(wi->local_id, 0, sizeof(wi->local_id));
+    for(i = 0; i < count; i++) {
+        wis[i] = *wi;
+        wis[i].barrier = &state;
+        vscfl_next_id(wi->local_id, local_size);
+    }
+    for(i = 0; i < count; i++) {
+        if(pthread_create(&threads[i], NULL, fun, &wis[i]) != 0) {
+            res = -2;
+            break;
+        }
+        created++;
+    }
+    if(res != 0) {
+        pthread_mutex_lock(&state.mutex);
+        state.count = created;
+        if(state.waiting >= created && created > 0) {
+            state.waiting = 0;
+            state.generation++;
+            pthread_cond_broadcast(&state.cond);
+        }
+        pthread_mutex_unlock(&state.mutex);
+    }
+    for(i = 0; i < created; i++) {
+        pthread_join(threads[i], NULL);
+    }
+    pthread_cond_destroy(&state.cond);
+    pthread_mutex_destroy(&state.mutex);
+    free(wis);
+    free(threads);
+    return res;
+}
+
+";
+
+const SEQUENTIAL_MACROS: &str = "#define VSCFL_LOCK() ((void) 0)
+#define VSCFL_UNLOCK() ((void) 0)
+
+";
+
+const UMUL_HI64_FUN: &str = "static uint64_t vscfl_umul_hi64(uint64_t a, uint64_t b)
+{
+    uint64_t a0 = a & 0xffffffffU, a1 = a >> 32;
+    uint64_t b0 = b & 0xffffffffU, b1 = b >> 32;
+    uint64_t p00 = a0 * b0, p01 = a0 * b1, p10 = a1 * b0, p11 = a1 * b1;
+    uint64_t mid = (p00 >> 32) + (p01 & 0xffffffffU) + (p10 & 0xffffffffU);
+    return p11 + (p01 >> 32) + (p10 >> 32) + (mid >> 32);
+}
+
+";
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+enum ScalarKind
+{
+    Int(u32, bool),
+    Float(u32),
+}
+
+fn scalar_type_ident(typ: &IrType) -> Option<&'static str>
+{
+    match typ {
+        IrType::Void => Some("void"),
+        IrType::Char => Some("int8_t"),
+        IrType::Short => Some("int16_t"),
+        IrType::Int => Some("int32_t"),
+        IrType::Long => Some("int64_t"),
+        IrType::Uchar => Some("uint8_t"),
+        IrType::Ushort => Some("uint16_t"),
+        IrType::Uint => Some("uint32_t"),
+        IrType::Ulong => Some("uint64_t"),
+        IrType::Float => Some("float"),
+        IrType::Double => Some("double"),
+        IrType::SizeT => Some("size_t"),
+        IrType::PtrdiffT => Some("ptrdiff_t"),
+        IrType::IntptrT => Some("intptr_t"),
+        IrType::UintptrT => Some("uintptr_t"),
+        _ => None,
+    }
+}
+
+fn elem_type_name(typ: &IrType) -> Option<&'static str>
+{
+    match typ {
+        IrType::Char => Some("char"),
+        IrType::Short => Some("short"),
+        IrType::Int => Some("int"),
+        IrType::Long => Some("long"),
+        IrType::Uchar => Some("uchar"),
+        IrType::Ushort => Some("ushort"),
+        IrType::Uint => Some("uint"),
+        IrType::Ulong => Some("ulong"),
+        IrType::Float => Some("float"),
+        IrType::Double => Some("double"),
+        IrType::SizeT => Some("size_t"),
+        IrType::PtrdiffT => Some("ptrdiff_t"),
+        IrType::IntptrT => Some("intptr_t"),
+        IrType::UintptrT => Some("uintptr_t"),
+        _ => None,
+    }
+}
+
+fn scalar_kind(typ: &IrType) -> Option<ScalarKind>
+{
+    match typ {
+        IrType::Char => Some(ScalarKind::Int(8, true)),
+        IrType::Short => Some(ScalarKind::Int(16, true)),
+        IrType::Int => Some(ScalarKind::Int(32, true)),
+        IrType::Long => Some(ScalarKind::Int(64, true)),
+        IrType::Uchar => Some(ScalarKind::Int(8, false)),
+        IrType::Ushort => Some(ScalarKind::Int(16, false)),
+        IrType::Uint => Some(ScalarKind::Int(32, false)),
+        IrType::Ulong => Some(ScalarKind::Int(64, false)),
+        IrType::Float => Some(ScalarKind::Float(32)),
+        IrType::Double => Some(ScalarKind::Float(64)),
+        IrType::SizeT | IrType::UintptrT => Some(ScalarKind::Int(64, false)),
+        IrType::PtrdiffT | IrType::IntptrT => Some(ScalarKind::Int(64, true)),
+        _ => None,
+    }
+}
+
+fn kind_type(kind: ScalarKind) -> IrType
+{
+    match kind {
+        ScalarKind::Int(bits, is_signed) => int_type(bits, is_signed),
+        ScalarKind::Float(32) => IrType::Float,
+        ScalarKind::Float(_) => IrType::Double,
+    }
+}
+
+fn kind_type_ident(kind: ScalarKind) -> &'static str
+{ scalar_type_ident(&kind_type(kind)).unwrap_or("int32_t") }
+
+fn kind_name(kind: ScalarKind) -> &'static str
+{ elem_type_name(&kind_type(kind)).unwrap_or("int") }
+
+fn lanes_kind(typ: &IrType) -> Option<(ScalarKind, Option<usize>)>
+{
+    match (scalar_kind(typ), vector_type_parts(typ)) {
+        (Some(kind), _) => Some((kind, None)),
+        (None, Some((elem_type, n))) => scalar_kind(&elem_type).map(|kind| (kind, Some(n))),
+        (None, None) => None,
+    }
+}
+
+fn lanes_type(kind: ScalarKind, n: Option<usize>) -> Option<IrType>
+{
+    match n {
+        Some(n) => vector_type(&kind_type(kind), n),
+        None => Some(kind_type(kind)),
+    }
+}
+
+fn common_type(typ1: &IrType, typ2: &IrType) -> IrType
+{
+    let type1 = promoted_type(typ1);
+    let type2 = promoted_type(typ2);
+    if type1 == type2 {
+        return type1;
+    }
+    match (scalar_kind(&type1), scalar_kind(&type2)) {
+        (Some(ScalarKind::Float(bits1)), Some(ScalarKind::Float(bits2))) => if bits1 >= bits2 { type1 } else { type2 },
+        (Some(ScalarKind::Float(_)), _) => type1,
+        (_, Some(ScalarKind::Float(_))) => type2,
+        (Some(ScalarKind::Int(bits1, is_signed1)), Some(ScalarKind::Int(bits2, is_signed2))) => {
+            if is_signed1 == is_signed2 {
+                if bits1 >= bits2 { type1 } else { type2 }
+            } else if !is_signed1 && bits1 >= bits2 {
+                type1
+            } else if !is_signed2 && bits2 >= bits1 {
+                type2
+            } else if is_signed1 && bits1 > bits2 {
+                type1
+            } else if is_signed2 && bits2 > bits1 {
+                type2
+            } else {
+                int_type(bits1.max(bits2), false)
+            }
+        },
+        _ => type1,
+    }
+}
+
+fn cmp_elem_type(kind: ScalarKind) -> IrType
+{
+    match kind {
+        ScalarKind::Int(bits, _) => int_type(bits, true),
+        ScalarKind::Float(bits) => int_type(bits.max(32), true),
+    }
+}
+
+fn wide_uint_ident(bits: u32) -> &'static str
+{
+    if bits > 32 { "uint64_t" } else { "uint32_t" }
+}
+
+fn is_ptr_type(typ: &IrType) -> bool
+{
+    matches!(typ, IrType::Ptr(_, _, _))
+}
+
+fn is_half_type(typ: &IrType) -> bool
+{
+    match typ {
+        IrType::Half => true,
+        IrType::Array(elem_type, _) => is_half_type(elem_type),
+        IrType::Ptr(_, _, elem_type) => is_half_type(elem_type),
+        _ => false,
+    }
+}
+
+fn vector_type_ident(elem_type: &IrType, n: usize) -> Option<String>
+{ Some(format!("vscfl_{}{}", elem_type_name(elem_type)?, n)) }
+
+fn simple_type_ident(typ: &IrType) -> Option<String>
+{
+    match scalar_type_ident(typ) {
+        Some(ident) => Some(String::from(ident)),
+        None => {
+            let (elem_type, n) = vector_type_parts(typ)?;
+            vector_type_ident(&elem_type, n)
+        },
+    }
+}
+
+fn decl_to_string(typ: &IrType, quals: &str, declarator: &str) -> String
+{
+    match typ {
+        IrType::Struct(ident) => format!("{}struct {} {}", quals, ident, declarator).trim_end().to_string(),
+        IrType::Union(ident) => format!("{}union {} {}", quals, ident, declarator).trim_end().to_string(),
+        IrType::Array(elem_type, len) => {
+            if declarator.starts_with('*') {
+                decl_to_string(elem_type, quals, format!("({})[{}]", declarator, len).as_str())
+            } else {
+                decl_to_string(elem_type, quals, format!("{}[{}]", declarator, len).as_str())
+            }
+        },
+        IrType::Ptr(_, access_modifier, elem_type) => {
+            let elem_quals = if *access_modifier == IrAccessModifier::Const { "const " } else { "" };
+            decl_to_string(elem_type, elem_quals, format!("*{}{}", quals, declarator).as_str())
+        },
+        _ => {
+            match simple_type_ident(typ) {
+                Some(ident) => format!("{}{} {}", quals, ident, declarator).trim_end().to_string(),
+                None => String::new(),
+            }
+        },
+    }
+}
+
+fn type_to_string(typ: &IrType) -> String
+{ decl_to_string(typ, "", "") }
+
+fn type_helper_suffix(typ: &IrType) -> Option<String>
+{
+    match typ {
+        IrType::Struct(ident) | IrType::Union(ident) => Some(ident.clone()),
+        _ => {
+            match scalar_type_ident(typ) {
+                Some(_) => elem_type_name(typ).map(String::from),
+                None => {
+                    let (elem_type, n) = vector_type_parts(typ)?;
+                    Some(format!("{}{}", elem_type_name(&elem_type)?, n))
+                },
+            }
+        },
+    }
+}
+
+fn local_var_name(idx: usize) -> String
+{ format!("v{}", idx) }
+
+fn caller_arg_name(idx: usize) -> String
+{ format!("c{}", idx) }
+
+fn field_name(idx: usize) -> String
+{ format!("f{}", idx) }
+
+fn vector_field_name(idx: usize) -> String
+{ format!("s{:X}", idx) }
+
+fn panic_flag_value_name() -> &'static str
+{ "ps" }
+
+fn panic_ret_name() -> &'static str
+{ "pr" }
+
+fn private_heap_value_name() -> &'static str
+{ "phs" }
+
+fn local_heap_value_name() -> &'static str
+{ "vscfl_lhs" }
+
+fn driver_name(ident: &str) -> String
+{ format!("{}{}", C99_DRIVER_PREFIX, ident) }
+
+fn hidden_param(_modifier: IrPtrModifier, name: &str) -> String
+{ format!("uint32_t *{}", name) }
+
+fn int_literal(n: i128, typ: &IrType) -> String
+{
+    let s = match scalar_kind(typ) {
+        Some(ScalarKind::Int(64, true)) => {
+            if n == i64::MIN as i128 {
+                String::from("(-INT64_C(9223372036854775807) - 1)")
+            } else if n < 0 {
+                format!("(-INT64_C({}))", -n)
+            } else {
+                format!("INT64_C({})", n)
+            }
+        },
+        Some(ScalarKind::Int(64, false)) => format!("UINT64_C({})", n),
+        Some(ScalarKind::Int(_, false)) => format!("{}U", n),
+        _ => {
+            if n == i32::MIN as i128 {
+                String::from("(-2147483647 - 1)")
+            } else if n < 0 {
+                format!("({})", n)
+            } else {
+                format!("{}", n)
+            }
+        },
+    };
+    match typ {
+        IrType::Int | IrType::Uint | IrType::Long | IrType::Ulong => s,
+        _ => format!("(({}) {})", scalar_type_ident(typ).unwrap_or("int32_t"), s),
+    }
+}
+
+fn string_to_literal(bytes: &[u8]) -> String
+{
+    let mut s = String::from("\"");
+    for b in bytes {
+        match b {
+            b'"' => s.push_str("\\\""),
+            b'\\' => s.push_str("\\\\"),
+            b'?' => s.push_str("\\?"),
+            b'\n' => s.push_str("\\n"),
+            b'\t' => s.push_str("\\t"),
+            b'\r' => s.push_str("\\r"),
+            0x20..=0x7e => s.push(*b as char),
+            _ => s.push_str(format!("\\{:03o}", b).as_str()),
+        }
+    }
+    s.push('"');
+    s
+}
+
+fn builtin_var_literal(ident: &str) -> Option<(IrType, String)>
+{
+    match ident {
+        "CLK_LOCAL_MEM_FENCE" => Some((IrType::Uint, String::from("1U"))),
+        "CLK_GLOBAL_MEM_FENCE" => Some((IrType::Uint, String::from("2U"))),
+        "MAXFLOAT" => Some((IrType::Float, float_to_string(f32::MAX))),
+        "HUGE_VALF" | "INFINITY" => Some((IrType::Float, String::from("INFINITY"))),
+        "NAN" => Some((IrType::Float, String::from("NAN"))),
+        "HUGE_VAL" => Some((IrType::Double, String::from("HUGE_VAL"))),
+        "FLOAT_DIG" => Some((IrType::Uint, String::from("6U"))),
+        "FLOAT_MANT_DIG" => Some((IrType::Uint, String::from("24U"))),
+        "FLOAT_MAX_10_EXP" => Some((IrType::Int, String::from("38"))),
+        "FLOAT_MAX_EXP" => Some((IrType::Int, String::from("128"))),
+        "FLOAT_MIN_10_EXP" => Some((IrType::Int, String::from("(-37)"))),
+        "FLOAT_MIN_EXP" => Some((IrType::Int, String::from("(-125)"))),
+        "DOUBLE_DIG" => Some((IrType::Uint, String::from("15U"))),
+        "DOUBLE_MANT_DIG" => Some((IrType::Uint, String::from("53U"))),
+        "DOUBLE_MAX_10_EXP" => Some((IrType::Int, String::from("308"))),
+        "DOUBLE_MAX_EXP" => Some((IrType::Int, String::from("1024"))),
+        "DOUBLE_MIN_10_EXP" => Some((IrType::Int, String::from("(-307)"))),
+        "DOUBLE_MIN_EXP" => Some((IrType::Int, String::from("(-1021)"))),
+        _ => None,
+    }
+}
+
+fn case_value_to_string(value: &IrCaseValue) -> BackendResult<String>
+{
+    let (typ, n, cast_type) = match value {
+        IrCaseValue::Char(n, typ) => (IrType::Char, *n as i128, typ),
+        IrCaseValue::Short(n, typ) => (IrType::Short, *n as i128, typ),
+        IrCaseValue::Int(n, typ) => (IrType::Int, *n as i128, typ),
+        IrCaseValue::Long(n, typ) => (IrType::Long, *n as i128, typ),
+        IrCaseValue::Uchar(n, typ) => (IrType::Uchar, *n as i128, typ),
+        IrCaseValue::Ushort(n, typ) => (IrType::Ushort, *n as i128, typ),
+        IrCaseValue::Uint(n, typ) => (IrType::Uint, *n as i128, typ),
+        IrCaseValue::Ulong(n, typ) => (IrType::Ulong, *n as i128, typ),
+        IrCaseValue::SizeT(n, typ) => (IrType::SizeT, *n as i128, typ),
+        IrCaseValue::PtrdiffT(n, typ) => (IrType::PtrdiffT, *n as i128, typ),
+        IrCaseValue::IntptrT(n, typ) => (IrType::IntptrT, *n as i128, typ),
+        IrCaseValue::UintptrT(n, typ) => (IrType::UintptrT, *n as i128, typ),
+        IrCaseValue::BuiltinVar(ident, _, typ) => {
+            let s = match builtin_var_literal(ident.as_str()) {
+                Some((_, s)) => s,
+                None => return Err(BackendError::Internal(format!("case_value_to_string: unsupported builtin variable {}", ident))),
+            };
+            return match typ.as_ref().and_then(|t| scalar_type_ident(t)) {
+                Some(ident) => Ok(format!("(({}) {})", ident, s)),
+                None => Ok(s),
+            };
+        },
+    };
+    let s = int_literal(n, &typ);
+    match cast_type.as_ref().and_then(|t| scalar_type_ident(t)) {
+        Some(ident) => Ok(format!("(({}) {})", ident, s)),
+        None => Ok(s),
+    }
+}
+
+fn block_has_break(block: &IrBlock) -> bool
+{
+    block.instrs().iter().any(|instr| {
+            match instr {
+                IrInstr::Break => true,
+                IrInstr::Block(block2) => block_has_break(block2),
+                IrInstr::If(_, block2, block3) => block_has_break(block2) || block_has_break(block3),
+                IrInstr::Switch(_, cases) => {
+                    cases.iter().any(|case| {
+                            match case {
+                                IrCase::Case(_, block2) => block_has_break(block2),
+                                IrCase::Default(block2) => block_has_break(block2),
+                            }
+                    })
+                },
+                _ => false,
+            }
+    })
+}
+
+fn op_calls_builtin_fun(op: &IrOp, ident: &str) -> bool
+{
+    match op {
+        IrOp::CallBuiltinFun(ident2, _, _) => ident2 == ident,
+        _ => false,
+    }
+}
+
+fn block_calls_builtin_fun(block: &IrBlock, ident: &str) -> bool
+{
+    block.instrs().iter().any(|instr| {
+            match instr {
+                IrInstr::Op(op) | IrInstr::Assign(_, op) | IrInstr::Return(Some(op)) => op_calls_builtin_fun(op, ident),
+                IrInstr::Block(block2) | IrInstr::Loop(block2) => block_calls_builtin_fun(block2, ident),
+                IrInstr::If(op, block2, block3) => op_calls_builtin_fun(op, ident) || block_calls_builtin_fun(block2, ident) || block_calls_builtin_fun(block3, ident),
+                IrInstr::Switch(op, cases) => {
+                    op_calls_builtin_fun(op, ident) || cases.iter().any(|case| {
+                            match case {
+                                IrCase::Case(_, block2) => block_calls_builtin_fun(block2, ident),
+                                IrCase::Default(block2) => block_calls_builtin_fun(block2, ident),
+                            }
+                    })
+                },
+                _ => false,
+            }
+    })
+}
+
+fn tree_calls_builtin_fun(tree: &IrTree, ident: &str) -> bool
+{
+    tree.defs().iter().any(|def| {
+            match &**def {
+                IrDef::Var(_, var) => {
+                    match &*var.borrow() {
+                        IrVar::Fun(fun) => {
+                            match &**fun {
+                                IrFun::Fun(_, _, _, block, _, _, _, _) => block_calls_builtin_fun(block, ident),
+                                IrFun::Caller(_, _, _, caller_funs, _, _, _, _) => {
+                                    caller_funs.funs().iter().any(|(_, caller_fun)| {
+                                            match caller_fun {
+                                                IrCallerFun::Lambda(_, _, _, _, block) => block_calls_builtin_fun(block, ident),
+                                                _ => false,
+                                            }
+                                    })
+                                },
+                            }
+                        },
+                        _ => false,
+                    }
+                },
+                _ => false,
+            }
+    })
+}
+
+fn type_has(typ: &IrType, f: &dyn Fn(&IrType) -> bool) -> bool
+{
+    if f(typ) {
+        return true;
+    }
+    match typ {
+        IrType::Array(elem_type, _) => type_has(elem_type, f),
+        IrType::Ptr(_, _, elem_type) => type_has(elem_type, f),
+        _ => false,
+    }
+}
+
+fn block_types(block: &IrBlock, types: &mut Vec<Box<IrType>>)
+{
+    for local_var_pair in block.local_var_pairs() {
+        types.push(local_var_pair.1.clone());
+    }
+    for instr in block.instrs() {
+        match instr {
+            IrInstr::Block(block2) => block_types(block2, types),
+            IrInstr::If(_, block2, block3) => {
+                block_types(block2, types);
+                block_types(block3, types);
+            },
+            IrInstr::Switch(_, cases) => {
+                for case in cases {
+                    match case {
+                        IrCase::Case(_, block2) => block_types(block2, types),
+                        IrCase::Default(block2) => block_types(block2, types),
+                    }
+                }
+            },
+            IrInstr::Loop(block2) => block_types(block2, types),
+            _ => (),
+        }
+    }
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+enum BinOp
+{
+    Mul,
+    Div,
+    Rem,
+    Add,
+    Sub,
+    Shl,
+    Shr,
+    Eq,
+    Ne,
+    Lt,
+    Ge,
+    Gt,
+    Le,
+    And,
+    Xor,
+    Or,
+}
+
+impl BinOp
+{
+    fn is_cmp(&self) -> bool
+    {
+        matches!(self, BinOp::Eq | BinOp::Ne | BinOp::Lt | BinOp::Ge | BinOp::Gt | BinOp::Le)
+    }
+
+    fn is_shift(&self) -> bool
+    {
+        matches!(self, BinOp::Shl | BinOp::Shr)
+    }
+
+    fn name(&self) -> &'static str
+    {
+        match self {
+            BinOp::Mul => "mul",
+            BinOp::Div => "div",
+            BinOp::Rem => "rem",
+            BinOp::Add => "add",
+            BinOp::Sub => "sub",
+            BinOp::Shl => "shl",
+            BinOp::Shr => "shr",
+            BinOp::Eq => "eq",
+            BinOp::Ne => "ne",
+            BinOp::Lt => "lt",
+            BinOp::Ge => "ge",
+            BinOp::Gt => "gt",
+            BinOp::Le => "le",
+            BinOp::And => "and",
+            BinOp::Xor => "xor",
+            BinOp::Or => "or",
+        }
+    }
+
+    fn op_str(&self) -> &'static str
+    {
+        match self {
+            BinOp::Mul => "*",
+            BinOp::Div => "/",
+            BinOp::Rem => "%",
+            BinOp::Add => "+",
+            BinOp::Sub => "-",
+            BinOp::Shl => "<<",
+            BinOp::Shr => ">>",
+            BinOp::Eq => "==",
+            BinOp::Ne => "!=",
+            BinOp::Lt => "<",
+            BinOp::Ge => ">=",
+            BinOp::Gt => ">",
+            BinOp::Le => "<=",
+            BinOp::And => "&",
+            BinOp::Xor => "^",
+            BinOp::Or => "|",
+        }
+    }
+}
+
+fn lane_bin_op(bin_op: BinOp, kind: ScalarKind, a: &str, b: &str, is_vector: bool) -> BackendResult<String>
+{
+    if bin_op.is_cmp() {
+        let s = format!("({} {} {})", a, bin_op.op_str(), b);
+        if is_vector {
+            return Ok(format!("(({}) -{})", scalar_type_ident(&cmp_elem_type(kind)).unwrap_or("int32_t"), s));
+        }
+        return Ok(s);
+    }
+    match kind {
+        ScalarKind::Float(_) => {
+            match bin_op {
+                BinOp::Mul | BinOp::Div | BinOp::Add | BinOp::Sub => Ok(format!("({} {} {})", a, bin_op.op_str(), b)),
+                _ => Err(BackendError::Internal(format!("lane_bin_op: unsupported operation {} for floating-point type", bin_op.name()))),
+            }
+        },
+        ScalarKind::Int(bits, _) => {
+            let t = kind_type_ident(kind);
+            let w = wide_uint_ident(bits);
+            match bin_op {
+                BinOp::Mul | BinOp::Add | BinOp::Sub => Ok(format!("(({}) (({}) {} {} ({}) {}))", t, w, a, bin_op.op_str(), w, b)),
+                BinOp::Shl => Ok(format!("(({}) (({}) {} << ({} & {})))", t, w, a, b, bits - 1)),
+                BinOp::Shr => Ok(format!("(({}) ({} >> ({} & {})))", t, a, b, bits - 1)),
+                _ => Ok(format!("(({}) ({} {} {}))", t, a, bin_op.op_str(), b)),
+            }
+        },
+    }
+}
+
+fn lane_unary_op(kind: ScalarKind, a: &str, is_neg: bool) -> BackendResult<String>
+{
+    match (kind, is_neg) {
+        (ScalarKind::Float(_), true) => Ok(format!("(-{})", a)),
+        (ScalarKind::Float(_), false) => Err(BackendError::Internal(String::from("lane_unary_op: unsupported operation not for floating-point type"))),
+        (ScalarKind::Int(bits, _), true) => Ok(format!("(({}) -({}) {})", kind_type_ident(kind), wide_uint_ident(bits), a)),
+        (ScalarKind::Int(_, _), false) => Ok(format!("(({}) ~{})", kind_type_ident(kind), a)),
+    }
+}
+
+fn float_fun_expr(name: &str, argc: usize) -> Option<&'static str>
+{
+    match (name, argc) {
+        ("sqrt", 1) => Some("sqrt(x)"),
+        ("rsqrt", 1) => Some("1.0 / sqrt(x)"),
+        ("cbrt", 1) => Some("cbrt(x)"),
+        ("recip", 1) => Some("1.0 / x"),
+        ("sin", 1) => Some("sin(x)"),
+        ("cos", 1) => Some("cos(x)"),
+        ("tan", 1) => Some("tan(x)"),
+        ("asin", 1) => Some("asin(x)"),
+        ("acos", 1) => Some("acos(x)"),
+        ("atan", 1) => Some("atan(x)"),
+        ("sinh", 1) => Some("sinh(x)"),
+        ("cosh", 1) => Some("cosh(x)"),
+        ("tanh", 1) => Some("tanh(x)"),
+        ("asinh", 1) => Some("asinh(x)"),
+        ("acosh", 1) => Some("acosh(x)"),
+        ("atanh", 1) => Some("atanh(x)"),
+        ("sinpi", 1) => Some("sin(x * 3.141592653589793)"),
+        ("cospi", 1) => Some("cos(x * 3.141592653589793)"),
+        ("tanpi", 1) => Some("tan(x * 3.141592653589793)"),
+        ("asinpi", 1) => Some("asin(x) / 3.141592653589793"),
+        ("acospi", 1) => Some("acos(x) / 3.141592653589793"),
+        ("atanpi", 1) => Some("atan(x) / 3.141592653589793"),
+        ("exp", 1) => Some("exp(x)"),
+        ("exp2", 1) => Some("exp2(x)"),
+        ("exp10", 1) => Some("pow(10.0, x)"),
+        ("expm1", 1) => Some("expm1(x)"),
+        ("log", 1) => Some("log(x)"),
+        ("log2", 1) => Some("log2(x)"),
+        ("log10", 1) => Some("log10(x)"),
+        ("log1p", 1) => Some("log1p(x)"),
+        ("logb", 1) => Some("floor(log2(fabs(x)))"),
+        ("fabs", 1) => Some("fabs(x)"),
+        ("floor", 1) => Some("floor(x)"),
+        ("ceil", 1) => Some("ceil(x)"),
+        ("round", 1) => Some("round(x)"),
+        ("trunc", 1) => Some("trunc(x)"),
+        ("rint", 1) => Some("rint(x)"),
+        ("degrees", 1) => Some("x * (180.0 / 3.141592653589793)"),
+        ("radians", 1) => Some("x * (3.141592653589793 / 180.0)"),
+        ("sign", 1) => Some("x != x ? 0.0 : (x > 0.0 ? 1.0 : (x < 0.0 ? -1.0 : x))"),
+        ("pow" | "powr" | "pown", 2) => Some("pow(x, y)"),
+        ("rootn", 2) => Some("pow(x, 1.0 / y)"),
+        ("fmin", 2) => Some("fmin(x, y)"),
+        ("fmax", 2) => Some("fmax(x, y)"),
+        ("fmod", 2) => Some("fmod(x, y)"),
+        ("atan2", 2) => Some("atan2(x, y)"),
+        ("hypot", 2) => Some("hypot(x, y)"),
+        ("copysign", 2) => Some("copysign(x, y)"),
+        ("fdim", 2) => Some("x > y ? x - y : 0.0"),
+        ("divide", 2) => Some("x / y"),
+        ("maxmag", 2) => Some("fabs(x) > fabs(y) ? x : (fabs(y) > fabs(x) ? y : fmax(x, y))"),
+        ("minmag", 2) => Some("fabs(x) < fabs(y) ? x : (fabs(y) < fabs(x) ? y : fmin(x, y))"),
+        ("step", 2) => Some("y < x ? 0.0 : 1.0"),
+        ("ldexp", 2) => Some("x * pow(2.0, y)"),
+        ("fma", 3) => Some("fma(x, y, z)"),
+        ("mad", 3) => Some("x * y + z"),
+        ("mix", 3) => Some("x + (y - x) * z"),
+        _ => None,
+    }
+}
+
+fn relational_fun_cond(name: &str) -> Option<(&'static str, usize)>
+{
+    match name {
+        "isequal" => Some(("x == y", 2)),
+        "isnotequal" => Some(("x != y", 2)),
+        "isgreater" => Some(("x > y", 2)),
+        "isgreaterequal" => Some(("x >= y", 2)),
+        "isless" => Some(("x < y", 2)),
+        "islessequal" => Some(("x <= y", 2)),
+        "islessgreater" => Some(("!isnan(x) && !isnan(y) && x != y", 2)),
+        "isfinite" => Some(("isfinite(x)", 1)),
+        "isinf" => Some(("isinf(x)", 1)),
+        "isnan" => Some(("isnan(x)", 1)),
+        "isnormal" => Some(("isnormal(x)", 1)),
+        "isordered" => Some(("!isnan(x) && !isnan(y)", 2)),
+        "isunordered" => Some(("isnan(x) || isnan(y)", 2)),
+        "signbit" => Some(("signbit(x)", 1)),
+        _ => None,
+    }
+}
+
+fn relational_fun_name(ident: &str) -> Option<&str>
+{
+    let name = match ident.find('_') {
+        Some(i) => &ident[..i],
+        None => ident,
+    };
+    relational_fun_cond(name).map(|_| name)
+}
+
+fn float_vars(argc: usize) -> String
+{
+    let mut s = String::new();
+    for (i, (var, param)) in [("x", "a"), ("y", "b"), ("z", "c")].iter().enumerate() {
+        if i < argc {
+            writeln!(s, "    double {} = (double) {};", var, param).unwrap();
+        }
+    }
+    s
+}
+
+fn math_fun_body(name: &str, kind: ScalarKind, argc: usize) -> BackendResult<Option<(ScalarKind, String)>>
+{
+    let t = kind_type_ident(kind);
+    let mut s = String::new();
+    match (relational_fun_cond(name), float_fun_expr(name, argc)) {
+        (Some((cond, min_argc)), _) => {
+            if argc < min_argc {
+                return Ok(None);
+            }
+            s.push_str(float_vars(argc.min(min_argc)).as_str());
+            writeln!(s, "    return ({}) ? 1 : 0;", cond).unwrap();
+            return Ok(Some((ScalarKind::Int(32, true), s)));
+        },
+        (None, Some(expr)) => {
+            match kind {
+                ScalarKind::Float(_) => (),
+                ScalarKind::Int(_, _) => return Err(BackendError::Internal(format!("math_fun_body: invalid arguments for {}", name))),
+            }
+            s.push_str(float_vars(argc).as_str());
+            writeln!(s, "    return ({}) ({});", t, expr).unwrap();
+            return Ok(Some((kind, s)));
+        },
+        (None, None) => (),
+    }
+    match (name, argc, kind) {
+        ("smoothstep", 3, ScalarKind::Float(_)) => {
+            s.push_str(float_vars(argc).as_str());
+            s.push_str("    double t = (z - x) / (y - x);\n");
+            s.push_str("    t = t < 0.0 ? 0.0 : (t > 1.0 ? 1.0 : t);\n");
+            writeln!(s, "    return ({}) (t * t * (3.0 - 2.0 * t));", t).unwrap();
+            Ok(Some((kind, s)))
+        },
+        ("min", 2, _) => {
+            s.push_str("    return b < a ? b : a;\n");
+            Ok(Some((kind, s)))
+        },
+        ("max", 2, _) => {
+            s.push_str("    return a < b ? b : a;\n");
+            Ok(Some((kind, s)))
+        },
+        ("clamp", 3, _) => {
+            writeln!(s, "    {} t = a < b ? b : a;", t).unwrap();
+            s.push_str("    return c < t ? c : t;\n");
+            Ok(Some((kind, s)))
+        },
+        ("abs", 1, ScalarKind::Float(_)) => {
+            writeln!(s, "    return ({}) fabs((double) a);", t).unwrap();
+            Ok(Some((kind, s)))
+        },
+        ("abs", 1, ScalarKind::Int(bits, is_signed)) => {
+            let u = kind_type_ident(ScalarKind::Int(bits, false));
+            if is_signed {
+                writeln!(s, "    return ({}) (a < 0 ? ({}) 0 - ({}) a : ({}) a);", u, u, u, u).unwrap();
+            } else {
+                s.push_str("    return a;\n");
+            }
+            Ok(Some((ScalarKind::Int(bits, false), s)))
+        },
+        ("abs_diff", 2, ScalarKind::Int(bits, _)) => {
+            let u = kind_type_ident(ScalarKind::Int(bits, false));
+            writeln!(s, "    return ({}) (a > b ? ({}) a - ({}) b : ({}) b - ({}) a);", u, u, u, u, u).unwrap();
+            Ok(Some((ScalarKind::Int(bits, false), s)))
+        },
+        ("bitselect", 3, ScalarKind::Int(bits, _)) => {
+            let u = kind_type_ident(ScalarKind::Int(bits, false));
+            writeln!(s, "    return ({}) ((({}) a & ~({}) c) | (({}) b & ({}) c));", t, u, u, u, u).unwrap();
+            Ok(Some((kind, s)))
+        },
+        ("bitselect", 3, ScalarKind::Float(bits)) => {
+            let u = kind_type_ident(ScalarKind::Int(bits, false));
+            writeln!(s, "    {} x, y, m = ({}) (int64_t) c;", u, u).unwrap();
+            writeln!(s, "    {} r;", t).unwrap();
+            s.push_str("    memcpy(&x, &a, sizeof(x));\n");
+            s.push_str("    memcpy(&y, &b, sizeof(y));\n");
+            s.push_str("    x = (x & ~m) | (y & m);\n");
+            s.push_str("    memcpy(&r, &x, sizeof(r));\n");
+            s.push_str("    return r;\n");
+            Ok(Some((kind, s)))
+        },
+        (_, _, ScalarKind::Int(bits, is_signed)) => {
+            let w = wide_uint_ident(bits);
+            let min = int_literal(if is_signed { -(1i128 << (bits - 1)) } else { 0 }, &int_type(64, is_signed));
+            let max = int_literal(if is_signed { (1i128 << (bits - 1)) - 1 } else { (1i128 << bits) - 1 }, &int_type(64, is_signed));
+            match (name, argc, bits, is_signed) {
+                ("add_sat" | "sub_sat", 2, 64, true) => {
+                    let (pos_cond, neg_cond) = if name == "add_sat" {
+                        (format!("b > 0 && a > {} - b", max), format!("b < 0 && a < {} - b", min))
+                    } else {
+                        (format!("b < 0 && a > {} + b", max), format!("b > 0 && a < {} + b", min))
+                    };
+                    writeln!(s, "    if({}) {{", pos_cond).unwrap();
+                    writeln!(s, "        return {};", max).unwrap();
+                    s.push_str("    }\n");
+                    writeln!(s, "    if({}) {{", neg_cond).unwrap();
+                    writeln!(s, "        return {};", min).unwrap();
+                    s.push_str("    }\n");
+                    writeln!(s, "    return a {} b;", if name == "add_sat" { "+" } else { "-" }).unwrap();
+                },
+                ("add_sat", 2, 64, false) => {
+                    s.push_str("    uint64_t r = a + b;\n");
+                    writeln!(s, "    return r < a ? {} : r;", max).unwrap();
+                },
+                ("sub_sat", 2, 64, false) => s.push_str("    return a < b ? 0U : a - b;\n"),
+                ("add_sat" | "sub_sat", 2, _, _) => {
+                    writeln!(s, "    int64_t r = (int64_t) a {} (int64_t) b;", if name == "add_sat" { "+" } else { "-" }).unwrap();
+                    writeln!(s, "    return ({}) (r < {} ? {} : (r > {} ? {} : r));", t, min, min, max, max).unwrap();
+                },
+                ("hadd", 2, _, _) => writeln!(s, "    return ({}) ((a >> 1) + (b >> 1) + (a & b & 1));", t).unwrap(),
+                ("rhadd", 2, _, _) => writeln!(s, "    return ({}) ((a >> 1) + (b >> 1) + ((a | b) & 1));", t).unwrap(),
+                ("mul_hi", 2, 64, false) => s.push_str("    return vscfl_umul_hi64(a, b);\n"),
+                ("mul_hi", 2, 64, true) => {
+                    s.push_str("    uint64_t h = vscfl_umul_hi64((uint64_t) a, (uint64_t) b);\n");
+                    s.push_str("    if(a < 0) {\n");
+                    s.push_str("        h -= (uint64_t) b;\n");
+                    s.push_str("    }\n");
+                    s.push_str("    if(b < 0) {\n");
+                    s.push_str("        h -= (uint64_t) a;\n");
+                    s.push_str("    }\n");
+                    s.push_str("    return (int64_t) h;\n");
+                },
+                ("mul_hi", 2, _, _) => {
+                    let l = if is_signed { "int64_t" } else { "uint64_t" };
+                    writeln!(s, "    return ({}) ((({}) a * ({}) b) >> {});", t, l, l, bits).unwrap();
+                },
+                ("mad_hi", 3, _, _) => writeln!(s, "    return ({}) (({}) vscfl_mul_hi_{}(a, b) + ({}) c);", t, w, kind_name(kind), w).unwrap(),
+                ("mad_sat", 3, 64, _) => return Err(BackendError::Internal(String::from("math_fun_body: unsupported builtin function mad_sat for 64-bit integers"))),
+                ("mad_sat", 3, _, _) => {
+                    let l = if is_signed { "int64_t" } else { "uint64_t" };
+                    writeln!(s, "    {} r = ({}) a * ({}) b + ({}) c;", l, l, l, l).unwrap();
+                    if is_signed {
+                        writeln!(s, "    return ({}) (r < {} ? {} : (r > {} ? {} : r));", t, min, min, max, max).unwrap();
+                    } else {
+                        writeln!(s, "    return ({}) (r > {} ? {} : r);", t, max, max).unwrap();
+                    }
+                },
+                ("mul24", 2, _, _) => writeln!(s, "    return ({}) (({}) a * ({}) b);", t, w, w).unwrap(),
+                ("mad24", 3, _, _) => writeln!(s, "    return ({}) (({}) a * ({}) b + ({}) c);", t, w, w, w).unwrap(),
+                ("rotate", 2, _, _) => {
+                    let u = kind_type_ident(ScalarKind::Int(bits, false));
+                    writeln!(s, "    {} x = ({}) a;", u, u).unwrap();
+                    writeln!(s, "    unsigned n = (unsigned) (({}) b % {}U);", u, bits).unwrap();
+                    writeln!(s, "    return ({}) (n == 0U ? x : ({}) ((x << n) | (x >> ({}U - n))));", t, u, bits).unwrap();
+                },
+                ("popcount", 1, _, _) => {
+                    let u = kind_type_ident(ScalarKind::Int(bits, false));
+                    writeln!(s, "    {} x = ({}) a;", u, u).unwrap();
+                    s.push_str("    int n = 0;\n");
+                    s.push_str("    while(x != 0U) {\n");
+                    s.push_str("        n += (int) (x & 1U);\n");
+                    writeln!(s, "        x = ({}) (x >> 1);", u).unwrap();
+                    s.push_str("    }\n");
+                    writeln!(s, "    return ({}) n;", t).unwrap();
+                },
+                ("clz", 1, _, _) => {
+                    let u = kind_type_ident(ScalarKind::Int(bits, false));
+                    writeln!(s, "    {} x = ({}) a;", u, u).unwrap();
+                    writeln!(s, "    int n = {};", bits).unwrap();
+                    s.push_str("    while(x != 0U) {\n");
+                    s.push_str("        n--;\n");
+                    writeln!(s, "        x = ({}) (x >> 1);", u).unwrap();
+                    s.push_str("    }\n");
+                    writeln!(s, "    return ({}) n;", t).unwrap();
+                },
+                (_, _, _, _) => return Ok(None),
+            }
+            Ok(Some((kind, s)))
+        },
+        (_, _, _) => Ok(None),
+    }
+}
+
+fn printf_c_conv(conv: &PrintfConv) -> BackendResult<(String, String)>
+{
+    let mut spec = format!("%{}", conv.flags);
+    if let Some(width) = conv.width {
+        spec.push_str(format!("{}", width).as_str());
+    }
+    if let Some(precision) = conv.precision {
+        spec.push_str(format!(".{}", precision).as_str());
+    }
+    let bits = match conv.length {
+        PrintfLength::Hh => 8,
+        PrintfLength::H => 16,
+        PrintfLength::L => 64,
+        _ => 32,
+    };
+    let cast = match conv.conv {
+        'd' | 'i' => {
+            spec.push_str("ll");
+            format!("(long long) (int{}_t) ", bits)
+        },
+        'o' | 'u' | 'x' | 'X' => {
+            spec.push_str("ll");
+            format!("(unsigned long long) (uint{}_t) ", bits)
+        },
+        'c' => String::from("(int) (uint8_t) "),
+        's' => String::from("(const char *) "),
+        'p' => String::from("(void *) "),
+        'f' | 'F' | 'e' | 'E' | 'g' | 'G' | 'a' | 'A' => String::from("(double) "),
+        c => return Err(BackendError::Internal(format!("printf_c_conv: unsupported conversion {}", c))),
+    };
+    spec.push(conv.conv);
+    Ok((spec, cast))
+}
+
+#[derive(Clone, Debug)]
+struct C99Value
+{
+    s: String,
+    typ: IrType,
+}
+
+impl C99Value
+{
+    fn new(s: String, typ: IrType) -> Self
+    { C99Value { s, typ, } }
+}
+
+struct C99Helpers
+{
+    idents: BTreeSet<String>,
+    defs: String,
+}
+
+impl C99Helpers
+{
+    fn new() -> Self
+    { C99Helpers { idents: BTreeSet::new(), defs: String::new(), } }
+
+    fn has(&self, ident: &str) -> bool
+    { self.idents.contains(ident) }
+
+    fn add(&mut self, ident: &str, def: &str)
+    {
+        if self.idents.insert(String::from(ident)) {
+            self.defs.push_str(def);
+        }
+    }
+}
+
+trait C99Var
+{
+    fn c99_var(&self, ctx: &mut C99Context<'_>) -> BackendResult<C99Value>;
+}
+
+struct C99Context<'a>
+{
+    tree: &'a IrTree,
+    local_var_types: Vec<Box<IrType>>,
+    caller_arg_types: Vec<Box<IrType>>,
+    private_closure: Option<(String, Box<IrType>)>,
+    local_closure: Option<(String, Box<IrType>)>,
+    global_closure: Option<(String, Box<IrType>)>,
+    ret_type: Box<IrType>,
+    can_panic: bool,
+    heap_flags: (IrPrivateHeapFlag, IrLocalHeapFlag, IrGlobalHeapFlag),
+    panic_table: Option<&'a mut PanicTable>,
+    helpers: &'a mut C99Helpers,
+    is_initializer: bool,
+}
+
+impl<'a> C99Context<'a>
+{
+    fn new(tree: &'a IrTree, helpers: &'a mut C99Helpers) -> Self
+    {
+        C99Context {
+            tree,
+            local_var_types: Vec::new(),
+            caller_arg_types: Vec::new(),
+            private_closure: None,
+            local_closure: None,
+            global_closure: None,
+            ret_type: Box::new(IrType::Void),
+            can_panic: false,
+            heap_flags: (IrPrivateHeapFlag::None, IrLocalHeapFlag::None, IrGlobalHeapFlag::None),
+            panic_table: None,
+            helpers,
+            is_initializer: false,
+        }
+    }
+
+    fn fun_flags(&self, ident: &String) -> (IrPrivateHeapFlag, IrLocalHeapFlag, IrGlobalHeapFlag, IrPanicFlag)
+    {
+        match self.tree.var(ident) {
+            Some(var) => {
+                match &*var.borrow() {
+                    IrVar::Fun(fun) => {
+                        match &**fun {
+                            IrFun::Fun(_, _, _, _, private_heap_flag, local_heap_flag, global_heap_flag, panic_flag) => (*private_heap_flag, *local_heap_flag, *global_heap_flag, *panic_flag),
+                            IrFun::Caller(_, _, _, _, private_heap_flag, local_heap_flag, global_heap_flag, panic_flag) => (*private_heap_flag, *local_heap_flag, *global_heap_flag, *panic_flag),
+                        }
+                    },
+                    _ => (IrPrivateHeapFlag::None, IrLocalHeapFlag::None, IrGlobalHeapFlag::None, IrPanicFlag::None),
+                }
+            },
+            None => (IrPrivateHeapFlag::None, IrLocalHeapFlag::None, IrGlobalHeapFlag::None, IrPanicFlag::None),
+        }
+    }
+
+    fn fun_can_panic(&self, ident: &String) -> bool
+    { self.fun_flags(ident).3 == IrPanicFlag::Panic }
+
+    fn fun_sig(&self, ident: &String) -> BackendResult<(Vec<IrType>, IrType)>
+    {
+        match self.tree.var(ident) {
+            Some(var) => {
+                match &*var.borrow() {
+                    IrVar::Fun(fun) => {
+                        match &**fun {
+                            IrFun::Fun(_, arg_types, ret_type, _, _, _, _, _) => Ok((arg_types.iter().map(|t| (**t).clone()).collect(), (**ret_type).clone())),
+                            IrFun::Caller(fun_type, arg_types, ret_type, _, _, _, _, _) => {
+                                let mut param_types = vec![(**fun_type).clone()];
+                                param_types.extend(arg_types.iter().map(|t| (**t).clone()));
+                                Ok((param_types, (**ret_type).clone()))
+                            },
+                        }
+                    },
+                    _ => Err(BackendError::Internal(format!("fun_sig: variable {} isn't function", ident))),
+                }
+            },
+            None => Err(BackendError::Internal(format!("fun_sig: no function {}", ident))),
+        }
+    }
+
+    fn hidden_args(&self, ident: &String) -> BackendResult<Vec<&'static str>>
+    {
+        let (private_heap_flag, local_heap_flag, global_heap_flag, panic_flag) = self.fun_flags(ident);
+        if private_heap_flag > self.heap_flags.0 || local_heap_flag > self.heap_flags.1 || global_heap_flag > self.heap_flags.2 {
+            return Err(BackendError::Internal(format!("hidden_args: function {} uses heap", ident)));
+        }
+        if panic_flag == IrPanicFlag::Panic && !self.can_panic {
+            return Err(BackendError::Internal(format!("hidden_args: function {} can panic", ident)));
+        }
+        Ok(hidden_arg_names(private_heap_flag, local_heap_flag, global_heap_flag, panic_flag))
+    }
+
+    fn add_panic_entry(&mut self, entry: PanicEntry) -> BackendResult<u32>
+    {
+        match &mut self.panic_table {
+            Some(panic_table) => Ok(panic_table.add_entry(entry)),
+            None => Err(BackendError::Internal(String::from("add_panic_entry: no panic table"))),
+        }
+    }
+
+    fn panic_call_poses(&self, op: &IrOp) -> Option<Vec<Pos>>
+    {
+        match op {
+            IrOp::CallFun(ident, _, pos, panic_poses) if self.fun_can_panic(ident) => {
+                let mut poses = vec![pos.clone()];
+                poses.extend_from_slice(panic_poses.as_slice());
+                Some(poses)
+            },
+            _ => None,
+        }
+    }
+
+    fn panic_return_to_string(&self) -> String
+    {
+        if *self.ret_type == IrType::Void {
+            String::from("return;")
+        } else {
+            format!("return {};", panic_ret_name())
+        }
+    }
+
+    fn global_var_type(&self, ident: &String) -> BackendResult<Box<IrType>>
+    {
+        match self.tree.var(ident) {
+            Some(var) => {
+                match &*var.borrow() {
+                    IrVar::Const(typ, _) => Ok(typ.clone()),
+                    IrVar::Var(_, _, typ, _) => Ok(typ.clone()),
+                    IrVar::Fun(_) => Err(BackendError::Internal(format!("global_var_type: variable {} is function", ident))),
+                }
+            },
+            None => Err(BackendError::Internal(format!("global_var_type: no variable {}", ident))),
+        }
+    }
+
+    fn local_var_type(&self, idx: usize) -> BackendResult<Box<IrType>>
+    {
+        match self.local_var_types.get(idx) {
+            Some(typ) => Ok(typ.clone()),
+            None => Err(BackendError::Internal(format!("local_var_type: no local variable {}", idx))),
+        }
+    }
+
+    fn caller_arg(&self, idx: usize) -> BackendResult<(String, Box<IrType>)>
+    {
+        match self.caller_arg_types.get(idx) {
+            Some(typ) => Ok((caller_arg_name(idx), typ.clone())),
+            None => Err(BackendError::Internal(format!("caller_arg: no caller argument {}", idx))),
+        }
+    }
+
+    fn closure_var(&self, closure: &Option<(String, Box<IrType>)>, idx: usize) -> BackendResult<(String, Box<IrType>)>
+    {
+        match closure {
+            Some((s, typ)) => self.dot_to_string(s.as_str(), typ, idx),
+            None => Err(BackendError::Internal(format!("closure_var: no closure for variable {}", idx))),
+        }
+    }
+
+    fn field_types(&self, typ: &IrType) -> BackendResult<Vec<(usize, Box<IrType>)>>
+    {
+        match typ {
+            IrType::Struct(ident) => {
+                match self.tree.struct1(ident) {
+                    Some(struct1) => {
+                        match &*struct1.borrow() {
+                            IrStruct::Struct(field_types) => Ok(field_types.iter().cloned().enumerate().collect()),
+                            IrStruct::Closure(field_types) => Ok(field_types.iter().map(|p| (*p.0, p.1.clone())).collect()),
+                        }
+                    },
+                    None => Err(BackendError::Internal(format!("field_types: no structure {}", ident))),
+                }
+            },
+            IrType::Union(ident) => {
+                match self.tree.union(ident) {
+                    Some(union) => Ok(union.borrow().0.iter().cloned().enumerate().collect()),
+                    None => Err(BackendError::Internal(format!("field_types: no union {}", ident))),
+                }
+            },
+            _ => Err(BackendError::Internal(String::from("field_types: type isn't structure or union"))),
+        }
+    }
+
+    fn field_type(&self, typ: &IrType, idx: usize) -> BackendResult<Box<IrType>>
+    {
+        match vector_type_parts(typ) {
+            Some((elem_type, n)) if idx < n => return Ok(Box::new(elem_type)),
+            Some(_) => return Err(BackendError::Internal(format!("field_type: no component {} in vector", idx))),
+            None => (),
+        }
+        match self.field_types(typ)?.into_iter().find(|p| p.0 == idx) {
+            Some((_, field_type)) => Ok(field_type),
+            None => Err(BackendError::Internal(format!("field_type: no field {} in type {}", idx, type_to_string(typ)))),
+        }
+    }
+
+    fn dot_to_string(&self, s: &str, typ: &IrType, idx: usize) -> BackendResult<(String, Box<IrType>)>
+    {
+        let field_type = self.field_type(typ, idx)?;
+        if vector_type_parts(typ).is_some() {
+            Ok((format!("{}.{}", s, vector_field_name(idx)), field_type))
+        } else {
+            Ok((format!("{}.{}", s, field_name(idx)), field_type))
+        }
+    }
+
+    fn elem_type(&self, typ: &IrType) -> BackendResult<Box<IrType>>
+    {
+        match typ {
+            IrType::Array(elem_type, _) => Ok(elem_type.clone()),
+            IrType::Ptr(_, _, elem_type) => Ok(elem_type.clone()),
+            _ => Err(BackendError::Internal(String::from("elem_type: type isn't array or pointer"))),
+        }
+    }
+
+    fn arg_op_to_string(&self, s: String, typ: Box<IrType>, op: &IrArgOp) -> BackendResult<(String, Box<IrType>)>
+    {
+        match op {
+            IrArgOp::Deref(_) => Ok((format!("(*{})", s), self.elem_type(&typ)?)),
+            IrArgOp::Dot(_, idx) => self.dot_to_string(s.as_str(), &typ, *idx),
+            IrArgOp::Arrow(_, idx) => {
+                let elem_type = self.elem_type(&typ)?;
+                if vector_type_parts(&elem_type).is_some() {
+                    self.dot_to_string(format!("(*{})", s).as_str(), &elem_type, *idx)
+                } else {
+                    let field_type = self.field_type(&elem_type, *idx)?;
+                    Ok((format!("{}->{}", s, field_name(*idx)), field_type))
+                }
+            },
+            IrArgOp::Index(_, idx) => Ok((format!("{}[{}]", s, idx), self.elem_type(&typ)?)),
+            IrArgOp::GlobalIndex(_, ident) => Ok((format!("{}[{}]", s, ident), self.elem_type(&typ)?)),
+            IrArgOp::LocalIndex(_, idx) => Ok((format!("{}[{}]", s, local_var_name(*idx)), self.elem_type(&typ)?)),
+            IrArgOp::CallerFunArgIndex(_, idx) => Ok((format!("{}[{}]", s, self.caller_arg(*idx)?.0), self.elem_type(&typ)?)),
+            IrArgOp::PrivateClosureIndex(_, idx) => Ok((format!("{}[{}]", s, self.closure_var(&self.private_closure, *idx)?.0), self.elem_type(&typ)?)),
+            IrArgOp::LocalClosureIndex(_, idx) => Ok((format!("{}[{}]", s, self.closure_var(&self.local_closure, *idx)?.0), self.elem_type(&typ)?)),
+            IrArgOp::GlobalClosureIndex(_, idx) => Ok((format!("{}[{}]", s, self.closure_var(&self.global_closure, *idx)?.0), self.elem_type(&typ)?)),
+        }
+    }
+
+    fn arg_ops_to_string(&self, s: String, typ: Box<IrType>, ops: &[IrArgOp]) -> BackendResult<(String, Box<IrType>)>
+    {
+        let mut s2 = s;
+        let mut type2 = typ;
+        for op in ops {
+            let (tmp_s, tmp_type) = self.arg_op_to_string(s2, type2, op)?;
+            s2 = tmp_s;
+            type2 = tmp_type;
+        }
+        Ok((s2, type2))
+    }
+
+    fn ref_to_string(&self, s: String, typ: Box<IrType>, ops: &[IrArgOp], vector_elem_ptr_type: &Option<Box<IrType>>) -> BackendResult<C99Value>
+    {
+        match (ops.split_last(), vector_elem_ptr_type) {
+            (Some((IrArgOp::Dot(_, idx), ops2)), Some(ptr_type)) => {
+                let (s2, type2) = self.arg_ops_to_string(s, typ, ops2)?;
+                if vector_type_parts(&type2).is_some() {
+                    Ok(C99Value::new(format!("((({}) &{}) + {})", type_to_string(ptr_type), s2, idx), (**ptr_type).clone()))
+                } else {
+                    let (s3, _) = self.dot_to_string(s2.as_str(), &type2, *idx)?;
+                    Ok(C99Value::new(format!("(({}) &{})", type_to_string(ptr_type), s3), (**ptr_type).clone()))
+                }
+            },
+            (_, Some(ptr_type)) => {
+                let (s2, _) = self.arg_ops_to_string(s, typ, ops)?;
+                Ok(C99Value::new(format!("(({}) &{})", type_to_string(ptr_type), s2), (**ptr_type).clone()))
+            },
+            (_, None) => {
+                let (s2, type2) = self.arg_ops_to_string(s, typ, ops)?;
+                Ok(C99Value::new(format!("(&{})", s2), IrType::Ptr(IrPtrModifier::None, IrAccessModifier::None, type2)))
+            },
+        }
+    }
+
+    fn instr_var_to_string(&self, var: &IrInstrVar) -> BackendResult<(String, Box<IrType>)>
+    {
+        match var {
+            IrInstrVar::Global(ident, ops) => self.arg_ops_to_string(ident.clone(), self.global_var_type(ident)?, ops.as_slice()),
+            IrInstrVar::Local(idx, ops) => self.arg_ops_to_string(local_var_name(*idx), self.local_var_type(*idx)?, ops.as_slice()),
+            IrInstrVar::CallerFunArg(idx, ops) => {
+                let (s, typ) = self.caller_arg(*idx)?;
+                self.arg_ops_to_string(s, typ, ops.as_slice())
+            },
+            IrInstrVar::PrivateClosure(idx, ops) => {
+                let (s, typ) = self.closure_var(&self.private_closure, *idx)?;
+                self.arg_ops_to_string(s, typ, ops.as_slice())
+            },
+            IrInstrVar::LocalClosure(idx, ops) => {
+                let (s, typ) = self.closure_var(&self.local_closure, *idx)?;
+                self.arg_ops_to_string(s, typ, ops.as_slice())
+            },
+            IrInstrVar::GlobalClosure(idx, ops) => {
+                let (s, typ) = self.closure_var(&self.global_closure, *idx)?;
+                self.arg_ops_to_string(s, typ, ops.as_slice())
+            },
+            IrInstrVar::PrivateHeap(ops) => self.arg_ops_to_string(String::from(private_heap_name()), Box::new(IrType::Ptr(IrPtrModifier::Private, IrAccessModifier::None, Box::new(IrType::Uint))), ops.as_slice()),
+            IrInstrVar::LocalHeap(ops) => self.arg_ops_to_string(String::from(local_heap_name()), Box::new(IrType::Ptr(IrPtrModifier::Local, IrAccessModifier::None, Box::new(IrType::Uint))), ops.as_slice()),
+            IrInstrVar::GlobalHeap(ops) => self.arg_ops_to_string(String::from(global_heap_name()), Box::new(IrType::Ptr(IrPtrModifier::Global, IrAccessModifier::None, Box::new(IrType::Uint))), ops.as_slice()),
+        }
+    }
+
+    fn helper_suffix(&self, typ: &IrType) -> BackendResult<String>
+    {
+        match type_helper_suffix(typ) {
+            Some(suffix) => Ok(suffix),
+            None => Err(BackendError::Internal(format!("helper_suffix: no helper for type {}", type_to_string(typ)))),
+        }
+    }
+
+    fn lanes_helper(&mut self, ident: &str, arg_types: &[IrType], ret_type: &IrType, lane: &dyn Fn(&[String]) -> BackendResult<String>) -> BackendResult<()>
+    {
+        if self.helpers.has(ident) {
+            return Ok(());
+        }
+        let n = match vector_type_parts(ret_type) {
+            Some((_, n)) => n,
+            None => return Err(BackendError::Internal(format!("lanes_helper: return type of {} isn't vector type", ident))),
+        };
+        let params: Vec<String> = arg_types.iter().enumerate().map(|(i, t)| decl_to_string(t, "", format!("a{}", i).as_str())).collect();
+        let mut s = String::new();
+        writeln!(s, "static {}", decl_to_string(ret_type, "", format!("{}({})", ident, params.join(", ")).as_str())).unwrap();
+        s.push_str("{\n");
+        writeln!(s, "    {} r = {{ 0 }};", type_to_string(ret_type)).unwrap();
+        for i in 0..n {
+            let args: Vec<String> = arg_types.iter().enumerate().map(|(j, t)| {
+                    if vector_type_parts(t).is_some() {
+                        format!("a{}.{}", j, vector_field_name(i))
+                    } else {
+                        format!("a{}", j)
+                    }
+            }).collect();
+            writeln!(s, "    r.{} = {};", vector_field_name(i), lane(args.as_slice())?).unwrap();
+        }
+        s.push_str("    return r;\n");
+        s.push_str("}\n");
+        s.push('\n');
+        self.helpers.add(ident, s.as_str());
+        Ok(())
+    }
+
+    fn call_helper(&self, ident: &str, args: &[C99Value], ret_type: IrType) -> C99Value
+    {
+        let ss: Vec<&str> = args.iter().map(|arg| arg.s.as_str()).collect();
+        C99Value::new(format!("{}({})", ident, ss.join(", ")), ret_type)
+    }
+
+    fn cast(&mut self, value: C99Value, typ: &Option<Box<IrType>>) -> BackendResult<C99Value>
+    {
+        match typ {
+            Some(typ) => self.convert(value, typ),
+            None => Ok(value),
+        }
+    }
+
+    fn convert(&mut self, value: C99Value, typ: &IrType) -> BackendResult<C99Value>
+    {
+        if value.typ == *typ || *typ == IrType::Void {
+            return Ok(C99Value::new(value.s, typ.clone()));
+        }
+        let type_s = type_to_string(typ);
+        if type_to_string(&value.typ) == type_s {
+            return Ok(C99Value::new(value.s, typ.clone()));
+        }
+        match (lanes_kind(&value.typ), lanes_kind(typ)) {
+            (Some((_, None)), Some((_, None))) => return Ok(C99Value::new(format!("(({}) {})", type_s, value.s), typ.clone())),
+            (Some((_, Some(n1))), Some((kind2, Some(n2)))) if n1 == n2 => {
+                if self.is_initializer {
+                    return Err(BackendError::Internal(String::from("convert: unsupported conversion of vector in initializer")));
+                }
+                let ident = format!("vscfl_convert_{}_{}", self.helper_suffix(typ)?, self.helper_suffix(&value.typ)?);
+                let elem_ident = kind_type_ident(kind2);
+                self.lanes_helper(ident.as_str(), std::slice::from_ref(&value.typ), typ, &|args| Ok(format!("({}) {}", elem_ident, args[0])))?;
+                return Ok(self.call_helper(ident.as_str(), &[value], typ.clone()));
+            },
+            (Some((_, None)), Some((kind2, Some(n2)))) => {
+                let elem_value = self.convert(value, &kind_type(kind2))?;
+                if self.is_initializer {
+                    return Ok(C99Value::new(format!("{{ {} }}", vec![elem_value.s; n2].join(", ")), typ.clone()));
+                }
+                let ident = format!("vscfl_splat_{}", self.helper_suffix(typ)?);
+                self.lanes_helper(ident.as_str(), std::slice::from_ref(&elem_value.typ), typ, &|args| Ok(args[0].clone()))?;
+                return Ok(self.call_helper(ident.as_str(), &[elem_value], typ.clone()));
+            },
+            (_, _) => (),
+        }
+        match (&value.typ, typ) {
+            (IrType::Ptr(_, _, _), IrType::Ptr(_, _, _)) => Ok(C99Value::new(format!("(({}) {})", type_s, value.s), typ.clone())),
+            (IrType::Ptr(_, _, _), _) if scalar_kind(typ).is_some() => Ok(C99Value::new(format!("(({}) (uintptr_t) {})", type_s, value.s), typ.clone())),
+            (_, IrType::Ptr(_, _, _)) if scalar_kind(&value.typ).is_some() => Ok(C99Value::new(format!("(({}) (uintptr_t) {})", type_s, value.s), typ.clone())),
+            (_, _) => {
+                let size1 = ir_type_layout(&value.typ, self.tree)?.size;
+                let size2 = ir_type_layout(typ, self.tree)?.size;
+                if size1 != size2 || self.is_initializer {
+                    return Err(BackendError::Internal(String::from("convert: unsupported conversion")));
+                }
+                let ident = format!("vscfl_as_{}_{}", self.helper_suffix(typ)?, self.helper_suffix(&value.typ)?);
+                if !self.helpers.has(ident.as_str()) {
+                    let mut s = String::new();
+                    writeln!(s, "static {}", decl_to_string(typ, "", format!("{}({})", ident, decl_to_string(&value.typ, "", "a")).as_str())).unwrap();
+                    s.push_str("{\n");
+                    writeln!(s, "    {};", decl_to_string(typ, "", "r")).unwrap();
+                    s.push_str("    memcpy(&r, &a, sizeof(r));\n");
+                    s.push_str("    return r;\n");
+                    s.push_str("}\n");
+                    s.push('\n');
+                    self.helpers.add(ident.as_str(), s.as_str());
+                }
+                Ok(self.call_helper(ident.as_str(), &[value], typ.clone()))
+            },
+        }
+    }
+
+    fn value<T: C99Var>(&mut self, value: &IrValue<T>, expected: Option<&IrType>) -> BackendResult<C99Value>
+    {
+        let (typ, s, cast_type) = match value {
+            IrValue::Char(n, typ) => (IrType::Char, int_literal(*n as i128, &IrType::Char), typ),
+            IrValue::Short(n, typ) => (IrType::Short, int_literal(*n as i128, &IrType::Short), typ),
+            IrValue::Int(n, typ) => (IrType::Int, int_literal(*n as i128, &IrType::Int), typ),
+            IrValue::Long(n, typ) => (IrType::Long, int_literal(*n as i128, &IrType::Long), typ),
+            IrValue::Uchar(n, typ) => (IrType::Uchar, int_literal(*n as i128, &IrType::Uchar), typ),
+            IrValue::Ushort(n, typ) => (IrType::Ushort, int_literal(*n as i128, &IrType::Ushort), typ),
+            IrValue::Uint(n, typ) => (IrType::Uint, int_literal(*n as i128, &IrType::Uint), typ),
+            IrValue::Ulong(n, typ) => (IrType::Ulong, int_literal(*n as i128, &IrType::Ulong), typ),
+            IrValue::Float(x, typ) => (IrType::Float, float_to_string(*x), typ),
+            IrValue::Double(x, typ) => (IrType::Double, double_to_string(*x), typ),
+            IrValue::SizeT(n, typ) => (IrType::SizeT, int_literal(*n as i128, &IrType::SizeT), typ),
+            IrValue::PtrdiffT(n, typ) => (IrType::PtrdiffT, int_literal(*n as i128, &IrType::PtrdiffT), typ),
+            IrValue::IntptrT(n, typ) => (IrType::IntptrT, int_literal(*n as i128, &IrType::IntptrT), typ),
+            IrValue::UintptrT(n, typ) => (IrType::UintptrT, int_literal(*n as i128, &IrType::UintptrT), typ),
+            IrValue::Object(object) => return self.object_value(object, expected),
+        };
+        self.cast(C99Value::new(s, typ), cast_type)
+    }
+
+    fn object_type(&self, typ: &Option<Box<IrType>>, expected: Option<&IrType>) -> BackendResult<IrType>
+    {
+        match (typ, expected) {
+            (Some(typ), _) => Ok((**typ).clone()),
+            (None, Some(typ)) => Ok(typ.clone()),
+            (None, None) => Err(BackendError::Internal(String::from("object_type: no type of object"))),
+        }
+    }
+
+    fn compound(&self, typ: &IrType, s: String) -> C99Value
+    {
+        let s2 = if s.is_empty() { String::from("0") } else { s };
+        if self.is_initializer {
+            C99Value::new(format!("{{ {} }}", s2), typ.clone())
+        } else {
+            C99Value::new(format!("(({}) {{ {} }})", type_to_string(typ), s2), typ.clone())
+        }
+    }
+
+    fn object_value<T: C99Var>(&mut self, object: &IrObject<T>, expected: Option<&IrType>) -> BackendResult<C99Value>
+    {
+        match object {
+            IrObject::String(bytes) => Ok(C99Value::new(format!("((const int8_t *) {})", string_to_literal(bytes.as_slice())), IrType::Ptr(IrPtrModifier::Constant, IrAccessModifier::Const, Box::new(IrType::Char)))),
+            IrObject::BuiltinVar(ident, typ1, typ2) => {
+                let value = match builtin_var_literal(ident.as_str()) {
+                    Some((typ, s)) => C99Value::new(s, typ),
+                    None => return Err(BackendError::Internal(format!("object_value: unsupported builtin variable {}", ident))),
+                };
+                let value2 = self.cast(value, typ1)?;
+                self.cast(value2, typ2)
+            },
+            IrObject::Var(var, typ) => {
+                let value = var.c99_var(self)?;
+                self.cast(value, typ)
+            },
+            IrObject::Vector(values, typ) => self.vector_value(values.as_slice(), typ),
+            IrObject::Array(values, typ) => {
+                let typ2 = match (typ, expected, values.first()) {
+                    (None, None, Some(value)) => {
+                        let first = self.value(value, None)?;
+                        IrType::Array(Box::new(first.typ), values.len())
+                    },
+                    (_, _, _) => self.object_type(typ, expected)?,
+                };
+                let elem_type = match &typ2 {
+                    IrType::Array(elem_type, _) => (**elem_type).clone(),
+                    _ => return Err(BackendError::Internal(String::from("object_value: type of array isn't array type"))),
+                };
+                let mut ss: Vec<String> = Vec::new();
+                for value in values {
+                    let value2 = self.value(value, Some(&elem_type))?;
+                    ss.push(self.convert(value2, &elem_type)?.s);
+                }
+                Ok(self.compound(&typ2, ss.join(", ")))
+            },
+            IrObject::Struct(values, field_pairs, typ) => {
+                let typ2 = self.object_type(typ, expected)?;
+                let field_types = self.field_types(&typ2)?;
+                let mut pairs: Vec<(usize, &IrValue<T>)> = Vec::new();
+                for (i, value) in values.iter().enumerate() {
+                    match field_types.get(i) {
+                        Some((idx, _)) => pairs.push((*idx, value)),
+                        None => return Err(BackendError::Internal(String::from("object_value: too many fields"))),
+                    }
+                }
+                for field_pair in field_pairs {
+                    pairs.push((field_pair.0, &field_pair.1));
+                }
+                self.struct_value(&typ2, pairs)
+            },
+            IrObject::Union(idx, value, typ) => {
+                let typ2 = self.object_type(typ, expected)?;
+                let field_type = self.field_type(&typ2, *idx)?;
+                let value2 = self.value(value, Some(&field_type))?;
+                let value3 = self.convert(value2, &field_type)?;
+                Ok(self.compound(&typ2, format!(".{} = {}", field_name(*idx), value3.s)))
+            },
+            IrObject::Closure(field_pairs, typ) => {
+                let typ2 = self.object_type(typ, expected)?;
+                let pairs: Vec<(usize, &IrValue<T>)> = field_pairs.iter().map(|p| (p.0, &p.1)).collect();
+                self.struct_value(&typ2, pairs)
+            },
+            IrObject::Sizeof(typ, typ2) => {
+                let size = ir_type_layout(typ, self.tree)?.size;
+                let value = C99Value::new(int_literal(size as i128, &IrType::SizeT), IrType::SizeT);
+                self.cast(value, typ2)
+            },
+            IrObject::CallerFunIndex(_, idx, typ) => {
+                let value = C99Value::new(format!("{}U", idx), IrType::Uint);
+                self.cast(value, typ)
+            },
+        }
+    }
+
+    fn struct_value<T: C99Var>(&mut self, typ: &IrType, pairs: Vec<(usize, &IrValue<T>)>) -> BackendResult<C99Value>
+    {
+        let mut ss: Vec<String> = Vec::new();
+        for (idx, value) in pairs {
+            let field_type = self.field_type(typ, idx)?;
+            let value2 = self.value(value, Some(&field_type))?;
+            ss.push(format!(".{} = {}", field_name(idx), self.convert(value2, &field_type)?.s));
+        }
+        Ok(self.compound(typ, ss.join(", ")))
+    }
+
+    fn vector_value<T: C99Var>(&mut self, values: &[IrValue<T>], typ: &IrType) -> BackendResult<C99Value>
+    {
+        let (elem_type, n) = match vector_type_parts(typ) {
+            Some(pair) => pair,
+            None => return Err(BackendError::Internal(String::from("vector_value: type isn't vector type"))),
+        };
+        let mut parts: Vec<C99Value> = Vec::new();
+        let mut lens: Vec<usize> = Vec::new();
+        for value in values {
+            let value2 = self.value(value, Some(&elem_type))?;
+            match vector_type_parts(&value2.typ) {
+                Some((_, m)) => {
+                    parts.push(self.convert(value2, &vector_type(&elem_type, m).unwrap_or(IrType::Void))?);
+                    lens.push(m);
+                },
+                None => {
+                    parts.push(self.convert(value2, &elem_type)?);
+                    lens.push(1);
+                },
+            }
+        }
+        let count: usize = lens.iter().sum();
+        if count == 1 && parts.len() == 1 {
+            return self.convert(parts.remove(0), typ);
+        }
+        if count != n {
+            return Err(BackendError::Internal(String::from("vector_value: invalid number of components")));
+        }
+        if lens.iter().all(|m| *m == 1) {
+            let ss: Vec<String> = parts.into_iter().map(|part| part.s).collect();
+            return Ok(self.compound(typ, ss.join(", ")));
+        }
+        if self.is_initializer {
+            return Err(BackendError::Internal(String::from("vector_value: vector components in initializer are unsupported")));
+        }
+        let len_ss: Vec<String> = lens.iter().map(|m| format!("{}", m)).collect();
+        let ident = format!("vscfl_make_{}_{}", self.helper_suffix(typ)?, len_ss.join("_"));
+        if !self.helpers.has(ident.as_str()) {
+            let params: Vec<String> = parts.iter().enumerate().map(|(i, part)| decl_to_string(&part.typ, "", format!("a{}", i).as_str())).collect();
+            let mut s = String::new();
+            writeln!(s, "static {}", decl_to_string(typ, "", format!("{}({})", ident, params.join(", ")).as_str())).unwrap();
+            s.push_str("{\n");
+            writeln!(s, "    {} r = {{ 0 }};", type_to_string(typ)).unwrap();
+            let mut i = 0;
+            for (j, m) in lens.iter().enumerate() {
+                if *m == 1 {
+                    writeln!(s, "    r.{} = a{};", vector_field_name(i), j).unwrap();
+                    i += 1;
+                } else {
+                    for k in 0..*m {
+                        writeln!(s, "    r.{} = a{}.{};", vector_field_name(i), j, vector_field_name(k)).unwrap();
+                        i += 1;
+                    }
+                }
+            }
+            s.push_str("    return r;\n");
+            s.push_str("}\n");
+            s.push('\n');
+            self.helpers.add(ident.as_str(), s.as_str());
+        }
+        Ok(self.call_helper(ident.as_str(), parts.as_slice(), typ.clone()))
+    }
+
+    fn op(&mut self, op: &IrOp, expected: Option<&IrType>) -> BackendResult<C99Value>
+    {
+        match op {
+            IrOp::Load(value) => self.value(value, expected),
+            IrOp::Neg(value) => {
+                let value2 = self.value(value, None)?;
+                self.unary_op(value2, true)
+            },
+            IrOp::Not(value) => {
+                let value2 = self.value(value, None)?;
+                self.unary_op(value2, false)
+            },
+            IrOp::Mul(value1, value2) => self.bin_op(BinOp::Mul, value1, value2),
+            IrOp::Div(value1, value2) => self.bin_op(BinOp::Div, value1, value2),
+            IrOp::Rem(value1, value2) => self.bin_op(BinOp::Rem, value1, value2),
+            IrOp::Add(value1, value2) => self.bin_op(BinOp::Add, value1, value2),
+            IrOp::Sub(value1, value2) => self.bin_op(BinOp::Sub, value1, value2),
+            IrOp::Shl(value1, value2) => self.bin_op(BinOp::Shl, value1, value2),
+            IrOp::Shr(value1, value2) => self.bin_op(BinOp::Shr, value1, value2),
+            IrOp::Eq(value1, value2) => self.bin_op(BinOp::Eq, value1, value2),
+            IrOp::Ne(value1, value2) => self.bin_op(BinOp::Ne, value1, value2),
+            IrOp::Lt(value1, value2) => self.bin_op(BinOp::Lt, value1, value2),
+            IrOp::Ge(value1, value2) => self.bin_op(BinOp::Ge, value1, value2),
+            IrOp::Gt(value1, value2) => self.bin_op(BinOp::Gt, value1, value2),
+            IrOp::Le(value1, value2) => self.bin_op(BinOp::Le, value1, value2),
+            IrOp::And(value1, value2) => self.bin_op(BinOp::And, value1, value2),
+            IrOp::Xor(value1, value2) => self.bin_op(BinOp::Xor, value1, value2),
+            IrOp::Or(value1, value2) => self.bin_op(BinOp::Or, value1, value2),
+            IrOp::CallBuiltinFun(ident, typ, values) => self.call_builtin_fun(ident, typ, values.as_slice()),
+            IrOp::CallFun(ident, values, _, _) | IrOp::CallFunWithoutPanic(ident, values, _) => self.call_fun(ident, values.as_slice()),
+        }
+    }
+
+    fn unary_op(&mut self, value: C99Value, is_neg: bool) -> BackendResult<C99Value>
+    {
+        let typ = promoted_type(&value.typ);
+        let value2 = self.convert(value, &typ)?;
+        match lanes_kind(&typ) {
+            Some((kind, None)) => Ok(C99Value::new(lane_unary_op(kind, value2.s.as_str(), is_neg)?, typ)),
+            Some((kind, Some(_))) => {
+                lane_unary_op(kind, "a", is_neg)?;
+                let ident = format!("vscfl_{}_{}", if is_neg { "neg" } else { "not" }, self.helper_suffix(&typ)?);
+                self.lanes_helper(ident.as_str(), std::slice::from_ref(&typ), &typ, &|args| lane_unary_op(kind, args[0].as_str(), is_neg))?;
+                Ok(self.call_helper(ident.as_str(), &[value2], typ))
+            },
+            None => Err(BackendError::Internal(String::from("unary_op: unsupported type"))),
+        }
+    }
+
+    fn bin_op(&mut self, bin_op: BinOp, value1: &IrValue<IrArgVar>, value2: &IrValue<IrArgVar>) -> BackendResult<C99Value>
+    {
+        let a = self.value(value1, None)?;
+        let b = self.value(value2, None)?;
+        if is_ptr_type(&a.typ) || is_ptr_type(&b.typ) {
+            return self.ptr_bin_op(bin_op, a, b);
+        }
+        let typ = if bin_op.is_shift() {
+            promoted_type(&a.typ)
+        } else if vector_type_parts(&a.typ).is_some() {
+            a.typ.clone()
+        } else if vector_type_parts(&b.typ).is_some() {
+            b.typ.clone()
+        } else {
+            common_type(&a.typ, &b.typ)
+        };
+        let (kind, n) = match lanes_kind(&typ) {
+            Some(pair) => pair,
+            None => return Err(BackendError::Internal(String::from("bin_op: unsupported type"))),
+        };
+        let a2 = self.convert(a, &typ)?;
+        let b2 = self.convert(b, &typ)?;
+        match n {
+            Some(n) => {
+                let ret_type = if bin_op.is_cmp() {
+                    vector_type(&cmp_elem_type(kind), n).unwrap_or(IrType::Void)
+                } else {
+                    typ.clone()
+                };
+                lane_bin_op(bin_op, kind, "a", "b", true)?;
+                let ident = format!("vscfl_{}_{}", bin_op.name(), self.helper_suffix(&typ)?);
+                self.lanes_helper(ident.as_str(), &[typ.clone(), typ.clone()], &ret_type, &|args| lane_bin_op(bin_op, kind, args[0].as_str(), args[1].as_str(), true))?;
+                Ok(self.call_helper(ident.as_str(), &[a2, b2], ret_type))
+            },
+            None => {
+                let ret_type = if bin_op.is_cmp() { IrType::Int } else { typ };
+                Ok(C99Value::new(lane_bin_op(bin_op, kind, a2.s.as_str(), b2.s.as_str(), false)?, ret_type))
+            },
+        }
+    }
+
+    fn ptr_bin_op(&mut self, bin_op: BinOp, a: C99Value, b: C99Value) -> BackendResult<C99Value>
+    {
+        if bin_op.is_cmp() {
+            let a2 = self.convert(a, &IrType::UintptrT)?;
+            let b2 = self.convert(b, &IrType::UintptrT)?;
+            return Ok(C99Value::new(format!("({} {} {})", a2.s, bin_op.op_str(), b2.s), IrType::Int));
+        }
+        match (bin_op, &a.typ, &b.typ) {
+            (BinOp::Sub, IrType::Ptr(_, _, _), IrType::Ptr(_, _, _)) => Ok(C99Value::new(format!("((ptrdiff_t) ({} - {}))", a.s, b.s), IrType::PtrdiffT)),
+            (BinOp::Add | BinOp::Sub, IrType::Ptr(_, _, _), _) | (BinOp::Add, _, IrType::Ptr(_, _, _)) => {
+                let (ptr, offset) = if is_ptr_type(&a.typ) { (a, b) } else { (b, a) };
+                let offset2 = self.convert(offset, &IrType::Long)?;
+                Ok(C99Value::new(format!("({} {} {})", ptr.s, bin_op.op_str(), offset2.s), ptr.typ))
+            },
+            (_, _, _) => Err(BackendError::Internal(String::from("ptr_bin_op: unsupported operation for pointers"))),
+        }
+    }
+
+    fn call_fun(&mut self, ident: &String, values: &[IrValue<IrArgVar>]) -> BackendResult<C99Value>
+    {
+        let (param_types, ret_type) = self.fun_sig(ident)?;
+        let mut ss: Vec<String> = Vec::new();
+        for (i, value) in values.iter().enumerate() {
+            match param_types.get(i) {
+                Some(param_type) => {
+                    let value2 = self.value(value, Some(param_type))?;
+                    ss.push(self.convert(value2, param_type)?.s);
+                },
+                None => return Err(BackendError::Internal(format!("call_fun: too many arguments for function {}", ident))),
+            }
+        }
+        for arg in self.hidden_args(ident)? {
+            ss.push(String::from(arg));
+        }
+        Ok(C99Value::new(format!("{}({})", ident, ss.join(", ")), ret_type))
+    }
+
+    fn args_lanes_type(&self, args: &[C99Value]) -> BackendResult<(IrType, ScalarKind, Option<usize>)>
+    {
+        let typ = match args.iter().find(|arg| vector_type_parts(&arg.typ).is_some()).or(args.first()) {
+            Some(arg) => arg.typ.clone(),
+            None => return Err(BackendError::Internal(String::from("args_lanes_type: no arguments"))),
+        };
+        match lanes_kind(&typ) {
+            Some((kind, n)) => Ok((typ, kind, n)),
+            None => Err(BackendError::Internal(String::from("args_lanes_type: arguments aren't scalars or vectors"))),
+        }
+    }
+
+    fn convert_args(&mut self, args: &[C99Value], typ: &IrType) -> BackendResult<Vec<C99Value>>
+    {
+        let mut args2: Vec<C99Value> = Vec::new();
+        for arg in args {
+            args2.push(self.convert(arg.clone(), typ)?);
+        }
+        Ok(args2)
+    }
+
+    fn call_builtin_fun(&mut self, ident: &String, typ: &Option<Box<IrType>>, values: &[IrValue<IrArgVar>]) -> BackendResult<C99Value>
+    {
+        if ident == "printf" {
+            return self.call_printf(values);
+        }
+        let mut args: Vec<C99Value> = Vec::new();
+        for value in values {
+            args.push(self.value(value, None)?);
+        }
+        if *ident == mangle_private_alloc_fun_name() || *ident == mangle_local_alloc_fun_name() || *ident == mangle_global_alloc_fun_name() {
+            return self.call_alloc_fun(ident, typ, args);
+        }
+        match (ident.as_str(), args.as_slice()) {
+            ("convert", [value]) => {
+                match typ {
+                    Some(typ) => self.convert(value.clone(), typ),
+                    None => Err(BackendError::Internal(String::from("call_builtin_fun: no type for convert"))),
+                }
+            },
+            ("get_work_dim", []) => Ok(C99Value::new(String::from("get_work_dim()"), IrType::Uint)),
+            ("get_global_size" | "get_global_id" | "get_local_size" | "get_local_id" | "get_num_groups" | "get_group_id" | "get_global_offset", [dim]) => {
+                let dim2 = self.convert(dim.clone(), &IrType::Uint)?;
+                Ok(C99Value::new(format!("{}({})", ident, dim2.s), IrType::SizeT))
+            },
+            ("barrier", [flags]) => {
+                let flags2 = self.convert(flags.clone(), &IrType::Uint)?;
+                Ok(C99Value::new(format!("barrier({})", flags2.s), IrType::Void))
+            },
+            ("mem_fence" | "read_mem_fence" | "write_mem_fence", [_]) => Ok(C99Value::new(String::from("((void) 0)"), IrType::Void)),
+            ("all" | "any", [value]) => {
+                let n = match lanes_kind(&value.typ) {
+                    Some((ScalarKind::Int(_, _), n)) => n,
+                    _ => return Err(BackendError::Internal(format!("call_builtin_fun: invalid argument for {}", ident))),
+                };
+                match n {
+                    Some(n) => {
+                        let helper_ident = format!("vscfl_{}_{}", ident, self.helper_suffix(&value.typ)?);
+                        if !self.helpers.has(helper_ident.as_str()) {
+                            let conds: Vec<String> = (0..n).map(|i| format!("a.{} < 0", vector_field_name(i))).collect();
+                            let mut s = String::new();
+                            writeln!(s, "static int32_t {}({} a)", helper_ident, type_to_string(&value.typ)).unwrap();
+                            s.push_str("{\n");
+                            writeln!(s, "    return ({}) ? 1 : 0;", conds.join(if ident == "all" { " && " } else { " || " })).unwrap();
+                            s.push_str("}\n");
+                            s.push('\n');
+                            self.helpers.add(helper_ident.as_str(), s.as_str());
+                        }
+                        Ok(self.call_helper(helper_ident.as_str(), std::slice::from_ref(value), IrType::Int))
+                    },
+                    None => Ok(C99Value::new(format!("({} < 0)", value.s), IrType::Int)),
+                }
+            },
+            ("select", [a, b, c]) => self.call_select_fun(a.clone(), b.clone(), c.clone()),
+            _ => {
+                if ident.starts_with("vload") {
+                    return self.vector_load(ident.as_str(), args.as_slice());
+                }
+                if ident.starts_with("vstore") {
+                    return self.vector_store(ident.as_str(), args.as_slice());
+                }
+                self.call_math_fun(ident.as_str(), args.as_slice())
+            },
+        }
+    }
+
+    fn call_alloc_fun(&mut self, ident: &String, typ: &Option<Box<IrType>>, args: Vec<C99Value>) -> BackendResult<C99Value>
+    {
+        let typ = match typ {
+            Some(typ) => (**typ).clone(),
+            None => return Err(BackendError::Internal(String::from("call_alloc_fun: no type"))),
+        };
+        let modifier = if *ident == mangle_private_alloc_fun_name() {
+            IrPtrModifier::Private
+        } else if *ident == mangle_local_alloc_fun_name() {
+            IrPtrModifier::Local
+        } else {
+            IrPtrModifier::Global
+        };
+        let heap = match args.first() {
+            Some(heap) => self.convert(heap.clone(), &IrType::Ptr(modifier, IrAccessModifier::None, Box::new(IrType::Uint)))?,
+            None => return Err(BackendError::Internal(String::from("call_alloc_fun: no heap"))),
+        };
+        let layout = ir_type_layout(&typ, self.tree)?;
+        let ptr_type = IrType::Ptr(modifier, IrAccessModifier::None, Box::new(typ));
+        Ok(C99Value::new(format!("(({}) {}({}, {}U, {}U))", type_to_string(&ptr_type), ident, heap.s, layout.size, layout.align), ptr_type))
+    }
+
+    fn call_select_fun(&mut self, a: C99Value, b: C99Value, c: C99Value) -> BackendResult<C99Value>
+    {
+        let (typ, _, _) = self.args_lanes_type(&[a.clone(), b.clone()])?;
+        let mut args = self.convert_args(&[a, b], &typ)?;
+        let ident = format!("vscfl_select_{}_{}", self.helper_suffix(&typ)?, self.helper_suffix(&c.typ)?);
+        if vector_type_parts(&c.typ).is_some() {
+            if vector_type_parts(&typ).map(|p| p.1) != vector_type_parts(&c.typ).map(|p| p.1) {
+                return Err(BackendError::Internal(String::from("call_select_fun: invalid arguments for select")));
+            }
+            self.lanes_helper(ident.as_str(), &[typ.clone(), typ.clone(), c.typ.clone()], &typ, &|args| Ok(format!("{} < 0 ? {} : {}", args[2], args[1], args[0])))?;
+        } else if !self.helpers.has(ident.as_str()) {
+            let mut s = String::new();
+            writeln!(s, "static {} {}({} a0, {} a1, {} a2)", type_to_string(&typ), ident, type_to_string(&typ), type_to_string(&typ), type_to_string(&c.typ)).unwrap();
+            s.push_str("{\n");
+            s.push_str("    return a2 != 0 ? a1 : a0;\n");
+            s.push_str("}\n");
+            s.push('\n');
+            self.helpers.add(ident.as_str(), s.as_str());
+        }
+        args.push(c);
+        Ok(self.call_helper(ident.as_str(), args.as_slice(), typ))
+    }
+
+    fn vector_ptr_elem_type(&self, ident: &str, ptr: &C99Value) -> BackendResult<IrType>
+    {
+        match &ptr.typ {
+            IrType::Ptr(_, _, elem_type) if scalar_kind(elem_type).is_some() => Ok((**elem_type).clone()),
+            _ => Err(BackendError::Internal(format!("vector_ptr_elem_type: invalid pointer for {}", ident))),
+        }
+    }
+
+    fn vector_load(&mut self, ident: &str, args: &[C99Value]) -> BackendResult<C99Value>
+    {
+        if ident.starts_with("vload_half") || ident.starts_with("vloada_half") {
+            return Err(BackendError::Internal(format!("vector_load: unsupported builtin function {}", ident)));
+        }
+        let suffix = &ident["vload".len()..];
+        let n = if suffix.is_empty() { 1 } else { suffix.parse::<usize>().unwrap_or(0) };
+        let (offset, ptr) = match args {
+            [offset, ptr] => (self.convert(offset.clone(), &IrType::SizeT)?, ptr.clone()),
+            _ => return Err(BackendError::Internal(String::from("vector_load: invalid number of arguments"))),
+        };
+        let elem_type = self.vector_ptr_elem_type(ident, &ptr)?;
+        if n == 1 {
+            return Ok(C99Value::new(format!("({})[{}]", ptr.s, offset.s), elem_type));
+        }
+        let typ = match vector_type(&elem_type, n) {
+            Some(typ) => typ,
+            None => return Err(BackendError::Internal(format!("vector_load: invalid function {}", ident))),
+        };
+        let helper_ident = format!("vscfl_vload{}_{}", n, self.helper_suffix(&elem_type)?);
+        if !self.helpers.has(helper_ident.as_str()) {
+            let mut s = String::new();
+            writeln!(s, "static {} {}(size_t off, const {} *p)", type_to_string(&typ), helper_ident, type_to_string(&elem_type)).unwrap();
+            s.push_str("{\n");
+            writeln!(s, "    {} r = {{ 0 }};", type_to_string(&typ)).unwrap();
+            for i in 0..n {
+                writeln!(s, "    r.{} = p[off * {}U + {}U];", vector_field_name(i), n, i).unwrap();
+            }
+            s.push_str("    return r;\n");
+            s.push_str("}\n");
+            s.push('\n');
+            self.helpers.add(helper_ident.as_str(), s.as_str());
+        }
+        Ok(self.call_helper(helper_ident.as_str(), &[offset, ptr], typ))
+    }
+
+    fn vector_store(&mut self, ident: &str, args: &[C99Value]) -> BackendResult<C99Value>
+    {
+        if ident.starts_with("vstore_half") || ident.starts_with("vstorea_half") {
+            return Err(BackendError::Internal(format!("vector_store: unsupported builtin function {}", ident)));
+        }
+        let suffix = &ident["vstore".len()..];
+        let n = if suffix.is_empty() { 1 } else { suffix.parse::<usize>().unwrap_or(0) };
+        let (data, offset, ptr) = match args {
+            [data, offset, ptr] => (data.clone(), self.convert(offset.clone(), &IrType::SizeT)?, ptr.clone()),
+            _ => return Err(BackendError::Internal(String::from("vector_store: invalid number of arguments"))),
+        };
+        let elem_type = self.vector_ptr_elem_type(ident, &ptr)?;
+        if n == 1 {
+            let data2 = self.convert(data, &elem_type)?;
+            return Ok(C99Value::new(format!("(({})[{}] = {})", ptr.s, offset.s, data2.s), IrType::Void));
+        }
+        let typ = match vector_type(&elem_type, n) {
+            Some(typ) => typ,
+            None => return Err(BackendError::Internal(format!("vector_store: invalid function {}", ident))),
+        };
+        let data2 = self.convert(data, &typ)?;
+        let helper_ident = format!("vscfl_vstore{}_{}", n, self.helper_suffix(&elem_type)?);
+        if !self.helpers.has(helper_ident.as_str()) {
+            let mut s = String::new();
+            writeln!(s, "static void {}({} d, size_t off, {} *p)", helper_ident, type_to_string(&typ), type_to_string(&elem_type)).unwrap();
+            s.push_str("{\n");
+            for i in 0..n {
+                writeln!(s, "    p[off * {}U + {}U] = d.{};", n, i, vector_field_name(i)).unwrap();
+            }
+            s.push_str("}\n");
+            s.push('\n');
+            self.helpers.add(helper_ident.as_str(), s.as_str());
+        }
+        Ok(self.call_helper(helper_ident.as_str(), &[data2, offset, ptr], IrType::Void))
+    }
+
+    fn scalar_math_helper(&mut self, name: &str, kind: ScalarKind, argc: usize) -> BackendResult<Option<(String, ScalarKind)>>
+    {
+        let (ret_kind, body) = match math_fun_body(name, kind, argc)? {
+            Some(pair) => pair,
+            None => return Ok(None),
+        };
+        let ident = format!("vscfl_{}_{}", name, kind_name(kind));
+        if self.helpers.has(ident.as_str()) {
+            return Ok(Some((ident, ret_kind)));
+        }
+        match (name, kind) {
+            ("mad_hi", _) => {
+                self.scalar_math_helper("mul_hi", kind, 2)?;
+            },
+            ("mul_hi", ScalarKind::Int(64, _)) => self.helpers.add("vscfl_umul_hi64", UMUL_HI64_FUN),
+            (_, _) => (),
+        }
+        let params: Vec<String> = ["a", "b", "c"].iter().take(argc).map(|param| format!("{} {}", kind_type_ident(kind), param)).collect();
+        let mut s = String::new();
+        writeln!(s, "static {} {}({})", kind_type_ident(ret_kind), ident, params.join(", ")).unwrap();
+        s.push_str("{\n");
+        s.push_str(body.as_str());
+        s.push_str("}\n");
+        s.push('\n');
+        self.helpers.add(ident.as_str(), s.as_str());
+        Ok(Some((ident, ret_kind)))
+    }
+
+    fn call_math_fun(&mut self, ident: &str, args: &[C99Value]) -> BackendResult<C99Value>
+    {
+        let name = ident.strip_prefix("native_").or(ident.strip_prefix("half_")).unwrap_or(ident);
+        let (typ, kind, n) = self.args_lanes_type(args)?;
+        match name {
+            "dot" | "length" | "distance" | "normalize" | "cross" | "fast_length" | "fast_distance" | "fast_normalize" => return self.call_geometric_fun(name.strip_prefix("fast_").unwrap_or(name), args, &typ),
+            _ => (),
+        }
+        let relational_name = relational_fun_name(name);
+        let base_name = relational_name.unwrap_or(name);
+        let (scalar_ident, ret_kind) = match self.scalar_math_helper(base_name, kind, args.len())? {
+            Some(pair) => pair,
+            None => return Err(BackendError::Internal(format!("call_math_fun: unsupported builtin function {}", ident))),
+        };
+        let args2 = self.convert_args(args, &typ)?;
+        match n {
+            Some(n) => {
+                let (ret_type, cast) = match relational_name {
+                    Some(_) => {
+                        let cmp_type = cmp_elem_type(kind);
+                        (vector_type(&cmp_type, n).unwrap_or(IrType::Void), format!("({}) -", scalar_type_ident(&cmp_type).unwrap_or("int32_t")))
+                    },
+                    None => (lanes_type(ret_kind, Some(n)).unwrap_or(IrType::Void), String::new()),
+                };
+                let helper_ident = format!("vscfl_{}_{}", base_name, self.helper_suffix(&typ)?);
+                let arg_types = vec![typ.clone(); args2.len()];
+                self.lanes_helper(helper_ident.as_str(), arg_types.as_slice(), &ret_type, &|lanes| Ok(format!("{}{}({})", cast, scalar_ident, lanes.join(", "))))?;
+                Ok(self.call_helper(helper_ident.as_str(), args2.as_slice(), ret_type))
+            },
+            None => Ok(self.call_helper(scalar_ident.as_str(), args2.as_slice(), kind_type(ret_kind))),
+        }
+    }
+
+    fn call_geometric_fun(&mut self, name: &str, args: &[C99Value], typ: &IrType) -> BackendResult<C99Value>
+    {
+        let (kind, n) = match lanes_kind(typ) {
+            Some((ScalarKind::Float(bits), n)) => (ScalarKind::Float(bits), n),
+            _ => return Err(BackendError::Internal(format!("call_geometric_fun: invalid arguments for {}", name))),
+        };
+        let argc = match name {
+            "length" | "normalize" => 1,
+            _ => 2,
+        };
+        if args.len() != argc || (name == "cross" && n.unwrap_or(0) < 3) {
+            return Err(BackendError::Internal(format!("call_geometric_fun: invalid arguments for {}", name)));
+        }
+        let elem_type = kind_type(kind);
+        let e = kind_type_ident(kind);
+        let ret_type = match name {
+            "normalize" | "cross" => typ.clone(),
+            _ => elem_type.clone(),
+        };
+        let lane = |param: &str, i: usize| {
+            match n {
+                Some(_) => format!("(double) {}.{}", param, vector_field_name(i)),
+                None => format!("(double) {}", param),
+            }
+        };
+        let ident = format!("vscfl_{}_{}", name, self.helper_suffix(typ)?);
+        if !self.helpers.has(ident.as_str()) {
+            let len = n.unwrap_or(1);
+            let mut s = String::new();
+            let params: Vec<String> = ["a", "b"].iter().take(argc).map(|param| format!("{} {}", type_to_string(typ), param)).collect();
+            writeln!(s, "static {} {}({})", type_to_string(&ret_type), ident, params.join(", ")).unwrap();
+            s.push_str("{\n");
+            match name {
+                "dot" => {
+                    let terms: Vec<String> = (0..len).map(|i| format!("{} * {}", lane("a", i), lane("b", i))).collect();
+                    writeln!(s, "    return ({}) ({});", e, terms.join(" + ")).unwrap();
+                },
+                "length" => {
+                    let terms: Vec<String> = (0..len).map(|i| format!("{} * {}", lane("a", i), lane("a", i))).collect();
+                    writeln!(s, "    return ({}) sqrt({});", e, terms.join(" + ")).unwrap();
+                },
+                "distance" => {
+                    for i in 0..len {
+                        writeln!(s, "    double d{} = {} - {};", i, lane("a", i), lane("b", i)).unwrap();
+                    }
+                    let terms: Vec<String> = (0..len).map(|i| format!("d{} * d{}", i, i)).collect();
+                    writeln!(s, "    return ({}) sqrt({});", e, terms.join(" + ")).unwrap();
+                },
+                "normalize" => {
+                    let terms: Vec<String> = (0..len).map(|i| format!("{} * {}", lane("a", i), lane("a", i))).collect();
+                    writeln!(s, "    double l = sqrt({});", terms.join(" + ")).unwrap();
+                    match n {
+                        Some(_) => {
+                            s.push_str("    ");
+                            writeln!(s, "{} r = a;", type_to_string(typ)).unwrap();
+                            for i in 0..len {
+                                writeln!(s, "    r.{} = ({}) ({} / l);", vector_field_name(i), e, lane("a", i)).unwrap();
+                            }
+                            s.push_str("    return r;\n");
+                        },
+                        None => writeln!(s, "    return ({}) ({} / l);", e, lane("a", 0)).unwrap(),
+                    }
+                },
+                _ => {
+                    writeln!(s, "    {} r = {{ 0 }};", type_to_string(typ)).unwrap();
+                    for (i, (j, k)) in [(1, 2), (2, 0), (0, 1)].iter().enumerate() {
+                        writeln!(s, "    r.{} = ({}) ({} * {} - {} * {});", vector_field_name(i), e, lane("a", *j), lane("b", *k), lane("a", *k), lane("b", *j)).unwrap();
+                    }
+                    s.push_str("    return r;\n");
+                },
+            }
+            s.push_str("}\n");
+            s.push('\n');
+            self.helpers.add(ident.as_str(), s.as_str());
+        }
+        let args2 = self.convert_args(args, typ)?;
+        Ok(self.call_helper(ident.as_str(), args2.as_slice(), ret_type))
+    }
+
+    fn call_printf(&mut self, values: &[IrValue<IrArgVar>]) -> BackendResult<C99Value>
+    {
+        let bytes = match values.first() {
+            Some(IrValue::Object(object)) => {
+                match &**object {
+                    IrObject::String(bytes) => bytes.clone(),
+                    _ => return Err(BackendError::Internal(String::from("call_printf: format isn't string"))),
+                }
+            },
+            _ => return Err(BackendError::Internal(String::from("call_printf: no format"))),
+        };
+        let items = match parse_printf_format(bytes.as_slice()) {
+            Ok(items) => items,
+            Err(msg) => return Err(BackendError::Internal(format!("call_printf: {}", msg))),
+        };
+        let mut format: Vec<u8> = Vec::new();
+        let mut arg_ss: Vec<String> = Vec::new();
+        let mut value_iter = values[1..].iter();
+        for item in &items {
+            match item {
+                PrintfItem::Bytes(bytes2) => format.extend_from_slice(bytes2.as_slice()),
+                PrintfItem::Conv(conv) => {
+                    let value = match value_iter.next() {
+                        Some(value) => self.value(value, None)?,
+                        None => return Err(BackendError::Internal(String::from("call_printf: too few arguments"))),
+                    };
+                    let (spec, cast) = printf_c_conv(conv)?;
+                    let lanes: Vec<String> = match vector_type_parts(&value.typ) {
+                        Some((_, n)) => (0..n).map(|i| format!("({}).{}", value.s, vector_field_name(i))).collect(),
+                        None => vec![value.s.clone()],
+                    };
+                    format.extend_from_slice(vec![spec; lanes.len()].join(",").as_bytes());
+                    for lane in lanes {
+                        arg_ss.push(format!("{}({})", cast, lane));
+                    }
+                },
+            }
+        }
+        let mut s = format!("(printf({}", string_to_literal(format.as_slice()));
+        for arg_s in &arg_ss {
+            s.push_str(", ");
+            s.push_str(arg_s.as_str());
+        }
+        s.push_str("), 0)");
+        Ok(C99Value::new(s, IrType::Int))
+    }
+}
+
+impl C99Var for IrValueVar
+{
+    fn c99_var(&self, ctx: &mut C99Context<'_>) -> BackendResult<C99Value>
+    {
+        let mut s = self.0.clone();
+        let mut typ = ctx.global_var_type(&self.0)?;
+        for op in &self.1 {
+            let arg_op = match op {
+                IrValueOp::Deref(tmp_type) => IrArgOp::Deref(tmp_type.clone()),
+                IrValueOp::Dot(tmp_type, idx) => IrArgOp::Dot(tmp_type.clone(), *idx),
+                IrValueOp::Arrow(tmp_type, idx) => IrArgOp::Arrow(tmp_type.clone(), *idx),
+                IrValueOp::Index(tmp_type, idx) => IrArgOp::Index(tmp_type.clone(), *idx),
+            };
+            let (tmp_s, tmp_type) = ctx.arg_op_to_string(s, typ, &arg_op)?;
+            s = tmp_s;
+            typ = tmp_type;
+        }
+        Ok(C99Value::new(format!("(&{})", s), IrType::Ptr(IrPtrModifier::None, IrAccessModifier::None, typ)))
+    }
+}
+
+impl C99Var for IrArgVar
+{
+    fn c99_var(&self, ctx: &mut C99Context<'_>) -> BackendResult<C99Value>
+    {
+        let (s, typ) = match self {
+            IrArgVar::Global(ident, ops) => ctx.arg_ops_to_string(ident.clone(), ctx.global_var_type(ident)?, ops.as_slice())?,
+            IrArgVar::Local(idx, ops) => ctx.arg_ops_to_string(local_var_name(*idx), ctx.local_var_type(*idx)?, ops.as_slice())?,
+            IrArgVar::RefGlobal(ident, ops, vector_elem_ptr_type) => return ctx.ref_to_string(ident.clone(), ctx.global_var_type(ident)?, ops.as_slice(), vector_elem_ptr_type),
+            IrArgVar::RefLocal(idx, ops, vector_elem_ptr_type) => return ctx.ref_to_string(local_var_name(*idx), ctx.local_var_type(*idx)?, ops.as_slice(), vector_elem_ptr_type),
+            IrArgVar::CallerFunArg(idx, ops) => {
+                let (s, typ) = ctx.caller_arg(*idx)?;
+                ctx.arg_ops_to_string(s, typ, ops.as_slice())?
+            },
+            IrArgVar::PrivateClosure(idx, ops) => {
+                let (s, typ) = ctx.closure_var(&ctx.private_closure, *idx)?;
+                ctx.arg_ops_to_string(s, typ, ops.as_slice())?
+            },
+            IrArgVar::LocalClosure(idx, ops) => {
+                let (s, typ) = ctx.closure_var(&ctx.local_closure, *idx)?;
+                ctx.arg_ops_to_string(s, typ, ops.as_slice())?
+            },
+            IrArgVar::GlobalClosure(idx, ops) => {
+                let (s, typ) = ctx.closure_var(&ctx.global_closure, *idx)?;
+                ctx.arg_ops_to_string(s, typ, ops.as_slice())?
+            },
+            IrArgVar::RefCallerFunArg(idx, ops, vector_elem_ptr_type) => {
+                let (s, typ) = ctx.caller_arg(*idx)?;
+                return ctx.ref_to_string(s, typ, ops.as_slice(), vector_elem_ptr_type);
+            },
+            IrArgVar::RefPrivateClosure(idx, ops, vector_elem_ptr_type) => {
+                let (s, typ) = ctx.closure_var(&ctx.private_closure, *idx)?;
+                return ctx.ref_to_string(s, typ, ops.as_slice(), vector_elem_ptr_type);
+            },
+            IrArgVar::RefLocalClosure(idx, ops, vector_elem_ptr_type) => {
+                let (s, typ) = ctx.closure_var(&ctx.local_closure, *idx)?;
+                return ctx.ref_to_string(s, typ, ops.as_slice(), vector_elem_ptr_type);
+            },
+            IrArgVar::RefGlobalClosure(idx, ops, vector_elem_ptr_type) => {
+                let (s, typ) = ctx.closure_var(&ctx.global_closure, *idx)?;
+                return ctx.ref_to_string(s, typ, ops.as_slice(), vector_elem_ptr_type);
+            },
+            IrArgVar::PrivateHeap(ops) => ctx.arg_ops_to_string(String::from(private_heap_name()), Box::new(IrType::Ptr(IrPtrModifier::Private, IrAccessModifier::None, Box::new(IrType::Uint))), ops.as_slice())?,
+            IrArgVar::LocalHeap(ops) => ctx.arg_ops_to_string(String::from(local_heap_name()), Box::new(IrType::Ptr(IrPtrModifier::Local, IrAccessModifier::None, Box::new(IrType::Uint))), ops.as_slice())?,
+            IrArgVar::GlobalHeap(ops) => ctx.arg_ops_to_string(String::from(global_heap_name()), Box::new(IrType::Ptr(IrPtrModifier::Global, IrAccessModifier::None, Box::new(IrType::Uint))), ops.as_slice())?,
+            _ => return Err(BackendError::Internal(String::from("c99_var: references to heaps are unsupported"))),
+        };
+        Ok(C99Value::new(s, *typ))
+    }
+}
+
+pub struct C99Emitter
+{}
+
+impl C99Emitter
+{
+    pub fn new() -> Self
+    { C99Emitter {} }
+
+    pub fn emit(&self, tree: &IrTree) -> BackendResult<String>
+    {
+        let (s, _) = self.emit_with_panic_table(tree)?;
+        Ok(s)
+    }
+
+    pub fn emit_with_panic_table(&self, tree: &IrTree) -> BackendResult<(String, PanicTable)>
+    {
+        let mut s = String::new();
+        let mut panic_table = PanicTable::new();
+        let mut helpers = C99Helpers::new();
+        let mut types: Vec<Box<IrType>> = Vec::new();
+        self.collect_types(tree, &mut types);
+        if types.iter().any(|t| type_has(t, &is_half_type)) {
+            return Err(BackendError::Internal(String::from("emit_with_panic_table: half type is unsupported by C99 backend")));
+        }
+        let is_threaded = tree_calls_builtin_fun(tree, "barrier");
+        self.emit_prelude(is_threaded, &mut s);
+        self.emit_vector_type_defs(&mut s);
+        self.emit_type_defs(tree, &mut s)?;
+        self.emit_global_vars(tree, &mut helpers, &mut s)?;
+        self.emit_heap_funs(tree, &mut s);
+        self.emit_panic_funs(tree, &mut s);
+        self.emit_fun_protos(tree, &mut s)?;
+        let mut fun_s = String::new();
+        self.emit_funs(tree, &mut panic_table, &mut helpers, &mut fun_s)?;
+        self.emit_drivers(tree, is_threaded, &mut fun_s)?;
+        s.push_str(helpers.defs.as_str());
+        s.push_str(fun_s.as_str());
+        Ok((s, panic_table))
+    }
+
+    fn emit_prelude(&self, is_threaded: bool, s: &mut String)
+    {
+        s.push_str(PRELUDE);
+        if is_threaded {
+            s.push_str(THREAD_INCLUDE);
+        }
+        s.push_str(MACROS);
+        s.push_str(ND_RANGE_TYPES);
+        if is_threaded {
+            s.push_str(THREAD_TYPES);
+        }
+        s.push_str("typedef struct\n");
+        s.push_str("{\n");
+        s.push_str("    const vscfl_nd_range *nd_range;\n");
+        s.push_str("    size_t num_groups[3];\n");
+        s.push_str("    size_t group_id[3];\n");
+        s.push_str("    size_t local_id[3];\n");
+        if is_threaded {
+            s.push_str("    vscfl_barrier_state *barrier;\n");
+            s.push_str("    void *args;\n");
+        }
+        s.push_str("} vscfl_work_item;\n");
+        s.push('\n');
+        if is_threaded {
+            s.push_str("static VSCFL_THREAD_LOCAL vscfl_work_item *vscfl_wi;\n");
+        } else {
+            s.push_str("static vscfl_work_item *vscfl_wi;\n");
+        }
+        s.push('\n');
+        s.push_str(WORK_ITEM_FUNS);
+        if is_threaded {
+            s.push_str(THREAD_FUNS);
+        } else {
+            s.push_str(SEQUENTIAL_MACROS);
+        }
+    }
+
+    fn emit_vector_type_defs(&self, s: &mut String)
+    {
+        for elem_type in &VECTOR_ELEM_TYPES {
+            for n in &VECTOR_LENS {
+                let elem_ident = scalar_type_ident(elem_type).unwrap_or("int32_t");
+                let elem_size = match scalar_kind(elem_type) {
+                    Some(ScalarKind::Int(bits, _)) | Some(ScalarKind::Float(bits)) => (bits / 8) as usize,
+                    None => 4,
+                };
+                let fields: Vec<String> = (0..*n).map(vector_field_name).collect();
+                writeln!(s, "typedef struct VSCFL_ALIGNED({})", elem_size * if *n == 3 { 4 } else { *n }).unwrap();
+                s.push_str("{\n");
+                writeln!(s, "    {} {};", elem_ident, fields.join(", ")).unwrap();
+                writeln!(s, "}} {};", vector_type_ident(elem_type, *n).unwrap_or_default()).unwrap();
+                s.push('\n');
+            }
+        }
+    }
+
+    fn collect_types(&self, tree: &IrTree, types: &mut Vec<Box<IrType>>)
+    {
+        for def in tree.defs() {
+            match &**def {
+                IrDef::Struct(_, struct1) => {
+                    match &*struct1.borrow() {
+                        IrStruct::Struct(field_types) => types.extend(field_types.iter().cloned()),
+                        IrStruct::Closure(field_types) => types.extend(field_types.values().cloned()),
+                    }
+                },
+                IrDef::Union(_, union) => types.extend(union.borrow().0.iter().cloned()),
+                IrDef::Var(_, var) => {
+                    match &*var.borrow() {
+                        IrVar::Const(typ, _) => types.push(typ.clone()),
+                        IrVar::Var(_, _, typ, _) => types.push(typ.clone()),
+                        IrVar::Fun(fun) => {
+                            match &**fun {
+                                IrFun::Fun(_, arg_types, ret_type, block, _, _, _, _) => {
+                                    types.extend(arg_types.iter().cloned());
+                                    types.push(ret_type.clone());
+                                    block_types(block, types);
+                                },
+                                IrFun::Caller(fun_type, arg_types, ret_type, caller_funs, _, _, _, _) => {
+                                    types.push(fun_type.clone());
+                                    types.extend(arg_types.iter().cloned());
+                                    types.push(ret_type.clone());
+                                    for caller_fun in caller_funs.funs().values() {
+                                        if let IrCallerFun::Lambda(_, _, _, _, block) = caller_fun {
+                                            block_types(block, types);
+                                        }
+                                    }
+                                },
+                            }
+                        },
+                    }
+                },
+            }
+        }
+    }
+
+    fn type_def_idents(&self, tree: &IrTree) -> Vec<IrType>
+    {
+        let mut idents: Vec<IrType> = Vec::new();
+        let mut ident_set: BTreeSet<IrType> = BTreeSet::new();
+        for def in tree.defs() {
+            let typ = match &**def {
+                IrDef::Struct(ident, _) => IrType::Struct(ident.clone()),
+                IrDef::Union(ident, _) => IrType::Union(ident.clone()),
+                IrDef::Var(_, _) => continue,
+            };
+            if ident_set.insert(typ.clone()) {
+                idents.push(typ);
+            }
+        }
+        let mut rest_idents: BTreeSet<IrType> = BTreeSet::new();
+        for ident in tree.structs().keys() {
+            rest_idents.insert(IrType::Struct(ident.clone()));
+        }
+        for ident in tree.unions().keys() {
+            rest_idents.insert(IrType::Union(ident.clone()));
+        }
+        for typ in rest_idents {
+            if ident_set.insert(typ.clone()) {
+                idents.push(typ);
+            }
+        }
+        idents
+    }
+
+    fn field_types(&self, tree: &IrTree, typ: &IrType) -> BackendResult<Vec<(usize, Box<IrType>)>>
+    {
+        match typ {
+            IrType::Struct(ident) => {
+                match tree.struct1(ident) {
+                    Some(struct1) => {
+                        match &*struct1.borrow() {
+                            IrStruct::Struct(field_types) => Ok(field_types.iter().cloned().enumerate().collect()),
+                            IrStruct::Closure(field_types) => Ok(field_types.iter().map(|p| (*p.0, p.1.clone())).collect()),
+                        }
+                    },
+                    None => Err(BackendError::Internal(format!("field_types: no structure {}", ident))),
+                }
+            },
+            IrType::Union(ident) => {
+                match tree.union(ident) {
+                    Some(union) => Ok(union.borrow().0.iter().cloned().enumerate().collect()),
+                    None => Err(BackendError::Internal(format!("field_types: no union {}", ident))),
+                }
+            },
+            _ => Ok(Vec::new()),
+        }
+    }
+
+    fn add_type_deps(&self, tree: &IrTree, typ: &IrType, visited: &mut BTreeSet<IrType>, in_progress: &mut BTreeSet<IrType>, sorted_types: &mut Vec<IrType>) -> BackendResult<()>
+    {
+        match typ {
+            IrType::Struct(_) | IrType::Union(_) => {
+                if visited.contains(typ) {
+                    return Ok(());
+                }
+                if !in_progress.insert(typ.clone()) {
+                    return Err(BackendError::Internal(format!("add_type_deps: recursive type {}", type_to_string(typ))));
+                }
+                for (_, field_type) in self.field_types(tree, typ)? {
+                    self.add_type_deps(tree, &field_type, visited, in_progress, sorted_types)?;
+                }
+                in_progress.remove(typ);
+                visited.insert(typ.clone());
+                sorted_types.push(typ.clone());
+                Ok(())
+            },
+            IrType::Array(elem_type, _) => self.add_type_deps(tree, elem_type, visited, in_progress, sorted_types),
+            _ => Ok(()),
+        }
+    }
+
+    fn emit_type_defs(&self, tree: &IrTree, s: &mut String) -> BackendResult<()>
+    {
+        let idents = self.type_def_idents(tree);
+        if idents.is_empty() {
+            return Ok(());
+        }
+        for typ in &idents {
+            writeln!(s, "{};", type_to_string(typ)).unwrap();
+        }
+        s.push('\n');
+        let mut visited: BTreeSet<IrType> = BTreeSet::new();
+        let mut in_progress: BTreeSet<IrType> = BTreeSet::new();
+        let mut sorted_types: Vec<IrType> = Vec::new();
+        for typ in &idents {
+            self.add_type_deps(tree, typ, &mut visited, &mut in_progress, &mut sorted_types)?;
+        }
+        for typ in &sorted_types {
+            writeln!(s, "{}", type_to_string(typ)).unwrap();
+            s.push_str("{\n");
+            let field_types = self.field_types(tree, typ)?;
+            if field_types.is_empty() {
+                s.push_str("    char dummy;\n");
+            }
+            for (idx, field_type) in field_types {
+                writeln!(s, "    {};", decl_to_string(&field_type, "", field_name(idx).as_str())).unwrap();
+            }
+            s.push_str("};\n\n");
+        }
+        Ok(())
+    }
+
+    fn emit_global_vars(&self, tree: &IrTree, helpers: &mut C99Helpers, s: &mut String) -> BackendResult<()>
+    {
+        let mut ctx = C99Context::new(tree, helpers);
+        ctx.is_initializer = true;
+        let mut is_written = false;
+        for def in tree.defs() {
+            if let IrDef::Var(ident, var) = &**def {
+                let (quals, typ, value) = match &*var.borrow() {
+                    IrVar::Const(typ, value) => ("const ", typ.clone(), value.clone()),
+                    IrVar::Var(_, IrAccessModifier::Const, typ, value) => ("const ", typ.clone(), value.clone()),
+                    IrVar::Var(_, _, typ, value) => ("", typ.clone(), value.clone()),
+                    IrVar::Fun(_) => continue,
+                };
+                let value2 = ctx.value(&value, Some(&typ))?;
+                let value3 = ctx.convert(value2, &typ)?;
+                writeln!(s, "static {} = {};", decl_to_string(&typ, quals, ident.as_str()), value3.s).unwrap();
+                is_written = true;
+            }
+        }
+        if is_written {
+            s.push('\n');
+        }
+        Ok(())
+    }
+
+    fn fun_header(&self, ident: &String, fun: &IrFun) -> BackendResult<String>
+    {
+        match fun {
+            IrFun::Fun(modifier, arg_types, ret_type, _, private_heap_flag, local_heap_flag, global_heap_flag, panic_flag) => {
+                if let IrType::Array(_, _) = &**ret_type {
+                    return Err(BackendError::Internal(format!("fun_header: function {} returns array", ident)));
+                }
+                let prefix = match modifier {
+                    IrFunModifier::Inline => "static inline ",
+                    _ => "static ",
+                };
+                let mut args: Vec<String> = Vec::new();
+                for (i, arg_type) in arg_types.iter().enumerate() {
+                    args.push(decl_to_string(arg_type, "", local_var_name(i).as_str()));
+                }
+                args.extend(hidden_params(*private_heap_flag, *local_heap_flag, *global_heap_flag, *panic_flag, *modifier == IrFunModifier::Kernel, hidden_param));
+                if args.is_empty() {
+                    args.push(String::from("void"));
+                }
+                Ok(format!("{}{}", prefix, decl_to_string(ret_type, "", format!("{}({})", ident, args.join(", ")).as_str())))
+            },
+            IrFun::Caller(fun_type, arg_types, ret_type, _, private_heap_flag, local_heap_flag, global_heap_flag, panic_flag) => {
+                let mut args: Vec<String> = vec![decl_to_string(fun_type, "", caller_arg_name(0).as_str())];
+                for (i, arg_type) in arg_types.iter().enumerate() {
+                    args.push(decl_to_string(arg_type, "", caller_arg_name(i + 1).as_str()));
+                }
+                args.extend(hidden_params(*private_heap_flag, *local_heap_flag, *global_heap_flag, *panic_flag, false, hidden_param));
+                Ok(format!("static {}", decl_to_string(ret_type, "", format!("{}({})", ident, args.join(", ")).as_str())))
+            },
+        }
+    }
+
+    fn emit_fun_protos(&self, tree: &IrTree, s: &mut String) -> BackendResult<()>
+    {
+        let mut is_written = false;
+        for def in tree.defs() {
+            if let IrDef::Var(ident, var) = &**def {
+                if let IrVar::Fun(fun) = &*var.borrow() {
+                    writeln!(s, "{};", self.fun_header(ident, fun)?).unwrap();
+                    is_written = true;
+                }
+            }
+        }
+        if is_written {
+            s.push('\n');
+        }
+        Ok(())
+    }
+
+    fn tree_heap_flags(&self, tree: &IrTree) -> (IrPrivateHeapFlag, IrLocalHeapFlag, IrGlobalHeapFlag)
+    {
+        let mut heap_flags = (IrPrivateHeapFlag::None, IrLocalHeapFlag::None, IrGlobalHeapFlag::None);
+        for def in tree.defs() {
+            if let IrDef::Var(_, var) = &**def {
+                if let IrVar::Fun(fun) = &*var.borrow() {
+                    match &**fun {
+                        IrFun::Fun(_, _, _, _, private_heap_flag, local_heap_flag, global_heap_flag, _) | IrFun::Caller(_, _, _, _, private_heap_flag, local_heap_flag, global_heap_flag, _) => {
+                            heap_flags.0 = heap_flags.0.max(*private_heap_flag);
+                            heap_flags.1 = heap_flags.1.max(*local_heap_flag);
+                            heap_flags.2 = heap_flags.2.max(*global_heap_flag);
+                        },
+                    }
+                }
+            }
+        }
+        heap_flags
+    }
+
+    fn emit_heap_funs(&self, tree: &IrTree, s: &mut String)
+    {
+        let heap_flags = self.tree_heap_flags(tree);
+        if heap_flags.0 == IrPrivateHeapFlag::Heap {
+            writeln!(s, "#ifndef {}", PRIVATE_HEAP_SIZE_MACRO).unwrap();
+            writeln!(s, "#define {} {}", PRIVATE_HEAP_SIZE_MACRO, DEFAULT_PRIVATE_HEAP_SIZE).unwrap();
+            s.push_str("#endif\n");
+            s.push('\n');
+            self.emit_alloc_fun(mangle_private_alloc_fun_name().as_str(), private_heap_name(), false, s);
+        }
+        if heap_flags.1 == IrLocalHeapFlag::Heap {
+            writeln!(s, "#ifndef {}", LOCAL_HEAP_SIZE_MACRO).unwrap();
+            writeln!(s, "#define {} {}", LOCAL_HEAP_SIZE_MACRO, DEFAULT_LOCAL_HEAP_SIZE).unwrap();
+            s.push_str("#endif\n");
+            s.push('\n');
+            writeln!(s, "static VSCFL_ALIGNED({}) uint64_t {}[({} + {}U) / 8U];", HEAP_HEADER_SIZE, local_heap_value_name(), LOCAL_HEAP_SIZE_MACRO, HEAP_HEADER_SIZE + 7).unwrap();
+            s.push('\n');
+            self.emit_alloc_fun(mangle_local_alloc_fun_name().as_str(), local_heap_name(), true, s);
+        }
+        if heap_flags.2 == IrGlobalHeapFlag::Heap {
+            self.emit_alloc_fun(mangle_global_alloc_fun_name().as_str(), global_heap_name(), true, s);
+        }
+    }
+
+    fn emit_alloc_fun(&self, ident: &str, heap: &str, is_atomic: bool, s: &mut String)
+    {
+        writeln!(s, "static void *{}(uint32_t *{}, uint32_t size, uint32_t align)", ident, heap).unwrap();
+        s.push_str("{\n");
+        if is_atomic {
+            s.push_str("    uint32_t off;\n");
+            s.push_str("    VSCFL_LOCK();\n");
+            writeln!(s, "    off = {}[{}];", heap, HEAP_OFFSET_INDEX).unwrap();
+            writeln!(s, "    {}[{}] = off + size + align - 1U;", heap, HEAP_OFFSET_INDEX).unwrap();
+            s.push_str("    VSCFL_UNLOCK();\n");
+            writeln!(s, "    if(off > {}[{}]) {{", heap, HEAP_CAPACITY_INDEX).unwrap();
+            s.push_str("        return NULL;\n");
+            s.push_str("    }\n");
+            s.push_str("    off = (off + align - 1U) & ~(align - 1U);\n");
+        } else {
+            writeln!(s, "    uint32_t off = ({}[{}] + align - 1U) & ~(align - 1U);", heap, HEAP_OFFSET_INDEX).unwrap();
+        }
+        writeln!(s, "    if(off > {}[{}] || size > {}[{}] - off) {{", heap, HEAP_CAPACITY_INDEX, heap, HEAP_CAPACITY_INDEX).unwrap();
+        s.push_str("        return NULL;\n");
+        s.push_str("    }\n");
+        if !is_atomic {
+            writeln!(s, "    {}[{}] = off + size;", heap, HEAP_OFFSET_INDEX).unwrap();
+        }
+        writeln!(s, "    return (void *) ((uint8_t *) {} + {}U + off);", heap, HEAP_HEADER_SIZE).unwrap();
+        s.push_str("}\n");
+        s.push('\n');
+    }
+
+    fn emit_kernel_heaps(&self, private_heap_flag: IrPrivateHeapFlag, local_heap_flag: IrLocalHeapFlag, s: &mut String)
+    {
+        if local_heap_flag == IrLocalHeapFlag::Heap {
+            writeln!(s, "    uint32_t *{} = (uint32_t *) {};", local_heap_name(), local_heap_value_name()).unwrap();
+        }
+        if private_heap_flag == IrPrivateHeapFlag::Heap {
+            writeln!(s, "    VSCFL_ALIGNED({}) uint64_t {}[({} + {}U) / 8U];", HEAP_HEADER_SIZE, private_heap_value_name(), PRIVATE_HEAP_SIZE_MACRO, HEAP_HEADER_SIZE + 7).unwrap();
+            writeln!(s, "    uint32_t *{} = (uint32_t *) {};", private_heap_name(), private_heap_value_name()).unwrap();
+            writeln!(s, "    {}[{}] = 0U;", private_heap_name(), HEAP_OFFSET_INDEX).unwrap();
+            writeln!(s, "    {}[{}] = {};", private_heap_name(), HEAP_CAPACITY_INDEX, PRIVATE_HEAP_SIZE_MACRO).unwrap();
+        }
+    }
+
+    fn tree_can_panic(&self, tree: &IrTree) -> bool
+    {
+        tree.defs().iter().any(|def| {
+                match &**def {
+                    IrDef::Var(_, var) => {
+                        match &*var.borrow() {
+                            IrVar::Fun(fun) => {
+                                match &**fun {
+                                    IrFun::Fun(_, _, _, _, _, _, _, panic_flag) => *panic_flag == IrPanicFlag::Panic,
+                                    IrFun::Caller(_, _, _, _, _, _, _, panic_flag) => *panic_flag == IrPanicFlag::Panic,
+                                }
+                            },
+                            _ => false,
+                        }
+                    },
+                    _ => false,
+                }
+        })
+    }
+
+    fn emit_panic_funs(&self, tree: &IrTree, s: &mut String)
+    {
+        if !self.tree_can_panic(tree) {
+            return;
+        }
+        let pb = panic_buffer_name();
+        let pf = panic_flag_name();
+        writeln!(s, "static void {}(uint32_t *{}, uint32_t *{}, uint32_t id)", mangle_panic_fun_name(), pb, pf).unwrap();
+        s.push_str("{\n");
+        s.push_str("    int is_first;\n");
+        s.push_str("    VSCFL_LOCK();\n");
+        writeln!(s, "    is_first = {}[{}] == 0U;", pb, PANIC_ID_INDEX).unwrap();
+        s.push_str("    if(is_first) {\n");
+        writeln!(s, "        {}[{}] = id;", pb, PANIC_ID_INDEX).unwrap();
+        s.push_str("    }\n");
+        s.push_str("    VSCFL_UNLOCK();\n");
+        s.push_str("    if(is_first) {\n");
+        for i in 0..3 {
+            writeln!(s, "        {}[{}] = (uint32_t) get_global_id({}U);", pb, PANIC_GLOBAL_ID_INDEX + i, i).unwrap();
+        }
+        writeln!(s, "        {}[{}] = 0U;", pb, PANIC_CALL_COUNT_INDEX).unwrap();
+        writeln!(s, "        *{} = 2U;", pf).unwrap();
+        s.push_str("    } else {\n");
+        writeln!(s, "        *{} = 1U;", pf).unwrap();
+        s.push_str("    }\n");
+        s.push_str("}\n");
+        s.push('\n');
+        writeln!(s, "static void {}(uint32_t *{}, uint32_t *{}, uint32_t id)", mangle_panic_call_fun_name(), pb, pf).unwrap();
+        s.push_str("{\n");
+        writeln!(s, "    if(*{} == 2U) {{", pf).unwrap();
+        writeln!(s, "        uint32_t n = {}[{}];", pb, PANIC_CALL_COUNT_INDEX).unwrap();
+        writeln!(s, "        if(n < {}U) {{", MAX_PANIC_CALL_COUNT).unwrap();
+        writeln!(s, "            {}[{}U + n] = id;", pb, PANIC_CALL_ID_INDEX).unwrap();
+        s.push_str("        }\n");
+        writeln!(s, "        {}[{}] = n + 1U;", pb, PANIC_CALL_COUNT_INDEX).unwrap();
+        s.push_str("    }\n");
+        s.push_str("}\n");
+        s.push('\n');
+    }
+
+    fn emit_funs(&self, tree: &IrTree, panic_table: &mut PanicTable, helpers: &mut C99Helpers, s: &mut String) -> BackendResult<()>
+    {
+        for def in tree.defs() {
+            if let IrDef::Var(ident, var) = &**def {
+                if let IrVar::Fun(fun) = &*var.borrow() {
+                    writeln!(s, "{}", self.fun_header(ident, fun)?).unwrap();
+                    match &**fun {
+                        IrFun::Fun(modifier, arg_types, ret_type, block, private_heap_flag, local_heap_flag, global_heap_flag, panic_flag) => {
+                            let mut ctx = C99Context::new(tree, &mut *helpers);
+                            ctx.local_var_types = arg_types.clone();
+                            ctx.ret_type = ret_type.clone();
+                            ctx.can_panic = *panic_flag == IrPanicFlag::Panic;
+                            ctx.heap_flags = (*private_heap_flag, *local_heap_flag, *global_heap_flag);
+                            ctx.panic_table = Some(&mut *panic_table);
+                            let is_kernel = *modifier == IrFunModifier::Kernel;
+                            let has_kernel_heaps = is_kernel && (*private_heap_flag == IrPrivateHeapFlag::Heap || *local_heap_flag == IrLocalHeapFlag::Heap);
+                            if ctx.can_panic || has_kernel_heaps {
+                                s.push_str("{\n");
+                                if is_kernel {
+                                    self.emit_kernel_heaps(*private_heap_flag, *local_heap_flag, s);
+                                    if ctx.can_panic {
+                                        writeln!(s, "    uint32_t {} = 0U;", panic_flag_value_name()).unwrap();
+                                        writeln!(s, "    uint32_t *{} = &{};", panic_flag_name(), panic_flag_value_name()).unwrap();
+                                    }
+                                } else if **ret_type != IrType::Void {
+                                    writeln!(s, "    {};", decl_to_string(ret_type, "", panic_ret_name())).unwrap();
+                                }
+                                self.emit_nested_block_content(block, &mut ctx, 1, false, s)?;
+                                s.push_str("}\n");
+                            } else {
+                                self.emit_block(block, &mut ctx, 0, false, s)?;
+                            }
+                        },
+                        IrFun::Caller(fun_type, arg_types, ret_type, caller_funs, private_heap_flag, local_heap_flag, global_heap_flag, panic_flag) => {
+                            self.emit_caller_body(ident, fun_type, arg_types.as_slice(), ret_type, caller_funs, (*private_heap_flag, *local_heap_flag, *global_heap_flag, *panic_flag), tree, panic_table, helpers, s)?;
+                        },
+                    }
+                    s.push('\n');
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn closure_field(&self, fun_type: &IrType, closure_type: &Option<Box<IrType>>, tree: &IrTree) -> BackendResult<Option<(String, Box<IrType>)>>
+    {
+        match closure_type {
+            Some(closure_type) => {
+                for (idx, field_type) in self.field_types(tree, fun_type)? {
+                    if field_type == *closure_type {
+                        return Ok(Some((format!("{}.{}", caller_arg_name(0), field_name(idx)), closure_type.clone())));
+                    }
+                }
+                Err(BackendError::Internal(format!("closure_field: no field for closure {}", type_to_string(closure_type))))
+            },
+            None => Ok(None),
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn emit_caller_body(&self, ident: &String, fun_type: &IrType, arg_types: &[Box<IrType>], ret_type: &IrType, caller_funs: &IrCallerFuns, flags: (IrPrivateHeapFlag, IrLocalHeapFlag, IrGlobalHeapFlag, IrPanicFlag), tree: &IrTree, panic_table: &mut PanicTable, helpers: &mut C99Helpers, s: &mut String) -> BackendResult<()>
+    {
+        let (private_heap_flag, local_heap_flag, global_heap_flag, panic_flag) = flags;
+        let args: Vec<String> = (1..=arg_types.len()).map(caller_arg_name).collect();
+        let index_field = match fun_type {
+            IrType::Struct(_) => format!("{}.{}", caller_arg_name(0), field_name(0)),
+            _ => return Err(BackendError::Internal(format!("emit_caller_body: function type of caller {} isn't structure", ident))),
+        };
+        s.push_str("{\n");
+        if panic_flag == IrPanicFlag::Panic && *ret_type != IrType::Void {
+            writeln!(s, "    {};", decl_to_string(ret_type, "", panic_ret_name())).unwrap();
+        }
+        writeln!(s, "    switch({}) {{", index_field).unwrap();
+        for (idx, caller_fun) in caller_funs.funs() {
+            writeln!(s, "        case {}U:", idx).unwrap();
+            match caller_fun {
+                IrCallerFun::Fun(fun_ident) => {
+                    let mut ctx = C99Context::new(tree, &mut *helpers);
+                    ctx.can_panic = panic_flag == IrPanicFlag::Panic;
+                    ctx.heap_flags = (private_heap_flag, local_heap_flag, global_heap_flag);
+                    let mut fun_args = args.clone();
+                    fun_args.extend(ctx.hidden_args(fun_ident)?.into_iter().map(String::from));
+                    if *ret_type == IrType::Void {
+                        writeln!(s, "            {}({});", fun_ident, fun_args.join(", ")).unwrap();
+                        s.push_str("            return;\n");
+                    } else {
+                        writeln!(s, "            return {}({});", fun_ident, fun_args.join(", ")).unwrap();
+                    }
+                },
+                IrCallerFun::Lambda(private_closure_type, local_closure_type, global_closure_type, start_idx, block) => {
+                    let private_closure = self.closure_field(fun_type, private_closure_type, tree)?;
+                    let local_closure = self.closure_field(fun_type, local_closure_type, tree)?;
+                    let global_closure = self.closure_field(fun_type, global_closure_type, tree)?;
+                    let mut ctx = C99Context::new(tree, &mut *helpers);
+                    *ctx.ret_type = ret_type.clone();
+                    ctx.can_panic = panic_flag == IrPanicFlag::Panic;
+                    ctx.heap_flags = (private_heap_flag, local_heap_flag, global_heap_flag);
+                    ctx.panic_table = Some(&mut *panic_table);
+                    ctx.caller_arg_types.push(Box::new(fun_type.clone()));
+                    ctx.caller_arg_types.extend(arg_types.iter().cloned());
+                    ctx.private_closure = private_closure;
+                    ctx.local_closure = local_closure;
+                    ctx.global_closure = global_closure;
+                    ctx.local_var_types = vec![Box::new(IrType::Void); *start_idx];
+                    s.push_str("            {\n");
+                    for (i, arg_type) in arg_types.iter().enumerate() {
+                        writeln!(s, "                {} = {};", decl_to_string(arg_type, "", local_var_name(start_idx + i).as_str()), caller_arg_name(i + 1)).unwrap();
+                        ctx.local_var_types.push(arg_type.clone());
+                    }
+                    self.emit_nested_block_content(block, &mut ctx, 4, false, s)?;
+                    s.push_str("            }\n");
+                },
+                IrCallerFun::InlineFun(_, _) => return Err(BackendError::Internal(format!("emit_caller_body: inline functions of caller {} are unsupported", ident))),
+            }
+        }
+        s.push_str("    }\n");
+        if *ret_type != IrType::Void {
+            writeln!(s, "    {{\n        {} = {{ 0 }};\n        return r;\n    }}", decl_to_string(ret_type, "", "r")).unwrap();
+        }
+        s.push_str("}\n");
+        Ok(())
+    }
+
+    fn indent(&self, level: usize, s: &mut String)
+    {
+        for _ in 0..level {
+            s.push_str("    ");
+        }
+    }
+
+    fn emit_block(&self, block: &IrBlock, ctx: &mut C99Context<'_>, level: usize, is_in_switch: bool, s: &mut String) -> BackendResult<()>
+    {
+        self.indent(level, s);
+        s.push_str("{\n");
+        self.emit_nested_block_content(block, ctx, level + 1, is_in_switch, s)?;
+        self.indent(level, s);
+        s.push_str("}\n");
+        Ok(())
+    }
+
+    fn emit_array_copy(&self, dst: String, src: String, typ: &IrType, level: usize, s: &mut String)
+    {
+        match typ {
+            IrType::Array(elem_type, len) => {
+                let idx_name = format!("i{}", level);
+                self.indent(level, s);
+                writeln!(s, "for(size_t {} = 0; {} < {}; {}++) {{", idx_name, idx_name, len, idx_name).unwrap();
+                self.emit_array_copy(format!("{}[{}]", dst, idx_name), format!("{}[{}]", src, idx_name), elem_type, level + 1, s);
+                self.indent(level, s);
+                s.push_str("}\n");
+            },
+            _ => {
+                self.indent(level, s);
+                writeln!(s, "{} = {};", dst, src).unwrap();
+            },
+        }
+    }
+
+    fn op_to_string(&self, op: &IrOp, typ: &IrType, ctx: &mut C99Context<'_>) -> BackendResult<String>
+    {
+        let value = ctx.op(op, Some(typ))?;
+        match typ {
+            IrType::Array(_, _) => Ok(value.s),
+            _ => Ok(ctx.convert(value, typ)?.s),
+        }
+    }
+
+    fn emit_instr(&self, instr: &IrInstr, ctx: &mut C99Context<'_>, level: usize, is_in_switch: bool, s: &mut String) -> BackendResult<()>
+    {
+        match instr {
+            IrInstr::Op(op) => {
+                let value = ctx.op(op, None)?;
+                self.indent(level, s);
+                if value.typ == IrType::Void {
+                    writeln!(s, "{};", value.s).unwrap();
+                } else {
+                    writeln!(s, "(void) {};", value.s).unwrap();
+                }
+                self.emit_panic_call_check(op, ctx, level, s)?;
+            },
+            IrInstr::Assign(var, op) => {
+                let (var_s, typ) = ctx.instr_var_to_string(var)?;
+                let op_s = self.op_to_string(op, &typ, ctx)?;
+                match &*typ {
+                    IrType::Array(_, _) => self.emit_array_copy(var_s, op_s, &typ, level, s),
+                    _ => {
+                        self.indent(level, s);
+                        writeln!(s, "{} = {};", var_s, op_s).unwrap();
+                    },
+                }
+                self.emit_panic_call_check(op, ctx, level, s)?;
+            },
+            IrInstr::Return(None) => {
+                self.indent(level, s);
+                s.push_str("return;\n");
+            },
+            IrInstr::Return(Some(op)) => {
+                let ret_type = ctx.ret_type.clone();
+                let op_s = self.op_to_string(op, &ret_type, ctx)?;
+                if ctx.panic_call_poses(op).is_some() {
+                    self.indent(level, s);
+                    writeln!(s, "{} = {};", panic_ret_name(), op_s).unwrap();
+                    self.emit_panic_call_check(op, ctx, level, s)?;
+                    self.indent(level, s);
+                    writeln!(s, "return {};", panic_ret_name()).unwrap();
+                } else {
+                    self.indent(level, s);
+                    writeln!(s, "return {};", op_s).unwrap();
+                }
+            },
+            IrInstr::Break => {
+                if is_in_switch {
+                    return Err(BackendError::Internal(String::from("emit_instr: break in switch")));
+                }
+                self.indent(level, s);
+                s.push_str("break;\n");
+            },
+            IrInstr::Continue => {
+                self.indent(level, s);
+                s.push_str("continue;\n");
+            },
+            IrInstr::Block(block) => self.emit_block(block, ctx, level, is_in_switch, s)?,
+            IrInstr::If(op, block1, block2) => {
+                if ctx.panic_call_poses(op).is_some() {
+                    return Err(BackendError::Internal(String::from("emit_instr: call of function that can panic in condition")));
+                }
+                let op_s = ctx.op(op, None)?.s;
+                self.indent(level, s);
+                writeln!(s, "if({}) {{", op_s).unwrap();
+                self.emit_nested_block_content(block1, ctx, level + 1, is_in_switch, s)?;
+                if !block2.instrs().is_empty() || !block2.local_var_pairs().is_empty() {
+                    self.indent(level, s);
+                    s.push_str("} else {\n");
+                    self.emit_nested_block_content(block2, ctx, level + 1, is_in_switch, s)?;
+                }
+                self.indent(level, s);
+                s.push_str("}\n");
+            },
+            IrInstr::Switch(op, cases) => {
+                if ctx.panic_call_poses(op).is_some() {
+                    return Err(BackendError::Internal(String::from("emit_instr: call of function that can panic in condition")));
+                }
+                let has_break = cases.iter().any(|case| {
+                        match case {
+                            IrCase::Case(_, block) => block_has_break(block),
+                            IrCase::Default(block) => block_has_break(block),
+                        }
+                });
+                if has_break {
+                    self.emit_switch_as_ifs(op, cases, ctx, level, is_in_switch, s)?;
+                } else {
+                    let op_s = ctx.op(op, None)?.s;
+                    self.indent(level, s);
+                    writeln!(s, "switch({}) {{", op_s).unwrap();
+                    for case in cases {
+                        let block = match case {
+                            IrCase::Case(value, block) => {
+                                self.indent(level + 1, s);
+                                writeln!(s, "case {}: {{", case_value_to_string(value)?).unwrap();
+                                block
+                            },
+                            IrCase::Default(block) => {
+                                self.indent(level + 1, s);
+                                s.push_str("default: {\n");
+                                block
+                            },
+                        };
+                        self.emit_nested_block_content(block, ctx, level + 2, true, s)?;
+                        self.indent(level + 2, s);
+                        s.push_str("break;\n");
+                        self.indent(level + 1, s);
+                        s.push_str("}\n");
+                    }
+                    self.indent(level, s);
+                    s.push_str("}\n");
+                }
+            },
+            IrInstr::Loop(block) => {
+                self.indent(level, s);
+                s.push_str("for(;;) {\n");
+                self.emit_nested_block_content(block, ctx, level + 1, false, s)?;
+                self.indent(level, s);
+                s.push_str("}\n");
+            },
+            IrInstr::Panic(msg, pos, panic_poses) => {
+                if !ctx.can_panic {
+                    return Err(BackendError::Internal(String::from("emit_instr: panic in function that can't panic")));
+                }
+                let mut poses = vec![pos.clone()];
+                poses.extend_from_slice(panic_poses.as_slice());
+                let id = ctx.add_panic_entry(PanicEntry::Panic(msg.clone(), poses))?;
+                self.indent(level, s);
+                writeln!(s, "{}({}, {}, {}U);", mangle_panic_fun_name(), panic_buffer_name(), panic_flag_name(), id).unwrap();
+                self.indent(level, s);
+                writeln!(s, "{}", ctx.panic_return_to_string()).unwrap();
+            },
+        }
+        Ok(())
+    }
+
+    fn emit_panic_call_check(&self, op: &IrOp, ctx: &mut C99Context<'_>, level: usize, s: &mut String) -> BackendResult<()>
+    {
+        if let Some(poses) = ctx.panic_call_poses(op) {
+            let id = ctx.add_panic_entry(PanicEntry::Call(poses))?;
+            self.indent(level, s);
+            writeln!(s, "if(*{} != 0U) {{", panic_flag_name()).unwrap();
+            self.indent(level + 1, s);
+            writeln!(s, "{}({}, {}, {}U);", mangle_panic_call_fun_name(), panic_buffer_name(), panic_flag_name(), id).unwrap();
+            self.indent(level + 1, s);
+            writeln!(s, "{}", ctx.panic_return_to_string()).unwrap();
+            self.indent(level, s);
+            s.push_str("}\n");
+        }
+        Ok(())
+    }
+
+    fn emit_nested_block_content(&self, block: &IrBlock, ctx: &mut C99Context<'_>, level: usize, is_in_switch: bool, s: &mut String) -> BackendResult<()>
+    {
+        let old_len = ctx.local_var_types.len();
+        for local_var_pair in block.local_var_pairs() {
+            let IrLocalVarPair(modifier, typ) = local_var_pair;
+            let name = local_var_name(ctx.local_var_types.len());
+            let quals = match modifier {
+                IrLocalVarModifier::Local | IrLocalVarModifier::Global => "static ",
+                _ => "",
+            };
+            self.indent(level, s);
+            writeln!(s, "{}{};", quals, decl_to_string(typ, "", name.as_str())).unwrap();
+            ctx.local_var_types.push(typ.clone());
+        }
+        for instr in block.instrs() {
+            self.emit_instr(instr, ctx, level, is_in_switch, s)?;
+        }
+        ctx.local_var_types.truncate(old_len);
+        Ok(())
+    }
+
+    fn emit_switch_as_ifs(&self, op: &IrOp, cases: &[IrCase], ctx: &mut C99Context<'_>, level: usize, is_in_switch: bool, s: &mut String) -> BackendResult<()>
+    {
+        let op_s = ctx.op(op, None)?.s;
+        let mut is_first = true;
+        let mut default_block: Option<&IrBlock> = None;
+        for case in cases {
+            match case {
+                IrCase::Case(value, block) => {
+                    self.indent(level, s);
+                    if is_first {
+                        writeln!(s, "if({} == {}) {{", op_s, case_value_to_string(value)?).unwrap();
+                    } else {
+                        writeln!(s, "}} else if({} == {}) {{", op_s, case_value_to_string(value)?).unwrap();
+                    }
+                    self.emit_nested_block_content(block, ctx, level + 1, is_in_switch, s)?;
+                    is_first = false;
+                },
+                IrCase::Default(block) => default_block = Some(block),
+            }
+        }
+        match default_block {
+            Some(block) => {
+                self.indent(level, s);
+                if is_first {
+                    s.push_str("{\n");
+                } else {
+                    s.push_str("} else {\n");
+                }
+                self.emit_nested_block_content(block, ctx, level + 1, is_in_switch, s)?;
+                self.indent(level, s);
+                s.push_str("}\n");
+            },
+            None => {
+                if !is_first {
+                    self.indent(level, s);
+                    s.push_str("}\n");
+                }
+            },
+        }
+        Ok(())
+    }
+
+    fn emit_drivers(&self, tree: &IrTree, is_threaded: bool, s: &mut String) -> BackendResult<()>
+    {
+        let has_local_heap = self.tree_heap_flags(tree).1 == IrLocalHeapFlag::Heap;
+        for def in tree.defs() {
+            if let IrDef::Var(ident, var) = &**def {
+                if let IrVar::Fun(fun) = &*var.borrow() {
+                    if let IrFun::Fun(IrFunModifier::Kernel, arg_types, _, _, _, _, global_heap_flag, panic_flag) = &**fun {
+                        let mut params: Vec<String> = vec![String::from("const vscfl_nd_range *nd_range")];
+                        let mut args: Vec<String> = Vec::new();
+                        for (i, arg_type) in arg_types.iter().enumerate() {
+                            params.push(decl_to_string(arg_type, "", local_var_name(i).as_str()));
+                            args.push(local_var_name(i));
+                        }
+                        params.extend(hidden_params(IrPrivateHeapFlag::None, IrLocalHeapFlag::None, *global_heap_flag, *panic_flag, true, hidden_param));
+                        args.extend(hidden_arg_names(IrPrivateHeapFlag::None, IrLocalHeapFlag::None, *global_heap_flag, *panic_flag).into_iter().filter(|arg| *arg != panic_flag_name()).map(String::from));
+                        if is_threaded {
+                            self.emit_threaded_driver(ident, arg_types.as_slice(), params.as_slice(), args.as_slice(), has_local_heap, s);
+                        } else {
+                            self.emit_sequential_driver(ident, params.as_slice(), args.as_slice(), has_local_heap, s);
+                        }
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn emit_local_heap_reset(&self, level: usize, s: &mut String)
+    {
+        self.indent(level, s);
+        writeln!(s, "((uint32_t *) {})[{}] = 0U;", local_heap_value_name(), HEAP_OFFSET_INDEX).unwrap();
+        self.indent(level, s);
+        writeln!(s, "((uint32_t *) {})[{}] = {};", local_heap_value_name(), HEAP_CAPACITY_INDEX, LOCAL_HEAP_SIZE_MACRO).unwrap();
+    }
+
+    fn emit_sequential_driver(&self, ident: &String, params: &[String], args: &[String], has_local_heap: bool, s: &mut String)
+    {
+        writeln!(s, "int {}({})", driver_name(ident), params.join(", ")).unwrap();
+        s.push_str("{\n");
+        s.push_str("    vscfl_work_item wi;\n");
+        s.push_str("    if(vscfl_init_work_item(&wi, nd_range) != 0) {\n");
+        s.push_str("        return -1;\n");
+        s.push_str("    }\n");
+        s.push_str("    vscfl_wi = &wi;\n");
+        s.push_str("    do {\n");
+        if has_local_heap {
+            self.emit_local_heap_reset(2, s);
+        }
+        s.push_str("        memset(wi.local_id, 0, sizeof(wi.local_id));\n");
+        s.push_str("        do {\n");
+        writeln!(s, "            {}({});", ident, args.join(", ")).unwrap();
+        s.push_str("        } while(vscfl_next_id(wi.local_id, nd_range->local_size));\n");
+        s.push_str("    } while(vscfl_next_id(wi.group_id, wi.num_groups));\n");
+        s.push_str("    vscfl_wi = NULL;\n");
+        s.push_str("    return 0;\n");
+        s.push_str("}\n");
+        s.push('\n');
+    }
+
+    fn emit_threaded_driver(&self, ident: &String, arg_types: &[Box<IrType>], params: &[String], args: &[String], has_local_heap: bool, s: &mut String)
+    {
+        let args_ident = format!("vscfl_args_{}", ident);
+        let thread_ident = format!("vscfl_thread_{}", ident);
+        s.push_str("typedef struct\n");
+        s.push_str("{\n");
+        for param in &params[1..] {
+            writeln!(s, "    {};", param).unwrap();
+        }
+        if params.len() == 1 {
+            s.push_str("    char dummy;\n");
+        }
+        writeln!(s, "}} {};", args_ident).unwrap();
+        s.push('\n');
+        writeln!(s, "static void *{}(void *arg)", thread_ident).unwrap();
+        s.push_str("{\n");
+        writeln!(s, "    {} *args;", args_ident).unwrap();
+        s.push_str("    vscfl_wi = (vscfl_work_item *) arg;\n");
+        writeln!(s, "    args = ({} *) vscfl_wi->args;", args_ident).unwrap();
+        let arg_ss: Vec<String> = args.iter().map(|arg| format!("args->{}", arg)).collect();
+        writeln!(s, "    {}({});", ident, arg_ss.join(", ")).unwrap();
+        s.push_str("    return NULL;\n");
+        s.push_str("}\n");
+        s.push('\n');
+        writeln!(s, "int {}({})", driver_name(ident), params.join(", ")).unwrap();
+        s.push_str("{\n");
+        s.push_str("    vscfl_work_item wi;\n");
+        writeln!(s, "    {} args;", args_ident).unwrap();
+        s.push_str("    if(vscfl_init_work_item(&wi, nd_range) != 0) {\n");
+        s.push_str("        return -1;\n");
+        s.push_str("    }\n");
+        for (i, arg) in args.iter().enumerate() {
+            match arg_types.get(i).map(|t| &**t) {
+                Some(IrType::Array(_, _)) => writeln!(s, "    memcpy(args.{}, {}, sizeof(args.{}));", arg, arg, arg).unwrap(),
+                _ => writeln!(s, "    args.{} = {};", arg, arg).unwrap(),
+            }
+        }
+        s.push_str("    wi.args = &args;\n");
+        s.push_str("    do {\n");
+        if has_local_heap {
+            self.emit_local_heap_reset(2, s);
+        }
+        s.push_str("        if(vscfl_run_work_group(&wi, ");
+        s.push_str(thread_ident.as_str());
+        s.push_str(") != 0) {\n");
+        s.push_str("            return -2;\n");
+        s.push_str("        }\n");
+        s.push_str("    } while(vscfl_next_id(wi.group_id, wi.num_groups));\n");
+        s.push_str("    return 0;\n");
+        s.push_str("}\n");
+        s.push('\n');
+    }
+}
+
+impl Default for C99Emitter
+{
+    fn default() -> Self
+    { Self::new() }
+}
+
+pub fn emit_ir_tree_to_c99(tree: &IrTree) -> BackendResult<String>
+{
+    let emitter = C99Emitter::new();
+    emitter.emit(tree)
+}
+
+pub fn emit_ir_tree_to_c99_with_panic_table(tree: &IrTree) -> BackendResult<(String, PanicTable)>
+{
+    let emitter = C99Emitter::new();
+    emitter.emit_with_panic_table(tree)
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,344 @@
+//
+// Copyright (c) 2025 Łukasz Szpakowski
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+use std::process::Command;
+use crate::frontend::do_frontend_phases;
+use crate::backend::interpreter::*;
+use crate::backend::ir::test_utils::*;
+use crate::backend::lowering::*;
+use crate::utils::test_utils;
+use super::*;
+
+fn emit_ir_string(s: &str) -> String
+{
+    let ir_tree = parse_ir_string(s);
+    match emit_ir_tree_to_c99(&ir_tree) {
+        Ok(src) => src,
+        Err(err) => {
+            println!("{}", err);
+            panic!();
+        },
+    }
+}
+
+fn has_c_compiler() -> bool
+{
+    match Command::new("cc").arg("--version").output() {
+        Ok(output) => output.status.success(),
+        Err(_) => false,
+    }
+}
+
+fn compile_and_run(name: &str, src: &str, main: &str) -> String
+{
+    let src2 = format!("{}{}", src, main);
+    test_utils::compile_and_run(format!("c99_{}", name).as_str(), "main.c", src2.as_str(), |src_path, exe_path| {
+            let mut command = Command::new("cc");
+            command.arg("-std=c99").arg("-pthread").arg("-o").arg(exe_path).arg(src_path).arg("-lm");
+            command
+    })
+}
+
+fn buffer_main(kernel_ident: &str, global_size: usize, local_size: usize, init: &[i32], is_float: bool) -> String
+{
+    let elem_ident = if is_float { "float" } else { "int32_t" };
+    let len = init.len();
+    let init_ss: Vec<String> = init.iter().map(|x| if is_float { format!("{:?}f", f32::from_bits(*x as u32)) } else { format!("{}", x) }).collect();
+    let mut s = String::new();
+    s.push_str("int main(void)\n");
+    s.push_str("{\n");
+    writeln!(s, "    vscfl_nd_range nd_range = {{ 1U, {{ 0, 0, 0 }}, {{ {}, 1, 1 }}, {{ {}, 1, 1 }} }};", global_size, local_size).unwrap();
+    writeln!(s, "    {} buf[{}] = {{ {} }};", elem_ident, len, init_ss.join(", ")).unwrap();
+    s.push_str("    size_t i;\n");
+    writeln!(s, "    if({}(&nd_range, buf) != 0) {{", driver_name(kernel_ident)).unwrap();
+    s.push_str("        return 1;\n");
+    s.push_str("    }\n");
+    writeln!(s, "    for(i = 0; i < {}; i++) {{", len).unwrap();
+    if is_float {
+        s.push_str("        printf(\"%.4f\\n\", (double) buf[i]);\n");
+    } else {
+        s.push_str("        printf(\"%d\\n\", (int) buf[i]);\n");
+    }
+    s.push_str("    }\n");
+    s.push_str("    return 0;\n");
+    s.push_str("}\n");
+    s
+}
+
+fn run_ir_string_and_compare(name: &str, s: &str, global_size: usize, local_size: usize, init: &[i32], is_float: bool)
+{
+    let ir_tree = parse_ir_string(s);
+    let mut args = vec![KernelArg::Buffer(int_buffer(init))];
+    let output = match run_kernel(&ir_tree, "k", &NdRange::new(&[global_size], &[local_size]), args.as_mut_slice()) {
+        Ok(output) => output,
+        Err(err) => {
+            println!("{}", err);
+            panic!();
+        },
+    };
+    let ints = match &args[0] {
+        KernelArg::Buffer(bytes) => buffer_ints(bytes.as_slice()),
+        _ => panic!(),
+    };
+    let mut expected = output.printf_output.clone();
+    for x in &ints {
+        if is_float {
+            writeln!(expected, "{:.4}", f32::from_bits(*x as u32)).unwrap();
+        } else {
+            writeln!(expected, "{}", x).unwrap();
+        }
+    }
+    let src = match emit_ir_tree_to_c99(&ir_tree) {
+        Ok(src) => src,
+        Err(err) => {
+            println!("{}", err);
+            panic!();
+        },
+    };
+    assert_eq!(expected, compile_and_run(name, src.as_str(), buffer_main("k", global_size, local_size, init, is_float).as_str()));
+}
+
+#[test]
+fn test_c99_emitter_emit_emits_kernel_and_driver()
+{
+    let s = "
+import opencl;
+a: Int = 10;
+kernel k(xs: UniqGlobalSlice<Int>) -> () =
+    let i = get_global_id(0 as Uint);
+        (x, xs) = xs[i] ->;
+        _ = xs[i] <- (x + a);
+    in  ();
+";
+    let s2 = &s[1..];
+    let tree = match do_frontend_phases(s2) {
+        Ok(tree) => tree,
+        Err(errs) => {
+            println!("{}", errs);
+            panic!();
+        },
+    };
+    let ir_tree = match lower_tree(&tree) {
+        Ok(ir_tree) => ir_tree,
+        Err(err) => {
+            println!("{}", err);
+            panic!();
+        },
+    };
+    match emit_ir_tree_to_c99(&ir_tree) {
+        Ok(src) => {
+            assert!(src.contains("#include <stdint.h>\n"));
+            assert!(!src.contains("#include <pthread.h>\n"));
+            assert!(src.contains("static const int32_t _VV1a = 10;\n"));
+            assert!(src.contains("static void _VF1k(int32_t *v0, uint64_t v1);\n"));
+            assert!(src.contains("static void _VF1k(int32_t *v0, uint64_t v1)\n{\n"));
+            assert!(src.contains("int vscfl_run__VF1k(const vscfl_nd_range *nd_range, int32_t *v0, uint64_t v1)\n{\n"));
+            assert!(src.contains("            _VF1k(v0, v1);\n"));
+            assert!(src.find("static const int32_t _VV1a").unwrap() < src.find("static void _VF1k").unwrap());
+        },
+        Err(err) => {
+            println!("{}", err);
+            assert!(false);
+        },
+    }
+}
+
+#[test]
+fn test_c99_emitter_emit_emits_threaded_driver_for_barrier()
+{
+    let s = "
+kernel fun @k(*global int) -> void
+{
+    var local [int; 4];
+    var size_t;
+    v2 = call_builtin get_local_id(uint 0);
+    v1[v2] = load v0[v2];
+    call_builtin barrier(builtin CLK_LOCAL_MEM_FENCE: uint);
+    return;
+}
+";
+    let src = emit_ir_string(&s[1..]);
+    assert!(src.contains("#include <pthread.h>\n"));
+    assert!(src.contains("static VSCFL_THREAD_LOCAL vscfl_work_item *vscfl_wi;\n"));
+    assert!(src.contains("    static int32_t v1[4];\n"));
+    assert!(src.contains("    barrier(1U);\n"));
+    assert!(src.contains("static void *vscfl_thread_k(void *arg)\n"));
+    assert!(src.contains("        if(vscfl_run_work_group(&wi, vscfl_thread_k) != 0) {\n"));
+}
+
+#[test]
+fn test_c99_emitter_emit_emits_vector_helpers()
+{
+    let s = "
+kernel fun @k(*global float) -> void
+{
+    var float4;
+    v1 = load vector float4(float 1, float 2, float 3, float 4);
+    v1 = mul v1, v1;
+    v0[0] = call_builtin dot(v1, v1);
+    return;
+}
+";
+    let src = emit_ir_string(&s[1..]);
+    assert!(src.contains("} vscfl_float4;\n"));
+    assert!(src.contains("static vscfl_float4 vscfl_mul_float4(vscfl_float4 a0, vscfl_float4 a1)\n"));
+    assert!(src.contains("static float vscfl_dot_float4(vscfl_float4 a, vscfl_float4 b)\n"));
+    assert!(src.contains("    v1 = ((vscfl_float4) { 1.0f, 2.0f, 3.0f, 4.0f });\n"));
+    assert!(src.find("vscfl_mul_float4(vscfl_float4 a0").unwrap() < src.find("static void k(float *v0)\n{").unwrap());
+}
+
+#[test]
+fn test_c99_emitter_emit_complains_on_half_type()
+{
+    let s = "
+kernel fun @k(*global half) -> void
+{
+    return;
+}
+";
+    let ir_tree = parse_ir_string(&s[1..]);
+    match emit_ir_tree_to_c99(&ir_tree) {
+        Err(BackendError::Internal(msg)) => assert_eq!(String::from("emit_with_panic_table: half type is unsupported by C99 backend"), msg),
+        _ => assert!(false),
+    }
+}
+
+#[test]
+fn test_c99_emitter_emitted_source_behaves_like_interpreter()
+{
+    if !has_c_compiler() {
+        return;
+    }
+    let s = "
+kernel fun @k(*global int) -> void
+{
+    var size_t;
+    v1 = call_builtin get_global_id(uint 0);
+    v0[v1] = mul v1, size_t 2;
+    return;
+}
+";
+    run_ir_string_and_compare("global_ids", &s[1..], 8, 4, &[0; 8], false);
+    let s = "
+kernel fun @k(*global int) -> void
+{
+    var local [int; 4];
+    var size_t;
+    var size_t;
+    var size_t;
+    v2 = call_builtin get_local_id(uint 0);
+    v3 = call_builtin get_global_id(uint 0);
+    v1[v2] = load v0[v3];
+    call_builtin barrier(builtin CLK_LOCAL_MEM_FENCE: uint);
+    v4 = sub size_t 3, v2;
+    v0[v3] = load v1[v4];
+    return;
+}
+";
+    run_ir_string_and_compare("barrier", &s[1..], 8, 4, &[1, 2, 3, 4, 5, 6, 7, 8], false);
+    let s = "
+kernel fun @k(*global int) -> void
+{
+    var size_t;
+    var int;
+    var size_t;
+    v1 = call_builtin get_global_id(uint 0);
+    v2 = sub v1 as int, int 2;
+    v3 = mul v1, size_t 4;
+    v0[v3] = call_builtin add_sat(v2, int 2147483646);
+    v3 = add v3, size_t 1;
+    v0[v3] = call_builtin clamp(v2, int -1, int 1);
+    v3 = add v3, size_t 1;
+    v0[v3] = call_builtin rotate(v2, int 31);
+    v3 = add v3, size_t 1;
+    v0[v3] = shr v2, int 33;
+    call_builtin printf(string \"%d %+04d %x\\n\", v2, v2, v2);
+    return;
+}
+";
+    run_ir_string_and_compare("ints", &s[1..], 4, 2, &[0; 16], false);
+    let s = "
+kernel fun @k(*global float) -> void
+{
+    var size_t;
+    var float4;
+    var float;
+    v1 = call_builtin get_global_id(uint 0);
+    v2 = load vector float4(v1 as float, float 1, float -2, float 0.5);
+    v2 = mul v2, vector float4(float 2, float 2, float 2, float 2);
+    v3 = call_builtin length(v2);
+    v0[v1] = call_builtin sqrt(v3);
+    call_builtin printf(string \"%v4hlf %.3f\\n\", v2, v3);
+    return;
+}
+";
+    run_ir_string_and_compare("floats", &s[1..], 4, 4, &[0; 4], true);
+}
+
+#[test]
+fn test_c99_emitter_emitted_source_reports_panic()
+{
+    if !has_c_compiler() {
+        return;
+    }
+    let s = "
+fun @f(int) -> void with panic
+{
+    if eq v0, int 3 {
+        panic \"three\" at \"a.vscfl\":1:2;
+    } else {
+    }
+    return;
+}
+
+kernel fun @k(int) -> void with panic
+{
+    var size_t;
+    v1 = call_builtin get_global_id(uint 0);
+    call @f(v1 as int) at \"a.vscfl\":3:4;
+    return;
+}
+";
+    let ir_tree = parse_ir_string(&s[1..]);
+    let (src, panic_table) = match emit_ir_tree_to_c99_with_panic_table(&ir_tree) {
+        Ok(pair) => pair,
+        Err(err) => {
+            println!("{}", err);
+            panic!();
+        },
+    };
+    let main = format!("
+int main(void)
+{{
+    vscfl_nd_range nd_range = {{ 1U, {{ 0, 0, 0 }}, {{ 4, 1, 1 }}, {{ 2, 1, 1 }} }};
+    uint32_t pb[{}] = {{ 0 }};
+    size_t i;
+    if(vscfl_run_k(&nd_range, 255, pb) != 0) {{
+        return 1;
+    }}
+    for(i = 0; i < {}; i++) {{
+        printf(\"%u\\n\", pb[i]);
+    }}
+    return 0;
+}}
+", PANIC_BUFFER_LEN, PANIC_BUFFER_LEN);
+    let output = compile_and_run("panic", src.as_str(), main.as_str());
+    let buf: Vec<u32> = output.lines().map(|line| line.parse::<u32>().unwrap()).collect();
+    match panic_table.decode(buf.as_slice()) {
+        Ok(Some(panic)) => {
+            assert_eq!(String::from("three"), panic.msg);
+            assert_eq!([3, 0, 0], panic.global_id);
+            assert_eq!(false, panic.is_truncated);
+            assert_eq!(2, panic.poses.len());
+            assert_eq!(1, panic.poses[0].line);
+            assert_eq!(2, panic.poses[0].column);
+            assert_eq!(3, panic.poses[1].line);
+            assert_eq!(4, panic.poses[1].column);
+        },
+        _ => assert!(false),
+    }
+}
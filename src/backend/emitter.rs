@@ -0,0 +1,1648 @@
+//
+// Copyright (c) 2025 Łukasz Szpakowski
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+use std::collections::BTreeSet;
+use std::fmt;
+use std::fmt::Write;
+use crate::frontend::error::Pos;
+use crate::backend::error::*;
+use crate::backend::heap::*;
+use crate::backend::ir::*;
+use crate::backend::ir::utils::*;
+use crate::backend::layout::*;
+use crate::backend::mangling::*;
+use crate::backend::panic::*;
+
+fn scalar_type_ident(typ: &IrType) -> Option<&'static str>
+{
+    match typ {
+        IrType::Void => Some("void"),
+        IrType::Char => Some("char"),
+        IrType::Short => Some("short"),
+        IrType::Int => Some("int"),
+        IrType::Long => Some("long"),
+        IrType::Uchar => Some("uchar"),
+        IrType::Ushort => Some("ushort"),
+        IrType::Uint => Some("uint"),
+        IrType::Ulong => Some("ulong"),
+        IrType::Half => Some("half"),
+        IrType::Float => Some("float"),
+        IrType::Double => Some("double"),
+        IrType::SizeT => Some("size_t"),
+        IrType::PtrdiffT => Some("ptrdiff_t"),
+        IrType::IntptrT => Some("intptr_t"),
+        IrType::UintptrT => Some("uintptr_t"),
+        _ => None,
+    }
+}
+
+fn simple_type_ident(typ: &IrType) -> Option<String>
+{
+    match scalar_type_ident(typ) {
+        Some(ident) => Some(String::from(ident)),
+        None => {
+            let (elem_type, n) = vector_type_parts(typ)?;
+            Some(format!("{}{}", scalar_type_ident(&elem_type)?, n))
+        },
+    }
+}
+
+fn ptr_modifier_to_str(modifier: IrPtrModifier) -> &'static str
+{
+    match modifier {
+        IrPtrModifier::None => "",
+        IrPtrModifier::Private => "private ",
+        IrPtrModifier::Local => "local ",
+        IrPtrModifier::Global => "global ",
+        IrPtrModifier::Constant => "constant ",
+    }
+}
+
+fn local_var_modifier_to_str(modifier: IrLocalVarModifier) -> &'static str
+{
+    match modifier {
+        IrLocalVarModifier::None => "",
+        IrLocalVarModifier::Private => "private ",
+        IrLocalVarModifier::Local => "local ",
+        IrLocalVarModifier::Global => "global ",
+    }
+}
+
+fn decl_to_string(typ: &IrType, quals: &str, declarator: &str) -> String
+{
+    match typ {
+        IrType::Struct(ident) => format!("{}struct {} {}", quals, ident, declarator).trim_end().to_string(),
+        IrType::Union(ident) => format!("{}union {} {}", quals, ident, declarator).trim_end().to_string(),
+        IrType::Array(elem_type, len) => {
+            if declarator.starts_with('*') {
+                decl_to_string(elem_type, quals, format!("({})[{}]", declarator, len).as_str())
+            } else {
+                decl_to_string(elem_type, quals, format!("{}[{}]", declarator, len).as_str())
+            }
+        },
+        IrType::Ptr(ptr_modifier, access_modifier, elem_type) => {
+            let mut elem_quals = String::from(ptr_modifier_to_str(*ptr_modifier));
+            if *access_modifier == IrAccessModifier::Const {
+                elem_quals.push_str("const ");
+            }
+            decl_to_string(elem_type, elem_quals.as_str(), format!("*{}{}", quals, declarator).as_str())
+        },
+        _ => {
+            match simple_type_ident(typ) {
+                Some(ident) => format!("{}{} {}", quals, ident, declarator).trim_end().to_string(),
+                None => String::new(),
+            }
+        },
+    }
+}
+
+fn type_to_string(typ: &IrType) -> String
+{ decl_to_string(typ, "", "") }
+
+fn local_var_name(idx: usize) -> String
+{ format!("v{}", idx) }
+
+fn caller_arg_name(idx: usize) -> String
+{ format!("c{}", idx) }
+
+fn field_name(idx: usize) -> String
+{ format!("f{}", idx) }
+
+fn panic_flag_value_name() -> &'static str
+{ "ps" }
+
+fn panic_ret_name() -> &'static str
+{ "pr" }
+
+fn private_heap_value_name() -> &'static str
+{ "phs" }
+
+fn local_heap_value_name() -> &'static str
+{ "lhs" }
+
+fn hidden_param(modifier: IrPtrModifier, name: &str) -> String
+{ format!("{}uint *{}", ptr_modifier_to_str(modifier), name) }
+
+fn vector_field_name(idx: usize) -> String
+{ format!("s{:X}", idx) }
+
+fn is_neg_int_string(s: &str) -> bool
+{ s.starts_with('-') }
+
+fn int_to_string(s: String, suffix: &str, cast_ident: Option<&str>) -> String
+{
+    let s2 = if is_neg_int_string(s.as_str()) {
+        format!("({}{})", s, suffix)
+    } else {
+        format!("{}{}", s, suffix)
+    };
+    match cast_ident {
+        Some(cast_ident) => format!("(({}) {})", cast_ident, s2),
+        None => s2,
+    }
+}
+
+fn string_to_literal(bytes: &[u8]) -> String
+{
+    let mut s = String::from("\"");
+    for b in bytes {
+        match b {
+            b'"' => s.push_str("\\\""),
+            b'\\' => s.push_str("\\\\"),
+            b'\n' => s.push_str("\\n"),
+            b'\t' => s.push_str("\\t"),
+            b'\r' => s.push_str("\\r"),
+            0x20..=0x7e => s.push(*b as char),
+            _ => s.push_str(format!("\\{:03o}", b).as_str()),
+        }
+    }
+    s.push('"');
+    s
+}
+
+fn type_has(typ: &IrType, f: &dyn Fn(&IrType) -> bool) -> bool
+{
+    if f(typ) {
+        return true;
+    }
+    match typ {
+        IrType::Array(elem_type, _) => type_has(elem_type, f),
+        IrType::Ptr(_, _, elem_type) => type_has(elem_type, f),
+        _ => false,
+    }
+}
+
+fn is_double_type(typ: &IrType) -> bool
+{
+    matches!(typ, IrType::Double | IrType::DoubleN(_))
+}
+
+fn is_half_type(typ: &IrType) -> bool
+{
+    matches!(typ, IrType::Half)
+}
+
+fn block_types(block: &IrBlock, types: &mut Vec<Box<IrType>>)
+{
+    for local_var_pair in block.local_var_pairs() {
+        types.push(local_var_pair.1.clone());
+    }
+    for instr in block.instrs() {
+        match instr {
+            IrInstr::Block(block2) => block_types(block2, types),
+            IrInstr::If(_, block2, block3) => {
+                block_types(block2, types);
+                block_types(block3, types);
+            },
+            IrInstr::Switch(_, cases) => {
+                for case in cases {
+                    match case {
+                        IrCase::Case(_, block2) => block_types(block2, types),
+                        IrCase::Default(block2) => block_types(block2, types),
+                    }
+                }
+            },
+            IrInstr::Loop(block2) => block_types(block2, types),
+            _ => (),
+        }
+    }
+}
+
+fn block_has_break(block: &IrBlock) -> bool
+{
+    block.instrs().iter().any(|instr| {
+            match instr {
+                IrInstr::Break => true,
+                IrInstr::Block(block2) => block_has_break(block2),
+                IrInstr::If(_, block2, block3) => block_has_break(block2) || block_has_break(block3),
+                IrInstr::Switch(_, cases) => {
+                    cases.iter().any(|case| {
+                            match case {
+                                IrCase::Case(_, block2) => block_has_break(block2),
+                                IrCase::Default(block2) => block_has_break(block2),
+                            }
+                    })
+                },
+                _ => false,
+            }
+    })
+}
+
+fn case_value_to_string(value: &IrCaseValue) -> String
+{
+    match value {
+        IrCaseValue::Char(n, typ) => int_to_string(format!("{}", n), "", cast_ident(typ).or(Some("char"))),
+        IrCaseValue::Short(n, typ) => int_to_string(format!("{}", n), "", cast_ident(typ).or(Some("short"))),
+        IrCaseValue::Int(n, typ) => int_value_to_string(*n, typ),
+        IrCaseValue::Long(n, typ) => long_value_to_string(*n, typ),
+        IrCaseValue::Uchar(n, typ) => int_to_string(format!("{}", n), "U", cast_ident(typ).or(Some("uchar"))),
+        IrCaseValue::Ushort(n, typ) => int_to_string(format!("{}", n), "U", cast_ident(typ).or(Some("ushort"))),
+        IrCaseValue::Uint(n, typ) => int_to_string(format!("{}", n), "U", cast_ident(typ)),
+        IrCaseValue::Ulong(n, typ) => int_to_string(format!("{}", n), "UL", cast_ident(typ)),
+        IrCaseValue::SizeT(n, typ) => int_to_string(format!("{}", n), "UL", cast_ident(typ).or(Some("size_t"))),
+        IrCaseValue::PtrdiffT(n, typ) => long_like_value_to_string(*n, typ, "ptrdiff_t"),
+        IrCaseValue::IntptrT(n, typ) => long_like_value_to_string(*n, typ, "intptr_t"),
+        IrCaseValue::UintptrT(n, typ) => int_to_string(format!("{}", n), "UL", cast_ident(typ).or(Some("uintptr_t"))),
+        IrCaseValue::BuiltinVar(ident, _, typ) => {
+            match cast_ident(typ) {
+                Some(cast_ident) => format!("(({}) {})", cast_ident, ident),
+                None => ident.clone(),
+            }
+        },
+    }
+}
+
+fn cast_ident(typ: &Option<Box<IrType>>) -> Option<&'static str>
+{
+    match typ {
+        Some(typ) => scalar_type_ident(typ),
+        None => None,
+    }
+}
+
+fn int_value_to_string(n: i32, typ: &Option<Box<IrType>>) -> String
+{
+    if n == i32::MIN {
+        int_to_string(format!("{} - 1", n + 1), "", cast_ident(typ))
+    } else {
+        int_to_string(format!("{}", n), "", cast_ident(typ))
+    }
+}
+
+fn long_value_to_string(n: i64, typ: &Option<Box<IrType>>) -> String
+{
+    if n == i64::MIN {
+        int_to_string(format!("{}L - 1", n + 1), "L", cast_ident(typ))
+    } else {
+        int_to_string(format!("{}", n), "L", cast_ident(typ))
+    }
+}
+
+fn long_like_value_to_string(n: i64, typ: &Option<Box<IrType>>, type_ident: &'static str) -> String
+{
+    if n == i64::MIN {
+        int_to_string(format!("{}L - 1", n + 1), "L", cast_ident(typ).or(Some(type_ident)))
+    } else {
+        int_to_string(format!("{}", n), "L", cast_ident(typ).or(Some(type_ident)))
+    }
+}
+
+trait EmitVar
+{
+    fn emit_var(&self, ctx: &EmitterContext) -> BackendResult<(String, Option<Box<IrType>>)>;
+}
+
+struct EmitterContext<'a>
+{
+    tree: &'a IrTree,
+    local_var_types: Vec<Box<IrType>>,
+    caller_arg_types: Vec<Box<IrType>>,
+    private_closure: Option<(String, Box<IrType>)>,
+    local_closure: Option<(String, Box<IrType>)>,
+    global_closure: Option<(String, Box<IrType>)>,
+    ret_type: Box<IrType>,
+    can_panic: bool,
+    heap_flags: (IrPrivateHeapFlag, IrLocalHeapFlag, IrGlobalHeapFlag),
+    panic_table: Option<&'a mut PanicTable>,
+}
+
+impl<'a> EmitterContext<'a>
+{
+    fn new(tree: &'a IrTree) -> Self
+    {
+        EmitterContext {
+            tree,
+            local_var_types: Vec::new(),
+            caller_arg_types: Vec::new(),
+            private_closure: None,
+            local_closure: None,
+            global_closure: None,
+            ret_type: Box::new(IrType::Void),
+            can_panic: false,
+            heap_flags: (IrPrivateHeapFlag::None, IrLocalHeapFlag::None, IrGlobalHeapFlag::None),
+            panic_table: None,
+        }
+    }
+
+    fn fun_flags(&self, ident: &String) -> (IrPrivateHeapFlag, IrLocalHeapFlag, IrGlobalHeapFlag, IrPanicFlag)
+    {
+        match self.tree.var(ident) {
+            Some(var) => {
+                match &*var.borrow() {
+                    IrVar::Fun(fun) => {
+                        match &**fun {
+                            IrFun::Fun(_, _, _, _, private_heap_flag, local_heap_flag, global_heap_flag, panic_flag) => (*private_heap_flag, *local_heap_flag, *global_heap_flag, *panic_flag),
+                            IrFun::Caller(_, _, _, _, private_heap_flag, local_heap_flag, global_heap_flag, panic_flag) => (*private_heap_flag, *local_heap_flag, *global_heap_flag, *panic_flag),
+                        }
+                    },
+                    _ => (IrPrivateHeapFlag::None, IrLocalHeapFlag::None, IrGlobalHeapFlag::None, IrPanicFlag::None),
+                }
+            },
+            None => (IrPrivateHeapFlag::None, IrLocalHeapFlag::None, IrGlobalHeapFlag::None, IrPanicFlag::None),
+        }
+    }
+
+    fn fun_can_panic(&self, ident: &String) -> bool
+    { self.fun_flags(ident).3 == IrPanicFlag::Panic }
+
+    fn hidden_args(&self, ident: &String) -> BackendResult<Vec<&'static str>>
+    {
+        let (private_heap_flag, local_heap_flag, global_heap_flag, panic_flag) = self.fun_flags(ident);
+        if private_heap_flag > self.heap_flags.0 || local_heap_flag > self.heap_flags.1 || global_heap_flag > self.heap_flags.2 {
+            return Err(BackendError::Internal(format!("hidden_args: function {} uses heap", ident)));
+        }
+        if panic_flag == IrPanicFlag::Panic && !self.can_panic {
+            return Err(BackendError::Internal(format!("hidden_args: function {} can panic", ident)));
+        }
+        Ok(hidden_arg_names(private_heap_flag, local_heap_flag, global_heap_flag, panic_flag))
+    }
+
+    fn add_panic_entry(&mut self, entry: PanicEntry) -> BackendResult<u32>
+    {
+        match &mut self.panic_table {
+            Some(panic_table) => Ok(panic_table.add_entry(entry)),
+            None => Err(BackendError::Internal(String::from("add_panic_entry: no panic table"))),
+        }
+    }
+
+    fn panic_call_poses(&self, op: &IrOp) -> Option<Vec<Pos>>
+    {
+        match op {
+            IrOp::CallFun(ident, _, pos, panic_poses) if self.fun_can_panic(ident) => {
+                let mut poses = vec![pos.clone()];
+                poses.extend_from_slice(panic_poses.as_slice());
+                Some(poses)
+            },
+            _ => None,
+        }
+    }
+
+    fn panic_return_to_string(&self) -> String
+    {
+        if *self.ret_type == IrType::Void {
+            String::from("return;")
+        } else {
+            format!("return {};", panic_ret_name())
+        }
+    }
+
+    fn global_var_type(&self, ident: &String) -> BackendResult<Box<IrType>>
+    {
+        match self.tree.var(ident) {
+            Some(var) => {
+                match &*var.borrow() {
+                    IrVar::Const(typ, _) => Ok(typ.clone()),
+                    IrVar::Var(_, _, typ, _) => Ok(typ.clone()),
+                    IrVar::Fun(_) => Err(BackendError::Internal(format!("global_var_type: variable {} is function", ident))),
+                }
+            },
+            None => Err(BackendError::Internal(format!("global_var_type: no variable {}", ident))),
+        }
+    }
+
+    fn local_var_type(&self, idx: usize) -> BackendResult<Box<IrType>>
+    {
+        match self.local_var_types.get(idx) {
+            Some(typ) => Ok(typ.clone()),
+            None => Err(BackendError::Internal(format!("local_var_type: no local variable {}", idx))),
+        }
+    }
+
+    fn caller_arg(&self, idx: usize) -> BackendResult<(String, Box<IrType>)>
+    {
+        match self.caller_arg_types.get(idx) {
+            Some(typ) => Ok((caller_arg_name(idx), typ.clone())),
+            None => Err(BackendError::Internal(format!("caller_arg: no caller argument {}", idx))),
+        }
+    }
+
+    fn closure_var(&self, closure: &Option<(String, Box<IrType>)>, idx: usize) -> BackendResult<(String, Box<IrType>)>
+    {
+        match closure {
+            Some((s, typ)) => self.dot_to_string(s.as_str(), typ, idx),
+            None => Err(BackendError::Internal(format!("closure_var: no closure for variable {}", idx))),
+        }
+    }
+
+    fn field_type(&self, typ: &IrType, idx: usize) -> BackendResult<Box<IrType>>
+    {
+        match typ {
+            IrType::Struct(ident) => {
+                match self.tree.struct1(ident) {
+                    Some(struct1) => {
+                        match &*struct1.borrow() {
+                            IrStruct::Struct(field_types) => {
+                                match field_types.get(idx) {
+                                    Some(field_type) => Ok(field_type.clone()),
+                                    None => Err(BackendError::Internal(format!("field_type: no field {} in structure {}", idx, ident))),
+                                }
+                            },
+                            IrStruct::Closure(field_types) => {
+                                match field_types.get(&idx) {
+                                    Some(field_type) => Ok(field_type.clone()),
+                                    None => Err(BackendError::Internal(format!("field_type: no field {} in structure {}", idx, ident))),
+                                }
+                            },
+                        }
+                    },
+                    None => Err(BackendError::Internal(format!("field_type: no structure {}", ident))),
+                }
+            },
+            IrType::Union(ident) => {
+                match self.tree.union(ident) {
+                    Some(union) => {
+                        match union.borrow().0.get(idx) {
+                            Some(field_type) => Ok(field_type.clone()),
+                            None => Err(BackendError::Internal(format!("field_type: no field {} in union {}", idx, ident))),
+                        }
+                    },
+                    None => Err(BackendError::Internal(format!("field_type: no union {}", ident))),
+                }
+            },
+            _ => {
+                match vector_type_parts(typ) {
+                    Some((elem_type, n)) if idx < n => Ok(Box::new(elem_type)),
+                    _ => Err(BackendError::Internal(String::from("field_type: type hasn't fields"))),
+                }
+            },
+        }
+    }
+
+    fn dot_to_string(&self, s: &str, typ: &IrType, idx: usize) -> BackendResult<(String, Box<IrType>)>
+    {
+        let field_type = self.field_type(typ, idx)?;
+        if vector_type_parts(typ).is_some() {
+            Ok((format!("{}.{}", s, vector_field_name(idx)), field_type))
+        } else {
+            Ok((format!("{}.{}", s, field_name(idx)), field_type))
+        }
+    }
+
+    fn elem_type(&self, typ: &IrType) -> BackendResult<Box<IrType>>
+    {
+        match typ {
+            IrType::Array(elem_type, _) => Ok(elem_type.clone()),
+            IrType::Ptr(_, _, elem_type) => Ok(elem_type.clone()),
+            _ => Err(BackendError::Internal(String::from("elem_type: type isn't array or pointer"))),
+        }
+    }
+
+    fn arg_op_to_string(&self, s: String, typ: Box<IrType>, op: &IrArgOp) -> BackendResult<(String, Box<IrType>)>
+    {
+        match op {
+            IrArgOp::Deref(_) => Ok((format!("(*{})", s), self.elem_type(&typ)?)),
+            IrArgOp::Dot(_, idx) => self.dot_to_string(s.as_str(), &typ, *idx),
+            IrArgOp::Arrow(_, idx) => {
+                let elem_type = self.elem_type(&typ)?;
+                if vector_type_parts(&elem_type).is_some() {
+                    self.dot_to_string(format!("(*{})", s).as_str(), &elem_type, *idx)
+                } else {
+                    let field_type = self.field_type(&elem_type, *idx)?;
+                    Ok((format!("{}->{}", s, field_name(*idx)), field_type))
+                }
+            },
+            IrArgOp::Index(_, idx) => Ok((format!("{}[{}]", s, idx), self.elem_type(&typ)?)),
+            IrArgOp::GlobalIndex(_, ident) => Ok((format!("{}[{}]", s, ident), self.elem_type(&typ)?)),
+            IrArgOp::LocalIndex(_, idx) => Ok((format!("{}[{}]", s, local_var_name(*idx)), self.elem_type(&typ)?)),
+            IrArgOp::CallerFunArgIndex(_, idx) => Ok((format!("{}[{}]", s, self.caller_arg(*idx)?.0), self.elem_type(&typ)?)),
+            IrArgOp::PrivateClosureIndex(_, idx) => Ok((format!("{}[{}]", s, self.closure_var(&self.private_closure, *idx)?.0), self.elem_type(&typ)?)),
+            IrArgOp::LocalClosureIndex(_, idx) => Ok((format!("{}[{}]", s, self.closure_var(&self.local_closure, *idx)?.0), self.elem_type(&typ)?)),
+            IrArgOp::GlobalClosureIndex(_, idx) => Ok((format!("{}[{}]", s, self.closure_var(&self.global_closure, *idx)?.0), self.elem_type(&typ)?)),
+        }
+    }
+
+    fn arg_ops_to_string(&self, s: String, typ: Box<IrType>, ops: &[IrArgOp]) -> BackendResult<(String, Box<IrType>)>
+    {
+        let mut s2 = s;
+        let mut type2 = typ;
+        for op in ops {
+            let (tmp_s, tmp_type) = self.arg_op_to_string(s2, type2, op)?;
+            s2 = tmp_s;
+            type2 = tmp_type;
+        }
+        Ok((s2, type2))
+    }
+
+    fn ref_to_string(&self, s: String, typ: Box<IrType>, ops: &[IrArgOp], vector_elem_ptr_type: &Option<Box<IrType>>) -> BackendResult<(String, Option<Box<IrType>>)>
+    {
+        match (ops.split_last(), vector_elem_ptr_type) {
+            (Some((IrArgOp::Dot(_, idx), ops2)), Some(ptr_type)) => {
+                let (s2, type2) = self.arg_ops_to_string(s, typ, ops2)?;
+                if vector_type_parts(&type2).is_some() {
+                    Ok((format!("((({}) &{}) + {})", type_to_string(ptr_type), s2, idx), Some(ptr_type.clone())))
+                } else {
+                    let (s3, _) = self.dot_to_string(s2.as_str(), &type2, *idx)?;
+                    Ok((format!("(({}) &{})", type_to_string(ptr_type), s3), Some(ptr_type.clone())))
+                }
+            },
+            (_, Some(ptr_type)) => {
+                let (s2, _) = self.arg_ops_to_string(s, typ, ops)?;
+                Ok((format!("(({}) &{})", type_to_string(ptr_type), s2), Some(ptr_type.clone())))
+            },
+            (_, None) => {
+                let (s2, type2) = self.arg_ops_to_string(s, typ, ops)?;
+                Ok((format!("(&{})", s2), Some(Box::new(IrType::Ptr(IrPtrModifier::None, IrAccessModifier::None, type2)))))
+            },
+        }
+    }
+
+    fn value_to_string<T: EmitVar>(&self, value: &IrValue<T>) -> BackendResult<String>
+    {
+        match value {
+            IrValue::Char(n, typ) => Ok(int_to_string(format!("{}", n), "", cast_ident(typ).or(Some("char")))),
+            IrValue::Short(n, typ) => Ok(int_to_string(format!("{}", n), "", cast_ident(typ).or(Some("short")))),
+            IrValue::Int(n, typ) => Ok(int_value_to_string(*n, typ)),
+            IrValue::Long(n, typ) => Ok(long_value_to_string(*n, typ)),
+            IrValue::Uchar(n, typ) => Ok(int_to_string(format!("{}", n), "U", cast_ident(typ).or(Some("uchar")))),
+            IrValue::Ushort(n, typ) => Ok(int_to_string(format!("{}", n), "U", cast_ident(typ).or(Some("ushort")))),
+            IrValue::Uint(n, typ) => Ok(int_to_string(format!("{}", n), "U", cast_ident(typ))),
+            IrValue::Ulong(n, typ) => Ok(int_to_string(format!("{}", n), "UL", cast_ident(typ))),
+            IrValue::Float(x, typ) => {
+                match cast_ident(typ) {
+                    Some(cast_ident) => Ok(format!("(({}) {})", cast_ident, float_to_string(*x))),
+                    None => Ok(float_to_string(*x)),
+                }
+            },
+            IrValue::Double(x, typ) => {
+                match cast_ident(typ) {
+                    Some(cast_ident) => Ok(format!("(({}) {})", cast_ident, double_to_string(*x))),
+                    None => Ok(double_to_string(*x)),
+                }
+            },
+            IrValue::SizeT(n, typ) => Ok(int_to_string(format!("{}", n), "UL", cast_ident(typ).or(Some("size_t")))),
+            IrValue::PtrdiffT(n, typ) => Ok(long_like_value_to_string(*n, typ, "ptrdiff_t")),
+            IrValue::IntptrT(n, typ) => Ok(long_like_value_to_string(*n, typ, "intptr_t")),
+            IrValue::UintptrT(n, typ) => Ok(int_to_string(format!("{}", n), "UL", cast_ident(typ).or(Some("uintptr_t")))),
+            IrValue::Object(object) => self.object_to_string(object),
+        }
+    }
+
+    fn values_to_string<T: EmitVar>(&self, values: &[IrValue<T>]) -> BackendResult<String>
+    {
+        let mut ss: Vec<String> = Vec::new();
+        for value in values {
+            ss.push(self.value_to_string(value)?);
+        }
+        Ok(ss.join(", "))
+    }
+
+    fn field_pairs_to_string<T: EmitVar>(&self, field_pairs: &[IrFieldPair<T>]) -> BackendResult<String>
+    {
+        let mut ss: Vec<String> = Vec::new();
+        for field_pair in field_pairs {
+            ss.push(format!(".{} = {}", field_name(field_pair.0), self.value_to_string(&field_pair.1)?));
+        }
+        Ok(ss.join(", "))
+    }
+
+    fn compound_to_string(&self, s: String, typ: &Option<Box<IrType>>) -> String
+    {
+        match typ {
+            Some(typ) => format!("(({}) {{ {} }})", type_to_string(typ), s),
+            None => format!("{{ {} }}", s),
+        }
+    }
+
+    fn object_to_string<T: EmitVar>(&self, object: &IrObject<T>) -> BackendResult<String>
+    {
+        match object {
+            IrObject::String(bytes) => Ok(string_to_literal(bytes.as_slice())),
+            IrObject::BuiltinVar(ident, _, typ) => {
+                match typ {
+                    Some(typ) => Ok(format!("(({}) {})", type_to_string(typ), ident)),
+                    None => Ok(ident.clone()),
+                }
+            },
+            IrObject::Var(var, typ) => {
+                let (s, _) = var.emit_var(self)?;
+                match typ {
+                    Some(typ) => Ok(format!("(({}) {})", type_to_string(typ), s)),
+                    None => Ok(s),
+                }
+            },
+            IrObject::Vector(values, typ) => Ok(format!("(({}) ({}))", type_to_string(typ), self.values_to_string(values.as_slice())?)),
+            IrObject::Array(values, typ) => Ok(self.compound_to_string(self.values_to_string(values.as_slice())?, typ)),
+            IrObject::Struct(values, field_pairs, typ) => {
+                let mut s = self.values_to_string(values.as_slice())?;
+                if !field_pairs.is_empty() {
+                    if !s.is_empty() {
+                        s.push_str(", ");
+                    }
+                    s.push_str(self.field_pairs_to_string(field_pairs.as_slice())?.as_str());
+                }
+                Ok(self.compound_to_string(s, typ))
+            },
+            IrObject::Union(idx, value, typ) => Ok(self.compound_to_string(format!(".{} = {}", field_name(*idx), self.value_to_string(value)?), typ)),
+            IrObject::Closure(field_pairs, typ) => Ok(self.compound_to_string(self.field_pairs_to_string(field_pairs.as_slice())?, typ)),
+            IrObject::Sizeof(typ, typ2) => {
+                match typ2 {
+                    Some(typ2) => Ok(format!("(({}) sizeof({}))", type_to_string(typ2), type_to_string(typ))),
+                    None => Ok(format!("sizeof({})", type_to_string(typ))),
+                }
+            },
+            IrObject::CallerFunIndex(_, idx, typ) => {
+                match typ {
+                    Some(typ) => Ok(format!("(({}) {})", type_to_string(typ), idx)),
+                    None => Ok(format!("{}U", idx)),
+                }
+            },
+        }
+    }
+
+    fn builtin_fun_ident(&self, ident: &String, typ: &Option<Box<IrType>>) -> BackendResult<String>
+    {
+        match typ {
+            Some(typ) => {
+                match simple_type_ident(typ) {
+                    Some(type_ident) => Ok(format!("{}_{}", ident, type_ident)),
+                    None => Err(BackendError::Internal(format!("builtin_fun_ident: invalid type for builtin function {}", ident))),
+                }
+            },
+            None => Ok(ident.clone()),
+        }
+    }
+
+    fn op_to_string(&self, op: &IrOp) -> BackendResult<String>
+    {
+        match op {
+            IrOp::Load(value) => self.value_to_string(value),
+            IrOp::Neg(value) => Ok(format!("(-{})", self.value_to_string(value)?)),
+            IrOp::Not(value) => Ok(format!("(~{})", self.value_to_string(value)?)),
+            IrOp::Mul(value1, value2) => self.bin_op_to_string(value1, "*", value2),
+            IrOp::Div(value1, value2) => self.bin_op_to_string(value1, "/", value2),
+            IrOp::Rem(value1, value2) => self.bin_op_to_string(value1, "%", value2),
+            IrOp::Add(value1, value2) => self.bin_op_to_string(value1, "+", value2),
+            IrOp::Sub(value1, value2) => self.bin_op_to_string(value1, "-", value2),
+            IrOp::Shl(value1, value2) => self.bin_op_to_string(value1, "<<", value2),
+            IrOp::Shr(value1, value2) => self.bin_op_to_string(value1, ">>", value2),
+            IrOp::Eq(value1, value2) => self.bin_op_to_string(value1, "==", value2),
+            IrOp::Ne(value1, value2) => self.bin_op_to_string(value1, "!=", value2),
+            IrOp::Lt(value1, value2) => self.bin_op_to_string(value1, "<", value2),
+            IrOp::Ge(value1, value2) => self.bin_op_to_string(value1, ">=", value2),
+            IrOp::Gt(value1, value2) => self.bin_op_to_string(value1, ">", value2),
+            IrOp::Le(value1, value2) => self.bin_op_to_string(value1, "<=", value2),
+            IrOp::And(value1, value2) => self.bin_op_to_string(value1, "&", value2),
+            IrOp::Xor(value1, value2) => self.bin_op_to_string(value1, "^", value2),
+            IrOp::Or(value1, value2) => self.bin_op_to_string(value1, "|", value2),
+            IrOp::CallBuiltinFun(ident, Some(typ), values) if *ident == mangle_private_alloc_fun_name() || *ident == mangle_local_alloc_fun_name() || *ident == mangle_global_alloc_fun_name() => {
+                let align = ir_type_layout(typ, self.tree)?.align;
+                Ok(format!("{}({}, (uint) sizeof({}), {}U)", ident, self.values_to_string(values)?, type_to_string(typ), align))
+            },
+            IrOp::CallBuiltinFun(ident, typ, values) => Ok(format!("{}({})", self.builtin_fun_ident(ident, typ)?, self.values_to_string(values.as_slice())?)),
+            IrOp::CallFun(ident, values, _, _) => self.call_fun_to_string(ident, values.as_slice()),
+            IrOp::CallFunWithoutPanic(ident, values, _) => self.call_fun_to_string(ident, values.as_slice()),
+        }
+    }
+
+    fn call_fun_to_string(&self, ident: &String, values: &[IrValue<IrArgVar>]) -> BackendResult<String>
+    {
+        let mut args_s = self.values_to_string(values)?;
+        for arg in self.hidden_args(ident)? {
+            if !args_s.is_empty() {
+                args_s.push_str(", ");
+            }
+            args_s.push_str(arg);
+        }
+        Ok(format!("{}({})", ident, args_s))
+    }
+
+    fn bin_op_to_string(&self, value1: &IrValue<IrArgVar>, op_str: &str, value2: &IrValue<IrArgVar>) -> BackendResult<String>
+    { Ok(format!("({} {} {})", self.value_to_string(value1)?, op_str, self.value_to_string(value2)?)) }
+
+    fn instr_var_to_string(&self, var: &IrInstrVar) -> BackendResult<(String, Box<IrType>)>
+    {
+        match var {
+            IrInstrVar::Global(ident, ops) => self.arg_ops_to_string(ident.clone(), self.global_var_type(ident)?, ops.as_slice()),
+            IrInstrVar::Local(idx, ops) => self.arg_ops_to_string(local_var_name(*idx), self.local_var_type(*idx)?, ops.as_slice()),
+            IrInstrVar::CallerFunArg(idx, ops) => {
+                let (s, typ) = self.caller_arg(*idx)?;
+                self.arg_ops_to_string(s, typ, ops.as_slice())
+            },
+            IrInstrVar::PrivateClosure(idx, ops) => {
+                let (s, typ) = self.closure_var(&self.private_closure, *idx)?;
+                self.arg_ops_to_string(s, typ, ops.as_slice())
+            },
+            IrInstrVar::LocalClosure(idx, ops) => {
+                let (s, typ) = self.closure_var(&self.local_closure, *idx)?;
+                self.arg_ops_to_string(s, typ, ops.as_slice())
+            },
+            IrInstrVar::GlobalClosure(idx, ops) => {
+                let (s, typ) = self.closure_var(&self.global_closure, *idx)?;
+                self.arg_ops_to_string(s, typ, ops.as_slice())
+            },
+            IrInstrVar::PrivateHeap(ops) => self.arg_ops_to_string(String::from(private_heap_name()), Box::new(IrType::Ptr(IrPtrModifier::Private, IrAccessModifier::None, Box::new(IrType::Uint))), ops.as_slice()),
+            IrInstrVar::LocalHeap(ops) => self.arg_ops_to_string(String::from(local_heap_name()), Box::new(IrType::Ptr(IrPtrModifier::Local, IrAccessModifier::None, Box::new(IrType::Uint))), ops.as_slice()),
+            IrInstrVar::GlobalHeap(ops) => self.arg_ops_to_string(String::from(global_heap_name()), Box::new(IrType::Ptr(IrPtrModifier::Global, IrAccessModifier::None, Box::new(IrType::Uint))), ops.as_slice()),
+        }
+    }
+}
+
+impl EmitVar for IrValueVar
+{
+    fn emit_var(&self, ctx: &EmitterContext) -> BackendResult<(String, Option<Box<IrType>>)>
+    {
+        let mut s = self.0.clone();
+        let mut typ = ctx.global_var_type(&self.0)?;
+        for op in &self.1 {
+            let arg_op = match op {
+                IrValueOp::Deref(tmp_type) => IrArgOp::Deref(tmp_type.clone()),
+                IrValueOp::Dot(tmp_type, idx) => IrArgOp::Dot(tmp_type.clone(), *idx),
+                IrValueOp::Arrow(tmp_type, idx) => IrArgOp::Arrow(tmp_type.clone(), *idx),
+                IrValueOp::Index(tmp_type, idx) => IrArgOp::Index(tmp_type.clone(), *idx),
+            };
+            let (tmp_s, tmp_type) = ctx.arg_op_to_string(s, typ, &arg_op)?;
+            s = tmp_s;
+            typ = tmp_type;
+        }
+        Ok((format!("(&{})", s), Some(Box::new(IrType::Ptr(IrPtrModifier::None, IrAccessModifier::None, typ)))))
+    }
+}
+
+impl EmitVar for IrArgVar
+{
+    fn emit_var(&self, ctx: &EmitterContext) -> BackendResult<(String, Option<Box<IrType>>)>
+    {
+        match self {
+            IrArgVar::Global(ident, ops) => {
+                let (s, typ) = ctx.arg_ops_to_string(ident.clone(), ctx.global_var_type(ident)?, ops.as_slice())?;
+                Ok((s, Some(typ)))
+            },
+            IrArgVar::Local(idx, ops) => {
+                let (s, typ) = ctx.arg_ops_to_string(local_var_name(*idx), ctx.local_var_type(*idx)?, ops.as_slice())?;
+                Ok((s, Some(typ)))
+            },
+            IrArgVar::RefGlobal(ident, ops, vector_elem_ptr_type) => ctx.ref_to_string(ident.clone(), ctx.global_var_type(ident)?, ops.as_slice(), vector_elem_ptr_type),
+            IrArgVar::RefLocal(idx, ops, vector_elem_ptr_type) => ctx.ref_to_string(local_var_name(*idx), ctx.local_var_type(*idx)?, ops.as_slice(), vector_elem_ptr_type),
+            IrArgVar::CallerFunArg(idx, ops) => {
+                let (s, typ) = ctx.caller_arg(*idx)?;
+                let (s2, type2) = ctx.arg_ops_to_string(s, typ, ops.as_slice())?;
+                Ok((s2, Some(type2)))
+            },
+            IrArgVar::PrivateClosure(idx, ops) => {
+                let (s, typ) = ctx.closure_var(&ctx.private_closure, *idx)?;
+                let (s2, type2) = ctx.arg_ops_to_string(s, typ, ops.as_slice())?;
+                Ok((s2, Some(type2)))
+            },
+            IrArgVar::LocalClosure(idx, ops) => {
+                let (s, typ) = ctx.closure_var(&ctx.local_closure, *idx)?;
+                let (s2, type2) = ctx.arg_ops_to_string(s, typ, ops.as_slice())?;
+                Ok((s2, Some(type2)))
+            },
+            IrArgVar::GlobalClosure(idx, ops) => {
+                let (s, typ) = ctx.closure_var(&ctx.global_closure, *idx)?;
+                let (s2, type2) = ctx.arg_ops_to_string(s, typ, ops.as_slice())?;
+                Ok((s2, Some(type2)))
+            },
+            IrArgVar::RefCallerFunArg(idx, ops, vector_elem_ptr_type) => {
+                let (s, typ) = ctx.caller_arg(*idx)?;
+                ctx.ref_to_string(s, typ, ops.as_slice(), vector_elem_ptr_type)
+            },
+            IrArgVar::RefPrivateClosure(idx, ops, vector_elem_ptr_type) => {
+                let (s, typ) = ctx.closure_var(&ctx.private_closure, *idx)?;
+                ctx.ref_to_string(s, typ, ops.as_slice(), vector_elem_ptr_type)
+            },
+            IrArgVar::RefLocalClosure(idx, ops, vector_elem_ptr_type) => {
+                let (s, typ) = ctx.closure_var(&ctx.local_closure, *idx)?;
+                ctx.ref_to_string(s, typ, ops.as_slice(), vector_elem_ptr_type)
+            },
+            IrArgVar::RefGlobalClosure(idx, ops, vector_elem_ptr_type) => {
+                let (s, typ) = ctx.closure_var(&ctx.global_closure, *idx)?;
+                ctx.ref_to_string(s, typ, ops.as_slice(), vector_elem_ptr_type)
+            },
+            IrArgVar::PrivateHeap(ops) => {
+                let (s, typ) = ctx.arg_ops_to_string(String::from(private_heap_name()), Box::new(IrType::Ptr(IrPtrModifier::Private, IrAccessModifier::None, Box::new(IrType::Uint))), ops.as_slice())?;
+                Ok((s, Some(typ)))
+            },
+            IrArgVar::LocalHeap(ops) => {
+                let (s, typ) = ctx.arg_ops_to_string(String::from(local_heap_name()), Box::new(IrType::Ptr(IrPtrModifier::Local, IrAccessModifier::None, Box::new(IrType::Uint))), ops.as_slice())?;
+                Ok((s, Some(typ)))
+            },
+            IrArgVar::GlobalHeap(ops) => {
+                let (s, typ) = ctx.arg_ops_to_string(String::from(global_heap_name()), Box::new(IrType::Ptr(IrPtrModifier::Global, IrAccessModifier::None, Box::new(IrType::Uint))), ops.as_slice())?;
+                Ok((s, Some(typ)))
+            },
+            _ => Err(BackendError::Internal(String::from("emit_var: references to heaps are unsupported"))),
+        }
+    }
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+pub enum OpenClVersion
+{
+    V1_2,
+    V2_0,
+    V3_0,
+}
+
+impl OpenClVersion
+{
+    pub fn parse(s: &str) -> Option<Self>
+    {
+        match s {
+            "1.2" => Some(OpenClVersion::V1_2),
+            "2.0" => Some(OpenClVersion::V2_0),
+            "3.0" => Some(OpenClVersion::V3_0),
+            _ => None,
+        }
+    }
+
+    pub fn number(&self) -> u32
+    {
+        match self {
+            OpenClVersion::V1_2 => 120,
+            OpenClVersion::V2_0 => 200,
+            OpenClVersion::V3_0 => 300,
+        }
+    }
+}
+
+impl fmt::Display for OpenClVersion
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    { write!(f, "{}.{}", self.number() / 100, (self.number() / 10) % 10) }
+}
+
+pub struct Emitter
+{
+    version: Option<OpenClVersion>,
+}
+
+impl Emitter
+{
+    pub fn new() -> Self
+    { Emitter { version: None, } }
+
+    pub fn new_with_version(version: OpenClVersion) -> Self
+    { Emitter { version: Some(version), } }
+
+    pub fn version(&self) -> Option<OpenClVersion>
+    { self.version }
+
+    pub fn set_version(&mut self, version: Option<OpenClVersion>)
+    { self.version = version; }
+
+    pub fn emit(&self, tree: &IrTree) -> BackendResult<String>
+    {
+        let (s, _) = self.emit_with_panic_table(tree)?;
+        Ok(s)
+    }
+
+    pub fn emit_with_panic_table(&self, tree: &IrTree) -> BackendResult<(String, PanicTable)>
+    {
+        let mut s = String::new();
+        let mut panic_table = PanicTable::new();
+        let mut types: Vec<Box<IrType>> = Vec::new();
+        self.collect_types(tree, &mut types);
+        if let Some(version) = self.version {
+            writeln!(s, "#if !defined(__OPENCL_C_VERSION__) || __OPENCL_C_VERSION__ < {}", version.number()).unwrap();
+            writeln!(s, "#error \"OpenCL C {} or later is required\"", version).unwrap();
+            s.push_str("#endif\n");
+            s.push('\n');
+        }
+        let mut has_pragma = false;
+        if types.iter().any(|t| type_has(t, &is_double_type)) {
+            s.push_str("#pragma OPENCL EXTENSION cl_khr_fp64 : enable\n");
+            has_pragma = true;
+        }
+        if types.iter().any(|t| type_has(t, &is_half_type)) {
+            s.push_str("#pragma OPENCL EXTENSION cl_khr_fp16 : enable\n");
+            has_pragma = true;
+        }
+        if has_pragma {
+            s.push('\n');
+        }
+        self.emit_type_defs(tree, &mut s)?;
+        self.emit_global_vars(tree, &mut s)?;
+        self.emit_heap_funs(tree, &mut s);
+        self.emit_panic_funs(tree, &mut s);
+        self.emit_fun_protos(tree, &mut s)?;
+        self.emit_funs(tree, &mut panic_table, &mut s)?;
+        Ok((s, panic_table))
+    }
+
+    fn collect_types(&self, tree: &IrTree, types: &mut Vec<Box<IrType>>)
+    {
+        for def in tree.defs() {
+            match &**def {
+                IrDef::Struct(_, struct1) => {
+                    match &*struct1.borrow() {
+                        IrStruct::Struct(field_types) => types.extend(field_types.iter().cloned()),
+                        IrStruct::Closure(field_types) => types.extend(field_types.values().cloned()),
+                    }
+                },
+                IrDef::Union(_, union) => types.extend(union.borrow().0.iter().cloned()),
+                IrDef::Var(_, var) => {
+                    match &*var.borrow() {
+                        IrVar::Const(typ, _) => types.push(typ.clone()),
+                        IrVar::Var(_, _, typ, _) => types.push(typ.clone()),
+                        IrVar::Fun(fun) => {
+                            match &**fun {
+                                IrFun::Fun(_, arg_types, ret_type, block, _, _, _, _) => {
+                                    types.extend(arg_types.iter().cloned());
+                                    types.push(ret_type.clone());
+                                    block_types(block, types);
+                                },
+                                IrFun::Caller(fun_type, arg_types, ret_type, _, _, _, _, _) => {
+                                    types.push(fun_type.clone());
+                                    types.extend(arg_types.iter().cloned());
+                                    types.push(ret_type.clone());
+                                },
+                            }
+                        },
+                    }
+                },
+            }
+        }
+    }
+
+    fn type_def_idents(&self, tree: &IrTree) -> Vec<IrType>
+    {
+        let mut idents: Vec<IrType> = Vec::new();
+        let mut ident_set: BTreeSet<IrType> = BTreeSet::new();
+        for def in tree.defs() {
+            let typ = match &**def {
+                IrDef::Struct(ident, _) => IrType::Struct(ident.clone()),
+                IrDef::Union(ident, _) => IrType::Union(ident.clone()),
+                IrDef::Var(_, _) => continue,
+            };
+            if ident_set.insert(typ.clone()) {
+                idents.push(typ);
+            }
+        }
+        let mut rest_idents: BTreeSet<IrType> = BTreeSet::new();
+        for ident in tree.structs().keys() {
+            rest_idents.insert(IrType::Struct(ident.clone()));
+        }
+        for ident in tree.unions().keys() {
+            rest_idents.insert(IrType::Union(ident.clone()));
+        }
+        for typ in rest_idents {
+            if ident_set.insert(typ.clone()) {
+                idents.push(typ);
+            }
+        }
+        idents
+    }
+
+    fn field_types(&self, tree: &IrTree, typ: &IrType) -> BackendResult<Vec<(usize, Box<IrType>)>>
+    {
+        match typ {
+            IrType::Struct(ident) => {
+                match tree.struct1(ident) {
+                    Some(struct1) => {
+                        match &*struct1.borrow() {
+                            IrStruct::Struct(field_types) => Ok(field_types.iter().cloned().enumerate().collect()),
+                            IrStruct::Closure(field_types) => Ok(field_types.iter().map(|p| (*p.0, p.1.clone())).collect()),
+                        }
+                    },
+                    None => Err(BackendError::Internal(format!("field_types: no structure {}", ident))),
+                }
+            },
+            IrType::Union(ident) => {
+                match tree.union(ident) {
+                    Some(union) => Ok(union.borrow().0.iter().cloned().enumerate().collect()),
+                    None => Err(BackendError::Internal(format!("field_types: no union {}", ident))),
+                }
+            },
+            _ => Ok(Vec::new()),
+        }
+    }
+
+    fn add_type_deps(&self, tree: &IrTree, typ: &IrType, visited: &mut BTreeSet<IrType>, in_progress: &mut BTreeSet<IrType>, sorted_types: &mut Vec<IrType>) -> BackendResult<()>
+    {
+        match typ {
+            IrType::Struct(_) | IrType::Union(_) => {
+                if visited.contains(typ) {
+                    return Ok(());
+                }
+                if !in_progress.insert(typ.clone()) {
+                    return Err(BackendError::Internal(format!("add_type_deps: recursive type {}", type_to_string(typ))));
+                }
+                for (_, field_type) in self.field_types(tree, typ)? {
+                    self.add_type_deps(tree, &field_type, visited, in_progress, sorted_types)?;
+                }
+                in_progress.remove(typ);
+                visited.insert(typ.clone());
+                sorted_types.push(typ.clone());
+                Ok(())
+            },
+            IrType::Array(elem_type, _) => self.add_type_deps(tree, elem_type, visited, in_progress, sorted_types),
+            _ => Ok(()),
+        }
+    }
+
+    fn emit_type_defs(&self, tree: &IrTree, s: &mut String) -> BackendResult<()>
+    {
+        let idents = self.type_def_idents(tree);
+        if idents.is_empty() {
+            return Ok(());
+        }
+        for typ in &idents {
+            writeln!(s, "{};", type_to_string(typ)).unwrap();
+        }
+        s.push('\n');
+        let mut visited: BTreeSet<IrType> = BTreeSet::new();
+        let mut in_progress: BTreeSet<IrType> = BTreeSet::new();
+        let mut sorted_types: Vec<IrType> = Vec::new();
+        for typ in &idents {
+            self.add_type_deps(tree, typ, &mut visited, &mut in_progress, &mut sorted_types)?;
+        }
+        for typ in &sorted_types {
+            writeln!(s, "{}", type_to_string(typ)).unwrap();
+            s.push_str("{\n");
+            for (idx, field_type) in self.field_types(tree, typ)? {
+                writeln!(s, "    {};", decl_to_string(&field_type, "", field_name(idx).as_str())).unwrap();
+            }
+            s.push_str("};\n\n");
+        }
+        Ok(())
+    }
+
+    fn emit_global_vars(&self, tree: &IrTree, s: &mut String) -> BackendResult<()>
+    {
+        let ctx = EmitterContext::new(tree);
+        let mut is_written = false;
+        for def in tree.defs() {
+            if let IrDef::Var(ident, var) = &**def {
+                match &*var.borrow() {
+                    IrVar::Const(typ, value) => {
+                        writeln!(s, "{} = {};", decl_to_string(typ, "constant ", ident.as_str()), ctx.value_to_string(value)?).unwrap();
+                        is_written = true;
+                    },
+                    IrVar::Var(modifier, access_modifier, typ, value) => {
+                        let mut quals = String::new();
+                        if *modifier == IrGlobalVarModifier::Global {
+                            quals.push_str("global ");
+                        }
+                        if *access_modifier == IrAccessModifier::Const {
+                            quals.push_str("const ");
+                        }
+                        writeln!(s, "{} = {};", decl_to_string(typ, quals.as_str(), ident.as_str()), ctx.value_to_string(value)?).unwrap();
+                        is_written = true;
+                    },
+                    IrVar::Fun(_) => (),
+                }
+            }
+        }
+        if is_written {
+            s.push('\n');
+        }
+        Ok(())
+    }
+
+    fn fun_header(&self, ident: &String, fun: &IrFun) -> BackendResult<String>
+    {
+        match fun {
+            IrFun::Fun(modifier, arg_types, ret_type, _, private_heap_flag, local_heap_flag, global_heap_flag, panic_flag) => {
+                if let IrType::Array(_, _) = &**ret_type {
+                    return Err(BackendError::Internal(format!("fun_header: function {} returns array", ident)));
+                }
+                let prefix = match modifier {
+                    IrFunModifier::None => "",
+                    IrFunModifier::Inline => "inline ",
+                    IrFunModifier::Kernel => "kernel ",
+                };
+                let mut args: Vec<String> = Vec::new();
+                for (i, arg_type) in arg_types.iter().enumerate() {
+                    args.push(decl_to_string(arg_type, "", local_var_name(i).as_str()));
+                }
+                args.extend(hidden_params(*private_heap_flag, *local_heap_flag, *global_heap_flag, *panic_flag, *modifier == IrFunModifier::Kernel, hidden_param));
+                if args.is_empty() {
+                    args.push(String::from("void"));
+                }
+                Ok(format!("{}{}", prefix, decl_to_string(ret_type, "", format!("{}({})", ident, args.join(", ")).as_str())))
+            },
+            IrFun::Caller(fun_type, arg_types, ret_type, _, private_heap_flag, local_heap_flag, global_heap_flag, panic_flag) => {
+                let mut args: Vec<String> = vec![decl_to_string(fun_type, "", caller_arg_name(0).as_str())];
+                for (i, arg_type) in arg_types.iter().enumerate() {
+                    args.push(decl_to_string(arg_type, "", caller_arg_name(i + 1).as_str()));
+                }
+                args.extend(hidden_params(*private_heap_flag, *local_heap_flag, *global_heap_flag, *panic_flag, false, hidden_param));
+                Ok(decl_to_string(ret_type, "", format!("{}({})", ident, args.join(", ")).as_str()))
+            },
+        }
+    }
+
+    fn emit_fun_protos(&self, tree: &IrTree, s: &mut String) -> BackendResult<()>
+    {
+        let mut is_written = false;
+        for def in tree.defs() {
+            if let IrDef::Var(ident, var) = &**def {
+                if let IrVar::Fun(fun) = &*var.borrow() {
+                    writeln!(s, "{};", self.fun_header(ident, fun)?).unwrap();
+                    is_written = true;
+                }
+            }
+        }
+        if is_written {
+            s.push('\n');
+        }
+        Ok(())
+    }
+
+    fn emit_heap_funs(&self, tree: &IrTree, s: &mut String)
+    {
+        let mut heap_flags = (IrPrivateHeapFlag::None, IrLocalHeapFlag::None, IrGlobalHeapFlag::None);
+        for def in tree.defs() {
+            if let IrDef::Var(_, var) = &**def {
+                if let IrVar::Fun(fun) = &*var.borrow() {
+                    match &**fun {
+                        IrFun::Fun(_, _, _, _, private_heap_flag, local_heap_flag, global_heap_flag, _) | IrFun::Caller(_, _, _, _, private_heap_flag, local_heap_flag, global_heap_flag, _) => {
+                            heap_flags.0 = heap_flags.0.max(*private_heap_flag);
+                            heap_flags.1 = heap_flags.1.max(*local_heap_flag);
+                            heap_flags.2 = heap_flags.2.max(*global_heap_flag);
+                        },
+                    }
+                }
+            }
+        }
+        if heap_flags.0 == IrPrivateHeapFlag::Heap {
+            writeln!(s, "#ifndef {}", PRIVATE_HEAP_SIZE_MACRO).unwrap();
+            writeln!(s, "#define {} {}", PRIVATE_HEAP_SIZE_MACRO, DEFAULT_PRIVATE_HEAP_SIZE).unwrap();
+            s.push_str("#endif\n");
+            s.push('\n');
+            self.emit_alloc_fun(mangle_private_alloc_fun_name().as_str(), "private", private_heap_name(), false, s);
+        }
+        if heap_flags.1 == IrLocalHeapFlag::Heap {
+            writeln!(s, "#ifndef {}", LOCAL_HEAP_SIZE_MACRO).unwrap();
+            writeln!(s, "#define {} {}", LOCAL_HEAP_SIZE_MACRO, DEFAULT_LOCAL_HEAP_SIZE).unwrap();
+            s.push_str("#endif\n");
+            s.push('\n');
+            self.emit_alloc_fun(mangle_local_alloc_fun_name().as_str(), "local", local_heap_name(), true, s);
+        }
+        if heap_flags.2 == IrGlobalHeapFlag::Heap {
+            self.emit_alloc_fun(mangle_global_alloc_fun_name().as_str(), "global", global_heap_name(), true, s);
+        }
+    }
+
+    fn emit_alloc_fun(&self, ident: &str, modifier: &str, heap: &str, is_atomic: bool, s: &mut String)
+    {
+        writeln!(s, "{} void *{}({} uint *{}, uint size, uint align)", modifier, ident, modifier, heap).unwrap();
+        s.push_str("{\n");
+        if is_atomic {
+            writeln!(s, "    uint off = atomic_add({} + {}, size + align - 1U);", heap, HEAP_OFFSET_INDEX).unwrap();
+            writeln!(s, "    if(off > {}[{}]) {{", heap, HEAP_CAPACITY_INDEX).unwrap();
+            s.push_str("        return 0;\n");
+            s.push_str("    }\n");
+            s.push_str("    off = (off + align - 1U) & ~(align - 1U);\n");
+        } else {
+            writeln!(s, "    uint off = ({}[{}] + align - 1U) & ~(align - 1U);", heap, HEAP_OFFSET_INDEX).unwrap();
+        }
+        writeln!(s, "    if(off > {}[{}] || size > {}[{}] - off) {{", heap, HEAP_CAPACITY_INDEX, heap, HEAP_CAPACITY_INDEX).unwrap();
+        s.push_str("        return 0;\n");
+        s.push_str("    }\n");
+        if !is_atomic {
+            writeln!(s, "    {}[{}] = off + size;", heap, HEAP_OFFSET_INDEX).unwrap();
+        }
+        writeln!(s, "    return ({} void *) (({} uchar *) {} + {}U + off);", modifier, modifier, heap, HEAP_HEADER_SIZE).unwrap();
+        s.push_str("}\n");
+        s.push('\n');
+    }
+
+    fn emit_kernel_heaps(&self, private_heap_flag: IrPrivateHeapFlag, local_heap_flag: IrLocalHeapFlag, s: &mut String)
+    {
+        if local_heap_flag == IrLocalHeapFlag::Heap {
+            writeln!(s, "    local ulong16 {}[({} + {}U) / 128U];", local_heap_value_name(), LOCAL_HEAP_SIZE_MACRO, HEAP_HEADER_SIZE + 127).unwrap();
+            writeln!(s, "    local uint *{} = (local uint *) {};", local_heap_name(), local_heap_value_name()).unwrap();
+        }
+        if private_heap_flag == IrPrivateHeapFlag::Heap {
+            writeln!(s, "    ulong16 {}[({} + {}U) / 128U];", private_heap_value_name(), PRIVATE_HEAP_SIZE_MACRO, HEAP_HEADER_SIZE + 127).unwrap();
+            writeln!(s, "    private uint *{} = (private uint *) {};", private_heap_name(), private_heap_value_name()).unwrap();
+            writeln!(s, "    {}[{}] = 0U;", private_heap_name(), HEAP_OFFSET_INDEX).unwrap();
+            writeln!(s, "    {}[{}] = {};", private_heap_name(), HEAP_CAPACITY_INDEX, PRIVATE_HEAP_SIZE_MACRO).unwrap();
+        }
+        if local_heap_flag == IrLocalHeapFlag::Heap {
+            s.push_str("    if(get_local_id(0) == 0 && get_local_id(1) == 0 && get_local_id(2) == 0) {\n");
+            writeln!(s, "        {}[{}] = 0U;", local_heap_name(), HEAP_OFFSET_INDEX).unwrap();
+            writeln!(s, "        {}[{}] = {};", local_heap_name(), HEAP_CAPACITY_INDEX, LOCAL_HEAP_SIZE_MACRO).unwrap();
+            s.push_str("    }\n");
+            s.push_str("    barrier(CLK_LOCAL_MEM_FENCE);\n");
+        }
+    }
+
+    fn emit_panic_funs(&self, tree: &IrTree, s: &mut String)
+    {
+        let can_panic = tree.defs().iter().any(|def| {
+                match &**def {
+                    IrDef::Var(_, var) => {
+                        match &*var.borrow() {
+                            IrVar::Fun(fun) => {
+                                match &**fun {
+                                    IrFun::Fun(_, _, _, _, _, _, _, panic_flag) => *panic_flag == IrPanicFlag::Panic,
+                                    IrFun::Caller(_, _, _, _, _, _, _, panic_flag) => *panic_flag == IrPanicFlag::Panic,
+                                }
+                            },
+                            _ => false,
+                        }
+                    },
+                    _ => false,
+                }
+        });
+        if !can_panic {
+            return;
+        }
+        let pb = panic_buffer_name();
+        let pf = panic_flag_name();
+        writeln!(s, "void {}(global uint *{}, private uint *{}, uint id)", mangle_panic_fun_name(), pb, pf).unwrap();
+        s.push_str("{\n");
+        writeln!(s, "    if(atomic_cmpxchg({} + {}, 0U, id) == 0U) {{", pb, PANIC_ID_INDEX).unwrap();
+        for i in 0..3 {
+            writeln!(s, "        {}[{}] = (uint) get_global_id({});", pb, PANIC_GLOBAL_ID_INDEX + i, i).unwrap();
+        }
+        writeln!(s, "        {}[{}] = 0U;", pb, PANIC_CALL_COUNT_INDEX).unwrap();
+        writeln!(s, "        *{} = 2U;", pf).unwrap();
+        s.push_str("    } else {\n");
+        writeln!(s, "        *{} = 1U;", pf).unwrap();
+        s.push_str("    }\n");
+        s.push_str("}\n");
+        s.push('\n');
+        writeln!(s, "void {}(global uint *{}, private uint *{}, uint id)", mangle_panic_call_fun_name(), pb, pf).unwrap();
+        s.push_str("{\n");
+        writeln!(s, "    if(*{} == 2U) {{", pf).unwrap();
+        writeln!(s, "        uint n = {}[{}];", pb, PANIC_CALL_COUNT_INDEX).unwrap();
+        writeln!(s, "        if(n < {}U) {{", MAX_PANIC_CALL_COUNT).unwrap();
+        writeln!(s, "            {}[{}U + n] = id;", pb, PANIC_CALL_ID_INDEX).unwrap();
+        s.push_str("        }\n");
+        writeln!(s, "        {}[{}] = n + 1U;", pb, PANIC_CALL_COUNT_INDEX).unwrap();
+        s.push_str("    }\n");
+        s.push_str("}\n");
+        s.push('\n');
+    }
+
+    fn emit_funs(&self, tree: &IrTree, panic_table: &mut PanicTable, s: &mut String) -> BackendResult<()>
+    {
+        for def in tree.defs() {
+            if let IrDef::Var(ident, var) = &**def {
+                if let IrVar::Fun(fun) = &*var.borrow() {
+                    writeln!(s, "{}", self.fun_header(ident, fun)?).unwrap();
+                    match &**fun {
+                        IrFun::Fun(modifier, arg_types, ret_type, block, private_heap_flag, local_heap_flag, global_heap_flag, panic_flag) => {
+                            let mut ctx = EmitterContext::new(tree);
+                            ctx.local_var_types = arg_types.clone();
+                            ctx.ret_type = ret_type.clone();
+                            ctx.can_panic = *panic_flag == IrPanicFlag::Panic;
+                            ctx.heap_flags = (*private_heap_flag, *local_heap_flag, *global_heap_flag);
+                            ctx.panic_table = Some(&mut *panic_table);
+                            let is_kernel = *modifier == IrFunModifier::Kernel;
+                            let has_kernel_heaps = is_kernel && (*private_heap_flag == IrPrivateHeapFlag::Heap || *local_heap_flag == IrLocalHeapFlag::Heap);
+                            if ctx.can_panic || has_kernel_heaps {
+                                s.push_str("{\n");
+                                if is_kernel {
+                                    self.emit_kernel_heaps(*private_heap_flag, *local_heap_flag, s);
+                                    if ctx.can_panic {
+                                        writeln!(s, "    uint {} = 0U;", panic_flag_value_name()).unwrap();
+                                        writeln!(s, "    private uint *{} = &{};", panic_flag_name(), panic_flag_value_name()).unwrap();
+                                    }
+                                } else if **ret_type != IrType::Void {
+                                    writeln!(s, "    {};", decl_to_string(ret_type, "", panic_ret_name())).unwrap();
+                                }
+                                self.emit_nested_block_content(block, &mut ctx, 1, false, s)?;
+                                s.push_str("}\n");
+                            } else {
+                                self.emit_block(block, &mut ctx, 0, false, s)?;
+                            }
+                        },
+                        IrFun::Caller(fun_type, arg_types, ret_type, caller_funs, private_heap_flag, local_heap_flag, global_heap_flag, panic_flag) => {
+                            self.emit_caller_body(ident, fun_type, arg_types.as_slice(), ret_type, caller_funs, (*private_heap_flag, *local_heap_flag, *global_heap_flag, *panic_flag), tree, panic_table, s)?;
+                        },
+                    }
+                    s.push('\n');
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn closure_field(&self, fun_type: &IrType, closure_type: &Option<Box<IrType>>, ctx: &EmitterContext) -> BackendResult<Option<(String, Box<IrType>)>>
+    {
+        match closure_type {
+            Some(closure_type) => {
+                for (idx, field_type) in self.field_types(ctx.tree, fun_type)? {
+                    if field_type == *closure_type {
+                        return Ok(Some((format!("{}.{}", caller_arg_name(0), field_name(idx)), closure_type.clone())));
+                    }
+                }
+                Err(BackendError::Internal(format!("closure_field: no field for closure {}", type_to_string(closure_type))))
+            },
+            None => Ok(None),
+        }
+    }
+
+    fn emit_caller_body(&self, ident: &String, fun_type: &IrType, arg_types: &[Box<IrType>], ret_type: &IrType, caller_funs: &IrCallerFuns, flags: (IrPrivateHeapFlag, IrLocalHeapFlag, IrGlobalHeapFlag, IrPanicFlag), tree: &IrTree, panic_table: &mut PanicTable, s: &mut String) -> BackendResult<()>
+    {
+        let (private_heap_flag, local_heap_flag, global_heap_flag, panic_flag) = flags;
+        let args: Vec<String> = (1..=arg_types.len()).map(caller_arg_name).collect();
+        let index_field = match fun_type {
+            IrType::Struct(_) => format!("{}.{}", caller_arg_name(0), field_name(0)),
+            _ => return Err(BackendError::Internal(format!("emit_caller_body: function type of caller {} isn't structure", ident))),
+        };
+        s.push_str("{\n");
+        if panic_flag == IrPanicFlag::Panic && *ret_type != IrType::Void {
+            writeln!(s, "    {};", decl_to_string(ret_type, "", panic_ret_name())).unwrap();
+        }
+        writeln!(s, "    switch({}) {{", index_field).unwrap();
+        for (idx, caller_fun) in caller_funs.funs() {
+            writeln!(s, "        case {}U:", idx).unwrap();
+            match caller_fun {
+                IrCallerFun::Fun(fun_ident) => {
+                    let mut ctx = EmitterContext::new(tree);
+                    ctx.can_panic = panic_flag == IrPanicFlag::Panic;
+                    ctx.heap_flags = (private_heap_flag, local_heap_flag, global_heap_flag);
+                    let mut fun_args = args.clone();
+                    fun_args.extend(ctx.hidden_args(fun_ident)?.into_iter().map(String::from));
+                    if *ret_type == IrType::Void {
+                        writeln!(s, "            {}({});", fun_ident, fun_args.join(", ")).unwrap();
+                        s.push_str("            return;\n");
+                    } else {
+                        writeln!(s, "            return {}({});", fun_ident, fun_args.join(", ")).unwrap();
+                    }
+                },
+                IrCallerFun::Lambda(private_closure_type, local_closure_type, global_closure_type, start_idx, block) => {
+                    let mut ctx = EmitterContext::new(tree);
+                    *ctx.ret_type = ret_type.clone();
+                    ctx.can_panic = panic_flag == IrPanicFlag::Panic;
+                    ctx.heap_flags = (private_heap_flag, local_heap_flag, global_heap_flag);
+                    ctx.panic_table = Some(&mut *panic_table);
+                    ctx.caller_arg_types.push(Box::new(fun_type.clone()));
+                    ctx.caller_arg_types.extend(arg_types.iter().cloned());
+                    ctx.private_closure = self.closure_field(fun_type, private_closure_type, &ctx)?;
+                    ctx.local_closure = self.closure_field(fun_type, local_closure_type, &ctx)?;
+                    ctx.global_closure = self.closure_field(fun_type, global_closure_type, &ctx)?;
+                    ctx.local_var_types = vec![Box::new(IrType::Void); *start_idx];
+                    s.push_str("            {\n");
+                    for (i, arg_type) in arg_types.iter().enumerate() {
+                        writeln!(s, "                {} = {};", decl_to_string(arg_type, "", local_var_name(start_idx + i).as_str()), caller_arg_name(i + 1)).unwrap();
+                        ctx.local_var_types.push(arg_type.clone());
+                    }
+                    self.emit_nested_block_content(block, &mut ctx, 4, false, s)?;
+                    s.push_str("            }\n");
+                },
+                IrCallerFun::InlineFun(_, _) => return Err(BackendError::Internal(format!("emit_caller_body: inline functions of caller {} are unsupported", ident))),
+            }
+        }
+        s.push_str("    }\n");
+        s.push_str("}\n");
+        Ok(())
+    }
+
+    fn indent(&self, level: usize, s: &mut String)
+    {
+        for _ in 0..level {
+            s.push_str("    ");
+        }
+    }
+
+    fn emit_block(&self, block: &IrBlock, ctx: &mut EmitterContext, level: usize, is_in_switch: bool, s: &mut String) -> BackendResult<()>
+    {
+        self.indent(level, s);
+        s.push_str("{\n");
+        self.emit_nested_block_content(block, ctx, level + 1, is_in_switch, s)?;
+        self.indent(level, s);
+        s.push_str("}\n");
+        Ok(())
+    }
+
+    fn emit_array_copy(&self, dst: String, src: String, typ: &IrType, level: usize, s: &mut String) -> BackendResult<()>
+    {
+        match typ {
+            IrType::Array(elem_type, len) => {
+                let idx_name = format!("i{}", level);
+                self.indent(level, s);
+                writeln!(s, "for(size_t {} = 0; {} < {}; {}++) {{", idx_name, idx_name, len, idx_name).unwrap();
+                self.emit_array_copy(format!("{}[{}]", dst, idx_name), format!("{}[{}]", src, idx_name), elem_type, level + 1, s)?;
+                self.indent(level, s);
+                s.push_str("}\n");
+                Ok(())
+            },
+            _ => {
+                self.indent(level, s);
+                writeln!(s, "{} = {};", dst, src).unwrap();
+                Ok(())
+            },
+        }
+    }
+
+    fn emit_instr(&self, instr: &IrInstr, ctx: &mut EmitterContext, level: usize, is_in_switch: bool, s: &mut String) -> BackendResult<()>
+    {
+        match instr {
+            IrInstr::Op(op) => {
+                self.indent(level, s);
+                writeln!(s, "{};", ctx.op_to_string(op)?).unwrap();
+                self.emit_panic_call_check(op, ctx, level, s)?;
+            },
+            IrInstr::Assign(var, op) => {
+                let (var_s, typ) = ctx.instr_var_to_string(var)?;
+                let op_s = ctx.op_to_string(op)?;
+                match &*typ {
+                    IrType::Array(_, _) => self.emit_array_copy(var_s, op_s, &typ, level, s)?,
+                    _ => {
+                        self.indent(level, s);
+                        writeln!(s, "{} = {};", var_s, op_s).unwrap();
+                    },
+                }
+                self.emit_panic_call_check(op, ctx, level, s)?;
+            },
+            IrInstr::Return(None) => {
+                self.indent(level, s);
+                s.push_str("return;\n");
+            },
+            IrInstr::Return(Some(op)) => {
+                if ctx.panic_call_poses(op).is_some() {
+                    self.indent(level, s);
+                    writeln!(s, "{} = {};", panic_ret_name(), ctx.op_to_string(op)?).unwrap();
+                    self.emit_panic_call_check(op, ctx, level, s)?;
+                    self.indent(level, s);
+                    writeln!(s, "return {};", panic_ret_name()).unwrap();
+                } else {
+                    self.indent(level, s);
+                    writeln!(s, "return {};", ctx.op_to_string(op)?).unwrap();
+                }
+            },
+            IrInstr::Break => {
+                if is_in_switch {
+                    return Err(BackendError::Internal(String::from("emit_instr: break in switch")));
+                }
+                self.indent(level, s);
+                s.push_str("break;\n");
+            },
+            IrInstr::Continue => {
+                self.indent(level, s);
+                s.push_str("continue;\n");
+            },
+            IrInstr::Block(block) => self.emit_block(block, ctx, level, is_in_switch, s)?,
+            IrInstr::If(op, block1, block2) => {
+                if ctx.panic_call_poses(op).is_some() {
+                    return Err(BackendError::Internal(String::from("emit_instr: call of function that can panic in condition")));
+                }
+                self.indent(level, s);
+                writeln!(s, "if({}) {{", ctx.op_to_string(op)?).unwrap();
+                self.emit_nested_block_content(block1, ctx, level + 1, is_in_switch, s)?;
+                if !block2.instrs().is_empty() || !block2.local_var_pairs().is_empty() {
+                    self.indent(level, s);
+                    s.push_str("} else {\n");
+                    self.emit_nested_block_content(block2, ctx, level + 1, is_in_switch, s)?;
+                }
+                self.indent(level, s);
+                s.push_str("}\n");
+            },
+            IrInstr::Switch(op, cases) => {
+                if ctx.panic_call_poses(op).is_some() {
+                    return Err(BackendError::Internal(String::from("emit_instr: call of function that can panic in condition")));
+                }
+                let has_break = cases.iter().any(|case| {
+                        match case {
+                            IrCase::Case(_, block) => block_has_break(block),
+                            IrCase::Default(block) => block_has_break(block),
+                        }
+                });
+                if has_break {
+                    self.emit_switch_as_ifs(op, cases, ctx, level, is_in_switch, s)?;
+                } else {
+                    self.indent(level, s);
+                    writeln!(s, "switch({}) {{", ctx.op_to_string(op)?).unwrap();
+                    for case in cases {
+                        let block = match case {
+                            IrCase::Case(value, block) => {
+                                self.indent(level + 1, s);
+                                writeln!(s, "case {}: {{", case_value_to_string(value)).unwrap();
+                                block
+                            },
+                            IrCase::Default(block) => {
+                                self.indent(level + 1, s);
+                                s.push_str("default: {\n");
+                                block
+                            },
+                        };
+                        self.emit_nested_block_content(block, ctx, level + 2, true, s)?;
+                        self.indent(level + 2, s);
+                        s.push_str("break;\n");
+                        self.indent(level + 1, s);
+                        s.push_str("}\n");
+                    }
+                    self.indent(level, s);
+                    s.push_str("}\n");
+                }
+            },
+            IrInstr::Loop(block) => {
+                self.indent(level, s);
+                s.push_str("for(;;) {\n");
+                self.emit_nested_block_content(block, ctx, level + 1, false, s)?;
+                self.indent(level, s);
+                s.push_str("}\n");
+            },
+            IrInstr::Panic(msg, pos, panic_poses) => {
+                if !ctx.can_panic {
+                    return Err(BackendError::Internal(String::from("emit_instr: panic in function that can't panic")));
+                }
+                let mut poses = vec![pos.clone()];
+                poses.extend_from_slice(panic_poses.as_slice());
+                let id = ctx.add_panic_entry(PanicEntry::Panic(msg.clone(), poses))?;
+                self.indent(level, s);
+                writeln!(s, "{}({}, {}, {}U);", mangle_panic_fun_name(), panic_buffer_name(), panic_flag_name(), id).unwrap();
+                self.indent(level, s);
+                writeln!(s, "{}", ctx.panic_return_to_string()).unwrap();
+            },
+        }
+        Ok(())
+    }
+
+    fn emit_panic_call_check(&self, op: &IrOp, ctx: &mut EmitterContext, level: usize, s: &mut String) -> BackendResult<()>
+    {
+        if let Some(poses) = ctx.panic_call_poses(op) {
+            let id = ctx.add_panic_entry(PanicEntry::Call(poses))?;
+            self.indent(level, s);
+            writeln!(s, "if(*{} != 0U) {{", panic_flag_name()).unwrap();
+            self.indent(level + 1, s);
+            writeln!(s, "{}({}, {}, {}U);", mangle_panic_call_fun_name(), panic_buffer_name(), panic_flag_name(), id).unwrap();
+            self.indent(level + 1, s);
+            writeln!(s, "{}", ctx.panic_return_to_string()).unwrap();
+            self.indent(level, s);
+            s.push_str("}\n");
+        }
+        Ok(())
+    }
+
+    fn emit_nested_block_content(&self, block: &IrBlock, ctx: &mut EmitterContext, level: usize, is_in_switch: bool, s: &mut String) -> BackendResult<()>
+    {
+        let old_len = ctx.local_var_types.len();
+        for local_var_pair in block.local_var_pairs() {
+            let IrLocalVarPair(modifier, typ) = local_var_pair;
+            let name = local_var_name(ctx.local_var_types.len());
+            self.indent(level, s);
+            writeln!(s, "{};", decl_to_string(typ, local_var_modifier_to_str(*modifier), name.as_str())).unwrap();
+            ctx.local_var_types.push(typ.clone());
+        }
+        for instr in block.instrs() {
+            self.emit_instr(instr, ctx, level, is_in_switch, s)?;
+        }
+        ctx.local_var_types.truncate(old_len);
+        Ok(())
+    }
+
+    fn emit_switch_as_ifs(&self, op: &IrOp, cases: &[IrCase], ctx: &mut EmitterContext, level: usize, is_in_switch: bool, s: &mut String) -> BackendResult<()>
+    {
+        let op_s = ctx.op_to_string(op)?;
+        let mut is_first = true;
+        let mut default_block: Option<&IrBlock> = None;
+        for case in cases {
+            match case {
+                IrCase::Case(value, block) => {
+                    self.indent(level, s);
+                    if is_first {
+                        writeln!(s, "if({} == {}) {{", op_s, case_value_to_string(value)).unwrap();
+                    } else {
+                        writeln!(s, "}} else if({} == {}) {{", op_s, case_value_to_string(value)).unwrap();
+                    }
+                    self.emit_nested_block_content(block, ctx, level + 1, is_in_switch, s)?;
+                    is_first = false;
+                },
+                IrCase::Default(block) => default_block = Some(block),
+            }
+        }
+        match default_block {
+            Some(block) => {
+                self.indent(level, s);
+                if is_first {
+                    s.push_str("{\n");
+                } else {
+                    s.push_str("} else {\n");
+                }
+                self.emit_nested_block_content(block, ctx, level + 1, is_in_switch, s)?;
+                self.indent(level, s);
+                s.push_str("}\n");
+            },
+            None => {
+                if !is_first {
+                    self.indent(level, s);
+                    s.push_str("}\n");
+                }
+            },
+        }
+        Ok(())
+    }
+}
+
+impl Default for Emitter
+{
+    fn default() -> Self
+    { Self::new() }
+}
+
+pub fn emit_ir_tree(tree: &IrTree) -> BackendResult<String>
+{
+    let emitter = Emitter::new();
+    emitter.emit(tree)
+}
+
+pub fn emit_ir_tree_with_panic_table(tree: &IrTree) -> BackendResult<(String, PanicTable)>
+{
+    let emitter = Emitter::new();
+    emitter.emit_with_panic_table(tree)
+}
+
+#[cfg(test)]
+mod tests;
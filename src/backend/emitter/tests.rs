@@ -0,0 +1,691 @@
+//
+// Copyright (c) 2025 Łukasz Szpakowski
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+use std::cell::*;
+use std::rc::*;
+use crate::backend::lowering::lower_tree;
+use crate::backend::lowering::Lowerer;
+use crate::frontend::do_frontend_phases;
+use super::*;
+
+#[test]
+fn test_emitter_emit_emits_kernel()
+{
+    let s = "
+import opencl;
+a: Int = 10;
+kernel k(xs: UniqGlobalSlice<Int>) -> () =
+    let i = get_global_id(0 as Uint);
+        (x, xs) = xs[i] ->;
+        _ = xs[i] <- (x + a);
+    in  ();
+";
+    let s2 = &s[1..];
+    let tree = match do_frontend_phases(s2) {
+        Ok(tree) => tree,
+        Err(errs) => {
+            println!("{}", errs);
+            panic!();
+        },
+    };
+    let ir_tree = match lower_tree(&tree) {
+        Ok(ir_tree) => ir_tree,
+        Err(err) => {
+            println!("{}", err);
+            panic!();
+        },
+    };
+    match emit_ir_tree(&ir_tree) {
+        Ok(src) => {
+            println!("{}", src);
+            assert!(src.contains("constant int _VV1a = 10;\n"));
+            assert!(src.contains("kernel void _VF1k(global int *v0, ulong v1);\n"));
+            assert!(src.contains("kernel void _VF1k(global int *v0, ulong v1)\n{\n"));
+            assert!(src.find("constant int _VV1a").unwrap() < src.find("kernel void _VF1k").unwrap());
+            assert!(src.contains("get_global_id("));
+        },
+        Err(err) => {
+            println!("{}", err);
+            assert!(false);
+        },
+    }
+}
+
+#[test]
+fn test_emitter_emit_emits_same_source_for_same_tree()
+{
+    let s = "
+g(t: (Int, Float)) -> Float = let (a, b) = t in b * (a as Float);
+h(t: (Float, Int), u: (Int, Float)) -> Float = g(u);
+kernel k(x: Int, y: Float) -> () = let _ = h((y, x), (x, y)) in ();
+";
+    let s2 = &s[1..];
+    let tree = match do_frontend_phases(s2) {
+        Ok(tree) => tree,
+        Err(errs) => {
+            println!("{}", errs);
+            panic!();
+        },
+    };
+    let mut srcs: Vec<String> = Vec::new();
+    for _ in 0..4 {
+        let ir_tree = match Lowerer::new_with_inline_threshold(0).lower_tree(&tree) {
+            Ok(ir_tree) => ir_tree,
+            Err(err) => {
+                println!("{}", err);
+                panic!();
+            },
+        };
+        match emit_ir_tree(&ir_tree) {
+            Ok(src) => srcs.push(src),
+            Err(err) => {
+                println!("{}", err);
+                assert!(false);
+            },
+        }
+    }
+    println!("{}", srcs[0]);
+    assert!(srcs[0].contains("struct _VSL3IntNPE5FloatNPR\n{\n    int f0;\n    float f1;\n};\n"));
+    assert!(srcs[0].contains("float _VF1g(struct _VSL3IntNPE5FloatNPR v0);\n"));
+    for src in &srcs[1..] {
+        assert_eq!(srcs[0], *src);
+    }
+}
+
+#[test]
+fn test_emitter_emit_emits_structures_in_dependency_order()
+{
+    let mut ir_tree = IrTree::new();
+    let struct_a = Rc::new(RefCell::new(IrStruct::Struct(vec![
+                    Box::new(IrType::Union(String::from("B"))),
+                    Box::new(IrType::Ptr(IrPtrModifier::Global, IrAccessModifier::None, Box::new(IrType::Struct(String::from("A")))))
+    ])));
+    let union_b = Rc::new(RefCell::new(IrUnion(vec![
+                    Box::new(IrType::Array(Box::new(IrType::Struct(String::from("C"))), 2)),
+                    Box::new(IrType::Double)
+    ])));
+    let struct_c = Rc::new(RefCell::new(IrStruct::Struct(vec![
+                    Box::new(IrType::FloatN(4))
+    ])));
+    ir_tree.add_struct(String::from("A"), struct_a.clone());
+    ir_tree.add_def(IrDef::Struct(String::from("A"), struct_a));
+    ir_tree.add_union(String::from("B"), union_b.clone());
+    ir_tree.add_def(IrDef::Union(String::from("B"), union_b));
+    ir_tree.add_struct(String::from("C"), struct_c.clone());
+    ir_tree.add_def(IrDef::Struct(String::from("C"), struct_c));
+    match emit_ir_tree(&ir_tree) {
+        Ok(src) => {
+            println!("{}", src);
+            assert!(src.starts_with("#pragma OPENCL EXTENSION cl_khr_fp64 : enable\n"));
+            assert!(src.contains("struct A;\nunion B;\nstruct C;\n"));
+            let c_idx = src.find("struct C\n{\n    float4 f0;\n};\n").unwrap();
+            let b_idx = src.find("union B\n{\n    struct C f0[2];\n    double f1;\n};\n").unwrap();
+            let a_idx = src.find("struct A\n{\n    union B f0;\n    global struct A *f1;\n};\n").unwrap();
+            assert!(c_idx < b_idx);
+            assert!(b_idx < a_idx);
+        },
+        Err(err) => {
+            println!("{}", err);
+            assert!(false);
+        },
+    }
+}
+
+#[test]
+fn test_emitter_emit_emits_switch_in_loop_as_if_chain()
+{
+    let mut ir_tree = IrTree::new();
+    let mut block = IrBlock::new();
+    block.add_local_var_pair(IrLocalVarPair(IrLocalVarModifier::None, Box::new(IrType::Int)));
+    let mut loop_block = IrBlock::new();
+    let mut case_block = IrBlock::new();
+    case_block.add_instr(IrInstr::Break);
+    let mut default_block = IrBlock::new();
+    default_block.add_instr(IrInstr::Assign(Box::new(IrInstrVar::Local(1, Vec::new())), IrOp::Add(IrValue::Object(Box::new(IrObject::Var(IrArgVar::Local(1, Vec::new()), None))), IrValue::Int(1, None))));
+    loop_block.add_instr(IrInstr::Switch(IrOp::Load(IrValue::Object(Box::new(IrObject::Var(IrArgVar::Local(0, Vec::new()), None)))), vec![
+                IrCase::Case(IrCaseValue::Int(-1, None), Box::new(case_block)),
+                IrCase::Default(Box::new(default_block))
+    ]));
+    block.add_instr(IrInstr::Loop(Box::new(loop_block)));
+    block.add_instr(IrInstr::Return(Some(IrOp::Load(IrValue::Object(Box::new(IrObject::Var(IrArgVar::Local(1, Vec::new()), None)))))));
+    let fun = IrFun::Fun(IrFunModifier::None, vec![Box::new(IrType::Int)], Box::new(IrType::Int), Box::new(block), IrPrivateHeapFlag::None, IrLocalHeapFlag::None, IrGlobalHeapFlag::None, IrPanicFlag::None);
+    let var = Rc::new(RefCell::new(IrVar::Fun(Box::new(fun))));
+    ir_tree.add_var(String::from("f"), var.clone());
+    ir_tree.add_def(IrDef::Var(String::from("f"), var));
+    match emit_ir_tree(&ir_tree) {
+        Ok(src) => {
+            println!("{}", src);
+            let expected = "
+int f(int v0)
+{
+    int v1;
+    for(;;) {
+        if(v0 == (-1)) {
+            break;
+        } else {
+            v1 = (v1 + 1);
+        }
+    }
+    return v1;
+}
+";
+            assert!(src.contains(&expected[1..]));
+        },
+        Err(err) => {
+            println!("{}", err);
+            assert!(false);
+        },
+    }
+}
+
+#[test]
+fn test_emitter_emit_emits_caller_for_lambdas()
+{
+    let s = "
+apply(f: (Int) -> Int, x: Int) -> Int = f(x);
+inc(x: Int) -> Int = x + 1;
+kernel k(x: Int, y: Int) -> () =
+    let _ = apply(|z| z + y, x);
+        _ = apply(inc, x);
+    in  ();
+";
+    let s2 = &s[1..];
+    let tree = match do_frontend_phases(s2) {
+        Ok(tree) => tree,
+        Err(errs) => {
+            println!("{}", errs);
+            panic!();
+        },
+    };
+    let ir_tree = match Lowerer::new_with_inline_threshold(0).lower_tree(&tree) {
+        Ok(ir_tree) => ir_tree,
+        Err(err) => {
+            println!("{}", err);
+            panic!();
+        },
+    };
+    match emit_ir_tree(&ir_tree) {
+        Ok(src) => {
+            println!("{}", src);
+            let expected = "
+int _VCL3IntNPRA3IntNP(struct _VSL3IntNPRA3IntNP c0, int c1)
+{
+    switch(c0.f0) {
+        case 1U:
+            {
+                int v2 = c1;
+                int v3;
+                v3 = (v2 + c0.f1.f1);
+                return v3;
+            }
+        case 2U:
+            return _VF3inc(c1);
+    }
+}
+";
+            assert!(src.contains(&expected[1..]));
+            assert!(src.contains("struct _VSL3IntNPRA3IntNP\n{\n    uint f0;\n    struct _VDKPL3IntNPRA3IntNPI1I f1;\n};\n"));
+            assert!(src.contains("_VF5apply(((struct _VSL3IntNPRA3IntNP) { 1U, .f1 = ((struct _VDKPL3IntNPRA3IntNPI1I) { .f1 = v1 }) }), v0);\n"));
+            assert!(src.contains("_VF5apply(((struct _VSL3IntNPRA3IntNP) { 2U }), v0);\n"));
+        },
+        Err(err) => {
+            println!("{}", err);
+            assert!(false);
+        },
+    }
+}
+
+#[test]
+fn test_emitter_emit_emits_data_types_and_constructors()
+{
+    let s = "
+data T<t> = A() | B(t, Float) | C { x: Int, y: t, };
+data U = D { x: Int, y: Float, };
+data V = E() | F() | G();
+f(t: T<Int>, u: U, v: V) -> Int = u.x;
+kernel k(x: Int, y: Float) -> () =
+    let _ = f(A(), D { y: y, x: x, }, F());
+        _ = f(B(x, y), D(x, y), G());
+        _ = f(C { y: x, x: 2, }, D(x, y), E());
+    in  ();
+";
+    let s2 = &s[1..];
+    let tree = match do_frontend_phases(s2) {
+        Ok(tree) => tree,
+        Err(errs) => {
+            println!("{}", errs);
+            panic!();
+        },
+    };
+    let ir_tree = match Lowerer::new_with_inline_threshold(0).lower_tree(&tree) {
+        Ok(ir_tree) => ir_tree,
+        Err(err) => {
+            println!("{}", err);
+            panic!();
+        },
+    };
+    match emit_ir_tree(&ir_tree) {
+        Ok(src) => {
+            println!("{}", src);
+            assert!(src.contains("union _VU1TN3IntNPP\n{\n    struct _VG1BJ1TN3IntNPP f0;\n    struct _VG1CJ1TN3IntNPP f1;\n};\n"));
+            assert!(src.contains("struct _VS1TN3IntNPP\n{\n    uint f0;\n    union _VU1TN3IntNPP f1;\n};\n"));
+            assert!(src.contains("struct _VS1UNP\n{\n    int f0;\n    float f1;\n};\n"));
+            assert!(src.contains("int _VF1f(struct _VS1TN3IntNPP v0, struct _VS1UNP v1, uint v2)\n{\n    return v1.f0;\n}\n"));
+            assert!(src.contains("_VF1f(((struct _VS1TN3IntNPP) { 0U }), ((struct _VS1UNP) { v0, v1 }), 1U);\n"));
+            assert!(src.contains("_VF1f(((struct _VS1TN3IntNPP) { 1U, .f1 = ((union _VU1TN3IntNPP) { .f0 = ((struct _VG1BJ1TN3IntNPP) { v0, v1 }) }) }), ((struct _VS1UNP) { v0, v1 }), 2U);\n"));
+            assert!(src.contains("_VF1f(((struct _VS1TN3IntNPP) { 2U, .f1 = ((union _VU1TN3IntNPP) { .f1 = ((struct _VG1CJ1TN3IntNPP) { 2, v0 }) }) }), ((struct _VS1UNP) { v0, v1 }), 0U);\n"));
+        },
+        Err(err) => {
+            println!("{}", err);
+            assert!(false);
+        },
+    }
+}
+
+#[test]
+fn test_emitter_emit_emits_match_as_switches_and_if_chains()
+{
+    let s = "
+data V = E() | F() | G();
+X: Int = 7;
+f(v: V, n: Int, s: ConstantSlice<Char>) -> Int =
+    let a = v match {
+            E() | G() => 1;
+            _ => 2;
+        };
+        b = n match {
+            1 => 2;
+            X => 3;
+            k => k;
+        };
+        c = s match {
+            \"ab\" => 1;
+            _ => 2;
+        };
+    in  a + b + c;
+kernel k(x: Int) -> () =
+    let _ = f(F(), x, \"abc\");
+    in  ();
+";
+    let s2 = &s[1..];
+    let tree = match do_frontend_phases(s2) {
+        Ok(tree) => tree,
+        Err(errs) => {
+            println!("{}", errs);
+            panic!();
+        },
+    };
+    let ir_tree = match lower_tree(&tree) {
+        Ok(ir_tree) => ir_tree,
+        Err(err) => {
+            println!("{}", err);
+            panic!();
+        },
+    };
+    match emit_ir_tree(&ir_tree) {
+        Ok(src) => {
+            println!("{}", src);
+            let expected = "
+    switch(v0) {
+        case 0U: {
+            v3 = 1;
+            break;
+        }
+        case 2U: {
+            v3 = 1;
+            break;
+        }
+        default: {
+            v3 = 2;
+            break;
+        }
+    }
+";
+            assert!(src.contains(&expected[1..]));
+            let expected2 = "
+    if((v1 == 1)) {
+        v5 = 2;
+    } else {
+        if((v1 == _VV1X)) {
+            v5 = 3;
+        } else {
+            v6 = v1;
+            v5 = v6;
+        }
+    }
+";
+            assert!(src.contains(&expected2[1..]));
+            let expected3 = "
+    v9 = 0;
+    if((v2.f1 == ((size_t) 2UL))) {
+        if((v2.f0[0] == ((char) 97))) {
+            if((v2.f0[1] == ((char) 98))) {
+                v9 = 1;
+            }
+        }
+    }
+    if(v9) {
+        v8 = 1;
+    } else {
+        v8 = 2;
+    }
+";
+            assert!(src.contains(&expected3[1..]));
+        },
+        Err(err) => {
+            println!("{}", err);
+            assert!(false);
+        },
+    }
+}
+
+#[test]
+fn test_emitter_emit_emits_tail_recursive_functions_as_loops()
+{
+    let s = "
+import std_range;
+gcd(x: Int, y: Int) -> Int = if y == 0 then x else gcd(y, x % y);
+kernel k(x: Int, y: Int) -> () =
+    let _ = gcd(x, y);
+        _ = range_fold(0, x, 0, |a, i| a + i);
+    in  ();
+";
+    let s2 = &s[1..];
+    let tree = match do_frontend_phases(s2) {
+        Ok(tree) => tree,
+        Err(errs) => {
+            println!("{}", errs);
+            panic!();
+        },
+    };
+    let ir_tree = match Lowerer::new_with_inline_threshold(0).lower_tree(&tree) {
+        Ok(ir_tree) => ir_tree,
+        Err(err) => {
+            println!("{}", err);
+            panic!();
+        },
+    };
+    match emit_ir_tree(&ir_tree) {
+        Ok(src) => {
+            println!("{}", src);
+            let expected = "
+int _VF3gcd(int v0, int v1)
+{
+    int v2;
+    int v3;
+    int v4;
+    for(;;) {
+        v2 = (v1 == 0);
+        if(v2) {
+            return v0;
+        } else {
+            v3 = (v0 % v1);
+            v4 = v1;
+            v0 = v4;
+            v1 = v3;
+            continue;
+        }
+    }
+}
+";
+            assert!(src.contains(&expected[1..]));
+            let expected2 = "
+    for(;;) {
+        v4 = (v0 < v1);
+        if(v4) {
+            v5 = _VFH9std_range3oneN3IntP();
+            v6 = (v0 + v5);
+            v7 = _VCL3IntNPE3IntNPRA3IntNP(v3, v2, v0);
+            v0 = v6;
+            v2 = v7;
+            continue;
+        } else {
+            return v2;
+        }
+    }
+";
+            assert!(src.contains(&expected2[1..]));
+            assert!(!src.contains("_VFH9std_range10range_foldN3IntE3IntNPP(v6"));
+        },
+        Err(err) => {
+            println!("{}", err);
+            assert!(false);
+        },
+    }
+}
+
+#[test]
+fn test_emitter_emit_with_panic_table_emits_panics_for_bounded_recursive_functions()
+{
+    let s = "
+import opencl;
+recursive(8) fib(n: Int) -> Int = if n < 2 then n else fib(n - 1) + fib(n - 2);
+f(n: Int) -> Int = fib(n) + 1;
+kernel k(xs: UniqGlobalSlice<Int>) -> () =
+    let i = get_global_id(0 as Uint);
+        (x, xs) = xs[i] ->;
+        _ = xs[i] <- f(x);
+    in  ();
+";
+    let s2 = &s[1..];
+    let tree = match do_frontend_phases(s2) {
+        Ok(tree) => tree,
+        Err(errs) => {
+            println!("{}", errs);
+            panic!();
+        },
+    };
+    let ir_tree = match Lowerer::new_with_inline_threshold(0).lower_tree(&tree) {
+        Ok(ir_tree) => ir_tree,
+        Err(err) => {
+            println!("{}", err);
+            panic!();
+        },
+    };
+    match emit_ir_tree_with_panic_table(&ir_tree) {
+        Ok((src, panic_table)) => {
+            println!("{}", src);
+            assert!(src.contains("void _VPKP(global uint *pb, private uint *pf, uint id)\n"));
+            assert!(src.contains("    if(atomic_cmpxchg(pb + 0, 0U, id) == 0U) {\n"));
+            assert!(src.contains("void _VPKC(global uint *pb, private uint *pf, uint id)\n"));
+            assert!(src.contains("int _VF3fib(int v0, global uint *pb, private uint *pf)\n{\n    int pr;\n"));
+            assert!(src.contains("int _VF1f(int v0, global uint *pb, private uint *pf)\n{\n    int pr;\n"));
+            assert!(src.contains("kernel void _VF1k(global int *v0, ulong v1, global uint *pb)\n{\n    uint ps = 0U;\n    private uint *pf = &ps;\n"));
+            assert!(src.contains("_VPKC(pb, pf, 1U);\n        return;\n"));
+            assert!(src.contains("_VPKC(pb, pf, 2U);\n        return pr;\n"));
+            assert!(src.contains("_VPKP(pb, pf, 3U);\n"));
+            assert!(src.contains("_VPKP(pb, pf, 4U);\n"));
+            assert_eq!(4, panic_table.entries().len());
+            match panic_table.entry(1) {
+                Some(PanicEntry::Call(poses)) => {
+                    assert_eq!(1, poses.len());
+                    assert_eq!(7, poses[0].line);
+                    assert_eq!(22, poses[0].column);
+                },
+                _ => assert!(false),
+            }
+            match panic_table.entry(2) {
+                Some(PanicEntry::Call(poses)) => {
+                    assert_eq!(1, poses.len());
+                    assert_eq!(3, poses[0].line);
+                    assert_eq!(20, poses[0].column);
+                },
+                _ => assert!(false),
+            }
+            match panic_table.entry(3) {
+                Some(PanicEntry::Panic(msg, poses)) => {
+                    assert_eq!(&String::from("recursion depth of function fib is exceeded"), msg);
+                    assert_eq!(1, poses.len());
+                    assert_eq!(2, poses[0].line);
+                    assert_eq!(56, poses[0].column);
+                },
+                _ => assert!(false),
+            }
+            match panic_table.entry(4) {
+                Some(PanicEntry::Panic(msg, poses)) => {
+                    assert_eq!(&String::from("recursion depth of function fib is exceeded"), msg);
+                    assert_eq!(1, poses.len());
+                    assert_eq!(2, poses[0].line);
+                    assert_eq!(69, poses[0].column);
+                },
+                _ => assert!(false),
+            }
+        },
+        Err(err) => {
+            println!("{}", err);
+            assert!(false);
+        },
+    }
+}
+
+#[test]
+fn test_emitter_emit_emits_printf_with_opencl_format()
+{
+    let s = "
+s: ConstantSlice<Char> = \"cd\";
+f(x: Int, y: Long, v: Float4, c: Char) -> Int = printf(\"x=%d y=%5d v=%.2v4f c=%c %s %s 100%%\\n\", x, y, v, c, \"ab\", s);
+kernel k(x: Int, y: Long, v: Float4) -> () =
+    let _ = f(x, y, v, 'a');
+    in  ();
+";
+    let s2 = &s[1..];
+    let tree = match do_frontend_phases(s2) {
+        Ok(tree) => tree,
+        Err(errs) => {
+            println!("{}", errs);
+            panic!();
+        },
+    };
+    let ir_tree = match Lowerer::new_with_inline_threshold(0).lower_tree(&tree) {
+        Ok(ir_tree) => ir_tree,
+        Err(err) => {
+            println!("{}", err);
+            panic!();
+        },
+    };
+    match emit_ir_tree(&ir_tree) {
+        Ok(src) => {
+            println!("{}", src);
+            assert!(src.contains("constant struct _VS13ConstantSliceN4CharNPP _VV1s = { \"cd\", ((size_t) 2UL) };\n"));
+            assert!(src.contains("    v4 = ((struct _VS13ConstantSliceN4CharNPP) { \"ab\", ((size_t) 2UL) });\n"));
+            assert!(src.contains("    v5 = printf(\"x=%d y=%5ld v=%.2v4hlf c=%c %s %s 100%%\\n\", v0, v1, v2, v3, v4.f0, _VV1s.f0);\n"));
+        },
+        Err(err) => {
+            println!("{}", err);
+            assert!(false);
+        },
+    }
+}
+
+#[test]
+fn test_emitter_emit_with_panic_table_emits_heap_boxes()
+{
+    let s = "
+import opencl;
+data List = Nil() | Cons(Int, UniqPrivateBox<List>);
+recursive(16) sum(l: List) -> Int =
+    l match {
+        Nil() => 0;
+        Cons(x, b) => x + sum(op_get(b));
+    };
+f(n: Int) -> List = Cons(n, uniq_private_box(Cons(n + 1, uniq_private_box(Nil()))));
+lg(n: Int) -> Int = op_get(uniq_local_box(n)) + op_get(uniq_global_box(n));
+kernel k(xs: UniqGlobalSlice<Int>) -> () =
+    let i = get_global_id(0 as Uint);
+        _ = xs[i] <- (sum(f(1)) + lg(2));
+    in  ();
+";
+    let s2 = &s[1..];
+    let tree = match do_frontend_phases(s2) {
+        Ok(tree) => tree,
+        Err(errs) => {
+            println!("{}", errs);
+            panic!();
+        },
+    };
+    let ir_tree = match Lowerer::new_with_inline_threshold(0).lower_tree(&tree) {
+        Ok(ir_tree) => ir_tree,
+        Err(err) => {
+            println!("{}", err);
+            panic!();
+        },
+    };
+    match emit_ir_tree_with_panic_table(&ir_tree) {
+        Ok((src, panic_table)) => {
+            println!("{}", src);
+            assert!(src.contains("private void *_VHKP(private uint *ph, uint size, uint align)\n"));
+            assert!(src.contains("local void *_VHKL(local uint *lh, uint size, uint align)\n"));
+            assert!(src.contains("global void *_VHKG(global uint *gh, uint size, uint align)\n"));
+            assert!(src.contains("kernel void _VF1k(global int *v0, ulong v1, global uint *gh, global uint *pb)\n"));
+            assert!(src.contains("struct _VS4ListNP _VF1f(int v0, private uint *ph, global uint *pb, private uint *pf)\n"));
+            assert!(src.contains("int _VF2lg(int v0, local uint *lh, global uint *gh, global uint *pb, private uint *pf)\n"));
+            assert!(src.contains("    ulong16 phs[(VSCFL_PRIVATE_HEAP_SIZE + 255U) / 128U];\n"));
+            assert!(src.contains("    local ulong16 lhs[(VSCFL_LOCAL_HEAP_SIZE + 255U) / 128U];\n"));
+            assert!(src.contains("    barrier(CLK_LOCAL_MEM_FENCE);\n"));
+            assert!(src.contains("    v5 = _VF1f(1, ph, pb, pf);\n"));
+            assert!(src.contains("    v7 = _VF2lg(2, lh, gh, pb, pf);\n"));
+            assert!(src.contains("    v2 = _VHKP(ph, (uint) sizeof(struct _VS4ListNP), 8U);\n    if((v2 == 0)) {\n        _VPKP(pb, pf, 5U);\n"));
+            match panic_table.entry(5) {
+                Some(PanicEntry::Panic(msg, poses)) => {
+                    assert_eq!(String::from("out of private heap memory"), *msg);
+                    assert_eq!(1, poses.len());
+                    assert_eq!(8, poses[0].line);
+                    assert_eq!(58, poses[0].column);
+                },
+                _ => assert!(false),
+            }
+        },
+        Err(err) => {
+            println!("{}", err);
+            assert!(false);
+        },
+    }
+}
+
+#[test]
+fn test_emitter_emit_emits_version_check()
+{
+    let s = "
+import opencl;
+kernel k(xs: UniqGlobalSlice<Int>) -> () =
+    let i = get_global_id(0 as Uint);
+        _ = xs[i] <- 1;
+    in  ();
+";
+    let s2 = &s[1..];
+    let tree = match do_frontend_phases(s2) {
+        Ok(tree) => tree,
+        Err(errs) => {
+            println!("{}", errs);
+            panic!();
+        },
+    };
+    let ir_tree = match lower_tree(&tree) {
+        Ok(ir_tree) => ir_tree,
+        Err(err) => {
+            println!("{}", err);
+            panic!();
+        },
+    };
+    let emitter = Emitter::new_with_version(OpenClVersion::V2_0);
+    match emitter.emit(&ir_tree) {
+        Ok(src) => {
+            assert!(src.starts_with("#if !defined(__OPENCL_C_VERSION__) || __OPENCL_C_VERSION__ < 200\n#error \"OpenCL C 2.0 or later is required\"\n#endif\n\n"));
+        },
+        Err(err) => {
+            println!("{}", err);
+            assert!(false);
+        },
+    }
+    match emit_ir_tree(&ir_tree) {
+        Ok(src) => assert!(!src.contains("__OPENCL_C_VERSION__")),
+        Err(err) => {
+            println!("{}", err);
+            assert!(false);
+        },
+    }
+}
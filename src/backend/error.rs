@@ -26,10 +26,28 @@ impl fmt::Display for BackendMessageError
     }
 }
 
+#[derive(Debug)]
+pub struct BackendIrError(pub String, pub String, pub String);
+
+impl error::Error for BackendIrError
+{}
+
+impl fmt::Display for BackendIrError
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        match self {
+            BackendIrError(ident, loc, msg) if loc.is_empty() => write!(f, "{}: {}", ident, msg),
+            BackendIrError(ident, loc, msg) => write!(f, "{}: {}: {}", ident, loc, msg),
+        }
+    }
+}
+
 #[derive(Debug)]
 pub enum BackendError
 {
     Messages(Vec<BackendMessageError>),
+    Ir(Vec<BackendIrError>),
     Internal(String),
 }
 
@@ -45,13 +63,24 @@ impl fmt::Display for BackendError
                 let mut is_first = true;
                 for msg_err in msg_errs {
                     if !is_first {
-                        write!(f, "\n")?;
+                        writeln!(f)?;
                     }
                     write!(f, "{}", msg_err)?;
                     is_first = false;
                 }
                 Ok(())
             },
+            BackendError::Ir(ir_errs) => {
+                let mut is_first = true;
+                for ir_err in ir_errs {
+                    if !is_first {
+                        writeln!(f)?;
+                    }
+                    write!(f, "invalid IR: {}", ir_err)?;
+                    is_first = false;
+                }
+                Ok(())
+            },
             BackendError::Internal(msg) => write!(f, "backend internal error: {}", msg),
         }
     }
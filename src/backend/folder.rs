@@ -0,0 +1,879 @@
+//
+// Copyright (c) 2025 Łukasz Szpakowski
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use crate::backend::ir::*;
+use crate::backend::ir::utils::*;
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+enum ConstElem
+{
+    Int(i128),
+    Float(f64),
+}
+
+#[derive(Clone, PartialEq, Debug)]
+struct ConstValue(IrType, Vec<ConstElem>);
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+enum BinOp
+{
+    Mul,
+    Div,
+    Rem,
+    Add,
+    Sub,
+    Shl,
+    Shr,
+    Eq,
+    Ne,
+    Lt,
+    Ge,
+    Gt,
+    Le,
+    And,
+    Xor,
+    Or,
+}
+
+impl BinOp
+{
+    fn is_cmp(&self) -> bool
+    {
+        matches!(self, BinOp::Eq | BinOp::Ne | BinOp::Lt | BinOp::Ge | BinOp::Gt | BinOp::Le)
+    }
+}
+
+type ConstEnv = BTreeMap<usize, ConstValue>;
+
+fn vector_elem_type(typ: &IrType) -> Option<(IrType, usize)>
+{
+    match typ {
+        IrType::CharN(n) => Some((IrType::Char, *n)),
+        IrType::ShortN(n) => Some((IrType::Short, *n)),
+        IrType::IntN(n) => Some((IrType::Int, *n)),
+        IrType::LongN(n) => Some((IrType::Long, *n)),
+        IrType::UcharN(n) => Some((IrType::Uchar, *n)),
+        IrType::UshortN(n) => Some((IrType::Ushort, *n)),
+        IrType::UintN(n) => Some((IrType::Uint, *n)),
+        IrType::UlongN(n) => Some((IrType::Ulong, *n)),
+        IrType::FloatN(n) => Some((IrType::Float, *n)),
+        IrType::DoubleN(n) => Some((IrType::Double, *n)),
+        _ => None,
+    }
+}
+
+fn cmp_vector_type(elem_type: &IrType, n: usize) -> Option<IrType>
+{
+    match elem_type {
+        IrType::Char | IrType::Uchar => Some(IrType::CharN(n)),
+        IrType::Short | IrType::Ushort => Some(IrType::ShortN(n)),
+        IrType::Int | IrType::Uint | IrType::Float => Some(IrType::IntN(n)),
+        IrType::Long | IrType::Ulong | IrType::Double => Some(IrType::LongN(n)),
+        _ => None,
+    }
+}
+
+fn int_type_bits(typ: &IrType) -> Option<(u32, bool)>
+{
+    match typ {
+        IrType::Char => Some((8, true)),
+        IrType::Short => Some((16, true)),
+        IrType::Int => Some((32, true)),
+        IrType::Long => Some((64, true)),
+        IrType::Uchar => Some((8, false)),
+        IrType::Ushort => Some((16, false)),
+        IrType::Uint => Some((32, false)),
+        IrType::Ulong => Some((64, false)),
+        _ => None,
+    }
+}
+
+fn is_float_type(typ: &IrType) -> bool
+{
+    matches!(typ, IrType::Float | IrType::Double)
+}
+
+fn round_float(typ: &IrType, x: f64) -> f64
+{
+    match typ {
+        IrType::Float => (x as f32) as f64,
+        _ => x,
+    }
+}
+
+fn convert_elem(elem: ConstElem, typ: &IrType) -> Option<ConstElem>
+{
+    match (elem, int_type_bits(typ)) {
+        (ConstElem::Int(a), Some((bits, is_signed))) => Some(ConstElem::Int(wrap_int(a, bits, is_signed))),
+        (ConstElem::Float(x), Some((bits, is_signed))) => {
+            let y = x.trunc();
+            let (min, max) = if is_signed {
+                (-((1u128 << (bits - 1)) as f64), (1u128 << (bits - 1)) as f64)
+            } else {
+                (0.0, (1u128 << bits) as f64)
+            };
+            if !x.is_nan() && y >= min && y < max {
+                Some(ConstElem::Int(y as i128))
+            } else {
+                None
+            }
+        },
+        (ConstElem::Int(a), None) => {
+            match typ {
+                IrType::Float => Some(ConstElem::Float((a as f32) as f64)),
+                IrType::Double => Some(ConstElem::Float(a as f64)),
+                _ => None,
+            }
+        },
+        (ConstElem::Float(x), None) => {
+            if is_float_type(typ) {
+                Some(ConstElem::Float(round_float(typ, x)))
+            } else {
+                None
+            }
+        },
+    }
+}
+
+fn convert_const(value: ConstValue, typ: &IrType) -> Option<ConstValue>
+{
+    if value.0 == *typ {
+        return Some(value);
+    }
+    match (vector_elem_type(&value.0), vector_elem_type(typ)) {
+        (None, None) => Some(ConstValue(typ.clone(), vec![convert_elem(value.1[0], typ)?])),
+        (None, Some((elem_type, n))) => Some(ConstValue(typ.clone(), vec![convert_elem(value.1[0], &elem_type)?; n])),
+        (_, _) => None,
+    }
+}
+
+fn cast_const(value: ConstValue, cast_type: &Option<Box<IrType>>) -> Option<ConstValue>
+{
+    match cast_type {
+        Some(cast_type) => convert_const(value, cast_type),
+        None => Some(value),
+    }
+}
+
+fn int_const(typ: IrType, a: i128, cast_type: &Option<Box<IrType>>) -> Option<ConstValue>
+{ cast_const(ConstValue(typ, vec![ConstElem::Int(a)]), cast_type) }
+
+fn float_const(typ: IrType, x: f64, cast_type: &Option<Box<IrType>>) -> Option<ConstValue>
+{ cast_const(ConstValue(typ, vec![ConstElem::Float(x)]), cast_type) }
+
+fn vector_const<T, F>(values: &[IrValue<T>], typ: &IrType, f: &F) -> Option<ConstValue>
+    where F: Fn(&T) -> Option<ConstValue>
+{
+    let (elem_type, n) = vector_elem_type(typ)?;
+    let mut elems: Vec<ConstElem> = Vec::new();
+    for value in values {
+        let value = value_const(value, f)?;
+        for elem in &value.1 {
+            elems.push(convert_elem(*elem, &elem_type)?);
+        }
+    }
+    if elems.len() == 1 && n > 1 {
+        elems = vec![elems[0]; n];
+    }
+    if elems.len() == n {
+        Some(ConstValue(typ.clone(), elems))
+    } else {
+        None
+    }
+}
+
+fn value_const<T, F>(value: &IrValue<T>, f: &F) -> Option<ConstValue>
+    where F: Fn(&T) -> Option<ConstValue>
+{
+    match value {
+        IrValue::Char(a, cast_type) => int_const(IrType::Char, *a as i128, cast_type),
+        IrValue::Short(a, cast_type) => int_const(IrType::Short, *a as i128, cast_type),
+        IrValue::Int(a, cast_type) => int_const(IrType::Int, *a as i128, cast_type),
+        IrValue::Long(a, cast_type) => int_const(IrType::Long, *a as i128, cast_type),
+        IrValue::Uchar(a, cast_type) => int_const(IrType::Uchar, *a as i128, cast_type),
+        IrValue::Ushort(a, cast_type) => int_const(IrType::Ushort, *a as i128, cast_type),
+        IrValue::Uint(a, cast_type) => int_const(IrType::Uint, *a as i128, cast_type),
+        IrValue::Ulong(a, cast_type) => int_const(IrType::Ulong, *a as i128, cast_type),
+        IrValue::Float(x, cast_type) => float_const(IrType::Float, *x as f64, cast_type),
+        IrValue::Double(x, cast_type) => float_const(IrType::Double, *x, cast_type),
+        IrValue::Object(object) => {
+            match &**object {
+                IrObject::Var(var, cast_type) => cast_const(f(var)?, cast_type),
+                IrObject::Vector(values, typ) => vector_const(values.as_slice(), typ, f),
+                _ => None,
+            }
+        },
+        _ => None,
+    }
+}
+
+fn elem_to_value<T>(elem: ConstElem, typ: &IrType, cast_type: Option<Box<IrType>>) -> Option<IrValue<T>>
+{
+    match (typ, elem) {
+        (IrType::Char, ConstElem::Int(a)) => Some(IrValue::Char(a as i8, cast_type)),
+        (IrType::Short, ConstElem::Int(a)) => Some(IrValue::Short(a as i16, cast_type)),
+        (IrType::Int, ConstElem::Int(a)) => Some(IrValue::Int(a as i32, cast_type)),
+        (IrType::Long, ConstElem::Int(a)) => Some(IrValue::Long(a as i64, cast_type)),
+        (IrType::Uchar, ConstElem::Int(a)) => Some(IrValue::Uchar(a as u8, cast_type)),
+        (IrType::Ushort, ConstElem::Int(a)) => Some(IrValue::Ushort(a as u16, cast_type)),
+        (IrType::Uint, ConstElem::Int(a)) => Some(IrValue::Uint(a as u32, cast_type)),
+        (IrType::Ulong, ConstElem::Int(a)) => Some(IrValue::Ulong(a as u64, cast_type)),
+        (IrType::Float, ConstElem::Float(x)) => Some(IrValue::Float(x as f32, cast_type)),
+        (IrType::Double, ConstElem::Float(x)) => Some(IrValue::Double(x, cast_type)),
+        (_, _) => None,
+    }
+}
+
+fn const_to_value<T>(value: &ConstValue, cast_type: Option<Box<IrType>>) -> Option<IrValue<T>>
+{
+    match vector_elem_type(&value.0) {
+        Some((elem_type, _)) => {
+            if cast_type.is_some() {
+                return None;
+            }
+            let mut values: Vec<IrValue<T>> = Vec::new();
+            for elem in &value.1 {
+                values.push(elem_to_value(*elem, &elem_type, None)?);
+            }
+            Some(IrValue::Object(Box::new(IrObject::Vector(values, Box::new(value.0.clone())))))
+        },
+        None => elem_to_value(value.1[0], &value.0, cast_type),
+    }
+}
+
+fn fold_int_elem(bin_op: BinOp, typ: &IrType, a: i128, b: i128, is_vector: bool) -> Option<ConstElem>
+{
+    let (bits, is_signed) = int_type_bits(typ)?;
+    let arith = |c: i128| {
+        if is_signed {
+            if wrap_int(c, bits, is_signed) == c { Some(ConstElem::Int(c)) } else { None }
+        } else {
+            Some(ConstElem::Int(wrap_int(c, bits, is_signed)))
+        }
+    };
+    let cmp = |is_true: bool| {
+        if !is_true {
+            Some(ConstElem::Int(0))
+        } else if is_vector {
+            Some(ConstElem::Int(-1))
+        } else {
+            Some(ConstElem::Int(1))
+        }
+    };
+    match bin_op {
+        BinOp::Mul => arith(a.wrapping_mul(b)),
+        BinOp::Div => if b != 0 { arith(a / b) } else { None },
+        BinOp::Rem => if b != 0 && arith(a / b).is_some() { arith(a % b) } else { None },
+        BinOp::Add => arith(a + b),
+        BinOp::Sub => arith(a - b),
+        BinOp::Shl => Some(ConstElem::Int(wrap_int(a << (b & ((bits as i128) - 1)), bits, is_signed))),
+        BinOp::Shr => Some(ConstElem::Int(a >> (b & ((bits as i128) - 1)))),
+        BinOp::Eq => cmp(a == b),
+        BinOp::Ne => cmp(a != b),
+        BinOp::Lt => cmp(a < b),
+        BinOp::Ge => cmp(a >= b),
+        BinOp::Gt => cmp(a > b),
+        BinOp::Le => cmp(a <= b),
+        BinOp::And => Some(ConstElem::Int(a & b)),
+        BinOp::Xor => Some(ConstElem::Int(a ^ b)),
+        BinOp::Or => Some(ConstElem::Int(a | b)),
+    }
+}
+
+fn fold_float_elem(bin_op: BinOp, typ: &IrType, x: f64, y: f64, is_vector: bool) -> Option<ConstElem>
+{
+    let cmp = |is_true: bool| {
+        if !is_true {
+            Some(ConstElem::Int(0))
+        } else if is_vector {
+            Some(ConstElem::Int(-1))
+        } else {
+            Some(ConstElem::Int(1))
+        }
+    };
+    match bin_op {
+        BinOp::Mul => Some(ConstElem::Float(round_float(typ, x * y))),
+        BinOp::Div => Some(ConstElem::Float(round_float(typ, x / y))),
+        BinOp::Add => Some(ConstElem::Float(round_float(typ, x + y))),
+        BinOp::Sub => Some(ConstElem::Float(round_float(typ, x - y))),
+        BinOp::Eq => cmp(x == y),
+        BinOp::Ne => cmp(x != y),
+        BinOp::Lt => cmp(x < y),
+        BinOp::Ge => cmp(x >= y),
+        BinOp::Gt => cmp(x > y),
+        BinOp::Le => cmp(x <= y),
+        _ => None,
+    }
+}
+
+fn fold_elem(bin_op: BinOp, typ: &IrType, elem1: ConstElem, elem2: ConstElem, is_vector: bool) -> Option<ConstElem>
+{
+    match (elem1, elem2) {
+        (ConstElem::Int(a), ConstElem::Int(b)) => fold_int_elem(bin_op, typ, a, b, is_vector),
+        (ConstElem::Float(x), ConstElem::Float(y)) => fold_float_elem(bin_op, typ, x, y, is_vector),
+        (_, _) => None,
+    }
+}
+
+fn fold_bin_op(bin_op: BinOp, value1: &ConstValue, value2: &ConstValue) -> Option<ConstValue>
+{
+    if value1.0 != value2.0 {
+        return None;
+    }
+    match vector_elem_type(&value1.0) {
+        Some((elem_type, n)) => {
+            let typ = if bin_op.is_cmp() {
+                cmp_vector_type(&elem_type, n)?
+            } else {
+                value1.0.clone()
+            };
+            let mut elems: Vec<ConstElem> = Vec::new();
+            for (elem1, elem2) in value1.1.iter().zip(value2.1.iter()) {
+                elems.push(fold_elem(bin_op, &elem_type, *elem1, *elem2, true)?);
+            }
+            Some(ConstValue(typ, elems))
+        },
+        None => {
+            let typ = promoted_type(&value1.0);
+            let elem = fold_elem(bin_op, &typ, value1.1[0], value2.1[0], false)?;
+            if bin_op.is_cmp() {
+                Some(ConstValue(IrType::Int, vec![elem]))
+            } else {
+                Some(ConstValue(typ, vec![elem]))
+            }
+        },
+    }
+}
+
+fn fold_unary_elem(is_neg: bool, typ: &IrType, elem: ConstElem) -> Option<ConstElem>
+{
+    match elem {
+        ConstElem::Int(a) => {
+            let (bits, is_signed) = int_type_bits(typ)?;
+            if is_neg {
+                if is_signed && wrap_int(-a, bits, is_signed) != -a {
+                    None
+                } else {
+                    Some(ConstElem::Int(wrap_int(-a, bits, is_signed)))
+                }
+            } else {
+                Some(ConstElem::Int(wrap_int(!a, bits, is_signed)))
+            }
+        },
+        ConstElem::Float(x) => if is_neg { Some(ConstElem::Float(-x)) } else { None },
+    }
+}
+
+fn fold_unary_op(is_neg: bool, value: &ConstValue) -> Option<ConstValue>
+{
+    match vector_elem_type(&value.0) {
+        Some((elem_type, _)) => {
+            let mut elems: Vec<ConstElem> = Vec::new();
+            for elem in &value.1 {
+                elems.push(fold_unary_elem(is_neg, &elem_type, *elem)?);
+            }
+            Some(ConstValue(value.0.clone(), elems))
+        },
+        None => {
+            let typ = promoted_type(&value.0);
+            Some(ConstValue(typ.clone(), vec![fold_unary_elem(is_neg, &typ, value.1[0])?]))
+        },
+    }
+}
+
+fn is_true_const(value: &ConstValue) -> Option<bool>
+{
+    match vector_elem_type(&value.0) {
+        Some(_) => None,
+        None => {
+            match value.1[0] {
+                ConstElem::Int(a) => Some(a != 0),
+                ConstElem::Float(x) => Some(x != 0.0),
+            }
+        },
+    }
+}
+
+fn case_value_const(case_value: &IrCaseValue) -> Option<ConstValue>
+{
+    match case_value {
+        IrCaseValue::Char(a, cast_type) => int_const(IrType::Char, *a as i128, cast_type),
+        IrCaseValue::Short(a, cast_type) => int_const(IrType::Short, *a as i128, cast_type),
+        IrCaseValue::Int(a, cast_type) => int_const(IrType::Int, *a as i128, cast_type),
+        IrCaseValue::Long(a, cast_type) => int_const(IrType::Long, *a as i128, cast_type),
+        IrCaseValue::Uchar(a, cast_type) => int_const(IrType::Uchar, *a as i128, cast_type),
+        IrCaseValue::Ushort(a, cast_type) => int_const(IrType::Ushort, *a as i128, cast_type),
+        IrCaseValue::Uint(a, cast_type) => int_const(IrType::Uint, *a as i128, cast_type),
+        IrCaseValue::Ulong(a, cast_type) => int_const(IrType::Ulong, *a as i128, cast_type),
+        _ => None,
+    }
+}
+
+fn intersect_envs(env1: &ConstEnv, env2: &ConstEnv) -> ConstEnv
+{
+    let mut env = ConstEnv::new();
+    for (idx, value) in env1 {
+        match env2.get(idx) {
+            Some(value2) if value == value2 => {
+                env.insert(*idx, value.clone());
+            },
+            _ => (),
+        }
+    }
+    env
+}
+
+fn collect_ref_local_idxs_from_value(value: &IrValue<IrArgVar>, idxs: &mut BTreeSet<usize>)
+{
+    if let IrValue::Object(object) = value {
+        match &**object {
+            IrObject::Var(IrArgVar::RefLocal(idx, _, _), _) => {
+                idxs.insert(*idx);
+            },
+            IrObject::Vector(values, _) | IrObject::Array(values, _) => {
+                for value in values {
+                    collect_ref_local_idxs_from_value(value, idxs);
+                }
+            },
+            IrObject::Struct(values, field_pairs, _) => {
+                for value in values {
+                    collect_ref_local_idxs_from_value(value, idxs);
+                }
+                for field_pair in field_pairs {
+                    collect_ref_local_idxs_from_value(&field_pair.1, idxs);
+                }
+            },
+            IrObject::Union(_, value, _) => collect_ref_local_idxs_from_value(value, idxs),
+            IrObject::Closure(field_pairs, _) => {
+                for field_pair in field_pairs {
+                    collect_ref_local_idxs_from_value(&field_pair.1, idxs);
+                }
+            },
+            _ => (),
+        }
+    }
+}
+
+fn collect_ref_local_idxs_from_op(op: &IrOp, idxs: &mut BTreeSet<usize>)
+{
+    match op {
+        IrOp::Load(value) | IrOp::Neg(value) | IrOp::Not(value) => collect_ref_local_idxs_from_value(value, idxs),
+        IrOp::Mul(value1, value2) | IrOp::Div(value1, value2) | IrOp::Rem(value1, value2) |
+        IrOp::Add(value1, value2) | IrOp::Sub(value1, value2) | IrOp::Shl(value1, value2) |
+        IrOp::Shr(value1, value2) | IrOp::Eq(value1, value2) | IrOp::Ne(value1, value2) |
+        IrOp::Lt(value1, value2) | IrOp::Ge(value1, value2) | IrOp::Gt(value1, value2) |
+        IrOp::Le(value1, value2) | IrOp::And(value1, value2) | IrOp::Xor(value1, value2) |
+        IrOp::Or(value1, value2) => {
+            collect_ref_local_idxs_from_value(value1, idxs);
+            collect_ref_local_idxs_from_value(value2, idxs);
+        },
+        IrOp::CallBuiltinFun(_, _, values) | IrOp::CallFun(_, values, _, _) | IrOp::CallFunWithoutPanic(_, values, _) => {
+            for value in values {
+                collect_ref_local_idxs_from_value(value, idxs);
+            }
+        },
+    }
+}
+
+fn collect_ref_local_idxs(block: &IrBlock, idxs: &mut BTreeSet<usize>)
+{
+    for instr in block.instrs() {
+        match instr {
+            IrInstr::Op(op) | IrInstr::Assign(_, op) | IrInstr::Return(Some(op)) => collect_ref_local_idxs_from_op(op, idxs),
+            IrInstr::Block(block) | IrInstr::Loop(block) => collect_ref_local_idxs(block, idxs),
+            IrInstr::If(op, block1, block2) => {
+                collect_ref_local_idxs_from_op(op, idxs);
+                collect_ref_local_idxs(block1, idxs);
+                collect_ref_local_idxs(block2, idxs);
+            },
+            IrInstr::Switch(op, cases) => {
+                collect_ref_local_idxs_from_op(op, idxs);
+                for case in cases {
+                    match case {
+                        IrCase::Case(_, block) | IrCase::Default(block) => collect_ref_local_idxs(block, idxs),
+                    }
+                }
+            },
+            _ => (),
+        }
+    }
+}
+
+fn collect_assigned_local_idxs(block: &IrBlock, idxs: &mut BTreeSet<usize>)
+{
+    for instr in block.instrs() {
+        match instr {
+            IrInstr::Assign(var, _) => {
+                if let IrInstrVar::Local(idx, _) = &**var {
+                    idxs.insert(*idx);
+                }
+            },
+            IrInstr::Block(block) | IrInstr::Loop(block) => collect_assigned_local_idxs(block, idxs),
+            IrInstr::If(_, block1, block2) => {
+                collect_assigned_local_idxs(block1, idxs);
+                collect_assigned_local_idxs(block2, idxs);
+            },
+            IrInstr::Switch(_, cases) => {
+                for case in cases {
+                    match case {
+                        IrCase::Case(_, block) | IrCase::Default(block) => collect_assigned_local_idxs(block, idxs),
+                    }
+                }
+            },
+            _ => (),
+        }
+    }
+}
+
+struct FolderContext<'a>
+{
+    tree: &'a IrTree,
+    local_var_types: Vec<Option<Box<IrType>>>,
+    ref_local_idxs: BTreeSet<usize>,
+}
+
+impl<'a> FolderContext<'a>
+{
+    fn new(tree: &'a IrTree, local_var_types: Vec<Option<Box<IrType>>>, block: &IrBlock) -> Self
+    {
+        let mut ref_local_idxs: BTreeSet<usize> = BTreeSet::new();
+        collect_ref_local_idxs(block, &mut ref_local_idxs);
+        FolderContext {
+            tree,
+            local_var_types,
+            ref_local_idxs,
+        }
+    }
+
+    fn global_const(&self, ident: &String) -> Option<ConstValue>
+    {
+        match self.tree.var(ident) {
+            Some(var) => {
+                match &*var.borrow() {
+                    IrVar::Const(typ, value) => convert_const(value_const(value, &|_| None)?, typ),
+                    _ => None,
+                }
+            },
+            None => None,
+        }
+    }
+
+    fn var_const(&self, var: &IrArgVar, env: &ConstEnv) -> Option<ConstValue>
+    {
+        match var {
+            IrArgVar::Global(ident, ops) if ops.is_empty() => self.global_const(ident),
+            IrArgVar::Local(idx, ops) if ops.is_empty() => env.get(idx).cloned(),
+            _ => None,
+        }
+    }
+
+    fn value_const(&self, value: &IrValue<IrArgVar>, env: &ConstEnv) -> Option<ConstValue>
+    { value_const(value, &|var: &IrArgVar| self.var_const(var, env)) }
+
+    fn substitute_value(&self, value: &IrValue<IrArgVar>, env: &ConstEnv) -> IrValue<IrArgVar>
+    {
+        match value {
+            IrValue::Object(object) => {
+                match &**object {
+                    IrObject::Var(IrArgVar::Local(idx, ops), cast_type) if ops.is_empty() => {
+                        let new_value = match (env.get(idx), cast_type) {
+                            (Some(const_value), None) => const_to_value(const_value, None),
+                            (Some(const_value), Some(cast_type)) => {
+                                match convert_const(const_value.clone(), cast_type) {
+                                    Some(const_value2) => const_to_value(&const_value2, None),
+                                    None => const_to_value(const_value, Some(cast_type.clone())),
+                                }
+                            },
+                            (None, _) => None,
+                        };
+                        new_value.unwrap_or(value.clone())
+                    },
+                    IrObject::Vector(values, typ) => IrValue::Object(Box::new(IrObject::Vector(self.substitute_values(values.as_slice(), env), typ.clone()))),
+                    IrObject::Array(values, typ) => IrValue::Object(Box::new(IrObject::Array(self.substitute_values(values.as_slice(), env), typ.clone()))),
+                    IrObject::Struct(values, field_pairs, typ) => IrValue::Object(Box::new(IrObject::Struct(self.substitute_values(values.as_slice(), env), self.substitute_field_pairs(field_pairs.as_slice(), env), typ.clone()))),
+                    IrObject::Union(idx, value, typ) => IrValue::Object(Box::new(IrObject::Union(*idx, self.substitute_value(value, env), typ.clone()))),
+                    IrObject::Closure(field_pairs, typ) => IrValue::Object(Box::new(IrObject::Closure(self.substitute_field_pairs(field_pairs.as_slice(), env), typ.clone()))),
+                    _ => value.clone(),
+                }
+            },
+            _ => value.clone(),
+        }
+    }
+
+    fn substitute_values(&self, values: &[IrValue<IrArgVar>], env: &ConstEnv) -> Vec<IrValue<IrArgVar>>
+    { values.iter().map(|v| self.substitute_value(v, env)).collect() }
+
+    fn substitute_field_pairs(&self, field_pairs: &[IrFieldPair<IrArgVar>], env: &ConstEnv) -> Vec<IrFieldPair<IrArgVar>>
+    { field_pairs.iter().map(|p| IrFieldPair(p.0, self.substitute_value(&p.1, env))).collect() }
+
+    fn fold_bin_op(&self, bin_op: BinOp, value1: &IrValue<IrArgVar>, value2: &IrValue<IrArgVar>, env: &ConstEnv) -> Option<ConstValue>
+    { fold_bin_op(bin_op, &self.value_const(value1, env)?, &self.value_const(value2, env)?) }
+
+    fn op_const(&self, op: &IrOp, env: &ConstEnv) -> Option<ConstValue>
+    {
+        match op {
+            IrOp::Load(value) => self.value_const(value, env),
+            IrOp::Neg(value) => fold_unary_op(true, &self.value_const(value, env)?),
+            IrOp::Not(value) => fold_unary_op(false, &self.value_const(value, env)?),
+            IrOp::Mul(value1, value2) => self.fold_bin_op(BinOp::Mul, value1, value2, env),
+            IrOp::Div(value1, value2) => self.fold_bin_op(BinOp::Div, value1, value2, env),
+            IrOp::Rem(value1, value2) => self.fold_bin_op(BinOp::Rem, value1, value2, env),
+            IrOp::Add(value1, value2) => self.fold_bin_op(BinOp::Add, value1, value2, env),
+            IrOp::Sub(value1, value2) => self.fold_bin_op(BinOp::Sub, value1, value2, env),
+            IrOp::Shl(value1, value2) => self.fold_bin_op(BinOp::Shl, value1, value2, env),
+            IrOp::Shr(value1, value2) => self.fold_bin_op(BinOp::Shr, value1, value2, env),
+            IrOp::Eq(value1, value2) => self.fold_bin_op(BinOp::Eq, value1, value2, env),
+            IrOp::Ne(value1, value2) => self.fold_bin_op(BinOp::Ne, value1, value2, env),
+            IrOp::Lt(value1, value2) => self.fold_bin_op(BinOp::Lt, value1, value2, env),
+            IrOp::Ge(value1, value2) => self.fold_bin_op(BinOp::Ge, value1, value2, env),
+            IrOp::Gt(value1, value2) => self.fold_bin_op(BinOp::Gt, value1, value2, env),
+            IrOp::Le(value1, value2) => self.fold_bin_op(BinOp::Le, value1, value2, env),
+            IrOp::And(value1, value2) => self.fold_bin_op(BinOp::And, value1, value2, env),
+            IrOp::Xor(value1, value2) => self.fold_bin_op(BinOp::Xor, value1, value2, env),
+            IrOp::Or(value1, value2) => self.fold_bin_op(BinOp::Or, value1, value2, env),
+            _ => None,
+        }
+    }
+
+    fn substitute_op(&self, op: &IrOp, env: &ConstEnv) -> IrOp
+    {
+        match op {
+            IrOp::Load(value) => IrOp::Load(self.substitute_value(value, env)),
+            IrOp::Neg(value) => IrOp::Neg(self.substitute_value(value, env)),
+            IrOp::Not(value) => IrOp::Not(self.substitute_value(value, env)),
+            IrOp::Mul(value1, value2) => IrOp::Mul(self.substitute_value(value1, env), self.substitute_value(value2, env)),
+            IrOp::Div(value1, value2) => IrOp::Div(self.substitute_value(value1, env), self.substitute_value(value2, env)),
+            IrOp::Rem(value1, value2) => IrOp::Rem(self.substitute_value(value1, env), self.substitute_value(value2, env)),
+            IrOp::Add(value1, value2) => IrOp::Add(self.substitute_value(value1, env), self.substitute_value(value2, env)),
+            IrOp::Sub(value1, value2) => IrOp::Sub(self.substitute_value(value1, env), self.substitute_value(value2, env)),
+            IrOp::Shl(value1, value2) => IrOp::Shl(self.substitute_value(value1, env), self.substitute_value(value2, env)),
+            IrOp::Shr(value1, value2) => IrOp::Shr(self.substitute_value(value1, env), self.substitute_value(value2, env)),
+            IrOp::Eq(value1, value2) => IrOp::Eq(self.substitute_value(value1, env), self.substitute_value(value2, env)),
+            IrOp::Ne(value1, value2) => IrOp::Ne(self.substitute_value(value1, env), self.substitute_value(value2, env)),
+            IrOp::Lt(value1, value2) => IrOp::Lt(self.substitute_value(value1, env), self.substitute_value(value2, env)),
+            IrOp::Ge(value1, value2) => IrOp::Ge(self.substitute_value(value1, env), self.substitute_value(value2, env)),
+            IrOp::Gt(value1, value2) => IrOp::Gt(self.substitute_value(value1, env), self.substitute_value(value2, env)),
+            IrOp::Le(value1, value2) => IrOp::Le(self.substitute_value(value1, env), self.substitute_value(value2, env)),
+            IrOp::And(value1, value2) => IrOp::And(self.substitute_value(value1, env), self.substitute_value(value2, env)),
+            IrOp::Xor(value1, value2) => IrOp::Xor(self.substitute_value(value1, env), self.substitute_value(value2, env)),
+            IrOp::Or(value1, value2) => IrOp::Or(self.substitute_value(value1, env), self.substitute_value(value2, env)),
+            IrOp::CallBuiltinFun(ident, typ, values) => IrOp::CallBuiltinFun(ident.clone(), typ.clone(), self.substitute_values(values.as_slice(), env)),
+            IrOp::CallFun(ident, values, pos, poses) => IrOp::CallFun(ident.clone(), self.substitute_values(values.as_slice(), env), pos.clone(), poses.clone()),
+            IrOp::CallFunWithoutPanic(ident, values, pos) => IrOp::CallFunWithoutPanic(ident.clone(), self.substitute_values(values.as_slice(), env), pos.clone()),
+        }
+    }
+
+    fn fold_op(&self, op: &IrOp, env: &ConstEnv) -> (IrOp, Option<ConstValue>)
+    {
+        let new_op = self.substitute_op(op, env);
+        let const_value = self.op_const(&new_op, env);
+        match (&new_op, &const_value) {
+            (IrOp::Load(_), _) => (new_op, const_value),
+            (_, Some(const_value2)) => {
+                match const_to_value(const_value2, None) {
+                    Some(value) => (IrOp::Load(value), const_value),
+                    None => (new_op, const_value),
+                }
+            },
+            (_, None) => (new_op, const_value),
+        }
+    }
+
+    fn assign_local_var(&self, idx: usize, const_value: Option<ConstValue>, env: &mut ConstEnv)
+    {
+        let const_value = match (const_value, self.local_var_types.get(idx)) {
+            (Some(const_value), Some(Some(typ))) if !self.ref_local_idxs.contains(&idx) => convert_const(const_value, typ),
+            (_, _) => None,
+        };
+        match const_value {
+            Some(const_value) => {
+                env.insert(idx, const_value);
+            },
+            None => {
+                env.remove(&idx);
+            },
+        }
+    }
+
+    fn fold_block(&mut self, block: &IrBlock, env: &mut ConstEnv) -> IrBlock
+    {
+        let old_len = self.local_var_types.len();
+        let mut new_block = IrBlock::new();
+        for local_var_pair in block.local_var_pairs() {
+            self.local_var_types.push(Some(local_var_pair.1.clone()));
+            new_block.add_local_var_pair(local_var_pair.clone());
+        }
+        for instr in block.instrs() {
+            self.fold_instr(instr, env, &mut new_block);
+        }
+        self.local_var_types.truncate(old_len);
+        env.retain(|idx, _| *idx < old_len);
+        new_block
+    }
+
+    fn fold_instr(&mut self, instr: &IrInstr, env: &mut ConstEnv, new_block: &mut IrBlock)
+    {
+        match instr {
+            IrInstr::Op(op) => {
+                let (new_op, _) = self.fold_op(op, env);
+                new_block.add_instr(IrInstr::Op(new_op));
+            },
+            IrInstr::Assign(var, op) => {
+                let (new_op, const_value) = self.fold_op(op, env);
+                match &**var {
+                    IrInstrVar::Local(idx, ops) if ops.is_empty() => self.assign_local_var(*idx, const_value, env),
+                    IrInstrVar::Local(idx, _) => {
+                        env.remove(idx);
+                    },
+                    _ => (),
+                }
+                new_block.add_instr(IrInstr::Assign(var.clone(), new_op));
+            },
+            IrInstr::Return(Some(op)) => {
+                let (new_op, _) = self.fold_op(op, env);
+                new_block.add_instr(IrInstr::Return(Some(new_op)));
+            },
+            IrInstr::Block(block) => {
+                let block2 = self.fold_block(block, env);
+                new_block.add_instr(IrInstr::Block(Box::new(block2)));
+            },
+            IrInstr::If(op, block1, block2) => {
+                let (new_op, const_value) = self.fold_op(op, env);
+                match const_value.as_ref().and_then(is_true_const) {
+                    Some(true) => new_block.add_block(self.fold_block(block1, env)),
+                    Some(false) => new_block.add_block(self.fold_block(block2, env)),
+                    None => {
+                        let mut env1 = env.clone();
+                        let mut env2 = env.clone();
+                        let new_block1 = self.fold_block(block1, &mut env1);
+                        let new_block2 = self.fold_block(block2, &mut env2);
+                        *env = intersect_envs(&env1, &env2);
+                        new_block.add_instr(IrInstr::If(new_op, Box::new(new_block1), Box::new(new_block2)));
+                    },
+                }
+            },
+            IrInstr::Switch(op, cases) => {
+                let (new_op, const_value) = self.fold_op(op, env);
+                match const_value.and_then(|c| self.selected_case(&c, cases.as_slice())) {
+                    Some(Some(block)) => new_block.add_block(self.fold_block(block, env)),
+                    Some(None) => (),
+                    None => {
+                        let mut new_env: Option<ConstEnv> = None;
+                        let mut has_default = false;
+                        let mut new_cases: Vec<IrCase> = Vec::new();
+                        for case in cases {
+                            let mut case_env = env.clone();
+                            match case {
+                                IrCase::Case(value, block) => new_cases.push(IrCase::Case(value.clone(), Box::new(self.fold_block(block, &mut case_env)))),
+                                IrCase::Default(block) => {
+                                    has_default = true;
+                                    new_cases.push(IrCase::Default(Box::new(self.fold_block(block, &mut case_env))));
+                                },
+                            }
+                            new_env = match new_env {
+                                Some(new_env) => Some(intersect_envs(&new_env, &case_env)),
+                                None => Some(case_env),
+                            };
+                        }
+                        *env = match new_env {
+                            Some(new_env) if has_default => new_env,
+                            Some(new_env) => intersect_envs(&new_env, env),
+                            None => env.clone(),
+                        };
+                        new_block.add_instr(IrInstr::Switch(new_op, new_cases));
+                    },
+                }
+            },
+            IrInstr::Loop(block) => {
+                let mut idxs: BTreeSet<usize> = BTreeSet::new();
+                collect_assigned_local_idxs(block, &mut idxs);
+                env.retain(|idx, _| !idxs.contains(idx));
+                let mut loop_env = env.clone();
+                let block2 = self.fold_block(block, &mut loop_env);
+                new_block.add_instr(IrInstr::Loop(Box::new(block2)));
+            },
+            _ => new_block.add_instr(instr.clone()),
+        }
+    }
+
+    fn selected_case<'b>(&self, value: &ConstValue, cases: &'b [IrCase]) -> Option<Option<&'b IrBlock>>
+    {
+        if vector_elem_type(&value.0).is_some() || is_float_type(&value.0) {
+            return None;
+        }
+        let typ = promoted_type(&value.0);
+        let value = convert_const(value.clone(), &typ)?;
+        let mut default_block: Option<&'b IrBlock> = None;
+        for case in cases {
+            match case {
+                IrCase::Case(case_value, block) => {
+                    let case_value = convert_const(case_value_const(case_value)?, &typ)?;
+                    if case_value == value {
+                        return Some(Some(block));
+                    }
+                },
+                IrCase::Default(block) => default_block = Some(block),
+            }
+        }
+        Some(default_block)
+    }
+}
+
+fn fold_fun(fun: &IrFun, tree: &IrTree) -> IrFun
+{
+    match fun {
+        IrFun::Fun(modifier, arg_types, ret_type, block, private_heap_flag, local_heap_flag, global_heap_flag, panic_flag) => {
+            let local_var_types: Vec<Option<Box<IrType>>> = arg_types.iter().map(|t| Some(t.clone())).collect();
+            let mut ctx = FolderContext::new(tree, local_var_types, block);
+            let new_block = ctx.fold_block(block, &mut ConstEnv::new());
+            IrFun::Fun(*modifier, arg_types.clone(), ret_type.clone(), Box::new(new_block), *private_heap_flag, *local_heap_flag, *global_heap_flag, *panic_flag)
+        },
+        IrFun::Caller(fun_type, arg_types, ret_type, caller_funs, private_heap_flag, local_heap_flag, global_heap_flag, panic_flag) => {
+            let mut new_caller_funs = caller_funs.clone();
+            for (idx, caller_fun) in caller_funs.funs() {
+                let (local_var_types, block) = match caller_fun {
+                    IrCallerFun::Fun(_) => continue,
+                    IrCallerFun::InlineFun(_, block) => {
+                        let local_var_types: Vec<Option<Box<IrType>>> = arg_types.iter().map(|t| Some(t.clone())).collect();
+                        (local_var_types, block)
+                    },
+                    IrCallerFun::Lambda(_, _, _, start_idx, block) => {
+                        let mut local_var_types: Vec<Option<Box<IrType>>> = vec![None; *start_idx];
+                        local_var_types.extend(arg_types.iter().map(|t| Some(t.clone())));
+                        (local_var_types, block)
+                    },
+                };
+                let mut ctx = FolderContext::new(tree, local_var_types, block);
+                let new_block = ctx.fold_block(block, &mut ConstEnv::new());
+                if let Some(block2) = new_caller_funs.fun_block_mut(*idx) {
+                    **block2 = new_block;
+                }
+            }
+            IrFun::Caller(fun_type.clone(), arg_types.clone(), ret_type.clone(), new_caller_funs, *private_heap_flag, *local_heap_flag, *global_heap_flag, *panic_flag)
+        },
+    }
+}
+
+pub fn fold_ir_tree(tree: &IrTree)
+{
+    for def in tree.defs() {
+        if let IrDef::Var(_, var) = &**def {
+            let new_fun = match &*var.borrow() {
+                IrVar::Fun(fun) => Some(fold_fun(fun, tree)),
+                _ => None,
+            };
+            if let Some(new_fun) = new_fun {
+                *var.borrow_mut() = IrVar::Fun(Box::new(new_fun));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
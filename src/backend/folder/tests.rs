@@ -0,0 +1,300 @@
+//
+// Copyright (c) 2025 Łukasz Szpakowski
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+use crate::frontend::do_frontend_phases;
+use crate::backend::ir::parser::*;
+use crate::backend::ir::printer::*;
+use crate::backend::lowering::*;
+use super::*;
+
+fn fold_ir_string(s: &str) -> String
+{
+    let ir_tree = match parse_ir_tree(s) {
+        Ok(ir_tree) => ir_tree,
+        Err(err) => {
+            println!("{}", err);
+            panic!();
+        },
+    };
+    fold_ir_tree(&ir_tree);
+    ir_tree_to_string(&ir_tree)
+}
+
+#[test]
+fn test_folder_fold_ir_tree_folds_scalar_operations()
+{
+    let s = "
+const @c: long = int 2 as long;
+
+fun @f(int) -> int
+{
+    var int;
+    var uint;
+    var char;
+    var float;
+    var double;
+    v1 = add int 1, int 2;
+    v1 = mul @c, long 3;
+    v1 = shl int 1, int 33;
+    v1 = shr int -16, int 2;
+    v1 = add int 2147483647, int 1;
+    v1 = div int 1, int 0;
+    v1 = rem int 7, int -3;
+    v2 = sub uint 0, uint 1;
+    v2 = shr uint 4294967295, uint 31;
+    v3 = add char 127, char 1;
+    v1 = add char 127, char 1;
+    v1 = lt int 1 as uint, int -1 as uint;
+    v1 = not uchar 1;
+    v1 = neg int 2;
+    v4 = div float 1, float 3;
+    v5 = div double 1, double 3;
+    v1 = ge float 1.5, float 1;
+    v1 = load double 2.9 as int;
+    v1 = load double -1e30 as int;
+    v1 = add v0, int 1;
+    return load v1;
+}
+";
+    let t = "
+const @c: long = int 2 as long;
+
+fun @f(int) -> int
+{
+    var int;
+    var uint;
+    var char;
+    var float;
+    var double;
+    v1 = load int 3;
+    v1 = load long 6;
+    v1 = load int 2;
+    v1 = load int -4;
+    v1 = add int 2147483647, int 1;
+    v1 = div int 1, int 0;
+    v1 = load int 1;
+    v2 = load uint 4294967295;
+    v2 = load uint 1;
+    v3 = load int 128;
+    v1 = load int 128;
+    v1 = load int 1;
+    v1 = load int -2;
+    v1 = load int -2;
+    v4 = load float 0.33333334;
+    v5 = load double 0.3333333333333333;
+    v1 = load int 1;
+    v1 = load double 2.9 as int;
+    v1 = load double -1000000000000000000000000000000 as int;
+    v1 = add v0, int 1;
+    return load v1;
+}
+";
+    let s2 = &s[1..];
+    let t2 = &t[1..];
+    assert_eq!(t2, fold_ir_string(s2));
+}
+
+#[test]
+fn test_folder_fold_ir_tree_propagates_constants_and_folds_conditions()
+{
+    let s = "
+fun @f(int) -> int
+{
+    var int;
+    var int;
+    var int;
+    var int;
+    v1 = load int 4;
+    v2 = add v1, int 1;
+    if gt v2, int 3 {
+        v3 = mul v2, v1;
+    } else {
+        v3 = load int 0;
+    }
+    switch load v3 {
+        case int 1 {
+            return load int 1;
+        }
+        case int 20 {
+            v3 = add v3, int 1;
+        }
+        default {
+            return load int 0;
+        }
+    }
+    if lt v0, v3 {
+        v1 = load int 5;
+        v2 = load v1;
+    } else {
+        v1 = load int 6;
+    }
+    v3 = call_builtin f(v1, v2, &<*private int>v4);
+    loop {
+        v3 = add v3, int 1;
+        if eq v1, int 5 {
+            break;
+        } else {
+        }
+    }
+    return add v3, v2;
+}
+";
+    let t = "
+fun @f(int) -> int
+{
+    var int;
+    var int;
+    var int;
+    var int;
+    v1 = load int 4;
+    v2 = load int 5;
+    v3 = load int 20;
+    v3 = load int 21;
+    if lt v0, int 21 {
+        v1 = load int 5;
+        v2 = load int 5;
+    } else {
+        v1 = load int 6;
+    }
+    v3 = call_builtin f(v1, int 5, &<*private int>v4);
+    loop {
+        v3 = add v3, int 1;
+        if eq v1, int 5 {
+            break;
+        } else {
+        }
+    }
+    return add v3, int 5;
+}
+";
+    let s2 = &s[1..];
+    let t2 = &t[1..];
+    assert_eq!(t2, fold_ir_string(s2));
+}
+
+#[test]
+fn test_folder_fold_ir_tree_folds_vector_operations()
+{
+    let s = "
+fun @f() -> int4
+{
+    var int4;
+    var uchar4;
+    var int2;
+    v0 = add vector int4(int 1, int 2, int 3, int 4), vector int4(int 10);
+    v1 = add vector uchar4(uchar 255, uchar 1, uchar 2, uchar 3), vector uchar4(uchar 1);
+    v2 = lt vector float2(float 1, float 2), vector float2(float 1.5);
+    v2 = shl vector int2(int 1, int 1), vector int2(int 33, int 2);
+    v0 = mul v0, vector int4(int 2, int 2, int 2, int 2);
+    v0 = add vector int4(int 2147483647, int 0, int 0, int 0), vector int4(int 1);
+    return load v0;
+}
+";
+    let t = "
+fun @f() -> int4
+{
+    var int4;
+    var uchar4;
+    var int2;
+    v0 = load vector int4(int 11, int 12, int 13, int 14);
+    v1 = load vector uchar4(uchar 0, uchar 2, uchar 3, uchar 4);
+    v2 = load vector int2(int -1, int 0);
+    v2 = load vector int2(int 2, int 4);
+    v0 = load vector int4(int 22, int 24, int 26, int 28);
+    v0 = add vector int4(int 2147483647, int 0, int 0, int 0), vector int4(int 1);
+    return load v0;
+}
+";
+    let s2 = &s[1..];
+    let t2 = &t[1..];
+    assert_eq!(t2, fold_ir_string(s2));
+}
+
+#[test]
+fn test_folder_fold_ir_tree_folds_lowered_tree()
+{
+    let s = "
+import opencl;
+f(x: Int) -> Int = if 1 + 2 * 3 > 5 then x * (2 + 2) else 0;
+kernel k(xs: UniqGlobalSlice<Int>) -> () =
+    let i = get_global_id(0 as Uint);
+        _ = xs[i] <- f(3);
+    in  ();
+";
+    let s2 = &s[1..];
+    let tree = match do_frontend_phases(s2) {
+        Ok(tree) => tree,
+        Err(errs) => {
+            println!("{}", errs);
+            panic!();
+        },
+    };
+    let ir_tree = match Lowerer::new_with_inline_threshold(0).lower_tree(&tree) {
+        Ok(ir_tree) => ir_tree,
+        Err(err) => {
+            println!("{}", err);
+            panic!();
+        },
+    };
+    let t = "
+fun @_VF1f(int) -> int
+{
+    var int;
+    var int;
+    var int;
+    var int;
+    var int;
+    var int;
+    v1 = load int 6;
+    v2 = load int 7;
+    v3 = load int 1;
+    v5 = load int 4;
+    v6 = mul v0, int 4;
+    v4 = load v6;
+    return load v4;
+}
+";
+    let t2 = &t[1..];
+    match ir_tree.defs().iter().find(|d| match &***d { IrDef::Var(ident, _) => ident == "_VF1f", _ => false, }) {
+        Some(def) => assert_eq!(t2, ir_def_to_string(def)),
+        None => assert!(false),
+    }
+}
+
+#[test]
+fn test_folder_fold_ir_tree_folds_multiplications_at_type_limits()
+{
+    let s = "
+fun @f() -> ulong
+{
+    var ulong;
+    var long;
+    v0 = mul ulong 18446744073709551615, ulong 18446744073709551615;
+    v0 = mul ulong 18446744073709551615, ulong 2;
+    v1 = mul long 9223372036854775807, long -1;
+    v1 = mul long -9223372036854775808, long -9223372036854775808;
+    v1 = mul long -9223372036854775808, long -1;
+    return load v0;
+}
+";
+    let t = "
+fun @f() -> ulong
+{
+    var ulong;
+    var long;
+    v0 = load ulong 1;
+    v0 = load ulong 18446744073709551614;
+    v1 = load long -9223372036854775807;
+    v1 = mul long -9223372036854775808, long -9223372036854775808;
+    v1 = mul long -9223372036854775808, long -1;
+    return load ulong 18446744073709551614;
+}
+";
+    let s2 = &s[1..];
+    let t2 = &t[1..];
+    assert_eq!(t2, fold_ir_string(s2));
+}
@@ -0,0 +1,33 @@
+//
+// Copyright (c) 2025 Łukasz Szpakowski
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+pub const HEAP_OFFSET_INDEX: usize = 0;
+pub const HEAP_CAPACITY_INDEX: usize = 1;
+pub const HEAP_HEADER_SIZE: usize = 128;
+pub const DEFAULT_PRIVATE_HEAP_SIZE: usize = 1024;
+pub const DEFAULT_LOCAL_HEAP_SIZE: usize = 4096;
+pub const PRIVATE_HEAP_SIZE_MACRO: &str = "VSCFL_PRIVATE_HEAP_SIZE";
+pub const LOCAL_HEAP_SIZE_MACRO: &str = "VSCFL_LOCAL_HEAP_SIZE";
+
+pub fn new_global_heap_buffer(capacity: usize) -> Vec<u32>
+{
+    let mut buf = vec![0u32; (HEAP_HEADER_SIZE + capacity).div_ceil(4)];
+    buf[HEAP_CAPACITY_INDEX] = capacity as u32;
+    buf
+}
+
+pub fn global_heap_used_size(buf: &[u32]) -> Option<usize>
+{
+    if buf.len() > HEAP_CAPACITY_INDEX {
+        Some((buf[HEAP_OFFSET_INDEX] as usize).min(buf[HEAP_CAPACITY_INDEX] as usize))
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests;
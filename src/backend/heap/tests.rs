@@ -0,0 +1,29 @@
+//
+// Copyright (c) 2025 Łukasz Szpakowski
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+use super::*;
+
+#[test]
+fn test_new_global_heap_buffer_creates_buffer_with_header()
+{
+    let buf = new_global_heap_buffer(1000);
+    assert_eq!((HEAP_HEADER_SIZE + 1000) / 4, buf.len());
+    assert_eq!(0, buf[HEAP_OFFSET_INDEX]);
+    assert_eq!(1000, buf[HEAP_CAPACITY_INDEX]);
+}
+
+#[test]
+fn test_global_heap_used_size_returns_used_size()
+{
+    let mut buf = new_global_heap_buffer(64);
+    assert_eq!(Some(0), global_heap_used_size(&buf));
+    buf[HEAP_OFFSET_INDEX] = 40;
+    assert_eq!(Some(40), global_heap_used_size(&buf));
+    buf[HEAP_OFFSET_INDEX] = 100;
+    assert_eq!(Some(64), global_heap_used_size(&buf));
+    assert_eq!(None, global_heap_used_size(&[]));
+}
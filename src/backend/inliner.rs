@@ -0,0 +1,351 @@
+//
+// Copyright (c) 2025 Łukasz Szpakowski
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use crate::frontend::error::Pos;
+use crate::backend::error::*;
+use crate::backend::ir::*;
+
+pub const DEFAULT_INLINE_THRESHOLD: usize = 16;
+
+fn add_callees_for_op(op: &IrOp, callees: &mut BTreeSet<String>)
+{
+    match op {
+        IrOp::CallFun(ident, _, _, _) | IrOp::CallFunWithoutPanic(ident, _, _) => {
+            callees.insert(ident.clone());
+        },
+        _ => (),
+    }
+}
+
+fn add_callees_for_block(block: &IrBlock, callees: &mut BTreeSet<String>)
+{
+    for instr in block.instrs() {
+        match instr {
+            IrInstr::Op(op) | IrInstr::Assign(_, op) | IrInstr::Return(Some(op)) => add_callees_for_op(op, callees),
+            IrInstr::Block(block2) | IrInstr::Loop(block2) => add_callees_for_block(block2, callees),
+            IrInstr::If(op, block2, block3) => {
+                add_callees_for_op(op, callees);
+                add_callees_for_block(block2, callees);
+                add_callees_for_block(block3, callees);
+            },
+            IrInstr::Switch(op, cases) => {
+                add_callees_for_op(op, callees);
+                for case in cases {
+                    match case {
+                        IrCase::Case(_, block2) | IrCase::Default(block2) => add_callees_for_block(block2, callees),
+                    }
+                }
+            },
+            _ => (),
+        }
+    }
+}
+
+fn fun_callees(fun: &IrFun) -> BTreeSet<String>
+{
+    let mut callees: BTreeSet<String> = BTreeSet::new();
+    match fun {
+        IrFun::Fun(_, _, _, block, _, _, _, _) => add_callees_for_block(block, &mut callees),
+        IrFun::Caller(_, _, _, caller_funs, _, _, _, _) => {
+            for caller_fun in caller_funs.funs().values() {
+                match caller_fun {
+                    IrCallerFun::Fun(ident) => {
+                        callees.insert(ident.clone());
+                    },
+                    IrCallerFun::InlineFun(ident, block) => {
+                        callees.insert(ident.clone());
+                        add_callees_for_block(block, &mut callees);
+                    },
+                    IrCallerFun::Lambda(_, _, _, _, block) => add_callees_for_block(block, &mut callees),
+                }
+            }
+        },
+    }
+    callees
+}
+
+fn instr_count(block: &IrBlock) -> usize
+{
+    block.instrs().iter().fold(0usize, |n, instr| {
+            match instr {
+                IrInstr::Block(block2) | IrInstr::Loop(block2) => n + instr_count(block2) + 1,
+                IrInstr::If(_, block2, block3) => n + instr_count(block2) + instr_count(block3) + 1,
+                IrInstr::Switch(_, cases) => {
+                    cases.iter().fold(n + 1, |m, case| {
+                            match case {
+                                IrCase::Case(_, block2) | IrCase::Default(block2) => m + instr_count(block2),
+                            }
+                    })
+                },
+                _ => n + 1,
+            }
+    })
+}
+
+fn block_size(block: &IrBlock) -> usize
+{ block.block_count() + instr_count(block) }
+
+fn has_only_tail_returns(block: &IrBlock, is_tail: bool) -> bool
+{
+    let len = block.instrs().len();
+    block.instrs().iter().enumerate().all(|(i, instr)| {
+            let is_tail2 = is_tail && i + 1 == len;
+            match instr {
+                IrInstr::Return(_) => is_tail2,
+                IrInstr::Block(block2) => has_only_tail_returns(block2, is_tail2),
+                IrInstr::If(_, block2, block3) => has_only_tail_returns(block2, is_tail2) && has_only_tail_returns(block3, is_tail2),
+                IrInstr::Switch(_, cases) => {
+                    cases.iter().all(|case| {
+                            match case {
+                                IrCase::Case(_, block2) | IrCase::Default(block2) => has_only_tail_returns(block2, is_tail2),
+                            }
+                    })
+                },
+                IrInstr::Loop(block2) => has_only_tail_returns(block2, false),
+                _ => true,
+            }
+    })
+}
+
+struct SccFinder<'a>
+{
+    graph: &'a BTreeMap<String, BTreeSet<String>>,
+    index_counter: usize,
+    indices: BTreeMap<String, usize>,
+    low_links: BTreeMap<String, usize>,
+    stack: Vec<String>,
+    on_stack: BTreeSet<String>,
+    sccs: Vec<Vec<String>>,
+}
+
+impl<'a> SccFinder<'a>
+{
+    fn new(graph: &'a BTreeMap<String, BTreeSet<String>>) -> Self
+    {
+        SccFinder {
+            graph,
+            index_counter: 0,
+            indices: BTreeMap::new(),
+            low_links: BTreeMap::new(),
+            stack: Vec::new(),
+            on_stack: BTreeSet::new(),
+            sccs: Vec::new(),
+        }
+    }
+
+    fn visit(&mut self, ident: &String)
+    {
+        self.indices.insert(ident.clone(), self.index_counter);
+        self.low_links.insert(ident.clone(), self.index_counter);
+        self.index_counter += 1;
+        self.stack.push(ident.clone());
+        self.on_stack.insert(ident.clone());
+        if let Some(callees) = self.graph.get(ident) {
+            for callee in callees {
+                if !self.graph.contains_key(callee) {
+                    continue;
+                }
+                if !self.indices.contains_key(callee) {
+                    self.visit(callee);
+                    let low_link = self.low_links[ident].min(self.low_links[callee]);
+                    self.low_links.insert(ident.clone(), low_link);
+                } else if self.on_stack.contains(callee) {
+                    let low_link = self.low_links[ident].min(self.indices[callee]);
+                    self.low_links.insert(ident.clone(), low_link);
+                }
+            }
+        }
+        if self.low_links[ident] == self.indices[ident] {
+            let mut scc: Vec<String> = Vec::new();
+            while let Some(ident2) = self.stack.pop() {
+                self.on_stack.remove(&ident2);
+                let is_end = ident2 == *ident;
+                scc.push(ident2);
+                if is_end {
+                    break;
+                }
+            }
+            self.sccs.push(scc);
+        }
+    }
+
+    fn find_sccs(mut self) -> Vec<Vec<String>>
+    {
+        for ident in self.graph.keys() {
+            if !self.indices.contains_key(ident) {
+                self.visit(ident);
+            }
+        }
+        self.sccs
+    }
+}
+
+struct InlinerContext<'a>
+{
+    tree: &'a IrTree,
+    threshold: usize,
+    recursive_idents: BTreeSet<String>,
+}
+
+impl<'a> InlinerContext<'a>
+{
+    fn inlinable_callee(&self, ident: &String, has_panic_check: bool) -> Option<(Vec<Box<IrType>>, Box<IrBlock>)>
+    {
+        if self.recursive_idents.contains(ident) {
+            return None;
+        }
+        match self.tree.var(ident) {
+            Some(var) => {
+                match &*var.borrow() {
+                    IrVar::Fun(fun) => {
+                        match &**fun {
+                            IrFun::Fun(IrFunModifier::Kernel, _, _, _, _, _, _, _) => None,
+                            IrFun::Fun(_, _, _, _, _, _, _, IrPanicFlag::Panic) if !has_panic_check => None,
+                            IrFun::Fun(modifier, arg_types, _, block, _, _, _, _) => {
+                                if *modifier == IrFunModifier::Inline || block_size(block) <= self.threshold {
+                                    Some((arg_types.clone(), block.clone()))
+                                } else {
+                                    None
+                                }
+                            },
+                            IrFun::Caller(_, _, _, _, _, _, _, _) => None,
+                        }
+                    },
+                    _ => None,
+                }
+            },
+            None => None,
+        }
+    }
+
+    fn inline_call(&self, instr: &IrInstr, local_count: usize, new_block: &mut IrBlock) -> BackendResult<bool>
+    {
+        let (op, ret_var) = match instr {
+            IrInstr::Op(op) => (op, Some(None)),
+            IrInstr::Assign(var, op) => (op, Some(Some(var))),
+            IrInstr::Return(Some(op)) => (op, None),
+            _ => return Ok(false),
+        };
+        let empty_poses: Vec<Pos> = Vec::new();
+        let (ident, values, pos, panic_poses, has_panic_check) = match op {
+            IrOp::CallFun(ident, values, pos, panic_poses) => (ident, values, pos, panic_poses, true),
+            IrOp::CallFunWithoutPanic(ident, values, pos) => (ident, values, pos, &empty_poses, false),
+            _ => return Ok(false),
+        };
+        let (arg_types, block) = match self.inlinable_callee(ident, has_panic_check) {
+            Some(pair) => pair,
+            None => return Ok(false),
+        };
+        if ret_var.is_some() && !has_only_tail_returns(&block, true) {
+            return Ok(false);
+        }
+        let arg_substitutions: Vec<ArgSubstitution> = values.iter().map(|v| ArgSubstitution::Value(v.clone())).collect();
+        match new_block.add_inline_fun(0, arg_types.as_slice(), &block, local_count, arg_substitutions.as_slice(), ret_var, pos, panic_poses.as_slice(), self.tree) {
+            Ok(()) => Ok(true),
+            Err(err) => Err(BackendError::Internal(format!("inline_call: {}", err))),
+        }
+    }
+
+    fn inline_block(&self, block: &IrBlock, local_count: usize) -> BackendResult<IrBlock>
+    {
+        let new_local_count = local_count + block.local_var_pairs().len();
+        let mut new_block = IrBlock::new();
+        for local_var_pair in block.local_var_pairs() {
+            new_block.add_local_var_pair(local_var_pair.clone());
+        }
+        for instr in block.instrs() {
+            if self.inline_call(instr, new_local_count, &mut new_block)? {
+                continue;
+            }
+            match instr {
+                IrInstr::Block(block2) => new_block.add_instr(IrInstr::Block(Box::new(self.inline_block(block2, new_local_count)?))),
+                IrInstr::If(op, block2, block3) => new_block.add_instr(IrInstr::If(op.clone(), Box::new(self.inline_block(block2, new_local_count)?), Box::new(self.inline_block(block3, new_local_count)?))),
+                IrInstr::Switch(op, cases) => {
+                    let mut new_cases: Vec<IrCase> = Vec::new();
+                    for case in cases {
+                        match case {
+                            IrCase::Case(value, block2) => new_cases.push(IrCase::Case(value.clone(), Box::new(self.inline_block(block2, new_local_count)?))),
+                            IrCase::Default(block2) => new_cases.push(IrCase::Default(Box::new(self.inline_block(block2, new_local_count)?))),
+                        }
+                    }
+                    new_block.add_instr(IrInstr::Switch(op.clone(), new_cases));
+                },
+                IrInstr::Loop(block2) => new_block.add_instr(IrInstr::Loop(Box::new(self.inline_block(block2, new_local_count)?))),
+                _ => new_block.add_instr(instr.clone()),
+            }
+        }
+        Ok(new_block)
+    }
+
+    fn inline_fun(&self, fun: &IrFun) -> BackendResult<IrFun>
+    {
+        match fun {
+            IrFun::Fun(modifier, arg_types, ret_type, block, private_heap_flag, local_heap_flag, global_heap_flag, panic_flag) => {
+                let new_block = self.inline_block(block, arg_types.len())?;
+                Ok(IrFun::Fun(*modifier, arg_types.clone(), ret_type.clone(), Box::new(new_block), *private_heap_flag, *local_heap_flag, *global_heap_flag, *panic_flag))
+            },
+            IrFun::Caller(fun_type, arg_types, ret_type, caller_funs, private_heap_flag, local_heap_flag, global_heap_flag, panic_flag) => {
+                let mut new_caller_funs = caller_funs.clone();
+                for (idx, caller_fun) in caller_funs.funs() {
+                    let (local_count, block) = match caller_fun {
+                        IrCallerFun::Fun(_) => continue,
+                        IrCallerFun::InlineFun(_, block) => (arg_types.len(), block),
+                        IrCallerFun::Lambda(_, _, _, start_idx, block) => (start_idx + arg_types.len(), block),
+                    };
+                    let new_block = self.inline_block(block, local_count)?;
+                    if let Some(block2) = new_caller_funs.fun_block_mut(*idx) {
+                        **block2 = new_block;
+                    }
+                }
+                Ok(IrFun::Caller(fun_type.clone(), arg_types.clone(), ret_type.clone(), new_caller_funs, *private_heap_flag, *local_heap_flag, *global_heap_flag, *panic_flag))
+            },
+        }
+    }
+}
+
+pub fn inline_ir_tree(tree: &IrTree, threshold: usize) -> BackendResult<()>
+{
+    let mut graph: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+    for def in tree.defs() {
+        if let IrDef::Var(ident, var) = &**def {
+            if let IrVar::Fun(fun) = &*var.borrow() {
+                graph.insert(ident.clone(), fun_callees(fun));
+            }
+        }
+    }
+    let sccs = SccFinder::new(&graph).find_sccs();
+    let mut recursive_idents: BTreeSet<String> = BTreeSet::new();
+    for scc in &sccs {
+        if scc.len() > 1 || graph[&scc[0]].contains(&scc[0]) {
+            recursive_idents.extend(scc.iter().cloned());
+        }
+    }
+    let ctx = InlinerContext {
+        tree,
+        threshold,
+        recursive_idents,
+    };
+    for scc in &sccs {
+        for ident in scc {
+            if let Some(var) = tree.var(ident) {
+                let new_fun = match &*var.borrow() {
+                    IrVar::Fun(fun) => Some(ctx.inline_fun(fun)?),
+                    _ => None,
+                };
+                if let Some(new_fun) = new_fun {
+                    *var.borrow_mut() = IrVar::Fun(Box::new(new_fun));
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,203 @@
+//
+// Copyright (c) 2025 Łukasz Szpakowski
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+use crate::frontend::do_frontend_phases;
+use crate::backend::ir::parser::*;
+use crate::backend::ir::printer::*;
+use crate::backend::lowering::*;
+use super::*;
+
+fn inline_ir_string(s: &str, threshold: usize) -> String
+{
+    let ir_tree = match parse_ir_tree(s) {
+        Ok(ir_tree) => ir_tree,
+        Err(err) => {
+            println!("{}", err);
+            panic!();
+        },
+    };
+    match inline_ir_tree(&ir_tree, threshold) {
+        Ok(()) => (),
+        Err(err) => {
+            println!("{}", err);
+            panic!();
+        },
+    }
+    ir_tree_to_string(&ir_tree)
+}
+
+#[test]
+fn test_inliner_inline_ir_tree_inlines_inline_functions_and_small_functions()
+{
+    let s = "
+inline fun @f(int, int) -> int
+{
+    var int;
+    v2 = mul v0, v1;
+    if lt v2, int 0 {
+        v2 = neg v2;
+    } else {
+    }
+    return add v2, int 1;
+}
+
+fun @g(int) -> int
+{
+    return add v0, int 2;
+}
+
+fun @h(int) -> int
+{
+    var int;
+    v1 = call @f(v0, int 3) at \"a.vscfl\":1:1;
+    v1 = call @g(v1) at \"a.vscfl\":2:1;
+    return load v1;
+}
+
+kernel fun @k(*global int) -> void
+{
+    var int;
+    v1 = call @h(int 1) at \"a.vscfl\":3:1;
+    v0.* = load v1;
+    return;
+}
+";
+    let t = "
+inline fun @f(int, int) -> int
+{
+    var int;
+    v2 = mul v0, v1;
+    if lt v2, int 0 {
+        v2 = neg v2;
+    } else {
+    }
+    return add v2, int 1;
+}
+
+fun @g(int) -> int
+{
+    return add v0, int 2;
+}
+
+fun @h(int) -> int
+{
+    var int;
+    {
+        var int;
+        var int;
+        v2 = load v0;
+        v3 = load int 3;
+        {
+            var int;
+            v4 = mul v2, v3;
+            if lt v4, int 0 {
+                v4 = neg v4;
+            } else {
+            }
+            v1 = add v4, int 1;
+        }
+    }
+    {
+        var int;
+        v2 = load v1;
+        v1 = add v2, int 2;
+    }
+    return load v1;
+}
+
+kernel fun @k(*global int) -> void
+{
+    var int;
+    v1 = call @h(int 1) at \"a.vscfl\":3:1;
+    v0.* = load v1;
+    return;
+}
+";
+    let s2 = &s[1..];
+    let t2 = &t[1..];
+    assert_eq!(t2, inline_ir_string(s2, 4));
+}
+
+#[test]
+fn test_inliner_inline_ir_tree_does_not_inline_recursive_functions()
+{
+    let s = "
+fun @f(int) -> int
+{
+    return call @g(v0) at \"a.vscfl\":1:1;
+}
+
+fun @g(int) -> int
+{
+    return call @f(v0) at \"a.vscfl\":2:1;
+}
+
+fun @h(int) -> int
+{
+    return call @h(v0) at \"a.vscfl\":3:1;
+}
+
+kernel fun @k(*global int) -> void
+{
+    v0.* = call @f(int 1) at \"a.vscfl\":4:1;
+    v0.* = call @h(int 2) at \"a.vscfl\":5:1;
+    return;
+}
+";
+    let s2 = &s[1..];
+    assert_eq!(s2, inline_ir_string(s2, 16));
+}
+
+#[test]
+fn test_inliner_inline_ir_tree_removes_op_ne_call()
+{
+    let s = "
+import opencl;
+data P = P(Int, Int);
+impl Eq for P
+{
+    op_eq(p, q) =
+        p match {
+            P(a, b) =>
+                q match {
+                    P(c, d) => if a == c then b == d else false;
+                };
+        };
+};
+f(p: P, q: P) -> Int = if p != q then 1 else 0;
+kernel k(xs: UniqGlobalSlice<Int>, x: Int) -> () =
+    let i = get_global_id(0 as Uint);
+        _ = xs[i] <- f(P(x, 1), P(2, x));
+    in  ();
+";
+    let s2 = &s[1..];
+    let tree = match do_frontend_phases(s2) {
+        Ok(tree) => tree,
+        Err(errs) => {
+            println!("{}", errs);
+            panic!();
+        },
+    };
+    let ir_tree = match Lowerer::new_with_inline_threshold(0).lower_tree(&tree) {
+        Ok(ir_tree) => ir_tree,
+        Err(err) => {
+            println!("{}", err);
+            panic!();
+        },
+    };
+    let ir_tree2 = match lower_tree(&tree) {
+        Ok(ir_tree) => ir_tree,
+        Err(err) => {
+            println!("{}", err);
+            panic!();
+        },
+    };
+    let ir_s = ir_tree_to_string(&ir_tree);
+    let ir_s2 = ir_tree_to_string(&ir_tree2);
+    assert!(ir_s.contains("op_ne"));
+    assert!(!ir_s2.contains("op_ne"));
+}
@@ -0,0 +1,3042 @@
+//
+// Copyright (c) 2025 Łukasz Szpakowski
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+use std::collections::HashMap;
+use std::error;
+use std::fmt;
+use std::result;
+use crate::frontend::error::Pos;
+use crate::frontend::printf::*;
+use crate::backend::error::*;
+use crate::backend::heap::*;
+use crate::backend::ir::*;
+use crate::backend::ir::utils::*;
+use crate::backend::layout::*;
+use crate::backend::mangling::*;
+use crate::backend::panic::*;
+
+pub const DEFAULT_GLOBAL_HEAP_SIZE: usize = 65536;
+pub const DEFAULT_PRIVATE_MEMORY_SIZE: usize = 65536;
+
+const REGION_SHIFT: u32 = 40;
+const REGION_OFFSET_MASK: u64 = (1u64 << REGION_SHIFT) - 1;
+
+#[derive(Clone, Debug)]
+pub struct NdRange
+{
+    pub work_dim: usize,
+    pub global_offset: [usize; 3],
+    pub global_size: [usize; 3],
+    pub local_size: [usize; 3],
+}
+
+impl NdRange
+{
+    pub fn new(global_size: &[usize], local_size: &[usize]) -> Self
+    {
+        let mut global_size2 = [1usize; 3];
+        let mut local_size2 = [1usize; 3];
+        for (i, n) in global_size.iter().take(3).enumerate() {
+            global_size2[i] = *n;
+        }
+        for (i, n) in local_size.iter().take(3).enumerate() {
+            local_size2[i] = *n;
+        }
+        NdRange {
+            work_dim: global_size.len(),
+            global_offset: [0; 3],
+            global_size: global_size2,
+            local_size: local_size2,
+        }
+    }
+
+    pub fn num_groups(&self) -> [usize; 3]
+    {
+        let mut num_groups = [1usize; 3];
+        for (i, num_group) in num_groups.iter_mut().enumerate() {
+            if let Some(tmp_num_group) = self.global_size[i].checked_div(self.local_size[i]) {
+                *num_group = tmp_num_group;
+            }
+        }
+        num_groups
+    }
+}
+
+#[derive(Clone, Debug)]
+pub enum KernelArg
+{
+    Buffer(Vec<u8>),
+    Value(Vec<u8>),
+    Local(usize),
+}
+
+#[derive(Clone, Debug)]
+pub struct KernelOutput
+{
+    pub panic: Option<Panic>,
+    pub printf_output: String,
+}
+
+#[derive(Debug)]
+pub enum InterpreterError
+{
+    Backend(BackendError),
+    Kernel(String),
+    Fault([u32; 3], String),
+    StepLimit,
+}
+
+impl error::Error for InterpreterError
+{}
+
+impl fmt::Display for InterpreterError
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        match self {
+            InterpreterError::Backend(err) => write!(f, "{}", err),
+            InterpreterError::Kernel(msg) => write!(f, "invalid kernel launch: {}", msg),
+            InterpreterError::Fault(global_id, msg) => write!(f, "work-item {}, {}, {}: {}", global_id[0], global_id[1], global_id[2], msg),
+            InterpreterError::StepLimit => write!(f, "step limit is exceeded"),
+        }
+    }
+}
+
+pub type InterpreterResult<T> = result::Result<T, InterpreterError>;
+
+fn internal_error(msg: String) -> InterpreterError
+{ InterpreterError::Backend(BackendError::Internal(msg)) }
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+enum ScalarKind
+{
+    Int(u32, bool),
+    Float(u32),
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+enum Elem
+{
+    Int(i128),
+    Float(f64),
+}
+
+fn scalar_kind(typ: &IrType) -> Option<ScalarKind>
+{
+    match typ {
+        IrType::Char => Some(ScalarKind::Int(8, true)),
+        IrType::Short => Some(ScalarKind::Int(16, true)),
+        IrType::Int => Some(ScalarKind::Int(32, true)),
+        IrType::Long => Some(ScalarKind::Int(64, true)),
+        IrType::Uchar => Some(ScalarKind::Int(8, false)),
+        IrType::Ushort => Some(ScalarKind::Int(16, false)),
+        IrType::Uint => Some(ScalarKind::Int(32, false)),
+        IrType::Ulong => Some(ScalarKind::Int(64, false)),
+        IrType::Half => Some(ScalarKind::Float(16)),
+        IrType::Float => Some(ScalarKind::Float(32)),
+        IrType::Double => Some(ScalarKind::Float(64)),
+        IrType::SizeT | IrType::UintptrT => Some(ScalarKind::Int(64, false)),
+        IrType::PtrdiffT | IrType::IntptrT => Some(ScalarKind::Int(64, true)),
+        IrType::Ptr(_, _, _) => Some(ScalarKind::Int(64, false)),
+        _ => None,
+    }
+}
+
+fn kind_type(kind: ScalarKind) -> IrType
+{
+    match kind {
+        ScalarKind::Int(bits, is_signed) => int_type(bits, is_signed),
+        ScalarKind::Float(16) => IrType::Half,
+        ScalarKind::Float(32) => IrType::Float,
+        ScalarKind::Float(_) => IrType::Double,
+    }
+}
+
+fn kind_size(kind: ScalarKind) -> usize
+{
+    match kind {
+        ScalarKind::Int(bits, _) => (bits / 8) as usize,
+        ScalarKind::Float(bits) => (bits / 8) as usize,
+    }
+}
+
+fn common_type(typ1: &IrType, typ2: &IrType) -> IrType
+{
+    let type1 = promoted_type(typ1);
+    let type2 = promoted_type(typ2);
+    if type1 == type2 {
+        return type1;
+    }
+    match (scalar_kind(&type1), scalar_kind(&type2)) {
+        (Some(ScalarKind::Float(bits1)), Some(ScalarKind::Float(bits2))) => if bits1 >= bits2 { type1 } else { type2 },
+        (Some(ScalarKind::Float(_)), _) => type1,
+        (_, Some(ScalarKind::Float(_))) => type2,
+        (Some(ScalarKind::Int(bits1, is_signed1)), Some(ScalarKind::Int(bits2, is_signed2))) => {
+            if is_signed1 == is_signed2 {
+                if bits1 >= bits2 { type1 } else { type2 }
+            } else if !is_signed1 && bits1 >= bits2 {
+                type1
+            } else if !is_signed2 && bits2 >= bits1 {
+                type2
+            } else if is_signed1 && bits1 > bits2 {
+                type1
+            } else if is_signed2 && bits2 > bits1 {
+                type2
+            } else {
+                int_type(bits1.max(bits2), false)
+            }
+        },
+        _ => type1,
+    }
+}
+
+fn cmp_elem_type(kind: ScalarKind) -> IrType
+{
+    match kind {
+        ScalarKind::Int(bits, _) => int_type(bits, true),
+        ScalarKind::Float(bits) => int_type(bits.max(32), true),
+    }
+}
+
+fn mul_int_hi(a: i128, b: i128, bits: u32, is_signed: bool) -> i128
+{
+    if is_signed {
+        (a * b) >> bits
+    } else {
+        (((a as u128) * (b as u128)) >> bits) as i128
+    }
+}
+
+fn mad_int_sat(a: i128, b: i128, c: i128, bits: u32, is_signed: bool) -> i128
+{
+    if is_signed {
+        (a * b + c).clamp(int_min(bits, is_signed), int_max(bits, is_signed))
+    } else {
+        match (a as u128).checked_mul(b as u128).and_then(|d| d.checked_add(c as u128)) {
+            Some(d) if d <= (int_max(bits, is_signed) as u128) => d as i128,
+            _ => int_max(bits, is_signed),
+        }
+    }
+}
+
+fn round_float(x: f64, bits: u32) -> f64
+{
+    match bits {
+        16 => f16_bits_to_f32(f32_to_f16_bits(x as f32)) as f64,
+        32 => (x as f32) as f64,
+        _ => x,
+    }
+}
+
+fn convert_elem(elem: Elem, kind: ScalarKind) -> Elem
+{
+    match (elem, kind) {
+        (Elem::Int(a), ScalarKind::Int(bits, is_signed)) => Elem::Int(wrap_int(a, bits, is_signed)),
+        (Elem::Float(x), ScalarKind::Int(bits, is_signed)) => {
+            if x.is_nan() {
+                Elem::Int(0)
+            } else {
+                Elem::Int((x.trunc() as i128).clamp(int_min(bits, is_signed), int_max(bits, is_signed)))
+            }
+        },
+        (Elem::Int(a), ScalarKind::Float(bits)) => Elem::Float(round_float(a as f64, bits)),
+        (Elem::Float(x), ScalarKind::Float(bits)) => Elem::Float(round_float(x, bits)),
+    }
+}
+
+fn read_elem(bytes: &[u8], kind: ScalarKind) -> Elem
+{
+    match kind {
+        ScalarKind::Int(bits, is_signed) => {
+            let n = (bits / 8) as usize;
+            let mut buf = [0u8; 16];
+            buf[..n].copy_from_slice(&bytes[..n]);
+            Elem::Int(wrap_int(i128::from_le_bytes(buf), bits, is_signed))
+        },
+        ScalarKind::Float(16) => Elem::Float(f16_bits_to_f32(u16::from_le_bytes([bytes[0], bytes[1]])) as f64),
+        ScalarKind::Float(32) => Elem::Float(f32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) as f64),
+        ScalarKind::Float(_) => {
+            let mut buf = [0u8; 8];
+            buf.copy_from_slice(&bytes[..8]);
+            Elem::Float(f64::from_le_bytes(buf))
+        },
+    }
+}
+
+fn write_elem(bytes: &mut [u8], elem: Elem, kind: ScalarKind)
+{
+    match (convert_elem(elem, kind), kind) {
+        (Elem::Int(a), _) => {
+            let n = kind_size(kind);
+            bytes[..n].copy_from_slice(&a.to_le_bytes()[..n]);
+        },
+        (Elem::Float(x), ScalarKind::Float(16)) => bytes[..2].copy_from_slice(&f32_to_f16_bits(x as f32).to_le_bytes()),
+        (Elem::Float(x), ScalarKind::Float(32)) => bytes[..4].copy_from_slice(&(x as f32).to_le_bytes()),
+        (Elem::Float(x), _) => bytes[..8].copy_from_slice(&x.to_le_bytes()),
+    }
+}
+
+fn elem_is_true(elem: Elem) -> bool
+{
+    match elem {
+        Elem::Int(a) => a != 0,
+        Elem::Float(x) => x != 0.0,
+    }
+}
+
+fn elem_to_f64(elem: Elem) -> f64
+{
+    match elem {
+        Elem::Int(a) => a as f64,
+        Elem::Float(x) => x,
+    }
+}
+
+fn elem_to_i128(elem: Elem) -> i128
+{
+    match elem {
+        Elem::Int(a) => a,
+        Elem::Float(x) => x as i128,
+    }
+}
+
+fn align_up(offset: usize, align: usize) -> usize
+{ offset.div_ceil(align.max(1)) * align.max(1) }
+
+#[derive(Clone, Debug)]
+struct Value
+{
+    typ: IrType,
+    bytes: Vec<u8>,
+}
+
+impl Value
+{
+    fn new(typ: IrType, bytes: Vec<u8>) -> Self
+    { Value { typ, bytes, } }
+
+    fn void() -> Self
+    { Value::new(IrType::Void, Vec::new()) }
+
+    fn from_int(typ: IrType, a: i128) -> Self
+    {
+        match Value::from_elems(typ.clone(), &[Elem::Int(a)]) {
+            Some(value) => value,
+            None => Value::new(typ, Vec::new()),
+        }
+    }
+
+    fn from_float(typ: IrType, x: f64) -> Self
+    {
+        match Value::from_elems(typ.clone(), &[Elem::Float(x)]) {
+            Some(value) => value,
+            None => Value::new(typ, Vec::new()),
+        }
+    }
+
+    fn from_ptr(typ: IrType, addr: u64) -> Self
+    { Value::new(typ, addr.to_le_bytes().to_vec()) }
+
+    fn from_elems(typ: IrType, elems: &[Elem]) -> Option<Self>
+    {
+        match scalar_kind(&typ) {
+            Some(kind) => {
+                let mut bytes = vec![0u8; kind_size(kind)];
+                write_elem(bytes.as_mut_slice(), *elems.first()?, kind);
+                Some(Value::new(typ, bytes))
+            },
+            None => {
+                let (elem_type, n) = vector_type_parts(&typ)?;
+                let kind = scalar_kind(&elem_type)?;
+                let elem_size = kind_size(kind);
+                if elems.len() != n {
+                    return None;
+                }
+                let mut bytes = vec![0u8; elem_size * (if n == 3 { 4 } else { n })];
+                for (i, elem) in elems.iter().enumerate() {
+                    write_elem(&mut bytes[(i * elem_size)..], *elem, kind);
+                }
+                Some(Value::new(typ, bytes))
+            },
+        }
+    }
+
+    fn elems(&self) -> Option<(ScalarKind, Option<usize>, Vec<Elem>)>
+    {
+        match scalar_kind(&self.typ) {
+            Some(kind) => {
+                if self.bytes.len() < kind_size(kind) {
+                    return None;
+                }
+                Some((kind, None, vec![read_elem(self.bytes.as_slice(), kind)]))
+            },
+            None => {
+                let (elem_type, n) = vector_type_parts(&self.typ)?;
+                let kind = scalar_kind(&elem_type)?;
+                let elem_size = kind_size(kind);
+                if self.bytes.len() < elem_size * n {
+                    return None;
+                }
+                Some((kind, Some(n), (0..n).map(|i| read_elem(&self.bytes[(i * elem_size)..], kind)).collect()))
+            },
+        }
+    }
+
+    fn ptr(&self) -> Option<u64>
+    {
+        match self.typ {
+            IrType::Ptr(_, _, _) if self.bytes.len() >= 8 => {
+                let mut buf = [0u8; 8];
+                buf.copy_from_slice(&self.bytes[..8]);
+                Some(u64::from_le_bytes(buf))
+            },
+            _ => None,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+enum BinOp
+{
+    Mul,
+    Div,
+    Rem,
+    Add,
+    Sub,
+    Shl,
+    Shr,
+    Eq,
+    Ne,
+    Lt,
+    Ge,
+    Gt,
+    Le,
+    And,
+    Xor,
+    Or,
+}
+
+impl BinOp
+{
+    fn is_cmp(&self) -> bool
+    {
+        matches!(self, BinOp::Eq | BinOp::Ne | BinOp::Lt | BinOp::Ge | BinOp::Gt | BinOp::Le)
+    }
+
+    fn is_shift(&self) -> bool
+    { *self == BinOp::Shl || *self == BinOp::Shr }
+}
+
+struct Memory
+{
+    regions: Vec<Option<Vec<u8>>>,
+}
+
+impl Memory
+{
+    fn new() -> Self
+    { Memory { regions: vec![None], } }
+
+    fn region_count(&self) -> usize
+    { self.regions.len() }
+
+    fn truncate_regions(&mut self, count: usize)
+    { self.regions.truncate(count); }
+
+    fn add_region(&mut self, bytes: Vec<u8>) -> u64
+    {
+        self.regions.push(Some(bytes));
+        ((self.regions.len() - 1) as u64) << REGION_SHIFT
+    }
+
+    fn region(&self, addr: u64) -> Option<&Vec<u8>>
+    {
+        match self.regions.get((addr >> REGION_SHIFT) as usize) {
+            Some(Some(bytes)) => Some(bytes),
+            _ => None,
+        }
+    }
+
+    fn region_mut(&mut self, addr: u64) -> Option<&mut Vec<u8>>
+    {
+        match self.regions.get_mut((addr >> REGION_SHIFT) as usize) {
+            Some(Some(bytes)) => Some(bytes),
+            _ => None,
+        }
+    }
+
+    fn read(&self, addr: u64, size: usize) -> Option<&[u8]>
+    {
+        let bytes = self.region(addr)?;
+        let offset = (addr & REGION_OFFSET_MASK) as usize;
+        bytes.get(offset..offset.checked_add(size)?)
+    }
+
+    fn write(&mut self, addr: u64, src: &[u8]) -> bool
+    {
+        let offset = (addr & REGION_OFFSET_MASK) as usize;
+        match self.region_mut(addr) {
+            Some(bytes) => {
+                match bytes.get_mut(offset..(offset + src.len())) {
+                    Some(dst) => {
+                        dst.copy_from_slice(src);
+                        true
+                    },
+                    None => false,
+                }
+            },
+            None => false,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+struct Place
+{
+    addr: u64,
+    typ: IrType,
+}
+
+impl Place
+{
+    fn new(addr: u64, typ: IrType) -> Self
+    { Place { addr, typ, } }
+}
+
+#[derive(Clone, Debug)]
+enum Base
+{
+    Place(Place),
+    Value(Value),
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+enum BlockKind
+{
+    Block,
+    Loop,
+}
+
+struct BlockState<'a>
+{
+    block: &'a IrBlock,
+    instr_idx: usize,
+    kind: BlockKind,
+    local_var_count: usize,
+    stack_size: usize,
+}
+
+#[derive(Copy, Clone)]
+enum Cont<'a>
+{
+    None,
+    Assign(&'a IrInstrVar),
+    Return,
+    If(&'a IrBlock, &'a IrBlock),
+    Switch(&'a [IrCase]),
+}
+
+struct Frame<'a>
+{
+    local_vars: Vec<Place>,
+    caller_args: Vec<Place>,
+    private_closure: Option<Place>,
+    local_closure: Option<Place>,
+    global_closure: Option<Place>,
+    ret_type: IrType,
+    blocks: Vec<BlockState<'a>>,
+    stack_size: usize,
+    cont: Cont<'a>,
+    call_poses: Vec<Pos>,
+}
+
+impl<'a> Frame<'a>
+{
+    fn new(ret_type: IrType, stack_size: usize, cont: Cont<'a>, call_poses: Vec<Pos>) -> Self
+    {
+        Frame {
+            local_vars: Vec::new(),
+            caller_args: Vec::new(),
+            private_closure: None,
+            local_closure: None,
+            global_closure: None,
+            ret_type,
+            blocks: Vec::new(),
+            stack_size,
+            cont,
+            call_poses,
+        }
+    }
+
+    fn local_var(&self, idx: usize) -> InterpreterResult<Place>
+    {
+        match self.local_vars.get(idx) {
+            Some(place) => Ok(place.clone()),
+            None => Err(internal_error(format!("local_var: no local variable {}", idx))),
+        }
+    }
+
+    fn caller_arg(&self, idx: usize) -> InterpreterResult<Place>
+    {
+        match self.caller_args.get(idx) {
+            Some(place) => Ok(place.clone()),
+            None => Err(internal_error(format!("caller_arg: no caller argument {}", idx))),
+        }
+    }
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+enum WorkItemState
+{
+    Ready,
+    Barrier,
+    Done,
+}
+
+struct WorkItem<'a>
+{
+    local_id: [usize; 3],
+    global_id: [usize; 3],
+    stack: u64,
+    private_heap: u64,
+    frames: Vec<Frame<'a>>,
+    state: WorkItemState,
+}
+
+impl<'a> WorkItem<'a>
+{
+    fn frame(&self) -> InterpreterResult<&Frame<'a>>
+    {
+        match self.frames.last() {
+            Some(frame) => Ok(frame),
+            None => Err(internal_error(String::from("frame: no frame"))),
+        }
+    }
+
+    fn frame_mut(&mut self) -> InterpreterResult<&mut Frame<'a>>
+    {
+        match self.frames.last_mut() {
+            Some(frame) => Ok(frame),
+            None => Err(internal_error(String::from("frame_mut: no frame"))),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+enum KernelArgValue
+{
+    Value(Value),
+    Local(IrType, usize),
+}
+
+trait InterpretVar
+{
+    fn interpret_var<'a>(&self, machine: &mut Machine<'a>, frame: Option<&Frame<'a>>) -> InterpreterResult<Value>;
+}
+
+impl InterpretVar for IrValueVar
+{
+    fn interpret_var<'a>(&self, machine: &mut Machine<'a>, frame: Option<&Frame<'a>>) -> InterpreterResult<Value>
+    {
+        let ops: Vec<IrArgOp> = self.1.iter().map(|op| {
+                match op {
+                    IrValueOp::Deref(typ) => IrArgOp::Deref(typ.clone()),
+                    IrValueOp::Dot(typ, idx) => IrArgOp::Dot(typ.clone(), *idx),
+                    IrValueOp::Arrow(typ, idx) => IrArgOp::Arrow(typ.clone(), *idx),
+                    IrValueOp::Index(typ, idx) => IrArgOp::Index(typ.clone(), *idx),
+                }
+        }).collect();
+        let place = machine.global_place(&self.0)?;
+        match machine.apply_arg_ops(Base::Place(place), ops.as_slice(), frame)? {
+            Base::Place(place) => Ok(Value::from_ptr(IrType::Ptr(IrPtrModifier::None, IrAccessModifier::None, Box::new(place.typ)), place.addr)),
+            Base::Value(_) => Err(internal_error(String::from("interpret_var: reference to value"))),
+        }
+    }
+}
+
+impl InterpretVar for IrArgVar
+{
+    fn interpret_var<'a>(&self, machine: &mut Machine<'a>, frame: Option<&Frame<'a>>) -> InterpreterResult<Value>
+    { machine.arg_var_value(self, frame) }
+}
+
+struct Machine<'a>
+{
+    tree: &'a IrTree,
+    funs: &'a HashMap<String, IrFun>,
+    nd_range: &'a NdRange,
+    private_heap_size: usize,
+    local_heap_size: usize,
+    private_memory_size: usize,
+    step_limit: Option<u64>,
+    memory: Memory,
+    layouts: HashMap<IrType, Layout>,
+    globals: HashMap<String, Place>,
+    strings: HashMap<Vec<u8>, u64>,
+    string_region: u64,
+    global_local_vars: HashMap<usize, u64>,
+    global_local_var_region: u64,
+    local_vars: HashMap<usize, u64>,
+    global_heap: u64,
+    local_heap: u64,
+    private_heap: u64,
+    stack: u64,
+    group_id: [usize; 3],
+    local_id: [usize; 3],
+    global_id: [usize; 3],
+    is_barrier: bool,
+    step_count: u64,
+    panic: Option<Panic>,
+    printf_output: Vec<u8>,
+}
+
+impl<'a> Machine<'a>
+{
+    fn new(interp: &Interpreter, tree: &'a IrTree, funs: &'a HashMap<String, IrFun>, nd_range: &'a NdRange) -> Self
+    {
+        let mut memory = Memory::new();
+        let string_region = memory.add_region(Vec::new());
+        let global_local_var_region = memory.add_region(Vec::new());
+        Machine {
+            tree,
+            funs,
+            nd_range,
+            private_heap_size: interp.private_heap_size,
+            local_heap_size: interp.local_heap_size,
+            private_memory_size: interp.private_memory_size,
+            step_limit: interp.step_limit,
+            memory,
+            layouts: HashMap::new(),
+            globals: HashMap::new(),
+            strings: HashMap::new(),
+            string_region,
+            global_local_vars: HashMap::new(),
+            global_local_var_region,
+            local_vars: HashMap::new(),
+            global_heap: 0,
+            local_heap: 0,
+            private_heap: 0,
+            stack: 0,
+            group_id: [0; 3],
+            local_id: [0; 3],
+            global_id: [0; 3],
+            is_barrier: false,
+            step_count: 0,
+            panic: None,
+            printf_output: Vec::new(),
+        }
+    }
+
+    fn fault(&self, msg: String) -> InterpreterError
+    { InterpreterError::Fault([self.global_id[0] as u32, self.global_id[1] as u32, self.global_id[2] as u32], msg) }
+
+    fn layout(&mut self, typ: &IrType) -> InterpreterResult<&Layout>
+    {
+        if !self.layouts.contains_key(typ) {
+            match ir_type_layout(typ, self.tree) {
+                Ok(layout) => {
+                    self.layouts.insert(typ.clone(), layout);
+                },
+                Err(err) => return Err(InterpreterError::Backend(err)),
+            }
+        }
+        match self.layouts.get(typ) {
+            Some(layout) => Ok(layout),
+            None => Err(internal_error(String::from("layout: no layout"))),
+        }
+    }
+
+    fn type_size(&mut self, typ: &IrType) -> InterpreterResult<usize>
+    { Ok(self.layout(typ)?.size) }
+
+    fn heap_region(&mut self, capacity: usize) -> u64
+    {
+        let mut bytes = vec![0u8; HEAP_HEADER_SIZE + capacity];
+        bytes[(HEAP_CAPACITY_INDEX * 4)..(HEAP_CAPACITY_INDEX * 4 + 4)].copy_from_slice(&(capacity as u32).to_le_bytes());
+        self.memory.add_region(bytes)
+    }
+
+    fn read_memory(&self, addr: u64, size: usize) -> InterpreterResult<Vec<u8>>
+    {
+        match self.memory.read(addr, size) {
+            Some(bytes) => Ok(bytes.to_vec()),
+            None => Err(self.fault(format!("invalid memory read of {} bytes at address 0x{:x}", size, addr))),
+        }
+    }
+
+    fn write_memory(&mut self, addr: u64, bytes: &[u8]) -> InterpreterResult<()>
+    {
+        if self.memory.write(addr, bytes) {
+            Ok(())
+        } else {
+            Err(self.fault(format!("invalid memory write of {} bytes at address 0x{:x}", bytes.len(), addr)))
+        }
+    }
+
+    fn read_u32(&self, addr: u64) -> InterpreterResult<u32>
+    {
+        let bytes = self.read_memory(addr, 4)?;
+        Ok(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    fn read_c_string(&self, addr: u64) -> InterpreterResult<Vec<u8>>
+    {
+        match self.memory.region(addr) {
+            Some(bytes) => {
+                let offset = (addr & REGION_OFFSET_MASK) as usize;
+                match bytes.get(offset..) {
+                    Some(bytes2) => Ok(bytes2.iter().take_while(|b| **b != 0).cloned().collect()),
+                    None => Err(self.fault(format!("invalid string at address 0x{:x}", addr))),
+                }
+            },
+            None => Err(self.fault(format!("invalid string at address 0x{:x}", addr))),
+        }
+    }
+
+    fn string_value(&mut self, bytes: &[u8]) -> InterpreterResult<Value>
+    {
+        let typ = IrType::Ptr(IrPtrModifier::Constant, IrAccessModifier::Const, Box::new(IrType::Char));
+        if let Some(addr) = self.strings.get(bytes) {
+            return Ok(Value::from_ptr(typ, *addr));
+        }
+        let string_region = self.string_region;
+        match self.memory.region_mut(string_region) {
+            Some(region) => {
+                let addr = string_region + (region.len() as u64);
+                region.extend_from_slice(bytes);
+                region.push(0);
+                self.strings.insert(bytes.to_vec(), addr);
+                Ok(Value::from_ptr(typ, addr))
+            },
+            None => Err(internal_error(String::from("string_value: no string region"))),
+        }
+    }
+
+    fn stack_size(&self) -> InterpreterResult<usize>
+    {
+        match self.memory.region(self.stack) {
+            Some(bytes) => Ok(bytes.len()),
+            None => Err(internal_error(String::from("stack_size: no stack"))),
+        }
+    }
+
+    fn alloc_stack(&mut self, typ: &IrType) -> InterpreterResult<Place>
+    {
+        let layout = self.layout(typ)?;
+        let (size, align) = (layout.size, layout.align);
+        let private_memory_size = self.private_memory_size;
+        let stack = self.stack;
+        let offset = match self.memory.region_mut(stack) {
+            Some(bytes) => {
+                let offset = align_up(bytes.len(), align);
+                if offset + size > private_memory_size {
+                    None
+                } else {
+                    bytes.resize(offset + size, 0);
+                    Some(offset)
+                }
+            },
+            None => return Err(internal_error(String::from("alloc_stack: no stack"))),
+        };
+        match offset {
+            Some(offset) => Ok(Place::new(stack + (offset as u64), typ.clone())),
+            None => Err(self.fault(String::from("out of private memory"))),
+        }
+    }
+
+    fn truncate_stack(&mut self, size: usize)
+    {
+        let stack = self.stack;
+        if let Some(bytes) = self.memory.region_mut(stack) {
+            bytes.truncate(size);
+        }
+    }
+
+    fn alloc_global_local_var(&mut self, key: usize, typ: &IrType) -> InterpreterResult<Place>
+    {
+        if let Some(addr) = self.global_local_vars.get(&key) {
+            return Ok(Place::new(*addr, typ.clone()));
+        }
+        let layout = self.layout(typ)?;
+        let (size, align) = (layout.size, layout.align);
+        let region = self.global_local_var_region;
+        match self.memory.region_mut(region) {
+            Some(bytes) => {
+                let offset = align_up(bytes.len(), align);
+                bytes.resize(offset + size, 0);
+                let addr = region + (offset as u64);
+                self.global_local_vars.insert(key, addr);
+                Ok(Place::new(addr, typ.clone()))
+            },
+            None => Err(internal_error(String::from("alloc_global_local_var: no region"))),
+        }
+    }
+
+    fn alloc_local_var(&mut self, key: usize, typ: &IrType) -> InterpreterResult<Place>
+    {
+        if let Some(addr) = self.local_vars.get(&key) {
+            return Ok(Place::new(*addr, typ.clone()));
+        }
+        let size = self.type_size(typ)?;
+        let addr = self.memory.add_region(vec![0u8; size]);
+        self.local_vars.insert(key, addr);
+        Ok(Place::new(addr, typ.clone()))
+    }
+
+    fn init_global_vars(&mut self) -> InterpreterResult<()>
+    {
+        let tree = self.tree;
+        for def in tree.defs() {
+            if let IrDef::Var(ident, var) = &**def {
+                let typ = match &*var.borrow() {
+                    IrVar::Const(typ, _) => Some(typ.clone()),
+                    IrVar::Var(_, _, typ, _) => Some(typ.clone()),
+                    IrVar::Fun(_) => None,
+                };
+                if let Some(typ) = typ {
+                    let size = self.type_size(&typ)?;
+                    let addr = self.memory.add_region(vec![0u8; size]);
+                    self.globals.insert(ident.clone(), Place::new(addr, *typ));
+                }
+            }
+        }
+        for def in tree.defs() {
+            if let IrDef::Var(ident, var) = &**def {
+                match &*var.borrow() {
+                    IrVar::Const(_, value) | IrVar::Var(_, _, _, value) => {
+                        let place = self.global_place(ident)?;
+                        let value2 = self.eval_value(value, Some(&place.typ), None)?;
+                        self.store(&place, value2)?;
+                    },
+                    IrVar::Fun(_) => (),
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn global_place(&self, ident: &String) -> InterpreterResult<Place>
+    {
+        match self.globals.get(ident) {
+            Some(place) => Ok(place.clone()),
+            None => Err(internal_error(format!("global_place: no variable {}", ident))),
+        }
+    }
+
+    fn field_type(&self, typ: &IrType, idx: usize) -> InterpreterResult<IrType>
+    {
+        match typ {
+            IrType::Struct(ident) => {
+                match self.tree.struct1(ident) {
+                    Some(struct1) => {
+                        match &*struct1.borrow() {
+                            IrStruct::Struct(field_types) => {
+                                match field_types.get(idx) {
+                                    Some(field_type) => Ok((**field_type).clone()),
+                                    None => Err(internal_error(format!("field_type: no field {} in structure {}", idx, ident))),
+                                }
+                            },
+                            IrStruct::Closure(field_types) => {
+                                match field_types.get(&idx) {
+                                    Some(field_type) => Ok((**field_type).clone()),
+                                    None => Err(internal_error(format!("field_type: no field {} in structure {}", idx, ident))),
+                                }
+                            },
+                        }
+                    },
+                    None => Err(internal_error(format!("field_type: no structure {}", ident))),
+                }
+            },
+            IrType::Union(ident) => {
+                match self.tree.union(ident) {
+                    Some(union) => {
+                        match union.borrow().0.get(idx) {
+                            Some(field_type) => Ok((**field_type).clone()),
+                            None => Err(internal_error(format!("field_type: no field {} in union {}", idx, ident))),
+                        }
+                    },
+                    None => Err(internal_error(format!("field_type: no union {}", ident))),
+                }
+            },
+            _ => {
+                match vector_type_parts(typ) {
+                    Some((elem_type, n)) if idx < n => Ok(elem_type),
+                    _ => Err(internal_error(String::from("field_type: type hasn't fields"))),
+                }
+            },
+        }
+    }
+
+    fn field_offset(&mut self, typ: &IrType, idx: usize) -> InterpreterResult<usize>
+    {
+        match typ {
+            IrType::Struct(ident) => {
+                let pos = match self.tree.struct1(ident) {
+                    Some(struct1) => {
+                        match &*struct1.borrow() {
+                            IrStruct::Struct(_) => Some(idx),
+                            IrStruct::Closure(field_types) => field_types.keys().position(|key| *key == idx),
+                        }
+                    },
+                    None => return Err(internal_error(format!("field_offset: no structure {}", ident))),
+                };
+                match pos.and_then(|pos| self.layout(typ).ok().and_then(|layout| layout.field_offsets.get(pos).cloned())) {
+                    Some(offset) => Ok(offset),
+                    None => Err(internal_error(format!("field_offset: no field {} in structure {}", idx, ident))),
+                }
+            },
+            IrType::Union(_) => Ok(0),
+            _ => {
+                match vector_type_parts(typ).and_then(|(elem_type, n)| scalar_kind(&elem_type).map(|kind| (kind, n))) {
+                    Some((kind, n)) if idx < n => Ok(kind_size(kind) * idx),
+                    _ => Err(internal_error(String::from("field_offset: type hasn't fields"))),
+                }
+            },
+        }
+    }
+
+    fn field_place(&mut self, place: &Place, idx: usize) -> InterpreterResult<Place>
+    {
+        let field_type = self.field_type(&place.typ, idx)?;
+        let offset = self.field_offset(&place.typ, idx)?;
+        Ok(Place::new(place.addr.wrapping_add(offset as u64), field_type))
+    }
+
+    fn closure_var_place(&mut self, closure: &Option<Place>, idx: usize) -> InterpreterResult<Place>
+    {
+        match closure {
+            Some(place) => self.field_place(place, idx),
+            None => Err(internal_error(format!("closure_var_place: no closure for variable {}", idx))),
+        }
+    }
+
+    fn load(&self, place: &Place) -> InterpreterResult<Value>
+    {
+        match self.layouts.get(&place.typ) {
+            Some(layout) => Ok(Value::new(place.typ.clone(), self.read_memory(place.addr, layout.size)?)),
+            None => Err(internal_error(String::from("load: no layout"))),
+        }
+    }
+
+    fn load_place(&mut self, place: &Place) -> InterpreterResult<Value>
+    {
+        self.layout(&place.typ)?;
+        self.load(place)
+    }
+
+    fn store(&mut self, place: &Place, value: Value) -> InterpreterResult<()>
+    {
+        if place.typ == IrType::Void {
+            return Ok(());
+        }
+        let value2 = self.convert_value(value, &place.typ)?;
+        self.write_memory(place.addr, value2.bytes.as_slice())
+    }
+
+    fn base_value(&mut self, base: Base) -> InterpreterResult<Value>
+    {
+        match base {
+            Base::Place(place) => self.load_place(&place),
+            Base::Value(value) => Ok(value),
+        }
+    }
+
+    fn base_ptr(&mut self, base: Base) -> InterpreterResult<(u64, IrType)>
+    {
+        let value = self.base_value(base)?;
+        match (&value.typ, value.ptr()) {
+            (IrType::Ptr(_, _, elem_type), Some(addr)) => Ok((addr, (**elem_type).clone())),
+            _ => Err(internal_error(String::from("base_ptr: value isn't pointer"))),
+        }
+    }
+
+    fn value_to_int(&self, value: &Value) -> InterpreterResult<i128>
+    {
+        match value.elems() {
+            Some((ScalarKind::Int(_, _), None, elems)) => Ok(elem_to_i128(elems[0])),
+            _ => Err(internal_error(String::from("value_to_int: value isn't integer"))),
+        }
+    }
+
+    fn index_base(&mut self, base: Base, idx: i128) -> InterpreterResult<Base>
+    {
+        let typ = match &base {
+            Base::Place(place) => place.typ.clone(),
+            Base::Value(value) => value.typ.clone(),
+        };
+        match typ {
+            IrType::Array(elem_type, _) => {
+                let elem_size = self.type_size(&elem_type)?;
+                match base {
+                    Base::Place(place) => Ok(Base::Place(Place::new(place.addr.wrapping_add((idx * (elem_size as i128)) as u64), *elem_type))),
+                    Base::Value(value) => {
+                        let offset = (idx as usize) * elem_size;
+                        match value.bytes.get(offset..(offset + elem_size)) {
+                            Some(bytes) => Ok(Base::Value(Value::new(*elem_type, bytes.to_vec()))),
+                            None => Err(self.fault(format!("index {} is out of bounds", idx))),
+                        }
+                    },
+                }
+            },
+            IrType::Ptr(_, _, _) => {
+                let (addr, elem_type) = self.base_ptr(base)?;
+                let elem_size = self.type_size(&elem_type)?;
+                Ok(Base::Place(Place::new(addr.wrapping_add((idx * (elem_size as i128)) as u64), elem_type)))
+            },
+            _ => Err(internal_error(String::from("index_base: type isn't array or pointer"))),
+        }
+    }
+
+    fn dot_base(&mut self, base: Base, idx: usize) -> InterpreterResult<Base>
+    {
+        match base {
+            Base::Place(place) => Ok(Base::Place(self.field_place(&place, idx)?)),
+            Base::Value(value) => {
+                let field_type = self.field_type(&value.typ, idx)?;
+                let offset = self.field_offset(&value.typ, idx)?;
+                let size = self.type_size(&field_type)?;
+                match value.bytes.get(offset..(offset + size)) {
+                    Some(bytes) => Ok(Base::Value(Value::new(field_type, bytes.to_vec()))),
+                    None => Err(internal_error(String::from("dot_base: too short value"))),
+                }
+            },
+        }
+    }
+
+    fn var_index(&mut self, place: Place) -> InterpreterResult<i128>
+    {
+        let value = self.load_place(&place)?;
+        self.value_to_int(&value)
+    }
+
+    fn apply_arg_op(&mut self, base: Base, op: &IrArgOp, frame: Option<&Frame<'a>>) -> InterpreterResult<Base>
+    {
+        match op {
+            IrArgOp::Deref(_) => {
+                let (addr, elem_type) = self.base_ptr(base)?;
+                Ok(Base::Place(Place::new(addr, elem_type)))
+            },
+            IrArgOp::Dot(_, idx) => self.dot_base(base, *idx),
+            IrArgOp::Arrow(_, idx) => {
+                let (addr, elem_type) = self.base_ptr(base)?;
+                Ok(Base::Place(self.field_place(&Place::new(addr, elem_type), *idx)?))
+            },
+            IrArgOp::Index(_, idx) => self.index_base(base, *idx as i128),
+            IrArgOp::GlobalIndex(_, ident) => {
+                let place = self.global_place(ident)?;
+                let idx = self.var_index(place)?;
+                self.index_base(base, idx)
+            },
+            IrArgOp::LocalIndex(_, idx) => {
+                let place = self.frame(frame)?.local_var(*idx)?;
+                let idx2 = self.var_index(place)?;
+                self.index_base(base, idx2)
+            },
+            IrArgOp::CallerFunArgIndex(_, idx) => {
+                let place = self.frame(frame)?.caller_arg(*idx)?;
+                let idx2 = self.var_index(place)?;
+                self.index_base(base, idx2)
+            },
+            IrArgOp::PrivateClosureIndex(_, idx) => {
+                let place = self.closure_var_place(&self.frame(frame)?.private_closure, *idx)?;
+                let idx2 = self.var_index(place)?;
+                self.index_base(base, idx2)
+            },
+            IrArgOp::LocalClosureIndex(_, idx) => {
+                let place = self.closure_var_place(&self.frame(frame)?.local_closure, *idx)?;
+                let idx2 = self.var_index(place)?;
+                self.index_base(base, idx2)
+            },
+            IrArgOp::GlobalClosureIndex(_, idx) => {
+                let place = self.closure_var_place(&self.frame(frame)?.global_closure, *idx)?;
+                let idx2 = self.var_index(place)?;
+                self.index_base(base, idx2)
+            },
+        }
+    }
+
+    fn apply_arg_ops(&mut self, base: Base, ops: &[IrArgOp], frame: Option<&Frame<'a>>) -> InterpreterResult<Base>
+    {
+        let mut base2 = base;
+        for op in ops {
+            base2 = self.apply_arg_op(base2, op, frame)?;
+        }
+        Ok(base2)
+    }
+
+    fn frame<'b>(&self, frame: Option<&'b Frame<'a>>) -> InterpreterResult<&'b Frame<'a>>
+    {
+        match frame {
+            Some(frame) => Ok(frame),
+            None => Err(internal_error(String::from("frame: no frame"))),
+        }
+    }
+
+    fn heap_value(&self, modifier: IrPtrModifier) -> Value
+    {
+        let addr = match modifier {
+            IrPtrModifier::Private => self.private_heap,
+            IrPtrModifier::Local => self.local_heap,
+            _ => self.global_heap,
+        };
+        Value::from_ptr(IrType::Ptr(modifier, IrAccessModifier::None, Box::new(IrType::Uint)), addr)
+    }
+
+    fn arg_var_parts<'b>(&mut self, var: &'b IrArgVar, frame: Option<&Frame<'a>>) -> InterpreterResult<(Base, &'b [IrArgOp], Option<&'b Option<Box<IrType>>>)>
+    {
+        match var {
+            IrArgVar::Global(ident, ops) => Ok((Base::Place(self.global_place(ident)?), ops.as_slice(), None)),
+            IrArgVar::Local(idx, ops) => Ok((Base::Place(self.frame(frame)?.local_var(*idx)?), ops.as_slice(), None)),
+            IrArgVar::CallerFunArg(idx, ops) => Ok((Base::Place(self.frame(frame)?.caller_arg(*idx)?), ops.as_slice(), None)),
+            IrArgVar::PrivateClosure(idx, ops) => Ok((Base::Place(self.closure_var_place(&self.frame(frame)?.private_closure, *idx)?), ops.as_slice(), None)),
+            IrArgVar::LocalClosure(idx, ops) => Ok((Base::Place(self.closure_var_place(&self.frame(frame)?.local_closure, *idx)?), ops.as_slice(), None)),
+            IrArgVar::GlobalClosure(idx, ops) => Ok((Base::Place(self.closure_var_place(&self.frame(frame)?.global_closure, *idx)?), ops.as_slice(), None)),
+            IrArgVar::PrivateHeap(ops) => Ok((Base::Value(self.heap_value(IrPtrModifier::Private)), ops.as_slice(), None)),
+            IrArgVar::LocalHeap(ops) => Ok((Base::Value(self.heap_value(IrPtrModifier::Local)), ops.as_slice(), None)),
+            IrArgVar::GlobalHeap(ops) => Ok((Base::Value(self.heap_value(IrPtrModifier::Global)), ops.as_slice(), None)),
+            IrArgVar::RefGlobal(ident, ops, typ) => Ok((Base::Place(self.global_place(ident)?), ops.as_slice(), Some(typ))),
+            IrArgVar::RefLocal(idx, ops, typ) => Ok((Base::Place(self.frame(frame)?.local_var(*idx)?), ops.as_slice(), Some(typ))),
+            IrArgVar::RefCallerFunArg(idx, ops, typ) => Ok((Base::Place(self.frame(frame)?.caller_arg(*idx)?), ops.as_slice(), Some(typ))),
+            IrArgVar::RefPrivateClosure(idx, ops, typ) => Ok((Base::Place(self.closure_var_place(&self.frame(frame)?.private_closure, *idx)?), ops.as_slice(), Some(typ))),
+            IrArgVar::RefLocalClosure(idx, ops, typ) => Ok((Base::Place(self.closure_var_place(&self.frame(frame)?.local_closure, *idx)?), ops.as_slice(), Some(typ))),
+            IrArgVar::RefGlobalClosure(idx, ops, typ) => Ok((Base::Place(self.closure_var_place(&self.frame(frame)?.global_closure, *idx)?), ops.as_slice(), Some(typ))),
+            _ => Err(internal_error(String::from("arg_var_parts: references to heaps are unsupported"))),
+        }
+    }
+
+    fn arg_var_value(&mut self, var: &IrArgVar, frame: Option<&Frame<'a>>) -> InterpreterResult<Value>
+    {
+        let (base, ops, vector_elem_ptr_type) = self.arg_var_parts(var, frame)?;
+        let base2 = self.apply_arg_ops(base, ops, frame)?;
+        match vector_elem_ptr_type {
+            Some(vector_elem_ptr_type) => {
+                match base2 {
+                    Base::Place(place) => {
+                        let typ = match vector_elem_ptr_type {
+                            Some(ptr_type) => (**ptr_type).clone(),
+                            None => IrType::Ptr(IrPtrModifier::None, IrAccessModifier::None, Box::new(place.typ)),
+                        };
+                        Ok(Value::from_ptr(typ, place.addr))
+                    },
+                    Base::Value(_) => Err(internal_error(String::from("arg_var_value: reference to value"))),
+                }
+            },
+            None => self.base_value(base2),
+        }
+    }
+
+    fn instr_var_place(&mut self, var: &IrInstrVar, frame: Option<&Frame<'a>>) -> InterpreterResult<Place>
+    {
+        let (base, ops) = match var {
+            IrInstrVar::Global(ident, ops) => (Base::Place(self.global_place(ident)?), ops),
+            IrInstrVar::Local(idx, ops) => (Base::Place(self.frame(frame)?.local_var(*idx)?), ops),
+            IrInstrVar::CallerFunArg(idx, ops) => (Base::Place(self.frame(frame)?.caller_arg(*idx)?), ops),
+            IrInstrVar::PrivateClosure(idx, ops) => (Base::Place(self.closure_var_place(&self.frame(frame)?.private_closure, *idx)?), ops),
+            IrInstrVar::LocalClosure(idx, ops) => (Base::Place(self.closure_var_place(&self.frame(frame)?.local_closure, *idx)?), ops),
+            IrInstrVar::GlobalClosure(idx, ops) => (Base::Place(self.closure_var_place(&self.frame(frame)?.global_closure, *idx)?), ops),
+            IrInstrVar::PrivateHeap(ops) => (Base::Value(self.heap_value(IrPtrModifier::Private)), ops),
+            IrInstrVar::LocalHeap(ops) => (Base::Value(self.heap_value(IrPtrModifier::Local)), ops),
+            IrInstrVar::GlobalHeap(ops) => (Base::Value(self.heap_value(IrPtrModifier::Global)), ops),
+        };
+        match self.apply_arg_ops(base, ops.as_slice(), frame)? {
+            Base::Place(place) => Ok(place),
+            Base::Value(_) => Err(internal_error(String::from("instr_var_place: assignment to value"))),
+        }
+    }
+
+    fn convert_value(&mut self, value: Value, typ: &IrType) -> InterpreterResult<Value>
+    {
+        if value.typ == *typ {
+            return Ok(value);
+        }
+        if *typ == IrType::Void {
+            return Ok(Value::void());
+        }
+        let converted_value = match (value.elems(), scalar_kind(typ), vector_type_parts(typ)) {
+            (Some((_, None, elems)), Some(_), _) => Value::from_elems(typ.clone(), elems.as_slice()),
+            (Some((_, None, elems)), None, Some((_, n))) => Value::from_elems(typ.clone(), vec![elems[0]; n].as_slice()),
+            (Some((_, Some(n1), elems)), None, Some((_, n2))) if n1 == n2 => Value::from_elems(typ.clone(), elems.as_slice()),
+            _ => None,
+        };
+        match converted_value {
+            Some(converted_value) => Ok(converted_value),
+            None => {
+                if self.type_size(typ)? == value.bytes.len() {
+                    Ok(Value::new(typ.clone(), value.bytes))
+                } else {
+                    Err(internal_error(String::from("convert_value: can't convert value")))
+                }
+            },
+        }
+    }
+
+    fn cast_value(&mut self, value: Value, typ: &Option<Box<IrType>>) -> InterpreterResult<Value>
+    {
+        match typ {
+            Some(typ) => self.convert_value(value, typ),
+            None => Ok(value),
+        }
+    }
+
+    fn zero_value(&mut self, typ: &IrType) -> InterpreterResult<Value>
+    {
+        let size = self.type_size(typ)?;
+        Ok(Value::new(typ.clone(), vec![0u8; size]))
+    }
+
+    fn eval_value<T: InterpretVar>(&mut self, value: &IrValue<T>, expected_type: Option<&IrType>, frame: Option<&Frame<'a>>) -> InterpreterResult<Value>
+    {
+        match value {
+            IrValue::Char(n, typ) => self.cast_value(Value::from_int(IrType::Char, *n as i128), typ),
+            IrValue::Short(n, typ) => self.cast_value(Value::from_int(IrType::Short, *n as i128), typ),
+            IrValue::Int(n, typ) => self.cast_value(Value::from_int(IrType::Int, *n as i128), typ),
+            IrValue::Long(n, typ) => self.cast_value(Value::from_int(IrType::Long, *n as i128), typ),
+            IrValue::Uchar(n, typ) => self.cast_value(Value::from_int(IrType::Uchar, *n as i128), typ),
+            IrValue::Ushort(n, typ) => self.cast_value(Value::from_int(IrType::Ushort, *n as i128), typ),
+            IrValue::Uint(n, typ) => self.cast_value(Value::from_int(IrType::Uint, *n as i128), typ),
+            IrValue::Ulong(n, typ) => self.cast_value(Value::from_int(IrType::Ulong, *n as i128), typ),
+            IrValue::Float(x, typ) => self.cast_value(Value::from_float(IrType::Float, *x as f64), typ),
+            IrValue::Double(x, typ) => self.cast_value(Value::from_float(IrType::Double, *x), typ),
+            IrValue::SizeT(n, typ) => self.cast_value(Value::from_int(IrType::SizeT, *n as i128), typ),
+            IrValue::PtrdiffT(n, typ) => self.cast_value(Value::from_int(IrType::PtrdiffT, *n as i128), typ),
+            IrValue::IntptrT(n, typ) => self.cast_value(Value::from_int(IrType::IntptrT, *n as i128), typ),
+            IrValue::UintptrT(n, typ) => self.cast_value(Value::from_int(IrType::UintptrT, *n as i128), typ),
+            IrValue::Object(object) => self.eval_object(object, expected_type, frame),
+        }
+    }
+
+    fn eval_values(&mut self, values: &[IrValue<IrArgVar>], frame: Option<&Frame<'a>>) -> InterpreterResult<Vec<Value>>
+    {
+        let mut values2: Vec<Value> = Vec::new();
+        for value in values {
+            values2.push(self.eval_value(value, None, frame)?);
+        }
+        Ok(values2)
+    }
+
+    fn object_type(&self, typ: &Option<Box<IrType>>, expected_type: Option<&IrType>) -> InterpreterResult<IrType>
+    {
+        match (typ, expected_type) {
+            (Some(typ), _) => Ok((**typ).clone()),
+            (None, Some(expected_type)) => Ok(expected_type.clone()),
+            (None, None) => Err(internal_error(String::from("object_type: no object type"))),
+        }
+    }
+
+    fn write_field<T: InterpretVar>(&mut self, bytes: &mut [u8], typ: &IrType, idx: usize, value: &IrValue<T>, frame: Option<&Frame<'a>>) -> InterpreterResult<()>
+    {
+        let (field_type, offset) = match typ {
+            IrType::Array(elem_type, len) if idx < *len => ((**elem_type).clone(), self.type_size(elem_type)? * idx),
+            IrType::Array(_, _) => return Err(internal_error(String::from("write_field: too many array elements"))),
+            _ => (self.field_type(typ, idx)?, self.field_offset(typ, idx)?),
+        };
+        let field_value = self.eval_value(value, Some(&field_type), frame)?;
+        let field_value2 = self.convert_value(field_value, &field_type)?;
+        match bytes.get_mut(offset..(offset + field_value2.bytes.len())) {
+            Some(dst) => {
+                dst.copy_from_slice(field_value2.bytes.as_slice());
+                Ok(())
+            },
+            None => Err(internal_error(String::from("write_field: field is out of object"))),
+        }
+    }
+
+    fn eval_object<T: InterpretVar>(&mut self, object: &IrObject<T>, expected_type: Option<&IrType>, frame: Option<&Frame<'a>>) -> InterpreterResult<Value>
+    {
+        match object {
+            IrObject::String(bytes) => self.string_value(bytes.as_slice()),
+            IrObject::BuiltinVar(ident, typ, typ2) => {
+                let value = self.builtin_var_value(ident.as_str())?;
+                let value2 = self.cast_value(value, typ)?;
+                self.cast_value(value2, typ2)
+            },
+            IrObject::Var(var, typ) => {
+                let value = var.interpret_var(self, frame)?;
+                self.cast_value(value, typ)
+            },
+            IrObject::Vector(values, typ) => {
+                let n = match vector_type_parts(typ) {
+                    Some((_, n)) => n,
+                    None => return Err(internal_error(String::from("eval_object: type isn't vector type"))),
+                };
+                let mut elems: Vec<Elem> = Vec::new();
+                for value in values {
+                    let value2 = self.eval_value(value, None, frame)?;
+                    match value2.elems() {
+                        Some((_, _, elems2)) => elems.extend(elems2),
+                        None => return Err(internal_error(String::from("eval_object: vector element isn't number"))),
+                    }
+                }
+                if elems.len() == 1 {
+                    elems = vec![elems[0]; n];
+                }
+                match Value::from_elems((**typ).clone(), elems.as_slice()) {
+                    Some(value) => Ok(value),
+                    None => Err(internal_error(String::from("eval_object: invalid number of vector elements"))),
+                }
+            },
+            IrObject::Array(values, typ) => {
+                let typ2 = self.object_type(typ, expected_type)?;
+                let mut bytes = vec![0u8; self.type_size(&typ2)?];
+                for (i, value) in values.iter().enumerate() {
+                    self.write_field(bytes.as_mut_slice(), &typ2, i, value, frame)?;
+                }
+                Ok(Value::new(typ2, bytes))
+            },
+            IrObject::Struct(values, field_pairs, typ) => {
+                let typ2 = self.object_type(typ, expected_type)?;
+                let mut bytes = vec![0u8; self.type_size(&typ2)?];
+                for (i, value) in values.iter().enumerate() {
+                    self.write_field(bytes.as_mut_slice(), &typ2, i, value, frame)?;
+                }
+                for field_pair in field_pairs {
+                    self.write_field(bytes.as_mut_slice(), &typ2, field_pair.0, &field_pair.1, frame)?;
+                }
+                Ok(Value::new(typ2, bytes))
+            },
+            IrObject::Union(idx, value, typ) => {
+                let typ2 = self.object_type(typ, expected_type)?;
+                let mut bytes = vec![0u8; self.type_size(&typ2)?];
+                self.write_field(bytes.as_mut_slice(), &typ2, *idx, value, frame)?;
+                Ok(Value::new(typ2, bytes))
+            },
+            IrObject::Closure(field_pairs, typ) => {
+                let typ2 = self.object_type(typ, expected_type)?;
+                let mut bytes = vec![0u8; self.type_size(&typ2)?];
+                for field_pair in field_pairs {
+                    self.write_field(bytes.as_mut_slice(), &typ2, field_pair.0, &field_pair.1, frame)?;
+                }
+                Ok(Value::new(typ2, bytes))
+            },
+            IrObject::Sizeof(typ, typ2) => {
+                let size = self.type_size(typ)?;
+                self.cast_value(Value::from_int(IrType::SizeT, size as i128), typ2)
+            },
+            IrObject::CallerFunIndex(_, idx, typ) => self.cast_value(Value::from_int(IrType::Uint, *idx as i128), typ),
+        }
+    }
+
+    fn eval_elem_bin_op(&self, bin_op: BinOp, kind: ScalarKind, elem1: Elem, elem2: Elem) -> InterpreterResult<Elem>
+    {
+        match (kind, elem1, elem2) {
+            (ScalarKind::Int(bits, is_signed), Elem::Int(a), Elem::Int(b)) => {
+                let count = (b & ((bits - 1) as i128)) as u32;
+                let c = match bin_op {
+                    BinOp::Mul => a.wrapping_mul(b),
+                    BinOp::Div => {
+                        if b == 0 {
+                            return Err(self.fault(String::from("division by zero")));
+                        }
+                        a.wrapping_div(b)
+                    },
+                    BinOp::Rem => {
+                        if b == 0 {
+                            return Err(self.fault(String::from("division by zero")));
+                        }
+                        a.wrapping_rem(b)
+                    },
+                    BinOp::Add => a.wrapping_add(b),
+                    BinOp::Sub => a.wrapping_sub(b),
+                    BinOp::Shl => a.wrapping_shl(count),
+                    BinOp::Shr => a >> count,
+                    BinOp::Eq => (a == b) as i128,
+                    BinOp::Ne => (a != b) as i128,
+                    BinOp::Lt => (a < b) as i128,
+                    BinOp::Ge => (a >= b) as i128,
+                    BinOp::Gt => (a > b) as i128,
+                    BinOp::Le => (a <= b) as i128,
+                    BinOp::And => a & b,
+                    BinOp::Xor => a ^ b,
+                    BinOp::Or => a | b,
+                };
+                if bin_op.is_cmp() {
+                    Ok(Elem::Int(c))
+                } else {
+                    Ok(Elem::Int(wrap_int(c, bits, is_signed)))
+                }
+            },
+            (ScalarKind::Float(bits), Elem::Float(x), Elem::Float(y)) => {
+                let z = match bin_op {
+                    BinOp::Mul => x * y,
+                    BinOp::Div => x / y,
+                    BinOp::Add => x + y,
+                    BinOp::Sub => x - y,
+                    BinOp::Eq => return Ok(Elem::Int((x == y) as i128)),
+                    BinOp::Ne => return Ok(Elem::Int((x != y) as i128)),
+                    BinOp::Lt => return Ok(Elem::Int((x < y) as i128)),
+                    BinOp::Ge => return Ok(Elem::Int((x >= y) as i128)),
+                    BinOp::Gt => return Ok(Elem::Int((x > y) as i128)),
+                    BinOp::Le => return Ok(Elem::Int((x <= y) as i128)),
+                    _ => return Err(internal_error(String::from("eval_elem_bin_op: invalid operation for floating-point numbers"))),
+                };
+                Ok(Elem::Float(round_float(z, bits)))
+            },
+            _ => Err(internal_error(String::from("eval_elem_bin_op: invalid elements"))),
+        }
+    }
+
+    fn eval_ptr_bin_op(&mut self, bin_op: BinOp, value1: Value, value2: Value) -> InterpreterResult<Value>
+    {
+        if bin_op.is_cmp() {
+            let value3 = self.convert_value(value1, &IrType::Ulong)?;
+            let value4 = self.convert_value(value2, &IrType::Ulong)?;
+            return self.eval_bin_op(bin_op, value3, value4);
+        }
+        match (bin_op, value1.ptr(), value2.ptr()) {
+            (BinOp::Sub, Some(addr1), Some(addr2)) => {
+                let elem_size = match &value1.typ {
+                    IrType::Ptr(_, _, elem_type) => self.type_size(elem_type)?.max(1),
+                    _ => 1,
+                };
+                Ok(Value::from_int(IrType::PtrdiffT, ((addr1.wrapping_sub(addr2) as i64) as i128) / (elem_size as i128)))
+            },
+            (BinOp::Add | BinOp::Sub, Some(addr), None) | (BinOp::Add, None, Some(addr)) => {
+                let (ptr_value, int_value) = if value1.ptr().is_some() { (value1, value2) } else { (value2, value1) };
+                let elem_size = match &ptr_value.typ {
+                    IrType::Ptr(_, _, elem_type) => self.type_size(elem_type)?.max(1),
+                    _ => 1,
+                };
+                let n = self.value_to_int(&int_value)? * (elem_size as i128);
+                let new_addr = if bin_op == BinOp::Sub { addr.wrapping_sub(n as u64) } else { addr.wrapping_add(n as u64) };
+                Ok(Value::from_ptr(ptr_value.typ, new_addr))
+            },
+            _ => Err(internal_error(String::from("eval_ptr_bin_op: invalid operation for pointers"))),
+        }
+    }
+
+    fn eval_bin_op(&mut self, bin_op: BinOp, value1: Value, value2: Value) -> InterpreterResult<Value>
+    {
+        if value1.ptr().is_some() || value2.ptr().is_some() {
+            return self.eval_ptr_bin_op(bin_op, value1, value2);
+        }
+        let (elems1, n1) = match value1.elems() {
+            Some((_, n, elems)) => (elems, n),
+            None => return Err(internal_error(String::from("eval_bin_op: first operand isn't number"))),
+        };
+        let (elems2, n2) = match value2.elems() {
+            Some((_, n, elems)) => (elems, n),
+            None => return Err(internal_error(String::from("eval_bin_op: second operand isn't number"))),
+        };
+        match (n1, n2) {
+            (None, None) => {
+                let typ = if bin_op.is_shift() {
+                    promoted_type(&value1.typ)
+                } else {
+                    common_type(&value1.typ, &value2.typ)
+                };
+                let kind = match scalar_kind(&typ) {
+                    Some(kind) => kind,
+                    None => return Err(internal_error(String::from("eval_bin_op: no scalar kind"))),
+                };
+                let elem1 = convert_elem(elems1[0], kind);
+                let elem2 = if bin_op.is_shift() { convert_elem(elems2[0], ScalarKind::Int(64, false)) } else { convert_elem(elems2[0], kind) };
+                let elem3 = self.eval_elem_bin_op(bin_op, kind, elem1, elem2)?;
+                if bin_op.is_cmp() {
+                    Ok(Value::from_int(IrType::Int, elem_to_i128(elem3)))
+                } else {
+                    Ok(Value::from_elems(typ, &[elem3]).unwrap_or(Value::void()))
+                }
+            },
+            _ => {
+                let typ = if n1.is_some() { value1.typ.clone() } else { value2.typ.clone() };
+                let (elem_type, n) = match vector_type_parts(&typ) {
+                    Some(pair) => pair,
+                    None => return Err(internal_error(String::from("eval_bin_op: type isn't vector type"))),
+                };
+                let kind = match scalar_kind(&elem_type) {
+                    Some(kind) => kind,
+                    None => return Err(internal_error(String::from("eval_bin_op: no scalar kind"))),
+                };
+                if (n1.is_some() && elems1.len() != n) || (n2.is_some() && elems2.len() != n) {
+                    return Err(internal_error(String::from("eval_bin_op: vector lengths aren't equal")));
+                }
+                let mut elems3: Vec<Elem> = Vec::new();
+                for i in 0..n {
+                    let elem1 = convert_elem(if n1.is_some() { elems1[i] } else { elems1[0] }, kind);
+                    let elem2 = convert_elem(if n2.is_some() { elems2[i] } else { elems2[0] }, kind);
+                    let elem3 = self.eval_elem_bin_op(bin_op, kind, elem1, elem2)?;
+                    if bin_op.is_cmp() {
+                        elems3.push(Elem::Int(-elem_to_i128(elem3)));
+                    } else {
+                        elems3.push(elem3);
+                    }
+                }
+                let typ2 = if bin_op.is_cmp() {
+                    match vector_type(&cmp_elem_type(kind), n) {
+                        Some(cmp_type) => cmp_type,
+                        None => return Err(internal_error(String::from("eval_bin_op: no comparison type"))),
+                    }
+                } else {
+                    typ
+                };
+                match Value::from_elems(typ2, elems3.as_slice()) {
+                    Some(value) => Ok(value),
+                    None => Err(internal_error(String::from("eval_bin_op: can't create value"))),
+                }
+            },
+        }
+    }
+
+    fn eval_unary_op(&mut self, is_neg: bool, value: Value) -> InterpreterResult<Value>
+    {
+        let (elems, n) = match value.elems() {
+            Some((_, n, elems)) => (elems, n),
+            None => return Err(internal_error(String::from("eval_unary_op: operand isn't number"))),
+        };
+        let typ = if n.is_some() { value.typ.clone() } else { promoted_type(&value.typ) };
+        let kind = match (scalar_kind(&typ), vector_type_parts(&typ)) {
+            (Some(kind), _) => kind,
+            (None, Some((elem_type, _))) => {
+                match scalar_kind(&elem_type) {
+                    Some(kind) => kind,
+                    None => return Err(internal_error(String::from("eval_unary_op: no scalar kind"))),
+                }
+            },
+            (None, None) => return Err(internal_error(String::from("eval_unary_op: no scalar kind"))),
+        };
+        let mut elems2: Vec<Elem> = Vec::new();
+        for elem in elems {
+            match (convert_elem(elem, kind), kind) {
+                (Elem::Int(a), ScalarKind::Int(bits, is_signed)) => {
+                    if is_neg {
+                        elems2.push(Elem::Int(wrap_int(a.wrapping_neg(), bits, is_signed)));
+                    } else {
+                        elems2.push(Elem::Int(wrap_int(!a, bits, is_signed)));
+                    }
+                },
+                (Elem::Float(x), _) if is_neg => elems2.push(Elem::Float(-x)),
+                _ => return Err(internal_error(String::from("eval_unary_op: invalid operand"))),
+            }
+        }
+        match Value::from_elems(typ, elems2.as_slice()) {
+            Some(value) => Ok(value),
+            None => Err(internal_error(String::from("eval_unary_op: can't create value"))),
+        }
+    }
+
+    fn eval_op(&mut self, op: &IrOp, expected_type: Option<&IrType>, frame: Option<&Frame<'a>>) -> InterpreterResult<Value>
+    {
+        let (bin_op, value1, value2) = match op {
+            IrOp::Load(value) => return self.eval_value(value, expected_type, frame),
+            IrOp::Neg(value) => {
+                let value2 = self.eval_value(value, None, frame)?;
+                return self.eval_unary_op(true, value2);
+            },
+            IrOp::Not(value) => {
+                let value2 = self.eval_value(value, None, frame)?;
+                return self.eval_unary_op(false, value2);
+            },
+            IrOp::Mul(value1, value2) => (BinOp::Mul, value1, value2),
+            IrOp::Div(value1, value2) => (BinOp::Div, value1, value2),
+            IrOp::Rem(value1, value2) => (BinOp::Rem, value1, value2),
+            IrOp::Add(value1, value2) => (BinOp::Add, value1, value2),
+            IrOp::Sub(value1, value2) => (BinOp::Sub, value1, value2),
+            IrOp::Shl(value1, value2) => (BinOp::Shl, value1, value2),
+            IrOp::Shr(value1, value2) => (BinOp::Shr, value1, value2),
+            IrOp::Eq(value1, value2) => (BinOp::Eq, value1, value2),
+            IrOp::Ne(value1, value2) => (BinOp::Ne, value1, value2),
+            IrOp::Lt(value1, value2) => (BinOp::Lt, value1, value2),
+            IrOp::Ge(value1, value2) => (BinOp::Ge, value1, value2),
+            IrOp::Gt(value1, value2) => (BinOp::Gt, value1, value2),
+            IrOp::Le(value1, value2) => (BinOp::Le, value1, value2),
+            IrOp::And(value1, value2) => (BinOp::And, value1, value2),
+            IrOp::Xor(value1, value2) => (BinOp::Xor, value1, value2),
+            IrOp::Or(value1, value2) => (BinOp::Or, value1, value2),
+            IrOp::CallBuiltinFun(ident, typ, values) => {
+                let values2 = self.eval_values(values.as_slice(), frame)?;
+                return self.call_builtin_fun(ident.as_str(), typ, values2);
+            },
+            IrOp::CallFun(_, _, _, _) | IrOp::CallFunWithoutPanic(_, _, _) => return Err(internal_error(String::from("eval_op: unexpected function call"))),
+        };
+        let value3 = self.eval_value(value1, None, frame)?;
+        let value4 = self.eval_value(value2, None, frame)?;
+        self.eval_bin_op(bin_op, value3, value4)
+    }
+
+    fn is_true(&self, value: &Value) -> InterpreterResult<bool>
+    {
+        match value.elems() {
+            Some((_, None, elems)) => Ok(elem_is_true(elems[0])),
+            _ => Err(internal_error(String::from("is_true: condition isn't scalar"))),
+        }
+    }
+
+    fn case_value(&mut self, case_value: &IrCaseValue) -> InterpreterResult<Value>
+    {
+        match case_value {
+            IrCaseValue::Char(n, typ) => self.cast_value(Value::from_int(IrType::Char, *n as i128), typ),
+            IrCaseValue::Short(n, typ) => self.cast_value(Value::from_int(IrType::Short, *n as i128), typ),
+            IrCaseValue::Int(n, typ) => self.cast_value(Value::from_int(IrType::Int, *n as i128), typ),
+            IrCaseValue::Long(n, typ) => self.cast_value(Value::from_int(IrType::Long, *n as i128), typ),
+            IrCaseValue::Uchar(n, typ) => self.cast_value(Value::from_int(IrType::Uchar, *n as i128), typ),
+            IrCaseValue::Ushort(n, typ) => self.cast_value(Value::from_int(IrType::Ushort, *n as i128), typ),
+            IrCaseValue::Uint(n, typ) => self.cast_value(Value::from_int(IrType::Uint, *n as i128), typ),
+            IrCaseValue::Ulong(n, typ) => self.cast_value(Value::from_int(IrType::Ulong, *n as i128), typ),
+            IrCaseValue::SizeT(n, typ) => self.cast_value(Value::from_int(IrType::SizeT, *n as i128), typ),
+            IrCaseValue::PtrdiffT(n, typ) => self.cast_value(Value::from_int(IrType::PtrdiffT, *n as i128), typ),
+            IrCaseValue::IntptrT(n, typ) => self.cast_value(Value::from_int(IrType::IntptrT, *n as i128), typ),
+            IrCaseValue::UintptrT(n, typ) => self.cast_value(Value::from_int(IrType::UintptrT, *n as i128), typ),
+            IrCaseValue::BuiltinVar(ident, typ, typ2) => {
+                let value = self.builtin_var_value(ident.as_str())?;
+                let value2 = self.cast_value(value, typ)?;
+                self.cast_value(value2, typ2)
+            },
+        }
+    }
+
+    fn select_case(&mut self, value: &Value, cases: &'a [IrCase]) -> InterpreterResult<Option<&'a IrBlock>>
+    {
+        let mut default_block: Option<&'a IrBlock> = None;
+        for case in cases {
+            match case {
+                IrCase::Case(case_value, block) => {
+                    let case_value2 = self.case_value(case_value)?;
+                    let case_value3 = self.convert_value(case_value2, &value.typ)?;
+                    if case_value3.bytes == value.bytes {
+                        return Ok(Some(block));
+                    }
+                },
+                IrCase::Default(block) => default_block = Some(block),
+            }
+        }
+        Ok(default_block)
+    }
+
+    fn enter_block(&mut self, block: &'a IrBlock, kind: BlockKind, item: &mut WorkItem<'a>) -> InterpreterResult<()>
+    {
+        let stack_size = self.stack_size()?;
+        let frame = item.frame_mut()?;
+        let local_var_count = frame.local_vars.len();
+        for local_var_pair in block.local_var_pairs() {
+            let IrLocalVarPair(modifier, typ) = local_var_pair;
+            let key = local_var_pair as *const IrLocalVarPair as usize;
+            let place = match modifier {
+                IrLocalVarModifier::Local => self.alloc_local_var(key, typ)?,
+                IrLocalVarModifier::Global => self.alloc_global_local_var(key, typ)?,
+                _ => self.alloc_stack(typ)?,
+            };
+            frame.local_vars.push(place);
+        }
+        frame.blocks.push(BlockState {
+                block,
+                instr_idx: 0,
+                kind,
+                local_var_count,
+                stack_size,
+        });
+        Ok(())
+    }
+
+    fn leave_block(&mut self, item: &mut WorkItem<'a>) -> InterpreterResult<BlockKind>
+    {
+        let frame = item.frame_mut()?;
+        match frame.blocks.pop() {
+            Some(state) => {
+                frame.local_vars.truncate(state.local_var_count);
+                self.truncate_stack(state.stack_size);
+                Ok(state.kind)
+            },
+            None => Err(internal_error(String::from("leave_block: no block"))),
+        }
+    }
+
+    fn closure_place(&mut self, fun_type: &IrType, closure_type: &Option<Box<IrType>>, fun_place: &Place) -> InterpreterResult<Option<Place>>
+    {
+        match closure_type {
+            Some(closure_type) => {
+                let field_idxs: Vec<usize> = match fun_type {
+                    IrType::Struct(ident) => {
+                        match self.tree.struct1(ident) {
+                            Some(struct1) => {
+                                match &*struct1.borrow() {
+                                    IrStruct::Struct(field_types) => field_types.iter().enumerate().filter(|(_, t)| **t == *closure_type).map(|(i, _)| i).collect(),
+                                    IrStruct::Closure(field_types) => field_types.iter().filter(|(_, t)| **t == *closure_type).map(|(i, _)| *i).collect(),
+                                }
+                            },
+                            None => return Err(internal_error(format!("closure_place: no structure {}", ident))),
+                        }
+                    },
+                    _ => return Err(internal_error(String::from("closure_place: function type isn't structure"))),
+                };
+                match field_idxs.first() {
+                    Some(idx) => Ok(Some(self.field_place(fun_place, *idx)?)),
+                    None => Err(internal_error(String::from("closure_place: no field for closure"))),
+                }
+            },
+            None => Ok(None),
+        }
+    }
+
+    fn call_fun(&mut self, ident: &'a String, args: Vec<Value>, cont: Cont<'a>, call_poses: Vec<Pos>, item: &mut WorkItem<'a>) -> InterpreterResult<()>
+    {
+        let funs = self.funs;
+        match funs.get(ident) {
+            Some(IrFun::Fun(_, arg_types, ret_type, block, _, _, _, _)) => {
+                if args.len() != arg_types.len() {
+                    return Err(internal_error(format!("call_fun: invalid number of arguments for function {}", ident)));
+                }
+                let stack_size = self.stack_size()?;
+                let mut frame = Frame::new((**ret_type).clone(), stack_size, cont, call_poses);
+                for (arg, arg_type) in args.into_iter().zip(arg_types.iter()) {
+                    let place = self.alloc_stack(arg_type)?;
+                    self.store(&place, arg)?;
+                    frame.local_vars.push(place);
+                }
+                item.frames.push(frame);
+                self.enter_block(block, BlockKind::Block, item)
+            },
+            Some(IrFun::Caller(fun_type, arg_types, ret_type, caller_funs, _, _, _, _)) => {
+                if args.len() != arg_types.len() + 1 {
+                    return Err(internal_error(format!("call_fun: invalid number of arguments for caller {}", ident)));
+                }
+                let fun_value = self.convert_value(args[0].clone(), fun_type)?;
+                let idx_value = match self.dot_base(Base::Value(fun_value.clone()), 0)? {
+                    Base::Value(value) => value,
+                    Base::Place(_) => return Err(internal_error(String::from("call_fun: no function index"))),
+                };
+                let idx = self.value_to_int(&idx_value)? as usize;
+                match caller_funs.fun(idx) {
+                    Some(IrCallerFun::Fun(fun_ident)) => self.call_fun(fun_ident, args[1..].to_vec(), cont, call_poses, item),
+                    Some(IrCallerFun::Lambda(private_closure_type, local_closure_type, global_closure_type, start_idx, block)) => {
+                        let stack_size = self.stack_size()?;
+                        let mut frame = Frame::new((**ret_type).clone(), stack_size, cont, call_poses);
+                        let fun_place = self.alloc_stack(fun_type)?;
+                        self.store(&fun_place, fun_value)?;
+                        frame.caller_args.push(fun_place.clone());
+                        for (arg, arg_type) in args[1..].iter().zip(arg_types.iter()) {
+                            let place = self.alloc_stack(arg_type)?;
+                            self.store(&place, arg.clone())?;
+                            frame.caller_args.push(place);
+                        }
+                        frame.private_closure = self.closure_place(fun_type, private_closure_type, &fun_place)?;
+                        frame.local_closure = self.closure_place(fun_type, local_closure_type, &fun_place)?;
+                        frame.global_closure = self.closure_place(fun_type, global_closure_type, &fun_place)?;
+                        for _ in 0..*start_idx {
+                            frame.local_vars.push(Place::new(0, IrType::Void));
+                        }
+                        for (arg, arg_type) in args[1..].iter().zip(arg_types.iter()) {
+                            let place = self.alloc_stack(arg_type)?;
+                            self.store(&place, arg.clone())?;
+                            frame.local_vars.push(place);
+                        }
+                        item.frames.push(frame);
+                        self.enter_block(block, BlockKind::Block, item)
+                    },
+                    Some(IrCallerFun::InlineFun(_, _)) => Err(internal_error(format!("call_fun: inline functions of caller {} are unsupported", ident))),
+                    None => Err(self.fault(format!("invalid function index {} for caller {}", idx, ident))),
+                }
+            },
+            None => Err(internal_error(format!("call_fun: no function {}", ident))),
+        }
+    }
+
+    fn return_value(&mut self, value: Option<Value>, item: &mut WorkItem<'a>) -> InterpreterResult<()>
+    {
+        let frame = match item.frames.pop() {
+            Some(frame) => frame,
+            None => return Err(internal_error(String::from("return_value: no frame"))),
+        };
+        self.truncate_stack(frame.stack_size);
+        let value2 = match value {
+            Some(value) => self.convert_value(value, &frame.ret_type)?,
+            None => self.zero_value(&frame.ret_type)?,
+        };
+        if item.frames.is_empty() {
+            item.state = WorkItemState::Done;
+            Ok(())
+        } else {
+            self.continue_with(value2, frame.cont, item)
+        }
+    }
+
+    fn continue_with(&mut self, value: Value, cont: Cont<'a>, item: &mut WorkItem<'a>) -> InterpreterResult<()>
+    {
+        match cont {
+            Cont::None => Ok(()),
+            Cont::Assign(var) => {
+                let place = self.instr_var_place(var, Some(item.frame()?))?;
+                self.store(&place, value)
+            },
+            Cont::Return => self.return_value(Some(value), item),
+            Cont::If(block1, block2) => {
+                if self.is_true(&value)? {
+                    self.enter_block(block1, BlockKind::Block, item)
+                } else {
+                    self.enter_block(block2, BlockKind::Block, item)
+                }
+            },
+            Cont::Switch(cases) => {
+                match self.select_case(&value, cases)? {
+                    Some(block) => self.enter_block(block, BlockKind::Block, item),
+                    None => Ok(()),
+                }
+            },
+        }
+    }
+
+    fn exec_op(&mut self, op: &'a IrOp, cont: Cont<'a>, item: &mut WorkItem<'a>) -> InterpreterResult<()>
+    {
+        match op {
+            IrOp::CallFun(ident, values, pos, panic_poses) => {
+                let args = self.eval_values(values.as_slice(), Some(item.frame()?))?;
+                let mut call_poses = vec![pos.clone()];
+                call_poses.extend_from_slice(panic_poses.as_slice());
+                self.call_fun(ident, args, cont, call_poses, item)
+            },
+            IrOp::CallFunWithoutPanic(ident, values, _) => {
+                let args = self.eval_values(values.as_slice(), Some(item.frame()?))?;
+                self.call_fun(ident, args, cont, Vec::new(), item)
+            },
+            _ => {
+                match cont {
+                    Cont::Assign(var) => {
+                        let frame = item.frame()?;
+                        let place = self.instr_var_place(var, Some(frame))?;
+                        let value = self.eval_op(op, Some(&place.typ), Some(frame))?;
+                        self.store(&place, value)
+                    },
+                    Cont::Return => {
+                        let frame = item.frame()?;
+                        let value = self.eval_op(op, Some(&frame.ret_type), Some(frame))?;
+                        self.continue_with(value, cont, item)
+                    },
+                    _ => {
+                        let value = self.eval_op(op, None, Some(item.frame()?))?;
+                        self.continue_with(value, cont, item)
+                    },
+                }
+            },
+        }
+    }
+
+    fn raise_panic(&mut self, msg: &str, pos: &Pos, panic_poses: &[Pos], item: &mut WorkItem<'a>)
+    {
+        if self.panic.is_none() {
+            let mut poses = vec![pos.clone()];
+            poses.extend_from_slice(panic_poses);
+            let mut call_count = 0usize;
+            for frame in item.frames.iter().rev() {
+                if !frame.call_poses.is_empty() {
+                    if call_count < MAX_PANIC_CALL_COUNT {
+                        poses.extend_from_slice(frame.call_poses.as_slice());
+                    }
+                    call_count += 1;
+                }
+            }
+            self.panic = Some(Panic {
+                    msg: String::from(msg),
+                    poses,
+                    global_id: [self.global_id[0] as u32, self.global_id[1] as u32, self.global_id[2] as u32],
+                    is_truncated: call_count > MAX_PANIC_CALL_COUNT,
+            });
+        }
+        item.frames.clear();
+        item.state = WorkItemState::Done;
+    }
+
+    fn exec_instr(&mut self, instr: &'a IrInstr, item: &mut WorkItem<'a>) -> InterpreterResult<()>
+    {
+        match instr {
+            IrInstr::Op(op) => self.exec_op(op, Cont::None, item),
+            IrInstr::Assign(var, op) => self.exec_op(op, Cont::Assign(var), item),
+            IrInstr::Return(None) => self.return_value(None, item),
+            IrInstr::Return(Some(op)) => self.exec_op(op, Cont::Return, item),
+            IrInstr::Break => {
+                loop {
+                    if item.frame()?.blocks.is_empty() {
+                        return Err(internal_error(String::from("exec_instr: break outside loop")));
+                    }
+                    if self.leave_block(item)? == BlockKind::Loop {
+                        return Ok(());
+                    }
+                }
+            },
+            IrInstr::Continue => {
+                loop {
+                    let frame = item.frame_mut()?;
+                    match frame.blocks.last_mut() {
+                        Some(state) if state.kind == BlockKind::Loop => {
+                            state.instr_idx = 0;
+                            return Ok(());
+                        },
+                        Some(_) => {
+                            self.leave_block(item)?;
+                        },
+                        None => return Err(internal_error(String::from("exec_instr: continue outside loop"))),
+                    }
+                }
+            },
+            IrInstr::Block(block) => self.enter_block(block, BlockKind::Block, item),
+            IrInstr::If(op, block1, block2) => self.exec_op(op, Cont::If(block1, block2), item),
+            IrInstr::Switch(op, cases) => self.exec_op(op, Cont::Switch(cases.as_slice()), item),
+            IrInstr::Loop(block) => self.enter_block(block, BlockKind::Loop, item),
+            IrInstr::Panic(msg, pos, panic_poses) => {
+                self.raise_panic(msg.as_str(), pos, panic_poses.as_slice(), item);
+                Ok(())
+            },
+        }
+    }
+
+    fn step(&mut self, item: &mut WorkItem<'a>) -> InterpreterResult<()>
+    {
+        let (block, instr_idx, kind) = match item.frames.last() {
+            Some(frame) => {
+                match frame.blocks.last() {
+                    Some(state) => (state.block, state.instr_idx, state.kind),
+                    None => return self.return_value(None, item),
+                }
+            },
+            None => {
+                item.state = WorkItemState::Done;
+                return Ok(());
+            },
+        };
+        match block.instrs().get(instr_idx) {
+            Some(instr) => {
+                if let Some(state) = item.frame_mut()?.blocks.last_mut() {
+                    state.instr_idx += 1;
+                }
+                self.exec_instr(instr, item)
+            },
+            None => {
+                match kind {
+                    BlockKind::Loop => {
+                        if let Some(state) = item.frame_mut()?.blocks.last_mut() {
+                            state.instr_idx = 0;
+                        }
+                    },
+                    BlockKind::Block => {
+                        self.leave_block(item)?;
+                    },
+                }
+                Ok(())
+            },
+        }
+    }
+
+    fn run_work_item(&mut self, item: &mut WorkItem<'a>) -> InterpreterResult<()>
+    {
+        self.local_id = item.local_id;
+        self.global_id = item.global_id;
+        self.stack = item.stack;
+        self.private_heap = item.private_heap;
+        while item.state == WorkItemState::Ready {
+            match self.step_limit {
+                Some(step_limit) if self.step_count >= step_limit => return Err(InterpreterError::StepLimit),
+                _ => (),
+            }
+            self.step_count += 1;
+            self.step(item)?;
+            if self.is_barrier {
+                self.is_barrier = false;
+                if item.state == WorkItemState::Ready {
+                    item.state = WorkItemState::Barrier;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn run_work_group(&mut self, ident: &'a String, kernel_args: &[KernelArgValue], heap_flags: (IrPrivateHeapFlag, IrLocalHeapFlag), group_id: [usize; 3]) -> InterpreterResult<()>
+    {
+        let region_count = self.memory.region_count();
+        self.group_id = group_id;
+        self.local_vars.clear();
+        self.local_heap = 0;
+        if heap_flags.1 == IrLocalHeapFlag::Heap {
+            self.local_heap = self.heap_region(self.local_heap_size);
+        }
+        let mut args: Vec<Value> = Vec::new();
+        for kernel_arg in kernel_args {
+            match kernel_arg {
+                KernelArgValue::Value(value) => args.push(value.clone()),
+                KernelArgValue::Local(typ, size) => {
+                    let addr = self.memory.add_region(vec![0u8; *size]);
+                    args.push(Value::from_ptr(typ.clone(), addr));
+                },
+            }
+        }
+        let nd_range = self.nd_range;
+        let mut items: Vec<WorkItem<'a>> = Vec::new();
+        for z in 0..nd_range.local_size[2] {
+            for y in 0..nd_range.local_size[1] {
+                for x in 0..nd_range.local_size[0] {
+                    let local_id = [x, y, z];
+                    let mut global_id = [0usize; 3];
+                    for i in 0..3 {
+                        global_id[i] = nd_range.global_offset[i] + group_id[i] * nd_range.local_size[i] + local_id[i];
+                    }
+                    let stack = self.memory.add_region(Vec::new());
+                    let private_heap = if heap_flags.0 == IrPrivateHeapFlag::Heap {
+                        self.heap_region(self.private_heap_size)
+                    } else {
+                        0
+                    };
+                    let mut item = WorkItem {
+                        local_id,
+                        global_id,
+                        stack,
+                        private_heap,
+                        frames: Vec::new(),
+                        state: WorkItemState::Ready,
+                    };
+                    self.local_id = local_id;
+                    self.global_id = global_id;
+                    self.stack = stack;
+                    self.private_heap = private_heap;
+                    self.call_fun(ident, args.clone(), Cont::None, Vec::new(), &mut item)?;
+                    items.push(item);
+                }
+            }
+        }
+        loop {
+            for item in items.iter_mut() {
+                if item.state == WorkItemState::Ready {
+                    self.run_work_item(item)?;
+                }
+            }
+            let mut is_barrier = false;
+            for item in items.iter_mut() {
+                if item.state == WorkItemState::Barrier {
+                    item.state = WorkItemState::Ready;
+                    is_barrier = true;
+                }
+            }
+            if !is_barrier {
+                break;
+            }
+        }
+        self.memory.truncate_regions(region_count);
+        Ok(())
+    }
+
+    fn builtin_var_value(&self, ident: &str) -> InterpreterResult<Value>
+    {
+        match ident {
+            "CLK_LOCAL_MEM_FENCE" => Ok(Value::from_int(IrType::Uint, 1)),
+            "CLK_GLOBAL_MEM_FENCE" => Ok(Value::from_int(IrType::Uint, 2)),
+            "MAXFLOAT" => Ok(Value::from_float(IrType::Float, f32::MAX as f64)),
+            "HUGE_VALF" | "INFINITY" => Ok(Value::from_float(IrType::Float, f64::INFINITY)),
+            "NAN" => Ok(Value::from_float(IrType::Float, f64::NAN)),
+            "HUGE_VAL" => Ok(Value::from_float(IrType::Double, f64::INFINITY)),
+            "FLOAT_DIG" => Ok(Value::from_int(IrType::Uint, 6)),
+            "FLOAT_MANT_DIG" => Ok(Value::from_int(IrType::Uint, 24)),
+            "FLOAT_MAX_10_EXP" => Ok(Value::from_int(IrType::Int, 38)),
+            "FLOAT_MAX_EXP" => Ok(Value::from_int(IrType::Int, 128)),
+            "FLOAT_MIN_10_EXP" => Ok(Value::from_int(IrType::Int, -37)),
+            "FLOAT_MIN_EXP" => Ok(Value::from_int(IrType::Int, -125)),
+            "DOUBLE_DIG" => Ok(Value::from_int(IrType::Uint, 15)),
+            "DOUBLE_MANT_DIG" => Ok(Value::from_int(IrType::Uint, 53)),
+            "DOUBLE_MAX_10_EXP" => Ok(Value::from_int(IrType::Int, 308)),
+            "DOUBLE_MAX_EXP" => Ok(Value::from_int(IrType::Int, 1024)),
+            "DOUBLE_MIN_10_EXP" => Ok(Value::from_int(IrType::Int, -307)),
+            "DOUBLE_MIN_EXP" => Ok(Value::from_int(IrType::Int, -1021)),
+            _ => Err(internal_error(format!("builtin_var_value: unsupported builtin variable {}", ident))),
+        }
+    }
+
+    fn work_item_fun_value(&self, ident: &str, dim: usize) -> usize
+    {
+        let nd_range = self.nd_range;
+        if dim >= nd_range.work_dim {
+            return match ident {
+                "get_global_size" | "get_local_size" | "get_num_groups" => 1,
+                _ => 0,
+            };
+        }
+        match ident {
+            "get_global_size" => nd_range.global_size[dim],
+            "get_global_id" => self.global_id[dim],
+            "get_local_size" => nd_range.local_size[dim],
+            "get_local_id" => self.local_id[dim],
+            "get_num_groups" => nd_range.num_groups()[dim],
+            "get_group_id" => self.group_id[dim],
+            _ => nd_range.global_offset[dim],
+        }
+    }
+
+    fn alloc(&mut self, modifier: IrPtrModifier, typ: &Option<Box<IrType>>, args: &[Value], is_atomic: bool) -> InterpreterResult<Value>
+    {
+        let typ2 = match typ {
+            Some(typ2) => (**typ2).clone(),
+            None => return Err(internal_error(String::from("alloc: no type"))),
+        };
+        let heap = match args.first().and_then(|arg| arg.ptr()) {
+            Some(heap) => heap,
+            None => return Err(internal_error(String::from("alloc: no heap"))),
+        };
+        let layout = self.layout(&typ2)?;
+        let (size, align) = (layout.size as u32, layout.align as u32);
+        let ptr_type = IrType::Ptr(modifier, IrAccessModifier::None, Box::new(typ2));
+        let offset_addr = heap + (HEAP_OFFSET_INDEX * 4) as u64;
+        let capacity = self.read_u32(heap + (HEAP_CAPACITY_INDEX * 4) as u64)?;
+        let mut offset = self.read_u32(offset_addr)?;
+        if is_atomic {
+            self.write_memory(offset_addr, &offset.wrapping_add(size).wrapping_add(align - 1).to_le_bytes())?;
+            if offset > capacity {
+                return Ok(Value::from_ptr(ptr_type, 0));
+            }
+        }
+        offset = offset.wrapping_add(align - 1) & !(align - 1);
+        if offset > capacity || size > capacity - offset {
+            return Ok(Value::from_ptr(ptr_type, 0));
+        }
+        if !is_atomic {
+            self.write_memory(offset_addr, &(offset + size).to_le_bytes())?;
+        }
+        Ok(Value::from_ptr(ptr_type, heap + (HEAP_HEADER_SIZE as u64) + (offset as u64)))
+    }
+
+    fn vector_load(&mut self, ident: &str, args: &[Value]) -> InterpreterResult<Value>
+    {
+        let (suffix, is_half, is_aligned) = match (ident.strip_prefix("vloada_half"), ident.strip_prefix("vload_half"), ident.strip_prefix("vload")) {
+            (Some(suffix), _, _) => (suffix, true, true),
+            (None, Some(suffix), _) => (suffix, true, false),
+            (None, None, Some(suffix)) => (suffix, false, false),
+            (None, None, None) => return Err(internal_error(format!("vector_load: invalid function {}", ident))),
+        };
+        let n = if suffix.is_empty() { 1 } else { suffix.parse::<usize>().unwrap_or(0) };
+        let (offset, addr, elem_type) = match args {
+            [offset_value, ptr_value] => {
+                match (&ptr_value.typ, ptr_value.ptr()) {
+                    (IrType::Ptr(_, _, elem_type), Some(addr)) => (self.value_to_int(offset_value)?, addr, (**elem_type).clone()),
+                    _ => return Err(internal_error(String::from("vector_load: argument isn't pointer"))),
+                }
+            },
+            _ => return Err(internal_error(String::from("vector_load: invalid number of arguments"))),
+        };
+        let (elem_kind, value_elem_type) = if is_half {
+            (ScalarKind::Float(16), IrType::Float)
+        } else {
+            match scalar_kind(&elem_type) {
+                Some(kind) => (kind, elem_type),
+                None => return Err(internal_error(String::from("vector_load: invalid element type"))),
+            }
+        };
+        let stride = if is_aligned && n == 3 { 4 } else { n };
+        let elem_size = kind_size(elem_kind);
+        let start = addr.wrapping_add((offset * ((stride * elem_size) as i128)) as u64);
+        let bytes = self.read_memory(start, n * elem_size)?;
+        let elems: Vec<Elem> = (0..n).map(|i| read_elem(&bytes[(i * elem_size)..], elem_kind)).collect();
+        let typ = if n == 1 {
+            Some(value_elem_type)
+        } else {
+            vector_type(&value_elem_type, n)
+        };
+        match typ.and_then(|typ| Value::from_elems(typ, elems.as_slice())) {
+            Some(value) => Ok(value),
+            None => Err(internal_error(format!("vector_load: invalid function {}", ident))),
+        }
+    }
+
+    fn vector_store(&mut self, ident: &str, args: &[Value]) -> InterpreterResult<Value>
+    {
+        let (is_half, is_aligned) = if ident.starts_with("vstorea_half") {
+            (true, true)
+        } else if ident.starts_with("vstore_half") {
+            (true, false)
+        } else {
+            (false, false)
+        };
+        let (elems, offset, addr, elem_type) = match args {
+            [data_value, offset_value, ptr_value] => {
+                match (data_value.elems(), &ptr_value.typ, ptr_value.ptr()) {
+                    (Some((_, _, elems)), IrType::Ptr(_, _, elem_type), Some(addr)) => (elems, self.value_to_int(offset_value)?, addr, (**elem_type).clone()),
+                    _ => return Err(internal_error(String::from("vector_store: invalid arguments"))),
+                }
+            },
+            _ => return Err(internal_error(String::from("vector_store: invalid number of arguments"))),
+        };
+        let elem_kind = if is_half {
+            ScalarKind::Float(16)
+        } else {
+            match scalar_kind(&elem_type) {
+                Some(kind) => kind,
+                None => return Err(internal_error(String::from("vector_store: invalid element type"))),
+            }
+        };
+        let n = elems.len();
+        let stride = if is_aligned && n == 3 { 4 } else { n };
+        let elem_size = kind_size(elem_kind);
+        let mut bytes = vec![0u8; n * elem_size];
+        for (i, elem) in elems.iter().enumerate() {
+            write_elem(&mut bytes[(i * elem_size)..], *elem, elem_kind);
+        }
+        self.write_memory(addr.wrapping_add((offset * ((stride * elem_size) as i128)) as u64), bytes.as_slice())?;
+        Ok(Value::void())
+    }
+
+    fn lanes(&self, args: &[Value]) -> InterpreterResult<(IrType, ScalarKind, usize, Vec<Vec<Elem>>)>
+    {
+        let typ = match args.iter().find(|arg| vector_type_parts(&arg.typ).is_some()).or(args.first()) {
+            Some(arg) => arg.typ.clone(),
+            None => return Err(internal_error(String::from("lanes: no arguments"))),
+        };
+        let (kind, n) = match (scalar_kind(&typ), vector_type_parts(&typ)) {
+            (Some(kind), _) => (kind, 1),
+            (None, Some((elem_type, n))) => {
+                match scalar_kind(&elem_type) {
+                    Some(kind) => (kind, n),
+                    None => return Err(internal_error(String::from("lanes: invalid argument type"))),
+                }
+            },
+            (None, None) => return Err(internal_error(String::from("lanes: invalid argument type"))),
+        };
+        let mut arg_lanes: Vec<Vec<Elem>> = Vec::new();
+        for arg in args {
+            match arg.elems() {
+                Some((_, None, elems)) => arg_lanes.push(vec![convert_elem(elems[0], kind); n]),
+                Some((_, Some(n2), elems)) if n2 == n => arg_lanes.push(elems.into_iter().map(|elem| convert_elem(elem, kind)).collect()),
+                _ => return Err(internal_error(String::from("lanes: invalid argument"))),
+            }
+        }
+        Ok((typ, kind, n, arg_lanes))
+    }
+
+    fn map_lanes(&self, args: &[Value], ret_type: Option<IrType>, f: &dyn Fn(ScalarKind, &[Elem]) -> Option<Elem>) -> InterpreterResult<Value>
+    {
+        let (typ, kind, n, arg_lanes) = self.lanes(args)?;
+        let mut elems: Vec<Elem> = Vec::new();
+        for i in 0..n {
+            let xs: Vec<Elem> = arg_lanes.iter().map(|lane| lane[i]).collect();
+            match f(kind, xs.as_slice()) {
+                Some(elem) => elems.push(elem),
+                None => return Err(internal_error(String::from("map_lanes: invalid arguments"))),
+            }
+        }
+        let typ2 = match ret_type {
+            Some(ret_type) => {
+                if vector_type_parts(&typ).is_some() {
+                    match vector_type(&ret_type, n) {
+                        Some(vector_type) => vector_type,
+                        None => return Err(internal_error(String::from("map_lanes: no vector type"))),
+                    }
+                } else {
+                    ret_type
+                }
+            },
+            None => typ,
+        };
+        match Value::from_elems(typ2, elems.as_slice()) {
+            Some(value) => Ok(value),
+            None => Err(internal_error(String::from("map_lanes: can't create value"))),
+        }
+    }
+
+    fn map_float_lanes(&self, args: &[Value], f: &dyn Fn(&[f64]) -> f64) -> InterpreterResult<Value>
+    {
+        self.map_lanes(args, None, &|kind, xs| {
+                match kind {
+                    ScalarKind::Float(bits) => {
+                        let ys: Vec<f64> = xs.iter().map(|x| elem_to_f64(*x)).collect();
+                        Some(Elem::Float(round_float(f(ys.as_slice()), bits)))
+                    },
+                    _ => None,
+                }
+        })
+    }
+
+    fn map_relational_lanes(&self, args: &[Value], f: &dyn Fn(&[f64]) -> bool) -> InterpreterResult<Value>
+    {
+        let (typ, kind, _, _) = self.lanes(args)?;
+        let is_vector = vector_type_parts(&typ).is_some();
+        let ret_type = if is_vector { cmp_elem_type(kind) } else { IrType::Int };
+        self.map_lanes(args, Some(ret_type), &|_, xs| {
+                let ys: Vec<f64> = xs.iter().map(|x| elem_to_f64(*x)).collect();
+                match (f(ys.as_slice()), is_vector) {
+                    (true, true) => Some(Elem::Int(-1)),
+                    (true, false) => Some(Elem::Int(1)),
+                    (false, _) => Some(Elem::Int(0)),
+                }
+        })
+    }
+
+    fn call_math_fun(&mut self, ident: &str, args: &[Value]) -> InterpreterResult<Option<Value>>
+    {
+        let ident2 = ident.strip_prefix("native_").or(ident.strip_prefix("half_")).unwrap_or(ident);
+        let unary_fun: Option<fn(f64) -> f64> = match ident2 {
+            "sqrt" => Some(f64::sqrt),
+            "rsqrt" => Some(|x| 1.0 / x.sqrt()),
+            "cbrt" => Some(f64::cbrt),
+            "recip" => Some(|x| 1.0 / x),
+            "sin" => Some(f64::sin),
+            "cos" => Some(f64::cos),
+            "tan" => Some(f64::tan),
+            "asin" => Some(f64::asin),
+            "acos" => Some(f64::acos),
+            "atan" => Some(f64::atan),
+            "sinh" => Some(f64::sinh),
+            "cosh" => Some(f64::cosh),
+            "tanh" => Some(f64::tanh),
+            "asinh" => Some(f64::asinh),
+            "acosh" => Some(f64::acosh),
+            "atanh" => Some(f64::atanh),
+            "sinpi" => Some(|x| (x * std::f64::consts::PI).sin()),
+            "cospi" => Some(|x| (x * std::f64::consts::PI).cos()),
+            "tanpi" => Some(|x| (x * std::f64::consts::PI).tan()),
+            "asinpi" => Some(|x| x.asin() / std::f64::consts::PI),
+            "acospi" => Some(|x| x.acos() / std::f64::consts::PI),
+            "atanpi" => Some(|x| x.atan() / std::f64::consts::PI),
+            "exp" => Some(f64::exp),
+            "exp2" => Some(f64::exp2),
+            "exp10" => Some(|x| 10.0f64.powf(x)),
+            "expm1" => Some(f64::exp_m1),
+            "log" => Some(f64::ln),
+            "log2" => Some(f64::log2),
+            "log10" => Some(f64::log10),
+            "log1p" => Some(f64::ln_1p),
+            "logb" => Some(|x| x.abs().log2().floor()),
+            "fabs" => Some(f64::abs),
+            "floor" => Some(f64::floor),
+            "ceil" => Some(f64::ceil),
+            "round" => Some(f64::round),
+            "trunc" => Some(f64::trunc),
+            "rint" => Some(f64::round_ties_even),
+            "degrees" => Some(f64::to_degrees),
+            "radians" => Some(f64::to_radians),
+            "sign" => Some(|x| if x.is_nan() { 0.0 } else if x > 0.0 { 1.0 } else if x < 0.0 { -1.0 } else { x }),
+            _ => None,
+        };
+        match unary_fun {
+            Some(f) if args.len() == 1 => return Ok(Some(self.map_float_lanes(args, &|xs| f(xs[0]))?)),
+            _ => (),
+        }
+        let binary_fun: Option<fn(f64, f64) -> f64> = match ident2 {
+            "pow" | "powr" | "pown" => Some(f64::powf),
+            "rootn" => Some(|x, y| x.powf(1.0 / y)),
+            "fmin" => Some(f64::min),
+            "fmax" => Some(f64::max),
+            "fmod" => Some(|x, y| x % y),
+            "atan2" => Some(f64::atan2),
+            "hypot" => Some(f64::hypot),
+            "copysign" => Some(f64::copysign),
+            "fdim" => Some(|x, y| if x > y { x - y } else { 0.0 }),
+            "divide" => Some(|x, y| x / y),
+            "maxmag" => Some(|x, y| if x.abs() > y.abs() { x } else if y.abs() > x.abs() { y } else { x.max(y) }),
+            "minmag" => Some(|x, y| if x.abs() < y.abs() { x } else if y.abs() < x.abs() { y } else { x.min(y) }),
+            "step" => Some(|edge, x| if x < edge { 0.0 } else { 1.0 }),
+            "ldexp" => Some(|x, n| x * 2.0f64.powf(n)),
+            _ => None,
+        };
+        match binary_fun {
+            Some(f) if args.len() == 2 => return Ok(Some(self.map_float_lanes(args, &|xs| f(xs[0], xs[1]))?)),
+            _ => (),
+        }
+        let ternary_fun: Option<fn(f64, f64, f64) -> f64> = match ident2 {
+            "fma" => Some(f64::mul_add),
+            "mad" => Some(|x, y, z| x * y + z),
+            "mix" => Some(|x, y, a| x + (y - x) * a),
+            "smoothstep" => Some(|edge0, edge1, x| {
+                    let t = ((x - edge0) / (edge1 - edge0)).clamp(0.0, 1.0);
+                    t * t * (3.0 - 2.0 * t)
+            }),
+            _ => None,
+        };
+        match ternary_fun {
+            Some(f) if args.len() == 3 => return Ok(Some(self.map_float_lanes(args, &|xs| f(xs[0], xs[1], xs[2]))?)),
+            _ => (),
+        }
+        let relational_fun: Option<fn(&[f64]) -> bool> = match ident2.split('_').next().unwrap_or(ident2) {
+            "isequal" => Some(|xs| xs[0] == xs[1]),
+            "isnotequal" => Some(|xs| xs[0] != xs[1]),
+            "isgreater" => Some(|xs| xs[0] > xs[1]),
+            "isgreaterequal" => Some(|xs| xs[0] >= xs[1]),
+            "isless" => Some(|xs| xs[0] < xs[1]),
+            "islessequal" => Some(|xs| xs[0] <= xs[1]),
+            "islessgreater" => Some(|xs| !xs[0].is_nan() && !xs[1].is_nan() && xs[0] != xs[1]),
+            "isfinite" => Some(|xs| xs[0].is_finite()),
+            "isinf" => Some(|xs| xs[0].is_infinite()),
+            "isnan" => Some(|xs| xs[0].is_nan()),
+            "isnormal" => Some(|xs| xs[0].is_normal()),
+            "isordered" => Some(|xs| !xs[0].is_nan() && !xs[1].is_nan()),
+            "isunordered" => Some(|xs| xs[0].is_nan() || xs[1].is_nan()),
+            "signbit" => Some(|xs| xs[0].is_sign_negative()),
+            _ => None,
+        };
+        if let Some(f) = relational_fun {
+            return Ok(Some(self.map_relational_lanes(args, &f)?));
+        }
+        match ident2 {
+            "min" | "max" | "clamp" => {
+                Ok(Some(self.map_lanes(args, None, &|_, xs| {
+                        let lt = |a: Elem, b: Elem| {
+                            match (a, b) {
+                                (Elem::Int(a), Elem::Int(b)) => a < b,
+                                _ => elem_to_f64(a) < elem_to_f64(b),
+                            }
+                        };
+                        match (ident2, xs) {
+                            ("min", [x, y]) => Some(if lt(*y, *x) { *y } else { *x }),
+                            ("max", [x, y]) => Some(if lt(*x, *y) { *y } else { *x }),
+                            ("clamp", [x, lo, hi]) => {
+                                let z = if lt(*x, *lo) { *lo } else { *x };
+                                Some(if lt(*hi, z) { *hi } else { z })
+                            },
+                            _ => None,
+                        }
+                })?))
+            },
+            "abs" | "abs_diff" => {
+                let (_, kind, _, _) = self.lanes(args)?;
+                let ret_type = match kind {
+                    ScalarKind::Int(bits, _) => int_type(bits, false),
+                    ScalarKind::Float(_) => kind_type(kind),
+                };
+                Ok(Some(self.map_lanes(args, Some(ret_type), &|kind, xs| {
+                        match (kind, xs) {
+                            (ScalarKind::Int(bits, _), [Elem::Int(a)]) => Some(Elem::Int(wrap_int(a.abs(), bits, false))),
+                            (ScalarKind::Int(bits, _), [Elem::Int(a), Elem::Int(b)]) => Some(Elem::Int(wrap_int((a - b).abs(), bits, false))),
+                            (ScalarKind::Float(_), [Elem::Float(x)]) => Some(Elem::Float(x.abs())),
+                            _ => None,
+                        }
+                })?))
+            },
+            "add_sat" | "sub_sat" | "hadd" | "rhadd" | "mul_hi" | "rotate" | "mul24" | "mad24" | "mad_hi" | "mad_sat" | "popcount" | "clz" => {
+                Ok(Some(self.map_lanes(args, None, &|kind, xs| {
+                        let (bits, is_signed) = match kind {
+                            ScalarKind::Int(bits, is_signed) => (bits, is_signed),
+                            ScalarKind::Float(_) => return None,
+                        };
+                        let ys: Vec<i128> = xs.iter().map(|x| elem_to_i128(*x)).collect();
+                        let unsigned = |a: i128| wrap_int(a, bits, false);
+                        let z = match (ident2, ys.as_slice()) {
+                            ("add_sat", [a, b]) => (a + b).clamp(int_min(bits, is_signed), int_max(bits, is_signed)),
+                            ("sub_sat", [a, b]) => (a - b).clamp(int_min(bits, is_signed), int_max(bits, is_signed)),
+                            ("hadd", [a, b]) => (a + b) >> 1,
+                            ("rhadd", [a, b]) => (a + b + 1) >> 1,
+                            ("mul_hi", [a, b]) => mul_int_hi(*a, *b, bits, is_signed),
+                            ("mad_hi", [a, b, c]) => mul_int_hi(*a, *b, bits, is_signed).wrapping_add(*c),
+                            ("mad_sat", [a, b, c]) => mad_int_sat(*a, *b, *c, bits, is_signed),
+                            ("mul24", [a, b]) => a.wrapping_mul(*b),
+                            ("mad24", [a, b, c]) => a.wrapping_mul(*b).wrapping_add(*c),
+                            ("rotate", [a, b]) => {
+                                let count = unsigned(*b) % (bits as i128);
+                                let c = unsigned(*a);
+                                (c << count) | (c >> ((bits as i128) - count))
+                            },
+                            ("popcount", [a]) => unsigned(*a).count_ones() as i128,
+                            ("clz", [a]) => (unsigned(*a).leading_zeros() - (128 - bits)) as i128,
+                            _ => return None,
+                        };
+                        Some(Elem::Int(wrap_int(z, bits, is_signed)))
+                })?))
+            },
+            "dot" | "length" | "distance" | "normalize" | "fast_length" | "fast_distance" | "fast_normalize" => {
+                let (typ, kind, _, arg_lanes) = self.lanes(args)?;
+                let bits = match kind {
+                    ScalarKind::Float(bits) => bits,
+                    ScalarKind::Int(_, _) => return Err(internal_error(format!("call_math_fun: invalid arguments for {}", ident))),
+                };
+                let xs: Vec<f64> = arg_lanes[0].iter().map(|x| elem_to_f64(*x)).collect();
+                let ys: Vec<f64> = match arg_lanes.get(1) {
+                    Some(lane) => lane.iter().map(|x| elem_to_f64(*x)).collect(),
+                    None => Vec::new(),
+                };
+                let name = ident2.strip_prefix("fast_").unwrap_or(ident2);
+                let scalar = match name {
+                    "dot" => xs.iter().zip(ys.iter()).map(|(x, y)| x * y).sum::<f64>(),
+                    "length" | "normalize" => xs.iter().map(|x| x * x).sum::<f64>().sqrt(),
+                    _ => xs.iter().zip(ys.iter()).map(|(x, y)| (x - y) * (x - y)).sum::<f64>().sqrt(),
+                };
+                if name == "normalize" {
+                    let elems: Vec<Elem> = xs.iter().map(|x| Elem::Float(round_float(x / scalar, bits))).collect();
+                    match Value::from_elems(typ, elems.as_slice()) {
+                        Some(value) => Ok(Some(value)),
+                        None => Err(internal_error(String::from("call_math_fun: can't create value"))),
+                    }
+                } else {
+                    Ok(Some(Value::from_float(kind_type(kind), round_float(scalar, bits))))
+                }
+            },
+            "cross" => {
+                let (typ, _, n, arg_lanes) = self.lanes(args)?;
+                if n < 3 || arg_lanes.len() != 2 {
+                    return Err(internal_error(String::from("call_math_fun: invalid arguments for cross")));
+                }
+                let xs: Vec<f64> = arg_lanes[0].iter().map(|x| elem_to_f64(*x)).collect();
+                let ys: Vec<f64> = arg_lanes[1].iter().map(|x| elem_to_f64(*x)).collect();
+                let mut zs = vec![xs[1] * ys[2] - xs[2] * ys[1], xs[2] * ys[0] - xs[0] * ys[2], xs[0] * ys[1] - xs[1] * ys[0]];
+                zs.resize(n, 0.0);
+                let elems: Vec<Elem> = zs.into_iter().map(Elem::Float).collect();
+                match Value::from_elems(typ, elems.as_slice()) {
+                    Some(value) => Ok(Some(value)),
+                    None => Err(internal_error(String::from("call_math_fun: can't create value"))),
+                }
+            },
+            _ => Ok(None),
+        }
+    }
+
+    fn call_builtin_fun(&mut self, ident: &str, typ: &Option<Box<IrType>>, args: Vec<Value>) -> InterpreterResult<Value>
+    {
+        if ident == mangle_private_alloc_fun_name() {
+            return self.alloc(IrPtrModifier::Private, typ, args.as_slice(), false);
+        }
+        if ident == mangle_local_alloc_fun_name() {
+            return self.alloc(IrPtrModifier::Local, typ, args.as_slice(), true);
+        }
+        if ident == mangle_global_alloc_fun_name() {
+            return self.alloc(IrPtrModifier::Global, typ, args.as_slice(), true);
+        }
+        match (ident, args.as_slice()) {
+            ("convert", [value]) => {
+                match typ {
+                    Some(typ) => self.convert_value(value.clone(), typ),
+                    None => Err(internal_error(String::from("call_builtin_fun: no type for convert"))),
+                }
+            },
+            ("get_work_dim", []) => Ok(Value::from_int(IrType::Uint, self.nd_range.work_dim as i128)),
+            ("get_global_size" | "get_global_id" | "get_local_size" | "get_local_id" | "get_num_groups" | "get_group_id" | "get_global_offset", [dim]) => {
+                let dim2 = self.value_to_int(dim)?;
+                Ok(Value::from_int(IrType::SizeT, self.work_item_fun_value(ident, dim2 as usize) as i128))
+            },
+            ("barrier", [_]) => {
+                self.is_barrier = true;
+                Ok(Value::void())
+            },
+            ("mem_fence" | "read_mem_fence" | "write_mem_fence", [_]) => Ok(Value::void()),
+            ("printf", _) => self.printf(args.as_slice()),
+            ("all" | "any", [value]) => {
+                let elems = match value.elems() {
+                    Some((_, _, elems)) => elems,
+                    None => return Err(internal_error(format!("call_builtin_fun: invalid argument for {}", ident))),
+                };
+                let is_true = if ident == "all" {
+                    elems.iter().all(|elem| elem_to_i128(*elem) < 0)
+                } else {
+                    elems.iter().any(|elem| elem_to_i128(*elem) < 0)
+                };
+                Ok(Value::from_int(IrType::Int, is_true as i128))
+            },
+            ("select", [_, _, _]) | ("bitselect", [_, _, _]) => {
+                let is_scalar = vector_type_parts(&args[2].typ).is_none();
+                let is_bitselect = ident == "bitselect";
+                let conds = match args[2].elems() {
+                    Some((_, _, elems)) => elems,
+                    None => return Err(internal_error(format!("call_builtin_fun: invalid argument for {}", ident))),
+                };
+                let (typ, kind, n, arg_lanes) = self.lanes(&args[..2])?;
+                let mut elems: Vec<Elem> = Vec::new();
+                for i in 0..n {
+                    let cond = elem_to_i128(if is_scalar { conds[0] } else { conds[i] });
+                    if is_bitselect {
+                        let bits = match kind {
+                            ScalarKind::Int(bits, _) | ScalarKind::Float(bits) => bits,
+                        };
+                        let mut a = [0u8; 8];
+                        let mut b = [0u8; 8];
+                        write_elem(&mut a, arg_lanes[0][i], kind);
+                        write_elem(&mut b, arg_lanes[1][i], kind);
+                        let mut c = [0u8; 8];
+                        let mut mask = [0u8; 8];
+                        write_elem(&mut mask, Elem::Int(cond), ScalarKind::Int(bits, false));
+                        for j in 0..8 {
+                            c[j] = (a[j] & !mask[j]) | (b[j] & mask[j]);
+                        }
+                        elems.push(read_elem(&c, kind));
+                    } else if (is_scalar && cond != 0) || (!is_scalar && cond < 0) {
+                        elems.push(arg_lanes[1][i]);
+                    } else {
+                        elems.push(arg_lanes[0][i]);
+                    }
+                }
+                match Value::from_elems(typ, elems.as_slice()) {
+                    Some(value) => Ok(value),
+                    None => Err(internal_error(format!("call_builtin_fun: can't create value for {}", ident))),
+                }
+            },
+            _ => {
+                if ident.starts_with("vload") {
+                    return self.vector_load(ident, args.as_slice());
+                }
+                if ident.starts_with("vstore") {
+                    return self.vector_store(ident, args.as_slice());
+                }
+                match self.call_math_fun(ident, args.as_slice())? {
+                    Some(value) => Ok(value),
+                    None => Err(internal_error(format!("call_builtin_fun: unsupported builtin function {}", ident))),
+                }
+            },
+        }
+    }
+
+    fn printf_arg_string(&self, conv: &PrintfConv, value: &Value) -> InterpreterResult<String>
+    {
+        if conv.conv == 's' {
+            let addr = match value.ptr() {
+                Some(addr) => addr,
+                None => return Err(internal_error(String::from("printf_arg_string: argument isn't pointer"))),
+            };
+            let mut bytes = self.read_c_string(addr)?;
+            if let Some(precision) = conv.precision {
+                bytes.truncate(precision);
+            }
+            return Ok(pad_printf_field(conv, String::new(), String::from_utf8_lossy(bytes.as_slice()).into_owned(), false));
+        }
+        let elems = match value.elems() {
+            Some((_, _, elems)) => elems,
+            None => return Err(internal_error(String::from("printf_arg_string: argument isn't number"))),
+        };
+        let ss: Vec<String> = elems.iter().map(|elem| format_printf_elem(conv, *elem)).collect();
+        Ok(ss.join(","))
+    }
+
+    fn printf(&mut self, args: &[Value]) -> InterpreterResult<Value>
+    {
+        let format = match args.first().and_then(|arg| arg.ptr()) {
+            Some(addr) => self.read_c_string(addr)?,
+            None => return Err(internal_error(String::from("printf: no format"))),
+        };
+        let items = match parse_printf_format(format.as_slice()) {
+            Ok(items) => items,
+            Err(msg) => return Err(internal_error(format!("printf: {}", msg))),
+        };
+        let mut output: Vec<u8> = Vec::new();
+        let mut arg_iter = args[1..].iter();
+        for item in &items {
+            match item {
+                PrintfItem::Bytes(bytes) => {
+                    let mut i = 0usize;
+                    while i < bytes.len() {
+                        output.push(bytes[i]);
+                        if bytes[i] == b'%' && bytes.get(i + 1) == Some(&b'%') {
+                            i += 1;
+                        }
+                        i += 1;
+                    }
+                },
+                PrintfItem::Conv(conv) => {
+                    match arg_iter.next() {
+                        Some(arg) => output.extend_from_slice(self.printf_arg_string(conv, arg)?.as_bytes()),
+                        None => return Err(internal_error(String::from("printf: too few arguments"))),
+                    }
+                },
+            }
+        }
+        self.printf_output.extend_from_slice(output.as_slice());
+        Ok(Value::from_int(IrType::Int, 0))
+    }
+}
+
+fn pad_printf_field(conv: &PrintfConv, prefix: String, body: String, is_zero_padded: bool) -> String
+{
+    let width = conv.width.unwrap_or(0);
+    let len = prefix.chars().count() + body.chars().count();
+    if len >= width {
+        return format!("{}{}", prefix, body);
+    }
+    let padding = width - len;
+    if conv.flags.contains('-') {
+        format!("{}{}{}", prefix, body, " ".repeat(padding))
+    } else if is_zero_padded && conv.flags.contains('0') {
+        format!("{}{}{}", prefix, "0".repeat(padding), body)
+    } else {
+        format!("{}{}{}", " ".repeat(padding), prefix, body)
+    }
+}
+
+fn printf_sign_prefix(conv: &PrintfConv, is_neg: bool) -> String
+{
+    if is_neg {
+        String::from("-")
+    } else if conv.flags.contains('+') {
+        String::from("+")
+    } else if conv.flags.contains(' ') {
+        String::from(" ")
+    } else {
+        String::new()
+    }
+}
+
+fn format_printf_exp(x: f64, precision: usize, is_upper: bool) -> String
+{
+    let s = format!("{:.*e}", precision, x);
+    match s.split_once('e') {
+        Some((mantissa, exp)) => {
+            let exp_n = exp.parse::<i32>().unwrap_or(0);
+            format!("{}{}{}{:02}", mantissa, if is_upper { 'E' } else { 'e' }, if exp_n < 0 { '-' } else { '+' }, exp_n.abs())
+        },
+        None => s,
+    }
+}
+
+fn strip_printf_zeros(s: String) -> String
+{
+    let (mantissa, exp) = match s.find(['e', 'E']) {
+        Some(i) => (String::from(&s[..i]), String::from(&s[i..])),
+        None => (s.clone(), String::new()),
+    };
+    if mantissa.contains('.') {
+        format!("{}{}", mantissa.trim_end_matches('0').trim_end_matches('.'), exp)
+    } else {
+        s
+    }
+}
+
+fn format_printf_hex_float(x: f64, is_upper: bool) -> String
+{
+    let s = if x == 0.0 {
+        String::from("0x0p+0")
+    } else {
+        let bits = x.to_bits();
+        let exp = ((bits >> 52) & 0x7ff) as i64;
+        let mantissa = bits & ((1u64 << 52) - 1);
+        let (lead, exp2) = if exp == 0 { (0, -1022) } else { (1, exp - 1023) };
+        let hex = format!("{:013x}", mantissa);
+        let hex2 = hex.trim_end_matches('0');
+        if hex2.is_empty() {
+            format!("0x{}p{:+}", lead, exp2)
+        } else {
+            format!("0x{}.{}p{:+}", lead, hex2, exp2)
+        }
+    };
+    if is_upper {
+        s.to_uppercase()
+    } else {
+        s
+    }
+}
+
+fn format_printf_elem(conv: &PrintfConv, elem: Elem) -> String
+{
+    match conv.conv {
+        'd' | 'i' | 'o' | 'u' | 'x' | 'X' | 'c' => {
+            let bits = match (&conv.length, conv.vector_len) {
+                (PrintfLength::Hh, _) => 8,
+                (PrintfLength::H, _) => 16,
+                (PrintfLength::L, _) => 64,
+                _ => 32,
+            };
+            let a = elem_to_i128(elem);
+            match conv.conv {
+                'd' | 'i' => {
+                    let b = wrap_int(a, bits, true);
+                    let mut body = if conv.precision == Some(0) && b == 0 { String::new() } else { b.abs().to_string() };
+                    match conv.precision {
+                        Some(precision) if body.len() < precision => body = format!("{}{}", "0".repeat(precision - body.len()), body),
+                        _ => (),
+                    }
+                    pad_printf_field(conv, printf_sign_prefix(conv, b < 0), body, conv.precision.is_none())
+                },
+                'c' => pad_printf_field(conv, String::new(), String::from((wrap_int(a, 8, false) as u8) as char), false),
+                _ => {
+                    let b = wrap_int(a, bits, false);
+                    let mut body = if conv.precision == Some(0) && b == 0 {
+                        String::new()
+                    } else {
+                        match conv.conv {
+                            'o' => format!("{:o}", b),
+                            'x' => format!("{:x}", b),
+                            'X' => format!("{:X}", b),
+                            _ => b.to_string(),
+                        }
+                    };
+                    match conv.precision {
+                        Some(precision) if body.len() < precision => body = format!("{}{}", "0".repeat(precision - body.len()), body),
+                        _ => (),
+                    }
+                    let prefix = if conv.flags.contains('#') && b != 0 {
+                        match conv.conv {
+                            'o' if !body.starts_with('0') => String::from("0"),
+                            'x' => String::from("0x"),
+                            'X' => String::from("0X"),
+                            _ => String::new(),
+                        }
+                    } else {
+                        String::new()
+                    };
+                    pad_printf_field(conv, prefix, body, conv.precision.is_none())
+                },
+            }
+        },
+        _ => {
+            let x = elem_to_f64(elem);
+            let is_upper = conv.conv.is_ascii_uppercase();
+            let prefix = printf_sign_prefix(conv, x.is_sign_negative() && !x.is_nan());
+            if !x.is_finite() {
+                let body = if x.is_nan() { "nan" } else { "inf" };
+                return pad_printf_field(conv, prefix, if is_upper { body.to_uppercase() } else { String::from(body) }, false);
+            }
+            let y = x.abs();
+            let precision = conv.precision.unwrap_or(6);
+            let body = match conv.conv {
+                'f' | 'F' => {
+                    let s = format!("{:.*}", precision, y);
+                    if precision == 0 && conv.flags.contains('#') { format!("{}.", s) } else { s }
+                },
+                'e' | 'E' => format_printf_exp(y, precision, is_upper),
+                'g' | 'G' => {
+                    let p = if precision == 0 { 1 } else { precision };
+                    let exp = if y == 0.0 {
+                        0
+                    } else {
+                        let s = format!("{:.*e}", p - 1, y);
+                        s.split_once('e').and_then(|(_, exp)| exp.parse::<i64>().ok()).unwrap_or(0)
+                    };
+                    let s = if (p as i64) > exp && exp >= -4 {
+                        format!("{:.*}", ((p as i64) - 1 - exp) as usize, y)
+                    } else {
+                        format_printf_exp(y, p - 1, is_upper)
+                    };
+                    if conv.flags.contains('#') { s } else { strip_printf_zeros(s) }
+                },
+                _ => format_printf_hex_float(y, is_upper),
+            };
+            pad_printf_field(conv, prefix, body, true)
+        },
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Interpreter
+{
+    private_heap_size: usize,
+    local_heap_size: usize,
+    global_heap_size: usize,
+    private_memory_size: usize,
+    step_limit: Option<u64>,
+}
+
+impl Interpreter
+{
+    pub fn new() -> Self
+    {
+        Interpreter {
+            private_heap_size: DEFAULT_PRIVATE_HEAP_SIZE,
+            local_heap_size: DEFAULT_LOCAL_HEAP_SIZE,
+            global_heap_size: DEFAULT_GLOBAL_HEAP_SIZE,
+            private_memory_size: DEFAULT_PRIVATE_MEMORY_SIZE,
+            step_limit: None,
+        }
+    }
+
+    pub fn private_heap_size(&self) -> usize
+    { self.private_heap_size }
+
+    pub fn set_private_heap_size(&mut self, size: usize)
+    { self.private_heap_size = size; }
+
+    pub fn local_heap_size(&self) -> usize
+    { self.local_heap_size }
+
+    pub fn set_local_heap_size(&mut self, size: usize)
+    { self.local_heap_size = size; }
+
+    pub fn global_heap_size(&self) -> usize
+    { self.global_heap_size }
+
+    pub fn set_global_heap_size(&mut self, size: usize)
+    { self.global_heap_size = size; }
+
+    pub fn private_memory_size(&self) -> usize
+    { self.private_memory_size }
+
+    pub fn set_private_memory_size(&mut self, size: usize)
+    { self.private_memory_size = size; }
+
+    pub fn step_limit(&self) -> Option<u64>
+    { self.step_limit }
+
+    pub fn set_step_limit(&mut self, step_limit: Option<u64>)
+    { self.step_limit = step_limit; }
+
+    fn check_nd_range(&self, nd_range: &NdRange) -> InterpreterResult<()>
+    {
+        if nd_range.work_dim < 1 || nd_range.work_dim > 3 {
+            return Err(InterpreterError::Kernel(format!("invalid number of work dimensions {}", nd_range.work_dim)));
+        }
+        for (global_size, local_size) in nd_range.global_size.iter().zip(nd_range.local_size.iter()) {
+            if *local_size == 0 || !global_size.is_multiple_of(*local_size) {
+                return Err(InterpreterError::Kernel(format!("global size {} isn't divisible by local size {}", global_size, local_size)));
+            }
+        }
+        Ok(())
+    }
+
+    pub fn run_kernel(&self, tree: &IrTree, ident: &str, nd_range: &NdRange, args: &mut [KernelArg]) -> InterpreterResult<KernelOutput>
+    {
+        self.check_nd_range(nd_range)?;
+        let mut funs: HashMap<String, IrFun> = HashMap::new();
+        for def in tree.defs() {
+            if let IrDef::Var(fun_ident, var) = &**def {
+                if let IrVar::Fun(fun) = &*var.borrow() {
+                    funs.insert(fun_ident.clone(), (**fun).clone());
+                }
+            }
+        }
+        let (kernel_ident, arg_types, heap_flags) = match funs.get_key_value(ident) {
+            Some((kernel_ident, IrFun::Fun(IrFunModifier::Kernel, arg_types, _, _, private_heap_flag, local_heap_flag, global_heap_flag, _))) => (kernel_ident, arg_types, (*private_heap_flag, *local_heap_flag, *global_heap_flag)),
+            _ => return Err(InterpreterError::Kernel(format!("no kernel {}", ident))),
+        };
+        if args.len() != arg_types.len() {
+            return Err(InterpreterError::Kernel(format!("kernel {} takes {} arguments but {} arguments are given", ident, arg_types.len(), args.len())));
+        }
+        let mut machine = Machine::new(self, tree, &funs, nd_range);
+        machine.init_global_vars()?;
+        let mut kernel_args: Vec<KernelArgValue> = Vec::new();
+        let mut buffer_addrs: Vec<Option<u64>> = Vec::new();
+        for (i, (arg, arg_type)) in args.iter().zip(arg_types.iter()).enumerate() {
+            match (arg, &**arg_type) {
+                (KernelArg::Buffer(bytes), IrType::Ptr(IrPtrModifier::Global | IrPtrModifier::Constant, _, _)) => {
+                    let addr = machine.memory.add_region(bytes.clone());
+                    kernel_args.push(KernelArgValue::Value(Value::from_ptr((**arg_type).clone(), addr)));
+                    buffer_addrs.push(Some(addr));
+                },
+                (KernelArg::Local(size), IrType::Ptr(IrPtrModifier::Local, _, _)) => {
+                    kernel_args.push(KernelArgValue::Local((**arg_type).clone(), *size));
+                    buffer_addrs.push(None);
+                },
+                (KernelArg::Value(bytes), _) => {
+                    if machine.type_size(arg_type)? != bytes.len() {
+                        return Err(InterpreterError::Kernel(format!("argument {} has invalid size", i + 1)));
+                    }
+                    kernel_args.push(KernelArgValue::Value(Value::new((**arg_type).clone(), bytes.clone())));
+                    buffer_addrs.push(None);
+                },
+                (_, _) => return Err(InterpreterError::Kernel(format!("argument {} has invalid kind", i + 1))),
+            }
+        }
+        if heap_flags.2 == IrGlobalHeapFlag::Heap {
+            machine.global_heap = machine.heap_region(self.global_heap_size);
+        }
+        let num_groups = nd_range.num_groups();
+        for z in 0..num_groups[2] {
+            for y in 0..num_groups[1] {
+                for x in 0..num_groups[0] {
+                    machine.run_work_group(kernel_ident, kernel_args.as_slice(), (heap_flags.0, heap_flags.1), [x, y, z])?;
+                }
+            }
+        }
+        for (arg, buffer_addr) in args.iter_mut().zip(buffer_addrs.iter()) {
+            if let (KernelArg::Buffer(bytes), Some(addr)) = (arg, buffer_addr) {
+                match machine.memory.region(*addr) {
+                    Some(bytes2) => *bytes = bytes2.clone(),
+                    None => return Err(internal_error(String::from("run_kernel: no buffer"))),
+                }
+            }
+        }
+        Ok(KernelOutput {
+                panic: machine.panic,
+                printf_output: String::from_utf8_lossy(machine.printf_output.as_slice()).into_owned(),
+        })
+    }
+}
+
+impl Default for Interpreter
+{
+    fn default() -> Self
+    { Self::new() }
+}
+
+pub fn run_kernel(tree: &IrTree, ident: &str, nd_range: &NdRange, args: &mut [KernelArg]) -> InterpreterResult<KernelOutput>
+{
+    let interp = Interpreter::new();
+    interp.run_kernel(tree, ident, nd_range, args)
+}
+
+#[cfg(test)]
+mod tests;
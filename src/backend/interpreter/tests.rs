@@ -0,0 +1,244 @@
+//
+// Copyright (c) 2025 Łukasz Szpakowski
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+use crate::frontend::do_frontend_phases;
+use crate::backend::ir::test_utils::*;
+use crate::backend::lowering::*;
+use super::*;
+
+#[test]
+fn test_interpreter_run_kernel_runs_kernel_with_global_ids()
+{
+    let s = "
+kernel fun @k(*global int) -> void
+{
+    var size_t;
+    v1 = call_builtin get_global_id(uint 0);
+    v0[v1] = mul v1, size_t 2;
+    return;
+}
+";
+    let ir_tree = parse_ir_string(&s[1..]);
+    let mut args = vec![KernelArg::Buffer(int_buffer(&[0; 8]))];
+    match run_kernel(&ir_tree, "k", &NdRange::new(&[8], &[4]), args.as_mut_slice()) {
+        Ok(output) => {
+            assert!(output.panic.is_none());
+            assert_eq!(String::new(), output.printf_output);
+        },
+        Err(err) => {
+            println!("{}", err);
+            assert!(false);
+        },
+    }
+    match &args[0] {
+        KernelArg::Buffer(bytes) => assert_eq!(vec![0, 2, 4, 6, 8, 10, 12, 14], buffer_ints(bytes.as_slice())),
+        _ => assert!(false),
+    }
+}
+
+#[test]
+fn test_interpreter_run_kernel_runs_kernel_with_local_memory_and_barrier()
+{
+    let s = "
+kernel fun @k(*global int) -> void
+{
+    var local [int; 4];
+    var size_t;
+    var size_t;
+    var size_t;
+    v2 = call_builtin get_local_id(uint 0);
+    v3 = call_builtin get_global_id(uint 0);
+    v1[v2] = load v0[v3];
+    call_builtin barrier(builtin CLK_LOCAL_MEM_FENCE: uint);
+    v4 = sub size_t 3, v2;
+    v0[v3] = load v1[v4];
+    return;
+}
+";
+    let ir_tree = parse_ir_string(&s[1..]);
+    let mut args = vec![KernelArg::Buffer(int_buffer(&[1, 2, 3, 4, 5, 6, 7, 8]))];
+    match run_kernel(&ir_tree, "k", &NdRange::new(&[8], &[4]), args.as_mut_slice()) {
+        Ok(output) => assert!(output.panic.is_none()),
+        Err(err) => {
+            println!("{}", err);
+            assert!(false);
+        },
+    }
+    match &args[0] {
+        KernelArg::Buffer(bytes) => assert_eq!(vec![4, 3, 2, 1, 8, 7, 6, 5], buffer_ints(bytes.as_slice())),
+        _ => assert!(false),
+    }
+}
+
+#[test]
+fn test_interpreter_run_kernel_captures_panic_and_printf_output()
+{
+    let s = "
+fun @f(int) -> void with panic
+{
+    if eq v0, int 3 {
+        panic \"three\" at \"a.vscfl\":1:2;
+    } else {
+    }
+    return;
+}
+
+kernel fun @k(int) -> void with panic
+{
+    var size_t;
+    v1 = call_builtin get_global_id(uint 0);
+    call_builtin printf(string \"%d: %5.2f %x%%\\n\", v1 as int, float 1.5, v0);
+    call @f(v1 as int) at \"a.vscfl\":3:4;
+    return;
+}
+";
+    let ir_tree = parse_ir_string(&s[1..]);
+    let mut args = vec![KernelArg::Value(255i32.to_le_bytes().to_vec())];
+    match run_kernel(&ir_tree, "k", &NdRange::new(&[4], &[2]), args.as_mut_slice()) {
+        Ok(output) => {
+            match output.panic {
+                Some(panic) => {
+                    assert_eq!(String::from("three"), panic.msg);
+                    assert_eq!([3, 0, 0], panic.global_id);
+                    assert_eq!(false, panic.is_truncated);
+                    assert_eq!(2, panic.poses.len());
+                    assert_eq!(1, panic.poses[0].line);
+                    assert_eq!(2, panic.poses[0].column);
+                    assert_eq!(3, panic.poses[1].line);
+                    assert_eq!(4, panic.poses[1].column);
+                },
+                None => assert!(false),
+            }
+            assert_eq!(String::from("0:  1.50 ff%\n1:  1.50 ff%\n2:  1.50 ff%\n3:  1.50 ff%\n"), output.printf_output);
+        },
+        Err(err) => {
+            println!("{}", err);
+            assert!(false);
+        },
+    }
+}
+
+#[test]
+fn test_interpreter_run_kernel_complains_on_division_by_zero()
+{
+    let s = "
+kernel fun @k(*global int, int) -> void
+{
+    v0[0] = div int 1, v1;
+    return;
+}
+";
+    let ir_tree = parse_ir_string(&s[1..]);
+    let mut args = vec![KernelArg::Buffer(int_buffer(&[0])), KernelArg::Value(0i32.to_le_bytes().to_vec())];
+    match run_kernel(&ir_tree, "k", &NdRange::new(&[1], &[1]), args.as_mut_slice()) {
+        Err(InterpreterError::Fault(global_id, msg)) => {
+            assert_eq!([0, 0, 0], global_id);
+            assert_eq!(String::from("division by zero"), msg);
+        },
+        _ => assert!(false),
+    }
+}
+
+#[test]
+fn test_interpreter_run_kernel_runs_lowered_kernel()
+{
+    let s = "
+import opencl;
+f(x: Int) -> Int = if x < 3 then x * 2 else 0 - 1;
+kernel k(xs: UniqGlobalSlice<Int>, x: Int) -> () =
+    let i = get_global_id(0 as Uint);
+        _ = printf(\"%d %d\\n\", i as Int, x);
+        _ = xs[i] <- f(x + (i as Int));
+    in  ();
+";
+    let s2 = &s[1..];
+    let tree = match do_frontend_phases(s2) {
+        Ok(tree) => tree,
+        Err(errs) => {
+            println!("{}", errs);
+            panic!();
+        },
+    };
+    let ir_tree = match lower_tree(&tree) {
+        Ok(ir_tree) => ir_tree,
+        Err(err) => {
+            println!("{}", err);
+            panic!();
+        },
+    };
+    let mut args = vec![
+        KernelArg::Buffer(int_buffer(&[0; 4])),
+        KernelArg::Value(4u64.to_le_bytes().to_vec()),
+        KernelArg::Value(1i32.to_le_bytes().to_vec())
+    ];
+    match run_kernel(&ir_tree, "_VF1k", &NdRange::new(&[4], &[2]), args.as_mut_slice()) {
+        Ok(output) => {
+            assert!(output.panic.is_none());
+            assert_eq!(String::from("0 1\n1 1\n2 1\n3 1\n"), output.printf_output);
+        },
+        Err(err) => {
+            println!("{}", err);
+            assert!(false);
+        },
+    }
+    match &args[0] {
+        KernelArg::Buffer(bytes) => assert_eq!(vec![2, 4, -1, -1], buffer_ints(bytes.as_slice())),
+        _ => assert!(false),
+    }
+}
+
+#[test]
+fn test_interpreter_run_kernel_runs_integer_functions_at_type_limits()
+{
+    let s = "
+kernel fun @k(*global ulong, *global long) -> void
+{
+    var size_t;
+    var size_t;
+    var size_t;
+    var size_t;
+    var size_t;
+    v2 = load size_t 0;
+    v3 = load size_t 1;
+    v4 = load size_t 2;
+    v5 = load size_t 3;
+    v6 = load size_t 4;
+    v0[v2] = call_builtin mul_hi(ulong 18446744073709551615, ulong 18446744073709551615);
+    v0[v3] = call_builtin mad_hi(ulong 18446744073709551615, ulong 18446744073709551615, ulong 18446744073709551615);
+    v0[v4] = call_builtin mad_sat(ulong 18446744073709551615, ulong 18446744073709551615, ulong 1);
+    v0[v5] = call_builtin mul24(ulong 18446744073709551615, ulong 18446744073709551615);
+    v0[v6] = call_builtin mad24(ulong 18446744073709551615, ulong 18446744073709551615, ulong 18446744073709551615);
+    v1[v2] = call_builtin mul_hi(long -9223372036854775808, long -9223372036854775808);
+    v1[v3] = call_builtin mad_sat(long -9223372036854775808, long -9223372036854775808, long 1);
+    v1[v4] = call_builtin mad_sat(long -9223372036854775808, long 9223372036854775807, long -1);
+    return;
+}
+";
+    let ir_tree = parse_ir_string(&s[1..]);
+    let mut args = vec![KernelArg::Buffer(vec![0; 40]), KernelArg::Buffer(vec![0; 24])];
+    match run_kernel(&ir_tree, "k", &NdRange::new(&[1], &[1]), args.as_mut_slice()) {
+        Ok(output) => assert!(output.panic.is_none()),
+        Err(err) => {
+            println!("{}", err);
+            assert!(false);
+        },
+    }
+    match &args[0] {
+        KernelArg::Buffer(bytes) => {
+            let xs: Vec<u64> = bytes.chunks(8).map(|b| u64::from_le_bytes([b[0], b[1], b[2], b[3], b[4], b[5], b[6], b[7]])).collect();
+            assert_eq!(vec![u64::MAX - 1, u64::MAX - 2, u64::MAX, 1, 0], xs);
+        },
+        _ => assert!(false),
+    }
+    match &args[1] {
+        KernelArg::Buffer(bytes) => {
+            let xs: Vec<i64> = bytes.chunks(8).map(|b| i64::from_le_bytes([b[0], b[1], b[2], b[3], b[4], b[5], b[6], b[7]])).collect();
+            assert_eq!(vec![1i64 << 62, i64::MAX, i64::MIN], xs);
+        },
+        _ => assert!(false),
+    }
+}
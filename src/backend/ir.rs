@@ -13,6 +13,12 @@ use std::fmt;
 use std::rc::*;
 use crate::frontend::error::Pos;
 
+pub mod parser;
+pub mod printer;
+#[cfg(test)]
+pub mod test_utils;
+pub mod utils;
+
 #[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
 pub enum IrGlobalVarModifier
 {
@@ -81,6 +87,30 @@ impl IrTree
     pub fn add_def(&mut self, def: IrDef)
     { self.defs.push(Box::new(def)); }
 
+    pub fn retain_defs<F>(&mut self, mut f: F)
+        where F: FnMut(&IrDef) -> bool
+    {
+        let mut defs: Vec<Box<IrDef>> = Vec::new();
+        for def in self.defs.drain(..) {
+            if f(&def) {
+                defs.push(def);
+            } else {
+                match &*def {
+                    IrDef::Struct(ident, _) => {
+                        self.structs.remove(ident);
+                    },
+                    IrDef::Union(ident, _) => {
+                        self.unions.remove(ident);
+                    },
+                    IrDef::Var(ident, _) => {
+                        self.vars.remove(ident);
+                    },
+                }
+            }
+        }
+        self.defs = defs;
+    }
+
     pub fn structs(&self) -> &HashMap<String, Rc<RefCell<IrStruct>>>
     { &self.structs }
     
@@ -290,6 +320,23 @@ impl IrCallerFuns
     pub fn fun(&self, idx: usize) -> Option<&IrCallerFun>
     { self.funs.get(&idx) }
 
+    pub fn lambda_mut(&mut self, idx: usize) -> Option<&mut IrCallerFun>
+    {
+        match self.funs.get_mut(&idx) {
+            Some(fun @ IrCallerFun::Lambda(_, _, _, _, _)) => Some(fun),
+            _ => None,
+        }
+    }
+
+    pub fn fun_block_mut(&mut self, idx: usize) -> Option<&mut Box<IrBlock>>
+    {
+        match self.funs.get_mut(&idx) {
+            Some(IrCallerFun::InlineFun(_, block)) => Some(block),
+            Some(IrCallerFun::Lambda(_, _, _, _, block)) => Some(block),
+            _ => None,
+        }
+    }
+
     pub fn add_fun(&mut self, fun: IrCallerFun) -> usize
     {
         match &fun {
@@ -323,31 +370,46 @@ impl IrCallerFuns
         new_idx
     }
 
+    pub fn insert_fun(&mut self, idx: usize, fun: IrCallerFun) -> bool
+    {
+        if self.funs.contains_key(&idx) {
+            return false;
+        }
+        match &fun {
+            IrCallerFun::Fun(ident) | IrCallerFun::InlineFun(ident, _) => {
+                if self.fun_pairs.contains_key(ident) {
+                    return false;
+                }
+                self.fun_pairs.insert(ident.clone(), (idx, 1));
+            },
+            _ => (),
+        }
+        self.funs.insert(idx, fun);
+        if idx >= self.index_counter {
+            self.index_counter = idx + 1;
+        }
+        true
+    }
+
     pub fn remove_fun(&mut self, idx: usize) -> bool
     {
         match self.funs.get(&idx) {
             Some(IrCallerFun::Fun(ident)) => {
-                match self.fun_pairs.get_mut(ident) {
-                    Some((_, ref_count)) => {
-                        *ref_count -= 1;
-                        if *ref_count > 0 {
-                            return false;
-                        }
-                        self.fun_pairs.remove(ident);
-                    },
-                    None => (),
+                if let Some((_, ref_count)) = self.fun_pairs.get_mut(ident) {
+                    *ref_count -= 1;
+                    if *ref_count > 0 {
+                        return false;
+                    }
+                    self.fun_pairs.remove(ident);
                 }
             },
             Some(IrCallerFun::InlineFun(ident, _)) => {
-                match self.fun_pairs.get_mut(ident) {
-                    Some((_, ref_count)) => {
-                        *ref_count -= 1;
-                        if *ref_count > 0 {
-                            return false;
-                        }
-                        self.fun_pairs.remove(ident);
-                    },
-                    None => (),
+                if let Some((_, ref_count)) = self.fun_pairs.get_mut(ident) {
+                    *ref_count -= 1;
+                    if *ref_count > 0 {
+                        return false;
+                    }
+                    self.fun_pairs.remove(ident);
                 }
             },
             Some(_) => (),
@@ -533,7 +595,7 @@ impl IrBlock
                     }
                 },
                 None => {
-                    let value4 = self.substitute_arg_ops_for_value(&value3, substitutions, is_caller_fun_arg_change, is_closure_var_change, current_new_var_idx, var_tuples, var_tuple_idxs, new_var_tuples, new_var_tuple_idxs)?;
+                    let value4 = self.substitute_arg_ops_for_value(value3, substitutions, is_caller_fun_arg_change, is_closure_var_change, current_new_var_idx, var_tuples, var_tuple_idxs, new_var_tuples, new_var_tuple_idxs)?;
                     let new_var_idx = current_new_var_idx + new_var_tuples.len();
                     let new_var_tuple_idx = var_tuples.len() + new_var_tuples.len();
                     match type2 {
@@ -554,23 +616,20 @@ impl IrBlock
     
     fn add_arg_ops(&self, ops: &mut Vec<IrArgOp>, typ: &Option<Box<IrType>>, ops2: &[IrArgOp])
     {
-        match ops2.first() {
-            Some(op) => {
-                match op {
-                    IrArgOp::Deref(type2) => ops.push(IrArgOp::Deref(type2.clone().or(typ.clone()))),
-                    IrArgOp::Dot(type2, field_idx) => ops.push(IrArgOp::Dot(type2.clone().or(typ.clone()), *field_idx)),
-                    IrArgOp::Arrow(type2, field_idx) => ops.push(IrArgOp::Arrow(type2.clone().or(typ.clone()), *field_idx)),
-                    IrArgOp::Index(type2, idx) => ops.push(IrArgOp::Index(type2.clone().or(typ.clone()), *idx)),
-                    IrArgOp::GlobalIndex(type2, ident) => ops.push(IrArgOp::GlobalIndex(type2.clone().or(typ.clone()), ident.clone())),
-                    IrArgOp::LocalIndex(type2, var_idx) => ops.push(IrArgOp::LocalIndex(type2.clone().or(typ.clone()), *var_idx)),
-                    IrArgOp::CallerFunArgIndex(type2, var_idx) => ops.push(IrArgOp::CallerFunArgIndex(type2.clone().or(typ.clone()), *var_idx)),
-                    IrArgOp::PrivateClosureIndex(type2, var_idx) => ops.push(IrArgOp::PrivateClosureIndex(type2.clone().or(typ.clone()), *var_idx)),
-                    IrArgOp::LocalClosureIndex(type2, var_idx) => ops.push(IrArgOp::LocalClosureIndex(type2.clone().or(typ.clone()), *var_idx)),
-                    IrArgOp::GlobalClosureIndex(type2, var_idx) => ops.push(IrArgOp::GlobalClosureIndex(type2.clone().or(typ.clone()), *var_idx)),
-                }
-                ops.extend_from_slice(&ops2[1..]);
+        if let Some(op) = ops2.first() {
+            match op {
+                IrArgOp::Deref(type2) => ops.push(IrArgOp::Deref(type2.clone().or(typ.clone()))),
+                IrArgOp::Dot(type2, field_idx) => ops.push(IrArgOp::Dot(type2.clone().or(typ.clone()), *field_idx)),
+                IrArgOp::Arrow(type2, field_idx) => ops.push(IrArgOp::Arrow(type2.clone().or(typ.clone()), *field_idx)),
+                IrArgOp::Index(type2, idx) => ops.push(IrArgOp::Index(type2.clone().or(typ.clone()), *idx)),
+                IrArgOp::GlobalIndex(type2, ident) => ops.push(IrArgOp::GlobalIndex(type2.clone().or(typ.clone()), ident.clone())),
+                IrArgOp::LocalIndex(type2, var_idx) => ops.push(IrArgOp::LocalIndex(type2.clone().or(typ.clone()), *var_idx)),
+                IrArgOp::CallerFunArgIndex(type2, var_idx) => ops.push(IrArgOp::CallerFunArgIndex(type2.clone().or(typ.clone()), *var_idx)),
+                IrArgOp::PrivateClosureIndex(type2, var_idx) => ops.push(IrArgOp::PrivateClosureIndex(type2.clone().or(typ.clone()), *var_idx)),
+                IrArgOp::LocalClosureIndex(type2, var_idx) => ops.push(IrArgOp::LocalClosureIndex(type2.clone().or(typ.clone()), *var_idx)),
+                IrArgOp::GlobalClosureIndex(type2, var_idx) => ops.push(IrArgOp::GlobalClosureIndex(type2.clone().or(typ.clone()), *var_idx)),
             }
-            None => (),
+            ops.extend_from_slice(&ops2[1..]);
         }
     }
     
@@ -710,7 +769,7 @@ impl IrBlock
                                             IrArgVar::Global(ident, ops2) => {
                                                 let mut ops3 = ops2.clone();
                                                 if are_ops {
-                                                    self.add_arg_ops(&mut ops3, &type3, ops.as_slice());
+                                                    self.add_arg_ops(&mut ops3, type3, ops.as_slice());
                                                 }
                                                 match vector_elem_ptr_type {
                                                     Some(vector_elem_ptr_type) => Ok(IrValue::Object(Box::new(IrObject::Var(IrArgVar::RefGlobal(ident.clone(), ops3, vector_elem_ptr_type.clone()), typ.clone())))),
@@ -720,7 +779,7 @@ impl IrBlock
                                             IrArgVar::Local(new_var_idx3, ops2) => {
                                                 let mut ops3 = ops2.clone();
                                                 if are_ops {
-                                                    self.add_arg_ops(&mut ops3, &type3, ops.as_slice());
+                                                    self.add_arg_ops(&mut ops3, type3, ops.as_slice());
                                                 }
                                                 match vector_elem_ptr_type {
                                                     Some(vector_elem_ptr_type) => Ok(IrValue::Object(Box::new(IrObject::Var(IrArgVar::RefLocal(new_var_idx2.unwrap_or(*new_var_idx3), ops3, vector_elem_ptr_type.clone()), typ.clone())))),
@@ -730,7 +789,7 @@ impl IrBlock
                                             IrArgVar::CallerFunArg(new_var_idx3, ops2) => {
                                                 let mut ops3 = ops2.clone();
                                                 if are_ops {
-                                                    self.add_arg_ops(&mut ops3, &type3, ops.as_slice());
+                                                    self.add_arg_ops(&mut ops3, type3, ops.as_slice());
                                                 }
                                                 if is_change && is_caller_fun_arg_change {
                                                     match vector_elem_ptr_type {
@@ -747,7 +806,7 @@ impl IrBlock
                                             IrArgVar::PrivateClosure(new_var_idx3, ops2) => {
                                                 let mut ops3 = ops2.clone();
                                                 if are_ops {
-                                                    self.add_arg_ops(&mut ops3, &type3, ops.as_slice());
+                                                    self.add_arg_ops(&mut ops3, type3, ops.as_slice());
                                                 }
                                                 if is_change && is_closure_var_change {
                                                     match vector_elem_ptr_type {
@@ -764,7 +823,7 @@ impl IrBlock
                                             IrArgVar::LocalClosure(new_var_idx3, ops2) => {
                                                 let mut ops3 = ops2.clone();
                                                 if are_ops {
-                                                    self.add_arg_ops(&mut ops3, &type3, ops.as_slice());
+                                                    self.add_arg_ops(&mut ops3, type3, ops.as_slice());
                                                 }
                                                 if is_change && is_closure_var_change {
                                                     match vector_elem_ptr_type {
@@ -781,7 +840,7 @@ impl IrBlock
                                             IrArgVar::GlobalClosure(new_var_idx3, ops2) => {
                                                 let mut ops3 = ops2.clone();
                                                 if are_ops {
-                                                    self.add_arg_ops(&mut ops3, &type3, ops.as_slice());
+                                                    self.add_arg_ops(&mut ops3, type3, ops.as_slice());
                                                 }
                                                 if is_change && is_closure_var_change {
                                                     match vector_elem_ptr_type {
@@ -798,7 +857,7 @@ impl IrBlock
                                             IrArgVar::PrivateHeap(ops2) => {
                                                 let mut ops3 = ops2.clone();
                                                 if are_ops {
-                                                    self.add_arg_ops(&mut ops3, &type3, ops.as_slice());
+                                                    self.add_arg_ops(&mut ops3, type3, ops.as_slice());
                                                 }
                                                 match vector_elem_ptr_type {
                                                     Some(vector_elem_ptr_type) => Ok(IrValue::Object(Box::new(IrObject::Var(IrArgVar::RefPrivateHeap(ops3, vector_elem_ptr_type.clone()), typ.clone())))),
@@ -808,7 +867,7 @@ impl IrBlock
                                             IrArgVar::LocalHeap(ops2) => {
                                                 let mut ops3 = ops2.clone();
                                                 if are_ops {
-                                                    self.add_arg_ops(&mut ops3, &type3, ops.as_slice());
+                                                    self.add_arg_ops(&mut ops3, type3, ops.as_slice());
                                                 }
                                                 match vector_elem_ptr_type {
                                                     Some(vector_elem_ptr_type) => Ok(IrValue::Object(Box::new(IrObject::Var(IrArgVar::RefLocalHeap(ops3, vector_elem_ptr_type.clone()), typ.clone())))),
@@ -818,7 +877,7 @@ impl IrBlock
                                             IrArgVar::GlobalHeap(ops2) => {
                                                 let mut ops3 = ops2.clone();
                                                 if are_ops {
-                                                    self.add_arg_ops(&mut ops3, &type3, ops.as_slice());
+                                                    self.add_arg_ops(&mut ops3, type3, ops.as_slice());
                                                 }
                                                 match vector_elem_ptr_type {
                                                     Some(vector_elem_ptr_type) => Ok(IrValue::Object(Box::new(IrObject::Var(IrArgVar::RefGlobalHeap(ops3, vector_elem_ptr_type.clone()), typ.clone())))),
@@ -1013,7 +1072,7 @@ impl IrBlock
                 }
             },
             None => {
-                let value2 = self.substitute_value(&value, substitutions, is_caller_fun_arg_change, is_closure_var_change, current_new_var_idx, var_tuples, var_tuple_idxs, new_var_tuples, new_var_tuple_idxs)?;
+                let value2 = self.substitute_value(value, substitutions, is_caller_fun_arg_change, is_closure_var_change, current_new_var_idx, var_tuples, var_tuple_idxs, new_var_tuples, new_var_tuple_idxs)?;
                 let new_var_idx = current_new_var_idx + new_var_tuples.len();
                 let new_var_tuple_idx = var_tuples.len() + new_var_tuples.len();
                 match type2 {
@@ -1038,11 +1097,11 @@ impl IrBlock
                     IrValue::Uchar(c, tmp_type) => (*c as u64, tmp_type),
                     IrValue::Ushort(n, tmp_type) => (*n as u64, tmp_type),
                     IrValue::Uint(n, tmp_type) => (*n as u64, tmp_type),
-                    IrValue::Ulong(n, tmp_type) => (*n as u64, tmp_type),
-                    IrValue::SizeT(n, tmp_type) => (*n as u64, tmp_type),
+                    IrValue::Ulong(n, tmp_type) => ((*n), tmp_type),
+                    IrValue::SizeT(n, tmp_type) => ((*n), tmp_type),
                     IrValue::PtrdiffT(n, tmp_type) => (*n as u64, tmp_type),
                     IrValue::IntptrT(n, tmp_type) => (*n as u64, tmp_type),
-                    IrValue::UintptrT(n, tmp_type) => (*n as u64, tmp_type),
+                    IrValue::UintptrT(n, tmp_type) => ((*n), tmp_type),
                     IrValue::Object(object) => {
                         match &**object {
                             IrObject::Var(var, None) => {
@@ -1816,13 +1875,13 @@ impl IrBlock
                 None => true,
             };
             if is_var {
-                var_tuples.push(VarTuple::new(local_var_pair.1.clone(), Some(current_block_idx), Some(new_var_idx)));
-                var_tuple_idxs.insert(old_var_idx2, new_var_idx2);
+                var_tuples.push(VarTuple::new(local_var_pair.1.clone(), Some(current_block_idx), Some(new_var_idx2)));
                 new_block.add_local_var_pair(local_var_pair.clone());
                 new_var_idx2 += 1;
             } else {
                 var_tuples.push(VarTuple::new(local_var_pair.1.clone(), Some(current_block_idx), None));
             }
+            var_tuple_idxs.insert(old_var_idx2, var_tuples.len() - 1);
             old_var_idx2 += 1;
         }
         for instr in &self.instrs {
@@ -1977,6 +2036,11 @@ impl IrBlock
                 IrInstr::Loop(block) => {
                     (None, Some(IrInstr::Loop(Box::new(block.substitute_from(substitutions, ret_var, poses, tree, is_caller_fun_arg_change, is_closure_var_change, old_var_idx2, new_var_idx2, block_idx, var_tuples, var_tuple_idxs)?))))
                 },
+                IrInstr::Panic(msg, pos, panic_poses) => {
+                    let mut new_panic_poses = panic_poses.clone();
+                    new_panic_poses.extend_from_slice(poses);
+                    (None, Some(IrInstr::Panic(msg.clone(), pos.clone(), new_panic_poses)))
+                },
                 _ => (None, Some(instr.clone())),
             };
             if new_block2.is_some() || new_instr.is_some() {
@@ -1987,43 +2051,32 @@ impl IrBlock
                     }
                     let mut new_var_idx3 = new_var_idx2;
                     for new_var_tuple in &new_var_tuples {
-                        match &new_var_tuple.value {
-                            Some(value) => new_block3.add_instr(IrInstr::Assign(Box::new(IrInstrVar::Local(new_var_idx3, Vec::new())), IrOp::Load(value.clone()))),
-                            None => (), 
+                        if let Some(value) = &new_var_tuple.value {
+                            new_block3.add_instr(IrInstr::Assign(Box::new(IrInstrVar::Local(new_var_idx3, Vec::new())), IrOp::Load(value.clone())));
                         }
                         new_var_idx3 += 1;
                     }
-                    match new_block2 {
-                        Some(new_block2) => new_block3.add_block(new_block2),
-                        None => (),
+                    if let Some(new_block2) = new_block2 {
+                        new_block3.add_block(new_block2);
                     }
-                    match new_instr {
-                        Some(new_instr) => new_block3.add_instr(new_instr),
-                        None => (),
+                    if let Some(new_instr) = new_instr {
+                        new_block3.add_instr(new_instr);
                     }
                     new_block.add_block(new_block3);
                 } else {
-                    match new_block2 {
-                        Some(new_block2) => new_block.add_block(new_block2),
-                        None => (),
+                    if let Some(new_block2) = new_block2 {
+                        new_block.add_block(new_block2);
                     }
-                    match new_instr {
-                        Some(new_instr) => new_block.add_instr(new_instr),
-                        None => (),
+                    if let Some(new_instr) = new_instr {
+                        new_block.add_instr(new_instr);
                     }
                 }
             }
         }
         for _ in self.local_var_pairs.iter().rev() {
             old_var_idx2 -= 1;
-            let is_var = match  substitutions.get(&(old_var_idx2, current_block_idx)) {
-                Some(substitution) => substitution.has_var(),
-                None => true,
-            };
-            if is_var {
-                var_tuples.pop();
-                var_tuple_idxs.remove(&old_var_idx2);
-            }
+            var_tuples.pop();
+            var_tuple_idxs.remove(&old_var_idx2);
         }
         Ok(new_block)
     }
@@ -2043,20 +2096,20 @@ impl IrBlock
                 let mut var_tuple = VarTuple::new(var_type.clone(), Some(0), Some(new_var_idx));
                 var_tuple.assign_index = Some(0);
                 var_tuples.push(var_tuple);
-                var_tuple_idxs.insert(old_var_idx, new_var_idx);
                 new_var_idx += 1;
             } else {
                 let mut var_tuple = VarTuple::new(var_type.clone(), Some(0), None);
                 var_tuple.assign_index = Some(0);
                 var_tuples.push(var_tuple);
             }
+            var_tuple_idxs.insert(old_var_idx, var_tuples.len() - 1);
             old_var_idx += 1;
         }
         let mut block_idx = 1usize;
-        self.substitute_from(substitutions, ret_var, poses, tree, is_caller_fun_arg_change, is_closure_var_change, old_start_var_idx + var_types.len(), new_start_var_idx + var_tuples.len(), &mut block_idx, &mut var_tuples, &mut var_tuple_idxs)
+        self.substitute_from(substitutions, ret_var, poses, tree, is_caller_fun_arg_change, is_closure_var_change, old_start_var_idx + var_types.len(), new_var_idx, &mut block_idx, &mut var_tuples, &mut var_tuple_idxs)
     }
     
-    fn add_inline_fun(&mut self, fun_old_start_var_idx: usize, fun_arg_types: &[Box<IrType>], fun_block: &Box<IrBlock>, new_start_var_idx: usize, arg_substitutions: &[ArgSubstitution], ret_var: Option<Option<&Box<IrInstrVar>>>, pos: &Pos, panic_poses: &[Pos], tree: &IrTree) -> Result<(), IrBlockError>
+    pub fn add_inline_fun(&mut self, fun_old_start_var_idx: usize, fun_arg_types: &[Box<IrType>], fun_block: &Box<IrBlock>, new_start_var_idx: usize, arg_substitutions: &[ArgSubstitution], ret_var: Option<Option<&Box<IrInstrVar>>>, pos: &Pos, panic_poses: &[Pos], tree: &IrTree) -> Result<(), IrBlockError>
     {
         if fun_arg_types.len() != arg_substitutions.len() {
             return Err(IrBlockError::ArgCountsAreNotEqual);
@@ -2076,19 +2129,15 @@ impl IrBlock
         if !arg_substitutions.is_empty() {
             let mut new_fun_block2 = IrBlock::new();
             for (arg_substitution, fun_arg_type) in arg_substitutions.iter().zip(fun_arg_types.iter()) {
-                match arg_substitution {
-                    ArgSubstitution::Value(_) => new_fun_block2.add_local_var_pair(IrLocalVarPair(IrLocalVarModifier::None, fun_arg_type.clone())),
-                    _ => (),
+                if let ArgSubstitution::Value(_) = arg_substitution {
+                    new_fun_block2.add_local_var_pair(IrLocalVarPair(IrLocalVarModifier::None, fun_arg_type.clone()));
                 }
             }
             let mut i = 0usize;
             for arg_substitution in arg_substitutions {
-                match arg_substitution {
-                    ArgSubstitution::Value(arg_value) => {
-                        new_fun_block2.add_instr(IrInstr::Assign(Box::new(IrInstrVar::Local(new_start_var_idx + i, Vec::new())), IrOp::Load(arg_value.clone())));
-                        i += 1;
-                    },
-                    _ => (),
+                if let ArgSubstitution::Value(arg_value) = arg_substitution {
+                    new_fun_block2.add_instr(IrInstr::Assign(Box::new(IrInstrVar::Local(new_start_var_idx + i, Vec::new())), IrOp::Load(arg_value.clone())));
+                    i += 1;
                 }
             }
             new_fun_block2.add_block(new_fun_block);
@@ -2111,25 +2160,26 @@ impl IrBlock
         Ok(new_arg_values)
     }
     
-    pub fn add_fun_call(&mut self, fun_substitution: &ArgSubstitution, fun_type: &Box<IrType>, new_start_var_idx: usize, arg_substitutions: &[ArgSubstitution], ret_var: Option<Option<&Box<IrInstrVar>>>, pos: &Pos, panic_poses: &[Pos], tree: &IrTree) -> Result<(), IrBlockError>
+    pub fn add_fun_call(&mut self, fun_substitution: &ArgSubstitution, fun_type: &IrType, new_start_var_idx: usize, arg_substitutions: &[ArgSubstitution], ret_var: Option<Option<&Box<IrInstrVar>>>, pos: &Pos, panic_poses: &[Pos], tree: &IrTree) -> Result<(), IrBlockError>
     {
         let op = match fun_substitution {
             ArgSubstitution::Value(fun_value) => {
-                match &**fun_type {
+                match fun_type {
                     IrType::Struct(type_ident) => {
-                        if type_ident.starts_with("_VS") {
-                            let mut new_caller_arg_values = vec![fun_value.clone()];
-                            new_caller_arg_values.extend(self.arg_substitutions_to_arg_values(arg_substitutions)?);
-                            Some(IrOp::CallFun(String::from("_VC") + &type_ident[3..], new_caller_arg_values, pos.clone(), panic_poses.to_vec()))
-                        } else {
-                            return Err(IrBlockError::InvalidType);
+                        match type_ident.strip_prefix("_VS") {
+                            Some(type_ident2) => {
+                                let mut new_caller_arg_values = vec![fun_value.clone()];
+                                new_caller_arg_values.extend(self.arg_substitutions_to_arg_values(arg_substitutions)?);
+                                Some(IrOp::CallFun(String::from("_VC") + type_ident2, new_caller_arg_values, pos.clone(), panic_poses.to_vec()))
+                            },
+                            None => return Err(IrBlockError::InvalidType),
                         }
                     },
                     _ => return Err(IrBlockError::InvalidType),
                 }
             },
             ArgSubstitution::Fun(ident) => {
-                match tree.var(&ident) {
+                match tree.var(ident) {
                     Some(var) => {
                         let var_r = var.borrow();
                         match &*var_r {
@@ -2153,15 +2203,12 @@ impl IrBlock
                 None
             },
         };
-        match op {
-            Some(op) => {
-                match ret_var {
-                    Some(Some(ret_var)) => self.add_instr(IrInstr::Assign(ret_var.clone(), op.clone())),
-                    Some(None) => self.add_instr(IrInstr::Op(op.clone())),
-                    None => self.add_instr(IrInstr::Return(Some(op.clone()))),
-                }
-            },
-            None => (),
+        if let Some(op) = op {
+            match ret_var {
+                Some(Some(ret_var)) => self.add_instr(IrInstr::Assign(ret_var.clone(), op.clone())),
+                Some(None) => self.add_instr(IrInstr::Op(op.clone())),
+                None => self.add_instr(IrInstr::Return(Some(op.clone()))),
+            }
         }
         Ok(())
     }
@@ -2346,3 +2393,6 @@ pub enum IrPanicFlag
     None,
     Panic,
 }
+
+#[cfg(test)]
+mod tests;
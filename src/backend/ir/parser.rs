@@ -0,0 +1,1361 @@
+//
+// Copyright (c) 2025 Łukasz Szpakowski
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+// Syntax of textual IR:
+//
+// tree ::= def*
+// def ::= "struct" IDENT "{" types "}"
+//       | "closure" "struct" IDENT "{" [NUM ":" type ("," NUM ":" type)*] "}"
+//       | "union" IDENT "{" types "}"
+//       | "const" "@" IDENT ":" type "=" value ";"
+//       | "var" ["global"] ["const"] "@" IDENT ":" type "=" value ";"
+//       | ["inline" | "kernel"] "fun" "@" IDENT "(" types ")" "->" type [flags] block
+//       | "caller" "@" IDENT "(" type ";" types ")" "->" type [flags] "{" caller_fun* "}"
+// flags ::= "with" flag ("," flag)*
+// flag ::= "private_heap" | "local_heap" | "global_heap" | "panic"
+// caller_fun ::= NUM ":" "fun" "@" IDENT ";"
+//              | NUM ":" "inline" "@" IDENT block
+//              | NUM ":" "lambda" ["private" type] ["local" type] ["global" type] "start" NUM block
+// types ::= [type ("," type)*]
+// type ::= "void" | "char" | "short" | "int" | "long" | "uchar" | "ushort" | "uint" | "ulong"
+//        | "half" | "float" | "double" | "size_t" | "ptrdiff_t" | "intptr_t" | "uintptr_t"
+//        | "charN" | "shortN" | "intN" | "longN" | "ucharN" | "ushortN" | "uintN" | "ulongN"
+//        | "floatN" | "doubleN"
+//        | "struct" IDENT | "union" IDENT | "[" type ";" NUM "]"
+//        | "*" ["private" | "local" | "global" | "constant"] ["const"] type
+// block ::= "{" ("var" ["private" | "local" | "global"] type ";")* instr* "}"
+// instr ::= op ";" | var "=" op ";" | "return" [op] ";" | "break" ";" | "continue" ";"
+//         | block | "if" op block "else" block
+//         | "switch" op "{" ("case" case_value block | "default" block)* "}"
+//         | "loop" block | "panic" STRING pos ";"
+// op ::= ("load" | "neg" | "not") value
+//      | ("mul" | "div" | "rem" | "add" | "sub" | "shl" | "shr" | "eq" | "ne" | "lt" | "ge"
+//        | "gt" | "le" | "and" | "xor" | "or") value "," value
+//      | "call_builtin" IDENT [op_type] "(" values ")"
+//      | "call" "@" IDENT "(" values ")" pos
+//      | "call_without_panic" "@" IDENT "(" values ")" pos
+// pos ::= "at" STRING ":" NUM ":" NUM ["from" STRING ":" NUM ":" NUM ("," STRING ":" NUM ":" NUM)*]
+// values ::= [value ("," value)*]
+// value ::= SCALAR_TYPE NUM [as_type]
+//         | "string" STRING
+//         | "builtin" IDENT [":" type] [as_type]
+//         | "vector" type "(" values ")"
+//         | "array" "[" values "]" [as_type]
+//         | "struct" "{" [values] [";" field_pairs] "}" [as_type]
+//         | "union" "{" NUM "=" value "}" [as_type]
+//         | "closure" "{" [field_pairs] "}" [as_type]
+//         | "sizeof" "(" type ")" [as_type]
+//         | "caller_index" "@" IDENT NUM [as_type]
+//         | ["&" [op_type]] var [as_type]
+// case_value ::= INTEGER_TYPE NUM [as_type] | "builtin" IDENT [":" type] [as_type]
+// field_pairs ::= NUM "=" value ("," NUM "=" value)*
+// var ::= ("@" IDENT | "vN" | "cN" | "pcN" | "lcN" | "gcN" | "ph" | "lh" | "gh") var_op*
+// var_op ::= (".*" | ".fN" | "->fN" | "[" NUM "]" | "[" ("@" IDENT | "vN" | "cN" | "pcN" | "lcN" | "gcN") "]") [op_type]
+// as_type ::= "as" type
+// op_type ::= "<" type ">"
+//
+// Global variables are initialized by values whose variables are addresses of global variables
+// ("&" "@" IDENT var_op*). Line comments start with "//".
+//
+use std::cell::*;
+use std::collections::BTreeMap;
+use std::rc::*;
+use std::str::FromStr;
+use crate::frontend::error::Pos;
+use crate::backend::error::*;
+use crate::backend::ir::*;
+
+const VECTOR_TYPE_PREFIXES: [&str; 10] = ["char", "short", "int", "long", "uchar", "ushort", "uint", "ulong", "float", "double"];
+
+const VAR_PREFIXES: [&str; 5] = ["pc", "lc", "gc", "v", "c"];
+
+#[derive(Clone, PartialEq, Debug)]
+enum Token
+{
+    Ident(String),
+    Number(String),
+    String(Vec<u8>),
+    Sym(&'static str),
+    Eof,
+}
+
+const SYMS: [&str; 18] = ["->", "-", "{", "}", "(", ")", "[", "]", "<", ">", ",", ";", ":", "=", "@", "&", "*", "."];
+
+fn tokenize(path: &Rc<String>, src: &str) -> BackendResult<Vec<(Token, Pos)>>
+{
+    let bs = src.as_bytes();
+    let mut tokens: Vec<(Token, Pos)> = Vec::new();
+    let mut i = 0usize;
+    let mut line = 1u64;
+    let mut line_start = 0usize;
+    loop {
+        while i < bs.len() && (bs[i].is_ascii_whitespace() || bs[i..].starts_with(b"//")) {
+            if bs[i] == b'/' {
+                while i < bs.len() && bs[i] != b'\n' {
+                    i += 1;
+                }
+            } else {
+                if bs[i] == b'\n' {
+                    line += 1;
+                    line_start = i + 1;
+                }
+                i += 1;
+            }
+        }
+        let pos = Pos { path: path.clone(), line, column: (src[line_start..i].chars().count() + 1) as u64, };
+        if i >= bs.len() {
+            tokens.push((Token::Eof, pos));
+            break;
+        }
+        let start = i;
+        if bs[i].is_ascii_alphabetic() || bs[i] == b'_' {
+            while i < bs.len() && (bs[i].is_ascii_alphanumeric() || bs[i] == b'_') {
+                i += 1;
+            }
+            tokens.push((Token::Ident(String::from(&src[start..i])), pos));
+        } else if bs[i].is_ascii_digit() || (bs[i] == b'-' && i + 1 < bs.len() && bs[i + 1].is_ascii_digit()) {
+            i += 1;
+            while i < bs.len() && bs[i].is_ascii_digit() {
+                i += 1;
+            }
+            if i + 1 < bs.len() && bs[i] == b'.' && bs[i + 1].is_ascii_digit() {
+                i += 1;
+                while i < bs.len() && bs[i].is_ascii_digit() {
+                    i += 1;
+                }
+            }
+            if i < bs.len() && (bs[i] == b'e' || bs[i] == b'E') {
+                i += 1;
+                if i < bs.len() && (bs[i] == b'+' || bs[i] == b'-') {
+                    i += 1;
+                }
+                while i < bs.len() && bs[i].is_ascii_digit() {
+                    i += 1;
+                }
+            }
+            tokens.push((Token::Number(String::from(&src[start..i])), pos));
+        } else if bs[i] == b'"' {
+            i += 1;
+            let mut s: Vec<u8> = Vec::new();
+            loop {
+                if i >= bs.len() || bs[i] == b'\n' {
+                    return Err(BackendError::Messages(vec![BackendMessageError(pos, String::from("unclosed string"))]));
+                }
+                match bs[i] {
+                    b'"' => {
+                        i += 1;
+                        break;
+                    },
+                    b'\\' => {
+                        match bs.get(i + 1) {
+                            Some(b'"') => s.push(b'"'),
+                            Some(b'\\') => s.push(b'\\'),
+                            Some(b'n') => s.push(b'\n'),
+                            Some(b't') => s.push(b'\t'),
+                            Some(b'x') => {
+                                match bs.get((i + 2)..(i + 4)).and_then(|hs| u8::from_str_radix(&String::from_utf8_lossy(hs), 16).ok()) {
+                                    Some(b) => s.push(b),
+                                    None => return Err(BackendError::Messages(vec![BackendMessageError(pos, String::from("invalid escape"))])),
+                                }
+                                i += 2;
+                            },
+                            _ => return Err(BackendError::Messages(vec![BackendMessageError(pos, String::from("invalid escape"))])),
+                        }
+                        i += 2;
+                    },
+                    b => {
+                        s.push(b);
+                        i += 1;
+                    },
+                }
+            }
+            tokens.push((Token::String(s), pos));
+        } else {
+            match SYMS.iter().find(|sym| bs[i..].starts_with(sym.as_bytes())) {
+                Some(sym) => {
+                    i += sym.len();
+                    tokens.push((Token::Sym(sym), pos));
+                },
+                None => {
+                    let c = src[i..].chars().next().unwrap_or('?');
+                    return Err(BackendError::Messages(vec![BackendMessageError(pos, format!("unexpected character {}", c))]));
+                },
+            }
+        }
+    }
+    Ok(tokens)
+}
+
+fn split_var_ident(ident: &str) -> Option<(&'static str, usize)>
+{
+    for prefix in VAR_PREFIXES {
+        match ident.strip_prefix(prefix) {
+            Some(suffix) if !suffix.is_empty() && suffix.bytes().all(|b| b.is_ascii_digit()) => {
+                if let Ok(idx) = suffix.parse::<usize>() {
+                    return Some((prefix, idx));
+                }
+            },
+            _ => (),
+        }
+    }
+    None
+}
+
+fn is_op_ident(ident: &str) -> bool
+{
+    matches!(ident, "load" | "neg" | "not" | "mul" | "div" | "rem" | "add" | "sub" | "shl" | "shr" | "eq" | "ne" | "lt" | "ge" | "gt" | "le" | "and" | "xor" | "or" | "call_builtin" | "call" | "call_without_panic")
+}
+
+enum VarBase
+{
+    Global(String),
+    Local(usize),
+    CallerFunArg(usize),
+    PrivateClosure(usize),
+    LocalClosure(usize),
+    GlobalClosure(usize),
+    PrivateHeap,
+    LocalHeap,
+    GlobalHeap,
+}
+
+pub trait ParseVar: Sized
+{
+    fn parse_var(parser: &mut IrParser) -> BackendResult<Self>;
+}
+
+pub struct IrParser
+{
+    tokens: Vec<(Token, Pos)>,
+    idx: usize,
+}
+
+impl IrParser
+{
+    pub fn new(path: &str, src: &str) -> BackendResult<Self>
+    { Ok(IrParser { tokens: tokenize(&Rc::new(String::from(path)), src)?, idx: 0, }) }
+
+    fn token(&self) -> &Token
+    { &self.tokens[self.idx].0 }
+
+    fn pos(&self) -> Pos
+    { self.tokens[self.idx].1.clone() }
+
+    fn advance(&mut self)
+    {
+        if self.idx + 1 < self.tokens.len() {
+            self.idx += 1;
+        }
+    }
+
+    fn error<T>(&self, msg: &str) -> BackendResult<T>
+    { Err(BackendError::Messages(vec![BackendMessageError(self.pos(), String::from(msg))])) }
+
+    fn unexpected_error<T>(&self) -> BackendResult<T>
+    {
+        match self.token() {
+            Token::Ident(ident) => self.error(format!("unexpected identifier {}", ident).as_str()),
+            Token::Number(s) => self.error(format!("unexpected number {}", s).as_str()),
+            Token::String(_) => self.error("unexpected string"),
+            Token::Sym(sym) => self.error(format!("unexpected {}", sym).as_str()),
+            Token::Eof => self.error("unexpected end of file"),
+        }
+    }
+
+    fn is_sym(&self, sym: &str) -> bool
+    {
+        match self.token() {
+            Token::Sym(sym2) => *sym2 == sym,
+            _ => false,
+        }
+    }
+
+    fn is_keyword(&self, keyword: &str) -> bool
+    {
+        match self.token() {
+            Token::Ident(ident) => ident == keyword,
+            _ => false,
+        }
+    }
+
+    fn accept_sym(&mut self, sym: &str) -> bool
+    {
+        if self.is_sym(sym) {
+            self.advance();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn accept_keyword(&mut self, keyword: &str) -> bool
+    {
+        if self.is_keyword(keyword) {
+            self.advance();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect_sym(&mut self, sym: &str) -> BackendResult<()>
+    {
+        if self.accept_sym(sym) {
+            Ok(())
+        } else {
+            self.unexpected_error()
+        }
+    }
+
+    fn expect_keyword(&mut self, keyword: &str) -> BackendResult<()>
+    {
+        if self.accept_keyword(keyword) {
+            Ok(())
+        } else {
+            self.unexpected_error()
+        }
+    }
+
+    fn parse_ident(&mut self) -> BackendResult<String>
+    {
+        match self.token().clone() {
+            Token::Ident(ident) => {
+                self.advance();
+                Ok(ident)
+            },
+            _ => self.unexpected_error(),
+        }
+    }
+
+    fn parse_global_ident(&mut self) -> BackendResult<String>
+    {
+        self.expect_sym("@")?;
+        self.parse_ident()
+    }
+
+    fn parse_number<N: FromStr>(&mut self) -> BackendResult<N>
+    {
+        match self.token().clone() {
+            Token::Number(s) => {
+                match s.parse::<N>() {
+                    Ok(n) => {
+                        self.advance();
+                        Ok(n)
+                    },
+                    Err(_) => self.error(format!("invalid number {}", s).as_str()),
+                }
+            },
+            _ => self.unexpected_error(),
+        }
+    }
+
+    fn parse_float<N: FromStr>(&mut self) -> BackendResult<N>
+    {
+        let is_neg = self.accept_sym("-");
+        let s = match self.token().clone() {
+            Token::Ident(ident) if ident == "inf" || ident == "nan" => ident,
+            Token::Number(s) if !is_neg => s,
+            _ => return self.unexpected_error(),
+        };
+        let s = if is_neg { format!("-{}", s) } else { s };
+        match s.parse::<N>() {
+            Ok(n) => {
+                self.advance();
+                Ok(n)
+            },
+            Err(_) => self.error(format!("invalid number {}", s).as_str()),
+        }
+    }
+
+    fn parse_string(&mut self) -> BackendResult<Vec<u8>>
+    {
+        match self.token().clone() {
+            Token::String(s) => {
+                self.advance();
+                Ok(s)
+            },
+            _ => self.unexpected_error(),
+        }
+    }
+
+    fn parse_utf8_string(&mut self) -> BackendResult<String>
+    {
+        let pos = self.pos();
+        match String::from_utf8(self.parse_string()?) {
+            Ok(s) => Ok(s),
+            Err(_) => Err(BackendError::Messages(vec![BackendMessageError(pos, String::from("invalid UTF-8 string"))])),
+        }
+    }
+
+    fn parse_field_idx(&mut self) -> BackendResult<usize>
+    {
+        match self.token().clone() {
+            Token::Ident(ident) => {
+                match ident.strip_prefix('f').and_then(|s| s.parse::<usize>().ok()) {
+                    Some(idx) => {
+                        self.advance();
+                        Ok(idx)
+                    },
+                    None => self.unexpected_error(),
+                }
+            },
+            _ => self.unexpected_error(),
+        }
+    }
+
+    pub fn parse_type(&mut self) -> BackendResult<Box<IrType>>
+    {
+        if self.accept_sym("*") {
+            let ptr_modifier = if self.accept_keyword("private") {
+                IrPtrModifier::Private
+            } else if self.accept_keyword("local") {
+                IrPtrModifier::Local
+            } else if self.accept_keyword("global") {
+                IrPtrModifier::Global
+            } else if self.accept_keyword("constant") {
+                IrPtrModifier::Constant
+            } else {
+                IrPtrModifier::None
+            };
+            let access_modifier = if self.accept_keyword("const") {
+                IrAccessModifier::Const
+            } else {
+                IrAccessModifier::None
+            };
+            return Ok(Box::new(IrType::Ptr(ptr_modifier, access_modifier, self.parse_type()?)));
+        }
+        if self.accept_sym("[") {
+            let elem_type = self.parse_type()?;
+            self.expect_sym(";")?;
+            let len = self.parse_number::<usize>()?;
+            self.expect_sym("]")?;
+            return Ok(Box::new(IrType::Array(elem_type, len)));
+        }
+        let ident = match self.token() {
+            Token::Ident(ident) => ident.clone(),
+            _ => return self.unexpected_error(),
+        };
+        let typ = match ident.as_str() {
+            "void" => IrType::Void,
+            "char" => IrType::Char,
+            "short" => IrType::Short,
+            "int" => IrType::Int,
+            "long" => IrType::Long,
+            "uchar" => IrType::Uchar,
+            "ushort" => IrType::Ushort,
+            "uint" => IrType::Uint,
+            "ulong" => IrType::Ulong,
+            "half" => IrType::Half,
+            "float" => IrType::Float,
+            "double" => IrType::Double,
+            "size_t" => IrType::SizeT,
+            "ptrdiff_t" => IrType::PtrdiffT,
+            "intptr_t" => IrType::IntptrT,
+            "uintptr_t" => IrType::UintptrT,
+            "struct" => {
+                self.advance();
+                return Ok(Box::new(IrType::Struct(self.parse_ident()?)));
+            },
+            "union" => {
+                self.advance();
+                return Ok(Box::new(IrType::Union(self.parse_ident()?)));
+            },
+            _ => {
+                let mut vector_type: Option<IrType> = None;
+                for prefix in VECTOR_TYPE_PREFIXES {
+                    if let Some(n) = ident.strip_prefix(prefix).and_then(|s| s.parse::<usize>().ok()) {
+                        vector_type = match prefix {
+                            "char" => Some(IrType::CharN(n)),
+                            "short" => Some(IrType::ShortN(n)),
+                            "int" => Some(IrType::IntN(n)),
+                            "long" => Some(IrType::LongN(n)),
+                            "uchar" => Some(IrType::UcharN(n)),
+                            "ushort" => Some(IrType::UshortN(n)),
+                            "uint" => Some(IrType::UintN(n)),
+                            "ulong" => Some(IrType::UlongN(n)),
+                            "float" => Some(IrType::FloatN(n)),
+                            _ => Some(IrType::DoubleN(n)),
+                        };
+                        break;
+                    }
+                }
+                match vector_type {
+                    Some(vector_type) => vector_type,
+                    None => return self.error(format!("unknown type {}", ident).as_str()),
+                }
+            },
+        };
+        self.advance();
+        Ok(Box::new(typ))
+    }
+
+    fn parse_types(&mut self, end_sym: &str) -> BackendResult<Vec<Box<IrType>>>
+    {
+        let mut types: Vec<Box<IrType>> = Vec::new();
+        if !self.is_sym(end_sym) {
+            loop {
+                types.push(self.parse_type()?);
+                if !self.accept_sym(",") {
+                    break;
+                }
+            }
+        }
+        Ok(types)
+    }
+
+    fn parse_as_type(&mut self) -> BackendResult<Option<Box<IrType>>>
+    {
+        if self.accept_keyword("as") {
+            Ok(Some(self.parse_type()?))
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn parse_op_type(&mut self) -> BackendResult<Option<Box<IrType>>>
+    {
+        if self.accept_sym("<") {
+            let typ = self.parse_type()?;
+            self.expect_sym(">")?;
+            Ok(Some(typ))
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn parse_var_base(&mut self) -> BackendResult<VarBase>
+    {
+        if self.is_sym("@") {
+            return Ok(VarBase::Global(self.parse_global_ident()?));
+        }
+        let ident = match self.token() {
+            Token::Ident(ident) => ident.clone(),
+            _ => return self.unexpected_error(),
+        };
+        let base = match ident.as_str() {
+            "ph" => VarBase::PrivateHeap,
+            "lh" => VarBase::LocalHeap,
+            "gh" => VarBase::GlobalHeap,
+            _ => {
+                match split_var_ident(ident.as_str()) {
+                    Some(("v", idx)) => VarBase::Local(idx),
+                    Some(("c", idx)) => VarBase::CallerFunArg(idx),
+                    Some(("pc", idx)) => VarBase::PrivateClosure(idx),
+                    Some(("lc", idx)) => VarBase::LocalClosure(idx),
+                    Some((_, idx)) => VarBase::GlobalClosure(idx),
+                    None => return self.error(format!("unknown variable {}", ident).as_str()),
+                }
+            },
+        };
+        self.advance();
+        Ok(base)
+    }
+
+    fn parse_arg_ops(&mut self) -> BackendResult<Vec<IrArgOp>>
+    {
+        let mut ops: Vec<IrArgOp> = Vec::new();
+        loop {
+            if self.accept_sym(".") {
+                if self.accept_sym("*") {
+                    ops.push(IrArgOp::Deref(self.parse_op_type()?));
+                } else {
+                    let idx = self.parse_field_idx()?;
+                    ops.push(IrArgOp::Dot(self.parse_op_type()?, idx));
+                }
+            } else if self.accept_sym("->") {
+                let idx = self.parse_field_idx()?;
+                ops.push(IrArgOp::Arrow(self.parse_op_type()?, idx));
+            } else if self.accept_sym("[") {
+                let op = match self.token() {
+                    Token::Number(_) => {
+                        let idx = self.parse_number::<u64>()?;
+                        self.expect_sym("]")?;
+                        IrArgOp::Index(self.parse_op_type()?, idx)
+                    },
+                    _ => {
+                        let base = self.parse_var_base()?;
+                        self.expect_sym("]")?;
+                        let typ = self.parse_op_type()?;
+                        match base {
+                            VarBase::Global(ident) => IrArgOp::GlobalIndex(typ, ident),
+                            VarBase::Local(idx) => IrArgOp::LocalIndex(typ, idx),
+                            VarBase::CallerFunArg(idx) => IrArgOp::CallerFunArgIndex(typ, idx),
+                            VarBase::PrivateClosure(idx) => IrArgOp::PrivateClosureIndex(typ, idx),
+                            VarBase::LocalClosure(idx) => IrArgOp::LocalClosureIndex(typ, idx),
+                            VarBase::GlobalClosure(idx) => IrArgOp::GlobalClosureIndex(typ, idx),
+                            _ => return self.error("heap can't be index"),
+                        }
+                    },
+                };
+                ops.push(op);
+            } else {
+                break;
+            }
+        }
+        Ok(ops)
+    }
+
+    pub fn parse_instr_var(&mut self) -> BackendResult<Box<IrInstrVar>>
+    {
+        let base = self.parse_var_base()?;
+        let ops = self.parse_arg_ops()?;
+        let var = match base {
+            VarBase::Global(ident) => IrInstrVar::Global(ident, ops),
+            VarBase::Local(idx) => IrInstrVar::Local(idx, ops),
+            VarBase::CallerFunArg(idx) => IrInstrVar::CallerFunArg(idx, ops),
+            VarBase::PrivateClosure(idx) => IrInstrVar::PrivateClosure(idx, ops),
+            VarBase::LocalClosure(idx) => IrInstrVar::LocalClosure(idx, ops),
+            VarBase::GlobalClosure(idx) => IrInstrVar::GlobalClosure(idx, ops),
+            VarBase::PrivateHeap => IrInstrVar::PrivateHeap(ops),
+            VarBase::LocalHeap => IrInstrVar::LocalHeap(ops),
+            VarBase::GlobalHeap => IrInstrVar::GlobalHeap(ops),
+        };
+        Ok(Box::new(var))
+    }
+
+    fn parse_values<T: ParseVar>(&mut self, end_sym: &str) -> BackendResult<Vec<IrValue<T>>>
+    {
+        let mut values: Vec<IrValue<T>> = Vec::new();
+        if !self.is_sym(end_sym) {
+            loop {
+                values.push(self.parse_value()?);
+                if !self.accept_sym(",") {
+                    break;
+                }
+            }
+        }
+        Ok(values)
+    }
+
+    fn parse_field_pairs<T: ParseVar>(&mut self) -> BackendResult<Vec<IrFieldPair<T>>>
+    {
+        let mut field_pairs: Vec<IrFieldPair<T>> = Vec::new();
+        if !self.is_sym("}") {
+            loop {
+                let idx = self.parse_number::<usize>()?;
+                self.expect_sym("=")?;
+                field_pairs.push(IrFieldPair(idx, self.parse_value()?));
+                if !self.accept_sym(",") {
+                    break;
+                }
+            }
+        }
+        Ok(field_pairs)
+    }
+
+    fn parse_builtin_var(&mut self) -> BackendResult<(String, Option<Box<IrType>>, Option<Box<IrType>>)>
+    {
+        let ident = self.parse_ident()?;
+        let builtin_type = if self.accept_sym(":") {
+            Some(self.parse_type()?)
+        } else {
+            None
+        };
+        Ok((ident, builtin_type, self.parse_as_type()?))
+    }
+
+    pub fn parse_value<T: ParseVar>(&mut self) -> BackendResult<IrValue<T>>
+    {
+        let ident = match self.token() {
+            Token::Ident(ident) => ident.clone(),
+            _ => String::new(),
+        };
+        let value = match ident.as_str() {
+            "char" => {
+                self.advance();
+                let n = self.parse_number::<i8>()?;
+                IrValue::Char(n, self.parse_as_type()?)
+            },
+            "short" => {
+                self.advance();
+                let n = self.parse_number::<i16>()?;
+                IrValue::Short(n, self.parse_as_type()?)
+            },
+            "int" => {
+                self.advance();
+                let n = self.parse_number::<i32>()?;
+                IrValue::Int(n, self.parse_as_type()?)
+            },
+            "long" => {
+                self.advance();
+                let n = self.parse_number::<i64>()?;
+                IrValue::Long(n, self.parse_as_type()?)
+            },
+            "uchar" => {
+                self.advance();
+                let n = self.parse_number::<u8>()?;
+                IrValue::Uchar(n, self.parse_as_type()?)
+            },
+            "ushort" => {
+                self.advance();
+                let n = self.parse_number::<u16>()?;
+                IrValue::Ushort(n, self.parse_as_type()?)
+            },
+            "uint" => {
+                self.advance();
+                let n = self.parse_number::<u32>()?;
+                IrValue::Uint(n, self.parse_as_type()?)
+            },
+            "ulong" => {
+                self.advance();
+                let n = self.parse_number::<u64>()?;
+                IrValue::Ulong(n, self.parse_as_type()?)
+            },
+            "float" => {
+                self.advance();
+                let x = self.parse_float::<f32>()?;
+                IrValue::Float(x, self.parse_as_type()?)
+            },
+            "double" => {
+                self.advance();
+                let x = self.parse_float::<f64>()?;
+                IrValue::Double(x, self.parse_as_type()?)
+            },
+            "size_t" => {
+                self.advance();
+                let n = self.parse_number::<u64>()?;
+                IrValue::SizeT(n, self.parse_as_type()?)
+            },
+            "ptrdiff_t" => {
+                self.advance();
+                let n = self.parse_number::<i64>()?;
+                IrValue::PtrdiffT(n, self.parse_as_type()?)
+            },
+            "intptr_t" => {
+                self.advance();
+                let n = self.parse_number::<i64>()?;
+                IrValue::IntptrT(n, self.parse_as_type()?)
+            },
+            "uintptr_t" => {
+                self.advance();
+                let n = self.parse_number::<u64>()?;
+                IrValue::UintptrT(n, self.parse_as_type()?)
+            },
+            "string" => {
+                self.advance();
+                IrValue::Object(Box::new(IrObject::String(self.parse_string()?)))
+            },
+            "builtin" => {
+                self.advance();
+                let (ident, builtin_type, typ) = self.parse_builtin_var()?;
+                IrValue::Object(Box::new(IrObject::BuiltinVar(ident, builtin_type, typ)))
+            },
+            "vector" => {
+                self.advance();
+                let typ = self.parse_type()?;
+                self.expect_sym("(")?;
+                let values = self.parse_values(")")?;
+                self.expect_sym(")")?;
+                IrValue::Object(Box::new(IrObject::Vector(values, typ)))
+            },
+            "array" => {
+                self.advance();
+                self.expect_sym("[")?;
+                let values = self.parse_values("]")?;
+                self.expect_sym("]")?;
+                IrValue::Object(Box::new(IrObject::Array(values, self.parse_as_type()?)))
+            },
+            "struct" => {
+                self.advance();
+                self.expect_sym("{")?;
+                let values = if self.is_sym(";") {
+                    Vec::new()
+                } else {
+                    self.parse_values("}")?
+                };
+                let field_pairs = if self.accept_sym(";") {
+                    self.parse_field_pairs()?
+                } else {
+                    Vec::new()
+                };
+                self.expect_sym("}")?;
+                IrValue::Object(Box::new(IrObject::Struct(values, field_pairs, self.parse_as_type()?)))
+            },
+            "union" => {
+                self.advance();
+                self.expect_sym("{")?;
+                let idx = self.parse_number::<usize>()?;
+                self.expect_sym("=")?;
+                let value = self.parse_value()?;
+                self.expect_sym("}")?;
+                IrValue::Object(Box::new(IrObject::Union(idx, value, self.parse_as_type()?)))
+            },
+            "closure" => {
+                self.advance();
+                self.expect_sym("{")?;
+                let field_pairs = self.parse_field_pairs()?;
+                self.expect_sym("}")?;
+                IrValue::Object(Box::new(IrObject::Closure(field_pairs, self.parse_as_type()?)))
+            },
+            "sizeof" => {
+                self.advance();
+                self.expect_sym("(")?;
+                let typ = self.parse_type()?;
+                self.expect_sym(")")?;
+                IrValue::Object(Box::new(IrObject::Sizeof(typ, self.parse_as_type()?)))
+            },
+            "caller_index" => {
+                self.advance();
+                let ident = self.parse_global_ident()?;
+                let idx = self.parse_number::<usize>()?;
+                IrValue::Object(Box::new(IrObject::CallerFunIndex(ident, idx, self.parse_as_type()?)))
+            },
+            _ => {
+                let var = T::parse_var(self)?;
+                IrValue::Object(Box::new(IrObject::Var(var, self.parse_as_type()?)))
+            },
+        };
+        Ok(value)
+    }
+
+    fn parse_case_value(&mut self) -> BackendResult<IrCaseValue>
+    {
+        let ident = self.parse_ident()?;
+        let value = match ident.as_str() {
+            "char" => {
+                let n = self.parse_number::<i8>()?;
+                IrCaseValue::Char(n, self.parse_as_type()?)
+            },
+            "short" => {
+                let n = self.parse_number::<i16>()?;
+                IrCaseValue::Short(n, self.parse_as_type()?)
+            },
+            "int" => {
+                let n = self.parse_number::<i32>()?;
+                IrCaseValue::Int(n, self.parse_as_type()?)
+            },
+            "long" => {
+                let n = self.parse_number::<i64>()?;
+                IrCaseValue::Long(n, self.parse_as_type()?)
+            },
+            "uchar" => {
+                let n = self.parse_number::<u8>()?;
+                IrCaseValue::Uchar(n, self.parse_as_type()?)
+            },
+            "ushort" => {
+                let n = self.parse_number::<u16>()?;
+                IrCaseValue::Ushort(n, self.parse_as_type()?)
+            },
+            "uint" => {
+                let n = self.parse_number::<u32>()?;
+                IrCaseValue::Uint(n, self.parse_as_type()?)
+            },
+            "ulong" => {
+                let n = self.parse_number::<u64>()?;
+                IrCaseValue::Ulong(n, self.parse_as_type()?)
+            },
+            "size_t" => {
+                let n = self.parse_number::<u64>()?;
+                IrCaseValue::SizeT(n, self.parse_as_type()?)
+            },
+            "ptrdiff_t" => {
+                let n = self.parse_number::<i64>()?;
+                IrCaseValue::PtrdiffT(n, self.parse_as_type()?)
+            },
+            "intptr_t" => {
+                let n = self.parse_number::<i64>()?;
+                IrCaseValue::IntptrT(n, self.parse_as_type()?)
+            },
+            "uintptr_t" => {
+                let n = self.parse_number::<u64>()?;
+                IrCaseValue::UintptrT(n, self.parse_as_type()?)
+            },
+            "builtin" => {
+                let (ident, builtin_type, typ) = self.parse_builtin_var()?;
+                IrCaseValue::BuiltinVar(ident, builtin_type, typ)
+            },
+            _ => return self.error(format!("invalid case value {}", ident).as_str()),
+        };
+        Ok(value)
+    }
+
+    fn parse_pos(&mut self) -> BackendResult<Pos>
+    {
+        let path = self.parse_utf8_string()?;
+        self.expect_sym(":")?;
+        let line = self.parse_number::<u64>()?;
+        self.expect_sym(":")?;
+        let column = self.parse_number::<u64>()?;
+        Ok(Pos::new(path, line, column))
+    }
+
+    fn parse_poses(&mut self) -> BackendResult<(Pos, Vec<Pos>)>
+    {
+        self.expect_keyword("at")?;
+        let pos = self.parse_pos()?;
+        let mut poses: Vec<Pos> = Vec::new();
+        if self.accept_keyword("from") {
+            loop {
+                poses.push(self.parse_pos()?);
+                if !self.accept_sym(",") {
+                    break;
+                }
+            }
+        }
+        Ok((pos, poses))
+    }
+
+    fn parse_fun_call(&mut self) -> BackendResult<(String, Vec<IrValue<IrArgVar>>)>
+    {
+        let ident = self.parse_global_ident()?;
+        self.expect_sym("(")?;
+        let values = self.parse_values(")")?;
+        self.expect_sym(")")?;
+        Ok((ident, values))
+    }
+
+    pub fn parse_op(&mut self) -> BackendResult<IrOp>
+    {
+        let ident = match self.token() {
+            Token::Ident(ident) if is_op_ident(ident.as_str()) => ident.clone(),
+            _ => return self.unexpected_error(),
+        };
+        self.advance();
+        let op = match ident.as_str() {
+            "load" => IrOp::Load(self.parse_value()?),
+            "neg" => IrOp::Neg(self.parse_value()?),
+            "not" => IrOp::Not(self.parse_value()?),
+            "call_builtin" => {
+                let ident = self.parse_ident()?;
+                let typ = self.parse_op_type()?;
+                self.expect_sym("(")?;
+                let values = self.parse_values(")")?;
+                self.expect_sym(")")?;
+                IrOp::CallBuiltinFun(ident, typ, values)
+            },
+            "call" => {
+                let (ident, values) = self.parse_fun_call()?;
+                let (pos, poses) = self.parse_poses()?;
+                IrOp::CallFun(ident, values, pos, poses)
+            },
+            "call_without_panic" => {
+                let (ident, values) = self.parse_fun_call()?;
+                let (pos, poses) = self.parse_poses()?;
+                if !poses.is_empty() {
+                    return self.error("call without panic can't have panic positions");
+                }
+                IrOp::CallFunWithoutPanic(ident, values, pos)
+            },
+            _ => {
+                let value1 = self.parse_value()?;
+                self.expect_sym(",")?;
+                let value2 = self.parse_value()?;
+                match ident.as_str() {
+                    "mul" => IrOp::Mul(value1, value2),
+                    "div" => IrOp::Div(value1, value2),
+                    "rem" => IrOp::Rem(value1, value2),
+                    "add" => IrOp::Add(value1, value2),
+                    "sub" => IrOp::Sub(value1, value2),
+                    "shl" => IrOp::Shl(value1, value2),
+                    "shr" => IrOp::Shr(value1, value2),
+                    "eq" => IrOp::Eq(value1, value2),
+                    "ne" => IrOp::Ne(value1, value2),
+                    "lt" => IrOp::Lt(value1, value2),
+                    "ge" => IrOp::Ge(value1, value2),
+                    "gt" => IrOp::Gt(value1, value2),
+                    "le" => IrOp::Le(value1, value2),
+                    "and" => IrOp::And(value1, value2),
+                    "xor" => IrOp::Xor(value1, value2),
+                    _ => IrOp::Or(value1, value2),
+                }
+            },
+        };
+        Ok(op)
+    }
+
+    pub fn parse_block(&mut self) -> BackendResult<Box<IrBlock>>
+    {
+        self.expect_sym("{")?;
+        let mut block = IrBlock::new();
+        while self.accept_keyword("var") {
+            let modifier = if self.accept_keyword("private") {
+                IrLocalVarModifier::Private
+            } else if self.accept_keyword("local") {
+                IrLocalVarModifier::Local
+            } else if self.accept_keyword("global") {
+                IrLocalVarModifier::Global
+            } else {
+                IrLocalVarModifier::None
+            };
+            let typ = self.parse_type()?;
+            self.expect_sym(";")?;
+            block.add_local_var_pair(IrLocalVarPair(modifier, typ));
+        }
+        while !self.accept_sym("}") {
+            let instr = self.parse_instr()?;
+            block.add_instr(instr);
+        }
+        Ok(Box::new(block))
+    }
+
+    fn parse_instr(&mut self) -> BackendResult<IrInstr>
+    {
+        if self.is_sym("{") {
+            return Ok(IrInstr::Block(self.parse_block()?));
+        }
+        let ident = match self.token() {
+            Token::Ident(ident) => ident.clone(),
+            _ => String::new(),
+        };
+        let instr = match ident.as_str() {
+            "return" => {
+                self.advance();
+                if self.is_sym(";") {
+                    IrInstr::Return(None)
+                } else {
+                    IrInstr::Return(Some(self.parse_op()?))
+                }
+            },
+            "break" => {
+                self.advance();
+                IrInstr::Break
+            },
+            "continue" => {
+                self.advance();
+                IrInstr::Continue
+            },
+            "if" => {
+                self.advance();
+                let op = self.parse_op()?;
+                let block1 = self.parse_block()?;
+                self.expect_keyword("else")?;
+                let block2 = self.parse_block()?;
+                return Ok(IrInstr::If(op, block1, block2));
+            },
+            "switch" => {
+                self.advance();
+                let op = self.parse_op()?;
+                self.expect_sym("{")?;
+                let mut cases: Vec<IrCase> = Vec::new();
+                while !self.accept_sym("}") {
+                    if self.accept_keyword("case") {
+                        let value = self.parse_case_value()?;
+                        cases.push(IrCase::Case(value, self.parse_block()?));
+                    } else {
+                        self.expect_keyword("default")?;
+                        cases.push(IrCase::Default(self.parse_block()?));
+                    }
+                }
+                return Ok(IrInstr::Switch(op, cases));
+            },
+            "loop" => {
+                self.advance();
+                return Ok(IrInstr::Loop(self.parse_block()?));
+            },
+            "panic" => {
+                self.advance();
+                let msg = self.parse_utf8_string()?;
+                let (pos, poses) = self.parse_poses()?;
+                IrInstr::Panic(msg, pos, poses)
+            },
+            _ if is_op_ident(ident.as_str()) => IrInstr::Op(self.parse_op()?),
+            _ => {
+                let var = self.parse_instr_var()?;
+                self.expect_sym("=")?;
+                IrInstr::Assign(var, self.parse_op()?)
+            },
+        };
+        self.expect_sym(";")?;
+        Ok(instr)
+    }
+
+    fn parse_flags(&mut self) -> BackendResult<(IrPrivateHeapFlag, IrLocalHeapFlag, IrGlobalHeapFlag, IrPanicFlag)>
+    {
+        let mut flags = (IrPrivateHeapFlag::None, IrLocalHeapFlag::None, IrGlobalHeapFlag::None, IrPanicFlag::None);
+        if self.accept_keyword("with") {
+            loop {
+                if self.accept_keyword("private_heap") {
+                    flags.0 = IrPrivateHeapFlag::Heap;
+                } else if self.accept_keyword("local_heap") {
+                    flags.1 = IrLocalHeapFlag::Heap;
+                } else if self.accept_keyword("global_heap") {
+                    flags.2 = IrGlobalHeapFlag::Heap;
+                } else if self.accept_keyword("panic") {
+                    flags.3 = IrPanicFlag::Panic;
+                } else {
+                    return self.unexpected_error();
+                }
+                if !self.accept_sym(",") {
+                    break;
+                }
+            }
+        }
+        Ok(flags)
+    }
+
+    fn parse_caller_fun(&mut self) -> BackendResult<IrCallerFun>
+    {
+        if self.accept_keyword("fun") {
+            let ident = self.parse_global_ident()?;
+            self.expect_sym(";")?;
+            Ok(IrCallerFun::Fun(ident))
+        } else if self.accept_keyword("inline") {
+            let ident = self.parse_global_ident()?;
+            Ok(IrCallerFun::InlineFun(ident, self.parse_block()?))
+        } else {
+            self.expect_keyword("lambda")?;
+            let private_closure_type = if self.accept_keyword("private") {
+                Some(self.parse_type()?)
+            } else {
+                None
+            };
+            let local_closure_type = if self.accept_keyword("local") {
+                Some(self.parse_type()?)
+            } else {
+                None
+            };
+            let global_closure_type = if self.accept_keyword("global") {
+                Some(self.parse_type()?)
+            } else {
+                None
+            };
+            self.expect_keyword("start")?;
+            let start_idx = self.parse_number::<usize>()?;
+            Ok(IrCallerFun::Lambda(private_closure_type, local_closure_type, global_closure_type, start_idx, self.parse_block()?))
+        }
+    }
+
+    fn parse_def(&mut self, tree: &mut IrTree) -> BackendResult<()>
+    {
+        let pos = self.pos();
+        let ident = self.parse_ident()?;
+        match ident.as_str() {
+            "struct" | "closure" | "union" => {
+                if ident == "closure" {
+                    self.expect_keyword("struct")?;
+                }
+                let name_pos = self.pos();
+                let name = self.parse_ident()?;
+                self.expect_sym("{")?;
+                let is_struct = ident != "union";
+                if (is_struct && tree.struct1(&name).is_some()) || (!is_struct && tree.union(&name).is_some()) {
+                    return Err(BackendError::Messages(vec![BackendMessageError(name_pos, format!("{} {} is already defined", if is_struct { "structure" } else { "union" }, name))]));
+                }
+                if ident == "closure" {
+                    let mut field_types: BTreeMap<usize, Box<IrType>> = BTreeMap::new();
+                    if !self.is_sym("}") {
+                        loop {
+                            let idx = self.parse_number::<usize>()?;
+                            self.expect_sym(":")?;
+                            field_types.insert(idx, self.parse_type()?);
+                            if !self.accept_sym(",") {
+                                break;
+                            }
+                        }
+                    }
+                    self.expect_sym("}")?;
+                    let struct1 = Rc::new(RefCell::new(IrStruct::Closure(field_types)));
+                    tree.add_struct(name.clone(), struct1.clone());
+                    tree.add_def(IrDef::Struct(name, struct1));
+                } else if is_struct {
+                    let field_types = self.parse_types("}")?;
+                    self.expect_sym("}")?;
+                    let struct1 = Rc::new(RefCell::new(IrStruct::Struct(field_types)));
+                    tree.add_struct(name.clone(), struct1.clone());
+                    tree.add_def(IrDef::Struct(name, struct1));
+                } else {
+                    let field_types = self.parse_types("}")?;
+                    self.expect_sym("}")?;
+                    let union = Rc::new(RefCell::new(IrUnion(field_types)));
+                    tree.add_union(name.clone(), union.clone());
+                    tree.add_def(IrDef::Union(name, union));
+                }
+                return Ok(());
+            },
+            _ => (),
+        }
+        let name_pos = self.pos();
+        let var = match ident.as_str() {
+            "const" => {
+                let name = self.parse_global_ident()?;
+                self.expect_sym(":")?;
+                let typ = self.parse_type()?;
+                self.expect_sym("=")?;
+                let value = self.parse_value()?;
+                self.expect_sym(";")?;
+                (name, IrVar::Const(typ, value))
+            },
+            "var" => {
+                let modifier = if self.accept_keyword("global") {
+                    IrGlobalVarModifier::Global
+                } else {
+                    IrGlobalVarModifier::None
+                };
+                let access_modifier = if self.accept_keyword("const") {
+                    IrAccessModifier::Const
+                } else {
+                    IrAccessModifier::None
+                };
+                let name = self.parse_global_ident()?;
+                self.expect_sym(":")?;
+                let typ = self.parse_type()?;
+                self.expect_sym("=")?;
+                let value = self.parse_value()?;
+                self.expect_sym(";")?;
+                (name, IrVar::Var(modifier, access_modifier, typ, value))
+            },
+            "fun" | "inline" | "kernel" => {
+                let modifier = match ident.as_str() {
+                    "inline" => IrFunModifier::Inline,
+                    "kernel" => IrFunModifier::Kernel,
+                    _ => IrFunModifier::None,
+                };
+                if modifier != IrFunModifier::None {
+                    self.expect_keyword("fun")?;
+                }
+                let name = self.parse_global_ident()?;
+                self.expect_sym("(")?;
+                let arg_types = self.parse_types(")")?;
+                self.expect_sym(")")?;
+                self.expect_sym("->")?;
+                let ret_type = self.parse_type()?;
+                let (private_heap_flag, local_heap_flag, global_heap_flag, panic_flag) = self.parse_flags()?;
+                let block = self.parse_block()?;
+                (name, IrVar::Fun(Box::new(IrFun::Fun(modifier, arg_types, ret_type, block, private_heap_flag, local_heap_flag, global_heap_flag, panic_flag))))
+            },
+            "caller" => {
+                let name = self.parse_global_ident()?;
+                self.expect_sym("(")?;
+                let fun_type = self.parse_type()?;
+                self.expect_sym(";")?;
+                let arg_types = self.parse_types(")")?;
+                self.expect_sym(")")?;
+                self.expect_sym("->")?;
+                let ret_type = self.parse_type()?;
+                let (private_heap_flag, local_heap_flag, global_heap_flag, panic_flag) = self.parse_flags()?;
+                self.expect_sym("{")?;
+                let mut caller_funs = IrCallerFuns::new();
+                while !self.accept_sym("}") {
+                    let idx_pos = self.pos();
+                    let idx = self.parse_number::<usize>()?;
+                    self.expect_sym(":")?;
+                    let caller_fun = self.parse_caller_fun()?;
+                    if !caller_funs.insert_fun(idx, caller_fun) {
+                        return Err(BackendError::Messages(vec![BackendMessageError(idx_pos, format!("caller function {} is already defined", idx))]));
+                    }
+                }
+                (name, IrVar::Fun(Box::new(IrFun::Caller(fun_type, arg_types, ret_type, Box::new(caller_funs), private_heap_flag, local_heap_flag, global_heap_flag, panic_flag))))
+            },
+            _ => return Err(BackendError::Messages(vec![BackendMessageError(pos, format!("unexpected identifier {}", ident))])),
+        };
+        let (name, var) = var;
+        if tree.var(&name).is_some() {
+            return Err(BackendError::Messages(vec![BackendMessageError(name_pos, format!("variable {} is already defined", name))]));
+        }
+        let var = Rc::new(RefCell::new(var));
+        tree.add_var(name.clone(), var.clone());
+        tree.add_def(IrDef::Var(name, var));
+        Ok(())
+    }
+
+    pub fn parse_tree(&mut self) -> BackendResult<IrTree>
+    {
+        let mut tree = IrTree::new();
+        while *self.token() != Token::Eof {
+            self.parse_def(&mut tree)?;
+        }
+        Ok(tree)
+    }
+
+    fn expect_eof(&self) -> BackendResult<()>
+    {
+        if *self.token() == Token::Eof {
+            Ok(())
+        } else {
+            self.unexpected_error()
+        }
+    }
+}
+
+impl ParseVar for IrValueVar
+{
+    fn parse_var(parser: &mut IrParser) -> BackendResult<Self>
+    {
+        parser.expect_sym("&")?;
+        let ident = parser.parse_global_ident()?;
+        let mut ops: Vec<IrValueOp> = Vec::new();
+        loop {
+            if parser.accept_sym(".") {
+                if parser.accept_sym("*") {
+                    ops.push(IrValueOp::Deref(parser.parse_op_type()?));
+                } else {
+                    let idx = parser.parse_field_idx()?;
+                    ops.push(IrValueOp::Dot(parser.parse_op_type()?, idx));
+                }
+            } else if parser.accept_sym("->") {
+                let idx = parser.parse_field_idx()?;
+                ops.push(IrValueOp::Arrow(parser.parse_op_type()?, idx));
+            } else if parser.accept_sym("[") {
+                let idx = parser.parse_number::<u64>()?;
+                parser.expect_sym("]")?;
+                ops.push(IrValueOp::Index(parser.parse_op_type()?, idx));
+            } else {
+                break;
+            }
+        }
+        Ok(IrValueVar(ident, ops))
+    }
+}
+
+impl ParseVar for IrArgVar
+{
+    fn parse_var(parser: &mut IrParser) -> BackendResult<Self>
+    {
+        if parser.accept_sym("&") {
+            let typ = parser.parse_op_type()?;
+            let base = parser.parse_var_base()?;
+            let ops = parser.parse_arg_ops()?;
+            let var = match base {
+                VarBase::Global(ident) => IrArgVar::RefGlobal(ident, ops, typ),
+                VarBase::Local(idx) => IrArgVar::RefLocal(idx, ops, typ),
+                VarBase::CallerFunArg(idx) => IrArgVar::RefCallerFunArg(idx, ops, typ),
+                VarBase::PrivateClosure(idx) => IrArgVar::RefPrivateClosure(idx, ops, typ),
+                VarBase::LocalClosure(idx) => IrArgVar::RefLocalClosure(idx, ops, typ),
+                VarBase::GlobalClosure(idx) => IrArgVar::RefGlobalClosure(idx, ops, typ),
+                VarBase::PrivateHeap => IrArgVar::RefPrivateHeap(ops, typ),
+                VarBase::LocalHeap => IrArgVar::RefLocalHeap(ops, typ),
+                VarBase::GlobalHeap => IrArgVar::RefGlobalHeap(ops, typ),
+            };
+            Ok(var)
+        } else {
+            let base = parser.parse_var_base()?;
+            let ops = parser.parse_arg_ops()?;
+            let var = match base {
+                VarBase::Global(ident) => IrArgVar::Global(ident, ops),
+                VarBase::Local(idx) => IrArgVar::Local(idx, ops),
+                VarBase::CallerFunArg(idx) => IrArgVar::CallerFunArg(idx, ops),
+                VarBase::PrivateClosure(idx) => IrArgVar::PrivateClosure(idx, ops),
+                VarBase::LocalClosure(idx) => IrArgVar::LocalClosure(idx, ops),
+                VarBase::GlobalClosure(idx) => IrArgVar::GlobalClosure(idx, ops),
+                VarBase::PrivateHeap => IrArgVar::PrivateHeap(ops),
+                VarBase::LocalHeap => IrArgVar::LocalHeap(ops),
+                VarBase::GlobalHeap => IrArgVar::GlobalHeap(ops),
+            };
+            Ok(var)
+        }
+    }
+}
+
+pub fn parse_ir_tree_with_path(path: &str, src: &str) -> BackendResult<IrTree>
+{
+    let mut parser = IrParser::new(path, src)?;
+    parser.parse_tree()
+}
+
+pub fn parse_ir_tree(src: &str) -> BackendResult<IrTree>
+{ parse_ir_tree_with_path("(string)", src) }
+
+pub fn parse_ir_block_with_path(path: &str, src: &str) -> BackendResult<Box<IrBlock>>
+{
+    let mut parser = IrParser::new(path, src)?;
+    let block = parser.parse_block()?;
+    parser.expect_eof()?;
+    Ok(block)
+}
+
+pub fn parse_ir_block(src: &str) -> BackendResult<Box<IrBlock>>
+{ parse_ir_block_with_path("(string)", src) }
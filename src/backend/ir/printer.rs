@@ -0,0 +1,551 @@
+//
+// Copyright (c) 2025 Łukasz Szpakowski
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+use std::fmt;
+use std::fmt::Write;
+use crate::frontend::error::Pos;
+use crate::backend::ir::*;
+
+pub trait PrintVar
+{
+    fn var_to_string(&self) -> String;
+}
+
+fn indent_to_string(level: usize) -> String
+{ "    ".repeat(level) }
+
+pub fn ir_bytes_to_string(bytes: &[u8]) -> String
+{
+    let mut s = String::from("\"");
+    for b in bytes {
+        match *b {
+            b'"' => s.push_str("\\\""),
+            b'\\' => s.push_str("\\\\"),
+            b'\n' => s.push_str("\\n"),
+            b'\t' => s.push_str("\\t"),
+            0x20..=0x7e => s.push(*b as char),
+            _ => write!(s, "\\x{:02x}", b).unwrap(),
+        }
+    }
+    s.push('"');
+    s
+}
+
+pub fn ir_type_to_string(typ: &IrType) -> String
+{
+    match typ {
+        IrType::Void => String::from("void"),
+        IrType::Char => String::from("char"),
+        IrType::Short => String::from("short"),
+        IrType::Int => String::from("int"),
+        IrType::Long => String::from("long"),
+        IrType::Uchar => String::from("uchar"),
+        IrType::Ushort => String::from("ushort"),
+        IrType::Uint => String::from("uint"),
+        IrType::Ulong => String::from("ulong"),
+        IrType::Half => String::from("half"),
+        IrType::Float => String::from("float"),
+        IrType::Double => String::from("double"),
+        IrType::SizeT => String::from("size_t"),
+        IrType::PtrdiffT => String::from("ptrdiff_t"),
+        IrType::IntptrT => String::from("intptr_t"),
+        IrType::UintptrT => String::from("uintptr_t"),
+        IrType::CharN(n) => format!("char{}", n),
+        IrType::ShortN(n) => format!("short{}", n),
+        IrType::IntN(n) => format!("int{}", n),
+        IrType::LongN(n) => format!("long{}", n),
+        IrType::UcharN(n) => format!("uchar{}", n),
+        IrType::UshortN(n) => format!("ushort{}", n),
+        IrType::UintN(n) => format!("uint{}", n),
+        IrType::UlongN(n) => format!("ulong{}", n),
+        IrType::FloatN(n) => format!("float{}", n),
+        IrType::DoubleN(n) => format!("double{}", n),
+        IrType::Struct(ident) => format!("struct {}", ident),
+        IrType::Union(ident) => format!("union {}", ident),
+        IrType::Array(elem_type, len) => format!("[{}; {}]", ir_type_to_string(elem_type), len),
+        IrType::Ptr(ptr_modifier, access_modifier, elem_type) => {
+            let mut s = String::from("*");
+            match ptr_modifier {
+                IrPtrModifier::None => (),
+                IrPtrModifier::Private => s.push_str("private "),
+                IrPtrModifier::Local => s.push_str("local "),
+                IrPtrModifier::Global => s.push_str("global "),
+                IrPtrModifier::Constant => s.push_str("constant "),
+            }
+            if *access_modifier == IrAccessModifier::Const {
+                s.push_str("const ");
+            }
+            s.push_str(ir_type_to_string(elem_type).as_str());
+            s
+        },
+    }
+}
+
+fn as_type_to_string(typ: &Option<Box<IrType>>) -> String
+{
+    match typ {
+        Some(typ) => format!(" as {}", ir_type_to_string(typ)),
+        None => String::new(),
+    }
+}
+
+fn op_type_to_string(typ: &Option<Box<IrType>>) -> String
+{
+    match typ {
+        Some(typ) => format!("<{}>", ir_type_to_string(typ)),
+        None => String::new(),
+    }
+}
+
+fn float_to_string(x: f32) -> String
+{
+    if x.is_nan() {
+        String::from("nan")
+    } else {
+        format!("{}", x)
+    }
+}
+
+fn double_to_string(x: f64) -> String
+{
+    if x.is_nan() {
+        String::from("nan")
+    } else {
+        format!("{}", x)
+    }
+}
+
+pub fn ir_pos_to_string(pos: &Pos) -> String
+{ format!("{}:{}:{}", ir_bytes_to_string(pos.path.as_bytes()), pos.line, pos.column) }
+
+fn poses_to_string(pos: &Pos, poses: &[Pos]) -> String
+{
+    let mut s = format!("at {}", ir_pos_to_string(pos));
+    if !poses.is_empty() {
+        let ss: Vec<String> = poses.iter().map(ir_pos_to_string).collect();
+        write!(s, " from {}", ss.join(", ")).unwrap();
+    }
+    s
+}
+
+impl PrintVar for IrValueVar
+{
+    fn var_to_string(&self) -> String
+    {
+        let mut s = format!("&@{}", self.0);
+        for op in &self.1 {
+            match op {
+                IrValueOp::Deref(typ) => write!(s, ".*{}", op_type_to_string(typ)).unwrap(),
+                IrValueOp::Dot(typ, idx) => write!(s, ".f{}{}", idx, op_type_to_string(typ)).unwrap(),
+                IrValueOp::Arrow(typ, idx) => write!(s, "->f{}{}", idx, op_type_to_string(typ)).unwrap(),
+                IrValueOp::Index(typ, idx) => write!(s, "[{}]{}", idx, op_type_to_string(typ)).unwrap(),
+            }
+        }
+        s
+    }
+}
+
+pub fn ir_arg_ops_to_string(ops: &[IrArgOp]) -> String
+{
+    let mut s = String::new();
+    for op in ops {
+        match op {
+            IrArgOp::Deref(typ) => write!(s, ".*{}", op_type_to_string(typ)).unwrap(),
+            IrArgOp::Dot(typ, idx) => write!(s, ".f{}{}", idx, op_type_to_string(typ)).unwrap(),
+            IrArgOp::Arrow(typ, idx) => write!(s, "->f{}{}", idx, op_type_to_string(typ)).unwrap(),
+            IrArgOp::Index(typ, idx) => write!(s, "[{}]{}", idx, op_type_to_string(typ)).unwrap(),
+            IrArgOp::GlobalIndex(typ, ident) => write!(s, "[@{}]{}", ident, op_type_to_string(typ)).unwrap(),
+            IrArgOp::LocalIndex(typ, idx) => write!(s, "[v{}]{}", idx, op_type_to_string(typ)).unwrap(),
+            IrArgOp::CallerFunArgIndex(typ, idx) => write!(s, "[c{}]{}", idx, op_type_to_string(typ)).unwrap(),
+            IrArgOp::PrivateClosureIndex(typ, idx) => write!(s, "[pc{}]{}", idx, op_type_to_string(typ)).unwrap(),
+            IrArgOp::LocalClosureIndex(typ, idx) => write!(s, "[lc{}]{}", idx, op_type_to_string(typ)).unwrap(),
+            IrArgOp::GlobalClosureIndex(typ, idx) => write!(s, "[gc{}]{}", idx, op_type_to_string(typ)).unwrap(),
+        }
+    }
+    s
+}
+
+fn ref_to_string(s: String, vector_elem_ptr_type: &Option<Box<IrType>>) -> String
+{
+    match vector_elem_ptr_type {
+        Some(typ) => format!("&<{}>{}", ir_type_to_string(typ), s),
+        None => format!("&{}", s),
+    }
+}
+
+impl PrintVar for IrArgVar
+{
+    fn var_to_string(&self) -> String
+    {
+        match self {
+            IrArgVar::Global(ident, ops) => format!("@{}{}", ident, ir_arg_ops_to_string(ops)),
+            IrArgVar::Local(idx, ops) => format!("v{}{}", idx, ir_arg_ops_to_string(ops)),
+            IrArgVar::CallerFunArg(idx, ops) => format!("c{}{}", idx, ir_arg_ops_to_string(ops)),
+            IrArgVar::PrivateClosure(idx, ops) => format!("pc{}{}", idx, ir_arg_ops_to_string(ops)),
+            IrArgVar::LocalClosure(idx, ops) => format!("lc{}{}", idx, ir_arg_ops_to_string(ops)),
+            IrArgVar::GlobalClosure(idx, ops) => format!("gc{}{}", idx, ir_arg_ops_to_string(ops)),
+            IrArgVar::PrivateHeap(ops) => format!("ph{}", ir_arg_ops_to_string(ops)),
+            IrArgVar::LocalHeap(ops) => format!("lh{}", ir_arg_ops_to_string(ops)),
+            IrArgVar::GlobalHeap(ops) => format!("gh{}", ir_arg_ops_to_string(ops)),
+            IrArgVar::RefGlobal(ident, ops, typ) => ref_to_string(format!("@{}{}", ident, ir_arg_ops_to_string(ops)), typ),
+            IrArgVar::RefLocal(idx, ops, typ) => ref_to_string(format!("v{}{}", idx, ir_arg_ops_to_string(ops)), typ),
+            IrArgVar::RefCallerFunArg(idx, ops, typ) => ref_to_string(format!("c{}{}", idx, ir_arg_ops_to_string(ops)), typ),
+            IrArgVar::RefPrivateClosure(idx, ops, typ) => ref_to_string(format!("pc{}{}", idx, ir_arg_ops_to_string(ops)), typ),
+            IrArgVar::RefLocalClosure(idx, ops, typ) => ref_to_string(format!("lc{}{}", idx, ir_arg_ops_to_string(ops)), typ),
+            IrArgVar::RefGlobalClosure(idx, ops, typ) => ref_to_string(format!("gc{}{}", idx, ir_arg_ops_to_string(ops)), typ),
+            IrArgVar::RefPrivateHeap(ops, typ) => ref_to_string(format!("ph{}", ir_arg_ops_to_string(ops)), typ),
+            IrArgVar::RefLocalHeap(ops, typ) => ref_to_string(format!("lh{}", ir_arg_ops_to_string(ops)), typ),
+            IrArgVar::RefGlobalHeap(ops, typ) => ref_to_string(format!("gh{}", ir_arg_ops_to_string(ops)), typ),
+        }
+    }
+}
+
+pub fn ir_instr_var_to_string(var: &IrInstrVar) -> String
+{
+    match var {
+        IrInstrVar::Global(ident, ops) => format!("@{}{}", ident, ir_arg_ops_to_string(ops)),
+        IrInstrVar::Local(idx, ops) => format!("v{}{}", idx, ir_arg_ops_to_string(ops)),
+        IrInstrVar::CallerFunArg(idx, ops) => format!("c{}{}", idx, ir_arg_ops_to_string(ops)),
+        IrInstrVar::PrivateClosure(idx, ops) => format!("pc{}{}", idx, ir_arg_ops_to_string(ops)),
+        IrInstrVar::LocalClosure(idx, ops) => format!("lc{}{}", idx, ir_arg_ops_to_string(ops)),
+        IrInstrVar::GlobalClosure(idx, ops) => format!("gc{}{}", idx, ir_arg_ops_to_string(ops)),
+        IrInstrVar::PrivateHeap(ops) => format!("ph{}", ir_arg_ops_to_string(ops)),
+        IrInstrVar::LocalHeap(ops) => format!("lh{}", ir_arg_ops_to_string(ops)),
+        IrInstrVar::GlobalHeap(ops) => format!("gh{}", ir_arg_ops_to_string(ops)),
+    }
+}
+
+fn values_to_string<T: PrintVar>(values: &[IrValue<T>]) -> String
+{
+    let ss: Vec<String> = values.iter().map(ir_value_to_string).collect();
+    ss.join(", ")
+}
+
+fn field_pairs_to_string<T: PrintVar>(field_pairs: &[IrFieldPair<T>]) -> String
+{
+    let ss: Vec<String> = field_pairs.iter().map(|p| format!("{} = {}", p.0, ir_value_to_string(&p.1))).collect();
+    ss.join(", ")
+}
+
+fn braces_to_string(s: String) -> String
+{
+    if s.is_empty() {
+        String::from("{}")
+    } else {
+        format!("{{ {} }}", s)
+    }
+}
+
+pub fn ir_value_to_string<T: PrintVar>(value: &IrValue<T>) -> String
+{
+    match value {
+        IrValue::Char(n, typ) => format!("char {}{}", n, as_type_to_string(typ)),
+        IrValue::Short(n, typ) => format!("short {}{}", n, as_type_to_string(typ)),
+        IrValue::Int(n, typ) => format!("int {}{}", n, as_type_to_string(typ)),
+        IrValue::Long(n, typ) => format!("long {}{}", n, as_type_to_string(typ)),
+        IrValue::Uchar(n, typ) => format!("uchar {}{}", n, as_type_to_string(typ)),
+        IrValue::Ushort(n, typ) => format!("ushort {}{}", n, as_type_to_string(typ)),
+        IrValue::Uint(n, typ) => format!("uint {}{}", n, as_type_to_string(typ)),
+        IrValue::Ulong(n, typ) => format!("ulong {}{}", n, as_type_to_string(typ)),
+        IrValue::Float(x, typ) => format!("float {}{}", float_to_string(*x), as_type_to_string(typ)),
+        IrValue::Double(x, typ) => format!("double {}{}", double_to_string(*x), as_type_to_string(typ)),
+        IrValue::SizeT(n, typ) => format!("size_t {}{}", n, as_type_to_string(typ)),
+        IrValue::PtrdiffT(n, typ) => format!("ptrdiff_t {}{}", n, as_type_to_string(typ)),
+        IrValue::IntptrT(n, typ) => format!("intptr_t {}{}", n, as_type_to_string(typ)),
+        IrValue::UintptrT(n, typ) => format!("uintptr_t {}{}", n, as_type_to_string(typ)),
+        IrValue::Object(object) => {
+            match &**object {
+                IrObject::String(bytes) => format!("string {}", ir_bytes_to_string(bytes.as_slice())),
+                IrObject::BuiltinVar(ident, builtin_type, typ) => {
+                    let mut s = format!("builtin {}", ident);
+                    if let Some(builtin_type) = builtin_type {
+                        write!(s, ": {}", ir_type_to_string(builtin_type)).unwrap();
+                    }
+                    s.push_str(as_type_to_string(typ).as_str());
+                    s
+                },
+                IrObject::Var(var, typ) => format!("{}{}", var.var_to_string(), as_type_to_string(typ)),
+                IrObject::Vector(values, typ) => format!("vector {}({})", ir_type_to_string(typ), values_to_string(values.as_slice())),
+                IrObject::Array(values, typ) => format!("array [{}]{}", values_to_string(values.as_slice()), as_type_to_string(typ)),
+                IrObject::Struct(values, field_pairs, typ) => {
+                    let mut s = values_to_string(values.as_slice());
+                    if !field_pairs.is_empty() {
+                        if !s.is_empty() {
+                            s.push(' ');
+                        }
+                        write!(s, "; {}", field_pairs_to_string(field_pairs.as_slice())).unwrap();
+                    }
+                    format!("struct {}{}", braces_to_string(s), as_type_to_string(typ))
+                },
+                IrObject::Union(idx, value, typ) => format!("union {{ {} = {} }}{}", idx, ir_value_to_string(value), as_type_to_string(typ)),
+                IrObject::Closure(field_pairs, typ) => format!("closure {}{}", braces_to_string(field_pairs_to_string(field_pairs.as_slice())), as_type_to_string(typ)),
+                IrObject::Sizeof(typ, typ2) => format!("sizeof({}){}", ir_type_to_string(typ), as_type_to_string(typ2)),
+                IrObject::CallerFunIndex(ident, idx, typ) => format!("caller_index @{} {}{}", ident, idx, as_type_to_string(typ)),
+            }
+        },
+    }
+}
+
+pub fn ir_case_value_to_string(value: &IrCaseValue) -> String
+{
+    match value {
+        IrCaseValue::Char(n, typ) => format!("char {}{}", n, as_type_to_string(typ)),
+        IrCaseValue::Short(n, typ) => format!("short {}{}", n, as_type_to_string(typ)),
+        IrCaseValue::Int(n, typ) => format!("int {}{}", n, as_type_to_string(typ)),
+        IrCaseValue::Long(n, typ) => format!("long {}{}", n, as_type_to_string(typ)),
+        IrCaseValue::Uchar(n, typ) => format!("uchar {}{}", n, as_type_to_string(typ)),
+        IrCaseValue::Ushort(n, typ) => format!("ushort {}{}", n, as_type_to_string(typ)),
+        IrCaseValue::Uint(n, typ) => format!("uint {}{}", n, as_type_to_string(typ)),
+        IrCaseValue::Ulong(n, typ) => format!("ulong {}{}", n, as_type_to_string(typ)),
+        IrCaseValue::SizeT(n, typ) => format!("size_t {}{}", n, as_type_to_string(typ)),
+        IrCaseValue::PtrdiffT(n, typ) => format!("ptrdiff_t {}{}", n, as_type_to_string(typ)),
+        IrCaseValue::IntptrT(n, typ) => format!("intptr_t {}{}", n, as_type_to_string(typ)),
+        IrCaseValue::UintptrT(n, typ) => format!("uintptr_t {}{}", n, as_type_to_string(typ)),
+        IrCaseValue::BuiltinVar(ident, builtin_type, typ) => {
+            let mut s = format!("builtin {}", ident);
+            if let Some(builtin_type) = builtin_type {
+                write!(s, ": {}", ir_type_to_string(builtin_type)).unwrap();
+            }
+            s.push_str(as_type_to_string(typ).as_str());
+            s
+        },
+    }
+}
+
+pub fn ir_op_to_string(op: &IrOp) -> String
+{
+    match op {
+        IrOp::Load(value) => format!("load {}", ir_value_to_string(value)),
+        IrOp::Neg(value) => format!("neg {}", ir_value_to_string(value)),
+        IrOp::Not(value) => format!("not {}", ir_value_to_string(value)),
+        IrOp::Mul(value1, value2) => format!("mul {}, {}", ir_value_to_string(value1), ir_value_to_string(value2)),
+        IrOp::Div(value1, value2) => format!("div {}, {}", ir_value_to_string(value1), ir_value_to_string(value2)),
+        IrOp::Rem(value1, value2) => format!("rem {}, {}", ir_value_to_string(value1), ir_value_to_string(value2)),
+        IrOp::Add(value1, value2) => format!("add {}, {}", ir_value_to_string(value1), ir_value_to_string(value2)),
+        IrOp::Sub(value1, value2) => format!("sub {}, {}", ir_value_to_string(value1), ir_value_to_string(value2)),
+        IrOp::Shl(value1, value2) => format!("shl {}, {}", ir_value_to_string(value1), ir_value_to_string(value2)),
+        IrOp::Shr(value1, value2) => format!("shr {}, {}", ir_value_to_string(value1), ir_value_to_string(value2)),
+        IrOp::Eq(value1, value2) => format!("eq {}, {}", ir_value_to_string(value1), ir_value_to_string(value2)),
+        IrOp::Ne(value1, value2) => format!("ne {}, {}", ir_value_to_string(value1), ir_value_to_string(value2)),
+        IrOp::Lt(value1, value2) => format!("lt {}, {}", ir_value_to_string(value1), ir_value_to_string(value2)),
+        IrOp::Ge(value1, value2) => format!("ge {}, {}", ir_value_to_string(value1), ir_value_to_string(value2)),
+        IrOp::Gt(value1, value2) => format!("gt {}, {}", ir_value_to_string(value1), ir_value_to_string(value2)),
+        IrOp::Le(value1, value2) => format!("le {}, {}", ir_value_to_string(value1), ir_value_to_string(value2)),
+        IrOp::And(value1, value2) => format!("and {}, {}", ir_value_to_string(value1), ir_value_to_string(value2)),
+        IrOp::Xor(value1, value2) => format!("xor {}, {}", ir_value_to_string(value1), ir_value_to_string(value2)),
+        IrOp::Or(value1, value2) => format!("or {}, {}", ir_value_to_string(value1), ir_value_to_string(value2)),
+        IrOp::CallBuiltinFun(ident, typ, values) => format!("call_builtin {}{}({})", ident, op_type_to_string(typ), values_to_string(values.as_slice())),
+        IrOp::CallFun(ident, values, pos, panic_poses) => format!("call @{}({}) {}", ident, values_to_string(values.as_slice()), poses_to_string(pos, panic_poses.as_slice())),
+        IrOp::CallFunWithoutPanic(ident, values, pos) => format!("call_without_panic @{}({}) {}", ident, values_to_string(values.as_slice()), poses_to_string(pos, &[])),
+    }
+}
+
+fn local_var_modifier_to_string(modifier: IrLocalVarModifier) -> &'static str
+{
+    match modifier {
+        IrLocalVarModifier::None => "",
+        IrLocalVarModifier::Private => "private ",
+        IrLocalVarModifier::Local => "local ",
+        IrLocalVarModifier::Global => "global ",
+    }
+}
+
+fn add_block_to_string(block: &IrBlock, level: usize, s: &mut String)
+{
+    s.push_str("{\n");
+    for local_var_pair in block.local_var_pairs() {
+        writeln!(s, "{}var {}{};", indent_to_string(level + 1), local_var_modifier_to_string(local_var_pair.0), ir_type_to_string(&local_var_pair.1)).unwrap();
+    }
+    for instr in block.instrs() {
+        add_instr_to_string(instr, level + 1, s);
+    }
+    s.push_str(indent_to_string(level).as_str());
+    s.push('}');
+}
+
+fn add_instr_to_string(instr: &IrInstr, level: usize, s: &mut String)
+{
+    s.push_str(indent_to_string(level).as_str());
+    match instr {
+        IrInstr::Op(op) => writeln!(s, "{};", ir_op_to_string(op)).unwrap(),
+        IrInstr::Assign(var, op) => writeln!(s, "{} = {};", ir_instr_var_to_string(var), ir_op_to_string(op)).unwrap(),
+        IrInstr::Return(Some(op)) => writeln!(s, "return {};", ir_op_to_string(op)).unwrap(),
+        IrInstr::Return(None) => s.push_str("return;\n"),
+        IrInstr::Break => s.push_str("break;\n"),
+        IrInstr::Continue => s.push_str("continue;\n"),
+        IrInstr::Block(block) => {
+            add_block_to_string(block, level, s);
+            s.push('\n');
+        },
+        IrInstr::If(op, block1, block2) => {
+            write!(s, "if {} ", ir_op_to_string(op)).unwrap();
+            add_block_to_string(block1, level, s);
+            s.push_str(" else ");
+            add_block_to_string(block2, level, s);
+            s.push('\n');
+        },
+        IrInstr::Switch(op, cases) => {
+            writeln!(s, "switch {} {{", ir_op_to_string(op)).unwrap();
+            for case in cases {
+                s.push_str(indent_to_string(level + 1).as_str());
+                match case {
+                    IrCase::Case(value, block) => {
+                        write!(s, "case {} ", ir_case_value_to_string(value)).unwrap();
+                        add_block_to_string(block, level + 1, s);
+                    },
+                    IrCase::Default(block) => {
+                        s.push_str("default ");
+                        add_block_to_string(block, level + 1, s);
+                    },
+                }
+                s.push('\n');
+            }
+            s.push_str(indent_to_string(level).as_str());
+            s.push_str("}\n");
+        },
+        IrInstr::Loop(block) => {
+            s.push_str("loop ");
+            add_block_to_string(block, level, s);
+            s.push('\n');
+        },
+        IrInstr::Panic(msg, pos, poses) => writeln!(s, "panic {} {};", ir_bytes_to_string(msg.as_bytes()), poses_to_string(pos, poses.as_slice())).unwrap(),
+    }
+}
+
+pub fn ir_block_to_string(block: &IrBlock) -> String
+{
+    let mut s = String::new();
+    add_block_to_string(block, 0, &mut s);
+    s
+}
+
+fn flags_to_string(private_heap_flag: IrPrivateHeapFlag, local_heap_flag: IrLocalHeapFlag, global_heap_flag: IrGlobalHeapFlag, panic_flag: IrPanicFlag) -> String
+{
+    let mut flags: Vec<&str> = Vec::new();
+    if private_heap_flag == IrPrivateHeapFlag::Heap {
+        flags.push("private_heap");
+    }
+    if local_heap_flag == IrLocalHeapFlag::Heap {
+        flags.push("local_heap");
+    }
+    if global_heap_flag == IrGlobalHeapFlag::Heap {
+        flags.push("global_heap");
+    }
+    if panic_flag == IrPanicFlag::Panic {
+        flags.push("panic");
+    }
+    if flags.is_empty() {
+        String::new()
+    } else {
+        format!(" with {}", flags.join(", "))
+    }
+}
+
+fn types_to_string(types: &[Box<IrType>]) -> String
+{
+    let ss: Vec<String> = types.iter().map(|t| ir_type_to_string(t)).collect();
+    ss.join(", ")
+}
+
+fn add_fun_to_string(ident: &String, fun: &IrFun, s: &mut String)
+{
+    match fun {
+        IrFun::Fun(modifier, arg_types, ret_type, block, private_heap_flag, local_heap_flag, global_heap_flag, panic_flag) => {
+            match modifier {
+                IrFunModifier::None => (),
+                IrFunModifier::Inline => s.push_str("inline "),
+                IrFunModifier::Kernel => s.push_str("kernel "),
+            }
+            writeln!(s, "fun @{}({}) -> {}{}", ident, types_to_string(arg_types.as_slice()), ir_type_to_string(ret_type), flags_to_string(*private_heap_flag, *local_heap_flag, *global_heap_flag, *panic_flag)).unwrap();
+            add_block_to_string(block, 0, s);
+            s.push('\n');
+        },
+        IrFun::Caller(fun_type, arg_types, ret_type, caller_funs, private_heap_flag, local_heap_flag, global_heap_flag, panic_flag) => {
+            writeln!(s, "caller @{}({}; {}) -> {}{}", ident, ir_type_to_string(fun_type), types_to_string(arg_types.as_slice()), ir_type_to_string(ret_type), flags_to_string(*private_heap_flag, *local_heap_flag, *global_heap_flag, *panic_flag)).unwrap();
+            s.push_str("{\n");
+            for (idx, caller_fun) in caller_funs.funs() {
+                write!(s, "{}{}: ", indent_to_string(1), idx).unwrap();
+                match caller_fun {
+                    IrCallerFun::Fun(fun_ident) => writeln!(s, "fun @{};", fun_ident).unwrap(),
+                    IrCallerFun::InlineFun(fun_ident, block) => {
+                        write!(s, "inline @{} ", fun_ident).unwrap();
+                        add_block_to_string(block, 1, s);
+                        s.push('\n');
+                    },
+                    IrCallerFun::Lambda(private_closure_type, local_closure_type, global_closure_type, start_idx, block) => {
+                        s.push_str("lambda");
+                        if let Some(typ) = private_closure_type {
+                            write!(s, " private {}", ir_type_to_string(typ)).unwrap();
+                        }
+                        if let Some(typ) = local_closure_type {
+                            write!(s, " local {}", ir_type_to_string(typ)).unwrap();
+                        }
+                        if let Some(typ) = global_closure_type {
+                            write!(s, " global {}", ir_type_to_string(typ)).unwrap();
+                        }
+                        write!(s, " start {} ", start_idx).unwrap();
+                        add_block_to_string(block, 1, s);
+                        s.push('\n');
+                    },
+                }
+            }
+            s.push_str("}\n");
+        },
+    }
+}
+
+pub fn ir_def_to_string(def: &IrDef) -> String
+{
+    let mut s = String::new();
+    match def {
+        IrDef::Struct(ident, struct1) => {
+            match &*struct1.borrow() {
+                IrStruct::Struct(field_types) => writeln!(s, "struct {} {}", ident, braces_to_string(types_to_string(field_types.as_slice()))).unwrap(),
+                IrStruct::Closure(field_types) => {
+                    let ss: Vec<String> = field_types.iter().map(|p| format!("{}: {}", p.0, ir_type_to_string(p.1))).collect();
+                    writeln!(s, "closure struct {} {}", ident, braces_to_string(ss.join(", "))).unwrap();
+                },
+            }
+        },
+        IrDef::Union(ident, union) => writeln!(s, "union {} {}", ident, braces_to_string(types_to_string(union.borrow().0.as_slice()))).unwrap(),
+        IrDef::Var(ident, var) => {
+            match &*var.borrow() {
+                IrVar::Const(typ, value) => writeln!(s, "const @{}: {} = {};", ident, ir_type_to_string(typ), ir_value_to_string(value)).unwrap(),
+                IrVar::Var(modifier, access_modifier, typ, value) => {
+                    s.push_str("var ");
+                    if *modifier == IrGlobalVarModifier::Global {
+                        s.push_str("global ");
+                    }
+                    if *access_modifier == IrAccessModifier::Const {
+                        s.push_str("const ");
+                    }
+                    writeln!(s, "@{}: {} = {};", ident, ir_type_to_string(typ), ir_value_to_string(value)).unwrap();
+                },
+                IrVar::Fun(fun) => add_fun_to_string(ident, fun, &mut s),
+            }
+        },
+    }
+    s
+}
+
+pub fn ir_tree_to_string(tree: &IrTree) -> String
+{
+    let ss: Vec<String> = tree.defs().iter().map(|d| ir_def_to_string(d)).collect();
+    ss.join("\n")
+}
+
+impl fmt::Display for IrTree
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    { write!(f, "{}", ir_tree_to_string(self)) }
+}
+
+impl fmt::Display for IrBlock
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    { write!(f, "{}", ir_block_to_string(self)) }
+}
@@ -0,0 +1,26 @@
+//
+// Copyright (c) 2025 Łukasz Szpakowski
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+use crate::backend::ir::parser::*;
+use crate::backend::ir::*;
+
+pub fn parse_ir_string(s: &str) -> IrTree
+{
+    match parse_ir_tree(s) {
+        Ok(ir_tree) => ir_tree,
+        Err(err) => {
+            println!("{}", err);
+            panic!();
+        },
+    }
+}
+
+pub fn int_buffer(xs: &[i32]) -> Vec<u8>
+{ xs.iter().flat_map(|x| x.to_le_bytes()).collect() }
+
+pub fn buffer_ints(bytes: &[u8]) -> Vec<i32>
+{ bytes.chunks(4).map(|b| i32::from_le_bytes([b[0], b[1], b[2], b[3]])).collect() }
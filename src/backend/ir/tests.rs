@@ -0,0 +1,431 @@
+//
+// Copyright (c) 2025 Łukasz Szpakowski
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+use crate::frontend::do_frontend_phases;
+use crate::backend::error::*;
+use crate::backend::lowering::*;
+use super::parser::*;
+use super::printer::*;
+use super::*;
+
+#[test]
+fn test_ir_parse_ir_tree_parses_printed_lowered_tree()
+{
+    let s = "
+import opencl;
+data List = Nil() | Cons(Int, UniqPrivateBox<List>);
+data T<t> = A() | B(t, Float);
+recursive(16) sum(l: List) -> Int =
+    l match {
+        Nil() => 0;
+        Cons(x, b) => x + sum(op_get(b));
+    };
+f(n: Int) -> List = Cons(n, uniq_private_box(Cons(n + 1, uniq_private_box(Nil()))));
+lg(n: Int) -> Int = op_get(uniq_local_box(n)) + op_get(uniq_global_box(n));
+apply(f: (Int) -> Int, x: Int) -> Int = f(x);
+inc(x: Int) -> Int = x + 1;
+g(t: T<Int>) -> Float =
+    t match {
+        A() => 1.5;
+        B(_, y) => y / 0.1;
+    };
+kernel k(xs: UniqGlobalSlice<Int>, y: Int) -> () =
+    let i = get_global_id(0 as Uint);
+        _ = xs[i] <- (sum(f(1)) + lg(2) + apply(|z| z + y, y) + apply(inc, y));
+        _ = g(B(y, 2.5));
+    in  ();
+";
+    let s2 = &s[1..];
+    let tree = match do_frontend_phases(s2) {
+        Ok(tree) => tree,
+        Err(errs) => {
+            println!("{}", errs);
+            panic!();
+        },
+    };
+    let ir_tree = match Lowerer::new_with_inline_threshold(0).lower_tree(&tree) {
+        Ok(ir_tree) => ir_tree,
+        Err(err) => {
+            println!("{}", err);
+            panic!();
+        },
+    };
+    let ir_s = ir_tree_to_string(&ir_tree);
+    match parse_ir_tree(ir_s.as_str()) {
+        Ok(ir_tree2) => {
+            assert_eq!(ir_s, ir_tree_to_string(&ir_tree2));
+            assert_eq!(ir_tree.defs().len(), ir_tree2.defs().len());
+            assert_eq!(ir_tree.structs().len(), ir_tree2.structs().len());
+            assert_eq!(ir_tree.unions().len(), ir_tree2.unions().len());
+            assert_eq!(ir_tree.vars().len(), ir_tree2.vars().len());
+            match ir_tree2.var(&String::from("_VF3sum")) {
+                Some(var) => {
+                    match &*var.borrow() {
+                        IrVar::Fun(fun) => {
+                            match &**fun {
+                                IrFun::Fun(_, _, _, _, _, _, _, IrPanicFlag::Panic) => assert!(true),
+                                _ => assert!(false),
+                            }
+                        },
+                        _ => assert!(false),
+                    }
+                },
+                None => assert!(false),
+            }
+            match ir_tree2.var(&String::from("_VF1f")) {
+                Some(var) => {
+                    match &*var.borrow() {
+                        IrVar::Fun(fun) => {
+                            match &**fun {
+                                IrFun::Fun(_, _, _, _, IrPrivateHeapFlag::Heap, IrLocalHeapFlag::None, IrGlobalHeapFlag::None, IrPanicFlag::Panic) => assert!(true),
+                                _ => assert!(false),
+                            }
+                        },
+                        _ => assert!(false),
+                    }
+                },
+                None => assert!(false),
+            }
+        },
+        Err(err) => {
+            println!("{}", ir_s);
+            println!("{}", err);
+            assert!(false);
+        },
+    }
+}
+
+#[test]
+fn test_ir_parse_ir_tree_parses_hand_written_tree()
+{
+    let s = "
+struct S { int, *global float, [uchar; 4] }
+
+closure struct C { 0: int, 2: float4 }
+
+union U { struct S, double }
+
+const @c: int = int 2 as long;
+
+var global const @a: [struct S; 2] = array [struct { int 1, &@b.f1[3]<float>, array [uchar 255, uchar 0] }, struct { ; 0 = int -1 }];
+
+var @b: struct S = struct {};
+
+fun @f(*private struct S, int) -> int with panic
+{
+    var int;
+    var local float;
+    v2 = load c0->f0;
+    @b.f0 = add v2, int 1;
+    v3 = call_builtin convert_float<float>(v2 as float, builtin M_PI_F: float, sizeof(struct S) as uint);
+    if lt v2, int 0 {
+        panic \"negative \\\"number\\\"\\n\" at \"a.vscfl\":1:2 from \"b.vscfl\":3:4;
+    } else {
+    }
+    switch load v2 {
+        case int 0 {
+            return load int 0;
+        }
+        case builtin X: int as int {
+            break;
+        }
+        default {
+            {
+                var double;
+                v4 = load double -inf;
+                v4 = load double nan;
+            }
+        }
+    }
+    loop {
+        v2 = sub v2, int 1;
+        continue;
+    }
+    return call @g(&<*private int>v2[v3].*, union { 1 = double 1000 } as union U) at \"a.vscfl\":5:6 from \"a.vscfl\":7:8, \"a.vscfl\":9:10;
+}
+
+inline fun @g(*private int, union U) -> int
+{
+    ph.* = load closure { 0 = int 1, 2 = vector float4(float 0.5, float -0.25, float 1, float 2) } as struct C;
+    lh[pc0]<int>.f1 = load &gh[@c] as *global int;
+    return call_without_panic @h(caller_index @k 3, lc1, gc2, &<*private int>ph) at \"a.vscfl\":1:1;
+}
+
+kernel fun @k() -> void with private_heap, local_heap, global_heap
+{
+    return;
+}
+
+caller @h(struct S; int, float) -> int with panic
+{
+    1: fun @f;
+    3: inline @g {
+        return load int 1;
+    }
+    4: lambda private struct C global struct S start 2 {
+        return load pc0;
+    }
+}
+";
+    let s2 = &s[1..];
+    match parse_ir_tree_with_path("test.ir", s2) {
+        Ok(ir_tree) => {
+            assert_eq!(10, ir_tree.defs().len());
+            match ir_tree.var(&String::from("f")) {
+                Some(var) => {
+                    match &*var.borrow() {
+                        IrVar::Fun(fun) => {
+                            match &**fun {
+                                IrFun::Fun(IrFunModifier::None, arg_types, ret_type, block, IrPrivateHeapFlag::None, IrLocalHeapFlag::None, IrGlobalHeapFlag::None, IrPanicFlag::Panic) => {
+                                    assert_eq!(vec![Box::new(IrType::Ptr(IrPtrModifier::Private, IrAccessModifier::None, Box::new(IrType::Struct(String::from("S"))))), Box::new(IrType::Int)], *arg_types);
+                                    assert_eq!(IrType::Int, **ret_type);
+                                    assert_eq!(2, block.local_var_pairs().len());
+                                    assert_eq!(7, block.block_count());
+                                    match block.instrs().first() {
+                                        Some(IrInstr::Assign(var, IrOp::Load(IrValue::Object(object)))) => {
+                                            match &**var {
+                                                IrInstrVar::Local(2, ops) => assert!(ops.is_empty()),
+                                                _ => assert!(false),
+                                            }
+                                            match &**object {
+                                                IrObject::Var(IrArgVar::CallerFunArg(0, ops), None) => assert_eq!(vec![IrArgOp::Arrow(None, 0)], *ops),
+                                                _ => assert!(false),
+                                            }
+                                        },
+                                        _ => assert!(false),
+                                    }
+                                    match block.instrs().last() {
+                                        Some(IrInstr::Return(Some(IrOp::CallFun(ident, values, pos, poses)))) => {
+                                            assert_eq!(String::from("g"), *ident);
+                                            match values.first() {
+                                                Some(IrValue::Object(object)) => {
+                                                    match &**object {
+                                                        IrObject::Var(IrArgVar::RefLocal(2, ops, Some(typ)), None) => {
+                                                            assert_eq!(vec![IrArgOp::LocalIndex(None, 3), IrArgOp::Deref(None)], *ops);
+                                                            assert_eq!(IrType::Ptr(IrPtrModifier::Private, IrAccessModifier::None, Box::new(IrType::Int)), **typ);
+                                                        },
+                                                        _ => assert!(false),
+                                                    }
+                                                },
+                                                _ => assert!(false),
+                                            }
+                                            assert_eq!(String::from("a.vscfl"), *pos.path);
+                                            assert_eq!(5, pos.line);
+                                            assert_eq!(6, pos.column);
+                                            assert_eq!(2, poses.len());
+                                        },
+                                        _ => assert!(false),
+                                    }
+                                },
+                                _ => assert!(false),
+                            }
+                        },
+                        _ => assert!(false),
+                    }
+                },
+                None => assert!(false),
+            }
+            match ir_tree.var(&String::from("h")) {
+                Some(var) => {
+                    match &*var.borrow() {
+                        IrVar::Fun(fun) => {
+                            match &**fun {
+                                IrFun::Caller(_, _, _, caller_funs, _, _, _, IrPanicFlag::Panic) => {
+                                    assert_eq!(vec![1, 3, 4], caller_funs.funs().keys().cloned().collect::<Vec<usize>>());
+                                    match caller_funs.fun(4) {
+                                        Some(IrCallerFun::Lambda(Some(_), None, Some(_), 2, _)) => assert!(true),
+                                        _ => assert!(false),
+                                    }
+                                },
+                                _ => assert!(false),
+                            }
+                        },
+                        _ => assert!(false),
+                    }
+                },
+                None => assert!(false),
+            }
+            assert_eq!(s2, ir_tree_to_string(&ir_tree));
+        },
+        Err(err) => {
+            println!("{}", err);
+            assert!(false);
+        },
+    }
+}
+
+#[test]
+fn test_ir_parse_ir_block_parses_block()
+{
+    let s = "
+{
+    var float;
+    v0 = mul float 0.1, float 3.4028235e38;
+    v0 = load float -0;
+    return load v0;
+}";
+    let s2 = &s[1..];
+    match parse_ir_block(s2) {
+        Ok(block) => {
+            match block.instrs().first() {
+                Some(IrInstr::Assign(_, IrOp::Mul(IrValue::Float(x, None), IrValue::Float(y, None)))) => {
+                    assert_eq!(0.1f32, *x);
+                    assert_eq!(f32::MAX, *y);
+                },
+                _ => assert!(false),
+            }
+            match block.instrs().get(1) {
+                Some(IrInstr::Assign(_, IrOp::Load(IrValue::Float(x, None)))) => assert!(x.is_sign_negative()),
+                _ => assert!(false),
+            }
+            let s3 = ir_block_to_string(&block);
+            match parse_ir_block(s3.as_str()) {
+                Ok(block2) => assert_eq!(s3, format!("{}", block2)),
+                Err(_) => assert!(false),
+            }
+        },
+        Err(err) => {
+            println!("{}", err);
+            assert!(false);
+        },
+    }
+}
+
+#[test]
+fn test_ir_parse_ir_tree_complains_on_syntax_errors()
+{
+    let s = "
+fun @f() -> int
+{
+    v0 = load int 1
+}
+";
+    let s2 = &s[1..];
+    match parse_ir_tree_with_path("test.ir", s2) {
+        Err(BackendError::Messages(msg_errs)) => {
+            assert_eq!(1, msg_errs.len());
+            assert_eq!(String::from("test.ir"), *msg_errs[0].0.path);
+            assert_eq!(4, msg_errs[0].0.line);
+            assert_eq!(1, msg_errs[0].0.column);
+            assert_eq!(String::from("unexpected }"), msg_errs[0].1);
+        },
+        _ => assert!(false),
+    }
+    let s = "
+struct S { int }
+struct S { long }
+";
+    let s2 = &s[1..];
+    match parse_ir_tree_with_path("test.ir", s2) {
+        Err(BackendError::Messages(msg_errs)) => {
+            assert_eq!(1, msg_errs.len());
+            assert_eq!(2, msg_errs[0].0.line);
+            assert_eq!(8, msg_errs[0].0.column);
+            assert_eq!(String::from("structure S is already defined"), msg_errs[0].1);
+        },
+        _ => assert!(false),
+    }
+    match parse_ir_tree_with_path("test.ir", "var @x: int = int 128 as char; var @y: char = char 128;") {
+        Err(BackendError::Messages(msg_errs)) => {
+            assert_eq!(1, msg_errs.len());
+            assert_eq!(1, msg_errs[0].0.line);
+            assert_eq!(52, msg_errs[0].0.column);
+            assert_eq!(String::from("invalid number 128"), msg_errs[0].1);
+        },
+        _ => assert!(false),
+    }
+}
+
+#[test]
+fn test_ir_block_add_inline_fun_renumbers_variables_of_callee()
+{
+    let s = "
+{
+    var int;
+    var int;
+    v2 = add v1, int 1;
+    {
+        var int;
+        v4 = mul v2, int 2;
+        v3 = load v4;
+    }
+    return load v3;
+}";
+    let s2 = "
+{
+    {
+        var int;
+        v2 = load v0;
+        {
+            var int;
+            var int;
+            v3 = add v2, int 1;
+            {
+                var int;
+                v5 = mul v3, int 2;
+                v4 = load v5;
+            }
+            v1 = load v4;
+        }
+    }
+}";
+    let fun_block = parse_ir_block(&s[1..]).unwrap();
+    let fun_arg_types = vec![Box::new(IrType::Int), Box::new(IrType::Int)];
+    let arg_substitutions = vec![
+        ArgSubstitution::Fun(String::from("g")),
+        ArgSubstitution::Value(IrValue::Object(Box::new(IrObject::Var(IrArgVar::Local(0, Vec::new()), None))))
+    ];
+    let ret_var = Box::new(IrInstrVar::Local(1, Vec::new()));
+    let pos = Pos::new(String::from("a.vscfl"), 1, 2);
+    let mut block = IrBlock::new();
+    match block.add_inline_fun(0, fun_arg_types.as_slice(), &fun_block, 2, arg_substitutions.as_slice(), Some(Some(&ret_var)), &pos, &[], &IrTree::new()) {
+        Ok(()) => assert_eq!(String::from(&s2[1..]), ir_block_to_string(&block)),
+        Err(err) => {
+            println!("{:?}", err);
+            assert!(false);
+        },
+    }
+}
+
+#[test]
+fn test_ir_block_add_inline_fun_adds_call_positions_to_panics()
+{
+    let s = "
+{
+    if lt v0, int 0 {
+        panic \"negative\" at \"f.vscfl\":1:2;
+    } else {
+    }
+    return load v0;
+}";
+    let s2 = "
+{
+    {
+        var int;
+        v1 = load int 5;
+        if lt v1, int 0 {
+            panic \"negative\" at \"f.vscfl\":1:2 from \"a.vscfl\":3:4, \"b.vscfl\":5:6;
+        } else {
+        }
+        v0 = load v1;
+    }
+}";
+    let fun_block = parse_ir_block(&s[1..]).unwrap();
+    let fun_arg_types = vec![Box::new(IrType::Int)];
+    let arg_substitutions = vec![ArgSubstitution::Value(IrValue::Int(5, None))];
+    let ret_var = Box::new(IrInstrVar::Local(0, Vec::new()));
+    let pos = Pos::new(String::from("a.vscfl"), 3, 4);
+    let panic_poses = vec![Pos::new(String::from("b.vscfl"), 5, 6)];
+    let mut block = IrBlock::new();
+    match block.add_inline_fun(0, fun_arg_types.as_slice(), &fun_block, 1, arg_substitutions.as_slice(), Some(Some(&ret_var)), &pos, panic_poses.as_slice(), &IrTree::new()) {
+        Ok(()) => assert_eq!(String::from(&s2[1..]), ir_block_to_string(&block)),
+        Err(err) => {
+            println!("{:?}", err);
+            assert!(false);
+        },
+    }
+}
@@ -0,0 +1,225 @@
+//
+// Copyright (c) 2025 Łukasz Szpakowski
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+use crate::backend::ir::*;
+
+pub fn vector_type_parts(typ: &IrType) -> Option<(IrType, usize)>
+{
+    match typ {
+        IrType::CharN(n) => Some((IrType::Char, *n)),
+        IrType::ShortN(n) => Some((IrType::Short, *n)),
+        IrType::IntN(n) => Some((IrType::Int, *n)),
+        IrType::LongN(n) => Some((IrType::Long, *n)),
+        IrType::UcharN(n) => Some((IrType::Uchar, *n)),
+        IrType::UshortN(n) => Some((IrType::Ushort, *n)),
+        IrType::UintN(n) => Some((IrType::Uint, *n)),
+        IrType::UlongN(n) => Some((IrType::Ulong, *n)),
+        IrType::FloatN(n) => Some((IrType::Float, *n)),
+        IrType::DoubleN(n) => Some((IrType::Double, *n)),
+        _ => None,
+    }
+}
+
+pub fn vector_type(elem_type: &IrType, n: usize) -> Option<IrType>
+{
+    match elem_type {
+        IrType::Char => Some(IrType::CharN(n)),
+        IrType::Short => Some(IrType::ShortN(n)),
+        IrType::Int => Some(IrType::IntN(n)),
+        IrType::Long | IrType::PtrdiffT | IrType::IntptrT => Some(IrType::LongN(n)),
+        IrType::Uchar => Some(IrType::UcharN(n)),
+        IrType::Ushort => Some(IrType::UshortN(n)),
+        IrType::Uint => Some(IrType::UintN(n)),
+        IrType::Ulong | IrType::SizeT | IrType::UintptrT => Some(IrType::UlongN(n)),
+        IrType::Float => Some(IrType::FloatN(n)),
+        IrType::Double => Some(IrType::DoubleN(n)),
+        _ => None,
+    }
+}
+
+pub fn int_type(bits: u32, is_signed: bool) -> IrType
+{
+    match (bits, is_signed) {
+        (8, true) => IrType::Char,
+        (16, true) => IrType::Short,
+        (32, true) => IrType::Int,
+        (8, false) => IrType::Uchar,
+        (16, false) => IrType::Ushort,
+        (32, false) => IrType::Uint,
+        (_, true) => IrType::Long,
+        (_, false) => IrType::Ulong,
+    }
+}
+
+pub fn promoted_type(typ: &IrType) -> IrType
+{
+    match typ {
+        IrType::Char | IrType::Short | IrType::Uchar | IrType::Ushort => IrType::Int,
+        IrType::Half => IrType::Float,
+        _ => typ.clone(),
+    }
+}
+
+pub fn int_min(bits: u32, is_signed: bool) -> i128
+{
+    if is_signed {
+        -(1i128 << (bits - 1))
+    } else {
+        0
+    }
+}
+
+pub fn int_max(bits: u32, is_signed: bool) -> i128
+{
+    if is_signed {
+        (1i128 << (bits - 1)) - 1
+    } else {
+        (1i128 << bits) - 1
+    }
+}
+
+pub fn wrap_int(a: i128, bits: u32, is_signed: bool) -> i128
+{
+    let b = a & ((1i128 << bits) - 1);
+    if is_signed && ((b >> (bits - 1)) & 1) == 1 {
+        b - (1i128 << bits)
+    } else {
+        b
+    }
+}
+
+pub fn f16_bits_to_f32(h: u16) -> f32
+{
+    let sign = ((h >> 15) as u32) << 31;
+    let exp = ((h >> 10) & 0x1f) as u32;
+    let mant = (h & 0x3ff) as u32;
+    if exp == 0 {
+        let x = (mant as f32) * 2.0f32.powi(-24);
+        if sign != 0 { -x } else { x }
+    } else if exp == 0x1f {
+        f32::from_bits(sign | 0x7f800000 | (mant << 13))
+    } else {
+        f32::from_bits(sign | ((exp + 112) << 23) | (mant << 13))
+    }
+}
+
+pub fn f32_to_f16_bits(x: f32) -> u16
+{
+    let bits = x.to_bits();
+    let sign = ((bits >> 16) & 0x8000) as u16;
+    let exp = ((bits >> 23) & 0xff) as i32;
+    let mant = bits & 0x7fffff;
+    if exp == 0xff {
+        return sign | 0x7c00 | (if mant != 0 { 0x200 } else { 0 });
+    }
+    let e = exp - 127 + 15;
+    if e >= 0x1f {
+        return sign | 0x7c00;
+    }
+    if e <= 0 {
+        if e < -10 {
+            return sign;
+        }
+        let m = mant | 0x800000;
+        let shift = (14 - e) as u32;
+        let half = 1u32 << (shift - 1);
+        let rest = m & ((1u32 << shift) - 1);
+        let mut h = m >> shift;
+        if rest > half || (rest == half && (h & 1) == 1) {
+            h += 1;
+        }
+        return sign | (h as u16);
+    }
+    let mut h = ((e as u32) << 10) | (mant >> 13);
+    let rest = mant & 0x1fff;
+    if rest > 0x1000 || (rest == 0x1000 && (h & 1) == 1) {
+        h += 1;
+    }
+    sign | (h as u16)
+}
+
+pub fn panic_buffer_name() -> &'static str
+{ "pb" }
+
+pub fn panic_flag_name() -> &'static str
+{ "pf" }
+
+pub fn private_heap_name() -> &'static str
+{ "ph" }
+
+pub fn local_heap_name() -> &'static str
+{ "lh" }
+
+pub fn global_heap_name() -> &'static str
+{ "gh" }
+
+pub fn hidden_arg_names(private_heap_flag: IrPrivateHeapFlag, local_heap_flag: IrLocalHeapFlag, global_heap_flag: IrGlobalHeapFlag, panic_flag: IrPanicFlag) -> Vec<&'static str>
+{
+    let mut names: Vec<&'static str> = Vec::new();
+    if private_heap_flag == IrPrivateHeapFlag::Heap {
+        names.push(private_heap_name());
+    }
+    if local_heap_flag == IrLocalHeapFlag::Heap {
+        names.push(local_heap_name());
+    }
+    if global_heap_flag == IrGlobalHeapFlag::Heap {
+        names.push(global_heap_name());
+    }
+    if panic_flag == IrPanicFlag::Panic {
+        names.push(panic_buffer_name());
+        names.push(panic_flag_name());
+    }
+    names
+}
+
+pub fn hidden_params<F>(private_heap_flag: IrPrivateHeapFlag, local_heap_flag: IrLocalHeapFlag, global_heap_flag: IrGlobalHeapFlag, panic_flag: IrPanicFlag, is_kernel: bool, f: F) -> Vec<String>
+    where F: Fn(IrPtrModifier, &str) -> String
+{
+    let mut params: Vec<String> = Vec::new();
+    if private_heap_flag == IrPrivateHeapFlag::Heap && !is_kernel {
+        params.push(f(IrPtrModifier::Private, private_heap_name()));
+    }
+    if local_heap_flag == IrLocalHeapFlag::Heap && !is_kernel {
+        params.push(f(IrPtrModifier::Local, local_heap_name()));
+    }
+    if global_heap_flag == IrGlobalHeapFlag::Heap {
+        params.push(f(IrPtrModifier::Global, global_heap_name()));
+    }
+    if panic_flag == IrPanicFlag::Panic {
+        params.push(f(IrPtrModifier::Global, panic_buffer_name()));
+        if !is_kernel {
+            params.push(f(IrPtrModifier::Private, panic_flag_name()));
+        }
+    }
+    params
+}
+
+pub fn float_to_string(x: f32) -> String
+{
+    if x.is_nan() {
+        String::from("NAN")
+    } else if x.is_infinite() {
+        if x < 0.0 { String::from("(-INFINITY)") } else { String::from("INFINITY") }
+    } else if x < 0.0 || (x == 0.0 && x.is_sign_negative()) {
+        format!("({:?}f)", x)
+    } else {
+        format!("{:?}f", x)
+    }
+}
+
+pub fn double_to_string(x: f64) -> String
+{
+    if x.is_nan() {
+        String::from("((double) NAN)")
+    } else if x.is_infinite() {
+        if x < 0.0 { String::from("(-HUGE_VAL)") } else { String::from("HUGE_VAL") }
+    } else if x < 0.0 || (x == 0.0 && x.is_sign_negative()) {
+        format!("({:?})", x)
+    } else {
+        format!("{:?}", x)
+    }
+}
@@ -0,0 +1,248 @@
+//
+// Copyright (c) 2025 Łukasz Szpakowski
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+use std::cell::*;
+use std::rc::*;
+use crate::frontend::error::Pos;
+use crate::frontend::tree::*;
+use crate::backend::error::*;
+use crate::backend::ir::*;
+use crate::backend::lowering::*;
+
+// Layouts are only computed for devices with 64-bit addresses. Manifests and Rust
+// bindings export ADDRESS_BITS so that a host can reject other devices.
+pub const ADDRESS_BITS: u32 = 64;
+
+const PTR_SIZE: usize = (ADDRESS_BITS / 8) as usize;
+
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct Layout
+{
+    pub size: usize,
+    pub align: usize,
+    pub field_offsets: Vec<usize>,
+    pub tag_offset: Option<usize>,
+    pub con_layouts: Vec<ConLayout>,
+}
+
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct ConLayout
+{
+    pub ident: String,
+    pub tag: u32,
+    pub field_offsets: Vec<usize>,
+}
+
+fn align_offset(offset: usize, align: usize) -> usize
+{ offset.div_ceil(align) * align }
+
+fn scalar_size(typ: &IrType) -> Option<usize>
+{
+    match typ {
+        IrType::Char | IrType::Uchar => Some(1),
+        IrType::Short | IrType::Ushort | IrType::Half => Some(2),
+        IrType::Int | IrType::Uint | IrType::Float => Some(4),
+        IrType::Long | IrType::Ulong | IrType::Double => Some(8),
+        IrType::SizeT | IrType::PtrdiffT | IrType::IntptrT | IrType::UintptrT => Some(PTR_SIZE),
+        IrType::Ptr(_, _, _) => Some(PTR_SIZE),
+        _ => None,
+    }
+}
+
+fn vector_elem_size_and_len(typ: &IrType) -> Option<(usize, usize)>
+{
+    match typ {
+        IrType::CharN(n) | IrType::UcharN(n) => Some((1, *n)),
+        IrType::ShortN(n) | IrType::UshortN(n) => Some((2, *n)),
+        IrType::IntN(n) | IrType::UintN(n) | IrType::FloatN(n) => Some((4, *n)),
+        IrType::LongN(n) | IrType::UlongN(n) | IrType::DoubleN(n) => Some((8, *n)),
+        _ => None,
+    }
+}
+
+fn struct_layout(field_types: &[&IrType], ir_tree: &IrTree) -> BackendResult<Layout>
+{
+    let mut offset = 0usize;
+    let mut align = 1usize;
+    let mut field_offsets: Vec<usize> = Vec::new();
+    for field_type in field_types {
+        let field_layout = ir_type_layout(field_type, ir_tree)?;
+        offset = align_offset(offset, field_layout.align);
+        field_offsets.push(offset);
+        offset += field_layout.size;
+        align = align.max(field_layout.align);
+    }
+    Ok(Layout {
+            size: align_offset(offset, align),
+            align,
+            field_offsets,
+            tag_offset: None,
+            con_layouts: Vec::new(),
+    })
+}
+
+fn union_layout(field_types: &[Box<IrType>], ir_tree: &IrTree) -> BackendResult<Layout>
+{
+    let mut size = 0usize;
+    let mut align = 1usize;
+    for field_type in field_types {
+        let field_layout = ir_type_layout(field_type, ir_tree)?;
+        size = size.max(field_layout.size);
+        align = align.max(field_layout.align);
+    }
+    Ok(Layout {
+            size: align_offset(size, align),
+            align,
+            field_offsets: vec![0; field_types.len()],
+            tag_offset: None,
+            con_layouts: Vec::new(),
+    })
+}
+
+fn simple_layout(size: usize, align: usize) -> Layout
+{
+    Layout {
+        size,
+        align,
+        field_offsets: Vec::new(),
+        tag_offset: None,
+        con_layouts: Vec::new(),
+    }
+}
+
+pub fn ir_type_layout(typ: &IrType, ir_tree: &IrTree) -> BackendResult<Layout>
+{
+    if let Some(size) = scalar_size(typ) {
+        return Ok(simple_layout(size, size));
+    }
+    if let Some((elem_size, len)) = vector_elem_size_and_len(typ) {
+        let size = if len == 3 { elem_size * 4 } else { elem_size * len };
+        return Ok(simple_layout(size, size));
+    }
+    match typ {
+        IrType::Void => Ok(simple_layout(0, 1)),
+        IrType::Struct(ident) => {
+            match ir_tree.struct1(ident) {
+                Some(struct1) => {
+                    match &*struct1.borrow() {
+                        IrStruct::Struct(field_types) => {
+                            let field_types2: Vec<&IrType> = field_types.iter().map(|field_type| &**field_type).collect();
+                            struct_layout(field_types2.as_slice(), ir_tree)
+                        },
+                        IrStruct::Closure(field_types) => {
+                            let field_types2: Vec<&IrType> = field_types.values().map(|field_type| &**field_type).collect();
+                            struct_layout(field_types2.as_slice(), ir_tree)
+                        },
+                    }
+                },
+                None => Err(BackendError::Internal(format!("ir_type_layout: no structure {}", ident))),
+            }
+        },
+        IrType::Union(ident) => {
+            match ir_tree.union(ident) {
+                Some(union) => union_layout(union.borrow().0.as_slice(), ir_tree),
+                None => Err(BackendError::Internal(format!("ir_type_layout: no union {}", ident))),
+            }
+        },
+        IrType::Array(elem_type, len) => {
+            let elem_layout = ir_type_layout(elem_type, ir_tree)?;
+            Ok(Layout {
+                    size: elem_layout.size * len,
+                    align: elem_layout.align,
+                    field_offsets: (0..*len).map(|i| elem_layout.size * i).collect(),
+                    tag_offset: None,
+                    con_layouts: Vec::new(),
+            })
+        },
+        _ => Err(BackendError::Internal(String::from("ir_type_layout: unsupported type"))),
+    }
+}
+
+fn union_field_layout(typ: &IrType, union_field_idx: usize, ir_tree: &IrTree) -> BackendResult<Layout>
+{
+    match typ {
+        IrType::Struct(ident) => {
+            match ir_tree.struct1(ident) {
+                Some(struct1) => {
+                    match &*struct1.borrow() {
+                        IrStruct::Struct(field_types) if field_types.len() == 2 => {
+                            match &*field_types[1] {
+                                IrType::Union(union_ident) => {
+                                    match ir_tree.union(union_ident) {
+                                        Some(union) => {
+                                            match union.borrow().0.get(union_field_idx) {
+                                                Some(field_type) => ir_type_layout(field_type, ir_tree),
+                                                None => Err(BackendError::Internal(String::from("union_field_layout: no union field type"))),
+                                            }
+                                        },
+                                        None => Err(BackendError::Internal(String::from("union_field_layout: no union"))),
+                                    }
+                                },
+                                _ => Err(BackendError::Internal(String::from("union_field_layout: field type isn't union"))),
+                            }
+                        },
+                        _ => Err(BackendError::Internal(String::from("union_field_layout: no union field type"))),
+                    }
+                },
+                None => Err(BackendError::Internal(String::from("union_field_layout: no structure"))),
+            }
+        },
+        _ => Err(BackendError::Internal(String::from("union_field_layout: type isn't structure"))),
+    }
+}
+
+fn con_layouts(cons: &[Rc<RefCell<Con>>], layout: &mut Layout, typ: &IrType, ir_tree: &IrTree) -> BackendResult<()>
+{
+    let con_field_counts: Vec<usize> = cons.iter().map(|con| con_field_count(&con.borrow())).collect();
+    let is_nullary = con_field_counts.iter().all(|n| *n == 0);
+    if is_nullary || cons.len() > 1 {
+        layout.tag_offset = Some(0);
+    }
+    let mut union_field_idx = 0usize;
+    for (i, (con, con_field_count)) in cons.iter().zip(con_field_counts.iter()).enumerate() {
+        let ident = con_ident(&con.borrow()).clone();
+        let field_offsets = if is_nullary || *con_field_count == 0 {
+            Vec::new()
+        } else if cons.len() == 1 {
+            layout.field_offsets.clone()
+        } else {
+            let union_offset = match layout.field_offsets.get(1) {
+                Some(tmp_union_offset) => *tmp_union_offset,
+                None => return Err(BackendError::Internal(String::from("con_layouts: no union offset"))),
+            };
+            let field_layout = union_field_layout(typ, union_field_idx, ir_tree)?;
+            union_field_idx += 1;
+            field_layout.field_offsets.iter().map(|offset| union_offset + offset).collect()
+        };
+        layout.con_layouts.push(ConLayout {
+                ident,
+                tag: i as u32,
+                field_offsets,
+        });
+    }
+    Ok(())
+}
+
+pub fn type_value_layout(type_value: &Rc<TypeValue>, tree: &Tree) -> BackendResult<Layout>
+{
+    let lowerer = Lowerer::new();
+    let mut ir_tree = IrTree::new();
+    let pos = Pos::new(String::from("(type value)"), 1, 1);
+    let typ = lowerer.lower_type_value(type_value, &pos, tree, &mut ir_tree)?;
+    let mut layout = ir_type_layout(&typ, &ir_tree)?;
+    if let TypeValue::Type(_, TypeValueName::Name(ident), _) = &**type_value {
+        if let Some(type_var) = tree.type_var(ident) {
+            if let TypeVar::Data(_, cons, _) = &*type_var.borrow() {
+                con_layouts(cons.as_slice(), &mut layout, &typ, &ir_tree)?;
+            }
+        }
+    }
+    Ok(layout)
+}
+
+#[cfg(test)]
+mod tests;
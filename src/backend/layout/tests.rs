@@ -0,0 +1,167 @@
+//
+// Copyright (c) 2025 Łukasz Szpakowski
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+use crate::frontend::do_frontend_phases;
+use super::*;
+
+fn type_value(ident: &str, args: Vec<Rc<TypeValue>>) -> Rc<TypeValue>
+{ Rc::new(TypeValue::Type(UniqFlag::None, TypeValueName::Name(String::from(ident)), args)) }
+
+#[test]
+fn test_type_value_layout_returns_layout_for_tuple()
+{
+    let s = "
+kernel k() -> () = ();
+";
+    let s2 = &s[1..];
+    let tree = match do_frontend_phases(s2) {
+        Ok(tree) => tree,
+        Err(errs) => {
+            println!("{}", errs);
+            panic!();
+        },
+    };
+    let tuple_type_value = Rc::new(TypeValue::Type(UniqFlag::None, TypeValueName::Tuple, vec![
+                type_value("Char", Vec::new()),
+                type_value("Int", Vec::new()),
+                type_value("Double", Vec::new()),
+                type_value("Float3", Vec::new())
+    ]));
+    match type_value_layout(&tuple_type_value, &tree) {
+        Ok(layout) => {
+            assert_eq!(32, layout.size);
+            assert_eq!(16, layout.align);
+            assert_eq!(vec![0, 4, 8, 16], layout.field_offsets);
+            assert_eq!(None, layout.tag_offset);
+            assert!(layout.con_layouts.is_empty());
+        },
+        Err(err) => {
+            println!("{}", err);
+            assert!(false);
+        },
+    }
+}
+
+#[test]
+fn test_type_value_layout_returns_layout_for_data_type_with_many_constructors()
+{
+    let s = "
+data T<t> = A() | B(t, Float) | C { x: Int, y: t, };
+kernel k() -> () = ();
+";
+    let s2 = &s[1..];
+    let tree = match do_frontend_phases(s2) {
+        Ok(tree) => tree,
+        Err(errs) => {
+            println!("{}", errs);
+            panic!();
+        },
+    };
+    match type_value_layout(&type_value("T", vec![type_value("Double", Vec::new())]), &tree) {
+        Ok(layout) => {
+            assert_eq!(24, layout.size);
+            assert_eq!(8, layout.align);
+            assert_eq!(vec![0, 8], layout.field_offsets);
+            assert_eq!(Some(0), layout.tag_offset);
+            assert_eq!(vec![
+                    ConLayout { ident: String::from("A"), tag: 0, field_offsets: Vec::new(), },
+                    ConLayout { ident: String::from("B"), tag: 1, field_offsets: vec![8, 16], },
+                    ConLayout { ident: String::from("C"), tag: 2, field_offsets: vec![8, 16], }
+            ], layout.con_layouts);
+        },
+        Err(err) => {
+            println!("{}", err);
+            assert!(false);
+        },
+    }
+}
+
+#[test]
+fn test_type_value_layout_returns_layout_for_data_type_with_one_constructor()
+{
+    let s = "
+data U = D { x: Int, y: Float4, };
+kernel k() -> () = ();
+";
+    let s2 = &s[1..];
+    let tree = match do_frontend_phases(s2) {
+        Ok(tree) => tree,
+        Err(errs) => {
+            println!("{}", errs);
+            panic!();
+        },
+    };
+    match type_value_layout(&type_value("U", Vec::new()), &tree) {
+        Ok(layout) => {
+            assert_eq!(32, layout.size);
+            assert_eq!(16, layout.align);
+            assert_eq!(vec![0, 16], layout.field_offsets);
+            assert_eq!(None, layout.tag_offset);
+            assert_eq!(vec![ConLayout { ident: String::from("D"), tag: 0, field_offsets: vec![0, 16], }], layout.con_layouts);
+        },
+        Err(err) => {
+            println!("{}", err);
+            assert!(false);
+        },
+    }
+}
+
+#[test]
+fn test_type_value_layout_returns_layout_for_data_type_with_nullary_constructors()
+{
+    let s = "
+data V = E() | F() | G();
+kernel k() -> () = ();
+";
+    let s2 = &s[1..];
+    let tree = match do_frontend_phases(s2) {
+        Ok(tree) => tree,
+        Err(errs) => {
+            println!("{}", errs);
+            panic!();
+        },
+    };
+    match type_value_layout(&type_value("V", Vec::new()), &tree) {
+        Ok(layout) => {
+            assert_eq!(4, layout.size);
+            assert_eq!(4, layout.align);
+            assert_eq!(Some(0), layout.tag_offset);
+            assert_eq!(vec![
+                    ConLayout { ident: String::from("E"), tag: 0, field_offsets: Vec::new(), },
+                    ConLayout { ident: String::from("F"), tag: 1, field_offsets: Vec::new(), },
+                    ConLayout { ident: String::from("G"), tag: 2, field_offsets: Vec::new(), }
+            ], layout.con_layouts);
+        },
+        Err(err) => {
+            println!("{}", err);
+            assert!(false);
+        },
+    }
+}
+
+#[test]
+fn test_type_value_layout_complains_on_polymorphic_type()
+{
+    let s = "
+kernel k() -> () = ();
+";
+    let s2 = &s[1..];
+    let tree = match do_frontend_phases(s2) {
+        Ok(tree) => tree,
+        Err(errs) => {
+            println!("{}", errs);
+            panic!();
+        },
+    };
+    match type_value_layout(&type_value("Option", vec![Rc::new(TypeValue::Param(UniqFlag::None, LocalType::new(0)))]), &tree) {
+        Err(BackendError::Messages(msg_errs)) => {
+            assert_eq!(1, msg_errs.len());
+            assert_eq!(String::from("polymorphic type is unsupported"), msg_errs[0].1);
+        },
+        _ => assert!(false),
+    }
+}
@@ -0,0 +1,4146 @@
+//
+// Copyright (c) 2025 Łukasz Szpakowski
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+use std::cell::*;
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::collections::VecDeque;
+use std::rc::*;
+use crate::frontend::error::Pos;
+use crate::frontend::printf::*;
+use crate::frontend::private::*;
+use crate::frontend::resolver::unqualified_ident;
+use crate::frontend::resolver::unqualified_type_name;
+use crate::frontend::tree::*;
+use crate::frontend::type_stack::*;
+use crate::backend::error::*;
+use crate::backend::folder::*;
+use crate::backend::inliner::*;
+use crate::backend::ir::*;
+use crate::backend::mangling::*;
+use crate::backend::reachability::*;
+#[cfg(debug_assertions)]
+use crate::backend::verifier::*;
+use crate::utils::env::*;
+
+fn expr_local_type(expr: &Expr) -> BackendResult<LocalType>
+{
+    match expr {
+        Expr::Literal(_, Some(local_type), _) => Ok(*local_type),
+        Expr::Lambda(_, _, _, _, Some(local_type), _, _, _) => Ok(*local_type),
+        Expr::Var(_, Some(local_type), _) => Ok(*local_type),
+        Expr::NamedFieldConApp(_, _, _, Some(local_type), _) => Ok(*local_type),
+        Expr::PrintfApp(_, Some(local_type), _) => Ok(*local_type),
+        Expr::App(_, _, Some(local_type), _) => Ok(*local_type),
+        Expr::GetField(_, _, Some(local_type), _) => Ok(*local_type),
+        Expr::Get2Field(_, _, Some(local_type), _) => Ok(*local_type),
+        Expr::SetField(_, _, _, Some(local_type), _) => Ok(*local_type),
+        Expr::UpdateField(_, _, _, Some(local_type), _) => Ok(*local_type),
+        Expr::UpdateGet2Field(_, _, _, Some(local_type), _) => Ok(*local_type),
+        Expr::Uniq(_, Some(local_type), _) => Ok(*local_type),
+        Expr::Shared(_, Some(local_type), _) => Ok(*local_type),
+        Expr::Typed(_, _, Some(local_type), _) => Ok(*local_type),
+        Expr::As(_, _, Some(local_type), _) => Ok(*local_type),
+        Expr::Let(_, _, Some(local_type), _) => Ok(*local_type),
+        Expr::If(_, _, _, Some(local_type), _) => Ok(*local_type),
+        Expr::Match(_, _, Some(local_type), _) => Ok(*local_type),
+        _ => Err(BackendError::Internal(String::from("expr_local_type: no local type"))),
+    }
+}
+
+fn expr_pos(expr: &Expr) -> &Pos
+{
+    match expr {
+        Expr::Literal(_, _, pos) => pos,
+        Expr::Lambda(_, _, _, _, _, _, _, pos) => pos,
+        Expr::Var(_, _, pos) => pos,
+        Expr::NamedFieldConApp(_, _, _, _, pos) => pos,
+        Expr::PrintfApp(_, _, pos) => pos,
+        Expr::App(_, _, _, pos) => pos,
+        Expr::GetField(_, _, _, pos) => pos,
+        Expr::Get2Field(_, _, _, pos) => pos,
+        Expr::SetField(_, _, _, _, pos) => pos,
+        Expr::UpdateField(_, _, _, _, pos) => pos,
+        Expr::UpdateGet2Field(_, _, _, _, pos) => pos,
+        Expr::Uniq(_, _, pos) => pos,
+        Expr::Shared(_, _, pos) => pos,
+        Expr::Typed(_, _, _, pos) => pos,
+        Expr::As(_, _, _, pos) => pos,
+        Expr::Let(_, _, _, pos) => pos,
+        Expr::If(_, _, _, _, pos) => pos,
+        Expr::Match(_, _, _, pos) => pos,
+    }
+}
+
+fn pattern_local_type(pattern: &Pattern) -> BackendResult<LocalType>
+{
+    match pattern {
+        Pattern::Literal(_, Some(local_type), _) => Ok(*local_type),
+        Pattern::As(_, _, _, Some(local_type), _) => Ok(*local_type),
+        Pattern::Const(_, Some(local_type), _) => Ok(*local_type),
+        Pattern::UnnamedFieldCon(_, _, _, Some(local_type), _) => Ok(*local_type),
+        Pattern::NamedFieldCon(_, _, _, Some(local_type), _) => Ok(*local_type),
+        Pattern::Var(_, _, Some(local_type), _) => Ok(*local_type),
+        Pattern::At(_, _, _, Some(local_type), _) => Ok(*local_type),
+        Pattern::Wildcard(Some(local_type), _) => Ok(*local_type),
+        Pattern::Alt(_, Some(local_type), _) => Ok(*local_type),
+        _ => Err(BackendError::Internal(String::from("pattern_local_type: no local type"))),
+    }
+}
+
+fn pattern_con_ident(pattern: &Pattern) -> Option<&String>
+{
+    match pattern {
+        Pattern::UnnamedFieldCon(ident, _, _, _, _) => Some(ident),
+        Pattern::NamedFieldCon(ident, _, _, _, _) => Some(ident),
+        _ => None,
+    }
+}
+
+fn message_error<T>(pos: &Pos, msg: String) -> BackendResult<T>
+{ Err(BackendError::Messages(vec![BackendMessageError(pos.clone(), msg)])) }
+
+fn unit_type_value() -> Rc<TypeValue>
+{ Rc::new(TypeValue::Type(UniqFlag::None, TypeValueName::Tuple, Vec::new())) }
+
+fn type_value_without_uniq_flags(type_value: &Rc<TypeValue>) -> Rc<TypeValue>
+{
+    match &**type_value {
+        TypeValue::Param(_, local_type) => Rc::new(TypeValue::Param(UniqFlag::None, *local_type)),
+        TypeValue::Type(_, type_value_name, args) => Rc::new(TypeValue::Type(UniqFlag::None, type_value_name.clone(), args.iter().map(type_value_without_uniq_flags).collect())),
+    }
+}
+
+fn type_value_has_param(type_value: &Rc<TypeValue>) -> bool
+{
+    match &**type_value {
+        TypeValue::Param(_, _) => true,
+        TypeValue::Type(_, _, args) => args.iter().any(type_value_has_param),
+    }
+}
+
+fn type_value_for_type_value(type_value: &Rc<TypeValue>, local_types: &LocalTypes, type_values: &[Rc<TypeValue>]) -> BackendResult<Rc<TypeValue>>
+{
+    match local_types.type_entry_for_type_value(type_value) {
+        Some(LocalTypeEntry::Param(_, _, type_param_entry, local_type)) => {
+            for (i, type_value2) in type_values.iter().enumerate() {
+                match local_types.type_entry_for_type_value(&Rc::new(TypeValue::Param(UniqFlag::None, LocalType::new(i)))) {
+                    Some(LocalTypeEntry::Param(_, _, _, local_type2)) => {
+                        if local_type == local_type2 {
+                            return Ok(type_value2.clone());
+                        }
+                    },
+                    _ => return Err(BackendError::Internal(String::from("type_value_for_type_value: no local type entry or local type entry is type"))),
+                }
+            }
+            let type_param_entry_r = type_param_entry.borrow();
+            if type_param_entry_r.trait_names.contains(&TraitName::Fun) && !type_param_entry_r.type_values.is_empty() {
+                let mut new_args: Vec<Rc<TypeValue>> = Vec::new();
+                for arg in &type_param_entry_r.type_values {
+                    new_args.push(type_value_for_type_value(arg, local_types, type_values)?);
+                }
+                return Ok(Rc::new(TypeValue::Type(UniqFlag::None, TypeValueName::Fun, new_args)));
+            }
+            Ok(unit_type_value())
+        },
+        Some(LocalTypeEntry::Type(type_value2)) => {
+            match &*type_value2 {
+                TypeValue::Param(_, _) => Err(BackendError::Internal(String::from("type_value_for_type_value: type parameter in local type entry"))),
+                TypeValue::Type(uniq_flag, type_value_name, args) => {
+                    let mut new_args: Vec<Rc<TypeValue>> = Vec::new();
+                    for arg in args {
+                        new_args.push(type_value_for_type_value(arg, local_types, type_values)?);
+                    }
+                    Ok(Rc::new(TypeValue::Type(*uniq_flag, type_value_name.clone(), new_args)))
+                },
+            }
+        },
+        None => Err(BackendError::Internal(String::from("type_value_for_type_value: no local type entry"))),
+    }
+}
+
+fn type_value_without_uniq_flag(type_value: &Rc<TypeValue>) -> Rc<TypeValue>
+{
+    match &**type_value {
+        TypeValue::Param(_, local_type) => Rc::new(TypeValue::Param(UniqFlag::None, *local_type)),
+        TypeValue::Type(_, type_value_name, args) => Rc::new(TypeValue::Type(UniqFlag::None, type_value_name.clone(), args.clone())),
+    }
+}
+
+fn set_type_values_for_type_value(type_value1: &Rc<TypeValue>, type_value2: &Rc<TypeValue>, typ: &Type, type_values: &mut [Rc<TypeValue>]) -> BackendResult<()>
+{
+    match (&**type_value1, &**type_value2) {
+        (TypeValue::Param(uniq_flag, local_type), _) => {
+            match typ.type_param_entry(*local_type) {
+                Some(type_param_entry) => {
+                    let type_param_entry_r = type_param_entry.borrow();
+                    if !type_param_entry_r.type_values.is_empty() {
+                        match &**type_value2 {
+                            TypeValue::Type(_, _, args) => {
+                                for (type_value3, type_value4) in type_param_entry_r.type_values.iter().zip(args.iter()) {
+                                    set_type_values_for_type_value(type_value3, type_value4, typ, type_values)?;
+                                }
+                            },
+                            TypeValue::Param(_, _) => return Err(BackendError::Internal(String::from("set_type_values_for_type_value: type value is type parameter"))),
+                        }
+                    }
+                },
+                None => return Err(BackendError::Internal(String::from("set_type_values_for_type_value: no type parameter entry"))),
+            }
+            match type_values.get_mut(local_type.index()) {
+                Some(type_value) => {
+                    if *uniq_flag == UniqFlag::Uniq {
+                        *type_value = type_value_without_uniq_flag(type_value2);
+                    } else {
+                        *type_value = type_value2.clone();
+                    }
+                    Ok(())
+                },
+                None => Err(BackendError::Internal(String::from("set_type_values_for_type_value: no type value"))),
+            }
+        },
+        (TypeValue::Type(_, _, args1), TypeValue::Type(_, _, args2)) => {
+            for (type_value3, type_value4) in args1.iter().zip(args2.iter()) {
+                set_type_values_for_type_value(type_value3, type_value4, typ, type_values)?;
+            }
+            Ok(())
+        },
+        (TypeValue::Type(_, _, _), TypeValue::Param(_, _)) => Err(BackendError::Internal(String::from("set_type_values_for_type_value: type value is type parameter"))),
+    }
+}
+
+fn type_values_for_type(typ: &Type, type_value: &Rc<TypeValue>) -> BackendResult<Vec<Rc<TypeValue>>>
+{
+    let mut type_values = vec![unit_type_value(); typ.type_param_entries().len()];
+    set_type_values_for_type_value(typ.type_value(), type_value, typ, type_values.as_mut_slice())?;
+    Ok(type_values)
+}
+
+pub(crate) fn con_ident(con: &Con) -> &String
+{
+    match con {
+        Con::UnnamedField(_, ident, _, _, _) => ident,
+        Con::NamedField(_, ident, _, _, _, _) => ident,
+    }
+}
+
+pub(crate) fn con_field_count(con: &Con) -> usize
+{
+    match con {
+        Con::UnnamedField(_, _, type_exprs, _, _) => type_exprs.len(),
+        Con::NamedField(_, _, type_expr_named_field_pairs, _, _, _) => type_expr_named_field_pairs.len(),
+    }
+}
+
+fn data_cons(ident: &String, tree: &Tree) -> BackendResult<Vec<Rc<RefCell<Con>>>>
+{
+    match tree.type_var(ident) {
+        Some(type_var) => {
+            let type_var_r = type_var.borrow();
+            match &*type_var_r {
+                TypeVar::Data(_, cons, _) => Ok(cons.clone()),
+                _ => Err(BackendError::Internal(String::from("data_cons: type variable isn't data type"))),
+            }
+        },
+        None => Err(BackendError::Internal(String::from("data_cons: no type variable"))),
+    }
+}
+
+fn named_field_index(ident: &String, field_ident: &String, tree: &Tree) -> BackendResult<usize>
+{
+    match tree.var(ident) {
+        Some(var) => {
+            let var_r = var.borrow();
+            match &*var_r {
+                Var::Fun(fun, _, _) => {
+                    match &**fun {
+                        Fun::Con(con) => {
+                            let con_r = con.borrow();
+                            match &*con_r {
+                                Con::NamedField(_, _, _, _, Some(named_fields), _) => {
+                                    match named_fields.field_index(field_ident) {
+                                        Some(field_idx) => Ok(field_idx),
+                                        None => Err(BackendError::Internal(String::from("named_field_index: no field index"))),
+                                    }
+                                },
+                                _ => Err(BackendError::Internal(String::from("named_field_index: constructor isn't named field constructor or no named fields"))),
+                            }
+                        },
+                        _ => Err(BackendError::Internal(String::from("named_field_index: function isn't constructor"))),
+                    }
+                },
+                _ => Err(BackendError::Internal(String::from("named_field_index: variable isn't function"))),
+            }
+        },
+        None => Err(BackendError::Internal(String::from("named_field_index: no variable"))),
+    }
+}
+
+fn con_field_patterns<'a>(pattern: &'a Pattern, field_count: usize, tree: &Tree) -> BackendResult<Vec<Option<&'a Pattern>>>
+{
+    match pattern {
+        Pattern::UnnamedFieldCon(_, patterns, _, _, _) => Ok(patterns.iter().map(|pattern2| Some(&**pattern2)).collect()),
+        Pattern::NamedFieldCon(ident, pattern_named_field_pairs, _, _, _) => {
+            let mut field_patterns: Vec<Option<&'a Pattern>> = vec![None; field_count];
+            for pattern_named_field_pair in pattern_named_field_pairs {
+                match pattern_named_field_pair {
+                    NamedFieldPair(field_ident, pattern2, _) => {
+                        match field_patterns.get_mut(named_field_index(ident, field_ident, tree)?) {
+                            Some(field_pattern) => *field_pattern = Some(&**pattern2),
+                            None => return Err(BackendError::Internal(String::from("con_field_patterns: no field pattern"))),
+                        }
+                    },
+                }
+            }
+            Ok(field_patterns)
+        },
+        _ => Err(BackendError::Internal(String::from("con_field_patterns: pattern isn't constructor pattern"))),
+    }
+}
+
+fn con_field_type_values(ident: &String, args: &[Rc<TypeValue>], tree: &Tree) -> BackendResult<Vec<Rc<TypeValue>>>
+{
+    match tree.var(ident) {
+        Some(var) => {
+            let var_r = var.borrow();
+            match &*var_r {
+                Var::Fun(_, _, Some(typ)) => {
+                    match &**typ.type_value() {
+                        TypeValue::Type(_, TypeValueName::Fun, fun_args) if !fun_args.is_empty() => {
+                            let mut field_type_values: Vec<Rc<TypeValue>> = Vec::new();
+                            for fun_arg in &fun_args[0..(fun_args.len() - 1)] {
+                                match fun_arg.substitute(args) {
+                                    Ok(Some(field_type_value)) => field_type_values.push(field_type_value),
+                                    Ok(None) => field_type_values.push(fun_arg.clone()),
+                                    Err(_) => return Err(BackendError::Internal(String::from("con_field_type_values: can't substitute type value"))),
+                                }
+                            }
+                            Ok(field_type_values)
+                        },
+                        _ => Err(BackendError::Internal(String::from("con_field_type_values: type value isn't function type"))),
+                    }
+                },
+                _ => Err(BackendError::Internal(String::from("con_field_type_values: variable isn't function or no type"))),
+            }
+        },
+        None => Err(BackendError::Internal(String::from("con_field_type_values: no variable"))),
+    }
+}
+
+fn type_value_ident(type_value: &Rc<TypeValue>) -> Option<&str>
+{
+    match &**type_value {
+        TypeValue::Type(_, TypeValueName::Name(ident), _) => Some(ident.as_str()),
+        _ => None,
+    }
+}
+
+fn primitive_ir_type_for_ident(ident: &str) -> Option<IrType>
+{
+    match ident {
+        "Bool" => Some(IrType::Int),
+        "Char" => Some(IrType::Char),
+        "Short" => Some(IrType::Short),
+        "Int" => Some(IrType::Int),
+        "Long" => Some(IrType::Long),
+        "Uchar" => Some(IrType::Uchar),
+        "Ushort" => Some(IrType::Ushort),
+        "Uint" => Some(IrType::Uint),
+        "Ulong" => Some(IrType::Ulong),
+        "Half" => Some(IrType::Half),
+        "Float" => Some(IrType::Float),
+        "Double" => Some(IrType::Double),
+        "SizeT" => Some(IrType::SizeT),
+        "PtrdiffT" => Some(IrType::PtrdiffT),
+        "IntptrT" => Some(IrType::IntptrT),
+        "UintptrT" => Some(IrType::UintptrT),
+        "opencl::ClMemFenceFlags" => Some(IrType::Uint),
+        _ => {
+            let digit_idx = ident.find(|c: char| c.is_ascii_digit())?;
+            let n = match ident[digit_idx..].parse::<usize>() {
+                Ok(n @ (2 | 3 | 4 | 8 | 16)) => n,
+                _ => return None,
+            };
+            match &ident[..digit_idx] {
+                "Char" => Some(IrType::CharN(n)),
+                "Short" => Some(IrType::ShortN(n)),
+                "Int" => Some(IrType::IntN(n)),
+                "Long" => Some(IrType::LongN(n)),
+                "Uchar" => Some(IrType::UcharN(n)),
+                "Ushort" => Some(IrType::UshortN(n)),
+                "Uint" => Some(IrType::UintN(n)),
+                "Ulong" => Some(IrType::UlongN(n)),
+                "Float" => Some(IrType::FloatN(n)),
+                "Double" => Some(IrType::DoubleN(n)),
+                _ => None,
+            }
+        },
+    }
+}
+
+fn ptr_modifiers_for_ident(ident: &str, suffix: &str) -> Option<(IrPtrModifier, IrAccessModifier)>
+{
+    let (access_modifier, ident2) = match ident.strip_prefix("Uniq") {
+        Some(tmp_ident) => (IrAccessModifier::None, tmp_ident),
+        None => (IrAccessModifier::Const, ident),
+    };
+    match ident2.strip_suffix(suffix)? {
+        "" => Some((IrPtrModifier::None, access_modifier)),
+        "Private" => Some((IrPtrModifier::Private, access_modifier)),
+        "Local" => Some((IrPtrModifier::Local, access_modifier)),
+        "Global" => Some((IrPtrModifier::Global, access_modifier)),
+        "Constant" if access_modifier == IrAccessModifier::Const => Some((IrPtrModifier::Constant, access_modifier)),
+        _ => None,
+    }
+}
+
+fn vector_elem_count(typ: &IrType) -> Option<usize>
+{
+    match typ {
+        IrType::CharN(n) | IrType::ShortN(n) | IrType::IntN(n) | IrType::LongN(n) => Some(*n),
+        IrType::UcharN(n) | IrType::UshortN(n) | IrType::UintN(n) | IrType::UlongN(n) => Some(*n),
+        IrType::FloatN(n) | IrType::DoubleN(n) => Some(*n),
+        _ => None,
+    }
+}
+
+fn vector_elem_type(typ: &IrType) -> Option<IrType>
+{
+    match typ {
+        IrType::CharN(_) => Some(IrType::Char),
+        IrType::ShortN(_) => Some(IrType::Short),
+        IrType::IntN(_) => Some(IrType::Int),
+        IrType::LongN(_) => Some(IrType::Long),
+        IrType::UcharN(_) => Some(IrType::Uchar),
+        IrType::UshortN(_) => Some(IrType::Ushort),
+        IrType::UintN(_) => Some(IrType::Uint),
+        IrType::UlongN(_) => Some(IrType::Ulong),
+        IrType::FloatN(_) => Some(IrType::Float),
+        IrType::DoubleN(_) => Some(IrType::Double),
+        _ => None,
+    }
+}
+
+fn vector_con_ident(typ: &IrType) -> Option<String>
+{
+    let n = vector_elem_count(typ)?;
+    let elem_ident = match vector_elem_type(typ)? {
+        IrType::Char => "char",
+        IrType::Short => "short",
+        IrType::Int => "int",
+        IrType::Long => "long",
+        IrType::Uchar => "uchar",
+        IrType::Ushort => "ushort",
+        IrType::Uint => "uint",
+        IrType::Ulong => "ulong",
+        IrType::Float => "float",
+        IrType::Double => "double",
+        _ => return None,
+    };
+    Some(format!("{}{}", elem_ident, n))
+}
+
+fn vector_comparison_type(typ: &IrType) -> Option<IrType>
+{
+    match typ {
+        IrType::CharN(n) | IrType::UcharN(n) => Some(IrType::CharN(*n)),
+        IrType::ShortN(n) | IrType::UshortN(n) => Some(IrType::ShortN(*n)),
+        IrType::IntN(n) | IrType::UintN(n) | IrType::FloatN(n) => Some(IrType::IntN(*n)),
+        IrType::LongN(n) | IrType::UlongN(n) | IrType::DoubleN(n) => Some(IrType::LongN(*n)),
+        _ => None,
+    }
+}
+
+fn zero_value<T: Clone>(typ: &IrType) -> Option<IrValue<T>>
+{
+    match typ {
+        IrType::Char => Some(IrValue::Char(0, None)),
+        IrType::Short => Some(IrValue::Short(0, None)),
+        IrType::Int => Some(IrValue::Int(0, None)),
+        IrType::Long => Some(IrValue::Long(0, None)),
+        IrType::Uchar => Some(IrValue::Uchar(0, None)),
+        IrType::Ushort => Some(IrValue::Ushort(0, None)),
+        IrType::Uint => Some(IrValue::Uint(0, None)),
+        IrType::Ulong => Some(IrValue::Ulong(0, None)),
+        IrType::Half => Some(IrValue::Float(0.0, Some(Box::new(IrType::Half)))),
+        IrType::Float => Some(IrValue::Float(0.0, None)),
+        IrType::Double => Some(IrValue::Double(0.0, None)),
+        IrType::SizeT => Some(IrValue::SizeT(0, None)),
+        IrType::PtrdiffT => Some(IrValue::PtrdiffT(0, None)),
+        IrType::IntptrT => Some(IrValue::IntptrT(0, None)),
+        IrType::UintptrT => Some(IrValue::UintptrT(0, None)),
+        _ => {
+            let n = vector_elem_count(typ)?;
+            let elem_value = zero_value(&vector_elem_type(typ)?)?;
+            Some(IrValue::Object(Box::new(IrObject::Vector(vec![elem_value; n], Box::new(typ.clone())))))
+        },
+    }
+}
+
+fn local_var_value(idx: usize) -> IrValue<IrArgVar>
+{ IrValue::Object(Box::new(IrObject::Var(IrArgVar::Local(idx, Vec::new()), None))) }
+
+fn is_place_arg_var(var: &IrArgVar) -> bool
+{
+    matches!(var, IrArgVar::Global(_, _) | IrArgVar::Local(_, _) | IrArgVar::CallerFunArg(_, _) | IrArgVar::PrivateClosure(_, _) | IrArgVar::LocalClosure(_, _) | IrArgVar::GlobalClosure(_, _) | IrArgVar::PrivateHeap(_) | IrArgVar::LocalHeap(_) | IrArgVar::GlobalHeap(_))
+}
+
+fn arg_var_with_ops(var: &IrArgVar, ops: &[IrArgOp]) -> BackendResult<IrArgVar>
+{
+    let mut new_var = var.clone();
+    let var_ops = match &mut new_var {
+        IrArgVar::Global(_, tmp_ops) => tmp_ops,
+        IrArgVar::Local(_, tmp_ops) => tmp_ops,
+        IrArgVar::CallerFunArg(_, tmp_ops) => tmp_ops,
+        IrArgVar::PrivateClosure(_, tmp_ops) => tmp_ops,
+        IrArgVar::LocalClosure(_, tmp_ops) => tmp_ops,
+        IrArgVar::GlobalClosure(_, tmp_ops) => tmp_ops,
+        IrArgVar::PrivateHeap(tmp_ops) => tmp_ops,
+        IrArgVar::LocalHeap(tmp_ops) => tmp_ops,
+        IrArgVar::GlobalHeap(tmp_ops) => tmp_ops,
+        _ => return Err(BackendError::Internal(String::from("arg_var_with_ops: variable is reference"))),
+    };
+    var_ops.extend_from_slice(ops);
+    Ok(new_var)
+}
+
+fn ref_arg_var(var: &IrArgVar) -> BackendResult<IrArgVar>
+{
+    match var {
+        IrArgVar::Global(ident, ops) => Ok(IrArgVar::RefGlobal(ident.clone(), ops.clone(), None)),
+        IrArgVar::Local(idx, ops) => Ok(IrArgVar::RefLocal(*idx, ops.clone(), None)),
+        IrArgVar::CallerFunArg(idx, ops) => Ok(IrArgVar::RefCallerFunArg(*idx, ops.clone(), None)),
+        IrArgVar::PrivateClosure(idx, ops) => Ok(IrArgVar::RefPrivateClosure(*idx, ops.clone(), None)),
+        IrArgVar::LocalClosure(idx, ops) => Ok(IrArgVar::RefLocalClosure(*idx, ops.clone(), None)),
+        IrArgVar::GlobalClosure(idx, ops) => Ok(IrArgVar::RefGlobalClosure(*idx, ops.clone(), None)),
+        IrArgVar::PrivateHeap(ops) => Ok(IrArgVar::RefPrivateHeap(ops.clone(), None)),
+        IrArgVar::LocalHeap(ops) => Ok(IrArgVar::RefLocalHeap(ops.clone(), None)),
+        IrArgVar::GlobalHeap(ops) => Ok(IrArgVar::RefGlobalHeap(ops.clone(), None)),
+        _ => Err(BackendError::Internal(String::from("ref_arg_var: variable is reference"))),
+    }
+}
+
+fn instr_var_for_arg_var(var: &IrArgVar) -> BackendResult<IrInstrVar>
+{
+    match var {
+        IrArgVar::Global(ident, ops) => Ok(IrInstrVar::Global(ident.clone(), ops.clone())),
+        IrArgVar::Local(idx, ops) => Ok(IrInstrVar::Local(*idx, ops.clone())),
+        IrArgVar::CallerFunArg(idx, ops) => Ok(IrInstrVar::CallerFunArg(*idx, ops.clone())),
+        IrArgVar::PrivateClosure(idx, ops) => Ok(IrInstrVar::PrivateClosure(*idx, ops.clone())),
+        IrArgVar::LocalClosure(idx, ops) => Ok(IrInstrVar::LocalClosure(*idx, ops.clone())),
+        IrArgVar::GlobalClosure(idx, ops) => Ok(IrInstrVar::GlobalClosure(*idx, ops.clone())),
+        IrArgVar::PrivateHeap(ops) => Ok(IrInstrVar::PrivateHeap(ops.clone())),
+        IrArgVar::LocalHeap(ops) => Ok(IrInstrVar::LocalHeap(ops.clone())),
+        IrArgVar::GlobalHeap(ops) => Ok(IrInstrVar::GlobalHeap(ops.clone())),
+        _ => Err(BackendError::Internal(String::from("instr_var_for_arg_var: variable is reference"))),
+    }
+}
+
+fn case_value_for_integer(n: i128, typ: &IrType) -> Option<IrCaseValue>
+{
+    match typ {
+        IrType::Char => Some(IrCaseValue::Char(n as i8, None)),
+        IrType::Short => Some(IrCaseValue::Short(n as i16, None)),
+        IrType::Int => Some(IrCaseValue::Int(n as i32, None)),
+        IrType::Long => Some(IrCaseValue::Long(n as i64, None)),
+        IrType::Uchar => Some(IrCaseValue::Uchar(n as u8, None)),
+        IrType::Ushort => Some(IrCaseValue::Ushort(n as u16, None)),
+        IrType::Uint => Some(IrCaseValue::Uint(n as u32, None)),
+        IrType::Ulong => Some(IrCaseValue::Ulong(n as u64, None)),
+        IrType::SizeT => Some(IrCaseValue::SizeT(n as u64, None)),
+        IrType::PtrdiffT => Some(IrCaseValue::PtrdiffT(n as i64, None)),
+        IrType::IntptrT => Some(IrCaseValue::IntptrT(n as i64, None)),
+        IrType::UintptrT => Some(IrCaseValue::UintptrT(n as u64, None)),
+        _ => None,
+    }
+}
+
+fn case_value_to_value(value: &IrCaseValue) -> IrValue<IrArgVar>
+{
+    match value {
+        IrCaseValue::Char(n, typ) => IrValue::Char(*n, typ.clone()),
+        IrCaseValue::Short(n, typ) => IrValue::Short(*n, typ.clone()),
+        IrCaseValue::Int(n, typ) => IrValue::Int(*n, typ.clone()),
+        IrCaseValue::Long(n, typ) => IrValue::Long(*n, typ.clone()),
+        IrCaseValue::Uchar(n, typ) => IrValue::Uchar(*n, typ.clone()),
+        IrCaseValue::Ushort(n, typ) => IrValue::Ushort(*n, typ.clone()),
+        IrCaseValue::Uint(n, typ) => IrValue::Uint(*n, typ.clone()),
+        IrCaseValue::Ulong(n, typ) => IrValue::Ulong(*n, typ.clone()),
+        IrCaseValue::SizeT(n, typ) => IrValue::SizeT(*n, typ.clone()),
+        IrCaseValue::PtrdiffT(n, typ) => IrValue::PtrdiffT(*n, typ.clone()),
+        IrCaseValue::IntptrT(n, typ) => IrValue::IntptrT(*n, typ.clone()),
+        IrCaseValue::UintptrT(n, typ) => IrValue::UintptrT(*n, typ.clone()),
+        IrCaseValue::BuiltinVar(ident, typ, typ2) => IrValue::Object(Box::new(IrObject::BuiltinVar(ident.clone(), typ.clone(), typ2.clone()))),
+    }
+}
+
+fn float_value(x: f64, typ: &IrType) -> Option<IrValue<IrArgVar>>
+{
+    match typ {
+        IrType::Half => Some(IrValue::Float(x as f32, Some(Box::new(IrType::Half)))),
+        IrType::Float => Some(IrValue::Float(x as f32, None)),
+        IrType::Double => Some(IrValue::Double(x, None)),
+        _ => None,
+    }
+}
+
+fn cast_value(value: IrValue<IrArgVar>, typ: Box<IrType>) -> IrValue<IrArgVar>
+{
+    match value {
+        IrValue::Char(n, _) => IrValue::Char(n, Some(typ)),
+        IrValue::Short(n, _) => IrValue::Short(n, Some(typ)),
+        IrValue::Int(n, _) => IrValue::Int(n, Some(typ)),
+        IrValue::Long(n, _) => IrValue::Long(n, Some(typ)),
+        IrValue::Uchar(n, _) => IrValue::Uchar(n, Some(typ)),
+        IrValue::Ushort(n, _) => IrValue::Ushort(n, Some(typ)),
+        IrValue::Uint(n, _) => IrValue::Uint(n, Some(typ)),
+        IrValue::Ulong(n, _) => IrValue::Ulong(n, Some(typ)),
+        IrValue::Float(n, _) => IrValue::Float(n, Some(typ)),
+        IrValue::Double(n, _) => IrValue::Double(n, Some(typ)),
+        IrValue::SizeT(n, _) => IrValue::SizeT(n, Some(typ)),
+        IrValue::PtrdiffT(n, _) => IrValue::PtrdiffT(n, Some(typ)),
+        IrValue::IntptrT(n, _) => IrValue::IntptrT(n, Some(typ)),
+        IrValue::UintptrT(n, _) => IrValue::UintptrT(n, Some(typ)),
+        IrValue::Object(object) => {
+            match *object {
+                IrObject::Var(var, _) => IrValue::Object(Box::new(IrObject::Var(var, Some(typ)))),
+                IrObject::BuiltinVar(ident, builtin_type, _) => IrValue::Object(Box::new(IrObject::BuiltinVar(ident, builtin_type, Some(typ)))),
+                object2 => IrValue::Object(Box::new(object2)),
+            }
+        },
+    }
+}
+
+fn expr_key(expr: &Expr) -> usize
+{ expr as *const Expr as usize }
+
+fn literal_sub_exprs(literal: &Literal<Expr>) -> Vec<&Expr>
+{
+    match literal {
+        Literal::Tuple(field_exprs) => field_exprs.iter().map(|field_expr| &**field_expr).collect(),
+        Literal::Array(elem_exprs) => elem_exprs.iter().map(|elem_expr| &**elem_expr).collect(),
+        Literal::FilledArray(elem_expr, _) => vec![&**elem_expr],
+        _ => Vec::new(),
+    }
+}
+
+fn sub_exprs(expr: &Expr) -> Vec<&Expr>
+{
+    match expr {
+        Expr::Literal(literal, _, _) => literal_sub_exprs(literal),
+        Expr::Lambda(_, _, _, _, _, _, _, _) => Vec::new(),
+        Expr::Var(_, _, _) => Vec::new(),
+        Expr::NamedFieldConApp(_, expr_named_field_pairs, _, _, _) => {
+            expr_named_field_pairs.iter().map(|expr_named_field_pair| {
+                    match expr_named_field_pair {
+                        NamedFieldPair(_, expr2, _) => &**expr2,
+                    }
+            }).collect()
+        },
+        Expr::PrintfApp(exprs, _, _) => exprs.iter().map(|expr2| &**expr2).collect(),
+        Expr::App(expr2, exprs, _, _) => {
+            let mut exprs2: Vec<&Expr> = Vec::new();
+            match &**expr2 {
+                Expr::Var(_, _, _) => (),
+                _ => exprs2.push(&**expr2),
+            }
+            exprs2.extend(exprs.iter().map(|expr3| &**expr3));
+            exprs2
+        },
+        Expr::GetField(expr2, _, _, _) => vec![&**expr2],
+        Expr::Get2Field(expr2, _, _, _) => vec![&**expr2],
+        Expr::SetField(expr2, _, expr3, _, _) | Expr::UpdateField(expr2, _, expr3, _, _) | Expr::UpdateGet2Field(expr2, _, expr3, _, _) => vec![&**expr2, &**expr3],
+        Expr::Uniq(expr2, _, _) => vec![&**expr2],
+        Expr::Shared(expr2, _, _) => vec![&**expr2],
+        Expr::Typed(expr2, _, _, _) => vec![&**expr2],
+        Expr::As(expr2, _, _, _) => vec![&**expr2],
+        Expr::Let(binds, expr2, _, _) => {
+            let mut exprs2: Vec<&Expr> = binds.iter().map(|bind| {
+                    match bind {
+                        Bind(_, expr3) => &**expr3,
+                    }
+            }).collect();
+            exprs2.push(&**expr2);
+            exprs2
+        },
+        Expr::If(expr2, expr3, expr4, _, _) => vec![&**expr2, &**expr3, &**expr4],
+        Expr::Match(expr2, cases, _, _) => {
+            let mut exprs2 = vec![&**expr2];
+            exprs2.extend(cases.iter().map(|case| {
+                    match case {
+                        Case(_, expr3) => &**expr3,
+                    }
+            }));
+            exprs2
+        },
+    }
+}
+
+fn has_rec_app(expr: &Expr, apps: &BTreeSet<usize>) -> bool
+{ apps.contains(&expr_key(expr)) || sub_exprs(expr).into_iter().any(|expr2| has_rec_app(expr2, apps)) }
+
+fn has_non_tail_rec_app(expr: &Expr, apps: &BTreeSet<usize>) -> bool
+{
+    match expr {
+        Expr::App(_, arg_exprs, _, _) if apps.contains(&expr_key(expr)) => arg_exprs.iter().any(|arg_expr| has_rec_app(arg_expr, apps)),
+        Expr::Typed(expr2, _, _, _) => has_non_tail_rec_app(expr2, apps),
+        Expr::Let(binds, expr2, _, _) => {
+            binds.iter().any(|bind| {
+                    match bind {
+                        Bind(_, expr3) => has_rec_app(expr3, apps),
+                    }
+            }) || has_non_tail_rec_app(expr2, apps)
+        },
+        Expr::If(expr2, expr3, expr4, _, _) => has_rec_app(expr2, apps) || has_non_tail_rec_app(expr3, apps) || has_non_tail_rec_app(expr4, apps),
+        Expr::Match(expr2, cases, _, _) => {
+            has_rec_app(expr2, apps) || cases.iter().any(|case| {
+                    match case {
+                        Case(_, expr3) => has_non_tail_rec_app(expr3, apps),
+                    }
+            })
+        },
+        _ => has_rec_app(expr, apps),
+    }
+}
+
+fn fun_has_panic_flag(ident: &String, ir_tree: &IrTree) -> bool
+{
+    match ir_tree.var(ident) {
+        Some(var) => {
+            match &*var.borrow() {
+                IrVar::Fun(fun) => {
+                    match &**fun {
+                        IrFun::Fun(_, _, _, _, _, _, _, panic_flag) => *panic_flag == IrPanicFlag::Panic,
+                        IrFun::Caller(_, _, _, _, _, _, _, panic_flag) => *panic_flag == IrPanicFlag::Panic,
+                    }
+                },
+                _ => false,
+            }
+        },
+        None => false,
+    }
+}
+
+fn block_can_panic(block: &IrBlock, ir_tree: &IrTree) -> bool
+{
+    block.instrs().iter().any(|instr| {
+            let op = match instr {
+                IrInstr::Op(op) => Some(op),
+                IrInstr::Assign(_, op) => Some(op),
+                IrInstr::Return(Some(op)) => Some(op),
+                IrInstr::If(op, _, _) => Some(op),
+                IrInstr::Switch(op, _) => Some(op),
+                _ => None,
+            };
+            let is_panic_call = match op {
+                Some(IrOp::CallFun(ident, _, _, _)) => fun_has_panic_flag(ident, ir_tree),
+                Some(IrOp::CallFunWithoutPanic(ident, _, _)) => fun_has_panic_flag(ident, ir_tree),
+                _ => false,
+            };
+            is_panic_call || match instr {
+                IrInstr::Block(block2) => block_can_panic(block2, ir_tree),
+                IrInstr::If(_, block2, block3) => block_can_panic(block2, ir_tree) || block_can_panic(block3, ir_tree),
+                IrInstr::Switch(_, cases) => {
+                    cases.iter().any(|case| {
+                            match case {
+                                IrCase::Case(_, block2) => block_can_panic(block2, ir_tree),
+                                IrCase::Default(block2) => block_can_panic(block2, ir_tree),
+                            }
+                    })
+                },
+                IrInstr::Loop(block2) => block_can_panic(block2, ir_tree),
+                IrInstr::Panic(_, _, _) => true,
+                _ => false,
+            }
+    })
+}
+
+fn fun_can_panic(fun: &IrFun, ir_tree: &IrTree) -> bool
+{
+    match fun {
+        IrFun::Fun(_, _, _, block, _, _, _, panic_flag) => *panic_flag == IrPanicFlag::Panic || block_can_panic(block, ir_tree),
+        IrFun::Caller(_, _, _, caller_funs, _, _, _, panic_flag) => {
+            *panic_flag == IrPanicFlag::Panic || caller_funs.funs().values().any(|caller_fun| {
+                    match caller_fun {
+                        IrCallerFun::Fun(ident) => fun_has_panic_flag(ident, ir_tree),
+                        IrCallerFun::InlineFun(_, block) => block_can_panic(block, ir_tree),
+                        IrCallerFun::Lambda(_, _, _, _, block) => block_can_panic(block, ir_tree),
+                    }
+            })
+        },
+    }
+}
+
+fn set_panic_flags(ir_tree: &IrTree)
+{
+    let mut is_changed = true;
+    while is_changed {
+        is_changed = false;
+        for def in ir_tree.defs() {
+            if let IrDef::Var(_, var) = &**def {
+                let can_panic = match &*var.borrow() {
+                    IrVar::Fun(fun) => fun_can_panic(fun, ir_tree),
+                    _ => false,
+                };
+                if can_panic {
+                    if let IrVar::Fun(fun) = &mut *var.borrow_mut() {
+                        match &mut **fun {
+                            IrFun::Fun(_, _, _, _, _, _, _, panic_flag) | IrFun::Caller(_, _, _, _, _, _, _, panic_flag) => {
+                                if *panic_flag != IrPanicFlag::Panic {
+                                    *panic_flag = IrPanicFlag::Panic;
+                                    is_changed = true;
+                                }
+                            },
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn fun_heap_flags(ident: &String, ir_tree: &IrTree) -> (IrPrivateHeapFlag, IrLocalHeapFlag, IrGlobalHeapFlag)
+{
+    match ir_tree.var(ident) {
+        Some(var) => {
+            match &*var.borrow() {
+                IrVar::Fun(fun) => {
+                    match &**fun {
+                        IrFun::Fun(_, _, _, _, private_heap_flag, local_heap_flag, global_heap_flag, _) => (*private_heap_flag, *local_heap_flag, *global_heap_flag),
+                        IrFun::Caller(_, _, _, _, private_heap_flag, local_heap_flag, global_heap_flag, _) => (*private_heap_flag, *local_heap_flag, *global_heap_flag),
+                    }
+                },
+                _ => (IrPrivateHeapFlag::None, IrLocalHeapFlag::None, IrGlobalHeapFlag::None),
+            }
+        },
+        None => (IrPrivateHeapFlag::None, IrLocalHeapFlag::None, IrGlobalHeapFlag::None),
+    }
+}
+
+fn add_heap_flags(flags: &mut (IrPrivateHeapFlag, IrLocalHeapFlag, IrGlobalHeapFlag), flags2: (IrPrivateHeapFlag, IrLocalHeapFlag, IrGlobalHeapFlag))
+{
+    flags.0 = flags.0.max(flags2.0);
+    flags.1 = flags.1.max(flags2.1);
+    flags.2 = flags.2.max(flags2.2);
+}
+
+fn add_heap_flags_for_block(block: &IrBlock, ir_tree: &IrTree, flags: &mut (IrPrivateHeapFlag, IrLocalHeapFlag, IrGlobalHeapFlag))
+{
+    for instr in block.instrs() {
+        let op = match instr {
+            IrInstr::Op(op) => Some(op),
+            IrInstr::Assign(_, op) => Some(op),
+            IrInstr::Return(Some(op)) => Some(op),
+            IrInstr::If(op, _, _) => Some(op),
+            IrInstr::Switch(op, _) => Some(op),
+            _ => None,
+        };
+        match op {
+            Some(IrOp::CallBuiltinFun(ident, _, _)) => {
+                if *ident == mangle_private_alloc_fun_name() {
+                    flags.0 = IrPrivateHeapFlag::Heap;
+                } else if *ident == mangle_local_alloc_fun_name() {
+                    flags.1 = IrLocalHeapFlag::Heap;
+                } else if *ident == mangle_global_alloc_fun_name() {
+                    flags.2 = IrGlobalHeapFlag::Heap;
+                }
+            },
+            Some(IrOp::CallFun(ident, _, _, _)) => add_heap_flags(flags, fun_heap_flags(ident, ir_tree)),
+            Some(IrOp::CallFunWithoutPanic(ident, _, _)) => add_heap_flags(flags, fun_heap_flags(ident, ir_tree)),
+            _ => (),
+        }
+        match instr {
+            IrInstr::Block(block2) => add_heap_flags_for_block(block2, ir_tree, flags),
+            IrInstr::If(_, block2, block3) => {
+                add_heap_flags_for_block(block2, ir_tree, flags);
+                add_heap_flags_for_block(block3, ir_tree, flags);
+            },
+            IrInstr::Switch(_, cases) => {
+                for case in cases {
+                    match case {
+                        IrCase::Case(_, block2) => add_heap_flags_for_block(block2, ir_tree, flags),
+                        IrCase::Default(block2) => add_heap_flags_for_block(block2, ir_tree, flags),
+                    }
+                }
+            },
+            IrInstr::Loop(block2) => add_heap_flags_for_block(block2, ir_tree, flags),
+            _ => (),
+        }
+    }
+}
+
+fn heap_flags_for_fun(fun: &IrFun, ir_tree: &IrTree) -> (IrPrivateHeapFlag, IrLocalHeapFlag, IrGlobalHeapFlag)
+{
+    match fun {
+        IrFun::Fun(_, _, _, block, private_heap_flag, local_heap_flag, global_heap_flag, _) => {
+            let mut flags = (*private_heap_flag, *local_heap_flag, *global_heap_flag);
+            add_heap_flags_for_block(block, ir_tree, &mut flags);
+            flags
+        },
+        IrFun::Caller(_, _, _, caller_funs, private_heap_flag, local_heap_flag, global_heap_flag, _) => {
+            let mut flags = (*private_heap_flag, *local_heap_flag, *global_heap_flag);
+            for caller_fun in caller_funs.funs().values() {
+                match caller_fun {
+                    IrCallerFun::Fun(ident) => add_heap_flags(&mut flags, fun_heap_flags(ident, ir_tree)),
+                    IrCallerFun::InlineFun(_, block) => add_heap_flags_for_block(block, ir_tree, &mut flags),
+                    IrCallerFun::Lambda(_, _, _, _, block) => add_heap_flags_for_block(block, ir_tree, &mut flags),
+                }
+            }
+            flags
+        },
+    }
+}
+
+fn set_heap_flags(ir_tree: &IrTree)
+{
+    let mut is_changed = true;
+    while is_changed {
+        is_changed = false;
+        for def in ir_tree.defs() {
+            if let IrDef::Var(_, var) = &**def {
+                let flags = match &*var.borrow() {
+                    IrVar::Fun(fun) => Some(heap_flags_for_fun(fun, ir_tree)),
+                    _ => None,
+                };
+                if let Some(flags) = flags {
+                    if let IrVar::Fun(fun) = &mut *var.borrow_mut() {
+                        match &mut **fun {
+                            IrFun::Fun(_, _, _, _, private_heap_flag, local_heap_flag, global_heap_flag, _) | IrFun::Caller(_, _, _, _, private_heap_flag, local_heap_flag, global_heap_flag, _) => {
+                                if (*private_heap_flag, *local_heap_flag, *global_heap_flag) != flags {
+                                    *private_heap_flag = flags.0;
+                                    *local_heap_flag = flags.1;
+                                    *global_heap_flag = flags.2;
+                                    is_changed = true;
+                                }
+                            },
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[derive(Clone)]
+enum Inst
+{
+    Var(String, String, Option<TypeName>, Rc<TypeValue>, Pos),
+    Fun(String, String, Option<TypeName>, Rc<TypeValue>, Pos),
+}
+
+impl Inst
+{
+    fn name(&self) -> &String
+    {
+        match self {
+            Inst::Var(name, _, _, _, _) => name,
+            Inst::Fun(name, _, _, _, _) => name,
+        }
+    }
+}
+
+const ENTRY_STATE: u32 = 0;
+const RETURN_STATE: u32 = 1;
+
+struct RecFun
+{
+    ident: String,
+    max_depth: usize,
+    apps: BTreeSet<usize>,
+    state_idx: usize,
+    sp_idx: usize,
+    stack_idx: usize,
+    ret_idx: Option<usize>,
+    states: Vec<Option<IrBlock>>,
+    cur_states: Vec<u32>,
+}
+
+impl RecFun
+{
+    fn control_var_count(&self) -> usize
+    { if self.ret_idx.is_some() { 4 } else { 3 } }
+
+    fn is_control_var(&self, idx: usize) -> bool
+    { idx >= self.state_idx && idx < self.state_idx + self.control_var_count() }
+
+    fn frame_field_index(&self, idx: usize) -> usize
+    {
+        if idx < self.state_idx {
+            idx + 1
+        } else {
+            idx + 1 - self.control_var_count()
+        }
+    }
+}
+
+struct TailFun
+{
+    name: String,
+    args: Vec<Option<(usize, Box<IrType>)>>,
+    ret_type: Box<IrType>,
+    rec: Option<RecFun>,
+}
+
+#[derive(Copy, Clone)]
+enum BranchTarget
+{
+    Var(Option<usize>),
+    Join(Option<usize>, u32),
+    Tail,
+}
+
+struct FunContext<'a>
+{
+    local_types: &'a LocalTypes,
+    type_values: Vec<Rc<TypeValue>>,
+    type_stack: TypeStack,
+    var_env: Environment<Option<usize>>,
+    closure_vars: BTreeMap<usize, IrArgVar>,
+    first_local_var_index: usize,
+    local_var_pairs: Vec<IrLocalVarPair>,
+    blocks: Vec<IrBlock>,
+    tail_fun: Option<TailFun>,
+    hoisted_values: BTreeMap<usize, Option<IrValue<IrArgVar>>>,
+    insts: &'a mut Vec<Inst>,
+}
+
+impl<'a> FunContext<'a>
+{
+    fn new(local_types: &'a LocalTypes, type_values: Vec<Rc<TypeValue>>, typ: &Type, first_local_var_idx: usize, insts: &'a mut Vec<Inst>) -> Self
+    {
+        let mut type_stack = TypeStack::new();
+        type_stack.set_first_type_values_for_type(typ);
+        if !type_values.is_empty() {
+            type_stack.push_type_values(type_values.clone());
+        }
+        let mut var_env = Environment::new();
+        var_env.push_new_vars();
+        FunContext {
+            local_types,
+            type_values,
+            type_stack,
+            var_env,
+            closure_vars: BTreeMap::new(),
+            first_local_var_index: first_local_var_idx,
+            local_var_pairs: Vec::new(),
+            blocks: vec![IrBlock::new()],
+            tail_fun: None,
+            hoisted_values: BTreeMap::new(),
+            insts,
+        }
+    }
+
+    fn new_lambda_context(&mut self, closure_vars: BTreeMap<usize, IrArgVar>, first_local_var_idx: usize) -> FunContext<'_>
+    {
+        let mut var_env = Environment::new();
+        var_env.push_new_vars();
+        FunContext {
+            local_types: self.local_types,
+            type_values: self.type_values.clone(),
+            type_stack: self.type_stack.clone(),
+            var_env,
+            closure_vars,
+            first_local_var_index: first_local_var_idx,
+            local_var_pairs: Vec::new(),
+            blocks: vec![IrBlock::new()],
+            tail_fun: None,
+            hoisted_values: BTreeMap::new(),
+            insts: &mut *self.insts,
+        }
+    }
+
+    fn type_value(&self, local_type: LocalType) -> BackendResult<Rc<TypeValue>>
+    { type_value_for_type_value(&Rc::new(TypeValue::Param(UniqFlag::None, local_type)), self.local_types, self.type_values.as_slice()) }
+
+    fn var(&self, idx: usize) -> IrArgVar
+    {
+        match self.closure_vars.get(&idx) {
+            Some(var) => var.clone(),
+            None => IrArgVar::Local(idx, Vec::new()),
+        }
+    }
+
+    fn var_value(&self, idx: usize) -> IrValue<IrArgVar>
+    { IrValue::Object(Box::new(IrObject::Var(self.var(idx), None))) }
+
+    fn local_var_modifier(&self, idx: usize) -> IrLocalVarModifier
+    {
+        if idx >= self.first_local_var_index {
+            match self.local_var_pairs.get(idx - self.first_local_var_index) {
+                Some(IrLocalVarPair(modifier, _)) => *modifier,
+                None => IrLocalVarModifier::None,
+            }
+        } else {
+            IrLocalVarModifier::None
+        }
+    }
+
+    fn add_local_var(&mut self, modifier: IrLocalVarModifier, typ: Box<IrType>) -> usize
+    {
+        let idx = self.first_local_var_index + self.local_var_pairs.len();
+        self.local_var_pairs.push(IrLocalVarPair(modifier, typ));
+        idx
+    }
+
+    fn add_instr(&mut self, instr: IrInstr)
+    {
+        if let Some(block) = self.blocks.last_mut() {
+            block.add_instr(instr);
+        }
+    }
+
+    fn push_block(&mut self)
+    { self.blocks.push(IrBlock::new()); }
+
+    fn pop_block(&mut self) -> BackendResult<IrBlock>
+    {
+        match self.blocks.pop() {
+            Some(block) => Ok(block),
+            None => Err(BackendError::Internal(String::from("pop_block: no block"))),
+        }
+    }
+
+    fn rec_fun(&self) -> Option<&RecFun>
+    {
+        match &self.tail_fun {
+            Some(tail_fun) => tail_fun.rec.as_ref(),
+            None => None,
+        }
+    }
+
+    fn rec_fun_mut(&mut self) -> BackendResult<&mut RecFun>
+    {
+        match &mut self.tail_fun {
+            Some(TailFun { rec: Some(rec), .. }) => Ok(rec),
+            _ => Err(BackendError::Internal(String::from("rec_fun_mut: no recursive function"))),
+        }
+    }
+
+    fn is_rec_app(&self, expr: &Expr) -> bool
+    {
+        match self.rec_fun() {
+            Some(rec) => rec.apps.contains(&expr_key(expr)),
+            None => false,
+        }
+    }
+
+    fn has_rec_app(&self, expr: &Expr) -> bool
+    {
+        match self.rec_fun() {
+            Some(rec) => has_rec_app(expr, &rec.apps),
+            None => false,
+        }
+    }
+
+    fn has_non_tail_rec_app(&self, expr: &Expr) -> bool
+    {
+        match self.rec_fun() {
+            Some(rec) => has_non_tail_rec_app(expr, &rec.apps),
+            None => false,
+        }
+    }
+
+    fn new_state(&mut self) -> BackendResult<u32>
+    {
+        let rec = self.rec_fun_mut()?;
+        rec.states.push(None);
+        Ok((rec.states.len() - 1) as u32)
+    }
+
+    fn begin_state(&mut self, state: u32) -> BackendResult<()>
+    {
+        self.rec_fun_mut()?.cur_states.push(state);
+        self.push_block();
+        Ok(())
+    }
+
+    fn end_state(&mut self) -> BackendResult<()>
+    {
+        let block = self.pop_block()?;
+        let rec = self.rec_fun_mut()?;
+        match rec.cur_states.pop() {
+            Some(state) => {
+                match rec.states.get_mut(state as usize) {
+                    Some(state_block) => {
+                        *state_block = Some(block);
+                        Ok(())
+                    },
+                    None => Err(BackendError::Internal(String::from("end_state: no state"))),
+                }
+            },
+            None => Err(BackendError::Internal(String::from("end_state: no current state"))),
+        }
+    }
+
+    fn add_state_jump(&mut self, state: u32) -> BackendResult<()>
+    {
+        let state_idx = self.rec_fun_mut()?.state_idx;
+        self.add_instr(IrInstr::Assign(Box::new(IrInstrVar::Local(state_idx, Vec::new())), IrOp::Load(IrValue::Uint(state, None))));
+        self.add_instr(IrInstr::Continue);
+        Ok(())
+    }
+
+    fn into_block(mut self) -> BackendResult<IrBlock>
+    {
+        let body = self.pop_block()?;
+        let mut block = IrBlock::new();
+        for local_var_pair in self.local_var_pairs {
+            block.add_local_var_pair(local_var_pair);
+        }
+        for instr in body.instrs() {
+            block.add_instr(instr.clone());
+        }
+        Ok(block)
+    }
+}
+
+enum VarTarget
+{
+    Local(Option<usize>),
+    Builtin(String, Option<TypeName>),
+    Var(String, Option<TypeName>),
+    Fun(String, Option<TypeName>),
+    Con(String),
+}
+
+#[derive(Clone)]
+struct MatchRow<'a>
+{
+    patterns: Vec<Option<&'a Pattern>>,
+    binds: Vec<(&'a Pattern, usize)>,
+    case_idx: usize,
+}
+
+enum MatchTest
+{
+    Case(IrCaseValue),
+    Value(IrValue<IrArgVar>),
+    String(Vec<u8>),
+}
+
+impl MatchTest
+{
+    fn is_same(&self, test: &MatchTest) -> bool
+    {
+        match (self, test) {
+            (MatchTest::Case(value1), MatchTest::Case(value2)) => value1 == value2,
+            (MatchTest::Value(value1), MatchTest::Value(value2)) => value1 == value2,
+            (MatchTest::String(bytes1), MatchTest::String(bytes2)) => bytes1 == bytes2,
+            (_, _) => false,
+        }
+    }
+}
+
+fn simplify_match_row<'a>(mut row: MatchRow<'a>, cols: &[usize], rows: &mut Vec<MatchRow<'a>>)
+{
+    for j in 0..row.patterns.len() {
+        loop {
+            match row.patterns[j] {
+                Some(pattern @ Pattern::Var(_, _, _, _)) => {
+                    row.binds.push((pattern, cols[j]));
+                    row.patterns[j] = None;
+                },
+                Some(pattern @ Pattern::At(_, _, pattern2, _, _)) => {
+                    row.binds.push((pattern, cols[j]));
+                    row.patterns[j] = Some(&**pattern2);
+                },
+                Some(Pattern::Wildcard(_, _)) => row.patterns[j] = None,
+                Some(Pattern::Literal(literal, _, _)) if matches!(&**literal, Literal::Tuple(patterns) if patterns.is_empty()) => row.patterns[j] = None,
+                Some(Pattern::Alt(patterns, _, _)) => {
+                    for pattern2 in patterns {
+                        let mut new_row = row.clone();
+                        new_row.patterns[j] = Some(&**pattern2);
+                        simplify_match_row(new_row, cols, rows);
+                    }
+                    return;
+                },
+                _ => break,
+            }
+        }
+    }
+    rows.push(row);
+}
+
+fn specialize_match_rows<'a, F>(rows: &[MatchRow<'a>], j: usize, field_count: usize, mut f: F) -> BackendResult<Vec<MatchRow<'a>>>
+    where F: FnMut(usize, &'a Pattern) -> BackendResult<Option<Vec<Option<&'a Pattern>>>>
+{
+    let mut new_rows: Vec<MatchRow<'a>> = Vec::new();
+    for (i, row) in rows.iter().enumerate() {
+        let field_patterns = match row.patterns[j] {
+            Some(pattern) => {
+                match f(i, pattern)? {
+                    Some(tmp_field_patterns) => tmp_field_patterns,
+                    None => continue,
+                }
+            },
+            None => vec![None; field_count],
+        };
+        let mut new_row = row.clone();
+        new_row.patterns.splice(j..(j + 1), field_patterns);
+        new_rows.push(new_row);
+    }
+    Ok(new_rows)
+}
+
+fn specialize_match_cols(cols: &[usize], j: usize, field_cols: &[usize]) -> Vec<usize>
+{
+    let mut new_cols: Vec<usize> = cols[0..j].to_vec();
+    new_cols.extend_from_slice(field_cols);
+    new_cols.extend_from_slice(&cols[(j + 1)..]);
+    new_cols
+}
+
+pub struct Lowerer
+{
+    inline_threshold: usize,
+}
+
+impl Lowerer
+{
+    pub fn new() -> Self
+    { Lowerer { inline_threshold: DEFAULT_INLINE_THRESHOLD, } }
+
+    pub fn new_with_inline_threshold(inline_threshold: usize) -> Self
+    { Lowerer { inline_threshold, } }
+
+    pub fn inline_threshold(&self) -> usize
+    { self.inline_threshold }
+
+    pub fn set_inline_threshold(&mut self, inline_threshold: usize)
+    { self.inline_threshold = inline_threshold; }
+
+    pub fn lower_tree(&self, tree: &Tree) -> BackendResult<IrTree>
+    {
+        let mut ir_tree = IrTree::new();
+        let mut errs: Vec<BackendMessageError> = Vec::new();
+        let mut insts: VecDeque<Inst> = VecDeque::new();
+        let mut inst_names: BTreeSet<String> = BTreeSet::new();
+        for def in tree.defs() {
+            if let Def::Var(_, ident, var, pos) = &**def {
+                match self.kernel_inst(ident, var, pos) {
+                    Ok(Some(inst)) => {
+                        inst_names.insert(inst.name().clone());
+                        insts.push_back(inst);
+                    },
+                    Ok(None) => (),
+                    Err(BackendError::Messages(mut msg_errs)) => errs.append(&mut msg_errs),
+                    Err(err) => return Err(err),
+                }
+            }
+        }
+        while let Some(inst) = insts.pop_front() {
+            let mut new_insts: Vec<Inst> = Vec::new();
+            let res = self.lower_inst(&inst, tree, &mut ir_tree, &mut new_insts);
+            for new_inst in new_insts {
+                if inst_names.insert(new_inst.name().clone()) {
+                    insts.push_back(new_inst);
+                }
+            }
+            match res {
+                Ok(()) => (),
+                Err(BackendError::Messages(mut msg_errs)) => errs.append(&mut msg_errs),
+                Err(err) => return Err(err),
+            }
+        }
+        if errs.is_empty() {
+            set_panic_flags(&ir_tree);
+            set_heap_flags(&ir_tree);
+            #[cfg(debug_assertions)]
+            verify_ir_tree(&ir_tree)?;
+            inline_ir_tree(&ir_tree, self.inline_threshold)?;
+            #[cfg(debug_assertions)]
+            verify_ir_tree(&ir_tree)?;
+            fold_ir_tree(&ir_tree);
+            #[cfg(debug_assertions)]
+            verify_ir_tree(&ir_tree)?;
+            remove_unreachable_defs(&mut ir_tree);
+            #[cfg(debug_assertions)]
+            verify_ir_tree(&ir_tree)?;
+            Ok(ir_tree)
+        } else {
+            Err(BackendError::Messages(errs))
+        }
+    }
+
+    pub fn lower_type_value(&self, type_value: &Rc<TypeValue>, pos: &Pos, tree: &Tree, ir_tree: &mut IrTree) -> BackendResult<Box<IrType>>
+    {
+        if type_value_has_param(type_value) {
+            return message_error(pos, String::from("polymorphic type is unsupported"));
+        }
+        self.ir_type_for_type_value(type_value, pos, tree, ir_tree)
+    }
+
+    fn kernel_inst(&self, ident: &String, var: &Rc<RefCell<Var>>, pos: &Pos) -> BackendResult<Option<Inst>>
+    {
+        let var_r = var.borrow();
+        match &*var_r {
+            Var::Fun(fun, None, Some(typ)) => {
+                match &**fun {
+                    Fun::Fun(FunModifier::Kernel, _, _, _, _, _, _) => {
+                        if !typ.type_param_entries().is_empty() {
+                            return message_error(pos, format!("polymorphic kernel {} is unsupported", ident));
+                        }
+                        let name = mangle_fun_name(ident.as_str(), &[], typ)?;
+                        Ok(Some(Inst::Fun(name, ident.clone(), None, typ.type_value().clone(), pos.clone())))
+                    },
+                    _ => Ok(None),
+                }
+            },
+            _ => Ok(None),
+        }
+    }
+
+    fn impl_var(&self, ident: &String, type_name: &TypeName, tree: &Tree) -> BackendResult<Option<Rc<RefCell<ImplVar>>>>
+    {
+        let trait_ident = match tree.var(ident) {
+            Some(var) => {
+                match &*var.borrow() {
+                    Var::Builtin(Some(tmp_trait_ident), _) => tmp_trait_ident.clone(),
+                    Var::Var(_, _, _, _, Some(tmp_trait_ident), _, _, _, _) => tmp_trait_ident.clone(),
+                    Var::Fun(_, Some(tmp_trait_ident), _) => tmp_trait_ident.clone(),
+                    _ => return Err(BackendError::Internal(String::from("impl_var: variable isn't trait variable"))),
+                }
+            },
+            None => return Err(BackendError::Internal(String::from("impl_var: no variable"))),
+        };
+        let trait1 = match tree.trait1(&trait_ident) {
+            Some(tmp_trait) => tmp_trait,
+            None => return Err(BackendError::Internal(String::from("impl_var: no trait"))),
+        };
+        let trait_r = trait1.borrow();
+        let trait_vars = match &*trait_r {
+            Trait(_, _, Some(tmp_trait_vars)) => tmp_trait_vars,
+            _ => return Err(BackendError::Internal(String::from("impl_var: no trait variables"))),
+        };
+        let impl1 = match trait_vars.impl1(type_name) {
+            Some(tmp_impl) => tmp_impl,
+            None => return Err(BackendError::Internal(String::from("impl_var: no implementation"))),
+        };
+        let impl_r = impl1.borrow();
+        match &*impl_r {
+            Impl::Builtin(_, _, Some(impl_vars)) => Ok(impl_vars.var(ident).cloned()),
+            Impl::Impl(_, _, _, Some(impl_vars)) => Ok(impl_vars.var(ident).cloned()),
+            _ => Err(BackendError::Internal(String::from("impl_var: no implementation variables"))),
+        }
+    }
+
+    fn lower_inst(&self, inst: &Inst, tree: &Tree, ir_tree: &mut IrTree, insts: &mut Vec<Inst>) -> BackendResult<()>
+    {
+        match inst {
+            Inst::Var(name, ident, type_name, type_value, pos) => self.lower_var_inst(name, ident, type_name, type_value, pos, tree, ir_tree),
+            Inst::Fun(name, ident, type_name, type_value, pos) => self.lower_fun_inst(name, ident, type_name, type_value, pos, tree, ir_tree, insts),
+        }
+    }
+
+    fn lower_var_inst(&self, name: &str, ident: &String, type_name: &Option<TypeName>, type_value: &Rc<TypeValue>, pos: &Pos, tree: &Tree, ir_tree: &mut IrTree) -> BackendResult<()>
+    {
+        let impl_var = match type_name {
+            Some(type_name) => self.impl_var(ident, type_name, tree)?,
+            None => None,
+        };
+        match impl_var {
+            Some(impl_var) => {
+                let impl_var_r = impl_var.borrow();
+                match &*impl_var_r {
+                    ImplVar::Var(_, _, _, _, Some(value)) => self.lower_global_var(String::from(name), VarModifier::None, value, type_value, pos, tree, ir_tree),
+                    _ => Err(BackendError::Internal(String::from("lower_var_inst: implementation variable isn't variable or no value"))),
+                }
+            },
+            None => {
+                let var = match tree.var(ident) {
+                    Some(tmp_var) => tmp_var,
+                    None => return Err(BackendError::Internal(String::from("lower_var_inst: no variable"))),
+                };
+                let var_r = var.borrow();
+                match &*var_r {
+                    Var::Var(modifier, _, _, _, _, _, _, _, Some(value)) => self.lower_global_var(String::from(name), *modifier, value, type_value, pos, tree, ir_tree),
+                    _ => Err(BackendError::Internal(String::from("lower_var_inst: variable isn't variable or no value"))),
+                }
+            },
+        }
+    }
+
+    fn lower_fun_inst(&self, name: &str, ident: &String, type_name: &Option<TypeName>, type_value: &Rc<TypeValue>, pos: &Pos, tree: &Tree, ir_tree: &mut IrTree, insts: &mut Vec<Inst>) -> BackendResult<()>
+    {
+        let impl_var = match type_name {
+            Some(type_name) => self.impl_var(ident, type_name, tree)?,
+            None => None,
+        };
+        match impl_var {
+            Some(impl_var) => {
+                let impl_var_r = impl_var.borrow();
+                match &*impl_var_r {
+                    ImplVar::Fun(impl_fun, Some(impl_type)) => {
+                        match &**impl_fun {
+                            ImplFun(args, body, Some(ret_local_type), Some(local_types)) => {
+                                let mut arg_tuples: Vec<(String, LocalType, Pos)> = Vec::new();
+                                for arg in args {
+                                    match arg {
+                                        ImplArg(arg_ident, Some(arg_local_type), arg_pos) => arg_tuples.push((arg_ident.clone(), *arg_local_type, arg_pos.clone())),
+                                        _ => return Err(BackendError::Internal(String::from("lower_fun_inst: no local type of argument"))),
+                                    }
+                                }
+                                let type_values = type_values_for_type(impl_type, type_value)?;
+                                self.lower_fun(String::from(name), ident, IrFunModifier::None, None, arg_tuples.as_slice(), body, *ret_local_type, local_types, type_values, impl_type, tree, ir_tree, insts)
+                            },
+                            _ => Err(BackendError::Internal(String::from("lower_fun_inst: no local types of implementation function"))),
+                        }
+                    },
+                    ImplVar::Builtin(_) => message_error(pos, format!("built-in implementation function {} is unsupported", ident)),
+                    _ => Err(BackendError::Internal(String::from("lower_fun_inst: implementation variable isn't function or no type"))),
+                }
+            },
+            None => {
+                let var = match tree.var(ident) {
+                    Some(tmp_var) => tmp_var,
+                    None => return Err(BackendError::Internal(String::from("lower_fun_inst: no variable"))),
+                };
+                let var_r = var.borrow();
+                match &*var_r {
+                    Var::Fun(fun, _, Some(typ)) => {
+                        match &**fun {
+                            Fun::Fun(modifier, args, _, _, Some(body), Some(ret_local_type), Some(local_types)) => {
+                                let mut arg_tuples: Vec<(String, LocalType, Pos)> = Vec::new();
+                                for arg in args {
+                                    match arg {
+                                        Arg(arg_ident, _, Some(arg_local_type), arg_pos) => arg_tuples.push((arg_ident.clone(), *arg_local_type, arg_pos.clone())),
+                                        _ => return Err(BackendError::Internal(String::from("lower_fun_inst: no local type of argument"))),
+                                    }
+                                }
+                                let (ir_modifier, max_depth) = match modifier {
+                                    FunModifier::None => (IrFunModifier::None, None),
+                                    FunModifier::Kernel => (IrFunModifier::Kernel, None),
+                                    FunModifier::Inline => (IrFunModifier::Inline, None),
+                                    FunModifier::Recursive(depth) => (IrFunModifier::None, Some(*depth)),
+                                };
+                                let type_values = type_values_for_type(typ, type_value)?;
+                                self.lower_fun(String::from(name), ident, ir_modifier, max_depth, arg_tuples.as_slice(), body, *ret_local_type, local_types, type_values, typ, tree, ir_tree, insts)
+                            },
+                            Fun::Fun(_, _, _, _, None, _, _) => message_error(pos, format!("function {} without body is unsupported", ident)),
+                            _ => Err(BackendError::Internal(String::from("lower_fun_inst: function isn't function or no local types"))),
+                        }
+                    },
+                    _ => Err(BackendError::Internal(String::from("lower_fun_inst: variable isn't function or no type"))),
+                }
+            },
+        }
+    }
+
+    fn ir_type_for_type_value(&self, type_value: &Rc<TypeValue>, pos: &Pos, tree: &Tree, ir_tree: &mut IrTree) -> BackendResult<Box<IrType>>
+    {
+        match &**type_value {
+            TypeValue::Param(_, _) => Err(BackendError::Internal(String::from("ir_type_for_type_value: type value is type parameter"))),
+            TypeValue::Type(_, TypeValueName::Tuple, args) => {
+                if args.is_empty() {
+                    return Ok(Box::new(IrType::Void));
+                }
+                let mut field_types: Vec<Box<IrType>> = Vec::new();
+                for arg in args {
+                    let field_type = self.ir_type_for_type_value(arg, pos, tree, ir_tree)?;
+                    if *field_type == IrType::Void {
+                        return message_error(pos, String::from("unit type in tuple is unsupported"));
+                    }
+                    field_types.push(field_type);
+                }
+                let name = mangle_struct_name(&type_value_without_uniq_flags(type_value))?;
+                self.add_struct(&name, field_types, ir_tree);
+                Ok(Box::new(IrType::Struct(name)))
+            },
+            TypeValue::Type(_, TypeValueName::Array(Some(len)), args) => {
+                match args.first() {
+                    Some(arg) => {
+                        let elem_type = self.ir_type_for_type_value(arg, pos, tree, ir_tree)?;
+                        if *elem_type == IrType::Void {
+                            return message_error(pos, String::from("array of unit type is unsupported"));
+                        }
+                        Ok(Box::new(IrType::Array(elem_type, *len)))
+                    },
+                    None => Err(BackendError::Internal(String::from("ir_type_for_type_value: no element type of array"))),
+                }
+            },
+            TypeValue::Type(_, TypeValueName::Array(None), _) => message_error(pos, String::from("array type without length is unsupported")),
+            TypeValue::Type(_, TypeValueName::Fun, _) => {
+                let name = mangle_struct_name(&type_value_without_uniq_flags(type_value))?;
+                self.add_struct(&name, vec![Box::new(IrType::Uint)], ir_tree);
+                Ok(Box::new(IrType::Struct(name)))
+            },
+            TypeValue::Type(_, TypeValueName::Name(ident), args) => {
+                if let Some(typ) = primitive_ir_type_for_ident(ident.as_str()) {
+                    return Ok(Box::new(typ));
+                }
+                if let (Some((ptr_modifier, access_modifier)), Some(arg)) = (ptr_modifiers_for_ident(ident.as_str(), "Ref").or_else(|| ptr_modifiers_for_ident(ident.as_str(), "Box")), args.first()) {
+                    let elem_type = self.ir_type_for_type_value(arg, pos, tree, ir_tree)?;
+                    if *elem_type == IrType::Void {
+                        return message_error(pos, String::from("reference to unit type is unsupported"));
+                    }
+                    return Ok(Box::new(IrType::Ptr(ptr_modifier, access_modifier, elem_type)));
+                }
+                if let (Some((ptr_modifier, access_modifier)), Some(arg)) = (ptr_modifiers_for_ident(ident.as_str(), "Slice"), args.first()) {
+                    let elem_type = self.ir_type_for_type_value(arg, pos, tree, ir_tree)?;
+                    if *elem_type == IrType::Void {
+                        return message_error(pos, String::from("slice of unit type is unsupported"));
+                    }
+                    let name = mangle_struct_name(&type_value_without_uniq_flags(type_value))?;
+                    self.add_struct(&name, vec![Box::new(IrType::Ptr(ptr_modifier, access_modifier, elem_type)), Box::new(IrType::SizeT)], ir_tree);
+                    return Ok(Box::new(IrType::Struct(name)));
+                }
+                match tree.type_var(ident) {
+                    Some(type_var) => {
+                        let type_var_r = type_var.borrow();
+                        match &*type_var_r {
+                            TypeVar::Data(_, cons, _) => self.ir_type_for_data_type_value(ident, type_value, args.as_slice(), cons.as_slice(), pos, tree, ir_tree),
+                            _ => message_error(pos, format!("type {} is unsupported", ident)),
+                        }
+                    },
+                    None => Err(BackendError::Internal(format!("ir_type_for_type_value: no type variable {}", ident))),
+                }
+            },
+        }
+    }
+
+    fn add_struct(&self, ident: &String, field_types: Vec<Box<IrType>>, ir_tree: &mut IrTree)
+    {
+        if ir_tree.struct1(ident).is_none() {
+            let struct1 = Rc::new(RefCell::new(IrStruct::Struct(field_types)));
+            ir_tree.add_struct(ident.clone(), struct1.clone());
+            ir_tree.add_def(IrDef::Struct(ident.clone(), struct1));
+        }
+    }
+
+    fn set_struct_field_types(&self, ident: &String, field_types: Vec<Box<IrType>>, ir_tree: &IrTree) -> BackendResult<()>
+    {
+        match ir_tree.struct1(ident) {
+            Some(struct1) => {
+                *struct1.borrow_mut() = IrStruct::Struct(field_types);
+                Ok(())
+            },
+            None => Err(BackendError::Internal(String::from("set_struct_field_types: no structure"))),
+        }
+    }
+
+    fn add_union(&self, ident: &String, field_types: Vec<Box<IrType>>, ir_tree: &mut IrTree)
+    {
+        if ir_tree.union(ident).is_none() {
+            let union = Rc::new(RefCell::new(IrUnion(field_types)));
+            ir_tree.add_union(ident.clone(), union.clone());
+            ir_tree.add_def(IrDef::Union(ident.clone(), union));
+        }
+    }
+
+    fn ir_type_for_data_type_value(&self, ident: &String, type_value: &Rc<TypeValue>, args: &[Rc<TypeValue>], cons: &[Rc<RefCell<Con>>], pos: &Pos, tree: &Tree, ir_tree: &mut IrTree) -> BackendResult<Box<IrType>>
+    {
+        if cons.iter().all(|con| con_field_count(&con.borrow()) == 0) {
+            return Ok(Box::new(IrType::Uint));
+        }
+        let type_value2 = type_value_without_uniq_flags(type_value);
+        let name = mangle_struct_name(&type_value2)?;
+        if ir_tree.struct1(&name).is_some() {
+            return Ok(Box::new(IrType::Struct(name)));
+        }
+        self.add_struct(&name, Vec::new(), ir_tree);
+        let typ = IrType::Struct(name.clone());
+        let mut con_field_types: Vec<(String, Vec<Box<IrType>>)> = Vec::new();
+        for con in cons {
+            let con_ident = con_ident(&con.borrow()).clone();
+            let mut field_types: Vec<Box<IrType>> = Vec::new();
+            for field_type_value in &con_field_type_values(&con_ident, args, tree)? {
+                let field_type = self.ir_type_for_type_value(field_type_value, pos, tree, ir_tree)?;
+                if *field_type == IrType::Void {
+                    return message_error(pos, format!("unit type in constructor {} is unsupported", con_ident));
+                }
+                if self.has_type(&field_type, &typ, ir_tree) {
+                    return message_error(pos, format!("recursive data type {} is unsupported", ident));
+                }
+                field_types.push(field_type);
+            }
+            con_field_types.push((con_ident, field_types));
+        }
+        if con_field_types.len() == 1 {
+            match con_field_types.pop() {
+                Some((_, field_types)) => self.set_struct_field_types(&name, field_types, ir_tree)?,
+                None => return Err(BackendError::Internal(String::from("ir_type_for_data_type_value: no constructor"))),
+            }
+        } else {
+            let mut union_field_types: Vec<Box<IrType>> = Vec::new();
+            for (con_ident, field_types) in con_field_types {
+                if !field_types.is_empty() {
+                    let field_struct_name = mangle_union_field_struct_name(con_ident.as_str(), &type_value2)?;
+                    self.add_struct(&field_struct_name, field_types, ir_tree);
+                    union_field_types.push(Box::new(IrType::Struct(field_struct_name)));
+                }
+            }
+            let union_name = mangle_union_name(&type_value2)?;
+            self.add_union(&union_name, union_field_types, ir_tree);
+            self.set_struct_field_types(&name, vec![Box::new(IrType::Uint), Box::new(IrType::Union(union_name))], ir_tree)?;
+        }
+        Ok(Box::new(typ))
+    }
+
+    fn add_fun_struct_field(&self, fun_type: &IrType, field_type: Box<IrType>, ir_tree: &IrTree) -> BackendResult<usize>
+    {
+        match fun_type {
+            IrType::Struct(ident) => {
+                match ir_tree.struct1(ident) {
+                    Some(struct1) => {
+                        match &mut *struct1.borrow_mut() {
+                            IrStruct::Struct(field_types) => {
+                                field_types.push(field_type);
+                                Ok(field_types.len() - 1)
+                            },
+                            IrStruct::Closure(_) => Err(BackendError::Internal(String::from("add_fun_struct_field: function structure is closure"))),
+                        }
+                    },
+                    None => Err(BackendError::Internal(String::from("add_fun_struct_field: no function structure"))),
+                }
+            },
+            _ => Err(BackendError::Internal(String::from("add_fun_struct_field: function type isn't structure"))),
+        }
+    }
+
+    fn has_type(&self, typ: &IrType, typ2: &IrType, ir_tree: &IrTree) -> bool
+    {
+        if typ == typ2 {
+            return true;
+        }
+        match typ {
+            IrType::Struct(ident) => {
+                match ir_tree.struct1(ident) {
+                    Some(struct1) => {
+                        match &*struct1.borrow() {
+                            IrStruct::Struct(field_types) => field_types.iter().any(|field_type| self.has_type(field_type, typ2, ir_tree)),
+                            IrStruct::Closure(field_types) => field_types.values().any(|field_type| self.has_type(field_type, typ2, ir_tree)),
+                        }
+                    },
+                    None => false,
+                }
+            },
+            IrType::Union(ident) => {
+                match ir_tree.union(ident) {
+                    Some(union) => union.borrow().0.iter().any(|field_type| self.has_type(field_type, typ2, ir_tree)),
+                    None => false,
+                }
+            },
+            IrType::Array(elem_type, _) => self.has_type(elem_type, typ2, ir_tree),
+            _ => false,
+        }
+    }
+
+    fn add_closure_struct(&self, ident: &str, field_types: BTreeMap<usize, Box<IrType>>, ir_tree: &mut IrTree)
+    {
+        let struct1 = Rc::new(RefCell::new(IrStruct::Closure(field_types)));
+        ir_tree.add_struct(String::from(ident), struct1.clone());
+        ir_tree.add_def(IrDef::Struct(String::from(ident), struct1));
+    }
+
+    fn add_caller(&self, type_value: &Rc<TypeValue>, pos: &Pos, tree: &Tree, ir_tree: &mut IrTree) -> BackendResult<String>
+    {
+        let name = mangle_caller_name(type_value)?;
+        if ir_tree.var(&name).is_some() {
+            return Ok(name);
+        }
+        let fun_type = self.ir_type_for_type_value(type_value, pos, tree, ir_tree)?;
+        let (arg_type_values, ret_type_value) = match &**type_value {
+            TypeValue::Type(_, TypeValueName::Fun, args) => {
+                match args.split_last() {
+                    Some((ret_type_value, arg_type_values)) => (arg_type_values, ret_type_value),
+                    None => return Err(BackendError::Internal(String::from("add_caller: no return type"))),
+                }
+            },
+            _ => return Err(BackendError::Internal(String::from("add_caller: type value isn't function type"))),
+        };
+        let mut arg_types: Vec<Box<IrType>> = Vec::new();
+        for arg_type_value in arg_type_values {
+            let arg_type = self.ir_type_for_type_value(arg_type_value, pos, tree, ir_tree)?;
+            if *arg_type != IrType::Void {
+                arg_types.push(arg_type);
+            }
+        }
+        let ret_type = self.ir_type_for_type_value(ret_type_value, pos, tree, ir_tree)?;
+        let fun = IrFun::Caller(fun_type, arg_types, ret_type, Box::new(IrCallerFuns::new()), IrPrivateHeapFlag::None, IrLocalHeapFlag::None, IrGlobalHeapFlag::None, IrPanicFlag::None);
+        let ir_var = Rc::new(RefCell::new(IrVar::Fun(Box::new(fun))));
+        ir_tree.add_var(name.clone(), ir_var.clone());
+        ir_tree.add_def(IrDef::Var(name.clone(), ir_var));
+        Ok(name)
+    }
+
+    fn add_caller_fun(&self, caller_name: &String, caller_fun: IrCallerFun, ir_tree: &IrTree) -> BackendResult<usize>
+    {
+        match ir_tree.var(caller_name) {
+            Some(var) => {
+                match &mut *var.borrow_mut() {
+                    IrVar::Fun(fun) => {
+                        match &mut **fun {
+                            IrFun::Caller(_, _, _, caller_funs, _, _, _, _) => Ok(caller_funs.add_fun(caller_fun)),
+                            _ => Err(BackendError::Internal(String::from("add_caller_fun: function isn't caller"))),
+                        }
+                    },
+                    _ => Err(BackendError::Internal(String::from("add_caller_fun: variable isn't function"))),
+                }
+            },
+            None => Err(BackendError::Internal(String::from("add_caller_fun: no caller"))),
+        }
+    }
+
+    fn set_caller_lambda(&self, caller_name: &String, idx: usize, lambda: IrCallerFun, ir_tree: &IrTree) -> BackendResult<()>
+    {
+        match ir_tree.var(caller_name) {
+            Some(var) => {
+                match &mut *var.borrow_mut() {
+                    IrVar::Fun(fun) => {
+                        match &mut **fun {
+                            IrFun::Caller(_, _, _, caller_funs, _, _, _, _) => {
+                                match caller_funs.lambda_mut(idx) {
+                                    Some(caller_fun) => {
+                                        *caller_fun = lambda;
+                                        Ok(())
+                                    },
+                                    None => Err(BackendError::Internal(String::from("set_caller_lambda: no lambda"))),
+                                }
+                            },
+                            _ => Err(BackendError::Internal(String::from("set_caller_lambda: function isn't caller"))),
+                        }
+                    },
+                    _ => Err(BackendError::Internal(String::from("set_caller_lambda: variable isn't function"))),
+                }
+            },
+            None => Err(BackendError::Internal(String::from("set_caller_lambda: no caller"))),
+        }
+    }
+
+    fn ir_type_for_local_type(&self, local_type: LocalType, pos: &Pos, tree: &Tree, ctx: &FunContext, ir_tree: &mut IrTree) -> BackendResult<Box<IrType>>
+    {
+        let type_value = ctx.type_value(local_type)?;
+        self.ir_type_for_type_value(&type_value, pos, tree, ir_tree)
+    }
+
+    fn lower_global_var(&self, name: String, modifier: VarModifier, value: &Value, type_value: &Rc<TypeValue>, pos: &Pos, tree: &Tree, ir_tree: &mut IrTree) -> BackendResult<()>
+    {
+        let typ = self.ir_type_for_type_value(type_value, pos, tree, ir_tree)?;
+        if *typ == IrType::Void {
+            return Ok(());
+        }
+        let ir_value = self.ir_value_for_value(value, type_value, pos, tree, ir_tree)?;
+        let ir_var = match modifier {
+            VarModifier::None | VarModifier::Constant => IrVar::Const(typ, ir_value),
+            VarModifier::Global => IrVar::Var(IrGlobalVarModifier::Global, IrAccessModifier::None, typ, ir_value),
+            _ => return message_error(pos, String::from("private or local global variables are unsupported")),
+        };
+        let ir_var = Rc::new(RefCell::new(ir_var));
+        ir_tree.add_var(name.clone(), ir_var.clone());
+        ir_tree.add_def(IrDef::Var(name, ir_var));
+        Ok(())
+    }
+
+    fn ir_value_for_value(&self, value: &Value, type_value: &Rc<TypeValue>, pos: &Pos, tree: &Tree, ir_tree: &mut IrTree) -> BackendResult<IrValue<IrValueVar>>
+    {
+        let typ = self.ir_type_for_type_value(type_value, pos, tree, ir_tree)?;
+        let n = match value {
+            Value::Bool(b) => return Ok(IrValue::Int(if *b { 1 } else { 0 }, None)),
+            Value::Float(x) => {
+                return match &*typ {
+                    IrType::Half => Ok(IrValue::Float(*x, Some(typ))),
+                    _ => Ok(IrValue::Float(*x, None)),
+                }
+            },
+            Value::Double(x) => return Ok(IrValue::Double(*x, None)),
+            Value::Char(n) => *n as i128,
+            Value::Short(n) => *n as i128,
+            Value::Int(n) => *n as i128,
+            Value::Long(n) => *n as i128,
+            Value::Uchar(n) => *n as i128,
+            Value::Ushort(n) => *n as i128,
+            Value::Uint(n) => *n as i128,
+            Value::Ulong(n) => *n as i128,
+            Value::Object(_, object) => return self.ir_value_for_object(&object.borrow(), type_value, typ, pos, tree, ir_tree),
+        };
+        match &*typ {
+            IrType::Char => Ok(IrValue::Char(n as i8, None)),
+            IrType::Short => Ok(IrValue::Short(n as i16, None)),
+            IrType::Int => Ok(IrValue::Int(n as i32, None)),
+            IrType::Long => Ok(IrValue::Long(n as i64, None)),
+            IrType::Uchar => Ok(IrValue::Uchar(n as u8, None)),
+            IrType::Ushort => Ok(IrValue::Ushort(n as u16, None)),
+            IrType::Uint => Ok(IrValue::Uint(n as u32, None)),
+            IrType::Ulong => Ok(IrValue::Ulong(n as u64, None)),
+            IrType::SizeT => Ok(IrValue::SizeT(n as u64, None)),
+            IrType::PtrdiffT => Ok(IrValue::PtrdiffT(n as i64, None)),
+            IrType::IntptrT => Ok(IrValue::IntptrT(n as i64, None)),
+            IrType::UintptrT => Ok(IrValue::UintptrT(n as u64, None)),
+            _ => Err(BackendError::Internal(String::from("ir_value_for_value: invalid type for integer"))),
+        }
+    }
+
+    fn ir_value_for_object(&self, object: &Object, type_value: &Rc<TypeValue>, typ: Box<IrType>, pos: &Pos, tree: &Tree, ir_tree: &mut IrTree) -> BackendResult<IrValue<IrValueVar>>
+    {
+        let args: &[Rc<TypeValue>] = match &**type_value {
+            TypeValue::Type(_, _, args) => args.as_slice(),
+            TypeValue::Param(_, _) => return Err(BackendError::Internal(String::from("ir_value_for_object: type value is type parameter"))),
+        };
+        match object {
+            Object::String(bytes) => Ok(IrValue::Object(Box::new(IrObject::Struct(vec![IrValue::Object(Box::new(IrObject::String(bytes.clone()))), IrValue::SizeT(bytes.len() as u64, None)], Vec::new(), None)))),
+            Object::CharN(elems) => Ok(IrValue::Object(Box::new(IrObject::Vector(elems.iter().map(|e| IrValue::Char(*e, None)).collect(), typ)))),
+            Object::ShortN(elems) => Ok(IrValue::Object(Box::new(IrObject::Vector(elems.iter().map(|e| IrValue::Short(*e, None)).collect(), typ)))),
+            Object::IntN(elems) => Ok(IrValue::Object(Box::new(IrObject::Vector(elems.iter().map(|e| IrValue::Int(*e, None)).collect(), typ)))),
+            Object::LongN(elems) => Ok(IrValue::Object(Box::new(IrObject::Vector(elems.iter().map(|e| IrValue::Long(*e, None)).collect(), typ)))),
+            Object::UcharN(elems) => Ok(IrValue::Object(Box::new(IrObject::Vector(elems.iter().map(|e| IrValue::Uchar(*e, None)).collect(), typ)))),
+            Object::UshortN(elems) => Ok(IrValue::Object(Box::new(IrObject::Vector(elems.iter().map(|e| IrValue::Ushort(*e, None)).collect(), typ)))),
+            Object::UintN(elems) => Ok(IrValue::Object(Box::new(IrObject::Vector(elems.iter().map(|e| IrValue::Uint(*e, None)).collect(), typ)))),
+            Object::UlongN(elems) => Ok(IrValue::Object(Box::new(IrObject::Vector(elems.iter().map(|e| IrValue::Ulong(*e, None)).collect(), typ)))),
+            Object::FloatN(elems) => Ok(IrValue::Object(Box::new(IrObject::Vector(elems.iter().map(|e| IrValue::Float(*e, None)).collect(), typ)))),
+            Object::DoubleN(elems) => Ok(IrValue::Object(Box::new(IrObject::Vector(elems.iter().map(|e| IrValue::Double(*e, None)).collect(), typ)))),
+            Object::Tuple(field_values) => {
+                if field_values.len() != args.len() {
+                    return Err(BackendError::Internal(String::from("ir_value_for_object: numbers of fields aren't equal")));
+                }
+                let mut ir_field_values: Vec<IrValue<IrValueVar>> = Vec::new();
+                for (field_value, arg) in field_values.iter().zip(args.iter()) {
+                    ir_field_values.push(self.ir_value_for_value(field_value, arg, pos, tree, ir_tree)?);
+                }
+                Ok(IrValue::Object(Box::new(IrObject::Struct(ir_field_values, Vec::new(), None))))
+            },
+            Object::Array(elem_values) => {
+                match args.first() {
+                    Some(arg) => {
+                        let mut ir_elem_values: Vec<IrValue<IrValueVar>> = Vec::new();
+                        for elem_value in elem_values {
+                            ir_elem_values.push(self.ir_value_for_value(elem_value, arg, pos, tree, ir_tree)?);
+                        }
+                        Ok(IrValue::Object(Box::new(IrObject::Array(ir_elem_values, None))))
+                    },
+                    None => Err(BackendError::Internal(String::from("ir_value_for_object: no element type of array"))),
+                }
+            },
+            Object::Ref(idx, offs) => {
+                match args.first() {
+                    Some(arg) if offs.is_empty() => {
+                        let name = self.lower_ref_value(*idx, arg, pos, tree, ir_tree)?;
+                        Ok(IrValue::Object(Box::new(IrObject::Var(IrValueVar(name, Vec::new()), None))))
+                    },
+                    Some(_) => message_error(pos, String::from("reference to part of object is unsupported for global variables")),
+                    None => Err(BackendError::Internal(String::from("ir_value_for_object: no element type of reference"))),
+                }
+            },
+            Object::Slice(idx, offs, len) => {
+                let ref_values = tree.ref_values().borrow();
+                let array_len = match ref_values.value(*idx) {
+                    Some(RefValue(_, Value::Object(_, object))) => {
+                        match &*object.borrow() {
+                            Object::Array(elem_values) => elem_values.len(),
+                            _ => return message_error(pos, String::from("slice of non-array object is unsupported for global variables")),
+                        }
+                    },
+                    _ => return message_error(pos, String::from("slice of non-array object is unsupported for global variables")),
+                };
+                drop(ref_values);
+                match (args.first(), offs.as_slice()) {
+                    (Some(arg), [off]) => {
+                        let array_type_value = Rc::new(TypeValue::Type(UniqFlag::None, TypeValueName::Array(Some(array_len)), vec![arg.clone()]));
+                        let name = self.lower_ref_value(*idx, &array_type_value, pos, tree, ir_tree)?;
+                        let ptr_value = IrValue::Object(Box::new(IrObject::Var(IrValueVar(name, vec![IrValueOp::Index(None, *off as u64)]), None)));
+                        Ok(IrValue::Object(Box::new(IrObject::Struct(vec![ptr_value, IrValue::SizeT(*len as u64, None)], Vec::new(), None))))
+                    },
+                    (Some(_), _) => message_error(pos, String::from("slice of part of object is unsupported for global variables")),
+                    (None, _) => Err(BackendError::Internal(String::from("ir_value_for_object: no element type of slice"))),
+                }
+            },
+            _ => message_error(pos, String::from("value of global variable is unsupported")),
+        }
+    }
+
+    fn lower_ref_value(&self, idx: usize, type_value: &Rc<TypeValue>, pos: &Pos, tree: &Tree, ir_tree: &mut IrTree) -> BackendResult<String>
+    {
+        let name = mangle_ref_value_name(idx);
+        if ir_tree.var(&name).is_none() {
+            let ref_value = match tree.ref_values().borrow().value(idx) {
+                Some(ref_value) => ref_value.clone(),
+                None => return Err(BackendError::Internal(String::from("lower_ref_value: no reference value"))),
+            };
+            let modifier = match ref_value.0 {
+                RefValueFlag::None => VarModifier::Constant,
+                RefValueFlag::Global => VarModifier::Global,
+            };
+            self.lower_global_var(name.clone(), modifier, &ref_value.1, type_value, pos, tree, ir_tree)?;
+        }
+        Ok(name)
+    }
+
+    fn lower_fun(&self, name: String, ident: &String, modifier: IrFunModifier, max_depth: Option<usize>, args: &[(String, LocalType, Pos)], body: &Expr, ret_local_type: LocalType, local_types: &LocalTypes, type_values: Vec<Rc<TypeValue>>, typ: &Type, tree: &Tree, ir_tree: &mut IrTree, insts: &mut Vec<Inst>) -> BackendResult<()>
+    {
+        let frame_struct_name = mangle_frame_struct_name(ident, type_values.as_slice(), typ)?;
+        let mut ctx = FunContext::new(local_types, type_values, typ, 0, insts);
+        let mut arg_types: Vec<Box<IrType>> = Vec::new();
+        let mut arg_vars: Vec<(String, Option<usize>, Option<Box<IrType>>)> = Vec::new();
+        for (ident, arg_local_type, pos) in args {
+            let arg_type = self.ir_type_for_local_type(*arg_local_type, pos, tree, &ctx, ir_tree)?;
+            if *arg_type == IrType::Void {
+                arg_vars.push((ident.clone(), None, None));
+                continue;
+            }
+            let struct_field_types = match (&modifier, &*arg_type) {
+                (IrFunModifier::Kernel, IrType::Struct(struct_ident)) if struct_ident.starts_with("_VS") => {
+                    match ir_tree.struct1(struct_ident) {
+                        Some(struct1) => {
+                            match &*struct1.borrow() {
+                                IrStruct::Struct(field_types) if field_types.len() == 2 && *field_types[1] == IrType::SizeT => {
+                                    match &*field_types[0] {
+                                        IrType::Ptr(_, _, _) => Some(field_types[0].clone()),
+                                        _ => None,
+                                    }
+                                },
+                                _ => None,
+                            }
+                        },
+                        None => None,
+                    }
+                },
+                _ => None,
+            };
+            match struct_field_types {
+                Some(ptr_type) => {
+                    arg_vars.push((ident.clone(), Some(arg_types.len()), Some(arg_type.clone())));
+                    arg_types.push(ptr_type);
+                    arg_types.push(Box::new(IrType::Ulong));
+                },
+                None => {
+                    arg_vars.push((ident.clone(), Some(arg_types.len()), None));
+                    arg_types.push(arg_type);
+                },
+            }
+        }
+        ctx.first_local_var_index = arg_types.len();
+        let tail_args: Vec<Option<(usize, Box<IrType>)>> = arg_vars.iter().map(|(_, arg_idx, _)| arg_idx.map(|arg_idx| (arg_idx, arg_types[arg_idx].clone()))).collect();
+        let mut arg_var_env: Environment<()> = Environment::new();
+        arg_var_env.push_new_vars();
+        for (arg_ident, _, _) in &arg_vars {
+            arg_var_env.add_var(arg_ident.clone(), ());
+        }
+        for (ident, arg_idx, slice_type) in arg_vars {
+            match (arg_idx, slice_type) {
+                (Some(arg_idx), Some(slice_type)) => {
+                    let idx = ctx.add_local_var(IrLocalVarModifier::None, slice_type.clone());
+                    let len_value = IrValue::Object(Box::new(IrObject::Var(IrArgVar::Local(arg_idx + 1, Vec::new()), Some(Box::new(IrType::SizeT)))));
+                    let struct_value = IrValue::Object(Box::new(IrObject::Struct(vec![local_var_value(arg_idx), len_value], Vec::new(), Some(slice_type))));
+                    ctx.add_instr(IrInstr::Assign(Box::new(IrInstrVar::Local(idx, Vec::new())), IrOp::Load(struct_value)));
+                    ctx.var_env.add_var(ident, Some(idx));
+                },
+                (arg_idx, _) => {
+                    ctx.var_env.add_var(ident, arg_idx);
+                },
+            }
+        }
+        let ret_type_value = ctx.type_value(ret_local_type)?;
+        let ret_type = self.ir_type_for_type_value(&ret_type_value, expr_pos(body), tree, ir_tree)?;
+        let mut rec_apps: BTreeSet<usize> = BTreeSet::new();
+        if max_depth.is_some() {
+            self.add_rec_apps(body, ident, false, &mut arg_var_env, &mut rec_apps)?;
+        }
+        let mut panic_flag = IrPanicFlag::None;
+        if max_depth.is_some() && has_non_tail_rec_app(body, &rec_apps) {
+            let tail_fun = TailFun {
+                name: name.clone(),
+                args: tail_args,
+                ret_type: ret_type.clone(),
+                rec: None,
+            };
+            self.lower_rec_fun_body(tail_fun, ident, max_depth.unwrap_or(1), rec_apps, arg_types.as_slice(), &frame_struct_name, body, tree, &mut ctx, ir_tree)?;
+            panic_flag = IrPanicFlag::Panic;
+        } else if modifier != IrFunModifier::Kernel && self.has_tail_app(body, ident, &mut arg_var_env) {
+            ctx.tail_fun = Some(TailFun {
+                    name: name.clone(),
+                    args: tail_args,
+                    ret_type: ret_type.clone(),
+                    rec: None,
+            });
+            ctx.push_block();
+            match self.lower_tail_expr(body, tree, &mut ctx, ir_tree) {
+                Ok(()) => (),
+                Err(err) => {
+                    ctx.pop_block()?;
+                    return Err(err);
+                },
+            }
+            let loop_block = ctx.pop_block()?;
+            ctx.add_instr(IrInstr::Loop(Box::new(loop_block)));
+        } else {
+            let value = self.lower_expr(body, tree, &mut ctx, ir_tree)?;
+            match value {
+                Some(value) if *ret_type != IrType::Void => ctx.add_instr(IrInstr::Return(Some(IrOp::Load(value)))),
+                _ => ctx.add_instr(IrInstr::Return(None)),
+            }
+        }
+        let block = ctx.into_block()?;
+        let fun = IrFun::Fun(modifier, arg_types, ret_type, Box::new(block), IrPrivateHeapFlag::None, IrLocalHeapFlag::None, IrGlobalHeapFlag::None, panic_flag);
+        let ir_var = Rc::new(RefCell::new(IrVar::Fun(Box::new(fun))));
+        ir_tree.add_var(name.clone(), ir_var.clone());
+        ir_tree.add_def(IrDef::Var(name, ir_var));
+        Ok(())
+    }
+
+    fn lower_rec_fun_body(&self, mut tail_fun: TailFun, ident: &str, max_depth: usize, apps: BTreeSet<usize>, arg_types: &[Box<IrType>], frame_struct_name: &String, body: &Expr, tree: &Tree, ctx: &mut FunContext, ir_tree: &mut IrTree) -> BackendResult<()>
+    {
+        let state_idx = ctx.add_local_var(IrLocalVarModifier::None, Box::new(IrType::Uint));
+        let sp_idx = ctx.add_local_var(IrLocalVarModifier::None, Box::new(IrType::Uint));
+        let stack_idx = ctx.add_local_var(IrLocalVarModifier::None, Box::new(IrType::Array(Box::new(IrType::Struct(frame_struct_name.clone())), max_depth)));
+        let ret_idx = if *tail_fun.ret_type != IrType::Void {
+            Some(ctx.add_local_var(IrLocalVarModifier::None, tail_fun.ret_type.clone()))
+        } else {
+            None
+        };
+        tail_fun.rec = Some(RecFun {
+                ident: String::from(ident),
+                max_depth,
+                apps,
+                state_idx,
+                sp_idx,
+                stack_idx,
+                ret_idx,
+                states: vec![None, None],
+                cur_states: Vec::new(),
+        });
+        ctx.tail_fun = Some(tail_fun);
+        ctx.add_instr(IrInstr::Assign(Box::new(IrInstrVar::Local(state_idx, Vec::new())), IrOp::Load(IrValue::Uint(ENTRY_STATE, None))));
+        ctx.add_instr(IrInstr::Assign(Box::new(IrInstrVar::Local(sp_idx, Vec::new())), IrOp::Load(IrValue::Uint(0, None))));
+        ctx.begin_state(ENTRY_STATE)?;
+        self.lower_tail_expr(body, tree, ctx, ir_tree)?;
+        ctx.end_state()?;
+        ctx.begin_state(RETURN_STATE)?;
+        let mut ret_block = IrBlock::new();
+        ret_block.add_instr(IrInstr::Return(ret_idx.map(|ret_idx| IrOp::Load(local_var_value(ret_idx)))));
+        ctx.add_instr(IrInstr::If(IrOp::Eq(local_var_value(sp_idx), IrValue::Uint(0, None)), Box::new(ret_block), Box::new(IrBlock::new())));
+        ctx.add_instr(IrInstr::Assign(Box::new(IrInstrVar::Local(sp_idx, Vec::new())), IrOp::Sub(local_var_value(sp_idx), IrValue::Uint(1, None))));
+        let frame_state_value = IrValue::Object(Box::new(IrObject::Var(IrArgVar::Local(stack_idx, vec![IrArgOp::LocalIndex(None, sp_idx), IrArgOp::Dot(None, 0)]), None)));
+        ctx.add_instr(IrInstr::Assign(Box::new(IrInstrVar::Local(state_idx, Vec::new())), IrOp::Load(frame_state_value)));
+        ctx.add_instr(IrInstr::Continue);
+        ctx.end_state()?;
+        let rec = match ctx.tail_fun.take() {
+            Some(TailFun { rec: Some(rec), .. }) => rec,
+            _ => return Err(BackendError::Internal(String::from("lower_rec_fun_body: no recursive function"))),
+        };
+        let mut field_types: Vec<Box<IrType>> = vec![Box::new(IrType::Uint)];
+        field_types.extend(arg_types.iter().cloned());
+        for (i, IrLocalVarPair(_, local_var_type)) in ctx.local_var_pairs.iter().enumerate() {
+            if !rec.is_control_var(ctx.first_local_var_index + i) {
+                field_types.push(local_var_type.clone());
+            }
+        }
+        self.add_struct(frame_struct_name, field_types, ir_tree);
+        let mut cases: Vec<IrCase> = Vec::new();
+        for (state, state_block) in rec.states.into_iter().enumerate() {
+            match state_block {
+                Some(state_block) => cases.push(IrCase::Case(IrCaseValue::Uint(state as u32, None), Box::new(state_block))),
+                None => return Err(BackendError::Internal(String::from("lower_rec_fun_body: no state block"))),
+            }
+        }
+        let mut loop_block = IrBlock::new();
+        loop_block.add_instr(IrInstr::Switch(IrOp::Load(local_var_value(state_idx)), cases));
+        ctx.add_instr(IrInstr::Loop(Box::new(loop_block)));
+        Ok(())
+    }
+
+    fn value_to_arg_var(&self, value: IrValue<IrArgVar>, typ: &IrType, ctx: &mut FunContext) -> IrArgVar
+    {
+        if let IrValue::Object(object) = &value {
+            match &**object {
+                IrObject::Var(var, None) if is_place_arg_var(var) => return var.clone(),
+                _ => (),
+            }
+        }
+        let idx = ctx.add_local_var(IrLocalVarModifier::None, Box::new(typ.clone()));
+        ctx.add_instr(IrInstr::Assign(Box::new(IrInstrVar::Local(idx, Vec::new())), IrOp::Load(value)));
+        IrArgVar::Local(idx, Vec::new())
+    }
+
+    fn value_to_index_op(&self, value: IrValue<IrArgVar>, ctx: &mut FunContext) -> IrArgOp
+    {
+        match &value {
+            IrValue::SizeT(n, None) => return IrArgOp::Index(None, *n),
+            IrValue::Object(object) => {
+                match &**object {
+                    IrObject::Var(IrArgVar::Local(idx, ops), None) if ops.is_empty() => return IrArgOp::LocalIndex(None, *idx),
+                    _ => (),
+                }
+            },
+            _ => (),
+        }
+        let idx = ctx.add_local_var(IrLocalVarModifier::None, Box::new(IrType::SizeT));
+        ctx.add_instr(IrInstr::Assign(Box::new(IrInstrVar::Local(idx, Vec::new())), IrOp::Load(value)));
+        IrArgOp::LocalIndex(None, idx)
+    }
+
+    fn assign_to_new_local_var(&self, op: IrOp, typ: Box<IrType>, ctx: &mut FunContext) -> Option<IrValue<IrArgVar>>
+    {
+        if *typ == IrType::Void {
+            ctx.add_instr(IrInstr::Op(op));
+            None
+        } else {
+            let idx = ctx.add_local_var(IrLocalVarModifier::None, typ);
+            ctx.add_instr(IrInstr::Assign(Box::new(IrInstrVar::Local(idx, Vec::new())), op));
+            Some(local_var_value(idx))
+        }
+    }
+
+    fn lower_exprs(&self, exprs: &[Box<Expr>], tree: &Tree, ctx: &mut FunContext, ir_tree: &mut IrTree) -> BackendResult<Vec<Option<IrValue<IrArgVar>>>>
+    {
+        let mut values: Vec<Option<IrValue<IrArgVar>>> = Vec::new();
+        for expr in exprs {
+            values.push(self.lower_expr(expr, tree, ctx, ir_tree)?);
+        }
+        Ok(values)
+    }
+
+    fn lower_expr(&self, expr: &Expr, tree: &Tree, ctx: &mut FunContext, ir_tree: &mut IrTree) -> BackendResult<Option<IrValue<IrArgVar>>>
+    {
+        if let Some(value) = ctx.hoisted_values.remove(&expr_key(expr)) {
+            return Ok(value);
+        }
+        match expr {
+            Expr::Literal(literal, Some(local_type), pos) => self.lower_literal(literal, *local_type, pos, tree, ctx, ir_tree),
+            Expr::Lambda(args, _, body, Some(ret_local_type), Some(local_type), _, _, pos) => self.lower_lambda(args.as_slice(), body, *ret_local_type, *local_type, pos, tree, ctx, ir_tree),
+            Expr::Var(ident, Some(local_type), pos) => {
+                let typ = self.ir_type_for_local_type(*local_type, pos, tree, ctx, ir_tree)?;
+                match self.var_target(ident, *local_type, pos, tree, ctx)? {
+                    VarTarget::Local(idx) => Ok(idx.map(|idx| ctx.var_value(idx))),
+                    VarTarget::Builtin(_, _) if *typ == IrType::Void => Ok(None),
+                    VarTarget::Builtin(ident2, _) => Ok(Some(IrValue::Object(Box::new(IrObject::BuiltinVar(ident2, None, None))))),
+                    VarTarget::Var(ident2, type_name) => {
+                        let name = self.var_name(&ident2, &type_name, *local_type, pos, tree, ctx)?;
+                        if *typ == IrType::Void {
+                            Ok(None)
+                        } else {
+                            Ok(Some(IrValue::Object(Box::new(IrObject::Var(IrArgVar::Global(name, Vec::new()), None)))))
+                        }
+                    },
+                    VarTarget::Fun(ident2, type_name) => {
+                        let name = self.fun_name(&ident2, &type_name, *local_type, pos, tree, ctx)?;
+                        let type_value = type_value_without_uniq_flags(&ctx.type_value(*local_type)?);
+                        let caller_name = self.add_caller(&type_value, pos, tree, ir_tree)?;
+                        let idx = self.add_caller_fun(&caller_name, IrCallerFun::Fun(name), ir_tree)?;
+                        let idx_value = IrValue::Object(Box::new(IrObject::CallerFunIndex(caller_name, idx, None)));
+                        Ok(Some(IrValue::Object(Box::new(IrObject::Struct(vec![idx_value], Vec::new(), Some(typ))))))
+                    },
+                    VarTarget::Con(ident2) => message_error(pos, format!("constructor {} is unsupported", ident2)),
+                }
+            },
+            Expr::NamedFieldConApp(ident, expr_named_field_pairs, _, Some(local_type), pos) => self.lower_named_field_con_app(ident, expr_named_field_pairs.as_slice(), *local_type, pos, tree, ctx, ir_tree),
+            Expr::PrintfApp(exprs, Some(local_type), pos) => self.lower_printf_app(exprs.as_slice(), *local_type, pos, tree, ctx, ir_tree),
+            Expr::App(fun_expr, arg_exprs, Some(local_type), pos) => self.lower_app(fun_expr, arg_exprs.as_slice(), *local_type, pos, tree, ctx, ir_tree),
+            Expr::GetField(expr2, fields, Some(local_type), pos) => {
+                let typ = self.ir_type_for_local_type(*local_type, pos, tree, ctx, ir_tree)?;
+                let local_type2 = expr_local_type(expr2)?;
+                let type2 = self.ir_type_for_local_type(local_type2, pos, tree, ctx, ir_tree)?;
+                match self.lower_expr(expr2, tree, ctx, ir_tree)? {
+                    Some(value) => {
+                        if *typ == IrType::Void {
+                            return Ok(None);
+                        }
+                        let var = self.value_to_arg_var(value, &type2, ctx);
+                        let ops = self.field_ops(local_type2, fields.as_slice(), pos, tree, ctx)?;
+                        Ok(Some(IrValue::Object(Box::new(IrObject::Var(arg_var_with_ops(&var, ops.as_slice())?, None)))))
+                    },
+                    None => Err(BackendError::Internal(String::from("lower_expr: no value for field getting"))),
+                }
+            },
+            Expr::Get2Field(expr2, fields, Some(local_type), pos) => {
+                let typ = self.ir_type_for_local_type(*local_type, pos, tree, ctx, ir_tree)?;
+                let local_type2 = expr_local_type(expr2)?;
+                let type2 = self.ir_type_for_local_type(local_type2, pos, tree, ctx, ir_tree)?;
+                match self.lower_expr(expr2, tree, ctx, ir_tree)? {
+                    Some(value) => {
+                        let var = self.value_to_arg_var(value, &type2, ctx);
+                        let ops = self.field_ops(local_type2, fields.as_slice(), pos, tree, ctx)?;
+                        let field_value = IrValue::Object(Box::new(IrObject::Var(arg_var_with_ops(&var, ops.as_slice())?, None)));
+                        let var_value = IrValue::Object(Box::new(IrObject::Var(var, None)));
+                        Ok(Some(IrValue::Object(Box::new(IrObject::Struct(vec![field_value, var_value], Vec::new(), Some(typ))))))
+                    },
+                    None => Err(BackendError::Internal(String::from("lower_expr: no value for field getting"))),
+                }
+            },
+            Expr::SetField(expr2, fields, expr3, Some(local_type), pos) => {
+                let local_type2 = expr_local_type(expr2)?;
+                let type2 = self.ir_type_for_local_type(local_type2, pos, tree, ctx, ir_tree)?;
+                match self.lower_expr(expr2, tree, ctx, ir_tree)? {
+                    Some(value) => {
+                        let idx = ctx.add_local_var(IrLocalVarModifier::None, type2);
+                        ctx.add_instr(IrInstr::Assign(Box::new(IrInstrVar::Local(idx, Vec::new())), IrOp::Load(value)));
+                        let ops = self.field_ops(local_type2, fields.as_slice(), pos, tree, ctx)?;
+                        if let Some(value2) = self.lower_expr(expr3, tree, ctx, ir_tree)? {
+                            ctx.add_instr(IrInstr::Assign(Box::new(IrInstrVar::Local(idx, ops)), IrOp::Load(value2)));
+                        }
+                        let typ = self.ir_type_for_local_type(*local_type, pos, tree, ctx, ir_tree)?;
+                        if *typ == IrType::Void {
+                            Ok(None)
+                        } else {
+                            Ok(Some(local_var_value(idx)))
+                        }
+                    },
+                    None => Err(BackendError::Internal(String::from("lower_expr: no value for field setting"))),
+                }
+            },
+            Expr::UpdateField(_, _, _, _, pos) => message_error(pos, String::from("operator <-> is unsupported")),
+            Expr::UpdateGet2Field(_, _, _, _, pos) => message_error(pos, String::from("operator <-> -> is unsupported")),
+            Expr::Uniq(expr2, _, _) => self.lower_expr(expr2, tree, ctx, ir_tree),
+            Expr::Shared(expr2, _, _) => self.lower_expr(expr2, tree, ctx, ir_tree),
+            Expr::Typed(expr2, _, _, _) => self.lower_expr(expr2, tree, ctx, ir_tree),
+            Expr::As(expr2, _, Some(local_type), pos) => {
+                let typ = self.ir_type_for_local_type(*local_type, pos, tree, ctx, ir_tree)?;
+                let type_value = ctx.type_value(*local_type)?;
+                match self.lower_expr(expr2, tree, ctx, ir_tree)? {
+                    Some(value) => {
+                        if type_value_ident(&type_value) == Some("Bool") {
+                            Ok(self.assign_to_new_local_var(IrOp::Ne(value, IrValue::Int(0, None)), typ, ctx))
+                        } else if vector_elem_count(&typ).is_some() {
+                            Ok(self.assign_to_new_local_var(IrOp::CallBuiltinFun(String::from("convert"), Some(typ.clone()), vec![value]), typ, ctx))
+                        } else {
+                            Ok(Some(cast_value(value, typ)))
+                        }
+                    },
+                    None => Err(BackendError::Internal(String::from("lower_expr: no value for casting"))),
+                }
+            },
+            Expr::Let(binds, expr2, _, _) => {
+                ctx.var_env.push_new_vars();
+                for bind in binds {
+                    match bind {
+                        Bind(pattern, expr3) => {
+                            let value = self.lower_expr(expr3, tree, ctx, ir_tree)?;
+                            self.bind_pattern(pattern, value, tree, ctx, ir_tree)?;
+                        },
+                    }
+                }
+                let value = self.lower_expr(expr2, tree, ctx, ir_tree);
+                ctx.var_env.pop_vars();
+                value
+            },
+            Expr::If(cond_expr, then_expr, else_expr, Some(local_type), pos) => {
+                let typ = self.ir_type_for_local_type(*local_type, pos, tree, ctx, ir_tree)?;
+                let cond_value = match self.lower_expr(cond_expr, tree, ctx, ir_tree)? {
+                    Some(tmp_cond_value) => tmp_cond_value,
+                    None => return Err(BackendError::Internal(String::from("lower_expr: no value of condition"))),
+                };
+                let idx = if *typ != IrType::Void {
+                    Some(ctx.add_local_var(IrLocalVarModifier::None, typ))
+                } else {
+                    None
+                };
+                let then_block = self.lower_branch(then_expr, BranchTarget::Var(idx), tree, ctx, ir_tree)?;
+                let else_block = self.lower_branch(else_expr, BranchTarget::Var(idx), tree, ctx, ir_tree)?;
+                ctx.add_instr(IrInstr::If(IrOp::Load(cond_value), Box::new(then_block), Box::new(else_block)));
+                Ok(idx.map(local_var_value))
+            },
+            Expr::Match(expr2, cases, Some(local_type), pos) => {
+                let typ = self.ir_type_for_local_type(*local_type, pos, tree, ctx, ir_tree)?;
+                let idx = if *typ != IrType::Void {
+                    Some(ctx.add_local_var(IrLocalVarModifier::None, typ))
+                } else {
+                    None
+                };
+                self.lower_match(expr2, cases.as_slice(), BranchTarget::Var(idx), pos, tree, ctx, ir_tree)?;
+                Ok(idx.map(local_var_value))
+            },
+            _ => Err(BackendError::Internal(String::from("lower_expr: no local type"))),
+        }
+    }
+
+    fn lower_branch(&self, expr: &Expr, target: BranchTarget, tree: &Tree, ctx: &mut FunContext, ir_tree: &mut IrTree) -> BackendResult<IrBlock>
+    {
+        ctx.push_block();
+        match self.lower_branch_expr(expr, target, tree, ctx, ir_tree) {
+            Ok(()) => ctx.pop_block(),
+            Err(err) => {
+                ctx.pop_block()?;
+                Err(err)
+            },
+        }
+    }
+
+    fn lower_branch_expr(&self, expr: &Expr, target: BranchTarget, tree: &Tree, ctx: &mut FunContext, ir_tree: &mut IrTree) -> BackendResult<()>
+    {
+        match target {
+            BranchTarget::Var(idx) => {
+                if let (Some(idx), Some(value)) = (idx, self.lower_expr(expr, tree, ctx, ir_tree)?) {
+                    ctx.add_instr(IrInstr::Assign(Box::new(IrInstrVar::Local(idx, Vec::new())), IrOp::Load(value)));
+                }
+                Ok(())
+            },
+            BranchTarget::Join(idx, join_state) => {
+                if ctx.has_rec_app(expr) {
+                    let state = ctx.new_state()?;
+                    ctx.add_state_jump(state)?;
+                    ctx.begin_state(state)?;
+                    if let (Some(idx), Some(value)) = (idx, self.lower_rec_expr(expr, tree, ctx, ir_tree)?) {
+                        ctx.add_instr(IrInstr::Assign(Box::new(IrInstrVar::Local(idx, Vec::new())), IrOp::Load(value)));
+                    }
+                    ctx.add_state_jump(join_state)?;
+                    ctx.end_state()
+                } else {
+                    self.lower_branch_expr(expr, BranchTarget::Var(idx), tree, ctx, ir_tree)
+                }
+            },
+            BranchTarget::Tail => {
+                if ctx.has_non_tail_rec_app(expr) {
+                    let state = ctx.new_state()?;
+                    ctx.add_state_jump(state)?;
+                    ctx.begin_state(state)?;
+                    self.lower_tail_expr(expr, tree, ctx, ir_tree)?;
+                    ctx.end_state()
+                } else {
+                    self.lower_tail_expr(expr, tree, ctx, ir_tree)
+                }
+            },
+        }
+    }
+
+    fn has_tail_app(&self, expr: &Expr, ident: &String, var_env: &mut Environment<()>) -> bool
+    {
+        match expr {
+            Expr::App(fun_expr, _, _, _) => {
+                match &**fun_expr {
+                    Expr::Var(ident2, _, _) => ident2 == ident && var_env.var(ident2).is_none(),
+                    _ => false,
+                }
+            },
+            Expr::Typed(expr2, _, _, _) => self.has_tail_app(expr2, ident, var_env),
+            Expr::If(_, expr2, expr3, _, _) => self.has_tail_app(expr2, ident, var_env) || self.has_tail_app(expr3, ident, var_env),
+            Expr::Let(binds, expr2, _, _) => {
+                var_env.push_new_vars();
+                for bind in binds {
+                    match bind {
+                        Bind(pattern, _) => self.add_vars_for_pattern(pattern, var_env),
+                    }
+                }
+                let res = self.has_tail_app(expr2, ident, var_env);
+                var_env.pop_vars();
+                res
+            },
+            Expr::Match(_, cases, _, _) => {
+                cases.iter().any(|case| {
+                        match case {
+                            Case(pattern, expr2) => {
+                                var_env.push_new_vars();
+                                self.add_vars_for_pattern(pattern, var_env);
+                                let res = self.has_tail_app(expr2, ident, var_env);
+                                var_env.pop_vars();
+                                res
+                            },
+                        }
+                })
+            },
+            _ => false,
+        }
+    }
+
+    fn lower_tail_expr(&self, expr: &Expr, tree: &Tree, ctx: &mut FunContext, ir_tree: &mut IrTree) -> BackendResult<()>
+    {
+        match expr {
+            Expr::Typed(expr2, _, _, _) => self.lower_tail_expr(expr2, tree, ctx, ir_tree),
+            Expr::Let(binds, expr2, _, _) => {
+                ctx.var_env.push_new_vars();
+                for bind in binds {
+                    match bind {
+                        Bind(pattern, expr3) => {
+                            let value = self.lower_rec_expr(expr3, tree, ctx, ir_tree)?;
+                            self.bind_pattern(pattern, value, tree, ctx, ir_tree)?;
+                        },
+                    }
+                }
+                let res = self.lower_tail_expr(expr2, tree, ctx, ir_tree);
+                ctx.var_env.pop_vars();
+                res
+            },
+            Expr::If(cond_expr, then_expr, else_expr, _, _) => {
+                let cond_value = match self.lower_rec_expr(cond_expr, tree, ctx, ir_tree)? {
+                    Some(tmp_cond_value) => tmp_cond_value,
+                    None => return Err(BackendError::Internal(String::from("lower_tail_expr: no value of condition"))),
+                };
+                if ctx.has_non_tail_rec_app(then_expr) || ctx.has_non_tail_rec_app(else_expr) {
+                    let else_state = ctx.new_state()?;
+                    ctx.push_block();
+                    ctx.add_state_jump(else_state)?;
+                    let else_block = ctx.pop_block()?;
+                    ctx.add_instr(IrInstr::If(IrOp::Load(cond_value), Box::new(IrBlock::new()), Box::new(else_block)));
+                    self.lower_tail_expr(then_expr, tree, ctx, ir_tree)?;
+                    ctx.end_state()?;
+                    ctx.begin_state(else_state)?;
+                    return self.lower_tail_expr(else_expr, tree, ctx, ir_tree);
+                }
+                let then_block = self.lower_branch(then_expr, BranchTarget::Tail, tree, ctx, ir_tree)?;
+                let else_block = self.lower_branch(else_expr, BranchTarget::Tail, tree, ctx, ir_tree)?;
+                ctx.add_instr(IrInstr::If(IrOp::Load(cond_value), Box::new(then_block), Box::new(else_block)));
+                Ok(())
+            },
+            Expr::Match(expr2, cases, _, pos) => self.lower_match(expr2, cases.as_slice(), BranchTarget::Tail, pos, tree, ctx, ir_tree),
+            Expr::App(fun_expr, arg_exprs, _, _) => {
+                if let Expr::Var(ident, Some(fun_local_type), fun_pos) = &**fun_expr {
+                    if let VarTarget::Fun(ident2, type_name) = self.var_target(ident, *fun_local_type, fun_pos, tree, ctx)? {
+                        let name = self.fun_name(&ident2, &type_name, *fun_local_type, fun_pos, tree, ctx)?;
+                        let is_tail_app = match &ctx.tail_fun {
+                            Some(tail_fun) => tail_fun.name == name,
+                            None => false,
+                        };
+                        if is_tail_app {
+                            return self.lower_tail_app(arg_exprs.as_slice(), tree, ctx, ir_tree);
+                        }
+                    }
+                }
+                let value = self.lower_rec_expr(expr, tree, ctx, ir_tree)?;
+                self.add_tail_return(value, ctx)
+            },
+            _ => {
+                let value = self.lower_rec_expr(expr, tree, ctx, ir_tree)?;
+                self.add_tail_return(value, ctx)
+            },
+        }
+    }
+
+    fn add_tail_return(&self, value: Option<IrValue<IrArgVar>>, ctx: &mut FunContext) -> BackendResult<()>
+    {
+        if let Some(rec) = ctx.rec_fun() {
+            if let (Some(ret_idx), Some(value)) = (rec.ret_idx, value) {
+                ctx.add_instr(IrInstr::Assign(Box::new(IrInstrVar::Local(ret_idx, Vec::new())), IrOp::Load(value)));
+            }
+            return ctx.add_state_jump(RETURN_STATE);
+        }
+        let is_void = match &ctx.tail_fun {
+            Some(tail_fun) => *tail_fun.ret_type == IrType::Void,
+            None => return Err(BackendError::Internal(String::from("add_tail_return: no tail function"))),
+        };
+        match value {
+            Some(value) if !is_void => ctx.add_instr(IrInstr::Return(Some(IrOp::Load(value)))),
+            _ => ctx.add_instr(IrInstr::Return(None)),
+        }
+        Ok(())
+    }
+
+    fn lower_tail_app(&self, arg_exprs: &[Box<Expr>], tree: &Tree, ctx: &mut FunContext, ir_tree: &mut IrTree) -> BackendResult<()>
+    {
+        let args = match &ctx.tail_fun {
+            Some(tail_fun) => tail_fun.args.clone(),
+            None => return Err(BackendError::Internal(String::from("lower_tail_app: no tail function"))),
+        };
+        let arg_values = self.lower_rec_exprs(arg_exprs, tree, ctx, ir_tree)?;
+        self.assign_tail_args(arg_values, args, ctx)?;
+        if ctx.rec_fun().is_some() {
+            ctx.add_state_jump(ENTRY_STATE)
+        } else {
+            ctx.add_instr(IrInstr::Continue);
+            Ok(())
+        }
+    }
+
+    fn assign_tail_args(&self, arg_values: Vec<Option<IrValue<IrArgVar>>>, args: Vec<Option<(usize, Box<IrType>)>>, ctx: &mut FunContext) -> BackendResult<()>
+    {
+        if arg_values.len() != args.len() {
+            return Err(BackendError::Internal(String::from("assign_tail_args: number of arguments isn't equal to number of parameters")));
+        }
+        let mut assigns: Vec<(usize, IrValue<IrArgVar>)> = Vec::new();
+        for (arg_value, arg) in arg_values.into_iter().zip(args) {
+            if let (Some(arg_value), Some((arg_idx, arg_type))) = (arg_value, arg) {
+                let is_safe = match &arg_value {
+                    IrValue::Object(object) => {
+                        match &**object {
+                            IrObject::Var(IrArgVar::Local(idx, ops), None) if *idx == arg_idx && ops.is_empty() => continue,
+                            IrObject::Var(IrArgVar::Local(idx, _), _) => *idx >= ctx.first_local_var_index,
+                            IrObject::Var(IrArgVar::Global(_, _), _) => true,
+                            IrObject::String(_) | IrObject::BuiltinVar(_, _, _) | IrObject::Sizeof(_, _) | IrObject::CallerFunIndex(_, _, _) => true,
+                            _ => false,
+                        }
+                    },
+                    _ => true,
+                };
+                if is_safe {
+                    assigns.push((arg_idx, arg_value));
+                } else {
+                    let idx = ctx.add_local_var(IrLocalVarModifier::None, arg_type);
+                    ctx.add_instr(IrInstr::Assign(Box::new(IrInstrVar::Local(idx, Vec::new())), IrOp::Load(arg_value)));
+                    assigns.push((arg_idx, local_var_value(idx)));
+                }
+            }
+        }
+        for (arg_idx, arg_value) in assigns {
+            ctx.add_instr(IrInstr::Assign(Box::new(IrInstrVar::Local(arg_idx, Vec::new())), IrOp::Load(arg_value)));
+        }
+        Ok(())
+    }
+
+    fn add_rec_apps(&self, expr: &Expr, ident: &String, is_lambda: bool, var_env: &mut Environment<()>, apps: &mut BTreeSet<usize>) -> BackendResult<()>
+    {
+        match expr {
+            Expr::Lambda(args, _, body, _, _, _, _, _) => {
+                var_env.push_new_vars();
+                for arg in args {
+                    match arg {
+                        LambdaArg(ident2, _, _, _) => {
+                            var_env.add_var(ident2.clone(), ());
+                        },
+                    }
+                }
+                let res = self.add_rec_apps(body, ident, true, var_env, apps);
+                var_env.pop_vars();
+                res
+            },
+            Expr::Var(ident2, _, pos) if ident2 == ident && var_env.var(ident2).is_none() => message_error(pos, format!("bounded recursive function {} can't be used as value", ident)),
+            Expr::App(fun_expr, arg_exprs, _, pos) => {
+                match &**fun_expr {
+                    Expr::Var(ident2, _, _) if ident2 == ident && var_env.var(ident2).is_none() => {
+                        if is_lambda {
+                            return message_error(pos, format!("bounded recursive function {} can't be called in lambda", ident));
+                        }
+                        apps.insert(expr_key(expr));
+                    },
+                    _ => self.add_rec_apps(fun_expr, ident, is_lambda, var_env, apps)?,
+                }
+                for arg_expr in arg_exprs {
+                    self.add_rec_apps(arg_expr, ident, is_lambda, var_env, apps)?;
+                }
+                Ok(())
+            },
+            Expr::Let(binds, expr2, _, _) => {
+                var_env.push_new_vars();
+                for bind in binds {
+                    match bind {
+                        Bind(pattern, expr3) => {
+                            match self.add_rec_apps(expr3, ident, is_lambda, var_env, apps) {
+                                Ok(()) => (),
+                                Err(err) => {
+                                    var_env.pop_vars();
+                                    return Err(err);
+                                },
+                            }
+                            self.add_vars_for_pattern(pattern, var_env);
+                        },
+                    }
+                }
+                let res = self.add_rec_apps(expr2, ident, is_lambda, var_env, apps);
+                var_env.pop_vars();
+                res
+            },
+            Expr::Match(expr2, cases, _, _) => {
+                self.add_rec_apps(expr2, ident, is_lambda, var_env, apps)?;
+                for case in cases {
+                    match case {
+                        Case(pattern, expr3) => {
+                            var_env.push_new_vars();
+                            self.add_vars_for_pattern(pattern, var_env);
+                            let res = self.add_rec_apps(expr3, ident, is_lambda, var_env, apps);
+                            var_env.pop_vars();
+                            res?;
+                        },
+                    }
+                }
+                Ok(())
+            },
+            _ => {
+                for expr2 in sub_exprs(expr) {
+                    self.add_rec_apps(expr2, ident, is_lambda, var_env, apps)?;
+                }
+                Ok(())
+            },
+        }
+    }
+
+    fn lower_rec_exprs(&self, exprs: &[Box<Expr>], tree: &Tree, ctx: &mut FunContext, ir_tree: &mut IrTree) -> BackendResult<Vec<Option<IrValue<IrArgVar>>>>
+    {
+        let mut values: Vec<Option<IrValue<IrArgVar>>> = Vec::new();
+        for expr in exprs {
+            values.push(self.lower_rec_expr(expr, tree, ctx, ir_tree)?);
+        }
+        Ok(values)
+    }
+
+    fn lower_rec_expr(&self, expr: &Expr, tree: &Tree, ctx: &mut FunContext, ir_tree: &mut IrTree) -> BackendResult<Option<IrValue<IrArgVar>>>
+    {
+        if !ctx.has_rec_app(expr) {
+            return self.lower_expr(expr, tree, ctx, ir_tree);
+        }
+        match expr {
+            Expr::App(_, arg_exprs, Some(local_type), pos) if ctx.is_rec_app(expr) => self.lower_rec_app(arg_exprs.as_slice(), *local_type, pos, tree, ctx, ir_tree),
+            Expr::Let(binds, expr2, _, _) => {
+                ctx.var_env.push_new_vars();
+                for bind in binds {
+                    match bind {
+                        Bind(pattern, expr3) => {
+                            let value = self.lower_rec_expr(expr3, tree, ctx, ir_tree)?;
+                            self.bind_pattern(pattern, value, tree, ctx, ir_tree)?;
+                        },
+                    }
+                }
+                let value = self.lower_rec_expr(expr2, tree, ctx, ir_tree);
+                ctx.var_env.pop_vars();
+                value
+            },
+            Expr::If(cond_expr, then_expr, else_expr, Some(local_type), pos) if ctx.has_rec_app(then_expr) || ctx.has_rec_app(else_expr) => {
+                let typ = self.ir_type_for_local_type(*local_type, pos, tree, ctx, ir_tree)?;
+                let cond_value = match self.lower_rec_expr(cond_expr, tree, ctx, ir_tree)? {
+                    Some(tmp_cond_value) => tmp_cond_value,
+                    None => return Err(BackendError::Internal(String::from("lower_rec_expr: no value of condition"))),
+                };
+                let idx = if *typ != IrType::Void {
+                    Some(ctx.add_local_var(IrLocalVarModifier::None, typ))
+                } else {
+                    None
+                };
+                let else_state = ctx.new_state()?;
+                let join_state = ctx.new_state()?;
+                ctx.push_block();
+                ctx.add_state_jump(else_state)?;
+                let else_block = ctx.pop_block()?;
+                ctx.add_instr(IrInstr::If(IrOp::Load(cond_value), Box::new(IrBlock::new()), Box::new(else_block)));
+                if let (Some(idx), Some(value)) = (idx, self.lower_rec_expr(then_expr, tree, ctx, ir_tree)?) {
+                    ctx.add_instr(IrInstr::Assign(Box::new(IrInstrVar::Local(idx, Vec::new())), IrOp::Load(value)));
+                }
+                ctx.add_state_jump(join_state)?;
+                ctx.end_state()?;
+                ctx.begin_state(else_state)?;
+                if let (Some(idx), Some(value)) = (idx, self.lower_rec_expr(else_expr, tree, ctx, ir_tree)?) {
+                    ctx.add_instr(IrInstr::Assign(Box::new(IrInstrVar::Local(idx, Vec::new())), IrOp::Load(value)));
+                }
+                ctx.add_state_jump(join_state)?;
+                ctx.end_state()?;
+                ctx.begin_state(join_state)?;
+                Ok(idx.map(local_var_value))
+            },
+            Expr::Match(expr2, cases, Some(local_type), pos) if cases.iter().any(|case| match case { Case(_, expr3) => ctx.has_rec_app(expr3), }) => {
+                let typ = self.ir_type_for_local_type(*local_type, pos, tree, ctx, ir_tree)?;
+                let idx = if *typ != IrType::Void {
+                    Some(ctx.add_local_var(IrLocalVarModifier::None, typ))
+                } else {
+                    None
+                };
+                let join_state = ctx.new_state()?;
+                self.lower_match(expr2, cases.as_slice(), BranchTarget::Join(idx, join_state), pos, tree, ctx, ir_tree)?;
+                ctx.add_state_jump(join_state)?;
+                ctx.end_state()?;
+                ctx.begin_state(join_state)?;
+                Ok(idx.map(local_var_value))
+            },
+            _ => {
+                let exprs2 = sub_exprs(expr);
+                if let Some(last_idx) = exprs2.iter().rposition(|expr2| ctx.has_rec_app(expr2)) {
+                    for expr2 in &exprs2[..=last_idx] {
+                        let value = self.lower_rec_expr(expr2, tree, ctx, ir_tree)?;
+                        ctx.hoisted_values.insert(expr_key(expr2), value);
+                    }
+                }
+                self.lower_expr(expr, tree, ctx, ir_tree)
+            },
+        }
+    }
+
+    fn lower_rec_app(&self, arg_exprs: &[Box<Expr>], local_type: LocalType, pos: &Pos, tree: &Tree, ctx: &mut FunContext, ir_tree: &mut IrTree) -> BackendResult<Option<IrValue<IrArgVar>>>
+    {
+        let typ = self.ir_type_for_local_type(local_type, pos, tree, ctx, ir_tree)?;
+        let arg_values = self.lower_rec_exprs(arg_exprs, tree, ctx, ir_tree)?;
+        let local_var_count = ctx.first_local_var_index + ctx.local_var_pairs.len();
+        let (args, ident, max_depth, sp_idx, stack_idx, ret_idx, frame_var_pairs) = match &ctx.tail_fun {
+            Some(TailFun { args, rec: Some(rec), .. }) => {
+                let frame_var_pairs: Vec<(usize, usize)> = (0..local_var_count).filter(|idx| !rec.is_control_var(*idx)).map(|idx| (idx, rec.frame_field_index(idx))).collect();
+                (args.clone(), rec.ident.clone(), rec.max_depth, rec.sp_idx, rec.stack_idx, rec.ret_idx, frame_var_pairs)
+            },
+            _ => return Err(BackendError::Internal(String::from("lower_rec_app: no recursive function"))),
+        };
+        let frame_ops = |field_idx: usize| vec![IrArgOp::LocalIndex(None, sp_idx), IrArgOp::Dot(None, field_idx)];
+        let mut panic_block = IrBlock::new();
+        panic_block.add_instr(IrInstr::Panic(format!("recursion depth of function {} is exceeded", ident), pos.clone(), Vec::new()));
+        ctx.add_instr(IrInstr::If(IrOp::Ge(local_var_value(sp_idx), IrValue::Uint(max_depth as u32, None)), Box::new(panic_block), Box::new(IrBlock::new())));
+        let resume_state = ctx.new_state()?;
+        ctx.add_instr(IrInstr::Assign(Box::new(IrInstrVar::Local(stack_idx, frame_ops(0))), IrOp::Load(IrValue::Uint(resume_state, None))));
+        for (idx, field_idx) in &frame_var_pairs {
+            ctx.add_instr(IrInstr::Assign(Box::new(IrInstrVar::Local(stack_idx, frame_ops(*field_idx))), IrOp::Load(local_var_value(*idx))));
+        }
+        ctx.add_instr(IrInstr::Assign(Box::new(IrInstrVar::Local(sp_idx, Vec::new())), IrOp::Add(local_var_value(sp_idx), IrValue::Uint(1, None))));
+        self.assign_tail_args(arg_values, args, ctx)?;
+        ctx.add_state_jump(ENTRY_STATE)?;
+        ctx.end_state()?;
+        ctx.begin_state(resume_state)?;
+        for (idx, field_idx) in &frame_var_pairs {
+            let frame_var_value = IrValue::Object(Box::new(IrObject::Var(IrArgVar::Local(stack_idx, frame_ops(*field_idx)), None)));
+            ctx.add_instr(IrInstr::Assign(Box::new(IrInstrVar::Local(*idx, Vec::new())), IrOp::Load(frame_var_value)));
+        }
+        match ret_idx {
+            Some(ret_idx) if *typ != IrType::Void => Ok(self.assign_to_new_local_var(IrOp::Load(local_var_value(ret_idx)), typ, ctx)),
+            _ => Ok(None),
+        }
+    }
+
+    fn lower_match(&self, expr: &Expr, cases: &[Case], target: BranchTarget, pos: &Pos, tree: &Tree, ctx: &mut FunContext, ir_tree: &mut IrTree) -> BackendResult<()>
+    {
+        let local_type2 = expr_local_type(expr)?;
+        let type2 = self.ir_type_for_local_type(local_type2, pos, tree, ctx, ir_tree)?;
+        let var = self.lower_rec_expr(expr, tree, ctx, ir_tree)?.map(|value| self.value_to_arg_var(value, &type2, ctx));
+        let mut rows: Vec<MatchRow<'_>> = Vec::new();
+        for (i, case) in cases.iter().enumerate() {
+            match case {
+                Case(pattern, _) => rows.push(MatchRow { patterns: vec![Some(&**pattern)], binds: Vec::new(), case_idx: i, }),
+            }
+        }
+        let mut occs: Vec<Option<IrArgVar>> = vec![var];
+        self.lower_match_rows(rows, &[0], &mut occs, cases, target, tree, ctx, ir_tree)
+    }
+
+    fn lower_match_block<'a>(&self, rows: Vec<MatchRow<'a>>, cols: &[usize], occs: &mut Vec<Option<IrArgVar>>, cases: &'a [Case], target: BranchTarget, tree: &Tree, ctx: &mut FunContext, ir_tree: &mut IrTree) -> BackendResult<IrBlock>
+    {
+        ctx.push_block();
+        match self.lower_match_rows(rows, cols, occs, cases, target, tree, ctx, ir_tree) {
+            Ok(()) => ctx.pop_block(),
+            Err(err) => {
+                ctx.pop_block()?;
+                Err(err)
+            },
+        }
+    }
+
+    fn lower_match_rows<'a>(&self, rows: Vec<MatchRow<'a>>, cols: &[usize], occs: &mut Vec<Option<IrArgVar>>, cases: &'a [Case], target: BranchTarget, tree: &Tree, ctx: &mut FunContext, ir_tree: &mut IrTree) -> BackendResult<()>
+    {
+        let mut rows2: Vec<MatchRow<'a>> = Vec::new();
+        for row in rows {
+            simplify_match_row(row, cols, &mut rows2);
+        }
+        let (j, pattern) = match rows2.first() {
+            Some(row) => {
+                match row.patterns.iter().enumerate().find(|(_, pattern)| pattern.is_some()) {
+                    Some((j, Some(pattern))) => (j, *pattern),
+                    _ => return self.lower_match_case(row, occs, cases, target, tree, ctx, ir_tree),
+                }
+            },
+            None => return Ok(()),
+        };
+        let occ = match occs.get(cols[j]) {
+            Some(Some(occ)) => occ.clone(),
+            _ => return Err(BackendError::Internal(String::from("lower_match_rows: no occurrence"))),
+        };
+        match pattern {
+            Pattern::Literal(literal, _, _) => {
+                match &**literal {
+                    Literal::Tuple(patterns) => {
+                        let field_ops: Vec<IrArgOp> = (0..patterns.len()).map(|k| IrArgOp::Dot(None, k)).collect();
+                        self.lower_match_product(rows2, j, &occ, field_ops.as_slice(), cols, occs, cases, target, tree, ctx, ir_tree)
+                    },
+                    Literal::Array(patterns) => {
+                        let field_ops: Vec<IrArgOp> = (0..patterns.len()).map(|k| IrArgOp::Index(None, k as u64)).collect();
+                        self.lower_match_product(rows2, j, &occ, field_ops.as_slice(), cols, occs, cases, target, tree, ctx, ir_tree)
+                    },
+                    Literal::FilledArray(_, len) => {
+                        let field_ops: Vec<IrArgOp> = (0..*len).map(|k| IrArgOp::Index(None, k as u64)).collect();
+                        self.lower_match_product(rows2, j, &occ, field_ops.as_slice(), cols, occs, cases, target, tree, ctx, ir_tree)
+                    },
+                    _ => self.lower_match_tests(rows2, j, &occ, pattern, cols, occs, cases, target, tree, ctx, ir_tree),
+                }
+            },
+            Pattern::UnnamedFieldCon(_, _, _, Some(local_type), _) | Pattern::NamedFieldCon(_, _, _, Some(local_type), _) => self.lower_match_cons(rows2, j, &occ, *local_type, cols, occs, cases, target, tree, ctx, ir_tree),
+            _ => self.lower_match_tests(rows2, j, &occ, pattern, cols, occs, cases, target, tree, ctx, ir_tree),
+        }
+    }
+
+    fn lower_match_case<'a>(&self, row: &MatchRow<'a>, occs: &[Option<IrArgVar>], cases: &'a [Case], target: BranchTarget, tree: &Tree, ctx: &mut FunContext, ir_tree: &mut IrTree) -> BackendResult<()>
+    {
+        let expr = match cases.get(row.case_idx) {
+            Some(Case(_, expr)) => expr,
+            None => return Err(BackendError::Internal(String::from("lower_match_case: no case"))),
+        };
+        ctx.var_env.push_new_vars();
+        for (pattern, occ_idx) in &row.binds {
+            let value = match occs.get(*occ_idx) {
+                Some(occ) => occ.clone().map(|var| IrValue::Object(Box::new(IrObject::Var(var, None)))),
+                None => {
+                    ctx.var_env.pop_vars();
+                    return Err(BackendError::Internal(String::from("lower_match_case: no occurrence")));
+                },
+            };
+            let (modifier, ident, local_type, pos) = match pattern {
+                Pattern::Var(modifier, ident, Some(local_type), pos) => (*modifier, ident, *local_type, pos),
+                Pattern::At(modifier, ident, _, Some(local_type), pos) => (*modifier, ident, *local_type, pos),
+                _ => {
+                    ctx.var_env.pop_vars();
+                    return Err(BackendError::Internal(String::from("lower_match_case: pattern isn't variable pattern or no local type")));
+                },
+            };
+            match self.bind_var(modifier, local_type, value, pos, tree, ctx, ir_tree) {
+                Ok(var_idx) => {
+                    ctx.var_env.add_var(ident.clone(), var_idx);
+                },
+                Err(err) => {
+                    ctx.var_env.pop_vars();
+                    return Err(err);
+                },
+            }
+        }
+        let res = self.lower_branch_expr(expr, target, tree, ctx, ir_tree);
+        ctx.var_env.pop_vars();
+        res
+    }
+
+    fn add_match_occs(&self, occ: &IrArgVar, ops: &[IrArgOp], field_ops: &[IrArgOp], occs: &mut Vec<Option<IrArgVar>>) -> BackendResult<Vec<usize>>
+    {
+        let mut field_cols: Vec<usize> = Vec::new();
+        for field_op in field_ops {
+            let mut ops2 = ops.to_vec();
+            ops2.push(field_op.clone());
+            field_cols.push(occs.len());
+            occs.push(Some(arg_var_with_ops(occ, ops2.as_slice())?));
+        }
+        Ok(field_cols)
+    }
+
+    fn lower_match_product<'a>(&self, rows: Vec<MatchRow<'a>>, j: usize, occ: &IrArgVar, field_ops: &[IrArgOp], cols: &[usize], occs: &mut Vec<Option<IrArgVar>>, cases: &'a [Case], target: BranchTarget, tree: &Tree, ctx: &mut FunContext, ir_tree: &mut IrTree) -> BackendResult<()>
+    {
+        let field_cols = self.add_match_occs(occ, &[], field_ops, occs)?;
+        let new_rows = specialize_match_rows(rows.as_slice(), j, field_ops.len(), |_, pattern| {
+                match pattern {
+                    Pattern::Literal(literal, _, pos) => {
+                        match &**literal {
+                            Literal::Tuple(patterns) | Literal::Array(patterns) => Ok(Some(patterns.iter().map(|pattern2| Some(&**pattern2)).collect())),
+                            Literal::FilledArray(pattern2, len) => Ok(Some(vec![Some(&**pattern2); *len])),
+                            _ => Err(BackendError::Internal(format!("lower_match_product: literal pattern at {}:{} isn't compound", pos.line, pos.column))),
+                        }
+                    },
+                    Pattern::UnnamedFieldCon(_, _, _, _, _) | Pattern::NamedFieldCon(_, _, _, _, _) => Ok(Some(con_field_patterns(pattern, field_ops.len(), tree)?)),
+                    _ => message_error(pattern_pos(pattern), String::from("pattern of compound type is unsupported")),
+                }
+        })?;
+        let new_cols = specialize_match_cols(cols, j, field_cols.as_slice());
+        self.lower_match_rows(new_rows, new_cols.as_slice(), occs, cases, target, tree, ctx, ir_tree)
+    }
+
+    fn lower_match_cons<'a>(&self, rows: Vec<MatchRow<'a>>, j: usize, occ: &IrArgVar, local_type: LocalType, cols: &[usize], occs: &mut Vec<Option<IrArgVar>>, cases: &'a [Case], target: BranchTarget, tree: &Tree, ctx: &mut FunContext, ir_tree: &mut IrTree) -> BackendResult<()>
+    {
+        let data_ident = match type_value_ident(&ctx.type_value(local_type)?) {
+            Some(tmp_data_ident) => String::from(tmp_data_ident),
+            None => return Err(BackendError::Internal(String::from("lower_match_cons: type value isn't data type"))),
+        };
+        let cons = data_cons(&data_ident, tree)?;
+        let con_field_counts: Vec<usize> = cons.iter().map(|con| con_field_count(&con.borrow())).collect();
+        let con_idents: Vec<String> = cons.iter().map(|con| con_ident(&con.borrow()).clone()).collect();
+        let is_nullary = con_field_counts.iter().all(|n| *n == 0);
+        if cons.len() == 1 {
+            let field_ops: Vec<IrArgOp> = if is_nullary {
+                Vec::new()
+            } else {
+                (0..con_field_counts[0]).map(|k| IrArgOp::Dot(None, k)).collect()
+            };
+            return self.lower_match_product(rows, j, occ, field_ops.as_slice(), cols, occs, cases, target, tree, ctx, ir_tree);
+        }
+        let mut con_idxs: Vec<usize> = Vec::new();
+        for row in &rows {
+            if let Some(ident) = row.patterns[j].and_then(pattern_con_ident) {
+                match con_idents.iter().position(|con_ident| con_ident == ident) {
+                    Some(con_idx) => {
+                        if !con_idxs.contains(&con_idx) {
+                            con_idxs.push(con_idx);
+                        }
+                    },
+                    None => return Err(BackendError::Internal(String::from("lower_match_cons: no constructor"))),
+                }
+            }
+        }
+        let mut ir_cases: Vec<IrCase> = Vec::new();
+        for con_idx in &con_idxs {
+            let field_count = con_field_counts[*con_idx];
+            let union_field_idx = con_field_counts[0..*con_idx].iter().filter(|n| **n != 0).count();
+            let field_ops: Vec<IrArgOp> = (0..field_count).map(|k| IrArgOp::Dot(None, k)).collect();
+            let field_cols = self.add_match_occs(occ, &[IrArgOp::Dot(None, 1), IrArgOp::Dot(None, union_field_idx)], field_ops.as_slice(), occs)?;
+            let new_rows = specialize_match_rows(rows.as_slice(), j, field_count, |_, pattern| {
+                    match pattern_con_ident(pattern) {
+                        Some(ident) if *ident == con_idents[*con_idx] => Ok(Some(con_field_patterns(pattern, field_count, tree)?)),
+                        Some(_) => Ok(None),
+                        None => message_error(pattern_pos(pattern), String::from("pattern of data type is unsupported")),
+                    }
+            })?;
+            let new_cols = specialize_match_cols(cols, j, field_cols.as_slice());
+            let block = self.lower_match_block(new_rows, new_cols.as_slice(), occs, cases, target, tree, ctx, ir_tree)?;
+            ir_cases.push(IrCase::Case(IrCaseValue::Uint(*con_idx as u32, None), Box::new(block)));
+        }
+        if con_idxs.len() < cons.len() {
+            let default_rows: Vec<MatchRow<'a>> = rows.iter().filter(|row| row.patterns[j].is_none()).map(|row| {
+                    let mut new_row = row.clone();
+                    new_row.patterns.remove(j);
+                    new_row
+            }).collect();
+            if !default_rows.is_empty() {
+                let new_cols = specialize_match_cols(cols, j, &[]);
+                let block = self.lower_match_block(default_rows, new_cols.as_slice(), occs, cases, target, tree, ctx, ir_tree)?;
+                ir_cases.push(IrCase::Default(Box::new(block)));
+            }
+        }
+        let tag_var = if is_nullary {
+            occ.clone()
+        } else {
+            arg_var_with_ops(occ, &[IrArgOp::Dot(None, 0)])?
+        };
+        ctx.add_instr(IrInstr::Switch(IrOp::Load(IrValue::Object(Box::new(IrObject::Var(tag_var, None)))), ir_cases));
+        Ok(())
+    }
+
+    fn lower_match_tests<'a>(&self, rows: Vec<MatchRow<'a>>, j: usize, occ: &IrArgVar, pattern: &Pattern, cols: &[usize], occs: &mut Vec<Option<IrArgVar>>, cases: &'a [Case], target: BranchTarget, tree: &Tree, ctx: &mut FunContext, ir_tree: &mut IrTree) -> BackendResult<()>
+    {
+        let typ = self.ir_type_for_local_type(pattern_local_type(pattern)?, pattern_pos(pattern), tree, ctx, ir_tree)?;
+        let mut test_rows: Vec<(MatchTest, Vec<MatchRow<'a>>)> = Vec::new();
+        let mut default_rows: Vec<MatchRow<'a>> = Vec::new();
+        for row in &rows {
+            let mut new_row = row.clone();
+            new_row.patterns.remove(j);
+            match row.patterns[j] {
+                Some(pattern2) => {
+                    let test = self.match_test(pattern2, &typ, tree, ctx)?;
+                    match test_rows.iter_mut().find(|(test2, _)| test2.is_same(&test)) {
+                        Some((_, rows2)) => rows2.push(new_row),
+                        None => {
+                            let mut rows2 = default_rows.clone();
+                            rows2.push(new_row);
+                            test_rows.push((test, rows2));
+                        },
+                    }
+                },
+                None => {
+                    for (_, rows2) in &mut test_rows {
+                        rows2.push(new_row.clone());
+                    }
+                    default_rows.push(new_row);
+                },
+            }
+        }
+        let new_cols = specialize_match_cols(cols, j, &[]);
+        if test_rows.iter().all(|(test, _)| matches!(test, MatchTest::Case(_))) {
+            let mut ir_cases: Vec<IrCase> = Vec::new();
+            for (test, rows2) in test_rows {
+                match test {
+                    MatchTest::Case(case_value) => {
+                        let block = self.lower_match_block(rows2, new_cols.as_slice(), occs, cases, target, tree, ctx, ir_tree)?;
+                        ir_cases.push(IrCase::Case(case_value, Box::new(block)));
+                    },
+                    _ => return Err(BackendError::Internal(String::from("lower_match_tests: test isn't case"))),
+                }
+            }
+            if !default_rows.is_empty() {
+                let block = self.lower_match_block(default_rows, new_cols.as_slice(), occs, cases, target, tree, ctx, ir_tree)?;
+                ir_cases.push(IrCase::Default(Box::new(block)));
+            }
+            ctx.add_instr(IrInstr::Switch(IrOp::Load(IrValue::Object(Box::new(IrObject::Var(occ.clone(), None)))), ir_cases));
+            Ok(())
+        } else {
+            self.lower_match_if_chain(test_rows, default_rows, occ, &typ, new_cols.as_slice(), occs, cases, target, tree, ctx, ir_tree)
+        }
+    }
+
+    fn lower_match_if_chain<'a>(&self, mut test_rows: Vec<(MatchTest, Vec<MatchRow<'a>>)>, default_rows: Vec<MatchRow<'a>>, occ: &IrArgVar, typ: &IrType, cols: &[usize], occs: &mut Vec<Option<IrArgVar>>, cases: &'a [Case], target: BranchTarget, tree: &Tree, ctx: &mut FunContext, ir_tree: &mut IrTree) -> BackendResult<()>
+    {
+        if test_rows.is_empty() {
+            return self.lower_match_rows(default_rows, cols, occs, cases, target, tree, ctx, ir_tree);
+        }
+        let (test, rows) = test_rows.remove(0);
+        let cond_op = self.lower_match_test_cond(&test, occ, typ, ctx)?;
+        let then_block = self.lower_match_block(rows, cols, occs, cases, target, tree, ctx, ir_tree)?;
+        ctx.push_block();
+        match self.lower_match_if_chain(test_rows, default_rows, occ, typ, cols, occs, cases, target, tree, ctx, ir_tree) {
+            Ok(()) => (),
+            Err(err) => {
+                ctx.pop_block()?;
+                return Err(err);
+            },
+        }
+        let else_block = ctx.pop_block()?;
+        ctx.add_instr(IrInstr::If(cond_op, Box::new(then_block), Box::new(else_block)));
+        Ok(())
+    }
+
+    fn lower_match_test_cond(&self, test: &MatchTest, occ: &IrArgVar, typ: &IrType, ctx: &mut FunContext) -> BackendResult<IrOp>
+    {
+        let occ_value = IrValue::Object(Box::new(IrObject::Var(occ.clone(), None)));
+        match test {
+            MatchTest::Case(case_value) => Ok(IrOp::Eq(occ_value, case_value_to_value(case_value))),
+            MatchTest::Value(value) => {
+                match vector_comparison_type(typ) {
+                    Some(comparison_type) => {
+                        match self.assign_to_new_local_var(IrOp::Eq(occ_value, value.clone()), Box::new(comparison_type), ctx) {
+                            Some(comparison_value) => Ok(IrOp::CallBuiltinFun(String::from("all"), None, vec![comparison_value])),
+                            None => Err(BackendError::Internal(String::from("lower_match_test_cond: no comparison value"))),
+                        }
+                    },
+                    None => Ok(IrOp::Eq(occ_value, value.clone())),
+                }
+            },
+            MatchTest::String(bytes) => {
+                let flag_idx = ctx.add_local_var(IrLocalVarModifier::None, Box::new(IrType::Int));
+                ctx.add_instr(IrInstr::Assign(Box::new(IrInstrVar::Local(flag_idx, Vec::new())), IrOp::Load(IrValue::Int(0, None))));
+                let mut block = IrBlock::new();
+                block.add_instr(IrInstr::Assign(Box::new(IrInstrVar::Local(flag_idx, Vec::new())), IrOp::Load(IrValue::Int(1, None))));
+                for (i, b) in bytes.iter().enumerate().rev() {
+                    let byte_var = arg_var_with_ops(occ, &[IrArgOp::Dot(None, 0), IrArgOp::Index(None, i as u64)])?;
+                    let cond_op = IrOp::Eq(IrValue::Object(Box::new(IrObject::Var(byte_var, None))), IrValue::Char(*b as i8, None));
+                    let mut block2 = IrBlock::new();
+                    block2.add_instr(IrInstr::If(cond_op, Box::new(block), Box::new(IrBlock::new())));
+                    block = block2;
+                }
+                let len_var = arg_var_with_ops(occ, &[IrArgOp::Dot(None, 1)])?;
+                let cond_op = IrOp::Eq(IrValue::Object(Box::new(IrObject::Var(len_var, None))), IrValue::SizeT(bytes.len() as u64, None));
+                ctx.add_instr(IrInstr::If(cond_op, Box::new(block), Box::new(IrBlock::new())));
+                Ok(IrOp::Load(local_var_value(flag_idx)))
+            },
+        }
+    }
+
+    fn match_test(&self, pattern: &Pattern, typ: &IrType, tree: &Tree, ctx: &mut FunContext) -> BackendResult<MatchTest>
+    {
+        match pattern {
+            Pattern::Literal(literal, _, pos) => self.match_test_for_literal(literal, typ, pos),
+            Pattern::As(literal, _, _, _, pos) => self.match_test_for_literal(literal, typ, pos),
+            Pattern::Const(ident, Some(local_type), pos) => {
+                match self.var_target(ident, *local_type, pos, tree, ctx)? {
+                    VarTarget::Builtin(ident2, _) => {
+                        if case_value_for_integer(0, typ).is_some() {
+                            Ok(MatchTest::Case(IrCaseValue::BuiltinVar(ident2, None, None)))
+                        } else {
+                            Ok(MatchTest::Value(IrValue::Object(Box::new(IrObject::BuiltinVar(ident2, None, None)))))
+                        }
+                    },
+                    VarTarget::Var(ident2, type_name) => {
+                        let name = self.var_name(&ident2, &type_name, *local_type, pos, tree, ctx)?;
+                        Ok(MatchTest::Value(IrValue::Object(Box::new(IrObject::Var(IrArgVar::Global(name, Vec::new()), None)))))
+                    },
+                    _ => message_error(pos, format!("constant pattern {} is unsupported", ident)),
+                }
+            },
+            _ => message_error(pattern_pos(pattern), String::from("pattern is unsupported")),
+        }
+    }
+
+    fn match_test_for_literal(&self, literal: &Literal<Pattern>, typ: &IrType, pos: &Pos) -> BackendResult<MatchTest>
+    {
+        let n = match literal {
+            Literal::Bool(b) => if *b { 1 } else { 0 },
+            Literal::Char(n) => *n as i128,
+            Literal::Int(n) => *n as i128,
+            Literal::Long(n) => *n as i128,
+            Literal::Uint(n) => *n as i128,
+            Literal::Ulong(n) => *n as i128,
+            Literal::Float(x) => {
+                return match float_value(*x as f64, typ) {
+                    Some(value) => Ok(MatchTest::Value(value)),
+                    None => message_error(pos, String::from("literal pattern of this type is unsupported")),
+                };
+            },
+            Literal::Double(x) => {
+                return match float_value(*x, typ) {
+                    Some(value) => Ok(MatchTest::Value(value)),
+                    None => message_error(pos, String::from("literal pattern of this type is unsupported")),
+                };
+            },
+            Literal::String(bytes) => return Ok(MatchTest::String(bytes.clone())),
+            _ => return message_error(pos, String::from("compound literal pattern is unsupported")),
+        };
+        match case_value_for_integer(n, typ) {
+            Some(case_value) => Ok(MatchTest::Case(case_value)),
+            None => {
+                match float_value(n as f64, typ) {
+                    Some(value) => Ok(MatchTest::Value(value)),
+                    None => message_error(pos, String::from("literal pattern of this type is unsupported")),
+                }
+            },
+        }
+    }
+
+    fn lower_lambda(&self, args: &[LambdaArg], body: &Expr, ret_local_type: LocalType, local_type: LocalType, pos: &Pos, tree: &Tree, ctx: &mut FunContext, ir_tree: &mut IrTree) -> BackendResult<Option<IrValue<IrArgVar>>>
+    {
+        let type_value = type_value_without_uniq_flags(&ctx.type_value(local_type)?);
+        let fun_type = self.ir_type_for_type_value(&type_value, pos, tree, ir_tree)?;
+        let caller_name = self.add_caller(&type_value, pos, tree, ir_tree)?;
+        let mut lambda_var_env: Environment<()> = Environment::new();
+        lambda_var_env.push_new_vars();
+        for arg in args {
+            match arg {
+                LambdaArg(ident, _, _, _) => {
+                    lambda_var_env.add_var(ident.clone(), ());
+                },
+            }
+        }
+        let mut closure_var_tuples: BTreeMap<String, (usize, LocalType, Pos)> = BTreeMap::new();
+        self.add_closure_vars_for_expr(body, &ctx.var_env, &mut lambda_var_env, &mut closure_var_tuples);
+        lambda_var_env.pop_vars();
+        let start_idx = ctx.first_local_var_index + ctx.local_var_pairs.len();
+        let caller_fun_idx = self.add_caller_fun(&caller_name, IrCallerFun::Lambda(None, None, None, start_idx, Box::new(IrBlock::new())), ir_tree)?;
+        let mut private_field_types: BTreeMap<usize, Box<IrType>> = BTreeMap::new();
+        let mut local_field_types: BTreeMap<usize, Box<IrType>> = BTreeMap::new();
+        let mut global_field_types: BTreeMap<usize, Box<IrType>> = BTreeMap::new();
+        let mut private_field_pairs: Vec<IrFieldPair<IrArgVar>> = Vec::new();
+        let mut local_field_pairs: Vec<IrFieldPair<IrArgVar>> = Vec::new();
+        let mut global_field_pairs: Vec<IrFieldPair<IrArgVar>> = Vec::new();
+        let mut closure_vars: BTreeMap<usize, IrArgVar> = BTreeMap::new();
+        for (idx, closure_local_type, closure_pos) in closure_var_tuples.values() {
+            let closure_type = self.ir_type_for_local_type(*closure_local_type, closure_pos, tree, ctx, ir_tree)?;
+            if self.has_type(&closure_type, &fun_type, ir_tree) {
+                return message_error(closure_pos, String::from("capturing value with function type of lambda is unsupported"));
+            }
+            let field_pair = IrFieldPair(*idx, ctx.var_value(*idx));
+            let closure_var = match ctx.closure_vars.get(idx) {
+                Some(IrArgVar::LocalClosure(_, _)) => IrArgVar::LocalClosure(*idx, Vec::new()),
+                Some(IrArgVar::GlobalClosure(_, _)) => IrArgVar::GlobalClosure(*idx, Vec::new()),
+                Some(_) => IrArgVar::PrivateClosure(*idx, Vec::new()),
+                None => {
+                    match ctx.local_var_modifier(*idx) {
+                        IrLocalVarModifier::Local => IrArgVar::LocalClosure(*idx, Vec::new()),
+                        IrLocalVarModifier::Global => IrArgVar::GlobalClosure(*idx, Vec::new()),
+                        _ => IrArgVar::PrivateClosure(*idx, Vec::new()),
+                    }
+                },
+            };
+            match &closure_var {
+                IrArgVar::LocalClosure(_, _) => {
+                    local_field_types.insert(*idx, closure_type);
+                    local_field_pairs.push(field_pair);
+                },
+                IrArgVar::GlobalClosure(_, _) => {
+                    global_field_types.insert(*idx, closure_type);
+                    global_field_pairs.push(field_pair);
+                },
+                _ => {
+                    private_field_types.insert(*idx, closure_type);
+                    private_field_pairs.push(field_pair);
+                },
+            }
+            closure_vars.insert(*idx, closure_var);
+        }
+        let mut arg_types: Vec<Option<Box<IrType>>> = Vec::new();
+        for arg in args {
+            match arg {
+                LambdaArg(_, _, Some(arg_local_type), arg_pos) => {
+                    let arg_type = self.ir_type_for_local_type(*arg_local_type, arg_pos, tree, ctx, ir_tree)?;
+                    if *arg_type == IrType::Void {
+                        arg_types.push(None);
+                    } else {
+                        arg_types.push(Some(arg_type));
+                    }
+                },
+                LambdaArg(_, _, None, _) => return Err(BackendError::Internal(String::from("lower_lambda: no local type of argument"))),
+            }
+        }
+        let ret_type = self.ir_type_for_local_type(ret_local_type, pos, tree, ctx, ir_tree)?;
+        let block = {
+            let arg_count = arg_types.iter().flatten().count();
+            let mut lambda_ctx = ctx.new_lambda_context(closure_vars, start_idx + arg_count);
+            for (ident, (idx, _, _)) in &closure_var_tuples {
+                lambda_ctx.var_env.add_var(ident.clone(), Some(*idx));
+            }
+            let mut arg_idx = start_idx;
+            for (arg, arg_type) in args.iter().zip(arg_types.iter()) {
+                match (arg, arg_type) {
+                    (LambdaArg(ident, _, _, _), Some(_)) => {
+                        lambda_ctx.var_env.add_var(ident.clone(), Some(arg_idx));
+                        arg_idx += 1;
+                    },
+                    (LambdaArg(ident, _, _, _), None) => {
+                        lambda_ctx.var_env.add_var(ident.clone(), None);
+                    },
+                }
+            }
+            let value = self.lower_expr(body, tree, &mut lambda_ctx, ir_tree)?;
+            match value {
+                Some(value) if *ret_type != IrType::Void => lambda_ctx.add_instr(IrInstr::Return(Some(IrOp::Load(value)))),
+                _ => lambda_ctx.add_instr(IrInstr::Return(None)),
+            }
+            lambda_ctx.into_block()?
+        };
+        let mut field_pairs: Vec<IrFieldPair<IrArgVar>> = Vec::new();
+        let private_closure_type = if !private_field_types.is_empty() {
+            let name = mangle_private_closure_name(&type_value, caller_fun_idx)?;
+            self.add_closure_struct(&name, private_field_types, ir_tree);
+            let closure_type = Box::new(IrType::Struct(name));
+            let field_idx = self.add_fun_struct_field(&fun_type, closure_type.clone(), ir_tree)?;
+            field_pairs.push(IrFieldPair(field_idx, IrValue::Object(Box::new(IrObject::Closure(private_field_pairs, Some(closure_type.clone()))))));
+            Some(closure_type)
+        } else {
+            None
+        };
+        let local_closure_type = if !local_field_types.is_empty() {
+            let name = mangle_local_closure_name(&type_value, caller_fun_idx)?;
+            self.add_closure_struct(&name, local_field_types, ir_tree);
+            let closure_type = Box::new(IrType::Struct(name));
+            let field_idx = self.add_fun_struct_field(&fun_type, closure_type.clone(), ir_tree)?;
+            field_pairs.push(IrFieldPair(field_idx, IrValue::Object(Box::new(IrObject::Closure(local_field_pairs, Some(closure_type.clone()))))));
+            Some(closure_type)
+        } else {
+            None
+        };
+        let global_closure_type = if !global_field_types.is_empty() {
+            let name = mangle_global_closure_name(&type_value, caller_fun_idx)?;
+            self.add_closure_struct(&name, global_field_types, ir_tree);
+            let closure_type = Box::new(IrType::Struct(name));
+            let field_idx = self.add_fun_struct_field(&fun_type, closure_type.clone(), ir_tree)?;
+            field_pairs.push(IrFieldPair(field_idx, IrValue::Object(Box::new(IrObject::Closure(global_field_pairs, Some(closure_type.clone()))))));
+            Some(closure_type)
+        } else {
+            None
+        };
+        self.set_caller_lambda(&caller_name, caller_fun_idx, IrCallerFun::Lambda(private_closure_type, local_closure_type, global_closure_type, start_idx, Box::new(block)), ir_tree)?;
+        let idx_value = IrValue::Object(Box::new(IrObject::CallerFunIndex(caller_name, caller_fun_idx, None)));
+        Ok(Some(IrValue::Object(Box::new(IrObject::Struct(vec![idx_value], field_pairs, Some(fun_type))))))
+    }
+
+    fn add_closure_vars_for_literal(&self, literal: &Literal<Expr>, closure_var_env: &Environment<Option<usize>>, var_env: &mut Environment<()>, closure_var_tuples: &mut BTreeMap<String, (usize, LocalType, Pos)>)
+    {
+        match literal {
+            Literal::Tuple(exprs) | Literal::Array(exprs) => {
+                for expr in exprs {
+                    self.add_closure_vars_for_expr(expr, closure_var_env, var_env, closure_var_tuples);
+                }
+            },
+            Literal::FilledArray(expr, _) => self.add_closure_vars_for_expr(expr, closure_var_env, var_env, closure_var_tuples),
+            _ => (),
+        }
+    }
+
+    fn add_closure_vars_for_expr(&self, expr: &Expr, closure_var_env: &Environment<Option<usize>>, var_env: &mut Environment<()>, closure_var_tuples: &mut BTreeMap<String, (usize, LocalType, Pos)>)
+    {
+        match expr {
+            Expr::Literal(literal, _, _) => self.add_closure_vars_for_literal(literal, closure_var_env, var_env, closure_var_tuples),
+            Expr::Lambda(args, _, body, _, _, _, _, _) => {
+                var_env.push_new_vars();
+                for arg in args {
+                    match arg {
+                        LambdaArg(ident, _, _, _) => {
+                            var_env.add_var(ident.clone(), ());
+                        },
+                    }
+                }
+                self.add_closure_vars_for_expr(body, closure_var_env, var_env, closure_var_tuples);
+                var_env.pop_vars();
+            },
+            Expr::Var(ident, Some(local_type), pos) => {
+                if var_env.var(ident).is_none() {
+                    if let Some(Some(idx)) = closure_var_env.var(ident) {
+                        closure_var_tuples.insert(ident.clone(), (*idx, *local_type, pos.clone()));
+                    }
+                }
+            },
+            Expr::Var(_, None, _) => (),
+            Expr::NamedFieldConApp(_, expr_named_field_pairs, _, _, _) => {
+                for expr_named_field_pair in expr_named_field_pairs {
+                    match expr_named_field_pair {
+                        NamedFieldPair(_, expr2, _) => self.add_closure_vars_for_expr(expr2, closure_var_env, var_env, closure_var_tuples),
+                    }
+                }
+            },
+            Expr::PrintfApp(exprs, _, _) => {
+                for expr2 in exprs {
+                    self.add_closure_vars_for_expr(expr2, closure_var_env, var_env, closure_var_tuples);
+                }
+            },
+            Expr::App(expr2, exprs, _, _) => {
+                self.add_closure_vars_for_expr(expr2, closure_var_env, var_env, closure_var_tuples);
+                for expr3 in exprs {
+                    self.add_closure_vars_for_expr(expr3, closure_var_env, var_env, closure_var_tuples);
+                }
+            },
+            Expr::GetField(expr2, _, _, _) => self.add_closure_vars_for_expr(expr2, closure_var_env, var_env, closure_var_tuples),
+            Expr::Get2Field(expr2, _, _, _) => self.add_closure_vars_for_expr(expr2, closure_var_env, var_env, closure_var_tuples),
+            Expr::SetField(expr2, _, expr3, _, _) | Expr::UpdateField(expr2, _, expr3, _, _) | Expr::UpdateGet2Field(expr2, _, expr3, _, _) => {
+                self.add_closure_vars_for_expr(expr2, closure_var_env, var_env, closure_var_tuples);
+                self.add_closure_vars_for_expr(expr3, closure_var_env, var_env, closure_var_tuples);
+            },
+            Expr::Uniq(expr2, _, _) => self.add_closure_vars_for_expr(expr2, closure_var_env, var_env, closure_var_tuples),
+            Expr::Shared(expr2, _, _) => self.add_closure_vars_for_expr(expr2, closure_var_env, var_env, closure_var_tuples),
+            Expr::Typed(expr2, _, _, _) => self.add_closure_vars_for_expr(expr2, closure_var_env, var_env, closure_var_tuples),
+            Expr::As(expr2, _, _, _) => self.add_closure_vars_for_expr(expr2, closure_var_env, var_env, closure_var_tuples),
+            Expr::Let(binds, expr2, _, _) => {
+                var_env.push_new_vars();
+                for bind in binds {
+                    match bind {
+                        Bind(pattern, expr3) => {
+                            self.add_closure_vars_for_expr(expr3, closure_var_env, var_env, closure_var_tuples);
+                            self.add_vars_for_pattern(pattern, var_env);
+                        },
+                    }
+                }
+                self.add_closure_vars_for_expr(expr2, closure_var_env, var_env, closure_var_tuples);
+                var_env.pop_vars();
+            },
+            Expr::If(expr2, expr3, expr4, _, _) => {
+                self.add_closure_vars_for_expr(expr2, closure_var_env, var_env, closure_var_tuples);
+                self.add_closure_vars_for_expr(expr3, closure_var_env, var_env, closure_var_tuples);
+                self.add_closure_vars_for_expr(expr4, closure_var_env, var_env, closure_var_tuples);
+            },
+            Expr::Match(expr2, cases, _, _) => {
+                self.add_closure_vars_for_expr(expr2, closure_var_env, var_env, closure_var_tuples);
+                for case in cases {
+                    match case {
+                        Case(pattern, expr3) => {
+                            var_env.push_new_vars();
+                            self.add_vars_for_pattern(pattern, var_env);
+                            self.add_closure_vars_for_expr(expr3, closure_var_env, var_env, closure_var_tuples);
+                            var_env.pop_vars();
+                        },
+                    }
+                }
+            },
+        }
+    }
+
+    fn add_vars_for_pattern(&self, pattern: &Pattern, var_env: &mut Environment<()>)
+    {
+        match pattern {
+            Pattern::Literal(literal, _, _) => {
+                match &**literal {
+                    Literal::Tuple(patterns) | Literal::Array(patterns) => {
+                        for pattern2 in patterns {
+                            self.add_vars_for_pattern(pattern2, var_env);
+                        }
+                    },
+                    Literal::FilledArray(pattern2, _) => self.add_vars_for_pattern(pattern2, var_env),
+                    _ => (),
+                }
+            },
+            Pattern::UnnamedFieldCon(_, patterns, _, _, _) => {
+                for pattern2 in patterns {
+                    self.add_vars_for_pattern(pattern2, var_env);
+                }
+            },
+            Pattern::NamedFieldCon(_, pattern_named_field_pairs, _, _, _) => {
+                for pattern_named_field_pair in pattern_named_field_pairs {
+                    match pattern_named_field_pair {
+                        NamedFieldPair(_, pattern2, _) => self.add_vars_for_pattern(pattern2, var_env),
+                    }
+                }
+            },
+            Pattern::Var(_, ident, _, _) => {
+                var_env.add_var(ident.clone(), ());
+            },
+            Pattern::At(_, ident, pattern2, _, _) => {
+                var_env.add_var(ident.clone(), ());
+                self.add_vars_for_pattern(pattern2, var_env);
+            },
+            _ => (),
+        }
+    }
+
+    fn field_ops(&self, local_type: LocalType, fields: &[Field], pos: &Pos, tree: &Tree, ctx: &FunContext) -> BackendResult<Vec<IrArgOp>>
+    {
+        let mut ops: Vec<IrArgOp> = Vec::new();
+        let mut type_value = ctx.type_value(local_type)?;
+        for field in fields {
+            let (field_idx, field_type_value) = match (field, &*type_value) {
+                (Field::Unnamed(field_idx, _), TypeValue::Type(_, TypeValueName::Tuple, args)) => {
+                    match args.get(*field_idx) {
+                        Some(arg) => (*field_idx, arg.clone()),
+                        None => return Err(BackendError::Internal(String::from("field_ops: no tuple field"))),
+                    }
+                },
+                (Field::Named(ident, _), TypeValue::Type(_, TypeValueName::Name(type_ident), _)) => {
+                    match tree.type_var(type_ident) {
+                        Some(type_var) => {
+                            let type_var_r = type_var.borrow();
+                            match &*type_var_r {
+                                TypeVar::Builtin(_, Some(builtin_fields), _) => {
+                                    match builtin_fields.field_index(ident) {
+                                        Some(field_idx) => {
+                                            match builtin_fields.field_type_values().get(field_idx) {
+                                                Some(field_type_value) => (field_idx, field_type_value.clone()),
+                                                None => return Err(BackendError::Internal(String::from("field_ops: no field type value"))),
+                                            }
+                                        },
+                                        None => return Err(BackendError::Internal(String::from("field_ops: no field index"))),
+                                    }
+                                },
+                                TypeVar::Data(_, cons, _) if cons.len() == 1 => {
+                                    let con_r = cons[0].borrow();
+                                    match &*con_r {
+                                        Con::NamedField(_, con_ident, _, _, Some(named_fields), _) => {
+                                            match named_fields.field_index(ident) {
+                                                Some(field_idx) => {
+                                                    let args = match &*type_value {
+                                                        TypeValue::Type(_, _, args) => args.as_slice(),
+                                                        TypeValue::Param(_, _) => return Err(BackendError::Internal(String::from("field_ops: type value is type parameter"))),
+                                                    };
+                                                    match con_field_type_values(con_ident, args, tree)?.get(field_idx) {
+                                                        Some(field_type_value) => (field_idx, field_type_value.clone()),
+                                                        None => return Err(BackendError::Internal(String::from("field_ops: no field type value"))),
+                                                    }
+                                                },
+                                                None => return Err(BackendError::Internal(String::from("field_ops: no field index"))),
+                                            }
+                                        },
+                                        _ => return Err(BackendError::Internal(String::from("field_ops: constructor isn't named field constructor or no named fields"))),
+                                    }
+                                },
+                                _ => return message_error(pos, format!("fields of type {} are unsupported", type_ident)),
+                            }
+                        },
+                        None => return Err(BackendError::Internal(String::from("field_ops: no type variable"))),
+                    }
+                },
+                (_, _) => return message_error(pos, String::from("field of value is unsupported")),
+            };
+            ops.push(IrArgOp::Dot(None, field_idx));
+            type_value = field_type_value;
+        }
+        Ok(ops)
+    }
+
+    fn lower_literal(&self, literal: &Literal<Expr>, local_type: LocalType, pos: &Pos, tree: &Tree, ctx: &mut FunContext, ir_tree: &mut IrTree) -> BackendResult<Option<IrValue<IrArgVar>>>
+    {
+        match literal {
+            Literal::Bool(b) => Ok(Some(IrValue::Int(if *b { 1 } else { 0 }, None))),
+            Literal::Char(n) => Ok(Some(IrValue::Char(*n, None))),
+            Literal::Int(n) => Ok(Some(IrValue::Int(*n, None))),
+            Literal::Long(n) => Ok(Some(IrValue::Long(*n, None))),
+            Literal::Uint(n) => Ok(Some(IrValue::Uint(*n, None))),
+            Literal::Ulong(n) => Ok(Some(IrValue::Ulong(*n, None))),
+            Literal::Float(n) => Ok(Some(IrValue::Float(*n, None))),
+            Literal::Double(n) => Ok(Some(IrValue::Double(*n, None))),
+            Literal::String(bytes) => {
+                let typ = self.ir_type_for_local_type(local_type, pos, tree, ctx, ir_tree)?;
+                Ok(Some(IrValue::Object(Box::new(IrObject::Struct(vec![IrValue::Object(Box::new(IrObject::String(bytes.clone()))), IrValue::SizeT(bytes.len() as u64, None)], Vec::new(), Some(typ))))))
+            },
+            Literal::Tuple(exprs) => {
+                let typ = self.ir_type_for_local_type(local_type, pos, tree, ctx, ir_tree)?;
+                let values = self.lower_exprs(exprs.as_slice(), tree, ctx, ir_tree)?;
+                if *typ == IrType::Void {
+                    return Ok(None);
+                }
+                let mut field_values: Vec<IrValue<IrArgVar>> = Vec::new();
+                for value in values {
+                    match value {
+                        Some(value) => field_values.push(value),
+                        None => return message_error(pos, String::from("unit type in tuple is unsupported")),
+                    }
+                }
+                Ok(Some(IrValue::Object(Box::new(IrObject::Struct(field_values, Vec::new(), Some(typ))))))
+            },
+            Literal::Array(exprs) => {
+                let typ = self.ir_type_for_local_type(local_type, pos, tree, ctx, ir_tree)?;
+                let values = self.lower_exprs(exprs.as_slice(), tree, ctx, ir_tree)?;
+                let mut elem_values: Vec<IrValue<IrArgVar>> = Vec::new();
+                for value in values {
+                    match value {
+                        Some(value) => elem_values.push(value),
+                        None => return message_error(pos, String::from("array of unit type is unsupported")),
+                    }
+                }
+                Ok(Some(IrValue::Object(Box::new(IrObject::Array(elem_values, Some(typ))))))
+            },
+            Literal::FilledArray(expr, len) => {
+                let typ = self.ir_type_for_local_type(local_type, pos, tree, ctx, ir_tree)?;
+                match self.lower_expr(expr, tree, ctx, ir_tree)? {
+                    Some(value) => Ok(Some(IrValue::Object(Box::new(IrObject::Array(vec![value; *len], Some(typ)))))),
+                    None => message_error(pos, String::from("array of unit type is unsupported")),
+                }
+            },
+        }
+    }
+
+    fn bind_pattern(&self, pattern: &Pattern, value: Option<IrValue<IrArgVar>>, tree: &Tree, ctx: &mut FunContext, ir_tree: &mut IrTree) -> BackendResult<()>
+    {
+        match pattern {
+            Pattern::Var(modifier, ident, Some(local_type), pos) => {
+                let idx = self.bind_var(*modifier, *local_type, value, pos, tree, ctx, ir_tree)?;
+                ctx.var_env.add_var(ident.clone(), idx);
+                Ok(())
+            },
+            Pattern::At(modifier, ident, pattern2, Some(local_type), pos) => {
+                let idx = self.bind_var(*modifier, *local_type, value, pos, tree, ctx, ir_tree)?;
+                ctx.var_env.add_var(ident.clone(), idx);
+                self.bind_pattern(pattern2, idx.map(local_var_value), tree, ctx, ir_tree)
+            },
+            Pattern::Wildcard(_, _) => Ok(()),
+            Pattern::Literal(literal, Some(local_type), pos) => {
+                match &**literal {
+                    Literal::Tuple(patterns) => {
+                        match value {
+                            Some(value) => {
+                                let typ = self.ir_type_for_local_type(*local_type, pos, tree, ctx, ir_tree)?;
+                                let var = self.value_to_arg_var(value, &typ, ctx);
+                                for (i, pattern2) in patterns.iter().enumerate() {
+                                    let field_value = IrValue::Object(Box::new(IrObject::Var(arg_var_with_ops(&var, &[IrArgOp::Dot(None, i)])?, None)));
+                                    self.bind_pattern(pattern2, Some(field_value), tree, ctx, ir_tree)?;
+                                }
+                                Ok(())
+                            },
+                            None => {
+                                if patterns.is_empty() {
+                                    Ok(())
+                                } else {
+                                    message_error(pos, String::from("unit type in tuple is unsupported"))
+                                }
+                            },
+                        }
+                    },
+                    _ => message_error(pos, String::from("refutable patterns in bindings are unsupported")),
+                }
+            },
+            _ => message_error(pattern_pos(pattern), String::from("refutable patterns in bindings are unsupported")),
+        }
+    }
+
+    fn bind_var(&self, modifier: VarModifier, local_type: LocalType, value: Option<IrValue<IrArgVar>>, pos: &Pos, tree: &Tree, ctx: &mut FunContext, ir_tree: &mut IrTree) -> BackendResult<Option<usize>>
+    {
+        let typ = self.ir_type_for_local_type(local_type, pos, tree, ctx, ir_tree)?;
+        match value {
+            Some(value) if *typ != IrType::Void => {
+                let ir_modifier = match modifier {
+                    VarModifier::None => IrLocalVarModifier::None,
+                    VarModifier::Private => IrLocalVarModifier::Private,
+                    VarModifier::Local => IrLocalVarModifier::Local,
+                    VarModifier::Global => IrLocalVarModifier::Global,
+                    VarModifier::Constant => return message_error(pos, String::from("constant local variables are unsupported")),
+                };
+                let idx = ctx.add_local_var(ir_modifier, typ);
+                ctx.add_instr(IrInstr::Assign(Box::new(IrInstrVar::Local(idx, Vec::new())), IrOp::Load(value)));
+                Ok(Some(idx))
+            },
+            _ => Ok(None),
+        }
+    }
+
+    fn var_target(&self, ident: &String, local_type: LocalType, pos: &Pos, tree: &Tree, ctx: &mut FunContext) -> BackendResult<VarTarget>
+    {
+        if let Some(idx) = ctx.var_env.var(ident) {
+            return Ok(VarTarget::Local(*idx));
+        }
+        let type_name = match type_name_for_var_ident_and_local_type(ident, local_type, tree, &mut ctx.type_stack, ctx.local_types) {
+            Ok(tmp_type_name) => tmp_type_name,
+            Err(errs) => return Err(BackendError::Internal(format!("{}", errs))),
+        };
+        let var = match tree.var(ident) {
+            Some(tmp_var) => tmp_var,
+            None => return Err(BackendError::Internal(String::from("var_target: no variable"))),
+        };
+        let var_r = var.borrow();
+        let (trait_ident, target) = match &*var_r {
+            Var::Builtin(tmp_trait_ident, _) => (tmp_trait_ident, VarTarget::Builtin(String::from(unqualified_ident(ident.as_str())), None)),
+            Var::Var(_, _, _, _, tmp_trait_ident, _, _, _, _) => (tmp_trait_ident, VarTarget::Var(ident.clone(), None)),
+            Var::Fun(fun, tmp_trait_ident, _) => {
+                match &**fun {
+                    Fun::Fun(_, _, _, _, _, _, _) => (tmp_trait_ident, VarTarget::Fun(ident.clone(), None)),
+                    Fun::Con(_) => (tmp_trait_ident, VarTarget::Con(ident.clone())),
+                }
+            },
+        };
+        match (trait_ident, type_name) {
+            (Some(trait_ident), Some(type_name)) => {
+                let trait1 = match tree.trait1(trait_ident) {
+                    Some(tmp_trait) => tmp_trait,
+                    None => return Err(BackendError::Internal(String::from("var_target: no trait"))),
+                };
+                let trait_r = trait1.borrow();
+                let trait_vars = match &*trait_r {
+                    Trait(_, _, Some(tmp_trait_vars)) => tmp_trait_vars,
+                    _ => return Err(BackendError::Internal(String::from("var_target: no trait variables"))),
+                };
+                let type_name2 = match type_name {
+                    TypeName::Array(Some(_)) if trait_vars.impl1(&type_name).is_none() => TypeName::Array(None),
+                    _ => type_name,
+                };
+                let impl1 = match trait_vars.impl1(&type_name2) {
+                    Some(tmp_impl) => tmp_impl,
+                    None => return Err(BackendError::Internal(String::from("var_target: no implementation"))),
+                };
+                let impl_r = impl1.borrow();
+                let impl_vars = match &*impl_r {
+                    Impl::Builtin(_, _, Some(tmp_impl_vars)) => tmp_impl_vars,
+                    Impl::Impl(_, _, _, Some(tmp_impl_vars)) => tmp_impl_vars,
+                    _ => return Err(BackendError::Internal(String::from("var_target: no implementation variables"))),
+                };
+                match impl_vars.var(ident) {
+                    Some(impl_var) => {
+                        let impl_var_r = impl_var.borrow();
+                        match &*impl_var_r {
+                            ImplVar::Builtin(_) => Ok(VarTarget::Builtin(String::from(unqualified_ident(ident.as_str())), Some(unqualified_type_name(&type_name2)))),
+                            ImplVar::Var(_, _, _, _, _) => Ok(VarTarget::Var(ident.clone(), Some(type_name2))),
+                            ImplVar::Fun(_, _) => Ok(VarTarget::Fun(ident.clone(), Some(type_name2))),
+                        }
+                    },
+                    None => {
+                        match target {
+                            VarTarget::Var(ident2, None) => Ok(VarTarget::Var(ident2, Some(type_name2))),
+                            VarTarget::Fun(ident2, None) => Ok(VarTarget::Fun(ident2, Some(type_name2))),
+                            target2 => Ok(target2),
+                        }
+                    },
+                }
+            },
+            (Some(_), None) => message_error(pos, format!("can't determine implementation for {}", ident)),
+            (None, _) => Ok(target),
+        }
+    }
+
+    fn var_name(&self, ident: &String, type_name: &Option<TypeName>, local_type: LocalType, pos: &Pos, tree: &Tree, ctx: &mut FunContext) -> BackendResult<String>
+    {
+        let type_value = ctx.type_value(local_type)?;
+        let name = match tree.var(ident) {
+            Some(var) => {
+                let var_r = var.borrow();
+                match &*var_r {
+                    Var::Var(_, _, _, _, _, _, _, Some(typ), _) => mangle_var_name(ident.as_str(), type_values_for_type(typ, &type_value)?.as_slice(), typ)?,
+                    _ => return Err(BackendError::Internal(String::from("var_name: variable isn't variable or no type"))),
+                }
+            },
+            None => return Err(BackendError::Internal(String::from("var_name: no variable"))),
+        };
+        ctx.insts.push(Inst::Var(name.clone(), ident.clone(), type_name.clone(), type_value, pos.clone()));
+        Ok(name)
+    }
+
+    fn fun_name(&self, ident: &String, type_name: &Option<TypeName>, local_type: LocalType, pos: &Pos, tree: &Tree, ctx: &mut FunContext) -> BackendResult<String>
+    {
+        let type_value = ctx.type_value(local_type)?;
+        let name = match tree.var(ident) {
+            Some(var) => {
+                let var_r = var.borrow();
+                match &*var_r {
+                    Var::Fun(_, _, Some(typ)) => mangle_fun_name(ident.as_str(), type_values_for_type(typ, &type_value)?.as_slice(), typ)?,
+                    _ => return Err(BackendError::Internal(String::from("fun_name: variable isn't function or no type"))),
+                }
+            },
+            None => return Err(BackendError::Internal(String::from("fun_name: no variable"))),
+        };
+        ctx.insts.push(Inst::Fun(name.clone(), ident.clone(), type_name.clone(), type_value, pos.clone()));
+        Ok(name)
+    }
+
+    fn lower_app(&self, fun_expr: &Expr, arg_exprs: &[Box<Expr>], local_type: LocalType, pos: &Pos, tree: &Tree, ctx: &mut FunContext, ir_tree: &mut IrTree) -> BackendResult<Option<IrValue<IrArgVar>>>
+    {
+        let typ = self.ir_type_for_local_type(local_type, pos, tree, ctx, ir_tree)?;
+        match fun_expr {
+            Expr::Var(ident, Some(fun_local_type), fun_pos) => {
+                match self.var_target(ident, *fun_local_type, fun_pos, tree, ctx)? {
+                    VarTarget::Builtin(ident2, type_name) => {
+                        let mut arg_types: Vec<Box<IrType>> = Vec::new();
+                        for arg_expr in arg_exprs {
+                            let arg_local_type = expr_local_type(arg_expr)?;
+                            arg_types.push(self.ir_type_for_local_type(arg_local_type, expr_pos(arg_expr), tree, ctx, ir_tree)?);
+                        }
+                        let arg_values = self.lower_exprs(arg_exprs, tree, ctx, ir_tree)?;
+                        self.lower_builtin_app(&ident2, &type_name, arg_values, arg_types.as_slice(), typ, pos, ctx, ir_tree)
+                    },
+                    VarTarget::Fun(ident2, type_name) => {
+                        let name = self.fun_name(&ident2, &type_name, *fun_local_type, fun_pos, tree, ctx)?;
+                        let arg_values = self.lower_exprs(arg_exprs, tree, ctx, ir_tree)?;
+                        let arg_values2: Vec<IrValue<IrArgVar>> = arg_values.into_iter().flatten().collect();
+                        Ok(self.assign_to_new_local_var(IrOp::CallFun(name, arg_values2, pos.clone(), Vec::new()), typ, ctx))
+                    },
+                    VarTarget::Con(ident2) => {
+                        let arg_values = self.lower_exprs(arg_exprs, tree, ctx, ir_tree)?;
+                        self.lower_con_app(&ident2, arg_values, typ, local_type, pos, tree, ctx, ir_tree)
+                    },
+                    _ => self.lower_fun_value_app(fun_expr, arg_exprs, typ, pos, tree, ctx, ir_tree),
+                }
+            },
+            _ => self.lower_fun_value_app(fun_expr, arg_exprs, typ, pos, tree, ctx, ir_tree),
+        }
+    }
+
+    fn lower_fun_value_app(&self, fun_expr: &Expr, arg_exprs: &[Box<Expr>], typ: Box<IrType>, pos: &Pos, tree: &Tree, ctx: &mut FunContext, ir_tree: &mut IrTree) -> BackendResult<Option<IrValue<IrArgVar>>>
+    {
+        let fun_local_type = expr_local_type(fun_expr)?;
+        let type_value = type_value_without_uniq_flags(&ctx.type_value(fun_local_type)?);
+        let caller_name = self.add_caller(&type_value, pos, tree, ir_tree)?;
+        let fun_value = match self.lower_expr(fun_expr, tree, ctx, ir_tree)? {
+            Some(tmp_fun_value) => tmp_fun_value,
+            None => return Err(BackendError::Internal(String::from("lower_fun_value_app: no function value"))),
+        };
+        let arg_values = self.lower_exprs(arg_exprs, tree, ctx, ir_tree)?;
+        let mut values = vec![fun_value];
+        values.extend(arg_values.into_iter().flatten());
+        Ok(self.assign_to_new_local_var(IrOp::CallFun(caller_name, values, pos.clone(), Vec::new()), typ, ctx))
+    }
+
+    fn lower_printf_app(&self, exprs: &[Box<Expr>], local_type: LocalType, pos: &Pos, tree: &Tree, ctx: &mut FunContext, ir_tree: &mut IrTree) -> BackendResult<Option<IrValue<IrArgVar>>>
+    {
+        let items = match exprs.first().map(|expr| &**expr) {
+            Some(Expr::Literal(literal, _, _)) => {
+                match &**literal {
+                    Literal::String(bs) => {
+                        match parse_printf_format(bs.as_slice()) {
+                            Ok(items) => items,
+                            Err(msg) => return message_error(pos, msg),
+                        }
+                    },
+                    _ => return message_error(pos, String::from("printf takes first argument that must be string literal")),
+                }
+            },
+            _ => return message_error(pos, String::from("printf takes first argument that must be string literal")),
+        };
+        let mut format: Vec<u8> = Vec::new();
+        let mut values: Vec<IrValue<IrArgVar>> = Vec::new();
+        let mut arg_exprs = exprs[1..].iter();
+        for item in &items {
+            match item {
+                PrintfItem::Bytes(bs) => format.extend_from_slice(bs.as_slice()),
+                PrintfItem::Conv(conv) => {
+                    match arg_exprs.next() {
+                        Some(arg_expr) => {
+                            let arg_local_type = expr_local_type(arg_expr)?;
+                            let type_ident = match &*ctx.type_value(arg_local_type)? {
+                                TypeValue::Type(_, TypeValueName::Name(ident), _) => ident.clone(),
+                                _ => return Err(BackendError::Internal(String::from("lower_printf_app: invalid argument type"))),
+                            };
+                            format.extend_from_slice(printf_conv_to_string(conv, type_ident.as_str()).as_bytes());
+                            let arg_type = self.ir_type_for_local_type(arg_local_type, expr_pos(arg_expr), tree, ctx, ir_tree)?;
+                            match self.lower_expr(arg_expr, tree, ctx, ir_tree)? {
+                                Some(value) => {
+                                    if conv.conv == 's' {
+                                        let is_nul_terminated = match &**arg_expr {
+                                            Expr::Var(ident, _, _) => ctx.var_env.var(ident).is_none() && is_string_literal_var(ident, tree),
+                                            _ => is_string_literal_expr(arg_expr),
+                                        };
+                                        if !is_nul_terminated {
+                                            return message_error(expr_pos(arg_expr), format!("conversion specification {} requires string literal or variable with string literal", conv.spec));
+                                        }
+                                        let var = self.value_to_arg_var(value, &arg_type, ctx);
+                                        values.push(IrValue::Object(Box::new(IrObject::Var(arg_var_with_ops(&var, &[IrArgOp::Dot(None, 0)])?, None))));
+                                    } else {
+                                        values.push(value);
+                                    }
+                                },
+                                None => return Err(BackendError::Internal(String::from("lower_printf_app: no argument value"))),
+                            }
+                        },
+                        None => return message_error(pos, String::from("too few arguments for printf format")),
+                    }
+                },
+            }
+        }
+        if arg_exprs.next().is_some() {
+            return message_error(pos, String::from("too many arguments for printf format"));
+        }
+        values.insert(0, IrValue::Object(Box::new(IrObject::String(format))));
+        let typ = self.ir_type_for_local_type(local_type, pos, tree, ctx, ir_tree)?;
+        Ok(self.assign_to_new_local_var(IrOp::CallBuiltinFun(String::from("printf"), None, values), typ, ctx))
+    }
+
+    fn lower_named_field_con_app(&self, ident: &String, expr_named_field_pairs: &[NamedFieldPair<Expr>], local_type: LocalType, pos: &Pos, tree: &Tree, ctx: &mut FunContext, ir_tree: &mut IrTree) -> BackendResult<Option<IrValue<IrArgVar>>>
+    {
+        let typ = self.ir_type_for_local_type(local_type, pos, tree, ctx, ir_tree)?;
+        let mut field_idxs: Vec<usize> = Vec::new();
+        match tree.var(ident) {
+            Some(var) => {
+                let var_r = var.borrow();
+                match &*var_r {
+                    Var::Fun(fun, _, _) => {
+                        match &**fun {
+                            Fun::Con(con) => {
+                                let con_r = con.borrow();
+                                match &*con_r {
+                                    Con::NamedField(_, _, _, _, Some(named_fields), _) => {
+                                        for expr_named_field_pair in expr_named_field_pairs {
+                                            match expr_named_field_pair {
+                                                NamedFieldPair(field_ident, _, _) => {
+                                                    match named_fields.field_index(field_ident) {
+                                                        Some(field_idx) => field_idxs.push(field_idx),
+                                                        None => return Err(BackendError::Internal(String::from("lower_named_field_con_app: no field index"))),
+                                                    }
+                                                },
+                                            }
+                                        }
+                                    },
+                                    _ => return Err(BackendError::Internal(String::from("lower_named_field_con_app: constructor isn't named field constructor or no named fields"))),
+                                }
+                            },
+                            _ => return Err(BackendError::Internal(String::from("lower_named_field_con_app: function isn't constructor"))),
+                        }
+                    },
+                    _ => return Err(BackendError::Internal(String::from("lower_named_field_con_app: variable isn't function"))),
+                }
+            },
+            None => return Err(BackendError::Internal(String::from("lower_named_field_con_app: no variable"))),
+        }
+        let mut values: Vec<Option<IrValue<IrArgVar>>> = vec![None; expr_named_field_pairs.len()];
+        for (expr_named_field_pair, field_idx) in expr_named_field_pairs.iter().zip(field_idxs.iter()) {
+            match expr_named_field_pair {
+                NamedFieldPair(_, expr, _) => {
+                    match values.get_mut(*field_idx) {
+                        Some(value) => *value = self.lower_expr(expr, tree, ctx, ir_tree)?,
+                        None => return Err(BackendError::Internal(String::from("lower_named_field_con_app: no value"))),
+                    }
+                },
+            }
+        }
+        self.lower_con_app(ident, values, typ, local_type, pos, tree, ctx, ir_tree)
+    }
+
+    fn lower_con_app(&self, ident: &String, values: Vec<Option<IrValue<IrArgVar>>>, typ: Box<IrType>, local_type: LocalType, pos: &Pos, tree: &Tree, ctx: &FunContext, ir_tree: &IrTree) -> BackendResult<Option<IrValue<IrArgVar>>>
+    {
+        let mut field_values: Vec<IrValue<IrArgVar>> = Vec::new();
+        for value in values {
+            match value {
+                Some(value) => field_values.push(value),
+                None => return message_error(pos, format!("unit type in constructor {} is unsupported", ident)),
+            }
+        }
+        let data_ident = match type_value_ident(&ctx.type_value(local_type)?) {
+            Some(tmp_data_ident) => String::from(tmp_data_ident),
+            None => return Err(BackendError::Internal(String::from("lower_con_app: type value isn't data type"))),
+        };
+        let cons = data_cons(&data_ident, tree)?;
+        let con_idx = match cons.iter().position(|con| con_ident(&con.borrow()) == ident) {
+            Some(tmp_con_idx) => tmp_con_idx,
+            None => return Err(BackendError::Internal(String::from("lower_con_app: no constructor"))),
+        };
+        let union_field_idx = cons[0..con_idx].iter().filter(|con| con_field_count(&con.borrow()) != 0).count();
+        if cons.iter().all(|con| con_field_count(&con.borrow()) == 0) {
+            return Ok(Some(IrValue::Uint(con_idx as u32, None)));
+        }
+        if cons.len() == 1 {
+            return Ok(Some(IrValue::Object(Box::new(IrObject::Struct(field_values, Vec::new(), Some(typ))))));
+        }
+        let tag_value = IrValue::Uint(con_idx as u32, None);
+        if field_values.is_empty() {
+            return Ok(Some(IrValue::Object(Box::new(IrObject::Struct(vec![tag_value], Vec::new(), Some(typ))))));
+        }
+        let union_type = match &*typ {
+            IrType::Struct(struct_ident) => {
+                match ir_tree.struct1(struct_ident) {
+                    Some(struct1) => {
+                        match &*struct1.borrow() {
+                            IrStruct::Struct(field_types) if field_types.len() == 2 => field_types[1].clone(),
+                            _ => return Err(BackendError::Internal(String::from("lower_con_app: no union field type"))),
+                        }
+                    },
+                    None => return Err(BackendError::Internal(String::from("lower_con_app: no structure"))),
+                }
+            },
+            _ => return Err(BackendError::Internal(String::from("lower_con_app: type isn't structure"))),
+        };
+        let field_struct_type = match &*union_type {
+            IrType::Union(union_ident) => {
+                match ir_tree.union(union_ident) {
+                    Some(union) => {
+                        match union.borrow().0.get(union_field_idx) {
+                            Some(field_type) => field_type.clone(),
+                            None => return Err(BackendError::Internal(String::from("lower_con_app: no union field type"))),
+                        }
+                    },
+                    None => return Err(BackendError::Internal(String::from("lower_con_app: no union"))),
+                }
+            },
+            _ => return Err(BackendError::Internal(String::from("lower_con_app: field type isn't union"))),
+        };
+        let field_struct_value = IrValue::Object(Box::new(IrObject::Struct(field_values, Vec::new(), Some(field_struct_type))));
+        let union_value = IrValue::Object(Box::new(IrObject::Union(union_field_idx, field_struct_value, Some(union_type))));
+        Ok(Some(IrValue::Object(Box::new(IrObject::Struct(vec![tag_value], vec![IrFieldPair(1, union_value)], Some(typ))))))
+    }
+
+    fn lower_builtin_app(&self, ident: &String, type_name: &Option<TypeName>, arg_values: Vec<Option<IrValue<IrArgVar>>>, arg_types: &[Box<IrType>], typ: Box<IrType>, pos: &Pos, ctx: &mut FunContext, ir_tree: &IrTree) -> BackendResult<Option<IrValue<IrArgVar>>>
+    {
+        let mut values: Vec<IrValue<IrArgVar>> = Vec::new();
+        let mut types: Vec<Box<IrType>> = Vec::new();
+        for (arg_value, arg_type) in arg_values.into_iter().zip(arg_types.iter()) {
+            if let Some(arg_value) = arg_value {
+                values.push(arg_value);
+                types.push(arg_type.clone());
+            }
+        }
+        let is_bool = match type_name {
+            Some(TypeName::Name(type_ident)) => type_ident == "Bool",
+            _ => false,
+        };
+        match (ident.as_str(), values.as_slice()) {
+            ("op_neg", [x]) => Ok(self.assign_to_new_local_var(IrOp::Neg(x.clone()), typ, ctx)),
+            ("op_not", [x]) if is_bool => Ok(self.assign_to_new_local_var(IrOp::Eq(x.clone(), IrValue::Int(0, None)), typ, ctx)),
+            ("op_not", [x]) => Ok(self.assign_to_new_local_var(IrOp::Not(x.clone()), typ, ctx)),
+            ("op_mul", [x, y]) => Ok(self.assign_to_new_local_var(IrOp::Mul(x.clone(), y.clone()), typ, ctx)),
+            ("op_div", [x, y]) => Ok(self.assign_to_new_local_var(IrOp::Div(x.clone(), y.clone()), typ, ctx)),
+            ("op_rem", [x, y]) => Ok(self.assign_to_new_local_var(IrOp::Rem(x.clone(), y.clone()), typ, ctx)),
+            ("op_add", [x, y]) => Ok(self.assign_to_new_local_var(IrOp::Add(x.clone(), y.clone()), typ, ctx)),
+            ("op_sub", [x, y]) => Ok(self.assign_to_new_local_var(IrOp::Sub(x.clone(), y.clone()), typ, ctx)),
+            ("op_shl", [x, y]) => Ok(self.assign_to_new_local_var(IrOp::Shl(x.clone(), y.clone()), typ, ctx)),
+            ("op_shr", [x, y]) => Ok(self.assign_to_new_local_var(IrOp::Shr(x.clone(), y.clone()), typ, ctx)),
+            ("op_and", [x, y]) => Ok(self.assign_to_new_local_var(IrOp::And(x.clone(), y.clone()), typ, ctx)),
+            ("op_xor", [x, y]) => Ok(self.assign_to_new_local_var(IrOp::Xor(x.clone(), y.clone()), typ, ctx)),
+            ("op_or", [x, y]) => Ok(self.assign_to_new_local_var(IrOp::Or(x.clone(), y.clone()), typ, ctx)),
+            ("op_eq" | "op_ne", [x, y]) if vector_comparison_type(&types[0]).is_some() => {
+                let cmp_type = vector_comparison_type(&types[0]).map(Box::new);
+                let (op, fun_ident) = if ident == "op_eq" {
+                    (IrOp::Eq(x.clone(), y.clone()), "all")
+                } else {
+                    (IrOp::Ne(x.clone(), y.clone()), "any")
+                };
+                match cmp_type.and_then(|t| self.assign_to_new_local_var(op, t, ctx)) {
+                    Some(cmp_value) => Ok(self.assign_to_new_local_var(IrOp::CallBuiltinFun(String::from(fun_ident), None, vec![cmp_value]), typ, ctx)),
+                    None => Err(BackendError::Internal(String::from("lower_builtin_app: no comparison value"))),
+                }
+            },
+            ("op_eq", [x, y]) => Ok(self.assign_to_new_local_var(IrOp::Eq(x.clone(), y.clone()), typ, ctx)),
+            ("op_ne", [x, y]) => Ok(self.assign_to_new_local_var(IrOp::Ne(x.clone(), y.clone()), typ, ctx)),
+            ("op_lt", [x, y]) => Ok(self.assign_to_new_local_var(IrOp::Lt(x.clone(), y.clone()), typ, ctx)),
+            ("op_ge", [x, y]) => Ok(self.assign_to_new_local_var(IrOp::Ge(x.clone(), y.clone()), typ, ctx)),
+            ("op_gt", [x, y]) => Ok(self.assign_to_new_local_var(IrOp::Gt(x.clone(), y.clone()), typ, ctx)),
+            ("op_le", [x, y]) => Ok(self.assign_to_new_local_var(IrOp::Le(x.clone(), y.clone()), typ, ctx)),
+            ("op_get", [r]) => {
+                let var = self.value_to_arg_var(r.clone(), &types[0], ctx);
+                Ok(Some(IrValue::Object(Box::new(IrObject::Var(arg_var_with_ops(&var, &[IrArgOp::Deref(None)])?, None)))))
+            },
+            ("op_get2", [r]) => {
+                let var = self.value_to_arg_var(r.clone(), &types[0], ctx);
+                let elem_value = IrValue::Object(Box::new(IrObject::Var(arg_var_with_ops(&var, &[IrArgOp::Deref(None)])?, None)));
+                let ref_value = IrValue::Object(Box::new(IrObject::Var(var, None)));
+                Ok(Some(IrValue::Object(Box::new(IrObject::Struct(vec![elem_value, ref_value], Vec::new(), Some(typ))))))
+            },
+            ("op_set", [r, x]) => {
+                let var = self.value_to_arg_var(r.clone(), &types[0], ctx);
+                let instr_var = instr_var_for_arg_var(&arg_var_with_ops(&var, &[IrArgOp::Deref(None)])?)?;
+                ctx.add_instr(IrInstr::Assign(Box::new(instr_var), IrOp::Load(x.clone())));
+                Ok(Some(IrValue::Object(Box::new(IrObject::Var(var, None)))))
+            },
+            ("op_get_nth" | "op_get2_nth" | "op_set_nth" | "get_ref" | "get_private_ref" | "get_local_ref" | "get_global_ref" | "get_constant_ref", [a, i, ..]) => {
+                let var = self.value_to_arg_var(a.clone(), &types[0], ctx);
+                let index_op = self.value_to_index_op(i.clone(), ctx);
+                let elem_var = match &*types[0] {
+                    IrType::Array(_, _) => arg_var_with_ops(&var, &[index_op])?,
+                    _ => arg_var_with_ops(&var, &[IrArgOp::Dot(None, 0), index_op])?,
+                };
+                match (ident.as_str(), &values[2..]) {
+                    ("op_get_nth", []) => Ok(Some(IrValue::Object(Box::new(IrObject::Var(elem_var, None))))),
+                    ("op_get2_nth", []) => {
+                        let elem_value = IrValue::Object(Box::new(IrObject::Var(elem_var, None)));
+                        let var_value = IrValue::Object(Box::new(IrObject::Var(var, None)));
+                        Ok(Some(IrValue::Object(Box::new(IrObject::Struct(vec![elem_value, var_value], Vec::new(), Some(typ))))))
+                    },
+                    ("op_set_nth", [x]) => {
+                        ctx.add_instr(IrInstr::Assign(Box::new(instr_var_for_arg_var(&elem_var)?), IrOp::Load(x.clone())));
+                        Ok(Some(IrValue::Object(Box::new(IrObject::Var(var, None)))))
+                    },
+                    (_, []) => Ok(Some(IrValue::Object(Box::new(IrObject::Var(ref_arg_var(&elem_var)?, None))))),
+                    (_, _) => Err(BackendError::Internal(String::from("lower_builtin_app: invalid number of arguments"))),
+                }
+            },
+            ("len" | "len2", [a]) => {
+                let len_value = match &*types[0] {
+                    IrType::Array(_, len) => IrValue::SizeT(*len as u64, None),
+                    _ => {
+                        let var = self.value_to_arg_var(a.clone(), &types[0], ctx);
+                        IrValue::Object(Box::new(IrObject::Var(arg_var_with_ops(&var, &[IrArgOp::Dot(None, 1)])?, None)))
+                    },
+                };
+                if ident == "len" {
+                    Ok(Some(len_value))
+                } else {
+                    Ok(Some(IrValue::Object(Box::new(IrObject::Struct(vec![len_value, a.clone()], Vec::new(), Some(typ))))))
+                }
+            },
+            ("ref" | "private_ref" | "uniq_ref" | "uniq_private_ref", [x]) => {
+                let idx = ctx.add_local_var(IrLocalVarModifier::None, types[0].clone());
+                ctx.add_instr(IrInstr::Assign(Box::new(IrInstrVar::Local(idx, Vec::new())), IrOp::Load(x.clone())));
+                Ok(Some(IrValue::Object(Box::new(IrObject::Var(IrArgVar::RefLocal(idx, Vec::new(), None), None)))))
+            },
+            ("uniq_private_box" | "uniq_local_box" | "uniq_global_box", [x]) => {
+                let (heap_var, alloc_fun_ident, heap_ident) = match ident.as_str() {
+                    "uniq_private_box" => (IrArgVar::PrivateHeap(Vec::new()), mangle_private_alloc_fun_name(), "private"),
+                    "uniq_local_box" => (IrArgVar::LocalHeap(Vec::new()), mangle_local_alloc_fun_name(), "local"),
+                    _ => (IrArgVar::GlobalHeap(Vec::new()), mangle_global_alloc_fun_name(), "global"),
+                };
+                let idx = ctx.add_local_var(IrLocalVarModifier::None, typ);
+                let heap_value = IrValue::Object(Box::new(IrObject::Var(heap_var, None)));
+                ctx.add_instr(IrInstr::Assign(Box::new(IrInstrVar::Local(idx, Vec::new())), IrOp::CallBuiltinFun(alloc_fun_ident, Some(types[0].clone()), vec![heap_value])));
+                let mut panic_block = IrBlock::new();
+                panic_block.add_instr(IrInstr::Panic(format!("out of {} heap memory", heap_ident), pos.clone(), Vec::new()));
+                ctx.add_instr(IrInstr::If(IrOp::Eq(local_var_value(idx), IrValue::Int(0, None)), Box::new(panic_block), Box::new(IrBlock::new())));
+                ctx.add_instr(IrInstr::Assign(Box::new(IrInstrVar::Local(idx, vec![IrArgOp::Deref(None)])), IrOp::Load(x.clone())));
+                Ok(Some(local_var_value(idx)))
+            },
+            ("slice" | "private_slice" | "uniq_slice" | "uniq_private_slice", [a]) => {
+                match &*types[0] {
+                    IrType::Array(_, len) => {
+                        let idx = ctx.add_local_var(IrLocalVarModifier::None, types[0].clone());
+                        ctx.add_instr(IrInstr::Assign(Box::new(IrInstrVar::Local(idx, Vec::new())), IrOp::Load(a.clone())));
+                        let ptr_value = IrValue::Object(Box::new(IrObject::Var(IrArgVar::RefLocal(idx, vec![IrArgOp::Index(None, 0)], None), None)));
+                        Ok(Some(IrValue::Object(Box::new(IrObject::Struct(vec![ptr_value, IrValue::SizeT(*len as u64, None)], Vec::new(), Some(typ))))))
+                    },
+                    _ => message_error(pos, format!("builtin function {} is unsupported for this type", ident)),
+                }
+            },
+            (_, [x]) if ident.ends_with("_from_uniq") || ident.contains("_from_uniq_") || ident.ends_with("_from_private") || ident.ends_with("_from_local") || ident.ends_with("_from_global") => {
+                Ok(Some(x.clone()))
+            },
+            ("uninit", []) => {
+                if *typ == IrType::Void {
+                    Ok(None)
+                } else {
+                    let idx = ctx.add_local_var(IrLocalVarModifier::None, typ);
+                    Ok(Some(local_var_value(idx)))
+                }
+            },
+            ("zero", []) => {
+                match zero_value(&typ) {
+                    Some(value) => Ok(Some(value)),
+                    None => message_error(pos, String::from("zero value of this type is unsupported")),
+                }
+            },
+            _ => {
+                if vector_con_ident(&typ).as_ref() == Some(ident) {
+                    return Ok(Some(IrValue::Object(Box::new(IrObject::Vector(values, typ)))));
+                }
+                self.lower_builtin_fun_app(ident, values, types.as_slice(), typ, pos, ctx, ir_tree)
+            },
+        }
+    }
+
+    fn lower_builtin_fun_app(&self, ident: &String, values: Vec<IrValue<IrArgVar>>, types: &[Box<IrType>], typ: Box<IrType>, pos: &Pos, ctx: &mut FunContext, ir_tree: &IrTree) -> BackendResult<Option<IrValue<IrArgVar>>>
+    {
+        for arg_type in types {
+            match &**arg_type {
+                IrType::Ptr(_, _, _) | IrType::Array(_, _) => return message_error(pos, format!("builtin function {} is unsupported", ident)),
+                _ => (),
+            }
+        }
+        let vector_fun_ident = ["_vload", "_vloada", "_vstore", "_vstorea"].iter().find_map(|s| {
+                ident.find(s).map(|i| String::from(&ident[(i + 1)..]))
+        });
+        match vector_fun_ident {
+            Some(vector_fun_ident) => {
+                let (fun_ident, is_uniq) = match vector_fun_ident.strip_suffix("_uniq") {
+                    Some(tmp_fun_ident) => (String::from(tmp_fun_ident), true),
+                    None => (vector_fun_ident.clone(), false),
+                };
+                let slice_idx = values.len() - 1;
+                let slice_var = self.value_to_arg_var(values[slice_idx].clone(), &types[slice_idx], ctx);
+                let ptr_value = IrValue::Object(Box::new(IrObject::Var(arg_var_with_ops(&slice_var, &[IrArgOp::Dot(None, 0)])?, None)));
+                let mut new_values = values[..slice_idx].to_vec();
+                new_values.push(ptr_value);
+                let slice_value = IrValue::Object(Box::new(IrObject::Var(slice_var, None)));
+                if fun_ident.starts_with("vstore") {
+                    ctx.add_instr(IrInstr::Op(IrOp::CallBuiltinFun(fun_ident, None, new_values)));
+                    Ok(Some(slice_value))
+                } else if is_uniq {
+                    let elem_type = match &*typ {
+                        IrType::Struct(struct_ident) => {
+                            match ir_tree.struct1(struct_ident) {
+                                Some(struct1) => {
+                                    match &*struct1.borrow() {
+                                        IrStruct::Struct(field_types) if !field_types.is_empty() => field_types[0].clone(),
+                                        _ => return Err(BackendError::Internal(String::from("lower_builtin_fun_app: no field type"))),
+                                    }
+                                },
+                                None => return Err(BackendError::Internal(String::from("lower_builtin_fun_app: no structure"))),
+                            }
+                        },
+                        _ => return Err(BackendError::Internal(String::from("lower_builtin_fun_app: type isn't structure"))),
+                    };
+                    match self.assign_to_new_local_var(IrOp::CallBuiltinFun(fun_ident, None, new_values), elem_type, ctx) {
+                        Some(elem_value) => Ok(Some(IrValue::Object(Box::new(IrObject::Struct(vec![elem_value, slice_value], Vec::new(), Some(typ)))))),
+                        None => Err(BackendError::Internal(String::from("lower_builtin_fun_app: no loaded value"))),
+                    }
+                } else {
+                    Ok(self.assign_to_new_local_var(IrOp::CallBuiltinFun(fun_ident, None, new_values), typ, ctx))
+                }
+            },
+            None => Ok(self.assign_to_new_local_var(IrOp::CallBuiltinFun(ident.clone(), None, values), typ, ctx)),
+        }
+    }
+}
+
+impl Default for Lowerer
+{
+    fn default() -> Self
+    { Self::new() }
+}
+
+pub fn lower_tree(tree: &Tree) -> BackendResult<IrTree>
+{
+    let lowerer = Lowerer::new();
+    lowerer.lower_tree(tree)
+}
+
+#[cfg(test)]
+mod tests;
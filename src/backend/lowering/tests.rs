@@ -0,0 +1,831 @@
+//
+// Copyright (c) 2025 Łukasz Szpakowski
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+use crate::frontend::do_frontend_phases;
+use super::*;
+
+#[test]
+fn test_lowerer_lower_tree_lowers_function()
+{
+    let s = "
+f(x: Int, y: Int) -> Int = if x < y then x + 1 else y * 2;
+kernel k(x: Int, y: Int) -> () = let _ = f(x, y) in ();
+";
+    let s2 = &s[1..];
+    let tree = match do_frontend_phases(s2) {
+        Ok(tree) => tree,
+        Err(errs) => {
+            println!("{}", errs);
+            panic!();
+        },
+    };
+    match Lowerer::new_with_inline_threshold(0).lower_tree(&tree) {
+        Ok(ir_tree) => {
+            match ir_tree.var(&String::from("_VF1f")) {
+                Some(var) => {
+                    match &*var.borrow() {
+                        IrVar::Fun(fun) => {
+                            match &**fun {
+                                IrFun::Fun(IrFunModifier::None, arg_types, ret_type, block, _, _, _, _) => {
+                                    assert_eq!(vec![Box::new(IrType::Int), Box::new(IrType::Int)], *arg_types);
+                                    assert_eq!(IrType::Int, **ret_type);
+                                    assert_eq!(4, block.local_var_pairs().len());
+                                    match block.instrs().first() {
+                                        Some(IrInstr::Assign(var, IrOp::Lt(_, _))) => {
+                                            match &**var {
+                                                IrInstrVar::Local(2, ops) => assert!(ops.is_empty()),
+                                                _ => assert!(false),
+                                            }
+                                        },
+                                        _ => assert!(false),
+                                    }
+                                    match block.instrs().get(1) {
+                                        Some(IrInstr::If(_, then_block, else_block)) => {
+                                            match then_block.instrs().first() {
+                                                Some(IrInstr::Assign(_, IrOp::Add(_, _))) => assert!(true),
+                                                _ => assert!(false),
+                                            }
+                                            match else_block.instrs().first() {
+                                                Some(IrInstr::Assign(_, IrOp::Mul(_, _))) => assert!(true),
+                                                _ => assert!(false),
+                                            }
+                                        },
+                                        _ => assert!(false),
+                                    }
+                                    match block.instrs().last() {
+                                        Some(IrInstr::Return(Some(IrOp::Load(_)))) => assert!(true),
+                                        _ => assert!(false),
+                                    }
+                                },
+                                _ => assert!(false),
+                            }
+                        },
+                        _ => assert!(false),
+                    }
+                },
+                None => assert!(false),
+            }
+        },
+        Err(_) => assert!(false),
+    }
+}
+
+#[test]
+fn test_lowerer_lower_tree_lowers_function_with_tuple()
+{
+    let s = "
+g(t: (Int, Float)) -> Float = let (a, b) = t in b * (a as Float);
+kernel k(x: Int, y: Float) -> () = let _ = g((x, y)) in ();
+";
+    let s2 = &s[1..];
+    let tree = match do_frontend_phases(s2) {
+        Ok(tree) => tree,
+        Err(errs) => {
+            println!("{}", errs);
+            panic!();
+        },
+    };
+    match Lowerer::new_with_inline_threshold(0).lower_tree(&tree) {
+        Ok(ir_tree) => {
+            let struct_ident = String::from("_VSL3IntNPE5FloatNPR");
+            match ir_tree.struct1(&struct_ident) {
+                Some(struct1) => {
+                    match &*struct1.borrow() {
+                        IrStruct::Struct(field_types) => assert_eq!(vec![Box::new(IrType::Int), Box::new(IrType::Float)], *field_types),
+                        _ => assert!(false),
+                    }
+                },
+                None => assert!(false),
+            }
+            match ir_tree.var(&String::from("_VF1g")) {
+                Some(var) => {
+                    match &*var.borrow() {
+                        IrVar::Fun(fun) => {
+                            match &**fun {
+                                IrFun::Fun(_, arg_types, ret_type, block, _, _, _, _) => {
+                                    assert_eq!(vec![Box::new(IrType::Struct(struct_ident.clone()))], *arg_types);
+                                    assert_eq!(IrType::Float, **ret_type);
+                                    match block.instrs().first() {
+                                        Some(IrInstr::Assign(_, IrOp::Load(IrValue::Object(object)))) => {
+                                            match &**object {
+                                                IrObject::Var(IrArgVar::Local(0, ops), None) => assert_eq!(vec![IrArgOp::Dot(None, 0)], *ops),
+                                                _ => assert!(false),
+                                            }
+                                        },
+                                        _ => assert!(false),
+                                    }
+                                },
+                                _ => assert!(false),
+                            }
+                        },
+                        _ => assert!(false),
+                    }
+                },
+                None => assert!(false),
+            }
+            match ir_tree.defs().first().map(|def| &**def) {
+                Some(IrDef::Struct(ident, _)) => assert_eq!(struct_ident, *ident),
+                _ => assert!(false),
+            }
+        },
+        Err(_) => assert!(false),
+    }
+}
+
+#[test]
+fn test_lowerer_lower_tree_lowers_kernel_with_slice()
+{
+    let s = "
+import opencl;
+a: Int = 10;
+kernel k(xs: UniqGlobalSlice<Int>) -> () =
+    let i = get_global_id(0 as Uint);
+        (x, xs) = xs[i] ->;
+        _ = xs[i] <- (x + a);
+    in  ();
+";
+    let s2 = &s[1..];
+    let tree = match do_frontend_phases(s2) {
+        Ok(tree) => tree,
+        Err(errs) => {
+            println!("{}", errs);
+            panic!();
+        },
+    };
+    match lower_tree(&tree) {
+        Ok(ir_tree) => {
+            match ir_tree.var(&String::from("_VV1a")) {
+                Some(var) => {
+                    match &*var.borrow() {
+                        IrVar::Const(typ, IrValue::Int(10, None)) => assert_eq!(IrType::Int, **typ),
+                        _ => assert!(false),
+                    }
+                },
+                None => assert!(false),
+            }
+            match ir_tree.var(&String::from("_VF1k")) {
+                Some(var) => {
+                    match &*var.borrow() {
+                        IrVar::Fun(fun) => {
+                            match &**fun {
+                                IrFun::Fun(IrFunModifier::Kernel, arg_types, ret_type, block, _, _, _, _) => {
+                                    assert_eq!(vec![Box::new(IrType::Ptr(IrPtrModifier::Global, IrAccessModifier::None, Box::new(IrType::Int))), Box::new(IrType::Ulong)], *arg_types);
+                                    assert_eq!(IrType::Void, **ret_type);
+                                    match block.instrs().get(1) {
+                                        Some(IrInstr::Assign(_, IrOp::CallBuiltinFun(ident, None, _))) => assert_eq!(String::from("get_global_id"), *ident),
+                                        _ => assert!(false),
+                                    }
+                                    let is_set = block.instrs().iter().any(|instr| {
+                                            match instr {
+                                                IrInstr::Assign(var, _) => {
+                                                    match &**var {
+                                                        IrInstrVar::Local(_, ops) => ops.first() == Some(&IrArgOp::Dot(None, 0)),
+                                                        _ => false,
+                                                    }
+                                                },
+                                                _ => false,
+                                            }
+                                    });
+                                    assert!(is_set);
+                                    match block.instrs().last() {
+                                        Some(IrInstr::Return(None)) => assert!(true),
+                                        _ => assert!(false),
+                                    }
+                                },
+                                _ => assert!(false),
+                            }
+                        },
+                        _ => assert!(false),
+                    }
+                },
+                None => assert!(false),
+            }
+        },
+        Err(_) => assert!(false),
+    }
+}
+
+#[test]
+fn test_lowerer_lower_tree_complains_on_unsupported_constructions()
+{
+    let s = "
+f(x: Int) -> Int =
+    let (_, y) = ((), x);
+    in  y;
+g(x: Int, y: Int) -> Int = x + y;
+kernel k(x: Int) -> () =
+    let _ = f(x);
+        _ = g(x, x);
+    in  ();
+";
+    let s2 = &s[1..];
+    let tree = match do_frontend_phases(s2) {
+        Ok(tree) => tree,
+        Err(errs) => {
+            println!("{}", errs);
+            panic!();
+        },
+    };
+    match lower_tree(&tree) {
+        Ok(_) => assert!(false),
+        Err(BackendError::Messages(errs)) => {
+            assert_eq!(1, errs.len());
+            assert_eq!(2, errs[0].0.line);
+            assert_eq!(18, errs[0].0.column);
+        },
+        Err(_) => assert!(false),
+    }
+}
+
+#[test]
+fn test_lowerer_lower_tree_lowers_polymorphic_function_instances()
+{
+    let s = "
+twice(x: t) -> t where t: shared + OpAdd = x + x;
+trait Halve
+{
+    halve(x: t) -> t where t: shared + Halve;
+};
+impl Halve for Int
+{
+    halve(x) = x / 2;
+};
+impl Halve for Float
+{
+    halve(x) = x * 0.5;
+};
+kernel k(x: Int, y: Float) -> () =
+    let _ = twice(x);
+        _ = twice(y);
+        _ = halve(x);
+        _ = halve(y);
+        _ = twice(halve(x));
+    in  ();
+";
+    let s2 = &s[1..];
+    let tree = match do_frontend_phases(s2) {
+        Ok(tree) => tree,
+        Err(errs) => {
+            println!("{}", errs);
+            panic!();
+        },
+    };
+    match Lowerer::new_with_inline_threshold(0).lower_tree(&tree) {
+        Ok(ir_tree) => {
+            let mut names: Vec<String> = Vec::new();
+            for def in ir_tree.defs() {
+                match &**def {
+                    IrDef::Var(name, var) => {
+                        match &*var.borrow() {
+                            IrVar::Fun(_) => names.push(name.clone()),
+                            _ => (),
+                        }
+                    },
+                    _ => (),
+                }
+            }
+            println!("{:?}", names);
+            assert_eq!(5, names.len());
+            assert_eq!(String::from("_VF1k"), names[0]);
+            for name in &names[1..] {
+                match ir_tree.var(name) {
+                    Some(var) => {
+                        match &*var.borrow() {
+                            IrVar::Fun(fun) => {
+                                match &**fun {
+                                    IrFun::Fun(IrFunModifier::None, arg_types, ret_type, _, _, _, _, _) => {
+                                        assert_eq!(1, arg_types.len());
+                                        assert_eq!(arg_types[0], *ret_type);
+                                    },
+                                    _ => assert!(false),
+                                }
+                            },
+                            _ => assert!(false),
+                        }
+                    },
+                    None => assert!(false),
+                }
+            }
+        },
+        Err(err) => {
+            println!("{}", err);
+            assert!(false);
+        },
+    }
+}
+
+#[test]
+fn test_lowerer_lower_tree_lowers_lambdas_to_closures_and_callers()
+{
+    let s = "
+apply(f: (Int) -> Int, x: Int) -> Int = f(x);
+inc(x: Int) -> Int = x + 1;
+kernel k(x: Int, y: Int) -> () =
+    let _ = apply(|z| z + y, x);
+        _ = apply(inc, x);
+    in  ();
+";
+    let s2 = &s[1..];
+    let tree = match do_frontend_phases(s2) {
+        Ok(tree) => tree,
+        Err(errs) => {
+            println!("{}", errs);
+            panic!();
+        },
+    };
+    match Lowerer::new_with_inline_threshold(0).lower_tree(&tree) {
+        Ok(ir_tree) => {
+            let fun_struct_ident = String::from("_VSL3IntNPRA3IntNP");
+            let closure_struct_ident = String::from("_VDKPL3IntNPRA3IntNPI1I");
+            match ir_tree.struct1(&fun_struct_ident) {
+                Some(struct1) => {
+                    match &*struct1.borrow() {
+                        IrStruct::Struct(field_types) => assert_eq!(vec![Box::new(IrType::Uint), Box::new(IrType::Struct(closure_struct_ident.clone()))], *field_types),
+                        _ => assert!(false),
+                    }
+                },
+                None => assert!(false),
+            }
+            match ir_tree.struct1(&closure_struct_ident) {
+                Some(struct1) => {
+                    match &*struct1.borrow() {
+                        IrStruct::Closure(field_types) => {
+                            assert_eq!(1, field_types.len());
+                            assert_eq!(Some(&Box::new(IrType::Int)), field_types.get(&1));
+                        },
+                        _ => assert!(false),
+                    }
+                },
+                None => assert!(false),
+            }
+            match ir_tree.var(&String::from("_VCL3IntNPRA3IntNP")) {
+                Some(var) => {
+                    match &*var.borrow() {
+                        IrVar::Fun(fun) => {
+                            match &**fun {
+                                IrFun::Caller(fun_type, arg_types, ret_type, caller_funs, _, _, _, _) => {
+                                    assert_eq!(IrType::Struct(fun_struct_ident.clone()), **fun_type);
+                                    assert_eq!(vec![Box::new(IrType::Int)], *arg_types);
+                                    assert_eq!(IrType::Int, **ret_type);
+                                    assert_eq!(2, caller_funs.funs().len());
+                                    match caller_funs.fun(1) {
+                                        Some(IrCallerFun::Lambda(Some(private_closure_type), None, None, 2, block)) => {
+                                            assert_eq!(IrType::Struct(closure_struct_ident.clone()), **private_closure_type);
+                                            match block.instrs().first() {
+                                                Some(IrInstr::Assign(_, IrOp::Add(IrValue::Object(object1), IrValue::Object(object2)))) => {
+                                                    match (&**object1, &**object2) {
+                                                        (IrObject::Var(IrArgVar::Local(2, _), None), IrObject::Var(IrArgVar::PrivateClosure(1, _), None)) => assert!(true),
+                                                        _ => assert!(false),
+                                                    }
+                                                },
+                                                _ => assert!(false),
+                                            }
+                                        },
+                                        _ => assert!(false),
+                                    }
+                                    match caller_funs.fun(2) {
+                                        Some(IrCallerFun::Fun(ident)) => assert_eq!(String::from("_VF3inc"), *ident),
+                                        _ => assert!(false),
+                                    }
+                                },
+                                _ => assert!(false),
+                            }
+                        },
+                        _ => assert!(false),
+                    }
+                },
+                None => assert!(false),
+            }
+            match ir_tree.var(&String::from("_VF5apply")) {
+                Some(var) => {
+                    match &*var.borrow() {
+                        IrVar::Fun(fun) => {
+                            match &**fun {
+                                IrFun::Fun(_, _, _, block, _, _, _, _) => {
+                                    match block.instrs().first() {
+                                        Some(IrInstr::Assign(_, IrOp::CallFun(ident, values, _, _))) => {
+                                            assert_eq!(String::from("_VCL3IntNPRA3IntNP"), *ident);
+                                            assert_eq!(2, values.len());
+                                        },
+                                        _ => assert!(false),
+                                    }
+                                },
+                                _ => assert!(false),
+                            }
+                        },
+                        _ => assert!(false),
+                    }
+                },
+                None => assert!(false),
+            }
+            assert!(ir_tree.var(&String::from("_VF3inc")).is_some());
+        },
+        Err(err) => {
+            println!("{}", err);
+            assert!(false);
+        },
+    }
+}
+
+#[test]
+fn test_lowerer_lower_tree_lowers_data_types_to_tagged_unions()
+{
+    let s = "
+data T<t> = A() | B(t, Float);
+data U = C(Int, Float);
+data V = D() | E();
+f(t: T<Int>, u: U, v: V) -> () = ();
+kernel k(x: Int, y: Float) -> () = f(B(x, y), C(x, y), E());
+";
+    let s2 = &s[1..];
+    let tree = match do_frontend_phases(s2) {
+        Ok(tree) => tree,
+        Err(errs) => {
+            println!("{}", errs);
+            panic!();
+        },
+    };
+    match Lowerer::new_with_inline_threshold(0).lower_tree(&tree) {
+        Ok(ir_tree) => {
+            match ir_tree.struct1(&String::from("_VS1TN3IntNPP")) {
+                Some(struct1) => {
+                    match &*struct1.borrow() {
+                        IrStruct::Struct(field_types) => assert_eq!(vec![Box::new(IrType::Uint), Box::new(IrType::Union(String::from("_VU1TN3IntNPP")))], *field_types),
+                        _ => assert!(false),
+                    }
+                },
+                None => assert!(false),
+            }
+            match ir_tree.union(&String::from("_VU1TN3IntNPP")) {
+                Some(union) => assert_eq!(vec![Box::new(IrType::Struct(String::from("_VG1BJ1TN3IntNPP")))], union.borrow().0),
+                None => assert!(false),
+            }
+            match ir_tree.struct1(&String::from("_VG1BJ1TN3IntNPP")) {
+                Some(struct1) => {
+                    match &*struct1.borrow() {
+                        IrStruct::Struct(field_types) => assert_eq!(vec![Box::new(IrType::Int), Box::new(IrType::Float)], *field_types),
+                        _ => assert!(false),
+                    }
+                },
+                None => assert!(false),
+            }
+            match ir_tree.struct1(&String::from("_VS1UNP")) {
+                Some(struct1) => {
+                    match &*struct1.borrow() {
+                        IrStruct::Struct(field_types) => assert_eq!(vec![Box::new(IrType::Int), Box::new(IrType::Float)], *field_types),
+                        _ => assert!(false),
+                    }
+                },
+                None => assert!(false),
+            }
+            assert!(ir_tree.struct1(&String::from("_VS1VNP")).is_none());
+            match ir_tree.var(&String::from("_VF1f")) {
+                Some(var) => {
+                    match &*var.borrow() {
+                        IrVar::Fun(fun) => {
+                            match &**fun {
+                                IrFun::Fun(_, arg_types, _, _, _, _, _, _) => assert_eq!(IrType::Uint, *arg_types[2]),
+                                _ => assert!(false),
+                            }
+                        },
+                        _ => assert!(false),
+                    }
+                },
+                None => assert!(false),
+            }
+        },
+        Err(err) => {
+            println!("{}", err);
+            assert!(false);
+        },
+    }
+}
+
+#[test]
+fn test_lowerer_lower_tree_lowers_match_to_decision_tree()
+{
+    let s = "
+data T = A() | B(Int, Float) | C(Int);
+f(t: T) -> Int =
+    t match {
+        B(1, _) => 1;
+        B(_, 2.5) | C(2) => 2;
+        _ => 3;
+    };
+kernel k(x: Int, y: Float) -> () = let _ = f(B(x, y)) in ();
+";
+    let s2 = &s[1..];
+    let tree = match do_frontend_phases(s2) {
+        Ok(tree) => tree,
+        Err(errs) => {
+            println!("{}", errs);
+            panic!();
+        },
+    };
+    match lower_tree(&tree) {
+        Ok(ir_tree) => {
+            match ir_tree.var(&String::from("_VF1f")) {
+                Some(var) => {
+                    match &*var.borrow() {
+                        IrVar::Fun(fun) => {
+                            match &**fun {
+                                IrFun::Fun(_, _, _, block, _, _, _, _) => {
+                                    match block.instrs().first() {
+                                        Some(IrInstr::Switch(IrOp::Load(IrValue::Object(object)), cases)) => {
+                                            match &**object {
+                                                IrObject::Var(IrArgVar::Local(0, ops), None) => assert_eq!(vec![IrArgOp::Dot(None, 0)], *ops),
+                                                _ => assert!(false),
+                                            }
+                                            assert_eq!(3, cases.len());
+                                            match &cases[0] {
+                                                IrCase::Case(IrCaseValue::Uint(1, None), case_block) => {
+                                                    match case_block.instrs().first() {
+                                                        Some(IrInstr::Switch(_, cases2)) => {
+                                                            assert_eq!(2, cases2.len());
+                                                            match &cases2[0] {
+                                                                IrCase::Case(IrCaseValue::Int(1, None), _) => assert!(true),
+                                                                _ => assert!(false),
+                                                            }
+                                                            match &cases2[1] {
+                                                                IrCase::Default(default_block) => {
+                                                                    match default_block.instrs().first() {
+                                                                        Some(IrInstr::If(IrOp::Eq(_, IrValue::Float(x, None)), _, _)) => assert_eq!(2.5, *x),
+                                                                        _ => assert!(false),
+                                                                    }
+                                                                },
+                                                                _ => assert!(false),
+                                                            }
+                                                        },
+                                                        _ => assert!(false),
+                                                    }
+                                                },
+                                                _ => assert!(false),
+                                            }
+                                            match &cases[1] {
+                                                IrCase::Case(IrCaseValue::Uint(2, None), case_block) => {
+                                                    match case_block.instrs().first() {
+                                                        Some(IrInstr::Switch(_, cases2)) => assert_eq!(2, cases2.len()),
+                                                        _ => assert!(false),
+                                                    }
+                                                },
+                                                _ => assert!(false),
+                                            }
+                                            match &cases[2] {
+                                                IrCase::Default(_) => assert!(true),
+                                                _ => assert!(false),
+                                            }
+                                        },
+                                        _ => assert!(false),
+                                    }
+                                },
+                                _ => assert!(false),
+                            }
+                        },
+                        _ => assert!(false),
+                    }
+                },
+                None => assert!(false),
+            }
+        },
+        Err(err) => {
+            println!("{}", err);
+            assert!(false);
+        },
+    }
+}
+
+#[test]
+fn test_lowerer_lower_tree_lowers_tail_recursive_function_to_loop()
+{
+    let s = "
+sum(n: Int, acc: Int) -> Int =
+    n match {
+        0 => acc;
+        _ => sum(n - 1, acc + n);
+    };
+f(x: Int) -> Int = sum(x, 0);
+kernel k(x: Int) -> () = let _ = f(x) in ();
+";
+    let s2 = &s[1..];
+    let tree = match do_frontend_phases(s2) {
+        Ok(tree) => tree,
+        Err(errs) => {
+            println!("{}", errs);
+            panic!();
+        },
+    };
+    match Lowerer::new_with_inline_threshold(0).lower_tree(&tree) {
+        Ok(ir_tree) => {
+            match ir_tree.var(&String::from("_VF3sum")) {
+                Some(var) => {
+                    match &*var.borrow() {
+                        IrVar::Fun(fun) => {
+                            match &**fun {
+                                IrFun::Fun(_, _, _, block, _, _, _, _) => {
+                                    assert_eq!(1, block.instrs().len());
+                                    match block.instrs().first() {
+                                        Some(IrInstr::Loop(loop_block)) => {
+                                            match loop_block.instrs().first() {
+                                                Some(IrInstr::Switch(_, cases)) => {
+                                                    assert_eq!(2, cases.len());
+                                                    match &cases[0] {
+                                                        IrCase::Case(IrCaseValue::Int(0, None), case_block) => {
+                                                            match case_block.instrs().last() {
+                                                                Some(IrInstr::Return(Some(_))) => assert!(true),
+                                                                _ => assert!(false),
+                                                            }
+                                                        },
+                                                        _ => assert!(false),
+                                                    }
+                                                    match &cases[1] {
+                                                        IrCase::Default(default_block) => {
+                                                            let instrs = default_block.instrs();
+                                                            match instrs.last() {
+                                                                Some(IrInstr::Continue) => assert!(true),
+                                                                _ => assert!(false),
+                                                            }
+                                                            assert!(instrs.iter().all(|instr| !matches!(instr, IrInstr::Assign(_, IrOp::CallFun(_, _, _, _)))));
+                                                            match &instrs[instrs.len() - 3] {
+                                                                IrInstr::Assign(var, _) => {
+                                                                    match &**var {
+                                                                        IrInstrVar::Local(0, ops) => assert!(ops.is_empty()),
+                                                                        _ => assert!(false),
+                                                                    }
+                                                                },
+                                                                _ => assert!(false),
+                                                            }
+                                                            match &instrs[instrs.len() - 2] {
+                                                                IrInstr::Assign(var, _) => {
+                                                                    match &**var {
+                                                                        IrInstrVar::Local(1, ops) => assert!(ops.is_empty()),
+                                                                        _ => assert!(false),
+                                                                    }
+                                                                },
+                                                                _ => assert!(false),
+                                                            }
+                                                        },
+                                                        _ => assert!(false),
+                                                    }
+                                                },
+                                                _ => assert!(false),
+                                            }
+                                        },
+                                        _ => assert!(false),
+                                    }
+                                },
+                                _ => assert!(false),
+                            }
+                        },
+                        _ => assert!(false),
+                    }
+                },
+                None => assert!(false),
+            }
+            match ir_tree.var(&String::from("_VF1f")) {
+                Some(var) => {
+                    match &*var.borrow() {
+                        IrVar::Fun(fun) => {
+                            match &**fun {
+                                IrFun::Fun(_, _, _, block, _, _, _, _) => assert!(block.instrs().iter().all(|instr| !matches!(instr, IrInstr::Loop(_)))),
+                                _ => assert!(false),
+                            }
+                        },
+                        _ => assert!(false),
+                    }
+                },
+                None => assert!(false),
+            }
+        },
+        Err(err) => {
+            println!("{}", err);
+            assert!(false);
+        },
+    }
+}
+
+#[test]
+fn test_lowerer_lower_tree_lowers_bounded_recursive_function_to_stack_machine()
+{
+    let s = "
+recursive(32) fib(n: Int) -> Int = if n < 2 then n else fib(n - 1) + fib(n - 2);
+kernel k(x: Int) -> () = let _ = fib(x) in ();
+";
+    let s2 = &s[1..];
+    let tree = match do_frontend_phases(s2) {
+        Ok(tree) => tree,
+        Err(errs) => {
+            println!("{}", errs);
+            panic!();
+        },
+    };
+    match lower_tree(&tree) {
+        Ok(ir_tree) => {
+            match ir_tree.struct1(&String::from("_VR3fib")) {
+                Some(struct1) => {
+                    match &*struct1.borrow() {
+                        IrStruct::Struct(field_types) => {
+                            assert_eq!(8, field_types.len());
+                            assert_eq!(IrType::Uint, *field_types[0]);
+                            assert_eq!(IrType::Int, *field_types[1]);
+                        },
+                        _ => assert!(false),
+                    }
+                },
+                None => assert!(false),
+            }
+            match ir_tree.var(&String::from("_VF3fib")) {
+                Some(var) => {
+                    match &*var.borrow() {
+                        IrVar::Fun(fun) => {
+                            match &**fun {
+                                IrFun::Fun(IrFunModifier::None, _, _, block, _, _, _, IrPanicFlag::Panic) => {
+                                    match &*block.local_var_pairs()[2].1 {
+                                        IrType::Array(elem_type, 32) => assert_eq!(IrType::Struct(String::from("_VR3fib")), **elem_type),
+                                        _ => assert!(false),
+                                    }
+                                    match block.instrs().last() {
+                                        Some(IrInstr::Loop(loop_block)) => {
+                                            match loop_block.instrs().first() {
+                                                Some(IrInstr::Switch(_, cases)) => {
+                                                    assert_eq!(5, cases.len());
+                                                    match &cases[1] {
+                                                        IrCase::Case(IrCaseValue::Uint(1, None), case_block) => {
+                                                            match case_block.instrs().last() {
+                                                                Some(IrInstr::Continue) => assert!(true),
+                                                                _ => assert!(false),
+                                                            }
+                                                        },
+                                                        _ => assert!(false),
+                                                    }
+                                                    match &cases[2] {
+                                                        IrCase::Case(IrCaseValue::Uint(2, None), case_block) => {
+                                                            let has_panic = case_block.instrs().iter().any(|instr| {
+                                                                    match instr {
+                                                                        IrInstr::If(IrOp::Ge(_, IrValue::Uint(32, None)), then_block, _) => {
+                                                                            match then_block.instrs().first() {
+                                                                                Some(IrInstr::Panic(msg, pos, _)) => msg == "recursion depth of function fib is exceeded" && pos.line == 1 && pos.column == 57,
+                                                                                _ => false,
+                                                                            }
+                                                                        },
+                                                                        _ => false,
+                                                                    }
+                                                            });
+                                                            assert!(has_panic);
+                                                            assert!(case_block.instrs().iter().all(|instr| !matches!(instr, IrInstr::Assign(_, IrOp::CallFun(_, _, _, _)))));
+                                                        },
+                                                        _ => assert!(false),
+                                                    }
+                                                },
+                                                _ => assert!(false),
+                                            }
+                                        },
+                                        _ => assert!(false),
+                                    }
+                                },
+                                _ => assert!(false),
+                            }
+                        },
+                        _ => assert!(false),
+                    }
+                },
+                None => assert!(false),
+            }
+        },
+        Err(err) => {
+            println!("{}", err);
+            assert!(false);
+        },
+    }
+}
+
+#[test]
+fn test_lowerer_lower_tree_complains_on_bounded_recursive_function_can_not_be_called_in_lambda()
+{
+    let s = "
+import std_range;
+recursive(8) f(n: Int) -> Int = if n == 0 then 0 else f(n - 1) + range_fold(0, n, 0, |a, i| a + f(i));
+kernel k(x: Int) -> () = let _ = f(x) in ();
+";
+    let s2 = &s[1..];
+    let tree = match do_frontend_phases(s2) {
+        Ok(tree) => tree,
+        Err(errs) => {
+            println!("{}", errs);
+            panic!();
+        },
+    };
+    match lower_tree(&tree) {
+        Ok(_) => assert!(false),
+        Err(BackendError::Messages(errs)) => {
+            assert_eq!(1, errs.len());
+            assert_eq!(2, errs[0].0.line);
+            assert_eq!(97, errs[0].0.column);
+            assert_eq!(String::from("bounded recursive function f can't be called in lambda"), errs[0].1);
+        },
+        Err(_) => assert!(false),
+    }
+}
@@ -10,7 +10,16 @@ use crate::frontend::tree::*;
 use crate::backend::error::*;
 
 fn add_mangled_ident_to_string(s: &mut String, ident: &str)
-{ s.push_str(format!("{}{}", ident.len(), ident).as_str()); }
+{
+    match ident.split_once("::") {
+        Some((module_ident, ident2)) => {
+            s.push('H');
+            s.push_str(format!("{}{}", module_ident.len(), module_ident).as_str());
+            s.push_str(format!("{}{}", ident2.len(), ident2).as_str());
+        },
+        None => s.push_str(format!("{}{}", ident.len(), ident).as_str()),
+    }
+}
 
 fn add_mangled_usize_to_string(s: &mut String, n: usize)
 { s.push_str(format!("I{}I", n).as_str()); }
@@ -229,6 +238,14 @@ pub fn mangle_fun_name(ident: &str, type_values: &[Rc<TypeValue>], typ: &Type) -
     Ok(s)
 }
 
+pub fn mangle_frame_struct_name(ident: &str, type_values: &[Rc<TypeValue>], typ: &Type) -> BackendResult<String>
+{
+    let mut s = String::from("_VR");
+    add_mangled_ident_to_string(&mut s, ident);
+    add_mangled_type_params_to_string(&mut s, type_values, typ)?;
+    Ok(s)
+}
+
 pub fn mangle_private_alloc_fun_name() -> String
 { String::from("_VHKP") }
 
@@ -238,6 +255,12 @@ pub fn mangle_local_alloc_fun_name() -> String
 pub fn mangle_global_alloc_fun_name() -> String
 { String::from("_VHKG") }
 
+pub fn mangle_panic_fun_name() -> String
+{ String::from("_VPKP") }
+
+pub fn mangle_panic_call_fun_name() -> String
+{ String::from("_VPKC") }
+
 pub fn mangle_caller_name(type_value: &Rc<TypeValue>) -> BackendResult<String>
 {
     let mut s = String::from("_VC");
@@ -0,0 +1,423 @@
+//
+// Copyright (c) 2025 Łukasz Szpakowski
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+use std::fmt::Write;
+use crate::frontend::error::Pos;
+use crate::frontend::tree::*;
+use crate::backend::error::*;
+use crate::backend::ir::*;
+use crate::backend::ir::printer::*;
+use crate::backend::layout::*;
+use crate::backend::lowering::*;
+use crate::backend::mangling::*;
+use crate::backend::panic::*;
+
+pub const MANIFEST_VERSION: u32 = 1;
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum ArgKind
+{
+    Value,
+    Ptr,
+    SlicePtr,
+    SliceLen,
+    GlobalHeap,
+    PanicBuffer,
+}
+
+#[derive(Clone, Debug)]
+pub struct ArgDesc
+{
+    pub ident: String,
+    pub kind: ArgKind,
+    pub typ: Box<IrType>,
+    pub source_type: Option<String>,
+    pub is_uniq: bool,
+    pub layout: Layout,
+    pub elem_layout: Option<Layout>,
+}
+
+#[derive(Clone, Debug)]
+pub struct KernelDesc
+{
+    pub ident: String,
+    pub mangled_name: String,
+    pub pos: Pos,
+    pub args: Vec<ArgDesc>,
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum TypeDescKind
+{
+    Struct,
+    Closure,
+    Union,
+}
+
+#[derive(Clone, Debug)]
+pub struct TypeDesc
+{
+    pub ident: String,
+    pub kind: TypeDescKind,
+    pub field_indices: Vec<usize>,
+    pub field_types: Vec<Box<IrType>>,
+    pub layout: Layout,
+}
+
+#[derive(Clone, Debug)]
+pub struct Manifest
+{
+    pub kernels: Vec<KernelDesc>,
+    pub types: Vec<TypeDesc>,
+    pub panic_table: PanicTable,
+}
+
+fn json_string(s: &str) -> String
+{
+    let mut t = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '"' => t.push_str("\\\""),
+            '\\' => t.push_str("\\\\"),
+            '\n' => t.push_str("\\n"),
+            '\r' => t.push_str("\\r"),
+            '\t' => t.push_str("\\t"),
+            c if (c as u32) < 0x20 => write!(t, "\\u{:04x}", c as u32).unwrap(),
+            c => t.push(c),
+        }
+    }
+    t.push('"');
+    t
+}
+
+fn json_opt_string(s: &Option<String>) -> String
+{
+    match s {
+        Some(s) => json_string(s.as_str()),
+        None => String::from("null"),
+    }
+}
+
+fn json_pos(pos: &Pos) -> String
+{ format!("{{\"path\": {}, \"line\": {}, \"column\": {}}}", json_string(pos.path.as_str()), pos.line, pos.column) }
+
+fn json_poses(poses: &[Pos]) -> String
+{
+    let ss: Vec<String> = poses.iter().map(json_pos).collect();
+    format!("[{}]", ss.join(", "))
+}
+
+fn arg_kind_name(kind: ArgKind) -> &'static str
+{
+    match kind {
+        ArgKind::Value => "value",
+        ArgKind::Ptr => "ptr",
+        ArgKind::SlicePtr => "slice_ptr",
+        ArgKind::SliceLen => "slice_len",
+        ArgKind::GlobalHeap => "global_heap",
+        ArgKind::PanicBuffer => "panic_buffer",
+    }
+}
+
+fn type_desc_kind_name(kind: TypeDescKind) -> &'static str
+{
+    match kind {
+        TypeDescKind::Struct => "struct",
+        TypeDescKind::Closure => "closure",
+        TypeDescKind::Union => "union",
+    }
+}
+
+fn address_space_name(ptr_modifier: IrPtrModifier) -> &'static str
+{
+    match ptr_modifier {
+        IrPtrModifier::None => "generic",
+        IrPtrModifier::Private => "private",
+        IrPtrModifier::Local => "local",
+        IrPtrModifier::Global => "global",
+        IrPtrModifier::Constant => "constant",
+    }
+}
+
+fn json_array(items: &[String], indent: &str) -> String
+{
+    if items.is_empty() {
+        return String::from("[]");
+    }
+    let mut s = String::from("[\n");
+    for (i, item) in items.iter().enumerate() {
+        write!(s, "{}  {}", indent, item).unwrap();
+        s.push_str(if i + 1 < items.len() { ",\n" } else { "\n" });
+    }
+    write!(s, "{}]", indent).unwrap();
+    s
+}
+
+fn arg_desc_to_json(idx: usize, arg: &ArgDesc) -> String
+{
+    let (address_space, is_const) = match &*arg.typ {
+        IrType::Ptr(ptr_modifier, access_modifier, _) => (json_string(address_space_name(*ptr_modifier)), (*access_modifier == IrAccessModifier::Const).to_string()),
+        _ => (String::from("null"), String::from("null")),
+    };
+    let elem = match (&*arg.typ, &arg.elem_layout) {
+        (IrType::Ptr(_, _, elem_type), Some(elem_layout)) => format!("{{\"type\": {}, \"size\": {}, \"align\": {}}}", json_string(ir_type_to_string(elem_type).as_str()), elem_layout.size, elem_layout.align),
+        _ => String::from("null"),
+    };
+    let mut s = String::new();
+    write!(s, "{{\"index\": {}, \"name\": {}, \"kind\": {}, ", idx, json_string(arg.ident.as_str()), json_string(arg_kind_name(arg.kind))).unwrap();
+    write!(s, "\"type\": {}, \"source_type\": {}, ", json_string(ir_type_to_string(&arg.typ).as_str()), json_opt_string(&arg.source_type)).unwrap();
+    write!(s, "\"address_space\": {}, \"is_const\": {}, \"is_uniq\": {}, ", address_space, is_const, arg.is_uniq).unwrap();
+    write!(s, "\"size\": {}, \"align\": {}, \"elem\": {}}}", arg.layout.size, arg.layout.align, elem).unwrap();
+    s
+}
+
+fn kernel_to_json(kernel: &KernelDesc) -> String
+{
+    let arg_ss: Vec<String> = kernel.args.iter().enumerate().map(|(i, arg)| arg_desc_to_json(i, arg)).collect();
+    let mut s = String::from("{\n");
+    writeln!(s, "      \"name\": {},", json_string(kernel.ident.as_str())).unwrap();
+    writeln!(s, "      \"mangled_name\": {},", json_string(kernel.mangled_name.as_str())).unwrap();
+    writeln!(s, "      \"pos\": {},", json_pos(&kernel.pos)).unwrap();
+    writeln!(s, "      \"args\": {}", json_array(arg_ss.as_slice(), "      ")).unwrap();
+    s.push_str("    }");
+    s
+}
+
+fn type_desc_to_json(desc: &TypeDesc) -> String
+{
+    let field_ss: Vec<String> = desc.field_indices.iter().zip(desc.field_types.iter()).enumerate().map(|(i, (field_idx, field_type))| {
+            let offset = desc.layout.field_offsets.get(i).cloned().unwrap_or(0);
+            format!("{{\"index\": {}, \"type\": {}, \"offset\": {}}}", field_idx, json_string(ir_type_to_string(field_type).as_str()), offset)
+    }).collect();
+    let mut s = String::from("{\n");
+    writeln!(s, "      \"name\": {},", json_string(desc.ident.as_str())).unwrap();
+    writeln!(s, "      \"kind\": {},", json_string(type_desc_kind_name(desc.kind))).unwrap();
+    writeln!(s, "      \"size\": {},", desc.layout.size).unwrap();
+    writeln!(s, "      \"align\": {},", desc.layout.align).unwrap();
+    writeln!(s, "      \"fields\": {}", json_array(field_ss.as_slice(), "      ")).unwrap();
+    s.push_str("    }");
+    s
+}
+
+fn panic_entry_to_json(id: u32, entry: &PanicEntry) -> String
+{
+    match entry {
+        PanicEntry::Panic(msg, poses) => format!("{{\"id\": {}, \"kind\": \"panic\", \"message\": {}, \"poses\": {}}}", id, json_string(msg.as_str()), json_poses(poses.as_slice())),
+        PanicEntry::Call(poses) => format!("{{\"id\": {}, \"kind\": \"call\", \"message\": null, \"poses\": {}}}", id, json_poses(poses.as_slice())),
+    }
+}
+
+fn is_uniq_type_value(type_value: &TypeValue) -> bool
+{
+    match type_value {
+        TypeValue::Type(UniqFlag::None, TypeValueName::Name(ident), _) => ident.starts_with("Uniq"),
+        _ => type_value.uniq_flag() == UniqFlag::Uniq,
+    }
+}
+
+fn is_split_type(typ: &IrType, ir_tree: &IrTree) -> bool
+{
+    match typ {
+        IrType::Struct(ident) if ident.starts_with("_VS") => {
+            match ir_tree.struct1(ident) {
+                Some(struct1) => {
+                    match &*struct1.borrow() {
+                        IrStruct::Struct(field_types) if field_types.len() == 2 && *field_types[1] == IrType::SizeT => {
+                            matches!(&*field_types[0], IrType::Ptr(_, _, _))
+                        },
+                        _ => false,
+                    }
+                },
+                None => false,
+            }
+        },
+        _ => false,
+    }
+}
+
+fn arg_desc(ident: &str, kind: ArgKind, typ: &IrType, source_type: Option<String>, is_uniq: bool, ir_tree: &IrTree) -> BackendResult<ArgDesc>
+{
+    let elem_layout = match typ {
+        IrType::Ptr(_, _, elem_type) => Some(ir_type_layout(elem_type, ir_tree)?),
+        _ => None,
+    };
+    Ok(ArgDesc {
+            ident: String::from(ident),
+            kind,
+            typ: Box::new(typ.clone()),
+            source_type,
+            is_uniq,
+            layout: ir_type_layout(typ, ir_tree)?,
+            elem_layout,
+    })
+}
+
+fn kernel_desc(ident: &String, args: &[Arg], typ: &Type, pos: &Pos, tree: &Tree, ir_tree: &IrTree) -> BackendResult<Option<KernelDesc>>
+{
+    if !typ.type_param_entries().is_empty() {
+        return Ok(None);
+    }
+    let mangled_name = mangle_fun_name(ident.as_str(), &[], typ)?;
+    let (arg_types, global_heap_flag, panic_flag) = match ir_tree.var(&mangled_name) {
+        Some(var) => {
+            match &*var.borrow() {
+                IrVar::Fun(fun) => {
+                    match &**fun {
+                        IrFun::Fun(IrFunModifier::Kernel, arg_types, _, _, _, _, global_heap_flag, panic_flag) => (arg_types.clone(), *global_heap_flag, *panic_flag),
+                        _ => return Err(BackendError::Internal(format!("kernel_desc: function {} isn't kernel", mangled_name))),
+                    }
+                },
+                _ => return Err(BackendError::Internal(format!("kernel_desc: variable {} isn't function", mangled_name))),
+            }
+        },
+        None => return Err(BackendError::Internal(format!("kernel_desc: no kernel {}", mangled_name))),
+    };
+    let arg_type_values = match &**typ.type_value() {
+        TypeValue::Type(_, TypeValueName::Fun, type_values) if !type_values.is_empty() => &type_values[..(type_values.len() - 1)],
+        _ => return Err(BackendError::Internal(format!("kernel_desc: kernel {} hasn't function type", ident))),
+    };
+    if arg_type_values.len() != args.len() {
+        return Err(BackendError::Internal(format!("kernel_desc: numbers of arguments of kernel {} are different", ident)));
+    }
+    let lowerer = Lowerer::new();
+    let mut tmp_ir_tree = IrTree::new();
+    let mut arg_descs: Vec<ArgDesc> = Vec::new();
+    let mut i = 0usize;
+    for (Arg(arg_ident, _, _, arg_pos), type_value) in args.iter().zip(arg_type_values.iter()) {
+        let tmp_type = lowerer.lower_type_value(type_value, arg_pos, tree, &mut tmp_ir_tree)?;
+        if *tmp_type == IrType::Void {
+            continue;
+        }
+        let source_type = Some(type_value.to_string_without_fun());
+        let is_uniq = is_uniq_type_value(type_value);
+        if is_split_type(&tmp_type, &tmp_ir_tree) {
+            match (arg_types.get(i), arg_types.get(i + 1)) {
+                (Some(ptr_type), Some(len_type)) => {
+                    arg_descs.push(arg_desc(arg_ident.as_str(), ArgKind::SlicePtr, ptr_type, source_type.clone(), is_uniq, ir_tree)?);
+                    arg_descs.push(arg_desc(arg_ident.as_str(), ArgKind::SliceLen, len_type, source_type, is_uniq, ir_tree)?);
+                },
+                _ => return Err(BackendError::Internal(format!("kernel_desc: no slice arguments of kernel {}", ident))),
+            }
+            i += 2;
+        } else {
+            match arg_types.get(i) {
+                Some(arg_type) => {
+                    let kind = match &**arg_type {
+                        IrType::Ptr(_, _, _) => ArgKind::Ptr,
+                        _ => ArgKind::Value,
+                    };
+                    arg_descs.push(arg_desc(arg_ident.as_str(), kind, arg_type, source_type, is_uniq, ir_tree)?);
+                },
+                None => return Err(BackendError::Internal(format!("kernel_desc: no argument of kernel {}", ident))),
+            }
+            i += 1;
+        }
+    }
+    if i != arg_types.len() {
+        return Err(BackendError::Internal(format!("kernel_desc: too many arguments of kernel {}", ident)));
+    }
+    let uint_ptr_type = IrType::Ptr(IrPtrModifier::Global, IrAccessModifier::None, Box::new(IrType::Uint));
+    if global_heap_flag == IrGlobalHeapFlag::Heap {
+        arg_descs.push(arg_desc("gh", ArgKind::GlobalHeap, &uint_ptr_type, None, true, ir_tree)?);
+    }
+    if panic_flag == IrPanicFlag::Panic {
+        arg_descs.push(arg_desc("pb", ArgKind::PanicBuffer, &uint_ptr_type, None, true, ir_tree)?);
+    }
+    Ok(Some(KernelDesc {
+                ident: ident.clone(),
+                mangled_name,
+                pos: pos.clone(),
+                args: arg_descs,
+    }))
+}
+
+fn type_descs(ir_tree: &IrTree) -> BackendResult<Vec<TypeDesc>>
+{
+    let mut descs: Vec<TypeDesc> = Vec::new();
+    for def in ir_tree.defs() {
+        match &**def {
+            IrDef::Struct(ident, struct1) => {
+                let (kind, field_indices, field_types) = match &*struct1.borrow() {
+                    IrStruct::Struct(field_types) => (TypeDescKind::Struct, (0..field_types.len()).collect(), field_types.clone()),
+                    IrStruct::Closure(field_types) => (TypeDescKind::Closure, field_types.keys().cloned().collect(), field_types.values().cloned().collect()),
+                };
+                descs.push(TypeDesc {
+                        ident: ident.clone(),
+                        kind,
+                        field_indices,
+                        field_types,
+                        layout: ir_type_layout(&IrType::Struct(ident.clone()), ir_tree)?,
+                });
+            },
+            IrDef::Union(ident, union) => {
+                let field_types = union.borrow().0.clone();
+                descs.push(TypeDesc {
+                        ident: ident.clone(),
+                        kind: TypeDescKind::Union,
+                        field_indices: (0..field_types.len()).collect(),
+                        field_types,
+                        layout: ir_type_layout(&IrType::Union(ident.clone()), ir_tree)?,
+                });
+            },
+            IrDef::Var(_, _) => (),
+        }
+    }
+    Ok(descs)
+}
+
+impl Manifest
+{
+    pub fn new(tree: &Tree, ir_tree: &IrTree, panic_table: &PanicTable) -> BackendResult<Self>
+    {
+        let mut kernels: Vec<KernelDesc> = Vec::new();
+        for def in tree.defs() {
+            if let Def::Var(_, ident, var, pos) = &**def {
+                if let Var::Fun(fun, None, Some(typ)) = &*var.borrow() {
+                    if let Fun::Fun(FunModifier::Kernel, args, _, _, _, _, _) = &**fun {
+                        if let Some(kernel) = kernel_desc(ident, args.as_slice(), typ, pos, tree, ir_tree)? {
+                            kernels.push(kernel);
+                        }
+                    }
+                }
+            }
+        }
+        Ok(Manifest {
+                kernels,
+                types: type_descs(ir_tree)?,
+                panic_table: panic_table.clone(),
+        })
+    }
+
+    pub fn kernel(&self, ident: &str) -> Option<&KernelDesc>
+    { self.kernels.iter().find(|kernel| kernel.ident == ident) }
+
+    pub fn to_json(&self) -> String
+    {
+        let kernel_ss: Vec<String> = self.kernels.iter().map(kernel_to_json).collect();
+        let type_ss: Vec<String> = self.types.iter().map(type_desc_to_json).collect();
+        let panic_ss: Vec<String> = self.panic_table.entries().iter().enumerate().map(|(i, entry)| panic_entry_to_json((i + 1) as u32, entry)).collect();
+        let mut s = String::new();
+        writeln!(s, "{{").unwrap();
+        writeln!(s, "  \"version\": {},", MANIFEST_VERSION).unwrap();
+        writeln!(s, "  \"address_bits\": {},", ADDRESS_BITS).unwrap();
+        writeln!(s, "  \"kernels\": {},", json_array(kernel_ss.as_slice(), "  ")).unwrap();
+        writeln!(s, "  \"types\": {},", json_array(type_ss.as_slice(), "  ")).unwrap();
+        writeln!(s, "  \"panic_buffer_len\": {},", PANIC_BUFFER_LEN).unwrap();
+        writeln!(s, "  \"panics\": {}", json_array(panic_ss.as_slice(), "  ")).unwrap();
+        writeln!(s, "}}").unwrap();
+        s
+    }
+}
+
+pub fn emit_manifest(tree: &Tree, ir_tree: &IrTree, panic_table: &PanicTable) -> BackendResult<String>
+{
+    let manifest = Manifest::new(tree, ir_tree, panic_table)?;
+    Ok(manifest.to_json())
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,150 @@
+//
+// Copyright (c) 2025 Łukasz Szpakowski
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+use crate::frontend::do_frontend_phases;
+use crate::backend::emitter::*;
+use super::*;
+
+fn manifest_for_string(s: &str) -> Manifest
+{
+    let tree = match do_frontend_phases(s) {
+        Ok(tree) => tree,
+        Err(errs) => {
+            println!("{}", errs);
+            panic!();
+        },
+    };
+    let ir_tree = match lower_tree(&tree) {
+        Ok(ir_tree) => ir_tree,
+        Err(err) => {
+            println!("{}", err);
+            panic!();
+        },
+    };
+    let (_, panic_table) = match emit_ir_tree_with_panic_table(&ir_tree) {
+        Ok(pair) => pair,
+        Err(err) => {
+            println!("{}", err);
+            panic!();
+        },
+    };
+    match Manifest::new(&tree, &ir_tree, &panic_table) {
+        Ok(manifest) => manifest,
+        Err(err) => {
+            println!("{}", err);
+            panic!();
+        },
+    }
+}
+
+#[test]
+fn test_manifest_new_describes_kernel_arguments()
+{
+    let s = "
+import opencl;
+data U = C(Int, Float);
+kernel k(xs: UniqGlobalSlice<Int>, ys: GlobalSlice<Float>, n: Int, u: U) -> () =
+    let i = get_global_id(0 as Uint);
+        y = ys[i];
+        _ = xs[i] <- n;
+    in  ();
+";
+    let manifest = manifest_for_string(&s[1..]);
+    assert_eq!(1, manifest.kernels.len());
+    let kernel = manifest.kernel("k").unwrap();
+    assert_eq!(String::from("_VF1k"), kernel.mangled_name);
+    assert_eq!(3, kernel.pos.line);
+    assert_eq!(6, kernel.args.len());
+    let idents: Vec<&str> = kernel.args.iter().map(|arg| arg.ident.as_str()).collect();
+    assert_eq!(vec!["xs", "xs", "ys", "ys", "n", "u"], idents);
+    let kinds: Vec<ArgKind> = kernel.args.iter().map(|arg| arg.kind).collect();
+    assert_eq!(vec![ArgKind::SlicePtr, ArgKind::SliceLen, ArgKind::SlicePtr, ArgKind::SliceLen, ArgKind::Value, ArgKind::Value], kinds);
+    assert_eq!(IrType::Ptr(IrPtrModifier::Global, IrAccessModifier::None, Box::new(IrType::Int)), *kernel.args[0].typ);
+    assert_eq!(IrType::Ulong, *kernel.args[1].typ);
+    assert_eq!(IrType::Ptr(IrPtrModifier::Global, IrAccessModifier::Const, Box::new(IrType::Float)), *kernel.args[2].typ);
+    assert_eq!(IrType::Int, *kernel.args[4].typ);
+    assert_eq!(IrType::Struct(String::from("_VS1UNP")), *kernel.args[5].typ);
+    let uniqs: Vec<bool> = kernel.args.iter().map(|arg| arg.is_uniq).collect();
+    assert_eq!(vec![true, true, false, false, false, false], uniqs);
+    assert_eq!(Some(String::from("UniqGlobalSlice<Int>")), kernel.args[0].source_type);
+    assert_eq!(Some(String::from("U")), kernel.args[5].source_type);
+    assert_eq!(Some(4), kernel.args[0].elem_layout.as_ref().map(|layout| layout.size));
+    assert_eq!(8, kernel.args[5].layout.size);
+    let json = manifest.to_json();
+    assert!(json.contains("      \"mangled_name\": \"_VF1k\",\n"));
+    assert!(json.contains("        {\"index\": 0, \"name\": \"xs\", \"kind\": \"slice_ptr\", \"type\": \"*global int\", \"source_type\": \"UniqGlobalSlice<Int>\", \"address_space\": \"global\", \"is_const\": false, \"is_uniq\": true, \"size\": 8, \"align\": 8, \"elem\": {\"type\": \"int\", \"size\": 4, \"align\": 4}},\n"));
+    assert!(json.contains("        {\"index\": 3, \"name\": \"ys\", \"kind\": \"slice_len\", \"type\": \"ulong\", \"source_type\": \"GlobalSlice<Float>\", \"address_space\": null, \"is_const\": null, \"is_uniq\": false, \"size\": 8, \"align\": 8, \"elem\": null},\n"));
+    assert!(json.contains("  \"panics\": []\n"));
+}
+
+#[test]
+fn test_manifest_new_describes_struct_layouts()
+{
+    let s = "
+import opencl;
+data T = A() | B(Int, Double) | C(Char);
+kernel k(xs: UniqGlobalSlice<T>) -> () =
+    let i = get_global_id(0 as Uint);
+        _ = xs[i] <- C('a');
+    in  ();
+";
+    let manifest = manifest_for_string(&s[1..]);
+    let desc = manifest.types.iter().find(|desc| desc.ident == "_VS1TNP").unwrap();
+    assert_eq!(TypeDescKind::Struct, desc.kind);
+    assert_eq!(2, desc.field_types.len());
+    assert_eq!(vec![0, 8], desc.layout.field_offsets);
+    assert_eq!(24, desc.layout.size);
+    assert_eq!(8, desc.layout.align);
+    assert!(manifest.types.iter().any(|desc| desc.kind == TypeDescKind::Union && desc.layout.size == 16));
+    let json = manifest.to_json();
+    assert!(json.contains("      \"name\": \"_VS1TNP\",\n      \"kind\": \"struct\",\n      \"size\": 24,\n      \"align\": 8,\n"));
+    assert!(json.contains("        {\"index\": 1, \"type\": \"union _VU1TNP\", \"offset\": 8}\n"));
+}
+
+#[test]
+fn test_manifest_new_describes_panic_table()
+{
+    let s = "
+import opencl;
+recursive(4) fib(n: Int) -> Int = if n < 2 then n else fib(n - 1) + fib(n - 2);
+kernel k(xs: UniqGlobalSlice<Int>) -> () =
+    let i = get_global_id(0 as Uint);
+        (x, xs) = xs[i] ->;
+        _ = xs[i] <- fib(x);
+    in  ();
+";
+    let manifest = manifest_for_string(&s[1..]);
+    let kernel = manifest.kernel("k").unwrap();
+    let pb_arg = kernel.args.last().unwrap();
+    assert_eq!(ArgKind::PanicBuffer, pb_arg.kind);
+    assert_eq!(String::from("pb"), pb_arg.ident);
+    assert_eq!(IrType::Ptr(IrPtrModifier::Global, IrAccessModifier::None, Box::new(IrType::Uint)), *pb_arg.typ);
+    assert_eq!(None, pb_arg.source_type);
+    assert!(!manifest.panic_table.entries().is_empty());
+    let json = manifest.to_json();
+    assert!(json.contains(format!("  \"address_bits\": {},\n", ADDRESS_BITS).as_str()));
+    assert!(json.contains(format!("  \"panic_buffer_len\": {},\n", PANIC_BUFFER_LEN).as_str()));
+    assert!(json.contains("    {\"id\": 1, \"kind\": \"call\", \"message\": null, \"poses\": [{\"path\": \"(string)\", \"line\": 6, \"column\": 22}]},\n"));
+    assert!(json.contains("    {\"id\": 2, \"kind\": \"panic\", \"message\": \"recursion depth of function fib is exceeded\", \"poses\": [{\"path\": \"(string)\", \"line\": 2, \"column\": 56}]},\n"));
+}
+
+#[test]
+fn test_emit_manifest_escapes_strings()
+{
+    let mut panic_table = PanicTable::new();
+    panic_table.add_entry(PanicEntry::Panic(String::from("a \"b\"\\\n\u{1}"), Vec::new()));
+    let json = match emit_manifest(&Tree::new(), &IrTree::new(), &panic_table) {
+        Ok(json) => json,
+        Err(err) => {
+            println!("{}", err);
+            panic!();
+        },
+    };
+    assert!(json.contains("  \"kernels\": [],\n"));
+    assert!(json.contains("  \"types\": [],\n"));
+    assert!(json.contains("    {\"id\": 1, \"kind\": \"panic\", \"message\": \"a \\\"b\\\"\\\\\\n\\u0001\", \"poses\": []}\n"));
+}
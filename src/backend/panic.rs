@@ -0,0 +1,124 @@
+//
+// Copyright (c) 2025 Łukasz Szpakowski
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+use std::fmt;
+use crate::frontend::error::Pos;
+use crate::backend::error::*;
+
+pub const PANIC_ID_INDEX: usize = 0;
+pub const PANIC_GLOBAL_ID_INDEX: usize = 1;
+pub const PANIC_CALL_COUNT_INDEX: usize = 4;
+pub const PANIC_CALL_ID_INDEX: usize = 5;
+pub const MAX_PANIC_CALL_COUNT: usize = 27;
+pub const PANIC_BUFFER_LEN: usize = PANIC_CALL_ID_INDEX + MAX_PANIC_CALL_COUNT;
+
+#[derive(Clone, Debug)]
+pub enum PanicEntry
+{
+    Panic(String, Vec<Pos>),
+    Call(Vec<Pos>),
+}
+
+#[derive(Clone, Debug)]
+pub struct PanicTable
+{
+    entries: Vec<PanicEntry>,
+}
+
+impl PanicTable
+{
+    pub fn new() -> Self
+    { PanicTable { entries: Vec::new(), } }
+
+    pub fn entries(&self) -> &[PanicEntry]
+    { self.entries.as_slice() }
+
+    pub fn entry(&self, id: u32) -> Option<&PanicEntry>
+    {
+        if id > 0 {
+            self.entries.get((id - 1) as usize)
+        } else {
+            None
+        }
+    }
+
+    pub fn add_entry(&mut self, entry: PanicEntry) -> u32
+    {
+        self.entries.push(entry);
+        self.entries.len() as u32
+    }
+
+    pub fn decode(&self, buf: &[u32]) -> BackendResult<Option<Panic>>
+    {
+        if buf.len() < PANIC_BUFFER_LEN {
+            return Err(BackendError::Internal(String::from("decode: too short panic buffer")));
+        }
+        if buf[PANIC_ID_INDEX] == 0 {
+            return Ok(None);
+        }
+        let (msg, mut poses) = match self.entry(buf[PANIC_ID_INDEX]) {
+            Some(PanicEntry::Panic(msg, poses)) => (msg.clone(), poses.clone()),
+            _ => return Err(BackendError::Internal(format!("decode: invalid panic identifier {}", buf[PANIC_ID_INDEX]))),
+        };
+        let call_count = buf[PANIC_CALL_COUNT_INDEX] as usize;
+        for call_id in &buf[PANIC_CALL_ID_INDEX..(PANIC_CALL_ID_INDEX + call_count.min(MAX_PANIC_CALL_COUNT))] {
+            match self.entry(*call_id) {
+                Some(PanicEntry::Call(call_poses)) => poses.extend_from_slice(call_poses.as_slice()),
+                _ => return Err(BackendError::Internal(format!("decode: invalid call identifier {}", call_id))),
+            }
+        }
+        let mut global_id = [0u32; 3];
+        global_id.copy_from_slice(&buf[PANIC_GLOBAL_ID_INDEX..(PANIC_GLOBAL_ID_INDEX + 3)]);
+        Ok(Some(Panic {
+                msg,
+                poses,
+                global_id,
+                is_truncated: call_count > MAX_PANIC_CALL_COUNT,
+        }))
+    }
+}
+
+impl Default for PanicTable
+{
+    fn default() -> Self
+    { Self::new() }
+}
+
+#[derive(Clone, Debug)]
+pub struct Panic
+{
+    pub msg: String,
+    pub poses: Vec<Pos>,
+    pub global_id: [u32; 3],
+    pub is_truncated: bool,
+}
+
+impl fmt::Display for Panic
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        let mut is_first = true;
+        for pos in &self.poses {
+            if is_first {
+                write!(f, "{}: {}.{}: {} (work-item {}, {}, {})", pos.path, pos.line, pos.column, self.msg, self.global_id[0], self.global_id[1], self.global_id[2])?;
+            } else {
+                write!(f, "\n{}: {}.{}: called from here", pos.path, pos.line, pos.column)?;
+            }
+            is_first = false;
+        }
+        if self.is_truncated {
+            write!(f, "\n...")?;
+        }
+        Ok(())
+    }
+}
+
+pub fn decode_panic_buffer(buf: &[u32], table: &PanicTable) -> BackendResult<Option<Panic>>
+{ table.decode(buf) }
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,94 @@
+//
+// Copyright (c) 2025 Łukasz Szpakowski
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+use super::*;
+
+#[test]
+fn test_panic_table_decode_decodes_buffer_without_panic()
+{
+    let mut panic_table = PanicTable::new();
+    panic_table.add_entry(PanicEntry::Panic(String::from("some panic"), vec![Pos::new(String::from("test.vscfl"), 1, 2)]));
+    let buf = [0u32; PANIC_BUFFER_LEN];
+    match panic_table.decode(&buf) {
+        Ok(None) => (),
+        _ => assert!(false),
+    }
+}
+
+#[test]
+fn test_panic_table_decode_decodes_buffer_with_panic()
+{
+    let mut panic_table = PanicTable::new();
+    assert_eq!(1, panic_table.add_entry(PanicEntry::Panic(String::from("some panic"), vec![Pos::new(String::from("test.vscfl"), 1, 2)])));
+    assert_eq!(2, panic_table.add_entry(PanicEntry::Call(vec![Pos::new(String::from("test.vscfl"), 3, 4), Pos::new(String::from("test.vscfl"), 5, 6)])));
+    assert_eq!(3, panic_table.add_entry(PanicEntry::Call(vec![Pos::new(String::from("test.vscfl"), 7, 8)])));
+    let mut buf = [0u32; PANIC_BUFFER_LEN];
+    buf[PANIC_ID_INDEX] = 1;
+    buf[PANIC_GLOBAL_ID_INDEX] = 10;
+    buf[PANIC_GLOBAL_ID_INDEX + 1] = 2;
+    buf[PANIC_CALL_COUNT_INDEX] = 2;
+    buf[PANIC_CALL_ID_INDEX] = 2;
+    buf[PANIC_CALL_ID_INDEX + 1] = 3;
+    match panic_table.decode(&buf) {
+        Ok(Some(panic)) => {
+            assert_eq!(String::from("some panic"), panic.msg);
+            assert_eq!([10, 2, 0], panic.global_id);
+            assert_eq!(false, panic.is_truncated);
+            assert_eq!(4, panic.poses.len());
+            assert_eq!(1, panic.poses[0].line);
+            assert_eq!(2, panic.poses[0].column);
+            assert_eq!(3, panic.poses[1].line);
+            assert_eq!(4, panic.poses[1].column);
+            assert_eq!(5, panic.poses[2].line);
+            assert_eq!(6, panic.poses[2].column);
+            assert_eq!(7, panic.poses[3].line);
+            assert_eq!(8, panic.poses[3].column);
+            let expected = "
+test.vscfl: 1.2: some panic (work-item 10, 2, 0)
+test.vscfl: 3.4: called from here
+test.vscfl: 5.6: called from here
+test.vscfl: 7.8: called from here";
+            assert_eq!(String::from(&expected[1..]), format!("{}", panic));
+        },
+        _ => assert!(false),
+    }
+}
+
+#[test]
+fn test_panic_table_decode_decodes_buffer_with_truncated_call_chain()
+{
+    let mut panic_table = PanicTable::new();
+    panic_table.add_entry(PanicEntry::Panic(String::from("some panic"), vec![Pos::new(String::from("test.vscfl"), 1, 2)]));
+    panic_table.add_entry(PanicEntry::Call(vec![Pos::new(String::from("test.vscfl"), 3, 4)]));
+    let mut buf = [0u32; PANIC_BUFFER_LEN];
+    buf[PANIC_ID_INDEX] = 1;
+    buf[PANIC_CALL_COUNT_INDEX] = (MAX_PANIC_CALL_COUNT + 1) as u32;
+    for i in 0..MAX_PANIC_CALL_COUNT {
+        buf[PANIC_CALL_ID_INDEX + i] = 2;
+    }
+    match panic_table.decode(&buf) {
+        Ok(Some(panic)) => {
+            assert_eq!(true, panic.is_truncated);
+            assert_eq!(MAX_PANIC_CALL_COUNT + 1, panic.poses.len());
+            assert!(format!("{}", panic).ends_with("\n..."));
+        },
+        _ => assert!(false),
+    }
+}
+
+#[test]
+fn test_panic_table_decode_complains_on_invalid_panic_identifier()
+{
+    let mut panic_table = PanicTable::new();
+    panic_table.add_entry(PanicEntry::Call(vec![Pos::new(String::from("test.vscfl"), 3, 4)]));
+    let mut buf = [0u32; PANIC_BUFFER_LEN];
+    buf[PANIC_ID_INDEX] = 1;
+    match panic_table.decode(&buf) {
+        Err(BackendError::Internal(msg)) => assert_eq!(String::from("decode: invalid panic identifier 1"), msg),
+        _ => assert!(false),
+    }
+}
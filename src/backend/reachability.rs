@@ -0,0 +1,388 @@
+//
+// Copyright (c) 2025 Łukasz Szpakowski
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+use std::collections::BTreeSet;
+use crate::backend::ir::*;
+
+trait MarkVar
+{
+    fn mark_var(&self, marker: &mut Marker);
+}
+
+struct Marker<'a>
+{
+    tree: &'a IrTree,
+    structs: BTreeSet<String>,
+    unions: BTreeSet<String>,
+    vars: BTreeSet<String>,
+}
+
+impl<'a> Marker<'a>
+{
+    fn new(tree: &'a IrTree) -> Self
+    {
+        Marker {
+            tree,
+            structs: BTreeSet::new(),
+            unions: BTreeSet::new(),
+            vars: BTreeSet::new(),
+        }
+    }
+
+    fn mark_struct(&mut self, ident: &String)
+    {
+        if self.structs.contains(ident) {
+            return;
+        }
+        self.structs.insert(ident.clone());
+        if let Some(struct1) = self.tree.struct1(ident) {
+            match &*struct1.borrow() {
+                IrStruct::Struct(field_types) => {
+                    for field_type in field_types {
+                        self.mark_type(field_type);
+                    }
+                },
+                IrStruct::Closure(field_types) => {
+                    for field_type in field_types.values() {
+                        self.mark_type(field_type);
+                    }
+                },
+            }
+        }
+    }
+
+    fn mark_union(&mut self, ident: &String)
+    {
+        if self.unions.contains(ident) {
+            return;
+        }
+        self.unions.insert(ident.clone());
+        if let Some(union) = self.tree.union(ident) {
+            for field_type in &union.borrow().0 {
+                self.mark_type(field_type);
+            }
+        }
+    }
+
+    fn mark_global_var(&mut self, ident: &String)
+    {
+        if self.vars.contains(ident) {
+            return;
+        }
+        self.vars.insert(ident.clone());
+        if let Some(var) = self.tree.var(ident) {
+            match &*var.borrow() {
+                IrVar::Const(typ, value) => {
+                    self.mark_type(typ);
+                    self.mark_value(value);
+                },
+                IrVar::Var(_, _, typ, value) => {
+                    self.mark_type(typ);
+                    self.mark_value(value);
+                },
+                IrVar::Fun(fun) => self.mark_fun(fun),
+            }
+        }
+    }
+
+    fn mark_type(&mut self, typ: &IrType)
+    {
+        match typ {
+            IrType::Struct(ident) => self.mark_struct(ident),
+            IrType::Union(ident) => self.mark_union(ident),
+            IrType::Array(elem_type, _) => self.mark_type(elem_type),
+            IrType::Ptr(_, _, elem_type) => self.mark_type(elem_type),
+            _ => (),
+        }
+    }
+
+    fn mark_opt_type(&mut self, typ: &Option<Box<IrType>>)
+    {
+        if let Some(typ) = typ {
+            self.mark_type(typ);
+        }
+    }
+
+    fn mark_field_pairs<T: MarkVar>(&mut self, field_pairs: &[IrFieldPair<T>])
+    {
+        for field_pair in field_pairs {
+            self.mark_value(&field_pair.1);
+        }
+    }
+
+    fn mark_values<T: MarkVar>(&mut self, values: &[IrValue<T>])
+    {
+        for value in values {
+            self.mark_value(value);
+        }
+    }
+
+    fn mark_value<T: MarkVar>(&mut self, value: &IrValue<T>)
+    {
+        match value {
+            IrValue::Char(_, typ) | IrValue::Short(_, typ) | IrValue::Int(_, typ) | IrValue::Long(_, typ) => self.mark_opt_type(typ),
+            IrValue::Uchar(_, typ) | IrValue::Ushort(_, typ) | IrValue::Uint(_, typ) | IrValue::Ulong(_, typ) => self.mark_opt_type(typ),
+            IrValue::Float(_, typ) | IrValue::Double(_, typ) => self.mark_opt_type(typ),
+            IrValue::SizeT(_, typ) | IrValue::PtrdiffT(_, typ) | IrValue::IntptrT(_, typ) | IrValue::UintptrT(_, typ) => self.mark_opt_type(typ),
+            IrValue::Object(object) => {
+                match &**object {
+                    IrObject::String(_) => (),
+                    IrObject::BuiltinVar(_, typ, cast_type) => {
+                        self.mark_opt_type(typ);
+                        self.mark_opt_type(cast_type);
+                    },
+                    IrObject::Var(var, cast_type) => {
+                        var.mark_var(self);
+                        self.mark_opt_type(cast_type);
+                    },
+                    IrObject::Vector(values, typ) => {
+                        self.mark_values(values.as_slice());
+                        self.mark_type(typ);
+                    },
+                    IrObject::Array(values, cast_type) => {
+                        self.mark_values(values.as_slice());
+                        self.mark_opt_type(cast_type);
+                    },
+                    IrObject::Struct(values, field_pairs, cast_type) => {
+                        self.mark_values(values.as_slice());
+                        self.mark_field_pairs(field_pairs.as_slice());
+                        self.mark_opt_type(cast_type);
+                    },
+                    IrObject::Union(_, value, cast_type) => {
+                        self.mark_value(value);
+                        self.mark_opt_type(cast_type);
+                    },
+                    IrObject::Closure(field_pairs, cast_type) => {
+                        self.mark_field_pairs(field_pairs.as_slice());
+                        self.mark_opt_type(cast_type);
+                    },
+                    IrObject::Sizeof(typ, cast_type) => {
+                        self.mark_type(typ);
+                        self.mark_opt_type(cast_type);
+                    },
+                    IrObject::CallerFunIndex(ident, _, cast_type) => {
+                        self.mark_global_var(ident);
+                        self.mark_opt_type(cast_type);
+                    },
+                }
+            },
+        }
+    }
+
+    fn mark_arg_ops(&mut self, ops: &[IrArgOp])
+    {
+        for op in ops {
+            match op {
+                IrArgOp::Deref(typ) | IrArgOp::Dot(typ, _) | IrArgOp::Arrow(typ, _) | IrArgOp::Index(typ, _) => self.mark_opt_type(typ),
+                IrArgOp::GlobalIndex(typ, ident) => {
+                    self.mark_opt_type(typ);
+                    self.mark_global_var(ident);
+                },
+                IrArgOp::LocalIndex(typ, _) | IrArgOp::CallerFunArgIndex(typ, _) => self.mark_opt_type(typ),
+                IrArgOp::PrivateClosureIndex(typ, _) | IrArgOp::LocalClosureIndex(typ, _) | IrArgOp::GlobalClosureIndex(typ, _) => self.mark_opt_type(typ),
+            }
+        }
+    }
+
+    fn mark_instr_var(&mut self, var: &IrInstrVar)
+    {
+        match var {
+            IrInstrVar::Global(ident, ops) => {
+                self.mark_global_var(ident);
+                self.mark_arg_ops(ops.as_slice());
+            },
+            IrInstrVar::Local(_, ops) | IrInstrVar::CallerFunArg(_, ops) => self.mark_arg_ops(ops.as_slice()),
+            IrInstrVar::PrivateClosure(_, ops) | IrInstrVar::LocalClosure(_, ops) | IrInstrVar::GlobalClosure(_, ops) => self.mark_arg_ops(ops.as_slice()),
+            IrInstrVar::PrivateHeap(ops) | IrInstrVar::LocalHeap(ops) | IrInstrVar::GlobalHeap(ops) => self.mark_arg_ops(ops.as_slice()),
+        }
+    }
+
+    fn mark_op(&mut self, op: &IrOp)
+    {
+        match op {
+            IrOp::Load(value) | IrOp::Neg(value) | IrOp::Not(value) => self.mark_value(value),
+            IrOp::Mul(value1, value2) | IrOp::Div(value1, value2) | IrOp::Rem(value1, value2) |
+            IrOp::Add(value1, value2) | IrOp::Sub(value1, value2) | IrOp::Shl(value1, value2) |
+            IrOp::Shr(value1, value2) | IrOp::Eq(value1, value2) | IrOp::Ne(value1, value2) |
+            IrOp::Lt(value1, value2) | IrOp::Ge(value1, value2) | IrOp::Gt(value1, value2) |
+            IrOp::Le(value1, value2) | IrOp::And(value1, value2) | IrOp::Xor(value1, value2) |
+            IrOp::Or(value1, value2) => {
+                self.mark_value(value1);
+                self.mark_value(value2);
+            },
+            IrOp::CallBuiltinFun(_, typ, values) => {
+                self.mark_opt_type(typ);
+                self.mark_values(values.as_slice());
+            },
+            IrOp::CallFun(ident, values, _, _) | IrOp::CallFunWithoutPanic(ident, values, _) => {
+                self.mark_global_var(ident);
+                self.mark_values(values.as_slice());
+            },
+        }
+    }
+
+    fn mark_case_value(&mut self, value: &IrCaseValue)
+    {
+        match value {
+            IrCaseValue::Char(_, typ) | IrCaseValue::Short(_, typ) | IrCaseValue::Int(_, typ) | IrCaseValue::Long(_, typ) => self.mark_opt_type(typ),
+            IrCaseValue::Uchar(_, typ) | IrCaseValue::Ushort(_, typ) | IrCaseValue::Uint(_, typ) | IrCaseValue::Ulong(_, typ) => self.mark_opt_type(typ),
+            IrCaseValue::SizeT(_, typ) | IrCaseValue::PtrdiffT(_, typ) | IrCaseValue::IntptrT(_, typ) | IrCaseValue::UintptrT(_, typ) => self.mark_opt_type(typ),
+            IrCaseValue::BuiltinVar(_, typ, cast_type) => {
+                self.mark_opt_type(typ);
+                self.mark_opt_type(cast_type);
+            },
+        }
+    }
+
+    fn mark_block(&mut self, block: &IrBlock)
+    {
+        for local_var_pair in block.local_var_pairs() {
+            self.mark_type(&local_var_pair.1);
+        }
+        for instr in block.instrs() {
+            match instr {
+                IrInstr::Op(op) => self.mark_op(op),
+                IrInstr::Assign(var, op) => {
+                    self.mark_instr_var(var);
+                    self.mark_op(op);
+                },
+                IrInstr::Return(Some(op)) => self.mark_op(op),
+                IrInstr::Block(block) | IrInstr::Loop(block) => self.mark_block(block),
+                IrInstr::If(op, block1, block2) => {
+                    self.mark_op(op);
+                    self.mark_block(block1);
+                    self.mark_block(block2);
+                },
+                IrInstr::Switch(op, cases) => {
+                    self.mark_op(op);
+                    for case in cases {
+                        match case {
+                            IrCase::Case(value, block) => {
+                                self.mark_case_value(value);
+                                self.mark_block(block);
+                            },
+                            IrCase::Default(block) => self.mark_block(block),
+                        }
+                    }
+                },
+                _ => (),
+            }
+        }
+    }
+
+    fn mark_fun(&mut self, fun: &IrFun)
+    {
+        match fun {
+            IrFun::Fun(_, arg_types, ret_type, block, _, _, _, _) => {
+                for arg_type in arg_types {
+                    self.mark_type(arg_type);
+                }
+                self.mark_type(ret_type);
+                self.mark_block(block);
+            },
+            IrFun::Caller(fun_type, arg_types, ret_type, caller_funs, _, _, _, _) => {
+                self.mark_type(fun_type);
+                for arg_type in arg_types {
+                    self.mark_type(arg_type);
+                }
+                self.mark_type(ret_type);
+                for caller_fun in caller_funs.funs().values() {
+                    match caller_fun {
+                        IrCallerFun::Fun(ident) => self.mark_global_var(ident),
+                        IrCallerFun::InlineFun(ident, block) => {
+                            self.mark_global_var(ident);
+                            self.mark_block(block);
+                        },
+                        IrCallerFun::Lambda(private_closure_type, local_closure_type, global_closure_type, _, block) => {
+                            self.mark_opt_type(private_closure_type);
+                            self.mark_opt_type(local_closure_type);
+                            self.mark_opt_type(global_closure_type);
+                            self.mark_block(block);
+                        },
+                    }
+                }
+            },
+        }
+    }
+}
+
+impl MarkVar for IrValueVar
+{
+    fn mark_var(&self, marker: &mut Marker)
+    {
+        marker.mark_global_var(&self.0);
+        for op in &self.1 {
+            match op {
+                IrValueOp::Deref(typ) | IrValueOp::Dot(typ, _) | IrValueOp::Arrow(typ, _) | IrValueOp::Index(typ, _) => marker.mark_opt_type(typ),
+            }
+        }
+    }
+}
+
+impl MarkVar for IrArgVar
+{
+    fn mark_var(&self, marker: &mut Marker)
+    {
+        match self {
+            IrArgVar::Global(ident, ops) => {
+                marker.mark_global_var(ident);
+                marker.mark_arg_ops(ops.as_slice());
+            },
+            IrArgVar::RefGlobal(ident, ops, typ) => {
+                marker.mark_global_var(ident);
+                marker.mark_arg_ops(ops.as_slice());
+                marker.mark_opt_type(typ);
+            },
+            IrArgVar::Local(_, ops) | IrArgVar::CallerFunArg(_, ops) => marker.mark_arg_ops(ops.as_slice()),
+            IrArgVar::PrivateClosure(_, ops) | IrArgVar::LocalClosure(_, ops) | IrArgVar::GlobalClosure(_, ops) => marker.mark_arg_ops(ops.as_slice()),
+            IrArgVar::PrivateHeap(ops) | IrArgVar::LocalHeap(ops) | IrArgVar::GlobalHeap(ops) => marker.mark_arg_ops(ops.as_slice()),
+            IrArgVar::RefLocal(_, ops, typ) | IrArgVar::RefCallerFunArg(_, ops, typ) => {
+                marker.mark_arg_ops(ops.as_slice());
+                marker.mark_opt_type(typ);
+            },
+            IrArgVar::RefPrivateClosure(_, ops, typ) | IrArgVar::RefLocalClosure(_, ops, typ) | IrArgVar::RefGlobalClosure(_, ops, typ) => {
+                marker.mark_arg_ops(ops.as_slice());
+                marker.mark_opt_type(typ);
+            },
+            IrArgVar::RefPrivateHeap(ops, typ) | IrArgVar::RefLocalHeap(ops, typ) | IrArgVar::RefGlobalHeap(ops, typ) => {
+                marker.mark_arg_ops(ops.as_slice());
+                marker.mark_opt_type(typ);
+            },
+        }
+    }
+}
+
+pub fn remove_unreachable_defs(tree: &mut IrTree)
+{
+    let (structs, unions, vars) = {
+        let mut marker = Marker::new(tree);
+        for def in tree.defs() {
+            if let IrDef::Var(ident, var) = &**def {
+                let is_kernel = match &*var.borrow() {
+                    IrVar::Fun(fun) => {
+                        matches!(&**fun, IrFun::Fun(IrFunModifier::Kernel, _, _, _, _, _, _, _))
+                    },
+                    _ => false,
+                };
+                if is_kernel {
+                    marker.mark_global_var(ident);
+                }
+            }
+        }
+        (marker.structs, marker.unions, marker.vars)
+    };
+    tree.retain_defs(|def| {
+            match def {
+                IrDef::Struct(ident, _) => structs.contains(ident),
+                IrDef::Union(ident, _) => unions.contains(ident),
+                IrDef::Var(ident, _) => vars.contains(ident),
+            }
+    });
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,147 @@
+//
+// Copyright (c) 2025 Łukasz Szpakowski
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+use crate::frontend::do_frontend_phases;
+use crate::backend::ir::parser::*;
+use crate::backend::ir::printer::*;
+use crate::backend::lowering::*;
+use super::*;
+
+#[test]
+fn test_reachability_remove_unreachable_defs_removes_unreachable_defs()
+{
+    let s = "
+struct S { int, union U }
+
+struct T { float }
+
+union U { int, struct T }
+
+union V { int }
+
+closure struct C { 0: int }
+
+closure struct D { 0: int }
+
+struct F { uint }
+
+const @n: uint = uint 4;
+
+var global @a: [int; 4] = array [];
+
+var global @b: int = int 0;
+
+fun @f(*private struct S, int) -> int
+{
+    return load @a[@n];
+}
+
+fun @g(int) -> int
+{
+    var union V;
+    return load v0;
+}
+
+fun @l(struct F, int) -> int
+{
+    return call @h(v0, v1) at \"a.vscfl\":1:1;
+}
+
+caller @h(struct F; int) -> int
+{
+    1: fun @f2;
+    2: lambda private struct C start 1 {
+        return load pc0;
+    }
+}
+
+fun @f2(int) -> int
+{
+    return load v0;
+}
+
+fun @unused(struct D) -> int
+{
+    return call @g(int 1) at \"a.vscfl\":1:1;
+}
+
+kernel fun @k(*global int) -> void
+{
+    var struct S;
+    v0.* = call @f(&<*private struct S>v1, int 1) at \"a.vscfl\":1:1;
+    v0.* = call @l(struct { caller_index @h 2 as uint } as struct F, int 2) at \"a.vscfl\":1:1;
+    return;
+}
+";
+    let s2 = &s[1..];
+    let mut ir_tree = match parse_ir_tree(s2) {
+        Ok(ir_tree) => ir_tree,
+        Err(err) => {
+            println!("{}", err);
+            panic!();
+        },
+    };
+    remove_unreachable_defs(&mut ir_tree);
+    let idents: Vec<String> = ir_tree.defs().iter().map(|def| {
+            match &**def {
+                IrDef::Struct(ident, _) | IrDef::Union(ident, _) | IrDef::Var(ident, _) => ident.clone(),
+            }
+    }).collect();
+    assert_eq!(vec![
+            String::from("S"),
+            String::from("T"),
+            String::from("U"),
+            String::from("C"),
+            String::from("F"),
+            String::from("n"),
+            String::from("a"),
+            String::from("f"),
+            String::from("l"),
+            String::from("h"),
+            String::from("f2"),
+            String::from("k")
+    ], idents);
+    assert!(ir_tree.struct1(&String::from("D")).is_none());
+    assert!(ir_tree.union(&String::from("V")).is_none());
+    assert!(ir_tree.var(&String::from("b")).is_none());
+    assert!(ir_tree.var(&String::from("g")).is_none());
+    assert!(ir_tree.var(&String::from("unused")).is_none());
+    assert!(ir_tree.var(&String::from("k")).is_some());
+}
+
+#[test]
+fn test_reachability_remove_unreachable_defs_removes_functions_of_folded_branches()
+{
+    let s = "
+import opencl;
+g(x: Int) -> Int = x * 2;
+f(x: Int) -> Int = if 1 > 2 then g(x) else x + 1;
+kernel k(xs: UniqGlobalSlice<Int>) -> () =
+    let i = get_global_id(0 as Uint);
+        _ = xs[i] <- f(3);
+    in  ();
+";
+    let s2 = &s[1..];
+    let tree = match do_frontend_phases(s2) {
+        Ok(tree) => tree,
+        Err(errs) => {
+            println!("{}", errs);
+            panic!();
+        },
+    };
+    let ir_tree = match Lowerer::new_with_inline_threshold(0).lower_tree(&tree) {
+        Ok(ir_tree) => ir_tree,
+        Err(err) => {
+            println!("{}", err);
+            panic!();
+        },
+    };
+    assert!(ir_tree.var(&String::from("_VF1k")).is_some());
+    assert!(ir_tree.var(&String::from("_VF1f")).is_some());
+    assert!(ir_tree.var(&String::from("_VF1g")).is_none());
+    assert!(!ir_tree_to_string(&ir_tree).contains("_VF1g"));
+}
@@ -0,0 +1,321 @@
+//
+// Copyright (c) 2025 Łukasz Szpakowski
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::fmt::Write;
+use crate::backend::error::*;
+use crate::backend::ir::*;
+use crate::backend::layout::*;
+use crate::backend::manifest::*;
+use crate::backend::panic::*;
+
+const ARGS_NAME: &str = "vscfl_args";
+
+const RUST_KEYWORDS: &[&str] = &[
+    "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "do",
+    "dyn", "else", "enum", "extern", "false", "final", "fn", "for", "if", "impl", "in", "let",
+    "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref", "return",
+    "static", "struct", "trait", "true", "try", "type", "typeof", "unsafe", "unsized", "use",
+    "virtual", "where", "while", "yield"
+];
+
+fn rust_ident(ident: &str) -> String
+{
+    match ident {
+        "crate" | "self" | "Self" | "super" => format!("{}_", ident),
+        _ if RUST_KEYWORDS.contains(&ident) => format!("r#{}", ident),
+        _ => String::from(ident),
+    }
+}
+
+fn vector_elem_type_and_len(typ: &IrType) -> Option<(&'static str, &'static str, usize)>
+{
+    match typ {
+        IrType::CharN(n) => Some(("Char", "i8", *n)),
+        IrType::ShortN(n) => Some(("Short", "i16", *n)),
+        IrType::IntN(n) => Some(("Int", "i32", *n)),
+        IrType::LongN(n) => Some(("Long", "i64", *n)),
+        IrType::UcharN(n) => Some(("Uchar", "u8", *n)),
+        IrType::UshortN(n) => Some(("Ushort", "u16", *n)),
+        IrType::UintN(n) => Some(("Uint", "u32", *n)),
+        IrType::UlongN(n) => Some(("Ulong", "u64", *n)),
+        IrType::FloatN(n) => Some(("Float", "f32", *n)),
+        IrType::DoubleN(n) => Some(("Double", "f64", *n)),
+        _ => None,
+    }
+}
+
+fn rust_type(typ: &IrType) -> BackendResult<String>
+{
+    if let Some((ident, _, n)) = vector_elem_type_and_len(typ) {
+        return Ok(format!("{}{}", ident, n));
+    }
+    match typ {
+        IrType::Void => Err(BackendError::Internal(String::from("rust_type: void type"))),
+        IrType::Char => Ok(String::from("i8")),
+        IrType::Short => Ok(String::from("i16")),
+        IrType::Int => Ok(String::from("i32")),
+        IrType::Long => Ok(String::from("i64")),
+        IrType::Uchar => Ok(String::from("u8")),
+        IrType::Ushort | IrType::Half => Ok(String::from("u16")),
+        IrType::Uint => Ok(String::from("u32")),
+        IrType::Ulong => Ok(String::from("u64")),
+        IrType::Float => Ok(String::from("f32")),
+        IrType::Double => Ok(String::from("f64")),
+        IrType::SizeT | IrType::UintptrT => Ok(String::from("u64")),
+        IrType::PtrdiffT | IrType::IntptrT => Ok(String::from("i64")),
+        IrType::Struct(ident) | IrType::Union(ident) => Ok(ident.clone()),
+        IrType::Array(elem_type, len) => Ok(format!("[{}; {}]", rust_type(elem_type)?, len)),
+        IrType::Ptr(_, _, _) => Ok(String::from("u64")),
+        _ => Err(BackendError::Internal(String::from("rust_type: unsupported type"))),
+    }
+}
+
+fn add_vector_types(typ: &IrType, vector_types: &mut BTreeSet<String>)
+{
+    match typ {
+        IrType::Array(elem_type, _) | IrType::Ptr(_, _, elem_type) => add_vector_types(elem_type, vector_types),
+        _ => {
+            if let Some((ident, _, n)) = vector_elem_type_and_len(typ) {
+                vector_types.insert(format!("{}{}", ident, n));
+            }
+        },
+    }
+}
+
+fn data_type_ident(arg: &ArgDesc) -> Option<String>
+{
+    let source_type = arg.source_type.as_ref()?;
+    let ident = match arg.kind {
+        ArgKind::SlicePtr | ArgKind::Ptr => {
+            let start = source_type.find('<')?;
+            let end = source_type.rfind('>')?;
+            &source_type[(start + 1)..end]
+        },
+        _ => source_type.as_str(),
+    };
+    if ident.starts_with(|c: char| c.is_ascii_uppercase()) && ident.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+        Some(String::from(ident))
+    } else {
+        None
+    }
+}
+
+pub struct RustBindingsEmitter
+{}
+
+impl RustBindingsEmitter
+{
+    pub fn new() -> Self
+    { RustBindingsEmitter {} }
+
+    pub fn emit(&self, manifest: &Manifest) -> BackendResult<String>
+    {
+        let mut s = String::new();
+        let aliases = self.data_type_aliases(manifest);
+        self.emit_prelude(&mut s);
+        self.emit_vector_types(manifest, &mut s);
+        self.emit_types(manifest, &mut s)?;
+        for (alias, ident) in &aliases {
+            writeln!(s, "pub type {} = {};", alias, ident).unwrap();
+            s.push('\n');
+        }
+        for kernel in &manifest.kernels {
+            self.emit_kernel(kernel, &aliases, &mut s)?;
+        }
+        Ok(s)
+    }
+
+    fn emit_prelude(&self, s: &mut String)
+    {
+        writeln!(s, "pub const ADDRESS_BITS: u32 = {};", ADDRESS_BITS).unwrap();
+        writeln!(s, "pub const PANIC_BUFFER_LEN: usize = {};", PANIC_BUFFER_LEN).unwrap();
+        s.push('\n');
+        s.push_str("pub trait KernelArgs<'a> {\n");
+        s.push_str("    type Error;\n");
+        s.push('\n');
+        s.push_str("    fn set_value_arg<T: Copy + 'a>(&mut self, idx: u32, value: T) -> Result<(), Self::Error>;\n");
+        s.push('\n');
+        s.push_str("    fn set_slice_arg<T: Copy + 'a>(&mut self, idx: u32, slice: &'a [T]) -> Result<(), Self::Error>;\n");
+        s.push('\n');
+        s.push_str("    fn set_uniq_slice_arg<T: Copy + 'a>(&mut self, idx: u32, slice: &'a mut [T]) -> Result<(), Self::Error>;\n");
+        s.push('\n');
+        s.push_str("    fn set_local_arg<T: Copy + 'a>(&mut self, idx: u32, len: usize) -> Result<(), Self::Error>;\n");
+        s.push_str("}\n");
+        s.push('\n');
+    }
+
+    fn emit_vector_types(&self, manifest: &Manifest, s: &mut String)
+    {
+        let mut vector_types: BTreeSet<String> = BTreeSet::new();
+        for kernel in &manifest.kernels {
+            for arg in &kernel.args {
+                add_vector_types(&arg.typ, &mut vector_types);
+            }
+        }
+        for desc in &manifest.types {
+            for field_type in &desc.field_types {
+                add_vector_types(field_type, &mut vector_types);
+            }
+        }
+        for (ident, elem_ident, elem_size) in &[("Char", "i8", 1), ("Short", "i16", 2), ("Int", "i32", 4), ("Long", "i64", 8), ("Uchar", "u8", 1), ("Ushort", "u16", 2), ("Uint", "u32", 4), ("Ulong", "u64", 8), ("Float", "f32", 4), ("Double", "f64", 8)] {
+            for n in &[2usize, 3, 4, 8, 16] {
+                let name = format!("{}{}", ident, n);
+                if vector_types.contains(&name) {
+                    let len = if *n == 3 { 4 } else { *n };
+                    writeln!(s, "#[repr(C, align({}))]", elem_size * len).unwrap();
+                    s.push_str("#[derive(Copy, Clone, Debug, Default, PartialEq)]\n");
+                    writeln!(s, "pub struct {}(pub [{}; {}]);", name, elem_ident, len).unwrap();
+                    s.push('\n');
+                }
+            }
+        }
+    }
+
+    fn emit_types(&self, manifest: &Manifest, s: &mut String) -> BackendResult<()>
+    {
+        for desc in &manifest.types {
+            s.push_str("#[allow(non_camel_case_types)]\n");
+            s.push_str("#[repr(C)]\n");
+            s.push_str("#[derive(Copy, Clone)]\n");
+            let keyword = match desc.kind {
+                TypeDescKind::Struct | TypeDescKind::Closure => "struct",
+                TypeDescKind::Union => "union",
+            };
+            writeln!(s, "pub {} {} {{", keyword, desc.ident).unwrap();
+            for (field_idx, field_type) in desc.field_indices.iter().zip(desc.field_types.iter()) {
+                writeln!(s, "    pub f{}: {},", field_idx, rust_type(field_type)?).unwrap();
+            }
+            s.push_str("}\n");
+            s.push('\n');
+            writeln!(s, "const _: () = assert!(std::mem::size_of::<{}>() == {} && std::mem::align_of::<{}>() == {});", desc.ident, desc.layout.size, desc.ident, desc.layout.align).unwrap();
+            s.push('\n');
+        }
+        Ok(())
+    }
+
+    fn data_type_aliases(&self, manifest: &Manifest) -> BTreeMap<String, String>
+    {
+        let mut aliases: BTreeMap<String, String> = BTreeMap::new();
+        for kernel in &manifest.kernels {
+            for arg in &kernel.args {
+                let typ = match &*arg.typ {
+                    IrType::Ptr(_, _, elem_type) => &**elem_type,
+                    typ => typ,
+                };
+                if let (IrType::Struct(ident), Some(alias)) = (typ, data_type_ident(arg)) {
+                    aliases.insert(alias, ident.clone());
+                }
+            }
+        }
+        aliases
+    }
+
+    fn arg_rust_type(&self, arg: &ArgDesc, typ: &IrType, aliases: &BTreeMap<String, String>) -> BackendResult<String>
+    {
+        match (typ, data_type_ident(arg)) {
+            (IrType::Struct(_), Some(alias)) if aliases.contains_key(&alias) => Ok(alias),
+            _ => rust_type(typ),
+        }
+    }
+
+    fn emit_kernel(&self, kernel: &KernelDesc, aliases: &BTreeMap<String, String>, s: &mut String) -> BackendResult<()>
+    {
+        let mut params: Vec<String> = vec![format!("{}: &mut A", ARGS_NAME)];
+        let mut lines: Vec<String> = Vec::new();
+        let mut i = 0usize;
+        while i < kernel.args.len() {
+            let arg = &kernel.args[i];
+            let ident = rust_ident(arg.ident.as_str());
+            match (arg.kind, &*arg.typ) {
+                (ArgKind::SlicePtr, IrType::Ptr(ptr_modifier, access_modifier, elem_type)) => {
+                    let elem_ident = self.arg_rust_type(arg, elem_type, aliases)?;
+                    let len_ident = format!("{}_len", arg.ident);
+                    match ptr_modifier {
+                        IrPtrModifier::Local => {
+                            params.push(format!("{}: usize", len_ident));
+                            lines.push(format!("{}.set_local_arg::<{}>({}, {})?;", ARGS_NAME, elem_ident, i, len_ident));
+                        },
+                        IrPtrModifier::Global | IrPtrModifier::Constant => {
+                            if *access_modifier == IrAccessModifier::Const {
+                                params.push(format!("{}: &'a [{}]", ident, elem_ident));
+                                lines.push(format!("let {} = {}.len();", len_ident, ident));
+                                lines.push(format!("{}.set_slice_arg({}, {})?;", ARGS_NAME, i, ident));
+                            } else {
+                                params.push(format!("{}: &'a mut [{}]", ident, elem_ident));
+                                lines.push(format!("let {} = {}.len();", len_ident, ident));
+                                lines.push(format!("{}.set_uniq_slice_arg({}, {})?;", ARGS_NAME, i, ident));
+                            }
+                        },
+                        _ => return Err(BackendError::Internal(format!("emit_kernel: unsupported address space of argument {} of kernel {}", arg.ident, kernel.ident))),
+                    }
+                    match kernel.args.get(i + 1) {
+                        Some(len_arg) if len_arg.kind == ArgKind::SliceLen => lines.push(format!("{}.set_value_arg({}, {} as {})?;", ARGS_NAME, i + 1, len_ident, rust_type(&len_arg.typ)?)),
+                        _ => return Err(BackendError::Internal(format!("emit_kernel: no slice length of argument {} of kernel {}", arg.ident, kernel.ident))),
+                    }
+                    i += 2;
+                    continue;
+                },
+                (ArgKind::Ptr, IrType::Ptr(ptr_modifier, access_modifier, elem_type)) => {
+                    let elem_ident = self.arg_rust_type(arg, elem_type, aliases)?;
+                    match (ptr_modifier, access_modifier) {
+                        (IrPtrModifier::Global | IrPtrModifier::Constant, IrAccessModifier::Const) => {
+                            params.push(format!("{}: &'a {}", ident, elem_ident));
+                            lines.push(format!("{}.set_slice_arg({}, std::slice::from_ref({}))?;", ARGS_NAME, i, ident));
+                        },
+                        (IrPtrModifier::Global, IrAccessModifier::None) => {
+                            params.push(format!("{}: &'a mut {}", ident, elem_ident));
+                            lines.push(format!("{}.set_uniq_slice_arg({}, std::slice::from_mut({}))?;", ARGS_NAME, i, ident));
+                        },
+                        _ => return Err(BackendError::Internal(format!("emit_kernel: unsupported address space of argument {} of kernel {}", arg.ident, kernel.ident))),
+                    }
+                },
+                (ArgKind::Value, typ) => {
+                    params.push(format!("{}: {}", ident, self.arg_rust_type(arg, typ, aliases)?));
+                    lines.push(format!("{}.set_value_arg({}, {})?;", ARGS_NAME, i, ident));
+                },
+                (ArgKind::GlobalHeap, _) => {
+                    params.push(String::from("vscfl_global_heap: &'a mut [u32]"));
+                    lines.push(format!("{}.set_uniq_slice_arg({}, vscfl_global_heap)?;", ARGS_NAME, i));
+                },
+                (ArgKind::PanicBuffer, _) => {
+                    params.push(String::from("vscfl_panic_buffer: &'a mut [u32; PANIC_BUFFER_LEN]"));
+                    lines.push(format!("{}.set_uniq_slice_arg({}, &mut vscfl_panic_buffer[..])?;", ARGS_NAME, i));
+                },
+                _ => return Err(BackendError::Internal(format!("emit_kernel: invalid argument {} of kernel {}", arg.ident, kernel.ident))),
+            }
+            i += 1;
+        }
+        let kernel_ident = kernel.ident.replace("::", "_");
+        writeln!(s, "pub const {}_KERNEL_NAME: &str = \"{}\";", kernel_ident.to_uppercase(), kernel.mangled_name).unwrap();
+        s.push('\n');
+        writeln!(s, "pub fn set_{}_args<'a, A: KernelArgs<'a>>({}) -> Result<(), A::Error> {{", kernel_ident, params.join(", ")).unwrap();
+        for line in &lines {
+            writeln!(s, "    {}", line).unwrap();
+        }
+        s.push_str("    Ok(())\n");
+        s.push_str("}\n");
+        s.push('\n');
+        Ok(())
+    }
+}
+
+impl Default for RustBindingsEmitter
+{
+    fn default() -> Self
+    { Self::new() }
+}
+
+pub fn emit_manifest_to_rust_bindings(manifest: &Manifest) -> BackendResult<String>
+{
+    let emitter = RustBindingsEmitter::new();
+    emitter.emit(manifest)
+}
+
+#[cfg(test)]
+mod tests;
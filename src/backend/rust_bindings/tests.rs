@@ -0,0 +1,194 @@
+//
+// Copyright (c) 2025 Łukasz Szpakowski
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+use std::env;
+use std::process::Command;
+use crate::frontend::do_frontend_phases;
+use crate::backend::emitter::*;
+use crate::backend::lowering::*;
+use crate::utils::test_utils;
+use super::*;
+
+fn emit_string(s: &str) -> String
+{
+    let tree = match do_frontend_phases(s) {
+        Ok(tree) => tree,
+        Err(errs) => {
+            println!("{}", errs);
+            panic!();
+        },
+    };
+    let ir_tree = match lower_tree(&tree) {
+        Ok(ir_tree) => ir_tree,
+        Err(err) => {
+            println!("{}", err);
+            panic!();
+        },
+    };
+    let (_, panic_table) = match emit_ir_tree_with_panic_table(&ir_tree) {
+        Ok(pair) => pair,
+        Err(err) => {
+            println!("{}", err);
+            panic!();
+        },
+    };
+    let manifest = match Manifest::new(&tree, &ir_tree, &panic_table) {
+        Ok(manifest) => manifest,
+        Err(err) => {
+            println!("{}", err);
+            panic!();
+        },
+    };
+    match emit_manifest_to_rust_bindings(&manifest) {
+        Ok(src) => src,
+        Err(err) => {
+            println!("{}", err);
+            panic!();
+        },
+    }
+}
+
+fn rustc() -> String
+{
+    match env::var("RUSTC") {
+        Ok(rustc) => rustc,
+        Err(_) => String::from("rustc"),
+    }
+}
+
+fn has_rustc() -> bool
+{
+    match Command::new(rustc()).arg("--version").output() {
+        Ok(output) => output.status.success(),
+        Err(_) => false,
+    }
+}
+
+fn compile_and_run(name: &str, src: &str, main: &str) -> String
+{
+    let src2 = format!("mod bindings {{\n{}}}\n\n{}", src, main);
+    test_utils::compile_and_run(format!("rust_bindings_{}", name).as_str(), "main.rs", src2.as_str(), |src_path, exe_path| {
+            let mut command = Command::new(rustc());
+            command.arg("--edition=2018").arg("-D").arg("warnings").arg("-A").arg("dead_code").arg("-o").arg(exe_path).arg(src_path);
+            command
+    })
+}
+
+#[test]
+fn test_rust_bindings_emitter_emit_emits_kernel_function()
+{
+    let s = "
+import opencl;
+data U = C(Int, Float4);
+kernel k(xs: UniqGlobalSlice<Int>, ys: GlobalSlice<Float>, n: Int, u: U) -> () =
+    let i = get_global_id(0 as Uint);
+        _ = ys[i];
+        _ = xs[i] <- n;
+    in  ();
+";
+    let src = emit_string(&s[1..]);
+    assert!(src.contains("pub trait KernelArgs<'a> {\n"));
+    assert!(src.contains("#[repr(C, align(16))]\n#[derive(Copy, Clone, Debug, Default, PartialEq)]\npub struct Float4(pub [f32; 4]);\n"));
+    assert!(src.contains("#[repr(C)]\n#[derive(Copy, Clone)]\npub struct _VS1UNP {\n    pub f0: i32,\n    pub f1: Float4,\n}\n"));
+    assert!(src.contains("const _: () = assert!(std::mem::size_of::<_VS1UNP>() == 32 && std::mem::align_of::<_VS1UNP>() == 16);\n"));
+    assert!(src.contains("pub type U = _VS1UNP;\n"));
+    assert!(src.contains("pub const K_KERNEL_NAME: &str = \"_VF1k\";\n"));
+    assert!(src.contains("pub fn set_k_args<'a, A: KernelArgs<'a>>(vscfl_args: &mut A, xs: &'a mut [i32], ys: &'a [f32], n: i32, u: U) -> Result<(), A::Error> {\n"));
+    assert!(src.contains("    let xs_len = xs.len();\n    vscfl_args.set_uniq_slice_arg(0, xs)?;\n    vscfl_args.set_value_arg(1, xs_len as u64)?;\n"));
+    assert!(src.contains("    let ys_len = ys.len();\n    vscfl_args.set_slice_arg(2, ys)?;\n    vscfl_args.set_value_arg(3, ys_len as u64)?;\n"));
+    assert!(src.contains("    vscfl_args.set_value_arg(4, n)?;\n    vscfl_args.set_value_arg(5, u)?;\n    Ok(())\n}\n"));
+}
+
+#[test]
+fn test_rust_bindings_emitter_emit_emits_panic_buffer_argument()
+{
+    let s = "
+import opencl;
+recursive(4) fib(n: Int) -> Int = if n < 2 then n else fib(n - 1) + fib(n - 2);
+kernel k(xs: UniqGlobalSlice<Int>) -> () =
+    let i = get_global_id(0 as Uint);
+        (x, xs) = xs[i] ->;
+        _ = xs[i] <- fib(x);
+    in  ();
+";
+    let src = emit_string(&s[1..]);
+    assert!(src.contains(format!("pub const ADDRESS_BITS: u32 = {};\npub const PANIC_BUFFER_LEN: usize = {};\n", ADDRESS_BITS, PANIC_BUFFER_LEN).as_str()));
+    assert!(src.contains("pub fn set_k_args<'a, A: KernelArgs<'a>>(vscfl_args: &mut A, xs: &'a mut [i32], vscfl_panic_buffer: &'a mut [u32; PANIC_BUFFER_LEN]) -> Result<(), A::Error> {\n"));
+    assert!(src.contains("    vscfl_args.set_uniq_slice_arg(2, &mut vscfl_panic_buffer[..])?;\n"));
+}
+
+#[test]
+fn test_rust_bindings_emitter_emitted_source_sets_arguments_in_order()
+{
+    if !has_rustc() {
+        return;
+    }
+    let s = "
+import opencl;
+data T = A() | B(Int, Double) | C(Char);
+recursive(4) fib(n: Int) -> Int = if n < 2 then n else fib(n - 1) + fib(n - 2);
+kernel k(xs: UniqGlobalSlice<T>, ys: GlobalSlice<Float2>, ref: Int) -> () =
+    let i = get_global_id(0 as Uint);
+        _ = ys[i];
+        _ = xs[i] <- C(fib(ref) as Char);
+    in  ();
+";
+    let src = emit_string(&s[1..]);
+    let main = "
+use bindings::*;
+
+struct Recorder {
+    calls: Vec<String>,
+}
+
+impl<'a> KernelArgs<'a> for Recorder {
+    type Error = ();
+
+    fn set_value_arg<T: Copy + 'a>(&mut self, idx: u32, _value: T) -> Result<(), ()> {
+        self.calls.push(format!(\"value {} {}\", idx, std::mem::size_of::<T>()));
+        Ok(())
+    }
+
+    fn set_slice_arg<T: Copy + 'a>(&mut self, idx: u32, slice: &'a [T]) -> Result<(), ()> {
+        self.calls.push(format!(\"slice {} {} {}\", idx, slice.len(), std::mem::size_of::<T>()));
+        Ok(())
+    }
+
+    fn set_uniq_slice_arg<T: Copy + 'a>(&mut self, idx: u32, slice: &'a mut [T]) -> Result<(), ()> {
+        self.calls.push(format!(\"uniq_slice {} {} {}\", idx, slice.len(), std::mem::size_of::<T>()));
+        Ok(())
+    }
+
+    fn set_local_arg<T: Copy + 'a>(&mut self, idx: u32, len: usize) -> Result<(), ()> {
+        self.calls.push(format!(\"local {} {}\", idx, len));
+        Ok(())
+    }
+}
+
+fn main() {
+    let mut recorder = Recorder { calls: Vec::new() };
+    let mut xs: Vec<T> = vec![T { f0: 0, f1: _VU1TNP { f0: _VG1BJ1TNP { f0: 0, f1: 0.0 } } }; 3];
+    let ys = vec![Float2([1.0, 2.0]); 5];
+    let mut pb = [0u32; PANIC_BUFFER_LEN];
+    set_k_args(&mut recorder, &mut xs, &ys, 7, &mut pb).unwrap();
+    println!(\"{}\", K_KERNEL_NAME);
+    for call in &recorder.calls {
+        println!(\"{}\", call);
+    }
+}
+";
+    let expected = "
+_VF1k
+uniq_slice 0 3 24
+value 1 8
+slice 2 5 8
+value 3 8
+value 4 4
+uniq_slice 5 32 4
+";
+    assert_eq!(String::from(&expected[1..]), compile_and_run("order", src.as_str(), &main[1..]));
+}
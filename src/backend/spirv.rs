@@ -0,0 +1,3402 @@
+//
+// Copyright (c) 2025 Łukasz Szpakowski
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use crate::frontend::error::Pos;
+use crate::backend::error::*;
+use crate::backend::heap::*;
+use crate::backend::ir::*;
+use crate::backend::ir::utils::*;
+use crate::backend::layout::*;
+use crate::backend::mangling::*;
+use crate::backend::panic::*;
+
+pub mod disassembler;
+
+pub const SPIRV_MAGIC_NUMBER: u32 = 0x07230203;
+pub const SPIRV_VERSION: u32 = 0x00010000;
+pub const PRIVATE_HEAP_SIZE_SPEC_ID: u32 = 0;
+pub const LOCAL_HEAP_SIZE_SPEC_ID: u32 = 1;
+
+const OP_SOURCE: u32 = 3;
+const OP_NAME: u32 = 5;
+const OP_EXT_INST_IMPORT: u32 = 11;
+const OP_EXT_INST: u32 = 12;
+const OP_MEMORY_MODEL: u32 = 14;
+const OP_ENTRY_POINT: u32 = 15;
+const OP_CAPABILITY: u32 = 17;
+const OP_TYPE_VOID: u32 = 19;
+const OP_TYPE_BOOL: u32 = 20;
+const OP_TYPE_INT: u32 = 21;
+const OP_TYPE_FLOAT: u32 = 22;
+const OP_TYPE_VECTOR: u32 = 23;
+const OP_TYPE_ARRAY: u32 = 28;
+const OP_TYPE_STRUCT: u32 = 30;
+const OP_TYPE_POINTER: u32 = 32;
+const OP_TYPE_FUNCTION: u32 = 33;
+const OP_TYPE_FORWARD_POINTER: u32 = 39;
+const OP_CONSTANT: u32 = 43;
+const OP_CONSTANT_COMPOSITE: u32 = 44;
+const OP_CONSTANT_NULL: u32 = 46;
+const OP_SPEC_CONSTANT: u32 = 50;
+const OP_SPEC_CONSTANT_COMPOSITE: u32 = 51;
+const OP_SPEC_CONSTANT_OP: u32 = 52;
+const OP_FUNCTION: u32 = 54;
+const OP_FUNCTION_PARAMETER: u32 = 55;
+const OP_FUNCTION_END: u32 = 56;
+const OP_FUNCTION_CALL: u32 = 57;
+const OP_VARIABLE: u32 = 59;
+const OP_LOAD: u32 = 61;
+const OP_STORE: u32 = 62;
+const OP_ACCESS_CHAIN: u32 = 65;
+const OP_IN_BOUNDS_ACCESS_CHAIN: u32 = 66;
+const OP_PTR_ACCESS_CHAIN: u32 = 67;
+const OP_DECORATE: u32 = 71;
+const OP_VECTOR_EXTRACT_DYNAMIC: u32 = 77;
+const OP_COMPOSITE_CONSTRUCT: u32 = 80;
+const OP_COMPOSITE_EXTRACT: u32 = 81;
+const OP_CONVERT_F_TO_U: u32 = 109;
+const OP_CONVERT_F_TO_S: u32 = 110;
+const OP_CONVERT_S_TO_F: u32 = 111;
+const OP_CONVERT_U_TO_F: u32 = 112;
+const OP_U_CONVERT: u32 = 113;
+const OP_S_CONVERT: u32 = 114;
+const OP_F_CONVERT: u32 = 115;
+const OP_CONVERT_PTR_TO_U: u32 = 117;
+const OP_CONVERT_U_TO_PTR: u32 = 120;
+const OP_PTR_CAST_TO_GENERIC: u32 = 121;
+const OP_GENERIC_CAST_TO_PTR: u32 = 122;
+const OP_BITCAST: u32 = 124;
+const OP_S_NEGATE: u32 = 126;
+const OP_F_NEGATE: u32 = 127;
+const OP_I_ADD: u32 = 128;
+const OP_F_ADD: u32 = 129;
+const OP_I_SUB: u32 = 130;
+const OP_F_SUB: u32 = 131;
+const OP_I_MUL: u32 = 132;
+const OP_F_MUL: u32 = 133;
+const OP_U_DIV: u32 = 134;
+const OP_S_DIV: u32 = 135;
+const OP_F_DIV: u32 = 136;
+const OP_U_MOD: u32 = 137;
+const OP_S_REM: u32 = 138;
+const OP_F_REM: u32 = 140;
+const OP_DOT: u32 = 148;
+const OP_ANY: u32 = 154;
+const OP_ALL: u32 = 155;
+const OP_IS_NAN: u32 = 156;
+const OP_IS_INF: u32 = 157;
+const OP_IS_FINITE: u32 = 158;
+const OP_IS_NORMAL: u32 = 159;
+const OP_SIGN_BIT_SET: u32 = 160;
+const OP_ORDERED: u32 = 162;
+const OP_UNORDERED: u32 = 163;
+const OP_LOGICAL_OR: u32 = 166;
+const OP_LOGICAL_AND: u32 = 167;
+const OP_SELECT: u32 = 169;
+const OP_I_EQUAL: u32 = 170;
+const OP_I_NOT_EQUAL: u32 = 171;
+const OP_U_GREATER_THAN: u32 = 172;
+const OP_S_GREATER_THAN: u32 = 173;
+const OP_U_GREATER_THAN_EQUAL: u32 = 174;
+const OP_S_GREATER_THAN_EQUAL: u32 = 175;
+const OP_U_LESS_THAN: u32 = 176;
+const OP_S_LESS_THAN: u32 = 177;
+const OP_U_LESS_THAN_EQUAL: u32 = 178;
+const OP_S_LESS_THAN_EQUAL: u32 = 179;
+const OP_F_ORD_EQUAL: u32 = 180;
+const OP_F_ORD_NOT_EQUAL: u32 = 182;
+const OP_F_UNORD_NOT_EQUAL: u32 = 183;
+const OP_F_ORD_LESS_THAN: u32 = 184;
+const OP_F_ORD_GREATER_THAN: u32 = 186;
+const OP_F_ORD_LESS_THAN_EQUAL: u32 = 188;
+const OP_F_ORD_GREATER_THAN_EQUAL: u32 = 190;
+const OP_SHIFT_RIGHT_LOGICAL: u32 = 194;
+const OP_SHIFT_RIGHT_ARITHMETIC: u32 = 195;
+const OP_SHIFT_LEFT_LOGICAL: u32 = 196;
+const OP_BITWISE_OR: u32 = 197;
+const OP_BITWISE_XOR: u32 = 198;
+const OP_BITWISE_AND: u32 = 199;
+const OP_NOT: u32 = 200;
+const OP_CONTROL_BARRIER: u32 = 224;
+const OP_MEMORY_BARRIER: u32 = 225;
+const OP_ATOMIC_COMPARE_EXCHANGE: u32 = 230;
+const OP_ATOMIC_I_ADD: u32 = 234;
+const OP_LABEL: u32 = 248;
+const OP_BRANCH: u32 = 249;
+const OP_BRANCH_CONDITIONAL: u32 = 250;
+const OP_SWITCH: u32 = 251;
+const OP_RETURN: u32 = 253;
+const OP_RETURN_VALUE: u32 = 254;
+
+const CAPABILITY_ADDRESSES: u32 = 4;
+const CAPABILITY_KERNEL: u32 = 6;
+const CAPABILITY_VECTOR16: u32 = 7;
+const CAPABILITY_FLOAT16: u32 = 9;
+const CAPABILITY_FLOAT64: u32 = 10;
+const CAPABILITY_INT64: u32 = 11;
+const CAPABILITY_INT16: u32 = 22;
+const CAPABILITY_GENERIC_POINTER: u32 = 38;
+const CAPABILITY_INT8: u32 = 39;
+
+const ADDRESSING_MODEL_PHYSICAL64: u32 = 2;
+const MEMORY_MODEL_OPENCL: u32 = 2;
+const EXECUTION_MODEL_KERNEL: u32 = 6;
+const SOURCE_LANGUAGE_OPENCL_C: u32 = 3;
+const SOURCE_VERSION: u32 = 200000;
+
+const STORAGE_CLASS_UNIFORM_CONSTANT: u32 = 0;
+const STORAGE_CLASS_INPUT: u32 = 1;
+const STORAGE_CLASS_WORKGROUP: u32 = 4;
+const STORAGE_CLASS_CROSS_WORKGROUP: u32 = 5;
+const STORAGE_CLASS_FUNCTION: u32 = 7;
+const STORAGE_CLASS_GENERIC: u32 = 8;
+
+const DECORATION_SPEC_ID: u32 = 1;
+const DECORATION_BUILTIN: u32 = 11;
+const DECORATION_CONSTANT: u32 = 22;
+
+const BUILTIN_NUM_WORKGROUPS: u32 = 24;
+const BUILTIN_WORKGROUP_SIZE: u32 = 25;
+const BUILTIN_WORKGROUP_ID: u32 = 26;
+const BUILTIN_LOCAL_INVOCATION_ID: u32 = 27;
+const BUILTIN_GLOBAL_INVOCATION_ID: u32 = 28;
+const BUILTIN_WORK_DIM: u32 = 30;
+const BUILTIN_GLOBAL_SIZE: u32 = 31;
+const BUILTIN_GLOBAL_OFFSET: u32 = 33;
+
+const FUNCTION_CONTROL_NONE: u32 = 0;
+const FUNCTION_CONTROL_INLINE: u32 = 1;
+
+const SCOPE_DEVICE: u32 = 1;
+const SCOPE_WORKGROUP: u32 = 2;
+
+const MEMORY_SEMANTICS_ACQUIRE: u32 = 0x2;
+const MEMORY_SEMANTICS_RELEASE: u32 = 0x4;
+const MEMORY_SEMANTICS_ACQUIRE_RELEASE: u32 = 0x8;
+const MEMORY_SEMANTICS_SEQUENTIALLY_CONSISTENT: u32 = 0x10;
+const MEMORY_SEMANTICS_WORKGROUP_MEMORY: u32 = 0x100;
+const MEMORY_SEMANTICS_CROSS_WORKGROUP_MEMORY: u32 = 0x200;
+
+const OPENCL_STD_NAME: &str = "OpenCL.std";
+
+const OPENCL_STD_FUNS: &[(&str, u32)] = &[
+    ("acos", 0), ("acosh", 1), ("acospi", 2), ("asin", 3), ("asinh", 4), ("asinpi", 5),
+    ("atan", 6), ("atan2", 7), ("atanh", 8), ("atanpi", 9), ("atan2pi", 10), ("cbrt", 11),
+    ("ceil", 12), ("copysign", 13), ("cos", 14), ("cosh", 15), ("cospi", 16), ("erfc", 17),
+    ("erf", 18), ("exp", 19), ("exp2", 20), ("exp10", 21), ("expm1", 22), ("fabs", 23),
+    ("fdim", 24), ("floor", 25), ("fma", 26), ("fmax", 27), ("fmin", 28), ("fmod", 29),
+    ("fract", 30), ("frexp", 31), ("hypot", 32), ("ilogb", 33), ("ldexp", 34), ("lgamma", 35),
+    ("lgamma_r", 36), ("log", 37), ("log2", 38), ("log10", 39), ("log1p", 40), ("logb", 41),
+    ("mad", 42), ("maxmag", 43), ("minmag", 44), ("modf", 45), ("nan", 46), ("nextafter", 47),
+    ("pow", 48), ("pown", 49), ("powr", 50), ("remainder", 51), ("remquo", 52), ("rint", 53),
+    ("rootn", 54), ("round", 55), ("rsqrt", 56), ("sin", 57), ("sincos", 58), ("sinh", 59),
+    ("sinpi", 60), ("sqrt", 61), ("tan", 62), ("tanh", 63), ("tanpi", 64), ("tgamma", 65),
+    ("trunc", 66),
+    ("half_cos", 67), ("half_divide", 68), ("half_exp", 69), ("half_exp2", 70), ("half_exp10", 71),
+    ("half_log", 72), ("half_log2", 73), ("half_log10", 74), ("half_powr", 75), ("half_recip", 76),
+    ("half_rsqrt", 77), ("half_sin", 78), ("half_sqrt", 79), ("half_tan", 80),
+    ("native_cos", 81), ("native_divide", 82), ("native_exp", 83), ("native_exp2", 84), ("native_exp10", 85),
+    ("native_log", 86), ("native_log2", 87), ("native_log10", 88), ("native_powr", 89), ("native_recip", 90),
+    ("native_rsqrt", 91), ("native_sin", 92), ("native_sqrt", 93), ("native_tan", 94),
+    ("fclamp", 95), ("degrees", 96), ("fmax_common", 97), ("fmin_common", 98), ("mix", 99),
+    ("radians", 100), ("step", 101), ("smoothstep", 102), ("sign", 103),
+    ("cross", 104), ("distance", 105), ("length", 106), ("normalize", 107),
+    ("fast_distance", 108), ("fast_length", 109), ("fast_normalize", 110),
+    ("s_abs", 141), ("s_abs_diff", 142), ("s_add_sat", 143), ("u_add_sat", 144), ("s_hadd", 145),
+    ("u_hadd", 146), ("s_rhadd", 147), ("u_rhadd", 148), ("s_clamp", 149), ("u_clamp", 150),
+    ("clz", 151), ("ctz", 152), ("s_mad_hi", 153), ("u_mad_sat", 154), ("s_mad_sat", 155),
+    ("s_max", 156), ("u_max", 157), ("s_min", 158), ("u_min", 159), ("s_mul_hi", 160),
+    ("rotate", 161), ("s_sub_sat", 162), ("u_sub_sat", 163), ("u_upsample", 164), ("s_upsample", 165),
+    ("popcount", 166), ("s_mad24", 167), ("u_mad24", 168), ("s_mul24", 169), ("u_mul24", 170),
+    ("vloadn", 171), ("vstoren", 172), ("vload_half", 173), ("vload_halfn", 174), ("vstore_half", 175),
+    ("vstore_half_r", 176), ("vstore_halfn", 177), ("vstore_halfn_r", 178), ("vloada_halfn", 179),
+    ("vstorea_halfn", 180), ("vstorea_halfn_r", 181),
+    ("shuffle", 182), ("shuffle2", 183), ("printf", 184), ("prefetch", 185), ("bitselect", 186), ("select", 187),
+    ("u_abs", 201), ("u_abs_diff", 202), ("u_mul_hi", 203), ("u_mad_hi", 204),
+];
+
+const SPEC_CONSTANT_OPS: &[u32] = &[
+    OP_CONVERT_F_TO_U, OP_CONVERT_F_TO_S, OP_CONVERT_S_TO_F, OP_CONVERT_U_TO_F, OP_U_CONVERT,
+    OP_S_CONVERT, OP_F_CONVERT, OP_CONVERT_PTR_TO_U, OP_CONVERT_U_TO_PTR, OP_PTR_CAST_TO_GENERIC,
+    OP_GENERIC_CAST_TO_PTR, OP_BITCAST, OP_S_NEGATE, OP_F_NEGATE, OP_I_ADD, OP_I_SUB, OP_I_MUL,
+    OP_U_DIV, OP_S_DIV, OP_U_MOD, OP_S_REM, OP_F_ADD, OP_F_SUB, OP_F_MUL, OP_F_DIV, OP_F_REM,
+    OP_COMPOSITE_EXTRACT, OP_ACCESS_CHAIN, OP_IN_BOUNDS_ACCESS_CHAIN, OP_PTR_ACCESS_CHAIN,
+    OP_LOGICAL_OR, OP_LOGICAL_AND, OP_SELECT, OP_I_EQUAL, OP_I_NOT_EQUAL, OP_U_GREATER_THAN,
+    OP_S_GREATER_THAN, OP_U_GREATER_THAN_EQUAL, OP_S_GREATER_THAN_EQUAL, OP_U_LESS_THAN,
+    OP_S_LESS_THAN, OP_U_LESS_THAN_EQUAL, OP_S_LESS_THAN_EQUAL, OP_SHIFT_RIGHT_LOGICAL,
+    OP_SHIFT_RIGHT_ARITHMETIC, OP_SHIFT_LEFT_LOGICAL, OP_BITWISE_OR, OP_BITWISE_XOR,
+    OP_BITWISE_AND, OP_NOT,
+];
+
+fn opencl_std_fun(ident: &str) -> Option<u32>
+{ OPENCL_STD_FUNS.iter().find(|p| p.0 == ident).map(|p| p.1) }
+
+fn opencl_std_fun_name(inst: u32) -> Option<&'static str>
+{ OPENCL_STD_FUNS.iter().find(|p| p.1 == inst).map(|p| p.0) }
+
+fn push_instr(words: &mut Vec<u32>, opcode: u32, operands: &[u32])
+{
+    words.push((((operands.len() + 1) as u32) << 16) | opcode);
+    words.extend_from_slice(operands);
+}
+
+fn string_words(s: &str) -> Vec<u32>
+{
+    let mut bytes = s.as_bytes().to_vec();
+    bytes.push(0);
+    while bytes.len() & 3 != 0 {
+        bytes.push(0);
+    }
+    bytes.chunks(4).map(|c| u32::from_le_bytes([c[0], c[1], c[2], c[3]])).collect()
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+enum ScalarKind
+{
+    Int(u32, bool),
+    Float(u32),
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+enum ConstElem
+{
+    Int(i128),
+    Float(f64),
+}
+
+fn scalar_kind(typ: &IrType) -> Option<ScalarKind>
+{
+    match typ {
+        IrType::Char => Some(ScalarKind::Int(8, true)),
+        IrType::Short => Some(ScalarKind::Int(16, true)),
+        IrType::Int => Some(ScalarKind::Int(32, true)),
+        IrType::Long => Some(ScalarKind::Int(64, true)),
+        IrType::Uchar => Some(ScalarKind::Int(8, false)),
+        IrType::Ushort => Some(ScalarKind::Int(16, false)),
+        IrType::Uint => Some(ScalarKind::Int(32, false)),
+        IrType::Ulong => Some(ScalarKind::Int(64, false)),
+        IrType::Half => Some(ScalarKind::Float(16)),
+        IrType::Float => Some(ScalarKind::Float(32)),
+        IrType::Double => Some(ScalarKind::Float(64)),
+        IrType::SizeT | IrType::UintptrT => Some(ScalarKind::Int(64, false)),
+        IrType::PtrdiffT | IrType::IntptrT => Some(ScalarKind::Int(64, true)),
+        _ => None,
+    }
+}
+
+fn kind_type(kind: ScalarKind) -> IrType
+{
+    match kind {
+        ScalarKind::Int(bits, is_signed) => int_type(bits, is_signed),
+        ScalarKind::Float(16) => IrType::Half,
+        ScalarKind::Float(32) => IrType::Float,
+        ScalarKind::Float(_) => IrType::Double,
+    }
+}
+
+fn kind_bits(kind: ScalarKind) -> u32
+{
+    match kind {
+        ScalarKind::Int(bits, _) => bits,
+        ScalarKind::Float(bits) => bits,
+    }
+}
+
+fn lanes_kind(typ: &IrType) -> Option<(ScalarKind, Option<usize>)>
+{
+    match (scalar_kind(typ), vector_type_parts(typ)) {
+        (Some(kind), _) => Some((kind, None)),
+        (None, Some((elem_type, n))) => scalar_kind(&elem_type).map(|kind| (kind, Some(n))),
+        (None, None) => None,
+    }
+}
+
+fn lanes_type(kind: ScalarKind, n: Option<usize>) -> Option<IrType>
+{
+    match n {
+        Some(n) => vector_type(&kind_type(kind), n),
+        None => Some(kind_type(kind)),
+    }
+}
+
+fn common_type(typ1: &IrType, typ2: &IrType) -> IrType
+{
+    let type1 = promoted_type(typ1);
+    let type2 = promoted_type(typ2);
+    if type1 == type2 {
+        return type1;
+    }
+    match (scalar_kind(&type1), scalar_kind(&type2)) {
+        (Some(ScalarKind::Float(bits1)), Some(ScalarKind::Float(bits2))) => if bits1 >= bits2 { type1 } else { type2 },
+        (Some(ScalarKind::Float(_)), _) => type1,
+        (_, Some(ScalarKind::Float(_))) => type2,
+        (Some(ScalarKind::Int(bits1, is_signed1)), Some(ScalarKind::Int(bits2, is_signed2))) => {
+            if is_signed1 == is_signed2 {
+                if bits1 >= bits2 { type1 } else { type2 }
+            } else if !is_signed1 && bits1 >= bits2 {
+                type1
+            } else if !is_signed2 && bits2 >= bits1 {
+                type2
+            } else if is_signed1 && bits1 > bits2 {
+                type1
+            } else if is_signed2 && bits2 > bits1 {
+                type2
+            } else {
+                int_type(bits1.max(bits2), false)
+            }
+        },
+        _ => type1,
+    }
+}
+
+fn cmp_elem_type(kind: ScalarKind) -> IrType
+{
+    match kind {
+        ScalarKind::Int(bits, _) => int_type(bits, true),
+        ScalarKind::Float(bits) => int_type(bits.max(32), true),
+    }
+}
+
+fn convert_elem(elem: ConstElem, kind: ScalarKind) -> ConstElem
+{
+    match (elem, kind) {
+        (ConstElem::Int(a), ScalarKind::Int(bits, is_signed)) => ConstElem::Int(wrap_int(a, bits, is_signed)),
+        (ConstElem::Int(a), ScalarKind::Float(_)) => ConstElem::Float(a as f64),
+        (ConstElem::Float(x), ScalarKind::Int(bits, is_signed)) => ConstElem::Int(wrap_int(x.trunc() as i128, bits, is_signed)),
+        (ConstElem::Float(x), ScalarKind::Float(32)) => ConstElem::Float((x as f32) as f64),
+        (ConstElem::Float(x), ScalarKind::Float(_)) => ConstElem::Float(x),
+    }
+}
+
+fn elem_words(elem: ConstElem, kind: ScalarKind) -> Vec<u32>
+{
+    match (convert_elem(elem, kind), kind) {
+        (ConstElem::Int(a), ScalarKind::Int(bits, _)) => {
+            let b = (a as u128) & ((1u128 << bits) - 1);
+            if bits > 32 {
+                vec![b as u32, (b >> 32) as u32]
+            } else {
+                vec![b as u32]
+            }
+        },
+        (ConstElem::Float(x), ScalarKind::Float(16)) => vec![f32_to_f16_bits(x as f32) as u32],
+        (ConstElem::Float(x), ScalarKind::Float(32)) => vec![(x as f32).to_bits()],
+        (ConstElem::Float(x), _) => {
+            let b = x.to_bits();
+            vec![b as u32, (b >> 32) as u32]
+        },
+        (_, _) => Vec::new(),
+    }
+}
+
+fn storage_class(modifier: IrPtrModifier) -> u32
+{
+    match modifier {
+        IrPtrModifier::None => STORAGE_CLASS_GENERIC,
+        IrPtrModifier::Private => STORAGE_CLASS_FUNCTION,
+        IrPtrModifier::Local => STORAGE_CLASS_WORKGROUP,
+        IrPtrModifier::Global => STORAGE_CLASS_CROSS_WORKGROUP,
+        IrPtrModifier::Constant => STORAGE_CLASS_UNIFORM_CONSTANT,
+    }
+}
+
+fn ptr_type(modifier: IrPtrModifier, typ: IrType) -> IrType
+{ IrType::Ptr(modifier, IrAccessModifier::None, Box::new(typ)) }
+
+fn is_ptr_type(typ: &IrType) -> bool
+{
+    matches!(typ, IrType::Ptr(_, _, _))
+}
+
+fn case_value_parts(value: &IrCaseValue) -> Option<(IrType, ConstElem, &Option<Box<IrType>>)>
+{
+    match value {
+        IrCaseValue::Char(n, typ) => Some((IrType::Char, ConstElem::Int(*n as i128), typ)),
+        IrCaseValue::Short(n, typ) => Some((IrType::Short, ConstElem::Int(*n as i128), typ)),
+        IrCaseValue::Int(n, typ) => Some((IrType::Int, ConstElem::Int(*n as i128), typ)),
+        IrCaseValue::Long(n, typ) => Some((IrType::Long, ConstElem::Int(*n as i128), typ)),
+        IrCaseValue::Uchar(n, typ) => Some((IrType::Uchar, ConstElem::Int(*n as i128), typ)),
+        IrCaseValue::Ushort(n, typ) => Some((IrType::Ushort, ConstElem::Int(*n as i128), typ)),
+        IrCaseValue::Uint(n, typ) => Some((IrType::Uint, ConstElem::Int(*n as i128), typ)),
+        IrCaseValue::Ulong(n, typ) => Some((IrType::Ulong, ConstElem::Int(*n as i128), typ)),
+        IrCaseValue::SizeT(n, typ) => Some((IrType::SizeT, ConstElem::Int(*n as i128), typ)),
+        IrCaseValue::PtrdiffT(n, typ) => Some((IrType::PtrdiffT, ConstElem::Int(*n as i128), typ)),
+        IrCaseValue::IntptrT(n, typ) => Some((IrType::IntptrT, ConstElem::Int(*n as i128), typ)),
+        IrCaseValue::UintptrT(n, typ) => Some((IrType::UintptrT, ConstElem::Int(*n as i128), typ)),
+        IrCaseValue::BuiltinVar(_, _, _) => None,
+    }
+}
+
+fn builtin_var_elem(ident: &str) -> Option<(IrType, ConstElem)>
+{
+    match ident {
+        "CLK_LOCAL_MEM_FENCE" => Some((IrType::Uint, ConstElem::Int(1))),
+        "CLK_GLOBAL_MEM_FENCE" => Some((IrType::Uint, ConstElem::Int(2))),
+        "MAXFLOAT" => Some((IrType::Float, ConstElem::Float(f32::MAX as f64))),
+        "HUGE_VALF" | "INFINITY" => Some((IrType::Float, ConstElem::Float(f64::INFINITY))),
+        "NAN" => Some((IrType::Float, ConstElem::Float(f64::NAN))),
+        "HUGE_VAL" => Some((IrType::Double, ConstElem::Float(f64::INFINITY))),
+        "FLOAT_DIG" => Some((IrType::Uint, ConstElem::Int(6))),
+        "FLOAT_MANT_DIG" => Some((IrType::Uint, ConstElem::Int(24))),
+        "FLOAT_MAX_10_EXP" => Some((IrType::Int, ConstElem::Int(38))),
+        "FLOAT_MAX_EXP" => Some((IrType::Int, ConstElem::Int(128))),
+        "FLOAT_MIN_10_EXP" => Some((IrType::Int, ConstElem::Int(-37))),
+        "FLOAT_MIN_EXP" => Some((IrType::Int, ConstElem::Int(-125))),
+        "DOUBLE_DIG" => Some((IrType::Uint, ConstElem::Int(15))),
+        "DOUBLE_MANT_DIG" => Some((IrType::Uint, ConstElem::Int(53))),
+        "DOUBLE_MAX_10_EXP" => Some((IrType::Int, ConstElem::Int(308))),
+        "DOUBLE_MAX_EXP" => Some((IrType::Int, ConstElem::Int(1024))),
+        "DOUBLE_MIN_10_EXP" => Some((IrType::Int, ConstElem::Int(-307))),
+        "DOUBLE_MIN_EXP" => Some((IrType::Int, ConstElem::Int(-1021))),
+        _ => None,
+    }
+}
+
+fn work_item_builtin(ident: &str) -> Option<(u32, i128)>
+{
+    match ident {
+        "get_global_size" => Some((BUILTIN_GLOBAL_SIZE, 1)),
+        "get_global_id" => Some((BUILTIN_GLOBAL_INVOCATION_ID, 0)),
+        "get_local_size" => Some((BUILTIN_WORKGROUP_SIZE, 1)),
+        "get_local_id" => Some((BUILTIN_LOCAL_INVOCATION_ID, 0)),
+        "get_num_groups" => Some((BUILTIN_NUM_WORKGROUPS, 1)),
+        "get_group_id" => Some((BUILTIN_WORKGROUP_ID, 0)),
+        "get_global_offset" => Some((BUILTIN_GLOBAL_OFFSET, 0)),
+        _ => None,
+    }
+}
+
+fn relational_op(ident: &str) -> Option<u32>
+{
+    let name = match ident.find('_') {
+        Some(i) => &ident[..i],
+        None => ident,
+    };
+    match name {
+        "isequal" => Some(OP_F_ORD_EQUAL),
+        "isnotequal" => Some(OP_F_UNORD_NOT_EQUAL),
+        "isgreater" => Some(OP_F_ORD_GREATER_THAN),
+        "isgreaterequal" => Some(OP_F_ORD_GREATER_THAN_EQUAL),
+        "isless" => Some(OP_F_ORD_LESS_THAN),
+        "islessequal" => Some(OP_F_ORD_LESS_THAN_EQUAL),
+        "islessgreater" => Some(OP_F_ORD_NOT_EQUAL),
+        "isfinite" => Some(OP_IS_FINITE),
+        "isinf" => Some(OP_IS_INF),
+        "isnan" => Some(OP_IS_NAN),
+        "isnormal" => Some(OP_IS_NORMAL),
+        "isordered" => Some(OP_ORDERED),
+        "isunordered" => Some(OP_UNORDERED),
+        "signbit" => Some(OP_SIGN_BIT_SET),
+        _ => None,
+    }
+}
+
+fn strip_vector_suffix<'a>(ident: &'a str, names: &[&str]) -> Option<&'a str>
+{
+    let name = ident.trim_end_matches(|c: char| c.is_ascii_digit());
+    if names.contains(&name) {
+        Some(&ident[..name.len()])
+    } else {
+        None
+    }
+}
+
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+enum TypeKey
+{
+    Void,
+    Bool,
+    Int(u32),
+    Float(u32),
+    Vector(Box<TypeKey>, usize),
+    Array(Box<TypeKey>, usize),
+    SpecArray(Box<TypeKey>, u32),
+    Struct(String),
+    Union(String),
+    Ptr(u32, Box<TypeKey>),
+    Fun(Box<TypeKey>, Vec<TypeKey>),
+}
+
+#[derive(Clone, Debug)]
+struct SpirvValue
+{
+    id: u32,
+    typ: IrType,
+}
+
+impl SpirvValue
+{
+    fn new(id: u32, typ: IrType) -> Self
+    { SpirvValue { id, typ, } }
+}
+
+#[derive(Clone, Debug)]
+struct Place
+{
+    ptr: u32,
+    typ: IrType,
+    modifier: IrPtrModifier,
+}
+
+impl Place
+{
+    fn new(ptr: u32, typ: IrType, modifier: IrPtrModifier) -> Self
+    { Place { ptr, typ, modifier, } }
+
+    fn ptr_value(&self) -> SpirvValue
+    { SpirvValue::new(self.ptr, ptr_type(self.modifier, self.typ.clone())) }
+}
+
+#[derive(Clone, Debug)]
+enum Base
+{
+    Place(Place),
+    Value(SpirvValue),
+}
+
+#[derive(Clone, Debug)]
+struct FunSignature
+{
+    id: u32,
+    param_types: Vec<IrType>,
+    ret_type: IrType,
+    flags: (IrPrivateHeapFlag, IrLocalHeapFlag, IrGlobalHeapFlag, IrPanicFlag),
+}
+
+trait SpirvVar
+{
+    fn spirv_var(&self, module: &mut SpirvModule<'_>, ctx: &mut SpirvFunContext<'_>) -> BackendResult<SpirvValue>;
+}
+
+struct SpirvFunContext<'b>
+{
+    is_const: bool,
+    vars: Vec<u32>,
+    body: Vec<u32>,
+    is_terminated: bool,
+    local_vars: Vec<Place>,
+    caller_args: Vec<Place>,
+    private_closure: Option<Place>,
+    local_closure: Option<Place>,
+    global_closure: Option<Place>,
+    ret_type: IrType,
+    can_panic: bool,
+    heap_flags: (IrPrivateHeapFlag, IrLocalHeapFlag, IrGlobalHeapFlag),
+    heaps: (Option<SpirvValue>, Option<SpirvValue>, Option<SpirvValue>),
+    panic_buffer: Option<SpirvValue>,
+    panic_flag: Option<SpirvValue>,
+    loops: Vec<(u32, u32)>,
+    is_kernel: bool,
+    panic_table: Option<&'b mut PanicTable>,
+}
+
+impl<'b> SpirvFunContext<'b>
+{
+    fn new() -> Self
+    {
+        SpirvFunContext {
+            is_const: false,
+            vars: Vec::new(),
+            body: Vec::new(),
+            is_terminated: false,
+            local_vars: Vec::new(),
+            caller_args: Vec::new(),
+            private_closure: None,
+            local_closure: None,
+            global_closure: None,
+            ret_type: IrType::Void,
+            can_panic: false,
+            heap_flags: (IrPrivateHeapFlag::None, IrLocalHeapFlag::None, IrGlobalHeapFlag::None),
+            heaps: (None, None, None),
+            panic_buffer: None,
+            panic_flag: None,
+            loops: Vec::new(),
+            is_kernel: false,
+            panic_table: None,
+        }
+    }
+
+    fn new_const() -> Self
+    {
+        let mut ctx = Self::new();
+        ctx.is_const = true;
+        ctx
+    }
+
+    fn add_panic_entry(&mut self, entry: PanicEntry) -> BackendResult<u32>
+    {
+        match &mut self.panic_table {
+            Some(panic_table) => Ok(panic_table.add_entry(entry)),
+            None => Err(BackendError::Internal(String::from("add_panic_entry: no panic table"))),
+        }
+    }
+
+    fn local_var(&self, idx: usize) -> BackendResult<Place>
+    {
+        match self.local_vars.get(idx) {
+            Some(place) => Ok(place.clone()),
+            None => Err(BackendError::Internal(format!("local_var: no local variable {}", idx))),
+        }
+    }
+
+    fn caller_arg(&self, idx: usize) -> BackendResult<Place>
+    {
+        match self.caller_args.get(idx) {
+            Some(place) => Ok(place.clone()),
+            None => Err(BackendError::Internal(format!("caller_arg: no caller argument {}", idx))),
+        }
+    }
+
+    fn heap(&self, modifier: IrPtrModifier) -> BackendResult<SpirvValue>
+    {
+        let heap = match modifier {
+            IrPtrModifier::Private => &self.heaps.0,
+            IrPtrModifier::Local => &self.heaps.1,
+            _ => &self.heaps.2,
+        };
+        match heap {
+            Some(heap) => Ok(heap.clone()),
+            None => Err(BackendError::Internal(String::from("heap: no heap"))),
+        }
+    }
+}
+
+struct SpirvModule<'a>
+{
+    tree: &'a IrTree,
+    private_heap_size: usize,
+    local_heap_size: usize,
+    bound: u32,
+    capabilities: BTreeSet<u32>,
+    ext_inst_set: u32,
+    entry_points: Vec<(u32, String)>,
+    names: Vec<u32>,
+    decorations: Vec<u32>,
+    globals: Vec<u32>,
+    funs: Vec<u32>,
+    type_ids: HashMap<TypeKey, u32>,
+    in_progress_structs: BTreeSet<String>,
+    forward_ptrs: Vec<(u32, u32, TypeKey)>,
+    const_ids: HashMap<(u32, Vec<u32>), u32>,
+    const_elems: HashMap<u32, ConstElem>,
+    null_ids: HashMap<u32, u32>,
+    composite_ids: HashMap<(u32, Vec<u32>), u32>,
+    plain_const_ids: HashSet<u32>,
+    string_vars: HashMap<Vec<u8>, u32>,
+    global_vars: HashMap<String, Place>,
+    in_progress_global_vars: BTreeSet<String>,
+    fun_sigs: HashMap<String, FunSignature>,
+    builtin_vars: BTreeMap<u32, u32>,
+    spec_consts: HashMap<u32, u32>,
+    heap_len_ids: HashMap<u32, u32>,
+    local_heap_var: Option<u32>,
+    alloc_funs: HashMap<String, u32>,
+    panic_funs: Option<(u32, u32)>,
+}
+
+impl<'a> SpirvModule<'a>
+{
+    fn new(tree: &'a IrTree, private_heap_size: usize, local_heap_size: usize) -> Self
+    {
+        SpirvModule {
+            tree,
+            private_heap_size,
+            local_heap_size,
+            bound: 1,
+            capabilities: BTreeSet::new(),
+            ext_inst_set: 0,
+            entry_points: Vec::new(),
+            names: Vec::new(),
+            decorations: Vec::new(),
+            globals: Vec::new(),
+            funs: Vec::new(),
+            type_ids: HashMap::new(),
+            in_progress_structs: BTreeSet::new(),
+            forward_ptrs: Vec::new(),
+            const_ids: HashMap::new(),
+            const_elems: HashMap::new(),
+            null_ids: HashMap::new(),
+            composite_ids: HashMap::new(),
+            plain_const_ids: HashSet::new(),
+            string_vars: HashMap::new(),
+            global_vars: HashMap::new(),
+            in_progress_global_vars: BTreeSet::new(),
+            fun_sigs: HashMap::new(),
+            builtin_vars: BTreeMap::new(),
+            spec_consts: HashMap::new(),
+            heap_len_ids: HashMap::new(),
+            local_heap_var: None,
+            alloc_funs: HashMap::new(),
+            panic_funs: None,
+        }
+    }
+
+    fn new_id(&mut self) -> u32
+    {
+        let id = self.bound;
+        self.bound += 1;
+        id
+    }
+
+    fn add_name(&mut self, id: u32, name: &str)
+    {
+        let mut operands = vec![id];
+        operands.extend(string_words(name));
+        push_instr(&mut self.names, OP_NAME, operands.as_slice());
+    }
+
+    fn add_decoration(&mut self, id: u32, decoration: u32, operands: &[u32])
+    {
+        let mut operands2 = vec![id, decoration];
+        operands2.extend_from_slice(operands);
+        push_instr(&mut self.decorations, OP_DECORATE, operands2.as_slice());
+    }
+
+    fn field_types(&self, typ: &IrType) -> BackendResult<Vec<(usize, IrType)>>
+    {
+        match typ {
+            IrType::Struct(ident) => {
+                match self.tree.struct1(ident) {
+                    Some(struct1) => {
+                        match &*struct1.borrow() {
+                            IrStruct::Struct(field_types) => Ok(field_types.iter().enumerate().map(|(i, t)| (i, (**t).clone())).collect()),
+                            IrStruct::Closure(field_types) => Ok(field_types.iter().map(|(i, t)| (*i, (**t).clone())).collect()),
+                        }
+                    },
+                    None => Err(BackendError::Internal(format!("field_types: no structure {}", ident))),
+                }
+            },
+            _ => Err(BackendError::Internal(String::from("field_types: type isn't structure"))),
+        }
+    }
+
+    fn field_type(&self, typ: &IrType, idx: usize) -> BackendResult<IrType>
+    {
+        match typ {
+            IrType::Struct(ident) => {
+                match self.field_types(typ)?.into_iter().find(|p| p.0 == idx) {
+                    Some((_, field_type)) => Ok(field_type),
+                    None => Err(BackendError::Internal(format!("field_type: no field {} in structure {}", idx, ident))),
+                }
+            },
+            IrType::Union(ident) => {
+                match self.tree.union(ident) {
+                    Some(union) => {
+                        match union.borrow().0.get(idx) {
+                            Some(field_type) => Ok((**field_type).clone()),
+                            None => Err(BackendError::Internal(format!("field_type: no field {} in union {}", idx, ident))),
+                        }
+                    },
+                    None => Err(BackendError::Internal(format!("field_type: no union {}", ident))),
+                }
+            },
+            _ => {
+                match vector_type_parts(typ) {
+                    Some((elem_type, n)) if idx < n => Ok(elem_type),
+                    _ => Err(BackendError::Internal(String::from("field_type: type hasn't fields"))),
+                }
+            },
+        }
+    }
+
+    fn field_position(&self, typ: &IrType, idx: usize) -> BackendResult<usize>
+    {
+        match typ {
+            IrType::Struct(ident) => {
+                match self.field_types(typ)?.iter().position(|p| p.0 == idx) {
+                    Some(pos) => Ok(pos),
+                    None => Err(BackendError::Internal(format!("field_position: no field {} in structure {}", idx, ident))),
+                }
+            },
+            _ => Ok(idx),
+        }
+    }
+
+    fn type_key(&self, typ: &IrType) -> BackendResult<TypeKey>
+    {
+        match typ {
+            IrType::Void => Ok(TypeKey::Void),
+            IrType::Struct(ident) => Ok(TypeKey::Struct(ident.clone())),
+            IrType::Union(ident) => Ok(TypeKey::Union(ident.clone())),
+            IrType::Array(elem_type, len) => Ok(TypeKey::Array(Box::new(self.type_key(elem_type)?), *len)),
+            IrType::Ptr(modifier, _, elem_type) => {
+                let elem_key = match &**elem_type {
+                    IrType::Void => TypeKey::Int(8),
+                    _ => self.type_key(elem_type)?,
+                };
+                Ok(TypeKey::Ptr(storage_class(*modifier), Box::new(elem_key)))
+            },
+            _ => {
+                match (scalar_kind(typ), vector_type_parts(typ)) {
+                    (Some(ScalarKind::Int(bits, _)), _) => Ok(TypeKey::Int(bits)),
+                    (Some(ScalarKind::Float(bits)), _) => Ok(TypeKey::Float(bits)),
+                    (None, Some((elem_type, n))) => Ok(TypeKey::Vector(Box::new(self.type_key(&elem_type)?), n)),
+                    (None, None) => Err(BackendError::Internal(String::from("type_key: unsupported type"))),
+                }
+            },
+        }
+    }
+
+    fn key_has_in_progress_struct(&self, key: &TypeKey) -> bool
+    {
+        match key {
+            TypeKey::Struct(ident) => self.in_progress_structs.contains(ident),
+            TypeKey::Vector(elem_key, _) | TypeKey::Array(elem_key, _) | TypeKey::SpecArray(elem_key, _) => self.key_has_in_progress_struct(elem_key),
+            _ => false,
+        }
+    }
+
+    fn type_id(&mut self, key: &TypeKey) -> BackendResult<u32>
+    {
+        if let Some(id) = self.type_ids.get(key) {
+            return Ok(*id);
+        }
+        let mut operands: Vec<u32> = Vec::new();
+        let opcode = match key {
+            TypeKey::Void => OP_TYPE_VOID,
+            TypeKey::Bool => OP_TYPE_BOOL,
+            TypeKey::Int(bits) => {
+                match bits {
+                    8 => { self.capabilities.insert(CAPABILITY_INT8); },
+                    16 => { self.capabilities.insert(CAPABILITY_INT16); },
+                    64 => { self.capabilities.insert(CAPABILITY_INT64); },
+                    _ => (),
+                }
+                operands.push(*bits);
+                operands.push(0);
+                OP_TYPE_INT
+            },
+            TypeKey::Float(bits) => {
+                match bits {
+                    16 => { self.capabilities.insert(CAPABILITY_FLOAT16); },
+                    64 => { self.capabilities.insert(CAPABILITY_FLOAT64); },
+                    _ => (),
+                }
+                operands.push(*bits);
+                OP_TYPE_FLOAT
+            },
+            TypeKey::Vector(elem_key, n) => {
+                if *n >= 8 {
+                    self.capabilities.insert(CAPABILITY_VECTOR16);
+                }
+                operands.push(self.type_id(elem_key)?);
+                operands.push(*n as u32);
+                OP_TYPE_VECTOR
+            },
+            TypeKey::Array(elem_key, len) => {
+                if *len == 0 {
+                    return Err(BackendError::Internal(String::from("type_id: array has zero length")));
+                }
+                operands.push(self.type_id(elem_key)?);
+                operands.push(self.const_elem(&IrType::Uint, ConstElem::Int(*len as i128))?);
+                OP_TYPE_ARRAY
+            },
+            TypeKey::SpecArray(elem_key, len_id) => {
+                operands.push(self.type_id(elem_key)?);
+                operands.push(*len_id);
+                OP_TYPE_ARRAY
+            },
+            TypeKey::Struct(ident) => {
+                if self.in_progress_structs.contains(ident) {
+                    return Err(BackendError::Internal(format!("type_id: structure {} is recursive", ident)));
+                }
+                self.in_progress_structs.insert(ident.clone());
+                for (_, field_type) in self.field_types(&IrType::Struct(ident.clone()))? {
+                    let field_key = self.type_key(&field_type)?;
+                    operands.push(self.type_id(&field_key)?);
+                }
+                self.in_progress_structs.remove(ident);
+                OP_TYPE_STRUCT
+            },
+            TypeKey::Union(ident) => {
+                let layout = ir_type_layout(&IrType::Union(ident.clone()), self.tree)?;
+                let elem_key = match layout.align {
+                    1 => TypeKey::Int(8),
+                    2 => TypeKey::Int(16),
+                    4 => TypeKey::Int(32),
+                    8 => TypeKey::Int(64),
+                    align => TypeKey::Vector(Box::new(TypeKey::Int(64)), align / 8),
+                };
+                if layout.size > 0 {
+                    operands.push(self.type_id(&TypeKey::Array(Box::new(elem_key), layout.size / layout.align))?);
+                }
+                OP_TYPE_STRUCT
+            },
+            TypeKey::Ptr(storage, elem_key) => {
+                if *storage == STORAGE_CLASS_GENERIC {
+                    self.capabilities.insert(CAPABILITY_GENERIC_POINTER);
+                }
+                if self.key_has_in_progress_struct(elem_key) {
+                    let id = self.new_id();
+                    push_instr(&mut self.globals, OP_TYPE_FORWARD_POINTER, &[id, *storage]);
+                    self.forward_ptrs.push((id, *storage, (**elem_key).clone()));
+                    self.type_ids.insert(key.clone(), id);
+                    return Ok(id);
+                }
+                let elem_id = self.type_id(elem_key)?;
+                if let Some(id) = self.type_ids.get(key) {
+                    return Ok(*id);
+                }
+                operands.push(*storage);
+                operands.push(elem_id);
+                OP_TYPE_POINTER
+            },
+            TypeKey::Fun(ret_key, arg_keys) => {
+                operands.push(self.type_id(ret_key)?);
+                for arg_key in arg_keys {
+                    operands.push(self.type_id(arg_key)?);
+                }
+                OP_TYPE_FUNCTION
+            },
+        };
+        let id = self.new_id();
+        let mut operands2 = vec![id];
+        operands2.extend(operands);
+        push_instr(&mut self.globals, opcode, operands2.as_slice());
+        self.type_ids.insert(key.clone(), id);
+        match key {
+            TypeKey::Struct(ident) | TypeKey::Union(ident) => {
+                self.add_name(id, ident.as_str());
+                self.resolve_forward_ptrs()?;
+            },
+            _ => (),
+        }
+        Ok(id)
+    }
+
+    fn resolve_forward_ptrs(&mut self) -> BackendResult<()>
+    {
+        let mut i = 0;
+        while i < self.forward_ptrs.len() {
+            if !self.key_has_in_progress_struct(&self.forward_ptrs[i].2) {
+                let (id, storage, elem_key) = self.forward_ptrs.remove(i);
+                let elem_id = self.type_id(&elem_key)?;
+                push_instr(&mut self.globals, OP_TYPE_POINTER, &[id, storage, elem_id]);
+                i = 0;
+            } else {
+                i += 1;
+            }
+        }
+        Ok(())
+    }
+
+    fn ir_type_id(&mut self, typ: &IrType) -> BackendResult<u32>
+    {
+        let key = self.type_key(typ)?;
+        self.type_id(&key)
+    }
+
+    fn bool_type_id(&mut self, n: Option<usize>) -> BackendResult<u32>
+    {
+        match n {
+            Some(n) => self.type_id(&TypeKey::Vector(Box::new(TypeKey::Bool), n)),
+            None => self.type_id(&TypeKey::Bool),
+        }
+    }
+
+    fn fun_type_id(&mut self, param_types: &[IrType], ret_type: &IrType) -> BackendResult<u32>
+    {
+        let mut param_keys: Vec<TypeKey> = Vec::new();
+        for param_type in param_types {
+            param_keys.push(self.type_key(param_type)?);
+        }
+        let ret_key = self.type_key(ret_type)?;
+        self.type_id(&TypeKey::Fun(Box::new(ret_key), param_keys))
+    }
+
+    fn const_words(&mut self, type_id: u32, words: Vec<u32>) -> u32
+    {
+        if let Some(id) = self.const_ids.get(&(type_id, words.clone())) {
+            return *id;
+        }
+        let id = self.new_id();
+        let mut operands = vec![type_id, id];
+        operands.extend_from_slice(words.as_slice());
+        push_instr(&mut self.globals, OP_CONSTANT, operands.as_slice());
+        self.const_ids.insert((type_id, words), id);
+        self.plain_const_ids.insert(id);
+        id
+    }
+
+    fn const_elem(&mut self, typ: &IrType, elem: ConstElem) -> BackendResult<u32>
+    {
+        match scalar_kind(typ) {
+            Some(kind) => {
+                let type_id = self.ir_type_id(typ)?;
+                let id = self.const_words(type_id, elem_words(elem, kind));
+                self.const_elems.insert(id, convert_elem(elem, kind));
+                Ok(id)
+            },
+            None => Err(BackendError::Internal(String::from("const_elem: type isn't scalar type"))),
+        }
+    }
+
+    fn const_uint(&mut self, n: u32) -> BackendResult<u32>
+    { self.const_elem(&IrType::Uint, ConstElem::Int(n as i128)) }
+
+    fn const_ulong(&mut self, n: u64) -> BackendResult<u32>
+    { self.const_elem(&IrType::Ulong, ConstElem::Int(n as i128)) }
+
+    fn const_value(&mut self, typ: &IrType, elem: ConstElem) -> BackendResult<SpirvValue>
+    {
+        match vector_type_parts(typ) {
+            Some((elem_type, n)) => {
+                let elem_id = self.const_elem(&elem_type, elem)?;
+                let type_id = self.ir_type_id(typ)?;
+                Ok(SpirvValue::new(self.const_composite(type_id, vec![elem_id; n]), typ.clone()))
+            },
+            None => Ok(SpirvValue::new(self.const_elem(typ, elem)?, typ.clone())),
+        }
+    }
+
+    fn const_null(&mut self, typ: &IrType) -> BackendResult<u32>
+    {
+        let type_id = self.ir_type_id(typ)?;
+        if let Some(id) = self.null_ids.get(&type_id) {
+            return Ok(*id);
+        }
+        let id = self.new_id();
+        push_instr(&mut self.globals, OP_CONSTANT_NULL, &[type_id, id]);
+        self.null_ids.insert(type_id, id);
+        self.plain_const_ids.insert(id);
+        Ok(id)
+    }
+
+    fn const_composite(&mut self, type_id: u32, ids: Vec<u32>) -> u32
+    {
+        if let Some(id) = self.composite_ids.get(&(type_id, ids.clone())) {
+            return *id;
+        }
+        let id = self.new_id();
+        let is_plain = ids.iter().all(|id| self.plain_const_ids.contains(id));
+        let mut operands = vec![type_id, id];
+        operands.extend_from_slice(ids.as_slice());
+        push_instr(&mut self.globals, if is_plain { OP_CONSTANT_COMPOSITE } else { OP_SPEC_CONSTANT_COMPOSITE }, operands.as_slice());
+        self.composite_ids.insert((type_id, ids), id);
+        if is_plain {
+            self.plain_const_ids.insert(id);
+        }
+        id
+    }
+
+    fn spec_const(&mut self, spec_id: u32, default: usize) -> BackendResult<u32>
+    {
+        if let Some(id) = self.spec_consts.get(&spec_id) {
+            return Ok(*id);
+        }
+        let type_id = self.ir_type_id(&IrType::Uint)?;
+        let id = self.new_id();
+        push_instr(&mut self.globals, OP_SPEC_CONSTANT, &[type_id, id, default as u32]);
+        self.add_decoration(id, DECORATION_SPEC_ID, &[spec_id]);
+        self.spec_consts.insert(spec_id, id);
+        Ok(id)
+    }
+
+    fn const_op(&mut self, opcode: u32, type_id: u32, operands: &[u32]) -> BackendResult<u32>
+    {
+        if opcode == OP_COMPOSITE_CONSTRUCT {
+            return Ok(self.const_composite(type_id, operands.to_vec()));
+        }
+        if !SPEC_CONSTANT_OPS.contains(&opcode) {
+            return Err(BackendError::Internal(String::from("const_op: value of constant isn't constant")));
+        }
+        let id = self.new_id();
+        let mut operands2 = vec![type_id, id, opcode];
+        operands2.extend_from_slice(operands);
+        push_instr(&mut self.globals, OP_SPEC_CONSTANT_OP, operands2.as_slice());
+        Ok(id)
+    }
+
+    fn emit_op(&mut self, ctx: &mut SpirvFunContext<'_>, opcode: u32, type_id: u32, operands: &[u32]) -> BackendResult<u32>
+    {
+        if ctx.is_const {
+            return self.const_op(opcode, type_id, operands);
+        }
+        let id = self.new_id();
+        let mut operands2 = vec![type_id, id];
+        operands2.extend_from_slice(operands);
+        push_instr(&mut ctx.body, opcode, operands2.as_slice());
+        Ok(id)
+    }
+
+    fn emit_void_op(&mut self, ctx: &mut SpirvFunContext<'_>, opcode: u32, operands: &[u32]) -> BackendResult<()>
+    {
+        if ctx.is_const {
+            return Err(BackendError::Internal(String::from("emit_void_op: instruction in constant")));
+        }
+        push_instr(&mut ctx.body, opcode, operands);
+        Ok(())
+    }
+
+    fn emit_label(&mut self, ctx: &mut SpirvFunContext<'_>, label: u32)
+    {
+        push_instr(&mut ctx.body, OP_LABEL, &[label]);
+        ctx.is_terminated = false;
+    }
+
+    fn emit_terminator(&mut self, ctx: &mut SpirvFunContext<'_>, opcode: u32, operands: &[u32])
+    {
+        if !ctx.is_terminated {
+            push_instr(&mut ctx.body, opcode, operands);
+            ctx.is_terminated = true;
+        }
+    }
+
+    fn emit_branch(&mut self, ctx: &mut SpirvFunContext<'_>, label: u32)
+    { self.emit_terminator(ctx, OP_BRANCH, &[label]); }
+
+    fn ensure_block(&mut self, ctx: &mut SpirvFunContext<'_>)
+    {
+        if ctx.is_terminated {
+            let label = self.new_id();
+            self.emit_label(ctx, label);
+        }
+    }
+
+    fn temp_var(&mut self, ctx: &mut SpirvFunContext<'_>, typ: &IrType) -> BackendResult<Place>
+    {
+        if ctx.is_const {
+            return Err(BackendError::Internal(String::from("temp_var: variable in constant")));
+        }
+        let ptr_type_id = self.ir_type_id(&ptr_type(IrPtrModifier::Private, typ.clone()))?;
+        let id = self.new_id();
+        push_instr(&mut ctx.vars, OP_VARIABLE, &[ptr_type_id, id, STORAGE_CLASS_FUNCTION]);
+        Ok(Place::new(id, typ.clone(), IrPtrModifier::Private))
+    }
+
+    fn module_var(&mut self, typ: &IrType, modifier: IrPtrModifier, initializer: Option<u32>) -> BackendResult<Place>
+    {
+        let ptr_type_id = self.ir_type_id(&ptr_type(modifier, typ.clone()))?;
+        let id = self.new_id();
+        let mut operands = vec![ptr_type_id, id, storage_class(modifier)];
+        if let Some(initializer) = initializer {
+            operands.push(initializer);
+        }
+        push_instr(&mut self.globals, OP_VARIABLE, operands.as_slice());
+        Ok(Place::new(id, typ.clone(), modifier))
+    }
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+enum BinOp
+{
+    Mul,
+    Div,
+    Rem,
+    Add,
+    Sub,
+    Shl,
+    Shr,
+    Eq,
+    Ne,
+    Lt,
+    Ge,
+    Gt,
+    Le,
+    And,
+    Xor,
+    Or,
+}
+
+impl BinOp
+{
+    fn is_cmp(&self) -> bool
+    {
+        matches!(self, BinOp::Eq | BinOp::Ne | BinOp::Lt | BinOp::Ge | BinOp::Gt | BinOp::Le)
+    }
+
+    fn is_shift(&self) -> bool
+    {
+        matches!(self, BinOp::Shl | BinOp::Shr)
+    }
+}
+
+fn bin_opcode(bin_op: BinOp, kind: ScalarKind) -> Option<u32>
+{
+    match (bin_op, kind) {
+        (BinOp::Mul, ScalarKind::Int(_, _)) => Some(OP_I_MUL),
+        (BinOp::Mul, ScalarKind::Float(_)) => Some(OP_F_MUL),
+        (BinOp::Div, ScalarKind::Int(_, true)) => Some(OP_S_DIV),
+        (BinOp::Div, ScalarKind::Int(_, false)) => Some(OP_U_DIV),
+        (BinOp::Div, ScalarKind::Float(_)) => Some(OP_F_DIV),
+        (BinOp::Rem, ScalarKind::Int(_, true)) => Some(OP_S_REM),
+        (BinOp::Rem, ScalarKind::Int(_, false)) => Some(OP_U_MOD),
+        (BinOp::Rem, ScalarKind::Float(_)) => Some(OP_F_REM),
+        (BinOp::Add, ScalarKind::Int(_, _)) => Some(OP_I_ADD),
+        (BinOp::Add, ScalarKind::Float(_)) => Some(OP_F_ADD),
+        (BinOp::Sub, ScalarKind::Int(_, _)) => Some(OP_I_SUB),
+        (BinOp::Sub, ScalarKind::Float(_)) => Some(OP_F_SUB),
+        (BinOp::Shl, ScalarKind::Int(_, _)) => Some(OP_SHIFT_LEFT_LOGICAL),
+        (BinOp::Shr, ScalarKind::Int(_, true)) => Some(OP_SHIFT_RIGHT_ARITHMETIC),
+        (BinOp::Shr, ScalarKind::Int(_, false)) => Some(OP_SHIFT_RIGHT_LOGICAL),
+        (BinOp::Eq, ScalarKind::Int(_, _)) => Some(OP_I_EQUAL),
+        (BinOp::Eq, ScalarKind::Float(_)) => Some(OP_F_ORD_EQUAL),
+        (BinOp::Ne, ScalarKind::Int(_, _)) => Some(OP_I_NOT_EQUAL),
+        (BinOp::Ne, ScalarKind::Float(_)) => Some(OP_F_UNORD_NOT_EQUAL),
+        (BinOp::Lt, ScalarKind::Int(_, true)) => Some(OP_S_LESS_THAN),
+        (BinOp::Lt, ScalarKind::Int(_, false)) => Some(OP_U_LESS_THAN),
+        (BinOp::Lt, ScalarKind::Float(_)) => Some(OP_F_ORD_LESS_THAN),
+        (BinOp::Ge, ScalarKind::Int(_, true)) => Some(OP_S_GREATER_THAN_EQUAL),
+        (BinOp::Ge, ScalarKind::Int(_, false)) => Some(OP_U_GREATER_THAN_EQUAL),
+        (BinOp::Ge, ScalarKind::Float(_)) => Some(OP_F_ORD_GREATER_THAN_EQUAL),
+        (BinOp::Gt, ScalarKind::Int(_, true)) => Some(OP_S_GREATER_THAN),
+        (BinOp::Gt, ScalarKind::Int(_, false)) => Some(OP_U_GREATER_THAN),
+        (BinOp::Gt, ScalarKind::Float(_)) => Some(OP_F_ORD_GREATER_THAN),
+        (BinOp::Le, ScalarKind::Int(_, true)) => Some(OP_S_LESS_THAN_EQUAL),
+        (BinOp::Le, ScalarKind::Int(_, false)) => Some(OP_U_LESS_THAN_EQUAL),
+        (BinOp::Le, ScalarKind::Float(_)) => Some(OP_F_ORD_LESS_THAN_EQUAL),
+        (BinOp::And, ScalarKind::Int(_, _)) => Some(OP_BITWISE_AND),
+        (BinOp::Xor, ScalarKind::Int(_, _)) => Some(OP_BITWISE_XOR),
+        (BinOp::Or, ScalarKind::Int(_, _)) => Some(OP_BITWISE_OR),
+        (_, _) => None,
+    }
+}
+
+fn convert_opcode(kind1: ScalarKind, kind2: ScalarKind) -> u32
+{
+    match (kind1, kind2) {
+        (ScalarKind::Int(_, true), ScalarKind::Int(_, _)) => OP_S_CONVERT,
+        (ScalarKind::Int(_, false), ScalarKind::Int(_, _)) => OP_U_CONVERT,
+        (ScalarKind::Int(_, true), ScalarKind::Float(_)) => OP_CONVERT_S_TO_F,
+        (ScalarKind::Int(_, false), ScalarKind::Float(_)) => OP_CONVERT_U_TO_F,
+        (ScalarKind::Float(_), ScalarKind::Int(_, true)) => OP_CONVERT_F_TO_S,
+        (ScalarKind::Float(_), ScalarKind::Int(_, false)) => OP_CONVERT_F_TO_U,
+        (ScalarKind::Float(_), ScalarKind::Float(_)) => OP_F_CONVERT,
+    }
+}
+
+fn rounding_mode(s: &str) -> Option<u32>
+{
+    match s {
+        "rte" => Some(0),
+        "rtz" => Some(1),
+        "rtp" => Some(2),
+        "rtn" => Some(3),
+        _ => None,
+    }
+}
+
+#[derive(Clone, Debug)]
+enum Index
+{
+    Const(u64),
+    Var(SpirvValue),
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+enum HiddenParam
+{
+    PrivateHeap,
+    LocalHeap,
+    GlobalHeap,
+    PanicBuffer,
+    PanicFlag,
+}
+
+fn hidden_params(flags: (IrPrivateHeapFlag, IrLocalHeapFlag, IrGlobalHeapFlag, IrPanicFlag), is_kernel: bool) -> Vec<(HiddenParam, IrType)>
+{
+    let mut params: Vec<(HiddenParam, IrType)> = Vec::new();
+    if flags.0 == IrPrivateHeapFlag::Heap && !is_kernel {
+        params.push((HiddenParam::PrivateHeap, ptr_type(IrPtrModifier::Private, IrType::Uint)));
+    }
+    if flags.1 == IrLocalHeapFlag::Heap && !is_kernel {
+        params.push((HiddenParam::LocalHeap, ptr_type(IrPtrModifier::Local, IrType::Uint)));
+    }
+    if flags.2 == IrGlobalHeapFlag::Heap {
+        params.push((HiddenParam::GlobalHeap, ptr_type(IrPtrModifier::Global, IrType::Uint)));
+    }
+    if flags.3 == IrPanicFlag::Panic {
+        params.push((HiddenParam::PanicBuffer, ptr_type(IrPtrModifier::Global, IrType::Uint)));
+        if !is_kernel {
+            params.push((HiddenParam::PanicFlag, ptr_type(IrPtrModifier::Private, IrType::Uint)));
+        }
+    }
+    params
+}
+
+impl SpirvVar for IrValueVar
+{
+    fn spirv_var(&self, module: &mut SpirvModule<'_>, ctx: &mut SpirvFunContext<'_>) -> BackendResult<SpirvValue>
+    { module.value_var_value(ctx, self) }
+}
+
+impl SpirvVar for IrArgVar
+{
+    fn spirv_var(&self, module: &mut SpirvModule<'_>, ctx: &mut SpirvFunContext<'_>) -> BackendResult<SpirvValue>
+    { module.arg_var_value(ctx, self) }
+}
+
+impl<'a> SpirvModule<'a>
+{
+    fn void_value(&self) -> SpirvValue
+    { SpirvValue::new(0, IrType::Void) }
+
+    fn ext_inst(&mut self, ctx: &mut SpirvFunContext<'_>, typ: &IrType, inst: u32, operands: &[u32]) -> BackendResult<SpirvValue>
+    {
+        let type_id = self.ir_type_id(typ)?;
+        let mut operands2 = vec![self.ext_inst_set, inst];
+        operands2.extend_from_slice(operands);
+        let id = self.emit_op(ctx, OP_EXT_INST, type_id, operands2.as_slice())?;
+        Ok(SpirvValue::new(id, typ.clone()))
+    }
+
+    fn composite(&mut self, ctx: &mut SpirvFunContext<'_>, typ: &IrType, ids: Vec<u32>) -> BackendResult<SpirvValue>
+    {
+        let type_id = self.ir_type_id(typ)?;
+        let id = self.emit_op(ctx, OP_COMPOSITE_CONSTRUCT, type_id, ids.as_slice())?;
+        Ok(SpirvValue::new(id, typ.clone()))
+    }
+
+    fn load(&mut self, ctx: &mut SpirvFunContext<'_>, place: &Place) -> BackendResult<SpirvValue>
+    {
+        if place.typ == IrType::Void {
+            return Ok(self.void_value());
+        }
+        let type_id = self.ir_type_id(&place.typ)?;
+        let id = self.emit_op(ctx, OP_LOAD, type_id, &[place.ptr])?;
+        Ok(SpirvValue::new(id, place.typ.clone()))
+    }
+
+    fn store(&mut self, ctx: &mut SpirvFunContext<'_>, place: &Place, value: SpirvValue) -> BackendResult<()>
+    {
+        if place.typ == IrType::Void {
+            return Ok(());
+        }
+        let value2 = self.convert(ctx, value, &place.typ)?;
+        self.emit_void_op(ctx, OP_STORE, &[place.ptr, value2.id])
+    }
+
+    fn spill(&mut self, ctx: &mut SpirvFunContext<'_>, value: SpirvValue) -> BackendResult<Place>
+    {
+        let place = self.temp_var(ctx, &value.typ)?;
+        self.store(ctx, &place, value)?;
+        Ok(place)
+    }
+
+    fn elem_place(&mut self, ctx: &mut SpirvFunContext<'_>, ptr: &SpirvValue, idx: usize) -> BackendResult<Place>
+    {
+        let place = self.deref(ctx, Base::Value(ptr.clone()))?;
+        if idx == 0 {
+            return Ok(place);
+        }
+        let ptr_type_id = self.ir_type_id(&ptr.typ)?;
+        let idx_id = self.const_uint(idx as u32)?;
+        let id = self.emit_op(ctx, OP_PTR_ACCESS_CHAIN, ptr_type_id, &[ptr.id, idx_id])?;
+        Ok(Place::new(id, place.typ, place.modifier))
+    }
+
+    fn cast(&mut self, ctx: &mut SpirvFunContext<'_>, value: SpirvValue, typ: &Option<Box<IrType>>) -> BackendResult<SpirvValue>
+    {
+        match typ {
+            Some(typ) => self.convert(ctx, value, typ),
+            None => Ok(value),
+        }
+    }
+
+    fn convert(&mut self, ctx: &mut SpirvFunContext<'_>, value: SpirvValue, typ: &IrType) -> BackendResult<SpirvValue>
+    {
+        if value.typ == *typ || *typ == IrType::Void {
+            return Ok(SpirvValue::new(value.id, typ.clone()));
+        }
+        let key1 = self.type_key(&value.typ)?;
+        let key2 = self.type_key(typ)?;
+        if key1 == key2 {
+            return Ok(SpirvValue::new(value.id, typ.clone()));
+        }
+        let type_id = self.ir_type_id(typ)?;
+        match (lanes_kind(&value.typ), lanes_kind(typ)) {
+            (Some((kind1, None)), Some((kind2, None))) => {
+                if let Some(elem) = self.const_elems.get(&value.id) {
+                    let elem2 = convert_elem(convert_elem(*elem, kind1), kind2);
+                    return self.const_value(typ, elem2);
+                }
+                let id = self.emit_op(ctx, convert_opcode(kind1, kind2), type_id, &[value.id])?;
+                return Ok(SpirvValue::new(id, typ.clone()));
+            },
+            (Some((kind1, Some(n1))), Some((kind2, Some(n2)))) if n1 == n2 => {
+                let id = self.emit_op(ctx, convert_opcode(kind1, kind2), type_id, &[value.id])?;
+                return Ok(SpirvValue::new(id, typ.clone()));
+            },
+            (Some((_, None)), Some((kind2, Some(n2)))) => {
+                let elem_value = self.convert(ctx, value, &kind_type(kind2))?;
+                return self.composite(ctx, typ, vec![elem_value.id; n2]);
+            },
+            (_, _) => (),
+        }
+        match (&value.typ, typ) {
+            (IrType::Ptr(modifier1, _, _), IrType::Ptr(modifier2, _, elem_type2)) => {
+                let mut id = value.id;
+                let elem_key1 = match &key1 {
+                    TypeKey::Ptr(_, elem_key) => (**elem_key).clone(),
+                    _ => return Err(BackendError::Internal(String::from("convert: invalid pointer type"))),
+                };
+                let elem_key2 = match &key2 {
+                    TypeKey::Ptr(_, elem_key) => (**elem_key).clone(),
+                    _ => return Err(BackendError::Internal(String::from("convert: invalid pointer type"))),
+                };
+                if elem_key1 != elem_key2 {
+                    let tmp_type_id = self.ir_type_id(&ptr_type(*modifier1, (**elem_type2).clone()))?;
+                    id = self.emit_op(ctx, OP_BITCAST, tmp_type_id, &[id])?;
+                }
+                if modifier1 != modifier2 {
+                    if *modifier1 == IrPtrModifier::Constant || *modifier2 == IrPtrModifier::Constant {
+                        return Err(BackendError::Internal(String::from("convert: unsupported conversion of constant pointer")));
+                    }
+                    if *modifier1 != IrPtrModifier::None {
+                        let tmp_type_id = self.ir_type_id(&ptr_type(IrPtrModifier::None, (**elem_type2).clone()))?;
+                        id = self.emit_op(ctx, OP_PTR_CAST_TO_GENERIC, tmp_type_id, &[id])?;
+                    }
+                    if *modifier2 != IrPtrModifier::None {
+                        id = self.emit_op(ctx, OP_GENERIC_CAST_TO_PTR, type_id, &[id])?;
+                    }
+                }
+                Ok(SpirvValue::new(id, typ.clone()))
+            },
+            (IrType::Ptr(_, _, _), _) if scalar_kind(typ).is_some() => {
+                let ulong_type_id = self.ir_type_id(&IrType::Ulong)?;
+                let id = self.emit_op(ctx, OP_CONVERT_PTR_TO_U, ulong_type_id, &[value.id])?;
+                self.convert(ctx, SpirvValue::new(id, IrType::Ulong), typ)
+            },
+            (_, IrType::Ptr(_, _, _)) if scalar_kind(&value.typ).is_some() => {
+                if let Some(ConstElem::Int(0)) = self.const_elems.get(&value.id) {
+                    return Ok(SpirvValue::new(self.const_null(typ)?, typ.clone()));
+                }
+                let value2 = self.convert(ctx, value, &IrType::Ulong)?;
+                let id = self.emit_op(ctx, OP_CONVERT_U_TO_PTR, type_id, &[value2.id])?;
+                Ok(SpirvValue::new(id, typ.clone()))
+            },
+            (_, _) => {
+                let size1 = ir_type_layout(&value.typ, self.tree)?.size;
+                let size2 = ir_type_layout(typ, self.tree)?.size;
+                if size1 != size2 {
+                    return Err(BackendError::Internal(String::from("convert: unsupported conversion")));
+                }
+                let is_simple = |t: &IrType| lanes_kind(t).is_some() || is_ptr_type(t);
+                if is_simple(&value.typ) && is_simple(typ) {
+                    let id = self.emit_op(ctx, OP_BITCAST, type_id, &[value.id])?;
+                    Ok(SpirvValue::new(id, typ.clone()))
+                } else {
+                    let place = self.spill(ctx, value)?;
+                    let ptr_type_id = self.ir_type_id(&ptr_type(IrPtrModifier::Private, typ.clone()))?;
+                    let ptr = self.emit_op(ctx, OP_BITCAST, ptr_type_id, &[place.ptr])?;
+                    self.load(ctx, &Place::new(ptr, typ.clone(), IrPtrModifier::Private))
+                }
+            },
+        }
+    }
+
+    fn deref(&mut self, ctx: &mut SpirvFunContext<'_>, base: Base) -> BackendResult<Place>
+    {
+        let value = self.base_value(ctx, base)?;
+        match &value.typ {
+            IrType::Ptr(modifier, _, elem_type) => Ok(Place::new(value.id, (**elem_type).clone(), *modifier)),
+            _ => Err(BackendError::Internal(String::from("deref: value isn't pointer"))),
+        }
+    }
+
+    fn base_value(&mut self, ctx: &mut SpirvFunContext<'_>, base: Base) -> BackendResult<SpirvValue>
+    {
+        match base {
+            Base::Place(place) => self.load(ctx, &place),
+            Base::Value(value) => Ok(value),
+        }
+    }
+
+    fn field_place(&mut self, ctx: &mut SpirvFunContext<'_>, place: &Place, idx: usize) -> BackendResult<Place>
+    {
+        let field_type = self.field_type(&place.typ, idx)?;
+        let ptr_type_id = self.ir_type_id(&ptr_type(place.modifier, field_type.clone()))?;
+        let id = match &place.typ {
+            IrType::Union(_) => self.emit_op(ctx, OP_BITCAST, ptr_type_id, &[place.ptr])?,
+            _ => {
+                let pos = self.field_position(&place.typ, idx)?;
+                let pos_id = self.const_uint(pos as u32)?;
+                self.emit_op(ctx, OP_IN_BOUNDS_ACCESS_CHAIN, ptr_type_id, &[place.ptr, pos_id])?
+            },
+        };
+        Ok(Place::new(id, field_type, place.modifier))
+    }
+
+    fn dot_base(&mut self, ctx: &mut SpirvFunContext<'_>, base: Base, idx: usize) -> BackendResult<Base>
+    {
+        match base {
+            Base::Place(place) => Ok(Base::Place(self.field_place(ctx, &place, idx)?)),
+            Base::Value(value) => {
+                match &value.typ {
+                    IrType::Union(_) => {
+                        let place = self.spill(ctx, value)?;
+                        Ok(Base::Place(self.field_place(ctx, &place, idx)?))
+                    },
+                    _ => {
+                        let field_type = self.field_type(&value.typ, idx)?;
+                        let pos = self.field_position(&value.typ, idx)?;
+                        let type_id = self.ir_type_id(&field_type)?;
+                        let id = self.emit_op(ctx, OP_COMPOSITE_EXTRACT, type_id, &[value.id, pos as u32])?;
+                        Ok(Base::Value(SpirvValue::new(id, field_type)))
+                    },
+                }
+            },
+        }
+    }
+
+    fn index_id(&mut self, index: &Index) -> BackendResult<u32>
+    {
+        match index {
+            Index::Const(i) => self.const_ulong(*i),
+            Index::Var(value) => {
+                match scalar_kind(&value.typ) {
+                    Some(ScalarKind::Int(_, _)) => Ok(value.id),
+                    _ => Err(BackendError::Internal(String::from("index_id: index isn't integer"))),
+                }
+            },
+        }
+    }
+
+    fn index_base(&mut self, ctx: &mut SpirvFunContext<'_>, base: Base, index: Index) -> BackendResult<Base>
+    {
+        let typ = match &base {
+            Base::Place(place) => place.typ.clone(),
+            Base::Value(value) => value.typ.clone(),
+        };
+        match (&typ, base) {
+            (IrType::Array(elem_type, _), Base::Place(place)) => {
+                let idx_id = self.index_id(&index)?;
+                let ptr_type_id = self.ir_type_id(&ptr_type(place.modifier, (**elem_type).clone()))?;
+                let id = self.emit_op(ctx, OP_IN_BOUNDS_ACCESS_CHAIN, ptr_type_id, &[place.ptr, idx_id])?;
+                Ok(Base::Place(Place::new(id, (**elem_type).clone(), place.modifier)))
+            },
+            (IrType::Array(elem_type, _), Base::Value(value)) => {
+                match index {
+                    Index::Const(i) => {
+                        let type_id = self.ir_type_id(elem_type)?;
+                        let id = self.emit_op(ctx, OP_COMPOSITE_EXTRACT, type_id, &[value.id, i as u32])?;
+                        Ok(Base::Value(SpirvValue::new(id, (**elem_type).clone())))
+                    },
+                    Index::Var(_) => {
+                        let place = self.spill(ctx, value)?;
+                        self.index_base(ctx, Base::Place(place), index)
+                    },
+                }
+            },
+            (IrType::Ptr(_, _, _), base) => {
+                let ptr = self.base_value(ctx, base)?;
+                let place = self.deref(ctx, Base::Value(ptr.clone()))?;
+                let idx_id = self.index_id(&index)?;
+                let ptr_type_id = self.ir_type_id(&ptr.typ)?;
+                let id = self.emit_op(ctx, OP_PTR_ACCESS_CHAIN, ptr_type_id, &[ptr.id, idx_id])?;
+                Ok(Base::Place(Place::new(id, place.typ, place.modifier)))
+            },
+            (_, _) => Err(BackendError::Internal(String::from("index_base: type isn't array or pointer"))),
+        }
+    }
+
+    fn closure_place(&mut self, ctx: &mut SpirvFunContext<'_>, modifier: IrPtrModifier, idx: usize) -> BackendResult<Place>
+    {
+        let closure = match modifier {
+            IrPtrModifier::Private => ctx.private_closure.clone(),
+            IrPtrModifier::Local => ctx.local_closure.clone(),
+            _ => ctx.global_closure.clone(),
+        };
+        match closure {
+            Some(place) => self.field_place(ctx, &place, idx),
+            None => Err(BackendError::Internal(format!("closure_place: no closure for variable {}", idx))),
+        }
+    }
+
+    fn apply_arg_op(&mut self, ctx: &mut SpirvFunContext<'_>, base: Base, op: &IrArgOp) -> BackendResult<Base>
+    {
+        let index_place = match op {
+            IrArgOp::Deref(_) => return Ok(Base::Place(self.deref(ctx, base)?)),
+            IrArgOp::Dot(_, idx) => return self.dot_base(ctx, base, *idx),
+            IrArgOp::Arrow(_, idx) => {
+                let place = self.deref(ctx, base)?;
+                return self.dot_base(ctx, Base::Place(place), *idx);
+            },
+            IrArgOp::Index(_, idx) => return self.index_base(ctx, base, Index::Const(*idx)),
+            IrArgOp::GlobalIndex(_, ident) => self.global_place(ident)?,
+            IrArgOp::LocalIndex(_, idx) => ctx.local_var(*idx)?,
+            IrArgOp::CallerFunArgIndex(_, idx) => ctx.caller_arg(*idx)?,
+            IrArgOp::PrivateClosureIndex(_, idx) => self.closure_place(ctx, IrPtrModifier::Private, *idx)?,
+            IrArgOp::LocalClosureIndex(_, idx) => self.closure_place(ctx, IrPtrModifier::Local, *idx)?,
+            IrArgOp::GlobalClosureIndex(_, idx) => self.closure_place(ctx, IrPtrModifier::Global, *idx)?,
+        };
+        let index_value = self.load(ctx, &index_place)?;
+        self.index_base(ctx, base, Index::Var(index_value))
+    }
+
+    fn apply_arg_ops(&mut self, ctx: &mut SpirvFunContext<'_>, base: Base, ops: &[IrArgOp]) -> BackendResult<Base>
+    {
+        let mut base2 = base;
+        for op in ops {
+            base2 = self.apply_arg_op(ctx, base2, op)?;
+        }
+        Ok(base2)
+    }
+
+    fn ref_value(&mut self, ctx: &mut SpirvFunContext<'_>, base: Base, vector_elem_ptr_type: &Option<Box<IrType>>) -> BackendResult<SpirvValue>
+    {
+        let place = match base {
+            Base::Place(place) => place,
+            Base::Value(_) => return Err(BackendError::Internal(String::from("ref_value: reference to value"))),
+        };
+        match vector_elem_ptr_type {
+            Some(typ) => self.convert(ctx, place.ptr_value(), typ),
+            None => {
+                let typ = IrType::Ptr(IrPtrModifier::None, IrAccessModifier::None, Box::new(place.typ.clone()));
+                self.convert(ctx, place.ptr_value(), &typ)
+            },
+        }
+    }
+
+    fn arg_var_value(&mut self, ctx: &mut SpirvFunContext<'_>, var: &IrArgVar) -> BackendResult<SpirvValue>
+    {
+        let (base, ops, vector_elem_ptr_type) = match var {
+            IrArgVar::Global(ident, ops) => (Base::Place(self.global_place(ident)?), ops, None),
+            IrArgVar::Local(idx, ops) => (Base::Place(ctx.local_var(*idx)?), ops, None),
+            IrArgVar::CallerFunArg(idx, ops) => (Base::Place(ctx.caller_arg(*idx)?), ops, None),
+            IrArgVar::PrivateClosure(idx, ops) => (Base::Place(self.closure_place(ctx, IrPtrModifier::Private, *idx)?), ops, None),
+            IrArgVar::LocalClosure(idx, ops) => (Base::Place(self.closure_place(ctx, IrPtrModifier::Local, *idx)?), ops, None),
+            IrArgVar::GlobalClosure(idx, ops) => (Base::Place(self.closure_place(ctx, IrPtrModifier::Global, *idx)?), ops, None),
+            IrArgVar::PrivateHeap(ops) => (Base::Value(ctx.heap(IrPtrModifier::Private)?), ops, None),
+            IrArgVar::LocalHeap(ops) => (Base::Value(ctx.heap(IrPtrModifier::Local)?), ops, None),
+            IrArgVar::GlobalHeap(ops) => (Base::Value(ctx.heap(IrPtrModifier::Global)?), ops, None),
+            IrArgVar::RefGlobal(ident, ops, typ) => (Base::Place(self.global_place(ident)?), ops, Some(typ)),
+            IrArgVar::RefLocal(idx, ops, typ) => (Base::Place(ctx.local_var(*idx)?), ops, Some(typ)),
+            IrArgVar::RefCallerFunArg(idx, ops, typ) => (Base::Place(ctx.caller_arg(*idx)?), ops, Some(typ)),
+            IrArgVar::RefPrivateClosure(idx, ops, typ) => (Base::Place(self.closure_place(ctx, IrPtrModifier::Private, *idx)?), ops, Some(typ)),
+            IrArgVar::RefLocalClosure(idx, ops, typ) => (Base::Place(self.closure_place(ctx, IrPtrModifier::Local, *idx)?), ops, Some(typ)),
+            IrArgVar::RefGlobalClosure(idx, ops, typ) => (Base::Place(self.closure_place(ctx, IrPtrModifier::Global, *idx)?), ops, Some(typ)),
+            _ => return Err(BackendError::Internal(String::from("arg_var_value: references to heaps are unsupported"))),
+        };
+        let base2 = self.apply_arg_ops(ctx, base, ops.as_slice())?;
+        match vector_elem_ptr_type {
+            Some(typ) => self.ref_value(ctx, base2, typ),
+            None => self.base_value(ctx, base2),
+        }
+    }
+
+    fn value_var_value(&mut self, ctx: &mut SpirvFunContext<'_>, var: &IrValueVar) -> BackendResult<SpirvValue>
+    {
+        let mut base = Base::Place(self.global_place(&var.0)?);
+        for op in &var.1 {
+            let arg_op = match op {
+                IrValueOp::Deref(typ) => IrArgOp::Deref(typ.clone()),
+                IrValueOp::Dot(typ, idx) => IrArgOp::Dot(typ.clone(), *idx),
+                IrValueOp::Arrow(typ, idx) => IrArgOp::Arrow(typ.clone(), *idx),
+                IrValueOp::Index(typ, idx) => IrArgOp::Index(typ.clone(), *idx),
+            };
+            base = self.apply_arg_op(ctx, base, &arg_op)?;
+        }
+        self.ref_value(ctx, base, &None)
+    }
+
+    fn instr_var_place(&mut self, ctx: &mut SpirvFunContext<'_>, var: &IrInstrVar) -> BackendResult<Place>
+    {
+        let (base, ops) = match var {
+            IrInstrVar::Global(ident, ops) => (Base::Place(self.global_place(ident)?), ops),
+            IrInstrVar::Local(idx, ops) => (Base::Place(ctx.local_var(*idx)?), ops),
+            IrInstrVar::CallerFunArg(idx, ops) => (Base::Place(ctx.caller_arg(*idx)?), ops),
+            IrInstrVar::PrivateClosure(idx, ops) => (Base::Place(self.closure_place(ctx, IrPtrModifier::Private, *idx)?), ops),
+            IrInstrVar::LocalClosure(idx, ops) => (Base::Place(self.closure_place(ctx, IrPtrModifier::Local, *idx)?), ops),
+            IrInstrVar::GlobalClosure(idx, ops) => (Base::Place(self.closure_place(ctx, IrPtrModifier::Global, *idx)?), ops),
+            IrInstrVar::PrivateHeap(ops) => (Base::Value(ctx.heap(IrPtrModifier::Private)?), ops),
+            IrInstrVar::LocalHeap(ops) => (Base::Value(ctx.heap(IrPtrModifier::Local)?), ops),
+            IrInstrVar::GlobalHeap(ops) => (Base::Value(ctx.heap(IrPtrModifier::Global)?), ops),
+        };
+        match self.apply_arg_ops(ctx, base, ops.as_slice())? {
+            Base::Place(place) => Ok(place),
+            Base::Value(_) => Err(BackendError::Internal(String::from("instr_var_place: variable isn't place"))),
+        }
+    }
+
+    fn global_place(&mut self, ident: &String) -> BackendResult<Place>
+    {
+        if let Some(place) = self.global_vars.get(ident) {
+            return Ok(place.clone());
+        }
+        if self.in_progress_global_vars.contains(ident) {
+            return Err(BackendError::Internal(format!("global_place: variable {} is recursive", ident)));
+        }
+        let tree = self.tree;
+        let (typ, value, is_const) = match tree.var(ident) {
+            Some(var) => {
+                match &*var.borrow() {
+                    IrVar::Const(typ, value) => ((**typ).clone(), value.clone(), true),
+                    IrVar::Var(_, _, typ, value) => ((**typ).clone(), value.clone(), false),
+                    IrVar::Fun(_) => return Err(BackendError::Internal(format!("global_place: variable {} is function", ident))),
+                }
+            },
+            None => return Err(BackendError::Internal(format!("global_place: no variable {}", ident))),
+        };
+        self.in_progress_global_vars.insert(ident.clone());
+        let mut ctx = SpirvFunContext::new_const();
+        let value2 = self.value(&mut ctx, &value, Some(&typ))?;
+        let value3 = self.convert(&mut ctx, value2, &typ)?;
+        self.in_progress_global_vars.remove(ident);
+        let place = self.module_var(&typ, IrPtrModifier::Global, Some(value3.id))?;
+        if is_const {
+            self.add_decoration(place.ptr, DECORATION_CONSTANT, &[]);
+        }
+        self.add_name(place.ptr, ident.as_str());
+        self.global_vars.insert(ident.clone(), place.clone());
+        Ok(place)
+    }
+
+    fn string_value(&mut self, ctx: &mut SpirvFunContext<'_>, bytes: &[u8]) -> BackendResult<SpirvValue>
+    {
+        let var_id = match self.string_vars.get(bytes) {
+            Some(id) => *id,
+            None => {
+                let array_type = IrType::Array(Box::new(IrType::Char), bytes.len() + 1);
+                let mut ids: Vec<u32> = Vec::new();
+                for b in bytes.iter().chain([0u8].iter()) {
+                    ids.push(self.const_elem(&IrType::Char, ConstElem::Int(*b as i8 as i128))?);
+                }
+                let type_id = self.ir_type_id(&array_type)?;
+                let initializer = self.const_composite(type_id, ids);
+                let place = self.module_var(&array_type, IrPtrModifier::Constant, Some(initializer))?;
+                self.add_decoration(place.ptr, DECORATION_CONSTANT, &[]);
+                self.string_vars.insert(bytes.to_vec(), place.ptr);
+                place.ptr
+            },
+        };
+        let typ = IrType::Ptr(IrPtrModifier::Constant, IrAccessModifier::Const, Box::new(IrType::Char));
+        let type_id = self.ir_type_id(&typ)?;
+        let zero_id = self.const_uint(0)?;
+        let id = self.emit_op(ctx, OP_IN_BOUNDS_ACCESS_CHAIN, type_id, &[var_id, zero_id])?;
+        Ok(SpirvValue::new(id, typ))
+    }
+
+    fn value<T: SpirvVar>(&mut self, ctx: &mut SpirvFunContext<'_>, value: &IrValue<T>, expected: Option<&IrType>) -> BackendResult<SpirvValue>
+    {
+        let (typ, elem, cast_type) = match value {
+            IrValue::Char(n, typ) => (IrType::Char, ConstElem::Int(*n as i128), typ),
+            IrValue::Short(n, typ) => (IrType::Short, ConstElem::Int(*n as i128), typ),
+            IrValue::Int(n, typ) => (IrType::Int, ConstElem::Int(*n as i128), typ),
+            IrValue::Long(n, typ) => (IrType::Long, ConstElem::Int(*n as i128), typ),
+            IrValue::Uchar(n, typ) => (IrType::Uchar, ConstElem::Int(*n as i128), typ),
+            IrValue::Ushort(n, typ) => (IrType::Ushort, ConstElem::Int(*n as i128), typ),
+            IrValue::Uint(n, typ) => (IrType::Uint, ConstElem::Int(*n as i128), typ),
+            IrValue::Ulong(n, typ) => (IrType::Ulong, ConstElem::Int(*n as i128), typ),
+            IrValue::Float(x, typ) => (IrType::Float, ConstElem::Float(*x as f64), typ),
+            IrValue::Double(x, typ) => (IrType::Double, ConstElem::Float(*x), typ),
+            IrValue::SizeT(n, typ) => (IrType::SizeT, ConstElem::Int(*n as i128), typ),
+            IrValue::PtrdiffT(n, typ) => (IrType::PtrdiffT, ConstElem::Int(*n as i128), typ),
+            IrValue::IntptrT(n, typ) => (IrType::IntptrT, ConstElem::Int(*n as i128), typ),
+            IrValue::UintptrT(n, typ) => (IrType::UintptrT, ConstElem::Int(*n as i128), typ),
+            IrValue::Object(object) => return self.object_value(ctx, object, expected),
+        };
+        let value2 = self.const_value(&typ, elem)?;
+        self.cast(ctx, value2, cast_type)
+    }
+
+    fn object_type(&self, typ: &Option<Box<IrType>>, expected: Option<&IrType>) -> BackendResult<IrType>
+    {
+        match (typ, expected) {
+            (Some(typ), _) => Ok((**typ).clone()),
+            (None, Some(typ)) => Ok(typ.clone()),
+            (None, None) => Err(BackendError::Internal(String::from("object_type: no type of object"))),
+        }
+    }
+
+    fn object_value<T: SpirvVar>(&mut self, ctx: &mut SpirvFunContext<'_>, object: &IrObject<T>, expected: Option<&IrType>) -> BackendResult<SpirvValue>
+    {
+        match object {
+            IrObject::String(bytes) => self.string_value(ctx, bytes.as_slice()),
+            IrObject::BuiltinVar(ident, typ1, typ2) => {
+                let value = match builtin_var_elem(ident.as_str()) {
+                    Some((typ, elem)) => self.const_value(&typ, elem)?,
+                    None => return Err(BackendError::Internal(format!("object_value: unsupported builtin variable {}", ident))),
+                };
+                let value2 = self.cast(ctx, value, typ1)?;
+                self.cast(ctx, value2, typ2)
+            },
+            IrObject::Var(var, typ) => {
+                let value = var.spirv_var(self, ctx)?;
+                self.cast(ctx, value, typ)
+            },
+            IrObject::Vector(values, typ) => self.vector_value(ctx, values.as_slice(), typ),
+            IrObject::Array(values, typ) => {
+                let typ2 = match (typ, expected, values.first()) {
+                    (None, None, Some(value)) => {
+                        let first = self.value(ctx, value, None)?;
+                        IrType::Array(Box::new(first.typ), values.len())
+                    },
+                    (_, _, _) => self.object_type(typ, expected)?,
+                };
+                let elem_type = match &typ2 {
+                    IrType::Array(elem_type, _) => (**elem_type).clone(),
+                    _ => return Err(BackendError::Internal(String::from("object_value: type of array isn't array type"))),
+                };
+                let mut ids: Vec<u32> = Vec::new();
+                for value in values {
+                    let value2 = self.value(ctx, value, Some(&elem_type))?;
+                    ids.push(self.convert(ctx, value2, &elem_type)?.id);
+                }
+                self.composite(ctx, &typ2, ids)
+            },
+            IrObject::Struct(values, field_pairs, typ) => {
+                let typ2 = self.object_type(typ, expected)?;
+                let field_types = self.field_types(&typ2)?;
+                let mut pairs: Vec<(usize, &IrValue<T>)> = Vec::new();
+                for (i, value) in values.iter().enumerate() {
+                    match field_types.get(i) {
+                        Some((idx, _)) => pairs.push((*idx, value)),
+                        None => return Err(BackendError::Internal(String::from("object_value: too many fields"))),
+                    }
+                }
+                for field_pair in field_pairs {
+                    pairs.push((field_pair.0, &field_pair.1));
+                }
+                self.struct_value(ctx, &typ2, field_types, pairs)
+            },
+            IrObject::Union(idx, value, typ) => {
+                let typ2 = self.object_type(typ, expected)?;
+                let field_type = self.field_type(&typ2, *idx)?;
+                let value2 = self.value(ctx, value, Some(&field_type))?;
+                let place = self.temp_var(ctx, &typ2)?;
+                let field_place = self.field_place(ctx, &place, *idx)?;
+                self.store(ctx, &field_place, value2)?;
+                self.load(ctx, &place)
+            },
+            IrObject::Closure(field_pairs, typ) => {
+                let typ2 = self.object_type(typ, expected)?;
+                let field_types = self.field_types(&typ2)?;
+                let pairs: Vec<(usize, &IrValue<T>)> = field_pairs.iter().map(|p| (p.0, &p.1)).collect();
+                self.struct_value(ctx, &typ2, field_types, pairs)
+            },
+            IrObject::Sizeof(typ, typ2) => {
+                let size = ir_type_layout(typ, self.tree)?.size;
+                let value = self.const_value(&IrType::SizeT, ConstElem::Int(size as i128))?;
+                self.cast(ctx, value, typ2)
+            },
+            IrObject::CallerFunIndex(_, idx, typ) => {
+                let value = self.const_value(&IrType::Uint, ConstElem::Int(*idx as i128))?;
+                self.cast(ctx, value, typ)
+            },
+        }
+    }
+
+    fn struct_value<T: SpirvVar>(&mut self, ctx: &mut SpirvFunContext<'_>, typ: &IrType, field_types: Vec<(usize, IrType)>, pairs: Vec<(usize, &IrValue<T>)>) -> BackendResult<SpirvValue>
+    {
+        let mut ids: Vec<u32> = Vec::new();
+        for (idx, field_type) in &field_types {
+            match pairs.iter().find(|p| p.0 == *idx) {
+                Some((_, value)) => {
+                    let value2 = self.value(ctx, value, Some(field_type))?;
+                    ids.push(self.convert(ctx, value2, field_type)?.id);
+                },
+                None => ids.push(self.const_null(field_type)?),
+            }
+        }
+        self.composite(ctx, typ, ids)
+    }
+
+    fn vector_value<T: SpirvVar>(&mut self, ctx: &mut SpirvFunContext<'_>, values: &[IrValue<T>], typ: &IrType) -> BackendResult<SpirvValue>
+    {
+        let (elem_type, n) = match vector_type_parts(typ) {
+            Some(pair) => pair,
+            None => return Err(BackendError::Internal(String::from("vector_value: type isn't vector type"))),
+        };
+        let mut ids: Vec<u32> = Vec::new();
+        let mut count = 0;
+        for value in values {
+            let value2 = self.value(ctx, value, Some(&elem_type))?;
+            match vector_type_parts(&value2.typ) {
+                Some((_, m)) => {
+                    let value3 = self.convert(ctx, value2, &vector_type(&elem_type, m).unwrap_or(IrType::Void))?;
+                    ids.push(value3.id);
+                    count += m;
+                },
+                None => {
+                    let value3 = self.convert(ctx, value2, &elem_type)?;
+                    ids.push(value3.id);
+                    count += 1;
+                },
+            }
+        }
+        if count == 1 && ids.len() == 1 {
+            return self.composite(ctx, typ, vec![ids[0]; n]);
+        }
+        if count != n {
+            return Err(BackendError::Internal(String::from("vector_value: invalid number of components")));
+        }
+        self.composite(ctx, typ, ids)
+    }
+
+    fn op(&mut self, ctx: &mut SpirvFunContext<'_>, op: &IrOp, expected: Option<&IrType>) -> BackendResult<SpirvValue>
+    {
+        match op {
+            IrOp::Load(value) => self.value(ctx, value, expected),
+            IrOp::Neg(value) => {
+                let value2 = self.value(ctx, value, None)?;
+                self.unary_op(ctx, value2, true)
+            },
+            IrOp::Not(value) => {
+                let value2 = self.value(ctx, value, None)?;
+                self.unary_op(ctx, value2, false)
+            },
+            IrOp::Mul(value1, value2) => self.bin_op(ctx, BinOp::Mul, value1, value2),
+            IrOp::Div(value1, value2) => self.bin_op(ctx, BinOp::Div, value1, value2),
+            IrOp::Rem(value1, value2) => self.bin_op(ctx, BinOp::Rem, value1, value2),
+            IrOp::Add(value1, value2) => self.bin_op(ctx, BinOp::Add, value1, value2),
+            IrOp::Sub(value1, value2) => self.bin_op(ctx, BinOp::Sub, value1, value2),
+            IrOp::Shl(value1, value2) => self.bin_op(ctx, BinOp::Shl, value1, value2),
+            IrOp::Shr(value1, value2) => self.bin_op(ctx, BinOp::Shr, value1, value2),
+            IrOp::Eq(value1, value2) => self.bin_op(ctx, BinOp::Eq, value1, value2),
+            IrOp::Ne(value1, value2) => self.bin_op(ctx, BinOp::Ne, value1, value2),
+            IrOp::Lt(value1, value2) => self.bin_op(ctx, BinOp::Lt, value1, value2),
+            IrOp::Ge(value1, value2) => self.bin_op(ctx, BinOp::Ge, value1, value2),
+            IrOp::Gt(value1, value2) => self.bin_op(ctx, BinOp::Gt, value1, value2),
+            IrOp::Le(value1, value2) => self.bin_op(ctx, BinOp::Le, value1, value2),
+            IrOp::And(value1, value2) => self.bin_op(ctx, BinOp::And, value1, value2),
+            IrOp::Xor(value1, value2) => self.bin_op(ctx, BinOp::Xor, value1, value2),
+            IrOp::Or(value1, value2) => self.bin_op(ctx, BinOp::Or, value1, value2),
+            IrOp::CallBuiltinFun(ident, typ, values) => self.call_builtin_fun(ctx, ident, typ, values.as_slice(), expected),
+            IrOp::CallFun(ident, values, _, _) | IrOp::CallFunWithoutPanic(ident, values, _) => self.call_fun(ctx, ident, values.as_slice()),
+        }
+    }
+
+    fn unary_op(&mut self, ctx: &mut SpirvFunContext<'_>, value: SpirvValue, is_neg: bool) -> BackendResult<SpirvValue>
+    {
+        let typ = promoted_type(&value.typ);
+        let value2 = self.convert(ctx, value, &typ)?;
+        let opcode = match (lanes_kind(&typ), is_neg) {
+            (Some((ScalarKind::Float(_), _)), true) => OP_F_NEGATE,
+            (Some((ScalarKind::Int(_, _), _)), true) => OP_S_NEGATE,
+            (Some((ScalarKind::Int(_, _), _)), false) => OP_NOT,
+            (_, _) => return Err(BackendError::Internal(String::from("unary_op: unsupported type"))),
+        };
+        let type_id = self.ir_type_id(&typ)?;
+        let id = self.emit_op(ctx, opcode, type_id, &[value2.id])?;
+        Ok(SpirvValue::new(id, typ))
+    }
+
+    fn bool_to_int(&mut self, ctx: &mut SpirvFunContext<'_>, cond: u32, typ: &IrType) -> BackendResult<SpirvValue>
+    {
+        let is_vector = vector_type_parts(typ).is_some();
+        let one = self.const_value(typ, ConstElem::Int(if is_vector { -1 } else { 1 }))?;
+        let zero = self.const_value(typ, ConstElem::Int(0))?;
+        let type_id = self.ir_type_id(typ)?;
+        let id = self.emit_op(ctx, OP_SELECT, type_id, &[cond, one.id, zero.id])?;
+        Ok(SpirvValue::new(id, typ.clone()))
+    }
+
+    fn bin_op(&mut self, ctx: &mut SpirvFunContext<'_>, bin_op: BinOp, value1: &IrValue<IrArgVar>, value2: &IrValue<IrArgVar>) -> BackendResult<SpirvValue>
+    {
+        let a = self.value(ctx, value1, None)?;
+        let b = self.value(ctx, value2, None)?;
+        if is_ptr_type(&a.typ) || is_ptr_type(&b.typ) {
+            return self.ptr_bin_op(ctx, bin_op, a, b);
+        }
+        let typ = if bin_op.is_shift() {
+            promoted_type(&a.typ)
+        } else if vector_type_parts(&a.typ).is_some() {
+            a.typ.clone()
+        } else if vector_type_parts(&b.typ).is_some() {
+            b.typ.clone()
+        } else {
+            common_type(&a.typ, &b.typ)
+        };
+        let (kind, n) = match lanes_kind(&typ) {
+            Some(pair) => pair,
+            None => return Err(BackendError::Internal(String::from("bin_op: unsupported type"))),
+        };
+        let a2 = self.convert(ctx, a, &typ)?;
+        let mut b2 = self.convert(ctx, b, &typ)?;
+        if bin_op.is_shift() {
+            let mask = self.const_value(&typ, ConstElem::Int((kind_bits(kind) - 1) as i128))?;
+            let type_id = self.ir_type_id(&typ)?;
+            let id = self.emit_op(ctx, OP_BITWISE_AND, type_id, &[b2.id, mask.id])?;
+            b2 = SpirvValue::new(id, typ.clone());
+        }
+        let opcode = match bin_opcode(bin_op, kind) {
+            Some(opcode) => opcode,
+            None => return Err(BackendError::Internal(String::from("bin_op: unsupported operation for type"))),
+        };
+        if bin_op.is_cmp() {
+            let bool_type_id = self.bool_type_id(n)?;
+            let cond = self.emit_op(ctx, opcode, bool_type_id, &[a2.id, b2.id])?;
+            let int_type = match n {
+                Some(n) => vector_type(&cmp_elem_type(kind), n).unwrap_or(IrType::Int),
+                None => IrType::Int,
+            };
+            self.bool_to_int(ctx, cond, &int_type)
+        } else {
+            let type_id = self.ir_type_id(&typ)?;
+            let id = self.emit_op(ctx, opcode, type_id, &[a2.id, b2.id])?;
+            Ok(SpirvValue::new(id, typ))
+        }
+    }
+
+    fn ptr_bin_op(&mut self, ctx: &mut SpirvFunContext<'_>, bin_op: BinOp, a: SpirvValue, b: SpirvValue) -> BackendResult<SpirvValue>
+    {
+        if bin_op.is_cmp() {
+            let a2 = self.convert(ctx, a, &IrType::Ulong)?;
+            let b2 = self.convert(ctx, b, &IrType::Ulong)?;
+            let opcode = bin_opcode(bin_op, ScalarKind::Int(64, false)).unwrap_or(OP_I_EQUAL);
+            let bool_type_id = self.bool_type_id(None)?;
+            let cond = self.emit_op(ctx, opcode, bool_type_id, &[a2.id, b2.id])?;
+            return self.bool_to_int(ctx, cond, &IrType::Int);
+        }
+        match (bin_op, &a.typ, &b.typ) {
+            (BinOp::Sub, IrType::Ptr(_, _, elem_type), IrType::Ptr(_, _, _)) => {
+                let elem_size = ir_type_layout(elem_type, self.tree)?.size.max(1);
+                let a2 = self.convert(ctx, a, &IrType::Long)?;
+                let b2 = self.convert(ctx, b, &IrType::Long)?;
+                let long_type_id = self.ir_type_id(&IrType::Long)?;
+                let diff = self.emit_op(ctx, OP_I_SUB, long_type_id, &[a2.id, b2.id])?;
+                let size = self.const_value(&IrType::Long, ConstElem::Int(elem_size as i128))?;
+                let id = self.emit_op(ctx, OP_S_DIV, long_type_id, &[diff, size.id])?;
+                Ok(SpirvValue::new(id, IrType::PtrdiffT))
+            },
+            (BinOp::Add | BinOp::Sub, IrType::Ptr(_, _, _), _) | (BinOp::Add, _, IrType::Ptr(_, _, _)) => {
+                let (ptr, offset) = if is_ptr_type(&a.typ) { (a, b) } else { (b, a) };
+                let mut offset2 = self.convert(ctx, offset, &IrType::Long)?;
+                if bin_op == BinOp::Sub {
+                    let long_type_id = self.ir_type_id(&IrType::Long)?;
+                    let id = self.emit_op(ctx, OP_S_NEGATE, long_type_id, &[offset2.id])?;
+                    offset2 = SpirvValue::new(id, IrType::Long);
+                }
+                let ptr_type_id = self.ir_type_id(&ptr.typ)?;
+                let id = self.emit_op(ctx, OP_PTR_ACCESS_CHAIN, ptr_type_id, &[ptr.id, offset2.id])?;
+                Ok(SpirvValue::new(id, ptr.typ))
+            },
+            (_, _, _) => Err(BackendError::Internal(String::from("ptr_bin_op: unsupported operation for pointers"))),
+        }
+    }
+
+    fn cond(&mut self, ctx: &mut SpirvFunContext<'_>, op: &IrOp) -> BackendResult<u32>
+    {
+        let value = self.op(ctx, op, None)?;
+        let value2 = if is_ptr_type(&value.typ) {
+            self.convert(ctx, value, &IrType::Ulong)?
+        } else {
+            value
+        };
+        let opcode = match scalar_kind(&value2.typ) {
+            Some(ScalarKind::Int(_, _)) => OP_I_NOT_EQUAL,
+            Some(ScalarKind::Float(_)) => OP_F_UNORD_NOT_EQUAL,
+            None => return Err(BackendError::Internal(String::from("cond: condition isn't scalar"))),
+        };
+        let zero = self.const_value(&value2.typ, ConstElem::Int(0))?;
+        let bool_type_id = self.bool_type_id(None)?;
+        self.emit_op(ctx, opcode, bool_type_id, &[value2.id, zero.id])
+    }
+
+    fn fun_flags(&self, ident: &String) -> (IrPrivateHeapFlag, IrLocalHeapFlag, IrGlobalHeapFlag, IrPanicFlag)
+    {
+        match self.tree.var(ident) {
+            Some(var) => {
+                match &*var.borrow() {
+                    IrVar::Fun(fun) => {
+                        match &**fun {
+                            IrFun::Fun(_, _, _, _, private_heap_flag, local_heap_flag, global_heap_flag, panic_flag) => (*private_heap_flag, *local_heap_flag, *global_heap_flag, *panic_flag),
+                            IrFun::Caller(_, _, _, _, private_heap_flag, local_heap_flag, global_heap_flag, panic_flag) => (*private_heap_flag, *local_heap_flag, *global_heap_flag, *panic_flag),
+                        }
+                    },
+                    _ => (IrPrivateHeapFlag::None, IrLocalHeapFlag::None, IrGlobalHeapFlag::None, IrPanicFlag::None),
+                }
+            },
+            None => (IrPrivateHeapFlag::None, IrLocalHeapFlag::None, IrGlobalHeapFlag::None, IrPanicFlag::None),
+        }
+    }
+
+    fn panic_call_poses(&self, op: &IrOp) -> Option<Vec<Pos>>
+    {
+        match op {
+            IrOp::CallFun(ident, _, pos, panic_poses) if self.fun_flags(ident).3 == IrPanicFlag::Panic => {
+                let mut poses = vec![pos.clone()];
+                poses.extend_from_slice(panic_poses.as_slice());
+                Some(poses)
+            },
+            _ => None,
+        }
+    }
+
+    fn emit_block(&mut self, ctx: &mut SpirvFunContext<'_>, block: &IrBlock) -> BackendResult<()>
+    {
+        let old_len = ctx.local_vars.len();
+        for local_var_pair in block.local_var_pairs() {
+            let IrLocalVarPair(modifier, typ) = local_var_pair;
+            let place = if **typ == IrType::Void {
+                Place::new(0, IrType::Void, IrPtrModifier::Private)
+            } else {
+                match modifier {
+                    IrLocalVarModifier::None | IrLocalVarModifier::Private => self.temp_var(ctx, typ)?,
+                    IrLocalVarModifier::Local => self.module_var(typ, IrPtrModifier::Local, None)?,
+                    IrLocalVarModifier::Global => {
+                        let null_id = self.const_null(typ)?;
+                        self.module_var(typ, IrPtrModifier::Global, Some(null_id))?
+                    },
+                }
+            };
+            ctx.local_vars.push(place);
+        }
+        for instr in block.instrs() {
+            self.emit_instr(ctx, instr)?;
+        }
+        ctx.local_vars.truncate(old_len);
+        Ok(())
+    }
+
+    fn emit_instr(&mut self, ctx: &mut SpirvFunContext<'_>, instr: &IrInstr) -> BackendResult<()>
+    {
+        self.ensure_block(ctx);
+        match instr {
+            IrInstr::Op(op) => {
+                self.op(ctx, op, None)?;
+                self.emit_panic_check(ctx, op)?;
+            },
+            IrInstr::Assign(var, op) => {
+                let place = self.instr_var_place(ctx, var)?;
+                let value = self.op(ctx, op, Some(&place.typ))?;
+                self.store(ctx, &place, value)?;
+                self.emit_panic_check(ctx, op)?;
+            },
+            IrInstr::Return(None) => self.emit_return(ctx, None)?,
+            IrInstr::Return(Some(op)) => {
+                let ret_type = ctx.ret_type.clone();
+                let value = self.op(ctx, op, Some(&ret_type))?;
+                self.emit_panic_check(ctx, op)?;
+                self.emit_return(ctx, Some(value))?;
+            },
+            IrInstr::Break => {
+                match ctx.loops.last() {
+                    Some((_, brk)) => {
+                        let brk = *brk;
+                        self.emit_branch(ctx, brk);
+                    },
+                    None => return Err(BackendError::Internal(String::from("emit_instr: break outside loop"))),
+                }
+            },
+            IrInstr::Continue => {
+                match ctx.loops.last() {
+                    Some((header, _)) => {
+                        let header = *header;
+                        self.emit_branch(ctx, header);
+                    },
+                    None => return Err(BackendError::Internal(String::from("emit_instr: continue outside loop"))),
+                }
+            },
+            IrInstr::Block(block) => self.emit_block(ctx, block)?,
+            IrInstr::If(op, block1, block2) => {
+                if self.panic_call_poses(op).is_some() {
+                    return Err(BackendError::Internal(String::from("emit_instr: call of function that can panic in condition")));
+                }
+                let cond = self.cond(ctx, op)?;
+                let label1 = self.new_id();
+                let label2 = self.new_id();
+                let end_label = self.new_id();
+                self.emit_terminator(ctx, OP_BRANCH_CONDITIONAL, &[cond, label1, label2]);
+                self.emit_label(ctx, label1);
+                self.emit_block(ctx, block1)?;
+                self.emit_branch(ctx, end_label);
+                self.emit_label(ctx, label2);
+                self.emit_block(ctx, block2)?;
+                self.emit_branch(ctx, end_label);
+                self.emit_label(ctx, end_label);
+            },
+            IrInstr::Switch(op, cases) => {
+                if self.panic_call_poses(op).is_some() {
+                    return Err(BackendError::Internal(String::from("emit_instr: call of function that can panic in condition")));
+                }
+                let value = self.op(ctx, op, None)?;
+                let kind = match scalar_kind(&value.typ) {
+                    Some(kind @ ScalarKind::Int(_, _)) => kind,
+                    _ => return Err(BackendError::Internal(String::from("emit_instr: selector of switch isn't integer"))),
+                };
+                let end_label = self.new_id();
+                let mut default_label = end_label;
+                let mut targets: Vec<(Vec<u32>, u32)> = Vec::new();
+                let mut blocks: Vec<(u32, &IrBlock)> = Vec::new();
+                for case in cases {
+                    let label = self.new_id();
+                    match case {
+                        IrCase::Case(case_value, block) => {
+                            let elem = match case_value_parts(case_value) {
+                                Some((typ, elem, cast_type)) => {
+                                    match cast_type {
+                                        Some(cast_type) => convert_elem(convert_elem(elem, scalar_kind(&typ).unwrap_or(kind)), scalar_kind(cast_type).unwrap_or(kind)),
+                                        None => elem,
+                                    }
+                                },
+                                None => {
+                                    match case_value {
+                                        IrCaseValue::BuiltinVar(ident, _, _) => {
+                                            match builtin_var_elem(ident.as_str()) {
+                                                Some((_, elem)) => elem,
+                                                None => return Err(BackendError::Internal(format!("emit_instr: unsupported builtin variable {}", ident))),
+                                            }
+                                        },
+                                        _ => return Err(BackendError::Internal(String::from("emit_instr: invalid case value"))),
+                                    }
+                                },
+                            };
+                            let words = elem_words(elem, kind);
+                            if targets.iter().all(|p| p.0 != words) {
+                                targets.push((words, label));
+                            }
+                            blocks.push((label, block));
+                        },
+                        IrCase::Default(block) => {
+                            default_label = label;
+                            blocks.push((label, block));
+                        },
+                    }
+                }
+                let mut operands = vec![value.id, default_label];
+                for (words, label) in &targets {
+                    operands.extend_from_slice(words.as_slice());
+                    operands.push(*label);
+                }
+                self.emit_terminator(ctx, OP_SWITCH, operands.as_slice());
+                for (label, block) in blocks {
+                    self.emit_label(ctx, label);
+                    self.emit_block(ctx, block)?;
+                    self.emit_branch(ctx, end_label);
+                }
+                self.emit_label(ctx, end_label);
+            },
+            IrInstr::Loop(block) => {
+                let header = self.new_id();
+                let brk = self.new_id();
+                self.emit_branch(ctx, header);
+                self.emit_label(ctx, header);
+                ctx.loops.push((header, brk));
+                self.emit_block(ctx, block)?;
+                ctx.loops.pop();
+                self.emit_branch(ctx, header);
+                self.emit_label(ctx, brk);
+            },
+            IrInstr::Panic(msg, pos, panic_poses) => {
+                if !ctx.can_panic {
+                    return Err(BackendError::Internal(String::from("emit_instr: panic in function that can't panic")));
+                }
+                let mut poses = vec![pos.clone()];
+                poses.extend_from_slice(panic_poses.as_slice());
+                let id = ctx.add_panic_entry(PanicEntry::Panic(msg.clone(), poses))?;
+                let (panic_fun, _) = self.panic_funs()?;
+                self.emit_panic_fun_call(ctx, panic_fun, id)?;
+                self.emit_default_return(ctx)?;
+            },
+        }
+        Ok(())
+    }
+
+    fn emit_return(&mut self, ctx: &mut SpirvFunContext<'_>, value: Option<SpirvValue>) -> BackendResult<()>
+    {
+        let ret_type = ctx.ret_type.clone();
+        match value {
+            Some(value) if ret_type != IrType::Void => {
+                let value2 = self.convert(ctx, value, &ret_type)?;
+                self.emit_terminator(ctx, OP_RETURN_VALUE, &[value2.id]);
+            },
+            _ => self.emit_default_return(ctx)?,
+        }
+        Ok(())
+    }
+
+    fn emit_default_return(&mut self, ctx: &mut SpirvFunContext<'_>) -> BackendResult<()>
+    {
+        if ctx.ret_type == IrType::Void {
+            self.emit_terminator(ctx, OP_RETURN, &[]);
+        } else {
+            let ret_type = ctx.ret_type.clone();
+            let null_id = self.const_null(&ret_type)?;
+            self.emit_terminator(ctx, OP_RETURN_VALUE, &[null_id]);
+        }
+        Ok(())
+    }
+
+    fn emit_panic_fun_call(&mut self, ctx: &mut SpirvFunContext<'_>, fun_id: u32, id: u32) -> BackendResult<()>
+    {
+        let (panic_buffer, panic_flag) = match (&ctx.panic_buffer, &ctx.panic_flag) {
+            (Some(panic_buffer), Some(panic_flag)) => (panic_buffer.id, panic_flag.id),
+            (_, _) => return Err(BackendError::Internal(String::from("emit_panic_fun_call: no panic buffer or panic flag"))),
+        };
+        let void_type_id = self.type_id(&TypeKey::Void)?;
+        let id_id = self.const_uint(id)?;
+        self.emit_op(ctx, OP_FUNCTION_CALL, void_type_id, &[fun_id, panic_buffer, panic_flag, id_id])?;
+        Ok(())
+    }
+
+    fn emit_panic_check(&mut self, ctx: &mut SpirvFunContext<'_>, op: &IrOp) -> BackendResult<()>
+    {
+        if let Some(poses) = self.panic_call_poses(op) {
+            let id = ctx.add_panic_entry(PanicEntry::Call(poses))?;
+            let panic_flag = match &ctx.panic_flag {
+                Some(panic_flag) => panic_flag.id,
+                None => return Err(BackendError::Internal(String::from("emit_panic_check: no panic flag"))),
+            };
+            let flag = self.load(ctx, &Place::new(panic_flag, IrType::Uint, IrPtrModifier::Private))?;
+            let zero_id = self.const_uint(0)?;
+            let bool_type_id = self.bool_type_id(None)?;
+            let cond = self.emit_op(ctx, OP_I_NOT_EQUAL, bool_type_id, &[flag.id, zero_id])?;
+            let panic_label = self.new_id();
+            let next_label = self.new_id();
+            self.emit_terminator(ctx, OP_BRANCH_CONDITIONAL, &[cond, panic_label, next_label]);
+            self.emit_label(ctx, panic_label);
+            let (_, panic_call_fun) = self.panic_funs()?;
+            self.emit_panic_fun_call(ctx, panic_call_fun, id)?;
+            self.emit_default_return(ctx)?;
+            self.emit_label(ctx, next_label);
+        }
+        Ok(())
+    }
+
+    fn fun_sig(&mut self, ident: &String) -> BackendResult<FunSignature>
+    {
+        if let Some(sig) = self.fun_sigs.get(ident) {
+            return Ok(sig.clone());
+        }
+        let tree = self.tree;
+        let (mut param_types, ret_type, is_kernel) = match tree.var(ident) {
+            Some(var) => {
+                match &*var.borrow() {
+                    IrVar::Fun(fun) => {
+                        match &**fun {
+                            IrFun::Fun(modifier, arg_types, ret_type, _, _, _, _, _) => (arg_types.iter().map(|t| (**t).clone()).collect::<Vec<IrType>>(), (**ret_type).clone(), *modifier == IrFunModifier::Kernel),
+                            IrFun::Caller(fun_type, arg_types, ret_type, _, _, _, _, _) => {
+                                let mut param_types = vec![(**fun_type).clone()];
+                                param_types.extend(arg_types.iter().map(|t| (**t).clone()));
+                                (param_types, (**ret_type).clone(), false)
+                            },
+                        }
+                    },
+                    _ => return Err(BackendError::Internal(format!("fun_sig: variable {} isn't function", ident))),
+                }
+            },
+            None => return Err(BackendError::Internal(format!("fun_sig: no function {}", ident))),
+        };
+        let flags = self.fun_flags(ident);
+        for (_, param_type) in hidden_params(flags, is_kernel) {
+            param_types.push(param_type);
+        }
+        let id = self.new_id();
+        self.add_name(id, ident.as_str());
+        let sig = FunSignature { id, param_types, ret_type, flags, };
+        self.fun_sigs.insert(ident.clone(), sig.clone());
+        Ok(sig)
+    }
+
+    fn hidden_args(&self, ctx: &SpirvFunContext<'_>, ident: &String, flags: (IrPrivateHeapFlag, IrLocalHeapFlag, IrGlobalHeapFlag, IrPanicFlag)) -> BackendResult<Vec<u32>>
+    {
+        if flags.0 > ctx.heap_flags.0 || flags.1 > ctx.heap_flags.1 || flags.2 > ctx.heap_flags.2 {
+            return Err(BackendError::Internal(format!("hidden_args: function {} uses heap", ident)));
+        }
+        if flags.3 == IrPanicFlag::Panic && !ctx.can_panic {
+            return Err(BackendError::Internal(format!("hidden_args: function {} can panic", ident)));
+        }
+        let mut args: Vec<u32> = Vec::new();
+        for (param, _) in hidden_params(flags, false) {
+            let arg = match param {
+                HiddenParam::PrivateHeap => &ctx.heaps.0,
+                HiddenParam::LocalHeap => &ctx.heaps.1,
+                HiddenParam::GlobalHeap => &ctx.heaps.2,
+                HiddenParam::PanicBuffer => &ctx.panic_buffer,
+                HiddenParam::PanicFlag => &ctx.panic_flag,
+            };
+            match arg {
+                Some(arg) => args.push(arg.id),
+                None => return Err(BackendError::Internal(format!("hidden_args: no hidden argument for function {}", ident))),
+            }
+        }
+        Ok(args)
+    }
+
+    fn call_fun(&mut self, ctx: &mut SpirvFunContext<'_>, ident: &String, values: &[IrValue<IrArgVar>]) -> BackendResult<SpirvValue>
+    {
+        let sig = self.fun_sig(ident)?;
+        let mut ids = vec![sig.id];
+        for (i, value) in values.iter().enumerate() {
+            match sig.param_types.get(i) {
+                Some(param_type) => {
+                    let value2 = self.value(ctx, value, Some(param_type))?;
+                    ids.push(self.convert(ctx, value2, param_type)?.id);
+                },
+                None => return Err(BackendError::Internal(format!("call_fun: too many arguments for function {}", ident))),
+            }
+        }
+        ids.extend(self.hidden_args(ctx, ident, sig.flags)?);
+        let ret_type_id = self.ir_type_id(&sig.ret_type)?;
+        let id = self.emit_op(ctx, OP_FUNCTION_CALL, ret_type_id, ids.as_slice())?;
+        if sig.ret_type == IrType::Void {
+            Ok(self.void_value())
+        } else {
+            Ok(SpirvValue::new(id, sig.ret_type))
+        }
+    }
+
+    fn call_builtin_fun(&mut self, ctx: &mut SpirvFunContext<'_>, ident: &String, typ: &Option<Box<IrType>>, values: &[IrValue<IrArgVar>], expected: Option<&IrType>) -> BackendResult<SpirvValue>
+    {
+        let mut args: Vec<SpirvValue> = Vec::new();
+        for value in values {
+            args.push(self.value(ctx, value, None)?);
+        }
+        let alloc_modifier = if *ident == mangle_private_alloc_fun_name() {
+            Some(IrPtrModifier::Private)
+        } else if *ident == mangle_local_alloc_fun_name() {
+            Some(IrPtrModifier::Local)
+        } else if *ident == mangle_global_alloc_fun_name() {
+            Some(IrPtrModifier::Global)
+        } else {
+            None
+        };
+        if let Some(modifier) = alloc_modifier {
+            return self.call_alloc_fun(ctx, modifier, typ, args);
+        }
+        match (ident.as_str(), args.as_slice()) {
+            ("convert", [value]) => {
+                match typ {
+                    Some(typ) => self.convert(ctx, value.clone(), typ),
+                    None => Err(BackendError::Internal(String::from("call_builtin_fun: no type for convert"))),
+                }
+            },
+            ("get_work_dim", []) => {
+                let var_id = self.builtin_var(BUILTIN_WORK_DIM)?;
+                self.load(ctx, &Place::new(var_id, IrType::Uint, IrPtrModifier::Private))
+            },
+            (_, [dim]) if work_item_builtin(ident.as_str()).is_some() => {
+                let (builtin, default) = work_item_builtin(ident.as_str()).unwrap_or((BUILTIN_GLOBAL_SIZE, 1));
+                self.work_item_value(ctx, builtin, default, dim.clone())
+            },
+            ("barrier", [flags]) => {
+                let scope_id = self.const_uint(SCOPE_WORKGROUP)?;
+                let sem_id = self.memory_semantics(flags, MEMORY_SEMANTICS_SEQUENTIALLY_CONSISTENT)?;
+                self.emit_void_op(ctx, OP_CONTROL_BARRIER, &[scope_id, scope_id, sem_id])?;
+                Ok(self.void_value())
+            },
+            ("mem_fence" | "read_mem_fence" | "write_mem_fence", [flags]) => {
+                let base = match ident.as_str() {
+                    "read_mem_fence" => MEMORY_SEMANTICS_ACQUIRE,
+                    "write_mem_fence" => MEMORY_SEMANTICS_RELEASE,
+                    _ => MEMORY_SEMANTICS_ACQUIRE_RELEASE,
+                };
+                let scope_id = self.const_uint(SCOPE_WORKGROUP)?;
+                let sem_id = self.memory_semantics(flags, base)?;
+                self.emit_void_op(ctx, OP_MEMORY_BARRIER, &[scope_id, sem_id])?;
+                Ok(self.void_value())
+            },
+            ("printf", [_, ..]) => {
+                let mut ids = vec![args[0].id];
+                for arg in &args[1..] {
+                    let arg2 = match scalar_kind(&arg.typ) {
+                        Some(ScalarKind::Int(bits, _)) if bits < 32 => self.convert(ctx, arg.clone(), &IrType::Int)?,
+                        Some(ScalarKind::Float(16)) => self.convert(ctx, arg.clone(), &IrType::Float)?,
+                        _ => arg.clone(),
+                    };
+                    ids.push(arg2.id);
+                }
+                self.ext_inst(ctx, &IrType::Int, opencl_std_fun("printf").unwrap_or(184), ids.as_slice())
+            },
+            ("all" | "any", [value]) => {
+                let n = match lanes_kind(&value.typ) {
+                    Some((ScalarKind::Int(_, _), n)) => n,
+                    _ => return Err(BackendError::Internal(format!("call_builtin_fun: invalid argument for {}", ident))),
+                };
+                let zero = self.const_value(&value.typ, ConstElem::Int(0))?;
+                let bool_type_id = self.bool_type_id(n)?;
+                let mut cond = self.emit_op(ctx, OP_S_LESS_THAN, bool_type_id, &[value.id, zero.id])?;
+                if n.is_some() {
+                    let scalar_bool_type_id = self.bool_type_id(None)?;
+                    let opcode = if ident == "all" { OP_ALL } else { OP_ANY };
+                    cond = self.emit_op(ctx, opcode, scalar_bool_type_id, &[cond])?;
+                }
+                self.bool_to_int(ctx, cond, &IrType::Int)
+            },
+            ("select" | "bitselect", [a, b, c]) => self.call_select_fun(ctx, ident.as_str(), a.clone(), b.clone(), c.clone()),
+            _ => {
+                if ident.starts_with("vload") {
+                    return self.vector_load(ctx, ident.as_str(), args.as_slice());
+                }
+                if ident.starts_with("vstore") {
+                    return self.vector_store(ctx, ident.as_str(), args.as_slice());
+                }
+                self.call_math_fun(ctx, ident.as_str(), args.as_slice(), expected)
+            },
+        }
+    }
+
+    fn memory_semantics(&mut self, flags: &SpirvValue, base: u32) -> BackendResult<u32>
+    {
+        let sem = match self.const_elems.get(&flags.id) {
+            Some(ConstElem::Int(f)) => {
+                let mut sem = base;
+                if (f & 1) != 0 {
+                    sem |= MEMORY_SEMANTICS_WORKGROUP_MEMORY;
+                }
+                if (f & 2) != 0 {
+                    sem |= MEMORY_SEMANTICS_CROSS_WORKGROUP_MEMORY;
+                }
+                sem
+            },
+            _ => base | MEMORY_SEMANTICS_WORKGROUP_MEMORY | MEMORY_SEMANTICS_CROSS_WORKGROUP_MEMORY,
+        };
+        self.const_uint(sem)
+    }
+
+    fn work_item_value(&mut self, ctx: &mut SpirvFunContext<'_>, builtin: u32, default: i128, dim: SpirvValue) -> BackendResult<SpirvValue>
+    {
+        let var_id = self.builtin_var(builtin)?;
+        let vector = self.load(ctx, &Place::new(var_id, IrType::UlongN(3), IrPtrModifier::Private))?;
+        let ulong_type_id = self.ir_type_id(&IrType::Ulong)?;
+        let default_value = self.const_value(&IrType::Ulong, ConstElem::Int(default))?;
+        match self.const_elems.get(&dim.id) {
+            Some(ConstElem::Int(d)) => {
+                if *d >= 0 && *d < 3 {
+                    let d = *d as u32;
+                    let id = self.emit_op(ctx, OP_COMPOSITE_EXTRACT, ulong_type_id, &[vector.id, d])?;
+                    Ok(SpirvValue::new(id, IrType::SizeT))
+                } else {
+                    Ok(SpirvValue::new(default_value.id, IrType::SizeT))
+                }
+            },
+            _ => {
+                let dim2 = self.convert(ctx, dim, &IrType::Uint)?;
+                let three_id = self.const_uint(3)?;
+                let zero_id = self.const_uint(0)?;
+                let uint_type_id = self.ir_type_id(&IrType::Uint)?;
+                let bool_type_id = self.bool_type_id(None)?;
+                let cond = self.emit_op(ctx, OP_U_LESS_THAN, bool_type_id, &[dim2.id, three_id])?;
+                let idx = self.emit_op(ctx, OP_SELECT, uint_type_id, &[cond, dim2.id, zero_id])?;
+                let elem = self.emit_op(ctx, OP_VECTOR_EXTRACT_DYNAMIC, ulong_type_id, &[vector.id, idx])?;
+                let id = self.emit_op(ctx, OP_SELECT, ulong_type_id, &[cond, elem, default_value.id])?;
+                Ok(SpirvValue::new(id, IrType::SizeT))
+            },
+        }
+    }
+
+    fn args_lanes_type(&self, args: &[SpirvValue]) -> BackendResult<(IrType, ScalarKind, Option<usize>)>
+    {
+        let typ = match args.iter().find(|arg| vector_type_parts(&arg.typ).is_some()).or(args.first()) {
+            Some(arg) => arg.typ.clone(),
+            None => return Err(BackendError::Internal(String::from("args_lanes_type: no arguments"))),
+        };
+        match lanes_kind(&typ) {
+            Some((kind, n)) => Ok((typ, kind, n)),
+            None => Err(BackendError::Internal(String::from("args_lanes_type: arguments aren't scalars or vectors"))),
+        }
+    }
+
+    fn convert_args(&mut self, ctx: &mut SpirvFunContext<'_>, args: &[SpirvValue], typ: &IrType) -> BackendResult<Vec<u32>>
+    {
+        let mut ids: Vec<u32> = Vec::new();
+        for arg in args {
+            ids.push(self.convert(ctx, arg.clone(), typ)?.id);
+        }
+        Ok(ids)
+    }
+
+    fn call_select_fun(&mut self, ctx: &mut SpirvFunContext<'_>, ident: &str, a: SpirvValue, b: SpirvValue, c: SpirvValue) -> BackendResult<SpirvValue>
+    {
+        let (typ, kind, n) = self.args_lanes_type(&[a.clone(), b.clone()])?;
+        let ids = self.convert_args(ctx, &[a, b], &typ)?;
+        if ident == "bitselect" {
+            let c2 = self.convert(ctx, c, &typ)?;
+            return self.ext_inst(ctx, &typ, opencl_std_fun("bitselect").unwrap_or(186), &[ids[0], ids[1], c2.id]);
+        }
+        match vector_type_parts(&c.typ) {
+            Some(_) => {
+                let cond_type = lanes_type(ScalarKind::Int(kind_bits(kind), true), n).unwrap_or(IrType::Int);
+                let c2 = self.convert(ctx, c, &cond_type)?;
+                self.ext_inst(ctx, &typ, opencl_std_fun("select").unwrap_or(187), &[ids[0], ids[1], c2.id])
+            },
+            None => {
+                let zero = self.const_value(&c.typ, ConstElem::Int(0))?;
+                let bool_type_id = self.bool_type_id(None)?;
+                let opcode = match scalar_kind(&c.typ) {
+                    Some(ScalarKind::Float(_)) => OP_F_UNORD_NOT_EQUAL,
+                    _ => OP_I_NOT_EQUAL,
+                };
+                let mut cond = self.emit_op(ctx, opcode, bool_type_id, &[c.id, zero.id])?;
+                if let Some(n) = n {
+                    let bool_vector_type_id = self.bool_type_id(Some(n))?;
+                    cond = self.emit_op(ctx, OP_COMPOSITE_CONSTRUCT, bool_vector_type_id, vec![cond; n].as_slice())?;
+                }
+                let type_id = self.ir_type_id(&typ)?;
+                let id = self.emit_op(ctx, OP_SELECT, type_id, &[cond, ids[1], ids[0]])?;
+                Ok(SpirvValue::new(id, typ))
+            },
+        }
+    }
+
+    fn vector_ptr_args(&mut self, ctx: &mut SpirvFunContext<'_>, offset: &SpirvValue, ptr: &SpirvValue, is_half: bool) -> BackendResult<(u32, u32, IrType)>
+    {
+        let offset2 = self.convert(ctx, offset.clone(), &IrType::SizeT)?;
+        let (modifier, elem_type) = match &ptr.typ {
+            IrType::Ptr(modifier, _, elem_type) => (*modifier, (**elem_type).clone()),
+            _ => return Err(BackendError::Internal(String::from("vector_ptr_args: argument isn't pointer"))),
+        };
+        if is_half {
+            let ptr2 = self.convert(ctx, ptr.clone(), &ptr_type(modifier, IrType::Half))?;
+            Ok((offset2.id, ptr2.id, IrType::Half))
+        } else {
+            Ok((offset2.id, ptr.id, elem_type))
+        }
+    }
+
+    fn vector_load(&mut self, ctx: &mut SpirvFunContext<'_>, ident: &str, args: &[SpirvValue]) -> BackendResult<SpirvValue>
+    {
+        let (suffix, is_half, is_aligned) = match (ident.strip_prefix("vloada_half"), ident.strip_prefix("vload_half"), ident.strip_prefix("vload")) {
+            (Some(suffix), _, _) => (suffix, true, true),
+            (None, Some(suffix), _) => (suffix, true, false),
+            (None, None, Some(suffix)) => (suffix, false, false),
+            (None, None, None) => return Err(BackendError::Internal(format!("vector_load: invalid function {}", ident))),
+        };
+        let n = if suffix.is_empty() { 1 } else { suffix.parse::<u32>().unwrap_or(0) };
+        let (offset_id, ptr_id, elem_type) = match args {
+            [offset, ptr] => self.vector_ptr_args(ctx, offset, ptr, is_half)?,
+            _ => return Err(BackendError::Internal(String::from("vector_load: invalid number of arguments"))),
+        };
+        let value_elem_type = if is_half { IrType::Float } else { elem_type };
+        let typ = if n == 1 {
+            Some(value_elem_type)
+        } else {
+            vector_type(&value_elem_type, n as usize)
+        };
+        let typ = match typ {
+            Some(typ) => typ,
+            None => return Err(BackendError::Internal(format!("vector_load: invalid function {}", ident))),
+        };
+        let (inst, has_n) = match (is_half, is_aligned, n) {
+            (false, _, _) => ("vloadn", true),
+            (true, false, 1) => ("vload_half", false),
+            (true, false, _) => ("vload_halfn", true),
+            (true, true, _) => ("vloada_halfn", true),
+        };
+        let mut operands = vec![offset_id, ptr_id];
+        if has_n {
+            operands.push(n);
+        }
+        self.ext_inst(ctx, &typ, opencl_std_fun(inst).unwrap_or(0), operands.as_slice())
+    }
+
+    fn vector_store(&mut self, ctx: &mut SpirvFunContext<'_>, ident: &str, args: &[SpirvValue]) -> BackendResult<SpirvValue>
+    {
+        let (suffix, is_half, is_aligned) = match (ident.strip_prefix("vstorea_half"), ident.strip_prefix("vstore_half"), ident.strip_prefix("vstore")) {
+            (Some(suffix), _, _) => (suffix, true, true),
+            (None, Some(suffix), _) => (suffix, true, false),
+            (None, None, Some(suffix)) => (suffix, false, false),
+            (None, None, None) => return Err(BackendError::Internal(format!("vector_store: invalid function {}", ident))),
+        };
+        let mode = match suffix.find('_') {
+            Some(i) => {
+                match rounding_mode(&suffix[(i + 1)..]) {
+                    Some(mode) => Some(mode),
+                    None => return Err(BackendError::Internal(format!("vector_store: invalid function {}", ident))),
+                }
+            },
+            None => None,
+        };
+        let (data, offset_id, ptr_id) = match args {
+            [data, offset, ptr] => {
+                let (offset_id, ptr_id, _) = self.vector_ptr_args(ctx, offset, ptr, is_half)?;
+                (data.clone(), offset_id, ptr_id)
+            },
+            _ => return Err(BackendError::Internal(String::from("vector_store: invalid number of arguments"))),
+        };
+        let is_vector = vector_type_parts(&data.typ).is_some();
+        let inst = match (is_half, is_aligned, is_vector, mode.is_some()) {
+            (false, _, _, _) => "vstoren",
+            (true, false, false, false) => "vstore_half",
+            (true, false, false, true) => "vstore_half_r",
+            (true, false, true, false) => "vstore_halfn",
+            (true, false, true, true) => "vstore_halfn_r",
+            (true, true, _, false) => "vstorea_halfn",
+            (true, true, _, true) => "vstorea_halfn_r",
+        };
+        let mut operands = vec![data.id, offset_id, ptr_id];
+        if let Some(mode) = mode {
+            operands.push(mode);
+        }
+        self.ext_inst(ctx, &IrType::Void, opencl_std_fun(inst).unwrap_or(0), operands.as_slice())?;
+        Ok(self.void_value())
+    }
+
+    fn call_alloc_fun(&mut self, ctx: &mut SpirvFunContext<'_>, modifier: IrPtrModifier, typ: &Option<Box<IrType>>, args: Vec<SpirvValue>) -> BackendResult<SpirvValue>
+    {
+        let typ = match typ {
+            Some(typ) => (**typ).clone(),
+            None => return Err(BackendError::Internal(String::from("call_alloc_fun: no type"))),
+        };
+        let heap = match args.first() {
+            Some(heap) => self.convert(ctx, heap.clone(), &ptr_type(modifier, IrType::Uint))?,
+            None => return Err(BackendError::Internal(String::from("call_alloc_fun: no heap"))),
+        };
+        let layout = ir_type_layout(&typ, self.tree)?;
+        let fun_id = self.alloc_fun(modifier)?;
+        let size_id = self.const_uint(layout.size as u32)?;
+        let align_id = self.const_uint(layout.align as u32)?;
+        let ret_type_id = self.ir_type_id(&ptr_type(modifier, IrType::Uchar))?;
+        let ptr = self.emit_op(ctx, OP_FUNCTION_CALL, ret_type_id, &[fun_id, heap.id, size_id, align_id])?;
+        let ptr_type_id = self.ir_type_id(&ptr_type(modifier, typ.clone()))?;
+        let id = self.emit_op(ctx, OP_BITCAST, ptr_type_id, &[ptr])?;
+        Ok(SpirvValue::new(id, ptr_type(modifier, typ)))
+    }
+
+    fn call_math_fun(&mut self, ctx: &mut SpirvFunContext<'_>, ident: &str, args: &[SpirvValue], expected: Option<&IrType>) -> BackendResult<SpirvValue>
+    {
+        let geometric_name = ["float_", "half_", "double_"].iter().find_map(|prefix| {
+                match ident.strip_prefix(prefix) {
+                    Some(name @ ("dot" | "length" | "distance")) => Some(name),
+                    _ => None,
+                }
+        });
+        let name = geometric_name.unwrap_or(ident);
+        let (typ, kind, n) = self.args_lanes_type(args)?;
+        match name {
+            "dot" | "length" | "distance" | "normalize" | "cross" | "fast_length" | "fast_distance" | "fast_normalize" => {
+                let ids = self.convert_args(ctx, args, &typ)?;
+                let elem_type = kind_type(kind);
+                match (name, ids.as_slice()) {
+                    ("dot", [a, b]) => {
+                        let opcode = if n.is_some() { OP_DOT } else { OP_F_MUL };
+                        let type_id = self.ir_type_id(&elem_type)?;
+                        let id = self.emit_op(ctx, opcode, type_id, &[*a, *b])?;
+                        Ok(SpirvValue::new(id, elem_type))
+                    },
+                    ("length" | "distance" | "fast_length" | "fast_distance", _) => self.ext_inst(ctx, &elem_type, opencl_std_fun(name).unwrap_or(0), ids.as_slice()),
+                    (_, _) => self.ext_inst(ctx, &typ, opencl_std_fun(name).unwrap_or(0), ids.as_slice()),
+                }
+            },
+            _ => {
+                if let Some(opcode) = relational_op(name) {
+                    let ids = self.convert_args(ctx, args, &typ)?;
+                    let bool_type_id = self.bool_type_id(n)?;
+                    let cond = self.emit_op(ctx, opcode, bool_type_id, ids.as_slice())?;
+                    let int_type = match (expected, n) {
+                        (Some(expected), _) => expected.clone(),
+                        (None, Some(n)) => vector_type(&cmp_elem_type(kind), n).unwrap_or(IrType::Int),
+                        (None, None) => IrType::Int,
+                    };
+                    return self.bool_to_int(ctx, cond, &int_type);
+                }
+                let tuple_name = match name {
+                    "sincos" | "fract" | "modf" => Some(name),
+                    _ => strip_vector_suffix(name, &["frexp", "lgamma_r", "remquo"]),
+                };
+                if let Some(tuple_name) = tuple_name {
+                    return self.call_tuple_math_fun(ctx, tuple_name, args, expected);
+                }
+                let int_lanes_type = lanes_type(ScalarKind::Int(32, true), n).unwrap_or(IrType::Int);
+                match strip_vector_suffix(name, &["ldexp", "pown", "rootn", "ilogb", "nan_uint", "nan_ulong"]) {
+                    Some(base_name @ ("ldexp" | "pown" | "rootn")) => {
+                        match args {
+                            [x, k] => {
+                                let k2 = self.convert(ctx, k.clone(), &int_lanes_type)?;
+                                return self.ext_inst(ctx, &x.typ, opencl_std_fun(base_name).unwrap_or(0), &[x.id, k2.id]);
+                            },
+                            _ => return Err(BackendError::Internal(format!("call_math_fun: invalid number of arguments for {}", ident))),
+                        }
+                    },
+                    Some("ilogb") => {
+                        let ids = self.convert_args(ctx, args, &typ)?;
+                        return self.ext_inst(ctx, &int_lanes_type, opencl_std_fun("ilogb").unwrap_or(0), ids.as_slice());
+                    },
+                    Some(base_name) => {
+                        let ret_type = match expected {
+                            Some(expected) => expected.clone(),
+                            None => lanes_type(ScalarKind::Float(if base_name == "nan_uint" { 32 } else { 64 }), n).unwrap_or(IrType::Float),
+                        };
+                        let ids = self.convert_args(ctx, args, &typ)?;
+                        return self.ext_inst(ctx, &ret_type, opencl_std_fun("nan").unwrap_or(0), ids.as_slice());
+                    },
+                    None => (),
+                }
+                if ident.ends_with("_upsample") {
+                    match args {
+                        [hi, lo] => {
+                            let (hi_kind, hi_n) = match lanes_kind(&hi.typ) {
+                                Some(pair) => pair,
+                                None => return Err(BackendError::Internal(String::from("call_math_fun: invalid arguments for upsample"))),
+                            };
+                            let (bits, is_signed) = match hi_kind {
+                                ScalarKind::Int(bits, is_signed) => (bits, is_signed),
+                                ScalarKind::Float(_) => return Err(BackendError::Internal(String::from("call_math_fun: invalid arguments for upsample"))),
+                            };
+                            let lo_type = lanes_type(ScalarKind::Int(bits, false), hi_n).unwrap_or(IrType::Uint);
+                            let lo2 = self.convert(ctx, lo.clone(), &lo_type)?;
+                            let ret_type = match expected {
+                                Some(expected) => expected.clone(),
+                                None => lanes_type(ScalarKind::Int(bits * 2, is_signed), hi_n).unwrap_or(IrType::Long),
+                            };
+                            let inst_name = if is_signed { "s_upsample" } else { "u_upsample" };
+                            return self.ext_inst(ctx, &ret_type, opencl_std_fun(inst_name).unwrap_or(0), &[hi.id, lo2.id]);
+                        },
+                        _ => return Err(BackendError::Internal(String::from("call_math_fun: invalid number of arguments for upsample"))),
+                    }
+                }
+                let (inst, ret_type) = match kind {
+                    ScalarKind::Int(bits, is_signed) => {
+                        let prefixed_name = format!("{}_{}", if is_signed { "s" } else { "u" }, name);
+                        let inst = opencl_std_fun(prefixed_name.as_str()).or(opencl_std_fun(name));
+                        let ret_type = match name {
+                            "abs" | "abs_diff" => lanes_type(ScalarKind::Int(bits, false), n).unwrap_or(IrType::Uint),
+                            _ => typ.clone(),
+                        };
+                        (inst, ret_type)
+                    },
+                    ScalarKind::Float(_) => {
+                        let float_name = match name {
+                            "abs" => "fabs",
+                            "min" => "fmin",
+                            "max" => "fmax",
+                            "clamp" => "fclamp",
+                            _ => name,
+                        };
+                        (opencl_std_fun(float_name), typ.clone())
+                    },
+                };
+                match inst {
+                    Some(inst) => {
+                        let ids = self.convert_args(ctx, args, &typ)?;
+                        self.ext_inst(ctx, &ret_type, inst, ids.as_slice())
+                    },
+                    None => Err(BackendError::Internal(format!("call_math_fun: unsupported builtin function {}", ident))),
+                }
+            },
+        }
+    }
+
+    fn call_tuple_math_fun(&mut self, ctx: &mut SpirvFunContext<'_>, name: &str, args: &[SpirvValue], expected: Option<&IrType>) -> BackendResult<SpirvValue>
+    {
+        let ret_type = match expected {
+            Some(typ @ IrType::Struct(_)) => typ.clone(),
+            _ => return Err(BackendError::Internal(format!("call_tuple_math_fun: no tuple type for {}", name))),
+        };
+        let field_types = self.field_types(&ret_type)?;
+        let (value_type, out_type) = match field_types.as_slice() {
+            [(_, value_type), (_, out_type)] => (value_type.clone(), out_type.clone()),
+            _ => return Err(BackendError::Internal(format!("call_tuple_math_fun: invalid tuple type for {}", name))),
+        };
+        let mut ids = self.convert_args(ctx, args, &value_type)?;
+        let out_place = self.temp_var(ctx, &out_type)?;
+        ids.push(out_place.ptr);
+        let value = self.ext_inst(ctx, &value_type, opencl_std_fun(name).unwrap_or(0), ids.as_slice())?;
+        let out_value = self.load(ctx, &out_place)?;
+        self.composite(ctx, &ret_type, vec![value.id, out_value.id])
+    }
+
+    fn begin_fun(&mut self, id: u32, ret_type: &IrType, param_types: &[IrType], control: u32) -> BackendResult<(Vec<u32>, Vec<SpirvValue>)>
+    {
+        let ret_type_id = self.ir_type_id(ret_type)?;
+        let fun_type_id = self.fun_type_id(param_types, ret_type)?;
+        let mut header: Vec<u32> = Vec::new();
+        push_instr(&mut header, OP_FUNCTION, &[ret_type_id, id, control, fun_type_id]);
+        let mut params: Vec<SpirvValue> = Vec::new();
+        for param_type in param_types {
+            let param_type_id = self.ir_type_id(param_type)?;
+            let param_id = self.new_id();
+            push_instr(&mut header, OP_FUNCTION_PARAMETER, &[param_type_id, param_id]);
+            params.push(SpirvValue::new(param_id, param_type.clone()));
+        }
+        Ok((header, params))
+    }
+
+    fn end_fun(&mut self, header: Vec<u32>, mut ctx: SpirvFunContext<'_>) -> BackendResult<()>
+    {
+        self.emit_default_return(&mut ctx)?;
+        let label = self.new_id();
+        self.funs.extend(header);
+        push_instr(&mut self.funs, OP_LABEL, &[label]);
+        self.funs.extend(ctx.vars);
+        self.funs.extend(ctx.body);
+        push_instr(&mut self.funs, OP_FUNCTION_END, &[]);
+        Ok(())
+    }
+
+    fn store_elem(&mut self, ctx: &mut SpirvFunContext<'_>, ptr: &SpirvValue, idx: usize, id: u32) -> BackendResult<()>
+    {
+        let place = self.elem_place(ctx, ptr, idx)?;
+        self.emit_void_op(ctx, OP_STORE, &[place.ptr, id])
+    }
+
+    fn alloc_fun(&mut self, modifier: IrPtrModifier) -> BackendResult<u32>
+    {
+        let name = match modifier {
+            IrPtrModifier::Private => mangle_private_alloc_fun_name(),
+            IrPtrModifier::Local => mangle_local_alloc_fun_name(),
+            _ => mangle_global_alloc_fun_name(),
+        };
+        if let Some(id) = self.alloc_funs.get(&name) {
+            return Ok(*id);
+        }
+        let id = self.new_id();
+        self.add_name(id, name.as_str());
+        self.alloc_funs.insert(name, id);
+        let heap_type = ptr_type(modifier, IrType::Uint);
+        let ret_type = ptr_type(modifier, IrType::Uchar);
+        let mut ctx = SpirvFunContext::new();
+        ctx.ret_type = ret_type.clone();
+        let (header, params) = self.begin_fun(id, &ret_type, &[heap_type, IrType::Uint, IrType::Uint], FUNCTION_CONTROL_NONE)?;
+        let (heap, size, align) = (params[0].clone(), params[1].id, params[2].id);
+        let uint_type_id = self.ir_type_id(&IrType::Uint)?;
+        let bool_type_id = self.bool_type_id(None)?;
+        let one_id = self.const_uint(1)?;
+        let align_m1 = self.emit_op(&mut ctx, OP_I_SUB, uint_type_id, &[align, one_id])?;
+        let mask = self.emit_op(&mut ctx, OP_NOT, uint_type_id, &[align_m1])?;
+        let is_atomic = modifier != IrPtrModifier::Private;
+        let cap_place = self.elem_place(&mut ctx, &heap, HEAP_CAPACITY_INDEX)?;
+        let old = if is_atomic {
+            let scope_id = self.const_uint(if modifier == IrPtrModifier::Local { SCOPE_WORKGROUP } else { SCOPE_DEVICE })?;
+            let sem_id = self.const_uint(0)?;
+            let total = self.emit_op(&mut ctx, OP_I_ADD, uint_type_id, &[size, align_m1])?;
+            self.emit_op(&mut ctx, OP_ATOMIC_I_ADD, uint_type_id, &[heap.id, scope_id, sem_id, total])?
+        } else {
+            self.emit_op(&mut ctx, OP_LOAD, uint_type_id, &[heap.id])?
+        };
+        let cap = self.emit_op(&mut ctx, OP_LOAD, uint_type_id, &[cap_place.ptr])?;
+        let tmp_off = self.emit_op(&mut ctx, OP_I_ADD, uint_type_id, &[old, align_m1])?;
+        let off = self.emit_op(&mut ctx, OP_BITWISE_AND, uint_type_id, &[tmp_off, mask])?;
+        let cond1 = self.emit_op(&mut ctx, OP_U_GREATER_THAN, bool_type_id, &[old, cap])?;
+        let cond2 = self.emit_op(&mut ctx, OP_U_GREATER_THAN, bool_type_id, &[off, cap])?;
+        let rest = self.emit_op(&mut ctx, OP_I_SUB, uint_type_id, &[cap, off])?;
+        let cond3 = self.emit_op(&mut ctx, OP_U_GREATER_THAN, bool_type_id, &[size, rest])?;
+        let cond4 = self.emit_op(&mut ctx, OP_LOGICAL_OR, bool_type_id, &[cond1, cond2])?;
+        let cond = self.emit_op(&mut ctx, OP_LOGICAL_OR, bool_type_id, &[cond4, cond3])?;
+        let null_label = self.new_id();
+        let ok_label = self.new_id();
+        self.emit_terminator(&mut ctx, OP_BRANCH_CONDITIONAL, &[cond, null_label, ok_label]);
+        self.emit_label(&mut ctx, null_label);
+        self.emit_default_return(&mut ctx)?;
+        self.emit_label(&mut ctx, ok_label);
+        if !is_atomic {
+            let new_off = self.emit_op(&mut ctx, OP_I_ADD, uint_type_id, &[off, size])?;
+            self.emit_void_op(&mut ctx, OP_STORE, &[heap.id, new_off])?;
+        }
+        let ret_type_id = self.ir_type_id(&ret_type)?;
+        let bytes = self.emit_op(&mut ctx, OP_BITCAST, ret_type_id, &[heap.id])?;
+        let header_size_id = self.const_uint(HEAP_HEADER_SIZE as u32)?;
+        let tmp_idx = self.emit_op(&mut ctx, OP_I_ADD, uint_type_id, &[off, header_size_id])?;
+        let ulong_type_id = self.ir_type_id(&IrType::Ulong)?;
+        let idx = self.emit_op(&mut ctx, OP_U_CONVERT, ulong_type_id, &[tmp_idx])?;
+        let ptr = self.emit_op(&mut ctx, OP_PTR_ACCESS_CHAIN, ret_type_id, &[bytes, idx])?;
+        self.emit_terminator(&mut ctx, OP_RETURN_VALUE, &[ptr]);
+        self.end_fun(header, ctx)?;
+        Ok(id)
+    }
+
+    fn panic_funs(&mut self) -> BackendResult<(u32, u32)>
+    {
+        if let Some(pair) = self.panic_funs {
+            return Ok(pair);
+        }
+        let panic_fun = self.new_id();
+        let panic_call_fun = self.new_id();
+        self.add_name(panic_fun, mangle_panic_fun_name().as_str());
+        self.add_name(panic_call_fun, mangle_panic_call_fun_name().as_str());
+        self.panic_funs = Some((panic_fun, panic_call_fun));
+        let param_types = [ptr_type(IrPtrModifier::Global, IrType::Uint), ptr_type(IrPtrModifier::Private, IrType::Uint), IrType::Uint];
+        let uint_type_id = self.ir_type_id(&IrType::Uint)?;
+        let bool_type_id = self.bool_type_id(None)?;
+        let zero_id = self.const_uint(0)?;
+        let one_id = self.const_uint(1)?;
+        let two_id = self.const_uint(2)?;
+        // Function that records the first panic.
+        let mut ctx = SpirvFunContext::new();
+        let (header, params) = self.begin_fun(panic_fun, &IrType::Void, &param_types, FUNCTION_CONTROL_NONE)?;
+        let (pb, pf, id) = (params[0].clone(), params[1].id, params[2].id);
+        let scope_id = self.const_uint(SCOPE_DEVICE)?;
+        let old = self.emit_op(&mut ctx, OP_ATOMIC_COMPARE_EXCHANGE, uint_type_id, &[pb.id, scope_id, zero_id, zero_id, id, zero_id])?;
+        let cond = self.emit_op(&mut ctx, OP_I_EQUAL, bool_type_id, &[old, zero_id])?;
+        let first_label = self.new_id();
+        let other_label = self.new_id();
+        let end_label = self.new_id();
+        self.emit_terminator(&mut ctx, OP_BRANCH_CONDITIONAL, &[cond, first_label, other_label]);
+        self.emit_label(&mut ctx, first_label);
+        let global_id_var = self.builtin_var(BUILTIN_GLOBAL_INVOCATION_ID)?;
+        let global_id = self.load(&mut ctx, &Place::new(global_id_var, IrType::UlongN(3), IrPtrModifier::Private))?;
+        let ulong_type_id = self.ir_type_id(&IrType::Ulong)?;
+        for i in 0..3 {
+            let elem = self.emit_op(&mut ctx, OP_COMPOSITE_EXTRACT, ulong_type_id, &[global_id.id, i as u32])?;
+            let elem2 = self.emit_op(&mut ctx, OP_U_CONVERT, uint_type_id, &[elem])?;
+            self.store_elem(&mut ctx, &pb, PANIC_GLOBAL_ID_INDEX + i, elem2)?;
+        }
+        self.store_elem(&mut ctx, &pb, PANIC_CALL_COUNT_INDEX, zero_id)?;
+        self.emit_void_op(&mut ctx, OP_STORE, &[pf, two_id])?;
+        self.emit_branch(&mut ctx, end_label);
+        self.emit_label(&mut ctx, other_label);
+        self.emit_void_op(&mut ctx, OP_STORE, &[pf, one_id])?;
+        self.emit_branch(&mut ctx, end_label);
+        self.emit_label(&mut ctx, end_label);
+        self.end_fun(header, ctx)?;
+        // Function that records a call of function that panicked.
+        let mut ctx = SpirvFunContext::new();
+        let (header, params) = self.begin_fun(panic_call_fun, &IrType::Void, &param_types, FUNCTION_CONTROL_NONE)?;
+        let (pb, pf, id) = (params[0].clone(), params[1].id, params[2].id);
+        let flag = self.emit_op(&mut ctx, OP_LOAD, uint_type_id, &[pf])?;
+        let cond = self.emit_op(&mut ctx, OP_I_EQUAL, bool_type_id, &[flag, two_id])?;
+        let count_label = self.new_id();
+        let store_label = self.new_id();
+        let inc_label = self.new_id();
+        let end_label = self.new_id();
+        self.emit_terminator(&mut ctx, OP_BRANCH_CONDITIONAL, &[cond, count_label, end_label]);
+        self.emit_label(&mut ctx, count_label);
+        let count_place = self.elem_place(&mut ctx, &pb, PANIC_CALL_COUNT_INDEX)?;
+        let n = self.emit_op(&mut ctx, OP_LOAD, uint_type_id, &[count_place.ptr])?;
+        let max_id = self.const_uint(MAX_PANIC_CALL_COUNT as u32)?;
+        let cond2 = self.emit_op(&mut ctx, OP_U_LESS_THAN, bool_type_id, &[n, max_id])?;
+        self.emit_terminator(&mut ctx, OP_BRANCH_CONDITIONAL, &[cond2, store_label, inc_label]);
+        self.emit_label(&mut ctx, store_label);
+        let call_id_index_id = self.const_uint(PANIC_CALL_ID_INDEX as u32)?;
+        let idx = self.emit_op(&mut ctx, OP_I_ADD, uint_type_id, &[n, call_id_index_id])?;
+        let pb_type_id = self.ir_type_id(&pb.typ)?;
+        let ptr = self.emit_op(&mut ctx, OP_PTR_ACCESS_CHAIN, pb_type_id, &[pb.id, idx])?;
+        self.emit_void_op(&mut ctx, OP_STORE, &[ptr, id])?;
+        self.emit_branch(&mut ctx, inc_label);
+        self.emit_label(&mut ctx, inc_label);
+        let new_n = self.emit_op(&mut ctx, OP_I_ADD, uint_type_id, &[n, one_id])?;
+        self.emit_void_op(&mut ctx, OP_STORE, &[count_place.ptr, new_n])?;
+        self.emit_branch(&mut ctx, end_label);
+        self.emit_label(&mut ctx, end_label);
+        self.end_fun(header, ctx)?;
+        Ok((panic_fun, panic_call_fun))
+    }
+
+    fn builtin_var(&mut self, builtin: u32) -> BackendResult<u32>
+    {
+        if let Some(id) = self.builtin_vars.get(&builtin) {
+            return Ok(*id);
+        }
+        let key = if builtin == BUILTIN_WORK_DIM {
+            TypeKey::Int(32)
+        } else {
+            TypeKey::Vector(Box::new(TypeKey::Int(64)), 3)
+        };
+        let ptr_type_id = self.type_id(&TypeKey::Ptr(STORAGE_CLASS_INPUT, Box::new(key)))?;
+        let id = self.new_id();
+        push_instr(&mut self.globals, OP_VARIABLE, &[ptr_type_id, id, STORAGE_CLASS_INPUT]);
+        self.add_decoration(id, DECORATION_BUILTIN, &[builtin]);
+        self.builtin_vars.insert(builtin, id);
+        Ok(id)
+    }
+
+    fn heap_len_id(&mut self, spec_id: u32, default: usize) -> BackendResult<u32>
+    {
+        if let Some(id) = self.heap_len_ids.get(&spec_id) {
+            return Ok(*id);
+        }
+        let spec = self.spec_const(spec_id, default)?;
+        let uint_type_id = self.ir_type_id(&IrType::Uint)?;
+        let addend_id = self.const_uint((HEAP_HEADER_SIZE + 127) as u32)?;
+        let divisor_id = self.const_uint(128)?;
+        let sum = self.const_op(OP_I_ADD, uint_type_id, &[spec, addend_id])?;
+        let id = self.const_op(OP_U_DIV, uint_type_id, &[sum, divisor_id])?;
+        self.heap_len_ids.insert(spec_id, id);
+        Ok(id)
+    }
+
+    fn heap_array_key(&mut self, spec_id: u32, default: usize) -> BackendResult<TypeKey>
+    {
+        let len_id = self.heap_len_id(spec_id, default)?;
+        Ok(TypeKey::SpecArray(Box::new(TypeKey::Vector(Box::new(TypeKey::Int(64)), 16)), len_id))
+    }
+
+    fn emit_kernel_heaps(&mut self, ctx: &mut SpirvFunContext<'_>, private_heap_flag: IrPrivateHeapFlag, local_heap_flag: IrLocalHeapFlag) -> BackendResult<()>
+    {
+        let zero_id = self.const_uint(0)?;
+        if private_heap_flag == IrPrivateHeapFlag::Heap {
+            let array_key = self.heap_array_key(PRIVATE_HEAP_SIZE_SPEC_ID, self.private_heap_size)?;
+            let array_ptr_type_id = self.type_id(&TypeKey::Ptr(STORAGE_CLASS_FUNCTION, Box::new(array_key)))?;
+            let var_id = self.new_id();
+            push_instr(&mut ctx.vars, OP_VARIABLE, &[array_ptr_type_id, var_id, STORAGE_CLASS_FUNCTION]);
+            let heap_type = ptr_type(IrPtrModifier::Private, IrType::Uint);
+            let heap_type_id = self.ir_type_id(&heap_type)?;
+            let heap_id = self.emit_op(ctx, OP_BITCAST, heap_type_id, &[var_id])?;
+            let heap = SpirvValue::new(heap_id, heap_type);
+            let size_id = self.spec_const(PRIVATE_HEAP_SIZE_SPEC_ID, self.private_heap_size)?;
+            self.store_elem(ctx, &heap, HEAP_OFFSET_INDEX, zero_id)?;
+            self.store_elem(ctx, &heap, HEAP_CAPACITY_INDEX, size_id)?;
+            ctx.heaps.0 = Some(heap);
+        }
+        if local_heap_flag == IrLocalHeapFlag::Heap {
+            let var_id = match self.local_heap_var {
+                Some(id) => id,
+                None => {
+                    let array_key = self.heap_array_key(LOCAL_HEAP_SIZE_SPEC_ID, self.local_heap_size)?;
+                    let array_ptr_type_id = self.type_id(&TypeKey::Ptr(STORAGE_CLASS_WORKGROUP, Box::new(array_key)))?;
+                    let id = self.new_id();
+                    push_instr(&mut self.globals, OP_VARIABLE, &[array_ptr_type_id, id, STORAGE_CLASS_WORKGROUP]);
+                    self.local_heap_var = Some(id);
+                    id
+                },
+            };
+            let heap_type = ptr_type(IrPtrModifier::Local, IrType::Uint);
+            let heap_type_id = self.ir_type_id(&heap_type)?;
+            let heap_id = self.emit_op(ctx, OP_BITCAST, heap_type_id, &[var_id])?;
+            let heap = SpirvValue::new(heap_id, heap_type);
+            let local_id_var = self.builtin_var(BUILTIN_LOCAL_INVOCATION_ID)?;
+            let local_id = self.load(ctx, &Place::new(local_id_var, IrType::UlongN(3), IrPtrModifier::Private))?;
+            let null_id = self.const_null(&IrType::UlongN(3))?;
+            let bool_vector_type_id = self.bool_type_id(Some(3))?;
+            let bool_type_id = self.bool_type_id(None)?;
+            let conds = self.emit_op(ctx, OP_I_EQUAL, bool_vector_type_id, &[local_id.id, null_id])?;
+            let cond = self.emit_op(ctx, OP_ALL, bool_type_id, &[conds])?;
+            let init_label = self.new_id();
+            let end_label = self.new_id();
+            self.emit_terminator(ctx, OP_BRANCH_CONDITIONAL, &[cond, init_label, end_label]);
+            self.emit_label(ctx, init_label);
+            let size_id = self.spec_const(LOCAL_HEAP_SIZE_SPEC_ID, self.local_heap_size)?;
+            self.store_elem(ctx, &heap, HEAP_OFFSET_INDEX, zero_id)?;
+            self.store_elem(ctx, &heap, HEAP_CAPACITY_INDEX, size_id)?;
+            self.emit_branch(ctx, end_label);
+            self.emit_label(ctx, end_label);
+            let scope_id = self.const_uint(SCOPE_WORKGROUP)?;
+            let sem_id = self.const_uint(MEMORY_SEMANTICS_ACQUIRE_RELEASE | MEMORY_SEMANTICS_WORKGROUP_MEMORY)?;
+            self.emit_void_op(ctx, OP_CONTROL_BARRIER, &[scope_id, scope_id, sem_id])?;
+            ctx.heaps.1 = Some(heap);
+        }
+        Ok(())
+    }
+
+    fn set_hidden_params(&self, ctx: &mut SpirvFunContext<'_>, flags: (IrPrivateHeapFlag, IrLocalHeapFlag, IrGlobalHeapFlag, IrPanicFlag), is_kernel: bool, params: &[SpirvValue])
+    {
+        for ((param, _), value) in hidden_params(flags, is_kernel).into_iter().zip(params.iter()) {
+            match param {
+                HiddenParam::PrivateHeap => ctx.heaps.0 = Some(value.clone()),
+                HiddenParam::LocalHeap => ctx.heaps.1 = Some(value.clone()),
+                HiddenParam::GlobalHeap => ctx.heaps.2 = Some(value.clone()),
+                HiddenParam::PanicBuffer => ctx.panic_buffer = Some(value.clone()),
+                HiddenParam::PanicFlag => ctx.panic_flag = Some(value.clone()),
+            }
+        }
+    }
+
+    fn closure_field(&mut self, ctx: &mut SpirvFunContext<'_>, fun_place: &Place, closure_type: &Option<Box<IrType>>) -> BackendResult<Option<Place>>
+    {
+        match closure_type {
+            Some(closure_type) => {
+                for (idx, field_type) in self.field_types(&fun_place.typ)? {
+                    if field_type == **closure_type {
+                        return Ok(Some(self.field_place(ctx, fun_place, idx)?));
+                    }
+                }
+                Err(BackendError::Internal(String::from("closure_field: no field for closure")))
+            },
+            None => Ok(None),
+        }
+    }
+
+    fn emit_fun(&mut self, ident: &String, fun: &IrFun, panic_table: &mut PanicTable) -> BackendResult<()>
+    {
+        let sig = self.fun_sig(ident)?;
+        let flags = sig.flags;
+        let mut ctx = SpirvFunContext::new();
+        ctx.can_panic = flags.3 == IrPanicFlag::Panic;
+        ctx.heap_flags = (flags.0, flags.1, flags.2);
+        ctx.panic_table = Some(panic_table);
+        match fun {
+            IrFun::Fun(modifier, arg_types, ret_type, block, _, _, _, _) => {
+                let is_kernel = *modifier == IrFunModifier::Kernel;
+                let control = if *modifier == IrFunModifier::Inline { FUNCTION_CONTROL_INLINE } else { FUNCTION_CONTROL_NONE };
+                ctx.ret_type = (**ret_type).clone();
+                ctx.is_kernel = is_kernel;
+                let (header, params) = self.begin_fun(sig.id, ret_type, sig.param_types.as_slice(), control)?;
+                for (arg_type, param) in arg_types.iter().zip(params.iter()) {
+                    let place = self.temp_var(&mut ctx, arg_type)?;
+                    self.store(&mut ctx, &place, param.clone())?;
+                    ctx.local_vars.push(place);
+                }
+                self.set_hidden_params(&mut ctx, flags, is_kernel, &params[arg_types.len()..]);
+                if is_kernel {
+                    self.emit_kernel_heaps(&mut ctx, flags.0, flags.1)?;
+                    if ctx.can_panic {
+                        let place = self.temp_var(&mut ctx, &IrType::Uint)?;
+                        let zero_id = self.const_uint(0)?;
+                        self.emit_void_op(&mut ctx, OP_STORE, &[place.ptr, zero_id])?;
+                        ctx.panic_flag = Some(place.ptr_value());
+                    }
+                    self.entry_points.push((sig.id, ident.clone()));
+                }
+                self.emit_block(&mut ctx, block)?;
+                self.end_fun(header, ctx)
+            },
+            IrFun::Caller(fun_type, arg_types, ret_type, caller_funs, _, _, _, _) => {
+                ctx.ret_type = (**ret_type).clone();
+                let (header, params) = self.begin_fun(sig.id, ret_type, sig.param_types.as_slice(), FUNCTION_CONTROL_NONE)?;
+                for (i, param) in params.iter().take(arg_types.len() + 1).enumerate() {
+                    let param_type = if i == 0 { (**fun_type).clone() } else { (*arg_types[i - 1]).clone() };
+                    let place = self.temp_var(&mut ctx, &param_type)?;
+                    self.store(&mut ctx, &place, param.clone())?;
+                    ctx.caller_args.push(place);
+                }
+                self.set_hidden_params(&mut ctx, flags, false, &params[(arg_types.len() + 1)..]);
+                let fun_place = ctx.caller_arg(0)?;
+                let index_place = self.field_place(&mut ctx, &fun_place, 0)?;
+                let index_value = self.load(&mut ctx, &index_place)?;
+                let index = self.convert(&mut ctx, index_value, &IrType::Uint)?;
+                let end_label = self.new_id();
+                let mut operands = vec![index.id, end_label];
+                let mut labels: Vec<u32> = Vec::new();
+                for idx in caller_funs.funs().keys() {
+                    let label = self.new_id();
+                    operands.push(*idx as u32);
+                    operands.push(label);
+                    labels.push(label);
+                }
+                self.emit_terminator(&mut ctx, OP_SWITCH, operands.as_slice());
+                for ((_, caller_fun), label) in caller_funs.funs().iter().zip(labels) {
+                    self.emit_label(&mut ctx, label);
+                    match caller_fun {
+                        IrCallerFun::Fun(fun_ident) => {
+                            let callee_sig = self.fun_sig(fun_ident)?;
+                            let mut ids = vec![callee_sig.id];
+                            for (i, param_type) in callee_sig.param_types.iter().take(arg_types.len()).enumerate() {
+                                let place = ctx.caller_arg(i + 1)?;
+                                let value = self.load(&mut ctx, &place)?;
+                                ids.push(self.convert(&mut ctx, value, param_type)?.id);
+                            }
+                            ids.extend(self.hidden_args(&ctx, fun_ident, callee_sig.flags)?);
+                            let ret_type_id = self.ir_type_id(&callee_sig.ret_type)?;
+                            let id = self.emit_op(&mut ctx, OP_FUNCTION_CALL, ret_type_id, ids.as_slice())?;
+                            if callee_sig.ret_type == IrType::Void {
+                                self.emit_return(&mut ctx, None)?;
+                            } else {
+                                self.emit_return(&mut ctx, Some(SpirvValue::new(id, callee_sig.ret_type)))?;
+                            }
+                        },
+                        IrCallerFun::Lambda(private_closure_type, local_closure_type, global_closure_type, start_idx, block) => {
+                            ctx.private_closure = self.closure_field(&mut ctx, &fun_place, private_closure_type)?;
+                            ctx.local_closure = self.closure_field(&mut ctx, &fun_place, local_closure_type)?;
+                            ctx.global_closure = self.closure_field(&mut ctx, &fun_place, global_closure_type)?;
+                            ctx.local_vars = vec![Place::new(0, IrType::Void, IrPtrModifier::Private); *start_idx];
+                            for (i, arg_type) in arg_types.iter().enumerate() {
+                                let arg_place = ctx.caller_arg(i + 1)?;
+                                let value = self.load(&mut ctx, &arg_place)?;
+                                let place = self.temp_var(&mut ctx, arg_type)?;
+                                self.store(&mut ctx, &place, value)?;
+                                ctx.local_vars.push(place);
+                            }
+                            self.emit_block(&mut ctx, block)?;
+                            self.emit_default_return(&mut ctx)?;
+                            ctx.local_vars.clear();
+                            ctx.private_closure = None;
+                            ctx.local_closure = None;
+                            ctx.global_closure = None;
+                        },
+                        IrCallerFun::InlineFun(_, _) => return Err(BackendError::Internal(format!("emit_fun: inline functions of caller {} are unsupported", ident))),
+                    }
+                }
+                self.emit_label(&mut ctx, end_label);
+                self.end_fun(header, ctx)
+            },
+        }
+    }
+
+    fn emit_tree(&mut self, panic_table: &mut PanicTable) -> BackendResult<Vec<u32>>
+    {
+        self.ext_inst_set = self.new_id();
+        self.capabilities.insert(CAPABILITY_ADDRESSES);
+        self.capabilities.insert(CAPABILITY_KERNEL);
+        self.capabilities.insert(CAPABILITY_INT64);
+        let tree = self.tree;
+        for def in tree.defs() {
+            if let IrDef::Var(ident, var) = &**def {
+                match &*var.borrow() {
+                    IrVar::Fun(fun) => self.emit_fun(ident, fun, &mut *panic_table)?,
+                    _ => {
+                        self.global_place(ident)?;
+                    },
+                }
+            }
+        }
+        self.resolve_forward_ptrs()?;
+        if !self.forward_ptrs.is_empty() {
+            return Err(BackendError::Internal(String::from("emit_tree: unresolved forward pointers")));
+        }
+        let mut words = vec![SPIRV_MAGIC_NUMBER, SPIRV_VERSION, 0, self.bound, 0];
+        for capability in &self.capabilities {
+            push_instr(&mut words, OP_CAPABILITY, &[*capability]);
+        }
+        let mut operands = vec![self.ext_inst_set];
+        operands.extend(string_words(OPENCL_STD_NAME));
+        push_instr(&mut words, OP_EXT_INST_IMPORT, operands.as_slice());
+        push_instr(&mut words, OP_MEMORY_MODEL, &[ADDRESSING_MODEL_PHYSICAL64, MEMORY_MODEL_OPENCL]);
+        for (id, name) in &self.entry_points {
+            let mut operands = vec![EXECUTION_MODEL_KERNEL, *id];
+            operands.extend(string_words(name.as_str()));
+            operands.extend(self.builtin_vars.values());
+            push_instr(&mut words, OP_ENTRY_POINT, operands.as_slice());
+        }
+        push_instr(&mut words, OP_SOURCE, &[SOURCE_LANGUAGE_OPENCL_C, SOURCE_VERSION]);
+        words.extend_from_slice(self.names.as_slice());
+        words.extend_from_slice(self.decorations.as_slice());
+        words.extend_from_slice(self.globals.as_slice());
+        words.extend_from_slice(self.funs.as_slice());
+        Ok(words)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct SpirvEmitter
+{
+    private_heap_size: usize,
+    local_heap_size: usize,
+}
+
+impl SpirvEmitter
+{
+    pub fn new() -> Self
+    {
+        SpirvEmitter {
+            private_heap_size: DEFAULT_PRIVATE_HEAP_SIZE,
+            local_heap_size: DEFAULT_LOCAL_HEAP_SIZE,
+        }
+    }
+
+    pub fn private_heap_size(&self) -> usize
+    { self.private_heap_size }
+
+    pub fn set_private_heap_size(&mut self, size: usize)
+    { self.private_heap_size = size; }
+
+    pub fn local_heap_size(&self) -> usize
+    { self.local_heap_size }
+
+    pub fn set_local_heap_size(&mut self, size: usize)
+    { self.local_heap_size = size; }
+
+    pub fn emit(&self, tree: &IrTree) -> BackendResult<Vec<u32>>
+    {
+        let (words, _) = self.emit_with_panic_table(tree)?;
+        Ok(words)
+    }
+
+    pub fn emit_with_panic_table(&self, tree: &IrTree) -> BackendResult<(Vec<u32>, PanicTable)>
+    {
+        let mut panic_table = PanicTable::new();
+        let mut module = SpirvModule::new(tree, self.private_heap_size, self.local_heap_size);
+        let words = module.emit_tree(&mut panic_table)?;
+        Ok((words, panic_table))
+    }
+}
+
+impl Default for SpirvEmitter
+{
+    fn default() -> Self
+    { Self::new() }
+}
+
+pub fn emit_ir_tree_to_spirv(tree: &IrTree) -> BackendResult<Vec<u32>>
+{
+    let emitter = SpirvEmitter::new();
+    emitter.emit(tree)
+}
+
+pub fn emit_ir_tree_to_spirv_with_panic_table(tree: &IrTree) -> BackendResult<(Vec<u32>, PanicTable)>
+{
+    let emitter = SpirvEmitter::new();
+    emitter.emit_with_panic_table(tree)
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,432 @@
+//
+// Copyright (c) 2025 Łukasz Szpakowski
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+use std::fmt::Write;
+use crate::backend::error::*;
+use super::*;
+
+// Operand kinds of instruction layouts:
+// `t` - result type, `r` - result, `i` - identifier, `l` - literal number, `s` - literal
+// string, `e` - extended instruction. Operands after `*` are repeated to the end of
+// instruction.
+const OPCODES: &[(u32, &str, &str)] = &[
+    (OP_SOURCE, "OpSource", "ll"),
+    (OP_NAME, "OpName", "is"),
+    (OP_EXT_INST_IMPORT, "OpExtInstImport", "rs"),
+    (OP_EXT_INST, "OpExtInst", "trie*i"),
+    (OP_MEMORY_MODEL, "OpMemoryModel", "ll"),
+    (OP_ENTRY_POINT, "OpEntryPoint", "lis*i"),
+    (OP_CAPABILITY, "OpCapability", "l"),
+    (OP_TYPE_VOID, "OpTypeVoid", "r"),
+    (OP_TYPE_BOOL, "OpTypeBool", "r"),
+    (OP_TYPE_INT, "OpTypeInt", "rll"),
+    (OP_TYPE_FLOAT, "OpTypeFloat", "rl"),
+    (OP_TYPE_VECTOR, "OpTypeVector", "ril"),
+    (OP_TYPE_ARRAY, "OpTypeArray", "rii"),
+    (OP_TYPE_STRUCT, "OpTypeStruct", "r*i"),
+    (OP_TYPE_POINTER, "OpTypePointer", "rli"),
+    (OP_TYPE_FUNCTION, "OpTypeFunction", "ri*i"),
+    (OP_TYPE_FORWARD_POINTER, "OpTypeForwardPointer", "il"),
+    (OP_CONSTANT, "OpConstant", "tr*l"),
+    (OP_CONSTANT_COMPOSITE, "OpConstantComposite", "tr*i"),
+    (OP_CONSTANT_NULL, "OpConstantNull", "tr"),
+    (OP_SPEC_CONSTANT, "OpSpecConstant", "tr*l"),
+    (OP_SPEC_CONSTANT_COMPOSITE, "OpSpecConstantComposite", "tr*i"),
+    (OP_SPEC_CONSTANT_OP, "OpSpecConstantOp", "trl*i"),
+    (OP_FUNCTION, "OpFunction", "trli"),
+    (OP_FUNCTION_PARAMETER, "OpFunctionParameter", "tr"),
+    (OP_FUNCTION_END, "OpFunctionEnd", ""),
+    (OP_FUNCTION_CALL, "OpFunctionCall", "tr*i"),
+    (OP_VARIABLE, "OpVariable", "trl*i"),
+    (OP_LOAD, "OpLoad", "tri*l"),
+    (OP_STORE, "OpStore", "ii*l"),
+    (OP_ACCESS_CHAIN, "OpAccessChain", "tr*i"),
+    (OP_IN_BOUNDS_ACCESS_CHAIN, "OpInBoundsAccessChain", "tr*i"),
+    (OP_PTR_ACCESS_CHAIN, "OpPtrAccessChain", "tr*i"),
+    (OP_DECORATE, "OpDecorate", "il*l"),
+    (OP_VECTOR_EXTRACT_DYNAMIC, "OpVectorExtractDynamic", "tr*i"),
+    (OP_COMPOSITE_CONSTRUCT, "OpCompositeConstruct", "tr*i"),
+    (OP_COMPOSITE_EXTRACT, "OpCompositeExtract", "tri*l"),
+    (OP_CONVERT_F_TO_U, "OpConvertFToU", "tr*i"),
+    (OP_CONVERT_F_TO_S, "OpConvertFToS", "tr*i"),
+    (OP_CONVERT_S_TO_F, "OpConvertSToF", "tr*i"),
+    (OP_CONVERT_U_TO_F, "OpConvertUToF", "tr*i"),
+    (OP_U_CONVERT, "OpUConvert", "tr*i"),
+    (OP_S_CONVERT, "OpSConvert", "tr*i"),
+    (OP_F_CONVERT, "OpFConvert", "tr*i"),
+    (OP_CONVERT_PTR_TO_U, "OpConvertPtrToU", "tr*i"),
+    (OP_CONVERT_U_TO_PTR, "OpConvertUToPtr", "tr*i"),
+    (OP_PTR_CAST_TO_GENERIC, "OpPtrCastToGeneric", "tr*i"),
+    (OP_GENERIC_CAST_TO_PTR, "OpGenericCastToPtr", "tr*i"),
+    (OP_BITCAST, "OpBitcast", "tr*i"),
+    (OP_S_NEGATE, "OpSNegate", "tr*i"),
+    (OP_F_NEGATE, "OpFNegate", "tr*i"),
+    (OP_I_ADD, "OpIAdd", "tr*i"),
+    (OP_F_ADD, "OpFAdd", "tr*i"),
+    (OP_I_SUB, "OpISub", "tr*i"),
+    (OP_F_SUB, "OpFSub", "tr*i"),
+    (OP_I_MUL, "OpIMul", "tr*i"),
+    (OP_F_MUL, "OpFMul", "tr*i"),
+    (OP_U_DIV, "OpUDiv", "tr*i"),
+    (OP_S_DIV, "OpSDiv", "tr*i"),
+    (OP_F_DIV, "OpFDiv", "tr*i"),
+    (OP_U_MOD, "OpUMod", "tr*i"),
+    (OP_S_REM, "OpSRem", "tr*i"),
+    (OP_F_REM, "OpFRem", "tr*i"),
+    (OP_DOT, "OpDot", "tr*i"),
+    (OP_ANY, "OpAny", "tr*i"),
+    (OP_ALL, "OpAll", "tr*i"),
+    (OP_IS_NAN, "OpIsNan", "tr*i"),
+    (OP_IS_INF, "OpIsInf", "tr*i"),
+    (OP_IS_FINITE, "OpIsFinite", "tr*i"),
+    (OP_IS_NORMAL, "OpIsNormal", "tr*i"),
+    (OP_SIGN_BIT_SET, "OpSignBitSet", "tr*i"),
+    (OP_ORDERED, "OpOrdered", "tr*i"),
+    (OP_UNORDERED, "OpUnordered", "tr*i"),
+    (OP_LOGICAL_OR, "OpLogicalOr", "tr*i"),
+    (OP_LOGICAL_AND, "OpLogicalAnd", "tr*i"),
+    (OP_SELECT, "OpSelect", "tr*i"),
+    (OP_I_EQUAL, "OpIEqual", "tr*i"),
+    (OP_I_NOT_EQUAL, "OpINotEqual", "tr*i"),
+    (OP_U_GREATER_THAN, "OpUGreaterThan", "tr*i"),
+    (OP_S_GREATER_THAN, "OpSGreaterThan", "tr*i"),
+    (OP_U_GREATER_THAN_EQUAL, "OpUGreaterThanEqual", "tr*i"),
+    (OP_S_GREATER_THAN_EQUAL, "OpSGreaterThanEqual", "tr*i"),
+    (OP_U_LESS_THAN, "OpULessThan", "tr*i"),
+    (OP_S_LESS_THAN, "OpSLessThan", "tr*i"),
+    (OP_U_LESS_THAN_EQUAL, "OpULessThanEqual", "tr*i"),
+    (OP_S_LESS_THAN_EQUAL, "OpSLessThanEqual", "tr*i"),
+    (OP_F_ORD_EQUAL, "OpFOrdEqual", "tr*i"),
+    (OP_F_ORD_NOT_EQUAL, "OpFOrdNotEqual", "tr*i"),
+    (OP_F_UNORD_NOT_EQUAL, "OpFUnordNotEqual", "tr*i"),
+    (OP_F_ORD_LESS_THAN, "OpFOrdLessThan", "tr*i"),
+    (OP_F_ORD_GREATER_THAN, "OpFOrdGreaterThan", "tr*i"),
+    (OP_F_ORD_LESS_THAN_EQUAL, "OpFOrdLessThanEqual", "tr*i"),
+    (OP_F_ORD_GREATER_THAN_EQUAL, "OpFOrdGreaterThanEqual", "tr*i"),
+    (OP_SHIFT_RIGHT_LOGICAL, "OpShiftRightLogical", "tr*i"),
+    (OP_SHIFT_RIGHT_ARITHMETIC, "OpShiftRightArithmetic", "tr*i"),
+    (OP_SHIFT_LEFT_LOGICAL, "OpShiftLeftLogical", "tr*i"),
+    (OP_BITWISE_OR, "OpBitwiseOr", "tr*i"),
+    (OP_BITWISE_XOR, "OpBitwiseXor", "tr*i"),
+    (OP_BITWISE_AND, "OpBitwiseAnd", "tr*i"),
+    (OP_NOT, "OpNot", "tr*i"),
+    (OP_CONTROL_BARRIER, "OpControlBarrier", "iii"),
+    (OP_MEMORY_BARRIER, "OpMemoryBarrier", "ii"),
+    (OP_ATOMIC_COMPARE_EXCHANGE, "OpAtomicCompareExchange", "tr*i"),
+    (OP_ATOMIC_I_ADD, "OpAtomicIAdd", "tr*i"),
+    (OP_LABEL, "OpLabel", "r"),
+    (OP_BRANCH, "OpBranch", "i"),
+    (OP_BRANCH_CONDITIONAL, "OpBranchConditional", "iii*l"),
+    (OP_SWITCH, "OpSwitch", "ii*li"),
+    (OP_RETURN, "OpReturn", ""),
+    (OP_RETURN_VALUE, "OpReturnValue", "i"),
+
+    (OP_ATOMIC_COMPARE_EXCHANGE, "OpAtomicCompareExchange", "triiiiii"),
+    (OP_ATOMIC_I_ADD, "OpAtomicIAdd", "triiii"),
+];
+
+fn opcode_by_number(opcode: u32) -> Option<(&'static str, &'static str)>
+{ OPCODES.iter().find(|t| t.0 == opcode).map(|t| (t.1, t.2)) }
+
+fn opcode_by_name(name: &str) -> Option<(u32, &'static str)>
+{ OPCODES.iter().find(|t| t.1 == name).map(|t| (t.0, t.2)) }
+
+fn layout_kind(layout: &str, idx: usize) -> Option<char>
+{
+    let (fixed, repeated) = match layout.find('*') {
+        Some(i) => (&layout[..i], &layout[(i + 1)..]),
+        None => (layout, ""),
+    };
+    if idx < fixed.len() {
+        fixed.chars().nth(idx)
+    } else if !repeated.is_empty() {
+        repeated.chars().nth((idx - fixed.len()) % repeated.len())
+    } else {
+        None
+    }
+}
+
+fn decode_string(words: &[u32]) -> Option<(String, usize)>
+{
+    let mut bytes: Vec<u8> = Vec::new();
+    for (i, word) in words.iter().enumerate() {
+        for b in word.to_le_bytes().iter() {
+            if *b == 0 {
+                return String::from_utf8(bytes).ok().map(|s| (s, i + 1));
+            }
+            bytes.push(*b);
+        }
+    }
+    None
+}
+
+fn escape_string(s: &str) -> String
+{
+    let mut t = String::new();
+    for c in s.chars() {
+        match c {
+            '"' | '\\' => {
+                t.push('\\');
+                t.push(c);
+            },
+            _ => t.push(c),
+        }
+    }
+    t
+}
+
+pub fn disassemble(words: &[u32]) -> BackendResult<String>
+{
+    if words.len() < 5 || words[0] != SPIRV_MAGIC_NUMBER {
+        return Err(BackendError::Internal(String::from("disassemble: invalid header")));
+    }
+    let mut s = String::new();
+    writeln!(s, "; SPIR-V").unwrap();
+    writeln!(s, "; Version: {}.{}", (words[1] >> 16) & 0xff, (words[1] >> 8) & 0xff).unwrap();
+    writeln!(s, "; Generator: {}", words[2]).unwrap();
+    writeln!(s, "; Bound: {}", words[3]).unwrap();
+    writeln!(s, "; Schema: {}", words[4]).unwrap();
+    let mut i = 5;
+    while i < words.len() {
+        let count = (words[i] >> 16) as usize;
+        let opcode = words[i] & 0xffff;
+        if count == 0 || i + count > words.len() {
+            return Err(BackendError::Internal(format!("disassemble: invalid word count at word {}", i)));
+        }
+        let (name, layout) = match opcode_by_number(opcode) {
+            Some(pair) => pair,
+            None => return Err(BackendError::Internal(format!("disassemble: unknown opcode {}", opcode))),
+        };
+        let operands = &words[(i + 1)..(i + count)];
+        let mut result: Option<u32> = None;
+        let mut args: Vec<String> = Vec::new();
+        let mut j = 0;
+        let mut k = 0;
+        while j < operands.len() {
+            match layout_kind(layout, k) {
+                Some('r') => {
+                    result = Some(operands[j]);
+                    j += 1;
+                },
+                Some('t') | Some('i') => {
+                    args.push(format!("%{}", operands[j]));
+                    j += 1;
+                },
+                Some('s') => {
+                    match decode_string(&operands[j..]) {
+                        Some((t, n)) => {
+                            args.push(format!("\"{}\"", escape_string(t.as_str())));
+                            j += n;
+                        },
+                        None => return Err(BackendError::Internal(format!("disassemble: invalid string at word {}", i + 1 + j))),
+                    }
+                },
+                Some('e') => {
+                    match opencl_std_fun_name(operands[j]) {
+                        Some(fun_name) => args.push(String::from(fun_name)),
+                        None => args.push(format!("{}", operands[j])),
+                    }
+                    j += 1;
+                },
+                Some(_) => {
+                    args.push(format!("{}", operands[j]));
+                    j += 1;
+                },
+                None => return Err(BackendError::Internal(format!("disassemble: too many operands of {} at word {}", name, i))),
+            }
+            k += 1;
+        }
+        match result {
+            Some(result) => write!(s, "%{} = {}", result, name).unwrap(),
+            None => write!(s, "{}", name).unwrap(),
+        }
+        for arg in &args {
+            write!(s, " {}", arg).unwrap();
+        }
+        writeln!(s).unwrap();
+        i += count;
+    }
+    Ok(s)
+}
+
+fn tokenize(line: &str, line_no: usize) -> BackendResult<Vec<String>>
+{
+    let mut tokens: Vec<String> = Vec::new();
+    let mut chars = line.chars().peekable();
+    loop {
+        match chars.peek() {
+            Some(c) if c.is_whitespace() => {
+                chars.next();
+            },
+            Some(';') | None => break,
+            Some('"') => {
+                let mut t = String::from("\"");
+                chars.next();
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') => {
+                            match chars.next() {
+                                Some(c) => t.push(c),
+                                None => return Err(BackendError::Internal(format!("assemble: unterminated string in line {}", line_no))),
+                            }
+                        },
+                        Some(c) => t.push(c),
+                        None => return Err(BackendError::Internal(format!("assemble: unterminated string in line {}", line_no))),
+                    }
+                }
+                tokens.push(t);
+            },
+            Some(_) => {
+                let mut t = String::new();
+                loop {
+                    match chars.peek() {
+                        Some(c) if !c.is_whitespace() && *c != ';' => {
+                            t.push(*c);
+                            chars.next();
+                        },
+                        _ => break,
+                    }
+                }
+                tokens.push(t);
+            },
+        }
+    }
+    Ok(tokens)
+}
+
+fn parse_number(token: &str, line_no: usize) -> BackendResult<u32>
+{
+    match token.parse::<u32>() {
+        Ok(n) => Ok(n),
+        Err(_) => Err(BackendError::Internal(format!("assemble: invalid number {} in line {}", token, line_no))),
+    }
+}
+
+fn parse_id(token: &str, line_no: usize) -> BackendResult<u32>
+{
+    match token.strip_prefix('%') {
+        Some(token2) => parse_number(token2, line_no),
+        None => Err(BackendError::Internal(format!("assemble: invalid identifier {} in line {}", token, line_no))),
+    }
+}
+
+fn parse_header(line: &str, header: &mut [u32]) -> BackendResult<()>
+{
+    let (key, value) = match line.trim_start_matches(';').trim().find(':') {
+        Some(i) => {
+            let t = line.trim_start_matches(';').trim();
+            (&t[..i], t[(i + 1)..].trim())
+        },
+        None => return Ok(()),
+    };
+    let number = |s: &str| -> BackendResult<u32> {
+        match s.parse::<u32>() {
+            Ok(n) => Ok(n),
+            Err(_) => Err(BackendError::Internal(format!("assemble: invalid header value {}", s))),
+        }
+    };
+    match key {
+        "Version" => {
+            let mut iter = value.split('.');
+            let major = number(iter.next().unwrap_or(""))?;
+            let minor = number(iter.next().unwrap_or("0"))?;
+            header[1] = (major << 16) | (minor << 8);
+        },
+        "Generator" => header[2] = number(value)?,
+        "Bound" => header[3] = number(value)?,
+        "Schema" => header[4] = number(value)?,
+        _ => (),
+    }
+    Ok(())
+}
+
+pub fn assemble(s: &str) -> BackendResult<Vec<u32>>
+{
+    let mut header = [SPIRV_MAGIC_NUMBER, SPIRV_VERSION, 0, 0, 0];
+    let mut has_bound = false;
+    let mut max_id = 0;
+    let mut body: Vec<u32> = Vec::new();
+    for (line_idx, line) in s.lines().enumerate() {
+        let line_no = line_idx + 1;
+        if line.trim_start().starts_with(';') {
+            if line.contains("Bound:") {
+                has_bound = true;
+            }
+            parse_header(line.trim_start(), &mut header)?;
+            continue;
+        }
+        let tokens = tokenize(line, line_no)?;
+        if tokens.is_empty() {
+            continue;
+        }
+        let (result, rest) = if tokens.len() >= 2 && tokens[1] == "=" {
+            (Some(parse_id(tokens[0].as_str(), line_no)?), &tokens[2..])
+        } else {
+            (None, &tokens[..])
+        };
+        let (opcode, layout) = match rest.first() {
+            Some(name) => {
+                match opcode_by_name(name.as_str()) {
+                    Some(pair) => pair,
+                    None => return Err(BackendError::Internal(format!("assemble: unknown instruction {} in line {}", name, line_no))),
+                }
+            },
+            None => return Err(BackendError::Internal(format!("assemble: no instruction in line {}", line_no))),
+        };
+        let mut operands: Vec<u32> = Vec::new();
+        let mut args = rest[1..].iter();
+        let mut k = 0;
+        loop {
+            let kind = layout_kind(layout, k);
+            if kind == Some('r') {
+                match result {
+                    Some(result) => {
+                        operands.push(result);
+                        max_id = max_id.max(result);
+                    },
+                    None => return Err(BackendError::Internal(format!("assemble: no result in line {}", line_no))),
+                }
+                k += 1;
+                continue;
+            }
+            let arg = match args.next() {
+                Some(arg) => arg,
+                None => break,
+            };
+            match kind {
+                Some('t') | Some('i') => {
+                    let id = parse_id(arg.as_str(), line_no)?;
+                    max_id = max_id.max(id);
+                    operands.push(id);
+                },
+                Some('s') => {
+                    if !arg.starts_with('"') {
+                        return Err(BackendError::Internal(format!("assemble: expected string in line {}", line_no)));
+                    }
+                    operands.extend(string_words(&arg[1..]));
+                },
+                Some('e') => {
+                    match opencl_std_fun(arg.as_str()) {
+                        Some(inst) => operands.push(inst),
+                        None => operands.push(parse_number(arg.as_str(), line_no)?),
+                    }
+                },
+                Some(_) => operands.push(parse_number(arg.as_str(), line_no)?),
+                None => return Err(BackendError::Internal(format!("assemble: too many operands in line {}", line_no))),
+            }
+            k += 1;
+        }
+        push_instr(&mut body, opcode, operands.as_slice());
+    }
+    if !has_bound {
+        header[3] = max_id + 1;
+    }
+    let mut words = header.to_vec();
+    words.extend(body);
+    Ok(words)
+}
@@ -0,0 +1,164 @@
+//
+// Copyright (c) 2025 Łukasz Szpakowski
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+use crate::frontend::do_frontend_phases;
+use crate::backend::ir::test_utils::*;
+use crate::backend::lowering::*;
+use super::disassembler::*;
+use super::*;
+
+fn emit_and_disassemble(ir_tree: &IrTree) -> (Vec<u32>, String)
+{
+    let words = match emit_ir_tree_to_spirv(ir_tree) {
+        Ok(words) => words,
+        Err(err) => {
+            println!("{}", err);
+            panic!();
+        },
+    };
+    let text = match disassemble(words.as_slice()) {
+        Ok(text) => text,
+        Err(err) => {
+            println!("{}", err);
+            panic!();
+        },
+    };
+    match assemble(text.as_str()) {
+        Ok(words2) => assert_eq!(words, words2),
+        Err(err) => {
+            println!("{}", err);
+            assert!(false);
+        },
+    }
+    (words, text)
+}
+
+#[test]
+fn test_spirv_emit_ir_tree_to_spirv_emits_kernel_with_vector_math()
+{
+    let s = "
+kernel fun @k(*global float4) -> void
+{
+    var size_t;
+    var float4;
+    v1 = call_builtin get_global_id(uint 0);
+    v2 = load v0[v1];
+    v0[v1] = call_builtin sqrt(v2);
+    return;
+}
+";
+    let ir_tree = parse_ir_string(&s[1..]);
+    let (words, text) = emit_and_disassemble(&ir_tree);
+    assert_eq!(SPIRV_MAGIC_NUMBER, words[0]);
+    assert_eq!(SPIRV_VERSION, words[1]);
+    assert!(text.starts_with("; SPIR-V\n; Version: 1.0\n; Generator: 0\n"));
+    assert!(text.contains("OpCapability 6\n"));
+    assert!(text.contains("OpExtInstImport \"OpenCL.std\"\n"));
+    assert!(text.contains("OpMemoryModel 2 2\n"));
+    assert!(text.contains("OpEntryPoint 6 "));
+    assert!(text.contains(" \"k\" "));
+    assert!(text.contains("OpTypeVector "));
+    assert!(text.contains(" sqrt "));
+}
+
+#[test]
+fn test_spirv_emit_ir_tree_to_spirv_emits_kernel_with_local_memory_and_barrier()
+{
+    let s = "
+kernel fun @k(*global int) -> void
+{
+    var local [int; 4];
+    var size_t;
+    var size_t;
+    var size_t;
+    v2 = call_builtin get_local_id(uint 0);
+    v3 = call_builtin get_global_id(uint 0);
+    v1[v2] = load v0[v3];
+    call_builtin barrier(builtin CLK_LOCAL_MEM_FENCE: uint);
+    v4 = sub size_t 3, v2;
+    v0[v3] = load v1[v4];
+    return;
+}
+";
+    let ir_tree = parse_ir_string(&s[1..]);
+    let (_, text) = emit_and_disassemble(&ir_tree);
+    assert!(text.contains("OpTypePointer 4 "));
+    assert!(text.contains("OpVariable %"));
+    assert!(text.contains("OpControlBarrier "));
+    assert!(text.contains("OpDecorate %"));
+}
+
+#[test]
+fn test_spirv_emit_ir_tree_to_spirv_emits_kernel_with_panic_and_printf()
+{
+    let s = "
+fun @f(int) -> void with panic
+{
+    if eq v0, int 3 {
+        panic \"three\" at \"a.vscfl\":1:2;
+    } else {
+    }
+    return;
+}
+
+kernel fun @k(int) -> void with panic
+{
+    var size_t;
+    v1 = call_builtin get_global_id(uint 0);
+    call_builtin printf(string \"%d: %5.2f %x%%\\n\", v1 as int, float 1.5, v0);
+    call @f(v1 as int) at \"a.vscfl\":3:4;
+    return;
+}
+";
+    let ir_tree = parse_ir_string(&s[1..]);
+    let (_, text) = emit_and_disassemble(&ir_tree);
+    assert!(text.contains(" printf "));
+    assert!(text.contains("OpAtomicCompareExchange "));
+    assert!(text.contains("OpFunctionCall "));
+}
+
+#[test]
+fn test_spirv_emit_ir_tree_to_spirv_emits_lowered_kernel()
+{
+    let s = "
+import opencl;
+f(x: Int) -> Int = if x < 3 then x * 2 else 0 - 1;
+kernel k(xs: UniqGlobalSlice<Int>, x: Int) -> () =
+    let i = get_global_id(0 as Uint);
+        _ = printf(\"%d %d\\n\", i as Int, x);
+        _ = xs[i] <- f(x + (i as Int));
+    in  ();
+";
+    let s2 = &s[1..];
+    let tree = match do_frontend_phases(s2) {
+        Ok(tree) => tree,
+        Err(errs) => {
+            println!("{}", errs);
+            panic!();
+        },
+    };
+    let ir_tree = match lower_tree(&tree) {
+        Ok(ir_tree) => ir_tree,
+        Err(err) => {
+            println!("{}", err);
+            panic!();
+        },
+    };
+    let (_, text) = emit_and_disassemble(&ir_tree);
+    assert!(text.contains("OpEntryPoint 6 "));
+    assert!(text.contains("OpFunctionEnd\n"));
+}
+
+#[test]
+fn test_spirv_disassemble_complains_on_unknown_opcode()
+{
+    let words = vec![SPIRV_MAGIC_NUMBER, SPIRV_VERSION, 0, 1, 0, (1 << 16) | 0xffff];
+    match disassemble(words.as_slice()) {
+        Err(BackendError::Internal(msg)) => assert_eq!(String::from("disassemble: unknown opcode 65535"), msg),
+        _ => assert!(false),
+    }
+}
@@ -0,0 +1,606 @@
+//
+// Copyright (c) 2025 Łukasz Szpakowski
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+use crate::backend::error::*;
+use crate::backend::ir::*;
+use crate::backend::ir::printer::*;
+
+fn vector_elem_type(typ: &IrType) -> Option<(IrType, usize)>
+{
+    match typ {
+        IrType::CharN(n) => Some((IrType::Char, *n)),
+        IrType::ShortN(n) => Some((IrType::Short, *n)),
+        IrType::IntN(n) => Some((IrType::Int, *n)),
+        IrType::LongN(n) => Some((IrType::Long, *n)),
+        IrType::UcharN(n) => Some((IrType::Uchar, *n)),
+        IrType::UshortN(n) => Some((IrType::Ushort, *n)),
+        IrType::UintN(n) => Some((IrType::Uint, *n)),
+        IrType::UlongN(n) => Some((IrType::Ulong, *n)),
+        IrType::FloatN(n) => Some((IrType::Float, *n)),
+        IrType::DoubleN(n) => Some((IrType::Double, *n)),
+        _ => None,
+    }
+}
+
+fn is_integer_type(typ: &IrType) -> bool
+{
+    matches!(typ, IrType::Char | IrType::Short | IrType::Int | IrType::Long | IrType::Uchar | IrType::Ushort | IrType::Uint | IrType::Ulong | IrType::SizeT | IrType::PtrdiffT | IrType::IntptrT | IrType::UintptrT)
+}
+
+fn is_ptr_type(typ: &IrType) -> bool
+{
+    matches!(typ, IrType::Ptr(_, _, _))
+}
+
+fn is_zero_value(value: &IrValue<IrArgVar>) -> bool
+{
+    matches!(value, IrValue::Char(0, None) | IrValue::Short(0, None) | IrValue::Int(0, None) | IrValue::Long(0, None) | IrValue::Uchar(0, None) | IrValue::Ushort(0, None) | IrValue::Uint(0, None) | IrValue::Ulong(0, None) | IrValue::SizeT(0, None) | IrValue::PtrdiffT(0, None) | IrValue::IntptrT(0, None) | IrValue::UintptrT(0, None))
+}
+
+trait VerifyVar
+{
+    fn var_type(&self, ctx: &mut VerifierContext) -> Result<Box<IrType>, String>;
+}
+
+struct VerifierContext<'a>
+{
+    tree: &'a IrTree,
+    ident: String,
+    local_var_types: Vec<Option<Box<IrType>>>,
+    caller_arg_types: Vec<Box<IrType>>,
+    private_closure_type: Option<Box<IrType>>,
+    local_closure_type: Option<Box<IrType>>,
+    global_closure_type: Option<Box<IrType>>,
+    loop_count: usize,
+    path: Vec<String>,
+    errs: Vec<BackendIrError>,
+}
+
+impl<'a> VerifierContext<'a>
+{
+    fn new(tree: &'a IrTree, ident: &str) -> Self
+    {
+        VerifierContext {
+            tree,
+            ident: String::from(ident),
+            local_var_types: Vec::new(),
+            caller_arg_types: Vec::new(),
+            private_closure_type: None,
+            local_closure_type: None,
+            global_closure_type: None,
+            loop_count: 0,
+            path: Vec::new(),
+            errs: Vec::new(),
+        }
+    }
+
+    fn add_error(&mut self, msg: String)
+    { self.errs.push(BackendIrError(self.ident.clone(), self.path.join("."), msg)); }
+
+    fn global_var_type(&self, ident: &String) -> Result<Box<IrType>, String>
+    {
+        match self.tree.var(ident) {
+            Some(var) => {
+                match &*var.borrow() {
+                    IrVar::Const(typ, _) => Ok(typ.clone()),
+                    IrVar::Var(_, _, typ, _) => Ok(typ.clone()),
+                    IrVar::Fun(_) => Err(format!("variable {} is function", ident)),
+                }
+            },
+            None => Err(format!("undefined variable {}", ident)),
+        }
+    }
+
+    fn local_var_type(&self, idx: usize) -> Result<Box<IrType>, String>
+    {
+        match self.local_var_types.get(idx) {
+            Some(Some(typ)) => Ok(typ.clone()),
+            _ => Err(format!("local variable {} isn't declared", idx)),
+        }
+    }
+
+    fn caller_arg_type(&self, idx: usize) -> Result<Box<IrType>, String>
+    {
+        match self.caller_arg_types.get(idx) {
+            Some(typ) => Ok(typ.clone()),
+            None => Err(format!("no caller argument {}", idx)),
+        }
+    }
+
+    fn closure_var_type(&self, closure_type: &Option<Box<IrType>>, idx: usize) -> Result<Box<IrType>, String>
+    {
+        match closure_type {
+            Some(closure_type) => self.field_type(closure_type, idx),
+            None => Err(format!("no closure for closure variable {}", idx)),
+        }
+    }
+
+    fn field_type(&self, typ: &IrType, idx: usize) -> Result<Box<IrType>, String>
+    {
+        match typ {
+            IrType::Struct(ident) => {
+                match self.tree.struct1(ident) {
+                    Some(struct1) => {
+                        let field_type = match &*struct1.borrow() {
+                            IrStruct::Struct(field_types) => field_types.get(idx).cloned(),
+                            IrStruct::Closure(field_types) => field_types.get(&idx).cloned(),
+                        };
+                        match field_type {
+                            Some(field_type) => Ok(field_type),
+                            None => Err(format!("no field {} in structure {}", idx, ident)),
+                        }
+                    },
+                    None => Err(format!("undefined structure {}", ident)),
+                }
+            },
+            IrType::Union(ident) => {
+                match self.tree.union(ident) {
+                    Some(union) => {
+                        match union.borrow().0.get(idx) {
+                            Some(field_type) => Ok(field_type.clone()),
+                            None => Err(format!("no field {} in union {}", idx, ident)),
+                        }
+                    },
+                    None => Err(format!("undefined union {}", ident)),
+                }
+            },
+            _ => {
+                match vector_elem_type(typ) {
+                    Some((elem_type, n)) if idx < n => Ok(Box::new(elem_type)),
+                    _ => Err(format!("type {} hasn't field {}", ir_type_to_string(typ), idx)),
+                }
+            },
+        }
+    }
+
+    fn elem_type(&self, typ: &IrType) -> Result<Box<IrType>, String>
+    {
+        match typ {
+            IrType::Array(elem_type, _) => Ok(elem_type.clone()),
+            IrType::Ptr(_, _, elem_type) => Ok(elem_type.clone()),
+            _ => Err(format!("type {} isn't array or pointer", ir_type_to_string(typ))),
+        }
+    }
+
+    fn check_index_type(&self, typ: Result<Box<IrType>, String>) -> Result<(), String>
+    {
+        let typ = typ?;
+        if is_integer_type(&typ) {
+            Ok(())
+        } else {
+            Err(format!("index has type {}", ir_type_to_string(&typ)))
+        }
+    }
+
+    fn arg_op_type(&self, typ: Box<IrType>, op: &IrArgOp) -> Result<Box<IrType>, String>
+    {
+        match op {
+            IrArgOp::Deref(_) => {
+                if is_ptr_type(&typ) {
+                    self.elem_type(&typ)
+                } else {
+                    Err(format!("type {} isn't pointer", ir_type_to_string(&typ)))
+                }
+            },
+            IrArgOp::Dot(_, idx) => self.field_type(&typ, *idx),
+            IrArgOp::Arrow(_, idx) => {
+                if is_ptr_type(&typ) {
+                    self.field_type(&*self.elem_type(&typ)?, *idx)
+                } else {
+                    Err(format!("type {} isn't pointer", ir_type_to_string(&typ)))
+                }
+            },
+            IrArgOp::Index(_, _) => self.elem_type(&typ),
+            IrArgOp::GlobalIndex(_, ident) => {
+                self.check_index_type(self.global_var_type(ident))?;
+                self.elem_type(&typ)
+            },
+            IrArgOp::LocalIndex(_, idx) => {
+                self.check_index_type(self.local_var_type(*idx))?;
+                self.elem_type(&typ)
+            },
+            IrArgOp::CallerFunArgIndex(_, idx) => {
+                self.check_index_type(self.caller_arg_type(*idx))?;
+                self.elem_type(&typ)
+            },
+            IrArgOp::PrivateClosureIndex(_, idx) => {
+                self.check_index_type(self.closure_var_type(&self.private_closure_type, *idx))?;
+                self.elem_type(&typ)
+            },
+            IrArgOp::LocalClosureIndex(_, idx) => {
+                self.check_index_type(self.closure_var_type(&self.local_closure_type, *idx))?;
+                self.elem_type(&typ)
+            },
+            IrArgOp::GlobalClosureIndex(_, idx) => {
+                self.check_index_type(self.closure_var_type(&self.global_closure_type, *idx))?;
+                self.elem_type(&typ)
+            },
+        }
+    }
+
+    fn arg_ops_type(&self, typ: Box<IrType>, ops: &[IrArgOp]) -> Result<Box<IrType>, String>
+    {
+        let mut type2 = typ;
+        for op in ops {
+            type2 = self.arg_op_type(type2, op)?;
+        }
+        Ok(type2)
+    }
+
+    fn ref_type(&self, typ: Box<IrType>, ops: &[IrArgOp], vector_elem_ptr_type: &Option<Box<IrType>>) -> Result<Box<IrType>, String>
+    {
+        let type2 = self.arg_ops_type(typ, ops)?;
+        match vector_elem_ptr_type {
+            Some(ptr_type) => Ok(ptr_type.clone()),
+            None => Ok(Box::new(IrType::Ptr(IrPtrModifier::None, IrAccessModifier::None, type2))),
+        }
+    }
+
+    fn heap_type(&self, ptr_modifier: IrPtrModifier) -> Box<IrType>
+    { Box::new(IrType::Ptr(ptr_modifier, IrAccessModifier::None, Box::new(IrType::Uint))) }
+
+    fn instr_var_type(&self, var: &IrInstrVar) -> Result<Box<IrType>, String>
+    {
+        match var {
+            IrInstrVar::Global(ident, ops) => self.arg_ops_type(self.global_var_type(ident)?, ops.as_slice()),
+            IrInstrVar::Local(idx, ops) => self.arg_ops_type(self.local_var_type(*idx)?, ops.as_slice()),
+            IrInstrVar::CallerFunArg(idx, ops) => self.arg_ops_type(self.caller_arg_type(*idx)?, ops.as_slice()),
+            IrInstrVar::PrivateClosure(idx, ops) => self.arg_ops_type(self.closure_var_type(&self.private_closure_type, *idx)?, ops.as_slice()),
+            IrInstrVar::LocalClosure(idx, ops) => self.arg_ops_type(self.closure_var_type(&self.local_closure_type, *idx)?, ops.as_slice()),
+            IrInstrVar::GlobalClosure(idx, ops) => self.arg_ops_type(self.closure_var_type(&self.global_closure_type, *idx)?, ops.as_slice()),
+            IrInstrVar::PrivateHeap(ops) => self.arg_ops_type(self.heap_type(IrPtrModifier::Private), ops.as_slice()),
+            IrInstrVar::LocalHeap(ops) => self.arg_ops_type(self.heap_type(IrPtrModifier::Local), ops.as_slice()),
+            IrInstrVar::GlobalHeap(ops) => self.arg_ops_type(self.heap_type(IrPtrModifier::Global), ops.as_slice()),
+        }
+    }
+
+    fn value_type<T: VerifyVar>(&mut self, value: &IrValue<T>) -> Option<Box<IrType>>
+    {
+        match value {
+            IrValue::Char(_, typ) => Some(typ.clone().unwrap_or(Box::new(IrType::Char))),
+            IrValue::Short(_, typ) => Some(typ.clone().unwrap_or(Box::new(IrType::Short))),
+            IrValue::Int(_, typ) => Some(typ.clone().unwrap_or(Box::new(IrType::Int))),
+            IrValue::Long(_, typ) => Some(typ.clone().unwrap_or(Box::new(IrType::Long))),
+            IrValue::Uchar(_, typ) => Some(typ.clone().unwrap_or(Box::new(IrType::Uchar))),
+            IrValue::Ushort(_, typ) => Some(typ.clone().unwrap_or(Box::new(IrType::Ushort))),
+            IrValue::Uint(_, typ) => Some(typ.clone().unwrap_or(Box::new(IrType::Uint))),
+            IrValue::Ulong(_, typ) => Some(typ.clone().unwrap_or(Box::new(IrType::Ulong))),
+            IrValue::Float(_, typ) => Some(typ.clone().unwrap_or(Box::new(IrType::Float))),
+            IrValue::Double(_, typ) => Some(typ.clone().unwrap_or(Box::new(IrType::Double))),
+            IrValue::SizeT(_, typ) => Some(typ.clone().unwrap_or(Box::new(IrType::SizeT))),
+            IrValue::PtrdiffT(_, typ) => Some(typ.clone().unwrap_or(Box::new(IrType::PtrdiffT))),
+            IrValue::IntptrT(_, typ) => Some(typ.clone().unwrap_or(Box::new(IrType::IntptrT))),
+            IrValue::UintptrT(_, typ) => Some(typ.clone().unwrap_or(Box::new(IrType::UintptrT))),
+            IrValue::Object(object) => {
+                match &**object {
+                    IrObject::String(_) => None,
+                    IrObject::BuiltinVar(_, builtin_type, typ) => typ.clone().or(builtin_type.clone()),
+                    IrObject::Var(var, typ) => {
+                        match var.var_type(self) {
+                            Ok(var_type) => Some(typ.clone().unwrap_or(var_type)),
+                            Err(msg) => {
+                                self.add_error(msg);
+                                None
+                            },
+                        }
+                    },
+                    IrObject::Vector(values, typ) => {
+                        self.values_types(values.as_slice());
+                        Some(typ.clone())
+                    },
+                    IrObject::Array(values, typ) => {
+                        self.values_types(values.as_slice());
+                        typ.clone()
+                    },
+                    IrObject::Struct(values, field_pairs, typ) => {
+                        self.values_types(values.as_slice());
+                        for field_pair in field_pairs {
+                            self.value_type(&field_pair.1);
+                        }
+                        typ.clone()
+                    },
+                    IrObject::Union(_, value, typ) => {
+                        self.value_type(value);
+                        typ.clone()
+                    },
+                    IrObject::Closure(field_pairs, typ) => {
+                        for field_pair in field_pairs {
+                            self.value_type(&field_pair.1);
+                        }
+                        typ.clone()
+                    },
+                    IrObject::Sizeof(_, typ) => Some(typ.clone().unwrap_or(Box::new(IrType::SizeT))),
+                    IrObject::CallerFunIndex(ident, _, typ) => {
+                        match self.tree.var(ident) {
+                            Some(_) => (),
+                            None => self.add_error(format!("undefined caller {}", ident)),
+                        }
+                        Some(typ.clone().unwrap_or(Box::new(IrType::Uint)))
+                    },
+                }
+            },
+        }
+    }
+
+    fn values_types<T: VerifyVar>(&mut self, values: &[IrValue<T>]) -> Vec<Option<Box<IrType>>>
+    { values.iter().map(|v| self.value_type(v)).collect() }
+
+    fn check_bin_op(&mut self, name: &str, value1: &IrValue<IrArgVar>, value2: &IrValue<IrArgVar>, is_cmp: bool)
+    {
+        let type1 = self.value_type(value1);
+        let type2 = self.value_type(value2);
+        match (type1, type2) {
+            (Some(type1), Some(type2)) if type1 != type2 => {
+                if is_cmp && ((is_ptr_type(&type1) && is_zero_value(value2)) || (is_zero_value(value1) && is_ptr_type(&type2))) {
+                    return;
+                }
+                self.add_error(format!("operands of {} have different types {} and {}", name, ir_type_to_string(&type1), ir_type_to_string(&type2)));
+            },
+            _ => (),
+        }
+    }
+
+    fn check_fun_call(&mut self, ident: &String, values: &[IrValue<IrArgVar>])
+    {
+        let arg_types = self.values_types(values);
+        let param_types = match self.tree.var(ident) {
+            Some(var) => {
+                match &*var.borrow() {
+                    IrVar::Fun(fun) => {
+                        match &**fun {
+                            IrFun::Fun(_, arg_types, _, _, _, _, _, _) => arg_types.clone(),
+                            IrFun::Caller(fun_type, arg_types, _, _, _, _, _, _) => {
+                                let mut param_types = vec![fun_type.clone()];
+                                param_types.extend(arg_types.iter().cloned());
+                                param_types
+                            },
+                        }
+                    },
+                    _ => {
+                        self.add_error(format!("variable {} isn't function", ident));
+                        return;
+                    },
+                }
+            },
+            None => {
+                self.add_error(format!("undefined function {}", ident));
+                return;
+            },
+        };
+        if param_types.len() != arg_types.len() {
+            self.add_error(format!("number of arguments of function {} is {} instead of {}", ident, arg_types.len(), param_types.len()));
+            return;
+        }
+        for (i, (param_type, arg_type)) in param_types.iter().zip(arg_types.iter()).enumerate() {
+            match arg_type {
+                Some(arg_type) if arg_type != param_type => self.add_error(format!("argument {} of function {} has type {} instead of {}", i, ident, ir_type_to_string(arg_type), ir_type_to_string(param_type))),
+                _ => (),
+            }
+        }
+    }
+
+    fn verify_op(&mut self, op: &IrOp)
+    {
+        match op {
+            IrOp::Load(value) => {
+                self.value_type(value);
+            },
+            IrOp::Neg(value) => {
+                self.value_type(value);
+            },
+            IrOp::Not(value) => {
+                self.value_type(value);
+            },
+            IrOp::Mul(value1, value2) => self.check_bin_op("mul", value1, value2, false),
+            IrOp::Div(value1, value2) => self.check_bin_op("div", value1, value2, false),
+            IrOp::Rem(value1, value2) => self.check_bin_op("rem", value1, value2, false),
+            IrOp::Add(value1, value2) => self.check_bin_op("add", value1, value2, false),
+            IrOp::Sub(value1, value2) => self.check_bin_op("sub", value1, value2, false),
+            IrOp::Shl(value1, value2) => self.check_bin_op("shl", value1, value2, false),
+            IrOp::Shr(value1, value2) => self.check_bin_op("shr", value1, value2, false),
+            IrOp::Eq(value1, value2) => self.check_bin_op("eq", value1, value2, true),
+            IrOp::Ne(value1, value2) => self.check_bin_op("ne", value1, value2, true),
+            IrOp::Lt(value1, value2) => self.check_bin_op("lt", value1, value2, true),
+            IrOp::Ge(value1, value2) => self.check_bin_op("ge", value1, value2, true),
+            IrOp::Gt(value1, value2) => self.check_bin_op("gt", value1, value2, true),
+            IrOp::Le(value1, value2) => self.check_bin_op("le", value1, value2, true),
+            IrOp::And(value1, value2) => self.check_bin_op("and", value1, value2, false),
+            IrOp::Xor(value1, value2) => self.check_bin_op("xor", value1, value2, false),
+            IrOp::Or(value1, value2) => self.check_bin_op("or", value1, value2, false),
+            IrOp::CallBuiltinFun(_, _, values) => {
+                self.values_types(values.as_slice());
+            },
+            IrOp::CallFun(ident, values, _, _) => self.check_fun_call(ident, values.as_slice()),
+            IrOp::CallFunWithoutPanic(ident, values, _) => self.check_fun_call(ident, values.as_slice()),
+        }
+    }
+
+    fn verify_block(&mut self, block: &IrBlock)
+    {
+        let old_len = self.local_var_types.len();
+        for local_var_pair in block.local_var_pairs() {
+            self.local_var_types.push(Some(local_var_pair.1.clone()));
+        }
+        for (i, instr) in block.instrs().iter().enumerate() {
+            self.path.push(format!("{}", i));
+            self.verify_instr(instr);
+            self.path.pop();
+        }
+        self.local_var_types.truncate(old_len);
+    }
+
+    fn verify_nested_block(&mut self, name: String, block: &IrBlock)
+    {
+        self.path.push(name);
+        self.verify_block(block);
+        self.path.pop();
+    }
+
+    fn verify_instr(&mut self, instr: &IrInstr)
+    {
+        match instr {
+            IrInstr::Op(op) => self.verify_op(op),
+            IrInstr::Assign(var, op) => {
+                match self.instr_var_type(var) {
+                    Ok(_) => (),
+                    Err(msg) => self.add_error(msg),
+                }
+                self.verify_op(op);
+            },
+            IrInstr::Return(op) => {
+                if let Some(op) = op {
+                    self.verify_op(op);
+                }
+            },
+            IrInstr::Break => {
+                if self.loop_count == 0 {
+                    self.add_error(String::from("break isn't in loop"));
+                }
+            },
+            IrInstr::Continue => {
+                if self.loop_count == 0 {
+                    self.add_error(String::from("continue isn't in loop"));
+                }
+            },
+            IrInstr::Block(block) => self.verify_nested_block(String::from("block"), block),
+            IrInstr::If(op, block1, block2) => {
+                self.verify_op(op);
+                self.verify_nested_block(String::from("then"), block1);
+                self.verify_nested_block(String::from("else"), block2);
+            },
+            IrInstr::Switch(op, cases) => {
+                self.verify_op(op);
+                for (i, case) in cases.iter().enumerate() {
+                    match case {
+                        IrCase::Case(_, block) => self.verify_nested_block(format!("case{}", i), block),
+                        IrCase::Default(block) => self.verify_nested_block(format!("case{}", i), block),
+                    }
+                }
+            },
+            IrInstr::Loop(block) => {
+                self.loop_count += 1;
+                self.verify_nested_block(String::from("loop"), block);
+                self.loop_count -= 1;
+            },
+            IrInstr::Panic(_, _, _) => (),
+        }
+    }
+
+    fn verify_global_value(&mut self, value: &IrValue<IrValueVar>)
+    { self.value_type(value); }
+
+    fn verify_fun(&mut self, fun: &IrFun)
+    {
+        match fun {
+            IrFun::Fun(modifier, arg_types, ret_type, block, _, _, _, _) => {
+                if *modifier == IrFunModifier::Kernel && **ret_type != IrType::Void {
+                    self.add_error(format!("kernel returns {} instead of void", ir_type_to_string(ret_type)));
+                }
+                self.local_var_types = arg_types.iter().map(|t| Some(t.clone())).collect();
+                self.verify_block(block);
+            },
+            IrFun::Caller(fun_type, arg_types, _, caller_funs, _, _, _, _) => {
+                for (idx, caller_fun) in caller_funs.funs() {
+                    self.path.push(format!("fun{}", idx));
+                    self.caller_arg_types = vec![fun_type.clone()];
+                    self.caller_arg_types.extend(arg_types.iter().cloned());
+                    self.private_closure_type = None;
+                    self.local_closure_type = None;
+                    self.global_closure_type = None;
+                    match caller_fun {
+                        IrCallerFun::Fun(fun_ident) => {
+                            let values: Vec<IrValue<IrArgVar>> = (1..=arg_types.len()).map(|i| IrValue::Object(Box::new(IrObject::Var(IrArgVar::CallerFunArg(i, Vec::new()), None)))).collect();
+                            self.check_fun_call(fun_ident, values.as_slice());
+                        },
+                        IrCallerFun::InlineFun(_, block) => {
+                            self.local_var_types = arg_types.iter().map(|t| Some(t.clone())).collect();
+                            self.verify_block(block);
+                        },
+                        IrCallerFun::Lambda(private_closure_type, local_closure_type, global_closure_type, start_idx, block) => {
+                            self.private_closure_type = private_closure_type.clone();
+                            self.local_closure_type = local_closure_type.clone();
+                            self.global_closure_type = global_closure_type.clone();
+                            self.local_var_types = vec![None; *start_idx];
+                            self.local_var_types.extend(arg_types.iter().map(|t| Some(t.clone())));
+                            self.verify_block(block);
+                        },
+                    }
+                    self.path.pop();
+                }
+            },
+        }
+    }
+}
+
+impl VerifyVar for IrValueVar
+{
+    fn var_type(&self, ctx: &mut VerifierContext) -> Result<Box<IrType>, String>
+    {
+        let mut typ = ctx.global_var_type(&self.0)?;
+        for op in &self.1 {
+            let arg_op = match op {
+                IrValueOp::Deref(tmp_type) => IrArgOp::Deref(tmp_type.clone()),
+                IrValueOp::Dot(tmp_type, idx) => IrArgOp::Dot(tmp_type.clone(), *idx),
+                IrValueOp::Arrow(tmp_type, idx) => IrArgOp::Arrow(tmp_type.clone(), *idx),
+                IrValueOp::Index(tmp_type, idx) => IrArgOp::Index(tmp_type.clone(), *idx),
+            };
+            typ = ctx.arg_op_type(typ, &arg_op)?;
+        }
+        Ok(Box::new(IrType::Ptr(IrPtrModifier::None, IrAccessModifier::None, typ)))
+    }
+}
+
+impl VerifyVar for IrArgVar
+{
+    fn var_type(&self, ctx: &mut VerifierContext) -> Result<Box<IrType>, String>
+    {
+        match self {
+            IrArgVar::Global(ident, ops) => ctx.arg_ops_type(ctx.global_var_type(ident)?, ops.as_slice()),
+            IrArgVar::Local(idx, ops) => ctx.arg_ops_type(ctx.local_var_type(*idx)?, ops.as_slice()),
+            IrArgVar::CallerFunArg(idx, ops) => ctx.arg_ops_type(ctx.caller_arg_type(*idx)?, ops.as_slice()),
+            IrArgVar::PrivateClosure(idx, ops) => ctx.arg_ops_type(ctx.closure_var_type(&ctx.private_closure_type, *idx)?, ops.as_slice()),
+            IrArgVar::LocalClosure(idx, ops) => ctx.arg_ops_type(ctx.closure_var_type(&ctx.local_closure_type, *idx)?, ops.as_slice()),
+            IrArgVar::GlobalClosure(idx, ops) => ctx.arg_ops_type(ctx.closure_var_type(&ctx.global_closure_type, *idx)?, ops.as_slice()),
+            IrArgVar::PrivateHeap(ops) => ctx.arg_ops_type(ctx.heap_type(IrPtrModifier::Private), ops.as_slice()),
+            IrArgVar::LocalHeap(ops) => ctx.arg_ops_type(ctx.heap_type(IrPtrModifier::Local), ops.as_slice()),
+            IrArgVar::GlobalHeap(ops) => ctx.arg_ops_type(ctx.heap_type(IrPtrModifier::Global), ops.as_slice()),
+            IrArgVar::RefGlobal(ident, ops, typ) => ctx.ref_type(ctx.global_var_type(ident)?, ops.as_slice(), typ),
+            IrArgVar::RefLocal(idx, ops, typ) => ctx.ref_type(ctx.local_var_type(*idx)?, ops.as_slice(), typ),
+            IrArgVar::RefCallerFunArg(idx, ops, typ) => ctx.ref_type(ctx.caller_arg_type(*idx)?, ops.as_slice(), typ),
+            IrArgVar::RefPrivateClosure(idx, ops, typ) => ctx.ref_type(ctx.closure_var_type(&ctx.private_closure_type, *idx)?, ops.as_slice(), typ),
+            IrArgVar::RefLocalClosure(idx, ops, typ) => ctx.ref_type(ctx.closure_var_type(&ctx.local_closure_type, *idx)?, ops.as_slice(), typ),
+            IrArgVar::RefGlobalClosure(idx, ops, typ) => ctx.ref_type(ctx.closure_var_type(&ctx.global_closure_type, *idx)?, ops.as_slice(), typ),
+            IrArgVar::RefPrivateHeap(ops, typ) => ctx.ref_type(ctx.heap_type(IrPtrModifier::Private), ops.as_slice(), typ),
+            IrArgVar::RefLocalHeap(ops, typ) => ctx.ref_type(ctx.heap_type(IrPtrModifier::Local), ops.as_slice(), typ),
+            IrArgVar::RefGlobalHeap(ops, typ) => ctx.ref_type(ctx.heap_type(IrPtrModifier::Global), ops.as_slice(), typ),
+        }
+    }
+}
+
+pub fn verify_ir_tree(tree: &IrTree) -> BackendResult<()>
+{
+    let mut errs: Vec<BackendIrError> = Vec::new();
+    for def in tree.defs() {
+        if let IrDef::Var(ident, var) = &**def {
+            let mut ctx = VerifierContext::new(tree, ident);
+            match &*var.borrow() {
+                IrVar::Const(_, value) => ctx.verify_global_value(value),
+                IrVar::Var(_, _, _, value) => ctx.verify_global_value(value),
+                IrVar::Fun(fun) => ctx.verify_fun(fun),
+            }
+            errs.append(&mut ctx.errs);
+        }
+    }
+    if errs.is_empty() {
+        Ok(())
+    } else {
+        Err(BackendError::Ir(errs))
+    }
+}
+
+#[cfg(test)]
+mod tests;
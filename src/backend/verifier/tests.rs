@@ -0,0 +1,154 @@
+//
+// Copyright (c) 2025 Łukasz Szpakowski
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+use crate::frontend::do_frontend_phases;
+use crate::backend::ir::parser::*;
+use crate::backend::lowering::*;
+use super::*;
+
+#[test]
+fn test_verifier_verify_ir_tree_verifies_lowered_tree()
+{
+    let s = "
+import opencl;
+data List = Nil() | Cons(Int, UniqPrivateBox<List>);
+recursive(16) sum(l: List) -> Int =
+    l match {
+        Nil() => 0;
+        Cons(x, b) => x + sum(op_get(b));
+    };
+f(n: Int) -> List = Cons(n, uniq_private_box(Nil()));
+apply(f: (Int) -> Int, x: Int) -> Int = f(x);
+inc(x: Int) -> Int = x + 1;
+kernel k(xs: UniqGlobalSlice<Int>, y: Int) -> () =
+    let i = get_global_id(0 as Uint);
+        _ = xs[i] <- (sum(f(1)) + apply(|z| z + y, y) + apply(inc, y));
+    in  ();
+";
+    let s2 = &s[1..];
+    let tree = match do_frontend_phases(s2) {
+        Ok(tree) => tree,
+        Err(errs) => {
+            println!("{}", errs);
+            panic!();
+        },
+    };
+    let ir_tree = match lower_tree(&tree) {
+        Ok(ir_tree) => ir_tree,
+        Err(err) => {
+            println!("{}", err);
+            panic!();
+        },
+    };
+    match verify_ir_tree(&ir_tree) {
+        Ok(()) => assert!(true),
+        Err(err) => {
+            println!("{}", err);
+            assert!(false);
+        },
+    }
+}
+
+#[test]
+fn test_verifier_verify_ir_tree_complains_on_invalid_tree()
+{
+    let s = "
+struct S { int, float }
+
+fun @f(int, *private struct S) -> int
+{
+    var int;
+    v2 = add v0, c1->f0;
+    if lt v0, int 0 {
+        v3 = load v0;
+        break;
+    } else {
+        loop {
+            v2 = add v0, v1->f1;
+            continue;
+        }
+    }
+    v2 = call @g(v0) at \"a.vscfl\":1:1;
+    v2 = call @f(v0, int 1) at \"a.vscfl\":1:1;
+    return load v2;
+}
+
+kernel fun @k(int) -> int
+{
+    return load @x;
+}
+";
+    let s2 = &s[1..];
+    let ir_tree = match parse_ir_tree(s2) {
+        Ok(ir_tree) => ir_tree,
+        Err(err) => {
+            println!("{}", err);
+            panic!();
+        },
+    };
+    match verify_ir_tree(&ir_tree) {
+        Err(BackendError::Ir(ir_errs)) => {
+            let ss: Vec<String> = ir_errs.iter().map(|e| format!("{}", e)).collect();
+            assert_eq!(vec![
+                    String::from("f: 0: no caller argument 1"),
+                    String::from("f: 1.then.0: local variable 3 isn't declared"),
+                    String::from("f: 1.then.1: break isn't in loop"),
+                    String::from("f: 1.else.0.loop.0: operands of add have different types int and float"),
+                    String::from("f: 2: undefined function g"),
+                    String::from("f: 3: argument 1 of function f has type int instead of *private struct S"),
+                    String::from("k: kernel returns int instead of void"),
+                    String::from("k: 0: undefined variable x")
+            ], ss);
+        },
+        _ => assert!(false),
+    }
+}
+
+#[test]
+fn test_verifier_verify_ir_tree_complains_on_invalid_caller()
+{
+    let s = "
+struct F { uint, int }
+
+closure struct C { 1: int }
+
+fun @f(int, int) -> int
+{
+    return load v0;
+}
+
+caller @c(struct F; int) -> int
+{
+    1: fun @f;
+    2: lambda private struct C start 1 {
+        return add v0, pc1;
+    }
+    3: lambda start 1 {
+        return add v1, lc1;
+    }
+}
+";
+    let s2 = &s[1..];
+    let ir_tree = match parse_ir_tree(s2) {
+        Ok(ir_tree) => ir_tree,
+        Err(err) => {
+            println!("{}", err);
+            panic!();
+        },
+    };
+    match verify_ir_tree(&ir_tree) {
+        Err(BackendError::Ir(ir_errs)) => {
+            let ss: Vec<String> = ir_errs.iter().map(|e| format!("{}", e)).collect();
+            assert_eq!(vec![
+                    String::from("c: fun1: number of arguments of function f is 1 instead of 2"),
+                    String::from("c: fun2.0: local variable 0 isn't declared"),
+                    String::from("c: fun3.0: no closure for closure variable 1")
+            ], ss);
+        },
+        _ => assert!(false),
+    }
+}
@@ -0,0 +1,331 @@
+//
+// Copyright (c) 2025 Łukasz Szpakowski
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+use std::collections::BTreeSet;
+use std::env;
+use std::error;
+use std::fmt;
+use std::fmt::Write;
+use std::fs;
+use std::io;
+use std::path::*;
+use std::process;
+use std::result;
+use crate::frontend::error::*;
+use crate::frontend::tree::*;
+use crate::frontend::*;
+use crate::backend::emitter::*;
+use crate::backend::error::*;
+use crate::backend::inliner::*;
+use crate::backend::lowering::*;
+use crate::backend::manifest::*;
+use crate::backend::rust_bindings::*;
+
+pub const EXIT_SUCCESS: i32 = 0;
+pub const EXIT_USER_ERROR: i32 = 1;
+pub const EXIT_USAGE_ERROR: i32 = 2;
+pub const EXIT_INTERNAL_ERROR: i32 = 101;
+
+#[derive(Clone, Debug)]
+pub struct BuildOptions
+{
+    pub out_dir: Option<PathBuf>,
+    pub name: String,
+    pub include_paths: Vec<PathBuf>,
+    pub has_stdlib: bool,
+    pub inline_threshold: usize,
+    pub target_version: Option<OpenClVersion>,
+    pub has_rust_bindings: bool,
+}
+
+impl BuildOptions
+{
+    pub fn new() -> Self
+    {
+        BuildOptions {
+            out_dir: None,
+            name: String::from("kernels"),
+            include_paths: Vec::new(),
+            has_stdlib: true,
+            inline_threshold: DEFAULT_INLINE_THRESHOLD,
+            target_version: None,
+            has_rust_bindings: false,
+        }
+    }
+}
+
+impl Default for BuildOptions
+{
+    fn default() -> Self
+    { Self::new() }
+}
+
+#[derive(Clone, Debug)]
+pub struct BuildOutput
+{
+    pub opencl_path: PathBuf,
+    pub manifest_path: PathBuf,
+    pub rust_bindings_path: Option<PathBuf>,
+}
+
+#[derive(Debug)]
+pub enum BuildError
+{
+    Io(String, io::Error),
+    NoOutDir,
+    Frontend(FrontendErrors),
+    Backend(BackendError),
+}
+
+impl BuildError
+{
+    pub fn is_internal(&self) -> bool
+    {
+        match self {
+            BuildError::Frontend(errs) => errs.errors().iter().any(|err| matches!(err, FrontendError::Internal(_))),
+            BuildError::Backend(BackendError::Messages(_)) => false,
+            BuildError::Backend(_) => true,
+            _ => false,
+        }
+    }
+
+    pub fn exit_code(&self) -> i32
+    {
+        if self.is_internal() {
+            EXIT_INTERNAL_ERROR
+        } else {
+            EXIT_USER_ERROR
+        }
+    }
+
+    pub fn to_rustc_string(&self) -> String
+    {
+        let mut s = String::new();
+        let mut count = 0usize;
+        match self {
+            BuildError::Io(path, err) => {
+                add_diagnostic(&mut s, None, format!("couldn't read or write {}: {}", path, err).as_str());
+                count += 1;
+            },
+            BuildError::NoOutDir => {
+                add_diagnostic(&mut s, None, "environment variable OUT_DIR isn't set");
+                count += 1;
+            },
+            BuildError::Frontend(errs) => {
+                for err in errs.errors() {
+                    match err {
+                        FrontendError::Io(path, err) => add_diagnostic(&mut s, None, format!("couldn't read {}: {}", path, err).as_str()),
+                        FrontendError::Message(pos, msg) => add_diagnostic(&mut s, Some(pos), msg.as_str()),
+                        FrontendError::Internal(msg) => add_diagnostic(&mut s, None, format!("internal compiler error: {}", msg).as_str()),
+                    }
+                    count += 1;
+                }
+            },
+            BuildError::Backend(BackendError::Messages(msg_errs)) => {
+                for BackendMessageError(pos, msg) in msg_errs {
+                    add_diagnostic(&mut s, Some(pos), msg.as_str());
+                    count += 1;
+                }
+            },
+            BuildError::Backend(BackendError::Ir(ir_errs)) => {
+                for ir_err in ir_errs {
+                    add_diagnostic(&mut s, None, format!("internal compiler error: invalid IR: {}", ir_err).as_str());
+                    count += 1;
+                }
+            },
+            BuildError::Backend(BackendError::Internal(msg)) => {
+                add_diagnostic(&mut s, None, format!("internal compiler error: {}", msg).as_str());
+                count += 1;
+            },
+        }
+        if count > 1 {
+            writeln!(s, "error: aborting due to {} previous errors", count).unwrap();
+        } else {
+            writeln!(s, "error: aborting due to 1 previous error").unwrap();
+        }
+        s
+    }
+}
+
+impl error::Error for BuildError
+{}
+
+impl fmt::Display for BuildError
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        match self {
+            BuildError::Io(path, err) => write!(f, "{}: i/o error: {}", path, err),
+            BuildError::NoOutDir => write!(f, "environment variable OUT_DIR isn't set"),
+            BuildError::Frontend(errs) => write!(f, "{}", errs),
+            BuildError::Backend(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+pub type BuildResult<T> = result::Result<T, BuildError>;
+
+fn add_diagnostic(s: &mut String, pos: Option<&Pos>, msg: &str)
+{
+    writeln!(s, "error: {}", msg).unwrap();
+    if let Some(pos) = pos {
+        let line = match fs::read_to_string(pos.path.as_str()) {
+            Ok(src) => {
+                if pos.line > 0 {
+                    src.lines().nth((pos.line - 1) as usize).map(String::from)
+                } else {
+                    None
+                }
+            },
+            Err(_) => None,
+        };
+        let line_num = format!("{}", pos.line);
+        let gutter = " ".repeat(line_num.len());
+        writeln!(s, "{}--> {}:{}:{}", gutter, pos.path, pos.line, pos.column).unwrap();
+        if let Some(line) = line {
+            let mut caret = String::new();
+            for c in line.chars().take(pos.column.saturating_sub(1) as usize) {
+                caret.push(if c == '\t' { '\t' } else { ' ' });
+            }
+            caret.push('^');
+            writeln!(s, "{} |", gutter).unwrap();
+            writeln!(s, "{} | {}", line_num, line).unwrap();
+            writeln!(s, "{} | {}", gutter, caret).unwrap();
+        }
+    }
+    s.push('\n');
+}
+
+pub fn find_source_path(path: &Path, include_paths: &[PathBuf]) -> PathBuf
+{
+    if path.is_absolute() || path.exists() {
+        return path.to_path_buf();
+    }
+    for include_path in include_paths {
+        let path2 = include_path.join(path);
+        if path2.exists() {
+            return path2;
+        }
+    }
+    path.to_path_buf()
+}
+
+pub fn read_sources<P: AsRef<Path>>(paths: &[P], include_paths: &[PathBuf]) -> BuildResult<Vec<Source>>
+{
+    let mut srcs: Vec<Source> = Vec::new();
+    for path in paths {
+        let path2 = find_source_path(path.as_ref(), include_paths);
+        let path_s = path2.to_string_lossy().into_owned();
+        match fs::read_to_string(&path2) {
+            Ok(s) => srcs.push(Source::String(path_s, s)),
+            Err(err) => return Err(BuildError::Io(path_s, err)),
+        }
+    }
+    Ok(srcs)
+}
+
+fn write_file(path: &Path, s: &str) -> BuildResult<()>
+{
+    match fs::write(path, s) {
+        Ok(()) => Ok(()),
+        Err(err) => Err(BuildError::Io(path.to_string_lossy().into_owned(), err)),
+    }
+}
+
+fn check_sources<P: AsRef<Path>>(paths: &[P], opts: &BuildOptions) -> BuildResult<Tree>
+{
+    let srcs = read_sources(paths, opts.include_paths.as_slice())?;
+    let tree = if opts.has_stdlib {
+        do_frontend_phases_with_sources_and_include_paths(srcs.as_slice(), opts.include_paths.as_slice())
+    } else {
+        do_frontend_phases_with_sources_and_include_paths_without_stdlib(srcs.as_slice(), opts.include_paths.as_slice())
+    };
+    match tree {
+        Ok(tree) => Ok(tree),
+        Err(errs) => Err(BuildError::Frontend(errs)),
+    }
+}
+
+fn rerun_paths<P: AsRef<Path>>(paths: &[P], tree: Option<&Tree>, opts: &BuildOptions) -> BTreeSet<String>
+{
+    let mut rerun_paths: BTreeSet<String> = paths.iter().map(|path| find_source_path(path.as_ref(), opts.include_paths.as_slice()).to_string_lossy().into_owned()).collect();
+    if let Some(tree) = tree {
+        for module in tree.modules().values() {
+            if let Module(ModuleKind::User, module_paths) = module {
+                rerun_paths.extend(module_paths.iter().cloned());
+            }
+        }
+    }
+    rerun_paths
+}
+
+pub fn build<P: AsRef<Path>>(paths: &[P], opts: &BuildOptions) -> BuildResult<BuildOutput>
+{
+    let out_dir = match &opts.out_dir {
+        Some(out_dir) => out_dir.clone(),
+        None => {
+            match env::var_os("OUT_DIR") {
+                Some(out_dir) => PathBuf::from(out_dir),
+                None => return Err(BuildError::NoOutDir),
+            }
+        },
+    };
+    let tree = check_sources(paths, opts);
+    for rerun_path in rerun_paths(paths, tree.as_ref().ok(), opts) {
+        println!("cargo:rerun-if-changed={}", rerun_path);
+    }
+    let tree = tree?;
+    let lowerer = Lowerer::new_with_inline_threshold(opts.inline_threshold);
+    let ir_tree = match lowerer.lower_tree(&tree) {
+        Ok(ir_tree) => ir_tree,
+        Err(err) => return Err(BuildError::Backend(err)),
+    };
+    let mut emitter = Emitter::new();
+    emitter.set_version(opts.target_version);
+    let (opencl_src, panic_table) = match emitter.emit_with_panic_table(&ir_tree) {
+        Ok(pair) => pair,
+        Err(err) => return Err(BuildError::Backend(err)),
+    };
+    let manifest = match Manifest::new(&tree, &ir_tree, &panic_table) {
+        Ok(manifest) => manifest,
+        Err(err) => return Err(BuildError::Backend(err)),
+    };
+    let opencl_path = out_dir.join(format!("{}.cl", opts.name));
+    let manifest_path = out_dir.join(format!("{}.json", opts.name));
+    write_file(&opencl_path, opencl_src.as_str())?;
+    write_file(&manifest_path, manifest.to_json().as_str())?;
+    let rust_bindings_path = if opts.has_rust_bindings {
+        let rust_bindings_src = match emit_manifest_to_rust_bindings(&manifest) {
+            Ok(src) => src,
+            Err(err) => return Err(BuildError::Backend(err)),
+        };
+        let path = out_dir.join(format!("{}.rs", opts.name));
+        write_file(&path, rust_bindings_src.as_str())?;
+        Some(path)
+    } else {
+        None
+    };
+    Ok(BuildOutput {
+            opencl_path,
+            manifest_path,
+            rust_bindings_path,
+    })
+}
+
+pub fn build_or_exit<P: AsRef<Path>>(paths: &[P], opts: &BuildOptions) -> BuildOutput
+{
+    match build(paths, opts) {
+        Ok(output) => output,
+        Err(err) => {
+            eprint!("{}", err.to_rustc_string());
+            process::exit(err.exit_code());
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests;
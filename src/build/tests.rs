@@ -0,0 +1,104 @@
+//
+// Copyright (c) 2025 Łukasz Szpakowski
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+use crate::utils::test_utils::*;
+use super::*;
+
+#[test]
+fn test_build_writes_opencl_source_and_manifest()
+{
+    let dir = temp_dir("build_outputs");
+    let path = dir.join("a.vscfl");
+    fs::write(&path, "import opencl;\nkernel k(xs: UniqGlobalSlice<Int>) -> () =\n    let i = get_global_id(0 as Uint);\n        (x, xs) = xs[i] ->;\n        _ = xs[i] <- (x + 1);\n    in  ();\n").unwrap();
+    let mut opts = BuildOptions::new();
+    opts.out_dir = Some(dir.clone());
+    opts.name = String::from("b");
+    opts.has_rust_bindings = true;
+    match build(&[&path], &opts) {
+        Ok(output) => {
+            assert_eq!(dir.join("b.cl"), output.opencl_path);
+            assert_eq!(dir.join("b.json"), output.manifest_path);
+            assert_eq!(Some(dir.join("b.rs")), output.rust_bindings_path);
+            assert!(fs::read_to_string(&output.opencl_path).unwrap().contains("kernel void _VFH1a1k("));
+            assert!(fs::read_to_string(&output.manifest_path).unwrap().contains("      \"mangled_name\": \"_VFH1a1k\",\n"));
+            assert!(fs::read_to_string(dir.join("b.rs")).unwrap().contains("pub fn set_a_k_args<'a, A: KernelArgs<'a>>("));
+        },
+        Err(err) => {
+            println!("{}", err);
+            panic!();
+        },
+    }
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_build_complains_on_frontend_error()
+{
+    let dir = temp_dir("build_frontend_error");
+    let path = dir.join("a.vscfl");
+    fs::write(&path, "a: Int = 1;\nb: Int = c;\n").unwrap();
+    let mut opts = BuildOptions::new();
+    opts.out_dir = Some(dir.clone());
+    match build(&[&path], &opts) {
+        Err(err @ BuildError::Frontend(_)) => {
+            assert!(!err.is_internal());
+            assert_eq!(EXIT_USER_ERROR, err.exit_code());
+            let path_s = path.to_string_lossy().into_owned();
+            let expected = format!("error: undefined variable c\n --> {}:2:10\n  |\n2 | b: Int = c;\n  |          ^\n\nerror: aborting due to 1 previous error\n", path_s);
+            assert_eq!(expected, err.to_rustc_string());
+        },
+        _ => panic!(),
+    }
+    assert!(!dir.join("kernels.cl").exists());
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_build_complains_on_no_file()
+{
+    let dir = temp_dir("build_no_file");
+    let path = dir.join("a.vscfl");
+    let mut opts = BuildOptions::new();
+    opts.out_dir = Some(dir.clone());
+    match build(&[&path], &opts) {
+        Err(err @ BuildError::Io(_, _)) => {
+            assert!(!err.is_internal());
+            assert!(err.to_rustc_string().starts_with(format!("error: couldn't read or write {}: ", path.to_string_lossy()).as_str()));
+        },
+        _ => panic!(),
+    }
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_build_error_to_rustc_string_formats_internal_errors()
+{
+    let err = BuildError::Backend(BackendError::Internal(String::from("xxx")));
+    assert!(err.is_internal());
+    assert_eq!(String::from("error: internal compiler error: xxx\n\nerror: aborting due to 1 previous error\n"), err.to_rustc_string());
+}
+
+#[test]
+fn test_rerun_paths_returns_sorted_paths_of_sources_and_modules()
+{
+    let dir = temp_dir("build_rerun_paths");
+    let inc_dir = dir.join("inc");
+    fs::create_dir_all(&inc_dir).unwrap();
+    let path = dir.join("b.vscfl");
+    let path2 = dir.join("a.vscfl");
+    fs::write(&path, "import c;\nimport a;\nx: Int = c::y;\n").unwrap();
+    fs::write(&path2, "pub y: Int = 1;\n").unwrap();
+    fs::write(inc_dir.join("c.vscfl"), "pub y: Int = 2;\n").unwrap();
+    let mut opts = BuildOptions::new();
+    opts.include_paths.push(inc_dir.clone());
+    let paths = vec![path.clone(), path2.clone()];
+    let tree = check_sources(paths.as_slice(), &opts).unwrap();
+    let rerun_paths: Vec<String> = rerun_paths(paths.as_slice(), Some(&tree), &opts).into_iter().collect();
+    let expected: Vec<String> = vec![path2, path, inc_dir.join("c.vscfl")].iter().map(|path| path.to_string_lossy().into_owned()).collect();
+    assert_eq!(expected, rerun_paths);
+    fs::remove_dir_all(&dir).unwrap();
+}
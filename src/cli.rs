@@ -0,0 +1,307 @@
+//
+// Copyright (c) 2025 Łukasz Szpakowski
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+use std::fs;
+use std::io;
+use std::path::*;
+use crate::backend::emitter::*;
+use crate::backend::ir::printer::*;
+use crate::backend::lowering::*;
+use crate::backend::manifest::*;
+use crate::build::*;
+use crate::frontend::tree::*;
+use crate::frontend::*;
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum Command
+{
+    Check,
+    Build,
+    EmitIr,
+    Types,
+}
+
+#[derive(Clone, Debug)]
+pub struct Options
+{
+    pub command: Command,
+    pub paths: Vec<PathBuf>,
+    pub output: Option<PathBuf>,
+    pub manifest: Option<PathBuf>,
+    pub include_paths: Vec<PathBuf>,
+    pub has_stdlib: bool,
+    pub target_version: Option<OpenClVersion>,
+}
+
+#[derive(Clone, Debug)]
+pub enum Args
+{
+    Options(Options),
+    Help,
+    Version,
+}
+
+pub fn usage() -> String
+{
+    let mut s = String::new();
+    s.push_str("Usage: vscfl <command> [<option> ...] <file> ...\n");
+    s.push('\n');
+    s.push_str("Commands:\n");
+    s.push_str("  check                   check source files\n");
+    s.push_str("  build                   emit OpenCL C source and kernel manifest\n");
+    s.push_str("  emit-ir                 print intermediate representation\n");
+    s.push_str("  types                   print types of top-level definitions\n");
+    s.push('\n');
+    s.push_str("Options:\n");
+    s.push_str("  -o <file>               write output to <file> (- for standard output)\n");
+    s.push_str("  --manifest <file>       write kernel manifest to <file> (default: output file\n");
+    s.push_str("                          with .json extension, none for standard output)\n");
+    s.push_str("  -I <dir>                search source files in <dir>\n");
+    s.push_str("  --no-stdlib             don't use standard library\n");
+    s.push_str("  --target-version <ver>  require OpenCL C version <ver> (1.2, 2.0, 3.0)\n");
+    s.push_str("  -h, --help              print this help\n");
+    s.push_str("  -V, --version           print version\n");
+    s.push('\n');
+    s.push_str("Exit status:\n");
+    s.push_str("  0                       success\n");
+    s.push_str("  1                       errors in source files\n");
+    s.push_str("  2                       invalid command line\n");
+    s.push_str("  101                     internal compiler error\n");
+    s
+}
+
+pub fn parse_args(args: &[String]) -> Result<Args, String>
+{
+    let mut command: Option<Command> = None;
+    let mut paths: Vec<PathBuf> = Vec::new();
+    let mut output: Option<PathBuf> = None;
+    let mut manifest: Option<PathBuf> = None;
+    let mut include_paths: Vec<PathBuf> = Vec::new();
+    let mut has_stdlib = true;
+    let mut target_version: Option<OpenClVersion> = None;
+    let mut i = 0usize;
+    while i < args.len() {
+        let arg = args[i].as_str();
+        match arg {
+            "-h" | "--help" => return Ok(Args::Help),
+            "-V" | "--version" => return Ok(Args::Version),
+            "-o" | "--manifest" | "-I" | "--target-version" => {
+                let value = match args.get(i + 1) {
+                    Some(value) => value,
+                    None => return Err(format!("option {} requires argument", arg)),
+                };
+                match arg {
+                    "-o" => output = Some(PathBuf::from(value)),
+                    "--manifest" => manifest = Some(PathBuf::from(value)),
+                    "-I" => include_paths.push(PathBuf::from(value)),
+                    _ => {
+                        match OpenClVersion::parse(value.as_str()) {
+                            Some(version) => target_version = Some(version),
+                            None => return Err(format!("unsupported target version {}", value)),
+                        }
+                    },
+                }
+                i += 2;
+                continue;
+            },
+            "--no-stdlib" => has_stdlib = false,
+            _ if arg.starts_with("-I") => include_paths.push(PathBuf::from(&arg[2..])),
+            _ if arg.starts_with('-') && arg != "-" => return Err(format!("unknown option {}", arg)),
+            _ => {
+                if command.is_none() {
+                    command = match arg {
+                        "check" => Some(Command::Check),
+                        "build" => Some(Command::Build),
+                        "emit-ir" => Some(Command::EmitIr),
+                        "types" => Some(Command::Types),
+                        _ => return Err(format!("unknown command {}", arg)),
+                    };
+                } else {
+                    paths.push(PathBuf::from(arg));
+                }
+            },
+        }
+        i += 1;
+    }
+    let command = match command {
+        Some(tmp_command) => tmp_command,
+        None => return Err(String::from("no command")),
+    };
+    if paths.is_empty() {
+        return Err(String::from("no input files"));
+    }
+    if manifest.is_some() && command != Command::Build {
+        return Err(String::from("option --manifest requires build command"));
+    }
+    Ok(Args::Options(Options {
+                command,
+                paths,
+                output,
+                manifest,
+                include_paths,
+                has_stdlib,
+                target_version,
+    }))
+}
+
+fn top_level_types(tree: &Tree, srcs: &[Source]) -> String
+{
+    let paths: Vec<&String> = srcs.iter().map(|src| {
+            match src {
+                Source::String(path, _) | Source::File(path) => path,
+            }
+    }).collect();
+    let mut s = String::new();
+    for def in tree.defs() {
+        match &**def {
+            Def::Var(_, ident, var, pos) if paths.contains(&&*pos.path) => {
+                let typ = match &*var.borrow() {
+                    Var::Builtin(_, Some(typ)) => typ.to_string(),
+                    Var::Var(_, _, _, _, _, _, _, Some(typ), _) => typ.to_string(),
+                    Var::Fun(_, _, Some(typ)) => typ.to_string(),
+                    _ => continue,
+                };
+                s.push_str(format!("{}: {}\n", ident, typ).as_str());
+            },
+            _ => (),
+        }
+    }
+    s
+}
+
+fn compile(opts: &Options) -> BuildResult<Option<(String, Option<String>)>>
+{
+    let srcs = read_sources(opts.paths.as_slice(), opts.include_paths.as_slice())?;
+    let tree = if opts.has_stdlib {
+        do_frontend_phases_with_sources_and_include_paths(srcs.as_slice(), opts.include_paths.as_slice())
+    } else {
+        do_frontend_phases_with_sources_and_include_paths_without_stdlib(srcs.as_slice(), opts.include_paths.as_slice())
+    };
+    let tree = match tree {
+        Ok(tree) => tree,
+        Err(errs) => return Err(BuildError::Frontend(errs)),
+    };
+    match opts.command {
+        Command::Check => Ok(None),
+        Command::Types => Ok(Some((top_level_types(&tree, srcs.as_slice()), None))),
+        Command::EmitIr | Command::Build => {
+            let ir_tree = match lower_tree(&tree) {
+                Ok(ir_tree) => ir_tree,
+                Err(err) => return Err(BuildError::Backend(err)),
+            };
+            if opts.command == Command::EmitIr {
+                return Ok(Some((ir_tree_to_string(&ir_tree), None)));
+            }
+            let mut emitter = Emitter::new();
+            emitter.set_version(opts.target_version);
+            let (src, panic_table) = match emitter.emit_with_panic_table(&ir_tree) {
+                Ok(pair) => pair,
+                Err(err) => return Err(BuildError::Backend(err)),
+            };
+            match Manifest::new(&tree, &ir_tree, &panic_table) {
+                Ok(manifest) => Ok(Some((src, Some(manifest.to_json())))),
+                Err(err) => Err(BuildError::Backend(err)),
+            }
+        },
+    }
+}
+
+fn output_path(opts: &Options) -> Option<PathBuf>
+{
+    match (&opts.output, opts.command) {
+        (Some(path), _) if path.as_os_str() == "-" => None,
+        (Some(path), _) => Some(path.clone()),
+        (None, Command::Build) => opts.paths.first().map(|path| path.with_extension("cl")),
+        (None, _) => None,
+    }
+}
+
+fn manifest_path(opts: &Options) -> Option<PathBuf>
+{
+    match (&opts.manifest, opts.command) {
+        (Some(path), _) => Some(path.clone()),
+        (None, Command::Build) => output_path(opts).map(|path| path.with_extension("json")),
+        (None, _) => None,
+    }
+}
+
+fn write_output(path: &Path, s: &str, err: &mut dyn io::Write) -> io::Result<Option<i32>>
+{
+    match fs::write(path, s) {
+        Ok(()) => Ok(None),
+        Err(io_err) => {
+            let build_err = BuildError::Io(path.to_string_lossy().into_owned(), io_err);
+            write!(err, "{}", build_err.to_rustc_string())?;
+            Ok(Some(build_err.exit_code()))
+        },
+    }
+}
+
+fn run_with_output_and_io_result(args: &[String], out: &mut dyn io::Write, err: &mut dyn io::Write) -> io::Result<i32>
+{
+    let opts = match parse_args(args) {
+        Ok(Args::Options(opts)) => opts,
+        Ok(Args::Help) => {
+            write!(out, "{}", usage())?;
+            return Ok(EXIT_SUCCESS);
+        },
+        Ok(Args::Version) => {
+            writeln!(out, "vscfl {}", env!("CARGO_PKG_VERSION"))?;
+            return Ok(EXIT_SUCCESS);
+        },
+        Err(msg) => {
+            writeln!(err, "error: {}", msg)?;
+            writeln!(err)?;
+            write!(err, "{}", usage())?;
+            return Ok(EXIT_USAGE_ERROR);
+        },
+    };
+    let (s, manifest_json) = match compile(&opts) {
+        Ok(Some(pair)) => pair,
+        Ok(None) => return Ok(EXIT_SUCCESS),
+        Err(build_err) => {
+            write!(err, "{}", build_err.to_rustc_string())?;
+            return Ok(build_err.exit_code());
+        },
+    };
+    match output_path(&opts) {
+        Some(path) => {
+            if let Some(code) = write_output(&path, s.as_str(), err)? {
+                return Ok(code);
+            }
+        },
+        None => {
+            write!(out, "{}", s)?;
+            out.flush()?;
+        },
+    }
+    if let (Some(path), Some(manifest_json)) = (manifest_path(&opts), manifest_json) {
+        if let Some(code) = write_output(&path, manifest_json.as_str(), err)? {
+            return Ok(code);
+        }
+    }
+    Ok(EXIT_SUCCESS)
+}
+
+pub fn run_with_output(args: &[String], out: &mut dyn io::Write, err: &mut dyn io::Write) -> i32
+{
+    match run_with_output_and_io_result(args, out, err) {
+        Ok(code) => code,
+        Err(io_err) if io_err.kind() == io::ErrorKind::BrokenPipe => EXIT_SUCCESS,
+        Err(_) => EXIT_INTERNAL_ERROR,
+    }
+}
+
+pub fn run(args: &[String]) -> i32
+{
+    let stdout = io::stdout();
+    let stderr = io::stderr();
+    run_with_output(args, &mut stdout.lock(), &mut stderr.lock())
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,134 @@
+//
+// Copyright (c) 2025 Łukasz Szpakowski
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+use crate::utils::test_utils::*;
+use super::*;
+
+struct FailingWriter(io::ErrorKind);
+
+impl io::Write for FailingWriter
+{
+    fn write(&mut self, _buf: &[u8]) -> io::Result<usize>
+    { Err(io::Error::new(self.0, "failing writer")) }
+
+    fn flush(&mut self) -> io::Result<()>
+    { Ok(()) }
+}
+
+fn run_strings(args: &[&str]) -> (i32, String, String)
+{
+    let args2: Vec<String> = args.iter().map(|arg| String::from(*arg)).collect();
+    let mut out: Vec<u8> = Vec::new();
+    let mut err: Vec<u8> = Vec::new();
+    let code = run_with_output(args2.as_slice(), &mut out, &mut err);
+    (code, String::from_utf8(out).unwrap(), String::from_utf8(err).unwrap())
+}
+
+#[test]
+fn test_parse_args_parses_options()
+{
+    let args: Vec<String> = ["build", "-o", "b.cl", "--manifest", "m.json", "-I", "inc", "-Iinc2", "--no-stdlib", "--target-version", "2.0", "a.vscfl"].iter().map(|arg| String::from(*arg)).collect();
+    match parse_args(args.as_slice()) {
+        Ok(Args::Options(opts)) => {
+            assert_eq!(Command::Build, opts.command);
+            assert_eq!(vec![PathBuf::from("a.vscfl")], opts.paths);
+            assert_eq!(Some(PathBuf::from("b.cl")), opts.output);
+            assert_eq!(Some(PathBuf::from("m.json")), opts.manifest);
+            assert_eq!(vec![PathBuf::from("inc"), PathBuf::from("inc2")], opts.include_paths);
+            assert!(!opts.has_stdlib);
+            assert_eq!(Some(OpenClVersion::V2_0), opts.target_version);
+        },
+        _ => panic!(),
+    }
+}
+
+#[test]
+fn test_parse_args_complains_on_invalid_arguments()
+{
+    let cases: &[(&[&str], &str)] = &[
+        (&["check"], "no input files"),
+        (&["a.vscfl"], "unknown command a.vscfl"),
+        (&["check", "--xxx", "a.vscfl"], "unknown option --xxx"),
+        (&["check", "a.vscfl", "-o"], "option -o requires argument"),
+        (&["build", "--target-version", "1.1", "a.vscfl"], "unsupported target version 1.1"),
+        (&["check", "--manifest", "m.json", "a.vscfl"], "option --manifest requires build command"),
+    ];
+    for (args, msg) in cases {
+        let args2: Vec<String> = args.iter().map(|arg| String::from(*arg)).collect();
+        match parse_args(args2.as_slice()) {
+            Err(msg2) => assert_eq!(String::from(*msg), msg2),
+            _ => panic!(),
+        }
+    }
+}
+
+#[test]
+fn test_run_with_output_runs_commands()
+{
+    let dir = temp_dir("cli_commands");
+    let inc_dir = dir.join("inc");
+    fs::create_dir_all(&inc_dir).unwrap();
+    fs::write(inc_dir.join("a.vscfl"), "import opencl;\na: Int = 10;\nf(x: Int) -> Int = x + a;\nkernel k(xs: UniqGlobalSlice<Int>) -> () =\n    let i = get_global_id(0 as Uint);\n        (x, xs) = xs[i] ->;\n        _ = xs[i] <- f(x);\n    in  ();\n").unwrap();
+    let inc_dir_s = inc_dir.to_string_lossy().into_owned();
+    let (code, out, err) = run_strings(&["check", "-I", inc_dir_s.as_str(), "a.vscfl"]);
+    assert_eq!((EXIT_SUCCESS, String::new(), String::new()), (code, out, err));
+    let (code, out, _) = run_strings(&["types", "-I", inc_dir_s.as_str(), "a.vscfl"]);
+    assert_eq!(EXIT_SUCCESS, code);
+    assert_eq!(String::from("a::a: Int\na::f: (Int) -> Int\na::k: (UniqGlobalSlice<Int>) -> ()\n"), out);
+    let (code, out, _) = run_strings(&["emit-ir", "-I", inc_dir_s.as_str(), "a.vscfl"]);
+    assert_eq!(EXIT_SUCCESS, code);
+    assert!(out.contains("kernel fun @_VFH1a1k("));
+    let cl_path = dir.join("b.cl");
+    let cl_path_s = cl_path.to_string_lossy().into_owned();
+    let (code, _, _) = run_strings(&["build", "-I", inc_dir_s.as_str(), "--target-version", "1.2", "-o", cl_path_s.as_str(), "a.vscfl"]);
+    assert_eq!(EXIT_SUCCESS, code);
+    let src = fs::read_to_string(&cl_path).unwrap();
+    assert!(src.starts_with("#if !defined(__OPENCL_C_VERSION__) || __OPENCL_C_VERSION__ < 120\n"));
+    assert!(src.contains("kernel void _VFH1a1k("));
+    let manifest_json = fs::read_to_string(dir.join("b.json")).unwrap();
+    assert!(manifest_json.contains("      \"mangled_name\": \"_VFH1a1k\",\n"));
+    let manifest_path = dir.join("m.json");
+    let manifest_path_s = manifest_path.to_string_lossy().into_owned();
+    let (code, out, _) = run_strings(&["build", "-I", inc_dir_s.as_str(), "-o", "-", "--manifest", manifest_path_s.as_str(), "a.vscfl"]);
+    assert_eq!(EXIT_SUCCESS, code);
+    assert!(out.contains("kernel void _VFH1a1k("));
+    assert_eq!(manifest_json, fs::read_to_string(&manifest_path).unwrap());
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_run_with_output_returns_exit_codes_for_errors()
+{
+    let dir = temp_dir("cli_errors");
+    let path = dir.join("a.vscfl");
+    fs::write(&path, "a: Int = b;\n").unwrap();
+    let path_s = path.to_string_lossy().into_owned();
+    let (code, _, err) = run_strings(&["check", path_s.as_str()]);
+    assert_eq!(EXIT_USER_ERROR, code);
+    assert!(err.starts_with("error: undefined variable b\n"));
+    let (code, _, err) = run_strings(&["check", "--no-stdlib", path_s.as_str()]);
+    assert_eq!(EXIT_USER_ERROR, code);
+    assert!(err.contains("error: undefined type variable Int\n"));
+    let (code, _, err) = run_strings(&["check"]);
+    assert_eq!(EXIT_USAGE_ERROR, code);
+    assert!(err.starts_with("error: no input files\n\nUsage: vscfl "));
+    let (code, out, _) = run_strings(&["--help"]);
+    assert_eq!(EXIT_SUCCESS, code);
+    assert_eq!(usage(), out);
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_run_with_output_handles_output_errors()
+{
+    let args = vec![String::from("--help")];
+    let mut err: Vec<u8> = Vec::new();
+    let code = run_with_output(args.as_slice(), &mut FailingWriter(io::ErrorKind::BrokenPipe), &mut err);
+    assert_eq!(EXIT_SUCCESS, code);
+    let code = run_with_output(args.as_slice(), &mut FailingWriter(io::ErrorKind::Other), &mut err);
+    assert_eq!(EXIT_INTERNAL_ERROR, code);
+}
@@ -5,6 +5,7 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 //
+use std::path::*;
 use crate::stdlib::*;
 
 pub mod builtins;
@@ -16,8 +17,10 @@ pub mod lexer;
 pub mod limiter;
 pub mod namer;
 pub mod parser;
+pub mod printf;
 pub(crate) mod private;
 pub mod recurser;
+pub mod resolver;
 pub mod source;
 pub mod shared_flag;
 pub mod tree;
@@ -29,27 +32,99 @@ pub use source::Source;
 pub use tree::Tree;
 pub use error::FrontendResultWithErrors;
 
+use error::FrontendError;
 use error::FrontendErrors;
+use error::Pos;
+use tree::Import;
+use tree::ModuleKind;
 
-pub fn do_frontend_phases_with_sources_without_stdlib(srcs: &[Source]) -> FrontendResultWithErrors<Tree>
+fn parse_source(src: &Source, tree: &mut Tree) -> FrontendResultWithErrors<String>
 {
-    let mut tree = Tree::new();
+    match src {
+        Source::String(path, s) => {
+            match parser::parse_with_path(path.as_str(), s.as_str(), tree) {
+                Ok(()) => Ok(path.clone()),
+                Err(err) => Err(FrontendErrors::new(vec![err])),
+            }
+        },
+        Source::File(path) => {
+            match parser::parse_from_file(path.as_str(), tree) {
+                Ok(()) => Ok(path.clone()),
+                Err(err) => Err(FrontendErrors::new(vec![err])),
+            }
+        },
+    }
+}
+
+fn find_module_path(module_ident: &str, importing_path: &str, include_paths: &[PathBuf]) -> Option<PathBuf>
+{
+    let file_name = format!("{}.vscfl", module_ident);
+    let mut dir_paths: Vec<PathBuf> = Vec::new();
+    if let Some(dir_path) = Path::new(importing_path).parent() {
+        dir_paths.push(dir_path.to_path_buf());
+    }
+    dir_paths.extend_from_slice(include_paths);
+    dir_paths.iter().map(|dir_path| dir_path.join(file_name.as_str())).find(|path| path.is_file())
+}
+
+fn parse_stdlib_module(module_ident: &String, stdlib_modules: &[(String, Vec<Source>)], tree: &mut Tree, paths: &mut Vec<String>) -> FrontendResultWithErrors<bool>
+{
+    match stdlib_modules.iter().find(|(stdlib_module_ident, _)| stdlib_module_ident == module_ident) {
+        Some((_, srcs)) => {
+            for src in srcs {
+                let path = parse_source(src, tree)?;
+                tree.add_module_path(module_ident.clone(), ModuleKind::Stdlib, path.clone());
+                paths.push(path);
+            }
+            Ok(true)
+        },
+        None => Ok(false),
+    }
+}
+
+fn parse_sources_and_modules(stdlib_modules: &[(String, Vec<Source>)], srcs: &[Source], include_paths: &[PathBuf], tree: &mut Tree) -> FrontendResultWithErrors<()>
+{
+    let mut paths: Vec<String> = Vec::new();
+    for module_ident in resolver::PRELUDE_MODULE_IDENTS {
+        parse_stdlib_module(&String::from(*module_ident), stdlib_modules, tree, &mut paths)?;
+    }
     for src in srcs {
-        match src {
-            Source::String(path, s) => {
-                match parser::parse_with_path(path.as_str(), s.as_str(), &mut tree) {
-                    Ok(()) => (),
-                    Err(err) => return Err(FrontendErrors::new(vec![err])),
-                }
-            },
-            Source::File(path) => {
-                match parser::parse_from_file(path.as_str(), &mut tree) {
-                    Ok(()) => (),
-                    Err(err) => return Err(FrontendErrors::new(vec![err])),
-                }
-            },
+        let path = parse_source(src, tree)?;
+        if let Some(module_ident) = resolver::module_ident_for_path(path.as_str()) {
+            if stdlib_modules.iter().any(|(stdlib_module_ident, _)| *stdlib_module_ident == module_ident) {
+                let pos = Pos::new(path.clone(), 1, 1);
+                return Err(FrontendErrors::new(vec![FrontendError::Message(pos, format!("module {} is already defined in standard library", module_ident))]));
+            }
+            tree.add_module_path(module_ident, ModuleKind::User, path.clone());
+        }
+        paths.push(path);
+    }
+    let mut i = 0usize;
+    while i < paths.len() {
+        let imports = tree.imports(&paths[i]).to_vec();
+        for Import(module_ident, _) in imports {
+            if tree.module(&module_ident).is_some() {
+                continue;
+            }
+            if parse_stdlib_module(&module_ident, stdlib_modules, tree, &mut paths)? {
+                continue;
+            }
+            if let Some(module_path) = find_module_path(module_ident.as_str(), paths[i].as_str(), include_paths) {
+                let module_path = parse_source(&Source::File(module_path.to_string_lossy().into_owned()), tree)?;
+                tree.add_module_path(module_ident, ModuleKind::User, module_path.clone());
+                paths.push(module_path);
+            }
         }
+        i += 1;
     }
+    Ok(())
+}
+
+fn do_frontend_phases_with_stdlib_modules(stdlib_modules: &[(String, Vec<Source>)], srcs: &[Source], include_paths: &[PathBuf]) -> FrontendResultWithErrors<Tree>
+{
+    let mut tree = Tree::new();
+    parse_sources_and_modules(stdlib_modules, srcs, include_paths, &mut tree)?;
+    resolver::resolve_idents(&mut tree)?;
     namer::check_idents(&mut tree)?;
     typer::check_types(&tree)?;
     instancer::check_insts(&tree)?;
@@ -59,12 +134,17 @@ pub fn do_frontend_phases_with_sources_without_stdlib(srcs: &[Source]) -> Fronte
     Ok(tree)
 }
 
+pub fn do_frontend_phases_with_sources_and_include_paths_without_stdlib(srcs: &[Source], include_paths: &[PathBuf]) -> FrontendResultWithErrors<Tree>
+{ do_frontend_phases_with_stdlib_modules(&[], srcs, include_paths) }
+
+pub fn do_frontend_phases_with_sources_and_include_paths(srcs: &[Source], include_paths: &[PathBuf]) -> FrontendResultWithErrors<Tree>
+{ do_frontend_phases_with_stdlib_modules(stdlib_modules().as_slice(), srcs, include_paths) }
+
+pub fn do_frontend_phases_with_sources_without_stdlib(srcs: &[Source]) -> FrontendResultWithErrors<Tree>
+{ do_frontend_phases_with_sources_and_include_paths_without_stdlib(srcs, &[]) }
+
 pub fn do_frontend_phases_with_sources(srcs: &[Source]) -> FrontendResultWithErrors<Tree>
-{
-    let mut srcs_with_stdlib = stdlib_sources();
-    srcs_with_stdlib.extend_from_slice(srcs);
-    do_frontend_phases_with_sources_without_stdlib(srcs_with_stdlib.as_slice())
-}
+{ do_frontend_phases_with_sources_and_include_paths(srcs, &[]) }
 
 pub fn do_frontend_phases_with_path(path: &str, src: &str) -> FrontendResultWithErrors<Tree>
 {
@@ -7,6 +7,8 @@
 //
 use std::collections::HashMap;
 use std::collections::HashSet;
+use crate::frontend::resolver::unqualified_ident;
+use crate::frontend::resolver::unqualified_type_name;
 use crate::frontend::tree::*;
 
 #[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
@@ -133,11 +135,14 @@ impl Builtins
         type_vars.insert(String::from("UniqPrivateRef"), BuiltinTypeVar::new(String::from("t"), Vec::new(), Vec::new(), SharedFlag::None, RefTypeFlag::Ref, false, false));
         type_vars.insert(String::from("UniqLocalRef"), BuiltinTypeVar::new(String::from("t"), Vec::new(), Vec::new(), SharedFlag::None, RefTypeFlag::Ref, false, false));
         type_vars.insert(String::from("UniqGlobalRef"), BuiltinTypeVar::new(String::from("t"), Vec::new(), Vec::new(), SharedFlag::None, RefTypeFlag::Ref, false, false));
+        type_vars.insert(String::from("UniqPrivateBox"), BuiltinTypeVar::new(String::from("t"), Vec::new(), Vec::new(), SharedFlag::None, RefTypeFlag::Ref, false, false));
+        type_vars.insert(String::from("UniqLocalBox"), BuiltinTypeVar::new(String::from("t"), Vec::new(), Vec::new(), SharedFlag::None, RefTypeFlag::Ref, false, false));
+        type_vars.insert(String::from("UniqGlobalBox"), BuiltinTypeVar::new(String::from("t"), Vec::new(), Vec::new(), SharedFlag::None, RefTypeFlag::Ref, false, false));
         type_vars.insert(String::from("Slice"), BuiltinTypeVar::new(String::from("t"), Vec::new(), Vec::new(), SharedFlag::Shared, RefTypeFlag::Slice, false, true));
         type_vars.insert(String::from("PrivateSlice"), BuiltinTypeVar::new(String::from("t"), Vec::new(), Vec::new(), SharedFlag::Shared, RefTypeFlag::Slice, false, true));
         type_vars.insert(String::from("LocalSlice"), BuiltinTypeVar::new(String::from("t"), Vec::new(), Vec::new(), SharedFlag::Shared, RefTypeFlag::Slice, false, false));
         type_vars.insert(String::from("GlobalSlice"), BuiltinTypeVar::new(String::from("t"), Vec::new(), Vec::new(), SharedFlag::Shared, RefTypeFlag::Slice, false, true));
-        type_vars.insert(String::from("ConstantSlice"), BuiltinTypeVar::new(String::from("t"), Vec::new(), Vec::new(), SharedFlag::Shared, RefTypeFlag::Slice, false, false));
+        type_vars.insert(String::from("ConstantSlice"), BuiltinTypeVar::new(String::from("t"), Vec::new(), Vec::new(), SharedFlag::Shared, RefTypeFlag::Slice, false, true));
         type_vars.insert(String::from("UniqSlice"), BuiltinTypeVar::new(String::from("t"), Vec::new(), Vec::new(), SharedFlag::None, RefTypeFlag::Slice, false, true));
         type_vars.insert(String::from("UniqPrivateSlice"), BuiltinTypeVar::new(String::from("t"), Vec::new(), Vec::new(), SharedFlag::None, RefTypeFlag::Slice, false, true));
         type_vars.insert(String::from("UniqLocalSlice"), BuiltinTypeVar::new(String::from("t"), Vec::new(), Vec::new(), SharedFlag::None, RefTypeFlag::Slice, false, false));
@@ -175,6 +180,9 @@ impl Builtins
         vars.insert(String::from("uniq_private_ref"), BuiltinVar::new(String::from("(t) -> UniqPrivateRef<t>"), String::new()));
         vars.insert(String::from("uniq_local_ref"), BuiltinVar::new(String::from("(t) -> UniqLocalRef<t>"), String::new()));
         vars.insert(String::from("uniq_global_ref"), BuiltinVar::new(String::from("(t) -> UniqGlobalRef<t>"), String::new()));
+        vars.insert(String::from("uniq_private_box"), BuiltinVar::new(String::from("(t) -> UniqPrivateBox<t>"), String::new()));
+        vars.insert(String::from("uniq_local_box"), BuiltinVar::new(String::from("(t) -> UniqLocalBox<t>"), String::new()));
+        vars.insert(String::from("uniq_global_box"), BuiltinVar::new(String::from("(t) -> UniqGlobalBox<t>"), String::new()));
         vars.insert(String::from("ref_from_uniq"), BuiltinVar::new(String::from("(UniqRef<t>) -> Ref<t>"), String::new()));
         vars.insert(String::from("private_ref_from_uniq"), BuiltinVar::new(String::from("(UniqPrivateRef<t>) -> PrivateRef<t>"), String::new()));
         vars.insert(String::from("local_ref_from_uniq"), BuiltinVar::new(String::from("(UniqLocalRef<t>) -> LocalRef<t>"), String::new()));
@@ -258,7 +266,7 @@ impl Builtins
                     if u != "Constant" {
                         vars.insert(format!("{}_{}_vload_half{}_uniq", u.to_lowercase(), s.to_lowercase(), t), BuiltinVar::new(format!("(SizeT, Uniq{}Slice<Half>) -> ({}{}, Uniq{}Slice<Half>)", u, s, t, u), String::new()));
                     }
-                    if t != "" {
+                    if !t.is_empty() {
                         vars.insert(format!("{}_{}_vloada_half{}", u.to_lowercase(), s.to_lowercase(), t), BuiltinVar::new(format!("(SizeT, {}Slice<Half>) -> {}{}", u, s, t), String::new()));
                         if u != "Constant" {
                             vars.insert(format!("{}_{}_vloada_half{}_uniq", u.to_lowercase(), s.to_lowercase(), t), BuiltinVar::new(format!("(SizeT, Uniq{}Slice<Half>) -> ({}{}, Uniq{}Slice<Half>)", u, s, t, u), String::new()));
@@ -275,7 +283,7 @@ impl Builtins
                     vars.insert(format!("{}_{}_vstore_half{}_rtz", u.to_lowercase(), s.to_lowercase(), t), BuiltinVar::new(format!("({}{}, SizeT, Uniq{}Slice<Half>) -> Uniq{}Slice<Half>", s, t, u, u), String::new()));
                     vars.insert(format!("{}_{}_vstore_half{}_rtp", u.to_lowercase(), s.to_lowercase(), t), BuiltinVar::new(format!("({}{}, SizeT, Uniq{}Slice<Half>) -> Uniq{}Slice<Half>", s, t, u, u), String::new()));
                     vars.insert(format!("{}_{}_vstore_half{}_rtn", u.to_lowercase(), s.to_lowercase(), t), BuiltinVar::new(format!("({}{}, SizeT, Uniq{}Slice<Half>) -> Uniq{}Slice<Half>", s, t, u, u), String::new()));
-                    if t != "" {
+                    if !t.is_empty() {
                         vars.insert(format!("{}_{}_vstorea_half{}", u.to_lowercase(), s.to_lowercase(), t), BuiltinVar::new(format!("({}{}, SizeT, Uniq{}Slice<Half>) -> Uniq{}Slice<Half>", s, t, u, u), String::new()));
                         vars.insert(format!("{}_{}_vstorea_half{}_rte", u.to_lowercase(), s.to_lowercase(), t), BuiltinVar::new(format!("({}{}, SizeT, Uniq{}Slice<Half>) -> Uniq{}Slice<Half>", s, t, u, u), String::new()));
                         vars.insert(format!("{}_{}_vstorea_half{}_rtz", u.to_lowercase(), s.to_lowercase(), t), BuiltinVar::new(format!("({}{}, SizeT, Uniq{}Slice<Half>) -> Uniq{}Slice<Half>", s, t, u, u), String::new()));
@@ -419,15 +427,15 @@ impl Builtins
             }
         }
         // OpGet
-        for s in ["Ref", "PrivateRef", "LocalRef", "GlobalRef", "ConstantRef", "UniqRef", "UniqPrivateRef", "UniqLocalRef", "UniqGlobalRef"] {
+        for s in ["Ref", "PrivateRef", "LocalRef", "GlobalRef", "ConstantRef", "UniqRef", "UniqPrivateRef", "UniqLocalRef", "UniqGlobalRef", "UniqPrivateBox", "UniqLocalBox", "UniqGlobalBox"] {
             impl_pairs.insert((String::from("OpGet"), TypeName::Name(String::from(s))));
         }
         // OpSet
-        for s in ["UniqRef", "UniqPrivateRef", "UniqLocalRef", "UniqGlobalRef"] {
+        for s in ["UniqRef", "UniqPrivateRef", "UniqLocalRef", "UniqGlobalRef", "UniqPrivateBox", "UniqLocalBox", "UniqGlobalBox"] {
             impl_pairs.insert((String::from("OpSet"), TypeName::Name(String::from(s))));
         }
         // OpUpdate
-        for s in ["UniqRef", "UniqPrivateRef", "UniqLocalRef", "UniqGlobalRef"] {
+        for s in ["UniqRef", "UniqPrivateRef", "UniqLocalRef", "UniqGlobalRef", "UniqPrivateBox", "UniqLocalBox", "UniqGlobalBox"] {
             impl_pairs.insert((String::from("OpUpdate"), TypeName::Name(String::from(s))));
         }
         // OpGetNth
@@ -976,8 +984,8 @@ impl Builtins
     pub fn type_vars(&self) -> &HashMap<String, BuiltinTypeVar>
     { &self.type_vars }
 
-    pub fn type_var(&self, ident: &String) -> Option<&BuiltinTypeVar>
-    { self.type_vars.get(ident) }
+    pub fn type_var(&self, ident: &str) -> Option<&BuiltinTypeVar>
+    { self.type_vars.get(unqualified_ident(ident)) }
 
     pub fn add_type_var(&mut self, ident: String, type_var: BuiltinTypeVar)
     { self.type_vars.insert(ident, type_var); }
@@ -988,8 +996,8 @@ impl Builtins
     pub fn vars(&self) -> &HashMap<String, BuiltinVar>
     { &self.vars }
 
-    pub fn var(&self, ident: &String) -> Option<&BuiltinVar>
-    { self.vars.get(ident) }
+    pub fn var(&self, ident: &str) -> Option<&BuiltinVar>
+    { self.vars.get(unqualified_ident(ident)) }
 
     pub fn add_var(&mut self, ident: String, var: BuiltinVar)
     { self.vars.insert(ident, var); }
@@ -1001,7 +1009,7 @@ impl Builtins
     { &self.impl_pairs }
     
     pub fn has_impl_pair(&self, impl_pair: &(String, TypeName)) -> bool
-    { self.impl_pairs.contains(impl_pair) }
+    { self.impl_pairs.contains(&(String::from(unqualified_ident(impl_pair.0.as_str())), unqualified_type_name(&impl_pair.1))) }
 
     pub fn add_impl_pair(&mut self, impl_pair: (String, TypeName))
     { self.impl_pairs.insert(impl_pair); }
@@ -1013,7 +1021,7 @@ impl Builtins
     { &self.impl_var_tuples }
 
     pub fn has_impl_var_tuple(&self, impl_var_tuple: &(String, TypeName, String)) -> bool
-    { self.impl_var_tuples.contains(impl_var_tuple) }
+    { self.impl_var_tuples.contains(&(String::from(unqualified_ident(impl_var_tuple.0.as_str())), unqualified_type_name(&impl_var_tuple.1), String::from(unqualified_ident(impl_var_tuple.2.as_str())))) }
 
     pub fn add_impl_var_tuple(&mut self, impl_var_tuple: (String, TypeName, String))
     { self.impl_var_tuples.insert(impl_var_tuple); }
@@ -11,6 +11,8 @@ use std::cmp::min;
 use std::collections::HashMap;
 use std::rc::*;
 use crate::frontend::error::*;
+use crate::frontend::resolver::unqualified_ident;
+use crate::frontend::resolver::unqualified_type_name;
 use crate::frontend::tree::*;
 
 fn char_n(arg_values: &[Value], _ref_values: &mut RefValues, pos: &Pos) -> FrontendResult<Value>
@@ -2183,10 +2185,8 @@ impl Evals
 
     pub fn fun(&self, key: &(String, Option<TypeName>)) -> Option<fn(&[Value], &mut RefValues, &Pos) -> FrontendResult<Value>>
     {
-        match self.funs.get(key) {
-            Some(fun) => Some(*fun),
-            None => None,
-        }
+        let key2 = (String::from(unqualified_ident(key.0.as_str())), key.1.as_ref().map(unqualified_type_name));
+        self.funs.get(&key2).copied()
     }
     
     pub fn add_fun(&mut self, key: (String, Option<TypeName>), fun: fn(&[Value], &mut RefValues, &Pos) -> FrontendResult<Value>)
@@ -79,7 +79,7 @@ fn add_var_key(ident: &String, type_name: &Option<TypeName>, pos: Pos, tree: &Tr
                                     match &*trait_r {
                                         Trait(_, _, Some(trait_vars)) => {
                                             let type_name2 = match type_name {
-                                                TypeName::Array(Some(_)) if trait_vars.impl1(&type_name).is_none() => TypeName::Array(None),
+                                                TypeName::Array(Some(_)) if trait_vars.impl1(type_name).is_none() => TypeName::Array(None),
                                                 _ => type_name.clone(),
                                             };
                                             match trait_vars.impl1(&type_name2) {
@@ -150,7 +150,7 @@ fn do_var_for_var_key<T, F>(key: &(String, Option<TypeName>), tree: &Tree, z: T,
                                     let trait_r = trait1.borrow();
                                     match &*trait_r {
                                         Trait(_, _, Some(trait_vars)) => {
-                                            match trait_vars.impl1(&type_name) {
+                                            match trait_vars.impl1(type_name) {
                                                 Some(impl1) => {
                                                     let impl_r = impl1.borrow();
                                                     let impl_vars = match &*impl_r {
@@ -162,7 +162,7 @@ fn do_var_for_var_key<T, F>(key: &(String, Option<TypeName>), tree: &Tree, z: T,
                                                         Some(impl_var) => {
                                                             let impl_var_r = impl_var.borrow();
                                                             match &*impl_var_r {
-                                                                ImplVar::Var(expr2, _, Some(local_types2), Some(type2), value2) => f(&**expr2, &**local_types2, &**type2, value2),
+                                                                ImplVar::Var(expr2, _, Some(local_types2), Some(type2), value2) => f(expr2, local_types2, type2, value2),
                                                                 _ => Err(FrontendErrors::new(vec![FrontendError::Internal(String::from("do_var_for_var_key: implementation variable isn't variable or no local types or no type"))])),
                                                             }
                                                         },
@@ -182,7 +182,7 @@ fn do_var_for_var_key<T, F>(key: &(String, Option<TypeName>), tree: &Tree, z: T,
                             match var_tuple {
                                 Some((expr, Some(local_types), typ, value)) => {
                                     match expr {
-                                        Some(expr) => f(&**expr, &**local_types, &**typ, value),
+                                        Some(expr) => f(expr, local_types, typ, value),
                                         None => Ok(z),
                                     }
                                 },
@@ -196,7 +196,7 @@ fn do_var_for_var_key<T, F>(key: &(String, Option<TypeName>), tree: &Tree, z: T,
                     match var_tuple {
                         Some((expr, Some(local_types), typ, value)) => {
                             match expr {
-                                Some(expr) => f(&**expr, &**local_types, &**typ, value),
+                                Some(expr) => f(expr, local_types, typ, value),
                                 None => Ok(z),
                             }
                         },
@@ -230,7 +230,7 @@ fn do_var_mut_for_var_key<T, F>(key: &(String, Option<TypeName>), tree: &Tree, z
                                     let trait_r = trait1.borrow();
                                     match &*trait_r {
                                         Trait(_, _, Some(trait_vars)) => {
-                                            match trait_vars.impl1(&type_name) {
+                                            match trait_vars.impl1(type_name) {
                                                 Some(impl1) => {
                                                     let impl_r = impl1.borrow();
                                                     let impl_vars = match &*impl_r {
@@ -242,7 +242,7 @@ fn do_var_mut_for_var_key<T, F>(key: &(String, Option<TypeName>), tree: &Tree, z
                                                         Some(impl_var) => {
                                                             let mut impl_var_r = impl_var.borrow_mut();
                                                             match &mut *impl_var_r {
-                                                                ImplVar::Var(expr2, _, Some(local_types2), Some(type2), value2) => f(&mut **expr2, &**local_types2,  &**type2, value2),
+                                                                ImplVar::Var(expr2, _, Some(local_types2), Some(type2), value2) => f(expr2, local_types2,  type2, value2),
                                                                 _ => Err(FrontendErrors::new(vec![FrontendError::Internal(String::from("do_var_mut_for_var_key: implementation variable isn't variable or no local types or no type"))])),
                                                             }
                                                         },
@@ -262,7 +262,7 @@ fn do_var_mut_for_var_key<T, F>(key: &(String, Option<TypeName>), tree: &Tree, z
                             match var_tuple {
                                 Some((expr, Some(local_types), typ, value)) => {
                                     match expr {
-                                        Some(expr) => f(&mut **expr, &**local_types, &**typ, value),
+                                        Some(expr) => f(expr, local_types, typ, value),
                                         None => Ok(z),
                                     }
                                 },
@@ -276,7 +276,7 @@ fn do_var_mut_for_var_key<T, F>(key: &(String, Option<TypeName>), tree: &Tree, z
                     match var_tuple {
                         Some((expr, Some(local_types), typ, value)) => {
                             match expr {
-                                Some(expr) => f(&mut **expr, &**local_types, &**typ, value),
+                                Some(expr) => f(expr, local_types, typ, value),
                                 None => Ok(z),
                             }
                         },
@@ -327,7 +327,7 @@ fn type_for_ident_and_type_name_in<T, F>(ident: &String, type_name: &Option<Type
                                     let trait_r = trait1.borrow();
                                     match &*trait_r {
                                         Trait(_, _, Some(trait_vars)) => {
-                                            match trait_vars.impl1(&type_name) {
+                                            match trait_vars.impl1(type_name) {
                                                 Some(impl1) => {
                                                     let impl_r = impl1.borrow();
                                                     let impl_vars = match &*impl_r {
@@ -379,7 +379,7 @@ fn named_fields_for_con_ident_in<T, F>(ident: &String, tree: &Tree, mut f: F) ->
                         Fun::Con(con) => {
                             let con_r = con.borrow();
                             match &*con_r {
-                                Con::NamedField(_, _, _, Some(named_fields), _) => f(&**named_fields),
+                                Con::NamedField(_, _, _, _, Some(named_fields), _) => f(named_fields),
                                 _ => Err(FrontendErrors::new(vec![FrontendError::Internal(String::from("named_fields_for_con_ident_in: constructor isn't named field contructor or no named fields"))])),
                             }
                         },
@@ -615,7 +615,7 @@ impl Evaluator
                                         match &*trait_r {
                                             Trait(_, _, Some(trait_vars)) => {
                                                 let type_name2 = match type_name {
-                                                    TypeName::Array(Some(_)) if trait_vars.impl1(&type_name).is_none() => TypeName::Array(None),
+                                                    TypeName::Array(Some(_)) if trait_vars.impl1(type_name).is_none() => TypeName::Array(None),
                                                     _ => type_name.clone(),
                                                 };
                                                 match trait_vars.impl1(&type_name2) {
@@ -658,10 +658,8 @@ impl Evaluator
                     },
                     None => value,
                 };
-                if value3.is_none() {
-                    if are_errs  {
-                        errs.push(FrontendError::Message(pos, format!("unevaluated variable {}", ident)));
-                    }
+                if value3.is_none() && are_errs {
+                    errs.push(FrontendError::Message(pos, format!("unevaluated variable {}", ident)));
                 }
                 Ok(value3)
             },
@@ -773,8 +771,8 @@ impl Evaluator
         let mut visited_keys: BTreeSet<(String, Option<TypeName>)> = BTreeSet::new();
         for def in tree.defs() {
             match &**def {
-                Def::Var(ident, var, _) => self.evaluate_values_for_var(ident, var, tree, &mut visited_keys, errs)?,
-                Def::Trait(_, trait1, _) => {
+                Def::Var(_, ident, var, _) => self.evaluate_values_for_var(ident, var, tree, &mut visited_keys, errs)?,
+                Def::Trait(_, _, trait1, _) => {
                     let trait_r = trait1.borrow();
                     match &*trait_r {
                         Trait(_, trait_defs, _) => {
@@ -809,11 +807,11 @@ impl Evaluator
     {
         for def in tree.defs() {
             match &**def {
-                Def::Var(_, var, _) => {
+                Def::Var(_, _, var, _) => {
                     let var_r = var.borrow();
-                    self.check_pattern_exhaustions_for_fun(&*var_r, tree, errs)?;
+                    self.check_pattern_exhaustions_for_fun(&var_r, tree, errs)?;
                 },
-                Def::Trait(_, trait1, _) => {
+                Def::Trait(_, _, trait1, _) => {
                     let trait_r = trait1.borrow();
                     match &*trait_r {
                         Trait(_, trait_defs, _) => {
@@ -821,7 +819,7 @@ impl Evaluator
                                 match &**trait_def {
                                     TraitDef(_, var, _) => {
                                         let var_r = var.borrow();
-                                        self.check_pattern_exhaustions_for_fun(&*var_r, tree, errs)?;
+                                        self.check_pattern_exhaustions_for_fun(&var_r, tree, errs)?;
                                     },
                                 }
                             }
@@ -837,7 +835,7 @@ impl Evaluator
                                 match &**impl_def {
                                     ImplDef(_, impl_var, _) => {
                                         let impl_var_r = impl_var.borrow();
-                                        self.check_pattern_exhaustions_for_impl_fun(&*impl_var_r, tree, errs)?;
+                                        self.check_pattern_exhaustions_for_impl_fun(&impl_var_r, tree, errs)?;
                                     },
                                 }
                             }
@@ -875,7 +873,7 @@ impl Evaluator
                     Fun::Fun(_, _, _, _, Some(body), _, Some(local_types)) => {
                         let mut type_stack = TypeStack::new();
                         type_stack.set_first_type_values_for_type(typ);
-                        self.check_pattern_exhaustions_for_expr(&**body, tree, &mut type_stack, local_types, errs)?;
+                        self.check_pattern_exhaustions_for_expr(body, tree, &mut type_stack, local_types, errs)?;
                     },
                     Fun::Fun(_, _, _, _, None, _, _) => (),
                     Fun::Con(_) => return Err(FrontendErrors::new(vec![FrontendError::Internal(String::from("check_pattern_exhaustions_for_fun: variable is contructor"))])),
@@ -941,14 +939,14 @@ impl Evaluator
             Expr::Literal(literal, _, _) => self.do_literal(&**literal, errs, |evaluator, expr, errs| evaluator.add_var_keys_for_expr(expr, tree, var_env, type_stack, local_types, keys, processed_keys, errs))?,
             Expr::Lambda(args, _, body, _, _, _, _, _) => {
                 var_env.push_new_vars();
-                for arg in &*args {
+                for arg in args {
                     match arg {
                         LambdaArg(ident, _, _, _) => {
                             var_env.add_var(ident.clone(), ());
                         },
                     }
                 }
-                self.add_var_keys_for_expr(&**body, tree, var_env, type_stack, local_types, keys, processed_keys, errs)?;
+                self.add_var_keys_for_expr(body, tree, var_env, type_stack, local_types, keys, processed_keys, errs)?;
                 var_env.pop_vars();
             },
             Expr::Var(ident, Some(local_type), pos) => {
@@ -962,59 +960,59 @@ impl Evaluator
             },
             Expr::PrintfApp(exprs, _, _) => {
                 for expr2 in exprs {
-                    self.add_var_keys_for_expr(&**expr2, tree, var_env, type_stack, local_types, keys, processed_keys, errs)?;
+                    self.add_var_keys_for_expr(expr2, tree, var_env, type_stack, local_types, keys, processed_keys, errs)?;
                 }
             },
             Expr::App(expr2, exprs, _, _) => {
-                self.add_var_keys_for_expr(&**expr2, tree, var_env, type_stack, local_types, keys, processed_keys, errs)?;
+                self.add_var_keys_for_expr(expr2, tree, var_env, type_stack, local_types, keys, processed_keys, errs)?;
                 for expr3 in exprs {
-                    self.add_var_keys_for_expr(&**expr3, tree, var_env, type_stack, local_types, keys, processed_keys, errs)?;
+                    self.add_var_keys_for_expr(expr3, tree, var_env, type_stack, local_types, keys, processed_keys, errs)?;
                 }
             },
-            Expr::GetField(expr2, _, _, _) => self.add_var_keys_for_expr(&**expr2, tree, var_env, type_stack, local_types, keys, processed_keys, errs)?,
-            Expr::Get2Field(expr2, _, _, _) => self.add_var_keys_for_expr(&**expr2, tree, var_env, type_stack, local_types, keys, processed_keys, errs)?,
+            Expr::GetField(expr2, _, _, _) => self.add_var_keys_for_expr(expr2, tree, var_env, type_stack, local_types, keys, processed_keys, errs)?,
+            Expr::Get2Field(expr2, _, _, _) => self.add_var_keys_for_expr(expr2, tree, var_env, type_stack, local_types, keys, processed_keys, errs)?,
             Expr::SetField(expr2, _, expr3, _, _) => {
-                self.add_var_keys_for_expr(&**expr2, tree, var_env, type_stack, local_types, keys, processed_keys, errs)?;
-                self.add_var_keys_for_expr(&**expr3, tree, var_env, type_stack, local_types, keys, processed_keys, errs)?;
+                self.add_var_keys_for_expr(expr2, tree, var_env, type_stack, local_types, keys, processed_keys, errs)?;
+                self.add_var_keys_for_expr(expr3, tree, var_env, type_stack, local_types, keys, processed_keys, errs)?;
             },
             Expr::UpdateField(expr2, _, expr3, _, _) => {
-                self.add_var_keys_for_expr(&**expr2, tree, var_env, type_stack, local_types, keys, processed_keys, errs)?;
-                self.add_var_keys_for_expr(&**expr3, tree, var_env, type_stack, local_types, keys, processed_keys, errs)?;
+                self.add_var_keys_for_expr(expr2, tree, var_env, type_stack, local_types, keys, processed_keys, errs)?;
+                self.add_var_keys_for_expr(expr3, tree, var_env, type_stack, local_types, keys, processed_keys, errs)?;
             },
             Expr::UpdateGet2Field(expr2, _, expr3, _, _) => {
-                self.add_var_keys_for_expr(&**expr2, tree, var_env, type_stack, local_types, keys, processed_keys, errs)?;
-                self.add_var_keys_for_expr(&**expr3, tree, var_env, type_stack, local_types, keys, processed_keys, errs)?;
+                self.add_var_keys_for_expr(expr2, tree, var_env, type_stack, local_types, keys, processed_keys, errs)?;
+                self.add_var_keys_for_expr(expr3, tree, var_env, type_stack, local_types, keys, processed_keys, errs)?;
             },
-            Expr::Uniq(expr2, _, _) => self.add_var_keys_for_expr(&**expr2, tree, var_env, type_stack, local_types, keys, processed_keys, errs)?,
-            Expr::Shared(expr2, _, _) => self.add_var_keys_for_expr(&**expr2, tree, var_env, type_stack, local_types, keys, processed_keys, errs)?,
-            Expr::Typed(expr2, _, _, _) => self.add_var_keys_for_expr(&**expr2, tree, var_env, type_stack, local_types, keys, processed_keys, errs)?,
-            Expr::As(expr2, _, _, _) => self.add_var_keys_for_expr(&**expr2, tree, var_env, type_stack, local_types, keys, processed_keys, errs)?,
+            Expr::Uniq(expr2, _, _) => self.add_var_keys_for_expr(expr2, tree, var_env, type_stack, local_types, keys, processed_keys, errs)?,
+            Expr::Shared(expr2, _, _) => self.add_var_keys_for_expr(expr2, tree, var_env, type_stack, local_types, keys, processed_keys, errs)?,
+            Expr::Typed(expr2, _, _, _) => self.add_var_keys_for_expr(expr2, tree, var_env, type_stack, local_types, keys, processed_keys, errs)?,
+            Expr::As(expr2, _, _, _) => self.add_var_keys_for_expr(expr2, tree, var_env, type_stack, local_types, keys, processed_keys, errs)?,
             Expr::If(expr2, expr3, expr4, _, _) => {
-                self.add_var_keys_for_expr(&**expr2, tree, var_env, type_stack, local_types, keys, processed_keys, errs)?;
-                self.add_var_keys_for_expr(&**expr3, tree, var_env, type_stack, local_types, keys, processed_keys, errs)?;
-                self.add_var_keys_for_expr(&**expr4, tree, var_env, type_stack, local_types, keys, processed_keys, errs)?;
+                self.add_var_keys_for_expr(expr2, tree, var_env, type_stack, local_types, keys, processed_keys, errs)?;
+                self.add_var_keys_for_expr(expr3, tree, var_env, type_stack, local_types, keys, processed_keys, errs)?;
+                self.add_var_keys_for_expr(expr4, tree, var_env, type_stack, local_types, keys, processed_keys, errs)?;
             },
             Expr::Let(binds, expr2, _, _) => {
                 var_env.push_new_vars();
                 for bind in binds {
                     match bind {
                         Bind(pattern, expr3) => {
-                            self.add_var_keys_for_expr(&**expr3, tree, var_env, type_stack, local_types, keys, processed_keys, errs)?;
-                            self.add_var_keys_for_pattern(&**pattern, tree, var_env, type_stack, local_types, keys, processed_keys, errs)?;
+                            self.add_var_keys_for_expr(expr3, tree, var_env, type_stack, local_types, keys, processed_keys, errs)?;
+                            self.add_var_keys_for_pattern(pattern, tree, var_env, type_stack, local_types, keys, processed_keys, errs)?;
                         },
                     }
                 }
-                self.add_var_keys_for_expr(&**expr2, tree, var_env, type_stack, local_types, keys, processed_keys, errs)?;
+                self.add_var_keys_for_expr(expr2, tree, var_env, type_stack, local_types, keys, processed_keys, errs)?;
                 var_env.pop_vars();
             },
             Expr::Match(expr2, cases, _, _) => {
-                self.add_var_keys_for_expr(&**expr2, tree, var_env, type_stack, local_types, keys, processed_keys, errs)?;
+                self.add_var_keys_for_expr(expr2, tree, var_env, type_stack, local_types, keys, processed_keys, errs)?;
                 for case in cases {
                     match case {
                         Case(pattern, expr3) => {
                             var_env.push_new_vars();
-                            self.add_var_keys_for_pattern(&**pattern, tree, var_env, type_stack, local_types, keys, processed_keys, errs)?;
-                            self.add_var_keys_for_expr(&**expr3, tree, var_env, type_stack, local_types, keys, processed_keys, errs)?;
+                            self.add_var_keys_for_pattern(pattern, tree, var_env, type_stack, local_types, keys, processed_keys, errs)?;
+                            self.add_var_keys_for_expr(expr3, tree, var_env, type_stack, local_types, keys, processed_keys, errs)?;
                             var_env.pop_vars();
                         },
                     }
@@ -1036,7 +1034,7 @@ impl Evaluator
             },
             Pattern::UnnamedFieldCon(_, patterns, _, _, _) => {
                 for pattern2 in patterns {
-                    self.add_var_keys_for_pattern(&**pattern2, tree, var_env, type_stack, local_types, keys, processed_keys, errs)?;
+                    self.add_var_keys_for_pattern(pattern2, tree, var_env, type_stack, local_types, keys, processed_keys, errs)?;
                 }
             },
             Pattern::NamedFieldCon(_, pattern_named_field_pairs, _, _, _) => {
@@ -1047,12 +1045,12 @@ impl Evaluator
             },
             Pattern::At(_, ident, pattern2, _, _) => {
                 var_env.add_var(ident.clone(), ());
-                self.add_var_keys_for_pattern(&**pattern2, tree, var_env, type_stack, local_types, keys, processed_keys, errs)?;
+                self.add_var_keys_for_pattern(pattern2, tree, var_env, type_stack, local_types, keys, processed_keys, errs)?;
             },
             Pattern::Wildcard(_, _) => (),
             Pattern::Alt(patterns, _, _) => {
                 for pattern2 in patterns {
-                    self.add_var_keys_for_pattern(&**pattern2, tree, var_env, type_stack, local_types, keys, processed_keys, errs)?;
+                    self.add_var_keys_for_pattern(pattern2, tree, var_env, type_stack, local_types, keys, processed_keys, errs)?;
                 }
             },
             _ => return Err(FrontendErrors::new(vec![FrontendError::Internal(String::from("add_var_keys_for_pattern: no local type"))])),
@@ -1075,7 +1073,7 @@ impl Evaluator
                                         PatternForest::Alt(nodes, max2) => {
                                             for node2 in nodes {
                                                 let mut node2_r = node2.borrow_mut();
-                                                self.convert_pattern_ids_for_type_value(&mut *node2_r, max2, type_value2, tree, local_types)?
+                                                self.convert_pattern_ids_for_type_value(&mut node2_r, max2, type_value2, tree, local_types)?
                                             }
                                         },
                                         PatternForest::All => (),
@@ -1096,7 +1094,7 @@ impl Evaluator
                                                 PatternForest::Alt(nodes, max2) => {
                                                     for node2 in nodes {
                                                         let mut node2_r = node2.borrow_mut();
-                                                        self.convert_pattern_ids_for_type_value(&mut *node2_r, max2, type_value2, tree, local_types)?
+                                                        self.convert_pattern_ids_for_type_value(&mut node2_r, max2, type_value2, tree, local_types)?
                                                     }
                                                 },
                                                 PatternForest::All => (),
@@ -1109,7 +1107,7 @@ impl Evaluator
                                             PatternForest::Alt(nodes, max2) => {
                                                 for node2 in nodes {
                                                     let mut node2_r = node2.borrow_mut();
-                                                    self.convert_pattern_ids_for_type_value(&mut *node2_r, max2, type_value2, tree, local_types)?
+                                                    self.convert_pattern_ids_for_type_value(&mut node2_r, max2, type_value2, tree, local_types)?
                                                 }
                                             },
                                             PatternForest::All => (),
@@ -1131,7 +1129,7 @@ impl Evaluator
                                     TypeVar::Builtin(_, _, _) => {
                                         if ident == &String::from("Char") {
                                             match node.id() {
-                                                PatternId::Char(n) => node.set_id(PatternId::Char(*n as i8)),
+                                                PatternId::Char(n) => node.set_id(PatternId::Char(*n)),
                                                 PatternId::Short(n) => node.set_id(PatternId::Char(*n as i8)),
                                                 PatternId::Int(n) => node.set_id(PatternId::Char(*n as i8)),
                                                 PatternId::Long(n) => node.set_id(PatternId::Char(*n as i8)),
@@ -1147,7 +1145,7 @@ impl Evaluator
                                         } else if ident == &String::from("Short") {
                                             match node.id() {
                                                 PatternId::Char(n) => node.set_id(PatternId::Short(*n as i16)),
-                                                PatternId::Short(n) => node.set_id(PatternId::Short(*n as i16)),
+                                                PatternId::Short(n) => node.set_id(PatternId::Short(*n)),
                                                 PatternId::Int(n) => node.set_id(PatternId::Short(*n as i16)),
                                                 PatternId::Long(n) => node.set_id(PatternId::Short(*n as i16)),
                                                 PatternId::Uchar(n) => node.set_id(PatternId::Short(*n as i16)),
@@ -1163,7 +1161,7 @@ impl Evaluator
                                             match node.id() {
                                                 PatternId::Char(n) => node.set_id(PatternId::Int(*n as i32)),
                                                 PatternId::Short(n) => node.set_id(PatternId::Int(*n as i32)),
-                                                PatternId::Int(n) => node.set_id(PatternId::Int(*n as i32)),
+                                                PatternId::Int(n) => node.set_id(PatternId::Int(*n)),
                                                 PatternId::Long(n) => node.set_id(PatternId::Int(*n as i32)),
                                                 PatternId::Uchar(n) => node.set_id(PatternId::Int(*n as i32)),
                                                 PatternId::Ushort(n) => node.set_id(PatternId::Int(*n as i32)),
@@ -1179,7 +1177,7 @@ impl Evaluator
                                                 PatternId::Char(n) => node.set_id(PatternId::Long(*n as i64)),
                                                 PatternId::Short(n) => node.set_id(PatternId::Long(*n as i64)),
                                                 PatternId::Int(n) => node.set_id(PatternId::Long(*n as i64)),
-                                                PatternId::Long(n) => node.set_id(PatternId::Long(*n as i64)),
+                                                PatternId::Long(n) => node.set_id(PatternId::Long(*n)),
                                                 PatternId::Uchar(n) => node.set_id(PatternId::Long(*n as i64)),
                                                 PatternId::Ushort(n) => node.set_id(PatternId::Long(*n as i64)),
                                                 PatternId::Uint(n) => node.set_id(PatternId::Long(*n as i64)),
@@ -1195,7 +1193,7 @@ impl Evaluator
                                                 PatternId::Short(n) => node.set_id(PatternId::Uchar(*n as u8)),
                                                 PatternId::Int(n) => node.set_id(PatternId::Uchar(*n as u8)),
                                                 PatternId::Long(n) => node.set_id(PatternId::Uchar(*n as u8)),
-                                                PatternId::Uchar(n) => node.set_id(PatternId::Uchar(*n as u8)),
+                                                PatternId::Uchar(n) => node.set_id(PatternId::Uchar(*n)),
                                                 PatternId::Ushort(n) => node.set_id(PatternId::Uchar(*n as u8)),
                                                 PatternId::Uint(n) => node.set_id(PatternId::Uchar(*n as u8)),
                                                 PatternId::Ulong(n) => node.set_id(PatternId::Uchar(*n as u8)),
@@ -1211,7 +1209,7 @@ impl Evaluator
                                                 PatternId::Int(n) => node.set_id(PatternId::Ushort(*n as u16)),
                                                 PatternId::Long(n) => node.set_id(PatternId::Ushort(*n as u16)),
                                                 PatternId::Uchar(n) => node.set_id(PatternId::Ushort(*n as u16)),
-                                                PatternId::Ushort(n) => node.set_id(PatternId::Ushort(*n as u16)),
+                                                PatternId::Ushort(n) => node.set_id(PatternId::Ushort(*n)),
                                                 PatternId::Uint(n) => node.set_id(PatternId::Ushort(*n as u16)),
                                                 PatternId::Ulong(n) => node.set_id(PatternId::Ushort(*n as u16)),
                                                 PatternId::Half(n) => node.set_id(PatternId::Ushort(f32::from_bits(*n) as u16)),
@@ -1227,7 +1225,7 @@ impl Evaluator
                                                 PatternId::Long(n) => node.set_id(PatternId::Uint(*n as u32)),
                                                 PatternId::Uchar(n) => node.set_id(PatternId::Uint(*n as u32)),
                                                 PatternId::Ushort(n) => node.set_id(PatternId::Uint(*n as u32)),
-                                                PatternId::Uint(n) => node.set_id(PatternId::Uint(*n as u32)),
+                                                PatternId::Uint(n) => node.set_id(PatternId::Uint(*n)),
                                                 PatternId::Ulong(n) => node.set_id(PatternId::Uint(*n as u32)),
                                                 PatternId::Half(n) => node.set_id(PatternId::Uint(f32::from_bits(*n) as u32)),
                                                 PatternId::Float(n) => node.set_id(PatternId::Uint(f32::from_bits(*n) as u32)),
@@ -1243,7 +1241,7 @@ impl Evaluator
                                                 PatternId::Uchar(n) => node.set_id(PatternId::Ulong(*n as u64)),
                                                 PatternId::Ushort(n) => node.set_id(PatternId::Ulong(*n as u64)),
                                                 PatternId::Uint(n) => node.set_id(PatternId::Ulong(*n as u64)),
-                                                PatternId::Ulong(n) => node.set_id(PatternId::Ulong(*n as u64)),
+                                                PatternId::Ulong(n) => node.set_id(PatternId::Ulong(*n)),
                                                 PatternId::Half(n) => node.set_id(PatternId::Ulong(f32::from_bits(*n) as u64)),
                                                 PatternId::Float(n) => node.set_id(PatternId::Ulong(f32::from_bits(*n) as u64)),
                                                 PatternId::Double(n) => node.set_id(PatternId::Ulong(f64::from_bits(*n) as u64)),
@@ -1470,70 +1468,70 @@ impl Evaluator
     {
         match expr {
             Expr::Literal(literal, _, _) => self.do_literal(&**literal, errs, |evaluator, expr, errs| evaluator.check_pattern_exhaustions_for_expr(expr, tree, type_stack, local_types, errs))?,
-            Expr::Lambda(_, _, body, _, _, _, _, _) => self.check_pattern_exhaustions_for_expr(&**body, tree, type_stack, local_types, errs)?,
+            Expr::Lambda(_, _, body, _, _, _, _, _) => self.check_pattern_exhaustions_for_expr(body, tree, type_stack, local_types, errs)?,
             Expr::Var(_, _, _) => (),
             Expr::NamedFieldConApp(_, expr_named_field_pairs, _, _, _) => {
                 self.do_named_field_pairs(expr_named_field_pairs.as_slice(), errs, |evaluator, expr, errs| evaluator.check_pattern_exhaustions_for_expr(expr, tree, type_stack, local_types, errs))?
             },
             Expr::PrintfApp(exprs, _, _) => {
                 for expr2 in exprs {
-                    self.check_pattern_exhaustions_for_expr(&**expr2, tree, type_stack, local_types, errs)?;
+                    self.check_pattern_exhaustions_for_expr(expr2, tree, type_stack, local_types, errs)?;
                 }
             },
             Expr::App(expr2, exprs, _, _) => {
-                self.check_pattern_exhaustions_for_expr(&**expr2, tree, type_stack, local_types, errs)?;
+                self.check_pattern_exhaustions_for_expr(expr2, tree, type_stack, local_types, errs)?;
                 for expr3 in exprs {
-                    self.check_pattern_exhaustions_for_expr(&**expr3, tree, type_stack, local_types, errs)?;
+                    self.check_pattern_exhaustions_for_expr(expr3, tree, type_stack, local_types, errs)?;
                 }
             },
-            Expr::GetField(expr2, _, _, _) => self.check_pattern_exhaustions_for_expr(&**expr2, tree, type_stack, local_types, errs)?,
-            Expr::Get2Field(expr2, _, _, _) => self.check_pattern_exhaustions_for_expr(&**expr2, tree, type_stack, local_types, errs)?,
+            Expr::GetField(expr2, _, _, _) => self.check_pattern_exhaustions_for_expr(expr2, tree, type_stack, local_types, errs)?,
+            Expr::Get2Field(expr2, _, _, _) => self.check_pattern_exhaustions_for_expr(expr2, tree, type_stack, local_types, errs)?,
             Expr::SetField(expr2, _, expr3, _, _) => {
-                self.check_pattern_exhaustions_for_expr(&**expr2, tree, type_stack, local_types, errs)?;
-                self.check_pattern_exhaustions_for_expr(&**expr3, tree, type_stack, local_types, errs)?;
+                self.check_pattern_exhaustions_for_expr(expr2, tree, type_stack, local_types, errs)?;
+                self.check_pattern_exhaustions_for_expr(expr3, tree, type_stack, local_types, errs)?;
             },
             Expr::UpdateField(expr2, _, expr3, _, _) => {
-                self.check_pattern_exhaustions_for_expr(&**expr2, tree, type_stack, local_types, errs)?;
-                self.check_pattern_exhaustions_for_expr(&**expr3, tree, type_stack, local_types, errs)?;
+                self.check_pattern_exhaustions_for_expr(expr2, tree, type_stack, local_types, errs)?;
+                self.check_pattern_exhaustions_for_expr(expr3, tree, type_stack, local_types, errs)?;
             },
             Expr::UpdateGet2Field(expr2, _, expr3, _, _) => {
-                self.check_pattern_exhaustions_for_expr(&**expr2, tree, type_stack, local_types, errs)?;
-                self.check_pattern_exhaustions_for_expr(&**expr3, tree, type_stack, local_types, errs)?;
+                self.check_pattern_exhaustions_for_expr(expr2, tree, type_stack, local_types, errs)?;
+                self.check_pattern_exhaustions_for_expr(expr3, tree, type_stack, local_types, errs)?;
             },
-            Expr::Uniq(expr2, _, _) => self.check_pattern_exhaustions_for_expr(&**expr2, tree, type_stack, local_types, errs)?,
-            Expr::Shared(expr2, _, _) => self.check_pattern_exhaustions_for_expr(&**expr2, tree, type_stack, local_types, errs)?,
-            Expr::Typed(expr2, _, _, _) => self.check_pattern_exhaustions_for_expr(&**expr2, tree, type_stack, local_types, errs)?,
-            Expr::As(expr2, _, _, _) => self.check_pattern_exhaustions_for_expr(&**expr2, tree, type_stack, local_types, errs)?,
+            Expr::Uniq(expr2, _, _) => self.check_pattern_exhaustions_for_expr(expr2, tree, type_stack, local_types, errs)?,
+            Expr::Shared(expr2, _, _) => self.check_pattern_exhaustions_for_expr(expr2, tree, type_stack, local_types, errs)?,
+            Expr::Typed(expr2, _, _, _) => self.check_pattern_exhaustions_for_expr(expr2, tree, type_stack, local_types, errs)?,
+            Expr::As(expr2, _, _, _) => self.check_pattern_exhaustions_for_expr(expr2, tree, type_stack, local_types, errs)?,
             Expr::If(expr2, expr3, expr4, _, _) => {
-                self.check_pattern_exhaustions_for_expr(&**expr2, tree, type_stack, local_types, errs)?;
-                self.check_pattern_exhaustions_for_expr(&**expr3, tree, type_stack, local_types, errs)?;
-                self.check_pattern_exhaustions_for_expr(&**expr4, tree, type_stack, local_types, errs)?;
+                self.check_pattern_exhaustions_for_expr(expr2, tree, type_stack, local_types, errs)?;
+                self.check_pattern_exhaustions_for_expr(expr3, tree, type_stack, local_types, errs)?;
+                self.check_pattern_exhaustions_for_expr(expr4, tree, type_stack, local_types, errs)?;
             },
             Expr::Let(binds, expr2, _, _) => {
                 for bind in binds {
                     match bind {
                         Bind(pattern, expr3) => {
-                            self.check_pattern_exhaustions_for_expr(&**expr3, tree, type_stack, local_types, errs)?;
-                            let mut forest: PatternForest<PatternId> = PatternForest::Alt(Vec::new(), pattern_max_for_local_type(pattern_local_type(&pattern)?, tree, local_types)?);
-                            self.add_pattern_nodes_for_pattern(&**pattern, tree, type_stack, local_types, &mut forest, errs)?;
+                            self.check_pattern_exhaustions_for_expr(expr3, tree, type_stack, local_types, errs)?;
+                            let mut forest: PatternForest<PatternId> = PatternForest::Alt(Vec::new(), pattern_max_for_local_type(pattern_local_type(pattern)?, tree, local_types)?);
+                            self.add_pattern_nodes_for_pattern(pattern, tree, type_stack, local_types, &mut forest, errs)?;
                             self.normalize_pattern_forest(&mut forest)?;
                             match forest {
                                 PatternForest::All => (),
-                                _ => errs.push(FrontendError::Message(pattern_pos(&**pattern).clone(), String::from("non-exhaustive pattern"))),
+                                _ => errs.push(FrontendError::Message(pattern_pos(pattern).clone(), String::from("non-exhaustive pattern"))),
                             }
                         },
                     }
                 }
-                self.check_pattern_exhaustions_for_expr(&**expr2, tree, type_stack, local_types, errs)?;
+                self.check_pattern_exhaustions_for_expr(expr2, tree, type_stack, local_types, errs)?;
             },
             Expr::Match(expr2, cases, _, pos) => {
-                self.check_pattern_exhaustions_for_expr(&**expr2, tree, type_stack, local_types, errs)?;
-                let mut forest: PatternForest<PatternId> = PatternForest::Alt(Vec::new(), pattern_max_for_local_type(expr_local_type(&*expr2)?, tree, local_types)?);
+                self.check_pattern_exhaustions_for_expr(expr2, tree, type_stack, local_types, errs)?;
+                let mut forest: PatternForest<PatternId> = PatternForest::Alt(Vec::new(), pattern_max_for_local_type(expr_local_type(expr2)?, tree, local_types)?);
                 for case in cases {
                     match case {
                         Case(pattern, expr3) => {
-                            self.add_pattern_nodes_for_pattern(&**pattern, tree, type_stack, local_types, &mut forest, errs)?;
-                            self.check_pattern_exhaustions_for_expr(&**expr3, tree, type_stack, local_types, errs)?;
+                            self.add_pattern_nodes_for_pattern(pattern, tree, type_stack, local_types, &mut forest, errs)?;
+                            self.check_pattern_exhaustions_for_expr(expr3, tree, type_stack, local_types, errs)?;
                         },
                     }
                 }
@@ -1550,34 +1548,30 @@ impl Evaluator
     fn add_pattern_nodes_for_pattern(&self, pattern: &Pattern, tree: &Tree, type_stack: &mut TypeStack, local_types: &LocalTypes, forest: &mut PatternForest<PatternId>, errs: &mut Vec<FrontendError>) -> FrontendResultWithErrors<()>
     {
         match pattern {
-            Pattern::Literal(literal, _, _) => self.add_pattern_nodes_for_pattern_literal(&**literal, tree, type_stack, local_types, forest, errs)?,
+            Pattern::Literal(literal, _, _) => self.add_pattern_nodes_for_pattern_literal(literal, tree, type_stack, local_types, forest, errs)?,
             Pattern::As(literal, _, _, Some(local_type), _) => {
-                self.add_pattern_nodes_for_pattern_literal(&**literal, tree, type_stack, local_types, forest, errs)?;
-                match forest {
-                    PatternForest::Alt(nodes, max) => {
-                        match nodes.last() {
-                            Some(node) => {
-                                let mut node_r = node.borrow_mut();
-                                self.convert_pattern_ids_for_type_value(&mut *node_r, max, &Rc::new(TypeValue::Param(UniqFlag::None, *local_type)), tree, local_types)?; 
-                            },
-                            None => return Err(FrontendErrors::new(vec![FrontendError::Internal(String::from("add_pattern_nodes_for_pattern: no last pattern node"))])),
-                        }
-                    },
-                    _ => (),
+                self.add_pattern_nodes_for_pattern_literal(literal, tree, type_stack, local_types, forest, errs)?;
+                if let PatternForest::Alt(nodes, max) = forest {
+                    match nodes.last() {
+                        Some(node) => {
+                            let mut node_r = node.borrow_mut();
+                            self.convert_pattern_ids_for_type_value(&mut node_r, max, &Rc::new(TypeValue::Param(UniqFlag::None, *local_type)), tree, local_types)?; 
+                        },
+                        None => return Err(FrontendErrors::new(vec![FrontendError::Internal(String::from("add_pattern_nodes_for_pattern: no last pattern node"))])),
+                    }
                 }
             },
             Pattern::Const(ident, Some(local_type), pos) => {
                 let type_name = type_name_for_var_ident_and_local_type(ident, *local_type, tree, type_stack, local_types)?;
-                match self.value_for_ident_and_type_name(ident, &type_name, pos.clone(), tree, true, errs)? {
-                    Some(value) => self.add_pattern_node_for_value(&value, tree, forest)?,
-                    None => (),
+                if let Some(value) = self.value_for_ident_and_type_name(ident, &type_name, pos.clone(), tree, true, errs)? {
+                    self.add_pattern_node_for_value(&value, tree, forest)?;
                 }
             },
             Pattern::UnnamedFieldCon(ident, patterns, _, _, _) => {
                 let mut forests: Vec<PatternForest<PatternId>> = Vec::new();
                 for pattern2 in patterns {
-                    let mut forest2: PatternForest<PatternId> = PatternForest::Alt(Vec::new(), pattern_max_for_local_type(pattern_local_type(&**pattern2)?, tree, local_types)?);
-                    self.add_pattern_nodes_for_pattern(&**pattern2, tree, type_stack, local_types, &mut forest2, errs)?;
+                    let mut forest2: PatternForest<PatternId> = PatternForest::Alt(Vec::new(), pattern_max_for_local_type(pattern_local_type(pattern2)?, tree, local_types)?);
+                    self.add_pattern_nodes_for_pattern(pattern2, tree, type_stack, local_types, &mut forest2, errs)?;
                     forests.push(forest2);
                 }
                 forest.add_node(PatternNode::new(PatternId::Data(ident.clone()), PatternForests::Unfilled(forests)));
@@ -1590,8 +1584,8 @@ impl Evaluator
                                 NamedFieldPair(field_ident, pattern2, _) => {
                                     match named_fields.field_index(field_ident) {
                                         Some(field_idx) => {
-                                            let mut forest2: PatternForest<PatternId> = PatternForest::Alt(Vec::new(), pattern_max_for_local_type(pattern_local_type(&**pattern2)?, tree, local_types)?);
-                                            self.add_pattern_nodes_for_pattern(&**pattern2, tree, type_stack, local_types, &mut forest2, errs)?;
+                                            let mut forest2: PatternForest<PatternId> = PatternForest::Alt(Vec::new(), pattern_max_for_local_type(pattern_local_type(pattern2)?, tree, local_types)?);
+                                            self.add_pattern_nodes_for_pattern(pattern2, tree, type_stack, local_types, &mut forest2, errs)?;
                                             forests[field_idx] = forest2;
                                         },
                                         None => return Err(FrontendErrors::new(vec![FrontendError::Internal(String::from("add_pattern_nodes_for_pattern: no field index"))])),
@@ -1604,11 +1598,11 @@ impl Evaluator
                 })?;
             },
             Pattern::Var(_, _, _, _) => forest.set_all(),
-            Pattern::At(_, _, pattern2, _, _) => self.add_pattern_nodes_for_pattern(&**pattern2, tree, type_stack, local_types, forest, errs)?,
+            Pattern::At(_, _, pattern2, _, _) => self.add_pattern_nodes_for_pattern(pattern2, tree, type_stack, local_types, forest, errs)?,
             Pattern::Wildcard(_, _) => forest.set_all(),
             Pattern::Alt(patterns, _, _) => {
                 for pattern2 in patterns {
-                    self.add_pattern_nodes_for_pattern(&**pattern2, tree, type_stack, local_types, forest, errs)?;
+                    self.add_pattern_nodes_for_pattern(pattern2, tree, type_stack, local_types, forest, errs)?;
                 }
             },
             _ => return Err(FrontendErrors::new(vec![FrontendError::Internal(String::from("add_pattern_nodes_for_pattern: no local type"))])),
@@ -1649,8 +1643,8 @@ impl Evaluator
             Literal::Tuple(field_patterns) => {
                 let mut forests: Vec<PatternForest<PatternId>> = Vec::new();
                 for field_pattern in field_patterns {
-                    let mut forest2: PatternForest<PatternId> = PatternForest::Alt(Vec::new(), pattern_max_for_local_type(pattern_local_type(&**field_pattern)?, tree, local_types)?);
-                    self.add_pattern_nodes_for_pattern(&**field_pattern, tree, type_stack, local_types, &mut forest2, errs)?;
+                    let mut forest2: PatternForest<PatternId> = PatternForest::Alt(Vec::new(), pattern_max_for_local_type(pattern_local_type(field_pattern)?, tree, local_types)?);
+                    self.add_pattern_nodes_for_pattern(field_pattern, tree, type_stack, local_types, &mut forest2, errs)?;
                     forests.push(forest2);
                 }
                 forest.add_node(PatternNode::new(PatternId::Tuple(field_patterns.len()), PatternForests::Unfilled(forests)));
@@ -1658,15 +1652,15 @@ impl Evaluator
             Literal::Array(elem_patterns) => {
                 let mut forests: Vec<PatternForest<PatternId>> = Vec::new();
                 for elem_pattern in elem_patterns {
-                    let mut forest2: PatternForest<PatternId> = PatternForest::Alt(Vec::new(), pattern_max_for_local_type(pattern_local_type(&**elem_pattern)?, tree, local_types)?);
-                    self.add_pattern_nodes_for_pattern(&**elem_pattern, tree, type_stack, local_types, &mut forest2, errs)?;
+                    let mut forest2: PatternForest<PatternId> = PatternForest::Alt(Vec::new(), pattern_max_for_local_type(pattern_local_type(elem_pattern)?, tree, local_types)?);
+                    self.add_pattern_nodes_for_pattern(elem_pattern, tree, type_stack, local_types, &mut forest2, errs)?;
                     forests.push(forest2);
                 }
                 forest.add_node(PatternNode::new(PatternId::Array(elem_patterns.len()), PatternForests::Unfilled(forests)));
             },
             Literal::FilledArray(elem_pattern, len) => {
-                let mut forest2: PatternForest<PatternId> = PatternForest::Alt(Vec::new(), pattern_max_for_local_type(pattern_local_type(&**elem_pattern)?, tree, local_types)?);
-                self.add_pattern_nodes_for_pattern(&**elem_pattern, tree, type_stack, local_types, &mut forest2, errs)?;
+                let mut forest2: PatternForest<PatternId> = PatternForest::Alt(Vec::new(), pattern_max_for_local_type(pattern_local_type(elem_pattern)?, tree, local_types)?);
+                self.add_pattern_nodes_for_pattern(elem_pattern, tree, type_stack, local_types, &mut forest2, errs)?;
                 forest.add_node(PatternNode::new(PatternId::Array(*len), PatternForests::Filled(forest2, *len)));
             },
         }
@@ -1687,51 +1681,51 @@ impl Evaluator
             },
             Expr::PrintfApp(exprs, _, _) => {
                 for expr2 in exprs {
-                    self.set_local_funs_for_expr(&mut **expr2, local_fun_counter)?;
+                    self.set_local_funs_for_expr(expr2, local_fun_counter)?;
                 }
             },
             Expr::App(expr2, exprs, _, _) => {
-                self.set_local_funs_for_expr(&mut **expr2, local_fun_counter)?;
+                self.set_local_funs_for_expr(expr2, local_fun_counter)?;
                 for expr3 in exprs {
-                    self.set_local_funs_for_expr(&mut **expr3, local_fun_counter)?;
+                    self.set_local_funs_for_expr(expr3, local_fun_counter)?;
                 }
             },
-            Expr::GetField(expr2, _, _, _) => self.set_local_funs_for_expr(&mut **expr2, local_fun_counter)?,
-            Expr::Get2Field(expr2, _, _, _) => self.set_local_funs_for_expr(&mut **expr2, local_fun_counter)?,
+            Expr::GetField(expr2, _, _, _) => self.set_local_funs_for_expr(expr2, local_fun_counter)?,
+            Expr::Get2Field(expr2, _, _, _) => self.set_local_funs_for_expr(expr2, local_fun_counter)?,
             Expr::SetField(expr2, _, expr3, _, _) => {
-                self.set_local_funs_for_expr(&mut **expr2, local_fun_counter)?;
-                self.set_local_funs_for_expr(&mut **expr3, local_fun_counter)?;
+                self.set_local_funs_for_expr(expr2, local_fun_counter)?;
+                self.set_local_funs_for_expr(expr3, local_fun_counter)?;
             },
             Expr::UpdateField(expr2, _, expr3, _, _) => {
-                self.set_local_funs_for_expr(&mut **expr2, local_fun_counter)?;
-                self.set_local_funs_for_expr(&mut **expr3, local_fun_counter)?;
+                self.set_local_funs_for_expr(expr2, local_fun_counter)?;
+                self.set_local_funs_for_expr(expr3, local_fun_counter)?;
             },
             Expr::UpdateGet2Field(expr2, _, expr3, _, _) => {
-                self.set_local_funs_for_expr(&mut **expr2, local_fun_counter)?;
-                self.set_local_funs_for_expr(&mut **expr3, local_fun_counter)?;
+                self.set_local_funs_for_expr(expr2, local_fun_counter)?;
+                self.set_local_funs_for_expr(expr3, local_fun_counter)?;
             },
-            Expr::Uniq(expr2, _, _) => self.set_local_funs_for_expr(&mut **expr2, local_fun_counter)?,
-            Expr::Shared(expr2, _, _) => self.set_local_funs_for_expr(&mut **expr2, local_fun_counter)?,
-            Expr::Typed(expr2, _, _, _) => self.set_local_funs_for_expr(&mut **expr2, local_fun_counter)?,
-            Expr::As(expr2, _, _, _) => self.set_local_funs_for_expr(&mut **expr2, local_fun_counter)?,
+            Expr::Uniq(expr2, _, _) => self.set_local_funs_for_expr(expr2, local_fun_counter)?,
+            Expr::Shared(expr2, _, _) => self.set_local_funs_for_expr(expr2, local_fun_counter)?,
+            Expr::Typed(expr2, _, _, _) => self.set_local_funs_for_expr(expr2, local_fun_counter)?,
+            Expr::As(expr2, _, _, _) => self.set_local_funs_for_expr(expr2, local_fun_counter)?,
             Expr::If(expr2, expr3, expr4, _, _) => {
-                self.set_local_funs_for_expr(&mut **expr2, local_fun_counter)?;
-                self.set_local_funs_for_expr(&mut **expr3, local_fun_counter)?;
-                self.set_local_funs_for_expr(&mut **expr4, local_fun_counter)?;
+                self.set_local_funs_for_expr(expr2, local_fun_counter)?;
+                self.set_local_funs_for_expr(expr3, local_fun_counter)?;
+                self.set_local_funs_for_expr(expr4, local_fun_counter)?;
             },
             Expr::Let(binds, expr2, _, _) => {
                 for bind in binds {
                     match bind {
-                        Bind(_, expr3) => self.set_local_funs_for_expr(&mut **expr3, local_fun_counter)?,
+                        Bind(_, expr3) => self.set_local_funs_for_expr(expr3, local_fun_counter)?,
                     }
                 }
-                self.set_local_funs_for_expr(&mut **expr2, local_fun_counter)?;
+                self.set_local_funs_for_expr(expr2, local_fun_counter)?;
             },
             Expr::Match(expr2, cases, _, _) => {
-                self.set_local_funs_for_expr(&mut **expr2, local_fun_counter)?;
+                self.set_local_funs_for_expr(expr2, local_fun_counter)?;
                 for case in cases {
                     match case {
-                        Case(_, expr3) => self.set_local_funs_for_expr(&mut **expr3, local_fun_counter)?,
+                        Case(_, expr3) => self.set_local_funs_for_expr(expr3, local_fun_counter)?,
                     }
                 }
             },
@@ -1775,7 +1769,7 @@ impl Evaluator
                                                             Some(con) => {
                                                                 let con_r = con.borrow();
                                                                 match &*con_r {
-                                                                    Con::NamedField(_, _, _, Some(named_fields), _) => {
+                                                                    Con::NamedField(_, _, _, _, Some(named_fields), _) => {
                                                                         match named_fields.field_index(field_ident) {
                                                                             Some(tmp_field_idx) => tmp_field_idx,
                                                                             None => return Err(FrontendErrors::new(vec![FrontendError::Internal(String::from("value_for_fields_with_ref_fun_in: type variable hasn't field"))])),
@@ -1820,7 +1814,7 @@ impl Evaluator
                                                 Value::Char(c2) => *c = c2,
                                                 Value::Object(_, object2) => {
                                                     let object2_r = object2.borrow();
-                                                    add_error_for_object_and_vec_field(&*object2_r, pos.clone(), errs)?;
+                                                    add_error_for_object_and_vec_field(&object2_r, pos.clone(), errs)?;
                                                     return Ok(false);
                                                 },
                                                 _ => return Err(FrontendErrors::new(vec![FrontendError::Internal(String::from("value_for_fields_with_ref_fun_in: invalid value"))])),
@@ -1841,7 +1835,7 @@ impl Evaluator
                                                 Value::Short(n2) => *n = n2,
                                                 Value::Object(_, object2) => {
                                                     let object2_r = object2.borrow();
-                                                    add_error_for_object_and_vec_field(&*object2_r, pos.clone(), errs)?;
+                                                    add_error_for_object_and_vec_field(&object2_r, pos.clone(), errs)?;
                                                     return Ok(false);
                                                 },
                                                 _ => return Err(FrontendErrors::new(vec![FrontendError::Internal(String::from("value_for_fields_with_ref_fun_in: invalid value"))])),
@@ -1862,7 +1856,7 @@ impl Evaluator
                                                 Value::Int(n2) => *n = n2,
                                                 Value::Object(_, object2) => {
                                                     let object2_r = object2.borrow();
-                                                    add_error_for_object_and_vec_field(&*object2_r, pos.clone(), errs)?;
+                                                    add_error_for_object_and_vec_field(&object2_r, pos.clone(), errs)?;
                                                     return Ok(false);
                                                 },
                                                 _ => return Err(FrontendErrors::new(vec![FrontendError::Internal(String::from("value_for_fields_with_ref_fun_in: invalid value"))])),
@@ -1883,7 +1877,7 @@ impl Evaluator
                                                 Value::Long(n2) => *n = n2,
                                                 Value::Object(_, object2) => {
                                                     let object2_r = object2.borrow();
-                                                    add_error_for_object_and_vec_field(&*object2_r, pos.clone(), errs)?;
+                                                    add_error_for_object_and_vec_field(&object2_r, pos.clone(), errs)?;
                                                     return Ok(false);
                                                 },
                                                 _ => return Err(FrontendErrors::new(vec![FrontendError::Internal(String::from("value_for_fields_with_ref_fun_in: invalid value"))])),
@@ -1904,7 +1898,7 @@ impl Evaluator
                                                 Value::Uchar(c2) => *c = c2,
                                                 Value::Object(_, object2) => {
                                                     let object2_r = object2.borrow();
-                                                    add_error_for_object_and_vec_field(&*object2_r, pos.clone(), errs)?;
+                                                    add_error_for_object_and_vec_field(&object2_r, pos.clone(), errs)?;
                                                     return Ok(false);
                                                 },
                                                 _ => return Err(FrontendErrors::new(vec![FrontendError::Internal(String::from("value_for_fields_with_ref_fun_in: invalid value"))])),
@@ -1925,7 +1919,7 @@ impl Evaluator
                                                 Value::Ushort(n2) => *n = n2,
                                                 Value::Object(_, object2) => {
                                                     let object2_r = object2.borrow();
-                                                    add_error_for_object_and_vec_field(&*object2_r, pos.clone(), errs)?;
+                                                    add_error_for_object_and_vec_field(&object2_r, pos.clone(), errs)?;
                                                     return Ok(false);
                                                 },
                                                 _ => return Err(FrontendErrors::new(vec![FrontendError::Internal(String::from("value_for_fields_with_ref_fun_in: invalid value"))])),
@@ -1946,7 +1940,7 @@ impl Evaluator
                                                 Value::Uint(n2) => *n = n2,
                                                 Value::Object(_, object2) => {
                                                     let object2_r = object2.borrow();
-                                                    add_error_for_object_and_vec_field(&*object2_r, pos.clone(), errs)?;
+                                                    add_error_for_object_and_vec_field(&object2_r, pos.clone(), errs)?;
                                                     return Ok(false);
                                                 },
                                                 _ => return Err(FrontendErrors::new(vec![FrontendError::Internal(String::from("value_for_fields_with_ref_fun_in: invalid value"))])),
@@ -1967,7 +1961,7 @@ impl Evaluator
                                                 Value::Ulong(n2) => *n = n2,
                                                 Value::Object(_, object2) => {
                                                     let object2_r = object2.borrow();
-                                                    add_error_for_object_and_vec_field(&*object2_r, pos.clone(), errs)?;
+                                                    add_error_for_object_and_vec_field(&object2_r, pos.clone(), errs)?;
                                                     return Ok(false);
                                                 },
                                                 _ => return Err(FrontendErrors::new(vec![FrontendError::Internal(String::from("value_for_fields_with_ref_fun_in: invalid value"))])),
@@ -1988,7 +1982,7 @@ impl Evaluator
                                                 Value::Float(n2) => *n = n2,
                                                 Value::Object(_, object2) => {
                                                     let object2_r = object2.borrow();
-                                                    add_error_for_object_and_vec_field(&*object2_r, pos.clone(), errs)?;
+                                                    add_error_for_object_and_vec_field(&object2_r, pos.clone(), errs)?;
                                                     return Ok(false);
                                                 },
                                                 _ => return Err(FrontendErrors::new(vec![FrontendError::Internal(String::from("value_for_fields_with_ref_fun_in: invalid value"))])),
@@ -2009,7 +2003,7 @@ impl Evaluator
                                                 Value::Double(n2) => *n = n2,
                                                 Value::Object(_, object2) => {
                                                     let object2_r = object2.borrow();
-                                                    add_error_for_object_and_vec_field(&*object2_r, pos.clone(), errs)?;
+                                                    add_error_for_object_and_vec_field(&object2_r, pos.clone(), errs)?;
                                                     return Ok(false);
                                                 },
                                                 _ => return Err(FrontendErrors::new(vec![FrontendError::Internal(String::from("value_for_fields_with_ref_fun_in: invalid value"))])),
@@ -2125,7 +2119,7 @@ impl Evaluator
                                     TypeVar::Builtin(_, _, _) => {
                                         if ident == &String::from("Char") {
                                             match value {
-                                                Value::Char(c) => Ok(Some(Value::Char(*c as i8))),
+                                                Value::Char(c) => Ok(Some(Value::Char(*c))),
                                                 Value::Short(n) => Ok(Some(Value::Char(*n as i8))),
                                                 Value::Int(n) => Ok(Some(Value::Char(*n as i8))),
                                                 Value::Long(n) => Ok(Some(Value::Char(*n as i8))),
@@ -2137,7 +2131,7 @@ impl Evaluator
                                                 Value::Double(n) => Ok(Some(Value::Char(*n as i8))),
                                                 Value::Object(_, object) => {
                                                     let object_r = object.borrow();
-                                                    add_error_for_object_and_casting(&*object_r, pos.clone(), errs)?;
+                                                    add_error_for_object_and_casting(&object_r, pos.clone(), errs)?;
                                                     Ok(None)
                                                 },
                                                 _ => Err(FrontendErrors::new(vec![FrontendError::Internal(String::from("convert_value_for_type_value: invalid value"))])),
@@ -2145,7 +2139,7 @@ impl Evaluator
                                         } else if ident == &String::from("Short") {
                                             match value {
                                                 Value::Char(c) => Ok(Some(Value::Short(*c as i16))),
-                                                Value::Short(n) => Ok(Some(Value::Short(*n as i16))),
+                                                Value::Short(n) => Ok(Some(Value::Short(*n))),
                                                 Value::Int(n) => Ok(Some(Value::Short(*n as i16))),
                                                 Value::Long(n) => Ok(Some(Value::Short(*n as i16))),
                                                 Value::Uchar(c) => Ok(Some(Value::Short(*c as i16))),
@@ -2156,7 +2150,7 @@ impl Evaluator
                                                 Value::Double(n) => Ok(Some(Value::Short(*n as i16))),
                                                 Value::Object(_, object) => {
                                                     let object_r = object.borrow();
-                                                    add_error_for_object_and_casting(&*object_r, pos.clone(), errs)?;
+                                                    add_error_for_object_and_casting(&object_r, pos.clone(), errs)?;
                                                     Ok(None)
                                                 },
                                                 _ => Err(FrontendErrors::new(vec![FrontendError::Internal(String::from("convert_value_for_type_value: invalid value"))])),
@@ -2165,7 +2159,7 @@ impl Evaluator
                                             match value {
                                                 Value::Char(c) => Ok(Some(Value::Int(*c as i32))),
                                                 Value::Short(n) => Ok(Some(Value::Int(*n as i32))),
-                                                Value::Int(n) => Ok(Some(Value::Int(*n as i32))),
+                                                Value::Int(n) => Ok(Some(Value::Int(*n))),
                                                 Value::Long(n) => Ok(Some(Value::Int(*n as i32))),
                                                 Value::Uchar(c) => Ok(Some(Value::Int(*c as i32))),
                                                 Value::Ushort(n) => Ok(Some(Value::Int(*n as i32))),
@@ -2175,7 +2169,7 @@ impl Evaluator
                                                 Value::Double(n) => Ok(Some(Value::Int(*n as i32))),
                                                 Value::Object(_, object) => {
                                                     let object_r = object.borrow();
-                                                    add_error_for_object_and_casting(&*object_r, pos.clone(), errs)?;
+                                                    add_error_for_object_and_casting(&object_r, pos.clone(), errs)?;
                                                     Ok(None)
                                                 },
                                                 _ => Err(FrontendErrors::new(vec![FrontendError::Internal(String::from("convert_value_for_type_value: invalid value"))])),
@@ -2185,7 +2179,7 @@ impl Evaluator
                                                 Value::Char(c) => Ok(Some(Value::Long(*c as i64))),
                                                 Value::Short(n) => Ok(Some(Value::Long(*n as i64))),
                                                 Value::Int(n) => Ok(Some(Value::Long(*n as i64))),
-                                                Value::Long(n) => Ok(Some(Value::Long(*n as i64))),
+                                                Value::Long(n) => Ok(Some(Value::Long(*n))),
                                                 Value::Uchar(c) => Ok(Some(Value::Long(*c as i64))),
                                                 Value::Ushort(n) => Ok(Some(Value::Long(*n as i64))),
                                                 Value::Uint(n) => Ok(Some(Value::Long(*n as i64))),
@@ -2194,7 +2188,7 @@ impl Evaluator
                                                 Value::Double(n) => Ok(Some(Value::Long(*n as i64))),
                                                 Value::Object(_, object) => {
                                                     let object_r = object.borrow();
-                                                    add_error_for_object_and_casting(&*object_r, pos.clone(), errs)?;
+                                                    add_error_for_object_and_casting(&object_r, pos.clone(), errs)?;
                                                     Ok(None)
                                                 },
                                                 _ => Err(FrontendErrors::new(vec![FrontendError::Internal(String::from("convert_value_for_type_value: invalid value"))])),
@@ -2205,7 +2199,7 @@ impl Evaluator
                                                 Value::Short(n) => Ok(Some(Value::Uchar(*n as u8))),
                                                 Value::Int(n) => Ok(Some(Value::Uchar(*n as u8))),
                                                 Value::Long(n) => Ok(Some(Value::Uchar(*n as u8))),
-                                                Value::Uchar(c) => Ok(Some(Value::Uchar(*c as u8))),
+                                                Value::Uchar(c) => Ok(Some(Value::Uchar(*c))),
                                                 Value::Ushort(n) => Ok(Some(Value::Uchar(*n as u8))),
                                                 Value::Uint(n) => Ok(Some(Value::Uchar(*n as u8))),
                                                 Value::Ulong(n) => Ok(Some(Value::Uchar(*n as u8))),
@@ -2213,7 +2207,7 @@ impl Evaluator
                                                 Value::Double(n) => Ok(Some(Value::Uchar(*n as u8))),
                                                 Value::Object(_, object) => {
                                                     let object_r = object.borrow();
-                                                    add_error_for_object_and_casting(&*object_r, pos.clone(), errs)?;
+                                                    add_error_for_object_and_casting(&object_r, pos.clone(), errs)?;
                                                     Ok(None)
                                                 },
                                                 _ => Err(FrontendErrors::new(vec![FrontendError::Internal(String::from("convert_value_for_type_value: invalid value"))])),
@@ -2225,14 +2219,14 @@ impl Evaluator
                                                 Value::Int(n) => Ok(Some(Value::Ushort(*n as u16))),
                                                 Value::Long(n) => Ok(Some(Value::Ushort(*n as u16))),
                                                 Value::Uchar(c) => Ok(Some(Value::Ushort(*c as u16))),
-                                                Value::Ushort(n) => Ok(Some(Value::Ushort(*n as u16))),
+                                                Value::Ushort(n) => Ok(Some(Value::Ushort(*n))),
                                                 Value::Uint(n) => Ok(Some(Value::Ushort(*n as u16))),
                                                 Value::Ulong(n) => Ok(Some(Value::Ushort(*n as u16))),
                                                 Value::Float(n) => Ok(Some(Value::Ushort(*n as u16))),
                                                 Value::Double(n) => Ok(Some(Value::Ushort(*n as u16))),
                                                 Value::Object(_, object) => {
                                                     let object_r = object.borrow();
-                                                    add_error_for_object_and_casting(&*object_r, pos.clone(), errs)?;
+                                                    add_error_for_object_and_casting(&object_r, pos.clone(), errs)?;
                                                     Ok(None)
                                                 },
                                                 _ => Err(FrontendErrors::new(vec![FrontendError::Internal(String::from("convert_value_for_type_value: invalid value"))])),
@@ -2245,13 +2239,13 @@ impl Evaluator
                                                 Value::Long(n) => Ok(Some(Value::Uint(*n as u32))),
                                                 Value::Uchar(c) => Ok(Some(Value::Uint(*c as u32))),
                                                 Value::Ushort(n) => Ok(Some(Value::Uint(*n as u32))),
-                                                Value::Uint(n) => Ok(Some(Value::Uint(*n as u32))),
+                                                Value::Uint(n) => Ok(Some(Value::Uint(*n))),
                                                 Value::Ulong(n) => Ok(Some(Value::Uint(*n as u32))),
                                                 Value::Float(n) => Ok(Some(Value::Uint(*n as u32))),
                                                 Value::Double(n) => Ok(Some(Value::Uint(*n as u32))),
                                                 Value::Object(_, object) => {
                                                     let object_r = object.borrow();
-                                                    add_error_for_object_and_casting(&*object_r, pos.clone(), errs)?;
+                                                    add_error_for_object_and_casting(&object_r, pos.clone(), errs)?;
                                                     Ok(None)
                                                 },
                                                 _ => Err(FrontendErrors::new(vec![FrontendError::Internal(String::from("convert_value_for_type_value: invalid value"))])),
@@ -2265,12 +2259,12 @@ impl Evaluator
                                                 Value::Uchar(c) => Ok(Some(Value::Ulong(*c as u64))),
                                                 Value::Ushort(n) => Ok(Some(Value::Ulong(*n as u64))),
                                                 Value::Uint(n) => Ok(Some(Value::Ulong(*n as u64))),
-                                                Value::Ulong(n) => Ok(Some(Value::Ulong(*n as u64))),
+                                                Value::Ulong(n) => Ok(Some(Value::Ulong(*n))),
                                                 Value::Float(n) => Ok(Some(Value::Ulong(*n as u64))),
                                                 Value::Double(n) => Ok(Some(Value::Ulong(*n as u64))),
                                                 Value::Object(_, object) => {
                                                     let object_r = object.borrow();
-                                                    add_error_for_object_and_casting(&*object_r, pos.clone(), errs)?;
+                                                    add_error_for_object_and_casting(&object_r, pos.clone(), errs)?;
                                                     Ok(None)
                                                 },
                                                 _ => Err(FrontendErrors::new(vec![FrontendError::Internal(String::from("convert_value_for_type_value: invalid value"))])),
@@ -2288,11 +2282,11 @@ impl Evaluator
                                                 Value::Ushort(n) => Ok(Some(Value::Float(*n as f32))),
                                                 Value::Uint(n) => Ok(Some(Value::Float(*n as f32))),
                                                 Value::Ulong(n) => Ok(Some(Value::Float(*n as f32))),
-                                                Value::Float(n) => Ok(Some(Value::Float(*n as f32))),
+                                                Value::Float(n) => Ok(Some(Value::Float(*n))),
                                                 Value::Double(n) => Ok(Some(Value::Float(*n as f32))),
                                                 Value::Object(_, object) => {
                                                     let object_r = object.borrow();
-                                                    add_error_for_object_and_casting(&*object_r, pos.clone(), errs)?;
+                                                    add_error_for_object_and_casting(&object_r, pos.clone(), errs)?;
                                                     Ok(None)
                                                 },
                                                 _ => Err(FrontendErrors::new(vec![FrontendError::Internal(String::from("convert_value_for_type_value: invalid value"))])),
@@ -2308,10 +2302,10 @@ impl Evaluator
                                                 Value::Uint(n) => Ok(Some(Value::Double(*n as f64))),
                                                 Value::Ulong(n) => Ok(Some(Value::Double(*n as f64))),
                                                 Value::Float(n) => Ok(Some(Value::Double(*n as f64))),
-                                                Value::Double(n) => Ok(Some(Value::Double(*n as f64))),
+                                                Value::Double(n) => Ok(Some(Value::Double(*n))),
                                                 Value::Object(_, object) => {
                                                     let object_r = object.borrow();
-                                                    add_error_for_object_and_casting(&*object_r, pos.clone(), errs)?;
+                                                    add_error_for_object_and_casting(&object_r, pos.clone(), errs)?;
                                                     Ok(None)
                                                 },
                                                 _ => Err(FrontendErrors::new(vec![FrontendError::Internal(String::from("convert_value_for_type_value: invalid value"))])),
@@ -2396,17 +2390,14 @@ impl Evaluator
                         match pattern_value {
                             PatternValue::Object(pattern_object) => {
                                 let mut pattern_object_r = pattern_object.borrow_mut();
-                                match &mut *pattern_object_r {
-                                    PatternObject::Alt(pattern_values) => {
-                                        for pattern_value2 in pattern_values {
-                                            match self.convert_pattern_value_for_type_value(pattern_value2, type_value, pos, tree, local_types, errs)? {
-                                                Some(pattern_value3) => *pattern_value2 = pattern_value3,
-                                                None => return Ok(None),
-                                            }
+                                if let PatternObject::Alt(pattern_values) = &mut *pattern_object_r {
+                                    for pattern_value2 in pattern_values {
+                                        match self.convert_pattern_value_for_type_value(pattern_value2, type_value, pos, tree, local_types, errs)? {
+                                            Some(pattern_value3) => *pattern_value2 = pattern_value3,
+                                            None => return Ok(None),
                                         }
-                                        return Ok(Some(PatternValue::Object(pattern_object.clone())));
-                                    },
-                                    _ => (),
+                                    }
+                                    return Ok(Some(PatternValue::Object(pattern_object.clone())));
                                 }
                             },
                             PatternValue::Wildcard => return Ok(Some(PatternValue::Wildcard)),
@@ -2419,7 +2410,7 @@ impl Evaluator
                                     TypeVar::Builtin(_, _, _) => {
                                         if ident == &String::from("Char") {
                                             match pattern_value {
-                                                PatternValue::Char(c) => Ok(Some(PatternValue::Char(*c as i8))),
+                                                PatternValue::Char(c) => Ok(Some(PatternValue::Char(*c))),
                                                 PatternValue::Short(n) => Ok(Some(PatternValue::Char(*n as i8))),
                                                 PatternValue::Int(n) => Ok(Some(PatternValue::Char(*n as i8))),
                                                 PatternValue::Long(n) => Ok(Some(PatternValue::Char(*n as i8))),
@@ -2434,7 +2425,7 @@ impl Evaluator
                                         } else if ident == &String::from("Short") {
                                             match pattern_value {
                                                 PatternValue::Char(c) => Ok(Some(PatternValue::Short(*c as i16))),
-                                                PatternValue::Short(n) => Ok(Some(PatternValue::Short(*n as i16))),
+                                                PatternValue::Short(n) => Ok(Some(PatternValue::Short(*n))),
                                                 PatternValue::Int(n) => Ok(Some(PatternValue::Short(*n as i16))),
                                                 PatternValue::Long(n) => Ok(Some(PatternValue::Short(*n as i16))),
                                                 PatternValue::Uchar(c) => Ok(Some(PatternValue::Short(*c as i16))),
@@ -2449,7 +2440,7 @@ impl Evaluator
                                             match pattern_value {
                                                 PatternValue::Char(c) => Ok(Some(PatternValue::Int(*c as i32))),
                                                 PatternValue::Short(n) => Ok(Some(PatternValue::Int(*n as i32))),
-                                                PatternValue::Int(n) => Ok(Some(PatternValue::Int(*n as i32))),
+                                                PatternValue::Int(n) => Ok(Some(PatternValue::Int(*n))),
                                                 PatternValue::Long(n) => Ok(Some(PatternValue::Int(*n as i32))),
                                                 PatternValue::Uchar(c) => Ok(Some(PatternValue::Int(*c as i32))),
                                                 PatternValue::Ushort(n) => Ok(Some(PatternValue::Int(*n as i32))),
@@ -2464,7 +2455,7 @@ impl Evaluator
                                                 PatternValue::Char(c) => Ok(Some(PatternValue::Long(*c as i64))),
                                                 PatternValue::Short(n) => Ok(Some(PatternValue::Long(*n as i64))),
                                                 PatternValue::Int(n) => Ok(Some(PatternValue::Long(*n as i64))),
-                                                PatternValue::Long(n) => Ok(Some(PatternValue::Long(*n as i64))),
+                                                PatternValue::Long(n) => Ok(Some(PatternValue::Long(*n))),
                                                 PatternValue::Uchar(c) => Ok(Some(PatternValue::Long(*c as i64))),
                                                 PatternValue::Ushort(n) => Ok(Some(PatternValue::Long(*n as i64))),
                                                 PatternValue::Uint(n) => Ok(Some(PatternValue::Long(*n as i64))),
@@ -2479,7 +2470,7 @@ impl Evaluator
                                                 PatternValue::Short(n) => Ok(Some(PatternValue::Uchar(*n as u8))),
                                                 PatternValue::Int(n) => Ok(Some(PatternValue::Uchar(*n as u8))),
                                                 PatternValue::Long(n) => Ok(Some(PatternValue::Uchar(*n as u8))),
-                                                PatternValue::Uchar(c) => Ok(Some(PatternValue::Uchar(*c as u8))),
+                                                PatternValue::Uchar(c) => Ok(Some(PatternValue::Uchar(*c))),
                                                 PatternValue::Ushort(n) => Ok(Some(PatternValue::Uchar(*n as u8))),
                                                 PatternValue::Uint(n) => Ok(Some(PatternValue::Uchar(*n as u8))),
                                                 PatternValue::Ulong(n) => Ok(Some(PatternValue::Uchar(*n as u8))),
@@ -2494,7 +2485,7 @@ impl Evaluator
                                                 PatternValue::Int(n) => Ok(Some(PatternValue::Ushort(*n as u16))),
                                                 PatternValue::Long(n) => Ok(Some(PatternValue::Ushort(*n as u16))),
                                                 PatternValue::Uchar(c) => Ok(Some(PatternValue::Ushort(*c as u16))),
-                                                PatternValue::Ushort(n) => Ok(Some(PatternValue::Ushort(*n as u16))),
+                                                PatternValue::Ushort(n) => Ok(Some(PatternValue::Ushort(*n))),
                                                 PatternValue::Uint(n) => Ok(Some(PatternValue::Ushort(*n as u16))),
                                                 PatternValue::Ulong(n) => Ok(Some(PatternValue::Ushort(*n as u16))),
                                                 PatternValue::Float(n) => Ok(Some(PatternValue::Ushort(*n as u16))),
@@ -2509,7 +2500,7 @@ impl Evaluator
                                                 PatternValue::Long(n) => Ok(Some(PatternValue::Uint(*n as u32))),
                                                 PatternValue::Uchar(c) => Ok(Some(PatternValue::Uint(*c as u32))),
                                                 PatternValue::Ushort(n) => Ok(Some(PatternValue::Uint(*n as u32))),
-                                                PatternValue::Uint(n) => Ok(Some(PatternValue::Uint(*n as u32))),
+                                                PatternValue::Uint(n) => Ok(Some(PatternValue::Uint(*n))),
                                                 PatternValue::Ulong(n) => Ok(Some(PatternValue::Uint(*n as u32))),
                                                 PatternValue::Float(n) => Ok(Some(PatternValue::Uint(*n as u32))),
                                                 PatternValue::Double(n) => Ok(Some(PatternValue::Uint(*n as u32))),
@@ -2524,7 +2515,7 @@ impl Evaluator
                                                 PatternValue::Uchar(c) => Ok(Some(PatternValue::Ulong(*c as u64))),
                                                 PatternValue::Ushort(n) => Ok(Some(PatternValue::Ulong(*n as u64))),
                                                 PatternValue::Uint(n) => Ok(Some(PatternValue::Ulong(*n as u64))),
-                                                PatternValue::Ulong(n) => Ok(Some(PatternValue::Ulong(*n as u64))),
+                                                PatternValue::Ulong(n) => Ok(Some(PatternValue::Ulong(*n))),
                                                 PatternValue::Float(n) => Ok(Some(PatternValue::Ulong(*n as u64))),
                                                 PatternValue::Double(n) => Ok(Some(PatternValue::Ulong(*n as u64))),
                                                 _ => Err(FrontendErrors::new(vec![FrontendError::Internal(String::from("convert_pattern_value_for_type_value: invalid value"))])),
@@ -2542,7 +2533,7 @@ impl Evaluator
                                                 PatternValue::Ushort(n) => Ok(Some(PatternValue::Float(*n as f32))),
                                                 PatternValue::Uint(n) => Ok(Some(PatternValue::Float(*n as f32))),
                                                 PatternValue::Ulong(n) => Ok(Some(PatternValue::Float(*n as f32))),
-                                                PatternValue::Float(n) => Ok(Some(PatternValue::Float(*n as f32))),
+                                                PatternValue::Float(n) => Ok(Some(PatternValue::Float(*n))),
                                                 PatternValue::Double(n) => Ok(Some(PatternValue::Float(*n as f32))),
                                                 _ => Err(FrontendErrors::new(vec![FrontendError::Internal(String::from("convert_pattern_value_for_type_value: invalid value"))])),
                                             }
@@ -2557,7 +2548,7 @@ impl Evaluator
                                                 PatternValue::Uint(n) => Ok(Some(PatternValue::Double(*n as f64))),
                                                 PatternValue::Ulong(n) => Ok(Some(PatternValue::Double(*n as f64))),
                                                 PatternValue::Float(n) => Ok(Some(PatternValue::Double(*n as f64))),
-                                                PatternValue::Double(n) => Ok(Some(PatternValue::Double(*n as f64))),
+                                                PatternValue::Double(n) => Ok(Some(PatternValue::Double(*n))),
                                                 _ => Err(FrontendErrors::new(vec![FrontendError::Internal(String::from("convert_pattern_value_for_type_value: invalid value"))])),
                                             }
                                         } else {
@@ -2741,7 +2732,7 @@ impl Evaluator
                                             Some(false) => return Ok(Some(false)),
                                             None => return Ok(None),
                                         }
-                                        if (&elem_values[1..]).iter().any(|e| e != &elem_values[0]) {
+                                        if elem_values[1..].iter().any(|e| e != &elem_values[0]) {
                                             return Ok(Some(false));
                                         }
                                     }
@@ -2786,7 +2777,7 @@ impl Evaluator
     {
         match expr {
             Expr::Literal(literal, Some(local_type), _) => {
-                match self.evaluate_value_for_expr_literal(&**literal, tree, var_env, type_stack, local_types, closures, var_key, errs)? {
+                match self.evaluate_value_for_expr_literal(literal, tree, var_env, type_stack, local_types, closures, var_key, errs)? {
                     Some(mut value) => {
                         value.set_shared_flag(shared_flag_for_local_type(*local_type, tree, type_stack, local_types)?);
                         Ok(Some(value))
@@ -2797,7 +2788,7 @@ impl Evaluator
             Expr::Lambda(args, _, body, _, Some(local_type), Some(local_fun), _, _) => {
                 let mut lambda_var_env = Environment::new();
                 lambda_var_env.push_new_vars();
-                for arg in &*args {
+                for arg in args {
                     match arg {
                         LambdaArg(ident, _, _, _) => {
                             lambda_var_env.add_var(ident.clone(), ());
@@ -2805,7 +2796,7 @@ impl Evaluator
                     }
                 }
                 let mut closure = Closure::new();
-                self.add_closure_vars_for_expr(&**body, var_env, &mut lambda_var_env, &mut closure);
+                self.add_closure_vars_for_expr(body, var_env, &mut lambda_var_env, &mut closure);
                 closures.insert(*local_fun, closure);
                 lambda_var_env.pop_vars();
                 let shared_flag = shared_flag_for_local_type(*local_type, tree, type_stack, local_types)?;
@@ -2828,7 +2819,7 @@ impl Evaluator
                                 NamedFieldPair(field_ident, expr2, _) => {
                                     match named_fields.field_index(field_ident) {
                                         Some(field_idx) => {
-                                            match self.evaluate_value_for_expr(&**expr2, tree, var_env, type_stack, local_types, closures, var_key, errs)? {
+                                            match self.evaluate_value_for_expr(expr2, tree, var_env, type_stack, local_types, closures, var_key, errs)? {
                                                 Some(field_value) => field_values[field_idx] = field_value,
                                                 None => return Ok(None),
                                             }
@@ -2847,14 +2838,14 @@ impl Evaluator
                 Ok(None)
             },
             Expr::App(expr2, exprs, Some(local_type), pos) => {
-                match self.evaluate_value_for_expr(&**expr2, tree, var_env, type_stack, local_types, closures, var_key, errs)? {
+                match self.evaluate_value_for_expr(expr2, tree, var_env, type_stack, local_types, closures, var_key, errs)? {
                     Some(Value::Object(_, object)) => {
                         let object_r = object.borrow();
                         match &*object_r {
                             Object::Con(con_ident) => {
                                 let mut field_values: Vec<Value> = Vec::new();
                                 for expr3 in exprs {
-                                    match self.evaluate_value_for_expr(&**expr3, tree, var_env, type_stack, local_types, closures, var_key, errs)? {
+                                    match self.evaluate_value_for_expr(expr3, tree, var_env, type_stack, local_types, closures, var_key, errs)? {
                                         Some(field_value) => field_values.push(field_value),
                                         None => return Ok(None),
                                     }
@@ -2865,13 +2856,13 @@ impl Evaluator
                             Object::EvalFun(_, _, fun) => {
                                 let mut arg_values: Vec<Value> = Vec::new();
                                 for expr3 in exprs {
-                                    match self.evaluate_value_for_expr(&**expr3, tree, var_env, type_stack, local_types, closures, var_key, errs)? {
+                                    match self.evaluate_value_for_expr(expr3, tree, var_env, type_stack, local_types, closures, var_key, errs)? {
                                         Some(arg_value) => arg_values.push(arg_value),
                                         None => return Ok(None),
                                     }
                                 }
                                 let mut ref_values = tree.ref_values().borrow_mut();
-                                match fun(arg_values.as_slice(), &mut *ref_values, pos) {
+                                match fun(arg_values.as_slice(), &mut ref_values, pos) {
                                     Ok(mut value) => {
                                         value.set_shared_flag(shared_flag_for_local_type(*local_type, tree, type_stack, local_types)?);
                                         Ok(Some(value))
@@ -2897,10 +2888,10 @@ impl Evaluator
                 }
             },
             Expr::GetField(expr2, fields, _, pos) => {
-                match self.evaluate_value_for_expr(&**expr2, tree, var_env, type_stack, local_types, closures, var_key, errs)? {
+                match self.evaluate_value_for_expr(expr2, tree, var_env, type_stack, local_types, closures, var_key, errs)? {
                     Some(mut value) => {
                         let mut value2: Option<Value> = None;
-                        self.value_for_fields_in(&mut value, expr_local_type(&**expr2)?, fields.as_slice(), pos, tree, local_types, false, errs, |value, _| {
+                        self.value_for_fields_in(&mut value, expr_local_type(expr2)?, fields.as_slice(), pos, tree, local_types, false, errs, |value, _| {
                                 value2 = Some(value.clone());
                                 Ok(false)
                         })?;
@@ -2910,10 +2901,10 @@ impl Evaluator
                 }
             },
             Expr::Get2Field(expr2, fields, Some(local_type), pos) => {
-                match self.evaluate_value_for_expr(&**expr2, tree, var_env, type_stack, local_types, closures, var_key, errs)? {
+                match self.evaluate_value_for_expr(expr2, tree, var_env, type_stack, local_types, closures, var_key, errs)? {
                     Some(mut value) => {
                         let mut value2: Option<Value> = None;
-                        self.value_for_fields_in(&mut value, expr_local_type(&**expr2)?, fields.as_slice(), pos, tree, local_types, false, errs, |value, _| {
+                        self.value_for_fields_in(&mut value, expr_local_type(expr2)?, fields.as_slice(), pos, tree, local_types, false, errs, |value, _| {
                                 value2 = Some(value.clone());
                                 Ok(false)
                         })?;
@@ -2929,10 +2920,10 @@ impl Evaluator
                 }
             },
             Expr::SetField(expr2, fields, expr3, _, pos) => {
-                match self.evaluate_value_for_expr(&**expr2, tree, var_env, type_stack, local_types, closures, var_key, errs)? {
+                match self.evaluate_value_for_expr(expr2, tree, var_env, type_stack, local_types, closures, var_key, errs)? {
                     Some(mut value) => {
-                        let is_success = self.value_for_fields_in(&mut value, expr_local_type(&**expr2)?, fields.as_slice(), pos, tree, local_types, true, errs, |value, errs| {
-                                match self.evaluate_value_for_expr(&**expr3, tree, var_env, type_stack, local_types, closures, var_key, errs)? {
+                        let is_success = self.value_for_fields_in(&mut value, expr_local_type(expr2)?, fields.as_slice(), pos, tree, local_types, true, errs, |value, errs| {
+                                match self.evaluate_value_for_expr(expr3, tree, var_env, type_stack, local_types, closures, var_key, errs)? {
                                     Some(value2) => {
                                         *value = value2;
                                         Ok(true)
@@ -2958,7 +2949,7 @@ impl Evaluator
                 Ok(None)
             },
             Expr::Uniq(expr2, _, _) => {
-                match self.evaluate_value_for_expr(&**expr2, tree, var_env, type_stack, local_types, closures, var_key, errs)? {
+                match self.evaluate_value_for_expr(expr2, tree, var_env, type_stack, local_types, closures, var_key, errs)? {
                     Some(Value::Object(SharedFlag::Shared, object)) => {
                         let object_r = object.borrow();
                         Ok(Some(Value::Object(SharedFlag::None, Rc::new(RefCell::new(object_r.clone())))))
@@ -2968,7 +2959,7 @@ impl Evaluator
                 }
             },
             Expr::Shared(expr2, _, _) => {
-                match self.evaluate_value_for_expr(&**expr2, tree, var_env, type_stack, local_types, closures, var_key, errs)? {
+                match self.evaluate_value_for_expr(expr2, tree, var_env, type_stack, local_types, closures, var_key, errs)? {
                     Some(Value::Object(SharedFlag::None, object)) => {
                         Ok(Some(Value::Object(SharedFlag::Shared, object.clone())))
                     },
@@ -2976,17 +2967,17 @@ impl Evaluator
                     None => Ok(None),
                 }
             },
-            Expr::Typed(expr2, _, _, _) => self.evaluate_value_for_expr(&**expr2, tree, var_env, type_stack, local_types, closures, var_key, errs),
+            Expr::Typed(expr2, _, _, _) => self.evaluate_value_for_expr(expr2, tree, var_env, type_stack, local_types, closures, var_key, errs),
             Expr::As(expr2, _, Some(local_type), pos) => {
-                match self.evaluate_value_for_expr(&**expr2, tree, var_env, type_stack, local_types, closures, var_key, errs)? {
+                match self.evaluate_value_for_expr(expr2, tree, var_env, type_stack, local_types, closures, var_key, errs)? {
                     Some(value) => self.convert_value_for_type_value(&value, &Rc::new(TypeValue::Param(UniqFlag::None, *local_type)), pos, tree, local_types, errs),
                     None => Ok(None),
                 }
             },
             Expr::If(expr2, expr3, expr4, _, pos) => {
-                match self.evaluate_value_for_expr(&**expr2, tree, var_env, type_stack, local_types, closures, var_key, errs)? {
-                    Some(Value::Bool(true)) => self.evaluate_value_for_expr(&**expr3, tree, var_env, type_stack, local_types, closures, var_key, errs),
-                    Some(Value::Bool(false)) => self.evaluate_value_for_expr(&**expr4, tree, var_env, type_stack, local_types, closures, var_key, errs),
+                match self.evaluate_value_for_expr(expr2, tree, var_env, type_stack, local_types, closures, var_key, errs)? {
+                    Some(Value::Bool(true)) => self.evaluate_value_for_expr(expr3, tree, var_env, type_stack, local_types, closures, var_key, errs),
+                    Some(Value::Bool(false)) => self.evaluate_value_for_expr(expr4, tree, var_env, type_stack, local_types, closures, var_key, errs),
                     Some(Value::Object(_, object)) => {
                         let object_r = object.borrow();
                         match &*object_r {
@@ -3006,7 +2997,7 @@ impl Evaluator
                 for bind in binds {
                     match bind {
                         Bind(pattern, expr3) => {
-                            match self.evaluate_value_for_expr(&**expr3, tree, var_env, type_stack, local_types, closures, var_key, errs)? {
+                            match self.evaluate_value_for_expr(expr3, tree, var_env, type_stack, local_types, closures, var_key, errs)? {
                                 Some(value) => {
                                     match self.evaluate_pattern_value_for_pattern(pattern, tree, type_stack, local_types, errs)? {
                                         Some(pattern_value) => {
@@ -3024,7 +3015,7 @@ impl Evaluator
                         },
                     }
                 }
-                match self.evaluate_value_for_expr(&**expr2, tree, var_env, type_stack, local_types, closures, var_key, errs)? {
+                match self.evaluate_value_for_expr(expr2, tree, var_env, type_stack, local_types, closures, var_key, errs)? {
                     Some(value) => {
                         var_env.pop_vars();
                         Ok(Some(value))
@@ -3033,7 +3024,7 @@ impl Evaluator
                 }
             },
             Expr::Match(expr2, cases, _, pos) => {
-                match self.evaluate_value_for_expr(&**expr2, tree, var_env, type_stack, local_types, closures, var_key, errs)? {
+                match self.evaluate_value_for_expr(expr2, tree, var_env, type_stack, local_types, closures, var_key, errs)? {
                     Some(value) => {
                         for case in cases {
                             match case {
@@ -3043,7 +3034,7 @@ impl Evaluator
                                         Some(pattern_value) => {
                                             match self.match_value_with_pattern_value(&value, &pattern_value, pos, var_env, errs)? {
                                                 Some(true) => {
-                                                    match self.evaluate_value_for_expr(&**expr3, tree, var_env, type_stack, local_types, closures, var_key, errs)? {
+                                                    match self.evaluate_value_for_expr(expr3, tree, var_env, type_stack, local_types, closures, var_key, errs)? {
                                                         Some(value) => {
                                                            var_env.pop_vars();
                                                            return Ok(Some(value));
@@ -3073,9 +3064,9 @@ impl Evaluator
     fn evaluate_pattern_value_for_pattern(&self, pattern: &Pattern, tree: &Tree, type_stack: &mut TypeStack, local_types: &LocalTypes, errs: &mut Vec<FrontendError>) -> FrontendResultWithErrors<Option<PatternValue>>
     {
         match pattern {
-            Pattern::Literal(literal, _, _) => self.evaluate_pattern_value_for_pattern_literal(&**literal, tree, type_stack, local_types, errs),
+            Pattern::Literal(literal, _, _) => self.evaluate_pattern_value_for_pattern_literal(literal, tree, type_stack, local_types, errs),
             Pattern::As(literal, _, _, Some(local_type), pos) => {
-                match self.evaluate_pattern_value_for_pattern_literal(&**literal, tree, type_stack, local_types, errs)? {
+                match self.evaluate_pattern_value_for_pattern_literal(literal, tree, type_stack, local_types, errs)? {
                     Some(pattern_value) => self.convert_pattern_value_for_type_value(&pattern_value, &Rc::new(TypeValue::Param(UniqFlag::None, *local_type)), pos, tree, local_types, errs),
                     None => Ok(None),
                 }
@@ -3105,7 +3096,7 @@ impl Evaluator
                                 NamedFieldPair(field_ident, pattern2, _) => {
                                     match named_fields.field_index(field_ident) {
                                         Some(field_idx) => {
-                                            match self.evaluate_pattern_value_for_pattern(&**pattern2, tree, type_stack, local_types, errs)? {
+                                            match self.evaluate_pattern_value_for_pattern(pattern2, tree, type_stack, local_types, errs)? {
                                                 Some(field_pattern_value) => field_pattern_values[field_idx] = field_pattern_value,
                                                 None => return Ok(None),
                                             }
@@ -3120,7 +3111,7 @@ impl Evaluator
             },
             Pattern::Var(_, ident, _, _) => Ok(Some(PatternValue::Object(Rc::new(RefCell::new(PatternObject::Var(ident.clone())))))),
             Pattern::At(_, ident, pattern2, _, _) => {
-                match self.evaluate_pattern_value_for_pattern(&**pattern2, tree, type_stack, local_types, errs)? {
+                match self.evaluate_pattern_value_for_pattern(pattern2, tree, type_stack, local_types, errs)? {
                     Some(pattern_value) => Ok(Some(PatternValue::Object(Rc::new(RefCell::new(PatternObject::At(ident.clone(), pattern_value)))))),
                     None => Ok(None),
                 }
@@ -3155,7 +3146,7 @@ impl Evaluator
             Literal::Tuple(field_exprs) => {
                 let mut field_values: Vec<Value> = Vec::new();
                 for field_expr in field_exprs {
-                    match self.evaluate_value_for_expr(&**field_expr, tree, var_env, type_stack, local_types, closures, var_key, errs)? {
+                    match self.evaluate_value_for_expr(field_expr, tree, var_env, type_stack, local_types, closures, var_key, errs)? {
                         Some(field_value) => field_values.push(field_value),
                         None => return Ok(None),
                     }
@@ -3165,7 +3156,7 @@ impl Evaluator
             Literal::Array(elem_exprs) => {
                 let mut elem_values: Vec<Value> = Vec::new();
                 for elem_expr in elem_exprs {
-                    match self.evaluate_value_for_expr(&**elem_expr, tree, var_env, type_stack, local_types, closures, var_key, errs)? {
+                    match self.evaluate_value_for_expr(elem_expr, tree, var_env, type_stack, local_types, closures, var_key, errs)? {
                         Some(elem_value) => elem_values.push(elem_value),
                         None => return Ok(None),
                     }
@@ -3173,7 +3164,7 @@ impl Evaluator
                 Ok(Some(Value::Object(SharedFlag::Shared, Rc::new(RefCell::new(Object::Array(elem_values))))))
             },
             Literal::FilledArray(elem_expr, len) => {
-                match self.evaluate_value_for_expr(&**elem_expr, tree, var_env, type_stack, local_types, closures, var_key, errs)? {
+                match self.evaluate_value_for_expr(elem_expr, tree, var_env, type_stack, local_types, closures, var_key, errs)? {
                     Some(elem_value) => Ok(Some(Value::Object(SharedFlag::Shared, Rc::new(RefCell::new(Object::Array(vec![elem_value; *len])))))),
                     None => Ok(None),
                 }
@@ -3228,21 +3219,20 @@ impl Evaluator
             Expr::Literal(literal, _, _) => self.do_literal_for_closure(&**literal, |evaluator, expr| evaluator.add_closure_vars_for_expr(expr, closure_var_env, var_env, closure)),
             Expr::Lambda(args, _, body, _, _, _, _, _) => {
                 var_env.push_new_vars();
-                for arg in &*args {
+                for arg in args {
                     match arg {
                         LambdaArg(ident, _, _, _) => {
                             var_env.add_var(ident.clone(), ());
                         },
                     }
                 }
-                self.add_closure_vars_for_expr(&**body, closure_var_env, var_env, closure);
+                self.add_closure_vars_for_expr(body, closure_var_env, var_env, closure);
                 var_env.pop_vars();
             },
             Expr::Var(ident, _, _) => {
                 if var_env.var(ident).is_none() {
-                    match closure_var_env.var(ident) {
-                        Some(value) => closure.add_value(ident.clone(), value.clone()),
-                        None => (),
+                    if let Some(value) = closure_var_env.var(ident) {
+                        closure.add_value(ident.clone(), value.clone());
                     }
                 }
             },
@@ -3251,59 +3241,59 @@ impl Evaluator
             },
             Expr::PrintfApp(exprs, _, _) => {
                 for expr2 in exprs {
-                    self.add_closure_vars_for_expr(&**expr2, closure_var_env, var_env, closure);
+                    self.add_closure_vars_for_expr(expr2, closure_var_env, var_env, closure);
                 }
             },
             Expr::App(expr2, exprs, _, _) => {
-                self.add_closure_vars_for_expr(&**expr2, closure_var_env, var_env, closure);
+                self.add_closure_vars_for_expr(expr2, closure_var_env, var_env, closure);
                 for expr3 in exprs {
-                    self.add_closure_vars_for_expr(&**expr3, closure_var_env, var_env, closure);
+                    self.add_closure_vars_for_expr(expr3, closure_var_env, var_env, closure);
                 }
             },
-            Expr::GetField(expr2, _, _, _) => self.add_closure_vars_for_expr(&**expr2, closure_var_env, var_env, closure),
-            Expr::Get2Field(expr2, _, _, _) => self.add_closure_vars_for_expr(&**expr2, closure_var_env, var_env, closure),
+            Expr::GetField(expr2, _, _, _) => self.add_closure_vars_for_expr(expr2, closure_var_env, var_env, closure),
+            Expr::Get2Field(expr2, _, _, _) => self.add_closure_vars_for_expr(expr2, closure_var_env, var_env, closure),
             Expr::SetField(expr2, _, expr3, _, _) => {
-                self.add_closure_vars_for_expr(&**expr2, closure_var_env, var_env, closure);
-                self.add_closure_vars_for_expr(&**expr3, closure_var_env, var_env, closure);
+                self.add_closure_vars_for_expr(expr2, closure_var_env, var_env, closure);
+                self.add_closure_vars_for_expr(expr3, closure_var_env, var_env, closure);
             },
             Expr::UpdateField(expr2, _, expr3, _, _) => {
-                self.add_closure_vars_for_expr(&**expr2, closure_var_env, var_env, closure);
-                self.add_closure_vars_for_expr(&**expr3, closure_var_env, var_env, closure);
+                self.add_closure_vars_for_expr(expr2, closure_var_env, var_env, closure);
+                self.add_closure_vars_for_expr(expr3, closure_var_env, var_env, closure);
             },
             Expr::UpdateGet2Field(expr2, _, expr3, _, _) => {
-                self.add_closure_vars_for_expr(&**expr2, closure_var_env, var_env, closure);
-                self.add_closure_vars_for_expr(&**expr3, closure_var_env, var_env, closure);
+                self.add_closure_vars_for_expr(expr2, closure_var_env, var_env, closure);
+                self.add_closure_vars_for_expr(expr3, closure_var_env, var_env, closure);
             },
-            Expr::Uniq(expr2, _, _) => self.add_closure_vars_for_expr(&**expr2, closure_var_env, var_env, closure),
-            Expr::Shared(expr2, _, _) => self.add_closure_vars_for_expr(&**expr2, closure_var_env, var_env, closure),
-            Expr::Typed(expr2, _, _, _) => self.add_closure_vars_for_expr(&**expr2, closure_var_env, var_env, closure),
-            Expr::As(expr2, _, _, _) => self.add_closure_vars_for_expr(&**expr2, closure_var_env, var_env, closure),
+            Expr::Uniq(expr2, _, _) => self.add_closure_vars_for_expr(expr2, closure_var_env, var_env, closure),
+            Expr::Shared(expr2, _, _) => self.add_closure_vars_for_expr(expr2, closure_var_env, var_env, closure),
+            Expr::Typed(expr2, _, _, _) => self.add_closure_vars_for_expr(expr2, closure_var_env, var_env, closure),
+            Expr::As(expr2, _, _, _) => self.add_closure_vars_for_expr(expr2, closure_var_env, var_env, closure),
             Expr::If(expr2, expr3, expr4, _, _) => {
-                self.add_closure_vars_for_expr(&**expr2, closure_var_env, var_env, closure);
-                self.add_closure_vars_for_expr(&**expr3, closure_var_env, var_env, closure);
-                self.add_closure_vars_for_expr(&**expr4, closure_var_env, var_env, closure);
+                self.add_closure_vars_for_expr(expr2, closure_var_env, var_env, closure);
+                self.add_closure_vars_for_expr(expr3, closure_var_env, var_env, closure);
+                self.add_closure_vars_for_expr(expr4, closure_var_env, var_env, closure);
             },
             Expr::Let(binds, expr2, _, _) => {
                 var_env.push_new_vars();
                 for bind in binds {
                     match bind {
                         Bind(pattern, expr3) => {
-                            self.add_closure_vars_for_expr(&**expr3, closure_var_env, var_env, closure);
-                            self.add_vars_for_pattern(&**pattern, var_env);
+                            self.add_closure_vars_for_expr(expr3, closure_var_env, var_env, closure);
+                            self.add_vars_for_pattern(pattern, var_env);
                         },
                     }
                 }
-                self.add_closure_vars_for_expr(&**expr2, closure_var_env, var_env, closure);
+                self.add_closure_vars_for_expr(expr2, closure_var_env, var_env, closure);
                 var_env.pop_vars();
             },
             Expr::Match(expr2, cases, _, _) => {
-                self.add_closure_vars_for_expr(&**expr2, closure_var_env, var_env, closure);
+                self.add_closure_vars_for_expr(expr2, closure_var_env, var_env, closure);
                 for case in cases {
                     match case {
                         Case(pattern, expr3) => {
                             var_env.push_new_vars();
-                            self.add_vars_for_pattern(&**pattern, var_env);
-                            self.add_closure_vars_for_expr(&**expr3, closure_var_env, var_env, closure);
+                            self.add_vars_for_pattern(pattern, var_env);
+                            self.add_closure_vars_for_expr(expr3, closure_var_env, var_env, closure);
                             var_env.pop_vars();
                         },
                     }
@@ -3343,9 +3333,8 @@ impl Evaluator
         match expr {
             Expr::Literal(literal, _, _) => self.do_literal_mut_for_setting(&mut **literal, |evaluator, expr| evaluator.set_closures_for_expr(expr, closures))?,
             Expr::Lambda(_, _, _, _, _, Some(local_fun), closure, _) => {
-                match closures.remove(local_fun) {
-                    Some(closure2) => *closure = Some(Box::new(closure2)),
-                    None => (),
+                if let Some(closure2) = closures.remove(local_fun) {
+                    *closure = Some(Box::new(closure2));
                 }
             },
             Expr::Var(_, _, _) => (),
@@ -3354,51 +3343,51 @@ impl Evaluator
             },
             Expr::PrintfApp(exprs, _, _) => {
                 for expr2 in exprs {
-                    self.set_closures_for_expr(&mut **expr2, closures)?;
+                    self.set_closures_for_expr(expr2, closures)?;
                 }
             },
             Expr::App(expr2, exprs, _, _) => {
-                self.set_closures_for_expr(&mut **expr2, closures)?;
+                self.set_closures_for_expr(expr2, closures)?;
                 for expr3 in exprs {
-                    self.set_closures_for_expr(&mut **expr3, closures)?;
+                    self.set_closures_for_expr(expr3, closures)?;
                 }
             },
-            Expr::GetField(expr2, _, _, _) => self.set_closures_for_expr(&mut **expr2, closures)?,
-            Expr::Get2Field(expr2, _, _, _) => self.set_closures_for_expr(&mut **expr2, closures)?,
+            Expr::GetField(expr2, _, _, _) => self.set_closures_for_expr(expr2, closures)?,
+            Expr::Get2Field(expr2, _, _, _) => self.set_closures_for_expr(expr2, closures)?,
             Expr::SetField(expr2, _, expr3, _, _) => {
-                self.set_closures_for_expr(&mut **expr2, closures)?;
-                self.set_closures_for_expr(&mut **expr3, closures)?;
+                self.set_closures_for_expr(expr2, closures)?;
+                self.set_closures_for_expr(expr3, closures)?;
             },
             Expr::UpdateField(expr2, _, expr3, _, _) => {
-                self.set_closures_for_expr(&mut **expr2, closures)?;
-                self.set_closures_for_expr(&mut **expr3, closures)?;
+                self.set_closures_for_expr(expr2, closures)?;
+                self.set_closures_for_expr(expr3, closures)?;
             },
             Expr::UpdateGet2Field(expr2, _, expr3, _, _) => {
-                self.set_closures_for_expr(&mut **expr2, closures)?;
-                self.set_closures_for_expr(&mut **expr3, closures)?;
+                self.set_closures_for_expr(expr2, closures)?;
+                self.set_closures_for_expr(expr3, closures)?;
             },
-            Expr::Uniq(expr2, _, _) => self.set_closures_for_expr(&mut **expr2, closures)?,
-            Expr::Shared(expr2, _, _) => self.set_closures_for_expr(&mut **expr2, closures)?,
-            Expr::Typed(expr2, _, _, _) => self.set_closures_for_expr(&mut **expr2, closures)?,
-            Expr::As(expr2, _, _, _) => self.set_closures_for_expr(&mut **expr2, closures)?,
+            Expr::Uniq(expr2, _, _) => self.set_closures_for_expr(expr2, closures)?,
+            Expr::Shared(expr2, _, _) => self.set_closures_for_expr(expr2, closures)?,
+            Expr::Typed(expr2, _, _, _) => self.set_closures_for_expr(expr2, closures)?,
+            Expr::As(expr2, _, _, _) => self.set_closures_for_expr(expr2, closures)?,
             Expr::If(expr2, expr3, expr4, _, _) => {
-                self.set_closures_for_expr(&mut **expr2, closures)?;
-                self.set_closures_for_expr(&mut **expr3, closures)?;
-                self.set_closures_for_expr(&mut **expr4, closures)?;
+                self.set_closures_for_expr(expr2, closures)?;
+                self.set_closures_for_expr(expr3, closures)?;
+                self.set_closures_for_expr(expr4, closures)?;
             },
             Expr::Let(binds, expr2, _, _) => {
                 for bind in binds {
                     match bind {
-                        Bind(_, expr3) => self.set_closures_for_expr(&mut **expr3, closures)?,
+                        Bind(_, expr3) => self.set_closures_for_expr(expr3, closures)?,
                     }
                 }
-                self.set_closures_for_expr(&mut **expr2, closures)?;
+                self.set_closures_for_expr(expr2, closures)?;
             },
             Expr::Match(expr2, cases, _, _) => {
-                self.set_closures_for_expr(&mut **expr2, closures)?;
+                self.set_closures_for_expr(expr2, closures)?;
                 for case in cases {
                     match case {
-                        Case(_, expr3) => self.set_closures_for_expr(&mut **expr3, closures)?,
+                        Case(_, expr3) => self.set_closures_for_expr(expr3, closures)?,
                     }
                 }
             },
@@ -3432,7 +3421,7 @@ impl Evaluator
                     ImplFun(_, body, _, Some(local_types)) => {
                         let mut type_stack = TypeStack::new();
                         type_stack.set_first_type_values_for_type(typ);
-                        self.check_pattern_exhaustions_for_expr(&**body, tree, &mut type_stack, local_types, errs)?;
+                        self.check_pattern_exhaustions_for_expr(body, tree, &mut type_stack, local_types, errs)?;
                     },
                     _ => return Err(FrontendErrors::new(vec![FrontendError::Internal(String::from("check_pattern_exhaustions_for_impl_fun: no local types"))])),
                 }
@@ -61,7 +61,7 @@ a: Int = 1 + 2;
     }
     assert_eq!(4, tree.defs().len());
     match &*tree.defs()[3] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, _, _, _, _, _, Some(value)) => {
@@ -152,7 +152,7 @@ abc: U = D(C { x: 2, y: 2.5, z: 3, });
     }
     assert_eq!(27, tree.defs().len());
     match &*tree.defs()[10] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, expr, _, _, _, _, Some(value)) => {
@@ -176,7 +176,7 @@ abc: U = D(C { x: 2, y: 2.5, z: 3, });
         _ => assert!(false),
     }
     match &*tree.defs()[11] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, expr, _, _, _, _, Some(value)) => {
@@ -200,7 +200,7 @@ abc: U = D(C { x: 2, y: 2.5, z: 3, });
         _ => assert!(false),
     }
     match &*tree.defs()[12] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, _, _, _, _, _, Some(value)) => {
@@ -212,7 +212,7 @@ abc: U = D(C { x: 2, y: 2.5, z: 3, });
         _ => assert!(false),
     }
     match &*tree.defs()[13] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, _, _, _, _, _, Some(value)) => {
@@ -224,7 +224,7 @@ abc: U = D(C { x: 2, y: 2.5, z: 3, });
         _ => assert!(false),
     }
     match &*tree.defs()[14] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, _, _, _, _, _, Some(value)) => {
@@ -236,7 +236,7 @@ abc: U = D(C { x: 2, y: 2.5, z: 3, });
         _ => assert!(false),
     }
     match &*tree.defs()[15] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, _, _, _, _, _, Some(value)) => {
@@ -248,7 +248,7 @@ abc: U = D(C { x: 2, y: 2.5, z: 3, });
         _ => assert!(false),
     }
     match &*tree.defs()[16] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, _, _, _, _, _, Some(value)) => {
@@ -260,7 +260,7 @@ abc: U = D(C { x: 2, y: 2.5, z: 3, });
         _ => assert!(false),
     }
     match &*tree.defs()[17] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, _, _, _, _, _, Some(value)) => {
@@ -272,7 +272,7 @@ abc: U = D(C { x: 2, y: 2.5, z: 3, });
         _ => assert!(false),
     }
     match &*tree.defs()[18] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, _, _, _, _, _, Some(value)) => {
@@ -284,7 +284,7 @@ abc: U = D(C { x: 2, y: 2.5, z: 3, });
         _ => assert!(false),
     }
     match &*tree.defs()[19] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, _, _, _, _, _, Some(value)) => {
@@ -296,7 +296,7 @@ abc: U = D(C { x: 2, y: 2.5, z: 3, });
         _ => assert!(false),
     }
     match &*tree.defs()[20] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, _, _, _, _, _, Some(value)) => {
@@ -308,7 +308,7 @@ abc: U = D(C { x: 2, y: 2.5, z: 3, });
         _ => assert!(false),
     }
     match &*tree.defs()[21] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, _, _, _, _, _, Some(value)) => {
@@ -320,7 +320,7 @@ abc: U = D(C { x: 2, y: 2.5, z: 3, });
         _ => assert!(false),
     }
     match &*tree.defs()[22] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, _, _, _, _, _, Some(value)) => {
@@ -332,7 +332,7 @@ abc: U = D(C { x: 2, y: 2.5, z: 3, });
         _ => assert!(false),
     }
     match &*tree.defs()[23] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, _, _, _, _, _, Some(value)) => {
@@ -344,7 +344,7 @@ abc: U = D(C { x: 2, y: 2.5, z: 3, });
         _ => assert!(false),
     }
     match &*tree.defs()[24] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, _, _, _, _, _, Some(value)) => {
@@ -356,7 +356,7 @@ abc: U = D(C { x: 2, y: 2.5, z: 3, });
         _ => assert!(false),
     }
     match &*tree.defs()[25] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, _, _, _, _, _, Some(value)) => {
@@ -368,7 +368,7 @@ abc: U = D(C { x: 2, y: 2.5, z: 3, });
         _ => assert!(false),
     }
     match &*tree.defs()[26] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, _, _, _, _, _, Some(value)) => {
@@ -447,7 +447,7 @@ X: Int = 1;
     }
     assert_eq!(11, tree.defs().len());
     match &*tree.defs()[5] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, _, _, _, _, _, Some(value)) => {
@@ -459,7 +459,7 @@ X: Int = 1;
         _ => assert!(false),
     }
     match &*tree.defs()[6] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, _, _, _, _, _, Some(value)) => {
@@ -471,7 +471,7 @@ X: Int = 1;
         _ => assert!(false),
     }
     match &*tree.defs()[7] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, _, _, _, _, _, Some(value)) => {
@@ -483,7 +483,7 @@ X: Int = 1;
         _ => assert!(false),
     }
     match &*tree.defs()[8] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, _, _, _, _, _, Some(value)) => {
@@ -495,7 +495,7 @@ X: Int = 1;
         _ => assert!(false),
     }
     match &*tree.defs()[9] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, _, _, _, _, _, Some(value)) => {
@@ -507,7 +507,7 @@ X: Int = 1;
         _ => assert!(false),
     }
     match &*tree.defs()[10] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, _, _, _, _, _, Some(value)) => {
@@ -583,7 +583,7 @@ y: Int = 2;
     }
     assert_eq!(23, tree.defs().len());
     match &*tree.defs()[9] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, _, _, _, _, _, Some(value)) => {
@@ -595,7 +595,7 @@ y: Int = 2;
         _ => assert!(false),
     }
     match &*tree.defs()[10] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, _, _, _, _, _, Some(value)) => {
@@ -607,7 +607,7 @@ y: Int = 2;
         _ => assert!(false),
     }
     match &*tree.defs()[11] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, _, _, _, _, _, Some(value)) => {
@@ -619,7 +619,7 @@ y: Int = 2;
         _ => assert!(false),
     }
     match &*tree.defs()[12] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, _, _, _, _, _, Some(value)) => {
@@ -631,7 +631,7 @@ y: Int = 2;
         _ => assert!(false),
     }
     match &*tree.defs()[13] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, _, _, _, _, _, Some(value)) => {
@@ -643,7 +643,7 @@ y: Int = 2;
         _ => assert!(false),
     }
     match &*tree.defs()[14] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, _, _, _, _, _, Some(value)) => {
@@ -655,7 +655,7 @@ y: Int = 2;
         _ => assert!(false),
     }
     match &*tree.defs()[15] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, _, _, _, _, _, Some(value)) => {
@@ -667,7 +667,7 @@ y: Int = 2;
         _ => assert!(false),
     }
     match &*tree.defs()[16] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, _, _, _, _, _, Some(value)) => {
@@ -679,7 +679,7 @@ y: Int = 2;
         _ => assert!(false),
     }
     match &*tree.defs()[17] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, _, _, _, _, _, Some(value)) => {
@@ -691,7 +691,7 @@ y: Int = 2;
         _ => assert!(false),
     }
     match &*tree.defs()[18] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, _, _, _, _, _, Some(value)) => {
@@ -703,7 +703,7 @@ y: Int = 2;
         _ => assert!(false),
     }
     match &*tree.defs()[19] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, _, _, _, _, _, Some(value)) => {
@@ -715,7 +715,7 @@ y: Int = 2;
         _ => assert!(false),
     }
     match &*tree.defs()[20] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, _, _, _, _, _, Some(value)) => {
@@ -727,7 +727,7 @@ y: Int = 2;
         _ => assert!(false),
     }
     match &*tree.defs()[21] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, _, _, _, _, _, Some(value)) => {
@@ -739,7 +739,7 @@ y: Int = 2;
         _ => assert!(false),
     }
     match &*tree.defs()[22] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, _, _, _, _, _, Some(value)) => {
@@ -861,7 +861,7 @@ m: Int = 1 match {
     }
     assert_eq!(26, tree.defs().len());
     match &*tree.defs()[13] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, _, _, _, _, _, Some(value)) => {
@@ -873,7 +873,7 @@ m: Int = 1 match {
         _ => assert!(false),
     }
     match &*tree.defs()[14] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, _, _, _, _, _, Some(value)) => {
@@ -885,7 +885,7 @@ m: Int = 1 match {
         _ => assert!(false),
     }
     match &*tree.defs()[15] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, _, _, _, _, _, Some(value)) => {
@@ -897,7 +897,7 @@ m: Int = 1 match {
         _ => assert!(false),
     }
     match &*tree.defs()[16] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, _, _, _, _, _, Some(value)) => {
@@ -909,7 +909,7 @@ m: Int = 1 match {
         _ => assert!(false),
     }
     match &*tree.defs()[17] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, _, _, _, _, _, Some(value)) => {
@@ -921,7 +921,7 @@ m: Int = 1 match {
         _ => assert!(false),
     }
     match &*tree.defs()[18] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, _, _, _, _, _, Some(value)) => {
@@ -933,7 +933,7 @@ m: Int = 1 match {
         _ => assert!(false),
     }
     match &*tree.defs()[19] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, _, _, _, _, _, Some(value)) => {
@@ -945,7 +945,7 @@ m: Int = 1 match {
         _ => assert!(false),
     }
     match &*tree.defs()[20] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, _, _, _, _, _, Some(value)) => {
@@ -957,7 +957,7 @@ m: Int = 1 match {
         _ => assert!(false),
     }
     match &*tree.defs()[21] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, _, _, _, _, _, Some(value)) => {
@@ -969,7 +969,7 @@ m: Int = 1 match {
         _ => assert!(false),
     }
     match &*tree.defs()[22] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, _, _, _, _, _, Some(value)) => {
@@ -981,7 +981,7 @@ m: Int = 1 match {
         _ => assert!(false),
     }
     match &*tree.defs()[23] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, _, _, _, _, _, Some(value)) => {
@@ -993,7 +993,7 @@ m: Int = 1 match {
         _ => assert!(false),
     }
     match &*tree.defs()[24] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, _, _, _, _, _, Some(value)) => {
@@ -1005,7 +1005,7 @@ m: Int = 1 match {
         _ => assert!(false),
     }
     match &*tree.defs()[25] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, _, _, _, _, _, Some(value)) => {
@@ -1147,7 +1147,7 @@ x: V = a;
         _ => assert!(false),
     }
     match &*tree.defs()[7] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, _, _, _, _, _, Some(value)) => {
@@ -1214,7 +1214,7 @@ y: T<Float> = a;
     }
     assert_eq!(8, tree.defs().len());
     match &*tree.defs()[1] {
-        Def::Trait(_, trait1, _) => {
+        Def::Trait(_, _, trait1, _) => {
             let trait_r = trait1.borrow();
             match &*trait_r {
                 Trait(_, trait_defs, _) => {
@@ -1259,7 +1259,7 @@ y: T<Float> = a;
         _ => assert!(false),
     }
     match &*tree.defs()[6] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, _, _, _, _, _, Some(value)) => {
@@ -1271,7 +1271,7 @@ y: T<Float> = a;
         _ => assert!(false),
     }
     match &*tree.defs()[7] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, _, _, _, _, _, Some(value)) => {
@@ -1328,7 +1328,7 @@ b: Int = if false then 1 else 2;
     }
     assert_eq!(4, tree.defs().len());
     match &*tree.defs()[2] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, _, _, _, _, _, Some(value)) => {
@@ -1340,7 +1340,7 @@ b: Int = if false then 1 else 2;
         _ => assert!(false),
     }
     match &*tree.defs()[3] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, _, _, _, _, _, Some(value)) => {
@@ -1410,7 +1410,7 @@ c: Int = E() match {
     }
     assert_eq!(5, tree.defs().len());
     match &*tree.defs()[2] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, _, _, _, _, _, Some(value)) => {
@@ -1422,7 +1422,7 @@ c: Int = E() match {
         _ => assert!(false),
     }
     match &*tree.defs()[3] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, _, _, _, _, _, Some(value)) => {
@@ -1434,7 +1434,7 @@ c: Int = E() match {
         _ => assert!(false),
     }
     match &*tree.defs()[4] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, _, _, _, _, _, Some(value)) => {
@@ -1497,7 +1497,7 @@ c: (Int) -> Int = let x = 1; in if false then |y| 1 + y else |y| x + y;
     }
     assert_eq!(7, tree.defs().len());
     match &*tree.defs()[4] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, expr, _, _, _, _, Some(value)) => {
@@ -1534,7 +1534,7 @@ c: (Int) -> Int = let x = 1; in if false then |y| 1 + y else |y| x + y;
         _ => assert!(false),
     }
     match &*tree.defs()[5] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, expr, _, _, _, _, Some(value)) => {
@@ -1567,7 +1567,7 @@ c: (Int) -> Int = let x = 1; in if false then |y| 1 + y else |y| x + y;
         _ => assert!(false),
     }
     match &*tree.defs()[6] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, expr, _, _, _, _, Some(value)) => {
@@ -1682,7 +1682,7 @@ impl T for Int
     }
     assert_eq!(18, tree.defs().len());
     match &*tree.defs()[9] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, _, _, _, _, _, Some(value)) => {
@@ -1699,7 +1699,7 @@ impl T for Int
         _ => assert!(false),
     }
     match &*tree.defs()[10] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, _, _, _, _, _, Some(value)) => {
@@ -1716,7 +1716,7 @@ impl T for Int
         _ => assert!(false),
     }
     match &*tree.defs()[11] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, _, _, _, _, _, Some(value)) => {
@@ -1728,7 +1728,7 @@ impl T for Int
         _ => assert!(false),
     }
     match &*tree.defs()[12] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, _, _, _, _, _, Some(value)) => {
@@ -1740,7 +1740,7 @@ impl T for Int
         _ => assert!(false),
     }
     match &*tree.defs()[13] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, _, _, _, _, _, Some(value)) => {
@@ -1752,7 +1752,7 @@ impl T for Int
         _ => assert!(false),
     }
     match &*tree.defs()[14] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, _, _, _, _, _, Some(value)) => {
@@ -1811,7 +1811,7 @@ d: [Float; 2] = [1; 2] as [Float; 2];
     }
     assert_eq!(6, tree.defs().len());
     match &*tree.defs()[2] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, _, _, _, _, _, Some(value)) => {
@@ -1823,7 +1823,7 @@ d: [Float; 2] = [1; 2] as [Float; 2];
         _ => assert!(false),
     }
     match &*tree.defs()[3] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, _, _, _, _, _, Some(value)) => {
@@ -1835,7 +1835,7 @@ d: [Float; 2] = [1; 2] as [Float; 2];
         _ => assert!(false),
     }
     match &*tree.defs()[4] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, _, _, _, _, _, Some(value)) => {
@@ -1847,7 +1847,7 @@ d: [Float; 2] = [1; 2] as [Float; 2];
         _ => assert!(false),
     }    
     match &*tree.defs()[5] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, _, _, _, _, _, Some(value)) => {
@@ -1918,7 +1918,7 @@ d: Int = [1.0, 1.0] match {
     }
     assert_eq!(6, tree.defs().len());
     match &*tree.defs()[2] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, _, _, _, _, _, Some(value)) => {
@@ -1930,7 +1930,7 @@ d: Int = [1.0, 1.0] match {
         _ => assert!(false),
     }
     match &*tree.defs()[3] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, _, _, _, _, _, Some(value)) => {
@@ -1942,7 +1942,7 @@ d: Int = [1.0, 1.0] match {
         _ => assert!(false),
     }
     match &*tree.defs()[4] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, _, _, _, _, _, Some(value)) => {
@@ -1954,7 +1954,7 @@ d: Int = [1.0, 1.0] match {
         _ => assert!(false),
     }
     match &*tree.defs()[5] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, _, _, _, _, _, Some(value)) => {
@@ -2013,7 +2013,7 @@ a: Int = [1, 2, 3] match {
     }
     assert_eq!(2, tree.defs().len());
     match &*tree.defs()[1] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, _, _, _, _, _, Some(value)) => {
@@ -2075,7 +2075,7 @@ a: Int = float4(1.5, 2.5, 3.5, 4.5) match {
     }
     assert_eq!(6, tree.defs().len());
     match &*tree.defs()[4] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, _, _, _, _, _, Some(value)) => {
@@ -2087,7 +2087,7 @@ a: Int = float4(1.5, 2.5, 3.5, 4.5) match {
         _ => assert!(false),
     }
     match &*tree.defs()[5] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, _, _, _, _, _, Some(value)) => {
@@ -2158,7 +2158,7 @@ c: Int = [1, 2] match {
     }
     assert_eq!(9, tree.defs().len());
     match &*tree.defs()[3] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, _, _, _, _, _, Some(value)) => {
@@ -2170,7 +2170,7 @@ c: Int = [1, 2] match {
         _ => assert!(false),
     }
     match &*tree.defs()[4] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, _, _, _, _, _, Some(value)) => {
@@ -2182,7 +2182,7 @@ c: Int = [1, 2] match {
         _ => assert!(false),
     }
     match &*tree.defs()[5] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, _, _, _, _, _, Some(value)) => {
@@ -2194,7 +2194,7 @@ c: Int = [1, 2] match {
         _ => assert!(false),
     }
     match &*tree.defs()[6] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, _, _, _, _, _, Some(value)) => {
@@ -2206,7 +2206,7 @@ c: Int = [1, 2] match {
         _ => assert!(false),
     }
     match &*tree.defs()[7] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, _, _, _, _, _, Some(value)) => {
@@ -2218,7 +2218,7 @@ c: Int = [1, 2] match {
         _ => assert!(false),
     }
     match &*tree.defs()[8] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, _, _, _, _, _, Some(value)) => {
@@ -2297,7 +2297,7 @@ a: (Int, Int, Int) =
     }
     assert_eq!(9, tree.defs().len());
     match &*tree.defs()[8] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, _, _, _, _, _, Some(value)) => {
@@ -2376,7 +2376,7 @@ a: (Int, Int, Int) =
     }
     assert_eq!(9, tree.defs().len());
     match &*tree.defs()[8] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, _, _, _, _, _, Some(value)) => {
@@ -2441,7 +2441,7 @@ b: Int = let (x, _) = (1, 2.5); in x;
     }
     assert_eq!(6, tree.defs().len());
     match &*tree.defs()[4] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, _, _, _, _, _, Some(value)) => {
@@ -2453,7 +2453,7 @@ b: Int = let (x, _) = (1, 2.5); in x;
         _ => assert!(false),
     }
     match &*tree.defs()[5] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, _, _, _, _, _, Some(value)) => {
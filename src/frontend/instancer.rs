@@ -88,11 +88,11 @@ impl Instancer
     {
         for def in tree.defs() {
             match &**def {
-                Def::Var(_, var, _) => {
+                Def::Var(_, _, var, _) => {
                     let var_r = var.borrow();
-                    self.check_insts_for_var(&*var_r, tree, errs)?;
+                    self.check_insts_for_var(&var_r, tree, errs)?;
                 },
-                Def::Trait(_, trait1, _) => {
+                Def::Trait(_, _, trait1, _) => {
                     let trait_r = trait1.borrow();
                     match &*trait_r {
                         Trait(_, trait_defs, _) => {
@@ -100,7 +100,7 @@ impl Instancer
                                 match &**trait_def {
                                     TraitDef(_, var, _) => {
                                         let var_r = var.borrow();
-                                        self.check_insts_for_var(&*var_r, tree, errs)?;
+                                        self.check_insts_for_var(&var_r, tree, errs)?;
                                     },
                                 }
                             }
@@ -116,7 +116,7 @@ impl Instancer
                                 match &**impl_def {
                                     ImplDef(_, impl_var, _) => {
                                         let impl_var_r = impl_var.borrow();
-                                        self.check_insts_for_impl_var(&*impl_var_r, tree, errs)?;
+                                        self.check_insts_for_impl_var(&impl_var_r, tree, errs)?;
                                     },
                                 }
                             }
@@ -146,7 +146,7 @@ impl Instancer
             Var::Builtin(_, _) => (),
             Var::Var(_, _, _, Some(expr), _, _, Some(local_types), _, _) => {
                 let mut var_env: Environment<()> = Environment::new();
-                self.check_insts_for_expr(&**expr, tree, &mut var_env, &**local_types, errs)?;
+                self.check_insts_for_expr(expr, tree, &mut var_env, local_types, errs)?;
             },
             Var::Var(_, _, _, None, _, _, _, _, _) => (),
             Var::Fun(fun, _, _) => {
@@ -161,7 +161,7 @@ impl Instancer
                                 },
                             }
                         }
-                        self.check_insts_for_expr(&**body, tree, &mut var_env, &**local_types, errs)?;
+                        self.check_insts_for_expr(body, tree, &mut var_env, local_types, errs)?;
                     },
                     Fun::Fun(_, _, _, _, None, _, _) => (),
                     Fun::Con(_) => return Err(FrontendErrors::new(vec![FrontendError::Internal(String::from("check_insts_for_var: variable is contructor"))])),
@@ -179,14 +179,14 @@ impl Instancer
             Expr::Literal(literal, _, _) => self.check_insts_for_literal(&**literal, tree, var_env, local_types, errs, Self::check_insts_for_expr)?,
             Expr::Lambda(args, _, body, _, _, _, _, _) => {
                 var_env.push_new_vars();
-                for arg in &*args {
+                for arg in args {
                     match arg {
                         LambdaArg(ident, _, _, _) => {
                             var_env.add_var(ident.clone(), ());
                         },
                     }
                 }
-                self.check_insts_for_expr(&**body, tree, var_env, local_types, errs)?;
+                self.check_insts_for_expr(body, tree, var_env, local_types, errs)?;
                 var_env.pop_vars();
             },
             Expr::Var(ident, Some(local_type), pos) => {
@@ -200,59 +200,59 @@ impl Instancer
             },
             Expr::PrintfApp(exprs, _, _) => {
                 for expr2 in exprs {
-                    self.check_insts_for_expr(&**expr2, tree, var_env, local_types, errs)?;
+                    self.check_insts_for_expr(expr2, tree, var_env, local_types, errs)?;
                 }
             },
             Expr::App(expr2, exprs, _, _) => {
-                self.check_insts_for_expr(&**expr2, tree, var_env, local_types, errs)?;
+                self.check_insts_for_expr(expr2, tree, var_env, local_types, errs)?;
                 for expr3 in exprs {
-                    self.check_insts_for_expr(&**expr3, tree, var_env, local_types, errs)?;
+                    self.check_insts_for_expr(expr3, tree, var_env, local_types, errs)?;
                 }
             },
-            Expr::GetField(expr2, _, _, _) => self.check_insts_for_expr(&**expr2, tree, var_env, local_types, errs)?,
-            Expr::Get2Field(expr2, _, _, _) => self.check_insts_for_expr(&**expr2, tree, var_env, local_types, errs)?,
+            Expr::GetField(expr2, _, _, _) => self.check_insts_for_expr(expr2, tree, var_env, local_types, errs)?,
+            Expr::Get2Field(expr2, _, _, _) => self.check_insts_for_expr(expr2, tree, var_env, local_types, errs)?,
             Expr::SetField(expr2, _, expr3, _, _) => {
-                self.check_insts_for_expr(&**expr2, tree, var_env, local_types, errs)?;
-                self.check_insts_for_expr(&**expr3, tree, var_env, local_types, errs)?;
+                self.check_insts_for_expr(expr2, tree, var_env, local_types, errs)?;
+                self.check_insts_for_expr(expr3, tree, var_env, local_types, errs)?;
             },
             Expr::UpdateField(expr2, _, expr3, _, _) => {
-                self.check_insts_for_expr(&**expr2, tree, var_env, local_types, errs)?;
-                self.check_insts_for_expr(&**expr3, tree, var_env, local_types, errs)?;
+                self.check_insts_for_expr(expr2, tree, var_env, local_types, errs)?;
+                self.check_insts_for_expr(expr3, tree, var_env, local_types, errs)?;
             },
             Expr::UpdateGet2Field(expr2, _, expr3, _, _) => {
-                self.check_insts_for_expr(&**expr2, tree, var_env, local_types, errs)?;
-                self.check_insts_for_expr(&**expr3, tree, var_env, local_types, errs)?;
+                self.check_insts_for_expr(expr2, tree, var_env, local_types, errs)?;
+                self.check_insts_for_expr(expr3, tree, var_env, local_types, errs)?;
             },
-            Expr::Uniq(expr2, _, _) => self.check_insts_for_expr(&**expr2, tree, var_env, local_types, errs)?,
-            Expr::Shared(expr2, _, _) => self.check_insts_for_expr(&**expr2, tree, var_env, local_types, errs)?,
-            Expr::Typed(expr2, _, _, _) => self.check_insts_for_expr(&**expr2, tree, var_env, local_types, errs)?,
-            Expr::As(expr2, _, _, _) => self.check_insts_for_expr(&**expr2, tree, var_env, local_types, errs)?,
+            Expr::Uniq(expr2, _, _) => self.check_insts_for_expr(expr2, tree, var_env, local_types, errs)?,
+            Expr::Shared(expr2, _, _) => self.check_insts_for_expr(expr2, tree, var_env, local_types, errs)?,
+            Expr::Typed(expr2, _, _, _) => self.check_insts_for_expr(expr2, tree, var_env, local_types, errs)?,
+            Expr::As(expr2, _, _, _) => self.check_insts_for_expr(expr2, tree, var_env, local_types, errs)?,
             Expr::If(expr2, expr3, expr4, _, _) => {
-                self.check_insts_for_expr(&**expr2, tree, var_env, local_types, errs)?;
-                self.check_insts_for_expr(&**expr3, tree, var_env, local_types, errs)?;
-                self.check_insts_for_expr(&**expr4, tree, var_env, local_types, errs)?;
+                self.check_insts_for_expr(expr2, tree, var_env, local_types, errs)?;
+                self.check_insts_for_expr(expr3, tree, var_env, local_types, errs)?;
+                self.check_insts_for_expr(expr4, tree, var_env, local_types, errs)?;
             },
             Expr::Let(binds, expr2, _, _) => {
                 var_env.push_new_vars();
                 for bind in binds {
                     match bind {
                         Bind(pattern, expr3) => {
-                            self.check_insts_for_expr(&**expr3, tree, var_env, local_types, errs)?;
-                            self.check_insts_for_pattern(&**pattern, tree, var_env, local_types, errs)?;
+                            self.check_insts_for_expr(expr3, tree, var_env, local_types, errs)?;
+                            self.check_insts_for_pattern(pattern, tree, var_env, local_types, errs)?;
                         },
                     }
                 }
-                self.check_insts_for_expr(&**expr2, tree, var_env, local_types, errs)?;
+                self.check_insts_for_expr(expr2, tree, var_env, local_types, errs)?;
                 var_env.pop_vars();
             },
             Expr::Match(expr2, cases, _, _) => {
-                self.check_insts_for_expr(&**expr2, tree, var_env, local_types, errs)?;
+                self.check_insts_for_expr(expr2, tree, var_env, local_types, errs)?;
                 for case in cases {
                     match case {
                         Case(pattern, expr3) => {
                             var_env.push_new_vars();
-                            self.check_insts_for_pattern(&**pattern, tree, var_env, local_types, errs)?;
-                            self.check_insts_for_expr(&**expr3, tree, var_env, local_types, errs)?;
+                            self.check_insts_for_pattern(pattern, tree, var_env, local_types, errs)?;
+                            self.check_insts_for_expr(expr3, tree, var_env, local_types, errs)?;
                             var_env.pop_vars();
                         },
                     }
@@ -272,7 +272,7 @@ impl Instancer
             Pattern::UnnamedFieldCon(ident, patterns, Some(con_local_type), _, pos) => {
                 check_inst_for_var_ident_and_local_type(ident, *con_local_type, pos.clone(), tree, local_types, errs)?;
                 for pattern2 in patterns {
-                    self.check_insts_for_pattern(&**pattern2, tree, var_env, local_types, errs)?;
+                    self.check_insts_for_pattern(pattern2, tree, var_env, local_types, errs)?;
                 }
             },
             Pattern::NamedFieldCon(ident, pattern_named_field_pairs, Some(con_local_type), _, pos) => {
@@ -284,12 +284,12 @@ impl Instancer
             },
             Pattern::At(_, ident, pattern2, _, _) => {
                 var_env.add_var(ident.clone(), ());
-                self.check_insts_for_pattern(&**pattern2, tree, var_env, local_types, errs)?;
+                self.check_insts_for_pattern(pattern2, tree, var_env, local_types, errs)?;
             },
             Pattern::Wildcard(_, _) => (),
             Pattern::Alt(patterns, _, _) => {
                 for pattern2 in patterns {
-                    self.check_insts_for_pattern(&**pattern2, tree, var_env, local_types, errs)?;
+                    self.check_insts_for_pattern(pattern2, tree, var_env, local_types, errs)?;
                 }
             },
             _ => return Err(FrontendErrors::new(vec![FrontendError::Internal(String::from("check_insts_for_pattern: no local type"))])),
@@ -323,7 +323,7 @@ impl Instancer
             ImplVar::Builtin(_) => (),
             ImplVar::Var(expr, _, Some(local_types), _, _) => {
                 let mut var_env: Environment<()> = Environment::new();
-                self.check_insts_for_expr(&**expr, tree, &mut var_env, &**local_types, errs)?;
+                self.check_insts_for_expr(expr, tree, &mut var_env, local_types, errs)?;
             },
             ImplVar::Fun(impl_fun, _) => {
                 match &**impl_fun {
@@ -337,7 +337,7 @@ impl Instancer
                                 },
                             }
                         }
-                        self.check_insts_for_expr(&**body, tree, &mut var_env, &**local_types, errs)?;
+                        self.check_insts_for_expr(body, tree, &mut var_env, local_types, errs)?;
                     },
                     _ => return Err(FrontendErrors::new(vec![FrontendError::Internal(String::from("check_insts_for_impl_var: no local types"))])),
                 }
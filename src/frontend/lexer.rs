@@ -54,11 +54,14 @@ pub enum Token
     For,
     If,
     Impl,
+    Import,
     In,
     Inline,
     Let,
     Match,
     Printf,
+    Pub,
+    Recursive,
     Shared,
     Then,
     Trait,
@@ -116,11 +119,14 @@ impl<'a> Lexer<'a>
         keywords.insert(String::from("for"), Token::For);
         keywords.insert(String::from("if"), Token::If);
         keywords.insert(String::from("impl"), Token::Impl);
+        keywords.insert(String::from("import"), Token::Import);
         keywords.insert(String::from("in"), Token::In);
         keywords.insert(String::from("inline"), Token::Inline);
         keywords.insert(String::from("let"), Token::Let);
         keywords.insert(String::from("match"), Token::Match);
         keywords.insert(String::from("printf"), Token::Printf);
+        keywords.insert(String::from("pub"), Token::Pub);
+        keywords.insert(String::from("recursive"), Token::Recursive);
         keywords.insert(String::from("shared"), Token::Shared);
         keywords.insert(String::from("then"), Token::Then);
         keywords.insert(String::from("trait"), Token::Trait);
@@ -180,9 +186,8 @@ impl<'a> Lexer<'a>
                 }
             }
             if !is_eof {
-                match String::from_utf8(char_buf.clone()) {
-                    Ok(s) => return Ok(Some(s.chars().next().unwrap())),
-                    Err(_) => (),
+                if let Ok(s) = String::from_utf8(char_buf.clone()) {
+                    return Ok(Some(s.chars().next().unwrap()));
                 }
             } else {
                 if i == 0 {
@@ -623,6 +628,50 @@ impl<'a> Lexer<'a>
         }
     }    
 
+    fn next_qualified_ident_token(&mut self, module_ident: &String) -> FrontendResult<Option<Token>>
+    {
+        // module_ident, "::", ( con_ident | var_ident )
+        match self.next_char()? {
+            (None, _) => return Ok(None),
+            (Some(':'), pos) => {
+                match self.next_char()? {
+                    (None, _) => {
+                        self.undo_char(':', pos);
+                        return Ok(None);
+                    },
+                    (Some(':'), _) => (),
+                    (Some(c2), pos2) => {
+                        self.undo_char(c2, pos2);
+                        self.undo_char(':', pos);
+                        return Ok(None);
+                    },
+                }
+            },
+            (Some(c), pos) => {
+                self.undo_char(c, pos);
+                return Ok(None);
+            },
+        }
+        match self.next_char()? {
+            (None, pos) => Err(FrontendError::Message(pos, String::from("unexpected end of file"))),
+            (Some(c), pos) if c.is_ascii_alphabetic() || c == '_' => {
+                let mut s = String::new();
+                s.push(c);
+                self.read_ident_chars(&mut s)?;
+                if self.keywords.contains_key(&s) {
+                    return Err(FrontendError::Message(pos, String::from("unexpected keyword")));
+                }
+                let ident = format!("{}::{}", module_ident, s);
+                if c.is_ascii_uppercase() {
+                    Ok(Some(Token::ConIdent(ident)))
+                } else {
+                    Ok(Some(Token::VarIdent(ident)))
+                }
+            },
+            (Some(_), pos) => Err(FrontendError::Message(pos, String::from("unexpected character"))),
+        }
+    }
+
     fn next_keyword_token_or_var_ident_token(&mut self) -> FrontendResult<Option<(Token, Pos)>>
     {
         match self.next_char()? {
@@ -633,7 +682,12 @@ impl<'a> Lexer<'a>
                 self.read_ident_chars(&mut s)?;
                 match self.keywords.get(&s) {
                     Some(token) => Ok(Some((token.clone(), pos))),
-                    None => Ok(Some((Token::VarIdent(s), pos))),
+                    None => {
+                        match self.next_qualified_ident_token(&s)? {
+                            Some(token) => Ok(Some((token, pos))),
+                            None => Ok(Some((Token::VarIdent(s), pos))),
+                        }
+                    },
                 }
             },
             (Some(c), pos) => {
@@ -1493,3 +1493,39 @@ fn test_lexer_next_token_complains_on_invalid_number_for_unsigned_long_integer_n
         _ => assert!(false),
     }
 }
+
+#[test]
+fn test_lexer_next_token_returns_qualified_identifiers()
+{
+    let s = "geom::normalize geom::Vec3 x: Int";
+    let mut cursor = Cursor::new(s.as_bytes());
+    let mut lexer = Lexer::new(String::from("test.vscfl"), &mut cursor);
+    match lexer.next_token() {
+        Ok((Token::VarIdent(ident), pos)) => {
+            assert_eq!(String::from("geom::normalize"), ident);
+            assert_eq!(1, pos.line);
+            assert_eq!(1, pos.column);
+        },
+        _ => assert!(false),
+    }
+    match lexer.next_token() {
+        Ok((Token::ConIdent(ident), pos)) => {
+            assert_eq!(String::from("geom::Vec3"), ident);
+            assert_eq!(1, pos.line);
+            assert_eq!(17, pos.column);
+        },
+        _ => assert!(false),
+    }
+    match lexer.next_token() {
+        Ok((Token::VarIdent(ident), _)) => assert_eq!(String::from("x"), ident),
+        _ => assert!(false),
+    }
+    match lexer.next_token() {
+        Ok((Token::Colon, _)) => assert!(true),
+        _ => assert!(false),
+    }
+    match lexer.next_token() {
+        Ok((Token::ConIdent(ident), _)) => assert_eq!(String::from("Int"), ident),
+        _ => assert!(false),
+    }
+}
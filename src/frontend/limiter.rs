@@ -91,11 +91,11 @@ impl Limiter
     {
         for def in tree.defs() {
             match &**def {
-                Def::Var(ident, var, pos) => {
+                Def::Var(_, ident, var, pos) => {
                     let var_r = var.borrow();
-                    self.check_limits_for_var(ident, &*var_r, pos, errs)?;
+                    self.check_limits_for_var(ident, &var_r, pos, errs)?;
                 },
-                Def::Trait(_, trait1, _) => {
+                Def::Trait(_, _, trait1, _) => {
                     let trait_r = trait1.borrow();
                     match &*trait_r {
                         Trait(_, trait_defs, _) => {
@@ -103,7 +103,7 @@ impl Limiter
                                 match &**trait_def {
                                     TraitDef(ident, var, pos) => {
                                         let var_r = var.borrow();
-                                        self.check_limits_for_var(ident, &*var_r, pos, errs)?;
+                                        self.check_limits_for_var(ident, &var_r, pos, errs)?;
                                     },
                                 }
                             }
@@ -119,7 +119,7 @@ impl Limiter
                                 match &**impl_def {
                                     ImplDef(_, impl_var, _) => {
                                         let impl_var_r = impl_var.borrow();
-                                        self.check_limits_for_impl_var(&*impl_var_r, errs)?;
+                                        self.check_limits_for_impl_var(&impl_var_r, errs)?;
                                     },
                                 }
                             }
@@ -149,20 +149,20 @@ impl Limiter
             Var::Builtin(_, _) => (),
             Var::Var(var_modifier, _, _, Some(expr), trait_name, _, _, Some(typ), _) => {
                 check_global_var_modifier(*var_modifier, ident, pos.clone(), errs);
-                check_var_type(ident, trait_name, &**typ, pos.clone(), errs);
-                self.check_limits_for_expr(&**expr, true, errs)?;
+                check_var_type(ident, trait_name, typ, pos.clone(), errs);
+                self.check_limits_for_expr(expr, true, errs)?;
             }
             Var::Var(var_modifier, _, _, None, trait_name, _, _, Some(typ), _) => {
                 check_global_var_modifier(*var_modifier, ident, pos.clone(), errs);
-                check_var_type(ident, trait_name, &**typ, pos.clone(), errs);
+                check_var_type(ident, trait_name, typ, pos.clone(), errs);
             },
             Var::Fun(fun, trait_name, Some(typ)) => {
                 match &**fun {
                     Fun::Fun(fun_modifier, _, _, _, Some(body), _, _) => {
-                        check_fun_modifier(*fun_modifier, ident, trait_name, &**typ, pos.clone(), errs);
-                        self.check_limits_for_expr(&**body, false, errs)?;
+                        check_fun_modifier(*fun_modifier, ident, trait_name, typ, pos.clone(), errs);
+                        self.check_limits_for_expr(body, false, errs)?;
                     },
-                    Fun::Fun(fun_modifier, _, _, _, None, _, _) => check_fun_modifier(*fun_modifier, ident, trait_name, &**typ, pos.clone(), errs),
+                    Fun::Fun(fun_modifier, _, _, _, None, _, _) => check_fun_modifier(*fun_modifier, ident, trait_name, typ, pos.clone(), errs),
                     Fun::Con(_) => return Err(FrontendErrors::new(vec![FrontendError::Internal(String::from("check_limits_for_var: variable is contructor"))])),
                 }
             },
@@ -175,7 +175,7 @@ impl Limiter
     {
         match expr {
             Expr::Literal(literal, _, _) => self.check_limits_for_literal(&**literal, is_in_var, errs, Self::check_limits_for_expr)?,
-            Expr::Lambda(_, _, body, _, _, _, _, _) => self.check_limits_for_expr(&**body, false, errs)?,
+            Expr::Lambda(_, _, body, _, _, _, _, _) => self.check_limits_for_expr(body, false, errs)?,
             Expr::Var(_, _, _) => (),
             Expr::NamedFieldConApp(_, expr_named_field_pairs, _, _, _) => self.check_limits_for_named_field_pairs(expr_named_field_pairs.as_slice(), is_in_var, errs, Self::check_limits_for_expr)?,
             Expr::PrintfApp(exprs, _, pos) => {
@@ -189,56 +189,56 @@ impl Limiter
                     None => return Err(FrontendErrors::new(vec![FrontendError::Internal(String::from("check_limits_for_expr: no frist expression"))]))
                 }
                 for expr2 in exprs {
-                    self.check_limits_for_expr(&**expr2, is_in_var, errs)?;
+                    self.check_limits_for_expr(expr2, is_in_var, errs)?;
                 }
             },
             Expr::App(expr2, exprs, _, _) => {
-                self.check_limits_for_expr(&**expr2, is_in_var, errs)?;
+                self.check_limits_for_expr(expr2, is_in_var, errs)?;
                 for expr3 in exprs {
-                    self.check_limits_for_expr(&**expr3, is_in_var, errs)?;
+                    self.check_limits_for_expr(expr3, is_in_var, errs)?;
                 }
             },
-            Expr::GetField(expr2, _, _, _) => self.check_limits_for_expr(&**expr2, is_in_var, errs)?,
-            Expr::Get2Field(expr2, _, _, _) => self.check_limits_for_expr(&**expr2, is_in_var, errs)?,
+            Expr::GetField(expr2, _, _, _) => self.check_limits_for_expr(expr2, is_in_var, errs)?,
+            Expr::Get2Field(expr2, _, _, _) => self.check_limits_for_expr(expr2, is_in_var, errs)?,
             Expr::SetField(expr2, _, expr3, _, _) => {
-                self.check_limits_for_expr(&**expr2, is_in_var, errs)?;
-                self.check_limits_for_expr(&**expr3, is_in_var, errs)?;
+                self.check_limits_for_expr(expr2, is_in_var, errs)?;
+                self.check_limits_for_expr(expr3, is_in_var, errs)?;
             },
             Expr::UpdateField(expr2, _, expr3, _, _) => {
-                self.check_limits_for_expr(&**expr2, is_in_var, errs)?;
-                self.check_limits_for_expr(&**expr3, is_in_var, errs)?;
+                self.check_limits_for_expr(expr2, is_in_var, errs)?;
+                self.check_limits_for_expr(expr3, is_in_var, errs)?;
             },
             Expr::UpdateGet2Field(expr2, _, expr3, _, _) => {
-                self.check_limits_for_expr(&**expr2, is_in_var, errs)?;
-                self.check_limits_for_expr(&**expr3, is_in_var, errs)?;
+                self.check_limits_for_expr(expr2, is_in_var, errs)?;
+                self.check_limits_for_expr(expr3, is_in_var, errs)?;
             },
-            Expr::Uniq(expr2, _, _) => self.check_limits_for_expr(&**expr2, is_in_var, errs)?,
-            Expr::Shared(expr2, _, _) => self.check_limits_for_expr(&**expr2, is_in_var, errs)?,
-            Expr::Typed(expr2, _, _, _) => self.check_limits_for_expr(&**expr2, is_in_var, errs)?,
-            Expr::As(expr2, _, _, _) => self.check_limits_for_expr(&**expr2, is_in_var, errs)?,
+            Expr::Uniq(expr2, _, _) => self.check_limits_for_expr(expr2, is_in_var, errs)?,
+            Expr::Shared(expr2, _, _) => self.check_limits_for_expr(expr2, is_in_var, errs)?,
+            Expr::Typed(expr2, _, _, _) => self.check_limits_for_expr(expr2, is_in_var, errs)?,
+            Expr::As(expr2, _, _, _) => self.check_limits_for_expr(expr2, is_in_var, errs)?,
             Expr::If(expr2, expr3, expr4, _, _) => {
-                self.check_limits_for_expr(&**expr2, is_in_var, errs)?;
-                self.check_limits_for_expr(&**expr3, is_in_var, errs)?;
-                self.check_limits_for_expr(&**expr4, is_in_var, errs)?;
+                self.check_limits_for_expr(expr2, is_in_var, errs)?;
+                self.check_limits_for_expr(expr3, is_in_var, errs)?;
+                self.check_limits_for_expr(expr4, is_in_var, errs)?;
             },
             Expr::Let(binds, expr2, _, _) => {
                 for bind in binds {
                     match bind {
                         Bind(pattern, expr3) => {
-                            self.check_limits_for_expr(&**expr3, is_in_var, errs)?;
-                            self.check_limits_for_pattern(&**pattern, is_in_var, errs)?;
+                            self.check_limits_for_expr(expr3, is_in_var, errs)?;
+                            self.check_limits_for_pattern(pattern, is_in_var, errs)?;
                         },
                     }
                 }
-                self.check_limits_for_expr(&**expr2, is_in_var, errs)?;
+                self.check_limits_for_expr(expr2, is_in_var, errs)?;
             },
             Expr::Match(expr2, cases, _, _) => {
-                self.check_limits_for_expr(&**expr2, is_in_var, errs)?;
+                self.check_limits_for_expr(expr2, is_in_var, errs)?;
                 for case in cases {
                     match case {
                         Case(pattern, expr3) => {
-                            self.check_limits_for_pattern(&**pattern, is_in_var, errs)?;
-                            self.check_limits_for_expr(&**expr3, is_in_var, errs)?;
+                            self.check_limits_for_pattern(pattern, is_in_var, errs)?;
+                            self.check_limits_for_expr(expr3, is_in_var, errs)?;
                         },
                     }
                 }
@@ -255,14 +255,14 @@ impl Limiter
             Pattern::Const(_, _, _) => (),
             Pattern::UnnamedFieldCon(_, patterns, _, _, _) => {
                 for pattern2 in patterns {
-                    self.check_limits_for_pattern(&**pattern2, is_in_var, errs)?;
+                    self.check_limits_for_pattern(pattern2, is_in_var, errs)?;
                 }
             },
             Pattern::NamedFieldCon(_, pattern_named_field_pairs, _, _, _) => self.check_limits_for_named_field_pairs(pattern_named_field_pairs.as_slice(), is_in_var, errs, Self::check_limits_for_pattern)?,
             Pattern::Var(var_modifier, ident, _, pos) => check_local_var_modifier(*var_modifier, ident, pos.clone(), is_in_var, errs),
             Pattern::At(var_modifier, ident, pattern2, _, pos) => {
                 check_local_var_modifier(*var_modifier, ident, pos.clone(), is_in_var, errs);
-                self.check_limits_for_pattern(&**pattern2, is_in_var, errs)?;
+                self.check_limits_for_pattern(pattern2, is_in_var, errs)?;
             },
             Pattern::Wildcard(_, _) => (),
             Pattern::Alt(_, _, _) => (),
@@ -294,10 +294,10 @@ impl Limiter
     {
         match impl_var {
             ImplVar::Builtin(_) => (),
-            ImplVar::Var(expr, _, _, _, _) => self.check_limits_for_expr(&**expr, true, errs)?,
+            ImplVar::Var(expr, _, _, _, _) => self.check_limits_for_expr(expr, true, errs)?,
             ImplVar::Fun(impl_fun, _) => {
                 match &**impl_fun {
-                    ImplFun(_, body, _, _) => self.check_limits_for_expr(&**body, false, errs)?,
+                    ImplFun(_, body, _, _) => self.check_limits_for_expr(body, false, errs)?,
                 }
             },
         }
@@ -40,28 +40,22 @@ fn add_error_for_trait(ident: &str, pos: Pos, errs: &mut Vec<FrontendError>)
 
 fn check_type_param_ident(ident: &String, pos: Pos, type_param_env: &Environment<()>, are_errs: bool, errs: &mut Vec<FrontendError>)
 {
-    if type_param_env.var(ident).is_none() {
-        if are_errs {
-            errs.push(FrontendError::Message(pos, format!("undefined type parameter {}", ident)));
-        }
+    if type_param_env.var(ident).is_none() && are_errs {
+        errs.push(FrontendError::Message(pos, format!("undefined type parameter {}", ident)));
     }
 }
 
 fn check_type_var_ident(ident: &String, pos: Pos, tree: &Tree, are_errs: bool, errs: &mut Vec<FrontendError>)
 {
-    if !tree.type_vars.contains_key(ident) {
-        if are_errs {
-            errs.push(FrontendError::Message(pos, format!("undefined type variable {}", ident)));
-        }
+    if !tree.type_vars.contains_key(ident) && are_errs {
+        errs.push(FrontendError::Message(pos, format!("undefined type variable {}", ident)));
     }
 }
 
 fn check_var_ident(ident: &String, pos: Pos, tree: &Tree, var_env: &Environment<()>, errs: &mut Vec<FrontendError>)
 {
-    if var_env.var(ident).is_none() {
-        if !tree.vars.contains_key(ident) {
-            errs.push(FrontendError::Message(pos, format!("undefined variable {}", ident)));
-        }
+    if var_env.var(ident).is_none() && !tree.vars.contains_key(ident) {
+        errs.push(FrontendError::Message(pos, format!("undefined variable {}", ident)));
     }
 }
 
@@ -92,11 +86,11 @@ fn check_con_ident(ident: &String, pos: Pos, tree: &Tree, are_named_fields: bool
                             if are_named_fields {
                                 let con_r = con.borrow();
                                 match &*con_r {
-                                    Con::UnnamedField(_, _, _, _) => {
+                                    Con::UnnamedField(_, _, _, _, _) => {
                                         errs.push(FrontendError::Message(pos, format!("constructor {} hasn't named fields", ident)));
                                         None
                                     },
-                                    Con::NamedField(_, _, _, _, _) => Some(con.clone()),
+                                    Con::NamedField(_, _, _, _, _, _) => Some(con.clone()),
                                 }
                             } else {
                                 Some(con.clone())
@@ -123,13 +117,10 @@ fn check_con_ident(ident: &String, pos: Pos, tree: &Tree, are_named_fields: bool
 
 fn check_trait_name(trait_name: &TraitName, pos: Pos, tree: &Tree, errs: &mut Vec<FrontendError>)
 {
-    match trait_name {
-        TraitName::Name(ident) => {
-            if !tree.traits.contains_key(ident) {
-                errs.push(FrontendError::Message(pos, format!("undefined trait {}", ident)));
-            }
-        },
-        _ => (),
+    if let TraitName::Name(ident) = trait_name {
+        if !tree.traits.contains_key(ident) {
+            errs.push(FrontendError::Message(pos, format!("undefined trait {}", ident)));
+        }
     }
 }
 
@@ -245,73 +236,70 @@ impl Namer
     {
         for def in &tree.defs {
             match &**def {
-                Def::Type(ident, type_var, pos) => {
+                Def::Type(_, ident, type_var, pos) => {
                     match tree.type_vars.get(ident) {
                         Some(defined_type_var) => {
                             let defined_type_var_r = defined_type_var.borrow();
-                            add_error_for_type_var(ident.as_str(), pos.clone(), &*defined_type_var_r, errs);
+                            add_error_for_type_var(ident.as_str(), pos.clone(), &defined_type_var_r, errs);
                         },
                         None => {
                             tree.type_vars.insert(ident.clone(), type_var.clone());
                             let type_var_r = type_var.borrow();
-                            match &*type_var_r {
-                                TypeVar::Data(_, cons, _) => {
-                                    for con in cons {
-                                        let mut con_r = con.borrow_mut();
-                                        let (con_ident, con_pos) = match &*con_r {
-                                            Con::UnnamedField(tmp_ident, _, _, tmp_pos) => (tmp_ident, tmp_pos),
-                                            Con::NamedField(tmp_ident, _, _, _, tmp_pos) => (tmp_ident, tmp_pos),
-                                        };
-                                        match tree.vars.get(con_ident) {
-                                            Some(defined_var) => {
-                                                let defined_var_r = defined_var.borrow();
-                                                add_error_for_var(con_ident.as_str(), con_pos.clone(), &*defined_var_r, errs);
-                                            },
-                                            None => {
-                                                tree.vars.insert(con_ident.clone(), Rc::new(RefCell::new(Var::Fun(Box::new(Fun::Con(con.clone())), None, None))));
-                                            },
-                                        }
-                                        match &mut *con_r {
-                                            Con::UnnamedField(_, _, _, _) => (),
-                                            Con::NamedField(_, type_expr_named_field_pairs, _, named_fields, _) => {
-                                                let mut new_named_fields = NamedFields::new();
-                                                let mut field_idents: BTreeSet<String> = BTreeSet::new();
-                                                let mut field_idx = 0usize;
-                                                for type_expr_named_field_pair in type_expr_named_field_pairs {
-                                                    match type_expr_named_field_pair {
-                                                        NamedFieldPair(field_ident, _, field_pos) => {
-                                                            if !field_idents.contains(field_ident) {
-                                                                new_named_fields.add_field_index(field_ident.clone(), field_idx);
-                                                                field_idents.insert(field_ident.clone());
-                                                                field_idx += 1;
-                                                            } else {
-                                                                errs.push(FrontendError::Message(field_pos.clone(), format!("already defined field {}", field_ident)));
-                                                            }
-                                                        },
-                                                    }
-                                                }
-                                                *named_fields = Some(Box::new(new_named_fields));
-                                            },
-                                        };
+                            if let TypeVar::Data(_, cons, _) = &*type_var_r {
+                                for con in cons {
+                                    let mut con_r = con.borrow_mut();
+                                    let (con_ident, con_pos) = match &*con_r {
+                                        Con::UnnamedField(_, tmp_ident, _, _, tmp_pos) => (tmp_ident, tmp_pos),
+                                        Con::NamedField(_, tmp_ident, _, _, _, tmp_pos) => (tmp_ident, tmp_pos),
+                                    };
+                                    match tree.vars.get(con_ident) {
+                                        Some(defined_var) => {
+                                            let defined_var_r = defined_var.borrow();
+                                            add_error_for_var(con_ident.as_str(), con_pos.clone(), &defined_var_r, errs);
+                                        },
+                                        None => {
+                                            tree.vars.insert(con_ident.clone(), Rc::new(RefCell::new(Var::Fun(Box::new(Fun::Con(con.clone())), None, None))));
+                                        },
                                     }
-                                },
-                                _ => (),
+                                    match &mut *con_r {
+                                        Con::UnnamedField(_, _, _, _, _) => (),
+                                        Con::NamedField(_, _, type_expr_named_field_pairs, _, named_fields, _) => {
+                                            let mut new_named_fields = NamedFields::new();
+                                            let mut field_idents: BTreeSet<String> = BTreeSet::new();
+                                            let mut field_idx = 0usize;
+                                            for type_expr_named_field_pair in type_expr_named_field_pairs {
+                                                match type_expr_named_field_pair {
+                                                    NamedFieldPair(field_ident, _, field_pos) => {
+                                                        if !field_idents.contains(field_ident) {
+                                                            new_named_fields.add_field_index(field_ident.clone(), field_idx);
+                                                            field_idents.insert(field_ident.clone());
+                                                            field_idx += 1;
+                                                        } else {
+                                                            errs.push(FrontendError::Message(field_pos.clone(), format!("already defined field {}", field_ident)));
+                                                        }
+                                                    },
+                                                }
+                                            }
+                                            *named_fields = Some(Box::new(new_named_fields));
+                                        },
+                                    };
+                                }
                             }
                         },
                     }
                 },
-                Def::Var(ident, var, pos) => {
+                Def::Var(_, ident, var, pos) => {
                     match tree.vars.get(ident) {
                         Some(defined_var) => {
                             let defined_var_r = defined_var.borrow();
-                            add_error_for_var(ident.as_str(), pos.clone(), &*defined_var_r, errs);
+                            add_error_for_var(ident.as_str(), pos.clone(), &defined_var_r, errs);
                         },
                         None => {
                             tree.vars.insert(ident.clone(), var.clone());
                         },
                     }
                 },
-                Def::Trait(ident, trait1, pos) => {
+                Def::Trait(_, ident, trait1, pos) => {
                     match tree.traits.get(ident) {
                         Some(_) => add_error_for_trait(ident.as_str(), pos.clone(), errs),
                         None => {
@@ -326,7 +314,7 @@ impl Namer
                                                 match tree.vars.get(var_ident) {
                                                     Some(defined_var) => {
                                                         let defined_var_r = defined_var.borrow();
-                                                        add_error_for_var(var_ident.as_str(), var_pos.clone(), &*defined_var_r, errs);
+                                                        add_error_for_var(var_ident.as_str(), var_pos.clone(), &defined_var_r, errs);
                                                     },
                                                     None => {
                                                         tree.vars.insert(var_ident.clone(), var.clone());
@@ -351,105 +339,99 @@ impl Namer
     fn add_impls_for_impl_defs(&self, tree: &Tree, errs: &mut Vec<FrontendError>) -> FrontendResultWithErrors<()>
     {
         for def in &tree.defs {
-            match &**def {
-                Def::Impl(impl1, pos) => {
-                    let mut impl_r = impl1.borrow_mut();
-                    let (trait_ident, type_name) = match &*impl_r {
-                        Impl::Builtin(tmp_trait_ident, tmp_type_name, _) => (tmp_trait_ident, tmp_type_name),
-                        Impl::Impl(tmp_trait_ident, tmp_type_name, _, _) => (tmp_trait_ident, tmp_type_name),
-                    };
-                    match tree.traits.get(trait_ident) {
-                        Some(trait1) => {
-                            let mut trait_r = trait1.borrow_mut();
-                            match &mut *trait_r {
-                                Trait(_, _, Some(trait_vars)) => {
-                                    if check_type_name(type_name, pos.clone(), tree, errs) {
-                                        match trait_vars.impl1(type_name) {
-                                            Some(_) => errs.push(FrontendError::Message(pos.clone(), format!("already defined implementation {} for type {}", trait_ident, type_name))),
-                                            None => {
-                                                trait_vars.add_impl(type_name.clone(), impl1.clone());
-                                            },
-                                        }
-                                    }
-                                    match &mut *impl_r {
-                                        Impl::Builtin(_, _, impl_vars) => {
-                                            let mut new_impl_vars = ImplVars::new();
-                                            for trait_var_ident in trait_vars.vars().keys() {
-                                                new_impl_vars.add_var(trait_var_ident.clone(), Rc::new(RefCell::new(ImplVar::Builtin(None))));
-                                            }
-                                            *impl_vars = Some(Box::new(new_impl_vars));
+            if let Def::Impl(impl1, pos) = &**def {
+                let mut impl_r = impl1.borrow_mut();
+                let (trait_ident, type_name) = match &*impl_r {
+                    Impl::Builtin(tmp_trait_ident, tmp_type_name, _) => (tmp_trait_ident, tmp_type_name),
+                    Impl::Impl(tmp_trait_ident, tmp_type_name, _, _) => (tmp_trait_ident, tmp_type_name),
+                };
+                match tree.traits.get(trait_ident) {
+                    Some(trait1) => {
+                        let mut trait_r = trait1.borrow_mut();
+                        match &mut *trait_r {
+                            Trait(_, _, Some(trait_vars)) => {
+                                if check_type_name(type_name, pos.clone(), tree, errs) {
+                                    match trait_vars.impl1(type_name) {
+                                        Some(_) => errs.push(FrontendError::Message(pos.clone(), format!("already defined implementation {} for type {}", trait_ident, type_name))),
+                                        None => {
+                                            trait_vars.add_impl(type_name.clone(), impl1.clone());
                                         },
-                                        Impl::Impl(trait_ident, _, impl_defs, impl_vars) => {
-                                            let mut new_impl_vars = ImplVars::new();
-                                            for impl_def in impl_defs {
-                                                match &**impl_def {
-                                                    ImplDef(impl_var_ident, impl_var, impl_var_pos) => {
-                                                        match trait_vars.var(impl_var_ident) {
-                                                            Some(trait_var) => {
-                                                                let trait_var_r = trait_var.borrow();
-                                                                let impl_var_r = impl_var.borrow();
-                                                                let mut is_impl_var = false;
-                                                                match (&*trait_var_r, &*impl_var_r) {
-                                                                    (Var::Builtin(_, _), _) => is_impl_var = true,
-                                                                    (_, ImplVar::Builtin(_)) => is_impl_var = true,
-                                                                    (Var::Var(_, _, _, _, _, _, _, _, _), ImplVar::Var(_, _, _, _, _)) => is_impl_var = true,
-                                                                    (Var::Fun(fun, _, _), ImplVar::Fun(impl_fun, _)) => {
-                                                                        match (&**fun, &**impl_fun) {
-                                                                            (Fun::Fun(_, args, _, _, _, _, _), ImplFun(impl_args, _, _, _)) => {
-                                                                                if impl_args.len() < args.len() {
-                                                                                    errs.push(FrontendError::Message(impl_var_pos.clone(), String::from("too few arguments")));
-                                                                                } else if impl_args.len() > args.len() {
-                                                                                    errs.push(FrontendError::Message(impl_var_pos.clone(), String::from("too many arguments")));
-                                                                                }
-                                                                            },
-                                                                            _ => return Err(FrontendErrors::new(vec![FrontendError::Internal(String::from("add_impls_for_impl_defs: function is constuctor"))])),
-                                                                        }
-                                                                        is_impl_var = true
-                                                                    },
-                                                                    (Var::Var(_, _, _, _, _, _, _, _, _), ImplVar::Fun(_, _)) => errs.push(FrontendError::Message(impl_var_pos.clone(), format!("function {} must be variable in implementation {}", impl_var_ident, trait_ident))),
-                                                                    (Var::Fun(_, _, _), ImplVar::Var(_, _, _, _, _)) =>  errs.push(FrontendError::Message(impl_var_pos.clone(), format!("variable {} must be function in implementation {}", impl_var_ident, trait_ident))),
-                                                                }
-                                                                if is_impl_var {
-                                                                    new_impl_vars.add_var(impl_var_ident.clone(), impl_var.clone());
-                                                                }
-                                                            },
-                                                            None => errs.push(FrontendError::Message(impl_var_pos.clone(), format!("undefined variable {} in trait {}", impl_var_ident, trait_ident))),
-                                                        }
-                                                    },
-                                                }
+                                    }
+                                }
+                                match &mut *impl_r {
+                                    Impl::Builtin(_, _, impl_vars) => {
+                                        let mut new_impl_vars = ImplVars::new();
+                                        for trait_var_ident in trait_vars.vars().keys() {
+                                            new_impl_vars.add_var(trait_var_ident.clone(), Rc::new(RefCell::new(ImplVar::Builtin(None))));
+                                        }
+                                        *impl_vars = Some(Box::new(new_impl_vars));
+                                    },
+                                    Impl::Impl(trait_ident, _, impl_defs, impl_vars) => {
+                                        let mut new_impl_vars = ImplVars::new();
+                                        for impl_def in impl_defs {
+                                            match &**impl_def {
+                                                ImplDef(impl_var_ident, impl_var, impl_var_pos) => {
+                                                    match trait_vars.var(impl_var_ident) {
+                                                        Some(trait_var) => {
+                                                            let trait_var_r = trait_var.borrow();
+                                                            let impl_var_r = impl_var.borrow();
+                                                            let mut is_impl_var = false;
+                                                            match (&*trait_var_r, &*impl_var_r) {
+                                                                (Var::Builtin(_, _), _) => is_impl_var = true,
+                                                                (_, ImplVar::Builtin(_)) => is_impl_var = true,
+                                                                (Var::Var(_, _, _, _, _, _, _, _, _), ImplVar::Var(_, _, _, _, _)) => is_impl_var = true,
+                                                                (Var::Fun(fun, _, _), ImplVar::Fun(impl_fun, _)) => {
+                                                                    match (&**fun, &**impl_fun) {
+                                                                        (Fun::Fun(_, args, _, _, _, _, _), ImplFun(impl_args, _, _, _)) => {
+                                                                            if impl_args.len() < args.len() {
+                                                                                errs.push(FrontendError::Message(impl_var_pos.clone(), String::from("too few arguments")));
+                                                                            } else if impl_args.len() > args.len() {
+                                                                                errs.push(FrontendError::Message(impl_var_pos.clone(), String::from("too many arguments")));
+                                                                            }
+                                                                        },
+                                                                        _ => return Err(FrontendErrors::new(vec![FrontendError::Internal(String::from("add_impls_for_impl_defs: function is constuctor"))])),
+                                                                    }
+                                                                    is_impl_var = true
+                                                                },
+                                                                (Var::Var(_, _, _, _, _, _, _, _, _), ImplVar::Fun(_, _)) => errs.push(FrontendError::Message(impl_var_pos.clone(), format!("function {} must be variable in implementation {}", impl_var_ident, trait_ident))),
+                                                                (Var::Fun(_, _, _), ImplVar::Var(_, _, _, _, _)) =>  errs.push(FrontendError::Message(impl_var_pos.clone(), format!("variable {} must be function in implementation {}", impl_var_ident, trait_ident))),
+                                                            }
+                                                            if is_impl_var {
+                                                                new_impl_vars.add_var(impl_var_ident.clone(), impl_var.clone());
+                                                            }
+                                                        },
+                                                        None => errs.push(FrontendError::Message(impl_var_pos.clone(), format!("undefined variable {} in trait {}", impl_var_ident, trait_ident))),
+                                                    }
+                                                },
                                             }
-                                            for (trait_var_ident, trait_var) in trait_vars.vars() {
-                                                let trait_var_r = trait_var.borrow();
-                                                match &*trait_var_r {
-                                                    Var::Var(_, _, _, None, _, _, _, _, _) => {
+                                        }
+                                        for (trait_var_ident, trait_var) in trait_vars.vars() {
+                                            let trait_var_r = trait_var.borrow();
+                                            match &*trait_var_r {
+                                                Var::Var(_, _, _, None, _, _, _, _, _) => {
+                                                    if new_impl_vars.var(trait_var_ident).is_none() {
+                                                        errs.push(FrontendError::Message(pos.clone(), format!("undefined required variable {} in implementation {}", trait_var_ident, trait_ident)));
+                                                    }
+                                                },
+                                                Var::Fun(fun, _, _) => {
+                                                    if let Fun::Fun(_, _, _, _, None, _, _) = &**fun {
                                                         if new_impl_vars.var(trait_var_ident).is_none() {
-                                                            errs.push(FrontendError::Message(pos.clone(), format!("undefined required variable {} in implementation {}", trait_var_ident, trait_ident)));
-                                                        }
-                                                    },
-                                                    Var::Fun(fun, _, _) => {
-                                                        match &**fun {
-                                                            Fun::Fun(_, _, _, _, None, _, _) => {
-                                                                if new_impl_vars.var(trait_var_ident).is_none() {
-                                                                    errs.push(FrontendError::Message(pos.clone(), format!("undefined required function {} in implementation {}", trait_var_ident, trait_ident)));
-                                                                }
-                                                            },
-                                                            _ => (),
+                                                            errs.push(FrontendError::Message(pos.clone(), format!("undefined required function {} in implementation {}", trait_var_ident, trait_ident)));
                                                         }
                                                     }
-                                                    _ => (),
                                                 }
+                                                _ => (),
                                             }
-                                            *impl_vars = Some(Box::new(new_impl_vars));
-                                        },
-                                    }
-                                },
-                                _ => return Err(FrontendErrors::new(vec![FrontendError::Internal(String::from("add_impls_for_impl_defs: no trait variables"))])),
-                            }
-                        },
-                        None => errs.push(FrontendError::Message(pos.clone(), format!("undefined trait {}", trait_ident))),
-                    }
-                },
-                _ => (),
+                                        }
+                                        *impl_vars = Some(Box::new(new_impl_vars));
+                                    },
+                                }
+                            },
+                            _ => return Err(FrontendErrors::new(vec![FrontendError::Internal(String::from("add_impls_for_impl_defs: no trait variables"))])),
+                        }
+                    },
+                    None => errs.push(FrontendError::Message(pos.clone(), format!("undefined trait {}", trait_ident))),
+                }
             }
         }
         Ok(())
@@ -459,15 +441,15 @@ impl Namer
     {
         for def in &tree.defs {
             match &**def {
-                Def::Type(_, type_var, _) => {
+                Def::Type(_, _, type_var, _) => {
                     let type_var_r = type_var.borrow();
-                    self.check_idents_for_type_var(&*type_var_r, tree, errs)?;
+                    self.check_idents_for_type_var(&type_var_r, tree, errs)?;
                 },
-                Def::Var(_, var, _) => {
+                Def::Var(_, _, var, _) => {
                     let var_r = var.borrow();
-                    self.check_idents_for_var(&*var_r, tree, errs)?;
+                    self.check_idents_for_var(&var_r, tree, errs)?;
                 },
-                Def::Trait(_, trait1, _) => {
+                Def::Trait(_, _, trait1, _) => {
                     let trait_r = trait1.borrow();
                     match &*trait_r {
                         Trait(type_args, trait_defs, _) => {
@@ -478,7 +460,7 @@ impl Namer
                                 match &**trait_def {
                                     TraitDef(_, var, _) => {
                                         let var_r = var.borrow();
-                                        self.check_idents_for_var(&*var_r, tree, errs)?;
+                                        self.check_idents_for_var(&var_r, tree, errs)?;
                                     },
                                 }
                             }
@@ -494,7 +476,7 @@ impl Namer
                                 match &**impl_def {
                                     ImplDef(impl_var_ident, impl_var, _) => {
                                         let impl_var_r = impl_var.borrow();
-                                        self.check_idents_for_impl_var(&*impl_var_r, impl_var_ident, trait_ident, tree, errs)?;
+                                        self.check_idents_for_impl_var(&impl_var_r, impl_var_ident, trait_ident, tree, errs)?;
                                     },
                                 }
                             }
@@ -517,15 +499,15 @@ impl Namer
                 for con in cons {
                     let con_r = con.borrow();
                     match &*con_r {
-                        Con::UnnamedField(_, field_type_exprs, _, _) => {
+                        Con::UnnamedField(_, _, field_type_exprs, _, _) => {
                             for field_type_expr in field_type_exprs {
-                                self.check_idents_for_type_expr(&**field_type_expr, tree, &mut type_param_env, false, true, errs)?;
+                                self.check_idents_for_type_expr(field_type_expr, tree, &mut type_param_env, false, true, errs)?;
                             }
                         },
-                        Con::NamedField(_, type_expr_named_field_pairs, _, _, _) => {
+                        Con::NamedField(_, _, type_expr_named_field_pairs, _, _, _) => {
                             for type_expr_named_field_pair in type_expr_named_field_pairs {
                                 match type_expr_named_field_pair {
-                                    NamedFieldPair(_, field_type_expr, _) => self.check_idents_for_type_expr(&**field_type_expr, tree, &mut type_param_env, false, true, errs)?,
+                                    NamedFieldPair(_, field_type_expr, _) => self.check_idents_for_type_expr(field_type_expr, tree, &mut type_param_env, false, true, errs)?,
                                 }
                             }
                         }
@@ -536,7 +518,7 @@ impl Namer
                 let mut type_param_env: Environment<()> = Environment::new();
                 type_param_env.push_new_vars();
                 self.check_idents_for_type_args2(type_args.as_slice(), &mut type_param_env, errs)?;
-                self.check_idents_for_type_expr(&**type_expr, tree, &mut type_param_env, false, true, errs)?;
+                self.check_idents_for_type_expr(type_expr, tree, &mut type_param_env, false, true, errs)?;
             },
         }
         Ok(())
@@ -565,7 +547,7 @@ impl Namer
     {
         let con_r = con.borrow();
         match &*con_r {
-            Con::NamedField(_, _, _, Some(named_fields), _) => {
+            Con::NamedField(_, _, _, _, Some(named_fields), _) => {
                 let mut field_idents: BTreeSet<String> = BTreeSet::new();
                 let mut count = 0usize;
                 for named_field_pair in named_field_pairs {
@@ -605,16 +587,16 @@ impl Namer
         match type_expr {
             TypeExpr::Tuple(field_type_exprs, _) => {
                 for field_type_expr in field_type_exprs {
-                    self.check_idents_for_type_expr(&**field_type_expr, tree, type_param_env, can_add_type_params, are_errs, errs)?;
+                    self.check_idents_for_type_expr(field_type_expr, tree, type_param_env, can_add_type_params, are_errs, errs)?;
                 }
             },
             TypeExpr::Fun(arg_type_exprs, ret_type_expr, _) => {
                 for arg_type_expr in arg_type_exprs {
-                    self.check_idents_for_type_expr(&**arg_type_expr, tree, type_param_env, can_add_type_params, are_errs, errs)?;
+                    self.check_idents_for_type_expr(arg_type_expr, tree, type_param_env, can_add_type_params, are_errs, errs)?;
                 }
-                self.check_idents_for_type_expr(&**ret_type_expr, tree, type_param_env, can_add_type_params, are_errs, errs)?;
+                self.check_idents_for_type_expr(ret_type_expr, tree, type_param_env, can_add_type_params, are_errs, errs)?;
             },
-            TypeExpr::Array(elem_type_expr, _, _) => self.check_idents_for_type_expr(&**elem_type_expr, tree, type_param_env, can_add_type_params, are_errs, errs)?,
+            TypeExpr::Array(elem_type_expr, _, _) => self.check_idents_for_type_expr(elem_type_expr, tree, type_param_env, can_add_type_params, are_errs, errs)?,
             TypeExpr::Param(ident, pos) => {
                 if can_add_type_params {
                     type_param_env.add_var(ident.clone(), ());
@@ -626,10 +608,10 @@ impl Namer
             TypeExpr::App(ident, type_exprs, pos) => {
                 check_type_var_ident(ident, pos.clone(), tree, are_errs, errs);
                 for type_expr2 in type_exprs {
-                    self.check_idents_for_type_expr(&**type_expr2, tree, type_param_env, can_add_type_params, are_errs, errs)?;
+                    self.check_idents_for_type_expr(type_expr2, tree, type_param_env, can_add_type_params, are_errs, errs)?;
                 }
             },
-            TypeExpr::Uniq(type_expr2, _) => self.check_idents_for_type_expr(&**type_expr2, tree, type_param_env, can_add_type_params, are_errs, errs)?,
+            TypeExpr::Uniq(type_expr2, _) => self.check_idents_for_type_expr(type_expr2, tree, type_param_env, can_add_type_params, are_errs, errs)?,
         }
         Ok(())
     }
@@ -642,11 +624,10 @@ impl Namer
                 let mut var_env: Environment<()> = Environment::new();
                 let mut type_param_env: Environment<()> = Environment::new();
                 type_param_env.push_new_vars();
-                self.check_idents_for_type_expr(&**type_expr, tree, &mut type_param_env, true, true, errs)?;
+                self.check_idents_for_type_expr(type_expr, tree, &mut type_param_env, true, true, errs)?;
                 self.check_idents_for_where_tuples(where_tuples.as_slice(), tree, &mut type_param_env, errs)?;
-                match expr {
-                    Some(expr) => self.check_idents_for_expr(&**expr, tree, &mut var_env, &mut type_param_env, errs)?,
-                    None => (),
+                if let Some(expr) = expr {
+                    self.check_idents_for_expr(expr, tree, &mut var_env, &mut type_param_env, errs)?;
                 }
             },
             Var::Fun(fun, _, _) => {
@@ -657,11 +638,10 @@ impl Namer
                         var_env.push_new_vars();
                         type_param_env.push_new_vars();
                         self.check_idents_for_args(args.as_slice(), tree, &mut var_env, &mut type_param_env, true, errs)?;
-                        self.check_idents_for_type_expr(&**ret_type_expr, tree, &mut type_param_env, true, true, errs)?;
+                        self.check_idents_for_type_expr(ret_type_expr, tree, &mut type_param_env, true, true, errs)?;
                         self.check_idents_for_where_tuples(where_tuples.as_slice(), tree, &mut type_param_env, errs)?;
-                        match body {
-                            Some(body) => self.check_idents_for_expr(&**body, tree, &mut var_env, &mut type_param_env, errs)?,
-                            None => (),
+                        if let Some(body) = body {
+                            self.check_idents_for_expr(body, tree, &mut var_env, &mut type_param_env, errs)?;
                         }
                     },
                     Fun::Con(_) => return Err(FrontendErrors::new(vec![FrontendError::Internal(String::from("check_idents_for_var: variable is contructor"))])),
@@ -685,7 +665,7 @@ impl Namer
                             errs.push(FrontendError::Message(pos.clone(), format!("already defined argument {}", ident)));
                         }
                     }
-                    self.check_idents_for_type_expr(&**type_expr, tree, type_param_env, true, are_errs, errs)?;
+                    self.check_idents_for_type_expr(type_expr, tree, type_param_env, true, are_errs, errs)?;
                 },
             }
         }
@@ -705,10 +685,10 @@ impl Namer
                         errs.push(FrontendError::Message(pos.clone(), format!("already defined traits for type parameter {}", ident)));
                     }
                     for trait_name in trait_names {
-                        check_trait_name(trait_name, pos.clone(), &tree, errs);
+                        check_trait_name(trait_name, pos.clone(), tree, errs);
                     }
                     for type_expr in type_exprs {
-                        self.check_idents_for_type_expr(&**type_expr, tree, type_param_env, true, true, errs)?;
+                        self.check_idents_for_type_expr(type_expr, tree, type_param_env, true, true, errs)?;
                     }
                 }
                 WhereTuple::Eq(type_params) => {
@@ -730,83 +710,81 @@ impl Namer
             Expr::Lambda(args, ret_type_expr, body, _, _, _, _, _) => {
                 var_env.push_new_vars();
                 self.check_idents_for_lambda_args(args.as_slice(), tree, var_env, type_param_env, errs)?;
-                match ret_type_expr {
-                    Some(ret_type_expr) => self.check_idents_for_type_expr(&**ret_type_expr, tree, type_param_env, false, true, errs)?,
-                    None => (),
+                if let Some(ret_type_expr) = ret_type_expr {
+                    self.check_idents_for_type_expr(ret_type_expr, tree, type_param_env, false, true, errs)?;
                 }
-                self.check_idents_for_expr(&**body, tree, var_env, type_param_env, errs)?;
+                self.check_idents_for_expr(body, tree, var_env, type_param_env, errs)?;
                 var_env.pop_vars();
             },
             Expr::Var(ident, _, pos) => check_var_ident(ident, pos.clone(), tree, var_env, errs),
             Expr::NamedFieldConApp(ident, expr_named_field_pairs, _, _, pos) => {
-                match check_con_ident(ident, pos.clone(), tree, true, errs) {
-                    Some(con) => self.check_idents_for_named_field_pairs(expr_named_field_pairs.as_slice(), pos.clone(), con, tree, var_env, type_param_env, errs, Self::check_idents_for_expr)?,
-                    None => (),
+                if let Some(con) = check_con_ident(ident, pos.clone(), tree, true, errs) {
+                    self.check_idents_for_named_field_pairs(expr_named_field_pairs.as_slice(), pos.clone(), con, tree, var_env, type_param_env, errs, Self::check_idents_for_expr)?;
                 }
             },
             Expr::PrintfApp(exprs, _, _) => {
                 for expr2 in exprs {
-                    self.check_idents_for_expr(&**expr2, tree, var_env, type_param_env, errs)?;
+                    self.check_idents_for_expr(expr2, tree, var_env, type_param_env, errs)?;
                 }
             },
             Expr::App(expr2, exprs, _, _) => {
-                self.check_idents_for_expr(&**expr2, tree, var_env, type_param_env, errs)?;
+                self.check_idents_for_expr(expr2, tree, var_env, type_param_env, errs)?;
                 for expr3 in exprs {
-                    self.check_idents_for_expr(&**expr3, tree, var_env, type_param_env, errs)?;
+                    self.check_idents_for_expr(expr3, tree, var_env, type_param_env, errs)?;
                 }
             },
-            Expr::GetField(expr2, _, _, _) => self.check_idents_for_expr(&**expr2, tree, var_env, type_param_env, errs)?,
-            Expr::Get2Field(expr2, _, _, _) => self.check_idents_for_expr(&**expr2, tree, var_env, type_param_env, errs)?,
+            Expr::GetField(expr2, _, _, _) => self.check_idents_for_expr(expr2, tree, var_env, type_param_env, errs)?,
+            Expr::Get2Field(expr2, _, _, _) => self.check_idents_for_expr(expr2, tree, var_env, type_param_env, errs)?,
             Expr::SetField(expr2, _, expr3, _, _) => {
-                self.check_idents_for_expr(&**expr2, tree, var_env, type_param_env, errs)?;
-                self.check_idents_for_expr(&**expr3, tree, var_env, type_param_env, errs)?;
+                self.check_idents_for_expr(expr2, tree, var_env, type_param_env, errs)?;
+                self.check_idents_for_expr(expr3, tree, var_env, type_param_env, errs)?;
             },
             Expr::UpdateField(expr2, _, expr3, _, _) => {
-                self.check_idents_for_expr(&**expr2, tree, var_env, type_param_env, errs)?;
-                self.check_idents_for_expr(&**expr3, tree, var_env, type_param_env, errs)?;
+                self.check_idents_for_expr(expr2, tree, var_env, type_param_env, errs)?;
+                self.check_idents_for_expr(expr3, tree, var_env, type_param_env, errs)?;
             },
             Expr::UpdateGet2Field(expr2, _, expr3, _, _) => {
-                self.check_idents_for_expr(&**expr2, tree, var_env, type_param_env, errs)?;
-                self.check_idents_for_expr(&**expr3, tree, var_env, type_param_env, errs)?;
+                self.check_idents_for_expr(expr2, tree, var_env, type_param_env, errs)?;
+                self.check_idents_for_expr(expr3, tree, var_env, type_param_env, errs)?;
             },
-            Expr::Uniq(expr2, _, _) => self.check_idents_for_expr(&**expr2, tree, var_env, type_param_env, errs)?,
-            Expr::Shared(expr2, _, _) => self.check_idents_for_expr(&**expr2, tree, var_env, type_param_env, errs)?,
+            Expr::Uniq(expr2, _, _) => self.check_idents_for_expr(expr2, tree, var_env, type_param_env, errs)?,
+            Expr::Shared(expr2, _, _) => self.check_idents_for_expr(expr2, tree, var_env, type_param_env, errs)?,
             Expr::Typed(expr2, type_expr, _, _) => {
-                self.check_idents_for_expr(&**expr2, tree, var_env, type_param_env, errs)?;
-                self.check_idents_for_type_expr(&**type_expr, tree, type_param_env, false, true, errs)?;
+                self.check_idents_for_expr(expr2, tree, var_env, type_param_env, errs)?;
+                self.check_idents_for_type_expr(type_expr, tree, type_param_env, false, true, errs)?;
             },
             Expr::As(expr2, type_expr, _, _) => {
-                self.check_idents_for_expr(&**expr2, tree, var_env, type_param_env, errs)?;
-                self.check_idents_for_type_expr(&**type_expr, tree, type_param_env, false, true, errs)?;
+                self.check_idents_for_expr(expr2, tree, var_env, type_param_env, errs)?;
+                self.check_idents_for_type_expr(type_expr, tree, type_param_env, false, true, errs)?;
             },
             Expr::If(expr2, expr3, expr4, _, _) => {
-                self.check_idents_for_expr(&**expr2, tree, var_env, type_param_env, errs)?;
-                self.check_idents_for_expr(&**expr3, tree, var_env, type_param_env, errs)?;
-                self.check_idents_for_expr(&**expr4, tree, var_env, type_param_env, errs)?;
+                self.check_idents_for_expr(expr2, tree, var_env, type_param_env, errs)?;
+                self.check_idents_for_expr(expr3, tree, var_env, type_param_env, errs)?;
+                self.check_idents_for_expr(expr4, tree, var_env, type_param_env, errs)?;
             },
             Expr::Let(binds, expr2, _, _) => {
                 var_env.push_new_vars();
                 for bind in binds {
                     match bind {
                         Bind(pattern, expr3) => {
-                            self.check_idents_for_expr(&**expr3, tree, var_env, type_param_env, errs)?;
+                            self.check_idents_for_expr(expr3, tree, var_env, type_param_env, errs)?;
                             let mut var_idents: BTreeSet<String> = BTreeSet::new();
-                            self.check_idents_for_pattern(&**pattern, tree, var_env, type_param_env, &mut var_idents, false, false, errs)?;
+                            self.check_idents_for_pattern(pattern, tree, var_env, type_param_env, &mut var_idents, false, false, errs)?;
                         },
                     }
                 }
-                self.check_idents_for_expr(&**expr2, tree, var_env, type_param_env, errs)?;
+                self.check_idents_for_expr(expr2, tree, var_env, type_param_env, errs)?;
                 var_env.pop_vars();
             },
             Expr::Match(expr2, cases, _, _) => {
-                self.check_idents_for_expr(&**expr2, tree, var_env, type_param_env, errs)?;
+                self.check_idents_for_expr(expr2, tree, var_env, type_param_env, errs)?;
                 for case in cases {
                     match case {
                         Case(pattern, expr3) => {
                             var_env.push_new_vars();
                             let mut var_idents: BTreeSet<String> = BTreeSet::new();
-                            self.check_idents_for_pattern(&**pattern, tree, var_env, type_param_env, &mut var_idents, false, false, errs)?;
-                            self.check_idents_for_expr(&**expr3, tree, var_env, type_param_env, errs)?;
+                            self.check_idents_for_pattern(pattern, tree, var_env, type_param_env, &mut var_idents, false, false, errs)?;
+                            self.check_idents_for_expr(expr3, tree, var_env, type_param_env, errs)?;
                             var_env.pop_vars();
                         },
                     }
@@ -828,48 +806,42 @@ impl Namer
                 self.check_idents_for_literal(&**literal, tree, var_env, type_param_env, errs, |namer, pattern, tree, var_env, type_param_env, errs| {
                     namer.check_idents_for_pattern(pattern, tree, var_env, type_param_env, var_idents, true, is_in_alt_pattern, errs)
                 })?;
-                self.check_idents_for_type_expr(&**type_expr, tree, type_param_env, false, true, errs)?;
+                self.check_idents_for_type_expr(type_expr, tree, type_param_env, false, true, errs)?;
             },
             Pattern::Const(ident, _, pos) => check_const_ident(ident, pos.clone(), tree, errs),
             Pattern::UnnamedFieldCon(ident, patterns, _, _, pos) => {
-                match check_con_ident(ident, pos.clone(), tree, false, errs) {
-                    Some(con) => {
-                        let con_r = con.borrow();
-                        let field_count = match &*con_r {
-                            Con::UnnamedField(_, field_type_exprs, _, _) => field_type_exprs.len(),
-                            Con::NamedField(_, type_expr_named_field_pairs, _, _, _) => type_expr_named_field_pairs.len(),
-                        };
-                        if patterns.len() < field_count {
-                            errs.push(FrontendError::Message(pos.clone(), String::from("too few fields")));
-                        } else if patterns.len() > field_count {
-                            errs.push(FrontendError::Message(pos.clone(), String::from("too many fields")));
-                        }
-                    },
-                    None => (),
+                if let Some(con) = check_con_ident(ident, pos.clone(), tree, false, errs) {
+                    let con_r = con.borrow();
+                    let field_count = match &*con_r {
+                        Con::UnnamedField(_, _, field_type_exprs, _, _) => field_type_exprs.len(),
+                        Con::NamedField(_, _, type_expr_named_field_pairs, _, _, _) => type_expr_named_field_pairs.len(),
+                    };
+                    if patterns.len() < field_count {
+                        errs.push(FrontendError::Message(pos.clone(), String::from("too few fields")));
+                    } else if patterns.len() > field_count {
+                        errs.push(FrontendError::Message(pos.clone(), String::from("too many fields")));
+                    }
                 }
                 for pattern2 in patterns {
-                    self.check_idents_for_pattern(&**pattern2, tree, var_env, type_param_env, var_idents, is_in_as_pattern, is_in_alt_pattern, errs)?;
+                    self.check_idents_for_pattern(pattern2, tree, var_env, type_param_env, var_idents, is_in_as_pattern, is_in_alt_pattern, errs)?;
                 }
             },
             Pattern::NamedFieldCon(ident, pattern_named_field_pairs, _, _, pos) => {
-                match check_con_ident(ident, pos.clone(), tree, true, errs) {
-                    Some(con) => {
-                        self.check_idents_for_named_field_pairs(pattern_named_field_pairs.as_slice(), pos.clone(), con, tree, var_env, type_param_env, errs, |namer, pattern, tree, var_env, type_param_env, errs| {
-                                namer.check_idents_for_pattern(pattern, tree, var_env, type_param_env, var_idents, is_in_as_pattern, is_in_alt_pattern, errs)
-                        })?;
-                    },
-                    None => (),
+                if let Some(con) = check_con_ident(ident, pos.clone(), tree, true, errs) {
+                    self.check_idents_for_named_field_pairs(pattern_named_field_pairs.as_slice(), pos.clone(), con, tree, var_env, type_param_env, errs, |namer, pattern, tree, var_env, type_param_env, errs| {
+                            namer.check_idents_for_pattern(pattern, tree, var_env, type_param_env, var_idents, is_in_as_pattern, is_in_alt_pattern, errs)
+                    })?;
                 }
             }
             Pattern::Var(_, ident, _, pos) => add_var_ident(ident, pos, var_env, var_idents, is_in_as_pattern, is_in_alt_pattern, errs),
             Pattern::At(_, ident, pattern2, _, pos) => {
                 add_var_ident(ident, pos, var_env, var_idents, is_in_as_pattern, is_in_alt_pattern, errs);
-                self.check_idents_for_pattern(&**pattern2, tree, var_env, type_param_env, var_idents, is_in_as_pattern, is_in_alt_pattern, errs)?;
+                self.check_idents_for_pattern(pattern2, tree, var_env, type_param_env, var_idents, is_in_as_pattern, is_in_alt_pattern, errs)?;
             },
             Pattern::Wildcard(_, _) => (),
             Pattern::Alt(patterns, _, _) => {
                 for pattern2 in patterns {
-                    self.check_idents_for_pattern(&**pattern2, tree, var_env, type_param_env, var_idents, is_in_as_pattern, true, errs)?;
+                    self.check_idents_for_pattern(pattern2, tree, var_env, type_param_env, var_idents, is_in_as_pattern, true, errs)?;
                 }
             },
         }
@@ -908,9 +880,8 @@ impl Namer
                     } else {
                         errs.push(FrontendError::Message(pos.clone(), format!("already defined argument {}", ident)));
                     }
-                    match type_expr {
-                        Some(type_expr) => self.check_idents_for_type_expr(&**type_expr, tree, type_param_env, false, true, errs)?,
-                        None => (),
+                    if let Some(type_expr) = type_expr {
+                        self.check_idents_for_type_expr(type_expr, tree, type_param_env, false, true, errs)?;
                     }
                 },
             }
@@ -926,29 +897,23 @@ impl Namer
                 let mut var_env: Environment<()> = Environment::new();
                 let mut type_param_env: Environment<()> = Environment::new();
                 type_param_env.push_new_vars();
-                match tree.traits.get(trait_ident) {
-                    Some(trait1) => {
-                        let trait_r = trait1.borrow();
-                        match &*trait_r {
-                            Trait(_, _, Some(trait_vars)) => {
-                                match trait_vars.var(impl_var_ident) {
-                                    Some(var) => {
-                                        let var_r = var.borrow();
-                                        match &*var_r {
-                                            Var::Builtin(_, _) => (),
-                                            Var::Var(_, type_expr, _, _, _, _, _, _, _) => self.check_idents_for_type_expr(&**type_expr, tree, &mut type_param_env, true, false, errs)?,
-                                            _ => (),
-                                        }
-                                    },
-                                    None => (),
+                if let Some(trait1) = tree.traits.get(trait_ident) {
+                    let trait_r = trait1.borrow();
+                    match &*trait_r {
+                        Trait(_, _, Some(trait_vars)) => {
+                            if let Some(var) = trait_vars.var(impl_var_ident) {
+                                let var_r = var.borrow();
+                                match &*var_r {
+                                    Var::Builtin(_, _) => (),
+                                    Var::Var(_, type_expr, _, _, _, _, _, _, _) => self.check_idents_for_type_expr(type_expr, tree, &mut type_param_env, true, false, errs)?,
+                                    _ => (),
                                 }
-                            },
-                            _ => return Err(FrontendErrors::new(vec![FrontendError::Internal(String::from("check_idents_for_impl_var: no trait variables"))])),
-                        }
-                    },
-                    _ => (),
+                            }
+                        },
+                        _ => return Err(FrontendErrors::new(vec![FrontendError::Internal(String::from("check_idents_for_impl_var: no trait variables"))])),
+                    }
                 }
-                self.check_idents_for_expr(&**expr, tree, &mut var_env, &mut type_param_env, errs)?;
+                self.check_idents_for_expr(expr, tree, &mut var_env, &mut type_param_env, errs)?;
             },
             ImplVar::Fun(fun, _) => {
                 match &**fun {
@@ -957,40 +922,34 @@ impl Namer
                         let mut type_param_env: Environment<()> = Environment::new();
                         var_env.push_new_vars();
                         type_param_env.push_new_vars();
-                        match tree.traits.get(trait_ident) {
-                            Some(trait1) => {
-                                let trait_r = trait1.borrow();
-                                match &*trait_r {
-                                    Trait(_, _, Some(trait_vars)) => {
-                                        match trait_vars.var(impl_var_ident) {
-                                            Some(var) => {
-                                                let var_r = var.borrow();
-                                                match &*var_r {
-                                                    Var::Builtin(_, _) => (),
-                                                    Var::Fun(fun, _, _) => {
-                                                        match &**fun {
-                                                            Fun::Fun(_, args, ret_type_expr, _, _, _, _) => {
-                                                                let mut tmp_var_env: Environment<()> = Environment::new();
-                                                                tmp_var_env.push_new_vars();
-                                                                self.check_idents_for_args(args.as_slice(), tree, &mut tmp_var_env, &mut type_param_env, false, errs)?;
-                                                                self.check_idents_for_type_expr(&**ret_type_expr, tree, &mut type_param_env, true, false, errs)?;
-                                                            },
-                                                            Fun::Con(_) => return Err(FrontendErrors::new(vec![FrontendError::Internal(String::from("check_idents_for_impl_var: variable is constructor"))])),
-                                                        }
+                        if let Some(trait1) = tree.traits.get(trait_ident) {
+                            let trait_r = trait1.borrow();
+                            match &*trait_r {
+                                Trait(_, _, Some(trait_vars)) => {
+                                    if let Some(var) = trait_vars.var(impl_var_ident) {
+                                        let var_r = var.borrow();
+                                        match &*var_r {
+                                            Var::Builtin(_, _) => (),
+                                            Var::Fun(fun, _, _) => {
+                                                match &**fun {
+                                                    Fun::Fun(_, args, ret_type_expr, _, _, _, _) => {
+                                                        let mut tmp_var_env: Environment<()> = Environment::new();
+                                                        tmp_var_env.push_new_vars();
+                                                        self.check_idents_for_args(args.as_slice(), tree, &mut tmp_var_env, &mut type_param_env, false, errs)?;
+                                                        self.check_idents_for_type_expr(ret_type_expr, tree, &mut type_param_env, true, false, errs)?;
                                                     },
-                                                    _ => (),
+                                                    Fun::Con(_) => return Err(FrontendErrors::new(vec![FrontendError::Internal(String::from("check_idents_for_impl_var: variable is constructor"))])),
                                                 }
                                             },
-                                            None => (),
+                                            _ => (),
                                         }
-                                    },
-                                    _ => return Err(FrontendErrors::new(vec![FrontendError::Internal(String::from("check_idents_for_impl_var: no trait variables"))])),
-                                }
-                            },
-                            _ => (),
+                                    }
+                                },
+                                _ => return Err(FrontendErrors::new(vec![FrontendError::Internal(String::from("check_idents_for_impl_var: no trait variables"))])),
+                            }
                         }
                         self.check_idents_for_impl_args(impl_args.as_slice(), &mut var_env, errs)?;
-                        self.check_idents_for_expr(&**body, tree, &mut var_env, &mut type_param_env, errs)?;
+                        self.check_idents_for_expr(body, tree, &mut var_env, &mut type_param_env, errs)?;
                     },
                 }
             },
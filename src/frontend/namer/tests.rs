@@ -36,7 +36,7 @@ x: Int = 1;
     assert_eq!(1, tree.vars().len());
     assert_eq!(true, tree.traits().is_empty());
     match &*tree.defs()[0] {
-        Def::Type(_, type_var, _) => {
+        Def::Type(_, _, type_var, _) => {
             match tree.type_var(&String::from("Int")) {
                 Some(type_var2) => assert!(Rc::ptr_eq(type_var, type_var2)),
                 None => assert!(false),
@@ -45,7 +45,7 @@ x: Int = 1;
         _ => assert!(false),
     }
     match &*tree.defs()[1] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             match tree.var(&String::from("x")) {
                 Some(var2) => assert!(Rc::ptr_eq(var, var2)),
                 None => assert!(false),
@@ -82,7 +82,7 @@ type U = T<Float>;
     assert_eq!(2, tree.vars().len());
     assert_eq!(true, tree.traits().is_empty());
     match &*tree.defs()[0] {
-        Def::Type(_, type_var, _) => {
+        Def::Type(_, _, type_var, _) => {
             match tree.type_var(&String::from("Int")) {
                 Some(type_var2) => assert!(Rc::ptr_eq(type_var, type_var2)),
                 None => assert!(false),
@@ -91,7 +91,7 @@ type U = T<Float>;
         _ => assert!(false),
     }
     match &*tree.defs()[1] {
-        Def::Type(_, type_var, _) => {
+        Def::Type(_, _, type_var, _) => {
             match tree.type_var(&String::from("Float")) {
                 Some(type_var2) => assert!(Rc::ptr_eq(type_var, type_var2)),
                 None => assert!(false),
@@ -100,7 +100,7 @@ type U = T<Float>;
         _ => assert!(false),
     }
     match &*tree.defs()[2] {
-        Def::Type(_, type_var, _) => {
+        Def::Type(_, _, type_var, _) => {
             match tree.type_var(&String::from("T")) {
                 Some(type_var2) => assert!(Rc::ptr_eq(type_var, type_var2)),
                 None => assert!(false),
@@ -146,7 +146,7 @@ type U = T<Float>;
         _ => assert!(false),
     }
     match &*tree.defs()[3] {
-        Def::Type(_, type_var, _) => {
+        Def::Type(_, _, type_var, _) => {
             match tree.type_var(&String::from("U")) {
                 Some(type_var2) => assert!(Rc::ptr_eq(type_var, type_var2)),
                 None => assert!(false),
@@ -184,7 +184,7 @@ f(x: Float, y: Float) -> Float = x + y;
     assert_eq!(3, tree.vars().len());
     assert_eq!(true, tree.traits().is_empty());
     match &*tree.defs()[0] {
-        Def::Type(_, type_var, _) => {
+        Def::Type(_, _, type_var, _) => {
             match tree.type_var(&String::from("Int")) {
                 Some(type_var2) => assert!(Rc::ptr_eq(type_var, type_var2)),
                 None => assert!(false),
@@ -193,7 +193,7 @@ f(x: Float, y: Float) -> Float = x + y;
         _ => assert!(false),
     }
     match &*tree.defs()[1] {
-        Def::Type(_, type_var, _) => {
+        Def::Type(_, _, type_var, _) => {
             match tree.type_var(&String::from("Float")) {
                 Some(type_var2) => assert!(Rc::ptr_eq(type_var, type_var2)),
                 None => assert!(false),
@@ -202,7 +202,7 @@ f(x: Float, y: Float) -> Float = x + y;
         _ => assert!(false),
     }
     match &*tree.defs()[2] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             match tree.var(&String::from("op_add")) {
                 Some(var2) => assert!(Rc::ptr_eq(var, var2)),
                 None => assert!(false),
@@ -211,7 +211,7 @@ f(x: Float, y: Float) -> Float = x + y;
         _ => assert!(false),
     }
     match &*tree.defs()[3] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             match tree.var(&String::from("x")) {
                 Some(var2) => assert!(Rc::ptr_eq(var, var2)),
                 None => assert!(false),
@@ -220,7 +220,7 @@ f(x: Float, y: Float) -> Float = x + y;
         _ => assert!(false),
     }
     match &*tree.defs()[4] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             match tree.var(&String::from("f")) {
                 Some(var2) => assert!(Rc::ptr_eq(var, var2)),
                 None => assert!(false),
@@ -266,7 +266,7 @@ trait U
     assert_eq!(4, tree.vars().len());
     assert_eq!(2, tree.traits().len());
     match &*tree.defs()[0] {
-        Def::Type(_, type_var, _) => {
+        Def::Type(_, _, type_var, _) => {
             match tree.type_var(&String::from("Int")) {
                 Some(type_var2) => assert!(Rc::ptr_eq(type_var, type_var2)),
                 None => assert!(false),
@@ -275,7 +275,7 @@ trait U
         _ => assert!(false),
     }
     match &*tree.defs()[1] {
-        Def::Trait(_, trait1, _) => {
+        Def::Trait(_, _, trait1, _) => {
             match tree.trait1(&String::from("T")) {
                 Some(trait2) => assert!(Rc::ptr_eq(trait1, trait2)),
                 None => assert!(false),
@@ -328,7 +328,7 @@ trait U
         _ => assert!(false),
     }
     match &*tree.defs()[2] {
-        Def::Trait(_, trait1, _) => {
+        Def::Trait(_, _, trait1, _) => {
             match tree.trait1(&String::from("U")) {
                 Some(trait2) => assert!(Rc::ptr_eq(trait1, trait2)),
                 None => assert!(false),
@@ -407,7 +407,7 @@ impl T for U
     assert_eq!(4, tree.vars().len());
     assert_eq!(1, tree.traits().len());
     match &*tree.defs()[0] {
-        Def::Type(_, type_var, _) => {
+        Def::Type(_, _, type_var, _) => {
             match tree.type_var(&String::from("Int")) {
                 Some(type_var2) => assert!(Rc::ptr_eq(type_var, type_var2)),
                 None => assert!(false),
@@ -416,7 +416,7 @@ impl T for U
         _ => assert!(false),
     }
     match &*tree.defs()[1] {
-        Def::Type(_, type_var, _) => {
+        Def::Type(_, _, type_var, _) => {
             match tree.type_var(&String::from("Float")) {
                 Some(type_var2) => assert!(Rc::ptr_eq(type_var, type_var2)),
                 None => assert!(false),
@@ -425,7 +425,7 @@ impl T for U
         _ => assert!(false),
     }
     match &*tree.defs()[2] {
-        Def::Type(_, type_var, _) => {
+        Def::Type(_, _, type_var, _) => {
             match tree.type_var(&String::from("U")) {
                 Some(type_var2) => assert!(Rc::ptr_eq(type_var, type_var2)),
                 None => assert!(false),
@@ -456,7 +456,7 @@ impl T for U
         _ => assert!(false),
     }
     match &*tree.defs()[3] {
-        Def::Trait(_, trait1, _) => {
+        Def::Trait(_, _, trait1, _) => {
             match tree.trait1(&String::from("T")) {
                 Some(trait2) => assert!(Rc::ptr_eq(trait1, trait2)),
                 None => assert!(false),
@@ -742,7 +742,7 @@ data T = C { x: Int, y: Float, z: Int, };
     assert_eq!(1, tree.vars().len());
     assert_eq!(true, tree.traits().is_empty());
     match &*tree.defs()[0] {
-        Def::Type(_, type_var, _) => {
+        Def::Type(_, _, type_var, _) => {
             match tree.type_var(&String::from("Int")) {
                 Some(type_var2) => assert!(Rc::ptr_eq(type_var, type_var2)),
                 None => assert!(false),
@@ -751,7 +751,7 @@ data T = C { x: Int, y: Float, z: Int, };
         _ => assert!(false),
     }
     match &*tree.defs()[1] {
-        Def::Type(_, type_var, _) => {
+        Def::Type(_, _, type_var, _) => {
             match tree.type_var(&String::from("Float")) {
                 Some(type_var2) => assert!(Rc::ptr_eq(type_var, type_var2)),
                 None => assert!(false),
@@ -760,7 +760,7 @@ data T = C { x: Int, y: Float, z: Int, };
         _ => assert!(false),
     }
     match &*tree.defs()[2] {
-        Def::Type(_, type_var, _) => {
+        Def::Type(_, _, type_var, _) => {
             match tree.type_var(&String::from("T")) {
                 Some(type_var2) => assert!(Rc::ptr_eq(type_var, type_var2)),
                 None => assert!(false),
@@ -786,7 +786,7 @@ data T = C { x: Int, y: Float, z: Int, };
                     }
                     let con_r = cons[0].borrow();
                     match &*con_r {
-                        Con::NamedField(_, _, _, Some(named_fields), _) => {
+                        Con::NamedField(_, _, _, _, Some(named_fields), _) => {
                             assert_eq!(3, named_fields.field_indices().len());
                             match named_fields.field_index(&String::from("x")) {
                                 Some(field_idx) => assert_eq!(0, field_idx),
@@ -58,13 +58,11 @@ enum LiteralEither<T>
 
 fn check_modifiers_for_builtin_var(modifiers: &Modifiers, trait_ident: Option<String>) -> FrontendResult<Rc<RefCell<Var>>>
 {
-    match &modifiers.var_modifier_pair {
-        Some((_, tmp_pos)) => return Err(FrontendError::Message(tmp_pos.clone(), String::from("built-in variable mustn't have variable modifier"))),
-        None => (),
+    if let Some((_, tmp_pos)) = &modifiers.var_modifier_pair {
+        return Err(FrontendError::Message(tmp_pos.clone(), String::from("built-in variable mustn't have variable modifier")));
     }
-    match &modifiers.fun_modifier_pair {
-        Some((_, tmp_pos)) => return Err(FrontendError::Message(tmp_pos.clone(), String::from("built-in variable mustn't have function modifier"))),
-        None => (),
+    if let Some((_, tmp_pos)) = &modifiers.fun_modifier_pair {
+        return Err(FrontendError::Message(tmp_pos.clone(), String::from("built-in variable mustn't have function modifier")));
     }
     Ok(Rc::new(RefCell::new(Var::Builtin(trait_ident, None))))
 }
@@ -81,6 +79,7 @@ impl<'a> Parser<'a>
 
     pub fn parse(&mut self, tree: &mut Tree) -> FrontendResult<()>
     {
+        self.parse_imports(tree)?;
         let mut defs = self.parse_defs(&[Token::Eof])?;
         match self.lexer.next_token()? {
             (Token::Eof, _) => {
@@ -91,6 +90,33 @@ impl<'a> Parser<'a>
         }
     }
     
+    fn parse_imports(&mut self, tree: &mut Tree) -> FrontendResult<()>
+    {
+        loop {
+            match self.lexer.next_token()? {
+                (Token::Import, _) => {
+                    // "import", var_ident, ";"
+                    match self.lexer.next_token()? {
+                        (Token::Eof, pos) => return Err(FrontendError::Message(pos, String::from("unexpected end of file"))),
+                        (Token::VarIdent(ident), pos) if !ident.contains("::") => {
+                            match self.lexer.next_token()? {
+                                (Token::Eof, pos2) => return Err(FrontendError::Message(pos2, String::from("unexpected end of file"))),
+                                (Token::Semi, _) => tree.add_import((*pos.path).clone(), Import(ident, pos)),
+                                (_, pos2) => return Err(FrontendError::Message(pos2, String::from("unexpected token"))),
+                            }
+                        },
+                        (_, pos) => return Err(FrontendError::Message(pos, String::from("unexpected token"))),
+                    }
+                },
+                (token, pos) => {
+                    self.lexer.undo_token(token, pos);
+                    break;
+                },
+            }
+        }
+        Ok(())
+    }
+
     pub fn parse_type_args(&mut self) -> FrontendResult<Vec<TypeArg>>
     {
         let type_args = self.parse_zero_or_more(&Token::Comma, &[Token::Eof], Self::parse_type_arg)?;
@@ -191,6 +217,17 @@ impl<'a> Parser<'a>
         }
     }
 
+    fn parse_visibility(&mut self) -> FrontendResult<(Visibility, Option<Pos>)>
+    {
+        match self.lexer.next_token()? {
+            (Token::Pub, pos) => Ok((Visibility::Public, Some(pos))),
+            (token, pos) => {
+                self.lexer.undo_token(token, pos);
+                Ok((Visibility::Private, None))
+            },
+        }
+    }
+
     fn parse_modifiers(&mut self) -> FrontendResult<Modifiers>
     {
         let mut modifiers = Modifiers::new();
@@ -238,6 +275,29 @@ impl<'a> Parser<'a>
                         return Err(FrontendError::Message(pos, String::from("already used function modifier")));
                     }
                 },
+                (Token::Recursive, pos) => {
+                    if modifiers.fun_modifier_pair.is_none() {
+                        match self.lexer.next_token()? {
+                            (Token::Eof, pos2) => return Err(FrontendError::Message(pos2, String::from("unexpected end of file"))),
+                            (Token::LParen, _) => (),
+                            (_, pos2) => return Err(FrontendError::Message(pos2, String::from("unexpected token"))),
+                        }
+                        let (token2, pos2) = self.lexer.next_token()?;
+                        self.lexer.undo_token(token2, pos2.clone());
+                        let depth = self.parse_usize()?;
+                        if depth == 0 {
+                            return Err(FrontendError::Message(pos2, String::from("recursion depth must be greater than zero")));
+                        }
+                        match self.lexer.next_token()? {
+                            (Token::Eof, pos3) => return Err(FrontendError::Message(pos3, String::from("unexpected end of file"))),
+                            (Token::RParen, _) => (),
+                            (_, pos3) => return Err(FrontendError::Message(pos3, String::from("unclosed parenthesis"))),
+                        }
+                        modifiers.fun_modifier_pair = Some((FunModifier::Recursive(depth), pos));
+                    } else {
+                        return Err(FrontendError::Message(pos, String::from("already used function modifier")));
+                    }
+                },
                 (token, pos) => {
                     self.lexer.undo_token(token, pos);
                     break;
@@ -252,9 +312,8 @@ impl<'a> Parser<'a>
         match self.lexer.next_token()? {
             (Token::LParen, _) => {
                 // "(", args, ")", "->", type_expr, [ "where", where_pairs ], [ "=", expr ]
-                match &modifiers.var_modifier_pair {
-                    Some((_, tmp_pos)) => return Err(FrontendError::Message(tmp_pos.clone(), String::from("function mustn't have variable modifier"))),
-                    None => (),
+                if let Some((_, tmp_pos)) = &modifiers.var_modifier_pair {
+                    return Err(FrontendError::Message(tmp_pos.clone(), String::from("function mustn't have variable modifier")));
                 }
                 let fun_modifier = match &modifiers.fun_modifier_pair {
                     Some((tmp_fun_modifier, _)) => *tmp_fun_modifier,
@@ -296,9 +355,8 @@ impl<'a> Parser<'a>
                     Some((tmp_var_modifier, _)) => *tmp_var_modifier,
                     None => VarModifier::None,
                 };
-                match &modifiers.fun_modifier_pair {
-                    Some((_, tmp_pos)) => return Err(FrontendError::Message(tmp_pos.clone(), String::from("variable mustn't have function modifier"))),
-                    None => (),
+                if let Some((_, tmp_pos)) = &modifiers.fun_modifier_pair {
+                    return Err(FrontendError::Message(tmp_pos.clone(), String::from("variable mustn't have function modifier")));
                 }
                 let type_expr = self.parse_type_expr()?;
                 let where_tuples = match self.lexer.next_token()? {
@@ -327,6 +385,7 @@ impl<'a> Parser<'a>
     {
         let (tmp_token, first_pos) = self.lexer.next_token()?;
         self.lexer.undo_token(tmp_token, first_pos.clone());
+        let (visibility, visibility_pos) = self.parse_visibility()?;
         let modifiers = self.parse_modifiers()?;
         match self.lexer.next_token()? {
             (Token::Eof, pos) => Err(FrontendError::Message(pos, String::from("unexpected end of file"))),
@@ -340,15 +399,13 @@ impl<'a> Parser<'a>
                         match self.lexer.next_token()? {
                             (Token::Eof, pos3) => Err(FrontendError::Message(pos3, String::from("unexpected end of file"))),
                             (Token::ConIdent(ident), _) => {
-                                match modifiers.var_modifier_pair {
-                                    Some((_, tmp_pos)) => return Err(FrontendError::Message(tmp_pos, String::from("built-in type mustn't have variable modifier"))),
-                                    None => (),
+                                if let Some((_, tmp_pos)) = modifiers.var_modifier_pair {
+                                    return Err(FrontendError::Message(tmp_pos, String::from("built-in type mustn't have variable modifier")));
                                 }
-                                match modifiers.fun_modifier_pair {
-                                    Some((_, tmp_pos)) => return Err(FrontendError::Message(tmp_pos, String::from("built-in type mustn't have function modifier"))),
-                                    None => (),
+                                if let Some((_, tmp_pos)) = modifiers.fun_modifier_pair {
+                                    return Err(FrontendError::Message(tmp_pos, String::from("built-in type mustn't have function modifier")));
                                 }
-                                Ok(Box::new(Def::Type(ident, Rc::new(RefCell::new(TypeVar::Builtin(None, None, None))), first_pos)))
+                                Ok(Box::new(Def::Type(visibility, ident, Rc::new(RefCell::new(TypeVar::Builtin(None, None, None))), first_pos)))
                             },
                             (_, pos3) => Err(FrontendError::Message(pos3, String::from("unexpected token"))),
                         }
@@ -359,19 +416,20 @@ impl<'a> Parser<'a>
                             Token::VarIdent(tmp_ident) => tmp_ident,
                             _ => return Err(FrontendError::Internal(String::from("parse_def: no identifier"))),
                         };
-                        Ok(Box::new(Def::Var(ident, check_modifiers_for_builtin_var(&modifiers, None)?, first_pos)))
+                        Ok(Box::new(Def::Var(visibility, ident, check_modifiers_for_builtin_var(&modifiers, None)?, first_pos)))
                     },
                     (Token::Impl, _) => {
                         match self.lexer.next_token()? {
                             (Token::Eof, pos3) => Err(FrontendError::Message(pos3, String::from("unexpected end of file"))),
                             (Token::ConIdent(ident), _) => {
-                                match modifiers.var_modifier_pair {
-                                    Some((_, tmp_pos)) => return Err(FrontendError::Message(tmp_pos, String::from("built-in implementation mustn't have variable modifier"))),
-                                    None => (),
+                                if let Some((_, tmp_pos)) = modifiers.var_modifier_pair {
+                                    return Err(FrontendError::Message(tmp_pos, String::from("built-in implementation mustn't have variable modifier")));
+                                }
+                                if let Some((_, tmp_pos)) = modifiers.fun_modifier_pair {
+                                    return Err(FrontendError::Message(tmp_pos, String::from("built-in implementation mustn't have function modifier")));
                                 }
-                                match modifiers.fun_modifier_pair {
-                                    Some((_, tmp_pos)) => return Err(FrontendError::Message(tmp_pos, String::from("built-in implementation mustn't have function modifier"))),
-                                    None => (),
+                                if let Some(tmp_pos) = visibility_pos {
+                                    return Err(FrontendError::Message(tmp_pos, String::from("built-in implementation mustn't have visibility modifier")));
                                 }
                                 match self.lexer.next_token()? {
                                     (Token::Eof, pos4) => Err(FrontendError::Message(pos4, String::from("unexpected end of file"))),
@@ -393,13 +451,11 @@ impl<'a> Parser<'a>
                 match self.lexer.next_token()? {
                     (Token::Eof, pos2) => Err(FrontendError::Message(pos2, String::from("unexpected end of file"))),
                     (Token::ConIdent(ident), _) => { 
-                        match modifiers.var_modifier_pair {
-                            Some((_, tmp_pos)) => return Err(FrontendError::Message(tmp_pos, String::from("type mustn't have variable modifier"))),
-                            None => (),
+                        if let Some((_, tmp_pos)) = modifiers.var_modifier_pair {
+                            return Err(FrontendError::Message(tmp_pos, String::from("type mustn't have variable modifier")));
                         }
-                        match modifiers.fun_modifier_pair {
-                            Some((_, tmp_pos)) => return Err(FrontendError::Message(tmp_pos, String::from("type mustn't have function modifier"))),
-                            None => (),
+                        if let Some((_, tmp_pos)) = modifiers.fun_modifier_pair {
+                            return Err(FrontendError::Message(tmp_pos, String::from("type mustn't have function modifier")));
                         }
                         let saved_single_greater_flag = self.lexer.has_single_greater();
                         self.lexer.set_single_greater(true);
@@ -421,11 +477,11 @@ impl<'a> Parser<'a>
                         match self.lexer.next_token()? {
                             (Token::Eq, _) => {
                                 let cons = self.parse_one_or_more_cons(ident.as_str())?;
-                                Ok(Box::new(Def::Type(ident, Rc::new(RefCell::new(TypeVar::Data(type_args, cons, None))), first_pos)))
+                                Ok(Box::new(Def::Type(visibility, ident, Rc::new(RefCell::new(TypeVar::Data(type_args, cons, None))), first_pos)))
                             },
                             (token2, pos2) => {
                                 self.lexer.undo_token(token2, pos2);
-                                Ok(Box::new(Def::Type(ident, Rc::new(RefCell::new(TypeVar::Data(type_args, Vec::new(), None))), first_pos)))
+                                Ok(Box::new(Def::Type(visibility, ident, Rc::new(RefCell::new(TypeVar::Data(type_args, Vec::new(), None))), first_pos)))
                             },
                         }
                     },
@@ -437,13 +493,11 @@ impl<'a> Parser<'a>
                 match self.lexer.next_token()? {
                     (Token::Eof, pos2) => Err(FrontendError::Message(pos2, String::from("unexpected end of file"))),
                     (Token::ConIdent(ident), _) => {
-                        match modifiers.var_modifier_pair {
-                            Some((_, tmp_pos)) => return Err(FrontendError::Message(tmp_pos, String::from("type synonym mustn't have variable modifier"))),
-                            None => (),
+                        if let Some((_, tmp_pos)) = modifiers.var_modifier_pair {
+                            return Err(FrontendError::Message(tmp_pos, String::from("type synonym mustn't have variable modifier")));
                         }
-                        match modifiers.fun_modifier_pair {
-                            Some((_, tmp_pos)) => return Err(FrontendError::Message(tmp_pos, String::from("type synonym mustn't have function modifier"))),
-                            None => (),
+                        if let Some((_, tmp_pos)) = modifiers.fun_modifier_pair {
+                            return Err(FrontendError::Message(tmp_pos, String::from("type synonym mustn't have function modifier")));
                         }
                         let saved_single_greater_flag = self.lexer.has_single_greater();
                         self.lexer.set_single_greater(true);
@@ -463,12 +517,12 @@ impl<'a> Parser<'a>
                         };
                         self.lexer.set_single_greater(saved_single_greater_flag);
                         match self.lexer.next_token()? {
-                            (Token::Eof, pos3) => return Err(FrontendError::Message(pos3, String::from("unexpected end of file"))),
+                            (Token::Eof, pos3) => Err(FrontendError::Message(pos3, String::from("unexpected end of file"))),
                             (Token::Eq, _) => {
                                 let type_expr = self.parse_type_expr()?;
-                                Ok(Box::new(Def::Type(ident, Rc::new(RefCell::new(TypeVar::Synonym(type_args, type_expr, None))), first_pos)))
+                                Ok(Box::new(Def::Type(visibility, ident, Rc::new(RefCell::new(TypeVar::Synonym(type_args, type_expr, None))), first_pos)))
                             },
-                            (_, pos3) => return Err(FrontendError::Message(pos3, String::from("unexpected token"))),
+                            (_, pos3) => Err(FrontendError::Message(pos3, String::from("unexpected token"))),
                         }
                     },
                     (_, pos2) => Err(FrontendError::Message(pos2, String::from("unexpected token"))),
@@ -481,20 +535,18 @@ impl<'a> Parser<'a>
                     Token::VarIdent(tmp_ident) => tmp_ident,
                     _ => return Err(FrontendError::Internal(String::from("parse_def: no identifier"))),
                 };
-                Ok(Box::new(Def::Var(ident, self.parse_var(&modifiers, None)?, first_pos)))
+                Ok(Box::new(Def::Var(visibility, ident, self.parse_var(&modifiers, None)?, first_pos)))
             },
             (Token::Trait, _) => {
                 // "trait", con_ident, [ "<," one_or_more_type_args, ">" ]
                 match self.lexer.next_token()? {
                     (Token::Eof, pos2) => Err(FrontendError::Message(pos2, String::from("unexpected end of file"))),
                     (Token::ConIdent(ident), _) => {
-                        match modifiers.var_modifier_pair {
-                            Some((_, tmp_pos)) => return Err(FrontendError::Message(tmp_pos, String::from("trait mustn't have variable modifier"))),
-                            None => (),
+                        if let Some((_, tmp_pos)) = modifiers.var_modifier_pair {
+                            return Err(FrontendError::Message(tmp_pos, String::from("trait mustn't have variable modifier")));
                         }
-                        match modifiers.fun_modifier_pair {
-                            Some((_, tmp_pos)) => return Err(FrontendError::Message(tmp_pos, String::from("trait mustn't have function modifier"))),
-                            None => (),
+                        if let Some((_, tmp_pos)) = modifiers.fun_modifier_pair {
+                            return Err(FrontendError::Message(tmp_pos, String::from("trait mustn't have function modifier")));
                         }
                         let saved_single_greater_flag = self.lexer.has_single_greater();
                         self.lexer.set_single_greater(true);
@@ -514,16 +566,16 @@ impl<'a> Parser<'a>
                         };
                         self.lexer.set_single_greater(saved_single_greater_flag);
                         match self.lexer.next_token()? {
-                            (Token::Eof, pos3) => return Err(FrontendError::Message(pos3, String::from("unexpected end of file"))),
+                            (Token::Eof, pos3) => Err(FrontendError::Message(pos3, String::from("unexpected end of file"))),
                             (Token::LBrace, _) => {
                                 let trait_defs = self.parse_trait_defs(ident.as_str(), &[Token::RBrace])?;
                                 match self.lexer.next_token()? {
-                                    (Token::Eof, pos4) => return Err(FrontendError::Message(pos4, String::from("unexpected end of file"))),
-                                    (Token::RBrace, _) => Ok(Box::new(Def::Trait(ident, Rc::new(RefCell::new(Trait(type_args, trait_defs, None))), first_pos))),
-                                    (_, pos4) => return Err(FrontendError::Message(pos4, String::from("unexpected token"))),
+                                    (Token::Eof, pos4) => Err(FrontendError::Message(pos4, String::from("unexpected end of file"))),
+                                    (Token::RBrace, _) => Ok(Box::new(Def::Trait(visibility, ident, Rc::new(RefCell::new(Trait(type_args, trait_defs, None))), first_pos))),
+                                    (_, pos4) => Err(FrontendError::Message(pos4, String::from("unexpected token"))),
                                 }
                             },
-                            (_, pos3) => return Err(FrontendError::Message(pos3, String::from("unexpected token"))),
+                            (_, pos3) => Err(FrontendError::Message(pos3, String::from("unexpected token"))),
                         }
                     },
                     (_, pos2) => Err(FrontendError::Message(pos2, String::from("unexpected token"))),
@@ -534,13 +586,14 @@ impl<'a> Parser<'a>
                 match self.lexer.next_token()? {
                     (Token::Eof, pos2) => Err(FrontendError::Message(pos2, String::from("unexpected end of file"))),
                     (Token::ConIdent(ident), _) => {
-                        match modifiers.var_modifier_pair {
-                            Some((_, tmp_pos)) => return Err(FrontendError::Message(tmp_pos, String::from("implementation mustn't have variable modifier"))),
-                            None => (),
+                        if let Some((_, tmp_pos)) = modifiers.var_modifier_pair {
+                            return Err(FrontendError::Message(tmp_pos, String::from("implementation mustn't have variable modifier")));
+                        }
+                        if let Some((_, tmp_pos)) = modifiers.fun_modifier_pair {
+                            return Err(FrontendError::Message(tmp_pos, String::from("implementation mustn't have function modifier")));
                         }
-                        match modifiers.fun_modifier_pair {
-                            Some((_, tmp_pos)) => return Err(FrontendError::Message(tmp_pos, String::from("implementation mustn't have function modifier"))),
-                            None => (),
+                        if let Some(tmp_pos) = visibility_pos {
+                            return Err(FrontendError::Message(tmp_pos, String::from("implementation mustn't have visibility modifier")));
                         }
                         match self.lexer.next_token()? {
                             (Token::Eof, pos3) => Err(FrontendError::Message(pos3, String::from("unexpected end of file"))),
@@ -589,6 +642,7 @@ impl<'a> Parser<'a>
 
     fn parse_con(&mut self, data_ident: String) -> FrontendResult<Rc<RefCell<Con>>>
     {
+        let (visibility, _) = self.parse_visibility()?;
         match self.lexer.next_token()? {
             (Token::Eof, pos) => Err(FrontendError::Message(pos, String::from("unexpected end of file"))),
             (Token::ConIdent(ident), pos) => {
@@ -600,7 +654,7 @@ impl<'a> Parser<'a>
                         let type_exprs = self.parse_type_exprs(&[Token::RParen])?;
                         match self.lexer.next_token()? {
                             (Token::Eof, pos3) => Err(FrontendError::Message(pos3, String::from("unexpected end of file"))),
-                            (Token::RParen, _) => Ok(Rc::new(RefCell::new(Con::UnnamedField(ident, type_exprs, data_ident, pos)))),
+                            (Token::RParen, _) => Ok(Rc::new(RefCell::new(Con::UnnamedField(visibility, ident, type_exprs, data_ident, pos)))),
                             (_, pos3) => Err(FrontendError::Message(pos3, String::from("unclosed parenthesis"))),
                         }
                     },
@@ -608,7 +662,7 @@ impl<'a> Parser<'a>
                         let type_expr_named_field_pairs = self.parse_named_field_pairs(&[Token::RBrace], Self::parse_type_expr)?;
                         match self.lexer.next_token()? {
                             (Token::Eof, pos3) => Err(FrontendError::Message(pos3, String::from("unexpected end of file"))),
-                            (Token::RBrace, _) => Ok(Rc::new(RefCell::new(Con::NamedField(ident, type_expr_named_field_pairs, data_ident, None, pos)))),
+                            (Token::RBrace, _) => Ok(Rc::new(RefCell::new(Con::NamedField(visibility, ident, type_expr_named_field_pairs, data_ident, None, pos)))),
                             (_, pos3) => Err(FrontendError::Message(pos3, String::from("unclosed brace"))),
                         }
                     },
@@ -850,7 +904,7 @@ impl<'a> Parser<'a>
                 // "|", lambda_args, "|", [ "->", type_expr ], expr1 
                 let args = self.parse_lambda_args(&[Token::Bar])?;
                 match self.lexer.next_token()? {
-                    (Token::Eof, pos2) => return Err(FrontendError::Message(pos2, String::from("unexpected end of file"))),
+                    (Token::Eof, pos2) => Err(FrontendError::Message(pos2, String::from("unexpected end of file"))),
                     (Token::Bar, _) => {
                         let type_expr = match self.lexer.next_token()? {
                             (Token::RArrow, _) => Some(self.parse_type_expr()?),
@@ -24,7 +24,7 @@ x: Int = 1;
     }
     assert_eq!(1, tree.defs().len());
     match &*tree.defs()[0] {
-        Def::Var(ident, var, pos) => {
+        Def::Var(_, ident, var, pos) => {
             assert_eq!(1, pos.line);
             assert_eq!(1, pos.column);
             assert_eq!(String::from("x"), *ident);
@@ -80,7 +80,7 @@ builtin type T;
     }
     assert_eq!(1, tree.defs().len());
     match &*tree.defs()[0] {
-        Def::Type(ident, type_var, pos) => {
+        Def::Type(_, ident, type_var, pos) => {
             assert_eq!(1, pos.line);
             assert_eq!(1, pos.column);
             assert_eq!(String::from("T"), *ident);
@@ -112,7 +112,7 @@ data V<t1, t2> = D() | E(t1, t2);
     }
     assert_eq!(3, tree.defs().len());
     match &*tree.defs()[0] {
-        Def::Type(ident, type_var, pos) => {
+        Def::Type(_, ident, type_var, pos) => {
             assert_eq!(1, pos.line);
             assert_eq!(1, pos.column);
             assert_eq!(String::from("T"), *ident);
@@ -128,7 +128,7 @@ data V<t1, t2> = D() | E(t1, t2);
         _ => assert!(false),
     }
     match &*tree.defs()[1] {
-        Def::Type(ident, type_var, pos) => {
+        Def::Type(_, ident, type_var, pos) => {
             assert_eq!(2, pos.line);
             assert_eq!(1, pos.column);
             assert_eq!(String::from("U"), *ident);
@@ -139,7 +139,7 @@ data V<t1, t2> = D() | E(t1, t2);
                     assert_eq!(1, cons.len());
                     let con1_r = cons[0].borrow();
                     match &*con1_r {
-                        Con::UnnamedField(con_ident, type_exprs, data_ident, pos) => {
+                        Con::UnnamedField(_, con_ident, type_exprs, data_ident, pos) => {
                             assert_eq!(2, pos.line);
                             assert_eq!(10, pos.column);
                             assert_eq!(String::from("C"), *con_ident);
@@ -155,7 +155,7 @@ data V<t1, t2> = D() | E(t1, t2);
         _ => assert!(false),
     }
     match &*tree.defs()[2] {
-        Def::Type(ident, type_var, pos) => {
+        Def::Type(_, ident, type_var, pos) => {
             assert_eq!(3, pos.line);
             assert_eq!(1, pos.column);
             assert_eq!(String::from("V"), *ident);
@@ -180,7 +180,7 @@ data V<t1, t2> = D() | E(t1, t2);
                     assert_eq!(2, cons.len());
                     let con1_r = cons[0].borrow();
                     match &*con1_r {
-                        Con::UnnamedField(con_ident, type_exprs, data_ident, pos) => {
+                        Con::UnnamedField(_, con_ident, type_exprs, data_ident, pos) => {
                             assert_eq!(3, pos.line);
                             assert_eq!(18, pos.column);
                             assert_eq!(String::from("D"), *con_ident);
@@ -191,7 +191,7 @@ data V<t1, t2> = D() | E(t1, t2);
                     }
                     let con2_r = cons[1].borrow();
                     match &*con2_r {
-                        Con::UnnamedField(con_ident, type_exprs, data_ident, pos) => {
+                        Con::UnnamedField(_, con_ident, type_exprs, data_ident, pos) => {
                             assert_eq!(3, pos.line);
                             assert_eq!(24, pos.column);
                             assert_eq!(String::from("E"), *con_ident);
@@ -241,7 +241,7 @@ type U<t1, t2> = (t1, t2);
     }
     assert_eq!(2, tree.defs().len());
     match &*tree.defs()[0] {
-        Def::Type(ident, type_var, pos) => {
+        Def::Type(_, ident, type_var, pos) => {
             assert_eq!(1, pos.line);
             assert_eq!(1, pos.column);
             assert_eq!(String::from("T"), *ident);
@@ -264,7 +264,7 @@ type U<t1, t2> = (t1, t2);
         _ => assert!(false),
     }
     match &*tree.defs()[1] {
-        Def::Type(ident, type_var, pos) => {
+        Def::Type(_, ident, type_var, pos) => {
             assert_eq!(2, pos.line);
             assert_eq!(1, pos.column);
             assert_eq!(String::from("U"), *ident);
@@ -335,7 +335,7 @@ builtin a;
     }
     assert_eq!(2, tree.defs().len());
     match &*tree.defs()[0] {
-        Def::Var(ident, var, pos) => {
+        Def::Var(_, ident, var, pos) => {
             assert_eq!(1, pos.line);
             assert_eq!(1, pos.column);
             assert_eq!(String::from("A"), *ident);
@@ -348,7 +348,7 @@ builtin a;
         _ => assert!(false),
     }
     match &*tree.defs()[1] {
-        Def::Var(ident, var, pos) => {
+        Def::Var(_, ident, var, pos) => {
             assert_eq!(2, pos.line);
             assert_eq!(1, pos.column);
             assert_eq!(String::from("a"), *ident);
@@ -383,7 +383,7 @@ constant e: Int = 6;
     }
     assert_eq!(6, tree.defs().len());
     match &*tree.defs()[0] {
-        Def::Var(ident, var, pos) => {
+        Def::Var(_, ident, var, pos) => {
             assert_eq!(1, pos.line);
             assert_eq!(1, pos.column);
             assert_eq!(String::from("A"), *ident);
@@ -422,7 +422,7 @@ constant e: Int = 6;
         _ => assert!(false),
     }
     match &*tree.defs()[1] {
-        Def::Var(ident, var, pos) => {
+        Def::Var(_, ident, var, pos) => {
             assert_eq!(2, pos.line);
             assert_eq!(1, pos.column);
             assert_eq!(String::from("a"), *ident);
@@ -461,7 +461,7 @@ constant e: Int = 6;
         _ => assert!(false),
     }
     match &*tree.defs()[2] {
-        Def::Var(ident, var, pos) => {
+        Def::Var(_, ident, var, pos) => {
             assert_eq!(3, pos.line);
             assert_eq!(1, pos.column);
             assert_eq!(String::from("b"), *ident);
@@ -500,7 +500,7 @@ constant e: Int = 6;
         _ => assert!(false),
     }
     match &*tree.defs()[3] {
-        Def::Var(ident, var, pos) => {
+        Def::Var(_, ident, var, pos) => {
             assert_eq!(4, pos.line);
             assert_eq!(1, pos.column);
             assert_eq!(String::from("c"), *ident);
@@ -539,7 +539,7 @@ constant e: Int = 6;
         _ => assert!(false),
     }
     match &*tree.defs()[4] {
-        Def::Var(ident, var, pos) => {
+        Def::Var(_, ident, var, pos) => {
             assert_eq!(5, pos.line);
             assert_eq!(1, pos.column);
             assert_eq!(String::from("d"), *ident);
@@ -578,7 +578,7 @@ constant e: Int = 6;
         _ => assert!(false),
     }
     match &*tree.defs()[5] {
-        Def::Var(ident, var, pos) => {
+        Def::Var(_, ident, var, pos) => {
             assert_eq!(6, pos.line);
             assert_eq!(1, pos.column);
             assert_eq!(String::from("e"), *ident);
@@ -638,7 +638,7 @@ kernel i() -> () = ();
     }
     assert_eq!(5, tree.defs().len());
     match &*tree.defs()[0] {
-        Def::Var(ident, var, pos) => {
+        Def::Var(_, ident, var, pos) => {
             assert_eq!(1, pos.line);
             assert_eq!(1, pos.column);
             assert_eq!(String::from("F"), *ident);
@@ -683,7 +683,7 @@ kernel i() -> () = ();
         _ => assert!(false),
     }
     match &*tree.defs()[1] {
-        Def::Var(ident, var, pos) => {
+        Def::Var(_, ident, var, pos) => {
             assert_eq!(2, pos.line);
             assert_eq!(1, pos.column);
             assert_eq!(String::from("f"), *ident);
@@ -728,7 +728,7 @@ kernel i() -> () = ();
         _ => assert!(false),
     }
     match &*tree.defs()[2] {
-        Def::Var(ident, var, pos) => {
+        Def::Var(_, ident, var, pos) => {
             assert_eq!(3, pos.line);
             assert_eq!(1, pos.column);
             assert_eq!(String::from("g"), *ident);
@@ -826,7 +826,7 @@ kernel i() -> () = ();
         _ => assert!(false),
     }
     match &*tree.defs()[3] {
-        Def::Var(ident, var, pos) => {
+        Def::Var(_, ident, var, pos) => {
             assert_eq!(4, pos.line);
             assert_eq!(1, pos.column);
             assert_eq!(String::from("h"), *ident);
@@ -924,7 +924,7 @@ kernel i() -> () = ();
         _ => assert!(false),
     }
     match &*tree.defs()[4] {
-        Def::Var(ident, var, pos) => {
+        Def::Var(_, ident, var, pos) => {
             assert_eq!(5, pos.line);
             assert_eq!(1, pos.column);
             assert_eq!(String::from("i"), *ident);
@@ -970,6 +970,66 @@ kernel i() -> () = ();
     }
 }
 
+#[test]
+fn test_parser_parse_parses_bounded_recursive_function_definition()
+{
+    let s = "
+recursive(16) f(x: Int) -> Int = x;
+";
+    let s2 = &s[1..];
+    let mut cursor = Cursor::new(s2.as_bytes());
+    let mut parser = Parser::new(Lexer::new(String::from("test.vscfl"), &mut cursor));
+    let mut tree = Tree::new();
+    match parser.parse(&mut tree) {
+        Ok(()) => assert!(true),
+        Err(_) => assert!(false),
+    }
+    assert_eq!(1, tree.defs().len());
+    match &*tree.defs()[0] {
+        Def::Var(_, ident, var, pos) => {
+            assert_eq!(1, pos.line);
+            assert_eq!(1, pos.column);
+            assert_eq!(String::from("f"), *ident);
+            let var_r = var.borrow();
+            match &*var_r {
+                Var::Fun(fun, None, None) => {
+                    match &**fun {
+                        Fun::Fun(FunModifier::Recursive(depth), args, _, where_tuples, expr, None, None) => {
+                            assert_eq!(16, *depth);
+                            assert_eq!(1, args.len());
+                            match &args[0] {
+                                Arg(arg_ident, _, None, pos) => {
+                                    assert_eq!(1, pos.line);
+                                    assert_eq!(17, pos.column);
+                                    assert_eq!(String::from("x"), *arg_ident);
+                                },
+                                _ => assert!(false),
+                            }
+                            assert_eq!(true, where_tuples.is_empty());
+                            match expr {
+                                Some(expr) => {
+                                    match &**expr {
+                                        Expr::Var(var_ident, None, pos) => {
+                                            assert_eq!(1, pos.line);
+                                            assert_eq!(34, pos.column);
+                                            assert_eq!(String::from("x"), *var_ident);
+                                        },
+                                        _ => assert!(false),
+                                    }
+                                },
+                                None => assert!(false),
+                            }
+                        },
+                        _ => assert!(false),
+                    }
+                },
+                _ => assert!(false),
+            }
+        },
+        _ => assert!(false),
+    }
+}
+
 #[test]
 fn test_parser_parse_parses_trait_definitions()
 {
@@ -987,7 +1047,7 @@ trait U<t1, t2> {};
     }
     assert_eq!(2, tree.defs().len());
     match &*tree.defs()[0] {
-        Def::Trait(ident, trait1, pos) => {
+        Def::Trait(_, ident, trait1, pos) => {
             assert_eq!(1, pos.line);
             assert_eq!(1, pos.column);
             assert_eq!(String::from("T"), *ident);
@@ -1003,7 +1063,7 @@ trait U<t1, t2> {};
         _ => assert!(false),
     }
     match &*tree.defs()[1] {
-        Def::Trait(ident, trait1, pos) => {
+        Def::Trait(_, ident, trait1, pos) => {
             assert_eq!(2, pos.line);
             assert_eq!(1, pos.column);
             assert_eq!(String::from("U"), *ident);
@@ -1118,7 +1178,7 @@ data T = C()
     }
     assert_eq!(1, tree.defs().len());
     match &*tree.defs()[0] {
-        Def::Type(ident, type_var, pos) => {
+        Def::Type(_, ident, type_var, pos) => {
             assert_eq!(1, pos.line);
             assert_eq!(1, pos.column);
             assert_eq!(String::from("T"), *ident);
@@ -1129,7 +1189,7 @@ data T = C()
                     assert_eq!(4, cons.len());
                     let con1_r = cons[0].borrow();
                     match &*con1_r {
-                        Con::UnnamedField(con_ident, type_exprs, data_ident, pos) => {
+                        Con::UnnamedField(_, con_ident, type_exprs, data_ident, pos) => {
                             assert_eq!(1, pos.line);
                             assert_eq!(10, pos.column);
                             assert_eq!(String::from("C"), *con_ident);
@@ -1140,7 +1200,7 @@ data T = C()
                     }
                     let con2_r = cons[1].borrow();
                     match &*con2_r {
-                        Con::UnnamedField(con_ident, type_exprs, data_ident, pos) => {
+                        Con::UnnamedField(_, con_ident, type_exprs, data_ident, pos) => {
                             assert_eq!(2, pos.line);
                             assert_eq!(10, pos.column);
                             assert_eq!(String::from("D"), *con_ident);
@@ -1167,7 +1227,7 @@ data T = C()
                     }
                     let con3_r = cons[2].borrow();
                     match &*con3_r {
-                        Con::NamedField(con_ident, type_expr_named_field_pairs, data_ident, None, pos) => {
+                        Con::NamedField(_, con_ident, type_expr_named_field_pairs, data_ident, None, pos) => {
                             assert_eq!(3, pos.line);
                             assert_eq!(10, pos.column);
                             assert_eq!(String::from("E"), *con_ident);
@@ -1178,7 +1238,7 @@ data T = C()
                     }
                     let con4_r = cons[3].borrow();
                     match &*con4_r {
-                        Con::NamedField(con_ident, type_expr_named_field_pairs, data_ident, None, pos) => {
+                        Con::NamedField(_, con_ident, type_expr_named_field_pairs, data_ident, None, pos) => {
                             assert_eq!(4, pos.line);
                             assert_eq!(10, pos.column);
                             assert_eq!(String::from("F"), *con_ident);
@@ -1251,7 +1311,7 @@ type D = uniq (Int);
     }
     assert_eq!(11, tree.defs().len());
     match &*tree.defs()[0] {
-        Def::Type(ident, type_var, pos) => {
+        Def::Type(_, ident, type_var, pos) => {
             assert_eq!(1, pos.line);
             assert_eq!(1, pos.column);
             assert_eq!(String::from("T"), *ident);
@@ -1274,7 +1334,7 @@ type D = uniq (Int);
         _ => assert!(false),
     }
     match &*tree.defs()[1] {
-        Def::Type(ident, type_var, pos) => {
+        Def::Type(_, ident, type_var, pos) => {
             assert_eq!(2, pos.line);
             assert_eq!(1, pos.column);
             assert_eq!(String::from("U"), *ident);
@@ -1313,7 +1373,7 @@ type D = uniq (Int);
         _ => assert!(false),
     }
     match &*tree.defs()[2] {
-        Def::Type(ident, type_var, pos) => {
+        Def::Type(_, ident, type_var, pos) => {
             assert_eq!(3, pos.line);
             assert_eq!(1, pos.column);
             assert_eq!(String::from("V"), *ident);
@@ -1344,7 +1404,7 @@ type D = uniq (Int);
         _ => assert!(false),
     }
     match &*tree.defs()[3] {
-        Def::Type(ident, type_var, pos) => {
+        Def::Type(_, ident, type_var, pos) => {
             assert_eq!(4, pos.line);
             assert_eq!(1, pos.column);
             assert_eq!(String::from("W"), *ident);
@@ -1391,7 +1451,7 @@ type D = uniq (Int);
         _ => assert!(false),
     }
     match &*tree.defs()[4] {
-        Def::Type(ident, type_var, pos) => {
+        Def::Type(_, ident, type_var, pos) => {
             assert_eq!(5, pos.line);
             assert_eq!(1, pos.column);
             assert_eq!(String::from("X"), *ident);
@@ -1421,7 +1481,7 @@ type D = uniq (Int);
         _ => assert!(false),
     }
     match &*tree.defs()[5] {
-        Def::Type(ident, type_var, pos) => {
+        Def::Type(_, ident, type_var, pos) => {
             assert_eq!(6, pos.line);
             assert_eq!(1, pos.column);
             assert_eq!(String::from("Y"), *ident);
@@ -1452,7 +1512,7 @@ type D = uniq (Int);
         _ => assert!(false),
     }
     match &*tree.defs()[6] {
-        Def::Type(ident, type_var, pos) => {
+        Def::Type(_, ident, type_var, pos) => {
             assert_eq!(7, pos.line);
             assert_eq!(1, pos.column);
             assert_eq!(String::from("Z"), *ident);
@@ -1482,7 +1542,7 @@ type D = uniq (Int);
         _ => assert!(false),
     }
     match &*tree.defs()[7] {
-        Def::Type(ident, type_var, pos) => {
+        Def::Type(_, ident, type_var, pos) => {
             assert_eq!(8, pos.line);
             assert_eq!(1, pos.column);
             assert_eq!(String::from("A"), *ident);
@@ -1505,7 +1565,7 @@ type D = uniq (Int);
         _ => assert!(false),
     }
     match &*tree.defs()[8] {
-        Def::Type(ident, type_var, pos) => {
+        Def::Type(_, ident, type_var, pos) => {
             assert_eq!(9, pos.line);
             assert_eq!(1, pos.column);
             assert_eq!(String::from("B"), *ident);
@@ -1554,7 +1614,7 @@ type D = uniq (Int);
         _ => assert!(false),
     }
     match &*tree.defs()[9] {
-        Def::Type(ident, type_var, pos) => {
+        Def::Type(_, ident, type_var, pos) => {
             assert_eq!(10, pos.line);
             assert_eq!(1, pos.column);
             assert_eq!(String::from("C"), *ident);
@@ -1584,7 +1644,7 @@ type D = uniq (Int);
         _ => assert!(false),
     }
     match &*tree.defs()[10] {
-        Def::Type(ident, type_var, pos) => {
+        Def::Type(_, ident, type_var, pos) => {
             assert_eq!(11, pos.line);
             assert_eq!(1, pos.column);
             assert_eq!(String::from("D"), *ident);
@@ -1640,7 +1700,7 @@ f() -> (t1, t2, t3)
     }
     assert_eq!(2, tree.defs().len());
     match &*tree.defs()[0] {
-        Def::Var(ident, var, pos) => {
+        Def::Var(_, ident, var, pos) => {
             assert_eq!(1, pos.line);
             assert_eq!(1, pos.column);
             assert_eq!(String::from("a"), *ident);
@@ -1793,7 +1853,7 @@ f() -> (t1, t2, t3)
         _ => assert!(false),
     }
     match &*tree.defs()[1] {
-        Def::Var(ident, var, pos) => {
+        Def::Var(_, ident, var, pos) => {
             assert_eq!(6, pos.line);
             assert_eq!(1, pos.column);
             assert_eq!(String::from("f"), *ident);
@@ -1978,7 +2038,7 @@ c: Int =
     }
     assert_eq!(3, tree.defs().len());
     match &*tree.defs()[0] {
-        Def::Var(ident, var, pos) => {
+        Def::Var(_, ident, var, pos) => {
             assert_eq!(1, pos.line);
             assert_eq!(1, pos.column);
             assert_eq!(String::from("a"), *ident);
@@ -2046,7 +2106,7 @@ c: Int =
         _ => assert!(false),
     }
     match &*tree.defs()[1] {
-        Def::Var(ident, var, pos) => {
+        Def::Var(_, ident, var, pos) => {
             assert_eq!(2, pos.line);
             assert_eq!(1, pos.column);
             assert_eq!(String::from("b"), *ident);
@@ -2161,7 +2221,7 @@ c: Int =
         _ => assert!(false),
     }
     match &*tree.defs()[2] {
-        Def::Var(ident, var, pos) => {
+        Def::Var(_, ident, var, pos) => {
             assert_eq!(6, pos.line);
             assert_eq!(1, pos.column);
             assert_eq!(String::from("c"), *ident);
@@ -2274,7 +2334,7 @@ b: Int = shared 2.5 as Int;
     }
     assert_eq!(2, tree.defs().len());
     match &*tree.defs()[0] {
-        Def::Var(ident, var, pos) => {
+        Def::Var(_, ident, var, pos) => {
             assert_eq!(1, pos.line);
             assert_eq!(1, pos.column);
             assert_eq!(String::from("a"), *ident);
@@ -2335,7 +2395,7 @@ b: Int = shared 2.5 as Int;
         _ => assert!(false),
     }
     match &*tree.defs()[1] {
-        Def::Var(ident, var, pos) => {
+        Def::Var(_, ident, var, pos) => {
             assert_eq!(2, pos.line);
             assert_eq!(1, pos.column);
             assert_eq!(String::from("b"), *ident);
@@ -2413,7 +2473,7 @@ b: Bool = shared false | true;
         Err(_) => assert!(false),
     }
     match &*tree.defs()[0] {
-        Def::Var(ident, var, pos) => {
+        Def::Var(_, ident, var, pos) => {
             assert_eq!(1, pos.line);
             assert_eq!(1, pos.column);
             assert_eq!(String::from("a"), *ident);
@@ -2493,7 +2553,7 @@ b: Bool = shared false | true;
         _ => assert!(false),
     }
     match &*tree.defs()[1] {
-        Def::Var(ident, var, pos) => {
+        Def::Var(_, ident, var, pos) => {
             assert_eq!(2, pos.line);
             assert_eq!(1, pos.column);
             assert_eq!(String::from("b"), *ident);
@@ -2583,7 +2643,7 @@ a: Bool = true ^ false | false ^ true;
     }
     assert_eq!(1, tree.defs().len());
     match &*tree.defs()[0] {
-        Def::Var(ident, var, pos) => {
+        Def::Var(_, ident, var, pos) => {
             assert_eq!(1, pos.line);
             assert_eq!(1, pos.column);
             assert_eq!(String::from("a"), *ident);
@@ -2720,7 +2780,7 @@ a: Bool = true & false ^ false & true;
     }
     assert_eq!(1, tree.defs().len());
     match &*tree.defs()[0] {
-        Def::Var(ident, var, pos) => {
+        Def::Var(_, ident, var, pos) => {
             assert_eq!(1, pos.line);
             assert_eq!(1, pos.column);
             assert_eq!(String::from("a"), *ident);
@@ -2857,7 +2917,7 @@ a: Bool = 1 == 2 & 3 != 4;
     }
     assert_eq!(1, tree.defs().len());
     match &*tree.defs()[0] {
-        Def::Var(ident, var, pos) => {
+        Def::Var(_, ident, var, pos) => {
             assert_eq!(1, pos.line);
             assert_eq!(1, pos.column);
             assert_eq!(String::from("a"), *ident);
@@ -2999,7 +3059,7 @@ f: Bool = 1 << 2 <= 3 >> 4;
     }
     assert_eq!(6, tree.defs().len());
     match &*tree.defs()[0] {
-        Def::Var(ident, var, pos) => {
+        Def::Var(_, ident, var, pos) => {
             assert_eq!(1, pos.line);
             assert_eq!(1, pos.column);
             assert_eq!(String::from("a"), *ident);
@@ -3119,7 +3179,7 @@ f: Bool = 1 << 2 <= 3 >> 4;
         _ => assert!(false),
     }
     match &*tree.defs()[1] {
-        Def::Var(ident, var, pos) => {
+        Def::Var(_, ident, var, pos) => {
             assert_eq!(2, pos.line);
             assert_eq!(1, pos.column);
             assert_eq!(String::from("b"), *ident);
@@ -3239,7 +3299,7 @@ f: Bool = 1 << 2 <= 3 >> 4;
         _ => assert!(false),
     }
     match &*tree.defs()[2] {
-        Def::Var(ident, var, pos) => {
+        Def::Var(_, ident, var, pos) => {
             assert_eq!(3, pos.line);
             assert_eq!(1, pos.column);
             assert_eq!(String::from("c"), *ident);
@@ -3359,7 +3419,7 @@ f: Bool = 1 << 2 <= 3 >> 4;
         _ => assert!(false),
     }
     match &*tree.defs()[3] {
-        Def::Var(ident, var, pos) => {
+        Def::Var(_, ident, var, pos) => {
             assert_eq!(4, pos.line);
             assert_eq!(1, pos.column);
             assert_eq!(String::from("d"), *ident);
@@ -3479,7 +3539,7 @@ f: Bool = 1 << 2 <= 3 >> 4;
         _ => assert!(false),
     }
     match &*tree.defs()[4] {
-        Def::Var(ident, var, pos) => {
+        Def::Var(_, ident, var, pos) => {
             assert_eq!(5, pos.line);
             assert_eq!(1, pos.column);
             assert_eq!(String::from("e"), *ident);
@@ -3599,7 +3659,7 @@ f: Bool = 1 << 2 <= 3 >> 4;
         _ => assert!(false),
     }
     match &*tree.defs()[5] {
-        Def::Var(ident, var, pos) => {
+        Def::Var(_, ident, var, pos) => {
             assert_eq!(6, pos.line);
             assert_eq!(1, pos.column);
             assert_eq!(String::from("f"), *ident);
@@ -3737,7 +3797,7 @@ b: Int = 1 + 2 >> 3 - 4;
     }
     assert_eq!(2, tree.defs().len());
     match &*tree.defs()[0] {
-        Def::Var(ident, var, pos) => {
+        Def::Var(_, ident, var, pos) => {
             assert_eq!(1, pos.line);
             assert_eq!(1, pos.column);
             assert_eq!(String::from("a"), *ident);
@@ -3857,7 +3917,7 @@ b: Int = 1 + 2 >> 3 - 4;
         _ => assert!(false),
     }
     match &*tree.defs()[1] {
-        Def::Var(ident, var, pos) => {
+        Def::Var(_, ident, var, pos) => {
             assert_eq!(2, pos.line);
             assert_eq!(1, pos.column);
             assert_eq!(String::from("b"), *ident);
@@ -3995,7 +4055,7 @@ b: Int = 1 * 2 - 3 / 4;
     }
     assert_eq!(2, tree.defs().len());
     match &*tree.defs()[0] {
-        Def::Var(ident, var, pos) => {
+        Def::Var(_, ident, var, pos) => {
             assert_eq!(1, pos.line);
             assert_eq!(1, pos.column);
             assert_eq!(String::from("a"), *ident);
@@ -4115,7 +4175,7 @@ b: Int = 1 * 2 - 3 / 4;
         _ => assert!(false),
     }
     match &*tree.defs()[1] {
-        Def::Var(ident, var, pos) => {
+        Def::Var(_, ident, var, pos) => {
             assert_eq!(2, pos.line);
             assert_eq!(1, pos.column);
             assert_eq!(String::from("b"), *ident);
@@ -4254,7 +4314,7 @@ c: Int = -1 % !2;
     }
     assert_eq!(3, tree.defs().len());
     match &*tree.defs()[0] {
-        Def::Var(ident, var, pos) => {
+        Def::Var(_, ident, var, pos) => {
             assert_eq!(1, pos.line);
             assert_eq!(1, pos.column);
             assert_eq!(String::from("a"), *ident);
@@ -4352,7 +4412,7 @@ c: Int = -1 % !2;
         _ => assert!(false),
     }
     match &*tree.defs()[1] {
-        Def::Var(ident, var, pos) => {
+        Def::Var(_, ident, var, pos) => {
             assert_eq!(2, pos.line);
             assert_eq!(1, pos.column);
             assert_eq!(String::from("b"), *ident);
@@ -4450,7 +4510,7 @@ c: Int = -1 % !2;
         _ => assert!(false),
     }
     match &*tree.defs()[2] {
-        Def::Var(ident, var, pos) => {
+        Def::Var(_, ident, var, pos) => {
             assert_eq!(3, pos.line);
             assert_eq!(1, pos.column);
             assert_eq!(String::from("c"), *ident);
@@ -4565,7 +4625,7 @@ a: Int = -!1;
     }
     assert_eq!(1, tree.defs().len());
     match &*tree.defs()[0] {
-        Def::Var(ident, var, pos) => {
+        Def::Var(_, ident, var, pos) => {
             assert_eq!(1, pos.line);
             assert_eq!(1, pos.column);
             assert_eq!(String::from("a"), *ident);
@@ -4677,7 +4737,7 @@ d: Int =
     }
     assert_eq!(4, tree.defs().len());
     match &*tree.defs()[0] {
-        Def::Var(ident, var, pos) => {
+        Def::Var(_, ident, var, pos) => {
             assert_eq!(1, pos.line);
             assert_eq!(1, pos.column);
             assert_eq!(String::from("a"), *ident);
@@ -4743,7 +4803,7 @@ d: Int =
         _ => assert!(false),
     }
     match &*tree.defs()[1] {
-        Def::Var(ident, var, pos) => {
+        Def::Var(_, ident, var, pos) => {
             assert_eq!(2, pos.line);
             assert_eq!(1, pos.column);
             assert_eq!(String::from("b"), *ident);
@@ -5000,7 +5060,7 @@ d: Int =
         _ => assert!(false),
     }
     match &*tree.defs()[2] {
-        Def::Var(ident, var, pos) => {
+        Def::Var(_, ident, var, pos) => {
             assert_eq!(10, pos.line);
             assert_eq!(1, pos.column);
             assert_eq!(String::from("c"), *ident);
@@ -5353,7 +5413,7 @@ d: Int =
         _ => assert!(false),
     }    
     match &*tree.defs()[3] {
-        Def::Var(ident, var, pos) => {
+        Def::Var(_, ident, var, pos) => {
             assert_eq!(18, pos.line);
             assert_eq!(1, pos.column);
             assert_eq!(String::from("d"), *ident);
@@ -5682,7 +5742,7 @@ h: () = printf(\"%d\\n\", 2);
     }
     assert_eq!(8, tree.defs().len());
     match &*tree.defs()[0] {
-        Def::Var(ident, var, pos) => {
+        Def::Var(_, ident, var, pos) => {
             assert_eq!(1, pos.line);
             assert_eq!(1, pos.column);
             assert_eq!(String::from("a"), *ident);
@@ -5802,7 +5862,7 @@ h: () = printf(\"%d\\n\", 2);
         _ => assert!(false),
     }
     match &*tree.defs()[1] {
-        Def::Var(ident, var, pos) => {
+        Def::Var(_, ident, var, pos) => {
             assert_eq!(2, pos.line);
             assert_eq!(1, pos.column);
             assert_eq!(String::from("b"), *ident);
@@ -5841,7 +5901,7 @@ h: () = printf(\"%d\\n\", 2);
         _ => assert!(false),
     }
     match &*tree.defs()[2] {
-        Def::Var(ident, var, pos) => {
+        Def::Var(_, ident, var, pos) => {
             assert_eq!(3, pos.line);
             assert_eq!(1, pos.column);
             assert_eq!(String::from("c"), *ident);
@@ -5938,7 +5998,7 @@ h: () = printf(\"%d\\n\", 2);
         _ => assert!(false),
     }
     match &*tree.defs()[3] {
-        Def::Var(ident, var, pos) => {
+        Def::Var(_, ident, var, pos) => {
             assert_eq!(4, pos.line);
             assert_eq!(1, pos.column);
             assert_eq!(String::from("d"), *ident);
@@ -6048,7 +6108,7 @@ h: () = printf(\"%d\\n\", 2);
         _ => assert!(false),
     }
     match &*tree.defs()[4] {
-        Def::Var(ident, var, pos) => {
+        Def::Var(_, ident, var, pos) => {
             assert_eq!(5, pos.line);
             assert_eq!(1, pos.column);
             assert_eq!(String::from("e"), *ident);
@@ -6084,7 +6144,7 @@ h: () = printf(\"%d\\n\", 2);
         _ => assert!(false),
     }
     match &*tree.defs()[5] {
-        Def::Var(ident, var, pos) => {
+        Def::Var(_, ident, var, pos) => {
             assert_eq!(6, pos.line);
             assert_eq!(1, pos.column);
             assert_eq!(String::from("f"), *ident);
@@ -6120,7 +6180,7 @@ h: () = printf(\"%d\\n\", 2);
         _ => assert!(false),
     }
     match &*tree.defs()[6] {
-        Def::Var(ident, var, pos) => {
+        Def::Var(_, ident, var, pos) => {
             assert_eq!(7, pos.line);
             assert_eq!(1, pos.column);
             assert_eq!(String::from("g"), *ident);
@@ -6193,7 +6253,7 @@ h: () = printf(\"%d\\n\", 2);
         _ => assert!(false),
     }
     match &*tree.defs()[7] {
-        Def::Var(ident, var, pos) => {
+        Def::Var(_, ident, var, pos) => {
             assert_eq!(8, pos.line);
             assert_eq!(1, pos.column);
             assert_eq!(String::from("h"), *ident);
@@ -6271,7 +6331,7 @@ a: Int =
     }
     assert_eq!(1, tree.defs().len());
     match &*tree.defs()[0] {
-        Def::Var(ident, var, pos) => {
+        Def::Var(_, ident, var, pos) => {
             assert_eq!(1, pos.line);
             assert_eq!(1, pos.column);
             assert_eq!(String::from("a"), *ident);
@@ -6394,7 +6454,7 @@ a: Int =
     }
     assert_eq!(1, tree.defs().len());
     match &*tree.defs()[0] {
-        Def::Var(ident, var, pos) => {
+        Def::Var(_, ident, var, pos) => {
             assert_eq!(1, pos.line);
             assert_eq!(1, pos.column);
             assert_eq!(String::from("a"), *ident);
@@ -7052,7 +7112,7 @@ o: A3 = [1; 10];
     }
     assert_eq!(15, tree.defs().len());
     match &*tree.defs()[0] {
-        Def::Var(ident, var, pos) => {
+        Def::Var(_, ident, var, pos) => {
             assert_eq!(1, pos.line);
             assert_eq!(1, pos.column);
             assert_eq!(String::from("a"), *ident);
@@ -7091,7 +7151,7 @@ o: A3 = [1; 10];
         _ => assert!(false),
     }
     match &*tree.defs()[1] {
-        Def::Var(ident, var, pos) => {
+        Def::Var(_, ident, var, pos) => {
             assert_eq!(2, pos.line);
             assert_eq!(1, pos.column);
             assert_eq!(String::from("b"), *ident);
@@ -7130,7 +7190,7 @@ o: A3 = [1; 10];
         _ => assert!(false),
     }
     match &*tree.defs()[2] {
-        Def::Var(ident, var, pos) => {
+        Def::Var(_, ident, var, pos) => {
             assert_eq!(3, pos.line);
             assert_eq!(1, pos.column);
             assert_eq!(String::from("c"), *ident);
@@ -7169,7 +7229,7 @@ o: A3 = [1; 10];
         _ => assert!(false),
     }
     match &*tree.defs()[3] {
-        Def::Var(ident, var, pos) => {
+        Def::Var(_, ident, var, pos) => {
             assert_eq!(4, pos.line);
             assert_eq!(1, pos.column);
             assert_eq!(String::from("d"), *ident);
@@ -7208,7 +7268,7 @@ o: A3 = [1; 10];
         _ => assert!(false),
     }
     match &*tree.defs()[4] {
-        Def::Var(ident, var, pos) => {
+        Def::Var(_, ident, var, pos) => {
             assert_eq!(5, pos.line);
             assert_eq!(1, pos.column);
             assert_eq!(String::from("e"), *ident);
@@ -7247,7 +7307,7 @@ o: A3 = [1; 10];
         _ => assert!(false),
     }
     match &*tree.defs()[5] {
-        Def::Var(ident, var, pos) => {
+        Def::Var(_, ident, var, pos) => {
             assert_eq!(6, pos.line);
             assert_eq!(1, pos.column);
             assert_eq!(String::from("f"), *ident);
@@ -7286,7 +7346,7 @@ o: A3 = [1; 10];
         _ => assert!(false),
     }
     match &*tree.defs()[6] {
-        Def::Var(ident, var, pos) => {
+        Def::Var(_, ident, var, pos) => {
             assert_eq!(7, pos.line);
             assert_eq!(1, pos.column);
             assert_eq!(String::from("g"), *ident);
@@ -7325,7 +7385,7 @@ o: A3 = [1; 10];
         _ => assert!(false),
     }
     match &*tree.defs()[7] {
-        Def::Var(ident, var, pos) => {
+        Def::Var(_, ident, var, pos) => {
             assert_eq!(8, pos.line);
             assert_eq!(1, pos.column);
             assert_eq!(String::from("h"), *ident);
@@ -7364,7 +7424,7 @@ o: A3 = [1; 10];
         _ => assert!(false),
     }
     match &*tree.defs()[8] {
-        Def::Var(ident, var, pos) => {
+        Def::Var(_, ident, var, pos) => {
             assert_eq!(9, pos.line);
             assert_eq!(1, pos.column);
             assert_eq!(String::from("i"), *ident);
@@ -7403,7 +7463,7 @@ o: A3 = [1; 10];
         _ => assert!(false),
     }
     match &*tree.defs()[9] {
-        Def::Var(ident, var, pos) => {
+        Def::Var(_, ident, var, pos) => {
             assert_eq!(10, pos.line);
             assert_eq!(1, pos.column);
             assert_eq!(String::from("j"), *ident);
@@ -7442,7 +7502,7 @@ o: A3 = [1; 10];
         _ => assert!(false),
     }
     match &*tree.defs()[10] {
-        Def::Var(ident, var, pos) => {
+        Def::Var(_, ident, var, pos) => {
             assert_eq!(11, pos.line);
             assert_eq!(1, pos.column);
             assert_eq!(String::from("k"), *ident);
@@ -7481,7 +7541,7 @@ o: A3 = [1; 10];
         _ => assert!(false),
     }
     match &*tree.defs()[11] {
-        Def::Var(ident, var, pos) => {
+        Def::Var(_, ident, var, pos) => {
             assert_eq!(12, pos.line);
             assert_eq!(1, pos.column);
             assert_eq!(String::from("l"), *ident);
@@ -7544,7 +7604,7 @@ o: A3 = [1; 10];
         _ => assert!(false),
     }
     match &*tree.defs()[12] {
-        Def::Var(ident, var, pos) => {
+        Def::Var(_, ident, var, pos) => {
             assert_eq!(13, pos.line);
             assert_eq!(1, pos.column);
             assert_eq!(String::from("m"), *ident);
@@ -7583,7 +7643,7 @@ o: A3 = [1; 10];
         _ => assert!(false),
     }
     match &*tree.defs()[13] {
-        Def::Var(ident, var, pos) => {
+        Def::Var(_, ident, var, pos) => {
             assert_eq!(14, pos.line);
             assert_eq!(1, pos.column);
             assert_eq!(String::from("n"), *ident);
@@ -7657,7 +7717,7 @@ o: A3 = [1; 10];
         _ => assert!(false),
     }
     match &*tree.defs()[14] {
-        Def::Var(ident, var, pos) => {
+        Def::Var(_, ident, var, pos) => {
             assert_eq!(15, pos.line);
             assert_eq!(1, pos.column);
             assert_eq!(String::from("o"), *ident);
@@ -7752,7 +7812,7 @@ a: Int =
     }
     assert_eq!(1, tree.defs().len());
     match &*tree.defs()[0] {
-        Def::Var(ident, var, pos) => {
+        Def::Var(_, ident, var, pos) => {
             assert_eq!(1, pos.line);
             assert_eq!(1, pos.column);
             assert_eq!(String::from("a"), *ident);
@@ -8515,7 +8575,7 @@ trait T
     }
     assert_eq!(1, tree.defs().len());
     match &*tree.defs()[0] {
-        Def::Trait(ident, trait1, pos) => {
+        Def::Trait(_, ident, trait1, pos) => {
             assert_eq!(1, pos.line);
             assert_eq!(1, pos.column);
             assert_eq!(String::from("T"), *ident);
@@ -8581,7 +8641,7 @@ trait T
     }
     assert_eq!(1, tree.defs().len());
     match &*tree.defs()[0] {
-        Def::Trait(ident, trait1, pos) => {
+        Def::Trait(_, ident, trait1, pos) => {
             assert_eq!(1, pos.line);
             assert_eq!(1, pos.column);
             assert_eq!(String::from("T"), *ident);
@@ -8938,7 +8998,7 @@ trait T
     }
     assert_eq!(1, tree.defs().len());
     match &*tree.defs()[0] {
-        Def::Trait(ident, trait1, pos) => {
+        Def::Trait(_, ident, trait1, pos) => {
             assert_eq!(1, pos.line);
             assert_eq!(1, pos.column);
             assert_eq!(String::from("T"), *ident);
@@ -9825,6 +9885,92 @@ impl T for ()
     }
 }
 
+#[test]
+fn test_parser_parse_parses_public_definitions()
+{
+    let s = "
+pub data T = pub C() | D();
+pub type U = Int;
+pub f(x: Int) -> Int = x;
+g: Int = 1;
+pub trait V {};
+";
+    let s2 = &s[1..];
+    let mut cursor = Cursor::new(s2.as_bytes());
+    let mut parser = Parser::new(Lexer::new(String::from("test.vscfl"), &mut cursor));
+    let mut tree = Tree::new();
+    match parser.parse(&mut tree) {
+        Ok(()) => assert!(true),
+        Err(_) => assert!(false),
+    }
+    assert_eq!(5, tree.defs().len());
+    match &*tree.defs()[0] {
+        Def::Type(Visibility::Public, ident, type_var, pos) => {
+            assert_eq!(1, pos.line);
+            assert_eq!(1, pos.column);
+            assert_eq!(String::from("T"), *ident);
+            let type_var_r = type_var.borrow();
+            match &*type_var_r {
+                TypeVar::Data(_, cons, None) => {
+                    assert_eq!(2, cons.len());
+                    let con1_r = cons[0].borrow();
+                    match &*con1_r {
+                        Con::UnnamedField(Visibility::Public, con_ident, _, _, pos) => {
+                            assert_eq!(1, pos.line);
+                            assert_eq!(18, pos.column);
+                            assert_eq!(String::from("C"), *con_ident);
+                        },
+                        _ => assert!(false),
+                    }
+                    let con2_r = cons[1].borrow();
+                    match &*con2_r {
+                        Con::UnnamedField(Visibility::Private, con_ident, _, _, pos) => {
+                            assert_eq!(1, pos.line);
+                            assert_eq!(24, pos.column);
+                            assert_eq!(String::from("D"), *con_ident);
+                        },
+                        _ => assert!(false),
+                    }
+                },
+                _ => assert!(false),
+            }
+        },
+        _ => assert!(false),
+    }
+    match &*tree.defs()[1] {
+        Def::Type(Visibility::Public, ident, _, pos) => {
+            assert_eq!(2, pos.line);
+            assert_eq!(1, pos.column);
+            assert_eq!(String::from("U"), *ident);
+        },
+        _ => assert!(false),
+    }
+    match &*tree.defs()[2] {
+        Def::Var(Visibility::Public, ident, _, pos) => {
+            assert_eq!(3, pos.line);
+            assert_eq!(1, pos.column);
+            assert_eq!(String::from("f"), *ident);
+        },
+        _ => assert!(false),
+    }
+    match &*tree.defs()[3] {
+        Def::Var(Visibility::Private, ident, _, pos) => {
+            assert_eq!(4, pos.line);
+            assert_eq!(1, pos.column);
+            assert_eq!(String::from("g"), *ident);
+        },
+        _ => assert!(false),
+    }
+    match &*tree.defs()[4] {
+        Def::Trait(Visibility::Public, ident, _, pos) => {
+            assert_eq!(5, pos.line);
+            assert_eq!(1, pos.column);
+            assert_eq!(String::from("V"), *ident);
+        },
+        _ => assert!(false),
+    }
+}
+
 #[test]
 fn test_parser_parse_complains_on_unexpected_token_for_data_definition()
 {
@@ -10567,6 +10713,26 @@ kernel inline
     }
 }
 
+#[test]
+fn test_parser_parse_complains_on_recursion_depth_must_be_greater_than_zero()
+{
+    let s = "
+recursive(0) f(x: Int) -> Int = x;
+";
+    let s2 = &s[1..];
+    let mut cursor = Cursor::new(s2.as_bytes());
+    let mut parser = Parser::new(Lexer::new(String::from("test.vscfl"), &mut cursor));
+    let mut tree = Tree::new();
+    match parser.parse(&mut tree) {
+        Err(FrontendError::Message(pos, msg)) => {
+            assert_eq!(1, pos.line);
+            assert_eq!(11, pos.column);
+            assert_eq!(String::from("recursion depth must be greater than zero"), msg);
+        },
+        _ => assert!(false),
+    }
+}
+
 #[test]
 fn test_parser_parse_complains_on_built_in_type_must_not_have_variable_modifier()
 {
@@ -10847,6 +11013,26 @@ kernel impl T for U {};
     }
 }
 
+#[test]
+fn test_parser_parse_complains_on_implementation_must_not_have_visibility_modifier()
+{
+    let s = "
+pub impl T for U {};
+";
+    let s2 = &s[1..];
+    let mut cursor = Cursor::new(s2.as_bytes());
+    let mut parser = Parser::new(Lexer::new(String::from("test.vscfl"), &mut cursor));
+    let mut tree = Tree::new();
+    match parser.parse(&mut tree) {
+        Err(FrontendError::Message(pos, msg)) => {
+            assert_eq!(1, pos.line);
+            assert_eq!(1, pos.column);
+            assert_eq!(String::from("implementation mustn't have visibility modifier"), msg);
+        },
+        _ => assert!(false),
+    }
+}
+
 #[test]
 fn test_parser_parse_type_args_parses_type_arguments()
 {
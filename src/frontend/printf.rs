@@ -0,0 +1,242 @@
+//
+// Copyright (c) 2025 Łukasz Szpakowski
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+use std::result;
+use crate::frontend::tree::*;
+
+const PRINTF_SCALAR_TYPE_IDENTS: [&str; 10] = ["Char", "Short", "Int", "Long", "Uchar", "Ushort", "Uint", "Ulong", "Float", "Double"];
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum PrintfLength
+{
+    None,
+    Hh,
+    H,
+    Hl,
+    L,
+}
+
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct PrintfConv
+{
+    pub spec: String,
+    pub flags: String,
+    pub width: Option<usize>,
+    pub precision: Option<usize>,
+    pub vector_len: Option<usize>,
+    pub length: PrintfLength,
+    pub conv: char,
+}
+
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub enum PrintfItem
+{
+    Bytes(Vec<u8>),
+    Conv(PrintfConv),
+}
+
+fn parse_printf_usize(bs: &[u8], i: &mut usize) -> Option<usize>
+{
+    let start = *i;
+    while *i < bs.len() && bs[*i].is_ascii_digit() {
+        *i += 1;
+    }
+    if start < *i {
+        String::from_utf8_lossy(&bs[start..*i]).parse::<usize>().ok()
+    } else {
+        None
+    }
+}
+
+pub fn parse_printf_format(bs: &[u8]) -> result::Result<Vec<PrintfItem>, String>
+{
+    let mut items: Vec<PrintfItem> = Vec::new();
+    let mut text: Vec<u8> = Vec::new();
+    let mut i = 0usize;
+    while i < bs.len() {
+        if bs[i] != b'%' {
+            text.push(bs[i]);
+            i += 1;
+            continue;
+        }
+        let start = i;
+        i += 1;
+        if i < bs.len() && bs[i] == b'%' {
+            text.extend_from_slice(b"%%");
+            i += 1;
+            continue;
+        }
+        let mut flags = String::new();
+        while i < bs.len() && b"-+ #0".contains(&bs[i]) {
+            flags.push(bs[i] as char);
+            i += 1;
+        }
+        let width = parse_printf_usize(bs, &mut i);
+        let mut precision: Option<usize> = None;
+        if i < bs.len() && bs[i] == b'.' {
+            i += 1;
+            precision = Some(parse_printf_usize(bs, &mut i).unwrap_or(0));
+        }
+        let mut vector_len: Option<usize> = None;
+        if i < bs.len() && bs[i] == b'v' {
+            i += 1;
+            match parse_printf_usize(bs, &mut i) {
+                Some(n) if [2, 3, 4, 8, 16].contains(&n) => vector_len = Some(n),
+                _ => return Err(String::from("invalid vector size in printf format")),
+            }
+        }
+        let length = if bs[i..].starts_with(b"hh") {
+            i += 2;
+            PrintfLength::Hh
+        } else if bs[i..].starts_with(b"hl") {
+            i += 2;
+            PrintfLength::Hl
+        } else if bs[i..].starts_with(b"h") {
+            i += 1;
+            PrintfLength::H
+        } else if bs[i..].starts_with(b"l") {
+            i += 1;
+            PrintfLength::L
+        } else {
+            PrintfLength::None
+        };
+        if i >= bs.len() {
+            return Err(String::from("unterminated conversion specification in printf format"));
+        }
+        let conv = bs[i] as char;
+        i += 1;
+        if !"diouxXcsfFeEgGaA".contains(conv) {
+            return Err(format!("invalid conversion specifier {} in printf format", conv));
+        }
+        if length == PrintfLength::Hl && vector_len.is_none() {
+            return Err(String::from("length modifier hl without vector size in printf format"));
+        }
+        if !text.is_empty() {
+            items.push(PrintfItem::Bytes(text));
+            text = Vec::new();
+        }
+        items.push(PrintfItem::Conv(PrintfConv {
+                    spec: String::from_utf8_lossy(&bs[start..i]).into_owned(),
+                    flags,
+                    width,
+                    precision,
+                    vector_len,
+                    length,
+                    conv,
+        }));
+    }
+    if !text.is_empty() {
+        items.push(PrintfItem::Bytes(text));
+    }
+    Ok(items)
+}
+
+pub fn printf_type_parts(ident: &str) -> Option<(&'static str, Option<usize>)>
+{
+    for scalar_ident in PRINTF_SCALAR_TYPE_IDENTS {
+        if ident == scalar_ident {
+            return Some((scalar_ident, None));
+        }
+        if let Some(suffix) = ident.strip_prefix(scalar_ident) {
+            if let Ok(n) = suffix.parse::<usize>() {
+                return Some((scalar_ident, Some(n)));
+            }
+        }
+    }
+    None
+}
+
+fn printf_length_for_type(scalar_ident: &str, vector_len: Option<usize>, conv: char) -> PrintfLength
+{
+    match (scalar_ident, conv) {
+        ("Char" | "Uchar", 'c') => PrintfLength::None,
+        ("Char" | "Uchar", _) => PrintfLength::Hh,
+        ("Short" | "Ushort", _) => PrintfLength::H,
+        ("Long" | "Ulong", _) => PrintfLength::L,
+        ("Double", _) if vector_len.is_some() => PrintfLength::L,
+        ("Int" | "Uint" | "Float", _) if vector_len.is_some() => PrintfLength::Hl,
+        _ => PrintfLength::None,
+    }
+}
+
+pub fn printf_conv_matches_type(conv: &PrintfConv, type_ident: &str, elem_type_ident: Option<&str>) -> bool
+{
+    if conv.conv == 's' {
+        return conv.vector_len.is_none() && conv.length == PrintfLength::None && type_ident == "ConstantSlice" && elem_type_ident == Some("Char");
+    }
+    match printf_type_parts(type_ident) {
+        Some((scalar_ident, vector_len)) => {
+            if vector_len != conv.vector_len {
+                return false;
+            }
+            let is_conv_matched = match conv.conv {
+                'd' | 'i' | 'o' | 'u' | 'x' | 'X' => scalar_ident != "Float" && scalar_ident != "Double",
+                'c' => vector_len.is_none() && (scalar_ident == "Char" || scalar_ident == "Uchar"),
+                _ => scalar_ident == "Float" || scalar_ident == "Double",
+            };
+            let length = printf_length_for_type(scalar_ident, vector_len, conv.conv);
+            let is_double_l = scalar_ident == "Double" && conv.length == PrintfLength::L;
+            is_conv_matched && (conv.length == PrintfLength::None || conv.length == length || is_double_l)
+        },
+        None => false,
+    }
+}
+
+pub fn is_string_literal_expr(expr: &Expr) -> bool
+{
+    match expr {
+        Expr::Literal(literal, _, _) => {
+            matches!(&**literal, Literal::String(_))
+        },
+        _ => false,
+    }
+}
+
+pub fn is_string_literal_var(ident: &String, tree: &Tree) -> bool
+{
+    match tree.var(ident) {
+        Some(var) => {
+            let var_r = var.borrow();
+            match &*var_r {
+                Var::Var(_, _, _, Some(expr), None, _, _, _, _) => is_string_literal_expr(expr),
+                _ => false,
+            }
+        },
+        None => false,
+    }
+}
+
+pub fn printf_conv_to_string(conv: &PrintfConv, type_ident: &str) -> String
+{
+    let mut s = String::from("%");
+    s.push_str(conv.flags.as_str());
+    if let Some(width) = conv.width {
+        s.push_str(width.to_string().as_str());
+    }
+    if let Some(precision) = conv.precision {
+        s.push_str(format!(".{}", precision).as_str());
+    }
+    if let Some(n) = conv.vector_len {
+        s.push_str(format!("v{}", n).as_str());
+    }
+    let length = match printf_type_parts(type_ident) {
+        Some((scalar_ident, vector_len)) => printf_length_for_type(scalar_ident, vector_len, conv.conv),
+        None => PrintfLength::None,
+    };
+    match length {
+        PrintfLength::None => (),
+        PrintfLength::Hh => s.push_str("hh"),
+        PrintfLength::H => s.push('h'),
+        PrintfLength::Hl => s.push_str("hl"),
+        PrintfLength::L => s.push('l'),
+    }
+    s.push(conv.conv);
+    s
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,142 @@
+//
+// Copyright (c) 2025 Łukasz Szpakowski
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+use super::*;
+
+#[test]
+fn test_parse_printf_format_parses_format()
+{
+    match parse_printf_format(b"x=%-5d %.2v4f 100%%\n") {
+        Ok(items) => {
+            assert_eq!(5, items.len());
+            assert_eq!(PrintfItem::Bytes(b"x=".to_vec()), items[0]);
+            match &items[1] {
+                PrintfItem::Conv(conv) => {
+                    assert_eq!(String::from("%-5d"), conv.spec);
+                    assert_eq!(String::from("-"), conv.flags);
+                    assert_eq!(Some(5), conv.width);
+                    assert_eq!(None, conv.precision);
+                    assert_eq!(None, conv.vector_len);
+                    assert_eq!(PrintfLength::None, conv.length);
+                    assert_eq!('d', conv.conv);
+                },
+                _ => assert!(false),
+            }
+            assert_eq!(PrintfItem::Bytes(b" ".to_vec()), items[2]);
+            match &items[3] {
+                PrintfItem::Conv(conv) => {
+                    assert_eq!(String::from("%.2v4f"), conv.spec);
+                    assert_eq!(Some(2), conv.precision);
+                    assert_eq!(Some(4), conv.vector_len);
+                    assert_eq!('f', conv.conv);
+                },
+                _ => assert!(false),
+            }
+            assert_eq!(PrintfItem::Bytes(b" 100%%\n".to_vec()), items[4]);
+        },
+        Err(_) => assert!(false),
+    }
+}
+
+#[test]
+fn test_parse_printf_format_complains_on_invalid_formats()
+{
+    assert_eq!(Err(String::from("unterminated conversion specification in printf format")), parse_printf_format(b"%5"));
+    assert_eq!(Err(String::from("invalid conversion specifier p in printf format")), parse_printf_format(b"%p"));
+    assert_eq!(Err(String::from("invalid vector size in printf format")), parse_printf_format(b"%v5d"));
+    assert_eq!(Err(String::from("length modifier hl without vector size in printf format")), parse_printf_format(b"%hld"));
+}
+
+#[test]
+fn test_printf_conv_matches_type_checks_types()
+{
+    let convs: Vec<PrintfConv> = match parse_printf_format(b"%d%ld%hhx%c%f%v4f%v4hlf%s%lf") {
+        Ok(items) => {
+            items.into_iter().filter_map(|item| {
+                    match item {
+                        PrintfItem::Conv(conv) => Some(conv),
+                        _ => None,
+                    }
+            }).collect()
+        },
+        Err(_) => {
+            assert!(false);
+            Vec::new()
+        },
+    };
+    assert!(printf_conv_matches_type(&convs[0], "Int", None));
+    assert!(printf_conv_matches_type(&convs[0], "Long", None));
+    assert!(!printf_conv_matches_type(&convs[0], "Float", None));
+    assert!(!printf_conv_matches_type(&convs[0], "Int4", None));
+    assert!(printf_conv_matches_type(&convs[1], "Long", None));
+    assert!(!printf_conv_matches_type(&convs[1], "Int", None));
+    assert!(printf_conv_matches_type(&convs[2], "Uchar", None));
+    assert!(printf_conv_matches_type(&convs[3], "Char", None));
+    assert!(!printf_conv_matches_type(&convs[3], "Int", None));
+    assert!(printf_conv_matches_type(&convs[4], "Double", None));
+    assert!(printf_conv_matches_type(&convs[5], "Float4", None));
+    assert!(!printf_conv_matches_type(&convs[5], "Float8", None));
+    assert!(printf_conv_matches_type(&convs[6], "Float4", None));
+    assert!(!printf_conv_matches_type(&convs[6], "Double4", None));
+    assert!(printf_conv_matches_type(&convs[7], "ConstantSlice", Some("Char")));
+    assert!(!printf_conv_matches_type(&convs[7], "ConstantSlice", Some("Int")));
+    assert!(!printf_conv_matches_type(&convs[7], "PrivateSlice", Some("Char")));
+    assert!(!printf_conv_matches_type(&convs[7], "GlobalSlice", Some("Char")));
+    assert!(printf_conv_matches_type(&convs[8], "Double", None));
+    assert!(!printf_conv_matches_type(&convs[8], "Double2", None));
+    assert!(!printf_conv_matches_type(&convs[8], "Float", None));
+}
+
+#[test]
+fn test_printf_conv_to_string_converts_conversion_specifications()
+{
+    let convs: Vec<PrintfConv> = match parse_printf_format(b"%+5d%.3v2f%c%x%v8d") {
+        Ok(items) => {
+            items.into_iter().filter_map(|item| {
+                    match item {
+                        PrintfItem::Conv(conv) => Some(conv),
+                        _ => None,
+                    }
+            }).collect()
+        },
+        Err(_) => {
+            assert!(false);
+            Vec::new()
+        },
+    };
+    assert_eq!(String::from("%+5ld"), printf_conv_to_string(&convs[0], "Long"));
+    assert_eq!(String::from("%.3v2hlf"), printf_conv_to_string(&convs[1], "Float2"));
+    assert_eq!(String::from("%.3v2lf"), printf_conv_to_string(&convs[1], "Double2"));
+    assert_eq!(String::from("%c"), printf_conv_to_string(&convs[2], "Char"));
+    assert_eq!(String::from("%hx"), printf_conv_to_string(&convs[3], "Ushort"));
+    assert_eq!(String::from("%v8hhd"), printf_conv_to_string(&convs[4], "Char8"));
+}
+
+#[test]
+fn test_printf_conv_to_string_adds_length_modifiers_for_argument_types()
+{
+    let convs: Vec<PrintfConv> = match parse_printf_format(b"%d%u%lf") {
+        Ok(items) => {
+            items.into_iter().filter_map(|item| {
+                    match item {
+                        PrintfItem::Conv(conv) => Some(conv),
+                        _ => None,
+                    }
+            }).collect()
+        },
+        Err(_) => {
+            assert!(false);
+            Vec::new()
+        },
+    };
+    assert!(printf_conv_matches_type(&convs[0], "Long", None));
+    assert_eq!(String::from("%ld"), printf_conv_to_string(&convs[0], "Long"));
+    assert!(printf_conv_matches_type(&convs[1], "Uchar", None));
+    assert_eq!(String::from("%hhu"), printf_conv_to_string(&convs[1], "Uchar"));
+    assert!(printf_conv_matches_type(&convs[2], "Double", None));
+    assert_eq!(String::from("%f"), printf_conv_to_string(&convs[2], "Double"));
+}
@@ -40,7 +40,7 @@ fn add_fun_key(ident: &String, type_name: &Option<TypeName>, pos: Pos, tree: &Tr
                                     match &*trait_r {
                                         Trait(_, _, Some(trait_vars)) => {
                                             let type_name2 = match type_name {
-                                                TypeName::Array(Some(_)) if trait_vars.impl1(&type_name).is_none() => TypeName::Array(None),
+                                                TypeName::Array(Some(_)) if trait_vars.impl1(type_name).is_none() => TypeName::Array(None),
                                                 _ => type_name.clone(),
                                             };
                                             match trait_vars.impl1(&type_name2) {
@@ -128,7 +128,7 @@ fn do_fun_for_fun_key<T, F>(key: &(String, Option<TypeName>), tree: &Tree, z: T,
                                     let trait_r = trait1.borrow();
                                     match &*trait_r {
                                         Trait(_, _, Some(trait_vars)) => {
-                                            match trait_vars.impl1(&type_name) {
+                                            match trait_vars.impl1(type_name) {
                                                 Some(impl1) => {
                                                     let impl_r = impl1.borrow();
                                                     let impl_vars = match &*impl_r {
@@ -148,7 +148,7 @@ fn do_fun_for_fun_key<T, F>(key: &(String, Option<TypeName>), tree: &Tree, z: T,
                                                                                         ImplArg(ident, _, _) => ident.clone(),
                                                                                     }
                                                                             }).collect();
-                                                                            f(arg_idents2.as_slice(), &**body2, &**local_types2, &**type2)
+                                                                            f(arg_idents2.as_slice(), body2, local_types2, type2)
                                                                         },
                                                                         _ => Err(FrontendErrors::new(vec![FrontendError::Internal(String::from("do_fun_for_fun_key: no local types no type"))])),
                                                                     }
@@ -172,7 +172,7 @@ fn do_fun_for_fun_key<T, F>(key: &(String, Option<TypeName>), tree: &Tree, z: T,
                             match fun_tuple {
                                 Some((arg_idents, body, Some(local_types), typ)) => {
                                     match body {
-                                        Some(body) => f(arg_idents.as_slice(), &**body, &**local_types, &**typ),
+                                        Some(body) => f(arg_idents.as_slice(), body, local_types, typ),
                                         None => Ok(z),
                                     }
                                 },
@@ -186,7 +186,7 @@ fn do_fun_for_fun_key<T, F>(key: &(String, Option<TypeName>), tree: &Tree, z: T,
                     match fun_tuple {
                         Some((arg_idents, body, Some(local_types), typ)) => {
                             match body {
-                                Some(body) => f(arg_idents.as_slice(), &**body, &**local_types, &**typ),
+                                Some(body) => f(arg_idents.as_slice(), body, local_types, typ),
                                 None => Ok(z),
                             }
                         },
@@ -200,6 +200,24 @@ fn do_fun_for_fun_key<T, F>(key: &(String, Option<TypeName>), tree: &Tree, z: T,
     }
 }
 
+fn is_bounded_recursive_fun_key(key: &(String, Option<TypeName>), tree: &Tree) -> bool
+{
+    if key.1.is_some() {
+        return false;
+    }
+    match tree.var(&key.0) {
+        Some(var) => {
+            match &*var.borrow() {
+                Var::Fun(fun, _, _) => {
+                    matches!(&**fun, Fun::Fun(FunModifier::Recursive(_), _, _, _, _, _, _))
+                },
+                _ => false,
+            }
+        },
+        None => false,
+    }
+}
+
 pub struct Recurser
 {}
 
@@ -224,8 +242,8 @@ impl Recurser
         let mut visited_keys: BTreeSet<(String, Option<TypeName>)> = BTreeSet::new();
         for def in tree.defs() {
             match &**def {
-                Def::Var(ident, var, _) => self.check_recursions_for_fun(ident, var, tree, &mut visited_keys, errs)?,
-                Def::Trait(_, trait1, _) => {
+                Def::Var(_, ident, var, _) => self.check_recursions_for_fun(ident, var, tree, &mut visited_keys, errs)?,
+                Def::Trait(_, _, trait1, _) => {
                     let trait_r = trait1.borrow();
                     match &*trait_r {
                         Trait(_, trait_defs, _) => {
@@ -260,10 +278,7 @@ impl Recurser
     {
         let is_fun = {
             let var_r = var.borrow();
-            match &*var_r {
-                Var::Fun(_, _, _) => true,
-                _ => false,
-            }
+            matches!(&*var_r, Var::Fun(_, _, _))
         };
         if is_fun {
             self.check_recursions_for_fun_key(&(ident.clone(), None), tree, visited_keys, errs)?;
@@ -280,6 +295,14 @@ impl Recurser
     
     fn check_recursions_for_fun_key2(&self, key: &(String, Option<TypeName>), tree: &Tree, processed_keys: &BTreeSet<(String, Option<TypeName>)>, errs: &mut Vec<FrontendError>) -> FrontendResultWithErrors<Vec<(String, Option<TypeName>)>>
     {
+        let bounded_processed_keys = if is_bounded_recursive_fun_key(key, tree) {
+            let mut tmp_processed_keys = processed_keys.clone();
+            tmp_processed_keys.remove(key);
+            Some(tmp_processed_keys)
+        } else {
+            None
+        };
+        let processed_keys = bounded_processed_keys.as_ref().unwrap_or(processed_keys);
         do_fun_for_fun_key(key, tree, Vec::new(), |arg_idents, body, local_types, typ| {
                 let mut keys: Vec<(String, Option<TypeName>)> = Vec::new();
                 let mut type_stack = TypeStack::new();
@@ -297,17 +320,17 @@ impl Recurser
     fn add_fun_keys_for_expr(&self, expr: &Expr, tree: &Tree, var_env: &mut Environment<()>, type_stack: &mut TypeStack, local_types: &LocalTypes, keys: &mut Vec<(String, Option<TypeName>)>, processed_keys: &BTreeSet<(String, Option<TypeName>)>, rec_key: Option<&(String, Option<TypeName>)>, errs: &mut Vec<FrontendError>) -> FrontendResultWithErrors<()>
     {
         match expr {
-            Expr::Literal(literal, _, _) => self.add_fun_keys_for_expr_literal(&**literal, tree, var_env, type_stack, local_types, keys, processed_keys, errs)?,
+            Expr::Literal(literal, _, _) => self.add_fun_keys_for_expr_literal(literal, tree, var_env, type_stack, local_types, keys, processed_keys, errs)?,
             Expr::Lambda(args, _, body, _, _, _, _, _) => {
                 var_env.push_new_vars();
-                for arg in &*args {
+                for arg in args {
                     match arg {
                         LambdaArg(ident, _, _, _) => {
                             var_env.add_var(ident.clone(), ());
                         },
                     }
                 }
-                self.add_fun_keys_for_expr(&**body, tree, var_env, type_stack, local_types, keys, processed_keys, None, errs)?;
+                self.add_fun_keys_for_expr(body, tree, var_env, type_stack, local_types, keys, processed_keys, None, errs)?;
                 var_env.pop_vars();
             },
             Expr::Var(ident, Some(local_type), pos) => {
@@ -319,13 +342,13 @@ impl Recurser
             Expr::NamedFieldConApp(_, expr_named_field_pairs, _, _, _) => {
                 for expr_named_field_pair in expr_named_field_pairs {
                     match expr_named_field_pair {
-                        NamedFieldPair(_, expr2, _) => self.add_fun_keys_for_expr(&**expr2, tree, var_env, type_stack, local_types, keys, processed_keys, None, errs)?,
+                        NamedFieldPair(_, expr2, _) => self.add_fun_keys_for_expr(expr2, tree, var_env, type_stack, local_types, keys, processed_keys, None, errs)?,
                     }
                 }
             },
             Expr::PrintfApp(exprs, _, _) => {
                 for expr2 in exprs {
-                    self.add_fun_keys_for_expr(&**expr2, tree, var_env, type_stack, local_types, keys, processed_keys, None, errs)?;
+                    self.add_fun_keys_for_expr(expr2, tree, var_env, type_stack, local_types, keys, processed_keys, None, errs)?;
                 }
             },
             Expr::App(expr2, exprs, _, _) => {
@@ -338,59 +361,59 @@ impl Recurser
                                     add_fun_key(ident, &type_name, pos.clone(), tree, keys, processed_keys, Some(rec_key), errs)?;
                                 }
                             },
-                            _ => self.add_fun_keys_for_expr(&**expr2, tree, var_env, type_stack, local_types, keys, processed_keys, None, errs)?,
+                            _ => self.add_fun_keys_for_expr(expr2, tree, var_env, type_stack, local_types, keys, processed_keys, None, errs)?,
                         }
                     },
-                    None => self.add_fun_keys_for_expr(&**expr2, tree, var_env, type_stack, local_types, keys, processed_keys, None, errs)?,
+                    None => self.add_fun_keys_for_expr(expr2, tree, var_env, type_stack, local_types, keys, processed_keys, None, errs)?,
                 }
                 for expr3 in exprs {
-                    self.add_fun_keys_for_expr(&**expr3, tree, var_env, type_stack, local_types, keys, processed_keys, None, errs)?;
+                    self.add_fun_keys_for_expr(expr3, tree, var_env, type_stack, local_types, keys, processed_keys, None, errs)?;
                 }
             },
-            Expr::GetField(expr2, _, _, _) => self.add_fun_keys_for_expr(&**expr2, tree, var_env, type_stack, local_types, keys, processed_keys, None, errs)?,
-            Expr::Get2Field(expr2, _, _, _) => self.add_fun_keys_for_expr(&**expr2, tree, var_env, type_stack, local_types, keys, processed_keys, None, errs)?,
+            Expr::GetField(expr2, _, _, _) => self.add_fun_keys_for_expr(expr2, tree, var_env, type_stack, local_types, keys, processed_keys, None, errs)?,
+            Expr::Get2Field(expr2, _, _, _) => self.add_fun_keys_for_expr(expr2, tree, var_env, type_stack, local_types, keys, processed_keys, None, errs)?,
             Expr::SetField(expr2, _, expr3, _, _) => {
-                self.add_fun_keys_for_expr(&**expr2, tree, var_env, type_stack, local_types, keys, processed_keys, None, errs)?;
-                self.add_fun_keys_for_expr(&**expr3, tree, var_env, type_stack, local_types, keys, processed_keys, None, errs)?;
+                self.add_fun_keys_for_expr(expr2, tree, var_env, type_stack, local_types, keys, processed_keys, None, errs)?;
+                self.add_fun_keys_for_expr(expr3, tree, var_env, type_stack, local_types, keys, processed_keys, None, errs)?;
             },
             Expr::UpdateField(expr2, _, expr3, _, _) => {
-                self.add_fun_keys_for_expr(&**expr2, tree, var_env, type_stack, local_types, keys, processed_keys, None, errs)?;
-                self.add_fun_keys_for_expr(&**expr3, tree, var_env, type_stack, local_types, keys, processed_keys, None, errs)?;
+                self.add_fun_keys_for_expr(expr2, tree, var_env, type_stack, local_types, keys, processed_keys, None, errs)?;
+                self.add_fun_keys_for_expr(expr3, tree, var_env, type_stack, local_types, keys, processed_keys, None, errs)?;
             },
             Expr::UpdateGet2Field(expr2, _, expr3, _, _) => {
-                self.add_fun_keys_for_expr(&**expr2, tree, var_env, type_stack, local_types, keys, processed_keys, None, errs)?;
-                self.add_fun_keys_for_expr(&**expr3, tree, var_env, type_stack, local_types, keys, processed_keys, None, errs)?;
+                self.add_fun_keys_for_expr(expr2, tree, var_env, type_stack, local_types, keys, processed_keys, None, errs)?;
+                self.add_fun_keys_for_expr(expr3, tree, var_env, type_stack, local_types, keys, processed_keys, None, errs)?;
             },
-            Expr::Uniq(expr2, _, _) => self.add_fun_keys_for_expr(&**expr2, tree, var_env, type_stack, local_types, keys, processed_keys, None, errs)?,
-            Expr::Shared(expr2, _, _) => self.add_fun_keys_for_expr(&**expr2, tree, var_env, type_stack, local_types, keys, processed_keys, None, errs)?,
-            Expr::Typed(expr2, _, _, _) => self.add_fun_keys_for_expr(&**expr2, tree, var_env, type_stack, local_types, keys, processed_keys, rec_key, errs)?,
-            Expr::As(expr2, _, _, _) => self.add_fun_keys_for_expr(&**expr2, tree, var_env, type_stack, local_types, keys, processed_keys, None, errs)?,
+            Expr::Uniq(expr2, _, _) => self.add_fun_keys_for_expr(expr2, tree, var_env, type_stack, local_types, keys, processed_keys, None, errs)?,
+            Expr::Shared(expr2, _, _) => self.add_fun_keys_for_expr(expr2, tree, var_env, type_stack, local_types, keys, processed_keys, None, errs)?,
+            Expr::Typed(expr2, _, _, _) => self.add_fun_keys_for_expr(expr2, tree, var_env, type_stack, local_types, keys, processed_keys, rec_key, errs)?,
+            Expr::As(expr2, _, _, _) => self.add_fun_keys_for_expr(expr2, tree, var_env, type_stack, local_types, keys, processed_keys, None, errs)?,
             Expr::If(expr2, expr3, expr4, _, _) => {
-                self.add_fun_keys_for_expr(&**expr2, tree, var_env, type_stack, local_types, keys, processed_keys, None, errs)?;
-                self.add_fun_keys_for_expr(&**expr3, tree, var_env, type_stack, local_types, keys, processed_keys, rec_key, errs)?;
-                self.add_fun_keys_for_expr(&**expr4, tree, var_env, type_stack, local_types, keys, processed_keys, rec_key, errs)?;
+                self.add_fun_keys_for_expr(expr2, tree, var_env, type_stack, local_types, keys, processed_keys, None, errs)?;
+                self.add_fun_keys_for_expr(expr3, tree, var_env, type_stack, local_types, keys, processed_keys, rec_key, errs)?;
+                self.add_fun_keys_for_expr(expr4, tree, var_env, type_stack, local_types, keys, processed_keys, rec_key, errs)?;
             },
             Expr::Let(binds, expr2, _, _) => {
                 var_env.push_new_vars();
                 for bind in binds {
                     match bind {
                         Bind(pattern, expr3) => {
-                            self.add_fun_keys_for_expr(&**expr3, tree, var_env, type_stack, local_types, keys, processed_keys, None, errs)?;
-                            self.add_vars_for_pattern(&**pattern, var_env);
+                            self.add_fun_keys_for_expr(expr3, tree, var_env, type_stack, local_types, keys, processed_keys, None, errs)?;
+                            self.add_vars_for_pattern(pattern, var_env);
                         },
                     }
                 }
-                self.add_fun_keys_for_expr(&**expr2, tree, var_env, type_stack, local_types, keys, processed_keys, rec_key, errs)?;
+                self.add_fun_keys_for_expr(expr2, tree, var_env, type_stack, local_types, keys, processed_keys, rec_key, errs)?;
                 var_env.pop_vars();
             },
             Expr::Match(expr2, cases, _, _) => {
-                self.add_fun_keys_for_expr(&**expr2, tree, var_env, type_stack, local_types, keys, processed_keys, None, errs)?;
+                self.add_fun_keys_for_expr(expr2, tree, var_env, type_stack, local_types, keys, processed_keys, None, errs)?;
                 for case in cases {
                     match case {
                         Case(pattern, expr3) => {
                             var_env.push_new_vars();
-                            self.add_vars_for_pattern(&**pattern, var_env);
-                            self.add_fun_keys_for_expr(&**expr3, tree, var_env, type_stack, local_types, keys, processed_keys, rec_key, errs)?;
+                            self.add_vars_for_pattern(pattern, var_env);
+                            self.add_fun_keys_for_expr(expr3, tree, var_env, type_stack, local_types, keys, processed_keys, rec_key, errs)?;
                             var_env.pop_vars();
                         },
                     }
@@ -404,7 +427,7 @@ impl Recurser
     fn add_vars_for_pattern(&self, pattern: &Pattern, var_env: &mut Environment<()>)
     {
         match pattern {
-            Pattern::Literal(literal, _, _) => self.add_vars_for_pattern_literal(&**literal, var_env),
+            Pattern::Literal(literal, _, _) => self.add_vars_for_pattern_literal(literal, var_env),
             Pattern::As(_, _, _, _, _) => (),
             Pattern::Const(_, _, _) => (),
             Pattern::UnnamedFieldCon(_, patterns, _, _, _) => {
@@ -436,15 +459,15 @@ impl Recurser
         match literal {
             Literal::Tuple(field_exprs) => {
                 for field_expr in field_exprs {
-                    self.add_fun_keys_for_expr(&**field_expr, tree, var_env, type_stack, local_types, keys, processed_keys, None, errs)?;
+                    self.add_fun_keys_for_expr(field_expr, tree, var_env, type_stack, local_types, keys, processed_keys, None, errs)?;
                 }
             },
             Literal::Array(elem_exprs) => {
                 for elem_expr in elem_exprs {
-                    self.add_fun_keys_for_expr(&**elem_expr, tree, var_env, type_stack, local_types, keys, processed_keys, None, errs)?;
+                    self.add_fun_keys_for_expr(elem_expr, tree, var_env, type_stack, local_types, keys, processed_keys, None, errs)?;
                 }
             },
-            Literal::FilledArray(elem_expr, _) => self.add_fun_keys_for_expr(&**elem_expr, tree, var_env, type_stack, local_types, keys, processed_keys, None, errs)?,
+            Literal::FilledArray(elem_expr, _) => self.add_fun_keys_for_expr(elem_expr, tree, var_env, type_stack, local_types, keys, processed_keys, None, errs)?,
             _ => (),
         }
         Ok(())
@@ -472,10 +495,7 @@ impl Recurser
     {
         let is_impl_fun = {
             let impl_var_r = impl_var.borrow();
-            match &*impl_var_r {
-                ImplVar::Fun(_, _) => true,
-                _ => false,
-            }
+            matches!(&*impl_var_r, ImplVar::Fun(_, _))
         };
         if is_impl_fun {
             self.check_recursions_for_fun_key(&(ident.clone(), Some(type_name.clone())), tree, visited_keys, errs)?;
@@ -0,0 +1,881 @@
+//
+// Copyright (c) 2025 Łukasz Szpakowski
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::collections::HashMap;
+use std::path::Path;
+use crate::frontend::error::*;
+use crate::frontend::tree::*;
+use crate::utils::env::*;
+
+pub fn module_ident_for_path(path: &str) -> Option<String>
+{
+    let stem = Path::new(path).file_stem()?.to_str()?;
+    let mut chars = stem.chars();
+    match chars.next() {
+        Some(c) if c.is_ascii_lowercase() || c == '_' => (),
+        _ => return None,
+    }
+    if stem != "_" && chars.all(|c| c.is_ascii_alphanumeric() || c == '_') {
+        Some(String::from(stem))
+    } else {
+        None
+    }
+}
+
+pub fn split_qualified_ident(ident: &str) -> Option<(&str, &str)>
+{ ident.split_once("::") }
+
+pub fn qualify_ident(module_ident: &str, ident: &str) -> String
+{ format!("{}::{}", module_ident, ident) }
+
+pub fn unqualified_ident(ident: &str) -> &str
+{
+    match split_qualified_ident(ident) {
+        Some((_, ident2)) => ident2,
+        None => ident,
+    }
+}
+
+pub fn unqualified_type_name(type_name: &TypeName) -> TypeName
+{
+    match type_name {
+        TypeName::Name(ident) => TypeName::Name(String::from(unqualified_ident(ident.as_str()))),
+        _ => type_name.clone(),
+    }
+}
+
+fn module_def_ident(module_ident: &str, ident: &str) -> String
+{
+    if PRELUDE_MODULE_IDENTS.contains(&module_ident) {
+        String::from(ident)
+    } else {
+        qualify_ident(module_ident, ident)
+    }
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+enum Namespace
+{
+    TypeVar,
+    Var,
+    Trait,
+}
+
+#[derive(Clone, Debug)]
+struct Idents
+{
+    type_vars: BTreeMap<String, (Visibility, String)>,
+    vars: BTreeMap<String, (Visibility, String)>,
+    traits: BTreeMap<String, (Visibility, String)>,
+}
+
+impl Idents
+{
+    fn new() -> Self
+    { Idents { type_vars: BTreeMap::new(), vars: BTreeMap::new(), traits: BTreeMap::new(), } }
+
+    fn visibility_and_path(&self, ident: &str, namespace: Namespace) -> Option<&(Visibility, String)>
+    {
+        match namespace {
+            Namespace::TypeVar => self.type_vars.get(ident),
+            Namespace::Var => self.vars.get(ident),
+            Namespace::Trait => self.traits.get(ident),
+        }
+    }
+
+    fn contains(&self, ident: &str, namespace: Namespace) -> bool
+    { self.visibility_and_path(ident, namespace).is_some() }
+
+    fn is_accessible(&self, ident: &str, namespace: Namespace, path: &String) -> bool
+    {
+        match self.visibility_and_path(ident, namespace) {
+            Some((Visibility::Public, _)) => true,
+            Some((Visibility::Private, def_path)) => def_path == path,
+            None => false,
+        }
+    }
+
+    fn add_def(&mut self, def: &Def)
+    {
+        match def {
+            Def::Type(visibility, ident, type_var, pos) => {
+                self.type_vars.insert(ident.clone(), (*visibility, (*pos.path).clone()));
+                let type_var_r = type_var.borrow();
+                if let TypeVar::Data(_, cons, _) = &*type_var_r {
+                    for con in cons {
+                        let con_r = con.borrow();
+                        match &*con_r {
+                            Con::UnnamedField(con_visibility, con_ident, _, _, _) => {
+                                self.vars.insert(con_ident.clone(), (*con_visibility, (*pos.path).clone()));
+                            },
+                            Con::NamedField(con_visibility, con_ident, _, _, _, _) => {
+                                self.vars.insert(con_ident.clone(), (*con_visibility, (*pos.path).clone()));
+                            },
+                        }
+                    }
+                }
+            },
+            Def::Var(visibility, ident, _, pos) => {
+                self.vars.insert(ident.clone(), (*visibility, (*pos.path).clone()));
+            },
+            Def::Trait(visibility, ident, trait1, pos) => {
+                self.traits.insert(ident.clone(), (*visibility, (*pos.path).clone()));
+                let trait_r = trait1.borrow();
+                match &*trait_r {
+                    Trait(_, trait_defs, _) => {
+                        for trait_def in trait_defs {
+                            match &**trait_def {
+                                TraitDef(var_ident, _, _) => {
+                                    self.vars.insert(var_ident.clone(), (*visibility, (*pos.path).clone()));
+                                },
+                            }
+                        }
+                    },
+                }
+            },
+            Def::Impl(_, _) => (),
+        }
+    }
+}
+
+fn namespace_name(namespace: Namespace) -> &'static str
+{
+    match namespace {
+        Namespace::TypeVar => "type variable",
+        Namespace::Var => "variable",
+        Namespace::Trait => "trait",
+    }
+}
+
+fn check_unqualified_ident(ident: &String, pos: &Pos, errs: &mut Vec<FrontendError>)
+{
+    if split_qualified_ident(ident.as_str()).is_some() {
+        errs.push(FrontendError::Message(pos.clone(), format!("unexpected qualified identifier {}", ident)));
+    }
+}
+
+fn check_accessible_ident(idents: &Idents, ident: &str, namespace: Namespace, new_ident: String, path: &String, pos: &Pos, errs: &mut Vec<FrontendError>) -> Option<String>
+{
+    if idents.is_accessible(ident, namespace, path) {
+        Some(new_ident)
+    } else {
+        errs.push(FrontendError::Message(pos.clone(), format!("{} {} is private", namespace_name(namespace), new_ident)));
+        None
+    }
+}
+
+fn add_var_ident(ident: &String, pos: &Pos, var_env: &mut Environment<()>, errs: &mut Vec<FrontendError>)
+{
+    check_unqualified_ident(ident, pos, errs);
+    var_env.add_var(ident.clone(), ());
+}
+
+pub const PRELUDE_MODULE_IDENTS: &[&str] = &["lang"];
+
+pub struct Resolver
+{
+    modules: HashMap<String, Idents>,
+    path_module_idents: HashMap<String, String>,
+    imports: HashMap<String, BTreeSet<String>>,
+    root_idents: Idents,
+}
+
+impl Resolver
+{
+    pub fn new(tree: &Tree) -> Self
+    {
+        let mut path_module_idents: HashMap<String, String> = HashMap::new();
+        let mut modules: HashMap<String, Idents> = HashMap::new();
+        for (module_ident, module) in &tree.modules {
+            match module {
+                Module(_, paths) => {
+                    for path in paths {
+                        path_module_idents.insert(path.clone(), module_ident.clone());
+                    }
+                    modules.insert(module_ident.clone(), Idents::new());
+                },
+            }
+        }
+        let mut imports: HashMap<String, BTreeSet<String>> = HashMap::new();
+        for (path, path_imports) in &tree.imports {
+            imports.insert(path.clone(), path_imports.iter().map(|import| import.0.clone()).collect());
+        }
+        let mut root_idents = Idents::new();
+        for def in &tree.defs {
+            let pos = match &**def {
+                Def::Type(_, _, _, pos) | Def::Var(_, _, _, pos) | Def::Trait(_, _, _, pos) | Def::Impl(_, pos) => pos,
+            };
+            match path_module_idents.get(&*pos.path) {
+                Some(module_ident) => {
+                    if let Some(idents) = modules.get_mut(module_ident) {
+                        idents.add_def(def);
+                    }
+                },
+                None => root_idents.add_def(def),
+            }
+        }
+        Resolver {
+            modules,
+            path_module_idents,
+            imports,
+            root_idents,
+        }
+    }
+
+    fn current_module(&self, path: &String) -> Option<&String>
+    {
+        match self.path_module_idents.get(path) {
+            Some(module_ident) if self.modules.contains_key(module_ident) => Some(module_ident),
+            _ => None,
+        }
+    }
+
+    fn is_visible_module(&self, module_ident: &str, path: &String) -> bool
+    {
+        let is_imported = match self.imports.get(path) {
+            Some(module_idents) => module_idents.contains(module_ident),
+            None => false,
+        };
+        match self.current_module(path) {
+            Some(current_module_ident) if current_module_ident == module_ident => true,
+            _ => is_imported || PRELUDE_MODULE_IDENTS.contains(&module_ident),
+        }
+    }
+
+    fn visible_module_idents(&self, path: &String) -> BTreeSet<&str>
+    {
+        let mut module_idents: BTreeSet<&str> = BTreeSet::new();
+        for module_ident in PRELUDE_MODULE_IDENTS {
+            if self.modules.contains_key(*module_ident) {
+                module_idents.insert(module_ident);
+            }
+        }
+        if let Some(imported_module_idents) = self.imports.get(path) {
+            for module_ident in imported_module_idents {
+                if self.modules.contains_key(module_ident) {
+                    module_idents.insert(module_ident.as_str());
+                }
+            }
+        }
+        if let Some(current_module_ident) = self.current_module(path) {
+            module_idents.remove(current_module_ident.as_str());
+        }
+        module_idents
+    }
+
+    fn qualify_def_ident(&self, ident: &mut String, path: &String)
+    {
+        if let Some(module_ident) = self.current_module(path) {
+            *ident = module_def_ident(module_ident.as_str(), ident.as_str());
+        }
+    }
+
+    fn resolve_qualified_ident(&self, module_ident: &str, ident: &str, namespace: Namespace, path: &String, pos: &Pos, errs: &mut Vec<FrontendError>) -> Option<String>
+    {
+        match self.modules.get(module_ident) {
+            Some(idents) => {
+                if !self.is_visible_module(module_ident, path) {
+                    errs.push(FrontendError::Message(pos.clone(), format!("module {} isn't imported", module_ident)));
+                    None
+                } else if !idents.contains(ident, namespace) {
+                    errs.push(FrontendError::Message(pos.clone(), format!("undefined {} {}", namespace_name(namespace), qualify_ident(module_ident, ident))));
+                    None
+                } else {
+                    check_accessible_ident(idents, ident, namespace, module_def_ident(module_ident, ident), path, pos, errs)
+                }
+            },
+            None => {
+                errs.push(FrontendError::Message(pos.clone(), format!("undefined module {}", module_ident)));
+                None
+            },
+        }
+    }
+
+    fn resolve_unqualified_ident(&self, ident: &str, namespace: Namespace, path: &String, pos: &Pos, errs: &mut Vec<FrontendError>) -> Option<String>
+    {
+        match self.current_module(path) {
+            Some(module_ident) => {
+                match self.modules.get(module_ident) {
+                    Some(idents) if idents.contains(ident, namespace) => {
+                        return check_accessible_ident(idents, ident, namespace, module_def_ident(module_ident.as_str(), ident), path, pos, errs);
+                    },
+                    _ => (),
+                }
+            },
+            None => {
+                if self.root_idents.contains(ident, namespace) {
+                    return check_accessible_ident(&self.root_idents, ident, namespace, String::from(ident), path, pos, errs);
+                }
+            },
+        }
+        let mut new_idents: BTreeSet<String> = BTreeSet::new();
+        let mut private_idents: BTreeSet<String> = BTreeSet::new();
+        for module_ident in self.visible_module_idents(path) {
+            match self.modules.get(module_ident) {
+                Some(idents) if idents.contains(ident, namespace) => {
+                    if idents.is_accessible(ident, namespace, path) {
+                        new_idents.insert(module_def_ident(module_ident, ident));
+                    } else {
+                        private_idents.insert(module_def_ident(module_ident, ident));
+                    }
+                },
+                _ => (),
+            }
+        }
+        if new_idents.len() > 1 {
+            let new_idents: Vec<String> = new_idents.into_iter().collect();
+            errs.push(FrontendError::Message(pos.clone(), format!("ambiguous {} {} (candidates: {})", namespace_name(namespace), ident, new_idents.join(", "))));
+            None
+        } else {
+            match (new_idents.into_iter().next(), private_idents.into_iter().next()) {
+                (Some(new_ident), _) => Some(new_ident),
+                (None, Some(private_ident)) => {
+                    errs.push(FrontendError::Message(pos.clone(), format!("{} {} is private", namespace_name(namespace), private_ident)));
+                    None
+                },
+                (None, None) => {
+                    errs.push(FrontendError::Message(pos.clone(), format!("undefined {} {}", namespace_name(namespace), ident)));
+                    None
+                },
+            }
+        }
+    }
+
+    fn resolve_ident(&self, ident: &mut String, namespace: Namespace, path: &String, pos: &Pos, errs: &mut Vec<FrontendError>)
+    {
+        let new_ident = match split_qualified_ident(ident.as_str()) {
+            Some((module_ident, ident2)) => self.resolve_qualified_ident(module_ident, ident2, namespace, path, pos, errs),
+            None => self.resolve_unqualified_ident(ident.as_str(), namespace, path, pos, errs),
+        };
+        if let Some(new_ident) = new_ident {
+            *ident = new_ident;
+        }
+    }
+
+    pub fn resolve_idents(&self, tree: &mut Tree) -> FrontendResultWithErrors<()>
+    {
+        let mut errs: Vec<FrontendError> = Vec::new();
+        self.check_imports(tree, &mut errs);
+        for def in &mut tree.defs {
+            self.resolve_idents_for_def(def, &mut errs)?;
+        }
+        if errs.is_empty() {
+            Ok(())
+        } else {
+            Err(FrontendErrors::new(errs))
+        }
+    }
+
+    fn check_imports(&self, tree: &Tree, errs: &mut Vec<FrontendError>)
+    {
+        let mut paths: Vec<&String> = tree.imports.keys().collect();
+        paths.sort();
+        for path in paths {
+            for import in tree.imports(path) {
+                match import {
+                    Import(module_ident, pos) => {
+                        if !self.modules.contains_key(module_ident) {
+                            errs.push(FrontendError::Message(pos.clone(), format!("undefined module {}", module_ident)));
+                        }
+                    },
+                }
+            }
+        }
+    }
+
+    fn resolve_idents_for_def(&self, def: &mut Def, errs: &mut Vec<FrontendError>) -> FrontendResultWithErrors<()>
+    {
+        match def {
+            Def::Type(_, ident, type_var, pos) => {
+                let path = (*pos.path).clone();
+                check_unqualified_ident(ident, pos, errs);
+                self.qualify_def_ident(ident, &path);
+                let mut type_var_r = type_var.borrow_mut();
+                self.resolve_idents_for_type_var(&mut type_var_r, &path, errs)?;
+            },
+            Def::Var(_, ident, var, pos) => {
+                let path = (*pos.path).clone();
+                check_unqualified_ident(ident, pos, errs);
+                self.qualify_def_ident(ident, &path);
+                let mut var_r = var.borrow_mut();
+                self.resolve_idents_for_var(&mut var_r, &path, errs)?;
+            },
+            Def::Trait(_, ident, trait1, pos) => {
+                let path = (*pos.path).clone();
+                check_unqualified_ident(ident, pos, errs);
+                self.qualify_def_ident(ident, &path);
+                let mut trait_r = trait1.borrow_mut();
+                match &mut *trait_r {
+                    Trait(type_args, trait_defs, _) => {
+                        for type_arg in type_args {
+                            match type_arg {
+                                TypeArg(type_arg_ident, type_arg_pos) => check_unqualified_ident(type_arg_ident, type_arg_pos, errs),
+                            }
+                        }
+                        for trait_def in trait_defs {
+                            match &mut **trait_def {
+                                TraitDef(var_ident, var, var_pos) => {
+                                    check_unqualified_ident(var_ident, var_pos, errs);
+                                    self.qualify_def_ident(var_ident, &path);
+                                    let mut var_r = var.borrow_mut();
+                                    match &mut *var_r {
+                                        Var::Builtin(Some(trait_ident), _) => *trait_ident = ident.clone(),
+                                        Var::Var(_, _, _, _, Some(trait_ident), _, _, _, _) => *trait_ident = ident.clone(),
+                                        Var::Fun(_, Some(trait_ident), _) => *trait_ident = ident.clone(),
+                                        _ => (),
+                                    }
+                                    self.resolve_idents_for_var(&mut var_r, &path, errs)?;
+                                },
+                            }
+                        }
+                    },
+                }
+            },
+            Def::Impl(impl1, pos) => {
+                let path = (*pos.path).clone();
+                let mut impl_r = impl1.borrow_mut();
+                let (trait_ident, type_name, impl_defs) = match &mut *impl_r {
+                    Impl::Builtin(tmp_trait_ident, tmp_type_name, _) => (tmp_trait_ident, tmp_type_name, None),
+                    Impl::Impl(tmp_trait_ident, tmp_type_name, tmp_impl_defs, _) => (tmp_trait_ident, tmp_type_name, Some(tmp_impl_defs)),
+                };
+                self.resolve_ident(trait_ident, Namespace::Trait, &path, pos, errs);
+                if let TypeName::Name(type_ident) = type_name {
+                    self.resolve_ident(type_ident, Namespace::TypeVar, &path, pos, errs);
+                }
+                if let Some(impl_defs) = impl_defs {
+                    for impl_def in impl_defs {
+                        match &mut **impl_def {
+                            ImplDef(impl_var_ident, impl_var, impl_var_pos) => {
+                                check_unqualified_ident(impl_var_ident, impl_var_pos, errs);
+                                if let Some((module_ident, _)) = split_qualified_ident(trait_ident.as_str()) {
+                                    *impl_var_ident = qualify_ident(module_ident, impl_var_ident.as_str());
+                                }
+                                let mut impl_var_r = impl_var.borrow_mut();
+                                self.resolve_idents_for_impl_var(&mut impl_var_r, &path, errs)?;
+                            },
+                        }
+                    }
+                }
+            },
+        }
+        Ok(())
+    }
+
+    fn resolve_idents_for_type_var(&self, type_var: &mut TypeVar, path: &String, errs: &mut Vec<FrontendError>) -> FrontendResultWithErrors<()>
+    {
+        match type_var {
+            TypeVar::Builtin(_, _, _) => (),
+            TypeVar::Data(type_args, cons, _) => {
+                for type_arg in type_args {
+                    match type_arg {
+                        TypeArg(ident, type_arg_pos) => check_unqualified_ident(ident, type_arg_pos, errs),
+                    }
+                }
+                for con in cons {
+                    let mut con_r = con.borrow_mut();
+                    match &mut *con_r {
+                        Con::UnnamedField(_, ident, field_type_exprs, data_ident, con_pos) => {
+                            check_unqualified_ident(ident, con_pos, errs);
+                            self.qualify_def_ident(ident, path);
+                            self.qualify_def_ident(data_ident, path);
+                            for field_type_expr in field_type_exprs {
+                                self.resolve_idents_for_type_expr(field_type_expr, path, errs)?;
+                            }
+                        },
+                        Con::NamedField(_, ident, type_expr_named_field_pairs, data_ident, _, con_pos) => {
+                            check_unqualified_ident(ident, con_pos, errs);
+                            self.qualify_def_ident(ident, path);
+                            self.qualify_def_ident(data_ident, path);
+                            for type_expr_named_field_pair in type_expr_named_field_pairs {
+                                match type_expr_named_field_pair {
+                                    NamedFieldPair(field_ident, field_type_expr, field_pos) => {
+                                        check_unqualified_ident(field_ident, field_pos, errs);
+                                        self.resolve_idents_for_type_expr(field_type_expr, path, errs)?;
+                                    },
+                                }
+                            }
+                        },
+                    }
+                }
+            },
+            TypeVar::Synonym(type_args, type_expr, _) => {
+                for type_arg in type_args {
+                    match type_arg {
+                        TypeArg(ident, type_arg_pos) => check_unqualified_ident(ident, type_arg_pos, errs),
+                    }
+                }
+                self.resolve_idents_for_type_expr(type_expr, path, errs)?;
+            },
+        }
+        Ok(())
+    }
+
+    fn resolve_idents_for_type_expr(&self, type_expr: &mut TypeExpr, path: &String, errs: &mut Vec<FrontendError>) -> FrontendResultWithErrors<()>
+    {
+        match type_expr {
+            TypeExpr::Tuple(field_type_exprs, _) => {
+                for field_type_expr in field_type_exprs {
+                    self.resolve_idents_for_type_expr(field_type_expr, path, errs)?;
+                }
+            },
+            TypeExpr::Fun(arg_type_exprs, ret_type_expr, _) => {
+                for arg_type_expr in arg_type_exprs {
+                    self.resolve_idents_for_type_expr(arg_type_expr, path, errs)?;
+                }
+                self.resolve_idents_for_type_expr(ret_type_expr, path, errs)?;
+            },
+            TypeExpr::Array(elem_type_expr, _, _) => self.resolve_idents_for_type_expr(elem_type_expr, path, errs)?,
+            TypeExpr::Param(ident, pos) => check_unqualified_ident(ident, pos, errs),
+            TypeExpr::Var(ident, pos) => self.resolve_ident(ident, Namespace::TypeVar, path, pos, errs),
+            TypeExpr::App(ident, type_exprs, pos) => {
+                self.resolve_ident(ident, Namespace::TypeVar, path, pos, errs);
+                for type_expr2 in type_exprs {
+                    self.resolve_idents_for_type_expr(type_expr2, path, errs)?;
+                }
+            },
+            TypeExpr::Uniq(type_expr2, _) => self.resolve_idents_for_type_expr(type_expr2, path, errs)?,
+        }
+        Ok(())
+    }
+
+    fn resolve_idents_for_var(&self, var: &mut Var, path: &String, errs: &mut Vec<FrontendError>) -> FrontendResultWithErrors<()>
+    {
+        match var {
+            Var::Builtin(_, _) => (),
+            Var::Var(_, type_expr, where_tuples, expr, _, _, _, _, _) => {
+                let mut var_env: Environment<()> = Environment::new();
+                self.resolve_idents_for_type_expr(type_expr, path, errs)?;
+                self.resolve_idents_for_where_tuples(where_tuples.as_mut_slice(), path, errs)?;
+                if let Some(expr) = expr {
+                    self.resolve_idents_for_expr(expr, path, &mut var_env, errs)?;
+                }
+            },
+            Var::Fun(fun, _, _) => {
+                match &mut **fun {
+                    Fun::Fun(_, args, ret_type_expr, where_tuples, body, _, _) => {
+                        let mut var_env: Environment<()> = Environment::new();
+                        var_env.push_new_vars();
+                        for arg in args {
+                            match arg {
+                                Arg(ident, type_expr, _, arg_pos) => {
+                                    add_var_ident(ident, arg_pos, &mut var_env, errs);
+                                    self.resolve_idents_for_type_expr(type_expr, path, errs)?;
+                                },
+                            }
+                        }
+                        self.resolve_idents_for_type_expr(ret_type_expr, path, errs)?;
+                        self.resolve_idents_for_where_tuples(where_tuples.as_mut_slice(), path, errs)?;
+                        if let Some(body) = body {
+                            self.resolve_idents_for_expr(body, path, &mut var_env, errs)?;
+                        }
+                    },
+                    Fun::Con(_) => return Err(FrontendErrors::new(vec![FrontendError::Internal(String::from("resolve_idents_for_var: variable is contructor"))])),
+                }
+            },
+        }
+        Ok(())
+    }
+
+    fn resolve_idents_for_where_tuples(&self, where_tuples: &mut [WhereTuple], path: &String, errs: &mut Vec<FrontendError>) -> FrontendResultWithErrors<()>
+    {
+        for where_tuple in where_tuples {
+            match where_tuple {
+                WhereTuple::Traits(ident, trait_names, type_exprs, pos) => {
+                    check_unqualified_ident(ident, pos, errs);
+                    for trait_name in trait_names {
+                        if let TraitName::Name(trait_ident) = trait_name {
+                            self.resolve_ident(trait_ident, Namespace::Trait, path, pos, errs);
+                        }
+                    }
+                    for type_expr in type_exprs {
+                        self.resolve_idents_for_type_expr(type_expr, path, errs)?;
+                    }
+                },
+                WhereTuple::Eq(type_params) => {
+                    for type_param in type_params {
+                        match type_param {
+                            TypeParam(ident, pos) => check_unqualified_ident(ident, pos, errs),
+                        }
+                    }
+                },
+            }
+        }
+        Ok(())
+    }
+
+    fn resolve_idents_for_expr(&self, expr: &mut Expr, path: &String, var_env: &mut Environment<()>, errs: &mut Vec<FrontendError>) -> FrontendResultWithErrors<()>
+    {
+        match expr {
+            Expr::Literal(literal, _, _) => {
+                self.resolve_idents_for_literal(&mut **literal, |resolver, expr2| {
+                        resolver.resolve_idents_for_expr(expr2, path, var_env, errs)
+                })?;
+            },
+            Expr::Lambda(args, ret_type_expr, body, _, _, _, _, _) => {
+                var_env.push_new_vars();
+                for arg in args {
+                    match arg {
+                        LambdaArg(ident, type_expr, _, pos) => {
+                            add_var_ident(ident, pos, var_env, errs);
+                            if let Some(type_expr) = type_expr {
+                                self.resolve_idents_for_type_expr(type_expr, path, errs)?;
+                            }
+                        },
+                    }
+                }
+                if let Some(ret_type_expr) = ret_type_expr {
+                    self.resolve_idents_for_type_expr(ret_type_expr, path, errs)?;
+                }
+                self.resolve_idents_for_expr(body, path, var_env, errs)?;
+                var_env.pop_vars();
+            },
+            Expr::Var(ident, _, pos) => {
+                if split_qualified_ident(ident.as_str()).is_some() || var_env.var(ident).is_none() {
+                    self.resolve_ident(ident, Namespace::Var, path, pos, errs);
+                }
+            },
+            Expr::NamedFieldConApp(ident, expr_named_field_pairs, _, _, pos) => {
+                self.resolve_ident(ident, Namespace::Var, path, pos, errs);
+                for expr_named_field_pair in expr_named_field_pairs {
+                    match expr_named_field_pair {
+                        NamedFieldPair(_, expr2, _) => self.resolve_idents_for_expr(expr2, path, var_env, errs)?,
+                    }
+                }
+            },
+            Expr::PrintfApp(exprs, _, _) => {
+                for expr2 in exprs {
+                    self.resolve_idents_for_expr(expr2, path, var_env, errs)?;
+                }
+            },
+            Expr::App(expr2, exprs, _, _) => {
+                self.resolve_idents_for_expr(expr2, path, var_env, errs)?;
+                for expr3 in exprs {
+                    self.resolve_idents_for_expr(expr3, path, var_env, errs)?;
+                }
+            },
+            Expr::GetField(expr2, _, _, _) => self.resolve_idents_for_expr(expr2, path, var_env, errs)?,
+            Expr::Get2Field(expr2, _, _, _) => self.resolve_idents_for_expr(expr2, path, var_env, errs)?,
+            Expr::SetField(expr2, _, expr3, _, _) => {
+                self.resolve_idents_for_expr(expr2, path, var_env, errs)?;
+                self.resolve_idents_for_expr(expr3, path, var_env, errs)?;
+            },
+            Expr::UpdateField(expr2, _, expr3, _, _) => {
+                self.resolve_idents_for_expr(expr2, path, var_env, errs)?;
+                self.resolve_idents_for_expr(expr3, path, var_env, errs)?;
+            },
+            Expr::UpdateGet2Field(expr2, _, expr3, _, _) => {
+                self.resolve_idents_for_expr(expr2, path, var_env, errs)?;
+                self.resolve_idents_for_expr(expr3, path, var_env, errs)?;
+            },
+            Expr::Uniq(expr2, _, _) => self.resolve_idents_for_expr(expr2, path, var_env, errs)?,
+            Expr::Shared(expr2, _, _) => self.resolve_idents_for_expr(expr2, path, var_env, errs)?,
+            Expr::Typed(expr2, type_expr, _, _) => {
+                self.resolve_idents_for_expr(expr2, path, var_env, errs)?;
+                self.resolve_idents_for_type_expr(type_expr, path, errs)?;
+            },
+            Expr::As(expr2, type_expr, _, _) => {
+                self.resolve_idents_for_expr(expr2, path, var_env, errs)?;
+                self.resolve_idents_for_type_expr(type_expr, path, errs)?;
+            },
+            Expr::If(expr2, expr3, expr4, _, _) => {
+                self.resolve_idents_for_expr(expr2, path, var_env, errs)?;
+                self.resolve_idents_for_expr(expr3, path, var_env, errs)?;
+                self.resolve_idents_for_expr(expr4, path, var_env, errs)?;
+            },
+            Expr::Let(binds, expr2, _, _) => {
+                var_env.push_new_vars();
+                for bind in binds {
+                    match bind {
+                        Bind(pattern, expr3) => {
+                            self.resolve_idents_for_expr(expr3, path, var_env, errs)?;
+                            self.resolve_idents_for_pattern(pattern, path, var_env, errs)?;
+                        },
+                    }
+                }
+                self.resolve_idents_for_expr(expr2, path, var_env, errs)?;
+                var_env.pop_vars();
+            },
+            Expr::Match(expr2, cases, _, _) => {
+                self.resolve_idents_for_expr(expr2, path, var_env, errs)?;
+                for case in cases {
+                    match case {
+                        Case(pattern, expr3) => {
+                            var_env.push_new_vars();
+                            self.resolve_idents_for_pattern(pattern, path, var_env, errs)?;
+                            self.resolve_idents_for_expr(expr3, path, var_env, errs)?;
+                            var_env.pop_vars();
+                        },
+                    }
+                }
+            },
+        }
+        Ok(())
+    }
+
+    fn resolve_idents_for_pattern(&self, pattern: &mut Pattern, path: &String, var_env: &mut Environment<()>, errs: &mut Vec<FrontendError>) -> FrontendResultWithErrors<()>
+    {
+        match pattern {
+            Pattern::Literal(literal, _, _) => {
+                self.resolve_idents_for_literal(&mut **literal, |resolver, pattern2| {
+                        resolver.resolve_idents_for_pattern(pattern2, path, var_env, errs)
+                })?;
+            },
+            Pattern::As(literal, type_expr, _, _, _) => {
+                self.resolve_idents_for_literal(&mut **literal, |resolver, pattern2| {
+                        resolver.resolve_idents_for_pattern(pattern2, path, var_env, errs)
+                })?;
+                self.resolve_idents_for_type_expr(type_expr, path, errs)?;
+            },
+            Pattern::Const(ident, _, pos) => self.resolve_ident(ident, Namespace::Var, path, pos, errs),
+            Pattern::UnnamedFieldCon(ident, patterns, _, _, pos) => {
+                self.resolve_ident(ident, Namespace::Var, path, pos, errs);
+                for pattern2 in patterns {
+                    self.resolve_idents_for_pattern(pattern2, path, var_env, errs)?;
+                }
+            },
+            Pattern::NamedFieldCon(ident, pattern_named_field_pairs, _, _, pos) => {
+                self.resolve_ident(ident, Namespace::Var, path, pos, errs);
+                for pattern_named_field_pair in pattern_named_field_pairs {
+                    match pattern_named_field_pair {
+                        NamedFieldPair(_, pattern2, _) => self.resolve_idents_for_pattern(pattern2, path, var_env, errs)?,
+                    }
+                }
+            },
+            Pattern::Var(_, ident, _, pos) => add_var_ident(ident, pos, var_env, errs),
+            Pattern::At(_, ident, pattern2, _, pos) => {
+                add_var_ident(ident, pos, var_env, errs);
+                self.resolve_idents_for_pattern(pattern2, path, var_env, errs)?;
+            },
+            Pattern::Wildcard(_, _) => (),
+            Pattern::Alt(patterns, _, _) => {
+                for pattern2 in patterns {
+                    self.resolve_idents_for_pattern(pattern2, path, var_env, errs)?;
+                }
+            },
+        }
+        Ok(())
+    }
+
+    fn resolve_idents_for_literal<T, F>(&self, literal: &mut Literal<T>, mut f: F) -> FrontendResultWithErrors<()>
+        where F: FnMut(&Self, &mut T) -> FrontendResultWithErrors<()>
+    {
+        match literal {
+            Literal::Tuple(field_others) => {
+                for field_other in field_others {
+                    f(self, &mut **field_other)?;
+                }
+            },
+            Literal::Array(elem_others) => {
+                for elem_other in elem_others {
+                    f(self, &mut **elem_other)?;
+                }
+            },
+            Literal::FilledArray(elem_other, _) => f(self, &mut **elem_other)?,
+            _ => (),
+        }
+        Ok(())
+    }
+
+    fn resolve_idents_for_impl_var(&self, impl_var: &mut ImplVar, path: &String, errs: &mut Vec<FrontendError>) -> FrontendResultWithErrors<()>
+    {
+        match impl_var {
+            ImplVar::Builtin(_) => (),
+            ImplVar::Var(expr, _, _, _, _) => {
+                let mut var_env: Environment<()> = Environment::new();
+                self.resolve_idents_for_expr(expr, path, &mut var_env, errs)?;
+            },
+            ImplVar::Fun(fun, _) => {
+                match &mut **fun {
+                    ImplFun(impl_args, body, _, _) => {
+                        let mut var_env: Environment<()> = Environment::new();
+                        var_env.push_new_vars();
+                        for impl_arg in impl_args {
+                            match impl_arg {
+                                ImplArg(ident, _, pos) => add_var_ident(ident, pos, &mut var_env, errs),
+                            }
+                        }
+                        self.resolve_idents_for_expr(body, path, &mut var_env, errs)?;
+                    },
+                }
+            },
+        }
+        Ok(())
+    }
+}
+
+fn resolve_builtin_ident<F>(ident: &mut String, module_ident: &str, is_defined: F)
+    where F: Fn(&String) -> bool
+{
+    if !is_defined(ident) {
+        let new_ident = qualify_ident(module_ident, ident.as_str());
+        if is_defined(&new_ident) {
+            *ident = new_ident;
+        }
+    }
+}
+
+fn resolve_builtin_idents_for_type_expr(type_expr: &mut TypeExpr, module_ident: &str, tree: &Tree)
+{
+    match type_expr {
+        TypeExpr::Tuple(field_type_exprs, _) => {
+            for field_type_expr in field_type_exprs {
+                resolve_builtin_idents_for_type_expr(field_type_expr, module_ident, tree);
+            }
+        },
+        TypeExpr::Fun(arg_type_exprs, ret_type_expr, _) => {
+            for arg_type_expr in arg_type_exprs {
+                resolve_builtin_idents_for_type_expr(arg_type_expr, module_ident, tree);
+            }
+            resolve_builtin_idents_for_type_expr(ret_type_expr, module_ident, tree);
+        },
+        TypeExpr::Array(elem_type_expr, _, _) => resolve_builtin_idents_for_type_expr(elem_type_expr, module_ident, tree),
+        TypeExpr::Param(_, _) => (),
+        TypeExpr::Var(ident, _) => resolve_builtin_ident(ident, module_ident, |ident2| tree.type_var(ident2).is_some()),
+        TypeExpr::App(ident, type_exprs, _) => {
+            resolve_builtin_ident(ident, module_ident, |ident2| tree.type_var(ident2).is_some());
+            for type_expr2 in type_exprs {
+                resolve_builtin_idents_for_type_expr(type_expr2, module_ident, tree);
+            }
+        },
+        TypeExpr::Uniq(type_expr2, _) => resolve_builtin_idents_for_type_expr(type_expr2, module_ident, tree),
+    }
+}
+
+pub fn resolve_builtin_idents_for_type_with_where(ident: &str, type_expr: &mut TypeExpr, where_tuples: &mut [WhereTuple], tree: &Tree)
+{
+    let module_ident = match split_qualified_ident(ident) {
+        Some((module_ident, _)) => module_ident,
+        None => return,
+    };
+    resolve_builtin_idents_for_type_expr(type_expr, module_ident, tree);
+    for where_tuple in where_tuples {
+        match where_tuple {
+            WhereTuple::Traits(_, trait_names, type_exprs, _) => {
+                for trait_name in trait_names {
+                    if let TraitName::Name(trait_ident) = trait_name {
+                        resolve_builtin_ident(trait_ident, module_ident, |ident2| tree.trait1(ident2).is_some());
+                    }
+                }
+                for type_expr2 in type_exprs {
+                    resolve_builtin_idents_for_type_expr(type_expr2, module_ident, tree);
+                }
+            },
+            WhereTuple::Eq(_) => (),
+        }
+    }
+}
+
+pub fn resolve_idents(tree: &mut Tree) -> FrontendResultWithErrors<()>
+{
+    let resolver = Resolver::new(tree);
+    resolver.resolve_idents(tree)
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,564 @@
+//
+// Copyright (c) 2025 Łukasz Szpakowski
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+use crate::frontend::*;
+use super::*;
+
+fn sources(pairs: &[(&str, &str)]) -> Vec<Source>
+{ pairs.iter().map(|(path, s)| Source::String(String::from(*path), String::from(*s))).collect() }
+
+#[test]
+fn test_resolver_resolve_idents_resolves_qualified_identifiers()
+{
+    let s = "
+import geom;
+
+helper(x: Int) -> Int = x * 2;
+
+f(x: Int) -> Int = geom::helper(helper(x));
+
+g(p: geom::Point) -> geom::Point = geom::shift(p);
+";
+    let s2 = "
+pub data Point = pub Point(Int, Int);
+
+pub helper(x: Int) -> Int = x + 1;
+
+origin: Point = Point(0, 0);
+
+pub shift(p: Point) -> Point = p match { Point(x, y) => Point(helper(x), y); };
+";
+    let srcs = sources(&[("main.vscfl", &s[1..]), ("geom.vscfl", &s2[1..])]);
+    match do_frontend_phases_with_sources(srcs.as_slice()) {
+        Ok(tree) => {
+            assert!(tree.var(&String::from("main::helper")).is_some());
+            assert!(tree.var(&String::from("geom::helper")).is_some());
+            assert!(tree.var(&String::from("helper")).is_none());
+            assert!(tree.var(&String::from("geom::origin")).is_some());
+            assert!(tree.var(&String::from("geom::Point")).is_some());
+            assert!(tree.type_var(&String::from("geom::Point")).is_some());
+            assert!(tree.var(&String::from("origin")).is_none());
+            match tree.module(&String::from("geom")) {
+                Some(Module(kind, paths)) => {
+                    assert_eq!(ModuleKind::User, *kind);
+                    assert_eq!(vec![String::from("geom.vscfl")], *paths);
+                },
+                None => assert!(false),
+            }
+        },
+        Err(errs) => {
+            println!("{}", errs);
+            assert!(false);
+        },
+    }
+}
+
+#[test]
+fn test_resolver_resolve_idents_resolves_qualified_identifiers_for_standard_library_module()
+{
+    let s = "
+import trig;
+
+f(x: Float) -> Float = trig::cos(cos(x));
+";
+    let s2 = "
+import std_math;
+
+pub cos(x: Float) -> Float = std_math::cos(x) * 2.0f;
+";
+    let srcs = sources(&[("main.vscfl", &s[1..]), ("trig.vscfl", &s2[1..])]);
+    match do_frontend_phases_with_sources(srcs.as_slice()) {
+        Ok(tree) => {
+            assert!(tree.var(&String::from("cos")).is_none());
+            assert!(tree.var(&String::from("trig::cos")).is_some());
+            assert!(tree.var(&String::from("std_math::cos")).is_some());
+            match tree.module(&String::from("std_math")) {
+                Some(Module(kind, _)) => assert_eq!(ModuleKind::Stdlib, *kind),
+                None => assert!(false),
+            }
+        },
+        Err(errs) => {
+            println!("{}", errs);
+            assert!(false);
+        },
+    }
+}
+
+#[test]
+fn test_resolver_resolve_idents_complains_on_not_imported_module()
+{
+    let s = "
+x: Int = geom::y;
+";
+    let s2 = "
+y: Int = 1;
+";
+    let srcs = sources(&[("main.vscfl", &s[1..]), ("geom.vscfl", &s2[1..])]);
+    match do_frontend_phases_with_sources(srcs.as_slice()) {
+        Ok(_) => assert!(false),
+        Err(errs) => {
+            assert_eq!(1, errs.errors().len());
+            match &errs.errors()[0] {
+                FrontendError::Message(pos, msg) => {
+                    assert_eq!(1, pos.line);
+                    assert_eq!(10, pos.column);
+                    assert_eq!(String::from("module geom isn't imported"), *msg);
+                },
+                _ => assert!(false),
+            }
+        },
+    }
+}
+
+#[test]
+fn test_resolver_resolve_idents_complains_on_undefined_module_and_identifiers()
+{
+    let s = "
+import geom;
+import nonexistent_module;
+
+x: Int = geom::z;
+
+y: Int = std_math::cos;
+";
+    let s2 = "
+y: Int = 1;
+";
+    let srcs = sources(&[("main.vscfl", &s[1..]), ("geom.vscfl", &s2[1..])]);
+    match do_frontend_phases_with_sources(srcs.as_slice()) {
+        Ok(_) => assert!(false),
+        Err(errs) => {
+            assert_eq!(3, errs.errors().len());
+            match &errs.errors()[0] {
+                FrontendError::Message(pos, msg) => {
+                    assert_eq!(2, pos.line);
+                    assert_eq!(8, pos.column);
+                    assert_eq!(String::from("undefined module nonexistent_module"), *msg);
+                },
+                _ => assert!(false),
+            }
+            match &errs.errors()[1] {
+                FrontendError::Message(pos, msg) => {
+                    assert_eq!(4, pos.line);
+                    assert_eq!(10, pos.column);
+                    assert_eq!(String::from("undefined variable geom::z"), *msg);
+                },
+                _ => assert!(false),
+            }
+            match &errs.errors()[2] {
+                FrontendError::Message(pos, msg) => {
+                    assert_eq!(6, pos.line);
+                    assert_eq!(10, pos.column);
+                    assert_eq!(String::from("undefined module std_math"), *msg);
+                },
+                _ => assert!(false),
+            }
+        },
+    }
+}
+
+#[test]
+fn test_resolver_resolve_idents_complains_on_root_identifiers_in_module()
+{
+    let s = "
+import geom;
+
+x: Int = 1;
+";
+    let s2 = "
+y: Int = x;
+";
+    let srcs = sources(&[("main.vscfl", &s[1..]), ("geom.vscfl", &s2[1..])]);
+    match do_frontend_phases_with_sources(srcs.as_slice()) {
+        Ok(_) => assert!(false),
+        Err(errs) => {
+            assert_eq!(1, errs.errors().len());
+            match &errs.errors()[0] {
+                FrontendError::Message(pos, msg) => {
+                    assert_eq!(String::from("geom.vscfl"), *pos.path);
+                    assert_eq!(1, pos.line);
+                    assert_eq!(10, pos.column);
+                    assert_eq!(String::from("undefined variable x"), *msg);
+                },
+                _ => assert!(false),
+            }
+        },
+    }
+}
+
+#[test]
+fn test_resolver_resolve_idents_resolves_public_identifiers()
+{
+    let s = "
+import geom;
+
+f(p: geom::Point) -> Int = geom::size(p);
+
+g(x: t) -> Int where t: geom::Shape = geom::sides(x);
+
+impl geom::Shape for Int
+{
+    sides(x) = x;
+}
+";
+    let s2 = "
+pub data Point = Point(Int, Int);
+
+pub size(p: Point) -> Int = p match { Point(x, y) => helper(x) + y; };
+
+helper(x: Int) -> Int = x;
+
+pub trait Shape
+{
+    sides(x: t) -> Int where t: Shape;
+};
+";
+    let srcs = sources(&[("main.vscfl", &s[1..]), ("geom.vscfl", &s2[1..])]);
+    match do_frontend_phases_with_sources(srcs.as_slice()) {
+        Ok(tree) => {
+            assert!(tree.var(&String::from("geom::size")).is_some());
+            assert!(tree.var(&String::from("geom::helper")).is_some());
+            assert!(tree.var(&String::from("geom::Point")).is_some());
+            assert!(tree.trait1(&String::from("geom::Shape")).is_some());
+        },
+        Err(errs) => {
+            println!("{}", errs);
+            assert!(false);
+        },
+    }
+}
+
+#[test]
+fn test_resolver_resolve_idents_complains_on_private_identifiers()
+{
+    let s = "
+import geom;
+
+f(p: geom::Point) -> Int = p match { geom::Point(x, _) => geom::helper(x); };
+
+x: geom::Hidden = geom::hidden;
+
+g(x: t) -> Int where t: geom::Shape = 1;
+";
+    let s2 = "
+pub data Point = Point(Int, Int);
+
+data Hidden = pub Hidden();
+
+helper(x: Int) -> Int = x;
+
+pub hidden: Hidden = Hidden();
+
+trait Shape
+{
+    sides(x: t) -> Int where t: Shape;
+};
+";
+    let srcs = sources(&[("main.vscfl", &s[1..]), ("geom.vscfl", &s2[1..])]);
+    match do_frontend_phases_with_sources(srcs.as_slice()) {
+        Ok(_) => assert!(false),
+        Err(errs) => {
+            assert_eq!(4, errs.errors().len());
+            match &errs.errors()[0] {
+                FrontendError::Message(pos, msg) => {
+                    assert_eq!(String::from("main.vscfl"), *pos.path);
+                    assert_eq!(3, pos.line);
+                    assert_eq!(38, pos.column);
+                    assert_eq!(String::from("variable geom::Point is private"), *msg);
+                },
+                _ => assert!(false),
+            }
+            match &errs.errors()[1] {
+                FrontendError::Message(pos, msg) => {
+                    assert_eq!(3, pos.line);
+                    assert_eq!(59, pos.column);
+                    assert_eq!(String::from("variable geom::helper is private"), *msg);
+                },
+                _ => assert!(false),
+            }
+            match &errs.errors()[2] {
+                FrontendError::Message(pos, msg) => {
+                    assert_eq!(5, pos.line);
+                    assert_eq!(4, pos.column);
+                    assert_eq!(String::from("type variable geom::Hidden is private"), *msg);
+                },
+                _ => assert!(false),
+            }
+            match &errs.errors()[3] {
+                FrontendError::Message(pos, msg) => {
+                    assert_eq!(7, pos.line);
+                    assert_eq!(22, pos.column);
+                    assert_eq!(String::from("trait geom::Shape is private"), *msg);
+                },
+                _ => assert!(false),
+            }
+        },
+    }
+}
+
+#[test]
+fn test_resolver_resolve_idents_complains_on_private_constructor_in_pattern()
+{
+    let s = "
+import shape;
+
+area(s: Shape) -> Int = s match { Square(a) => a * a; Rect(a, b) => a * b; };
+";
+    let s2 = "
+pub data Shape = pub Square(Int) | Rect(Int, Int);
+";
+    let srcs = sources(&[("main.vscfl", &s[1..]), ("shape.vscfl", &s2[1..])]);
+    match do_frontend_phases_with_sources(srcs.as_slice()) {
+        Ok(_) => assert!(false),
+        Err(errs) => {
+            assert_eq!(1, errs.errors().len());
+            match &errs.errors()[0] {
+                FrontendError::Message(pos, msg) => {
+                    assert_eq!(String::from("main.vscfl"), *pos.path);
+                    assert_eq!(3, pos.line);
+                    assert_eq!(55, pos.column);
+                    assert_eq!(String::from("variable shape::Rect is private"), *msg);
+                },
+                _ => assert!(false),
+            }
+        },
+    }
+}
+
+#[test]
+fn test_resolver_resolve_idents_complains_on_private_identifiers_of_standard_library_module()
+{
+    let s = "
+import std_fake;
+
+x: Int = std_fake::twice(1);
+
+y: Int = std_fake::helper(1);
+";
+    let s2 = "
+helper(x: Int) -> Int = x;
+
+pub twice(x: Int) -> Int = helper(x) * 2;
+";
+    let mut stdlib_modules = stdlib_modules();
+    stdlib_modules.push((String::from("std_fake"), sources(&[("(stdlib)/std_fake.vscfl", &s2[1..])])));
+    let srcs = sources(&[("main.vscfl", &s[1..])]);
+    match do_frontend_phases_with_stdlib_modules(stdlib_modules.as_slice(), srcs.as_slice(), &[]) {
+        Ok(_) => assert!(false),
+        Err(errs) => {
+            assert_eq!(1, errs.errors().len());
+            match &errs.errors()[0] {
+                FrontendError::Message(pos, msg) => {
+                    assert_eq!(String::from("main.vscfl"), *pos.path);
+                    assert_eq!(5, pos.line);
+                    assert_eq!(10, pos.column);
+                    assert_eq!(String::from("variable std_fake::helper is private"), *msg);
+                },
+                _ => assert!(false),
+            }
+        },
+    }
+}
+
+#[test]
+fn test_resolver_resolve_idents_resolves_identifiers_of_root_sources_in_separate_modules()
+{
+    let s = "
+g: Int = 1;
+
+h: Int = g + 1;
+";
+    let s2 = "
+g: Int = 2;
+";
+    let srcs = sources(&[("a.vscfl", &s[1..]), ("b.vscfl", &s2[1..])]);
+    match do_frontend_phases_with_sources(srcs.as_slice()) {
+        Ok(tree) => {
+            assert!(tree.var(&String::from("a::g")).is_some());
+            assert!(tree.var(&String::from("a::h")).is_some());
+            assert!(tree.var(&String::from("b::g")).is_some());
+            assert!(tree.var(&String::from("g")).is_none());
+            match tree.module(&String::from("b")) {
+                Some(Module(kind, paths)) => {
+                    assert_eq!(ModuleKind::User, *kind);
+                    assert_eq!(vec![String::from("b.vscfl")], *paths);
+                },
+                None => assert!(false),
+            }
+        },
+        Err(errs) => {
+            println!("{}", errs);
+            assert!(false);
+        },
+    }
+}
+
+#[test]
+fn test_resolver_resolve_idents_resolves_unqualified_identifiers_of_imported_modules()
+{
+    let s = "
+import geom;
+import std_math;
+
+f(p: Point) -> Int = size(p);
+
+g(x: Float) -> Float = sqrt(x);
+";
+    let s2 = "
+pub data Point = pub Point(Int, Int);
+
+pub size(p: Point) -> Int = p match { Point(x, y) => x + y; };
+";
+    let srcs = sources(&[("main.vscfl", &s[1..]), ("geom.vscfl", &s2[1..])]);
+    match do_frontend_phases_with_sources(srcs.as_slice()) {
+        Ok(tree) => {
+            assert!(tree.var(&String::from("main::f")).is_some());
+            assert!(tree.var(&String::from("main::g")).is_some());
+            assert!(tree.var(&String::from("geom::size")).is_some());
+        },
+        Err(errs) => {
+            println!("{}", errs);
+            assert!(false);
+        },
+    }
+}
+
+#[test]
+fn test_resolver_resolve_idents_complains_on_ambiguous_and_not_imported_identifiers()
+{
+    let s = "
+import a;
+import b;
+
+x: Int = g;
+
+y: Float = sqrt(2.0f);
+";
+    let s2 = "
+pub g: Int = 1;
+";
+    let s3 = "
+pub g: Int = 2;
+";
+    let srcs = sources(&[("main.vscfl", &s[1..]), ("a.vscfl", &s2[1..]), ("b.vscfl", &s3[1..])]);
+    match do_frontend_phases_with_sources(srcs.as_slice()) {
+        Ok(_) => assert!(false),
+        Err(errs) => {
+            assert_eq!(2, errs.errors().len());
+            match &errs.errors()[0] {
+                FrontendError::Message(pos, msg) => {
+                    assert_eq!(4, pos.line);
+                    assert_eq!(10, pos.column);
+                    assert_eq!(String::from("ambiguous variable g (candidates: a::g, b::g)"), *msg);
+                },
+                _ => assert!(false),
+            }
+            match &errs.errors()[1] {
+                FrontendError::Message(pos, msg) => {
+                    assert_eq!(6, pos.line);
+                    assert_eq!(12, pos.column);
+                    assert_eq!(String::from("undefined variable sqrt"), *msg);
+                },
+                _ => assert!(false),
+            }
+        },
+    }
+}
+
+#[test]
+fn test_resolver_resolve_idents_complains_on_private_identifiers_of_other_root_sources()
+{
+    let s = "
+import util;
+
+x: Int = helper(1);
+
+y: Box = util::Box(2);
+
+z: Int = unbox(Box(3));
+";
+    let s2 = "
+helper(x: Int) -> Int = x;
+
+pub data Box = Box(Int);
+
+pub unbox(b: Box) -> Int = b match { Box(x) => helper(x); };
+";
+    let srcs = sources(&[("main.vscfl", &s[1..]), ("util.vscfl", &s2[1..])]);
+    match do_frontend_phases_with_sources(srcs.as_slice()) {
+        Ok(_) => assert!(false),
+        Err(errs) => {
+            assert_eq!(3, errs.errors().len());
+            match &errs.errors()[0] {
+                FrontendError::Message(pos, msg) => {
+                    assert_eq!(String::from("main.vscfl"), *pos.path);
+                    assert_eq!(3, pos.line);
+                    assert_eq!(10, pos.column);
+                    assert_eq!(String::from("variable util::helper is private"), *msg);
+                },
+                _ => assert!(false),
+            }
+            match &errs.errors()[1] {
+                FrontendError::Message(pos, msg) => {
+                    assert_eq!(5, pos.line);
+                    assert_eq!(10, pos.column);
+                    assert_eq!(String::from("variable util::Box is private"), *msg);
+                },
+                _ => assert!(false),
+            }
+            match &errs.errors()[2] {
+                FrontendError::Message(pos, msg) => {
+                    assert_eq!(7, pos.line);
+                    assert_eq!(16, pos.column);
+                    assert_eq!(String::from("variable util::Box is private"), *msg);
+                },
+                _ => assert!(false),
+            }
+        },
+    }
+}
+
+#[test]
+fn test_resolver_resolve_idents_complains_on_private_identifiers_of_other_root_sources_without_modules()
+{
+    let s = "
+x: Int = helper(1);
+
+y: Int = unbox(Box(2));
+";
+    let s2 = "
+helper(x: Int) -> Int = x;
+
+pub data Box = Box(Int);
+
+pub unbox(b: Box) -> Int = b match { Box(x) => helper(x); };
+";
+    let srcs = sources(&[("Main.vscfl", &s[1..]), ("Util.vscfl", &s2[1..])]);
+    match do_frontend_phases_with_sources(srcs.as_slice()) {
+        Ok(_) => assert!(false),
+        Err(errs) => {
+            assert_eq!(2, errs.errors().len());
+            match &errs.errors()[0] {
+                FrontendError::Message(pos, msg) => {
+                    assert_eq!(String::from("Main.vscfl"), *pos.path);
+                    assert_eq!(1, pos.line);
+                    assert_eq!(10, pos.column);
+                    assert_eq!(String::from("variable helper is private"), *msg);
+                },
+                _ => assert!(false),
+            }
+            match &errs.errors()[1] {
+                FrontendError::Message(pos, msg) => {
+                    assert_eq!(3, pos.line);
+                    assert_eq!(16, pos.column);
+                    assert_eq!(String::from("variable Box is private"), *msg);
+                },
+                _ => assert!(false),
+            }
+        },
+    }
+}
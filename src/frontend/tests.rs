@@ -22,3 +22,45 @@ kernel mykernel() -> () = ();
         },
     }
 }
+
+#[test]
+fn test_do_frontend_phases_does_frontend_phases_with_each_standard_library_module()
+{
+    for (module_ident, _) in stdlib_modules() {
+        let s = format!("import {};\n\nkernel mykernel() -> () = ();\n", module_ident);
+        match do_frontend_phases(s.as_str()) {
+            Ok(tree) => assert!(tree.module(&module_ident).is_some()),
+            Err(errs) => {
+                println!("{}: {}", module_ident, errs);
+                assert!(false);
+            },
+        }
+    }
+}
+
+#[test]
+fn test_do_frontend_phases_parses_only_imported_standard_library_modules()
+{
+    let s = "
+import std_option;
+
+kernel mykernel() -> () = ();
+";
+    let s2 = &s[1..];
+    match do_frontend_phases(s2) {
+        Ok(tree) => {
+            assert!(tree.module(&String::from("lang")).is_some());
+            assert!(tree.module(&String::from("std_option")).is_some());
+            assert!(tree.module(&String::from("std")).is_some());
+            assert!(tree.module(&String::from("std_math")).is_none());
+            assert!(tree.module(&String::from("opencl")).is_none());
+            assert!(tree.type_var(&String::from("std_option::Option")).is_some());
+            assert!(tree.trait1(&String::from("std::Map")).is_some());
+            assert!(tree.type_var(&String::from("Int")).is_some());
+        },
+        Err(errs) => {
+            println!("{}", errs);
+            assert!(false);
+        },
+    }
+}
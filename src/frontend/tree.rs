@@ -28,12 +28,20 @@ pub enum VarModifier
     Constant,
 }
 
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+pub enum Visibility
+{
+    Private,
+    Public,
+}
+
 #[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
 pub enum FunModifier
 {
     None,
     Kernel,
     Inline,
+    Recursive(usize),
 }
 
 #[derive(Clone, Debug)]
@@ -43,6 +51,8 @@ pub struct Tree
     pub(crate) type_vars: HashMap<String, Rc<RefCell<TypeVar>>>,
     pub(crate) vars: HashMap<String, Rc<RefCell<Var>>>,
     pub(crate) traits: HashMap<String, Rc<RefCell<Trait>>>,
+    pub(crate) imports: HashMap<String, Vec<Import>>,
+    pub(crate) modules: HashMap<String, Module>,
     pub(crate) ref_values: RefCell<RefValues>,
 }
 
@@ -55,6 +65,8 @@ impl Tree
             type_vars: HashMap::new(),
             vars: HashMap::new(),
             traits: HashMap::new(),
+            imports: HashMap::new(),
+            modules: HashMap::new(),
             ref_values: RefCell::new(RefValues::new()),
         }
     }
@@ -91,6 +103,40 @@ impl Tree
 
     pub fn add_trait(&mut self, ident: String, trait1: Rc<RefCell<Trait>>)
     { self.traits.insert(ident, trait1); }
+
+    pub fn imports(&self, path: &String) -> &[Import]
+    {
+        match self.imports.get(path) {
+            Some(imports) => imports.as_slice(),
+            None => &[],
+        }
+    }
+
+    pub fn add_import(&mut self, path: String, import: Import)
+    {
+        match self.imports.get_mut(&path) {
+            Some(imports) => imports.push(import),
+            None => {
+                self.imports.insert(path, vec![import]);
+            },
+        }
+    }
+
+    pub fn modules(&self) -> &HashMap<String, Module>
+    { &self.modules }
+
+    pub fn module(&self, ident: &String) -> Option<&Module>
+    { self.modules.get(ident) }
+
+    pub fn add_module_path(&mut self, ident: String, kind: ModuleKind, path: String)
+    {
+        match self.modules.get_mut(&ident) {
+            Some(module) => module.1.push(path),
+            None => {
+                self.modules.insert(ident, Module(kind, vec![path]));
+            },
+        }
+    }
     
     pub fn ref_values(&self) -> &RefCell<RefValues>
     { &self.ref_values }
@@ -99,12 +145,25 @@ impl Tree
 #[derive(Clone, Debug)]
 pub enum Def
 {
-    Type(String, Rc<RefCell<TypeVar>>, Pos),
-    Var(String, Rc<RefCell<Var>>, Pos),
-    Trait(String, Rc<RefCell<Trait>>, Pos),
+    Type(Visibility, String, Rc<RefCell<TypeVar>>, Pos),
+    Var(Visibility, String, Rc<RefCell<Var>>, Pos),
+    Trait(Visibility, String, Rc<RefCell<Trait>>, Pos),
     Impl(Rc<RefCell<Impl>>, Pos),
 }
 
+#[derive(Clone, Debug)]
+pub struct Import(pub String, pub Pos);
+
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum ModuleKind
+{
+    Stdlib,
+    User,
+}
+
+#[derive(Clone, Debug)]
+pub struct Module(pub ModuleKind, pub Vec<String>);
+
 #[derive(Clone, Debug)]
 pub enum TypeVar
 {
@@ -119,8 +178,8 @@ pub struct TypeArg(pub String, pub Pos);
 #[derive(Clone, Debug)]
 pub enum Con
 {
-    UnnamedField(String, Vec<Box<TypeExpr>>, String, Pos),
-    NamedField(String, Vec<NamedFieldPair<TypeExpr>>, String, Option<Box<NamedFields>>, Pos),
+    UnnamedField(Visibility, String, Vec<Box<TypeExpr>>, String, Pos),
+    NamedField(Visibility, String, Vec<NamedFieldPair<TypeExpr>>, String, Option<Box<NamedFields>>, Pos),
 }
 
 #[derive(Clone, Debug)]
@@ -419,10 +478,7 @@ impl Fields
     
     pub fn field_index(&self, ident: &String) -> Option<usize>
     {
-       match self.field_indices.get(ident) {
-           Some(i) => Some(*i),
-           None => None,
-       }
+       self.field_indices.get(ident).map(|i| *i)
     }
     
     pub fn add_field_index(&mut self, ident: String, field_idx: usize)
@@ -445,10 +501,7 @@ impl NamedFields
     
     pub fn field_index(&self, ident: &String) -> Option<usize>
     {
-       match self.field_indices.get(ident) {
-           Some(i) => Some(*i),
-           None => None,
-       }
+       self.field_indices.get(ident).map(|i| *i)
     }
     
     pub fn add_field_index(&mut self, ident: String, field_idx: usize)
@@ -537,7 +590,7 @@ impl TypeValue
             TypeValue::Param(_, _) => None,
             TypeValue::Type(_, TypeValueName::Tuple, args) => Some(TypeName::Tuple(args.len())),
             TypeValue::Type(_, TypeValueName::Fun, args) => {
-                if args.len() >= 1 {
+                if !args.is_empty() {
                     Some(TypeName::Fun(args.len() - 1))
                 } else {
                     None
@@ -696,7 +749,7 @@ impl TypeParamEntry
     pub fn new_with_ident(ident: String) -> Self
     {
         let num = if ident.starts_with("t") {
-            match (&ident[1..]).parse::<usize>() {
+            match ident[1..].parse::<usize>() {
                 Ok(n) => Some(n),
                 Err(_) => None
             }
@@ -1016,35 +1069,30 @@ impl LocalTypes
             let tmp_local_type = LocalType::new(self.type_entries.len());
             self.type_entries.push(LocalTypeEntry::Param(DefinedFlag::Defined, UniqFlag::None, type_param_entry.clone(), tmp_local_type));
             let type_param_entry_r = type_param_entry.borrow();
-            match type_param_entry_r.number {
-                Some(num) => {
-                    self.type_param_numbers.insert(num);
-                },
-                None => (),
+            if let Some(num) = type_param_entry_r.number {
+                self.type_param_numbers.insert(num);
             }
         }
         self.eq_type_param_entries = disjoint_set_vec![EqTypeParamEntry::new_defined(); typ.eq_type_param_set.len()];
         for i in 0..typ.eq_type_param_set.len() {
             for j in (i + 1)..typ.eq_type_param_set.len() {
-                if typ.eq_type_param_set.is_joined(i, j) {
-                    if !self.eq_type_param_entries.is_joined(i, j) {
-                        let eq_root_idx1 = self.eq_type_param_entries.root_of(i);
-                        let eq_root_idx2 = self.eq_type_param_entries.root_of(j);
-                        self.eq_type_param_entries.join(i, j);
-                        let eq_root_idx = self.eq_type_param_entries.root_of(i);
-                        let mut eq_local_types: BTreeSet<LocalType> = self.eq_type_param_entries[eq_root_idx1].local_types.union(&self.eq_type_param_entries[eq_root_idx2].local_types).map(|e| *e).collect();
-                        self.eq_type_param_entries[eq_root_idx1].local_types.clear();
-                        self.eq_type_param_entries[eq_root_idx2].local_types.clear();
-                        eq_local_types.insert(LocalType::new(eq_root_idx1));
-                        eq_local_types.insert(LocalType::new(eq_root_idx2));
-                        if eq_root_idx == eq_root_idx1 {
-                            eq_local_types.remove(&LocalType::new(eq_root_idx1));
-                        }
-                        if eq_root_idx == eq_root_idx2 {
-                            eq_local_types.remove(&LocalType::new(eq_root_idx2));
-                        }
-                        self.eq_type_param_entries[eq_root_idx].local_types = eq_local_types;
+                if typ.eq_type_param_set.is_joined(i, j) && !self.eq_type_param_entries.is_joined(i, j) {
+                    let eq_root_idx1 = self.eq_type_param_entries.root_of(i);
+                    let eq_root_idx2 = self.eq_type_param_entries.root_of(j);
+                    self.eq_type_param_entries.join(i, j);
+                    let eq_root_idx = self.eq_type_param_entries.root_of(i);
+                    let mut eq_local_types: BTreeSet<LocalType> = self.eq_type_param_entries[eq_root_idx1].local_types.union(&self.eq_type_param_entries[eq_root_idx2].local_types).copied().collect();
+                    self.eq_type_param_entries[eq_root_idx1].local_types.clear();
+                    self.eq_type_param_entries[eq_root_idx2].local_types.clear();
+                    eq_local_types.insert(LocalType::new(eq_root_idx1));
+                    eq_local_types.insert(LocalType::new(eq_root_idx2));
+                    if eq_root_idx == eq_root_idx1 {
+                        eq_local_types.remove(&LocalType::new(eq_root_idx1));
+                    }
+                    if eq_root_idx == eq_root_idx2 {
+                        eq_local_types.remove(&LocalType::new(eq_root_idx2));
                     }
+                    self.eq_type_param_entries[eq_root_idx].local_types = eq_local_types;
                 }
             }
         }
@@ -1107,25 +1155,23 @@ impl LocalTypes
             }
             for i in idx..(idx + typ.eq_type_param_set.len()) {
                 for j in (i + 1)..(idx + typ.eq_type_param_set.len()) {
-                    if typ.eq_type_param_set.is_joined(i - idx, j - idx) {
-                        if !self.eq_type_param_entries.is_joined(i, j) {
-                            let eq_root_idx1 = self.eq_type_param_entries.root_of(i);
-                            let eq_root_idx2 = self.eq_type_param_entries.root_of(j);
-                            self.eq_type_param_entries.join(i, j);
-                            let eq_root_idx = self.eq_type_param_entries.root_of(i);
-                            let mut eq_local_types: BTreeSet<LocalType> = self.eq_type_param_entries[eq_root_idx1].local_types.union(&self.eq_type_param_entries[eq_root_idx2].local_types).map(|e| *e).collect();
-                            self.eq_type_param_entries[eq_root_idx1].local_types.clear();
-                            self.eq_type_param_entries[eq_root_idx2].local_types.clear();
-                            eq_local_types.insert(LocalType::new(eq_root_idx1));
-                            eq_local_types.insert(LocalType::new(eq_root_idx2));
-                            if eq_root_idx == eq_root_idx1 {
-                                eq_local_types.remove(&LocalType::new(eq_root_idx1));
-                            }
-                            if eq_root_idx == eq_root_idx2 {
-                                eq_local_types.remove(&LocalType::new(eq_root_idx2));
-                            }
-                            self.eq_type_param_entries[eq_root_idx].local_types = eq_local_types;
+                    if typ.eq_type_param_set.is_joined(i - idx, j - idx) && !self.eq_type_param_entries.is_joined(i, j) {
+                        let eq_root_idx1 = self.eq_type_param_entries.root_of(i);
+                        let eq_root_idx2 = self.eq_type_param_entries.root_of(j);
+                        self.eq_type_param_entries.join(i, j);
+                        let eq_root_idx = self.eq_type_param_entries.root_of(i);
+                        let mut eq_local_types: BTreeSet<LocalType> = self.eq_type_param_entries[eq_root_idx1].local_types.union(&self.eq_type_param_entries[eq_root_idx2].local_types).copied().collect();
+                        self.eq_type_param_entries[eq_root_idx1].local_types.clear();
+                        self.eq_type_param_entries[eq_root_idx2].local_types.clear();
+                        eq_local_types.insert(LocalType::new(eq_root_idx1));
+                        eq_local_types.insert(LocalType::new(eq_root_idx2));
+                        if eq_root_idx == eq_root_idx1 {
+                            eq_local_types.remove(&LocalType::new(eq_root_idx1));
                         }
+                        if eq_root_idx == eq_root_idx2 {
+                            eq_local_types.remove(&LocalType::new(eq_root_idx2));
+                        }
+                        self.eq_type_param_entries[eq_root_idx].local_types = eq_local_types;
                     }
                 }
             }
@@ -1137,9 +1183,8 @@ impl LocalTypes
             for new_type_param_entry in &new_type_param_entries {
                 let mut new_type_param_entry_r = new_type_param_entry.borrow_mut();
                 for type_value in &mut new_type_param_entry_r.type_values {
-                    match type_value.substitute(type_values.as_slice())? {
-                        Some(new_type_value) => *type_value = new_type_value,
-                        None => (),
+                    if let Some(new_type_value) = type_value.substitute(type_values.as_slice())? {
+                        *type_value = new_type_value;
                     }
                 }
             }
@@ -1194,12 +1239,9 @@ impl LocalTypes
     {
         if local_type.index() < self.type_entries.len() {
             let root_idx = self.type_entries.root_of(local_type.index());
-            match &*type_value {
-                TypeValue::Type(_, type_value_name, _) => {
-                    let eq_root_idx = self.eq_type_param_entries.root_of(local_type.index());
-                    self.eq_type_param_entries[eq_root_idx].type_value_name = Some(type_value_name.clone());
-                },
-                _ => (),
+            if let TypeValue::Type(_, type_value_name, _) = &*type_value {
+                let eq_root_idx = self.eq_type_param_entries.root_of(local_type.index());
+                self.eq_type_param_entries[eq_root_idx].type_value_name = Some(type_value_name.clone());
             }
             self.type_entries[root_idx] = LocalTypeEntry::Type(type_value);
             true
@@ -1212,13 +1254,10 @@ impl LocalTypes
     {
         if local_type.index() < self.type_entries.len() {
             let root_idx = self.type_entries.root_of(local_type.index());
-            match &self.type_entries[root_idx] {
-                LocalTypeEntry::Param(_, _, _, _) => {
-                    if local_type.index() != root_idx {
-                        self.type_entries[local_type.index()] = LocalTypeEntry::Type(Rc::new(TypeValue::Param(UniqFlag::None, LocalType::new(root_idx))));
-                    }
-                },
-                _ => (),
+            if let LocalTypeEntry::Param(_, _, _, _) = &self.type_entries[root_idx] {
+                if local_type.index() != root_idx {
+                    self.type_entries[local_type.index()] = LocalTypeEntry::Type(Rc::new(TypeValue::Param(UniqFlag::None, LocalType::new(root_idx))));
+                }
             }
             match &self.type_entries[root_idx] {
                 LocalTypeEntry::Param(defined_flag, uniq_flag, type_param_entry, _) => {
@@ -1345,28 +1384,19 @@ impl LocalTypes
             self.eq_type_param_entries.join(local_type1.index(), local_type2.index());
             let root_idx = self.type_entries.root_of(local_type1.index());
             let eq_root_idx = self.eq_type_param_entries.root_of(local_type1.index());
-            let mut eq_local_types: BTreeSet<LocalType> = self.eq_type_param_entries[eq_root_idx1].local_types.union(&self.eq_type_param_entries[eq_root_idx2].local_types).map(|e| *e).collect();
+            let mut eq_local_types: BTreeSet<LocalType> = self.eq_type_param_entries[eq_root_idx1].local_types.union(&self.eq_type_param_entries[eq_root_idx2].local_types).copied().collect();
             self.eq_type_param_entries[eq_root_idx1].local_types.clear();
             self.eq_type_param_entries[eq_root_idx2].local_types.clear();
-            match &self.type_entries[self.type_entries.root_of(eq_root_idx1)] {
-                LocalTypeEntry::Param(_, _, _, _) => {
-                    eq_local_types.insert(LocalType::new(self.type_entries.root_of(eq_root_idx1)));
-                },
-                _ => (),
+            if let LocalTypeEntry::Param(_, _, _, _) = &self.type_entries[self.type_entries.root_of(eq_root_idx1)] {
+                eq_local_types.insert(LocalType::new(self.type_entries.root_of(eq_root_idx1)));
             }
-            match &self.type_entries[self.type_entries.root_of(eq_root_idx2)] {
-                LocalTypeEntry::Param(_, _, _, _) => {
-                    eq_local_types.insert(LocalType::new(self.type_entries.root_of(eq_root_idx2)));
-                },
-                _ => (),
+            if let LocalTypeEntry::Param(_, _, _, _) = &self.type_entries[self.type_entries.root_of(eq_root_idx2)] {
+                eq_local_types.insert(LocalType::new(self.type_entries.root_of(eq_root_idx2)));
             }
             eq_local_types.remove(&LocalType::new(root_idx1));
             eq_local_types.remove(&LocalType::new(root_idx2));
-            match &self.type_entries[root_idx] {
-                LocalTypeEntry::Param(_, _, _, _) => {
-                    eq_local_types.insert(LocalType::new(root_idx));
-                },
-                _ => (),
+            if let LocalTypeEntry::Param(_, _, _, _) = &self.type_entries[root_idx] {
+                eq_local_types.insert(LocalType::new(root_idx));
             }
             eq_local_types.remove(&LocalType::new(self.type_entries.root_of(eq_root_idx)));
             self.eq_type_param_entries[eq_root_idx].local_types = eq_local_types;
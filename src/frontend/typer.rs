@@ -13,7 +13,9 @@ use crate::frontend::builtins::*;
 use crate::frontend::error::*;
 use crate::frontend::namer::*;
 use crate::frontend::parser::*;
+use crate::frontend::printf::*;
 use crate::frontend::private::*;
+use crate::frontend::resolver::*;
 use crate::frontend::tree::*;
 use crate::frontend::type_matcher::*;
 use crate::utils::dfs::*;
@@ -59,7 +61,7 @@ fn expr_pos(expr: &Expr) -> &Pos
 fn add_error(err: FrontendError, errs2: &mut Vec<FrontendError>) -> FrontendResultWithErrors<()>
 {
     match err {
-        FrontendError::Internal(msg) => return Err(FrontendErrors::new(vec![FrontendError::Internal(msg.clone())])),
+        FrontendError::Internal(msg) => Err(FrontendErrors::new(vec![FrontendError::Internal(msg.clone())])),
         _ => {
             errs2.push(err);
             Ok(())
@@ -70,9 +72,8 @@ fn add_error(err: FrontendError, errs2: &mut Vec<FrontendError>) -> FrontendResu
 fn add_errors(errs: &mut FrontendErrors, errs2: &mut Vec<FrontendError>) -> FrontendResultWithErrors<()>
 {
     for err in errs.errors() {
-        match err {
-            FrontendError::Internal(msg) => return Err(FrontendErrors::new(vec![FrontendError::Internal(msg.clone())])),
-            _ => (),
+        if let FrontendError::Internal(msg) = err {
+            return Err(FrontendErrors::new(vec![FrontendError::Internal(msg.clone())]));
         }
     }
     errs.append_to(errs2);
@@ -347,9 +348,8 @@ fn add_local_type_for_substitution(local_type: LocalType, type_values: &[Rc<Type
 {
     if !processed_local_types.contains(&local_type) {
         if local_type.index() < type_values.len() {
-            match &*type_values[local_type.index()] {
-                TypeValue::Type(_, _, _) => local_types.push(local_type),
-                _ => (),
+            if let TypeValue::Type(_, _, _) = &*type_values[local_type.index()] {
+                local_types.push(local_type);
             }
             Ok(())
         } else {
@@ -386,6 +386,19 @@ fn type_for_var_ident_in<T, F>(ident: &String, tree: &Tree, rec_pair: Option<(&S
     }
 }
 
+fn is_nul_terminated_string_expr(expr: &Expr, tree: &Tree, var_env: &Environment<()>, rec_pair: Option<(&String, &Type)>) -> bool
+{
+    match expr {
+        Expr::Var(ident, _, _) => {
+            match rec_pair {
+                Some((rec_ident, _)) if ident == rec_ident => false,
+                _ => var_env.var(ident).is_none() && is_string_literal_var(ident, tree),
+            }
+        },
+        _ => is_string_literal_expr(expr),
+    }
+}
+
 fn type_and_named_fields_for_con_ident_in<T, F>(ident: &String, tree: &Tree, mut f: F) -> FrontendResultWithErrors<T>
     where F: FnMut(&Type, &NamedFields) -> FrontendResultWithErrors<T>
 {
@@ -398,7 +411,7 @@ fn type_and_named_fields_for_con_ident_in<T, F>(ident: &String, tree: &Tree, mut
                         Fun::Con(con) => {
                             let con_r = con.borrow();
                             match &*con_r {
-                                Con::NamedField(_, _, _, Some(named_fields), _) => f(&**typ, &**named_fields),
+                                Con::NamedField(_, _, _, _, Some(named_fields), _) => f(typ, named_fields),
                                 _ => Err(FrontendErrors::new(vec![FrontendError::Internal(String::from("type_and_named_fields_for_con_ident_in: constructor isn't named field contructor or no named fields"))])),
                             }
                         },
@@ -442,10 +455,8 @@ impl ClosureStack
             let mut new_local_types = self.stack[self.stack.len() - 2].0.clone();
             let new_stack_idx = self.stack[self.stack.len() - 2].1;
             for (key @ (_, stack_idx), local_type) in local_types {
-                if *stack_idx < new_stack_idx {
-                    if !new_local_types.contains_key(key) {
-                        new_local_types.insert(key.clone(), *local_type);
-                    }
+                if *stack_idx < new_stack_idx && !new_local_types.contains_key(key) {
+                    new_local_types.insert(key.clone(), *local_type);
                 }
             }
             let len = self.stack.len();
@@ -472,13 +483,10 @@ impl ClosureStack
     pub fn foreach_with_result<E, F>(&self, mut f: F) -> Result<(), E>
         where F: FnMut(&(String, usize), LocalType) -> Result<(), E>
     {
-        match self.stack.last() {
-            Some((local_types, _)) => {
-                for (key, local_type) in local_types {
-                    f(key, *local_type)?;
-                }
-            },
-            None => (),
+        if let Some((local_types, _)) = self.stack.last() {
+            for (key, local_type) in local_types {
+                f(key, *local_type)?;
+            }
         }
         Ok(())
     }
@@ -710,12 +718,9 @@ impl Typer
     fn evaluate_type_args_for_builtin_type_defs(&self, tree: &Tree, errs: &mut Vec<FrontendError>) -> FrontendResultWithErrors<()>
     {
         for def in tree.defs() {
-            match &**def {
-                Def::Type(ident, type_var, pos) => {
-                    let mut type_var_r = type_var.borrow_mut();
-                    self.evaluate_type_args_for_builtin_type(ident, &mut *type_var_r, pos.clone(), errs)?;
-                },
-                _ => (),
+            if let Def::Type(_, ident, type_var, pos) = &**def {
+                let mut type_var_r = type_var.borrow_mut();
+                self.evaluate_type_args_for_builtin_type(ident, &mut type_var_r, pos.clone(), errs)?;
             }
         }
         Ok(())
@@ -723,32 +728,29 @@ impl Typer
 
     fn evaluate_type_args_for_builtin_type(&self, ident: &String, type_var: &mut TypeVar, pos: Pos, errs: &mut Vec<FrontendError>) -> FrontendResultWithErrors<()>
     {
-        match type_var {
-            TypeVar::Builtin(type_args, _, _) => {
-                match self.builtins.type_var(ident) {
-                    Some(builtin_type_var) => {
-                        match parse_type_args_with_path(format!("({} type args).vscfl", ident).as_str(), builtin_type_var.type_arg_source.as_str()) {
-                            Ok(type_args2) => {
-                                match check_idents_for_type_args(type_args2.as_slice()) {
-                                    Ok(()) => {
-                                        let mut new_type_args = TypeArgs::new();
-                                        for type_arg2 in type_args2 {
-                                            match type_arg2 {
-                                                TypeArg(type_arg_ident, _) => new_type_args.add_type_arg_ident(type_arg_ident),
-                                            }
+        if let TypeVar::Builtin(type_args, _, _) = type_var {
+            match self.builtins.type_var(ident) {
+                Some(builtin_type_var) => {
+                    match parse_type_args_with_path(format!("({} type args).vscfl", ident).as_str(), builtin_type_var.type_arg_source.as_str()) {
+                        Ok(type_args2) => {
+                            match check_idents_for_type_args(type_args2.as_slice()) {
+                                Ok(()) => {
+                                    let mut new_type_args = TypeArgs::new();
+                                    for type_arg2 in type_args2 {
+                                        match type_arg2 {
+                                            TypeArg(type_arg_ident, _) => new_type_args.add_type_arg_ident(type_arg_ident),
                                         }
-                                        *type_args = Some(Box::new(new_type_args));
-                                    },
-                                    Err(mut errs2) => add_errors(&mut errs2, errs)?,
-                                }
-                            },
-                            Err(err) => add_error(err, errs)?,
-                        }
-                    },
-                    None => errs.push(FrontendError::Message(pos, format!("type variable {} mustn't be built-in type variable", ident))),
-                }
-            },
-            _ => (),
+                                    }
+                                    *type_args = Some(Box::new(new_type_args));
+                                },
+                                Err(mut errs2) => add_errors(&mut errs2, errs)?,
+                            }
+                        },
+                        Err(err) => add_error(err, errs)?,
+                    }
+                },
+                None => errs.push(FrontendError::Message(pos, format!("type variable {} mustn't be built-in type variable", ident))),
+            }
         }
         Ok(())
     }
@@ -757,9 +759,8 @@ impl Typer
     {
         let mut visited_idents: BTreeSet<String> = BTreeSet::new();
         for def in tree.defs() {
-            match &**def {
-                Def::Type(ident, type_var, _) => self.evaluate_types_for_type_synonym(ident, type_var, &mut visited_idents, tree, errs)?,
-                _ => (),
+            if let Def::Type(_, ident, type_var, _) = &**def {
+                self.evaluate_types_for_type_synonym(ident, type_var, &mut visited_idents, tree, errs)?;
             }
         }
         Ok(())
@@ -787,9 +788,8 @@ impl Typer
     fn evaluate_types_for_type_defs(&self, tree: &Tree, errs: &mut Vec<FrontendError>) -> FrontendResultWithErrors<()>
     {
         for def in tree.defs() {
-            match &**def {
-                Def::Type(ident, type_var, pos) => self.evaluate_types_for_type(ident, type_var, pos.clone(), tree, errs)?,
-                _ => (),
+            if let Def::Type(_, ident, type_var, pos) = &**def {
+                self.evaluate_types_for_type(ident, type_var, pos.clone(), tree, errs)?;
             }
         }
         Ok(())
@@ -800,21 +800,17 @@ impl Typer
         let mut rec_idents: BTreeSet<String> = BTreeSet::new();
         let mut visited_idents: BTreeSet<String> = BTreeSet::new();
         for def in tree.defs() {
-            match &**def {
-                Def::Type(ident, type_var, _) => self.evaluate_shared_flags_for_type(ident, &type_var, &mut visited_idents, tree, &mut rec_idents)?,
-                _ => (),
+            if let Def::Type(_, ident, type_var, _) = &**def {
+                self.evaluate_shared_flags_for_type(ident, type_var, &mut visited_idents, tree, &mut rec_idents)?;
             }
         }
         let mut rec_idents2: BTreeSet<String> = BTreeSet::new();
         let mut visited_idents2: BTreeSet<String> = BTreeSet::new();
         for def in tree.defs() {
-            match &**def {
-                Def::Type(ident, type_var, _) => {
-                    if rec_idents.contains(ident) {
-                        self.evaluate_shared_flags_for_type(ident, &type_var, &mut visited_idents2, tree, &mut rec_idents2)?;
-                    }
-                },
-                _ => (),
+            if let Def::Type(_, ident, type_var, _) = &**def {
+                if rec_idents.contains(ident) {
+                    self.evaluate_shared_flags_for_type(ident, type_var, &mut visited_idents2, tree, &mut rec_idents2)?;
+                }
             }
         }
         Ok(())
@@ -824,9 +820,8 @@ impl Typer
     {
         let mut visited_idents: BTreeSet<String> = BTreeSet::new();
         for def in tree.defs() {
-            match &**def {
-                Def::Type(ident, type_var, _) => self.check_type_recursions_for_data(ident, type_var, &mut visited_idents, tree, errs)?,
-                _ => (),
+            if let Def::Type(_, ident, type_var, _) = &**def {
+                self.check_type_recursions_for_data(ident, type_var, &mut visited_idents, tree, errs)?;
             }
         }
         Ok(())
@@ -839,41 +834,38 @@ impl Typer
             let type_var_r = type_var.borrow();
             match &*type_var_r {
                 TypeVar::Builtin(Some(type_args), _, _) => {
-                    match self.builtins.type_var(ident) {
-                        Some(builtin_type_var) => {
-                            let mut type_param_env: Environment<LocalType> = Environment::new();
-                            type_param_env.push_new_vars();
-                            let mut type_arg_idents: Vec<String> = Vec::new();
-                            for (i, type_arg_ident) in type_args.type_arg_idents().iter().enumerate() {
-                                type_param_env.add_var(type_arg_ident.clone(), LocalType::new(i));
-                                type_arg_idents.push(type_arg_ident.clone());
-                            }
-                            let mut new_fields = Fields::new();
-                            let mut is_success = true;
-                            for (i, field_src) in builtin_type_var.field_type_sources.iter().enumerate() {
-                                match parse_type_with_path(format!("({} field {}).vscfl", ident, i).as_str(), field_src) {
-                                    Ok(type_expr) => {
-                                        match check_idents_for_type_with_type_args(&type_expr, type_arg_idents.as_slice(), tree) {
-                                            Ok(()) => {
-                                                match self.evaluate_type_for_type_expr(&type_expr, tree, &mut type_param_env, &mut None, errs)? {
-                                                    Some(type_value) => new_fields.add_field_type_value(type_value),
-                                                    None => is_success = false, 
-                                                }
-                                            },
-                                            Err(mut errs2) => add_errors(&mut errs2, errs)?,
-                                        }
-                                    },
-                                    Err(err) => add_error(err, errs)?,
-                                }
+                    if let Some(builtin_type_var) = self.builtins.type_var(ident) {
+                        let mut type_param_env: Environment<LocalType> = Environment::new();
+                        type_param_env.push_new_vars();
+                        let mut type_arg_idents: Vec<String> = Vec::new();
+                        for (i, type_arg_ident) in type_args.type_arg_idents().iter().enumerate() {
+                            type_param_env.add_var(type_arg_ident.clone(), LocalType::new(i));
+                            type_arg_idents.push(type_arg_ident.clone());
+                        }
+                        let mut new_fields = Fields::new();
+                        let mut is_success = true;
+                        for (i, field_src) in builtin_type_var.field_type_sources.iter().enumerate() {
+                            match parse_type_with_path(format!("({} field {}).vscfl", ident, i).as_str(), field_src) {
+                                Ok(type_expr) => {
+                                    match check_idents_for_type_with_type_args(&type_expr, type_arg_idents.as_slice(), tree) {
+                                        Ok(()) => {
+                                            match self.evaluate_type_for_type_expr(&type_expr, tree, &mut type_param_env, &mut None, errs)? {
+                                                Some(type_value) => new_fields.add_field_type_value(type_value),
+                                                None => is_success = false, 
+                                            }
+                                        },
+                                        Err(mut errs2) => add_errors(&mut errs2, errs)?,
+                                    }
+                                },
+                                Err(err) => add_error(err, errs)?,
                             }
-                            if is_success {
-                                for (field_ident, i) in &builtin_type_var.field_indices {
-                                    new_fields.add_field_index(field_ident.clone(), *i);
-                                }
-                                new_opt_fields = Some(Box::new(new_fields));
+                        }
+                        if is_success {
+                            for (field_ident, i) in &builtin_type_var.field_indices {
+                                new_fields.add_field_index(field_ident.clone(), *i);
                             }
-                        },
-                        None => (),
+                            new_opt_fields = Some(Box::new(new_fields));
+                        }
                     }
                 },
                 TypeVar::Builtin(None, _, _) => errs.push(FrontendError::Message(pos, format!("built-in type {} hasn't evaluated type arguments", ident))),
@@ -892,14 +884,14 @@ impl Typer
                         }
                     }
                     let ret_type_value = Rc::new(TypeValue::Type(UniqFlag::None, TypeValueName::Name(ident.clone()), tmp_type_values));
-                    for con in &*cons {
+                    for con in cons {
                         let con_r = con.borrow();
                         let pair = match &*con_r {
-                            Con::UnnamedField(con_ident, field_type_exprs, _, _) => {
+                            Con::UnnamedField(_, con_ident, field_type_exprs, _, _) => {
                                 let mut type_values: Vec<Rc<TypeValue>> = Vec::new();
                                 let mut is_success = true;
                                 for field_type_expr in field_type_exprs {
-                                    match self.evaluate_type_for_type_expr(&**field_type_expr, tree, &mut type_param_env, &mut None, errs)? {
+                                    match self.evaluate_type_for_type_expr(field_type_expr, tree, &mut type_param_env, &mut None, errs)? {
                                         Some(type_value) => type_values.push(type_value),
                                         None => is_success = false, 
                                     }
@@ -910,13 +902,13 @@ impl Typer
                                     None
                                 }
                             },
-                            Con::NamedField(con_ident, type_expr_named_field_pairs, _, _, _) => {
+                            Con::NamedField(_, con_ident, type_expr_named_field_pairs, _, _, _) => {
                                 let mut type_values: Vec<Rc<TypeValue>> = Vec::new();
                                 let mut is_success = true;
                                 for type_expr_named_field_pair in type_expr_named_field_pairs {
                                     match type_expr_named_field_pair {
                                         NamedFieldPair(_, field_type_expr, _) => {
-                                            match self.evaluate_type_for_type_expr(&**field_type_expr, tree, &mut type_param_env, &mut None, errs)? {
+                                            match self.evaluate_type_for_type_expr(field_type_expr, tree, &mut type_param_env, &mut None, errs)? {
                                                 Some(type_value) => type_values.push(type_value),
                                                 None => is_success = false, 
                                             }
@@ -930,24 +922,21 @@ impl Typer
                                 }
                             }
                         };
-                        match pair {
-                            Some((con_ident, mut type_values)) => {
-                                match tree.var(&con_ident) {
-                                    Some(var) => {
-                                        let mut var_r = var.borrow_mut();
-                                        match &mut *var_r {
-                                            Var::Fun(_, _, typ) => {
-                                                type_values.push(ret_type_value.clone());
-                                                let type_value = Rc::new(TypeValue::Type(UniqFlag::None, TypeValueName::Fun, type_values));
-                                                *typ = Some(Box::new(Type::new(type_value, type_arg_idents.as_slice())));
-                                            },
-                                            _ => return Err(FrontendErrors::new(vec![FrontendError::Internal(String::from("evaluate_types_for_type: variable isn't function"))])),
-                                        }
-                                    },
-                                    None => return Err(FrontendErrors::new(vec![FrontendError::Internal(String::from("evaluate_types_for_type: no variable"))])),
-                                }
-                            },
-                            None => (),
+                        if let Some((con_ident, mut type_values)) = pair {
+                            match tree.var(&con_ident) {
+                                Some(var) => {
+                                    let mut var_r = var.borrow_mut();
+                                    match &mut *var_r {
+                                        Var::Fun(_, _, typ) => {
+                                            type_values.push(ret_type_value.clone());
+                                            let type_value = Rc::new(TypeValue::Type(UniqFlag::None, TypeValueName::Fun, type_values));
+                                            *typ = Some(Box::new(Type::new(type_value, type_arg_idents.as_slice())));
+                                        },
+                                        _ => return Err(FrontendErrors::new(vec![FrontendError::Internal(String::from("evaluate_types_for_type: variable isn't function"))])),
+                                    }
+                                },
+                                None => return Err(FrontendErrors::new(vec![FrontendError::Internal(String::from("evaluate_types_for_type: no variable"))])),
+                            }
                         }
                     }
                 },
@@ -956,9 +945,8 @@ impl Typer
         }
         {
             let mut type_var_r = type_var.borrow_mut();
-            match &mut *type_var_r {
-                TypeVar::Builtin(_, fields, _) => *fields = new_opt_fields,
-                _ => (),
+            if let TypeVar::Builtin(_, fields, _) = &mut *type_var_r {
+                *fields = new_opt_fields;
             }
         }
         Ok(())
@@ -972,7 +960,7 @@ impl Typer
                 match &*type_var_r {
                     TypeVar::Synonym(_, type_expr, _) => {
                         let mut idents: Vec<String> = Vec::new();
-                        self.add_type_synonym_idents_for_type_expr(&**type_expr, tree, &mut idents, processed_idents, errs)?;
+                        self.add_type_synonym_idents_for_type_expr(type_expr, tree, &mut idents, processed_idents, errs)?;
                         Ok(idents)
                     },
                     _ => Err(FrontendErrors::new(vec![FrontendError::Internal(String::from("type_synonym_idents_for_type_synonym_ident: type variable isn't type synonym"))])),
@@ -987,25 +975,25 @@ impl Typer
         match type_expr {
             TypeExpr::Tuple(field_type_exprs, _) => {
                 for field_type_expr in field_type_exprs {
-                    self.add_type_synonym_idents_for_type_expr(&**field_type_expr, tree, idents, processed_idents, errs)?;
+                    self.add_type_synonym_idents_for_type_expr(field_type_expr, tree, idents, processed_idents, errs)?;
                 }
             },
             TypeExpr::Fun(arg_type_exprs, ret_type_expr, _) => {
                 for arg_type_expr in arg_type_exprs {
-                    self.add_type_synonym_idents_for_type_expr(&**arg_type_expr, tree, idents, processed_idents, errs)?;
+                    self.add_type_synonym_idents_for_type_expr(arg_type_expr, tree, idents, processed_idents, errs)?;
                 }
-                self.add_type_synonym_idents_for_type_expr(&**ret_type_expr, tree, idents, processed_idents, errs)?
+                self.add_type_synonym_idents_for_type_expr(ret_type_expr, tree, idents, processed_idents, errs)?
             },
-            TypeExpr::Array(elem_type_expr, _, _) => self.add_type_synonym_idents_for_type_expr(&**elem_type_expr, tree, idents, processed_idents, errs)?,
+            TypeExpr::Array(elem_type_expr, _, _) => self.add_type_synonym_idents_for_type_expr(elem_type_expr, tree, idents, processed_idents, errs)?,
             TypeExpr::Param(_, _) => (),
             TypeExpr::Var(ident, pos) => add_type_synonym_ident(ident, pos.clone(), tree, idents, processed_idents, errs)?,
             TypeExpr::App(ident, type_exprs, pos) => {
                 add_type_synonym_ident(ident, pos.clone(), tree, idents, processed_idents, errs)?;
                 for type_expr2 in type_exprs {
-                    self.add_type_synonym_idents_for_type_expr(&**type_expr2, tree, idents, processed_idents, errs)?;
+                    self.add_type_synonym_idents_for_type_expr(type_expr2, tree, idents, processed_idents, errs)?;
                 }
             },
-            TypeExpr::Uniq(type_expr2, _) => self.add_type_synonym_idents_for_type_expr(&**type_expr2, tree, idents, processed_idents, errs)?,
+            TypeExpr::Uniq(type_expr2, _) => self.add_type_synonym_idents_for_type_expr(type_expr2, tree, idents, processed_idents, errs)?,
         }
         Ok(())
     }
@@ -1028,7 +1016,7 @@ impl Typer
                                     },
                                 }
                             }
-                            new_opt_type_value = self.evaluate_type_for_type_expr(&**type_expr, tree, &mut type_param_env, &mut None, errs)?;
+                            new_opt_type_value = self.evaluate_type_for_type_expr(type_expr, tree, &mut type_param_env, &mut None, errs)?;
                         },
                         _ => return Err(FrontendErrors::new(vec![FrontendError::Internal(String::from("evaluate_type_for_type_synonym_ident: type variable isn't type synonym"))])),
                     }
@@ -1055,7 +1043,7 @@ impl Typer
                 let mut type_values: Vec<Rc<TypeValue>> = Vec::new();
                 let mut is_success = true;
                 for field_type_expr in field_type_exprs {
-                    match self.evaluate_type_for_type_expr(&**field_type_expr, tree, type_param_env, local_type_counter, errs)? {
+                    match self.evaluate_type_for_type_expr(field_type_expr, tree, type_param_env, local_type_counter, errs)? {
                         Some(type_value) => type_values.push(type_value),
                         None => is_success = false,
                     }
@@ -1070,12 +1058,12 @@ impl Typer
                 let mut type_values: Vec<Rc<TypeValue>> = Vec::new();
                 let mut is_success = true;
                 for arg_type_expr in arg_type_exprs {
-                    match self.evaluate_type_for_type_expr(&**arg_type_expr, tree, type_param_env, local_type_counter, errs)? {
+                    match self.evaluate_type_for_type_expr(arg_type_expr, tree, type_param_env, local_type_counter, errs)? {
                         Some(type_value) => type_values.push(type_value),
                         None => is_success = false,
                     }
                 }
-                match self.evaluate_type_for_type_expr(&**ret_type_expr, tree, type_param_env, local_type_counter, errs)? {
+                match self.evaluate_type_for_type_expr(ret_type_expr, tree, type_param_env, local_type_counter, errs)? {
                     Some(type_value) => type_values.push(type_value),
                     None => is_success = false,
                 }
@@ -1088,7 +1076,7 @@ impl Typer
             TypeExpr::Array(elem_type_expr, len, _) => {
                 let mut type_values: Vec<Rc<TypeValue>> = Vec::new();
                 let mut is_success = true;
-                match self.evaluate_type_for_type_expr(&**elem_type_expr, tree, type_param_env, local_type_counter, errs)? {
+                match self.evaluate_type_for_type_expr(elem_type_expr, tree, type_param_env, local_type_counter, errs)? {
                     Some(type_value) => type_values.push(type_value),
                     None => is_success = false,
                 }
@@ -1122,16 +1110,16 @@ impl Typer
                 match type_value_and_type_arg_count_for_type_var_ident(ident, pos.clone(), tree, errs)? {
                     Some((type_value, type_arg_count)) => {
                         if type_exprs.len() < type_arg_count {
-                            errs.push(FrontendError::Message(pos.clone(), format!("too few type arguments")));
+                            errs.push(FrontendError::Message(pos.clone(), "too few type arguments".to_string()));
                             return Ok(None);
                         } else if type_exprs.len() > type_arg_count {
-                            errs.push(FrontendError::Message(pos.clone(), format!("too many type arguments")));
+                            errs.push(FrontendError::Message(pos.clone(), "too many type arguments".to_string()));
                             return Ok(None);
                         }
                         let mut type_values: Vec<Rc<TypeValue>> = Vec::new();
                         let mut is_success = true;
                         for type_expr2 in type_exprs {
-                            match self.evaluate_type_for_type_expr(&**type_expr2, tree, type_param_env, local_type_counter, errs)? {
+                            match self.evaluate_type_for_type_expr(type_expr2, tree, type_param_env, local_type_counter, errs)? {
                                 Some(type_value) => type_values.push(type_value),
                                 None => is_success = false,
                             }
@@ -1150,7 +1138,7 @@ impl Typer
                 }
             },
             TypeExpr::Uniq(type_expr2, _) => {
-                match self.evaluate_type_for_type_expr(&**type_expr2, tree, type_param_env, local_type_counter, errs)? {
+                match self.evaluate_type_for_type_expr(type_expr2, tree, type_param_env, local_type_counter, errs)? {
                     Some(type_value) => {
                         if type_value.uniq_flag() == UniqFlag::None {
                             let mut type_value3 = (*type_value).clone();
@@ -1203,8 +1191,8 @@ impl Typer
         for con in &*cons {
             let con_r = con.borrow();
             let con_ident = match &*con_r {
-                Con::UnnamedField(tmp_con_ident, _, _, _) => tmp_con_ident.clone(),
-                Con::NamedField(tmp_con_ident, _, _, _, _) => tmp_con_ident.clone(),
+                Con::UnnamedField(_, tmp_con_ident, _, _, _) => tmp_con_ident.clone(),
+                Con::NamedField(_, tmp_con_ident, _, _, _, _) => tmp_con_ident.clone(),
             };
             match tree.var(&con_ident) {
                 Some(var) => {
@@ -1213,9 +1201,9 @@ impl Typer
                         Var::Fun(_, _, Some(typ)) => {
                             match &**typ.type_value() {
                                 TypeValue::Type(UniqFlag::None, TypeValueName::Fun, type_values) => {
-                                    if type_values.len() >= 1 {
+                                    if !type_values.is_empty() {
                                         for type_value2 in &type_values[0..(type_values.len() - 1)]  {
-                                            self.add_shared_type_idents_for_type_value(&**type_value2, tree, &mut idents, rec_idents, processed_idents)?
+                                            self.add_shared_type_idents_for_type_value(type_value2, tree, &mut idents, rec_idents, processed_idents)?
                                         }
                                     } else {
                                         return Err(FrontendErrors::new(vec![FrontendError::Internal(String::from("shared_type_idents_for_type_ident: too few type values"))]))
@@ -1236,17 +1224,13 @@ impl Typer
     
     fn add_shared_type_idents_for_type_value(&self, type_value: &TypeValue, tree: &Tree, idents: &mut Vec<String>, rec_idents: &mut BTreeSet<String>, processed_idents: &BTreeSet<String>) -> FrontendResultWithErrors<()>
     {
-        match type_value {
-            TypeValue::Type(UniqFlag::None, type_value_name, type_values) => {
-                match type_value_name {
-                    TypeValueName::Name(ident) => add_type_ident(ident, tree, idents, rec_idents, processed_idents)?,
-                    _ => (),
-                }
-                for type_value2 in type_values {
-                    self.add_shared_type_idents_for_type_value(&**type_value2, tree, idents, rec_idents, processed_idents)?;
-                }
-            },
-            _ => (),
+        if let TypeValue::Type(UniqFlag::None, type_value_name, type_values) = type_value {
+            if let TypeValueName::Name(ident) = type_value_name {
+                add_type_ident(ident, tree, idents, rec_idents, processed_idents)?;
+            }
+            for type_value2 in type_values {
+                self.add_shared_type_idents_for_type_value(type_value2, tree, idents, rec_idents, processed_idents)?;
+            }
         }
         Ok(())
     }
@@ -1260,20 +1244,19 @@ impl Typer
                     let type_var_r = type_var.borrow();
                     match &*type_var_r {
                         TypeVar::Builtin(_, _, _) => {
-                            match self.builtins.type_var(ident) {
-                                Some(builtin_type_var) => new_opt_shared_flag = Some(builtin_type_var.shared_flag),
-                                None => (),
+                            if let Some(builtin_type_var) = self.builtins.type_var(ident) {
+                                new_opt_shared_flag = Some(builtin_type_var.shared_flag);
                             }
                         },
                         TypeVar::Data(_, cons, _) => {
                             let mut new_shared_flag = SharedFlag::Shared;
                             let mut is_success = true;
                             let mut is_rec = false;
-                            for con in &*cons {
+                            for con in cons {
                                 let con_r = con.borrow();
                                 let con_ident = match &*con_r {
-                                    Con::UnnamedField(tmp_con_ident, _, _, _) => tmp_con_ident.clone(),
-                                    Con::NamedField(tmp_con_ident, _, _, _, _) => tmp_con_ident.clone(),
+                                    Con::UnnamedField(_, tmp_con_ident, _, _, _) => tmp_con_ident.clone(),
+                                    Con::NamedField(_, tmp_con_ident, _, _, _, _) => tmp_con_ident.clone(),
                                 };
                                 match tree.var(&con_ident) {
                                     Some(var) => {
@@ -1282,9 +1265,9 @@ impl Typer
                                             Var::Fun(_, _, Some(typ)) => {
                                                 match &**typ.type_value() {
                                                     TypeValue::Type(UniqFlag::None, TypeValueName::Fun, type_values) => {
-                                                        if type_values.len() >= 1 {
+                                                        if !type_values.is_empty() {
                                                             for type_value2 in &type_values[0..(type_values.len() - 1)]  {
-                                                                match self.evaluate_shared_flag_for_type_value(&**type_value2, tree, rec_idents, &mut is_rec)? {
+                                                                match self.evaluate_shared_flag_for_type_value(type_value2, tree, rec_idents, &mut is_rec)? {
                                                                     Some(shared_flag2) => {
                                                                         if shared_flag2 == SharedFlag::None {
                                                                             new_shared_flag = SharedFlag::None;
@@ -1358,7 +1341,7 @@ impl Typer
                         let mut is_success = true;
                         if shared_flag == SharedFlag::Shared {
                             for type_value2 in type_values {
-                                match self.evaluate_shared_flag_for_type_value(&**type_value2, tree, rec_idents, is_rec)? {
+                                match self.evaluate_shared_flag_for_type_value(type_value2, tree, rec_idents, is_rec)? {
                                     Some(shared_flag2) => {
                                         if shared_flag2 == SharedFlag::None {
                                             shared_flag = SharedFlag::None;
@@ -1406,21 +1389,21 @@ impl Typer
                 match &*type_var_r {
                     TypeVar::Data(_, cons, _) => {
                         let mut idents: Vec<String> = Vec::new();
-                        for con in &*cons {
+                        for con in cons {
                             let con_r = con.borrow();
                             let (con_ident, poses) = match &*con_r {
-                                Con::UnnamedField(tmp_con_ident, field_type_exprs, _, _) => {
+                                Con::UnnamedField(_, tmp_con_ident, field_type_exprs, _, _) => {
                                     let mut tmp_poses: Vec<Pos> = Vec::new();
                                     for field_type_expr in field_type_exprs {
-                                        tmp_poses.push(type_expr_pos(&**field_type_expr).clone());
+                                        tmp_poses.push(type_expr_pos(field_type_expr).clone());
                                     }
                                     (tmp_con_ident.clone(), tmp_poses)
                                 },
-                                Con::NamedField(tmp_con_ident, type_expr_named_field_pairs, _, _, _) => {
+                                Con::NamedField(_, tmp_con_ident, type_expr_named_field_pairs, _, _, _) => {
                                     let mut tmp_poses: Vec<Pos> = Vec::new();
                                     for type_expr_named_field_pair in type_expr_named_field_pairs {
                                         match type_expr_named_field_pair {
-                                            NamedFieldPair(_, field_type_expr, _) => tmp_poses.push(type_expr_pos(&**field_type_expr).clone()),
+                                            NamedFieldPair(_, field_type_expr, _) => tmp_poses.push(type_expr_pos(field_type_expr).clone()),
                                         }
                                     }
                                     (tmp_con_ident.clone(), tmp_poses)
@@ -1433,9 +1416,9 @@ impl Typer
                                         Var::Fun(_, _, Some(typ)) => {
                                             match &**typ.type_value() {
                                                 TypeValue::Type(UniqFlag::None, TypeValueName::Fun, type_values) => {
-                                                    if type_values.len() >= 1 {
-                                                        for (type_value2, pos) in (&type_values[0..(type_values.len() - 1)]).iter().zip(poses.iter())  {
-                                                            self.add_data_type_idents_for_type_value(&**type_value2, &pos, tree, &mut idents, processed_idents, errs)?
+                                                    if !type_values.is_empty() {
+                                                        for (type_value2, pos) in type_values[0..(type_values.len() - 1)].iter().zip(poses.iter())  {
+                                                            self.add_data_type_idents_for_type_value(type_value2, pos, tree, &mut idents, processed_idents, errs)?
                                                         }
                                                     } else {
                                                         return Err(FrontendErrors::new(vec![FrontendError::Internal(String::from("check_type_recursions_for_data_ident: too few type values"))]))
@@ -1482,22 +1465,19 @@ impl Typer
     
     fn add_data_type_idents_for_type_value(&self, type_value: &TypeValue, pos: &Pos, tree: &Tree, idents: &mut Vec<String>, processed_idents: &BTreeSet<String>, errs: &mut Vec<FrontendError>) -> FrontendResultWithErrors<()>
     {
-        match type_value {
-            TypeValue::Type(_, type_value_name, type_values) => {
-                let ref_type_flag = match type_value_name {
-                    TypeValueName::Name(ident) => {
-                        add_data_ident(ident, pos.clone(), tree, idents, processed_idents, errs)?;
-                        self.ref_type_flag_for_type_ident(ident, tree)?
-                    },
-                    _ => RefTypeFlag::None,
-                };
-                if ref_type_flag == RefTypeFlag::None {
-                    for type_value2 in type_values {
-                        self.add_data_type_idents_for_type_value(&**type_value2, pos, tree, idents, processed_idents, errs)?;
-                    }
+        if let TypeValue::Type(_, type_value_name, type_values) = type_value {
+            let ref_type_flag = match type_value_name {
+                TypeValueName::Name(ident) => {
+                    add_data_ident(ident, pos.clone(), tree, idents, processed_idents, errs)?;
+                    self.ref_type_flag_for_type_ident(ident, tree)?
+                },
+                _ => RefTypeFlag::None,
+            };
+            if ref_type_flag == RefTypeFlag::None {
+                for type_value2 in type_values {
+                    self.add_data_type_idents_for_type_value(type_value2, pos, tree, idents, processed_idents, errs)?;
                 }
-            },
-            _ => (),
+            }
         }
         Ok(())
     }
@@ -1509,35 +1489,29 @@ impl Typer
     fn check_type_arg_counts_for_impl_defs(&self, tree: &Tree, errs: &mut Vec<FrontendError>) -> FrontendResultWithErrors<()>
     {
         for def in tree.defs() {
-            match &**def {
-                Def::Impl(impl1, pos) => {
-                    let impl_r = impl1.borrow();
-                    match &*impl_r {
-                        Impl::Builtin(trait_ident, type_name, _) => {
-                            if !self.builtins.has_impl_pair(&(trait_ident.clone(), type_name.clone())) {
-                                errs.push(FrontendError::Message(pos.clone(), format!("implementation of trait {} for type {} isn't built-in implementation", trait_ident, type_name)));
-                            }
-                        },
-                        _ => (),
+            if let Def::Impl(impl1, pos) = &**def {
+                let impl_r = impl1.borrow();
+                if let Impl::Builtin(trait_ident, type_name, _) = &*impl_r {
+                    if !self.builtins.has_impl_pair(&(trait_ident.clone(), type_name.clone())) {
+                        errs.push(FrontendError::Message(pos.clone(), format!("implementation of trait {} for type {} isn't built-in implementation", trait_ident, type_name)));
                     }
-                    let (trait_ident, type_name) = match &*impl_r {
-                        Impl::Builtin(tmp_trait_ident, tmp_type_name, _) => (tmp_trait_ident, tmp_type_name),
-                        Impl::Impl(tmp_trait_ident, tmp_type_name, _, _) => (tmp_trait_ident, tmp_type_name),
-                    };
-                    let trait_type_arg_count = type_arg_count_for_trait_ident(trait_ident, tree)?;
-                    let type_arg_count = match &type_name {
-                        TypeName::Tuple(count) => *count,
-                        TypeName::Array(_) => 1,
-                        TypeName::Fun(count) => *count + 1,
-                        TypeName::Name(ident) => type_arg_count_for_type_ident(ident, tree)?,
-                    };
-                    if type_arg_count < trait_type_arg_count {
-                        errs.push(FrontendError::Message(pos.clone(), format!("too few type arguments of type {}", type_name)));
-                    } else if type_arg_count > trait_type_arg_count {
-                        errs.push(FrontendError::Message(pos.clone(), format!("too many type arguments of type {}", type_name)));
-                    }
-                },
-                _ => (),
+                }
+                let (trait_ident, type_name) = match &*impl_r {
+                    Impl::Builtin(tmp_trait_ident, tmp_type_name, _) => (tmp_trait_ident, tmp_type_name),
+                    Impl::Impl(tmp_trait_ident, tmp_type_name, _, _) => (tmp_trait_ident, tmp_type_name),
+                };
+                let trait_type_arg_count = type_arg_count_for_trait_ident(trait_ident, tree)?;
+                let type_arg_count = match &type_name {
+                    TypeName::Tuple(count) => *count,
+                    TypeName::Array(_) => 1,
+                    TypeName::Fun(count) => *count + 1,
+                    TypeName::Name(ident) => type_arg_count_for_type_ident(ident, tree)?,
+                };
+                if type_arg_count < trait_type_arg_count {
+                    errs.push(FrontendError::Message(pos.clone(), format!("too few type arguments of type {}", type_name)));
+                } else if type_arg_count > trait_type_arg_count {
+                    errs.push(FrontendError::Message(pos.clone(), format!("too many type arguments of type {}", type_name)));
+                }
             }
         }
         Ok(())
@@ -1547,11 +1521,11 @@ impl Typer
     {
         for def in tree.defs() {
             match &**def {
-                Def::Var(ident, var, pos) => {
+                Def::Var(_, ident, var, pos) => {
                     let mut var_r = var.borrow_mut();
-                    self.evaluate_types_for_var(ident, &mut *var_r, pos.clone(), tree, errs)?;
+                    self.evaluate_types_for_var(ident, &mut var_r, pos.clone(), tree, errs)?;
                 },
-                Def::Trait(_, trait1, _) => {
+                Def::Trait(_, _, trait1, _) => {
                     let trait_r = trait1.borrow();
                     match &*trait_r {
                         Trait(_, trait_defs, _) => {
@@ -1559,7 +1533,7 @@ impl Typer
                                 match &**trait_def {
                                     TraitDef(ident, var, pos) => {
                                         let mut var_r = var.borrow_mut();
-                                        self.evaluate_types_for_var(ident, &mut *var_r, pos.clone(), tree, errs)?;
+                                        self.evaluate_types_for_var(ident, &mut var_r, pos.clone(), tree, errs)?;
                                     },
                                 }
                             }
@@ -1575,141 +1549,128 @@ impl Typer
     fn check_impls_for_impl_defs(&self, tree: &Tree, errs: &mut Vec<FrontendError>) -> FrontendResultWithErrors<()>
     {
         for def in tree.defs() {
-            match &**def {
-                Def::Impl(impl1, pos) => {
-                    let impl_r = impl1.borrow();
-                    let (trait_ident, type_name) = match &*impl_r {
-                        Impl::Builtin(tmp_trait_ident, tmp_type_name, _) => (tmp_trait_ident, tmp_type_name), 
-                        Impl::Impl(tmp_trait_ident, tmp_type_name, _, _) => (tmp_trait_ident, tmp_type_name),
-                    };
-                    if shared_flag_for_type_name(&type_name, tree)? == SharedFlag::None {
-                        match tree.trait1(&trait_ident) {
-                            Some(trait1) => {
-                                let trait_r = trait1.borrow();
-                                let mut is_success = true;
-                                match &*trait_r {
-                                    Trait(_, _, Some(trait_vars)) => {
-                                        for trait_var in trait_vars.vars().values() {
-                                            let trait_var_r = trait_var.borrow();
-                                            let typ = match &*trait_var_r {
-                                                Var::Builtin(_, Some(tmp_type)) => tmp_type,
-                                                Var::Var(_, _, _, _, _, _, _, Some(tmp_type), _) => tmp_type,
-                                                Var::Fun(_, _, Some(tmp_type)) => tmp_type,
-                                                _ => return Err(FrontendErrors::new(vec![FrontendError::Internal(String::from("check_impls_for_impl_defs: no type"))])),
-                                            };
-                                            for type_param_entry in typ.type_param_entries() {
-                                                let type_param_entry_r = type_param_entry.borrow();
-                                                if type_param_entry_r.trait_names.contains(&TraitName::Name(trait_ident.clone())) {
-                                                    if type_param_entry_r.trait_names.contains(&TraitName::Shared) {
-                                                        is_success = false;
-                                                    }
-                                                }
+            if let Def::Impl(impl1, pos) = &**def {
+                let impl_r = impl1.borrow();
+                let (trait_ident, type_name) = match &*impl_r {
+                    Impl::Builtin(tmp_trait_ident, tmp_type_name, _) => (tmp_trait_ident, tmp_type_name), 
+                    Impl::Impl(tmp_trait_ident, tmp_type_name, _, _) => (tmp_trait_ident, tmp_type_name),
+                };
+                if shared_flag_for_type_name(type_name, tree)? == SharedFlag::None {
+                    match tree.trait1(trait_ident) {
+                        Some(trait1) => {
+                            let trait_r = trait1.borrow();
+                            let mut is_success = true;
+                            match &*trait_r {
+                                Trait(_, _, Some(trait_vars)) => {
+                                    for trait_var in trait_vars.vars().values() {
+                                        let trait_var_r = trait_var.borrow();
+                                        let typ = match &*trait_var_r {
+                                            Var::Builtin(_, Some(tmp_type)) => tmp_type,
+                                            Var::Var(_, _, _, _, _, _, _, Some(tmp_type), _) => tmp_type,
+                                            Var::Fun(_, _, Some(tmp_type)) => tmp_type,
+                                            _ => return Err(FrontendErrors::new(vec![FrontendError::Internal(String::from("check_impls_for_impl_defs: no type"))])),
+                                        };
+                                        for type_param_entry in typ.type_param_entries() {
+                                            let type_param_entry_r = type_param_entry.borrow();
+                                            if type_param_entry_r.trait_names.contains(&TraitName::Name(trait_ident.clone())) && type_param_entry_r.trait_names.contains(&TraitName::Shared) {
+                                                is_success = false;
                                             }
                                         }
-                                    },
-                                    _ => return Err(FrontendErrors::new(vec![FrontendError::Internal(String::from("check_impls_for_impl_defs: no trait variables"))])),
-                                }
-                                if !is_success {
-                                    errs.push(FrontendError::Message(pos.clone(), format!("defined implementation of trait {} for type {} that is unique type", trait_ident, type_name)));
-                                }
-                            }
-                            None => return Err(FrontendErrors::new(vec![FrontendError::Internal(String::from("check_impls_for_impl_defs: no trait"))])),
-                        }
-                    }
-                    match type_name {
-                        TypeName::Fun(_) => {
-                            match tree.trait1(&trait_ident) {
-                                Some(trait1) => {
-                                    let trait_r = trait1.borrow();
-                                    let mut is_success = true;
-                                    match &*trait_r {
-                                        Trait(_, _, Some(trait_vars)) => {
-                                            for trait_var in trait_vars.vars().values() {
-                                                let trait_var_r = trait_var.borrow();
-                                                let typ = match &*trait_var_r {
-                                                    Var::Builtin(_, Some(tmp_type)) => tmp_type,
-                                                    Var::Var(_, _, _, _, _, _, _, Some(tmp_type), _) => tmp_type,
-                                                    Var::Fun(_, _, Some(tmp_type)) => tmp_type,
-                                                    _ => return Err(FrontendErrors::new(vec![FrontendError::Internal(String::from("check_impls_for_impl_defs: no type"))])),
-                                                };
-                                                for type_param_entry in typ.type_param_entries() {
-                                                    let type_param_entry_r = type_param_entry.borrow();
-                                                    if type_param_entry_r.trait_names.contains(&TraitName::Name(trait_ident.clone())) {
-                                                        if !type_param_entry_r.trait_names.contains(&TraitName::Shared) {
-                                                            is_success = false;
-                                                        }
-                                                    }
-                                                }
-                                            }
-                                        },
-                                        _ => return Err(FrontendErrors::new(vec![FrontendError::Internal(String::from("check_impls_for_impl_defs: no trait variables"))])),
-                                    }
-                                    if !is_success {
-                                        errs.push(FrontendError::Message(pos.clone(), format!("defined implementation of trait {} for type {} that is function type", trait_ident, type_name)));
                                     }
-                                }
-                                None => return Err(FrontendErrors::new(vec![FrontendError::Internal(String::from("check_impls_for_impl_defs: no trait"))])),
+                                },
+                                _ => return Err(FrontendErrors::new(vec![FrontendError::Internal(String::from("check_impls_for_impl_defs: no trait variables"))])),
                             }
-                        },
-                        _ => (),
+                            if !is_success {
+                                errs.push(FrontendError::Message(pos.clone(), format!("defined implementation of trait {} for type {} that is unique type", trait_ident, type_name)));
+                            }
+                        }
+                        None => return Err(FrontendErrors::new(vec![FrontendError::Internal(String::from("check_impls_for_impl_defs: no trait"))])),
                     }
-                    match tree.trait1(&trait_ident) {
+                }
+                if let TypeName::Fun(_) = type_name {
+                    match tree.trait1(trait_ident) {
                         Some(trait1) => {
                             let trait_r = trait1.borrow();
+                            let mut is_success = true;
                             match &*trait_r {
                                 Trait(_, _, Some(trait_vars)) => {
-                                    match &*impl_r {
-                                        Impl::Impl(_, _, impl_defs, _) => {
-                                            for impl_def in impl_defs {
-                                                match &**impl_def {
-                                                    ImplDef(impl_var_ident, impl_var, impl_var_pos) => {
-                                                       match trait_vars.var(impl_var_ident) {
-                                                            Some(trait_var) => {
-                                                                let trait_var_r = trait_var.borrow();
-                                                                let impl_var_r = impl_var.borrow();
-                                                                match (&*trait_var_r, &*impl_var_r) {
-                                                                    (Var::Builtin(_, Some(trait_var_type)), ImplVar::Fun(impl_fun, _)) => {
-                                                                        match &**trait_var_type.type_value() {
-                                                                            TypeValue::Type(_, TypeValueName::Fun, type_values) => {
-                                                                                if type_values.len() >= 1 {
-                                                                                    match &**impl_fun {
-                                                                                        ImplFun(impl_args, _, _, _) => {
-                                                                                            if impl_args.len() < type_values.len() - 1  {
-                                                                                                errs.push(FrontendError::Message(impl_var_pos.clone(), String::from("too few arguments")));
-                                                                                            } else if impl_args.len() > type_values.len() - 1 {
-                                                                                                errs.push(FrontendError::Message(impl_var_pos.clone(), String::from("too many arguments")));
-                                                                                            }
-                                                                                        },
-                                                                                    }
-                                                                                } else {
-                                                                                    return Err(FrontendErrors::new(vec![FrontendError::Internal(String::from("check_impls_for_impl_defs: too few type values"))]))
-                                                                                }
-                                                                            },
-                                                                            _ => errs.push(FrontendError::Message(impl_var_pos.clone(), format!("type of built-in variable {} isn't function type", impl_var_ident))),
-                                                                        }
-                                                                    },
-                                                                    (Var::Builtin(_, None), ImplVar::Fun(_, _)) => {
-                                                                        errs.push(FrontendError::Message(impl_var_pos.clone(), format!("unevaluated type of built-in variable {}", impl_var_ident)));
-                                                                    },
-                                                                    _ => (),
-                                                                }
-                                                            },
-                                                            None => return Err(FrontendErrors::new(vec![FrontendError::Internal(String::from("check_impls_for_impl_defs: no trait variable"))])),
-                                                       }
-                                                    },
-                                                }
+                                    for trait_var in trait_vars.vars().values() {
+                                        let trait_var_r = trait_var.borrow();
+                                        let typ = match &*trait_var_r {
+                                            Var::Builtin(_, Some(tmp_type)) => tmp_type,
+                                            Var::Var(_, _, _, _, _, _, _, Some(tmp_type), _) => tmp_type,
+                                            Var::Fun(_, _, Some(tmp_type)) => tmp_type,
+                                            _ => return Err(FrontendErrors::new(vec![FrontendError::Internal(String::from("check_impls_for_impl_defs: no type"))])),
+                                        };
+                                        for type_param_entry in typ.type_param_entries() {
+                                            let type_param_entry_r = type_param_entry.borrow();
+                                            if type_param_entry_r.trait_names.contains(&TraitName::Name(trait_ident.clone())) && !type_param_entry_r.trait_names.contains(&TraitName::Shared) {
+                                                is_success = false;
                                             }
-                                        },
-                                        _ => (),
+                                        }
                                     }
                                 },
                                 _ => return Err(FrontendErrors::new(vec![FrontendError::Internal(String::from("check_impls_for_impl_defs: no trait variables"))])),
                             }
-                        },
+                            if !is_success {
+                                errs.push(FrontendError::Message(pos.clone(), format!("defined implementation of trait {} for type {} that is function type", trait_ident, type_name)));
+                            }
+                        }
                         None => return Err(FrontendErrors::new(vec![FrontendError::Internal(String::from("check_impls_for_impl_defs: no trait"))])),
                     }
-                },
-                _ => (),
+                }
+                match tree.trait1(trait_ident) {
+                    Some(trait1) => {
+                        let trait_r = trait1.borrow();
+                        match &*trait_r {
+                            Trait(_, _, Some(trait_vars)) => {
+                                if let Impl::Impl(_, _, impl_defs, _) = &*impl_r {
+                                    for impl_def in impl_defs {
+                                        match &**impl_def {
+                                            ImplDef(impl_var_ident, impl_var, impl_var_pos) => {
+                                               match trait_vars.var(impl_var_ident) {
+                                                    Some(trait_var) => {
+                                                        let trait_var_r = trait_var.borrow();
+                                                        let impl_var_r = impl_var.borrow();
+                                                        match (&*trait_var_r, &*impl_var_r) {
+                                                            (Var::Builtin(_, Some(trait_var_type)), ImplVar::Fun(impl_fun, _)) => {
+                                                                match &**trait_var_type.type_value() {
+                                                                    TypeValue::Type(_, TypeValueName::Fun, type_values) => {
+                                                                        if !type_values.is_empty() {
+                                                                            match &**impl_fun {
+                                                                                ImplFun(impl_args, _, _, _) => {
+                                                                                    if impl_args.len() < type_values.len() - 1  {
+                                                                                        errs.push(FrontendError::Message(impl_var_pos.clone(), String::from("too few arguments")));
+                                                                                    } else if impl_args.len() > type_values.len() - 1 {
+                                                                                        errs.push(FrontendError::Message(impl_var_pos.clone(), String::from("too many arguments")));
+                                                                                    }
+                                                                                },
+                                                                            }
+                                                                        } else {
+                                                                            return Err(FrontendErrors::new(vec![FrontendError::Internal(String::from("check_impls_for_impl_defs: too few type values"))]))
+                                                                        }
+                                                                    },
+                                                                    _ => errs.push(FrontendError::Message(impl_var_pos.clone(), format!("type of built-in variable {} isn't function type", impl_var_ident))),
+                                                                }
+                                                            },
+                                                            (Var::Builtin(_, None), ImplVar::Fun(_, _)) => {
+                                                                errs.push(FrontendError::Message(impl_var_pos.clone(), format!("unevaluated type of built-in variable {}", impl_var_ident)));
+                                                            },
+                                                            _ => (),
+                                                        }
+                                                    },
+                                                    None => return Err(FrontendErrors::new(vec![FrontendError::Internal(String::from("check_impls_for_impl_defs: no trait variable"))])),
+                                               }
+                                            },
+                                        }
+                                    }
+                                }
+                            },
+                            _ => return Err(FrontendErrors::new(vec![FrontendError::Internal(String::from("check_impls_for_impl_defs: no trait variables"))])),
+                        }
+                    },
+                    None => return Err(FrontendErrors::new(vec![FrontendError::Internal(String::from("check_impls_for_impl_defs: no trait"))])),
+                }
             }
         }
         Ok(())
@@ -1718,30 +1679,27 @@ impl Typer
     fn evaluate_types_for_impl_defs(&self, tree: &Tree, errs: &mut Vec<FrontendError>) -> FrontendResultWithErrors<()>
     {
         for def in tree.defs() {
-            match &**def {
-                Def::Impl(impl1, pos) => {
-                    let impl_r = impl1.borrow();
-                    match &*impl_r {
-                        Impl::Builtin(trait_ident, type_name, Some(impl_vars)) => {
-                            for (ident, impl_var) in impl_vars.vars() {
-                                let mut impl_var_r = impl_var.borrow_mut();
-                                self.evaluate_types_for_impl_var(ident, &mut *impl_var_r, pos.clone(), trait_ident, type_name, tree, true, errs)?;
-                            }
-                        },
-                        Impl::Builtin(_, _, None) => return Err(FrontendErrors::new(vec![FrontendError::Internal(String::from("evaluate_types_for_impl_defs: no implementation variables"))])),
-                        Impl::Impl(trait_ident, type_name, impl_defs, _) => {
-                            for impl_def in impl_defs {
-                                match &**impl_def {
-                                    ImplDef(ident, impl_var, impl_var_pos) => {
-                                        let mut impl_var_r = impl_var.borrow_mut();
-                                        self.evaluate_types_for_impl_var(ident, &mut *impl_var_r, impl_var_pos.clone(), trait_ident, type_name, tree, false, errs)?;
-                                    },
-                                }
+            if let Def::Impl(impl1, pos) = &**def {
+                let impl_r = impl1.borrow();
+                match &*impl_r {
+                    Impl::Builtin(trait_ident, type_name, Some(impl_vars)) => {
+                        for (ident, impl_var) in impl_vars.vars() {
+                            let mut impl_var_r = impl_var.borrow_mut();
+                            self.evaluate_types_for_impl_var(ident, &mut impl_var_r, pos.clone(), trait_ident, type_name, tree, true, errs)?;
+                        }
+                    },
+                    Impl::Builtin(_, _, None) => return Err(FrontendErrors::new(vec![FrontendError::Internal(String::from("evaluate_types_for_impl_defs: no implementation variables"))])),
+                    Impl::Impl(trait_ident, type_name, impl_defs, _) => {
+                        for impl_def in impl_defs {
+                            match &**impl_def {
+                                ImplDef(ident, impl_var, impl_var_pos) => {
+                                    let mut impl_var_r = impl_var.borrow_mut();
+                                    self.evaluate_types_for_impl_var(ident, &mut impl_var_r, impl_var_pos.clone(), trait_ident, type_name, tree, false, errs)?;
+                                },
                             }
-                        },
-                    }
-                },
-                _ => (),
+                        }
+                    },
+                }
             }
         }
         Ok(())
@@ -1754,26 +1712,24 @@ impl Typer
                 match self.builtins.var(ident) {
                     Some(builtin_var) => {
                         match parse_type_with_path(format!("({} type).vscfl", ident).as_str(), builtin_var.type_source.as_str()) {
-                            Ok(type_expr) => {
+                            Ok(mut type_expr) => {
                                 match parse_where_with_path(format!("({} where).vscfl", ident).as_str(), builtin_var.where_source.as_str()) {
-                                    Ok(where_tuples) => {
+                                    Ok(mut where_tuples) => {
+                                        resolve_builtin_idents_for_type_with_where(ident.as_str(), &mut type_expr, where_tuples.as_mut_slice(), tree);
                                         match check_idents_for_type_with_where(&type_expr, &where_tuples, tree) {
                                             Ok(()) => {
                                                 let mut type_param_env: Environment<LocalType> = Environment::new();
                                                 let mut local_type_counter = Some(0usize);
                                                 type_param_env.push_new_vars();
-                                                match self.evaluate_type_for_type_expr(&type_expr, tree, &mut type_param_env, &mut local_type_counter, errs)? {
-                                                    Some(type_value) => {
-                                                        let mut new_type = new_type_from_type_value_and_type_param_env(type_value, &type_param_env, local_type_counter)?;
-                                                        if self.evaluate_types_for_where_tuples(ident.as_str(), where_tuples.as_slice(), trait_ident, pos, tree, &mut type_param_env, &mut new_type, &mut local_type_counter, errs)? {
-                                                            if self.shared_flag_for_type(&new_type, tree)? == SharedFlag::Shared {
-                                                                *typ = Some(Box::new(new_type));
-                                                            } else {
-                                                                errs.push(FrontendError::Message(type_expr_pos(&type_expr).clone(), format!("built-in variable {} mustn't non-shared with type {}", ident, new_type)))
-                                                            }
+                                                if let Some(type_value) = self.evaluate_type_for_type_expr(&type_expr, tree, &mut type_param_env, &mut local_type_counter, errs)? {
+                                                    let mut new_type = new_type_from_type_value_and_type_param_env(type_value, &type_param_env, local_type_counter)?;
+                                                    if self.evaluate_types_for_where_tuples(ident.as_str(), where_tuples.as_slice(), trait_ident, pos, tree, &mut type_param_env, &mut new_type, &mut local_type_counter, errs)? {
+                                                        if self.shared_flag_for_type(&new_type, tree)? == SharedFlag::Shared {
+                                                            *typ = Some(Box::new(new_type));
+                                                        } else {
+                                                            errs.push(FrontendError::Message(type_expr_pos(&type_expr).clone(), format!("built-in variable {} mustn't non-shared with type {}", ident, new_type)))
                                                         }
-                                                    },
-                                                    None => (),
+                                                    }
                                                 }
                                             },
                                             Err(mut errs2) => add_errors(&mut errs2, errs)?,
@@ -1792,7 +1748,7 @@ impl Typer
                 let mut type_param_env: Environment<LocalType> = Environment::new();
                 let mut local_type_counter = Some(0usize);
                 type_param_env.push_new_vars();
-                match self.evaluate_type_for_type_expr(&**type_expr, tree, &mut type_param_env, &mut local_type_counter, errs)? {
+                match self.evaluate_type_for_type_expr(type_expr, tree, &mut type_param_env, &mut local_type_counter, errs)? {
                     Some(type_value) => {
                         let mut new_type = new_type_from_type_value_and_type_param_env(type_value, &type_param_env, local_type_counter)?;
                         if self.evaluate_types_for_where_tuples(ident.as_str(), where_tuples.as_slice(), trait_ident, pos, tree, &mut type_param_env, &mut new_type, &mut local_type_counter, errs)? {
@@ -1802,9 +1758,9 @@ impl Typer
                                         let mut new_local_types = LocalTypes::new();
                                         let new_local_type = new_local_types.set_defined_type(&new_type);
                                         let mut var_env: Environment<LocalType> = Environment::new();
-                                        self.evaluate_types_for_expr(&mut **expr, tree, &mut var_env, &mut type_param_env, &mut new_local_types, errs)?;
+                                        self.evaluate_types_for_expr(expr, tree, &mut var_env, &mut type_param_env, &mut new_local_types, errs)?;
                                         let mut var_env2: Environment<(LocalType, usize, Pos)> = Environment::new();
-                                        self.set_shareds_for_expr(&**expr, tree, &mut var_env2, &new_local_types, errs)?;
+                                        self.set_shareds_for_expr(expr, tree, &mut var_env2, &new_local_types, errs)?;
                                         *local_type = Some(new_local_type);
                                         *local_types = Some(Box::new(new_local_types));
                                     },
@@ -1816,7 +1772,7 @@ impl Typer
                                 *typ = Some(Box::new(new_type));
                             } else {
                                 *typ = None;
-                                errs.push(FrontendError::Message(type_expr_pos(&type_expr).clone(), format!("variable {} mustn't be non-shared with type {}", ident, new_type)))
+                                errs.push(FrontendError::Message(type_expr_pos(type_expr).clone(), format!("variable {} mustn't be non-shared with type {}", ident, new_type)))
                             }
                         } else {
                             *typ = None;
@@ -1836,14 +1792,14 @@ impl Typer
                         for arg in &*args {
                             match arg {
                                 Arg(_, type_expr, _, _) => {
-                                    match self.evaluate_type_for_type_expr(&**type_expr, tree, &mut type_param_env, &mut local_type_counter, errs)? {
+                                    match self.evaluate_type_for_type_expr(type_expr, tree, &mut type_param_env, &mut local_type_counter, errs)? {
                                         Some(type_value) => type_values.push(type_value),
                                         None => is_success = false,
                                     }
                                 },
                             }
                         }
-                        match self.evaluate_type_for_type_expr(&**ret_type_expr, tree, &mut type_param_env, &mut local_type_counter, errs)? {
+                        match self.evaluate_type_for_type_expr(ret_type_expr, tree, &mut type_param_env, &mut local_type_counter, errs)? {
                             Some(type_value) => type_values.push(type_value),
                             None => is_success = false,
                         }
@@ -1858,8 +1814,8 @@ impl Typer
                                             Some(new_local_types2) => {
                                                 let mut var_env: Environment<LocalType> = Environment::new();
                                                 var_env.push_new_vars();
-                                                if new_local_types2.len() >= 1 {
-                                                    for (arg, new_local_type) in args.iter_mut().zip((&new_local_types2[0..(new_local_types2.len() - 1)]).iter()) {
+                                                if !new_local_types2.is_empty() {
+                                                    for (arg, new_local_type) in args.iter_mut().zip(new_local_types2[0..(new_local_types2.len() - 1)].iter()) {
                                                         match arg {
                                                             Arg(arg_ident, _, arg_local_type, _) => {
                                                                 var_env.add_var(arg_ident.clone(), *new_local_type);
@@ -1867,17 +1823,17 @@ impl Typer
                                                             },
                                                         }
                                                     }
-                                                    self.evaluate_types_for_expr(&mut **body, tree, &mut var_env, &mut type_param_env, &mut new_local_types, errs)?;
+                                                    self.evaluate_types_for_expr(body, tree, &mut var_env, &mut type_param_env, &mut new_local_types, errs)?;
                                                     let mut var_env2: Environment<(LocalType, usize, Pos)> = Environment::new();
                                                     var_env2.push_new_vars();
-                                                    for (arg, new_local_type) in args.iter().zip((&new_local_types2[0..(new_local_types2.len() - 1)]).iter()) {
+                                                    for (arg, new_local_type) in args.iter().zip(new_local_types2[0..(new_local_types2.len() - 1)].iter()) {
                                                         match arg {
                                                             Arg(arg_ident, _, _, pos) => {
                                                                 var_env2.add_var(arg_ident.clone(), (*new_local_type, 0, pos.clone()));
                                                             },
                                                         }
                                                     }
-                                                    self.set_shareds_for_expr(&**body, tree, &mut var_env2, &new_local_types, errs)?;
+                                                    self.set_shareds_for_expr(body, tree, &mut var_env2, &new_local_types, errs)?;
                                                     var_env2.foreach_with_result(|ident, tuple| self.set_shared_for_tuple(ident, tuple, tree, &new_local_types, errs))?;
                                                     *ret_local_type = Some(new_local_types2[new_local_types2.len() - 1]);
                                                     *local_types = Some(Box::new(new_local_types));
@@ -1914,7 +1870,7 @@ impl Typer
     }
     
     fn shared_flag_for_type(&self, typ: &Type, tree: &Tree) -> FrontendResultWithErrors<SharedFlag>
-    { self.shared_flag_for_type_value(&**typ.type_value(), tree, typ) }
+    { self.shared_flag_for_type_value(typ.type_value(), tree, typ) }
 
     fn shared_flag_for_type_value(&self, type_value: &TypeValue, tree: &Tree, typ: &Type) -> FrontendResultWithErrors<SharedFlag>
     {
@@ -1940,7 +1896,7 @@ impl Typer
                 };
                 if shared_flag == SharedFlag::Shared {
                     for type_value2 in type_values {
-                        if self.shared_flag_for_type_value(&**type_value2, tree, typ)? == SharedFlag::None {
+                        if self.shared_flag_for_type_value(type_value2, tree, typ)? == SharedFlag::None {
                             shared_flag = SharedFlag::None;
                         }
                     }
@@ -1961,7 +1917,7 @@ impl Typer
                     match &type_param_entry_r.pos {
                         Some(pos) => {
                             for type_value in &type_param_entry_r.type_values {
-                                self.add_local_types_for_type_value_and_recursion(&**type_value, pos, typ, &mut local_types, processed_local_types, errs)?;
+                                self.add_local_types_for_type_value_and_recursion(type_value, pos, typ, &mut local_types, processed_local_types, errs)?;
                             }
                             Ok(local_types)
                         },
@@ -1981,7 +1937,7 @@ impl Typer
             TypeValue::Param(_, local_type) => add_local_type_for_recursion(*local_type, pos.clone(), typ, local_types, processed_local_types, errs)?,
             TypeValue::Type(_, _, type_values) => {
                 for type_value2 in type_values {
-                    self.add_local_types_for_type_value_and_recursion(&**type_value2, pos, typ, local_types, processed_local_types, errs)?;
+                    self.add_local_types_for_type_value_and_recursion(type_value2, pos, typ, local_types, processed_local_types, errs)?;
                 }
             },
         }
@@ -1993,90 +1949,84 @@ impl Typer
         if !where_tuples.is_empty() {
             let mut is_success = true;
             for where_tuple in where_tuples {
-                match where_tuple {
-                    WhereTuple::Traits(type_param_ident, trait_names, type_exprs, where_tuple_pos) => {
-                        for trait_name in trait_names {
-                            match trait_name {
-                                TraitName::Shared => (),
-                                TraitName::Fun => {
-                                    if type_exprs.len() < 1 {
-                                        errs.push(FrontendError::Message(where_tuple_pos.clone(), format!("no type expressions of type parameter {} for trait ->", type_param_ident)));
-                                        is_success = false;
-                                    }
-                                },
-                                TraitName::Name(trait_ident) => {
-                                    let type_arg_count = type_arg_count_for_trait_ident(trait_ident, tree)?;
-                                    if type_arg_count != type_exprs.len() {
-                                        errs.push(FrontendError::Message(where_tuple_pos.clone(), format!("number of type arguments of trait {} isn't equal to number of type expressions of type parameter {}", trait_ident, type_param_ident)));
-                                        is_success = false;
-                                    }
-                                },
-                            }
+                if let WhereTuple::Traits(type_param_ident, trait_names, type_exprs, where_tuple_pos) = where_tuple {
+                    for trait_name in trait_names {
+                        match trait_name {
+                            TraitName::Shared => (),
+                            TraitName::Fun => {
+                                if type_exprs.is_empty() {
+                                    errs.push(FrontendError::Message(where_tuple_pos.clone(), format!("no type expressions of type parameter {} for trait ->", type_param_ident)));
+                                    is_success = false;
+                                }
+                            },
+                            TraitName::Name(trait_ident) => {
+                                let type_arg_count = type_arg_count_for_trait_ident(trait_ident, tree)?;
+                                if type_arg_count != type_exprs.len() {
+                                    errs.push(FrontendError::Message(where_tuple_pos.clone(), format!("number of type arguments of trait {} isn't equal to number of type expressions of type parameter {}", trait_ident, type_param_ident)));
+                                    is_success = false;
+                                }
+                            },
                         }
-                    },
-                    _ => (),
+                    }
                 }
             }
             if !is_success {
                 return Ok(false);
             }
             for where_tuple in where_tuples {
-                match where_tuple {
-                    WhereTuple::Traits(type_param_ident, trait_names, type_exprs, where_tuple_pos) => {
-                        match type_param_env.var(type_param_ident) {
-                            Some(local_type) => {
-                                match typ.type_param_entry(*local_type).map(|tpe| tpe.clone()) {
-                                    Some(type_param_entry) => {
-                                        let mut type_param_entry_r = type_param_entry.borrow_mut();
-                                        type_param_entry_r.trait_names.clear();
-                                        for trait_name in trait_names {
-                                            type_param_entry_r.trait_names.insert(trait_name.clone());
-                                        }
-                                        let mut tmp_is_success = true;
-                                        type_param_entry_r.type_values.clear();
-                                        let old_local_type_counter = match local_type_counter {
-                                            Some(tmp_local_type_counter) => *tmp_local_type_counter,
-                                            None => return Err(FrontendErrors::new(vec![FrontendError::Internal(String::from("evaluate_types_for_where_tuples: no local type counter"))])),
-                                        };
-                                        for type_expr in type_exprs {
-                                            match self.evaluate_type_for_type_expr(&**type_expr, tree, type_param_env, local_type_counter, errs)? {
-                                                Some(type_value) => type_param_entry_r.type_values.push(type_value),
-                                                None => tmp_is_success = false,
-                                            }
+                if let WhereTuple::Traits(type_param_ident, trait_names, type_exprs, where_tuple_pos) = where_tuple {
+                    match type_param_env.var(type_param_ident) {
+                        Some(local_type) => {
+                            match typ.type_param_entry(*local_type).cloned() {
+                                Some(type_param_entry) => {
+                                    let mut type_param_entry_r = type_param_entry.borrow_mut();
+                                    type_param_entry_r.trait_names.clear();
+                                    for trait_name in trait_names {
+                                        type_param_entry_r.trait_names.insert(trait_name.clone());
+                                    }
+                                    let mut tmp_is_success = true;
+                                    type_param_entry_r.type_values.clear();
+                                    let old_local_type_counter = match local_type_counter {
+                                        Some(tmp_local_type_counter) => *tmp_local_type_counter,
+                                        None => return Err(FrontendErrors::new(vec![FrontendError::Internal(String::from("evaluate_types_for_where_tuples: no local type counter"))])),
+                                    };
+                                    for type_expr in type_exprs {
+                                        match self.evaluate_type_for_type_expr(type_expr, tree, type_param_env, local_type_counter, errs)? {
+                                            Some(type_value) => type_param_entry_r.type_values.push(type_value),
+                                            None => tmp_is_success = false,
                                         }
-                                        type_param_entry_r.pos = Some(where_tuple_pos.clone());
-                                        let new_local_type_counter = match local_type_counter {
-                                            Some(tmp_local_type_counter) => *tmp_local_type_counter,
-                                            None => return Err(FrontendErrors::new(vec![FrontendError::Internal(String::from("evaluate_types_for_where_tuples: no local type counter"))])),
-                                        };
-                                        let mut type_param_idents: Vec<Option<String>> = vec![None; new_local_type_counter - old_local_type_counter];
-                                        type_param_env.foreach(|type_param_ident, local_type| {
-                                                if local_type.index() >= old_local_type_counter {
-                                                    type_param_idents[local_type.index() - old_local_type_counter] = Some(type_param_ident.clone());
-                                                }
-                                        });
-                                        let mut type_param_idents2: Vec<String> = Vec::new();
-                                        for type_param_ident in type_param_idents {
-                                            match type_param_ident {
-                                                Some(type_param_ident) => type_param_idents2.push(type_param_ident.clone()),
-                                                None => return Err(FrontendErrors::new(vec![FrontendError::Internal(String::from("new_type_from_type_value_and_type_param_env: no identifier"))])),
+                                    }
+                                    type_param_entry_r.pos = Some(where_tuple_pos.clone());
+                                    let new_local_type_counter = match local_type_counter {
+                                        Some(tmp_local_type_counter) => *tmp_local_type_counter,
+                                        None => return Err(FrontendErrors::new(vec![FrontendError::Internal(String::from("evaluate_types_for_where_tuples: no local type counter"))])),
+                                    };
+                                    let mut type_param_idents: Vec<Option<String>> = vec![None; new_local_type_counter - old_local_type_counter];
+                                    type_param_env.foreach(|type_param_ident, local_type| {
+                                            if local_type.index() >= old_local_type_counter {
+                                                type_param_idents[local_type.index() - old_local_type_counter] = Some(type_param_ident.clone());
                                             }
+                                    });
+                                    let mut type_param_idents2: Vec<String> = Vec::new();
+                                    for type_param_ident in type_param_idents {
+                                        match type_param_ident {
+                                            Some(type_param_ident) => type_param_idents2.push(type_param_ident.clone()),
+                                            None => return Err(FrontendErrors::new(vec![FrontendError::Internal(String::from("new_type_from_type_value_and_type_param_env: no identifier"))])),
                                         }
-                                        typ.add_type_params(type_param_idents2.as_slice());
-                                        if !tmp_is_success {
-                                            type_param_entry_r.trait_names.clear();
-                                            type_param_entry_r.type_values.clear();
-                                            type_param_entry_r.pos = None;
-                                            is_success = false;
-                                        }
-                                    },
-                                    None => return Err(FrontendErrors::new(vec![FrontendError::Internal(String::from("evaluate_types_for_where_tuples: no type parameter entry"))])),
-                                }
-                            },
-                            None => return Err(FrontendErrors::new(vec![FrontendError::Internal(String::from("evaluate_types_for_where_tuples: no type parameter"))])),
-                        }
-                    },
-                    _ => (),
+                                    }
+                                    typ.add_type_params(type_param_idents2.as_slice());
+                                    if !tmp_is_success {
+                                        type_param_entry_r.trait_names.clear();
+                                        type_param_entry_r.type_values.clear();
+                                        type_param_entry_r.pos = None;
+                                        is_success = false;
+                                    }
+                                },
+                                None => return Err(FrontendErrors::new(vec![FrontendError::Internal(String::from("evaluate_types_for_where_tuples: no type parameter entry"))])),
+                            }
+                        },
+                        None => return Err(FrontendErrors::new(vec![FrontendError::Internal(String::from("evaluate_types_for_where_tuples: no type parameter"))])),
+                    }
                 }
             }
             if !is_success {
@@ -2087,7 +2037,7 @@ impl Typer
                 if type_param_entry_r.trait_names.contains(&TraitName::Shared) && !type_param_entry_r.trait_names.contains(&TraitName::Fun) {
                     let mut tmp_is_success = true;
                     for type_value in &type_param_entry_r.type_values {
-                        if self.shared_flag_for_type_value(&**type_value, tree, typ)? == SharedFlag::None {
+                        if self.shared_flag_for_type_value(type_value, tree, typ)? == SharedFlag::None {
                             tmp_is_success = false;
                         }
                     }
@@ -2114,93 +2064,87 @@ impl Typer
                 }
             }
             for where_tuple in where_tuples {
-                match where_tuple {
-                    WhereTuple::Eq(type_params) => {
-                        match type_params.first() {
-                            Some(TypeParam(type_param_ident, _)) => {
-                                match type_param_env.var(type_param_ident) {
-                                    Some(local_type) => {
-                                        match typ.type_param_entry(*local_type) {
-                                            Some(type_param_entry) => {
-                                                let cloned_type_param_entry = type_param_entry.clone();
-                                                for type_param in &type_params[1..] {
-                                                    match type_param {
-                                                        TypeParam(type_param_ident2, type_param_pos2) => {
-                                                            match type_param_env.var(type_param_ident2) {
-                                                                Some(local_type2) => {
-                                                                    match typ.type_param_entry(*local_type2) {
-                                                                        Some(type_param_entry2) => {
-                                                                            let cloned_type_param_entry2 = type_param_entry2.clone();
-                                                                            let type_param_entry_r = cloned_type_param_entry.borrow();
-                                                                            let type_param_entry2_r = cloned_type_param_entry2.borrow();
-                                                                            let mut tmp_is_success = true;
-                                                                            if type_param_entry_r.type_values.len() != type_param_entry2_r.type_values.len() {
-                                                                                errs.push(FrontendError::Message(type_param_pos2.clone(), format!("type parameter {} hasn't same number of type arguments as type parameter {}", type_param_ident2, type_param_ident)));
-                                                                                tmp_is_success = false;
-                                                                            }
-                                                                            if type_param_entry_r.trait_names != type_param_entry2_r.trait_names {
-                                                                                errs.push(FrontendError::Message(type_param_pos2.clone(), format!("type parameter {} hasn't same traits as type parameter {}", type_param_ident2, type_param_ident)));
-                                                                                tmp_is_success = false;
-                                                                            }
-                                                                            if tmp_is_success {
-                                                                                typ.set_eq_type_params(*local_type, *local_type2);
-                                                                            } else {
-                                                                                is_success = tmp_is_success;
-                                                                            }
-                                                                        },
-                                                                        None => return Err(FrontendErrors::new(vec![FrontendError::Internal(String::from("evaluate_types_for_where_tuples: no type parameter entry"))]))
-                                                                    }
-                                                                },
-                                                                None => return Err(FrontendErrors::new(vec![FrontendError::Internal(String::from("evaluate_types_for_where_tuples: no type parameter"))]))
-                                                            }
-                                                        },
-                                                    }
+                if let WhereTuple::Eq(type_params) = where_tuple {
+                    match type_params.first() {
+                        Some(TypeParam(type_param_ident, _)) => {
+                            match type_param_env.var(type_param_ident) {
+                                Some(local_type) => {
+                                    match typ.type_param_entry(*local_type) {
+                                        Some(type_param_entry) => {
+                                            let cloned_type_param_entry = type_param_entry.clone();
+                                            for type_param in &type_params[1..] {
+                                                match type_param {
+                                                    TypeParam(type_param_ident2, type_param_pos2) => {
+                                                        match type_param_env.var(type_param_ident2) {
+                                                            Some(local_type2) => {
+                                                                match typ.type_param_entry(*local_type2) {
+                                                                    Some(type_param_entry2) => {
+                                                                        let cloned_type_param_entry2 = type_param_entry2.clone();
+                                                                        let type_param_entry_r = cloned_type_param_entry.borrow();
+                                                                        let type_param_entry2_r = cloned_type_param_entry2.borrow();
+                                                                        let mut tmp_is_success = true;
+                                                                        if type_param_entry_r.type_values.len() != type_param_entry2_r.type_values.len() {
+                                                                            errs.push(FrontendError::Message(type_param_pos2.clone(), format!("type parameter {} hasn't same number of type arguments as type parameter {}", type_param_ident2, type_param_ident)));
+                                                                            tmp_is_success = false;
+                                                                        }
+                                                                        if type_param_entry_r.trait_names != type_param_entry2_r.trait_names {
+                                                                            errs.push(FrontendError::Message(type_param_pos2.clone(), format!("type parameter {} hasn't same traits as type parameter {}", type_param_ident2, type_param_ident)));
+                                                                            tmp_is_success = false;
+                                                                        }
+                                                                        if tmp_is_success {
+                                                                            typ.set_eq_type_params(*local_type, *local_type2);
+                                                                        } else {
+                                                                            is_success = tmp_is_success;
+                                                                        }
+                                                                    },
+                                                                    None => return Err(FrontendErrors::new(vec![FrontendError::Internal(String::from("evaluate_types_for_where_tuples: no type parameter entry"))]))
+                                                                }
+                                                            },
+                                                            None => return Err(FrontendErrors::new(vec![FrontendError::Internal(String::from("evaluate_types_for_where_tuples: no type parameter"))]))
+                                                        }
+                                                    },
                                                 }
-                                            },
-                                            None => return Err(FrontendErrors::new(vec![FrontendError::Internal(String::from("evaluate_types_for_where_tuples: no type parameter entry"))]))
-                                        }
-                                    },
-                                    None => return Err(FrontendErrors::new(vec![FrontendError::Internal(String::from("evaluate_types_for_where_tuples: no type parameter"))]))
-                                }
-                            },
-                            None => return Err(FrontendErrors::new(vec![FrontendError::Internal(String::from("evaluate_types_for_where_tuples: no type parameters"))])),
-                        }
-                    },
-                    _ => (),
+                                            }
+                                        },
+                                        None => return Err(FrontendErrors::new(vec![FrontendError::Internal(String::from("evaluate_types_for_where_tuples: no type parameter entry"))]))
+                                    }
+                                },
+                                None => return Err(FrontendErrors::new(vec![FrontendError::Internal(String::from("evaluate_types_for_where_tuples: no type parameter"))]))
+                            }
+                        },
+                        None => return Err(FrontendErrors::new(vec![FrontendError::Internal(String::from("evaluate_types_for_where_tuples: no type parameters"))])),
+                    }
                 }
             }
             if !is_success {
                 return Ok(false);
             }
-            match trait_ident {
-                Some(trait_ident) => {
-                    let mut local_types: Vec<LocalType> = Vec::new();
-                    for (i, type_param_entry) in typ.type_param_entries().iter().enumerate() {
-                        let type_param_entry_r = type_param_entry.borrow();
-                        if type_param_entry_r.trait_names.contains(&TraitName::Name(trait_ident.clone())) {
-                            local_types.push(LocalType::new(i));
-                        }
+            if let Some(trait_ident) = trait_ident {
+                let mut local_types: Vec<LocalType> = Vec::new();
+                for (i, type_param_entry) in typ.type_param_entries().iter().enumerate() {
+                    let type_param_entry_r = type_param_entry.borrow();
+                    if type_param_entry_r.trait_names.contains(&TraitName::Name(trait_ident.clone())) {
+                        local_types.push(LocalType::new(i));
                     }
-                    match local_types.first() {
-                        Some(local_type) => {
-                            let mut tmp_is_success = true;
-                            for local_type2 in &local_types[1..] {
-                                if !typ.has_eq_type_params(*local_type, *local_type2) {
-                                    tmp_is_success = false;
-                                }
-                            }
-                            if !tmp_is_success {
-                                errs.push(FrontendError::Message(pos, format!("type of variable {} has type parameters with trait {} which aren't equal", ident, trait_ident)));
-                                is_success = false;
+                }
+                match local_types.first() {
+                    Some(local_type) => {
+                        let mut tmp_is_success = true;
+                        for local_type2 in &local_types[1..] {
+                            if !typ.has_eq_type_params(*local_type, *local_type2) {
+                                tmp_is_success = false;
                             }
-                        },
-                        None => {
-                            errs.push(FrontendError::Message(pos, format!("type of variable {} hasn't type parameters with trait {}", ident, trait_ident)));
+                        }
+                        if !tmp_is_success {
+                            errs.push(FrontendError::Message(pos, format!("type of variable {} has type parameters with trait {} which aren't equal", ident, trait_ident)));
                             is_success = false;
-                        },
-                    }
-                },
-                None => (),
+                        }
+                    },
+                    None => {
+                        errs.push(FrontendError::Message(pos, format!("type of variable {} hasn't type parameters with trait {}", ident, trait_ident)));
+                        is_success = false;
+                    },
+                }
             }
             Ok(is_success)
         } else {
@@ -2239,7 +2183,7 @@ impl Typer
                         LambdaArg(ident, arg_type_expr, arg_local_type, _) => {
                             let tmp_local_type = match arg_type_expr {
                                 Some(arg_type_expr) => {
-                                    match self.evaluate_type_for_type_expr(&**arg_type_expr, tree, type_param_env, &mut None, errs)? {
+                                    match self.evaluate_type_for_type_expr(arg_type_expr, tree, type_param_env, &mut None, errs)? {
                                         Some(type_value) => local_types.add_type_value(type_value),
                                         None => local_types.add_type_param(Rc::new(RefCell::new(TypeParamEntry::new()))),
                                     }
@@ -2253,14 +2197,14 @@ impl Typer
                 }
                 match ret_type_expr {
                     Some(ret_type_expr) => {
-                        match self.evaluate_type_for_type_expr(&**ret_type_expr, tree, type_param_env, &mut None, errs)? {
+                        match self.evaluate_type_for_type_expr(ret_type_expr, tree, type_param_env, &mut None, errs)? {
                             Some(type_value) => *ret_local_type = Some(local_types.add_type_value(type_value)),
                             None => *ret_local_type = Some(local_types.add_type_param(Rc::new(RefCell::new(TypeParamEntry::new())))),
                         }
                     },
                     None => *ret_local_type = Some(local_types.add_type_param(Rc::new(RefCell::new(TypeParamEntry::new())))),
                 }
-                self.evaluate_types_for_expr(&mut **body, tree, var_env, type_param_env, local_types, errs)?;
+                self.evaluate_types_for_expr(body, tree, var_env, type_param_env, local_types, errs)?;
                 *local_type = Some(local_types.add_type_param(Rc::new(RefCell::new(TypeParamEntry::new()))));
                 var_env.pop_vars();
             },
@@ -2277,66 +2221,66 @@ impl Typer
             },
             Expr::PrintfApp(exprs, local_type, _) => {
                 for expr2 in exprs {
-                    self.evaluate_types_for_expr(&mut **expr2, tree, var_env, type_param_env, local_types, errs)?;
+                    self.evaluate_types_for_expr(expr2, tree, var_env, type_param_env, local_types, errs)?;
                 }
                 *local_type = Some(local_types.add_type_param(Rc::new(RefCell::new(TypeParamEntry::new()))));
             },
             Expr::App(expr2, exprs, local_type, _) => {
-                self.evaluate_types_for_expr(&mut **expr2, tree, var_env, type_param_env, local_types, errs)?;
+                self.evaluate_types_for_expr(expr2, tree, var_env, type_param_env, local_types, errs)?;
                 for expr3 in exprs {
-                    self.evaluate_types_for_expr(&mut **expr3, tree, var_env, type_param_env, local_types, errs)?;
+                    self.evaluate_types_for_expr(expr3, tree, var_env, type_param_env, local_types, errs)?;
                 }
                 *local_type = Some(local_types.add_type_param(Rc::new(RefCell::new(TypeParamEntry::new()))));
             },
             Expr::GetField(expr2, _, local_type, _) => {
-                self.evaluate_types_for_expr(&mut **expr2, tree, var_env, type_param_env, local_types, errs)?;
+                self.evaluate_types_for_expr(expr2, tree, var_env, type_param_env, local_types, errs)?;
                 *local_type = Some(local_types.add_type_param(Rc::new(RefCell::new(TypeParamEntry::new()))));
             },
             Expr::Get2Field(expr2, _, local_type, _) => {
-                self.evaluate_types_for_expr(&mut **expr2, tree, var_env, type_param_env, local_types, errs)?;
+                self.evaluate_types_for_expr(expr2, tree, var_env, type_param_env, local_types, errs)?;
                 *local_type = Some(local_types.add_type_param(Rc::new(RefCell::new(TypeParamEntry::new()))));
             },
             Expr::SetField(expr2, _, expr3, local_type, _) => {
-                self.evaluate_types_for_expr(&mut **expr2, tree, var_env, type_param_env, local_types, errs)?;
-                self.evaluate_types_for_expr(&mut **expr3, tree, var_env, type_param_env, local_types, errs)?;
+                self.evaluate_types_for_expr(expr2, tree, var_env, type_param_env, local_types, errs)?;
+                self.evaluate_types_for_expr(expr3, tree, var_env, type_param_env, local_types, errs)?;
                 *local_type = Some(local_types.add_type_param(Rc::new(RefCell::new(TypeParamEntry::new()))));
             },
             Expr::UpdateField(expr2, _, expr3, local_type, _) => {
-                self.evaluate_types_for_expr(&mut **expr2, tree, var_env, type_param_env, local_types, errs)?;
-                self.evaluate_types_for_expr(&mut **expr3, tree, var_env, type_param_env, local_types, errs)?;
+                self.evaluate_types_for_expr(expr2, tree, var_env, type_param_env, local_types, errs)?;
+                self.evaluate_types_for_expr(expr3, tree, var_env, type_param_env, local_types, errs)?;
                 *local_type = Some(local_types.add_type_param(Rc::new(RefCell::new(TypeParamEntry::new()))));
             },
             Expr::UpdateGet2Field(expr2, _, expr3, local_type, _) => {
-                self.evaluate_types_for_expr(&mut **expr2, tree, var_env, type_param_env, local_types, errs)?;
-                self.evaluate_types_for_expr(&mut **expr3, tree, var_env, type_param_env, local_types, errs)?;
+                self.evaluate_types_for_expr(expr2, tree, var_env, type_param_env, local_types, errs)?;
+                self.evaluate_types_for_expr(expr3, tree, var_env, type_param_env, local_types, errs)?;
                 *local_type = Some(local_types.add_type_param(Rc::new(RefCell::new(TypeParamEntry::new()))));
             },
             Expr::Uniq(expr2, local_type, _) => {
-                self.evaluate_types_for_expr(&mut **expr2, tree, var_env, type_param_env, local_types, errs)?;
+                self.evaluate_types_for_expr(expr2, tree, var_env, type_param_env, local_types, errs)?;
                 *local_type = Some(local_types.add_type_param(Rc::new(RefCell::new(TypeParamEntry::new()))));
             },
             Expr::Shared(expr2, local_type, _) => {
-                self.evaluate_types_for_expr(&mut **expr2, tree, var_env, type_param_env, local_types, errs)?;
+                self.evaluate_types_for_expr(expr2, tree, var_env, type_param_env, local_types, errs)?;
                 *local_type = Some(local_types.add_type_param(Rc::new(RefCell::new(TypeParamEntry::new()))));
             },
             Expr::Typed(expr2, type_expr, local_type, _) => {
-                self.evaluate_types_for_expr(&mut **expr2, tree, var_env, type_param_env, local_types, errs)?;
-                match self.evaluate_type_for_type_expr(&**type_expr, tree, type_param_env, &mut None, errs)? {
+                self.evaluate_types_for_expr(expr2, tree, var_env, type_param_env, local_types, errs)?;
+                match self.evaluate_type_for_type_expr(type_expr, tree, type_param_env, &mut None, errs)? {
                     Some(type_value) => *local_type = Some(local_types.add_type_value(type_value)),
                     None => *local_type = Some(local_types.add_type_param(Rc::new(RefCell::new(TypeParamEntry::new())))),
                 }
             },
             Expr::As(expr2, type_expr, local_type, _) => {
-                self.evaluate_types_for_expr(&mut **expr2, tree, var_env, type_param_env, local_types, errs)?;
-                match self.evaluate_type_for_type_expr(&**type_expr, tree, type_param_env, &mut None, errs)? {
+                self.evaluate_types_for_expr(expr2, tree, var_env, type_param_env, local_types, errs)?;
+                match self.evaluate_type_for_type_expr(type_expr, tree, type_param_env, &mut None, errs)? {
                     Some(type_value) => *local_type = Some(local_types.add_type_value(type_value.clone())),
                     None => *local_type = Some(local_types.add_type_param(Rc::new(RefCell::new(TypeParamEntry::new())))),
                 }
             },
             Expr::If(expr2, expr3, expr4, local_type, _) => {
-                self.evaluate_types_for_expr(&mut **expr2, tree, var_env, type_param_env, local_types, errs)?;
-                self.evaluate_types_for_expr(&mut **expr3, tree, var_env, type_param_env, local_types, errs)?;
-                self.evaluate_types_for_expr(&mut **expr4, tree, var_env, type_param_env, local_types, errs)?;
+                self.evaluate_types_for_expr(expr2, tree, var_env, type_param_env, local_types, errs)?;
+                self.evaluate_types_for_expr(expr3, tree, var_env, type_param_env, local_types, errs)?;
+                self.evaluate_types_for_expr(expr4, tree, var_env, type_param_env, local_types, errs)?;
                 *local_type = Some(local_types.add_type_param(Rc::new(RefCell::new(TypeParamEntry::new()))));
             },
             Expr::Let(binds, expr2, local_type, _) => {
@@ -2344,23 +2288,23 @@ impl Typer
                 for bind in binds {
                     match bind {
                         Bind(pattern, expr3) => {
-                            self.evaluate_types_for_expr(&mut **expr3, tree, var_env, type_param_env, local_types, errs)?;
-                            self.evaluate_types_for_pattern(&mut **pattern, tree, var_env, type_param_env, local_types, errs)?;
+                            self.evaluate_types_for_expr(expr3, tree, var_env, type_param_env, local_types, errs)?;
+                            self.evaluate_types_for_pattern(pattern, tree, var_env, type_param_env, local_types, errs)?;
                         },
                     }
                 }
-                self.evaluate_types_for_expr(&mut **expr2, tree, var_env, type_param_env, local_types, errs)?;
+                self.evaluate_types_for_expr(expr2, tree, var_env, type_param_env, local_types, errs)?;
                 *local_type = Some(local_types.add_type_param(Rc::new(RefCell::new(TypeParamEntry::new()))));
                 var_env.pop_vars();
             },
             Expr::Match(expr2, cases, local_type, _) => {
-                self.evaluate_types_for_expr(&mut **expr2, tree, var_env, type_param_env, local_types, errs)?;
+                self.evaluate_types_for_expr(expr2, tree, var_env, type_param_env, local_types, errs)?;
                 for case in cases {
                     match case {
                         Case(pattern, expr3) => {
                             var_env.push_new_vars();
-                            self.evaluate_types_for_pattern(&mut **pattern, tree, var_env, type_param_env, local_types, errs)?;
-                            self.evaluate_types_for_expr(&mut **expr3, tree, var_env, type_param_env, local_types, errs)?;
+                            self.evaluate_types_for_pattern(pattern, tree, var_env, type_param_env, local_types, errs)?;
+                            self.evaluate_types_for_expr(expr3, tree, var_env, type_param_env, local_types, errs)?;
                             var_env.pop_vars();
                         },
                     }
@@ -2381,7 +2325,7 @@ impl Typer
             Pattern::As(literal, type_expr, local_type1, local_type2, _) => {
                 self.evaluate_types_for_literal(&mut **literal, tree, var_env, type_param_env, local_types, errs, Self::evaluate_types_for_pattern)?;
                 *local_type1 = Some(local_types.add_type_param(Rc::new(RefCell::new(TypeParamEntry::new()))));
-                match self.evaluate_type_for_type_expr(&**type_expr, tree, type_param_env, &mut None, errs)? {
+                match self.evaluate_type_for_type_expr(type_expr, tree, type_param_env, &mut None, errs)? {
                     Some(type_value) => *local_type2 = Some(local_types.add_type_value(type_value.clone())),
                     None => *local_type2 = Some(local_types.add_type_param(Rc::new(RefCell::new(TypeParamEntry::new())))),
                 }
@@ -2389,7 +2333,7 @@ impl Typer
             Pattern::Const(_, local_type, _) => *local_type = Some(local_types.add_type_param(Rc::new(RefCell::new(TypeParamEntry::new())))),
             Pattern::UnnamedFieldCon(_, patterns, con_local_type, local_type, _) => {
                 for pattern2 in patterns {
-                    self.evaluate_types_for_pattern(&mut **pattern2, tree, var_env, type_param_env, local_types, errs)?;
+                    self.evaluate_types_for_pattern(pattern2, tree, var_env, type_param_env, local_types, errs)?;
                 }
                 *con_local_type = Some(local_types.add_type_param(Rc::new(RefCell::new(TypeParamEntry::new()))));
                 *local_type = Some(local_types.add_type_param(Rc::new(RefCell::new(TypeParamEntry::new()))));
@@ -2408,12 +2352,12 @@ impl Typer
                 let new_local_type = local_types.add_type_param(Rc::new(RefCell::new(TypeParamEntry::new())));
                 var_env.add_var(ident.clone(), new_local_type);
                 *local_type = Some(new_local_type);
-                self.evaluate_types_for_pattern(&mut **pattern2, tree, var_env, type_param_env, local_types, errs)?;
+                self.evaluate_types_for_pattern(pattern2, tree, var_env, type_param_env, local_types, errs)?;
             },
             Pattern::Wildcard(local_type, _) => *local_type = Some(local_types.add_type_param(Rc::new(RefCell::new(TypeParamEntry::new())))),
             Pattern::Alt(patterns, local_type, _) => {
                 for pattern2 in patterns {
-                    self.evaluate_types_for_pattern(&mut **pattern2, tree, var_env, type_param_env, local_types, errs)?;
+                    self.evaluate_types_for_pattern(pattern2, tree, var_env, type_param_env, local_types, errs)?;
                 }
                 *local_type = Some(local_types.add_type_param(Rc::new(RefCell::new(TypeParamEntry::new()))));
             },
@@ -2474,17 +2418,14 @@ impl Typer
                         LambdaArg(_, _, None, _) => return Err(FrontendErrors::new(vec![FrontendError::Internal(String::from("set_shareds_for_expr: no local type"))])),
                     }
                 }
-                self.set_shareds_for_expr(&**body, tree, var_env, local_types, errs)?;
+                self.set_shareds_for_expr(body, tree, var_env, local_types, errs)?;
                 var_env.foreach_with_result(|ident, tuple| self.set_shared_for_tuple(ident, tuple, tree, local_types, errs))?;
                 var_env.pop_vars();
             },
             Expr::Var(ident, _, pos) => {
-                match var_env.var_mut(ident) {
-                    Some((_, counter, pos2)) => {
-                        *counter += 1;
-                        *pos2 = pos.clone();
-                    },
-                    None => (),
+                if let Some((_, counter, pos2)) = var_env.var_mut(ident) {
+                    *counter += 1;
+                    *pos2 = pos.clone();
                 }
             },
             Expr::NamedFieldConApp(_, expr_named_field_pairs, _, _, _) => {
@@ -2492,41 +2433,41 @@ impl Typer
             },
             Expr::PrintfApp(exprs, _, _) => {
                 for expr2 in exprs {
-                    self.set_shareds_for_expr(&**expr2, tree, var_env, local_types, errs)?;
+                    self.set_shareds_for_expr(expr2, tree, var_env, local_types, errs)?;
                 }
             },
             Expr::App(expr2, exprs, _, _) => {
-                self.set_shareds_for_expr(&**expr2, tree, var_env, local_types, errs)?;
+                self.set_shareds_for_expr(expr2, tree, var_env, local_types, errs)?;
                 for expr3 in exprs {
-                    self.set_shareds_for_expr(&**expr3, tree, var_env, local_types, errs)?;
+                    self.set_shareds_for_expr(expr3, tree, var_env, local_types, errs)?;
                 }
             },
-            Expr::GetField(expr2, _, _, _) => self.set_shareds_for_expr(&**expr2, tree, var_env, local_types, errs)?,
-            Expr::Get2Field(expr2, _, _, _) => self.set_shareds_for_expr(&**expr2, tree, var_env, local_types, errs)?,
+            Expr::GetField(expr2, _, _, _) => self.set_shareds_for_expr(expr2, tree, var_env, local_types, errs)?,
+            Expr::Get2Field(expr2, _, _, _) => self.set_shareds_for_expr(expr2, tree, var_env, local_types, errs)?,
             Expr::SetField(expr2, _, expr3, _, _) => {
-                self.set_shareds_for_expr(&**expr2, tree, var_env, local_types, errs)?;
-                self.set_shareds_for_expr(&**expr3, tree, var_env, local_types, errs)?;
+                self.set_shareds_for_expr(expr2, tree, var_env, local_types, errs)?;
+                self.set_shareds_for_expr(expr3, tree, var_env, local_types, errs)?;
             },
             Expr::UpdateField(expr2, _, expr3, _, _) => {
-                self.set_shareds_for_expr(&**expr2, tree, var_env, local_types, errs)?;
-                self.set_shareds_for_expr(&**expr3, tree, var_env, local_types, errs)?;
+                self.set_shareds_for_expr(expr2, tree, var_env, local_types, errs)?;
+                self.set_shareds_for_expr(expr3, tree, var_env, local_types, errs)?;
             },
             Expr::UpdateGet2Field(expr2, _, expr3, _, _) => {
-                self.set_shareds_for_expr(&**expr2, tree, var_env, local_types, errs)?;
-                self.set_shareds_for_expr(&**expr3, tree, var_env, local_types, errs)?;
+                self.set_shareds_for_expr(expr2, tree, var_env, local_types, errs)?;
+                self.set_shareds_for_expr(expr3, tree, var_env, local_types, errs)?;
             },
-            Expr::Uniq(expr2, _, _) => self.set_shareds_for_expr(&**expr2, tree, var_env, local_types, errs)?,
-            Expr::Shared(expr2, _, _) => self.set_shareds_for_expr(&**expr2, tree, var_env, local_types, errs)?,
-            Expr::Typed(expr2, _, _, _) => self.set_shareds_for_expr(&**expr2, tree, var_env, local_types, errs)?,
-            Expr::As(expr2, _, _, _) => self.set_shareds_for_expr(&**expr2, tree, var_env, local_types, errs)?,
+            Expr::Uniq(expr2, _, _) => self.set_shareds_for_expr(expr2, tree, var_env, local_types, errs)?,
+            Expr::Shared(expr2, _, _) => self.set_shareds_for_expr(expr2, tree, var_env, local_types, errs)?,
+            Expr::Typed(expr2, _, _, _) => self.set_shareds_for_expr(expr2, tree, var_env, local_types, errs)?,
+            Expr::As(expr2, _, _, _) => self.set_shareds_for_expr(expr2, tree, var_env, local_types, errs)?,
             Expr::If(expr2, expr3, expr4, _, _) => {
-                self.set_shareds_for_expr(&**expr2, tree, var_env, local_types, errs)?;
+                self.set_shareds_for_expr(expr2, tree, var_env, local_types, errs)?;
                 let saved_var_stack_idx = var_env.saved_var_stack_len();
                 var_env.push_saved_vars();
-                self.set_shareds_for_expr(&**expr3, tree, var_env, local_types, errs)?;
+                self.set_shareds_for_expr(expr3, tree, var_env, local_types, errs)?;
                 var_env.swap_saved_vars();
                 var_env.push_saved_vars();
-                self.set_shareds_for_expr(&**expr4, tree, var_env, local_types, errs)?;
+                self.set_shareds_for_expr(expr4, tree, var_env, local_types, errs)?;
                 var_env.swap_saved_vars();
                 var_env.merge_and_pop_saved_var_vars(saved_var_stack_idx, merge_tuples);
             },
@@ -2535,25 +2476,25 @@ impl Typer
                 for bind in binds {
                     match bind {
                         Bind(pattern, expr3) => {
-                            self.set_shareds_for_expr(&**expr3, tree, var_env, local_types, errs)?;
-                            self.set_shareds_for_pattern(&**pattern, tree, var_env, local_types, errs)?;
+                            self.set_shareds_for_expr(expr3, tree, var_env, local_types, errs)?;
+                            self.set_shareds_for_pattern(pattern, tree, var_env, local_types, errs)?;
                         },
                     }
                 }
-                self.set_shareds_for_expr(&**expr2, tree, var_env, local_types, errs)?;
+                self.set_shareds_for_expr(expr2, tree, var_env, local_types, errs)?;
                 var_env.foreach_with_result(|ident, tuple| self.set_shared_for_tuple(ident, tuple, tree, local_types, errs))?;
                 var_env.pop_vars();
             },
             Expr::Match(expr2, cases, _, _) => {
-                self.set_shareds_for_expr(&**expr2, tree, var_env, local_types, errs)?;
+                self.set_shareds_for_expr(expr2, tree, var_env, local_types, errs)?;
                 let saved_var_stack_idx = var_env.saved_var_stack_len();
                 for case in cases {
                     match case {
                         Case(pattern, expr3) => {
                             var_env.push_saved_vars();
                             var_env.push_new_vars();
-                            self.set_shareds_for_pattern(&**pattern, tree, var_env, local_types, errs)?;
-                            self.set_shareds_for_expr(&**expr3, tree, var_env, local_types, errs)?;
+                            self.set_shareds_for_pattern(pattern, tree, var_env, local_types, errs)?;
+                            self.set_shareds_for_expr(expr3, tree, var_env, local_types, errs)?;
                             var_env.foreach_with_result(|ident, tuple| self.set_shared_for_tuple(ident, tuple, tree, local_types, errs))?;
                             var_env.pop_vars();
                             var_env.swap_saved_vars();
@@ -2574,7 +2515,7 @@ impl Typer
             Pattern::Const(_, _, _) => (),
             Pattern::UnnamedFieldCon(_, patterns, _, _, _) => {
                 for pattern2 in patterns {
-                    self.set_shareds_for_pattern(&**pattern2, tree, var_env, local_types, errs)?;
+                    self.set_shareds_for_pattern(pattern2, tree, var_env, local_types, errs)?;
                 }
             },
             Pattern::NamedFieldCon(_, pattern_named_field_pairs, _, _, _) => {
@@ -2586,7 +2527,7 @@ impl Typer
             Pattern::Var(_, _, None, _) => return Err(FrontendErrors::new(vec![FrontendError::Internal(String::from("set_shareds_for_pattern: no local type"))])),
             Pattern::At(_, ident, pattern2, Some(local_type), pos) => {
                 var_env.add_var(ident.clone(), (*local_type, 0, pos.clone()));
-                self.set_shareds_for_pattern(&**pattern2, tree, var_env, local_types, errs)?;
+                self.set_shareds_for_pattern(pattern2, tree, var_env, local_types, errs)?;
             },
             Pattern::At(_, _, _, None, _) => return Err(FrontendErrors::new(vec![FrontendError::Internal(String::from("set_shareds_for_pattern: no local type"))])),
             Pattern::Wildcard(_, _) => (),
@@ -2622,7 +2563,7 @@ impl Typer
                 let mut local_types: Vec<LocalType> = Vec::new();
                 let type_param_entry_r = type_param_entry.borrow();
                 for type_value in &type_param_entry_r.type_values {
-                    self.add_local_types_for_type_value_and_substitution(&**type_value, type_values, &mut local_types, processed_local_types)?;
+                    self.add_local_types_for_type_value_and_substitution(type_value, type_values, &mut local_types, processed_local_types)?;
                 }
                 Ok(local_types)
             },
@@ -2636,7 +2577,7 @@ impl Typer
             TypeValue::Param(_, local_type) => add_local_type_for_substitution(*local_type, type_values, local_types, processed_local_types)?,
             TypeValue::Type(_, _, type_values2) => {
                 for type_value2 in type_values2 {
-                    self.add_local_types_for_type_value_and_substitution(&**type_value2, type_values, local_types, processed_local_types)?;
+                    self.add_local_types_for_type_value_and_substitution(type_value2, type_values, local_types, processed_local_types)?;
                 }
             },
         }
@@ -2698,36 +2639,30 @@ impl Typer
         };
         let mut new_type = Type::new_with_type_param_entry_count(new_type_value, i);
         for (local_type, type_param_entry) in local_types.iter().zip(typ.type_param_entries().iter()) {
-            match local_type {
-                Some(local_type) => {
-                    let type_param_entry_r = type_param_entry.borrow();
-                    match new_type.type_param_entry(*local_type) {
-                        Some(new_type_param_entry) => {
-                            let mut new_type_param_entry_r = new_type_param_entry.borrow_mut();
-                            *new_type_param_entry_r = (*type_param_entry_r).clone();
-                            for type_value in &mut new_type_param_entry_r.type_values {
-                                match typ.type_value().substitute(&type_values) {
-                                    Ok(Some(type_value2)) => *type_value = type_value2,
-                                    Ok(None) => (),
-                                    Err(err) => return Err(FrontendErrors::new(vec![FrontendError::Internal(format!("new_type_by_substitution: {}", err))])),
-                                }
+            if let Some(local_type) = local_type {
+                let type_param_entry_r = type_param_entry.borrow();
+                match new_type.type_param_entry(*local_type) {
+                    Some(new_type_param_entry) => {
+                        let mut new_type_param_entry_r = new_type_param_entry.borrow_mut();
+                        *new_type_param_entry_r = (*type_param_entry_r).clone();
+                        for type_value in &mut new_type_param_entry_r.type_values {
+                            match typ.type_value().substitute(&type_values) {
+                                Ok(Some(type_value2)) => *type_value = type_value2,
+                                Ok(None) => (),
+                                Err(err) => return Err(FrontendErrors::new(vec![FrontendError::Internal(format!("new_type_by_substitution: {}", err))])),
                             }
-                        },
-                        None => return Err(FrontendErrors::new(vec![FrontendError::Internal(String::from("new_type_by_substitution: no new type parameter entry"))])),
-                    }
-                },
-                None => (),
+                        }
+                    },
+                    None => return Err(FrontendErrors::new(vec![FrontendError::Internal(String::from("new_type_by_substitution: no new type parameter entry"))])),
+                }
             }
         }
         for j in 0..typ.type_param_entries().len() {
             for k in (j + 1)..typ.type_param_entries().len() {
-                match (local_types[j], local_types[k]) {
-                    (Some(local_type1), Some(local_type2)) => {
-                        if typ.has_eq_type_params(LocalType::new(j), LocalType::new(k)) {
-                            new_type.set_eq_type_params(local_type1, local_type2);
-                        }
-                    },
-                    _ => (),
+                if let (Some(local_type1), Some(local_type2)) = (local_types[j], local_types[k]) {
+                    if typ.has_eq_type_params(LocalType::new(j), LocalType::new(k)) {
+                        new_type.set_eq_type_params(local_type1, local_type2);
+                    }
                 }
             }
         }
@@ -2755,9 +2690,9 @@ impl Typer
                             Some(var) => {
                                 let var_r = var.borrow();
                                 match &*var_r {
-                                    Var::Builtin(_, Some(typ)) => self.new_type_by_substitution(&**typ, trait_ident, type_name)?,
-                                    Var::Var(_, _, _, _, _, _, _, Some(typ), _) => self.new_type_by_substitution(&**typ, trait_ident, type_name)?,
-                                    Var::Fun(_, _, Some(typ)) => self.new_type_by_substitution(&**typ, trait_ident, type_name)?,
+                                    Var::Builtin(_, Some(typ)) => self.new_type_by_substitution(typ, trait_ident, type_name)?,
+                                    Var::Var(_, _, _, _, _, _, _, Some(typ), _) => self.new_type_by_substitution(typ, trait_ident, type_name)?,
+                                    Var::Fun(_, _, Some(typ)) => self.new_type_by_substitution(typ, trait_ident, type_name)?,
                                     Var::Builtin(_, None) => {
                                         errs.push(FrontendError::Message(pos, format!("built-in variable {} hasn't evaluated type in trait {}", ident, trait_ident)));
                                         return Ok(());
@@ -2782,10 +2717,8 @@ impl Typer
         };
         match impl_var {
             ImplVar::Builtin(typ) => {
-                if !is_builtin_impl {
-                    if !self.builtins.has_impl_var_tuple(&(trait_ident.clone(), type_name.clone(), ident.clone())) {
-                        errs.push(FrontendError::Message(pos, format!("implementation variable {} mustn't be built-in variable", ident)))
-                    }
+                if !is_builtin_impl && !self.builtins.has_impl_var_tuple(&(trait_ident.clone(), type_name.clone(), ident.clone())) {
+                    errs.push(FrontendError::Message(pos, format!("implementation variable {} mustn't be built-in variable", ident)))
                 }
                 *typ = Some(Box::new(new_type));
             },
@@ -2808,9 +2741,9 @@ impl Typer
                     type_param_env.add_var(type_param_ident.clone(), new_local_type);
                 }
                 let mut var_env: Environment<LocalType> = Environment::new();
-                self.evaluate_types_for_expr(&mut **expr, tree, &mut var_env, &mut type_param_env, &mut new_local_types, errs)?;
+                self.evaluate_types_for_expr(expr, tree, &mut var_env, &mut type_param_env, &mut new_local_types, errs)?;
                 let mut var_env2: Environment<(LocalType, usize, Pos)> = Environment::new();
-                self.set_shareds_for_expr(&**expr, tree, &mut var_env2, &new_local_types, errs)?;
+                self.set_shareds_for_expr(expr, tree, &mut var_env2, &new_local_types, errs)?;
                 *local_type = Some(new_local_type);
                 *local_types = Some(Box::new(new_local_types));
                 *typ = Some(Box::new(new_type));
@@ -2838,7 +2771,7 @@ impl Typer
                                 }
                                 let mut var_env: Environment<LocalType> = Environment::new();
                                 var_env.push_new_vars();
-                                if new_local_types2.len() >= 1 {
+                                if !new_local_types2.is_empty() {
                                     let mut new_local_types3: Vec<LocalType> = Vec::new();
                                     for i in 0..args.len() {
                                         match &mut args[i] {
@@ -2855,7 +2788,7 @@ impl Typer
                                             },
                                         }
                                     }
-                                    self.evaluate_types_for_expr(&mut **body, tree, &mut var_env, &mut type_param_env, &mut new_local_types, errs)?;
+                                    self.evaluate_types_for_expr(body, tree, &mut var_env, &mut type_param_env, &mut new_local_types, errs)?;
                                     let mut var_env2: Environment<(LocalType, usize, Pos)> = Environment::new();
                                     var_env2.push_new_vars();
                                     for i in 0..args.len() {
@@ -2869,7 +2802,7 @@ impl Typer
                                             },
                                         }
                                     }
-                                    self.set_shareds_for_expr(&**body, tree, &mut var_env2, &new_local_types, errs)?;
+                                    self.set_shareds_for_expr(body, tree, &mut var_env2, &new_local_types, errs)?;
                                     var_env2.foreach_with_result(|ident, tuple| self.set_shared_for_tuple(ident, tuple, tree, &new_local_types, errs))?;
                                     *ret_local_type = Some(new_local_types2[new_local_types2.len() - 1]);
                                     *local_types = Some(Box::new(new_local_types));
@@ -2904,11 +2837,11 @@ impl Typer
     {
         for def in tree.defs() {
             match &**def {
-                Def::Var(ident, var, _) => {
+                Def::Var(_, ident, var, _) => {
                     let mut var_r = var.borrow_mut();
-                    self.infer_types_for_var(ident, &mut *var_r, tree, errs)?;
+                    self.infer_types_for_var(ident, &mut var_r, tree, errs)?;
                 },
-                Def::Trait(ident, trait1, _) => {
+                Def::Trait(_, ident, trait1, _) => {
                     let trait_r = trait1.borrow();
                     match &*trait_r {
                         Trait(_, trait_defs, _) => {
@@ -2916,7 +2849,7 @@ impl Typer
                                 match &**trait_def {
                                     TraitDef(_, var, _) => {
                                         let mut var_r = var.borrow_mut();
-                                        self.infer_types_for_var(ident, &mut *var_r, tree, errs)?;
+                                        self.infer_types_for_var(ident, &mut var_r, tree, errs)?;
                                     },
                                 }
                             }
@@ -2932,7 +2865,7 @@ impl Typer
                                 match &**impl_def {
                                     ImplDef(_, impl_var, pos) => {
                                         let mut impl_var_r = impl_var.borrow_mut();
-                                        self.infer_types_for_impl_var(&mut *impl_var_r, pos, tree, errs)?;
+                                        self.infer_types_for_impl_var(&mut impl_var_r, pos, tree, errs)?;
                                     },
                                 }
                             }
@@ -3006,8 +2939,8 @@ impl Typer
             Var::Var(_, type_expr, _, Some(expr), _, Some(local_type), Some(local_types), Some(typ), _) => {
                 let mut var_env: Environment<()> = Environment::new();
                 let mut closure_stack = ClosureStack::new();
-                let local_type2 = self.infer_types_for_expr(&mut **expr, tree, &mut var_env, &mut closure_stack, &mut **local_types, Some((ident, typ)), errs)?;
-                self.match_local_types(*local_type, local_type2, type_expr_pos(&**type_expr), tree, local_types, errs)?;
+                let local_type2 = self.infer_types_for_expr(expr, tree, &mut var_env, &mut closure_stack, local_types, Some((ident, typ)), errs)?;
+                self.match_local_types(*local_type, local_type2, type_expr_pos(type_expr), tree, local_types, errs)?;
             },
             Var::Var(_, _, _, None, _, _, _, _, _) => (),
             Var::Fun(fun, _, Some(typ)) => {
@@ -3023,8 +2956,8 @@ impl Typer
                                 },
                             }
                         }
-                        let ret_local_type2 = self.infer_types_for_expr(&mut **body, tree, &mut var_env, &mut closure_stack, &mut **local_types, Some((ident, typ)), errs)?;
-                        self.match_local_types(*ret_local_type, ret_local_type2, type_expr_pos(&**ret_type_expr), tree, local_types, errs)?;
+                        let ret_local_type2 = self.infer_types_for_expr(body, tree, &mut var_env, &mut closure_stack, local_types, Some((ident, typ)), errs)?;
+                        self.match_local_types(*ret_local_type, ret_local_type2, type_expr_pos(ret_type_expr), tree, local_types, errs)?;
                     },
                     Fun::Fun(_, _, _, _, None, _, _) => (),
                     Fun::Con(_) => return Err(FrontendErrors::new(vec![FrontendError::Internal(String::from("infer_types_for_var: variable is contructor"))])),
@@ -3132,7 +3065,7 @@ impl Typer
                                                     if cons.len() == 1 {
                                                         let con_r = cons[0].borrow();
                                                         match &*con_r {
-                                                            Con::NamedField(_, _, _, Some(named_fields), _) => {
+                                                            Con::NamedField(_, _, _, _, Some(named_fields), _) => {
                                                                 match named_fields.field_index(field_ident) {
                                                                     Some(tmp_field_idx) => Some(tmp_field_idx),
                                                                     None => {
@@ -3141,13 +3074,13 @@ impl Typer
                                                                     },
                                                                 }
                                                             },
-                                                            Con::NamedField(_, _, _, None, _) => return Err(FrontendErrors::new(vec![FrontendError::Internal(String::from("local_type_for_fields: type variable isn't type or no fields"))])),
+                                                            Con::NamedField(_, _, _, _, None, _) => return Err(FrontendErrors::new(vec![FrontendError::Internal(String::from("local_type_for_fields: type variable isn't type or no fields"))])),
                                                             _ => {
                                                                 errs.push(FrontendError::Message(pos.clone(), format!("type {} has constructor without named fields", LocalTypeWithLocalTypes(current_local_type, local_types))));
                                                                 None
                                                             },
                                                         }
-                                                    } else if cons.len() == 0 {
+                                                    } else if cons.is_empty() {
                                                         errs.push(FrontendError::Message(pos.clone(), format!("type {} hasn't constructor for fields", LocalTypeWithLocalTypes(current_local_type, local_types))));
                                                         None
                                                     } else {
@@ -3235,13 +3168,13 @@ impl Typer
                                                     if cons.len() == 1 {
                                                         let con_r = cons[0].borrow();
                                                         let con_ident = match &*con_r {
-                                                            Con::UnnamedField(tmp_con_ident, _, _, _) => tmp_con_ident,
-                                                            Con::NamedField(tmp_con_ident, _, _, _, _) => tmp_con_ident,
+                                                            Con::UnnamedField(_, tmp_con_ident, _, _, _) => tmp_con_ident,
+                                                            Con::NamedField(_, tmp_con_ident, _, _, _, _) => tmp_con_ident,
                                                         };
                                                         let is_success = type_for_fun_ident_in(con_ident, tree, |typ| {
                                                                 match &**typ.type_value() {
                                                                     TypeValue::Type(_, TypeValueName::Fun, type_values2) => {
-                                                                        if type_values2.len() >= 1 {
+                                                                        if !type_values2.is_empty() {
                                                                             if field_idx < type_values2.len() - 1 {
                                                                                 let new_type_value = match type_values2[field_idx].substitute(type_values) {
                                                                                     Ok(Some(tmp_type_value)) => tmp_type_value,
@@ -3264,7 +3197,7 @@ impl Typer
                                                         if !is_success {
                                                             return Ok(None);
                                                         }
-                                                    } else if cons.len() == 0 {
+                                                    } else if cons.is_empty() {
                                                         errs.push(FrontendError::Message(pos.clone(), format!("type {} hasn't constructor for fields", LocalTypeWithLocalTypes(current_local_type, local_types))));
                                                         return Ok(None);
                                                     } else {
@@ -3291,14 +3224,11 @@ impl Typer
                         },
                         None => return Err(FrontendErrors::new(vec![FrontendError::Internal(String::from("local_type_for_fields: no local type entry"))])),
                     }
-                    match &ref_type_tuple {
-                        Some((ref_uniq_flag, ref_type_ident, ref_type_values)) => {
-                            let mut new_ref_type_values: Vec<Rc<TypeValue>> = vec![Rc::new(TypeValue::Param(UniqFlag::None, current_local_type))];
-                            new_ref_type_values.extend_from_slice(ref_type_values.as_slice());
-                            let new_type_value = Rc::new(TypeValue::Type(*ref_uniq_flag, TypeValueName::Name(ref_type_ident.clone()), new_ref_type_values));
-                            current_local_type = local_types.add_type_value(new_type_value);
-                        },
-                        None => (),
+                    if let Some((ref_uniq_flag, ref_type_ident, ref_type_values)) = &ref_type_tuple {
+                        let mut new_ref_type_values: Vec<Rc<TypeValue>> = vec![Rc::new(TypeValue::Param(UniqFlag::None, current_local_type))];
+                        new_ref_type_values.extend_from_slice(ref_type_values.as_slice());
+                        let new_type_value = Rc::new(TypeValue::Type(*ref_uniq_flag, TypeValueName::Name(ref_type_ident.clone()), new_ref_type_values));
+                        current_local_type = local_types.add_type_value(new_type_value);
                     }
                 },
                 _ => return Ok(None),
@@ -3414,46 +3344,98 @@ impl Typer
                 match exprs.first_mut() {
                     Some(expr2) => {
                         if self.check_builtin_type_ident(&String::from("Char"), 0, pos.clone(), tree, errs)? && self.check_builtin_type_ident(&String::from("ConstantSlice"), 1, pos.clone(), tree, errs)? {
-                            let expr2_local_type = self.infer_types_for_expr(&mut **expr2, tree, var_env, closure_stack, local_types, rec_pair, errs)?;
+                            let expr2_local_type = self.infer_types_for_expr(expr2, tree, var_env, closure_stack, local_types, rec_pair, errs)?;
                             let str_type_value = Rc::new(TypeValue::Type(UniqFlag::None, TypeValueName::Name(String::from("ConstantSlice")), vec![Rc::new(TypeValue::Type(UniqFlag::None, TypeValueName::Name(String::from("Char")), Vec::new()))]));
-                            self.match_type_values(&Rc::new(TypeValue::Param(UniqFlag::None, expr2_local_type)), &str_type_value, expr_pos(&**expr2), tree, local_types, errs)?;
+                            self.match_type_values(&Rc::new(TypeValue::Param(UniqFlag::None, expr2_local_type)), &str_type_value, expr_pos(expr2), tree, local_types, errs)?;
                         }
-                        for expr3 in &mut exprs[1..] {
-                            let expr3_local_type = self.infer_types_for_expr(&mut **expr3, tree, var_env, closure_stack, local_types, rec_pair, errs)?;
+                        let convs = match &**expr2 {
+                            Expr::Literal(literal, _, _) => {
+                                match &**literal {
+                                    Literal::String(bs) => {
+                                        match parse_printf_format(bs.as_slice()) {
+                                            Ok(items) => {
+                                                let mut convs: Vec<PrintfConv> = Vec::new();
+                                                for item in items {
+                                                    match item {
+                                                        PrintfItem::Conv(conv) => convs.push(conv),
+                                                        PrintfItem::Bytes(_) => (),
+                                                    }
+                                                }
+                                                Some(convs)
+                                            },
+                                            Err(msg) => {
+                                                errs.push(FrontendError::Message(expr_pos(expr2).clone(), msg));
+                                                None
+                                            },
+                                        }
+                                    },
+                                    _ => None,
+                                }
+                            },
+                            _ => None,
+                        };
+                        for (i, expr3) in exprs[1..].iter_mut().enumerate() {
+                            let expr3_local_type = self.infer_types_for_expr(expr3, tree, var_env, closure_stack, local_types, rec_pair, errs)?;
                             match local_types.type_entry_for_type_value(&Rc::new(TypeValue::Param(UniqFlag::None, expr3_local_type))) {
                                 Some(LocalTypeEntry::Type(type_value)) => {
                                     match &*type_value {
                                         TypeValue::Type(_, TypeValueName::Name(expr3_type_ident), expr3_type_values) => {
                                             if !self.has_printable_for_type_ident(expr3_type_ident, tree)? {
-                                                errs.push(FrontendError::Message(expr_pos(&**expr3).clone(), format!("printf mustn't take values with type {}", LocalTypeWithLocalTypes(expr3_local_type, local_types))));
-                                            }
-                                            match expr3_type_values.first() {
-                                                Some(expr3_type_value) => {
-                                                    match local_types.type_entry_for_type_value(expr3_type_value) {
-                                                        Some(LocalTypeEntry::Type(type_value2)) => {
-                                                            match &*type_value2 {
-                                                                TypeValue::Type(_, TypeValueName::Name(expr3_type_ident2), _) => {
-                                                                    if !self.check_builtin_type_ident(&String::from("Char"), 0, pos.clone(), tree, errs)? || expr3_type_ident2 != &String::from("Char") {
-                                                                        errs.push(FrontendError::Message(expr_pos(&**expr3).clone(), format!("printf mustn't take values with type {}", LocalTypeWithLocalTypes(expr3_local_type, local_types))))
+                                                errs.push(FrontendError::Message(expr_pos(expr3).clone(), format!("printf mustn't take values with type {}", LocalTypeWithLocalTypes(expr3_local_type, local_types))));
+                                            } else {
+                                                if let Some(conv) = convs.as_ref().and_then(|convs| convs.get(i)) {
+                                                    let elem_type_ident = match expr3_type_values.first() {
+                                                        Some(expr3_type_value) => {
+                                                            match local_types.type_entry_for_type_value(expr3_type_value) {
+                                                                Some(LocalTypeEntry::Type(type_value2)) => {
+                                                                    match &*type_value2 {
+                                                                        TypeValue::Type(_, TypeValueName::Name(ident), _) => Some(ident.clone()),
+                                                                        _ => None,
                                                                     }
                                                                 },
-                                                                _ => errs.push(FrontendError::Message(expr_pos(&**expr3).clone(), format!("printf mustn't take values with type {}", LocalTypeWithLocalTypes(expr3_local_type, local_types)))),
+                                                                _ => None,
                                                             }
                                                         },
-                                                        Some(LocalTypeEntry::Param(_, _, _, _)) => errs.push(FrontendError::Message(expr_pos(expr3).clone(), format!("printf mustn't take values with type {}", LocalTypeWithLocalTypes(expr3_local_type, local_types)))),
-                                                        None => return Err(FrontendErrors::new(vec![FrontendError::Internal(String::from("infer_types_for_expr: no local type entry"))])), 
+                                                        None => None,
+                                                    };
+                                                    if !printf_conv_matches_type(conv, expr3_type_ident.as_str(), elem_type_ident.as_deref()) {
+                                                        errs.push(FrontendError::Message(expr_pos(expr3).clone(), format!("conversion specification {} doesn't match type {}", conv.spec, LocalTypeWithLocalTypes(expr3_local_type, local_types))));
+                                                    } else if conv.conv == 's' && !is_nul_terminated_string_expr(expr3, tree, var_env, rec_pair) {
+                                                        errs.push(FrontendError::Message(expr_pos(expr3).clone(), format!("conversion specification {} requires string literal or variable with string literal", conv.spec)));
                                                     }
-                                                },
-                                                None => (),
+                                                }
+                                            }
+                                            if let Some(expr3_type_value) = expr3_type_values.first() {
+                                                match local_types.type_entry_for_type_value(expr3_type_value) {
+                                                    Some(LocalTypeEntry::Type(type_value2)) => {
+                                                        match &*type_value2 {
+                                                            TypeValue::Type(_, TypeValueName::Name(expr3_type_ident2), _) => {
+                                                                if !self.check_builtin_type_ident(&String::from("Char"), 0, pos.clone(), tree, errs)? || expr3_type_ident2 != &String::from("Char") {
+                                                                    errs.push(FrontendError::Message(expr_pos(expr3).clone(), format!("printf mustn't take values with type {}", LocalTypeWithLocalTypes(expr3_local_type, local_types))))
+                                                                }
+                                                            },
+                                                            _ => errs.push(FrontendError::Message(expr_pos(expr3).clone(), format!("printf mustn't take values with type {}", LocalTypeWithLocalTypes(expr3_local_type, local_types)))),
+                                                        }
+                                                    },
+                                                    Some(LocalTypeEntry::Param(_, _, _, _)) => errs.push(FrontendError::Message(expr_pos(expr3).clone(), format!("printf mustn't take values with type {}", LocalTypeWithLocalTypes(expr3_local_type, local_types)))),
+                                                    None => return Err(FrontendErrors::new(vec![FrontendError::Internal(String::from("infer_types_for_expr: no local type entry"))])), 
+                                                }
                                             }
                                         },
-                                        _ => errs.push(FrontendError::Message(expr_pos(&**expr3).clone(), format!("printf mustn't take values with type {}", LocalTypeWithLocalTypes(expr3_local_type, local_types)))),
+                                        _ => errs.push(FrontendError::Message(expr_pos(expr3).clone(), format!("printf mustn't take values with type {}", LocalTypeWithLocalTypes(expr3_local_type, local_types)))),
                                     }
                                 },
                                 Some(LocalTypeEntry::Param(_, _, _, _)) => errs.push(FrontendError::Message(expr_pos(expr3).clone(), format!("printf must't take values with type {}", LocalTypeWithLocalTypes(expr3_local_type, local_types)))),
                                 None => return Err(FrontendErrors::new(vec![FrontendError::Internal(String::from("infer_types_for_expr: no local type entry"))])), 
                             }
                         }
+                        if let Some(convs) = convs {
+                            if convs.len() + 1 > exprs.len() {
+                                errs.push(FrontendError::Message(pos.clone(), String::from("too few arguments for printf format")));
+                            } else if convs.len() + 1 < exprs.len() {
+                                errs.push(FrontendError::Message(expr_pos(&exprs[convs.len() + 1]).clone(), String::from("too many arguments for printf format")));
+                            }
+                        }
                     },
                     None => errs.push(FrontendError::Message(pos.clone(), String::from("too few arguments for printf"))),
                 }
@@ -3463,10 +3445,10 @@ impl Typer
                 Ok(*local_type)
             },
             Expr::App(expr2, exprs, Some(local_type), pos) => {
-                let local_type2 = self.infer_types_for_expr(&mut **expr2, tree, var_env, closure_stack, local_types, rec_pair, errs)?;
+                let local_type2 = self.infer_types_for_expr(expr2, tree, var_env, closure_stack, local_types, rec_pair, errs)?;
                 let mut local_types2: Vec<LocalType> = Vec::new();
                 for expr3 in exprs {
-                    local_types2.push(self.infer_types_for_expr(&mut **expr3, tree, var_env, closure_stack, local_types, rec_pair, errs)?);
+                    local_types2.push(self.infer_types_for_expr(expr3, tree, var_env, closure_stack, local_types, rec_pair, errs)?);
                 }
                 let mut type_param_entry = TypeParamEntry::new();
                 type_param_entry.trait_names.insert(TraitName::Fun);
@@ -3477,81 +3459,68 @@ impl Typer
                 Ok(*local_type)
             },
             Expr::GetField(expr2, fields, Some(local_type), pos) => {
-                let local_type2 = self.infer_types_for_expr(&mut **expr2, tree, var_env, closure_stack, local_types, rec_pair, errs)?;
-                match self.local_type_for_fields(local_type2, fields, pos, tree, local_types, errs)? {
-                    Some(local_type3) => self.match_local_types(local_type3, *local_type, pos, tree, local_types, errs)?, 
-                    None => (),
+                let local_type2 = self.infer_types_for_expr(expr2, tree, var_env, closure_stack, local_types, rec_pair, errs)?;
+                if let Some(local_type3) = self.local_type_for_fields(local_type2, fields, pos, tree, local_types, errs)? {
+                    self.match_local_types(local_type3, *local_type, pos, tree, local_types, errs)?;
                 }
                 Ok(*local_type)
             },
             Expr::Get2Field(expr2, fields, Some(local_type), pos) => {
-                let local_type2 = self.infer_types_for_expr(&mut **expr2, tree, var_env, closure_stack, local_types, rec_pair, errs)?;
-                match self.local_type_for_fields(local_type2, fields, pos, tree, local_types, errs)? {
-                    Some(local_type3) => {
-                        // (t3, t2)
-                        let type_value = Rc::new(TypeValue::Type(UniqFlag::None, TypeValueName::Tuple, vec![Rc::new(TypeValue::Param(UniqFlag::None, local_type3)), Rc::new(TypeValue::Param(UniqFlag::None, local_type2))]));
-                        self.match_type_values(&Rc::new(TypeValue::Param(UniqFlag::None, *local_type)), &type_value, pos, tree, local_types, errs)?;
-                        self.set_shared_for_local_type_and_value(local_type3, pos, tree, local_types, errs)?;
-                    }
-                    None => (),
+                let local_type2 = self.infer_types_for_expr(expr2, tree, var_env, closure_stack, local_types, rec_pair, errs)?;
+                if let Some(local_type3) = self.local_type_for_fields(local_type2, fields, pos, tree, local_types, errs)? {
+                    // (t3, t2)
+                    let type_value = Rc::new(TypeValue::Type(UniqFlag::None, TypeValueName::Tuple, vec![Rc::new(TypeValue::Param(UniqFlag::None, local_type3)), Rc::new(TypeValue::Param(UniqFlag::None, local_type2))]));
+                    self.match_type_values(&Rc::new(TypeValue::Param(UniqFlag::None, *local_type)), &type_value, pos, tree, local_types, errs)?;
+                    self.set_shared_for_local_type_and_value(local_type3, pos, tree, local_types, errs)?;
                 }
                 Ok(*local_type)
             },
             Expr::SetField(expr2, fields, expr3, Some(local_type), pos) => {
-                let local_type2 = self.infer_types_for_expr(&mut **expr2, tree, var_env, closure_stack, local_types, rec_pair, errs)?;
-                let local_type3 = self.infer_types_for_expr(&mut **expr3, tree, var_env, closure_stack, local_types, rec_pair, errs)?;
-                match self.local_type_for_fields(local_type2, fields, pos, tree, local_types, errs)? {
-                    Some(local_type4) => {
-                        // t4
-                        self.match_local_types(local_type3, local_type4, expr_pos(&**expr3), tree, local_types, errs)?;
-                        // t2
-                        self.match_local_types(local_type2, *local_type, pos, tree, local_types, errs)?;
-                    },
-                    None => (),
+                let local_type2 = self.infer_types_for_expr(expr2, tree, var_env, closure_stack, local_types, rec_pair, errs)?;
+                let local_type3 = self.infer_types_for_expr(expr3, tree, var_env, closure_stack, local_types, rec_pair, errs)?;
+                if let Some(local_type4) = self.local_type_for_fields(local_type2, fields, pos, tree, local_types, errs)? {
+                    // t4
+                    self.match_local_types(local_type3, local_type4, expr_pos(expr3), tree, local_types, errs)?;
+                    // t2
+                    self.match_local_types(local_type2, *local_type, pos, tree, local_types, errs)?;
                 }
                 Ok(*local_type)
             },
             Expr::UpdateField(expr2, fields, expr3, Some(local_type), pos) => {
-                let local_type2 = self.infer_types_for_expr(&mut **expr2, tree, var_env, closure_stack, local_types, rec_pair, errs)?;
-                let local_type3 = self.infer_types_for_expr(&mut **expr3, tree, var_env, closure_stack, local_types, rec_pair, errs)?;
-                match self.local_type_for_fields(local_type2, fields, pos, tree, local_types, errs)? {
-                    Some(local_type4) => {
-                        // (t4) -> t4
-                        let mut type_param_entry = TypeParamEntry::new();
-                        type_param_entry.trait_names.insert(TraitName::Fun);
-                        type_param_entry.type_values = vec![Rc::new(TypeValue::Param(UniqFlag::None, local_type4)); 2];
-                        let fun_local_type = local_types.add_type_param(Rc::new(RefCell::new(type_param_entry)));
-                        self.match_local_types(local_type3, fun_local_type, expr_pos(&**expr3), tree, local_types, errs)?;
-                        // t2
-                        self.match_local_types(local_type2, *local_type, pos, tree, local_types, errs)?;
-                    },
-                    None => (),
+                let local_type2 = self.infer_types_for_expr(expr2, tree, var_env, closure_stack, local_types, rec_pair, errs)?;
+                let local_type3 = self.infer_types_for_expr(expr3, tree, var_env, closure_stack, local_types, rec_pair, errs)?;
+                if let Some(local_type4) = self.local_type_for_fields(local_type2, fields, pos, tree, local_types, errs)? {
+                    // (t4) -> t4
+                    let mut type_param_entry = TypeParamEntry::new();
+                    type_param_entry.trait_names.insert(TraitName::Fun);
+                    type_param_entry.type_values = vec![Rc::new(TypeValue::Param(UniqFlag::None, local_type4)); 2];
+                    let fun_local_type = local_types.add_type_param(Rc::new(RefCell::new(type_param_entry)));
+                    self.match_local_types(local_type3, fun_local_type, expr_pos(expr3), tree, local_types, errs)?;
+                    // t2
+                    self.match_local_types(local_type2, *local_type, pos, tree, local_types, errs)?;
                 }
                 Ok(*local_type)
             },
             Expr::UpdateGet2Field(expr2, fields, expr3, Some(local_type), pos) => {
-                let local_type2 = self.infer_types_for_expr(&mut **expr2, tree, var_env, closure_stack, local_types, rec_pair, errs)?;
-                let local_type3 = self.infer_types_for_expr(&mut **expr3, tree, var_env, closure_stack, local_types, rec_pair, errs)?;
-                match self.local_type_for_fields(local_type2, fields, pos, tree, local_types, errs)? {
-                    Some(local_type4) => {
-                        // (t4) -> (t5, t4)
-                        let mut type_param_entry = TypeParamEntry::new();
-                        type_param_entry.trait_names.insert(TraitName::Fun);
-                        let local_type5 = local_types.add_type_param(Rc::new(RefCell::new(TypeParamEntry::new())));
-                        let ret_type_value = Rc::new(TypeValue::Type(UniqFlag::None, TypeValueName::Tuple, vec![Rc::new(TypeValue::Param(UniqFlag::None, local_type5)), Rc::new(TypeValue::Param(UniqFlag::None, local_type4))]));
-                        type_param_entry.type_values = vec![Rc::new(TypeValue::Param(UniqFlag::None, local_type4)), ret_type_value];
-                        let fun_local_type = local_types.add_type_param(Rc::new(RefCell::new(type_param_entry)));
-                        self.match_local_types(local_type3, fun_local_type, expr_pos(&**expr3), tree, local_types, errs)?;
-                        // (t5, t2)
-                        let ret_type_value2 = Rc::new(TypeValue::Type(UniqFlag::None, TypeValueName::Tuple, vec![Rc::new(TypeValue::Param(UniqFlag::None, local_type5)), Rc::new(TypeValue::Param(UniqFlag::None, local_type2))]));
-                        self.match_type_values(&ret_type_value2, &Rc::new(TypeValue::Param(UniqFlag::None, *local_type)), pos, tree, local_types, errs)?;
-                    },
-                    None => (),
+                let local_type2 = self.infer_types_for_expr(expr2, tree, var_env, closure_stack, local_types, rec_pair, errs)?;
+                let local_type3 = self.infer_types_for_expr(expr3, tree, var_env, closure_stack, local_types, rec_pair, errs)?;
+                if let Some(local_type4) = self.local_type_for_fields(local_type2, fields, pos, tree, local_types, errs)? {
+                    // (t4) -> (t5, t4)
+                    let mut type_param_entry = TypeParamEntry::new();
+                    type_param_entry.trait_names.insert(TraitName::Fun);
+                    let local_type5 = local_types.add_type_param(Rc::new(RefCell::new(TypeParamEntry::new())));
+                    let ret_type_value = Rc::new(TypeValue::Type(UniqFlag::None, TypeValueName::Tuple, vec![Rc::new(TypeValue::Param(UniqFlag::None, local_type5)), Rc::new(TypeValue::Param(UniqFlag::None, local_type4))]));
+                    type_param_entry.type_values = vec![Rc::new(TypeValue::Param(UniqFlag::None, local_type4)), ret_type_value];
+                    let fun_local_type = local_types.add_type_param(Rc::new(RefCell::new(type_param_entry)));
+                    self.match_local_types(local_type3, fun_local_type, expr_pos(expr3), tree, local_types, errs)?;
+                    // (t5, t2)
+                    let ret_type_value2 = Rc::new(TypeValue::Type(UniqFlag::None, TypeValueName::Tuple, vec![Rc::new(TypeValue::Param(UniqFlag::None, local_type5)), Rc::new(TypeValue::Param(UniqFlag::None, local_type2))]));
+                    self.match_type_values(&ret_type_value2, &Rc::new(TypeValue::Param(UniqFlag::None, *local_type)), pos, tree, local_types, errs)?;
                 }
                 Ok(*local_type)
             },
             Expr::Uniq(expr2, Some(local_type), pos) => {
-                let local_type2 = self.infer_types_for_expr(&mut **expr2, tree, var_env, closure_stack, local_types, rec_pair, errs)?;
+                let local_type2 = self.infer_types_for_expr(expr2, tree, var_env, closure_stack, local_types, rec_pair, errs)?;
                 match local_types.type_entry_for_type_value(&Rc::new(TypeValue::Param(UniqFlag::None, local_type2))) {
                     Some(LocalTypeEntry::Type(type_value)) => {
                         match &*type_value {
@@ -3576,7 +3545,7 @@ impl Typer
                 Ok(*local_type)
             },
             Expr::Shared(expr2, Some(local_type), pos) => {
-                let local_type2 = self.infer_types_for_expr(&mut **expr2, tree, var_env, closure_stack, local_types, rec_pair, errs)?;
+                let local_type2 = self.infer_types_for_expr(expr2, tree, var_env, closure_stack, local_types, rec_pair, errs)?;
                 match local_types.type_entry_for_type_value(&Rc::new(TypeValue::Param(UniqFlag::None, local_type2))) {
                     Some(LocalTypeEntry::Type(type_value)) => {
                         match &*type_value {
@@ -3597,22 +3566,22 @@ impl Typer
                 Ok(*local_type)
             },
             Expr::Typed(expr2, _, Some(local_type), pos) => {
-                let local_type2 = self.infer_types_for_expr(&mut **expr2, tree, var_env, closure_stack, local_types, rec_pair, errs)?;
+                let local_type2 = self.infer_types_for_expr(expr2, tree, var_env, closure_stack, local_types, rec_pair, errs)?;
                 self.match_local_types(local_type2, *local_type, pos, tree, local_types, errs)?;
                 Ok(*local_type)
             },
             Expr::As(expr2, _, Some(local_type), pos) => {
-                let local_type2 = self.infer_types_for_expr(&mut **expr2, tree, var_env, closure_stack, local_types, rec_pair, errs)?;
+                let local_type2 = self.infer_types_for_expr(expr2, tree, var_env, closure_stack, local_types, rec_pair, errs)?;
                 self.cast_local_type(local_type2, *local_type, pos, tree, local_types, errs)?;
                 Ok(*local_type)
             },
             Expr::If(expr2, expr3, expr4, Some(local_type), pos) => {
-                let local_type2 = self.infer_types_for_expr(&mut **expr2, tree, var_env, closure_stack, local_types, rec_pair, errs)?;
+                let local_type2 = self.infer_types_for_expr(expr2, tree, var_env, closure_stack, local_types, rec_pair, errs)?;
                 if self.check_builtin_type_ident(&String::from("Bool"), 0, pos.clone(), tree, errs)? {
                     self.match_type_values(&Rc::new(TypeValue::Param(UniqFlag::None, local_type2)), &Rc::new(TypeValue::Type(UniqFlag::None, TypeValueName::Name(String::from("Bool")), Vec::new())), pos, tree, local_types, errs)?;
                 }
-                let local_type3 = self.infer_types_for_expr(&mut **expr3, tree, var_env, closure_stack, local_types, rec_pair, errs)?;
-                let local_type4 = self.infer_types_for_expr(&mut **expr4, tree, var_env, closure_stack, local_types, rec_pair, errs)?;
+                let local_type3 = self.infer_types_for_expr(expr3, tree, var_env, closure_stack, local_types, rec_pair, errs)?;
+                let local_type4 = self.infer_types_for_expr(expr4, tree, var_env, closure_stack, local_types, rec_pair, errs)?;
                 self.match_local_types(local_type3, local_type4, pos, tree, local_types, errs)?;
                 self.match_local_types(local_type3, *local_type, pos, tree, local_types, errs)?;
                 Ok(*local_type)
@@ -3622,31 +3591,31 @@ impl Typer
                 for bind in binds {
                     match bind {
                         Bind(pattern, expr3) => {
-                            let expr3_local_type = self.infer_types_for_expr(&mut **expr3, tree, var_env, closure_stack, local_types, rec_pair, errs)?;
+                            let expr3_local_type = self.infer_types_for_expr(expr3, tree, var_env, closure_stack, local_types, rec_pair, errs)?;
                             let mut var_local_types: Vec<(String, LocalType, Pos)> = Vec::new();
                             let mut is_var = false;
-                            let pattern_local_type = self.infer_types_for_pattern(&mut **pattern, tree, var_env, &mut var_local_types, &mut is_var, local_types, rec_pair, false, errs)?;
+                            let pattern_local_type = self.infer_types_for_pattern(pattern, tree, var_env, &mut var_local_types, &mut is_var, local_types, rec_pair, false, errs)?;
                             self.match_local_types_for_first_pattern_type(pattern_local_type, is_var, expr3_local_type, pattern_pos(pattern), tree, local_types, errs)?;
                         },
                     }
                 }
-                let local_type2 = self.infer_types_for_expr(&mut **expr2, tree, var_env, closure_stack, local_types, rec_pair, errs)?;
+                let local_type2 = self.infer_types_for_expr(expr2, tree, var_env, closure_stack, local_types, rec_pair, errs)?;
                 self.match_local_types(local_type2, *local_type, pos, tree, local_types, errs)?;
                 var_env.pop_vars();
                 Ok(*local_type)
             },
             Expr::Match(expr2, cases, Some(local_type), _) => {
-                let local_type2 = self.infer_types_for_expr(&mut **expr2, tree, var_env, closure_stack, local_types, rec_pair, errs)?;
+                let local_type2 = self.infer_types_for_expr(expr2, tree, var_env, closure_stack, local_types, rec_pair, errs)?;
                 for case in cases {
                     match case {
                         Case(pattern, expr3) => {
                             var_env.push_new_vars();
                             let mut var_local_types: Vec<(String, LocalType, Pos)> = Vec::new();
                             let mut is_var = false;
-                            let pattern_local_type = self.infer_types_for_pattern(&mut **pattern, tree, var_env, &mut var_local_types, &mut is_var, local_types, rec_pair, false, errs)?;
+                            let pattern_local_type = self.infer_types_for_pattern(pattern, tree, var_env, &mut var_local_types, &mut is_var, local_types, rec_pair, false, errs)?;
                             self.match_local_types_for_second_pattern_type(local_type2, pattern_local_type, is_var, pattern_pos(pattern), tree, local_types, errs)?;
-                            let expr3_local_type = self.infer_types_for_expr(&mut **expr3, tree, var_env, closure_stack, local_types, rec_pair, errs)?;
-                            self.match_local_types(expr3_local_type, *local_type, expr_pos(&**expr3), tree, local_types, errs)?;
+                            let expr3_local_type = self.infer_types_for_expr(expr3, tree, var_env, closure_stack, local_types, rec_pair, errs)?;
+                            self.match_local_types(expr3_local_type, *local_type, expr_pos(expr3), tree, local_types, errs)?;
                             var_env.pop_vars();
                         },
                     }
@@ -3684,16 +3653,16 @@ impl Typer
                             Some(LocalTypeEntry::Type(type_value)) => {
                                 match &*type_value {
                                     TypeValue::Type(_, TypeValueName::Fun, type_values) => {
-                                        if type_values.len() >= 1 {
+                                        if !type_values.is_empty() {
                                             for (pattern2, type_value2) in patterns.iter_mut().zip(type_values.iter()) {
                                                 let mut is_var2 = false;
-                                                let field_local_type = self.infer_types_for_pattern(&mut **pattern2, tree, var_env, var_local_types, &mut is_var2, local_types, rec_pair, can_add_var_local_type, errs)?;
-                                                let uniq_flag = self.real_uniq_flag_for_type_value(&type_value2, local_types)?;
+                                                let field_local_type = self.infer_types_for_pattern(pattern2, tree, var_env, var_local_types, &mut is_var2, local_types, rec_pair, can_add_var_local_type, errs)?;
+                                                let uniq_flag = self.real_uniq_flag_for_type_value(type_value2, local_types)?;
                                                 let mut is_success = true;
                                                 if uniq_flag == UniqFlag::Uniq {
                                                     if is_var2 && self.shared_flag_for_local_type(field_local_type, tree, local_types)? == SharedFlag::Shared {
-                                                        errs.push(FrontendError::Message(pattern_pos(&**pattern2).clone(), format!("can't match type {} with type {}", LocalTypeWithLocalTypes(field_local_type, local_types), TypeValueWithLocalTypes(type_value2.clone(), local_types))));
-                                                        errs.push(FrontendError::Message(pattern_pos(&**pattern2).clone(), format!("{}", MismatchedTypeInfoWidthLocalTypes(&MismatchedTypeInfo::UniqParam(field_local_type), local_types))));
+                                                        errs.push(FrontendError::Message(pattern_pos(pattern2).clone(), format!("can't match type {} with type {}", LocalTypeWithLocalTypes(field_local_type, local_types), TypeValueWithLocalTypes(type_value2.clone(), local_types))));
+                                                        errs.push(FrontendError::Message(pattern_pos(pattern2).clone(), format!("{}", MismatchedTypeInfoWidthLocalTypes(&MismatchedTypeInfo::UniqParam(field_local_type), local_types))));
                                                         is_success = false;
                                                     }
                                                     if is_success {
@@ -3701,7 +3670,7 @@ impl Typer
                                                     }
                                                 }
                                                 if is_success {
-                                                    self.match_type_values(&Rc::new(TypeValue::Param(UniqFlag::None, field_local_type)), type_value2, pattern_pos(&**pattern2), tree, local_types, errs)?;
+                                                    self.match_type_values(&Rc::new(TypeValue::Param(UniqFlag::None, field_local_type)), type_value2, pattern_pos(pattern2), tree, local_types, errs)?;
                                                 }
                                             }
                                             local_types.set_type_value(*local_type, type_values[type_values.len() - 1].clone());
@@ -3743,7 +3712,7 @@ impl Typer
                 }
                 *is_var = true;
                 let mut var_local_types2: Vec<(String, LocalType, Pos)> = Vec::new();
-                let local_type2 = self.infer_types_for_pattern(&mut **pattern2, tree, var_env, &mut var_local_types2, is_var, local_types, rec_pair, true, errs)?;
+                let local_type2 = self.infer_types_for_pattern(pattern2, tree, var_env, &mut var_local_types2, is_var, local_types, rec_pair, true, errs)?;
                 self.match_local_types(*local_type, local_type2, pos, tree, local_types, errs)?;
                 for (ident2, var_local_type2, pos2) in &var_local_types2 {
                     self.set_shared_for_local_type_and_var(ident2.as_str(), *var_local_type2, pos2, tree, local_types, errs)?;
@@ -3753,8 +3722,8 @@ impl Typer
             Pattern::Wildcard(Some(local_type), _) => Ok(*local_type),
             Pattern::Alt(patterns, Some(local_type), _) => {
                 for pattern2 in patterns {
-                    let local_type2 = self.infer_types_for_pattern(&mut **pattern2, tree, var_env, var_local_types, is_var, local_types, rec_pair, can_add_var_local_type, errs)?;
-                    self.match_local_types(local_type2, *local_type, pattern_pos(&**pattern2), tree, local_types, errs)?;
+                    let local_type2 = self.infer_types_for_pattern(pattern2, tree, var_env, var_local_types, is_var, local_types, rec_pair, can_add_var_local_type, errs)?;
+                    self.match_local_types(local_type2, *local_type, pattern_pos(pattern2), tree, local_types, errs)?;
                 }
                 Ok(*local_type)
             },
@@ -3847,7 +3816,7 @@ impl Typer
             ImplVar::Var(expr, Some(local_type), Some(local_types), _, _) => {
                 let mut var_env: Environment<()> = Environment::new();
                 let mut closure_stack = ClosureStack::new();
-                let local_type2 = self.infer_types_for_expr(&mut **expr, tree, &mut var_env, &mut closure_stack, &mut **local_types, None, errs)?;
+                let local_type2 = self.infer_types_for_expr(expr, tree, &mut var_env, &mut closure_stack, local_types, None, errs)?;
                 self.match_local_types(*local_type, local_type2, pos, tree, local_types, errs)?;
             },
             ImplVar::Fun(impl_fun, _) => {
@@ -3863,7 +3832,7 @@ impl Typer
                                 },
                             }
                         }
-                        let ret_local_type2 = self.infer_types_for_expr(&mut **expr, tree, &mut var_env, &mut closure_stack, &mut **local_types, None, errs)?;
+                        let ret_local_type2 = self.infer_types_for_expr(expr, tree, &mut var_env, &mut closure_stack, local_types, None, errs)?;
                         self.match_local_types(*ret_local_type, ret_local_type2, pos, tree, local_types, errs)?;
                     },
                     _ => return Err(FrontendErrors::new(vec![FrontendError::Internal(String::from("infer_types_for_impl_var: no local type or no local types"))])),
@@ -43,7 +43,7 @@ type T = (Int, Char);
     }
     assert_eq!(3, tree.defs().len());
     match &*tree.defs()[0] {
-        Def::Type(_, type_var, _) => {
+        Def::Type(_, _, type_var, _) => {
             let type_var_r = type_var.borrow();
             match &*type_var_r {
                 TypeVar::Builtin(Some(type_args), Some(fields), Some(shared_flag)) => {
@@ -58,7 +58,7 @@ type T = (Int, Char);
         _ => assert!(false),
     }
     match &*tree.defs()[1] {
-        Def::Type(_, type_var, _) => {
+        Def::Type(_, _, type_var, _) => {
             let type_var_r = type_var.borrow();
             match &*type_var_r {
                 TypeVar::Builtin(Some(type_args), Some(fields), Some(shared_flag)) => {
@@ -73,7 +73,7 @@ type T = (Int, Char);
         _ => assert!(false),
     }
     match &*tree.defs()[2] {
-        Def::Type(_, type_var, _) => {
+        Def::Type(_, _, type_var, _) => {
             let type_var_r = type_var.borrow();
             match &*type_var_r {
                 TypeVar::Synonym(_, _, Some(type_value)) => {
@@ -112,7 +112,7 @@ builtin type Slice;
     }
     assert_eq!(1, tree.defs().len());
     match &*tree.defs()[0] {
-        Def::Type(_, type_var, _) => {
+        Def::Type(_, _, type_var, _) => {
             let type_var_r = type_var.borrow();
             match &*type_var_r {
                 TypeVar::Builtin(Some(type_args), Some(fields), Some(shared_flag)) => {
@@ -168,7 +168,7 @@ type D<t1, t2> = (t2, t1);
     }
     assert_eq!(14, tree.defs().len());
     match &*tree.defs()[0] {
-        Def::Type(_, type_var, _) => {
+        Def::Type(_, _, type_var, _) => {
             let type_var_r = type_var.borrow();
             match &*type_var_r {
                 TypeVar::Builtin(Some(type_args), Some(fields), Some(shared_flag)) => {
@@ -183,7 +183,7 @@ type D<t1, t2> = (t2, t1);
         _ => assert!(false),
     }
     match &*tree.defs()[1] {
-        Def::Type(_, type_var, _) => {
+        Def::Type(_, _, type_var, _) => {
             let type_var_r = type_var.borrow();
             match &*type_var_r {
                 TypeVar::Builtin(Some(type_args), Some(fields), Some(shared_flag)) => {
@@ -198,7 +198,7 @@ type D<t1, t2> = (t2, t1);
         _ => assert!(false),
     }
     match &*tree.defs()[2] {
-        Def::Type(_, type_var, _) => {
+        Def::Type(_, _, type_var, _) => {
             let type_var_r = type_var.borrow();
             match &*type_var_r {
                 TypeVar::Builtin(Some(type_args), Some(fields), Some(shared_flag)) => {
@@ -213,7 +213,7 @@ type D<t1, t2> = (t2, t1);
         _ => assert!(false),
     }
     match &*tree.defs()[3] {
-        Def::Type(_, type_var, _) => {
+        Def::Type(_, _, type_var, _) => {
             let type_var_r = type_var.borrow();
             match &*type_var_r {
                 TypeVar::Data(_, cons, Some(shared_flag)) => {
@@ -236,7 +236,7 @@ type D<t1, t2> = (t2, t1);
         _ => assert!(false),
     }
     match &*tree.defs()[4] {
-        Def::Type(_, type_var, _) => {
+        Def::Type(_, _, type_var, _) => {
             let type_var_r = type_var.borrow();
             match &*type_var_r {
                 TypeVar::Synonym(_, _, Some(type_value)) => {
@@ -248,7 +248,7 @@ type D<t1, t2> = (t2, t1);
         _ => assert!(false),
     }
     match &*tree.defs()[5] {
-        Def::Type(_, type_var, _) => {
+        Def::Type(_, _, type_var, _) => {
             let type_var_r = type_var.borrow();
             match &*type_var_r {
                 TypeVar::Synonym(_, _, Some(type_value)) => {
@@ -260,7 +260,7 @@ type D<t1, t2> = (t2, t1);
         _ => assert!(false),
     }
     match &*tree.defs()[6] {
-        Def::Type(_, type_var, _) => {
+        Def::Type(_, _, type_var, _) => {
             let type_var_r = type_var.borrow();
             match &*type_var_r {
                 TypeVar::Synonym(_, _, Some(type_value)) => {
@@ -272,7 +272,7 @@ type D<t1, t2> = (t2, t1);
         _ => assert!(false),
     }
     match &*tree.defs()[7] {
-        Def::Type(_, type_var, _) => {
+        Def::Type(_, _, type_var, _) => {
             let type_var_r = type_var.borrow();
             match &*type_var_r {
                 TypeVar::Synonym(_, _, Some(type_value)) => {
@@ -284,7 +284,7 @@ type D<t1, t2> = (t2, t1);
         _ => assert!(false),
     }
     match &*tree.defs()[8] {
-        Def::Type(_, type_var, _) => {
+        Def::Type(_, _, type_var, _) => {
             let type_var_r = type_var.borrow();
             match &*type_var_r {
                 TypeVar::Synonym(_, _, Some(type_value)) => {
@@ -296,7 +296,7 @@ type D<t1, t2> = (t2, t1);
         _ => assert!(false),
     }
     match &*tree.defs()[9] {
-        Def::Type(_, type_var, _) => {
+        Def::Type(_, _, type_var, _) => {
             let type_var_r = type_var.borrow();
             match &*type_var_r {
                 TypeVar::Synonym(_, _, Some(type_value)) => {
@@ -308,7 +308,7 @@ type D<t1, t2> = (t2, t1);
         _ => assert!(false),
     }
     match &*tree.defs()[10] {
-        Def::Type(_, type_var, _) => {
+        Def::Type(_, _, type_var, _) => {
             let type_var_r = type_var.borrow();
             match &*type_var_r {
                 TypeVar::Synonym(_, _, Some(type_value)) => {
@@ -320,7 +320,7 @@ type D<t1, t2> = (t2, t1);
         _ => assert!(false),
     }
     match &*tree.defs()[11] {
-        Def::Type(_, type_var, _) => {
+        Def::Type(_, _, type_var, _) => {
             let type_var_r = type_var.borrow();
             match &*type_var_r {
                 TypeVar::Synonym(_, _, Some(type_value)) => {
@@ -332,7 +332,7 @@ type D<t1, t2> = (t2, t1);
         _ => assert!(false),
     }
     match &*tree.defs()[12] {
-        Def::Type(_, type_var, _) => {
+        Def::Type(_, _, type_var, _) => {
             let type_var_r = type_var.borrow();
             match &*type_var_r {
                 TypeVar::Synonym(_, _, Some(type_value)) => {
@@ -344,7 +344,7 @@ type D<t1, t2> = (t2, t1);
         _ => assert!(false),
     }
     match &*tree.defs()[13] {
-        Def::Type(_, type_var, _) => {
+        Def::Type(_, _, type_var, _) => {
             let type_var_r = type_var.borrow();
             match &*type_var_r {
                 TypeVar::Synonym(_, _, Some(type_value)) => {
@@ -393,7 +393,7 @@ type B<t1> = T<t1, t1>;
     }
     assert_eq!(11, tree.defs().len());
     match &*tree.defs()[0] {
-        Def::Type(_, type_var, _) => {
+        Def::Type(_, _, type_var, _) => {
             let type_var_r = type_var.borrow();
             match &*type_var_r {
                 TypeVar::Builtin(Some(type_args), Some(fields), Some(shared_flag)) => {
@@ -408,7 +408,7 @@ type B<t1> = T<t1, t1>;
         _ => assert!(false),
     }
     match &*tree.defs()[1] {
-        Def::Type(_, type_var, _) => {
+        Def::Type(_, _, type_var, _) => {
             let type_var_r = type_var.borrow();
             match &*type_var_r {
                 TypeVar::Builtin(Some(type_args), Some(fields), Some(shared_flag)) => {
@@ -423,7 +423,7 @@ type B<t1> = T<t1, t1>;
         _ => assert!(false),
     }
     match &*tree.defs()[2] {
-        Def::Type(_, type_var, _) => {
+        Def::Type(_, _, type_var, _) => {
             let type_var_r = type_var.borrow();
             match &*type_var_r {
                 TypeVar::Data(_, cons, Some(shared_flag)) => {
@@ -446,7 +446,7 @@ type B<t1> = T<t1, t1>;
         _ => assert!(false),
     }
     match &*tree.defs()[3] {
-        Def::Type(_, type_var, _) => {
+        Def::Type(_, _, type_var, _) => {
             let type_var_r = type_var.borrow();
             match &*type_var_r {
                 TypeVar::Data(_, cons, Some(shared_flag)) => {
@@ -469,7 +469,7 @@ type B<t1> = T<t1, t1>;
         _ => assert!(false),
     }
     match &*tree.defs()[4] {
-        Def::Type(_, type_var, _) => {
+        Def::Type(_, _, type_var, _) => {
             let type_var_r = type_var.borrow();
             match &*type_var_r {
                 TypeVar::Synonym(_, _, Some(type_value)) => {
@@ -481,7 +481,7 @@ type B<t1> = T<t1, t1>;
         _ => assert!(false),
     }
     match &*tree.defs()[5] {
-        Def::Type(_, type_var, _) => {
+        Def::Type(_, _, type_var, _) => {
             let type_var_r = type_var.borrow();
             match &*type_var_r {
                 TypeVar::Synonym(_, _, Some(type_value)) => {
@@ -493,7 +493,7 @@ type B<t1> = T<t1, t1>;
         _ => assert!(false),
     }
     match &*tree.defs()[6] {
-        Def::Type(_, type_var, _) => {
+        Def::Type(_, _, type_var, _) => {
             let type_var_r = type_var.borrow();
             match &*type_var_r {
                 TypeVar::Synonym(_, _, Some(type_value)) => {
@@ -505,7 +505,7 @@ type B<t1> = T<t1, t1>;
         _ => assert!(false),
     }
     match &*tree.defs()[7] {
-        Def::Type(_, type_var, _) => {
+        Def::Type(_, _, type_var, _) => {
             let type_var_r = type_var.borrow();
             match &*type_var_r {
                 TypeVar::Synonym(_, _, Some(type_value)) => {
@@ -517,7 +517,7 @@ type B<t1> = T<t1, t1>;
         _ => assert!(false),
     }
     match &*tree.defs()[8] {
-        Def::Type(_, type_var, _) => {
+        Def::Type(_, _, type_var, _) => {
             let type_var_r = type_var.borrow();
             match &*type_var_r {
                 TypeVar::Synonym(_, _, Some(type_value)) => {
@@ -529,7 +529,7 @@ type B<t1> = T<t1, t1>;
         _ => assert!(false),
     }
     match &*tree.defs()[9] {
-        Def::Type(_, type_var, _) => {
+        Def::Type(_, _, type_var, _) => {
             let type_var_r = type_var.borrow();
             match &*type_var_r {
                 TypeVar::Synonym(_, _, Some(type_value)) => {
@@ -541,7 +541,7 @@ type B<t1> = T<t1, t1>;
         _ => assert!(false),
     }
     match &*tree.defs()[10] {
-        Def::Type(_, type_var, _) => {
+        Def::Type(_, _, type_var, _) => {
             let type_var_r = type_var.borrow();
             match &*type_var_r {
                 TypeVar::Synonym(_, _, Some(type_value)) => {
@@ -583,7 +583,7 @@ builtin type Float4;
     }
     assert_eq!(4, tree.defs().len());
     match &*tree.defs()[0] {
-        Def::Type(_, type_var, _) => {
+        Def::Type(_, _, type_var, _) => {
             let type_var_r = type_var.borrow();
             match &*type_var_r {
                 TypeVar::Builtin(Some(type_args), Some(fields), Some(shared_flag)) => {
@@ -598,7 +598,7 @@ builtin type Float4;
         _ => assert!(false),
     }
     match &*tree.defs()[1] {
-        Def::Type(_, type_var, _) => {
+        Def::Type(_, _, type_var, _) => {
             let type_var_r = type_var.borrow();
             match &*type_var_r {
                 TypeVar::Builtin(Some(type_args), Some(fields), Some(shared_flag)) => {
@@ -613,7 +613,7 @@ builtin type Float4;
         _ => assert!(false),
     }
     match &*tree.defs()[2] {
-        Def::Type(_, type_var, _) => {
+        Def::Type(_, _, type_var, _) => {
             let type_var_r = type_var.borrow();
             match &*type_var_r {
                 TypeVar::Builtin(Some(type_args), Some(fields), Some(shared_flag)) => {
@@ -634,7 +634,7 @@ builtin type Float4;
         _ => assert!(false),
     }
     match &*tree.defs()[3] {
-        Def::Type(_, type_var, _) => {
+        Def::Type(_, _, type_var, _) => {
             let type_var_r = type_var.borrow();
             match &*type_var_r {
                 TypeVar::Builtin(Some(type_args), Some(fields), Some(shared_flag)) => {
@@ -691,7 +691,7 @@ type V<t1, t2> = (t1, t2);
     }
     assert_eq!(4, tree.defs().len());
     match &*tree.defs()[0] {
-        Def::Type(_, type_var, _) => {
+        Def::Type(_, _, type_var, _) => {
             let type_var_r = type_var.borrow();
             match &*type_var_r {
                 TypeVar::Builtin(Some(type_args), Some(fields), Some(shared_flag)) => {
@@ -706,7 +706,7 @@ type V<t1, t2> = (t1, t2);
         _ => assert!(false),
     }
     match &*tree.defs()[1] {
-        Def::Type(_, type_var, _) => {
+        Def::Type(_, _, type_var, _) => {
             let type_var_r = type_var.borrow();
             match &*type_var_r {
                 TypeVar::Data(_, cons, Some(shared_flag)) => {
@@ -739,7 +739,7 @@ type V<t1, t2> = (t1, t2);
         _ => assert!(false),
     }
     match &*tree.defs()[2] {
-        Def::Type(_, type_var, _) => {
+        Def::Type(_, _, type_var, _) => {
             let type_var_r = type_var.borrow();
             match &*type_var_r {
                 TypeVar::Data(_, cons, Some(shared_flag)) => {
@@ -800,7 +800,7 @@ data A = L(W);
     }
     assert_eq!(12, tree.defs().len());
     match &*tree.defs()[0] {
-        Def::Type(_, type_var, _) => {
+        Def::Type(_, _, type_var, _) => {
             let type_var_r = type_var.borrow();
             match &*type_var_r {
                 TypeVar::Builtin(Some(type_args), Some(fields), Some(shared_flag)) => {
@@ -815,7 +815,7 @@ data A = L(W);
         _ => assert!(false),
     }
     match &*tree.defs()[1] {
-        Def::Type(_, type_var, _) => {
+        Def::Type(_, _, type_var, _) => {
             let type_var_r = type_var.borrow();
             match &*type_var_r {
                 TypeVar::Builtin(Some(type_args), Some(fields), Some(shared_flag)) => {
@@ -830,7 +830,7 @@ data A = L(W);
         _ => assert!(false),
     }
     match &*tree.defs()[2] {
-        Def::Type(_, type_var, _) => {
+        Def::Type(_, _, type_var, _) => {
             let type_var_r = type_var.borrow();
             match &*type_var_r {
                 TypeVar::Builtin(Some(type_args), Some(fields), Some(shared_flag)) => {
@@ -846,7 +846,7 @@ data A = L(W);
         _ => assert!(false),
     }
     match &*tree.defs()[3] {
-        Def::Type(_, type_var, _) => {
+        Def::Type(_, _, type_var, _) => {
             let type_var_r = type_var.borrow();
             match &*type_var_r {
                 TypeVar::Builtin(Some(type_args), Some(fields), Some(shared_flag)) => {
@@ -862,7 +862,7 @@ data A = L(W);
         _ => assert!(false),
     }
     match &*tree.defs()[4] {
-        Def::Type(_, type_var, _) => {
+        Def::Type(_, _, type_var, _) => {
             let type_var_r = type_var.borrow();
             match &*type_var_r {
                 TypeVar::Data(_, cons, Some(shared_flag)) => {
@@ -895,7 +895,7 @@ data A = L(W);
         _ => assert!(false),
     }
     match &*tree.defs()[5] {
-        Def::Type(_, type_var, _) => {
+        Def::Type(_, _, type_var, _) => {
             let type_var_r = type_var.borrow();
             match &*type_var_r {
                 TypeVar::Data(_, cons, Some(shared_flag)) => {
@@ -918,7 +918,7 @@ data A = L(W);
         _ => assert!(false),
     }
     match &*tree.defs()[6] {
-        Def::Type(_, type_var, _) => {
+        Def::Type(_, _, type_var, _) => {
             let type_var_r = type_var.borrow();
             match &*type_var_r {
                 TypeVar::Data(_, cons, Some(shared_flag)) => {
@@ -951,7 +951,7 @@ data A = L(W);
         _ => assert!(false),
     }
     match &*tree.defs()[7] {
-        Def::Type(_, type_var, _) => {
+        Def::Type(_, _, type_var, _) => {
             let type_var_r = type_var.borrow();
             match &*type_var_r {
                 TypeVar::Data(_, cons, Some(shared_flag)) => {
@@ -974,7 +974,7 @@ data A = L(W);
         _ => assert!(false),
     }
     match &*tree.defs()[8] {
-        Def::Type(_, type_var, _) => {
+        Def::Type(_, _, type_var, _) => {
             let type_var_r = type_var.borrow();
             match &*type_var_r {
                 TypeVar::Data(_, cons, Some(shared_flag)) => {
@@ -997,7 +997,7 @@ data A = L(W);
         _ => assert!(false),
     }
     match &*tree.defs()[9] {
-        Def::Type(_, type_var, _) => {
+        Def::Type(_, _, type_var, _) => {
             let type_var_r = type_var.borrow();
             match &*type_var_r {
                 TypeVar::Data(_, cons, Some(shared_flag)) => {
@@ -1020,7 +1020,7 @@ data A = L(W);
         _ => assert!(false),
     }
     match &*tree.defs()[10] {
-        Def::Type(_, type_var, _) => {
+        Def::Type(_, _, type_var, _) => {
             let type_var_r = type_var.borrow();
             match &*type_var_r {
                 TypeVar::Data(_, cons, Some(shared_flag)) => {
@@ -1043,7 +1043,7 @@ data A = L(W);
         _ => assert!(false),
     }
     match &*tree.defs()[11] {
-        Def::Type(_, type_var, _) => {
+        Def::Type(_, _, type_var, _) => {
             let type_var_r = type_var.borrow();
             match &*type_var_r {
                 TypeVar::Data(_, cons, Some(shared_flag)) => {
@@ -1473,6 +1473,37 @@ data U = D(T);
     }
 }
 
+#[test]
+fn test_typer_evaluate_types_for_type_vars_evaluates_types_for_recursive_data_type_in_box()
+{
+    let s = "
+builtin type Int;
+builtin type UniqPrivateBox;
+data T = C() | D(Int, UniqPrivateBox<T>);
+";
+    let s2 = &s[1..];
+    let mut cursor = Cursor::new(s2.as_bytes());
+    let mut parser = Parser::new(Lexer::new(String::from("test.vscfl"), &mut cursor));
+    let mut tree = Tree::new();
+    match parser.parse(&mut tree) {
+        Ok(()) => assert!(true),
+        Err(_) => assert!(false),
+    }
+    let namer = Namer::new();
+    match namer.check_idents(&mut tree) {
+        Ok(()) => assert!(true),
+        Err(_) => assert!(false),
+    }
+    let typer = Typer::new();
+    match typer.evaluate_types_for_type_vars(&tree) {
+        Ok(()) => assert!(true),
+        Err(errs) => {
+            println!("{}", errs);
+            assert!(false);
+        },
+    }
+}
+
 //
 // Evaluation of types with where tuples.
 //
@@ -3098,7 +3129,7 @@ a: Int = 1 + 2;
     }
     assert_eq!(4, tree.defs().len());
     match &*tree.defs()[0] {
-        Def::Trait(_, trait1, _) => {
+        Def::Trait(_, _, trait1, _) => {
             let trait_r = trait1.borrow();
             match &*trait_r {
                 Trait(_, trait_defs, _) => {
@@ -3155,7 +3186,7 @@ a: Int = 1 + 2;
         _ => assert!(false),
     }
     match &*tree.defs()[3] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, expr, _, Some(local_type), Some(local_types), Some(typ), None) => {
@@ -3332,7 +3363,7 @@ fug2(x: Int) -> (Float, Int) = (1.5, x);
     }
     assert_eq!(36, tree.defs().len());
     match &*tree.defs()[0] {
-        Def::Trait(_, trait1, _) => {
+        Def::Trait(_, _, trait1, _) => {
             let trait_r = trait1.borrow();
             match &*trait_r {
                 Trait(_, trait_defs, _) => {
@@ -3365,7 +3396,7 @@ fug2(x: Int) -> (Float, Int) = (1.5, x);
         _ => assert!(false),
     }
     match &*tree.defs()[1] {
-        Def::Trait(_, trait1, _) => {
+        Def::Trait(_, _, trait1, _) => {
             let trait_r = trait1.borrow();
             match &*trait_r {
                 Trait(_, trait_defs, _) => {
@@ -3470,7 +3501,7 @@ fug2(x: Int) -> (Float, Int) = (1.5, x);
         _ => assert!(false),
     }
     match &*tree.defs()[12] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, expr, _, Some(local_type), Some(local_types), Some(typ), None) => {
@@ -3575,7 +3606,7 @@ fug2(x: Int) -> (Float, Int) = (1.5, x);
         _ => assert!(false),
     }
     match &*tree.defs()[13] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, expr, _, Some(local_type), Some(local_types), Some(typ), None) => {
@@ -3673,7 +3704,7 @@ fug2(x: Int) -> (Float, Int) = (1.5, x);
         _ => assert!(false),
     }
     match &*tree.defs()[14] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, expr, _, Some(local_type), Some(local_types), Some(typ), None) => {
@@ -3706,7 +3737,7 @@ fug2(x: Int) -> (Float, Int) = (1.5, x);
         _ => assert!(false),
     }
     match &*tree.defs()[15] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, expr, _, Some(local_type), Some(local_types), Some(typ), None) => {
@@ -3778,7 +3809,7 @@ fug2(x: Int) -> (Float, Int) = (1.5, x);
         _ => assert!(false),
     }
     match &*tree.defs()[16] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, expr, _, Some(local_type), Some(local_types), Some(typ), None) => {
@@ -3875,7 +3906,7 @@ fug2(x: Int) -> (Float, Int) = (1.5, x);
         _ => assert!(false),
     }
     match &*tree.defs()[17] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, expr, _, Some(local_type), Some(local_types), Some(typ), None) => {
@@ -3937,7 +3968,7 @@ fug2(x: Int) -> (Float, Int) = (1.5, x);
         _ => assert!(false),
     }
     match &*tree.defs()[18] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, expr, _, Some(local_type), Some(local_types), Some(typ), None) => {
@@ -4013,7 +4044,7 @@ fug2(x: Int) -> (Float, Int) = (1.5, x);
         _ => assert!(false),
     }
     match &*tree.defs()[19] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, expr, _, Some(local_type), Some(local_types), Some(typ), None) => {
@@ -4060,7 +4091,7 @@ fug2(x: Int) -> (Float, Int) = (1.5, x);
         _ => assert!(false),
     }
     match &*tree.defs()[20] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, expr, _, Some(local_type), Some(local_types), Some(typ), None) => {
@@ -4180,7 +4211,7 @@ fug2(x: Int) -> (Float, Int) = (1.5, x);
         _ => assert!(false),
     }
     match &*tree.defs()[21] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, expr, _, Some(local_type), Some(local_types), Some(typ), None) => {
@@ -4280,7 +4311,7 @@ fug2(x: Int) -> (Float, Int) = (1.5, x);
         _ => assert!(false),
     }
     match &*tree.defs()[22] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, expr, _, Some(local_type), Some(local_types), Some(typ), None) => {
@@ -4380,7 +4411,7 @@ fug2(x: Int) -> (Float, Int) = (1.5, x);
         _ => assert!(false),
     }
     match &*tree.defs()[23] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, expr, _, Some(local_type), Some(local_types), Some(typ), None) => {
@@ -4514,7 +4545,7 @@ fug2(x: Int) -> (Float, Int) = (1.5, x);
         _ => assert!(false),
     }
     match &*tree.defs()[24] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Fun(fun, _, Some(typ)) => {
@@ -4567,7 +4598,7 @@ fug2(x: Int) -> (Float, Int) = (1.5, x);
         _ => assert!(false),
     }
     match &*tree.defs()[25] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Fun(fun, _, Some(typ)) => {
@@ -4635,7 +4666,7 @@ fug2(x: Int) -> (Float, Int) = (1.5, x);
         _ => assert!(false),
     }
     match &*tree.defs()[26] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Fun(fun, _, Some(typ)) => {
@@ -4725,7 +4756,7 @@ fug2(x: Int) -> (Float, Int) = (1.5, x);
         _ => assert!(false),
     }
     match &*tree.defs()[27] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, expr, _, Some(local_type), Some(local_types), Some(typ), None) => {
@@ -4765,7 +4796,7 @@ fug2(x: Int) -> (Float, Int) = (1.5, x);
         _ => assert!(false),
     }
     match &*tree.defs()[28] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, expr, _, Some(local_type), Some(local_types), Some(typ), None) => {
@@ -4840,7 +4871,7 @@ fug2(x: Int) -> (Float, Int) = (1.5, x);
         _ => assert!(false),
     }
     match &*tree.defs()[29] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, expr, _, Some(local_type), Some(local_types), Some(typ), None) => {
@@ -4979,7 +5010,7 @@ fug2(x: Int) -> (Float, Int) = (1.5, x);
         _ => assert!(false),
     }
     match &*tree.defs()[30] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, expr, _, Some(local_type), Some(local_types), Some(typ), None) => {
@@ -5214,7 +5245,7 @@ fug2(x: Int) -> (Float, Int) = (1.5, x);
         _ => assert!(false),
     }
     match &*tree.defs()[31] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, expr, _, Some(local_type), Some(local_types), Some(typ), None) => {
@@ -5247,7 +5278,7 @@ fug2(x: Int) -> (Float, Int) = (1.5, x);
         _ => assert!(false),
     }
     match &*tree.defs()[32] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, expr, _, Some(local_type), Some(local_types), Some(typ), None) => {
@@ -5280,7 +5311,7 @@ fug2(x: Int) -> (Float, Int) = (1.5, x);
         _ => assert!(false),
     }
     match &*tree.defs()[33] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, expr, _, Some(local_type), Some(local_types), Some(typ), None) => {
@@ -5406,7 +5437,7 @@ fug2(x: Int) -> (Float, Int) = (1.5, x);
         _ => assert!(false),
     }
     match &*tree.defs()[34] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Fun(fun, _, Some(typ)) => {
@@ -5444,7 +5475,7 @@ fug2(x: Int) -> (Float, Int) = (1.5, x);
         _ => assert!(false),
     }
     match &*tree.defs()[35] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Fun(fun, _, Some(typ)) => {
@@ -5568,7 +5599,7 @@ X: Int = 1;
     }
     assert_eq!(11, tree.defs().len());
     match &*tree.defs()[0] {
-        Def::Trait(_, trait1, _) => {
+        Def::Trait(_, _, trait1, _) => {
             let trait_r = trait1.borrow();
             match &*trait_r {
                 Trait(_, trait_defs, _) => {
@@ -5625,7 +5656,7 @@ X: Int = 1;
         _ => assert!(false),
     }
     match &*tree.defs()[5] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, expr, _, Some(local_type), Some(local_types), Some(typ), None) => {
@@ -5737,7 +5768,7 @@ X: Int = 1;
         _ => assert!(false),
     }
     match &*tree.defs()[6] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, expr, _, Some(local_type), Some(local_types), Some(typ), None) => {
@@ -6055,7 +6086,7 @@ X: Int = 1;
         _ => assert!(false),
     }
     match &*tree.defs()[7] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, expr, _, Some(local_type), Some(local_types), Some(typ), None) => {
@@ -6127,7 +6158,7 @@ X: Int = 1;
         _ => assert!(false),
     }
     match &*tree.defs()[8] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, expr, _, Some(local_type), Some(local_types), Some(typ), None) => {
@@ -6395,7 +6426,7 @@ X: Int = 1;
         _ => assert!(false),
     }
     match &*tree.defs()[9] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, expr, _, Some(local_type), Some(local_types), Some(typ), None) => {
@@ -6681,7 +6712,7 @@ X: Int = 1;
         _ => assert!(false),
     }
     match &*tree.defs()[10] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, expr, _, Some(local_type), Some(local_types), Some(typ), None) => {
@@ -6748,7 +6779,7 @@ y: Int = 2;
     }
     assert_eq!(8, tree.defs().len());
     match &*tree.defs()[2] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, expr, _, Some(local_type), Some(local_types), Some(typ), None) => {
@@ -6781,7 +6812,7 @@ y: Int = 2;
         _ => assert!(false),
     }
     match &*tree.defs()[3] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, expr, _, Some(local_type), Some(local_types), Some(typ), None) => {
@@ -6848,7 +6879,7 @@ y: Int = 2;
         _ => assert!(false),
     }
     match &*tree.defs()[4] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, expr, _, Some(local_type), Some(local_types), Some(typ), None) => {
@@ -6915,7 +6946,7 @@ y: Int = 2;
         _ => assert!(false),
     }
     match &*tree.defs()[5] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, expr, _, Some(local_type), Some(local_types), Some(typ), None) => {
@@ -6967,7 +6998,7 @@ y: Int = 2;
         _ => assert!(false),
     }
     match &*tree.defs()[6] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, expr, _, Some(local_type), Some(local_types), Some(typ), None) => {
@@ -7000,7 +7031,7 @@ y: Int = 2;
         _ => assert!(false),
     }
     match &*tree.defs()[7] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, expr, _, Some(local_type), Some(local_types), Some(typ), None) => {
@@ -7085,7 +7116,7 @@ d: Int = 1 match {
     }
     assert_eq!(10, tree.defs().len());
     match &*tree.defs()[0] {
-        Def::Trait(_, trait1, _) => {
+        Def::Trait(_, _, trait1, _) => {
             let trait_r = trait1.borrow();
             match &*trait_r {
                 Trait(_, trait_defs, _) => {
@@ -7118,7 +7149,7 @@ d: Int = 1 match {
         _ => assert!(false),
     }
     match &*tree.defs()[1] {
-        Def::Trait(_, trait1, _) => {
+        Def::Trait(_, _, trait1, _) => {
             let trait_r = trait1.borrow();
             match &*trait_r {
                 Trait(_, trait_defs, _) => {
@@ -7199,7 +7230,7 @@ d: Int = 1 match {
         _ => assert!(false),
     }
     match &*tree.defs()[6] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, expr, _, Some(local_type), Some(local_types), Some(typ), None) => {
@@ -7381,7 +7412,7 @@ d: Int = 1 match {
         _ => assert!(false),
     }
     match &*tree.defs()[7] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, expr, _, Some(local_type), Some(local_types), Some(typ), None) => {
@@ -7556,7 +7587,7 @@ d: Int = 1 match {
         _ => assert!(false),
     }
     match &*tree.defs()[8] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, expr, _, Some(local_type), Some(local_types), Some(typ), None) => {
@@ -7698,7 +7729,7 @@ d: Int = 1 match {
         _ => assert!(false),
     }
     match &*tree.defs()[9] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, expr, _, Some(local_type), Some(local_types), Some(typ), None) => {
@@ -7857,7 +7888,7 @@ d(x: t) -> (t, t) where t: shared = (x, x);
     }
     assert_eq!(6, tree.defs().len());
     match &*tree.defs()[2] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, expr, _, Some(local_type), Some(local_types), Some(typ), None) => {
@@ -7955,7 +7986,7 @@ d(x: t) -> (t, t) where t: shared = (x, x);
         _ => assert!(false),
     }
     match &*tree.defs()[3] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, expr, _, Some(local_type), Some(local_types), Some(typ), None) => {
@@ -8189,7 +8220,7 @@ d(x: t) -> (t, t) where t: shared = (x, x);
         _ => assert!(false),
     }
     match &*tree.defs()[4] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, expr, _, Some(local_type), Some(local_types), Some(typ), None) => {
@@ -8548,7 +8579,7 @@ d(x: t) -> (t, t) where t: shared = (x, x);
         _ => assert!(false),
     }
     match &*tree.defs()[5] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Fun(fun, _, Some(typ)) => {
@@ -8658,7 +8689,7 @@ g(x: t, y: u) -> (t, u) = (x, y);
     }
     assert_eq!(5, tree.defs().len());
     match &*tree.defs()[0] {
-        Def::Trait(_, trait1, _) => {
+        Def::Trait(_, _, trait1, _) => {
             let trait_r = trait1.borrow();
             match &*trait_r {
                 Trait(_, trait_defs, _) => {
@@ -8715,7 +8746,7 @@ g(x: t, y: u) -> (t, u) = (x, y);
         _ => assert!(false),
     }
     match &*tree.defs()[3] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Fun(fun, _, Some(typ)) => {
@@ -8795,7 +8826,7 @@ g(x: t, y: u) -> (t, u) = (x, y);
         _ => assert!(false),
     }
     match &*tree.defs()[4] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Fun(fun, _, Some(typ)) => {
@@ -8930,7 +8961,7 @@ impl T for Int
     }
     assert_eq!(6, tree.defs().len());
     match &*tree.defs()[0] {
-        Def::Trait(_, trait1, _) => {
+        Def::Trait(_, _, trait1, _) => {
             let trait_r = trait1.borrow();
             match &*trait_r {
                 Trait(_, trait_defs, _) => {
@@ -8987,7 +9018,7 @@ impl T for Int
         _ => assert!(false),
     }
     match &*tree.defs()[4] {
-        Def::Trait(_, trait1, _) => {
+        Def::Trait(_, _, trait1, _) => {
             let trait_r = trait1.borrow();
             match &*trait_r {
                 Trait(_, trait_defs, _) => {
@@ -9215,7 +9246,7 @@ impl T for V
     }
     assert_eq!(10, tree.defs().len());
     match &*tree.defs()[0] {
-        Def::Trait(_, trait1, _) => {
+        Def::Trait(_, _, trait1, _) => {
             let trait_r = trait1.borrow();
             match &*trait_r {
                 Trait(_, trait_defs, _) => {
@@ -9296,7 +9327,7 @@ impl T for V
         _ => assert!(false),
     }
     match &*tree.defs()[5] {
-        Def::Trait(_, trait1, _) => {
+        Def::Trait(_, _, trait1, _) => {
             let trait_r = trait1.borrow();
             match &*trait_r {
                 Trait(_, trait_defs, _) => {
@@ -9821,7 +9852,7 @@ impl T for U
     }
     assert_eq!(3, tree.defs().len());
     match &*tree.defs()[0] {
-        Def::Trait(_, trait1, _) => {
+        Def::Trait(_, _, trait1, _) => {
             let trait_r = trait1.borrow();
             match &*trait_r {
                 Trait(_, trait_defs, _) => {
@@ -10000,7 +10031,7 @@ impl Zero for T {};
     }
     assert_eq!(4, tree.defs().len());
     match &*tree.defs()[0] {
-        Def::Trait(_, trait1, _) => {
+        Def::Trait(_, _, trait1, _) => {
             let trait_r = trait1.borrow();
             match &*trait_r {
                 Trait(_, trait_defs, _) => {
@@ -10840,7 +10871,7 @@ a: Int = 1 + 2;
     }
     assert_eq!(4, tree.defs().len());
     match &*tree.defs()[3] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, expr, _, Some(local_type), Some(local_types), _, None) => {
@@ -10960,7 +10991,7 @@ fug2(x: Int) -> (Float, Int) = (1.5, x);
     }
     assert_eq!(36, tree.defs().len());
     match &*tree.defs()[12] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, expr, _, Some(local_type), Some(local_types), _, None) => {
@@ -11029,7 +11060,7 @@ fug2(x: Int) -> (Float, Int) = (1.5, x);
         _ => assert!(false),
     }
     match &*tree.defs()[13] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, expr, _, Some(local_type), Some(local_types), _, None) => {
@@ -11098,7 +11129,7 @@ fug2(x: Int) -> (Float, Int) = (1.5, x);
         _ => assert!(false),
     }
     match &*tree.defs()[14] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, expr, _, Some(local_type), Some(local_types), _, None) => {
@@ -11123,7 +11154,7 @@ fug2(x: Int) -> (Float, Int) = (1.5, x);
         _ => assert!(false),
     }
     match &*tree.defs()[15] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, expr, _, Some(local_type), Some(local_types), _, None) => {
@@ -11173,7 +11204,7 @@ fug2(x: Int) -> (Float, Int) = (1.5, x);
         _ => assert!(false),
     }
     match &*tree.defs()[16] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, expr, _, Some(local_type), Some(local_types), _, None) => {
@@ -11234,7 +11265,7 @@ fug2(x: Int) -> (Float, Int) = (1.5, x);
         _ => assert!(false),
     }
     match &*tree.defs()[17] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, expr, _, Some(local_type), Some(local_types), _, None) => {
@@ -11274,7 +11305,7 @@ fug2(x: Int) -> (Float, Int) = (1.5, x);
         _ => assert!(false),
     }
     match &*tree.defs()[18] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, expr, _, Some(local_type), Some(local_types), _, None) => {
@@ -11321,7 +11352,7 @@ fug2(x: Int) -> (Float, Int) = (1.5, x);
         _ => assert!(false),
     }
     match &*tree.defs()[19] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, expr, _, Some(local_type), Some(local_types), _, None) => {
@@ -11368,7 +11399,7 @@ fug2(x: Int) -> (Float, Int) = (1.5, x);
         _ => assert!(false),
     }
     match &*tree.defs()[20] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, expr, _, Some(local_type), Some(local_types), _, None) => {
@@ -11460,7 +11491,7 @@ fug2(x: Int) -> (Float, Int) = (1.5, x);
         _ => assert!(false),
     }    
     match &*tree.defs()[21] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, expr, _, Some(local_type), Some(local_types), _, None) => {
@@ -11539,7 +11570,7 @@ fug2(x: Int) -> (Float, Int) = (1.5, x);
         _ => assert!(false),
     }
     match &*tree.defs()[22] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, expr, _, Some(local_type), Some(local_types), _, None) => {
@@ -11618,7 +11649,7 @@ fug2(x: Int) -> (Float, Int) = (1.5, x);
         _ => assert!(false),
     }
     match &*tree.defs()[23] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, expr, _, Some(local_type), Some(local_types), _, None) => {
@@ -11717,7 +11748,7 @@ fug2(x: Int) -> (Float, Int) = (1.5, x);
         _ => assert!(false),
     }    
     match &*tree.defs()[24] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Fun(fun, _, _) => {
@@ -11755,7 +11786,7 @@ fug2(x: Int) -> (Float, Int) = (1.5, x);
         _ => assert!(false),
     }
     match &*tree.defs()[25] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Fun(fun, _, _) => {
@@ -11801,7 +11832,7 @@ fug2(x: Int) -> (Float, Int) = (1.5, x);
         _ => assert!(false),
     }    
     match &*tree.defs()[26] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Fun(fun, _, _) => {
@@ -11866,7 +11897,7 @@ fug2(x: Int) -> (Float, Int) = (1.5, x);
         _ => assert!(false),
     }
     match &*tree.defs()[27] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, expr, _, Some(local_type), Some(local_types), _, None) => {
@@ -11898,7 +11929,7 @@ fug2(x: Int) -> (Float, Int) = (1.5, x);
         _ => assert!(false),
     }
     match &*tree.defs()[28] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, expr, _, Some(local_type), Some(local_types), _, None) => {
@@ -11944,7 +11975,7 @@ fug2(x: Int) -> (Float, Int) = (1.5, x);
         _ => assert!(false),
     }
     match &*tree.defs()[29] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, expr, _, Some(local_type), Some(local_types), Some(typ), None) => {
@@ -12034,7 +12065,7 @@ fug2(x: Int) -> (Float, Int) = (1.5, x);
         _ => assert!(false),
     }
     match &*tree.defs()[30] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, expr, _, Some(local_type), Some(local_types), _, None) => {
@@ -12177,7 +12208,7 @@ fug2(x: Int) -> (Float, Int) = (1.5, x);
         _ => assert!(false),
     }
     match &*tree.defs()[31] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, expr, _, Some(local_type), Some(local_types), _, None) => {
@@ -12202,7 +12233,7 @@ fug2(x: Int) -> (Float, Int) = (1.5, x);
         _ => assert!(false),
     }
     match &*tree.defs()[32] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, expr, _, Some(local_type), Some(local_types), _, None) => {
@@ -12227,7 +12258,7 @@ fug2(x: Int) -> (Float, Int) = (1.5, x);
         _ => assert!(false),
     }
     match &*tree.defs()[33] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, expr, _, Some(local_type), Some(local_types), _, None) => {
@@ -12303,7 +12334,7 @@ fug2(x: Int) -> (Float, Int) = (1.5, x);
         _ => assert!(false),
     }
     match &*tree.defs()[34] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Fun(fun, _, _) => {
@@ -12340,7 +12371,7 @@ fug2(x: Int) -> (Float, Int) = (1.5, x);
         _ => assert!(false),
     }
     match &*tree.defs()[35] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Fun(fun, _, _) => {
@@ -12449,7 +12480,7 @@ X: Int = 1;
     }
     assert_eq!(11, tree.defs().len());
     match &*tree.defs()[5] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, expr, _, Some(local_type), Some(local_types), _, None) => {
@@ -12518,7 +12549,7 @@ X: Int = 1;
         _ => assert!(false),
     }
     match &*tree.defs()[6] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, expr, _, Some(local_type), Some(local_types), _, None) => {
@@ -12709,7 +12740,7 @@ X: Int = 1;
         _ => assert!(false),
     }
     match &*tree.defs()[7] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, expr, _, Some(local_type), Some(local_types), _, None) => {
@@ -12759,7 +12790,7 @@ X: Int = 1;
         _ => assert!(false),
     }
     match &*tree.defs()[8] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, expr, _, Some(local_type), Some(local_types), _, None) => {
@@ -12914,7 +12945,7 @@ X: Int = 1;
         _ => assert!(false),
     }
     match &*tree.defs()[9] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, expr, _, Some(local_type), Some(local_types), _, None) => {
@@ -13073,7 +13104,7 @@ X: Int = 1;
         _ => assert!(false),
     }
     match &*tree.defs()[10] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, expr, _, Some(local_type), Some(local_types), _, None) => {
@@ -13147,7 +13178,7 @@ y: Int = 2;
     }
     assert_eq!(23, tree.defs().len());
     match &*tree.defs()[9] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, expr, _, Some(local_type), Some(local_types), _, None) => {
@@ -13172,7 +13203,7 @@ y: Int = 2;
         _ => assert!(false),
     }
     match &*tree.defs()[10] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, expr, _, Some(local_type), Some(local_types), _, None) => {
@@ -13197,7 +13228,7 @@ y: Int = 2;
         _ => assert!(false),
     }
     match &*tree.defs()[11] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, expr, _, Some(local_type), Some(local_types), _, None) => {
@@ -13222,7 +13253,7 @@ y: Int = 2;
         _ => assert!(false),
     }
     match &*tree.defs()[12] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, expr, _, Some(local_type), Some(local_types), _, None) => {
@@ -13247,7 +13278,7 @@ y: Int = 2;
         _ => assert!(false),
     }
     match &*tree.defs()[13] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, expr, _, Some(local_type), Some(local_types), _, None) => {
@@ -13272,7 +13303,7 @@ y: Int = 2;
         _ => assert!(false),
     }
     match &*tree.defs()[14] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, expr, _, Some(local_type), Some(local_types), _, None) => {
@@ -13297,7 +13328,7 @@ y: Int = 2;
         _ => assert!(false),
     }
     match &*tree.defs()[15] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, expr, _, Some(local_type), Some(local_types), _, None) => {
@@ -13322,7 +13353,7 @@ y: Int = 2;
         _ => assert!(false),
     }
     match &*tree.defs()[16] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, expr, _, Some(local_type), Some(local_types), _, None) => {
@@ -13347,7 +13378,7 @@ y: Int = 2;
         _ => assert!(false),
     }
     match &*tree.defs()[17] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, expr, _, Some(local_type), Some(local_types), _, None) => {
@@ -13372,7 +13403,7 @@ y: Int = 2;
         _ => assert!(false),
     }
     match &*tree.defs()[18] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, expr, _, Some(local_type), Some(local_types), _, None) => {
@@ -13417,7 +13448,7 @@ y: Int = 2;
         _ => assert!(false),
     }
     match &*tree.defs()[19] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, expr, _, Some(local_type), Some(local_types), _, None) => {
@@ -13462,7 +13493,7 @@ y: Int = 2;
         _ => assert!(false),
     }
     match &*tree.defs()[20] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, expr, _, Some(local_type), Some(local_types), _, None) => {
@@ -13499,7 +13530,7 @@ y: Int = 2;
         _ => assert!(false),
     }
     match &*tree.defs()[21] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, expr, _, Some(local_type), Some(local_types), _, None) => {
@@ -13524,7 +13555,7 @@ y: Int = 2;
         _ => assert!(false),
     }
     match &*tree.defs()[22] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, expr, _, Some(local_type), Some(local_types), _, None) => {
@@ -13644,7 +13675,7 @@ m: Int = 1 match {
     }
     assert_eq!(26, tree.defs().len());
     match &*tree.defs()[13] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, expr, _, Some(local_type), Some(local_types), _, None) => {
@@ -13713,7 +13744,7 @@ m: Int = 1 match {
         _ => assert!(false),
     }    
     match &*tree.defs()[14] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, expr, _, Some(local_type), Some(local_types), _, None) => {
@@ -13782,7 +13813,7 @@ m: Int = 1 match {
         _ => assert!(false),
     }    
     match &*tree.defs()[15] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, expr, _, Some(local_type), Some(local_types), _, None) => {
@@ -13851,7 +13882,7 @@ m: Int = 1 match {
         _ => assert!(false),
     }    
     match &*tree.defs()[16] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, expr, _, Some(local_type), Some(local_types), _, None) => {
@@ -13920,7 +13951,7 @@ m: Int = 1 match {
         _ => assert!(false),
     }    
     match &*tree.defs()[17] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, expr, _, Some(local_type), Some(local_types), _, None) => {
@@ -13989,7 +14020,7 @@ m: Int = 1 match {
         _ => assert!(false),
     }    
     match &*tree.defs()[18] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, expr, _, Some(local_type), Some(local_types), _, None) => {
@@ -14058,7 +14089,7 @@ m: Int = 1 match {
         _ => assert!(false),
     }    
     match &*tree.defs()[19] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, expr, _, Some(local_type), Some(local_types), _, None) => {
@@ -14127,7 +14158,7 @@ m: Int = 1 match {
         _ => assert!(false),
     }    
     match &*tree.defs()[20] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, expr, _, Some(local_type), Some(local_types), _, None) => {
@@ -14196,7 +14227,7 @@ m: Int = 1 match {
         _ => assert!(false),
     }
     match &*tree.defs()[21] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, expr, _, Some(local_type), Some(local_types), _, None) => {
@@ -14265,7 +14296,7 @@ m: Int = 1 match {
         _ => assert!(false),
     }
     match &*tree.defs()[22] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, expr, _, Some(local_type), Some(local_types), _, None) => {
@@ -14383,7 +14414,7 @@ m: Int = 1 match {
         _ => assert!(false),
     }
     match &*tree.defs()[23] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, expr, _, Some(local_type), Some(local_types), _, None) => {
@@ -14494,7 +14525,7 @@ m: Int = 1 match {
         _ => assert!(false),
     }
     match &*tree.defs()[24] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, expr, _, Some(local_type), Some(local_types), _, None) => {
@@ -14586,7 +14617,7 @@ m: Int = 1 match {
         _ => assert!(false),
     }
     match &*tree.defs()[25] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, expr, _, Some(local_type), Some(local_types), _, None) => {
@@ -14702,7 +14733,7 @@ d(x: t) -> (t, t) where t: shared = (x, x);
     }
     assert_eq!(6, tree.defs().len());
     match &*tree.defs()[2] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, expr, _, Some(local_type), Some(local_types), _, None) => {
@@ -14771,7 +14802,7 @@ d(x: t) -> (t, t) where t: shared = (x, x);
         _ => assert!(false),
     }
     match &*tree.defs()[3] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, expr, _, Some(local_type), Some(local_types), _, None) => {
@@ -14927,7 +14958,7 @@ d(x: t) -> (t, t) where t: shared = (x, x);
         _ => assert!(false),
     }
     match &*tree.defs()[4] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, expr, _, Some(local_type), Some(local_types), _, None) => {
@@ -15166,7 +15197,7 @@ d(x: t) -> (t, t) where t: shared = (x, x);
         _ => assert!(false),
     }
     match &*tree.defs()[5] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Fun(fun, _, _) => {
@@ -15257,7 +15288,7 @@ g(x: t, y: u) -> (t, u) = (x, y);
     }
     assert_eq!(5, tree.defs().len());
     match &*tree.defs()[3] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Fun(fun, _, _) => {
@@ -15322,7 +15353,7 @@ g(x: t, y: u) -> (t, u) = (x, y);
         _ => assert!(false),
     }
     match &*tree.defs()[4] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Fun(fun, _, _) => {
@@ -15574,7 +15605,7 @@ i(x: Int, y: t, z: u) -> Int =
     }
     assert_eq!(7, tree.defs().len());
     match &*tree.defs()[3] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Fun(fun, _, _) => {
@@ -15754,7 +15785,7 @@ i(x: Int, y: t, z: u) -> Int =
         _ => assert!(false),
     }
     match &*tree.defs()[4] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Fun(fun, _, _) => {
@@ -15955,7 +15986,7 @@ i(x: Int, y: t, z: u) -> Int =
         _ => assert!(false),
     }
     match &*tree.defs()[5] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Fun(fun, _, _) => {
@@ -16135,7 +16166,7 @@ i(x: Int, y: t, z: u) -> Int =
         _ => assert!(false),
     }
     match &*tree.defs()[6] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Fun(fun, _, _) => {
@@ -16428,7 +16459,7 @@ d(x: Ref<T>) -> Ref<Int> = x.0;
     }
     assert_eq!(10, tree.defs().len());
     match &*tree.defs()[6] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, expr, _, Some(local_type), Some(local_types), _, None) => {
@@ -16488,7 +16519,7 @@ d(x: Ref<T>) -> Ref<Int> = x.0;
         _ => assert!(false),
     }
     match &*tree.defs()[7] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, expr, _, Some(local_type), Some(local_types), _, None) => {
@@ -16564,7 +16595,7 @@ d(x: Ref<T>) -> Ref<Int> = x.0;
         _ => assert!(false),
     }
     match &*tree.defs()[8] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, expr, _, Some(local_type), Some(local_types), _, None) => {
@@ -16626,7 +16657,7 @@ d(x: Ref<T>) -> Ref<Int> = x.0;
         _ => assert!(false),
     }
     match &*tree.defs()[9] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Fun(fun, _, _) => {
@@ -16683,24 +16714,13 @@ d(x: Ref<T>) -> Ref<Int> = x.0;
 fn test_typer_check_types_infers_types_for_printf()
 {
     let s = "
-builtin type PrivateSlice;
-builtin type GlobalSlice;
-trait PrivateSliceFrom<t1>
-{
-    private_slice(a: t) -> PrivateSlice<u> where t: PrivateSliceFrom <u>;
-};
-trait GlobalSliceFrom<t1>
-{
-    global_slice(a: t) -> GlobalSlice<u> where t: GlobalSliceFrom <u>;
-};
-builtin impl PrivateSliceFrom for [_; _];
-builtin impl GlobalSliceFrom for [_; _];
 builtin type Char;
 builtin type Int;
 builtin type ConstantSlice;
 a: Int = printf(\"%d\\n\", 1);
-b: Int = printf(\"%s\\n\", private_slice(['a', '\\0']));
-c: Int = printf(\"%s\\n\", global_slice(['a', '\\0']));
+b: Int = printf(\"%s\\n\", \"a\");
+s: ConstantSlice<Char> = \"b\";
+c: Int = printf(\"%s\\n\", s);
 ";
     let s2 = &s[1..];
     let mut cursor = Cursor::new(s2.as_bytes());
@@ -16720,9 +16740,9 @@ c: Int = printf(\"%s\\n\", global_slice(['a', '\\0']));
         Ok(()) => assert!(true),
         Err(_) => assert!(false),
     }
-    assert_eq!(12, tree.defs().len());
-    match &*tree.defs()[9] {
-        Def::Var(_, var, _) => {
+    assert_eq!(7, tree.defs().len());
+    match &*tree.defs()[3] {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, expr, _, Some(local_type), Some(local_types), _, None) => {
@@ -16761,8 +16781,8 @@ c: Int = printf(\"%s\\n\", global_slice(['a', '\\0']));
         },
         _ => assert!(false),
     }
-    match &*tree.defs()[10] {
-        Def::Var(_, var, _) => {
+    match &*tree.defs()[4] {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, expr, _, Some(local_type), Some(local_types), _, None) => {
@@ -16781,48 +16801,13 @@ c: Int = printf(\"%s\\n\", global_slice(['a', '\\0']));
                                         _ => assert!(false),
                                     }
                                     match &*exprs[1] {
-                                        Expr::App(expr, exprs, Some(local_type), _) => {
-                                            match &**expr {
-                                                Expr::Var(_, Some(local_type), _) => {
-                                                    assert_eq!(LocalType::new(2), *local_type);
-                                                    assert_eq!(String::from("([Char; 2]) -> PrivateSlice<Char>"), local_types.local_type_to_string(*local_type));
-                                                },
-                                                _ => assert!(false),
-                                            }
-                                            assert_eq!(1, exprs.len());
-                                            match &*exprs[0] {
-                                                Expr::Literal(literal, Some(local_type), _) => {
-                                                    match &**literal {
-                                                        Literal::Array(exprs) => {
-                                                            assert_eq!(2, exprs.len());
-                                                            match &*exprs[0] {
-                                                                Expr::Literal(_, Some(local_type), _) => {
-                                                                    assert_eq!(LocalType::new(3), *local_type);
-                                                                    assert_eq!(String::from("Char"), local_types.local_type_to_string(*local_type));
-                                                                },
-                                                                _ => assert!(false),
-                                                            }
-                                                            match &*exprs[1] {
-                                                                Expr::Literal(_, Some(local_type), _) => {
-                                                                    assert_eq!(LocalType::new(4), *local_type);
-                                                                    assert_eq!(String::from("Char"), local_types.local_type_to_string(*local_type));
-                                                                },
-                                                                _ => assert!(false),
-                                                            }
-                                                        },
-                                                        _ => assert!(false),
-                                                    }
-                                                    assert_eq!(LocalType::new(5), *local_type);
-                                                    assert_eq!(String::from("[Char; 2]"), local_types.local_type_to_string(*local_type));
-                                                },
-                                                _ => assert!(false),
-                                            }
-                                            assert_eq!(LocalType::new(6), *local_type);
-                                            assert_eq!(String::from("PrivateSlice<Char>"), local_types.local_type_to_string(*local_type));
+                                        Expr::Literal(_, Some(local_type), _) => {
+                                            assert_eq!(LocalType::new(2), *local_type);
+                                            assert_eq!(String::from("ConstantSlice<Char>"), local_types.local_type_to_string(*local_type));
                                         },
                                         _ => assert!(false),
                                     }
-                                    assert_eq!(LocalType::new(7), *local_type);
+                                    assert_eq!(LocalType::new(3), *local_type);
                                     assert_eq!(String::from("Int"), local_types.local_type_to_string(*local_type));
                                 },
                                 _ => assert!(false),
@@ -16836,8 +16821,8 @@ c: Int = printf(\"%s\\n\", global_slice(['a', '\\0']));
         },
         _ => assert!(false),
     }
-    match &*tree.defs()[11] {
-        Def::Var(_, var, _) => {
+    match &*tree.defs()[6] {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, expr, _, Some(local_type), Some(local_types), _, None) => {
@@ -16856,48 +16841,13 @@ c: Int = printf(\"%s\\n\", global_slice(['a', '\\0']));
                                         _ => assert!(false),
                                     }
                                     match &*exprs[1] {
-                                        Expr::App(expr, exprs, Some(local_type), _) => {
-                                            match &**expr {
-                                                Expr::Var(_, Some(local_type), _) => {
-                                                    assert_eq!(LocalType::new(2), *local_type);
-                                                    assert_eq!(String::from("([Char; 2]) -> GlobalSlice<Char>"), local_types.local_type_to_string(*local_type));
-                                                },
-                                                _ => assert!(false),
-                                            }
-                                            assert_eq!(1, exprs.len());
-                                            match &*exprs[0] {
-                                                Expr::Literal(literal, Some(local_type), _) => {
-                                                    match &**literal {
-                                                        Literal::Array(exprs) => {
-                                                            assert_eq!(2, exprs.len());
-                                                            match &*exprs[0] {
-                                                                Expr::Literal(_, Some(local_type), _) => {
-                                                                    assert_eq!(LocalType::new(3), *local_type);
-                                                                    assert_eq!(String::from("Char"), local_types.local_type_to_string(*local_type));
-                                                                },
-                                                                _ => assert!(false),
-                                                            }
-                                                            match &*exprs[1] {
-                                                                Expr::Literal(_, Some(local_type), _) => {
-                                                                    assert_eq!(LocalType::new(4), *local_type);
-                                                                    assert_eq!(String::from("Char"), local_types.local_type_to_string(*local_type));
-                                                                },
-                                                                _ => assert!(false),
-                                                            }
-                                                        },
-                                                        _ => assert!(false),
-                                                    }
-                                                    assert_eq!(LocalType::new(5), *local_type);
-                                                    assert_eq!(String::from("[Char; 2]"), local_types.local_type_to_string(*local_type));
-                                                },
-                                                _ => assert!(false),
-                                            }
-                                            assert_eq!(LocalType::new(6), *local_type);
-                                            assert_eq!(String::from("GlobalSlice<Char>"), local_types.local_type_to_string(*local_type));
+                                        Expr::Var(_, Some(local_type), _) => {
+                                            assert_eq!(LocalType::new(2), *local_type);
+                                            assert_eq!(String::from("ConstantSlice<Char>"), local_types.local_type_to_string(*local_type));
                                         },
                                         _ => assert!(false),
                                     }
-                                    assert_eq!(LocalType::new(7), *local_type);
+                                    assert_eq!(LocalType::new(3), *local_type);
                                     assert_eq!(String::from("Int"), local_types.local_type_to_string(*local_type));
                                 },
                                 _ => assert!(false),
@@ -16959,7 +16909,7 @@ m(x: V) -> (V, Int, Int) = x match { y @ E(_, z, w) => (y, z, w); };
     }
     assert_eq!(13, tree.defs().len());
     match &*tree.defs()[5] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Fun(fun, _, _) => {
@@ -17022,7 +16972,7 @@ m(x: V) -> (V, Int, Int) = x match { y @ E(_, z, w) => (y, z, w); };
         _ => assert!(false),
     }
     match &*tree.defs()[6] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Fun(fun, _, _) => {
@@ -17085,7 +17035,7 @@ m(x: V) -> (V, Int, Int) = x match { y @ E(_, z, w) => (y, z, w); };
         _ => assert!(false),
     }
     match &*tree.defs()[7] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Fun(fun, _, _) => {
@@ -17164,7 +17114,7 @@ m(x: V) -> (V, Int, Int) = x match { y @ E(_, z, w) => (y, z, w); };
         _ => assert!(false),
     }
     match &*tree.defs()[8] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Fun(fun, _, _) => {
@@ -17251,7 +17201,7 @@ m(x: V) -> (V, Int, Int) = x match { y @ E(_, z, w) => (y, z, w); };
         _ => assert!(false),
     }
     match &*tree.defs()[9] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Fun(fun, _, _) => {
@@ -17333,7 +17283,7 @@ m(x: V) -> (V, Int, Int) = x match { y @ E(_, z, w) => (y, z, w); };
         _ => assert!(false),
     }
     match &*tree.defs()[10] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Fun(fun, _, _) => {
@@ -17430,7 +17380,7 @@ m(x: V) -> (V, Int, Int) = x match { y @ E(_, z, w) => (y, z, w); };
         _ => assert!(false),
     }
     match &*tree.defs()[11] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Fun(fun, _, _) => {
@@ -17535,7 +17485,7 @@ m(x: V) -> (V, Int, Int) = x match { y @ E(_, z, w) => (y, z, w); };
         _ => assert!(false),
     }
     match &*tree.defs()[12] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Fun(fun, _, _) => {
@@ -17681,7 +17631,7 @@ a: Int = a;
     }
     assert_eq!(2, tree.defs().len());
     match &*tree.defs()[1] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, expr, _, Some(local_type), Some(local_types), _, None) => {
@@ -17737,7 +17687,7 @@ A: Int = 1 match {
     }
     assert_eq!(2, tree.defs().len());
     match &*tree.defs()[1] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Var(_, _, _, expr, _, Some(local_type), Some(local_types), _, None) => {
@@ -17834,7 +17784,7 @@ f() -> Int = f();
     }
     assert_eq!(2, tree.defs().len());
     match &*tree.defs()[1] {
-        Def::Var(_, var, _) => {
+        Def::Var(_, _, var, _) => {
             let var_r = var.borrow();
             match &*var_r {
                 Var::Fun(fun, _, _) => {
@@ -18975,6 +18925,227 @@ a: Int = printf(\"%d\\n\", true);
     }
 }
 
+#[test]
+fn test_typer_check_types_complains_on_conversion_specification_does_not_match_type()
+{
+    let s = "
+builtin type Char;
+builtin type Int;
+builtin type ConstantSlice;
+a: Int = printf(\"%f\\n\", 1);
+";
+    let s2 = &s[1..];
+    let mut cursor = Cursor::new(s2.as_bytes());
+    let mut parser = Parser::new(Lexer::new(String::from("test.vscfl"), &mut cursor));
+    let mut tree = Tree::new();
+    match parser.parse(&mut tree) {
+        Ok(()) => assert!(true),
+        Err(_) => assert!(false),
+    }
+    let namer = Namer::new();
+    match namer.check_idents(&mut tree) {
+        Ok(()) => assert!(true),
+        Err(_) => assert!(false),
+    }
+    let typer = Typer::new();
+    match typer.check_types(&tree) {
+        Err(errs) => {
+            assert_eq!(1, errs.errors().len());
+            match &errs.errors()[0] {
+                FrontendError::Message(pos, msg) => {
+                    assert_eq!(4, pos.line);
+                    assert_eq!(25, pos.column);
+                    assert_eq!(String::from("conversion specification %f doesn't match type Int"), *msg);
+                },
+                _ => assert!(false),
+            }
+        },
+        _ => assert!(false),
+    }
+}
+
+#[test]
+fn test_typer_check_types_complains_on_string_conversion_specification_for_not_string_literal()
+{
+    let s = "
+builtin type PrivateSlice;
+trait PrivateSliceFrom<t1>
+{
+    private_slice(a: t) -> PrivateSlice<u> where t: PrivateSliceFrom <u>;
+};
+builtin impl PrivateSliceFrom for [_; _];
+builtin type Char;
+builtin type Int;
+builtin type ConstantSlice;
+s: ConstantSlice<Char> = \"a\";
+t: ConstantSlice<Char> = s;
+f(x: ConstantSlice<Char>) -> Int = printf(\"%s\\n\", x);
+a: Int = printf(\"%s\\n\", t);
+b: Int = printf(\"%s\\n\", private_slice(['a', '\\0']));
+";
+    let s2 = &s[1..];
+    let mut cursor = Cursor::new(s2.as_bytes());
+    let mut parser = Parser::new(Lexer::new(String::from("test.vscfl"), &mut cursor));
+    let mut tree = Tree::new();
+    match parser.parse(&mut tree) {
+        Ok(()) => assert!(true),
+        Err(_) => assert!(false),
+    }
+    let namer = Namer::new();
+    match namer.check_idents(&mut tree) {
+        Ok(()) => assert!(true),
+        Err(_) => assert!(false),
+    }
+    let typer = Typer::new();
+    match typer.check_types(&tree) {
+        Err(errs) => {
+            assert_eq!(3, errs.errors().len());
+            match &errs.errors()[0] {
+                FrontendError::Message(pos, msg) => {
+                    assert_eq!(12, pos.line);
+                    assert_eq!(51, pos.column);
+                    assert_eq!(String::from("conversion specification %s requires string literal or variable with string literal"), *msg);
+                },
+                _ => assert!(false),
+            }
+            match &errs.errors()[1] {
+                FrontendError::Message(pos, msg) => {
+                    assert_eq!(13, pos.line);
+                    assert_eq!(25, pos.column);
+                    assert_eq!(String::from("conversion specification %s requires string literal or variable with string literal"), *msg);
+                },
+                _ => assert!(false),
+            }
+            match &errs.errors()[2] {
+                FrontendError::Message(pos, msg) => {
+                    assert_eq!(14, pos.line);
+                    assert_eq!(25, pos.column);
+                    assert_eq!(String::from("conversion specification %s doesn't match type PrivateSlice<Char>"), *msg);
+                },
+                _ => assert!(false),
+            }
+        },
+        _ => assert!(false),
+    }
+}
+
+#[test]
+fn test_typer_check_types_complains_on_too_few_arguments_for_printf_format()
+{
+    let s = "
+builtin type Char;
+builtin type Int;
+builtin type ConstantSlice;
+a: Int = printf(\"%d %d\\n\", 1);
+";
+    let s2 = &s[1..];
+    let mut cursor = Cursor::new(s2.as_bytes());
+    let mut parser = Parser::new(Lexer::new(String::from("test.vscfl"), &mut cursor));
+    let mut tree = Tree::new();
+    match parser.parse(&mut tree) {
+        Ok(()) => assert!(true),
+        Err(_) => assert!(false),
+    }
+    let namer = Namer::new();
+    match namer.check_idents(&mut tree) {
+        Ok(()) => assert!(true),
+        Err(_) => assert!(false),
+    }
+    let typer = Typer::new();
+    match typer.check_types(&tree) {
+        Err(errs) => {
+            assert_eq!(1, errs.errors().len());
+            match &errs.errors()[0] {
+                FrontendError::Message(pos, msg) => {
+                    assert_eq!(4, pos.line);
+                    assert_eq!(10, pos.column);
+                    assert_eq!(String::from("too few arguments for printf format"), *msg);
+                },
+                _ => assert!(false),
+            }
+        },
+        _ => assert!(false),
+    }
+}
+
+#[test]
+fn test_typer_check_types_complains_on_too_many_arguments_for_printf_format()
+{
+    let s = "
+builtin type Char;
+builtin type Int;
+builtin type ConstantSlice;
+a: Int = printf(\"%d\\n\", 1, 2);
+";
+    let s2 = &s[1..];
+    let mut cursor = Cursor::new(s2.as_bytes());
+    let mut parser = Parser::new(Lexer::new(String::from("test.vscfl"), &mut cursor));
+    let mut tree = Tree::new();
+    match parser.parse(&mut tree) {
+        Ok(()) => assert!(true),
+        Err(_) => assert!(false),
+    }
+    let namer = Namer::new();
+    match namer.check_idents(&mut tree) {
+        Ok(()) => assert!(true),
+        Err(_) => assert!(false),
+    }
+    let typer = Typer::new();
+    match typer.check_types(&tree) {
+        Err(errs) => {
+            assert_eq!(1, errs.errors().len());
+            match &errs.errors()[0] {
+                FrontendError::Message(pos, msg) => {
+                    assert_eq!(4, pos.line);
+                    assert_eq!(28, pos.column);
+                    assert_eq!(String::from("too many arguments for printf format"), *msg);
+                },
+                _ => assert!(false),
+            }
+        },
+        _ => assert!(false),
+    }
+}
+
+#[test]
+fn test_typer_check_types_complains_on_invalid_conversion_specifier_in_printf_format()
+{
+    let s = "
+builtin type Char;
+builtin type Int;
+builtin type ConstantSlice;
+a: Int = printf(\"%y\\n\", 1);
+";
+    let s2 = &s[1..];
+    let mut cursor = Cursor::new(s2.as_bytes());
+    let mut parser = Parser::new(Lexer::new(String::from("test.vscfl"), &mut cursor));
+    let mut tree = Tree::new();
+    match parser.parse(&mut tree) {
+        Ok(()) => assert!(true),
+        Err(_) => assert!(false),
+    }
+    let namer = Namer::new();
+    match namer.check_idents(&mut tree) {
+        Ok(()) => assert!(true),
+        Err(_) => assert!(false),
+    }
+    let typer = Typer::new();
+    match typer.check_types(&tree) {
+        Err(errs) => {
+            assert_eq!(1, errs.errors().len());
+            match &errs.errors()[0] {
+                FrontendError::Message(pos, msg) => {
+                    assert_eq!(4, pos.line);
+                    assert_eq!(17, pos.column);
+                    assert_eq!(String::from("invalid conversion specifier y in printf format"), *msg);
+                },
+                _ => assert!(false),
+            }
+        },
+        _ => assert!(false),
+    }
+}
+
 #[test]
 fn test_typer_check_types_complains_on_type_has_not_field_for_unnamed_field()
 {
@@ -6,6 +6,8 @@
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 //
 pub mod backend;
+pub mod build;
+pub mod cli;
 pub mod frontend;
 pub mod stdlib;
 pub mod utils;
@@ -1,3 +1,16 @@
-fn main() {
-    println!("Hello, world!");
+//
+// Copyright (c) 2025 Łukasz Szpakowski
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+use std::env;
+use std::process;
+use vscfl::cli::run;
+
+fn main()
+{
+    let args: Vec<String> = env::args().skip(1).collect();
+    process::exit(run(args.as_slice()));
 }
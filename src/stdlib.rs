@@ -7,7 +7,7 @@
 //
 pub use crate::frontend::source::Source;
 
-const LANG_SOURCE: &'static str = include_str!("stdlib/lang.vscfl");
+const LANG_SOURCE: &str = include_str!("stdlib/lang.vscfl");
 
 fn generate_lang_impls_source() -> Source
 {
@@ -134,15 +134,15 @@ fn generate_lang_impls_source() -> Source
         }
     }
     // OpGet
-    for s in ["Ref", "PrivateRef", "LocalRef", "GlobalRef", "ConstantRef", "UniqRef", "UniqPrivateRef", "UniqLocalRef", "UniqGlobalRef"] {
+    for s in ["Ref", "PrivateRef", "LocalRef", "GlobalRef", "ConstantRef", "UniqRef", "UniqPrivateRef", "UniqLocalRef", "UniqGlobalRef", "UniqPrivateBox", "UniqLocalBox", "UniqGlobalBox"] {
         src += format!("builtin impl OpGet for {};\n", s).as_str();
     }
     // OpSet
-    for s in ["UniqRef", "UniqPrivateRef", "UniqLocalRef", "UniqGlobalRef"] {
+    for s in ["UniqRef", "UniqPrivateRef", "UniqLocalRef", "UniqGlobalRef", "UniqPrivateBox", "UniqLocalBox", "UniqGlobalBox"] {
         src += format!("builtin impl OpSet for {};\n", s).as_str();
     }
     // OpUpdate
-    for s in ["UniqRef", "UniqPrivateRef", "UniqLocalRef", "UniqGlobalRef"] {
+    for s in ["UniqRef", "UniqPrivateRef", "UniqLocalRef", "UniqGlobalRef", "UniqPrivateBox", "UniqLocalBox", "UniqGlobalBox"] {
         src += format!("builtin impl OpUpdate for {};\n", s).as_str();
     }
     // OpGetNth
@@ -171,13 +171,13 @@ fn generate_lang_impls_source() -> Source
     Source::String(String::from("(stdlib)/lang_impls.vscfl"), src)
 }
 
-const STD_SOURCE: &'static str = include_str!("stdlib/std.vscfl");
-const STD_COMMON_SOURCE: &'static str = include_str!("stdlib/std_common.vscfl");
-const STD_GEOMETRY_SOURCE: &'static str = include_str!("stdlib/std_geometry.vscfl");
-const STD_MATH_SOURCE: &'static str = include_str!("stdlib/std_math.vscfl");
-const STD_OPTION_SOURCE: &'static str = include_str!("stdlib/std_option.vscfl");
-const STD_RANGE_SOURCE: &'static str = include_str!("stdlib/std_range.vscfl");
-const STD_VALUES_SOURCE: &'static str = include_str!("stdlib/std_values.vscfl");
+const STD_SOURCE: &str = include_str!("stdlib/std.vscfl");
+const STD_COMMON_SOURCE: &str = include_str!("stdlib/std_common.vscfl");
+const STD_GEOMETRY_SOURCE: &str = include_str!("stdlib/std_geometry.vscfl");
+const STD_MATH_SOURCE: &str = include_str!("stdlib/std_math.vscfl");
+const STD_OPTION_SOURCE: &str = include_str!("stdlib/std_option.vscfl");
+const STD_RANGE_SOURCE: &str = include_str!("stdlib/std_range.vscfl");
+const STD_VALUES_SOURCE: &str = include_str!("stdlib/std_values.vscfl");
 
 fn generate_std_impls_source() -> Source
 {
@@ -281,6 +281,72 @@ fn generate_std_impls_source() -> Source
     src += "builtin impl FoldUpdateUniqLocalRefs for UniqLocalSlice;\n";
     // FoldUpdateUniqGlobalRefs
     src += "builtin impl FoldUpdateUniqGlobalRefs for UniqGlobalSlice;\n";
+    Source::String(String::from("(stdlib)/std_impls.vscfl"), src)
+}
+
+fn generate_std_common_impls_source() -> Source
+{
+    let mut src = String::new();
+    // Common
+    for s in ["Char", "Short", "Int", "Long", "Uchar", "Ushort", "Uint", "Ulong", "Float", "Double"] {
+        src += format!("builtin impl Common for {};\n", s).as_str();
+    }
+    for s in ["Char", "Short", "Int", "Long", "Uchar", "Ushort", "Uint", "Ulong", "Float", "Double"] {
+        for n in [2, 3, 4, 8, 16] {
+            src += format!("builtin impl Common for {}{};\n", s, n).as_str();
+        }
+    }
+    // CommonExt
+    for s in ["Float", "Double"] {
+        src += format!("builtin impl CommonExt for {};\n", s).as_str();
+    }
+    for s in ["Float", "Double"] {
+        for n in [2, 3, 4, 8, 16] {
+            src += format!("builtin impl CommonExt for {}{};\n", s, n).as_str();
+        }
+    }
+    Source::String(String::from("(stdlib)/std_common_impls.vscfl"), src)
+}
+
+fn generate_std_geometry_impls_source() -> Source
+{
+    let mut src = String::new();
+    // Cross
+    for s in ["Float", "Double"] {
+        for n in [3, 4] {
+            src += format!("builtin impl Cross for {}{};\n", s, n).as_str();
+        }
+    }
+    // HalfGeometric
+    src += "builtin impl HalfGeometric for Float;\n";
+    for n in [2, 3, 4] {
+        src += format!("builtin impl HalfGeometric for Float{};\n", n).as_str();
+    }
+    // FloatGeometric
+    src += "builtin impl FloatGeometric for Float;\n";
+    for n in [2, 3, 4] {
+        src += format!("builtin impl FloatGeometric for Float{};\n", n).as_str();
+    }
+    // DoubleGeometric
+    src += "builtin impl DoubleGeometric for Double;\n";
+    for n in [2, 3, 4] {
+        src += format!("builtin impl DoubleGeometric for Double{};\n", n).as_str();
+    }
+    // Normalize
+    for s in ["Float", "Double"] {
+        src += format!("builtin impl Normalize for {};\n", s).as_str();
+    }
+    for s in ["Float", "Double"] {
+        for n in [2, 3, 4] {
+            src += format!("builtin impl Normalize for {}{};\n", s, n).as_str();
+        }
+    }
+    Source::String(String::from("(stdlib)/std_geometry_impls.vscfl"), src)
+}
+
+fn generate_std_math_impls_source() -> Source
+{
+    let mut src = String::new();
     // Trigonometric
     for s in ["Half", "Float", "Double"] {
         src += format!("builtin impl Trigonometric for {};\n", s).as_str();
@@ -468,24 +534,12 @@ fn generate_std_impls_source() -> Source
     for s in ["Float", "Double"] {
         src += format!("builtin impl EpsilonValue for {};\n", s).as_str();
     }
-    // Common
-    for s in ["Char", "Short", "Int", "Long", "Uchar", "Ushort", "Uint", "Ulong", "Float", "Double"] {
-        src += format!("builtin impl Common for {};\n", s).as_str();
-    }
-    for s in ["Char", "Short", "Int", "Long", "Uchar", "Ushort", "Uint", "Ulong", "Float", "Double"] {
-        for n in [2, 3, 4, 8, 16] {
-            src += format!("builtin impl Common for {}{};\n", s, n).as_str();
-        }
-    }
-    // CommonExt
-    for s in ["Float", "Double"] {
-        src += format!("builtin impl CommonExt for {};\n", s).as_str();
-    }
-    for s in ["Float", "Double"] {
-        for n in [2, 3, 4, 8, 16] {
-            src += format!("builtin impl CommonExt for {}{};\n", s, n).as_str();
-        }
-    }
+    Source::String(String::from("(stdlib)/std_math_impls.vscfl"), src)
+}
+
+fn generate_std_values_impls_source() -> Source
+{
+    let mut src = String::new();
     // MaxValue
     for s in ["Char", "Short", "Int", "Long", "Uchar", "Ushort", "Uint", "Ulong", "Float", "Double"] {
         src += format!("builtin impl MaxValue for {};\n", s).as_str();
@@ -494,47 +548,17 @@ fn generate_std_impls_source() -> Source
     for s in ["Char", "Short", "Int", "Long", "Uchar", "Ushort", "Uint", "Ulong", "Float", "Double"] {
         src += format!("builtin impl MinValue for {};\n", s).as_str();
     }
-    // Cross
-    for s in ["Float", "Double"] {
-        for n in [3, 4] {
-            src += format!("builtin impl Cross for {}{};\n", s, n).as_str();
-        }
-    }
-    // HalfGeometric
-    src += "builtin impl HalfGeometric for Float;\n";
-    for n in [2, 3, 4] {
-        src += format!("builtin impl HalfGeometric for Float{};\n", n).as_str();
-    }
-    // FloatGeometric
-    src += "builtin impl FloatGeometric for Float;\n";
-    for n in [2, 3, 4] {
-        src += format!("builtin impl FloatGeometric for Float{};\n", n).as_str();
-    }
-    // DoubleGeometric
-    src += "builtin impl DoubleGeometric for Double;\n";
-    for n in [2, 3, 4] {
-        src += format!("builtin impl DoubleGeometric for Double{};\n", n).as_str();
-    }
-    // Normalize
-    for s in ["Float", "Double"] {
-        src += format!("builtin impl Normalize for {};\n", s).as_str();
-    }
-    for s in ["Float", "Double"] {
-        for n in [2, 3, 4] {
-            src += format!("builtin impl Normalize for {}{};\n", s, n).as_str();
-        }
-    }
-    Source::String(String::from("(stdlib)/std_impls.vscfl"), src)
+    Source::String(String::from("(stdlib)/std_values_impls.vscfl"), src)
 }
 
-const OPENCL_SOURCE: &'static str = include_str!("stdlib/opencl.vscfl");
+const OPENCL_SOURCE: &str = include_str!("stdlib/opencl.vscfl");
 
 fn generate_opencl_convert_source() -> Source
 {
     let mut src = String::new();
     // ConvertS
     for s in ["Char", "Short", "Int", "Long", "Uchar", "Ushort", "Uint", "Ulong", "Float", "Double"] {
-        src += format!("trait Convert{}\n", s).as_str();
+        src += format!("pub trait Convert{}\n", s).as_str();
         src += "{\n";
         src += format!("    convert_{}(x: t) -> {} where t: Convert{};\n", s.to_lowercase(), s, s).as_str();
         src += format!("    convert_{}_rte(x: t) -> {} where t: Convert{};\n", s.to_lowercase(), s, s).as_str();
@@ -551,7 +575,7 @@ fn generate_opencl_convert_source() -> Source
     // ConvertSN
     for s in ["Char", "Short", "Int", "Long", "Uchar", "Ushort", "Uint", "Ulong", "Float", "Double"] {
         for n in [2, 3, 4, 8, 16] {
-            src += format!("trait Convert{}{}\n", s, n).as_str();
+            src += format!("pub trait Convert{}{}\n", s, n).as_str();
             src += "{\n";
             src += format!("    convert_{}{}(x: t) -> {}{} where t: Convert{}{};\n", s.to_lowercase(), n, s, n, s, n).as_str();
             src += format!("    convert_{}{}_rte(x: t) -> {}{} where t: Convert{}{};\n", s.to_lowercase(), n, s, n, s, n).as_str();
@@ -574,7 +598,7 @@ fn generate_opencl_shuffle_source() -> Source
     let mut src = String::new();
     for s in ["Char", "Short", "Int", "Long", "Uchar", "Ushort", "Uint", "Ulong", "Float", "Double"] {
         for n in [2, 4, 8, 16] {
-            src += format!("trait {}{}Shuffle\n", s, n).as_str();
+            src += format!("pub trait {}{}Shuffle\n", s, n).as_str();
             src += "{\n";
             src += format!("    {}{}_shuffle_uchar{}(x: t, mask: Uchar{}) -> {}{} where t: {}{}Shuffle;\n", s, n, n, n, s, n, s, n).as_str();
             src += format!("    {}{}_shuffle_ushort{}(x: t, mask: Ushort{}) -> {}{} where t: {}{}Shuffle;\n", s, n, n, n, s, n, s, n).as_str();
@@ -594,12 +618,12 @@ fn generate_opencl_upsample_source() -> Source
 {
     let mut src = String::new();
     for s in ["", "2", "3", "4", "8", "16"] {
-        src += format!("builtin short{}_upsample;\n", s).as_str();
-        src += format!("builtin int{}_upsample;\n", s).as_str();
-        src += format!("builtin long{}_upsample;\n", s).as_str();
-        src += format!("builtin ushort{}_upsample;\n", s).as_str();
-        src += format!("builtin uint{}_upsample;\n", s).as_str();
-        src += format!("builtin ulong{}_upsample;\n", s).as_str();
+        src += format!("pub builtin short{}_upsample;\n", s).as_str();
+        src += format!("pub builtin int{}_upsample;\n", s).as_str();
+        src += format!("pub builtin long{}_upsample;\n", s).as_str();
+        src += format!("pub builtin ushort{}_upsample;\n", s).as_str();
+        src += format!("pub builtin uint{}_upsample;\n", s).as_str();
+        src += format!("pub builtin ulong{}_upsample;\n", s).as_str();
     }
     Source::String(String::from("(stdlib)/opencl_upsample.vscfl"), src)
 }
@@ -610,9 +634,9 @@ fn generate_opencl_vector_source() -> Source
     for s in ["Char", "Short", "Int", "Long", "Uchar", "Ushort", "Uint", "Ulong", "Float", "Double"] {
         for t in ["2", "3", "4", "8", "16"] {
             for u in ["Private", "Local", "Global", "Constant"] {
-                src += format!("builtin {}_{}_vload{};\n", u.to_lowercase(), s.to_lowercase(), t).as_str();
+                src += format!("pub builtin {}_{}_vload{};\n", u.to_lowercase(), s.to_lowercase(), t).as_str();
                 if u != "Constant" {
-                    src += format!("builtin {}_{}_vload{}_uniq;\n", u.to_lowercase(), s.to_lowercase(), t).as_str();
+                    src += format!("pub builtin {}_{}_vload{}_uniq;\n", u.to_lowercase(), s.to_lowercase(), t).as_str();
                 }
             }
         }
@@ -620,21 +644,21 @@ fn generate_opencl_vector_source() -> Source
     for s in ["Char", "Short", "Int", "Long", "Uchar", "Ushort", "Uint", "Ulong", "Float", "Double"] {
         for t in ["2", "3", "4", "8", "16"] {
             for u in ["Private", "Local", "Global"] {
-                src += format!("builtin {}_{}_vstore{};\n", u.to_lowercase(), s.to_lowercase(), t).as_str();
+                src += format!("pub builtin {}_{}_vstore{};\n", u.to_lowercase(), s.to_lowercase(), t).as_str();
             }
         }
     }
     for s in ["Float"] {
         for t in ["", "2", "3", "4", "8", "16"] {
             for u in ["Private", "Local", "Global", "Constant"] {
-                src += format!("builtin {}_{}_vload_half{};\n", u.to_lowercase(), s.to_lowercase(), t).as_str();
+                src += format!("pub builtin {}_{}_vload_half{};\n", u.to_lowercase(), s.to_lowercase(), t).as_str();
                 if u != "Constant" {
-                    src += format!("builtin {}_{}_vload_half{}_uniq;\n", u.to_lowercase(), s.to_lowercase(), t).as_str();
+                    src += format!("pub builtin {}_{}_vload_half{}_uniq;\n", u.to_lowercase(), s.to_lowercase(), t).as_str();
                 }
-                if t != "" {
-                    src += format!("builtin {}_{}_vloada_half{};\n", u.to_lowercase(), s.to_lowercase(), t).as_str();
+                if !t.is_empty() {
+                    src += format!("pub builtin {}_{}_vloada_half{};\n", u.to_lowercase(), s.to_lowercase(), t).as_str();
                     if u != "Constant" {
-                        src += format!("builtin {}_{}_vloada_half{}_uniq;\n", u.to_lowercase(), s.to_lowercase(), t).as_str();
+                        src += format!("pub builtin {}_{}_vloada_half{}_uniq;\n", u.to_lowercase(), s.to_lowercase(), t).as_str();
                     }
                 }
             }
@@ -643,17 +667,17 @@ fn generate_opencl_vector_source() -> Source
     for s in ["Float", "Double"] {
         for t in ["", "2", "3", "4", "8", "16"] {
             for u in ["Private", "Local", "Global"] {
-                src += format!("builtin {}_{}_vstore_half{};\n", u.to_lowercase(), s.to_lowercase(), t).as_str();
-                src += format!("builtin {}_{}_vstore_half{}_rte;\n", u.to_lowercase(), s.to_lowercase(), t).as_str();
-                src += format!("builtin {}_{}_vstore_half{}_rtz;\n", u.to_lowercase(), s.to_lowercase(), t).as_str();
-                src += format!("builtin {}_{}_vstore_half{}_rtp;\n", u.to_lowercase(), s.to_lowercase(), t).as_str();
-                src += format!("builtin {}_{}_vstore_half{}_rtn;\n", u.to_lowercase(), s.to_lowercase(), t).as_str();
-                if t != "" {
-                    src += format!("builtin {}_{}_vstorea_half{};\n", u.to_lowercase(), s.to_lowercase(), t).as_str();
-                    src += format!("builtin {}_{}_vstorea_half{}_rte;\n", u.to_lowercase(), s.to_lowercase(), t).as_str();
-                    src += format!("builtin {}_{}_vstorea_half{}_rtz;\n", u.to_lowercase(), s.to_lowercase(), t).as_str();
-                    src += format!("builtin {}_{}_vstorea_half{}_rtp;\n", u.to_lowercase(), s.to_lowercase(), t).as_str();
-                    src += format!("builtin {}_{}_vstorea_half{}_rtn;\n", u.to_lowercase(), s.to_lowercase(), t).as_str();
+                src += format!("pub builtin {}_{}_vstore_half{};\n", u.to_lowercase(), s.to_lowercase(), t).as_str();
+                src += format!("pub builtin {}_{}_vstore_half{}_rte;\n", u.to_lowercase(), s.to_lowercase(), t).as_str();
+                src += format!("pub builtin {}_{}_vstore_half{}_rtz;\n", u.to_lowercase(), s.to_lowercase(), t).as_str();
+                src += format!("pub builtin {}_{}_vstore_half{}_rtp;\n", u.to_lowercase(), s.to_lowercase(), t).as_str();
+                src += format!("pub builtin {}_{}_vstore_half{}_rtn;\n", u.to_lowercase(), s.to_lowercase(), t).as_str();
+                if !t.is_empty() {
+                    src += format!("pub builtin {}_{}_vstorea_half{};\n", u.to_lowercase(), s.to_lowercase(), t).as_str();
+                    src += format!("pub builtin {}_{}_vstorea_half{}_rte;\n", u.to_lowercase(), s.to_lowercase(), t).as_str();
+                    src += format!("pub builtin {}_{}_vstorea_half{}_rtz;\n", u.to_lowercase(), s.to_lowercase(), t).as_str();
+                    src += format!("pub builtin {}_{}_vstorea_half{}_rtp;\n", u.to_lowercase(), s.to_lowercase(), t).as_str();
+                    src += format!("pub builtin {}_{}_vstorea_half{}_rtn;\n", u.to_lowercase(), s.to_lowercase(), t).as_str();
                 }
             }
         }
@@ -663,7 +687,7 @@ fn generate_opencl_vector_source() -> Source
 
 fn generate_opencl_impls_source() -> Source
 {
-    let mut src = String::new();
+    let mut src = String::from("import std;\n");
     // OpNot
     src += "builtin impl OpNot for ClMemFenceFlags;\n";
     // Eq
@@ -825,24 +849,49 @@ fn generate_opencl_impls_source() -> Source
     Source::String(String::from("(stdlib)/opencl_impls.vscfl"), src)
 }
 
-pub fn stdlib_sources() -> Vec<Source>
-{ 
+pub fn stdlib_modules() -> Vec<(String, Vec<Source>)>
+{
     vec![
-        Source::String(String::from("(stdlib)/lang.vscfl"), String::from(LANG_SOURCE)),
-        generate_lang_impls_source(),
-        Source::String(String::from("(stdlib)/std.vscfl"), String::from(STD_SOURCE)),
-        Source::String(String::from("(stdlib)/std_common.vscfl"), String::from(STD_COMMON_SOURCE)),
-        Source::String(String::from("(stdlib)/std_geometry.vscfl"), String::from(STD_GEOMETRY_SOURCE)),
-        Source::String(String::from("(stdlib)/std_math.vscfl"), String::from(STD_MATH_SOURCE)),
-        Source::String(String::from("(stdlib)/std_option.vscfl"), String::from(STD_OPTION_SOURCE)),
-        Source::String(String::from("(stdlib)/std_range.vscfl"), String::from(STD_RANGE_SOURCE)),
-        Source::String(String::from("(stdlib)/std_values.vscfl"), String::from(STD_VALUES_SOURCE)),
-        generate_std_impls_source(),
-        Source::String(String::from("(stdlib)/opencl.vscfl"), String::from(OPENCL_SOURCE)),
-        generate_opencl_convert_source(),
-        generate_opencl_shuffle_source(),
-        generate_opencl_upsample_source(),
-        generate_opencl_vector_source(),
-        generate_opencl_impls_source()
+        (String::from("lang"), vec![
+            Source::String(String::from("(stdlib)/lang.vscfl"), String::from(LANG_SOURCE)),
+            generate_lang_impls_source()
+        ]),
+        (String::from("std"), vec![
+            Source::String(String::from("(stdlib)/std.vscfl"), String::from(STD_SOURCE)),
+            generate_std_impls_source()
+        ]),
+        (String::from("std_common"), vec![
+            Source::String(String::from("(stdlib)/std_common.vscfl"), String::from(STD_COMMON_SOURCE)),
+            generate_std_common_impls_source()
+        ]),
+        (String::from("std_geometry"), vec![
+            Source::String(String::from("(stdlib)/std_geometry.vscfl"), String::from(STD_GEOMETRY_SOURCE)),
+            generate_std_geometry_impls_source()
+        ]),
+        (String::from("std_math"), vec![
+            Source::String(String::from("(stdlib)/std_math.vscfl"), String::from(STD_MATH_SOURCE)),
+            generate_std_math_impls_source()
+        ]),
+        (String::from("std_option"), vec![
+            Source::String(String::from("(stdlib)/std_option.vscfl"), String::from(STD_OPTION_SOURCE))
+        ]),
+        (String::from("std_range"), vec![
+            Source::String(String::from("(stdlib)/std_range.vscfl"), String::from(STD_RANGE_SOURCE))
+        ]),
+        (String::from("std_values"), vec![
+            Source::String(String::from("(stdlib)/std_values.vscfl"), String::from(STD_VALUES_SOURCE)),
+            generate_std_values_impls_source()
+        ]),
+        (String::from("opencl"), vec![
+            Source::String(String::from("(stdlib)/opencl.vscfl"), String::from(OPENCL_SOURCE)),
+            generate_opencl_convert_source(),
+            generate_opencl_shuffle_source(),
+            generate_opencl_upsample_source(),
+            generate_opencl_vector_source(),
+            generate_opencl_impls_source()
+        ])
     ]
 }
+
+pub fn stdlib_sources() -> Vec<Source>
+{ stdlib_modules().into_iter().flat_map(|(_, srcs)| srcs).collect() }
@@ -8,3 +8,5 @@
 pub mod dfs;
 pub mod env;
 pub mod pattern;
+#[cfg(test)]
+pub mod test_utils;
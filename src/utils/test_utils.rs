@@ -0,0 +1,39 @@
+//
+// Copyright (c) 2025 Łukasz Szpakowski
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+//
+use std::env;
+use std::fs;
+use std::path::Path;
+use std::path::PathBuf;
+use std::process;
+use std::process::Command;
+
+pub fn temp_dir(name: &str) -> PathBuf
+{
+    let dir = env::temp_dir().join(format!("vscfl_{}_{}", process::id(), name));
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+pub fn compile_and_run<F>(name: &str, src_file_name: &str, src: &str, f: F) -> String
+    where F: FnOnce(&Path, &Path) -> Command
+{
+    let dir = temp_dir(name);
+    let src_path = dir.join(src_file_name);
+    let exe_path = dir.join("main");
+    fs::write(&src_path, src).unwrap();
+    let output = f(src_path.as_path(), exe_path.as_path()).output().unwrap();
+    if !output.status.success() {
+        println!("{}", src);
+        println!("{}", String::from_utf8_lossy(output.stderr.as_slice()));
+        panic!();
+    }
+    let output2 = Command::new(&exe_path).output().unwrap();
+    fs::remove_dir_all(&dir).unwrap();
+    assert!(output2.status.success());
+    String::from_utf8(output2.stdout).unwrap()
+}